@@ -0,0 +1,148 @@
+use super::{error_arguments::ArgsError, process::configuration::Configuration};
+
+use cargosos_bitcoin::{
+    configurations::interface::Interface, connections::network::Network, logs::level::Level,
+};
+
+const CONFIG_FLAG: &str = "--config";
+const TUI_FLAG: &str = "--tui";
+const GUI_FLAG: &str = "--gui";
+const DAEMON_FLAG: &str = "--daemon";
+const NETWORK_FLAG: &str = "--network";
+const DATA_DIRECTORY_FLAG: &str = "--datadir";
+const LOG_LEVEL_FLAG: &str = "--loglevel";
+const SCRIPT_FLAG: &str = "--script";
+
+const FLAG_PREFIX: &str = "--";
+const FLAG_VALUE_SEPARATOR: char = '=';
+const LIST_SEPARATOR: char = ',';
+
+/// It represents the arguments given in the command line. The configuration file can be given
+/// with `--config <file>` or as the only positional argument, and the rest of the flags override
+/// the values of the configuration file:
+///  * `--tui`, `--gui` or `--daemon`: the interface to use
+///  * `--network <network>`: the network to connect to
+///  * `--datadir <directory>`: the data directory, where the relative paths of the saved files and the logs are
+///  * `--loglevel <level>[,<level>...]`: the levels of the messages registered in the logs
+///  * `--script <file>`: runs the commands of the file without asking the user, with the TUI. With `-` the
+///    commands are read from the standard input
+///
+/// The values can also be given as `--flag=value`
+#[derive(Debug, Default)]
+pub struct Arguments {
+    pub config_name: Option<String>,
+    pub interface: Option<Interface>,
+    pub network: Option<Network>,
+    pub data_directory: Option<String>,
+    pub log_levels: Option<Vec<Level>>,
+    pub script: Option<String>,
+}
+
+impl Arguments {
+    /// Reads the arguments of the command line, where the first one is the name of the program
+    ///
+    /// ### Error
+    ///  * `ArgsError::UnknownFlag`: It will appear when a flag is not known
+    ///  * `ArgsError::MissingValue`: It will appear when a flag that needs a value is the last argument
+    ///  * `ArgsError::InvalidValue`: It will appear when the value of a flag cannot be parsed
+    ///  * `ArgsError::ConflictingInterfaces`: It will appear when more than one interface is selected
+    ///  * `ArgsError::RepeatedConfigurationFile`: It will appear when more than one configuration file is given
+    pub fn new(arguments: Vec<String>) -> Result<Self, ArgsError> {
+        let mut parsed = Arguments::default();
+        let mut arguments = arguments.into_iter().skip(1);
+
+        while let Some(argument) = arguments.next() {
+            if !argument.starts_with(FLAG_PREFIX) {
+                parsed.set_config_name(argument)?;
+                continue;
+            }
+
+            let (flag, value) = match argument.split_once(FLAG_VALUE_SEPARATOR) {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (argument, None),
+            };
+
+            match flag.as_str() {
+                TUI_FLAG => parsed.set_interface(Interface::Tui)?,
+                GUI_FLAG => parsed.set_interface(Interface::Gui)?,
+                DAEMON_FLAG => parsed.set_interface(Interface::Daemon)?,
+                CONFIG_FLAG | NETWORK_FLAG | DATA_DIRECTORY_FLAG | LOG_LEVEL_FLAG | SCRIPT_FLAG => {
+                    let value = match value.or_else(|| arguments.next()) {
+                        Some(value) => value,
+                        None => return Err(ArgsError::MissingValue(flag)),
+                    };
+                    parsed.set_value(&flag, value)?;
+                }
+                _ => return Err(ArgsError::UnknownFlag(flag)),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// Overrides the values of the configuration with the ones given in the command line
+    pub fn override_configuration(&self, configuration: &mut Configuration) {
+        if let Some(interface) = self.interface {
+            configuration.ui_config.interface = interface;
+        }
+
+        if let Some(network) = self.network {
+            configuration.connection_config.network = network;
+        }
+
+        if let Some(log_levels) = &self.log_levels {
+            configuration.log_config.levels = Some(log_levels.clone());
+        }
+
+        if let Some(data_directory) = &self.data_directory {
+            configuration.save_config.data_directory = Some(data_directory.clone());
+        }
+    }
+
+    fn set_config_name(&mut self, config_name: String) -> Result<(), ArgsError> {
+        if self.config_name.is_some() {
+            return Err(ArgsError::RepeatedConfigurationFile);
+        }
+        self.config_name = Some(config_name);
+        Ok(())
+    }
+
+    fn set_interface(&mut self, interface: Interface) -> Result<(), ArgsError> {
+        match self.interface {
+            Some(selected) if selected != interface => Err(ArgsError::ConflictingInterfaces),
+            _ => {
+                self.interface = Some(interface);
+                Ok(())
+            }
+        }
+    }
+
+    /// Sets the value of a flag that needs one
+    fn set_value(&mut self, flag: &str, value: String) -> Result<(), ArgsError> {
+        match flag {
+            CONFIG_FLAG => self.set_config_name(value)?,
+            NETWORK_FLAG => match value.parse::<Network>() {
+                Ok(network) => self.network = Some(network),
+                Err(_) => return Err(ArgsError::InvalidValue(flag.to_string(), value)),
+            },
+            DATA_DIRECTORY_FLAG => self.data_directory = Some(value),
+            SCRIPT_FLAG => {
+                self.set_interface(Interface::Tui)?;
+                self.script = Some(value);
+            }
+            LOG_LEVEL_FLAG => {
+                let mut log_levels: Vec<Level> = Vec::new();
+                for level in value.split(LIST_SEPARATOR) {
+                    match level.trim().parse::<Level>() {
+                        Ok(level) => log_levels.push(level),
+                        Err(_) => return Err(ArgsError::InvalidValue(flag.to_string(), value)),
+                    }
+                }
+                self.log_levels = Some(log_levels);
+            }
+            _ => return Err(ArgsError::UnknownFlag(flag.to_string())),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+use super::rpc_server::RpcServer;
+
+use crate::{
+    error_execution::ErrorExecution,
+    gui::{
+        input_handler_gui::InputHandlerGUI, notifier_gui::NotifierGUI, signal_to_back::SignalToBack,
+    },
+    process::{
+        backend::{self, BackendConfigs},
+        load_system::LoadSystem,
+        save_system::SaveSystem,
+        shutdown,
+    },
+    ui::error_ui::ErrorUI,
+};
+
+use cargosos_bitcoin::{
+    configurations::mode_config::ModeConfig,
+    logs::logger_sender::LoggerSender,
+    notifications::{
+        notification_history::NotificationHistory, notifier::Notifier,
+        wallet_notifier::WalletNotifier, websocket_notifier::WebSocketNotifier,
+    },
+    wallet_structure::transaction_options::TransactionOptions,
+};
+
+use std::{
+    io::{stdin, BufRead},
+    net::{Ipv4Addr, SocketAddr, TcpListener},
+    sync::mpsc::{channel, Sender},
+    thread,
+};
+
+const EXIT_COMMAND: &str = "exit";
+const NOTIFICATION_HISTORY_CAPACITY: usize = 100;
+
+/// Creates a thread that stops the daemon when the exit command is written in the terminal
+fn spawn_exit_listener(tx_to_back: Sender<SignalToBack>, logger: LoggerSender) {
+    thread::spawn(move || {
+        for line in stdin().lock().lines() {
+            match line {
+                Ok(line) if line.trim() == EXIT_COMMAND => {
+                    let _ = logger.log_interface("Stopping the daemon".to_string());
+                    let _ = tx_to_back.send(SignalToBack::ExitProgram);
+                    break;
+                }
+                Ok(_) => println!("Write {EXIT_COMMAND} to stop the daemon"),
+                Err(_) => break,
+            }
+        }
+    });
+}
+
+/// Stops the daemon when a signal asks the program to stop
+fn stop_on_shutdown(tx_to_back: Sender<SignalToBack>, logger: LoggerSender) {
+    shutdown::on_shutdown(move || {
+        let _ = logger.log_interface("Stopping the daemon after a signal".to_string());
+        let _ = tx_to_back.send(SignalToBack::ExitProgram);
+    });
+}
+
+/// Accepts the WebSocket clients connecting to the given local port
+///
+/// ### Error
+///  * `ErrorUI::ConnectionAborted`: It will appear when the port cannot be used
+fn listen_websocket<N: Notifier + 'static>(
+    websocket_notifier: &WebSocketNotifier<N>,
+    websocket_port: u16,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, websocket_port));
+
+    match TcpListener::bind(address) {
+        Ok(listener) => {
+            let _ = logger.log_connection(format!("Listening for WebSocket clients at {address}"));
+            websocket_notifier.listen(listener);
+            Ok(())
+        }
+        Err(error) => {
+            let _ = logger.log_error(format!(
+                "Cannot listen for WebSocket clients at {address}: {:?}",
+                error
+            ));
+            Err(ErrorUI::ConnectionAborted)
+        }
+    }
+}
+
+/// The main function of the program without interface. The graphical interfaces connected to the
+/// local RPC observe the node, which keeps running until the exit command is written in the terminal
+/// or a signal asks it to stop.
+/// If a WebSocket port is given, the web dashboards connected to it receive the notifications as JSON,
+/// starting with the last ones sent before they connected
+pub fn program_execution(
+    ports: (u16, Option<u16>, (Option<u16>, Option<u16>)),
+    mode_config: ModeConfig,
+    configs: BackendConfigs,
+    load_system: &mut LoadSystem,
+    logger: LoggerSender,
+) -> Result<SaveSystem, ErrorExecution> {
+    let (rpc_port, websocket_port, local_ports) = ports;
+    let (tx_to_back, rx_from_observers) = channel::<SignalToBack>();
+
+    let rpc_server = RpcServer::new(logger.clone());
+    rpc_server.listen(rpc_port, tx_to_back.clone())?;
+    spawn_exit_listener(tx_to_back.clone(), logger.clone());
+    stop_on_shutdown(tx_to_back, logger.clone());
+
+    let websocket_notifier =
+        WebSocketNotifier::new(NotifierGUI::new(rpc_server, logger.clone()), logger.clone())
+            .with_history(NotificationHistory::new(NOTIFICATION_HISTORY_CAPACITY));
+    if let Some(websocket_port) = websocket_port {
+        listen_websocket(&websocket_notifier, websocket_port, logger.clone())?;
+    }
+
+    let notifier = WalletNotifier::new(&load_system.get_wallet_name(), websocket_notifier);
+    let input_handler = InputHandlerGUI::new(rx_from_observers, notifier.clone(), logger.clone())
+        .with_transaction_options(TransactionOptions::new(configs.0.replaceable));
+
+    backend::backend(
+        mode_config,
+        configs,
+        local_ports,
+        load_system,
+        input_handler,
+        notifier,
+        logger,
+    )
+}
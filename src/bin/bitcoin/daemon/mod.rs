@@ -0,0 +1,2 @@
+pub mod execution;
+pub mod rpc_server;
@@ -0,0 +1,324 @@
+use crate::{
+    gui::{
+        rpc_message::{decode_signal_to_back, encode_signal_to_front},
+        signal_to_back::SignalToBack,
+        signal_to_front::{FrontSender, SignalToFront},
+    },
+    process::{
+        reference::{get_reference, MutArc},
+        state::SharedState,
+    },
+    ui::{error_ui::ErrorUI, from_hexa},
+};
+
+use cargosos_bitcoin::{
+    block_structure::hash::{self, HashType, HASH_TYPE_SIZE},
+    logs::{level::Level, logger_sender::LoggerSender},
+};
+
+use std::{
+    collections::HashSet,
+    io::{BufRead, BufReader, Write},
+    net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream},
+    sync::mpsc::Sender,
+    thread::{self, JoinHandle},
+};
+
+/// It's the state of the node that an observer needs to show when it connects to the daemon
+#[derive(Debug, Clone, Default)]
+struct ObserverSnapshot {
+    available_wallets: Option<String>,
+    accounts: Vec<String>,
+    blockchain_ready: Option<String>,
+    last_block: Option<String>,
+    tip: Option<String>,
+    last_saved: Option<String>,
+    mempool_info: Option<String>,
+}
+
+impl ObserverSnapshot {
+    /// Updates the state with the signal sent to the observers
+    fn remember(&mut self, signal: &SignalToFront, line: &str) {
+        match signal {
+            SignalToFront::AvailableWallets(..) => self.available_wallets = Some(line.to_string()),
+            SignalToFront::WalletChanged(_) | SignalToFront::WalletClosed(_) => {
+                self.accounts.clear()
+            }
+            SignalToFront::RegisterAccount(_) => self.accounts.push(line.to_string()),
+            SignalToFront::AccountRenamed(account_name, new_account_name) => {
+                let account_line =
+                    encode_signal_to_front(&SignalToFront::RegisterAccount(account_name.clone()));
+                let new_account_line = encode_signal_to_front(&SignalToFront::RegisterAccount(
+                    new_account_name.clone(),
+                ));
+                for line in self.accounts.iter_mut() {
+                    if *line == account_line {
+                        *line = new_account_line.clone();
+                    }
+                }
+            }
+            SignalToFront::NotifyBlockchainIsReady => {
+                self.blockchain_ready = Some(line.to_string())
+            }
+            SignalToFront::NewBlockAdded(..) => self.last_block = Some(line.to_string()),
+            SignalToFront::TipAdvanced(..) => self.tip = Some(line.to_string()),
+            SignalToFront::StateSaved(_) => self.last_saved = Some(line.to_string()),
+            SignalToFront::MempoolInfo(_) => self.mempool_info = Some(line.to_string()),
+            _ => {}
+        }
+    }
+
+    /// Returns the lines that an observer needs to receive to be up to date
+    fn lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self.available_wallets.iter().cloned().collect();
+        lines.extend(self.accounts.iter().cloned());
+        lines.extend(self.blockchain_ready.iter().cloned());
+        lines.extend(self.last_block.iter().cloned());
+        lines.extend(self.tip.iter().cloned());
+        lines.extend(self.last_saved.iter().cloned());
+        lines.extend(self.mempool_info.iter().cloned());
+        lines
+    }
+}
+
+/// It's an observer connected to the daemon, with the script hashes it's subscribed to
+struct Observer {
+    id: usize,
+    stream: TcpStream,
+    script_hashes: HashSet<HashType>,
+}
+
+/// It's the RPC of the daemon, that sends the signals of the node to every connected observer.
+/// The answers to the requests of an observer are received by all of them, while the events of a
+/// script are only received by the observers subscribed to it
+#[derive(Clone)]
+pub struct RpcServer {
+    observers: MutArc<Vec<Observer>>,
+    next_id: MutArc<usize>,
+    snapshot: MutArc<ObserverSnapshot>,
+    logger: LoggerSender,
+}
+
+impl RpcServer {
+    pub fn new(logger: LoggerSender) -> Self {
+        RpcServer {
+            observers: SharedState::new(Vec::new()),
+            next_id: SharedState::new(0),
+            snapshot: SharedState::new(ObserverSnapshot::default()),
+            logger,
+        }
+    }
+
+    /// Creates a thread that accepts the observers connecting to the given local port, sending
+    /// their requests to the back
+    ///
+    /// ### Error
+    ///  * `ErrorUI::ConnectionAborted`: It will appear when the port cannot be used
+    pub fn listen(
+        &self,
+        rpc_port: u16,
+        tx_to_back: Sender<SignalToBack>,
+    ) -> Result<JoinHandle<()>, ErrorUI> {
+        let address = SocketAddr::from((Ipv4Addr::LOCALHOST, rpc_port));
+
+        let listener = match TcpListener::bind(address) {
+            Ok(listener) => listener,
+            Err(error) => {
+                let _ = self.logger.log_error(format!(
+                    "Cannot listen for observers at {address}: {:?}",
+                    error
+                ));
+                return Err(ErrorUI::ConnectionAborted);
+            }
+        };
+
+        let _ = self
+            .logger
+            .log_connection(format!("Listening for observers at {address}"));
+
+        let rpc_server = self.clone();
+        Ok(thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => continue,
+                };
+
+                if let Err(error) = rpc_server.add_observer(stream, tx_to_back.clone()) {
+                    let _ = rpc_server.logger.log_data(Level::ERROR, error);
+                }
+            }
+        }))
+    }
+
+    /// Sends the current state of the node to the observer and starts reading its requests
+    ///
+    /// ### Error
+    ///  * `ErrorUI::ErrorWriting`: It will appear when the state cannot be sent to the observer
+    fn add_observer(
+        &self,
+        mut stream: TcpStream,
+        tx_to_back: Sender<SignalToBack>,
+    ) -> Result<(), ErrorUI> {
+        let reader = match stream.try_clone() {
+            Ok(reader) => reader,
+            Err(_) => {
+                return Err(ErrorUI::ErrorReading(
+                    "Cannot share the connection with the observer".to_string(),
+                ))
+            }
+        };
+
+        let snapshot = get_reference(&self.snapshot)?;
+        let mut observers = get_reference(&self.observers)?;
+
+        for line in snapshot.lines() {
+            if writeln!(stream, "{line}").is_err() {
+                return Err(ErrorUI::ErrorWriting(
+                    "Cannot send the state of the node to the observer".to_string(),
+                ));
+            }
+        }
+
+        let id = {
+            let mut next_id = get_reference(&self.next_id)?;
+            *next_id += 1;
+            *next_id
+        };
+        observers.push(Observer {
+            id,
+            stream,
+            script_hashes: HashSet::new(),
+        });
+
+        let logger = self.logger.clone();
+        let _ = logger.log_connection("Observer connected to the daemon".to_string());
+
+        let rpc_server = self.clone();
+        thread::spawn(move || {
+            for line in BufReader::new(reader).lines() {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(_) => break,
+                };
+
+                let signal = match decode_signal_to_back(&line) {
+                    Some(signal) => signal,
+                    None => {
+                        let _ =
+                            logger.log_interface(format!("Unknown request from observer: {line}"));
+                        continue;
+                    }
+                };
+
+                match rpc_server.update_subscriptions(id, &signal) {
+                    Ok(true) => {}
+                    Ok(false) => continue,
+                    Err(error) => {
+                        let _ = logger.log_data(Level::ERROR, error);
+                        continue;
+                    }
+                }
+
+                if tx_to_back.send(signal).is_err() {
+                    break;
+                }
+            }
+
+            match rpc_server.remove_observer(id) {
+                Ok(script_hashes) => {
+                    for script_hash in script_hashes {
+                        let _ = tx_to_back.send(SignalToBack::UnsubscribeScriptHash(
+                            encode_script_hash(&script_hash),
+                        ));
+                    }
+                }
+                Err(error) => {
+                    let _ = logger.log_data(Level::ERROR, error);
+                }
+            }
+
+            let _ = logger.log_connection("Observer disconnected from the daemon".to_string());
+        });
+
+        Ok(())
+    }
+
+    /// Records the script hashes the observer subscribes or unsubscribes to. Returns false when the signal
+    /// must not reach the back, because the script hash is invalid or another observer is still subscribed
+    fn update_subscriptions(&self, id: usize, signal: &SignalToBack) -> Result<bool, ErrorUI> {
+        let (script_hash, is_subscribing) = match signal {
+            SignalToBack::SubscribeScriptHash(script_hash) => (script_hash, true),
+            SignalToBack::UnsubscribeScriptHash(script_hash) => (script_hash, false),
+            _ => return Ok(true),
+        };
+
+        let script_hash = match from_hexa::from::<HASH_TYPE_SIZE>(script_hash) {
+            Ok(script_hash) => script_hash,
+            Err(_) => {
+                let _ = self
+                    .logger
+                    .log_interface(format!("Invalid script hash from observer: {script_hash}"));
+                return Ok(false);
+            }
+        };
+
+        let mut observers = get_reference(&self.observers)?;
+        if let Some(observer) = observers.iter_mut().find(|observer| observer.id == id) {
+            match is_subscribing {
+                true => observer.script_hashes.insert(script_hash),
+                false => observer.script_hashes.remove(&script_hash),
+            };
+        }
+
+        Ok(is_subscribing || !is_subscribed(&observers, &script_hash))
+    }
+
+    /// Removes the observer, returning the script hashes that no other observer is subscribed to
+    fn remove_observer(&self, id: usize) -> Result<Vec<HashType>, ErrorUI> {
+        let mut observers = get_reference(&self.observers)?;
+
+        let script_hashes = match observers.iter().position(|observer| observer.id == id) {
+            Some(position) => observers.remove(position).script_hashes,
+            None => HashSet::new(),
+        };
+
+        Ok(script_hashes
+            .into_iter()
+            .filter(|script_hash| !is_subscribed(&observers, script_hash))
+            .collect())
+    }
+}
+
+/// Returns true if any of the observers is subscribed to the script hash
+fn is_subscribed(observers: &[Observer], script_hash: &HashType) -> bool {
+    observers
+        .iter()
+        .any(|observer| observer.script_hashes.contains(script_hash))
+}
+
+/// Turns a script hash into a string in the internal order, as the observers send it
+fn encode_script_hash(script_hash: &HashType) -> String {
+    hash::to_hex(script_hash)
+}
+
+impl FrontSender for RpcServer {
+    fn send(&self, signal: SignalToFront) -> Result<(), ErrorUI> {
+        let line = encode_signal_to_front(&signal);
+
+        let mut snapshot = get_reference(&self.snapshot)?;
+        snapshot.remember(&signal, &line);
+
+        let mut observers = get_reference(&self.observers)?;
+        observers.retain_mut(|observer| {
+            if let SignalToFront::ScriptHashEvent(event) = &signal {
+                if !observer.script_hashes.contains(&event.script_hash) {
+                    return true;
+                }
+            }
+            writeln!(observer.stream, "{line}").is_ok()
+        });
+
+        Ok(())
+    }
+}
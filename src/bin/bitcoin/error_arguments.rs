@@ -0,0 +1,35 @@
+use std::fmt::{Debug, Error, Formatter};
+
+/// It represents all posible errors that can occur reading the arguments of the command line
+pub enum ArgsError {
+    /// It will appear when a flag is not known
+    UnknownFlag(String),
+
+    /// It will appear when a flag that needs a value is the last argument
+    MissingValue(String),
+
+    /// It will appear when the value of a flag cannot be parsed
+    InvalidValue(String, String),
+
+    /// It will appear when more than one interface is selected
+    ConflictingInterfaces,
+
+    /// It will appear when more than one configuration file is given
+    RepeatedConfigurationFile,
+}
+
+impl Debug for ArgsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            ArgsError::UnknownFlag(flag) => write!(f, "Unknown flag {flag}"),
+            ArgsError::MissingValue(flag) => write!(f, "The flag {flag} needs a value"),
+            ArgsError::InvalidValue(flag, value) => {
+                write!(f, "The value {:?} is not valid for the flag {flag}", value)
+            }
+            ArgsError::ConflictingInterfaces => write!(f, "Only one interface can be selected"),
+            ArgsError::RepeatedConfigurationFile => {
+                write!(f, "Only one configuration file can be given")
+            }
+        }
+    }
+}
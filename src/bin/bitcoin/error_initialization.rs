@@ -1,5 +1,11 @@
+use super::error_arguments::ArgsError;
+
+use std::{
+    convert::From,
+    fmt::{Debug, Error, Formatter},
+};
+
 /// It represents all posible errors that can occur initializing the program
-#[derive(Debug)]
 pub enum ErrorInitialization {
     /// It will appear when there is not argument pass that configuration declaration
     NoGivenConfigurationFile,
@@ -10,6 +16,25 @@ pub enum ErrorInitialization {
     /// It will appear when the file does not exist
     LogFileDoesntExist,
 
-    /// It will appear when the blockchain file does not exist
-    ValueFileDoesntExist,
+    /// It represents all posible errors that can occur reading the arguments of the command line
+    Arguments(ArgsError),
+}
+
+impl Debug for ErrorInitialization {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            ErrorInitialization::NoGivenConfigurationFile => write!(f, "NoGivenConfigurationFile"),
+            ErrorInitialization::ConfigurationFileDoesntExist => {
+                write!(f, "ConfigurationFileDoesntExist")
+            }
+            ErrorInitialization::LogFileDoesntExist => write!(f, "LogFileDoesntExist"),
+            ErrorInitialization::Arguments(error_arguments) => write!(f, "{:?}", error_arguments),
+        }
+    }
+}
+
+impl From<ArgsError> for ErrorInitialization {
+    fn from(value: ArgsError) -> Self {
+        ErrorInitialization::Arguments(value)
+    }
 }
@@ -1,35 +1,46 @@
 use super::{
-    frontend, input_handler_gui::InputHandlerGUI, notifier_gui::NotifierGUI,
+    frontend, input_handler_gui::InputHandlerGUI, notifier_gui::NotifierGUI, rpc_client::RpcClient,
     signal_to_back::SignalToBack, signal_to_front::SignalToFront,
 };
 
 use crate::{
     error_execution::ErrorExecution,
-    process::{backend, load_system::LoadSystem, save_system::SaveSystem},
+    process::{
+        backend::{self, BackendConfigs},
+        load_system::LoadSystem,
+        save_system::SaveSystem,
+        shutdown::{self, SHUTDOWN_POLL_INTERVAL},
+    },
+    ui::message_catalog::MessageCatalog,
 };
 
 use cargosos_bitcoin::{
     configurations::{
-        connection_config::ConnectionConfig, download_config::DownloadConfig,
-        mode_config::ModeConfig, save_config::SaveConfig,
+        language::Language, mode_config::ModeConfig, save_config::SaveConfig, theme::Theme,
     },
-    logs::logger_sender::LoggerSender,
-    notifications::notifier::Notifier,
+    logs::{log_buffer::LogBuffer, logger_sender::LoggerSender},
+    notifications::{notifier::Notifier, wallet_notifier::WalletNotifier},
+    wallet_structure::transaction_options::TransactionOptions,
 };
 
 use std::{
     cell::Cell,
-    sync::mpsc::{channel, Receiver},
+    sync::mpsc::{channel, Receiver, Sender},
     thread::{self, JoinHandle},
 };
 
 use gtk::{glib, prelude::*, Application};
 
+const TRANSLATABLE_TEXT: &str = "translatable=\"yes\">";
+
+/// It's the theme, the language and the path of the configuration file of the interface
+pub type InterfaceConfig = (Theme, Language, String);
+
 /// Function that spawns the backend handler thread
 fn spawn_backend_handler<N: Notifier + 'static>(
     mode_config: ModeConfig,
-    connection_config: ConnectionConfig,
-    download_config: DownloadConfig,
+    configs: BackendConfigs,
+    local_ports: (Option<u16>, Option<u16>),
     save_config: SaveConfig,
     rx_from_front: Receiver<SignalToBack>,
     notifier: N,
@@ -37,13 +48,15 @@ fn spawn_backend_handler<N: Notifier + 'static>(
 ) -> JoinHandle<Result<SaveSystem, ErrorExecution>> {
     thread::spawn(move || {
         let mut load_system = LoadSystem::new(save_config.clone(), logger.clone());
+        let notifier = WalletNotifier::new(&load_system.get_wallet_name(), notifier);
 
-        let input_handler = InputHandlerGUI::new(rx_from_front, notifier.clone(), logger.clone());
+        let input_handler = InputHandlerGUI::new(rx_from_front, notifier.clone(), logger.clone())
+            .with_transaction_options(TransactionOptions::new(configs.0.replaceable));
 
         backend::backend(
             mode_config,
-            connection_config,
-            download_config,
+            configs,
+            local_ports,
             &mut load_system,
             input_handler,
             notifier,
@@ -55,28 +68,110 @@ fn spawn_backend_handler<N: Notifier + 'static>(
 /// The main function of the program for the graphical interface.
 pub fn program_execution(
     mode_config: ModeConfig,
-    connection_config: ConnectionConfig,
-    download_config: DownloadConfig,
+    configs: BackendConfigs,
+    local_ports: (Option<u16>, Option<u16>),
     save_config: SaveConfig,
     logger: LoggerSender,
+    (log_buffer, interface_config): (LogBuffer, InterfaceConfig),
 ) -> Result<SaveSystem, ErrorExecution> {
     let (tx_to_back, rx_from_front) = channel::<SignalToBack>();
     let (tx_to_front, rx_from_back) =
         glib::MainContext::channel::<SignalToFront>(glib::PRIORITY_DEFAULT);
 
-    let notifier = NotifierGUI::new(tx_to_front, logger.clone());
+    let notifier = NotifierGUI::new(tx_to_front, logger.clone()).with_language(interface_config.1);
 
     let backend_handler = spawn_backend_handler(
         mode_config,
-        connection_config,
-        download_config,
+        configs,
+        local_ports,
         save_config,
         rx_from_front,
         notifier,
         logger,
     );
 
-    let glade_src = include_str!("WindowNotebook.glade");
+    run_application(tx_to_back, rx_from_back, log_buffer, interface_config);
+
+    match backend_handler.join() {
+        Ok(save_system) => save_system,
+        Err(_) => Err(ErrorExecution::FailThread),
+    }
+}
+
+/// The main function of the program for the graphical interface observing a daemon, connected to its local RPC.
+/// The node keeps running in the daemon after the window is closed
+pub fn observer_execution(
+    rpc_port: u16,
+    logger: LoggerSender,
+    log_buffer: LogBuffer,
+    interface_config: InterfaceConfig,
+) -> Result<(), ErrorExecution> {
+    let (tx_to_back, rx_from_front) = channel::<SignalToBack>();
+    let (tx_to_front, rx_from_back) =
+        glib::MainContext::channel::<SignalToFront>(glib::PRIORITY_DEFAULT);
+
+    let rpc_client = RpcClient::connect(rpc_port, logger)?;
+    let receiver_handler = rpc_client.spawn_receiver(tx_to_front.clone())?;
+    let client_handler =
+        thread::spawn(move || rpc_client.forward_signals(rx_from_front, tx_to_front));
+
+    run_application(tx_to_back, rx_from_back, log_buffer, interface_config);
+
+    match (client_handler.join(), receiver_handler.join()) {
+        (Ok(result), Ok(_)) => Ok(result?),
+        _ => Err(ErrorExecution::FailThread),
+    }
+}
+
+/// Closes the windows of the application when a signal asks the program to stop, going through
+/// the same path as when the user closes them, so the backend is told to stop and save
+fn close_on_shutdown(application: &Application) {
+    let application = application.clone();
+    glib::timeout_add_local(SHUTDOWN_POLL_INTERVAL, move || {
+        if !shutdown::is_shutdown_requested() {
+            return glib::Continue(true);
+        }
+
+        for window in application.windows() {
+            window.close();
+        }
+        glib::Continue(false)
+    });
+}
+
+/// Returns the definition of the window with the texts marked as translatable in the language of the
+/// catalog. The texts are looked up as they are written in the definition, so the translations must be
+/// already escaped
+fn translate_glade(glade_src: &str, catalog: MessageCatalog) -> String {
+    let mut translated_src = String::with_capacity(glade_src.len());
+    let mut rest = glade_src;
+
+    while let Some(position) = rest.find(TRANSLATABLE_TEXT) {
+        let (before, after) = rest.split_at(position + TRANSLATABLE_TEXT.len());
+        translated_src.push_str(before);
+
+        let end = after.find('<').unwrap_or(after.len());
+        translated_src.push_str(&catalog.text(&after[..end]));
+        rest = &after[end..];
+    }
+    translated_src.push_str(rest);
+
+    translated_src
+}
+
+/// Runs the window until it's closed or a signal asks the program to stop, showing the log messages
+/// kept in the buffer in the debug console and using the theme and the language of the configuration
+fn run_application(
+    tx_to_back: Sender<SignalToBack>,
+    rx_from_back: glib::Receiver<SignalToFront>,
+    log_buffer: LogBuffer,
+    (theme, language, config_name): InterfaceConfig,
+) {
+    let glade_src = translate_glade(
+        include_str!("WindowNotebook.glade"),
+        MessageCatalog::new(language),
+    );
+    let theme_config = (theme, config_name);
 
     let application = Application::builder().build();
 
@@ -88,16 +183,14 @@ pub fn program_execution(
             tx_to_back.clone(),
             wrapped_rx_to_back.take(),
             app,
-            glade_src,
+            &glade_src,
+            log_buffer.clone(),
+            theme_config.clone(),
         ) {
             println!("Error: {:?}", error);
         }
+        close_on_shutdown(app);
     });
     let vector: Vec<String> = Vec::new();
     application.run_with_args(&vector);
-
-    match backend_handler.join() {
-        Ok(save_system) => save_system,
-        Err(_) => Err(ErrorExecution::FailThread),
-    }
 }
@@ -3,7 +3,7 @@ use super::{signal_to_back::SignalToBack, signal_to_front::SignalToFront};
 use crate::{
     process::{
         reference::{self, MutArc},
-        transaction,
+        shutdown, transaction,
     },
     ui::{account, error_ui::ErrorUI, from_hexa},
 };
@@ -11,24 +11,78 @@ use crate::{
 use cargosos_bitcoin::{
     block_structure::{
         block_chain::BlockChain,
-        hash::{HashType, HASH_TYPE_SIZE},
+        block_summary::BlockSummary,
+        coinbase::CoinbaseInfo,
+        hash::{self, HashType},
+        mempool_info::MempoolInfo,
     },
-    logs::logger_sender::LoggerSender,
-    node_structure::connection_id::ConnectionId,
+    configurations::theme::{self, Theme},
+    logs::{log_buffer::LogBuffer, logger_sender::LoggerSender},
+    node_structure::{connection_id::ConnectionId, peer_information::PeerInformation},
     notifications::{notification::Notification, notifier::Notifier},
-    wallet_structure::{private_key::PrivateKey, public_key::PublicKey, wallet::Wallet},
+    wallet_structure::{
+        address::Address,
+        amount::Amount,
+        balance_history::BalanceCheckpoint,
+        exchange_rate::ExchangeRate,
+        payment_uri::PaymentUri,
+        private_key::PrivateKey,
+        public_key::PublicKey,
+        qr_code::QrCode,
+        transaction_history::{Direction, HistoryEntry},
+        transaction_preview::TransactionPreview,
+        wallet::Wallet,
+    },
 };
 
 use gtk::{
-    glib, prelude::*, Builder, Button, ComboBoxText, Entry, Image, Label, ProgressBar, SpinButton,
-    TreeStore, Window,
+    cairo, gdk, glib, prelude::*, Builder, Button, CellRendererText, CheckButton, ComboBoxText,
+    CssProvider, DrawingArea, Entry, Image, Inhibit, Label, MenuItem, ProgressBar, RadioMenuItem,
+    Settings, SortColumn, SortType, SpinButton, StyleContext, TextView, TreeIter, TreeModel,
+    TreeModelFilter, TreeModelSort, TreeStore, TreeView, Window,
 };
 
-use glib::GString;
-
-use std::sync::mpsc::Sender;
-
-use chrono::{DateTime, NaiveDateTime, Utc};
+use std::{cell::RefCell, fs, path::Path, rc::Rc, sync::mpsc::Sender, time::Duration};
+
+const BALANCE_HISTORY_FROM_HEIGHT: u64 = 0;
+const BALANCE_HISTORY_CHART_MARGIN: f64 = 10.0;
+const DEBUG_CONSOLE_REFRESH: Duration = Duration::from_millis(500);
+const RECENT_BLOCKS_SHOWN: u32 = 20;
+const QR_CODE_BORDER: usize = 4;
+const PAYMENT_URI_SCHEME: &str = "bitcoin:";
+
+const LIGHT_THEME_CSS: &str = include_str!("light.css");
+const DARK_THEME_CSS: &str = include_str!("dark.css");
+const DARK_THEME_NAME: &str = "dark";
+const PREFER_DARK_THEME_PROPERTY: &str = "gtk-application-prefer-dark-theme";
+const THEME_NAME_PROPERTY: &str = "gtk-theme-name";
+const CHART_BACKGROUND_COLOR: &str = "chart_background_color";
+const CHART_LINE_COLOR: &str = "chart_line_color";
+const UI_CONFIG: &str = "UI";
+const TOML_EXTENSION: &str = "toml";
+
+const ALL_ACCOUNTS_ID: &str = "all";
+const ALL_ACCOUNTS: &str = "All accounts";
+const DATE_RANGE_TODAY: &str = "1";
+const DATE_RANGE_THIS_WEEK: &str = "2";
+const DATE_RANGE_THIS_MONTH: &str = "3";
+const DATE_RANGE_LAST_MONTH: &str = "4";
+const DATE_RANGE_THIS_YEAR: &str = "5";
+const DIRECTION_RECEIVED_ID: &str = "1";
+const DIRECTION_SENT_ID: &str = "2";
+
+const HISTORY_ACCOUNT_COLUMN: u32 = 1;
+const HISTORY_TRANSACTION_ID_COLUMN: u32 = 2;
+const HISTORY_DIRECTION_COLUMN: u32 = 3;
+const HISTORY_TIMESTAMP_COLUMN: u32 = 6;
+const HISTORY_SATOSHIS_COLUMN: u32 = 7;
+const HISTORY_LABEL_COLUMN: u32 = 8;
+
+type BalanceHistoryData = Rc<RefCell<Vec<BalanceCheckpoint>>>;
+type ReceiveQrCodeData = Rc<RefCell<Option<QrCode>>>;
+type BalanceInFiatData = Rc<RefCell<(Option<ExchangeRate>, (Amount, Amount))>>;
+
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, NaiveDateTime, TimeZone, Utc};
 
 /// Creates a new account with the data entered by the user
 ///
@@ -61,6 +115,17 @@ pub fn create_account<N: Notifier>(
     account::create_account(&mut wallet, account_name, private_key, public_key, notifier)
 }
 
+/// Creates a new account with a pair of keys generated by the wallet
+pub fn generate_account<N: Notifier>(
+    wallet: MutArc<Wallet>,
+    account_name: &str,
+    notifier: N,
+) -> Result<(), ErrorUI> {
+    let mut wallet = reference::get_reference(&wallet)?;
+    account::generate_account(&mut wallet, account_name, notifier);
+    Ok(())
+}
+
 /// This function sets up the main window
 fn login_main_window(
     application: &gtk::Application,
@@ -76,6 +141,7 @@ fn login_main_window(
     let application_clone = application.clone();
     let tx_to_back_clone = tx_to_back.clone();
     window.connect_destroy(move |_| {
+        shutdown::request_shutdown();
         application_clone.quit();
         if tx_to_back_clone.send(SignalToBack::ExitProgram).is_err() {
             println!("Error sending exit program signal");
@@ -106,7 +172,12 @@ fn login_main_window(
     });
 
     login_send_page(builder, tx_to_back.clone())?;
+    login_faucet_button(builder, tx_to_back.clone())?;
+    login_wallet_files_combo_box(builder, tx_to_back.clone())?;
     login_block_notification_window(builder)?;
+    login_transaction_history_page(builder, tx_to_back.clone())?;
+    login_blocks_page(builder, tx_to_back.clone())?;
+    login_peers_page(builder, tx_to_back.clone())?;
     login_merkle_proof_window(builder, tx_to_back)?;
     window.show_all();
     Ok(())
@@ -128,6 +199,34 @@ fn login_registration_window(
     };
     account_registration_window.set_application(Some(application));
 
+    let generate_account_button: Button = match builder.object("GenerateAccountButton") {
+        Some(generate_account_button) => generate_account_button,
+        None => return Err(ErrorUI::MissingElement("GenerateAccountButton".to_string())),
+    };
+    let cloned_window = account_registration_window.clone();
+    let cloned_builder = builder.clone();
+    let cloned_tx_to_back = tx_to_back.clone();
+    generate_account_button.connect_clicked(move |_| {
+        cloned_window.set_visible(false);
+
+        let name_entry: Entry = match cloned_builder.object("NameEntry") {
+            Some(entry) => entry,
+            None => {
+                println!("Error: Missing element NameEntry");
+                Entry::new()
+            }
+        };
+
+        if cloned_tx_to_back
+            .send(SignalToBack::GenerateAccount(name_entry.text().to_string()))
+            .is_err()
+        {
+            println!("Error sending generate account signal");
+        }
+
+        name_entry.set_text("");
+    });
+
     let cloned_builder = builder.clone();
 
     let save_wallet_button: Button = match builder.object("SaveWalletButton") {
@@ -177,436 +276,1388 @@ fn login_registration_window(
     Ok(())
 }
 
-/// This function sets up the combo box
-fn login_combo_box(builder: &Builder, tx_to_back: Sender<SignalToBack>) -> Result<(), ErrorUI> {
-    let combo_box: ComboBoxText = match builder.object("WalletsComboBox") {
-        Some(combo_box) => combo_box,
-        None => return Err(ErrorUI::MissingElement("WalletsComboBox".to_string())),
+/// This function sets up the window to edit the account selected in the combo box
+fn login_account_edit_window(
+    builder: &Builder,
+    application: &gtk::Application,
+    tx_to_back: Sender<SignalToBack>,
+) -> Result<(), ErrorUI> {
+    let account_edit_window: Window = match builder.object("AccountEditWindow") {
+        Some(account_edit_window) => account_edit_window,
+        None => return Err(ErrorUI::MissingElement("AccountEditWindow".to_string())),
+    };
+    account_edit_window.set_application(Some(application));
+
+    let edit_account_button: Button = match builder.object("EditAccountButton") {
+        Some(edit_account_button) => edit_account_button,
+        None => return Err(ErrorUI::MissingElement("EditAccountButton".to_string())),
     };
+    let cloned_window = account_edit_window.clone();
     let cloned_builder = builder.clone();
-    combo_box.connect_changed(move |_| {
-        let combo_box_cloned: ComboBoxText = match cloned_builder.object("WalletsComboBox") {
+    edit_account_button.connect_clicked(move |_| {
+        let combo_box: ComboBoxText = match cloned_builder.object("WalletsComboBox") {
             Some(combo_box) => combo_box,
             None => {
                 println!("Error: Missing element WalletsComboBox");
                 ComboBoxText::new()
             }
         };
-        let selected_wallet = match combo_box_cloned.active_text() {
-            Some(selected_wallet) => selected_wallet,
+        match combo_box.active_text() {
+            Some(_) => cloned_window.set_visible(true),
+            None => {
+                if let Err(error) = show_window_with_error(&cloned_builder, "No account selected") {
+                    println!("Error showing error window, with error {:?}", error);
+                }
+            }
+        }
+    });
+
+    let save_account_edit_button: Button = match builder.object("SaveAccountEditButton") {
+        Some(save_account_edit_button) => save_account_edit_button,
+        None => return Err(ErrorUI::MissingElement("SaveAccountEditButton".to_string())),
+    };
+    let cloned_builder = builder.clone();
+    save_account_edit_button.connect_clicked(move |_| {
+        account_edit_window.set_visible(false);
+
+        let combo_box: ComboBoxText = match cloned_builder.object("WalletsComboBox") {
+            Some(combo_box) => combo_box,
             None => {
                 println!("Error: Missing element WalletsComboBox");
-                GString::new()
+                ComboBoxText::new()
             }
         };
-        if let Err(error) = tx_to_back.send(SignalToBack::ChangeSelectedAccount(
-            selected_wallet.to_string(),
-        )) {
-            println!("Error sending change selected account signal: {}", error);
+        let mut entries: Vec<Entry> = Vec::new();
+        for entry_name in [
+            "AccountNewNameEntry",
+            "AccountLabelEntry",
+            "AccountDescriptionEntry",
+        ] {
+            match cloned_builder.object(entry_name) {
+                Some(entry) => entries.push(entry),
+                None => {
+                    println!("Error: Missing element {entry_name}");
+                    entries.push(Entry::new());
+                }
+            }
         }
-        if let Err(error) = tx_to_back.send(SignalToBack::GetAccountBalance) {
-            println!("Error sending get account balance signal: {}", error);
-        };
-        if let Err(error) = tx_to_back.send(SignalToBack::GetAccountTransactions) {
-            println!("Error sending get account transactions signal: {}", error);
-        };
+
+        if let Some(account_name) = combo_box.active_text() {
+            let mut values = entries
+                .iter()
+                .map(|entry| account::edited_value(entry.text().as_str()));
+            let signal = SignalToBack::EditAccount(
+                account_name.to_string(),
+                values.next().flatten(),
+                values.next().flatten(),
+                values.next().flatten(),
+            );
+            if tx_to_back.send(signal).is_err() {
+                println!("Error sending edit account signal");
+            }
+        }
+
+        entries.iter().for_each(|entry| entry.set_text(""));
     });
     Ok(())
 }
 
-/// This function sets up the error window
-fn login_transaction_error_window(builder: &Builder) -> Result<(), ErrorUI> {
-    let transaction_error_window: Window = match builder.object("TransactionErrorWindow") {
-        Some(transaction_error_window) => transaction_error_window,
-        None => {
-            return Err(ErrorUI::MissingElement(
-                "TransactionErrorWindow".to_string(),
-            ))
-        }
-    };
-    let transaction_error_button: Button = match builder.object("OkErrorButton") {
-        Some(transaction_error_button) => transaction_error_button,
-        None => return Err(ErrorUI::MissingElement("OkErrorButton".to_string())),
+/// This function sets up the window to export and import backups of the wallet
+fn login_wallet_backup_window(
+    builder: &Builder,
+    application: &gtk::Application,
+    tx_to_back: Sender<SignalToBack>,
+) -> Result<(), ErrorUI> {
+    let wallet_backup_window: Window = match builder.object("WalletBackupWindow") {
+        Some(wallet_backup_window) => wallet_backup_window,
+        None => return Err(ErrorUI::MissingElement("WalletBackupWindow".to_string())),
     };
-    transaction_error_button.connect_clicked(move |_| {
-        transaction_error_window.set_visible(false);
+    wallet_backup_window.set_application(Some(application));
+    wallet_backup_window.connect_delete_event(|window, _| {
+        window.set_visible(false);
+        Inhibit(true)
     });
-    Ok(())
-}
 
-/// This function sets up the error window
-fn login_merkle_error_window(builder: &Builder) -> Result<(), ErrorUI> {
-    let merkle_error_window: Window = match builder.object("MerkleProofErrorWindow") {
-        Some(merkle_error_window) => merkle_error_window,
-        None => {
-            return Err(ErrorUI::MissingElement(
-                "MerkleProofErrorWindow".to_string(),
-            ))
-        }
+    let wallet_backup_menu_item: MenuItem = match builder.object("WalletBackupMenuItem") {
+        Some(wallet_backup_menu_item) => wallet_backup_menu_item,
+        None => return Err(ErrorUI::MissingElement("WalletBackupMenuItem".to_string())),
     };
-    let merkle_error_button: Button = match builder.object("OkMerkleProofErrorButton") {
-        Some(merkle_error_button) => merkle_error_button,
-        None => {
-            return Err(ErrorUI::MissingElement(
-                "OkMerkleProofErrorButton".to_string(),
-            ))
+    let cloned_builder = builder.clone();
+    wallet_backup_menu_item.connect_activate(move |_| {
+        if let Some(status_label) = cloned_builder.object::<Label>("WalletBackupStatusLabel") {
+            status_label.set_text("");
         }
-    };
-    merkle_error_button.connect_clicked(move |_| {
-        merkle_error_window.set_visible(false);
+        wallet_backup_window.set_visible(true);
     });
+
+    for (button_name, is_export) in [("ExportWalletButton", true), ("ImportWalletButton", false)] {
+        let button: Button = match builder.object(button_name) {
+            Some(button) => button,
+            None => return Err(ErrorUI::MissingElement(button_name.to_string())),
+        };
+        let cloned_builder = builder.clone();
+        let tx_to_back = tx_to_back.clone();
+        button.connect_clicked(move |_| {
+            let path_entry: Entry = match cloned_builder.object("WalletBackupPathEntry") {
+                Some(entry) => entry,
+                None => {
+                    println!("Error: Missing element WalletBackupPathEntry");
+                    Entry::new()
+                }
+            };
+            let password_entry: Entry = match cloned_builder.object("WalletBackupPasswordEntry") {
+                Some(entry) => entry,
+                None => {
+                    println!("Error: Missing element WalletBackupPasswordEntry");
+                    Entry::new()
+                }
+            };
+
+            let path = path_entry.text().trim().to_string();
+            if path.is_empty() {
+                if let Some(status_label) =
+                    cloned_builder.object::<Label>("WalletBackupStatusLabel")
+                {
+                    status_label.set_text("Enter the path of the backup");
+                }
+                return;
+            }
+            let password = Some(password_entry.text().trim().to_string())
+                .filter(|password| !password.is_empty());
+
+            let signal = match is_export {
+                true => SignalToBack::ExportWallet(path, password),
+                false => SignalToBack::ImportWallet(path, password),
+            };
+            if tx_to_back.send(signal).is_err() {
+                println!("Error sending wallet backup signal");
+            }
+            password_entry.set_text("");
+        });
+    }
     Ok(())
 }
 
-/// Function that makes the error window for the merkle proof of inclusion visible
-fn show_merkle_error_window(builder: &Builder, error: String) -> Result<(), ErrorUI> {
-    let merkle_error_window: Window = match builder.object("MerkleProofErrorWindow") {
-        Some(merkle_error_window) => merkle_error_window,
+/// This function sets up the window to set, change or remove the spending password of the wallet
+fn login_spending_password_window(
+    builder: &Builder,
+    application: &gtk::Application,
+    tx_to_back: Sender<SignalToBack>,
+) -> Result<(), ErrorUI> {
+    let spending_password_window: Window = match builder.object("SpendingPasswordWindow") {
+        Some(spending_password_window) => spending_password_window,
         None => {
             return Err(ErrorUI::MissingElement(
-                "MerkleProofErrorWindow".to_string(),
+                "SpendingPasswordWindow".to_string(),
             ))
         }
     };
-    let merkle_error_label: Label = match builder.object("MerkleProofErrorLabelMessage") {
-        Some(merkle_error_label) => merkle_error_label,
+    spending_password_window.set_application(Some(application));
+    spending_password_window.connect_delete_event(|window, _| {
+        window.set_visible(false);
+        Inhibit(true)
+    });
+
+    let spending_password_menu_item: MenuItem = match builder.object("SpendingPasswordMenuItem") {
+        Some(spending_password_menu_item) => spending_password_menu_item,
         None => {
             return Err(ErrorUI::MissingElement(
-                "MerkleProofErrorLabelMessage".to_string(),
+                "SpendingPasswordMenuItem".to_string(),
             ))
         }
     };
-    merkle_error_label.set_text(&error);
-    merkle_error_window.set_visible(true);
-    Ok(())
-}
-
-/// Turns a hashtype to a string
-fn from_hashtype_to_string(hash: HashType) -> String {
-    let mut hash_string = "".to_string();
-    for byte in hash.iter() {
-        hash_string.push_str(&format!("{:02x}", byte));
-    }
-    hash_string
-}
+    let cloned_builder = builder.clone();
+    spending_password_menu_item.connect_activate(move |_| {
+        if let Some(status_label) = cloned_builder.object::<Label>("SpendingPasswordStatusLabel") {
+            status_label.set_text("");
+        }
+        spending_password_window.set_visible(true);
+    });
 
-fn show_merkle_proof_success_window(
-    builder: &Builder,
-    merkle_path: Vec<HashType>,
-    root: HashType,
-) -> Result<(), ErrorUI> {
-    let merkle_success_window: Window = match builder.object("MerkleProofSuccessfulWindow") {
-        Some(merkle_success_window) => merkle_success_window,
+    let change_button: Button = match builder.object("ChangeSpendingPasswordButton") {
+        Some(button) => button,
         None => {
             return Err(ErrorUI::MissingElement(
-                "MerkleProofSuccessfulWindow".to_string(),
+                "ChangeSpendingPasswordButton".to_string(),
             ))
         }
     };
-    let merkle_success_label: Label = match builder.object("MerkleProofNotificationLabel") {
-        Some(merkle_success_label) => merkle_success_label,
-        None => {
-            return Err(ErrorUI::MissingElement(
-                "MerkleProofNotificationLabel".to_string(),
-            ))
+    let cloned_builder = builder.clone();
+    let cloned_tx_to_back = tx_to_back.clone();
+    change_button.connect_clicked(move |_| {
+        let mut passwords: Vec<Option<String>> = Vec::new();
+        for entry_name in ["CurrentSpendingPasswordEntry", "NewSpendingPasswordEntry"] {
+            let entry: Entry = match cloned_builder.object(entry_name) {
+                Some(entry) => entry,
+                None => {
+                    println!("Error: Missing element {entry_name}");
+                    Entry::new()
+                }
+            };
+            passwords.push(Some(entry.text().to_string()).filter(|password| !password.is_empty()));
+            entry.set_text("");
         }
-    };
-
-    let mut message_path = "".to_string();
 
-    for hash in merkle_path {
-        message_path.push_str(&format!("{}\n", from_hashtype_to_string(hash)));
-    }
+        let new_password = passwords.pop().flatten();
+        let password = passwords.pop().flatten();
+        if cloned_tx_to_back
+            .send(SignalToBack::SetSpendingPassword(password, new_password))
+            .is_err()
+        {
+            println!("Error sending spending password signal");
+        }
+    });
 
-    let message = format!(
-        "Merkle root: \n{}\n Merkle path:\n{}",
-        from_hashtype_to_string(root),
-        message_path
-    );
+    let unlock_button: Button = match builder.object("UnlockWalletButton") {
+        Some(button) => button,
+        None => return Err(ErrorUI::MissingElement("UnlockWalletButton".to_string())),
+    };
+    let cloned_builder = builder.clone();
+    unlock_button.connect_clicked(move |_| {
+        let password_entry: Entry = match cloned_builder.object("CurrentSpendingPasswordEntry") {
+            Some(entry) => entry,
+            None => {
+                println!("Error: Missing element CurrentSpendingPasswordEntry");
+                Entry::new()
+            }
+        };
+        let password = password_entry.text().to_string();
+        password_entry.set_text("");
 
-    merkle_success_label.set_text(&message);
-    merkle_success_window.set_visible(true);
+        if tx_to_back
+            .send(SignalToBack::UnlockWallet(password))
+            .is_err()
+        {
+            println!("Error sending unlock wallet signal");
+        }
+    });
     Ok(())
 }
 
-/// This function sets up the error window
-fn login_merkle_proof_successful_window(builder: &Builder) -> Result<(), ErrorUI> {
-    let merkle_success_window: Window = match builder.object("MerkleProofSuccessfulWindow") {
-        Some(merkle_success_window) => merkle_success_window,
-        None => {
-            return Err(ErrorUI::MissingElement(
-                "MerkleProofSuccessfulWindow".to_string(),
-            ))
-        }
+/// This function sets up the window to rescan the blocks for the accounts of the wallet from a height
+/// or a timestamp, and the item of the file menu to show it
+fn login_rescan_wallet_window(
+    builder: &Builder,
+    application: &gtk::Application,
+    tx_to_back: Sender<SignalToBack>,
+) -> Result<(), ErrorUI> {
+    let rescan_wallet_window: Window = match builder.object("RescanWalletWindow") {
+        Some(rescan_wallet_window) => rescan_wallet_window,
+        None => return Err(ErrorUI::MissingElement("RescanWalletWindow".to_string())),
     };
-    let merkle_success_button: Button = match builder.object("OkMerkleProofNotificationButton") {
-        Some(merkle_success_button) => merkle_success_button,
-        None => {
-            return Err(ErrorUI::MissingElement(
-                "OkMerkleProofNotificationButton".to_string(),
-            ))
-        }
+    rescan_wallet_window.set_application(Some(application));
+    rescan_wallet_window.connect_delete_event(|window, _| {
+        window.set_visible(false);
+        Inhibit(true)
+    });
+
+    let rescan_wallet_menu_item: MenuItem = match builder.object("RescanWalletMenuItem") {
+        Some(rescan_wallet_menu_item) => rescan_wallet_menu_item,
+        None => return Err(ErrorUI::MissingElement("RescanWalletMenuItem".to_string())),
     };
-    merkle_success_button.connect_clicked(move |_| {
-        merkle_success_window.set_visible(false);
+    let cloned_builder = builder.clone();
+    let cloned_window = rescan_wallet_window.clone();
+    rescan_wallet_menu_item.connect_activate(move |_| {
+        if let Some(status_label) = cloned_builder.object::<Label>("RescanWalletStatusLabel") {
+            status_label.set_text("");
+        }
+        cloned_window.set_visible(true);
     });
-    Ok(())
-}
 
-/// This function sets up the notification window for transaction successfully sent
-fn login_transaction_sent_notification_window(builder: &Builder) -> Result<(), ErrorUI> {
-    let transaction_sent_notification_window: Window =
-        match builder.object("TransactionSentNotificationWindow") {
-            Some(transaction_sent_notification_window) => transaction_sent_notification_window,
+    let rescan_button: Button = match builder.object("RescanWalletButton") {
+        Some(button) => button,
+        None => return Err(ErrorUI::MissingElement("RescanWalletButton".to_string())),
+    };
+    let cloned_builder = builder.clone();
+    rescan_button.connect_clicked(move |_| {
+        let from_entry: Entry = match cloned_builder.object("RescanFromEntry") {
+            Some(entry) => entry,
             None => {
-                return Err(ErrorUI::MissingElement(
-                    "TransactionSentNotificationWindow".to_string(),
-                ))
+                println!("Error: Missing element RescanFromEntry");
+                Entry::new()
             }
         };
-    let transaction_sent_notification_button: Button =
-        match builder.object("OkSentNotificationButton") {
-            Some(transaction_sent_notification_button) => transaction_sent_notification_button,
-            None => {
-                return Err(ErrorUI::MissingElement(
-                    "OkSentNotificationButton".to_string(),
-                ))
+
+        let from = match from_entry.text().trim().parse::<u64>() {
+            Ok(from) => from,
+            Err(_) => {
+                if let Some(status_label) =
+                    cloned_builder.object::<Label>("RescanWalletStatusLabel")
+                {
+                    status_label.set_text("Enter a valid height or timestamp");
+                }
+                return;
             }
         };
-    transaction_sent_notification_button.connect_clicked(move |_| {
-        transaction_sent_notification_window.set_visible(false);
+
+        if tx_to_back.send(SignalToBack::RescanWallet(from)).is_err() {
+            println!("Error sending rescan wallet signal");
+        }
+        rescan_wallet_window.set_visible(false);
     });
     Ok(())
 }
 
-fn show_new_transaction_sent_notification(builder: &Builder, tx_id: String) -> Result<(), ErrorUI> {
-    let transaction_sent_notification_window: Window =
-        match builder.object("TransactionSentNotificationWindow") {
-            Some(transaction_notification_window) => transaction_notification_window,
+/// This function sets up the window to choose the wallet in use, to create a new one or to open
+/// the file of another, and the items of the file menu to show it and to close the wallet
+fn login_wallet_chooser_window(
+    builder: &Builder,
+    application: &gtk::Application,
+    tx_to_back: Sender<SignalToBack>,
+) -> Result<(), ErrorUI> {
+    let wallet_chooser_window: Window = match builder.object("WalletChooserWindow") {
+        Some(wallet_chooser_window) => wallet_chooser_window,
+        None => return Err(ErrorUI::MissingElement("WalletChooserWindow".to_string())),
+    };
+    wallet_chooser_window.set_application(Some(application));
+    wallet_chooser_window.connect_delete_event(|window, _| {
+        window.set_visible(false);
+        Inhibit(true)
+    });
+
+    for menu_item_name in ["NewWalletMenuItem", "OpenWalletMenuItem"] {
+        let menu_item: MenuItem = match builder.object(menu_item_name) {
+            Some(menu_item) => menu_item,
+            None => return Err(ErrorUI::MissingElement(menu_item_name.to_string())),
+        };
+        let cloned_builder = builder.clone();
+        menu_item.connect_activate(move |_| {
+            if let Err(error) = show_wallet_chooser(&cloned_builder, "") {
+                println!("Error showing the wallet chooser, with error {:?}", error);
+            }
+        });
+    }
+
+    let close_wallet_menu_item: MenuItem = match builder.object("CloseWalletMenuItem") {
+        Some(close_wallet_menu_item) => close_wallet_menu_item,
+        None => return Err(ErrorUI::MissingElement("CloseWalletMenuItem".to_string())),
+    };
+    let cloned_tx_to_back = tx_to_back.clone();
+    close_wallet_menu_item.connect_activate(move |_| {
+        if cloned_tx_to_back.send(SignalToBack::CloseWallet).is_err() {
+            println!("Error sending close wallet signal");
+        }
+    });
+
+    let use_wallet_button: Button = match builder.object("UseWalletButton") {
+        Some(button) => button,
+        None => return Err(ErrorUI::MissingElement("UseWalletButton".to_string())),
+    };
+    let cloned_builder = builder.clone();
+    let cloned_tx_to_back = tx_to_back.clone();
+    use_wallet_button.connect_clicked(move |_| {
+        let combo_box: ComboBoxText = match cloned_builder.object("WalletChooserComboBox") {
+            Some(combo_box) => combo_box,
             None => {
-                return Err(ErrorUI::MissingElement(
-                    "TransactionSentNotificationWindow".to_string(),
-                ))
+                println!("Error: Missing element WalletChooserComboBox");
+                ComboBoxText::new()
             }
         };
-    let notification_label: Label = match builder.object("TransactionSentNotificationLabel") {
-        Some(notification_label) => notification_label,
+        let wallet_name = match combo_box.active_text() {
+            Some(wallet_name) => wallet_name,
+            None => {
+                if let Some(status_label) =
+                    cloned_builder.object::<Label>("WalletChooserStatusLabel")
+                {
+                    status_label.set_text("Select a wallet");
+                }
+                return;
+            }
+        };
+
+        if cloned_tx_to_back
+            .send(SignalToBack::ChangeWallet(wallet_name.to_string()))
+            .is_err()
+        {
+            println!("Error sending change wallet signal");
+        }
+        wallet_chooser_window.set_visible(false);
+    });
+
+    for (button_name, entry_name, is_new) in [
+        ("CreateWalletButton", "NewWalletNameEntry", true),
+        ("OpenWalletFileButton", "WalletFilePathEntry", false),
+    ] {
+        let button: Button = match builder.object(button_name) {
+            Some(button) => button,
+            None => return Err(ErrorUI::MissingElement(button_name.to_string())),
+        };
+        let entry: Entry = match builder.object(entry_name) {
+            Some(entry) => entry,
+            None => return Err(ErrorUI::MissingElement(entry_name.to_string())),
+        };
+        let cloned_builder = builder.clone();
+        let tx_to_back = tx_to_back.clone();
+        button.connect_clicked(move |_| {
+            let value = entry.text().trim().to_string();
+            if value.is_empty() {
+                if let Some(status_label) =
+                    cloned_builder.object::<Label>("WalletChooserStatusLabel")
+                {
+                    status_label.set_text(match is_new {
+                        true => "Enter the name of the new wallet",
+                        false => "Enter the path of the wallet file",
+                    });
+                }
+                return;
+            }
+
+            let signal = match is_new {
+                true => SignalToBack::CreateWallet(value),
+                false => SignalToBack::OpenWalletFile(value),
+            };
+            if tx_to_back.send(signal).is_err() {
+                println!("Error sending wallet signal");
+            }
+            entry.set_text("");
+        });
+    }
+    Ok(())
+}
+
+/// This function shows the window to choose the wallet, with the given status
+fn show_wallet_chooser(builder: &Builder, status: &str) -> Result<(), ErrorUI> {
+    let wallet_chooser_window: Window = match builder.object("WalletChooserWindow") {
+        Some(wallet_chooser_window) => wallet_chooser_window,
+        None => return Err(ErrorUI::MissingElement("WalletChooserWindow".to_string())),
+    };
+    let status_label: Label = match builder.object("WalletChooserStatusLabel") {
+        Some(status_label) => status_label,
         None => {
             return Err(ErrorUI::MissingElement(
-                "TransactionSentNotificationLabel".to_string(),
+                "WalletChooserStatusLabel".to_string(),
             ))
         }
     };
 
-    let (first, second) = tx_id.split_at(tx_id.len() / 2);
+    status_label.set_text(status);
+    wallet_chooser_window.set_visible(true);
+    Ok(())
+}
 
-    notification_label.set_text(format!("{first}\n{second}").as_str());
-    transaction_sent_notification_window.set_visible(true);
+/// This function hides the window to choose the wallet, once a wallet is in use
+fn hide_wallet_chooser(builder: &Builder) -> Result<(), ErrorUI> {
+    let wallet_chooser_window: Window = match builder.object("WalletChooserWindow") {
+        Some(wallet_chooser_window) => wallet_chooser_window,
+        None => return Err(ErrorUI::MissingElement("WalletChooserWindow".to_string())),
+    };
+    wallet_chooser_window.set_visible(false);
     Ok(())
 }
 
-/// This function sets up the notification window for transactions
-fn login_transaction_notification_window(builder: &Builder) -> Result<(), ErrorUI> {
-    let transaction_notification_window: Window =
-        match builder.object("TransactionNotificationWindow") {
-            Some(transaction_notification_window) => transaction_notification_window,
+/// This function sets up the combo box
+fn login_combo_box(builder: &Builder, tx_to_back: Sender<SignalToBack>) -> Result<(), ErrorUI> {
+    let combo_box: ComboBoxText = match builder.object("WalletsComboBox") {
+        Some(combo_box) => combo_box,
+        None => return Err(ErrorUI::MissingElement("WalletsComboBox".to_string())),
+    };
+    let cloned_builder = builder.clone();
+    combo_box.connect_changed(move |_| {
+        let combo_box_cloned: ComboBoxText = match cloned_builder.object("WalletsComboBox") {
+            Some(combo_box) => combo_box,
             None => {
-                return Err(ErrorUI::MissingElement(
-                    "TransactionNotificationWindow".to_string(),
-                ))
+                println!("Error: Missing element WalletsComboBox");
+                ComboBoxText::new()
             }
         };
-    let transaction_notification_button: Button = match builder.object("OkNotificationButton") {
-        Some(transaction_notification_button) => transaction_notification_button,
-        None => return Err(ErrorUI::MissingElement("OkNotificationButton".to_string())),
-    };
-    transaction_notification_button.connect_clicked(move |_| {
-        transaction_notification_window.set_visible(false);
+        let selected_wallet = match combo_box_cloned.active_text() {
+            Some(selected_wallet) => selected_wallet,
+            None => return,
+        };
+        if let Err(error) = tx_to_back.send(SignalToBack::ChangeSelectedAccount(
+            selected_wallet.to_string(),
+        )) {
+            println!("Error sending change selected account signal: {}", error);
+        }
+        if let Err(error) = tx_to_back.send(SignalToBack::GetAccountBalance) {
+            println!("Error sending get account balance signal: {}", error);
+        };
+        if let Err(error) = tx_to_back.send(SignalToBack::GetTransactionHistory) {
+            println!("Error sending get transaction history signal: {}", error);
+        };
+        if let Err(error) = tx_to_back.send(SignalToBack::GetAccountBalanceHistory(
+            BALANCE_HISTORY_FROM_HEIGHT,
+        )) {
+            println!(
+                "Error sending get account balance history signal: {}",
+                error
+            );
+        };
     });
     Ok(())
 }
 
-/// This function sets up the notification window for blocks
-fn login_block_notification_window(builder: &Builder) -> Result<(), ErrorUI> {
-    let block_notification_window: Window = match builder.object("BlockNotificationWindow") {
-        Some(block_notification_window) => block_notification_window,
-        None => {
-            return Err(ErrorUI::MissingElement(
-                "BlockNotificationWindow".to_string(),
-            ))
-        }
+/// Draws the balance history as a line chart of balance over height, with the background and
+/// line colors of the selected theme
+fn draw_balance_history(
+    context: &cairo::Context,
+    (width, height): (f64, f64),
+    (background_color, line_color): (gdk::RGBA, gdk::RGBA),
+    history: &[BalanceCheckpoint],
+) -> Result<(), cairo::Error> {
+    set_source_color(context, &background_color);
+    context.paint()?;
+
+    let (first_height, last_height) = match (history.first(), history.last()) {
+        (Some((first_height, _)), Some((last_height, _))) => (*first_height, *last_height),
+        _ => return Ok(()),
     };
-    let block_notification_button: Button = match builder.object("OkBlockNotificationButton") {
-        Some(block_notification_button) => block_notification_button,
-        None => {
-            return Err(ErrorUI::MissingElement(
-                "OkBlockNotificationButton".to_string(),
-            ))
-        }
+    let max_balance = history
+        .iter()
+        .map(|(_, balance)| *balance)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let chart_width = width - 2.0 * BALANCE_HISTORY_CHART_MARGIN;
+    let chart_height = height - 2.0 * BALANCE_HISTORY_CHART_MARGIN;
+    let height_range = (last_height - first_height).max(1) as f64;
+
+    let position = |block_height: u64, balance: i64| -> (f64, f64) {
+        let x = BALANCE_HISTORY_CHART_MARGIN
+            + chart_width * ((block_height - first_height) as f64 / height_range);
+        let y = BALANCE_HISTORY_CHART_MARGIN
+            + chart_height * (1.0 - balance as f64 / max_balance as f64);
+        (x, y)
     };
-    block_notification_button.connect_clicked(move |_| {
-        block_notification_window.set_visible(false);
-    });
-    Ok(())
-}
 
-/// Requests the merkle proof of a transaction with the data entered by the user
-///
-/// ### Error
-///  * `ErrorUI::FailedSignalToFront`: It will appear when the sender fails
-pub fn request_merkle_proof<N: Notifier>(
-    block_chain: &BlockChain,
-    block_hash: &str,
-    transaction_id: &str,
-    notifier: N,
-    logger: LoggerSender,
-) -> Result<(), ErrorUI> {
-    let block_hash = from_hexa::from::<HASH_TYPE_SIZE>(block_hash)?;
+    set_source_color(context, &line_color);
+    context.set_line_width(2.0);
+
+    let mut previous_balance: Option<i64> = None;
+    for (block_height, balance) in history.iter() {
+        let (x, y) = position(*block_height, *balance);
+        match previous_balance {
+            Some(previous_balance) => {
+                let (_, previous_y) = position(*block_height, previous_balance);
+                context.line_to(x, previous_y);
+                context.line_to(x, y);
+            }
+            None => context.move_to(x, y),
+        }
+        previous_balance = Some(*balance);
+    }
 
-    let transaction_id = from_hexa::from::<HASH_TYPE_SIZE>(transaction_id)?;
+    if let Some(balance) = previous_balance {
+        let (_, y) = position(last_height, balance);
+        context.line_to(BALANCE_HISTORY_CHART_MARGIN + chart_width, y);
+    }
 
-    transaction::verify_transaction_merkle_proof_of_inclusion(
-        block_chain,
-        block_hash,
-        transaction_id,
-        notifier,
-        logger,
-    );
+    context.stroke()
+}
 
-    Ok(())
+fn set_source_color(context: &cairo::Context, color: &gdk::RGBA) {
+    context.set_source_rgba(color.red(), color.green(), color.blue(), color.alpha());
 }
 
-/// This function sets up the notification window for merkle proof
-fn login_merkle_proof_window(
+/// This function sets up the chart of the balance history
+fn login_balance_history_chart(
     builder: &Builder,
-    tx_to_back: Sender<SignalToBack>,
+    balance_history: BalanceHistoryData,
 ) -> Result<(), ErrorUI> {
-    let validation_button: Button = match builder.object("MerkleProofValidateButton") {
-        Some(validation_button) => validation_button,
+    let drawing_area: DrawingArea = match builder.object("BalanceHistoryDrawingArea") {
+        Some(drawing_area) => drawing_area,
         None => {
             return Err(ErrorUI::MissingElement(
-                "MerkleProofValidateButton".to_string(),
+                "BalanceHistoryDrawingArea".to_string(),
             ))
         }
     };
-    let cloned_builder = builder.clone();
-    validation_button.connect_clicked(move |_| {
-        let block_hash: Entry = match cloned_builder.object("BlockHeaderHashEntry") {
-            Some(entry) => entry,
-            None => {
-                println!("Error: Missing element BlockHeaderHashEntry");
-                Entry::new()
-            }
-        };
-        let transaction_id: Entry = match cloned_builder.object("TransactionIDEntry") {
-            Some(entry) => entry,
-            None => {
-                println!("Error: Missing element TransactionIDEntry");
-                Entry::new()
-            }
-        };
 
-        if tx_to_back
-            .send(SignalToBack::RequestMerkleProof(
-                block_hash.text().to_string(),
-                transaction_id.text().to_string(),
-            ))
-            .is_err()
+    drawing_area.connect_draw(move |widget, context| {
+        let width = widget.allocated_width() as f64;
+        let height = widget.allocated_height() as f64;
+        let style_context = widget.style_context();
+        let colors = (
+            style_context
+                .lookup_color(CHART_BACKGROUND_COLOR)
+                .unwrap_or(gdk::RGBA::new(1.0, 1.0, 1.0, 1.0)),
+            style_context
+                .lookup_color(CHART_LINE_COLOR)
+                .unwrap_or(gdk::RGBA::new(0.2, 0.4, 0.8, 1.0)),
+        );
+        if let Err(error) =
+            draw_balance_history(context, (width, height), colors, &balance_history.borrow())
         {
-            println!("Error sending merkle proof signal");
+            println!("Error drawing balance history, with error {:?}", error);
         }
+        Inhibit(false)
     });
 
     Ok(())
 }
 
-/// This function makes the error window visible and sets the error message
-fn show_window_with_error(builder: &Builder, error: &str) -> Result<(), ErrorUI> {
-    let transaction_error_window: Window = match builder.object("TransactionErrorWindow") {
-        Some(transaction_error_window) => transaction_error_window,
+/// Updates the chart with the new balance history
+fn show_balance_history_chart(
+    builder: &Builder,
+    balance_history: &BalanceHistoryData,
+    history: Vec<BalanceCheckpoint>,
+) -> Result<(), ErrorUI> {
+    let drawing_area: DrawingArea = match builder.object("BalanceHistoryDrawingArea") {
+        Some(drawing_area) => drawing_area,
         None => {
             return Err(ErrorUI::MissingElement(
-                "TransactionErrorWindow".to_string(),
+                "BalanceHistoryDrawingArea".to_string(),
             ))
         }
     };
-    let error_label: Label = match builder.object("ErrorLabel") {
-        Some(error_label) => error_label,
-        None => return Err(ErrorUI::MissingElement("ErrorLabel".to_string())),
-    };
-    error_label.set_text(error);
-    transaction_error_window.set_visible(true);
+
+    *balance_history.borrow_mut() = history;
+    drawing_area.queue_draw();
     Ok(())
 }
 
-/// This function makes the notification window visible and sets the notification message
-fn show_new_transaction_notification(
+/// Adds the checkpoint to the balance history shown, replacing the last one if it's at the same height
+fn add_balance_checkpoint_to_chart(
     builder: &Builder,
-    account_name: String,
+    balance_history: &BalanceHistoryData,
+    checkpoint: BalanceCheckpoint,
 ) -> Result<(), ErrorUI> {
-    let transaction_notification_window: Window =
-        match builder.object("TransactionNotificationWindow") {
-            Some(transaction_notification_window) => transaction_notification_window,
-            None => {
-                return Err(ErrorUI::MissingElement(
-                    "TransactionNotificationWindow".to_string(),
-                ))
-            }
-        };
-    let notification_label: Label = match builder.object("TransactionNotificationLabel") {
-        Some(notification_label) => notification_label,
+    let drawing_area: DrawingArea = match builder.object("BalanceHistoryDrawingArea") {
+        Some(drawing_area) => drawing_area,
         None => {
             return Err(ErrorUI::MissingElement(
-                "TransactionNotificationLabel".to_string(),
+                "BalanceHistoryDrawingArea".to_string(),
             ))
         }
     };
-    notification_label.set_text(format!("New transaction for account {}", account_name).as_str());
-    transaction_notification_window.set_visible(true);
+
+    let mut history = balance_history.borrow_mut();
+    match history.last_mut() {
+        Some(last_checkpoint) if last_checkpoint.0 == checkpoint.0 => *last_checkpoint = checkpoint,
+        _ => history.push(checkpoint),
+    }
+    drawing_area.queue_draw();
     Ok(())
 }
 
-/// This function makes the notification window visible and sets the notification message
-fn show_new_block_notification(
+/// Draws the QR code centered in the area, leaving a light border of four modules around it
+fn draw_qr_code(
+    context: &cairo::Context,
+    width: f64,
+    height: f64,
+    qr_code: &Option<QrCode>,
+) -> Result<(), cairo::Error> {
+    context.set_source_rgb(1.0, 1.0, 1.0);
+    context.paint()?;
+
+    let qr_code = match qr_code {
+        Some(qr_code) => qr_code,
+        None => return Ok(()),
+    };
+
+    let size = qr_code.size();
+    let module_size = (width.min(height) / (size + 2 * QR_CODE_BORDER) as f64).floor();
+    let x_offset = (width - module_size * size as f64) / 2.0;
+    let y_offset = (height - module_size * size as f64) / 2.0;
+
+    context.set_source_rgb(0.0, 0.0, 0.0);
+    for y in 0..size {
+        for x in 0..size {
+            if qr_code.is_dark(x, y) {
+                context.rectangle(
+                    x_offset + x as f64 * module_size,
+                    y_offset + y as f64 * module_size,
+                    module_size,
+                    module_size,
+                );
+            }
+        }
+    }
+    context.fill()
+}
+
+/// This function sets up the receive page, where the QR code of the address of the account is drawn
+fn login_receive_page(
     builder: &Builder,
-    block_hash: String,
-    transaction: String,
+    receive_qr_code: ReceiveQrCodeData,
 ) -> Result<(), ErrorUI> {
-    let block_notification_window: Window = match builder.object("BlockNotificationWindow") {
-        Some(block_notification_window) => block_notification_window,
+    let drawing_area: DrawingArea = match builder.object("ReceiveQrCodeDrawingArea") {
+        Some(drawing_area) => drawing_area,
         None => {
             return Err(ErrorUI::MissingElement(
-                "BlockNotificationWindow".to_string(),
+                "ReceiveQrCodeDrawingArea".to_string(),
             ))
         }
     };
-    let notification_label: Label = match builder.object("BlockNotificationLabel") {
-        Some(notification_label) => notification_label,
+
+    drawing_area.connect_draw(move |widget, context| {
+        let width = widget.allocated_width() as f64;
+        let height = widget.allocated_height() as f64;
+        if let Err(error) = draw_qr_code(context, width, height, &receive_qr_code.borrow()) {
+            println!("Error drawing QR code, with error {:?}", error);
+        }
+        Inhibit(false)
+    });
+
+    Ok(())
+}
+
+/// Shows the address of the account in use in the receive page, with the QR code of its payment request
+fn show_receive_address(
+    builder: &Builder,
+    receive_qr_code: &ReceiveQrCodeData,
+    address: String,
+) -> Result<(), ErrorUI> {
+    let address_label: Label = match builder.object("ReceiveAddressLabel") {
+        Some(label) => label,
+        None => return Err(ErrorUI::MissingElement("ReceiveAddressLabel".to_string())),
+    };
+    let drawing_area: DrawingArea = match builder.object("ReceiveQrCodeDrawingArea") {
+        Some(drawing_area) => drawing_area,
         None => {
             return Err(ErrorUI::MissingElement(
-                "BlockNotificationLabel".to_string(),
+                "ReceiveQrCodeDrawingArea".to_string(),
             ))
         }
     };
 
-    let (first_block, second_block) = block_hash.split_at(block_hash.len() / 2);
-    let (first_tx, second_tx) = transaction.split_at(transaction.len() / 2);
+    let payment_uri = match Address::new(&address) {
+        Ok(address) => PaymentUri::new(address),
+        Err(error) => return Err(ErrorUI::ErrorFromWallet(format!("{:?}", error))),
+    };
+    let qr_code = match QrCode::encode(payment_uri.to_string().as_bytes()) {
+        Ok(qr_code) => qr_code,
+        Err(error) => return Err(ErrorUI::ErrorFromWallet(format!("{:?}", error))),
+    };
 
-    notification_label.set_text(&format!(
-        "New block with hash\n{first_block}\n{second_block}\nand transaction\n{first_tx}\n{second_tx}"
-    ));
-    block_notification_window.set_visible(true);
+    address_label.set_text(&address);
+    *receive_qr_code.borrow_mut() = Some(qr_code);
+    drawing_area.queue_draw();
     Ok(())
 }
 
-/// This function adds an account to the combo box
-fn add_account_to_combo_box(builder: &Builder, account_name: &str) -> Result<(), ErrorUI> {
-    let combo_box: ComboBoxText = match builder.object("WalletsComboBox") {
-        Some(combo_box) => combo_box,
-        None => return Err(ErrorUI::MissingElement("WalletsComboBox".to_string())),
+/// This function sets up the error window
+fn login_transaction_error_window(builder: &Builder) -> Result<(), ErrorUI> {
+    let transaction_error_window: Window = match builder.object("TransactionErrorWindow") {
+        Some(transaction_error_window) => transaction_error_window,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "TransactionErrorWindow".to_string(),
+            ))
+        }
     };
-    combo_box.append_text(account_name);
-    Ok(())
+    let transaction_error_button: Button = match builder.object("OkErrorButton") {
+        Some(transaction_error_button) => transaction_error_button,
+        None => return Err(ErrorUI::MissingElement("OkErrorButton".to_string())),
+    };
+    transaction_error_button.connect_clicked(move |_| {
+        transaction_error_window.set_visible(false);
+    });
+    Ok(())
+}
+
+/// This function sets up the error window
+fn login_merkle_error_window(builder: &Builder) -> Result<(), ErrorUI> {
+    let merkle_error_window: Window = match builder.object("MerkleProofErrorWindow") {
+        Some(merkle_error_window) => merkle_error_window,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "MerkleProofErrorWindow".to_string(),
+            ))
+        }
+    };
+    let merkle_error_button: Button = match builder.object("OkMerkleProofErrorButton") {
+        Some(merkle_error_button) => merkle_error_button,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "OkMerkleProofErrorButton".to_string(),
+            ))
+        }
+    };
+    merkle_error_button.connect_clicked(move |_| {
+        merkle_error_window.set_visible(false);
+    });
+    Ok(())
+}
+
+/// Function that makes the error window for the merkle proof of inclusion visible
+fn show_merkle_error_window(builder: &Builder, error: String) -> Result<(), ErrorUI> {
+    let merkle_error_window: Window = match builder.object("MerkleProofErrorWindow") {
+        Some(merkle_error_window) => merkle_error_window,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "MerkleProofErrorWindow".to_string(),
+            ))
+        }
+    };
+    let merkle_error_label: Label = match builder.object("MerkleProofErrorLabelMessage") {
+        Some(merkle_error_label) => merkle_error_label,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "MerkleProofErrorLabelMessage".to_string(),
+            ))
+        }
+    };
+    merkle_error_label.set_text(&error);
+    merkle_error_window.set_visible(true);
+    Ok(())
+}
+
+fn show_merkle_proof_success_window(
+    builder: &Builder,
+    merkle_path: Vec<HashType>,
+    root: HashType,
+) -> Result<(), ErrorUI> {
+    let merkle_success_window: Window = match builder.object("MerkleProofSuccessfulWindow") {
+        Some(merkle_success_window) => merkle_success_window,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "MerkleProofSuccessfulWindow".to_string(),
+            ))
+        }
+    };
+    let merkle_success_label: Label = match builder.object("MerkleProofNotificationLabel") {
+        Some(merkle_success_label) => merkle_success_label,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "MerkleProofNotificationLabel".to_string(),
+            ))
+        }
+    };
+
+    let mut message_path = "".to_string();
+
+    for node in merkle_path {
+        message_path.push_str(&format!("{}\n", hash::to_hex_be(&node)));
+    }
+
+    let message = format!(
+        "Merkle root: \n{}\n Merkle path:\n{}",
+        hash::to_hex_be(&root),
+        message_path
+    );
+
+    merkle_success_label.set_text(&message);
+    merkle_success_window.set_visible(true);
+    Ok(())
+}
+
+/// This function sets up the error window
+fn login_merkle_proof_successful_window(builder: &Builder) -> Result<(), ErrorUI> {
+    let merkle_success_window: Window = match builder.object("MerkleProofSuccessfulWindow") {
+        Some(merkle_success_window) => merkle_success_window,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "MerkleProofSuccessfulWindow".to_string(),
+            ))
+        }
+    };
+    let merkle_success_button: Button = match builder.object("OkMerkleProofNotificationButton") {
+        Some(merkle_success_button) => merkle_success_button,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "OkMerkleProofNotificationButton".to_string(),
+            ))
+        }
+    };
+    merkle_success_button.connect_clicked(move |_| {
+        merkle_success_window.set_visible(false);
+    });
+    Ok(())
+}
+
+/// This function sets up the window with the description of a transaction
+fn login_transaction_description_window(builder: &Builder) -> Result<(), ErrorUI> {
+    let description_window: Window = match builder.object("TransactionDescriptionWindow") {
+        Some(description_window) => description_window,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "TransactionDescriptionWindow".to_string(),
+            ))
+        }
+    };
+    let description_button: Button = match builder.object("OkTransactionDescriptionButton") {
+        Some(description_button) => description_button,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "OkTransactionDescriptionButton".to_string(),
+            ))
+        }
+    };
+    description_button.connect_clicked(move |_| {
+        description_window.set_visible(false);
+    });
+    Ok(())
+}
+
+/// Function that makes the window with the description of a transaction visible
+fn show_transaction_description_window(
+    builder: &Builder,
+    description: String,
+) -> Result<(), ErrorUI> {
+    let description_window: Window = match builder.object("TransactionDescriptionWindow") {
+        Some(description_window) => description_window,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "TransactionDescriptionWindow".to_string(),
+            ))
+        }
+    };
+    let description_label: Label = match builder.object("TransactionDescriptionLabel") {
+        Some(description_label) => description_label,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "TransactionDescriptionLabel".to_string(),
+            ))
+        }
+    };
+    description_label.set_text(&description);
+    description_window.set_visible(true);
+    Ok(())
+}
+
+/// This function sets up the notification window for transaction successfully sent
+fn login_transaction_sent_notification_window(builder: &Builder) -> Result<(), ErrorUI> {
+    let transaction_sent_notification_window: Window =
+        match builder.object("TransactionSentNotificationWindow") {
+            Some(transaction_sent_notification_window) => transaction_sent_notification_window,
+            None => {
+                return Err(ErrorUI::MissingElement(
+                    "TransactionSentNotificationWindow".to_string(),
+                ))
+            }
+        };
+    let transaction_sent_notification_button: Button =
+        match builder.object("OkSentNotificationButton") {
+            Some(transaction_sent_notification_button) => transaction_sent_notification_button,
+            None => {
+                return Err(ErrorUI::MissingElement(
+                    "OkSentNotificationButton".to_string(),
+                ))
+            }
+        };
+    transaction_sent_notification_button.connect_clicked(move |_| {
+        transaction_sent_notification_window.set_visible(false);
+    });
+    Ok(())
+}
+
+fn show_new_transaction_sent_notification(builder: &Builder, tx_id: String) -> Result<(), ErrorUI> {
+    let transaction_sent_notification_window: Window =
+        match builder.object("TransactionSentNotificationWindow") {
+            Some(transaction_notification_window) => transaction_notification_window,
+            None => {
+                return Err(ErrorUI::MissingElement(
+                    "TransactionSentNotificationWindow".to_string(),
+                ))
+            }
+        };
+    let notification_label: Label = match builder.object("TransactionSentNotificationLabel") {
+        Some(notification_label) => notification_label,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "TransactionSentNotificationLabel".to_string(),
+            ))
+        }
+    };
+
+    let (first, second) = tx_id.split_at(tx_id.len() / 2);
+
+    notification_label.set_text(format!("{first}\n{second}").as_str());
+    transaction_sent_notification_window.set_visible(true);
+    Ok(())
+}
+
+/// This function sets up the notification window for transactions
+fn login_transaction_notification_window(builder: &Builder) -> Result<(), ErrorUI> {
+    let transaction_notification_window: Window =
+        match builder.object("TransactionNotificationWindow") {
+            Some(transaction_notification_window) => transaction_notification_window,
+            None => {
+                return Err(ErrorUI::MissingElement(
+                    "TransactionNotificationWindow".to_string(),
+                ))
+            }
+        };
+    let transaction_notification_button: Button = match builder.object("OkNotificationButton") {
+        Some(transaction_notification_button) => transaction_notification_button,
+        None => return Err(ErrorUI::MissingElement("OkNotificationButton".to_string())),
+    };
+    transaction_notification_button.connect_clicked(move |_| {
+        transaction_notification_window.set_visible(false);
+    });
+    Ok(())
+}
+
+/// This function sets up the notification window for blocks
+fn login_block_notification_window(builder: &Builder) -> Result<(), ErrorUI> {
+    let block_notification_window: Window = match builder.object("BlockNotificationWindow") {
+        Some(block_notification_window) => block_notification_window,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "BlockNotificationWindow".to_string(),
+            ))
+        }
+    };
+    let block_notification_button: Button = match builder.object("OkBlockNotificationButton") {
+        Some(block_notification_button) => block_notification_button,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "OkBlockNotificationButton".to_string(),
+            ))
+        }
+    };
+    block_notification_button.connect_clicked(move |_| {
+        block_notification_window.set_visible(false);
+    });
+    Ok(())
+}
+
+/// Requests the merkle proof of a transaction with the data entered by the user
+///
+/// ### Error
+///  * `ErrorUI::FailedSignalToFront`: It will appear when the sender fails
+pub fn request_merkle_proof<N: Notifier>(
+    block_chain: &BlockChain,
+    block_hash: &str,
+    transaction_id: &str,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let block_hash = from_hexa::hash_in_display_order(block_hash)?;
+
+    let transaction_id = from_hexa::hash_in_display_order(transaction_id)?;
+
+    transaction::verify_transaction_merkle_proof_of_inclusion(
+        block_chain,
+        block_hash,
+        transaction_id,
+        notifier,
+        logger,
+    );
+
+    Ok(())
+}
+
+/// This function sets up the notification window for merkle proof
+fn login_merkle_proof_window(
+    builder: &Builder,
+    tx_to_back: Sender<SignalToBack>,
+) -> Result<(), ErrorUI> {
+    let validation_button: Button = match builder.object("MerkleProofValidateButton") {
+        Some(validation_button) => validation_button,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "MerkleProofValidateButton".to_string(),
+            ))
+        }
+    };
+    let cloned_builder = builder.clone();
+    validation_button.connect_clicked(move |_| {
+        let block_hash: Entry = match cloned_builder.object("BlockHeaderHashEntry") {
+            Some(entry) => entry,
+            None => {
+                println!("Error: Missing element BlockHeaderHashEntry");
+                Entry::new()
+            }
+        };
+        let transaction_id: Entry = match cloned_builder.object("TransactionIDEntry") {
+            Some(entry) => entry,
+            None => {
+                println!("Error: Missing element TransactionIDEntry");
+                Entry::new()
+            }
+        };
+
+        if tx_to_back
+            .send(SignalToBack::RequestMerkleProof(
+                block_hash.text().to_string(),
+                transaction_id.text().to_string(),
+            ))
+            .is_err()
+        {
+            println!("Error sending merkle proof signal");
+        }
+    });
+
+    Ok(())
+}
+
+/// This function makes the error window visible and sets the error message
+fn show_window_with_error(builder: &Builder, error: &str) -> Result<(), ErrorUI> {
+    let transaction_error_window: Window = match builder.object("TransactionErrorWindow") {
+        Some(transaction_error_window) => transaction_error_window,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "TransactionErrorWindow".to_string(),
+            ))
+        }
+    };
+    let error_label: Label = match builder.object("ErrorLabel") {
+        Some(error_label) => error_label,
+        None => return Err(ErrorUI::MissingElement("ErrorLabel".to_string())),
+    };
+    error_label.set_text(error);
+    transaction_error_window.set_visible(true);
+    Ok(())
+}
+
+/// This function makes the notification window visible and sets the notification message
+fn show_new_transaction_notification(
+    builder: &Builder,
+    account_name: String,
+) -> Result<(), ErrorUI> {
+    let transaction_notification_window: Window =
+        match builder.object("TransactionNotificationWindow") {
+            Some(transaction_notification_window) => transaction_notification_window,
+            None => {
+                return Err(ErrorUI::MissingElement(
+                    "TransactionNotificationWindow".to_string(),
+                ))
+            }
+        };
+    let notification_label: Label = match builder.object("TransactionNotificationLabel") {
+        Some(notification_label) => notification_label,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "TransactionNotificationLabel".to_string(),
+            ))
+        }
+    };
+    notification_label.set_text(format!("New transaction for account {}", account_name).as_str());
+    transaction_notification_window.set_visible(true);
+    Ok(())
+}
+
+/// This function makes the notification window visible and sets the notification message
+fn show_new_block_notification(
+    builder: &Builder,
+    block_hash: String,
+    transaction: String,
+) -> Result<(), ErrorUI> {
+    let block_notification_window: Window = match builder.object("BlockNotificationWindow") {
+        Some(block_notification_window) => block_notification_window,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "BlockNotificationWindow".to_string(),
+            ))
+        }
+    };
+    let notification_label: Label = match builder.object("BlockNotificationLabel") {
+        Some(notification_label) => notification_label,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "BlockNotificationLabel".to_string(),
+            ))
+        }
+    };
+
+    let (first_block, second_block) = block_hash.split_at(block_hash.len() / 2);
+    let (first_tx, second_tx) = transaction.split_at(transaction.len() / 2);
+
+    notification_label.set_text(&format!(
+        "New block with hash\n{first_block}\n{second_block}\nand transaction\n{first_tx}\n{second_tx}"
+    ));
+    block_notification_window.set_visible(true);
+    Ok(())
+}
+
+/// This function shows the metadata of the last block added to the blockchain
+fn show_last_block(
+    builder: &Builder,
+    block_hash: String,
+    coinbase_info: CoinbaseInfo,
+) -> Result<(), ErrorUI> {
+    let last_block_label: Label = match builder.object("LastBlockLabel") {
+        Some(last_block_label) => last_block_label,
+        None => return Err(ErrorUI::MissingElement("LastBlockLabel".to_string())),
+    };
+
+    let height = match coinbase_info.height {
+        Some(height) => height.to_string(),
+        None => "unknown".to_string(),
+    };
+    let miner_tag = coinbase_info
+        .miner_tag
+        .unwrap_or_else(|| "unknown".to_string());
+
+    last_block_label.set_text(&format!(
+        "Last block height: {height}\nMined by: {miner_tag}"
+    ));
+    last_block_label.set_tooltip_text(Some(&block_hash));
+    Ok(())
+}
+
+/// This function shows the height of the tip of the blockchain, keeping the miner of the last block
+fn show_tip_height(builder: &Builder, height: u64, block_hash: String) -> Result<(), ErrorUI> {
+    let last_block_label: Label = match builder.object("LastBlockLabel") {
+        Some(last_block_label) => last_block_label,
+        None => return Err(ErrorUI::MissingElement("LastBlockLabel".to_string())),
+    };
+
+    let mut text = format!("Last block height: {height}");
+    if let Some((_, miner)) = last_block_label.text().split_once('\n') {
+        text.push('\n');
+        text.push_str(miner);
+    }
+
+    last_block_label.set_text(&text);
+    last_block_label.set_tooltip_text(Some(&block_hash));
+    Ok(())
+}
+
+/// Changes the name of an account in the combo boxes, keeping it selected if it was
+fn rename_account_in_combo_box(
+    builder: &Builder,
+    account_name: &str,
+    new_account_name: &str,
+) -> Result<(), ErrorUI> {
+    for combo_box_name in ["WalletsComboBox", "FilterComboBoxAccount"] {
+        let combo_box: ComboBoxText = match builder.object(combo_box_name) {
+            Some(combo_box) => combo_box,
+            None => return Err(ErrorUI::MissingElement(combo_box_name.to_string())),
+        };
+        let model = match combo_box.model() {
+            Some(model) => model,
+            None => continue,
+        };
+        let tree_iter = match model.iter_first() {
+            Some(tree_iter) => tree_iter,
+            None => continue,
+        };
+
+        let mut position = 0;
+        loop {
+            if model.value(&tree_iter, 0).get::<String>().ok().as_deref() == Some(account_name) {
+                let was_active = combo_box.active() == Some(position);
+                ComboBoxTextExt::remove(&combo_box, position as i32);
+                match combo_box_name {
+                    "FilterComboBoxAccount" => {
+                        combo_box.insert(position as i32, Some(new_account_name), new_account_name)
+                    }
+                    _ => combo_box.insert_text(position as i32, new_account_name),
+                }
+                if was_active {
+                    combo_box.set_active(Some(position));
+                }
+                break;
+            }
+            position += 1;
+            if !model.iter_next(&tree_iter) {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns if the account is the one selected in the combo box
+fn is_selected_account(builder: &Builder, account_name: &str) -> bool {
+    let combo_box: ComboBoxText = match builder.object("WalletsComboBox") {
+        Some(combo_box) => combo_box,
+        None => return false,
+    };
+    matches!(combo_box.active_text(), Some(selected) if selected == account_name)
+}
+
+/// This function shows the available, pending and total balance of the selected account
+fn show_available_balance(
+    builder: &Builder,
+    balance_in_fiat: &BalanceInFiatData,
+    balance: (Amount, Amount),
+) -> Result<(), ErrorUI> {
+    let balance_label: Label = match builder.object("AvailableBalanceLabel") {
+        Some(label) => label,
+        None => return Err(ErrorUI::MissingElement("AvailableBalanceLabel".to_string())),
+    };
+    let pending_label: Label = match builder.object("PendingBalanceLabel") {
+        Some(label) => label,
+        None => return Err(ErrorUI::MissingElement("PendingBalanceLabel".to_string())),
+    };
+    let total_label: Label = match builder.object("TotalBalanceLabel") {
+        Some(label) => label,
+        None => return Err(ErrorUI::MissingElement("TotalBalanceLabel".to_string())),
+    };
+
+    let mut balance_in_fiat = balance_in_fiat.borrow_mut();
+    balance_in_fiat.1 = balance;
+    let exchange_rate = &balance_in_fiat.0;
+
+    balance_label.set_text(&format_bitcoins(balance.0, exchange_rate));
+    pending_label.set_text(&format_bitcoins(balance.1, exchange_rate));
+    match balance.0.checked_add(balance.1) {
+        Some(total) => total_label.set_text(&format_bitcoins(total, exchange_rate)),
+        None => total_label.set_text(""),
+    }
+    Ok(())
+}
+
+/// Returns the amount of bitcoins, followed by its approximate value in the fiat currency if the
+/// exchange rate is known
+fn format_bitcoins(bitcoins: Amount, exchange_rate: &Option<ExchangeRate>) -> String {
+    match exchange_rate {
+        Some(exchange_rate) => format!(
+            "{} ({})",
+            bitcoins.format_btc(),
+            exchange_rate.format_fiat(bitcoins)
+        ),
+        None => bitcoins.format_btc(),
+    }
+}
+
+/// Shows again the last balance with the new exchange rate
+fn update_exchange_rate(
+    builder: &Builder,
+    balance_in_fiat: &BalanceInFiatData,
+    exchange_rate: ExchangeRate,
+) -> Result<(), ErrorUI> {
+    let balance = {
+        let mut balance_in_fiat = balance_in_fiat.borrow_mut();
+        balance_in_fiat.0 = Some(exchange_rate);
+        balance_in_fiat.1
+    };
+    show_available_balance(builder, balance_in_fiat, balance)
+}
+
+/// This function shows when the state of the node was last saved
+fn show_last_saved(builder: &Builder, timestamp: i64) -> Result<(), ErrorUI> {
+    let last_saved_label: Label = match builder.object("LastSavedLabel") {
+        Some(last_saved_label) => last_saved_label,
+        None => return Err(ErrorUI::MissingElement("LastSavedLabel".to_string())),
+    };
+    let saved_date = match Utc.timestamp_opt(timestamp, 0).single() {
+        Some(saved_date) => saved_date,
+        None => return Err(ErrorUI::ErrorReading("Error reading timestamp".to_string())),
+    };
+    last_saved_label.set_text(&format!(
+        "Last saved at {}",
+        saved_date.format("%Y-%m-%d %H:%M:%S")
+    ));
+    Ok(())
+}
+
+/// This function shows the statistics of the pending transactions
+fn show_mempool_info(builder: &Builder, mempool_info: MempoolInfo) -> Result<(), ErrorUI> {
+    let mempool_label: Label = match builder.object("MempoolLabel") {
+        Some(mempool_label) => mempool_label,
+        None => return Err(ErrorUI::MissingElement("MempoolLabel".to_string())),
+    };
+    let min_fee_rate = match mempool_info.min_fee_rate {
+        Some(min_fee_rate) => format!("{min_fee_rate} sat/kB"),
+        None => "-".to_string(),
+    };
+    mempool_label.set_text(&format!(
+        "Mempool: {} txs, {} bytes, {} sat in fees, min {min_fee_rate}",
+        mempool_info.transaction_count, mempool_info.total_size, mempool_info.total_fees
+    ));
+    Ok(())
+}
+
+/// This function adds an account to the combo box
+fn add_account_to_combo_box(builder: &Builder, account_name: &str) -> Result<(), ErrorUI> {
+    let combo_box: ComboBoxText = match builder.object("WalletsComboBox") {
+        Some(combo_box) => combo_box,
+        None => return Err(ErrorUI::MissingElement("WalletsComboBox".to_string())),
+    };
+    combo_box.append_text(account_name);
+
+    let account_filter: ComboBoxText = match builder.object("FilterComboBoxAccount") {
+        Some(combo_box) => combo_box,
+        None => return Err(ErrorUI::MissingElement("FilterComboBoxAccount".to_string())),
+    };
+    account_filter.append(Some(account_name), account_name);
+    Ok(())
 }
 
 ///Function that clears the contents of the send transaction window
@@ -628,67 +1679,444 @@ fn clear_send_transaction_contents(builder: &Builder) {
     let fee_spin_button: SpinButton = match builder.object("FeeSpinButton") {
         Some(entry) => entry,
         None => {
-            println!("Error: Missing element FeeSpinButton");
-            SpinButton::with_range(0.0, 0.0, 0.0)
+            println!("Error: Missing element FeeSpinButton");
+            SpinButton::with_range(0.0, 0.0, 0.0)
+        }
+    };
+    bitcoin_address_entry.set_text("");
+    amount_spin_button.set_value(0.0);
+    fee_spin_button.set_value(0.0);
+}
+
+/// Function that sets up the button to request coins from the faucet
+fn login_faucet_button(builder: &Builder, tx_to_back: Sender<SignalToBack>) -> Result<(), ErrorUI> {
+    let faucet_button: Button = match builder.object("FaucetButton") {
+        Some(button) => button,
+        None => return Err(ErrorUI::MissingElement("FaucetButton".to_string())),
+    };
+
+    faucet_button.connect_clicked(move |_| {
+        if tx_to_back.send(SignalToBack::RequestFaucetCoins).is_err() {
+            println!("Error sending signal to back");
+        }
+    });
+
+    Ok(())
+}
+
+/// Function that sets up the combo box to change the wallet in use
+fn login_wallet_files_combo_box(
+    builder: &Builder,
+    tx_to_back: Sender<SignalToBack>,
+) -> Result<(), ErrorUI> {
+    let combo_box: ComboBoxText = match builder.object("WalletFilesComboBox") {
+        Some(combo_box) => combo_box,
+        None => return Err(ErrorUI::MissingElement("WalletFilesComboBox".to_string())),
+    };
+
+    combo_box.connect_changed(move |combo_box| {
+        let wallet_name = match combo_box.active_text() {
+            Some(wallet_name) => wallet_name,
+            None => return,
+        };
+
+        if tx_to_back
+            .send(SignalToBack::ChangeWallet(wallet_name.to_string()))
+            .is_err()
+        {
+            println!("Error sending change wallet signal");
+        }
+    });
+
+    Ok(())
+}
+
+/// This function shows the wallets that can be used, selecting the one in use, in the main window
+/// and in the window to choose the wallet
+fn show_available_wallets(
+    builder: &Builder,
+    selected_wallet: &str,
+    wallet_names: &[String],
+) -> Result<(), ErrorUI> {
+    let position = wallet_names
+        .iter()
+        .position(|wallet_name| wallet_name == selected_wallet);
+
+    for combo_box_name in ["WalletFilesComboBox", "WalletChooserComboBox"] {
+        let combo_box: ComboBoxText = match builder.object(combo_box_name) {
+            Some(combo_box) => combo_box,
+            None => return Err(ErrorUI::MissingElement(combo_box_name.to_string())),
+        };
+
+        combo_box.remove_all();
+        for wallet_name in wallet_names {
+            combo_box.append_text(wallet_name);
+        }
+        combo_box.set_active(position.map(|position| position as u32));
+    }
+    Ok(())
+}
+
+/// This function removes the accounts of the previous wallet from the combo box
+fn clear_accounts_of_combo_box(builder: &Builder) -> Result<(), ErrorUI> {
+    let combo_box: ComboBoxText = match builder.object("WalletsComboBox") {
+        Some(combo_box) => combo_box,
+        None => return Err(ErrorUI::MissingElement("WalletsComboBox".to_string())),
+    };
+    combo_box.remove_all();
+
+    let account_filter: ComboBoxText = match builder.object("FilterComboBoxAccount") {
+        Some(combo_box) => combo_box,
+        None => return Err(ErrorUI::MissingElement("FilterComboBoxAccount".to_string())),
+    };
+    account_filter.remove_all();
+    account_filter.append(Some(ALL_ACCOUNTS_ID), ALL_ACCOUNTS);
+    account_filter.set_active_id(Some(ALL_ACCOUNTS_ID));
+    Ok(())
+}
+
+/// Function that sets up the send transaction page
+fn login_send_page(builder: &Builder, tx_to_back: Sender<SignalToBack>) -> Result<(), ErrorUI> {
+    let transaction_clear_all_button: Button = match builder.object("TransactionClearAllButton") {
+        Some(button) => button,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "TransactionClearAllButton".to_string(),
+            ))
+        }
+    };
+    let cloned_builder = builder.clone();
+    transaction_clear_all_button.connect_clicked(move |_| {
+        clear_send_transaction_contents(&cloned_builder);
+    });
+
+    let transaction_send_button: Button = match builder.object("TransactionSendButton") {
+        Some(button) => button,
+        None => return Err(ErrorUI::MissingElement("TransactionSendButton".to_string())),
+    };
+
+    let cloned_builder = builder.clone();
+    let cloned_tx_to_back = tx_to_back.clone();
+
+    transaction_send_button.connect_clicked(move |_| {
+        let bitcoin_address_entry: Entry = match cloned_builder.object("BitcoinAddressEntry") {
+            Some(entry) => entry,
+            None => {
+                println!("Error: Missing element BitcoinAddressEntry");
+                Entry::new()
+            }
+        };
+        let amount_spin_button: SpinButton = match cloned_builder.object("AmountSpinButton") {
+            Some(entry) => entry,
+            None => {
+                println!("Error: Missing element AmountSpinButton");
+                SpinButton::with_range(0.0, 0.0, 0.0)
+            }
+        };
+        let fee_spin_button: SpinButton = match cloned_builder.object("FeeSpinButton") {
+            Some(entry) => entry,
+            None => {
+                println!("Error: Missing element FeeSpinButton");
+                SpinButton::with_range(0.0, 0.0, 0.0)
+            }
+        };
+        let (amount, fee) =
+            match read_payment(&cloned_builder, &amount_spin_button, &fee_spin_button) {
+                Some(payment) => payment,
+                None => return,
+            };
+        let _ = cloned_tx_to_back.send(SignalToBack::PreviewTransaction(
+            bitcoin_address_entry.text().to_string(),
+            amount,
+            fee,
+        ));
+    });
+
+    login_send_confirmation_window(builder, tx_to_back.clone())?;
+    login_payment_uri(builder)?;
+    login_raw_transaction(builder, tx_to_back.clone())?;
+    login_sweep_private_key(builder, tx_to_back)
+}
+
+/// Function that sets up the window to confirm a transaction before sending it. Only when it's
+/// confirmed the transaction is created with the values of the send page
+fn login_send_confirmation_window(
+    builder: &Builder,
+    tx_to_back: Sender<SignalToBack>,
+) -> Result<(), ErrorUI> {
+    let send_confirmation_window: Window = match builder.object("SendConfirmationWindow") {
+        Some(window) => window,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "SendConfirmationWindow".to_string(),
+            ))
+        }
+    };
+    let cancel_send_button: Button = match builder.object("CancelSendButton") {
+        Some(button) => button,
+        None => return Err(ErrorUI::MissingElement("CancelSendButton".to_string())),
+    };
+    let confirm_send_button: Button = match builder.object("ConfirmSendButton") {
+        Some(button) => button,
+        None => return Err(ErrorUI::MissingElement("ConfirmSendButton".to_string())),
+    };
+
+    let spending_password_entry: Entry = match builder.object("SendSpendingPasswordEntry") {
+        Some(entry) => entry,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "SendSpendingPasswordEntry".to_string(),
+            ))
+        }
+    };
+
+    let cloned_window = send_confirmation_window.clone();
+    let cloned_entry = spending_password_entry.clone();
+    cancel_send_button.connect_clicked(move |_| {
+        cloned_entry.set_text("");
+        cloned_window.set_visible(false);
+    });
+
+    let cloned_builder = builder.clone();
+    confirm_send_button.connect_clicked(move |_| {
+        let bitcoin_address_entry: Entry = match cloned_builder.object("BitcoinAddressEntry") {
+            Some(entry) => entry,
+            None => {
+                println!("Error: Missing element BitcoinAddressEntry");
+                Entry::new()
+            }
+        };
+        let amount_spin_button: SpinButton = match cloned_builder.object("AmountSpinButton") {
+            Some(entry) => entry,
+            None => {
+                println!("Error: Missing element AmountSpinButton");
+                SpinButton::with_range(0.0, 0.0, 0.0)
+            }
+        };
+        let fee_spin_button: SpinButton = match cloned_builder.object("FeeSpinButton") {
+            Some(entry) => entry,
+            None => {
+                println!("Error: Missing element FeeSpinButton");
+                SpinButton::with_range(0.0, 0.0, 0.0)
+            }
+        };
+        let (amount, fee) =
+            match read_payment(&cloned_builder, &amount_spin_button, &fee_spin_button) {
+                Some(payment) => payment,
+                None => return,
+            };
+        let spending_password = Some(spending_password_entry.text().to_string())
+            .filter(|password| !password.is_empty());
+        let _ = tx_to_back.send(SignalToBack::CreateTransaction(
+            bitcoin_address_entry.text().to_string(),
+            amount,
+            fee,
+            spending_password,
+        ));
+        spending_password_entry.set_text("");
+        clear_send_transaction_contents(&cloned_builder);
+        send_confirmation_window.set_visible(false);
+    });
+
+    Ok(())
+}
+
+/// Function that shows the summary of the transaction to be sent, waiting for it to be confirmed
+fn show_send_confirmation(builder: &Builder, preview: TransactionPreview) -> Result<(), ErrorUI> {
+    let send_confirmation_window: Window = match builder.object("SendConfirmationWindow") {
+        Some(window) => window,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "SendConfirmationWindow".to_string(),
+            ))
+        }
+    };
+    let send_confirmation_label: Label = match builder.object("SendConfirmationLabel") {
+        Some(label) => label,
+        None => return Err(ErrorUI::MissingElement("SendConfirmationLabel".to_string())),
+    };
+
+    send_confirmation_label.set_text(&format!(
+        "Recipient: {}\nAmount: {}\nFee: {} ({:.2} satoshis/byte)\nChange: {}\nResulting balance: {}",
+        preview.recipient,
+        format_satoshis(preview.amount),
+        format_satoshis(preview.fee),
+        preview.fee_rate(),
+        format_satoshis(preview.change),
+        format_satoshis(preview.resulting_balance),
+    ));
+    send_confirmation_window.set_visible(true);
+    Ok(())
+}
+
+/// Returns the amount of satoshis in bitcoins, as `0.00012345 tBTC`
+fn format_satoshis(satoshis: i64) -> String {
+    Amount::from_sat(satoshis).to_string()
+}
+
+/// Returns the amount and the fee of the send page, rounded to satoshis. If any of them is not a valid
+/// amount of bitcoins, the error is shown and None is returned
+fn read_payment(
+    builder: &Builder,
+    amount_spin_button: &SpinButton,
+    fee_spin_button: &SpinButton,
+) -> Option<(Amount, Amount)> {
+    let amount = Amount::from_btc(&format!("{:.8}", amount_spin_button.value()));
+    let fee = Amount::from_btc(&format!("{:.8}", fee_spin_button.value()));
+
+    match (amount, fee) {
+        (Ok(amount), Ok(fee)) => Some((amount, fee)),
+        (Err(error), _) | (_, Err(error)) => {
+            if let Err(error) = show_window_with_error(builder, &format!("{:?}", error)) {
+                println!("Error showing invalid amount, with error {:?}", error);
+            }
+            None
+        }
+    }
+}
+
+/// Function that fills the address and the amount of the transaction when a BIP21 payment request
+/// (`bitcoin:<address>?amount=<amount>`) is pasted or scanned into the address entry
+fn login_payment_uri(builder: &Builder) -> Result<(), ErrorUI> {
+    let bitcoin_address_entry: Entry = match builder.object("BitcoinAddressEntry") {
+        Some(entry) => entry,
+        None => return Err(ErrorUI::MissingElement("BitcoinAddressEntry".to_string())),
+    };
+    let amount_spin_button: SpinButton = match builder.object("AmountSpinButton") {
+        Some(spin_button) => spin_button,
+        None => return Err(ErrorUI::MissingElement("AmountSpinButton".to_string())),
+    };
+
+    let cloned_builder = builder.clone();
+    bitcoin_address_entry.connect_changed(move |entry| {
+        let text = entry.text().to_string();
+        if !text.trim().to_lowercase().starts_with(PAYMENT_URI_SCHEME) {
+            return;
+        }
+
+        match PaymentUri::try_from(text.as_str()) {
+            Ok(payment_uri) => {
+                entry.set_text(&payment_uri.address.to_string());
+                if let Some(amount) = payment_uri.amount {
+                    amount_spin_button.set_value(amount.to_btc());
+                }
+            }
+            Err(error) => {
+                if let Err(error) = show_window_with_error(
+                    &cloned_builder,
+                    &format!("Invalid payment request: {:?}", error),
+                ) {
+                    println!(
+                        "Error showing invalid payment request, with error {:?}",
+                        error
+                    );
+                }
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Function that sets up the buttons to broadcast and to describe a transaction pasted in hexadecimal
+fn login_raw_transaction(
+    builder: &Builder,
+    tx_to_back: Sender<SignalToBack>,
+) -> Result<(), ErrorUI> {
+    let raw_transaction_entry: Entry = match builder.object("RawTransactionEntry") {
+        Some(entry) => entry,
+        None => return Err(ErrorUI::MissingElement("RawTransactionEntry".to_string())),
+    };
+    let validate_check_button: CheckButton =
+        match builder.object("ValidateRawTransactionCheckButton") {
+            Some(check_button) => check_button,
+            None => {
+                return Err(ErrorUI::MissingElement(
+                    "ValidateRawTransactionCheckButton".to_string(),
+                ))
+            }
+        };
+    let broadcast_button: Button = match builder.object("BroadcastRawTransactionButton") {
+        Some(button) => button,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "BroadcastRawTransactionButton".to_string(),
+            ))
         }
     };
-    bitcoin_address_entry.set_text("");
-    amount_spin_button.set_value(0.0);
-    fee_spin_button.set_value(0.0);
-}
 
-/// Function that sets up the send transaction page
-fn login_send_page(builder: &Builder, tx_to_back: Sender<SignalToBack>) -> Result<(), ErrorUI> {
-    let transaction_clear_all_button: Button = match builder.object("TransactionClearAllButton") {
+    let describe_button: Button = match builder.object("DescribeRawTransactionButton") {
         Some(button) => button,
         None => {
             return Err(ErrorUI::MissingElement(
-                "TransactionClearAllButton".to_string(),
+                "DescribeRawTransactionButton".to_string(),
             ))
         }
     };
-    let cloned_builder = builder.clone();
-    transaction_clear_all_button.connect_clicked(move |_| {
-        clear_send_transaction_contents(&cloned_builder);
+
+    let cloned_entry = raw_transaction_entry.clone();
+    let cloned_tx_to_back = tx_to_back.clone();
+    describe_button.connect_clicked(move |_| {
+        let raw_transaction = cloned_entry.text().to_string();
+        if raw_transaction.trim().is_empty() {
+            return;
+        }
+
+        let _ = cloned_tx_to_back.send(SignalToBack::DescribeRawTransaction(raw_transaction));
     });
 
-    let transaction_send_button: Button = match builder.object("TransactionSendButton") {
+    broadcast_button.connect_clicked(move |_| {
+        let raw_transaction = raw_transaction_entry.text().to_string();
+        if raw_transaction.trim().is_empty() {
+            return;
+        }
+
+        let _ = tx_to_back.send(SignalToBack::BroadcastRawTransaction(
+            raw_transaction,
+            validate_check_button.is_active(),
+        ));
+        raw_transaction_entry.set_text("");
+    });
+
+    Ok(())
+}
+
+/// Function that sets up the button to sweep a private key pasted in WIF into the selected account,
+/// paying the fee of the send page
+fn login_sweep_private_key(
+    builder: &Builder,
+    tx_to_back: Sender<SignalToBack>,
+) -> Result<(), ErrorUI> {
+    let sweep_entry: Entry = match builder.object("SweepPrivateKeyEntry") {
+        Some(entry) => entry,
+        None => return Err(ErrorUI::MissingElement("SweepPrivateKeyEntry".to_string())),
+    };
+    let sweep_button: Button = match builder.object("SweepPrivateKeyButton") {
         Some(button) => button,
-        None => return Err(ErrorUI::MissingElement("TransactionSendButton".to_string())),
+        None => return Err(ErrorUI::MissingElement("SweepPrivateKeyButton".to_string())),
+    };
+    let amount_spin_button: SpinButton = match builder.object("AmountSpinButton") {
+        Some(spin_button) => spin_button,
+        None => return Err(ErrorUI::MissingElement("AmountSpinButton".to_string())),
+    };
+    let fee_spin_button: SpinButton = match builder.object("FeeSpinButton") {
+        Some(spin_button) => spin_button,
+        None => return Err(ErrorUI::MissingElement("FeeSpinButton".to_string())),
     };
 
     let cloned_builder = builder.clone();
+    sweep_button.connect_clicked(move |_| {
+        let wif = sweep_entry.text().to_string();
+        if wif.trim().is_empty() {
+            return;
+        }
 
-    transaction_send_button.connect_clicked(move |_| {
-        let bitcoin_address_entry: Entry = match cloned_builder.object("BitcoinAddressEntry") {
-            Some(entry) => entry,
-            None => {
-                println!("Error: Missing element BitcoinAddressEntry");
-                Entry::new()
-            }
-        };
-        let amount_spin_button: SpinButton = match cloned_builder.object("AmountSpinButton") {
-            Some(entry) => entry,
-            None => {
-                println!("Error: Missing element AmountSpinButton");
-                SpinButton::with_range(0.0, 0.0, 0.0)
-            }
-        };
-        let fee_spin_button: SpinButton = match cloned_builder.object("FeeSpinButton") {
-            Some(entry) => entry,
-            None => {
-                println!("Error: Missing element FeeSpinButton");
-                SpinButton::with_range(0.0, 0.0, 0.0)
-            }
+        let (_, fee) = match read_payment(&cloned_builder, &amount_spin_button, &fee_spin_button) {
+            Some(payment) => payment,
+            None => return,
         };
-        let _ = tx_to_back.send(SignalToBack::CreateTransaction(
-            bitcoin_address_entry.text().to_string(),
-            amount_spin_button.value(),
-            fee_spin_button.value(),
-        ));
-        bitcoin_address_entry.set_text("");
-        amount_spin_button.set_value(0.0);
-        fee_spin_button.set_value(0.0);
+        let _ = tx_to_back.send(SignalToBack::SweepPrivateKey(wif.trim().to_string(), fee));
+        sweep_entry.set_text("");
     });
 
     Ok(())
@@ -704,67 +2132,590 @@ fn from_timestamp_to_string(timestamp: &u32) -> Result<String, ErrorUI> {
     Ok(datetime.format("%Y-%m-%d %H:%M:%S").to_string())
 }
 
-/// Function that takes a vector of u8 and turns it into a string
-fn from_vector_to_string(vector: &[u8; 32]) -> String {
-    let mut string = String::new();
-    for byte in vector.iter() {
-        string.push_str(&format!("{:02x}", byte));
+/// Function that sets up the transaction page, sorting the history from the newest transaction,
+/// filtering it with the values selected and saving the label written in a row
+fn login_transaction_history_page(
+    builder: &Builder,
+    tx_to_back: Sender<SignalToBack>,
+) -> Result<(), ErrorUI> {
+    let history_filter: TreeModelFilter = match builder.object("TransactionTreeModelFilter") {
+        Some(tree_model) => tree_model,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "TransactionTreeModelFilter".to_string(),
+            ))
+        }
+    };
+    let history_sort: TreeModelSort = match builder.object("TransactionTreeModelSort") {
+        Some(tree_model) => tree_model,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "TransactionTreeModelSort".to_string(),
+            ))
+        }
+    };
+    history_sort.set_sort_column_id(
+        SortColumn::Index(HISTORY_TIMESTAMP_COLUMN),
+        SortType::Descending,
+    );
+
+    let cloned_builder = builder.clone();
+    history_filter.set_visible_func(move |model, tree_iter| {
+        is_transaction_visible(&cloned_builder, model, tree_iter)
+    });
+
+    for combo_box_id in [
+        "FilterComboBoxDate",
+        "FilterComboBoxDirection",
+        "FilterComboBoxAccount",
+    ] {
+        let combo_box: ComboBoxText = match builder.object(combo_box_id) {
+            Some(combo_box) => combo_box,
+            None => return Err(ErrorUI::MissingElement(combo_box_id.to_string())),
+        };
+        let cloned_history_filter = history_filter.clone();
+        combo_box.connect_changed(move |_| cloned_history_filter.refilter());
+    }
+
+    for entry_id in ["FilterLabel", "MinAmountLabel"] {
+        let entry: Entry = match builder.object(entry_id) {
+            Some(entry) => entry,
+            None => return Err(ErrorUI::MissingElement(entry_id.to_string())),
+        };
+        let cloned_history_filter = history_filter.clone();
+        entry.connect_changed(move |_| cloned_history_filter.refilter());
+    }
+
+    let label_renderer: CellRendererText = match builder.object("LabelRenderer") {
+        Some(label_renderer) => label_renderer,
+        None => return Err(ErrorUI::MissingElement("LabelRenderer".to_string())),
+    };
+    label_renderer.connect_edited(move |_, path, label| {
+        let tree_iter = match history_sort.iter(&path) {
+            Some(tree_iter) => tree_iter,
+            None => return,
+        };
+        let transaction_id = match history_sort
+            .value(&tree_iter, HISTORY_TRANSACTION_ID_COLUMN as i32)
+            .get::<String>()
+        {
+            Ok(transaction_id) => transaction_id,
+            Err(_) => return,
+        };
+        if tx_to_back
+            .send(SignalToBack::SetTransactionLabel(
+                transaction_id,
+                label.to_string(),
+            ))
+            .is_err()
+        {
+            println!("Error sending signal to back");
+        };
+    });
+
+    Ok(())
+}
+
+/// Returns if the transaction of the row is in the range of dates, has the direction, the account,
+/// the transaction id and the minimum amount selected in the filters of the transaction page
+fn is_transaction_visible(builder: &Builder, model: &TreeModel, tree_iter: &TreeIter) -> bool {
+    let (date_filter, direction_filter, account_filter): (
+        ComboBoxText,
+        ComboBoxText,
+        ComboBoxText,
+    ) = match (
+        builder.object("FilterComboBoxDate"),
+        builder.object("FilterComboBoxDirection"),
+        builder.object("FilterComboBoxAccount"),
+    ) {
+        (Some(date_filter), Some(direction_filter), Some(account_filter)) => {
+            (date_filter, direction_filter, account_filter)
+        }
+        _ => return true,
+    };
+    let (search_entry, min_amount_entry): (Entry, Entry) = match (
+        builder.object("FilterLabel"),
+        builder.object("MinAmountLabel"),
+    ) {
+        (Some(search_entry), Some(min_amount_entry)) => (search_entry, min_amount_entry),
+        _ => return true,
+    };
+
+    let text_of = |column: u32| -> String {
+        model
+            .value(tree_iter, column as i32)
+            .get::<String>()
+            .unwrap_or_default()
+    };
+
+    if let Some((from, to)) = date_filter.active_id().and_then(|id| get_date_range(&id)) {
+        let timestamp = match model
+            .value(tree_iter, HISTORY_TIMESTAMP_COLUMN as i32)
+            .get::<u32>()
+        {
+            Ok(timestamp) => timestamp as i64,
+            Err(_) => return false,
+        };
+        if timestamp < from || timestamp >= to {
+            return false;
+        }
+    }
+
+    let direction = match direction_filter.active_id().as_deref() {
+        Some(DIRECTION_RECEIVED_ID) => Some(Direction::Received),
+        Some(DIRECTION_SENT_ID) => Some(Direction::Sent),
+        _ => None,
+    };
+    if let Some(direction) = direction {
+        if text_of(HISTORY_DIRECTION_COLUMN) != direction.to_string() {
+            return false;
+        }
+    }
+
+    if let Some(account_name) = account_filter.active_id() {
+        if account_name != ALL_ACCOUNTS_ID && text_of(HISTORY_ACCOUNT_COLUMN) != account_name {
+            return false;
+        }
+    }
+
+    let search = search_entry.text().trim().to_lowercase();
+    if !search.is_empty()
+        && !text_of(HISTORY_TRANSACTION_ID_COLUMN).contains(&search)
+        && !text_of(HISTORY_LABEL_COLUMN)
+            .to_lowercase()
+            .contains(&search)
+    {
+        return false;
+    }
+
+    if let Ok(min_amount) = min_amount_entry.text().trim().parse::<Amount>() {
+        let satoshis = model
+            .value(tree_iter, HISTORY_SATOSHIS_COLUMN as i32)
+            .get::<i64>()
+            .unwrap_or_default();
+        if satoshis.unsigned_abs() < min_amount.to_sat().unsigned_abs() {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Returns the first timestamp of the range of dates with the given id of the date filter, and the
+/// first one after it. There is no range for the option of showing every date
+fn get_date_range(range_id: &str) -> Option<(i64, i64)> {
+    let today = Utc::now().date_naive();
+    let first_of_month = today.with_day(1)?;
+    let first_of_year = NaiveDate::from_ymd_opt(today.year(), 1, 1)?;
+
+    let (from, to) = match range_id {
+        DATE_RANGE_TODAY => (today, today.checked_add_days(Days::new(1))?),
+        DATE_RANGE_THIS_WEEK => {
+            let days_from_monday = today.weekday().num_days_from_monday() as u64;
+            let monday = today.checked_sub_days(Days::new(days_from_monday))?;
+            (monday, monday.checked_add_days(Days::new(7))?)
+        }
+        DATE_RANGE_THIS_MONTH => (
+            first_of_month,
+            first_of_month.checked_add_months(Months::new(1))?,
+        ),
+        DATE_RANGE_LAST_MONTH => (
+            first_of_month.checked_sub_months(Months::new(1))?,
+            first_of_month,
+        ),
+        DATE_RANGE_THIS_YEAR => (
+            first_of_year,
+            first_of_year.checked_add_months(Months::new(12))?,
+        ),
+        _ => return None,
+    };
+
+    let to_timestamp = |date: NaiveDate| -> Option<i64> {
+        Some(
+            Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0)?)
+                .timestamp(),
+        )
+    };
+    Some((to_timestamp(from)?, to_timestamp(to)?))
+}
+
+/// Function that shows the transactions of every account of the wallet in the tree view
+fn show_transaction_history_in_tree_view(
+    builder: &Builder,
+    history: Vec<HistoryEntry>,
+) -> Result<(), ErrorUI> {
+    let transactions_tree_store: TreeStore = match builder.object("TransactionTreeStore") {
+        Some(list_store) => list_store,
+        None => return Err(ErrorUI::MissingElement("TransactionTreeStore".to_string())),
+    };
+
+    transactions_tree_store.clear();
+
+    for entry in history {
+        let satoshis = match entry.direction {
+            Direction::Received => entry.amount,
+            Direction::Sent => -entry.amount,
+        };
+
+        let tree_iter = transactions_tree_store.append(None);
+        transactions_tree_store.set_value(
+            &tree_iter,
+            0,
+            &glib::Value::from(from_timestamp_to_string(&entry.timestamp)?),
+        );
+        transactions_tree_store.set_value(&tree_iter, 1, &glib::Value::from(entry.account_name));
+        transactions_tree_store.set_value(
+            &tree_iter,
+            2,
+            &glib::Value::from(hash::to_hex_be(&entry.transaction_id)),
+        );
+        transactions_tree_store.set_value(
+            &tree_iter,
+            3,
+            &glib::Value::from(entry.direction.to_string()),
+        );
+        transactions_tree_store.set_value(
+            &tree_iter,
+            4,
+            &glib::Value::from(Amount::from_sat(satoshis).format_btc()),
+        );
+        transactions_tree_store.set_value(&tree_iter, 5, &glib::Value::from(entry.confirmations));
+        transactions_tree_store.set_value(&tree_iter, 6, &glib::Value::from(entry.timestamp));
+        transactions_tree_store.set_value(&tree_iter, 7, &glib::Value::from(satoshis));
+        transactions_tree_store.set_value(
+            &tree_iter,
+            HISTORY_LABEL_COLUMN,
+            &glib::Value::from(entry.label.unwrap_or_default()),
+        );
     }
-    string
+    Ok(())
 }
 
-/// Function that updates the tree vies with the transactions of the current account
-fn show_transactions_in_tree_view(
+/// Function that shows the new label of a transaction in every row of the history where it appears
+fn show_transaction_label(
     builder: &Builder,
-    transaction_information: Vec<(u32, [u8; 32], i64)>,
+    transaction_id: HashType,
+    label: Option<String>,
 ) -> Result<(), ErrorUI> {
     let transactions_tree_store: TreeStore = match builder.object("TransactionTreeStore") {
         Some(list_store) => list_store,
         None => return Err(ErrorUI::MissingElement("TransactionTreeStore".to_string())),
     };
 
-    transactions_tree_store.clear();
+    let transaction_id = hash::to_hex_be(&transaction_id);
+    let label = label.unwrap_or_default();
+
+    let tree_iter = match transactions_tree_store.iter_first() {
+        Some(tree_iter) => tree_iter,
+        None => return Ok(()),
+    };
+    loop {
+        if transactions_tree_store
+            .value(&tree_iter, HISTORY_TRANSACTION_ID_COLUMN as i32)
+            .get::<String>()
+            .ok()
+            == Some(transaction_id.clone())
+        {
+            transactions_tree_store.set_value(
+                &tree_iter,
+                HISTORY_LABEL_COLUMN,
+                &glib::Value::from(&label),
+            );
+        }
+        if !transactions_tree_store.iter_next(&tree_iter) {
+            return Ok(());
+        }
+    }
+}
+
+/// Function that displays the tree view with the connections
+fn show_connections_in_tree_view(
+    builder: &Builder,
+    connection: ConnectionId,
+) -> Result<(), ErrorUI> {
+    let connections_tree_store: TreeStore = match builder.object("ConnectionsTreeStore") {
+        Some(list_store) => list_store,
+        None => return Err(ErrorUI::MissingElement("ConnectionsTreeStore".to_string())),
+    };
+
+    let ip_address = connection.address.ip().to_string();
+    let port = connection.address.port().to_string();
+
+    let tree_iter = connections_tree_store.append(None);
+    connections_tree_store.set_value(
+        &tree_iter,
+        0,
+        &glib::Value::from(connection.connection_type.to_string()),
+    );
+    connections_tree_store.set_value(&tree_iter, 1, &glib::Value::from(ip_address));
+    connections_tree_store.set_value(&tree_iter, 2, &glib::Value::from(port));
+
+    Ok(())
+}
+
+/// Function that sets up the blocks page, requesting the last blocks and the transactions of the
+/// block activated in the tree view
+fn login_blocks_page(builder: &Builder, tx_to_back: Sender<SignalToBack>) -> Result<(), ErrorUI> {
+    let refresh_button: Button = match builder.object("RefreshBlocksButton") {
+        Some(button) => button,
+        None => return Err(ErrorUI::MissingElement("RefreshBlocksButton".to_string())),
+    };
+    let blocks_tree_view: TreeView = match builder.object("BlocksTreeView") {
+        Some(tree_view) => tree_view,
+        None => return Err(ErrorUI::MissingElement("BlocksTreeView".to_string())),
+    };
+
+    let cloned_tx_to_back = tx_to_back.clone();
+    refresh_button.connect_clicked(move |_| {
+        if cloned_tx_to_back
+            .send(SignalToBack::RequestRecentBlocks(RECENT_BLOCKS_SHOWN))
+            .is_err()
+        {
+            println!("Error sending signal to back");
+        };
+    });
+
+    blocks_tree_view.connect_row_activated(move |tree_view, path, _| {
+        let model = match tree_view.model() {
+            Some(model) => model,
+            None => return,
+        };
+        let tree_iter = match model.iter(path) {
+            Some(tree_iter) => tree_iter,
+            None => return,
+        };
+        let block_hash = match model.value(&tree_iter, 1).get::<String>() {
+            Ok(block_hash) => block_hash,
+            Err(_) => return,
+        };
+
+        if tx_to_back
+            .send(SignalToBack::RequestBlockTransactions(block_hash))
+            .is_err()
+        {
+            println!("Error sending signal to back");
+        };
+    });
+
+    Ok(())
+}
+
+/// Function that displays the last blocks of the blockchain in the tree view, from the newest one
+fn show_recent_blocks_in_tree_view(
+    builder: &Builder,
+    summaries: Vec<BlockSummary>,
+) -> Result<(), ErrorUI> {
+    let blocks_tree_store: TreeStore = match builder.object("BlocksTreeStore") {
+        Some(tree_store) => tree_store,
+        None => return Err(ErrorUI::MissingElement("BlocksTreeStore".to_string())),
+    };
+
+    blocks_tree_store.clear();
+
+    for summary in summaries {
+        let size = match summary.size {
+            Some(size) => size.to_string(),
+            None => "-".to_string(),
+        };
+        let fees = match summary.fees {
+            Some(fees) => fees.to_string(),
+            None => "-".to_string(),
+        };
+
+        let tree_iter = blocks_tree_store.append(None);
+        blocks_tree_store.set_value(
+            &tree_iter,
+            0,
+            &glib::Value::from(summary.height.to_string()),
+        );
+        blocks_tree_store.set_value(
+            &tree_iter,
+            1,
+            &glib::Value::from(hash::to_hex_be(&summary.hash)),
+        );
+        blocks_tree_store.set_value(
+            &tree_iter,
+            2,
+            &glib::Value::from(from_timestamp_to_string(&summary.time)?),
+        );
+        blocks_tree_store.set_value(
+            &tree_iter,
+            3,
+            &glib::Value::from(summary.transaction_count.to_string()),
+        );
+        blocks_tree_store.set_value(&tree_iter, 4, &glib::Value::from(size));
+        blocks_tree_store.set_value(
+            &tree_iter,
+            5,
+            &glib::Value::from(summary.subsidy.to_string()),
+        );
+        blocks_tree_store.set_value(&tree_iter, 6, &glib::Value::from(fees));
+    }
+    Ok(())
+}
+
+/// Function that displays the transactions of a block in the tree view
+fn show_block_transactions_in_tree_view(
+    builder: &Builder,
+    transactions: Vec<(HashType, usize, usize, i64)>,
+) -> Result<(), ErrorUI> {
+    let block_transactions_tree_store: TreeStore =
+        match builder.object("BlockTransactionsTreeStore") {
+            Some(tree_store) => tree_store,
+            None => {
+                return Err(ErrorUI::MissingElement(
+                    "BlockTransactionsTreeStore".to_string(),
+                ))
+            }
+        };
+
+    block_transactions_tree_store.clear();
 
-    for (timestamp, label, amount) in transaction_information.iter().rev() {
-        let tree_iter = transactions_tree_store.append(None);
-        transactions_tree_store.set_value(
+    for (transaction_id, inputs, outputs, total_output) in transactions {
+        let tree_iter = block_transactions_tree_store.append(None);
+        block_transactions_tree_store.set_value(
             &tree_iter,
             0,
-            &glib::Value::from(from_timestamp_to_string(timestamp)?),
+            &glib::Value::from(hash::to_hex_be(&transaction_id)),
         );
-        transactions_tree_store.set_value(&tree_iter, 1, &glib::Value::from("Mined".to_string()));
-        transactions_tree_store.set_value(
+        block_transactions_tree_store.set_value(
+            &tree_iter,
+            1,
+            &glib::Value::from(inputs.to_string()),
+        );
+        block_transactions_tree_store.set_value(
             &tree_iter,
             2,
-            &glib::Value::from(from_vector_to_string(label)),
+            &glib::Value::from(outputs.to_string()),
+        );
+        block_transactions_tree_store.set_value(
+            &tree_iter,
+            3,
+            &glib::Value::from(total_output.to_string()),
         );
-        transactions_tree_store.set_value(&tree_iter, 3, &glib::Value::from(amount.to_string()));
     }
     Ok(())
 }
 
-/// Function that displays the tree view with the connections
-fn show_connections_in_tree_view(
-    builder: &Builder,
-    connection: ConnectionId,
-) -> Result<(), ErrorUI> {
-    let connections_tree_store: TreeStore = match builder.object("ConnectionsTreeStore") {
-        Some(list_store) => list_store,
-        None => return Err(ErrorUI::MissingElement("ConnectionsTreeStore".to_string())),
+/// Function that sets up the peers page, disconnecting or banning the peer selected in the tree view
+fn login_peers_page(builder: &Builder, tx_to_back: Sender<SignalToBack>) -> Result<(), ErrorUI> {
+    let disconnect_button: Button = match builder.object("DisconnectPeerButton") {
+        Some(button) => button,
+        None => return Err(ErrorUI::MissingElement("DisconnectPeerButton".to_string())),
+    };
+    let ban_button: Button = match builder.object("BanPeerButton") {
+        Some(button) => button,
+        None => return Err(ErrorUI::MissingElement("BanPeerButton".to_string())),
+    };
+    let peers_tree_view: TreeView = match builder.object("PeersTreeView") {
+        Some(tree_view) => tree_view,
+        None => return Err(ErrorUI::MissingElement("PeersTreeView".to_string())),
     };
 
-    let ip_address = connection.address.ip().to_string();
-    let port = connection.address.port().to_string();
+    let cloned_tree_view = peers_tree_view.clone();
+    let cloned_tx_to_back = tx_to_back.clone();
+    disconnect_button.connect_clicked(move |_| {
+        if let Some(address) = get_selected_peer(&cloned_tree_view) {
+            if cloned_tx_to_back
+                .send(SignalToBack::DisconnectPeer(address))
+                .is_err()
+            {
+                println!("Error sending signal to back");
+            };
+        }
+    });
 
-    let tree_iter = connections_tree_store.append(None);
-    connections_tree_store.set_value(
+    ban_button.connect_clicked(move |_| {
+        if let Some(address) = get_selected_peer(&peers_tree_view) {
+            if tx_to_back.send(SignalToBack::BanPeer(address)).is_err() {
+                println!("Error sending signal to back");
+            };
+        }
+    });
+
+    Ok(())
+}
+
+/// Function that returns the address of the peer selected in the tree view
+fn get_selected_peer(peers_tree_view: &TreeView) -> Option<String> {
+    let (model, tree_iter) = peers_tree_view.selection().selected()?;
+    model.value(&tree_iter, 0).get::<String>().ok()
+}
+
+/// Function that returns the row of the tree store of the peer with the given address
+fn find_peer_in_tree_store(peers_tree_store: &TreeStore, address: &str) -> Option<TreeIter> {
+    let tree_iter = peers_tree_store.iter_first()?;
+    loop {
+        if peers_tree_store.value(&tree_iter, 0).get::<String>().ok()? == address {
+            return Some(tree_iter);
+        }
+        if !peers_tree_store.iter_next(&tree_iter) {
+            return None;
+        }
+    }
+}
+
+/// Function that shows the information of a peer in the tree view, updating its row if it's already shown
+fn show_peer_information(builder: &Builder, information: PeerInformation) -> Result<(), ErrorUI> {
+    let peers_tree_store: TreeStore = match builder.object("PeersTreeStore") {
+        Some(tree_store) => tree_store,
+        None => return Err(ErrorUI::MissingElement("PeersTreeStore".to_string())),
+    };
+
+    let address = information.connection.address.to_string();
+    let tree_iter = match find_peer_in_tree_store(&peers_tree_store, &address) {
+        Some(tree_iter) => tree_iter,
+        None => peers_tree_store.append(None),
+    };
+    let latency = match information.latency {
+        Some(latency) => latency.as_millis().to_string(),
+        None => "-".to_string(),
+    };
+
+    peers_tree_store.set_value(&tree_iter, 0, &glib::Value::from(address));
+    peers_tree_store.set_value(
         &tree_iter,
-        0,
-        &glib::Value::from(connection.connection_type.to_string()),
+        1,
+        &glib::Value::from(information.direction().to_string()),
     );
-    connections_tree_store.set_value(&tree_iter, 1, &glib::Value::from(ip_address));
-    connections_tree_store.set_value(&tree_iter, 2, &glib::Value::from(port));
+    peers_tree_store.set_value(&tree_iter, 2, &glib::Value::from(information.user_agent));
+    peers_tree_store.set_value(
+        &tree_iter,
+        3,
+        &glib::Value::from(i32::from(information.connection.features.version).to_string()),
+    );
+    peers_tree_store.set_value(&tree_iter, 4, &glib::Value::from(latency));
+    peers_tree_store.set_value(
+        &tree_iter,
+        5,
+        &glib::Value::from(information.bytes_received.to_string()),
+    );
+    peers_tree_store.set_value(
+        &tree_iter,
+        6,
+        &glib::Value::from(information.bytes_sent.to_string()),
+    );
+    peers_tree_store.set_value(
+        &tree_iter,
+        7,
+        &glib::Value::from(information.queued_messages.to_string()),
+    );
+
+    Ok(())
+}
+
+/// Function that removes the row of a disconnected peer from the tree view
+fn remove_peer_from_tree_view(builder: &Builder, connection: ConnectionId) -> Result<(), ErrorUI> {
+    let peers_tree_store: TreeStore = match builder.object("PeersTreeStore") {
+        Some(tree_store) => tree_store,
+        None => return Err(ErrorUI::MissingElement("PeersTreeStore".to_string())),
+    };
 
+    if let Some(tree_iter) =
+        find_peer_in_tree_store(&peers_tree_store, &connection.address.to_string())
+    {
+        peers_tree_store.remove(&tree_iter);
+    }
     Ok(())
 }
 
@@ -773,8 +2724,12 @@ fn spawn_local_handler(
     builder: &Builder,
     rx_from_back: glib::Receiver<SignalToFront>,
     tx_to_back: Sender<SignalToBack>,
+    balance_history: BalanceHistoryData,
+    receive_qr_code: ReceiveQrCodeData,
+    balance_in_fiat: BalanceInFiatData,
 ) {
     let cloned_builder = builder.clone();
+    let mut is_starting = true;
 
     rx_from_back.attach(None, move |signal| {
         match signal {
@@ -784,36 +2739,85 @@ fn spawn_local_handler(
                     println!("Error adding account to combo box, with error {:?}", error);
                 };
             }
+            SignalToFront::WalletBackupResult(result) => {
+                match cloned_builder.object::<Label>("WalletBackupStatusLabel") {
+                    Some(status_label) => status_label.set_text(&result),
+                    None => println!("Error: Missing element WalletBackupStatusLabel"),
+                }
+            }
+            SignalToFront::SpendingPasswordResult(result) => {
+                match cloned_builder.object::<Label>("SpendingPasswordStatusLabel") {
+                    Some(status_label) => status_label.set_text(&result),
+                    None => println!("Error: Missing element SpendingPasswordStatusLabel"),
+                }
+            }
+            SignalToFront::AccountRenamed(account_name, new_account_name) => {
+                if let Err(error) =
+                    rename_account_in_combo_box(&cloned_builder, &account_name, &new_account_name)
+                {
+                    println!(
+                        "Error renaming account in combo box, with error {:?}",
+                        error
+                    );
+                };
+            }
             SignalToFront::LoadAvailableBalance(balance) => {
-                let balance_label: Label = match cloned_builder.object("AvailableBalanceLabel") {
-                    Some(label) => label,
-                    None => {
-                        println!("Error: Missing element AvailableBalanceLabel");
-                        Label::new(None)
-                    }
+                if let Err(error) =
+                    show_available_balance(&cloned_builder, &balance_in_fiat, balance)
+                {
+                    println!("Error showing available balance, with error {:?}", error);
                 };
-                let pending_label: Label = match cloned_builder.object("PendingBalanceLabel") {
-                    Some(label) => label,
-                    None => {
-                        println!("Error: Missing element PendingBalanceLabel");
-                        Label::new(None)
-                    }
+            }
+            SignalToFront::AccountBalanceChanged(account_name, balance) => {
+                if is_selected_account(&cloned_builder, &account_name) {
+                    if let Err(error) =
+                        show_available_balance(&cloned_builder, &balance_in_fiat, balance)
+                    {
+                        println!("Error showing available balance, with error {:?}", error);
+                    };
+                }
+            }
+            SignalToFront::StateSaved(timestamp) => {
+                if let Err(error) = show_last_saved(&cloned_builder, timestamp) {
+                    println!(
+                        "Error showing the time of the last save, with error {:?}",
+                        error
+                    );
                 };
-                let total_label: Label = match cloned_builder.object("TotalBalanceLabel") {
-                    Some(label) => label,
-                    None => {
-                        println!("Error: Missing element TotalBalanceLabel");
-                        Label::new(None)
-                    }
+            }
+            SignalToFront::MempoolInfo(mempool_info) => {
+                if let Err(error) = show_mempool_info(&cloned_builder, mempool_info) {
+                    println!(
+                        "Error showing the mempool information, with error {:?}",
+                        error
+                    );
+                };
+            }
+            SignalToFront::ExchangeRateUpdated(exchange_rate) => {
+                if let Err(error) =
+                    update_exchange_rate(&cloned_builder, &balance_in_fiat, exchange_rate)
+                {
+                    println!("Error showing exchange rate, with error {:?}", error);
+                };
+            }
+            SignalToFront::NewBalanceCheckpoint(account_name, checkpoint) => {
+                if is_selected_account(&cloned_builder, &account_name) {
+                    if let Err(error) = add_balance_checkpoint_to_chart(
+                        &cloned_builder,
+                        &balance_history,
+                        checkpoint,
+                    ) {
+                        println!(
+                            "Error adding balance checkpoint to chart, with error {:?}",
+                            error
+                        );
+                    };
+                }
+            }
+            SignalToFront::TipAdvanced(height, block_hash) => {
+                if let Err(error) = show_tip_height(&cloned_builder, height, block_hash) {
+                    println!("Error showing tip height, with error {:?}", error);
                 };
-
-                let balance_string = format!("{:.8}", balance.0);
-                let pending_string = format!("{:.8}", balance.1);
-                let total_string = format!("{:.8}", balance.0 + balance.1);
-
-                balance_label.set_text(&balance_string);
-                pending_label.set_text(&pending_string);
-                total_label.set_text(&total_string);
             }
             SignalToFront::NotifyBlockchainIsReady => {
                 let signal_blockchain_not_ready: Image =
@@ -825,6 +2829,15 @@ fn spawn_local_handler(
                         }
                     };
                 signal_blockchain_not_ready.set_visible(false);
+                if tx_to_back
+                    .send(SignalToBack::RequestRecentBlocks(RECENT_BLOCKS_SHOWN))
+                    .is_err()
+                    || tx_to_back
+                        .send(SignalToBack::GetTransactionHistory)
+                        .is_err()
+                {
+                    println!("Error sending signal to back");
+                };
             }
             SignalToFront::ErrorInTransaction(error) => {
                 if let Err(error) = show_window_with_error(&cloned_builder, error.as_str()) {
@@ -849,16 +2862,64 @@ fn spawn_local_handler(
                     );
                 };
             }
-            SignalToFront::AccountTransactions(transaction_information) => {
+            SignalToFront::NewBlockAdded(block_hash, coinbase_info) => {
+                if let Err(error) = show_last_block(&cloned_builder, block_hash, coinbase_info) {
+                    println!("Error showing last block, with error {:?}", error);
+                };
+                if tx_to_back
+                    .send(SignalToBack::RequestRecentBlocks(RECENT_BLOCKS_SHOWN))
+                    .is_err()
+                    || tx_to_back
+                        .send(SignalToBack::GetTransactionHistory)
+                        .is_err()
+                {
+                    println!("Error sending signal to back");
+                };
+            }
+            SignalToFront::RecentBlocks(summaries) => {
+                if let Err(error) = show_recent_blocks_in_tree_view(&cloned_builder, summaries) {
+                    println!("Error showing recent blocks, with error {:?}", error);
+                };
+            }
+            SignalToFront::BlockTransactions(_, transactions) => {
+                if let Err(error) =
+                    show_block_transactions_in_tree_view(&cloned_builder, transactions)
+                {
+                    println!("Error showing block transactions, with error {:?}", error);
+                };
+            }
+            SignalToFront::TransactionHistory(history) => {
+                if let Err(error) = show_transaction_history_in_tree_view(&cloned_builder, history)
+                {
+                    println!(
+                        "Error showing transaction history in tree view, with error {:?}",
+                        error
+                    );
+                };
+            }
+            SignalToFront::TransactionLabelChanged(transaction_id, label) => {
+                if let Err(error) = show_transaction_label(&cloned_builder, transaction_id, label) {
+                    println!(
+                        "Error showing the label of the transaction, with error {:?}",
+                        error
+                    );
+                };
+            }
+            SignalToFront::AccountBalanceHistory(history) => {
                 if let Err(error) =
-                    show_transactions_in_tree_view(&cloned_builder, transaction_information)
+                    show_balance_history_chart(&cloned_builder, &balance_history, history)
                 {
                     println!(
-                        "Error showing transactions in tree view, with error {:?}",
+                        "Error showing balance history chart, with error {:?}",
                         error
                     );
                 };
             }
+            SignalToFront::TransactionPreview(preview) => {
+                if let Err(error) = show_send_confirmation(&cloned_builder, preview) {
+                    println!("Error showing send confirmation, with error {:?}", error);
+                };
+            }
             SignalToFront::SuccessfullySentTransaction(tx_id) => {
                 if let Err(error) = show_new_transaction_sent_notification(&cloned_builder, tx_id) {
                     println!(
@@ -867,10 +2928,58 @@ fn spawn_local_handler(
                     );
                 };
             }
+            SignalToFront::WalletChanged(wallet_name) => {
+                if let Err(error) = clear_accounts_of_combo_box(&cloned_builder)
+                    .and_then(|_| hide_wallet_chooser(&cloned_builder))
+                {
+                    println!(
+                        "Error changing to the wallet {wallet_name}, with error {:?}",
+                        error
+                    );
+                };
+            }
+            SignalToFront::WalletClosed(wallet_name) => {
+                if let Err(error) = clear_accounts_of_combo_box(&cloned_builder).and_then(|_| {
+                    show_wallet_chooser(
+                        &cloned_builder,
+                        &format!("The wallet {wallet_name} was closed"),
+                    )
+                }) {
+                    println!(
+                        "Error closing the wallet {wallet_name}, with error {:?}",
+                        error
+                    );
+                };
+            }
+            SignalToFront::AvailableWallets(selected_wallet, wallet_names) => {
+                if let Err(error) =
+                    show_available_wallets(&cloned_builder, &selected_wallet, &wallet_names)
+                {
+                    println!("Error showing available wallets, with error {:?}", error);
+                };
+
+                if is_starting {
+                    is_starting = false;
+                    if let Err(error) = show_wallet_chooser(&cloned_builder, "") {
+                        println!("Error showing the wallet chooser, with error {:?}", error);
+                    }
+                }
+            }
+            SignalToFront::SelectedAccountAddress(address) => {
+                if let Err(error) = show_receive_address(&cloned_builder, &receive_qr_code, address)
+                {
+                    println!("Error showing address to receive, with error {:?}", error);
+                };
+            }
             SignalToFront::Update => {
                 if tx_to_back.send(SignalToBack::GetAccountBalance).is_err()
                     || tx_to_back
-                        .send(SignalToBack::GetAccountTransactions)
+                        .send(SignalToBack::GetTransactionHistory)
+                        .is_err()
+                    || tx_to_back
+                        .send(SignalToBack::GetAccountBalanceHistory(
+                            BALANCE_HISTORY_FROM_HEIGHT,
+                        ))
                         .is_err()
                 {
                     println!("Error sending signal to back");
@@ -899,7 +3008,41 @@ fn spawn_local_handler(
                 };
                 progress_bar.set_fraction(to_update as f64 / total as f64);
             }
-            SignalToFront::UpdateBlockProgressBar(downloaded, total) => {
+            SignalToFront::UpdateRescanProgressBar(scanned, total) => {
+                let progress_label = match cloned_builder.object("ProgressLabel") {
+                    Some(progress_label) => progress_label,
+                    None => {
+                        println!("Error: Missing element ProgressLabel");
+                        Label::new(None)
+                    }
+                };
+                progress_label.set_text("Wallet Rescan Progress");
+                let progress_bar: ProgressBar = match cloned_builder.object("ProgressBar") {
+                    Some(progress_bar) => progress_bar,
+                    None => {
+                        println!("Error: Missing element ProgressBar");
+                        ProgressBar::new()
+                    }
+                };
+                progress_bar.set_fraction(match total {
+                    0 => 1.0,
+                    total => scanned as f64 / total as f64,
+                });
+
+                if scanned == total
+                    && (tx_to_back
+                        .send(SignalToBack::GetTransactionHistory)
+                        .is_err()
+                        || tx_to_back
+                            .send(SignalToBack::GetAccountBalanceHistory(
+                                BALANCE_HISTORY_FROM_HEIGHT,
+                            ))
+                            .is_err())
+                {
+                    println!("Error sending signal to back");
+                }
+            }
+            SignalToFront::UpdateBlockProgressBar(downloaded, total, description) => {
                 let progress_label = match cloned_builder.object("ProgressLabel") {
                     Some(progress_label) => progress_label,
                     None => {
@@ -916,6 +3059,8 @@ fn spawn_local_handler(
                     }
                 };
                 progress_bar.set_fraction(downloaded as f64 / total as f64);
+                progress_bar.set_text(Some(&description));
+                progress_bar.set_show_text(true);
             }
             SignalToFront::UpdateConnection(connection) => {
                 if let Err(error) = show_connections_in_tree_view(&cloned_builder, connection) {
@@ -925,6 +3070,16 @@ fn spawn_local_handler(
                     );
                 };
             }
+            SignalToFront::UpdatePeerInformation(information) => {
+                if let Err(error) = show_peer_information(&cloned_builder, information) {
+                    println!("Error showing peer information, with error {:?}", error);
+                };
+            }
+            SignalToFront::RemovePeer(connection) => {
+                if let Err(error) = remove_peer_from_tree_view(&cloned_builder, connection) {
+                    println!("Error removing peer from tree view, with error {:?}", error);
+                };
+            }
             SignalToFront::ErrorInMerkleProof(error) => {
                 if let Err(error) = show_merkle_error_window(&cloned_builder, error) {
                     println!(
@@ -943,9 +3098,207 @@ fn spawn_local_handler(
                     );
                 };
             }
+            SignalToFront::RawData(description, raw_data) => {
+                println!("Raw {description}:\n{raw_data}");
+            }
+            SignalToFront::DisplayTransactionDescription(description) => {
+                if let Err(error) =
+                    show_transaction_description_window(&cloned_builder, description)
+                {
+                    println!(
+                        "Error showing transaction description window, with error {:?}",
+                        error
+                    );
+                };
+            }
+            SignalToFront::ScriptHashEvent(_) => {}
+        }
+        glib::Continue(true)
+    });
+}
+
+/// Function that sets up the debug console, showing the last log messages registered
+fn login_debug_console(builder: &Builder, log_buffer: LogBuffer) -> Result<(), ErrorUI> {
+    let text_view: TextView = match builder.object("DebugConsoleTextView") {
+        Some(text_view) => text_view,
+        None => return Err(ErrorUI::MissingElement("DebugConsoleTextView".to_string())),
+    };
+    let text_buffer = match text_view.buffer() {
+        Some(text_buffer) => text_buffer,
+        None => {
+            return Err(ErrorUI::MissingElement(
+                "DebugConsoleTextBuffer".to_string(),
+            ))
+        }
+    };
+
+    let mut registered = 0;
+    glib::timeout_add_local(DEBUG_CONSOLE_REFRESH, move || {
+        let (lines, now_registered) = log_buffer.lines_since(registered);
+        registered = now_registered;
+        if lines.is_empty() {
+            return glib::Continue(true);
+        }
+
+        let mut end = text_buffer.end_iter();
+        for line in lines {
+            text_buffer.insert(&mut end, &format!("{line}\n"));
+        }
+
+        let excess = text_buffer.line_count() - 1 - log_buffer.capacity() as i32;
+        if excess > 0 {
+            text_buffer.delete(
+                &mut text_buffer.start_iter(),
+                &mut text_buffer.iter_at_line(excess),
+            );
         }
+
+        text_view.scroll_to_iter(&mut text_buffer.end_iter(), 0.0, false, 0.0, 0.0);
         glib::Continue(true)
     });
+
+    Ok(())
+}
+
+/// Loads the styles of the theme over the ones of the desktop theme. The system theme follows if
+/// the desktop prefers a dark theme
+///
+/// ### Error
+///  * `ErrorUI::ErrorReading`: It will appear when the styles of the theme cannot be loaded
+fn apply_theme(
+    css_provider: &CssProvider,
+    settings: &Settings,
+    theme: Theme,
+    system_prefers_dark: bool,
+) -> Result<(), ErrorUI> {
+    let is_dark = match theme {
+        Theme::Light => false,
+        Theme::Dark => true,
+        Theme::System => system_prefers_dark,
+    };
+
+    settings.set_property(PREFER_DARK_THEME_PROPERTY, is_dark);
+    let css = match is_dark {
+        true => DARK_THEME_CSS,
+        false => LIGHT_THEME_CSS,
+    };
+
+    match css_provider.load_from_data(css.as_bytes()) {
+        Ok(()) => Ok(()),
+        Err(error) => Err(ErrorUI::ErrorReading(format!(
+            "Cannot load the {theme} theme, with error {:?}",
+            error
+        ))),
+    }
+}
+
+/// Writes the theme in the UI structure of the configuration file, so it's used the next time
+///
+/// ### Error
+///  * `ErrorUI::ErrorReading`: It will appear when the configuration file cannot be read or it does not have the UI structure
+///  * `ErrorUI::ErrorWriting`: It will appear when the configuration file cannot be written
+fn save_theme(config_name: &str, theme: Theme) -> Result<(), ErrorUI> {
+    let configuration = match fs::read_to_string(config_name) {
+        Ok(configuration) => configuration,
+        Err(error) => {
+            return Err(ErrorUI::ErrorReading(format!(
+                "Cannot read the configuration file {config_name}, with error {:?}",
+                error
+            )))
+        }
+    };
+
+    let is_toml = Path::new(config_name)
+        .extension()
+        .is_some_and(|extension| extension == TOML_EXTENSION);
+    let configuration = match theme::write_theme(&configuration, UI_CONFIG, theme, is_toml) {
+        Ok(configuration) => configuration,
+        Err(error) => {
+            return Err(ErrorUI::ErrorReading(format!(
+                "Cannot write the theme in the configuration, with error {:?}",
+                error
+            )))
+        }
+    };
+
+    match fs::write(config_name, configuration) {
+        Ok(()) => Ok(()),
+        Err(error) => Err(ErrorUI::ErrorWriting(format!(
+            "Cannot write the configuration file {config_name}, with error {:?}",
+            error
+        ))),
+    }
+}
+
+/// Function that sets up the theme switcher of the menu, applying the theme of the configuration
+/// and saving the selected one in the configuration file
+fn login_theme_menu(
+    builder: &Builder,
+    (theme, config_name): (Theme, String),
+) -> Result<(), ErrorUI> {
+    let screen = match gdk::Screen::default() {
+        Some(screen) => screen,
+        None => return Err(ErrorUI::MissingElement("Screen".to_string())),
+    };
+    let settings = match Settings::default() {
+        Some(settings) => settings,
+        None => return Err(ErrorUI::MissingElement("Settings".to_string())),
+    };
+
+    let system_prefers_dark = settings.property::<bool>(PREFER_DARK_THEME_PROPERTY)
+        || settings
+            .property::<Option<String>>(THEME_NAME_PROPERTY)
+            .is_some_and(|theme_name| theme_name.to_lowercase().contains(DARK_THEME_NAME));
+
+    let css_provider = CssProvider::new();
+    StyleContext::add_provider_for_screen(
+        &screen,
+        &css_provider,
+        gtk::STYLE_PROVIDER_PRIORITY_APPLICATION,
+    );
+    apply_theme(&css_provider, &settings, theme, system_prefers_dark)?;
+
+    for (menu_item_id, menu_item_theme) in [
+        ("ThemeSystemMenuItem", Theme::System),
+        ("ThemeLightMenuItem", Theme::Light),
+        ("ThemeDarkMenuItem", Theme::Dark),
+    ] {
+        let menu_item: RadioMenuItem = match builder.object(menu_item_id) {
+            Some(menu_item) => menu_item,
+            None => return Err(ErrorUI::MissingElement(menu_item_id.to_string())),
+        };
+        menu_item.set_active(menu_item_theme == theme);
+
+        let css_provider = css_provider.clone();
+        let settings = settings.clone();
+        let config_name = config_name.clone();
+        let cloned_builder = builder.clone();
+        menu_item.connect_toggled(move |menu_item| {
+            if !menu_item.is_active() {
+                return;
+            }
+
+            if let Err(error) = apply_theme(
+                &css_provider,
+                &settings,
+                menu_item_theme,
+                system_prefers_dark,
+            ) {
+                println!("Error applying the theme, with error {:?}", error);
+            }
+            if let Some(drawing_area) =
+                cloned_builder.object::<DrawingArea>("BalanceHistoryDrawingArea")
+            {
+                drawing_area.queue_draw();
+            }
+
+            if let Err(error) = save_theme(&config_name, menu_item_theme) {
+                println!("Error saving the theme, with error {:?}", error);
+            }
+        });
+    }
+
+    Ok(())
 }
 
 /// Function that sets up all the elemeents in the ui
@@ -954,6 +3307,8 @@ pub fn build_ui(
     rx_from_back: Option<glib::Receiver<SignalToFront>>,
     application: &gtk::Application,
     glade_src: &str,
+    log_buffer: LogBuffer,
+    theme_config: (Theme, String),
 ) -> Result<(), ErrorUI> {
     let rx_from_back = match rx_from_back {
         Some(rx) => rx,
@@ -964,19 +3319,39 @@ pub fn build_ui(
 
     let builder: Builder = Builder::from_string(glade_src);
 
-    spawn_local_handler(&builder, rx_from_back, tx_to_back.clone());
+    let balance_history: BalanceHistoryData = Rc::new(RefCell::new(Vec::new()));
+    let receive_qr_code: ReceiveQrCodeData = Rc::new(RefCell::new(None));
+
+    spawn_local_handler(
+        &builder,
+        rx_from_back,
+        tx_to_back.clone(),
+        balance_history.clone(),
+        receive_qr_code.clone(),
+        Rc::new(RefCell::new((None, (Amount::ZERO, Amount::ZERO)))),
+    );
 
     login_main_window(application, &builder, tx_to_back.clone())?;
 
     login_registration_window(&builder, application, tx_to_back.clone())?;
+    login_account_edit_window(&builder, application, tx_to_back.clone())?;
+    login_wallet_backup_window(&builder, application, tx_to_back.clone())?;
+    login_spending_password_window(&builder, application, tx_to_back.clone())?;
+    login_rescan_wallet_window(&builder, application, tx_to_back.clone())?;
+    login_wallet_chooser_window(&builder, application, tx_to_back.clone())?;
 
     login_combo_box(&builder, tx_to_back)?;
+    login_balance_history_chart(&builder, balance_history)?;
+    login_receive_page(&builder, receive_qr_code)?;
 
     login_transaction_error_window(&builder)?;
     login_merkle_error_window(&builder)?;
     login_transaction_notification_window(&builder)?;
     login_transaction_sent_notification_window(&builder)?;
     login_merkle_proof_successful_window(&builder)?;
+    login_transaction_description_window(&builder)?;
+    login_debug_console(&builder, log_buffer)?;
+    login_theme_menu(&builder, theme_config)?;
 
     Ok(())
 }
@@ -2,22 +2,30 @@ use super::{frontend, signal_to_back::SignalToBack};
 
 use crate::{
     process::{
+        broadcasting::WalletState,
         reference::{get_reference, MutArc},
         transaction,
     },
-    ui::{account, error_ui::ErrorUI, input_handler::InputHandler},
+    ui::{
+        account, block_explorer, error_ui::ErrorUI, from_hexa, input_handler::InputHandler,
+        raw_dump,
+    },
 };
 
 use cargosos_bitcoin::{
-    block_structure::{block_chain::BlockChain, utxo_set::UTXOSet},
+    block_structure::{block_chain::BlockChain, hash::HASH_TYPE_SIZE},
     logs::logger_sender::LoggerSender,
-    node_structure::broadcasting::Broadcasting,
+    node_structure::{broadcasting::Broadcasting, script_subscriptions::ScriptSubscriptions},
     notifications::{notification::Notification, notifier::Notifier},
-    wallet_structure::{address::Address, wallet::Wallet},
+    wallet_structure::{
+        address::Address, transaction_options::TransactionOptions,
+        wallet_directory::WalletDirectory,
+    },
 };
 
 use std::{
     io::{Read, Write},
+    net::SocketAddr,
     sync::mpsc::Receiver,
 };
 
@@ -26,6 +34,7 @@ where
     N: Notifier,
 {
     rx_from_front: Receiver<SignalToBack>,
+    transaction_options: TransactionOptions,
     notifier: N,
     logger: LoggerSender,
 }
@@ -34,10 +43,19 @@ impl<N: Notifier> InputHandlerGUI<N> {
     pub fn new(rx_from_front: Receiver<SignalToBack>, notifier: N, logger: LoggerSender) -> Self {
         Self {
             rx_from_front,
+            transaction_options: TransactionOptions::default(),
             notifier,
             logger,
         }
     }
+
+    /// The transactions created by the user have the lock time and replaceability of the options
+    pub fn with_transaction_options(self, transaction_options: TransactionOptions) -> Self {
+        Self {
+            transaction_options,
+            ..self
+        }
+    }
 }
 
 impl<RW, N> InputHandler<RW> for InputHandlerGUI<N>
@@ -48,15 +66,19 @@ where
     fn handle_input(
         &self,
         broadcasting: MutArc<Broadcasting<RW>>,
-        wallet: MutArc<Wallet>,
-        utxo_set: MutArc<UTXOSet>,
+        wallet_state: WalletState,
         block_chain: MutArc<BlockChain>,
+        wallet_directory: Option<MutArc<WalletDirectory>>,
+        script_subscriptions: MutArc<ScriptSubscriptions>,
     ) -> Result<(), ErrorUI> {
+        let (wallet, utxo_set, balance_history, faucet) = wallet_state;
+
         for rx in &self.rx_from_front {
             let mut wallet_reference = get_reference(&wallet)?;
             let mut utxo_set_reference = get_reference(&utxo_set)?;
             let mut broadcasting_reference = get_reference(&broadcasting)?;
             let block_chain_reference = get_reference(&block_chain)?;
+            let mut balance_history_reference = get_reference(&balance_history)?;
 
             match rx {
                 SignalToBack::GetAccountBalance => {
@@ -73,7 +95,7 @@ where
                         self.notifier.clone(),
                     )?;
                 }
-                SignalToBack::CreateTransaction(address_string, amount, fee) => {
+                SignalToBack::CreateTransaction(address_string, amount, fee, password) => {
                     let address = match Address::new(&address_string) {
                         Ok(address) => address,
                         Err(_) => {
@@ -82,12 +104,59 @@ where
                         }
                     };
 
+                    if !account::unlock_wallet(
+                        &mut wallet_reference,
+                        password.as_deref(),
+                        self.notifier.clone(),
+                    ) {
+                        continue;
+                    }
+
                     transaction::sending_transaction(
                         &mut broadcasting_reference,
                         &wallet_reference,
                         &mut utxo_set_reference,
                         address,
-                        (amount, fee),
+                        (amount, fee, self.transaction_options),
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    )?;
+                }
+                SignalToBack::PreviewTransaction(address_string, amount, fee) => {
+                    let address = match Address::new(&address_string) {
+                        Ok(address) => address,
+                        Err(_) => {
+                            self.notifier.notify(Notification::InvalidAddressEnter);
+                            continue;
+                        }
+                    };
+
+                    transaction::preview_transaction(
+                        &wallet_reference,
+                        &utxo_set_reference,
+                        address,
+                        (amount, fee, self.transaction_options),
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    );
+                }
+                SignalToBack::BroadcastRawTransaction(raw_transaction, validate) => {
+                    transaction::broadcast_raw_transaction(
+                        &mut broadcasting_reference,
+                        &mut utxo_set_reference,
+                        &raw_transaction,
+                        validate,
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    )?;
+                }
+                SignalToBack::SweepPrivateKey(wif, fee) => {
+                    transaction::sweep_private_key(
+                        &mut broadcasting_reference,
+                        &wallet_reference,
+                        &mut utxo_set_reference,
+                        &wif,
+                        fee,
                         self.notifier.clone(),
                         self.logger.clone(),
                     )?;
@@ -101,14 +170,156 @@ where
                         self.notifier.clone(),
                     )?;
                 }
-                SignalToBack::GetAccountTransactions => {
-                    account::give_account_transactions(
+                SignalToBack::GenerateAccount(name) => {
+                    frontend::generate_account(wallet.clone(), &name, self.notifier.clone())?;
+                }
+                SignalToBack::EditAccount(name, new_name, label, description) => {
+                    account::edit_account(
+                        &mut wallet_reference,
+                        &name,
+                        new_name,
+                        label,
+                        description,
+                        self.notifier.clone(),
+                    );
+                }
+                SignalToBack::SetTransactionLabel(transaction_id, label) => {
+                    match from_hexa::hash_in_display_order(&transaction_id) {
+                        Ok(transaction_id) => account::label_transaction(
+                            &mut wallet_reference,
+                            transaction_id,
+                            Some(label),
+                            self.notifier.clone(),
+                            self.logger.clone(),
+                        ),
+                        Err(_) => {
+                            let _ = self.logger.log_wallet(format!(
+                                "Cannot label the transaction with invalid id {transaction_id}"
+                            ));
+                        }
+                    }
+                }
+                SignalToBack::GetTransactionHistory => {
+                    account::give_transaction_history(
                         &wallet_reference,
                         &block_chain_reference,
                         self.notifier.clone(),
                         self.logger.clone(),
+                    );
+                }
+                SignalToBack::GetAccountBalanceHistory(from_height) => {
+                    account::give_account_balance_history(
+                        &wallet_reference,
+                        &balance_history_reference,
+                        from_height,
+                        self.notifier.clone(),
+                    );
+                }
+                SignalToBack::RescanWallet(from) => {
+                    drop((
+                        wallet_reference,
+                        utxo_set_reference,
+                        broadcasting_reference,
+                        block_chain_reference,
+                        balance_history_reference,
+                    ));
+                    account::rescan_wallet(
+                        &wallet,
+                        &balance_history,
+                        &block_chain,
+                        from,
+                        self.notifier.clone(),
+                        self.logger.clone(),
                     )?;
                 }
+                SignalToBack::RequestFaucetCoins => {
+                    drop((
+                        wallet_reference,
+                        utxo_set_reference,
+                        broadcasting_reference,
+                        block_chain_reference,
+                        balance_history_reference,
+                    ));
+                    account::request_faucet_coins(
+                        &wallet,
+                        &faucet,
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    )?;
+                }
+                SignalToBack::ChangeWallet(wallet_name) => {
+                    account::change_wallet(
+                        &wallet_name,
+                        &mut wallet_reference,
+                        &mut balance_history_reference,
+                        &wallet_directory,
+                        &block_chain_reference,
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    )?;
+                }
+                SignalToBack::CreateWallet(wallet_name) => {
+                    account::create_wallet(
+                        &wallet_name,
+                        &mut wallet_reference,
+                        &mut balance_history_reference,
+                        &wallet_directory,
+                        &block_chain_reference,
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    )?;
+                }
+                SignalToBack::OpenWalletFile(path) => {
+                    account::open_wallet_file(
+                        &path,
+                        &mut wallet_reference,
+                        &mut balance_history_reference,
+                        &wallet_directory,
+                        &block_chain_reference,
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    )?;
+                }
+                SignalToBack::CloseWallet => {
+                    account::close_wallet(
+                        &mut wallet_reference,
+                        &mut balance_history_reference,
+                        &wallet_directory,
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    )?;
+                }
+                SignalToBack::ExportWallet(path, password) => {
+                    account::export_wallet(
+                        &wallet_reference,
+                        &path,
+                        password.as_deref(),
+                        self.notifier.clone(),
+                    );
+                }
+                SignalToBack::ImportWallet(path, password) => {
+                    account::import_wallet(
+                        &mut wallet_reference,
+                        &path,
+                        password.as_deref(),
+                        self.notifier.clone(),
+                    );
+                }
+                SignalToBack::SetSpendingPassword(password, new_password) => {
+                    account::set_spending_password(
+                        &mut wallet_reference,
+                        password.as_deref(),
+                        new_password.as_deref(),
+                        self.notifier.clone(),
+                    );
+                }
+                SignalToBack::UnlockWallet(password) => {
+                    account::unlock_wallet(
+                        &mut wallet_reference,
+                        Some(&password),
+                        self.notifier.clone(),
+                    );
+                }
                 SignalToBack::RequestMerkleProof(block_hash, transaction_id) => {
                     frontend::request_merkle_proof(
                         &block_chain_reference,
@@ -118,6 +329,108 @@ where
                         self.logger.clone(),
                     )?;
                 }
+                SignalToBack::RequestRawBlock(block_hash) => {
+                    match from_hexa::hash_in_display_order(&block_hash) {
+                        Ok(block_hash) => raw_dump::give_raw_block(
+                            &block_chain_reference,
+                            block_hash,
+                            self.notifier.clone(),
+                            self.logger.clone(),
+                        ),
+                        Err(_) => self.notifier.notify(Notification::RawDataProblem(
+                            "Invalid block hash entered".to_string(),
+                        )),
+                    }
+                }
+                SignalToBack::RequestRawTransaction(transaction_id) => {
+                    match from_hexa::hash_in_display_order(&transaction_id) {
+                        Ok(transaction_id) => raw_dump::give_raw_transaction(
+                            &block_chain_reference,
+                            transaction_id,
+                            self.notifier.clone(),
+                            self.logger.clone(),
+                        ),
+                        Err(_) => self.notifier.notify(Notification::RawDataProblem(
+                            "Invalid transaction id entered".to_string(),
+                        )),
+                    }
+                }
+                SignalToBack::RequestRecentBlocks(amount) => {
+                    block_explorer::give_recent_blocks(
+                        &block_chain_reference,
+                        amount as usize,
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    );
+                }
+                SignalToBack::RequestBlockTransactions(block_hash) => {
+                    match from_hexa::hash_in_display_order(&block_hash) {
+                        Ok(block_hash) => block_explorer::give_block_transactions(
+                            &block_chain_reference,
+                            block_hash,
+                            self.notifier.clone(),
+                            self.logger.clone(),
+                        ),
+                        Err(_) => self.notifier.notify(Notification::RawDataProblem(
+                            "Invalid block hash entered".to_string(),
+                        )),
+                    }
+                }
+                SignalToBack::DescribeRawTransaction(raw_transaction) => {
+                    transaction::describe_raw_transaction(
+                        &utxo_set_reference,
+                        &raw_transaction,
+                        wallet_reference.get_network(),
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    );
+                }
+                SignalToBack::SubscribeScriptHash(script_hash) => {
+                    match from_hexa::from::<HASH_TYPE_SIZE>(&script_hash) {
+                        Ok(script_hash) => {
+                            get_reference(&script_subscriptions)?.subscribe(script_hash);
+                        }
+                        Err(_) => {
+                            let _ = self
+                                .logger
+                                .log_interface(format!("Invalid script hash {script_hash}"));
+                        }
+                    }
+                }
+                SignalToBack::UnsubscribeScriptHash(script_hash) => {
+                    match from_hexa::from::<HASH_TYPE_SIZE>(&script_hash) {
+                        Ok(script_hash) => {
+                            get_reference(&script_subscriptions)?.unsubscribe(&script_hash);
+                        }
+                        Err(_) => {
+                            let _ = self
+                                .logger
+                                .log_interface(format!("Invalid script hash {script_hash}"));
+                        }
+                    }
+                }
+                SignalToBack::DisconnectPeer(address) => match address.parse::<SocketAddr>() {
+                    Ok(address) => {
+                        if !broadcasting_reference.disconnect_peer(address) {
+                            let _ = self
+                                .logger
+                                .log_interface(format!("There is no connection with {address}"));
+                        }
+                    }
+                    Err(_) => {
+                        let _ = self
+                            .logger
+                            .log_interface(format!("Invalid peer address {address}"));
+                    }
+                },
+                SignalToBack::BanPeer(address) => match address.parse::<SocketAddr>() {
+                    Ok(address) => broadcasting_reference.ban_peer(address.ip()),
+                    Err(_) => {
+                        let _ = self
+                            .logger
+                            .log_interface(format!("Invalid peer address {address}"));
+                    }
+                },
                 SignalToBack::ExitProgram => {
                     break;
                 }
@@ -2,6 +2,8 @@ pub mod execution;
 
 pub mod input_handler_gui;
 pub mod notifier_gui;
+pub mod rpc_client;
+pub mod rpc_message;
 pub mod signal_to_back;
 pub mod signal_to_front;
 
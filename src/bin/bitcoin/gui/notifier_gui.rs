@@ -1,41 +1,65 @@
-use super::signal_to_front::SignalToFront;
+use super::signal_to_front::{FrontSender, SignalToFront};
+
+use crate::ui::message_catalog::MessageCatalog;
 
 use cargosos_bitcoin::{
-    block_structure::transaction::Transaction,
+    block_structure::hash,
+    configurations::language::Language,
     logs::logger_sender::LoggerSender,
     notifications::{notification::Notification, notifier::Notifier},
-    wallet_structure::account::Account,
 };
 
-use gtk::glib::Sender;
+use chrono::offset::Utc;
 
 /// Struct that handles the representation of the notifications for the GUI.
 #[derive(Clone)]
-pub struct NotifierGUI {
-    tx_to_front: Sender<SignalToFront>,
+pub struct NotifierGUI<S: FrontSender> {
+    tx_to_front: S,
+    catalog: MessageCatalog,
     logger: LoggerSender,
 }
 
-impl NotifierGUI {
-    pub fn new(tx_to_front: Sender<SignalToFront>, logger: LoggerSender) -> Self {
+impl<S: FrontSender> NotifierGUI<S> {
+    pub fn new(tx_to_front: S, logger: LoggerSender) -> Self {
         Self {
             tx_to_front,
+            catalog: MessageCatalog::default(),
             logger,
         }
     }
+
+    /// The messages shown to the user are in the given language
+    pub fn with_language(self, language: Language) -> Self {
+        Self {
+            catalog: MessageCatalog::new(language),
+            ..self
+        }
+    }
 }
 
-impl Notifier for NotifierGUI {
+impl<S: FrontSender> Notifier for NotifierGUI<S> {
     fn notify(&self, notification: Notification) {
         match notification {
             Notification::AttemptingHandshakeWithPeer(peer) => {
-                println!("Attempting handshake with peer {}", peer)
+                println!(
+                    "{}",
+                    self.catalog
+                        .format("Attempting handshake with peer {0}", &[&peer])
+                )
             }
             Notification::SuccessfulHandshakeWithPeer(peer) => {
-                println!("Successful handshake with peer {}", peer)
+                println!(
+                    "{}",
+                    self.catalog
+                        .format("Successful handshake with peer {0}", &[&peer])
+                )
             }
             Notification::FailedHandshakeWithPeer(peer) => {
-                println!("Failed handshake with peer {}", peer)
+                println!(
+                    "{}",
+                    self.catalog
+                        .format("Failed handshake with peer {0}", &[&peer])
+                )
             }
             Notification::ConnectionUpdated(connection_id) => {
                 if self
@@ -48,14 +72,84 @@ impl Notifier for NotifierGUI {
                         .log_error("Error updating connection".to_string());
                 }
             }
+            Notification::PeerLatency(connection_id, latency) => {
+                println!(
+                    "{}",
+                    self.catalog.format(
+                        "Latency with peer {0}: {1} ms",
+                        &[&connection_id, &latency.as_millis()],
+                    )
+                )
+            }
+            Notification::PeerInformationUpdated(information) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::UpdatePeerInformation(information))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Error updating peer information".to_string());
+                }
+            }
+            Notification::PeerDisconnected(connection_id) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::RemovePeer(connection_id))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Error removing disconnected peer".to_string());
+                }
+            }
+            Notification::ClockSkew(skew) => {
+                println!(
+                    "{}",
+                    self.catalog.format("Our clock deviates {0} seconds from the time of the network, check the date of the computer", &[&skew])
+                )
+            }
+            Notification::StateSaved(timestamp) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::StateSaved(timestamp))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send the time of the save to front".to_string());
+                }
+            }
+            Notification::MempoolInfo(mempool_info) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::MempoolInfo(mempool_info))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send the mempool information to front".to_string());
+                }
+            }
+            Notification::LastSyncedTip(chain_tip) => {
+                println!(
+                    "{}",
+                    self.catalog.format(
+                        "Last synced to height {0}, {1} hours ago",
+                        &[
+                            &chain_tip.height,
+                            &chain_tip.hours_since_sync(Utc::now().timestamp()),
+                        ],
+                    )
+                )
+            }
             Notification::TransactionOfAccountReceived(accounts, _) => {
-                if self.tx_to_front.send(SignalToFront::Update).is_err()
-                    || self
-                        .tx_to_front
-                        .send(SignalToFront::TransactionOfAccountReceived(
-                            accounts[0].account_name.clone(),
-                        ))
-                        .is_err()
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::TransactionOfAccountReceived(
+                        accounts[0].account_name.clone(),
+                    ))
+                    .is_err()
                 {
                     let _ = self
                         .logger
@@ -76,14 +170,32 @@ impl Notifier for NotifierGUI {
                         .log_error("Error sending notification".to_string());
                 }
             }
-            Notification::NewBlockAddedToTheBlockchain(_) => {
-                if self.tx_to_front.send(SignalToFront::Update).is_err() {
+            Notification::NewBlockAddedToTheBlockchain(block, coinbase_info) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::NewBlockAdded(
+                        block.to_string(),
+                        coinbase_info,
+                    ))
+                    .is_err()
+                {
                     let _ = self
                         .logger
                         .log_error("Failed to send update of new block added".to_string());
                 }
             }
-            Notification::UpdatedSelectedAccount(_) => {
+            Notification::UpdatedSelectedAccount(account) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::SelectedAccountAddress(
+                        account.address.to_string(),
+                    ))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send address of selected account".to_string());
+                }
                 if self.tx_to_front.send(SignalToFront::Update).is_err() {
                     let _ = self
                         .logger
@@ -123,11 +235,72 @@ impl Notifier for NotifierGUI {
                         .log_error("Failed to send available balance to front".to_string());
                 }
             }
+            Notification::ExchangeRateUpdated(exchange_rate) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::ExchangeRateUpdated(exchange_rate))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send exchange rate to front".to_string());
+                }
+            }
+            Notification::AccountBalanceChanged(account, balance, pending) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::AccountBalanceChanged(
+                        account.account_name,
+                        (balance, pending),
+                    ))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send balance of account to front".to_string());
+                }
+            }
+            Notification::AccountTransactionConfirmed(account, _) => {
+                let _ = self.logger.log_wallet(format!(
+                    "A transaction of the account {} was confirmed, the history is updated with the new block",
+                    account.account_name
+                ));
+            }
+            Notification::AccountBalanceCheckpoint(account, checkpoint) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::NewBalanceCheckpoint(
+                        account.account_name,
+                        checkpoint,
+                    ))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send balance checkpoint to front".to_string());
+                }
+            }
+            Notification::TipAdvanced(height, block_hash) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::TipAdvanced(
+                        height,
+                        hash::to_hex_be(&block_hash),
+                    ))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send tip of the blockchain to front".to_string());
+                }
+            }
             Notification::AccountNotSelected => {
-                let message = "No account selected cannot get transactions";
+                let message = self
+                    .catalog
+                    .text("No account selected cannot get transactions");
                 if self
                     .tx_to_front
-                    .send(SignalToFront::ErrorInTransaction(message.to_string()))
+                    .send(SignalToFront::ErrorInTransaction(message))
                     .is_err()
                 {
                     let _ = self
@@ -136,19 +309,237 @@ impl Notifier for NotifierGUI {
                 }
             }
             Notification::AccountTransactions(account, transactions) => {
-                let transactions = get_account_transactions_information(&account, transactions);
+                let _ = self.logger.log_wallet(format!(
+                    "The account {} has {} transactions",
+                    account.account_name,
+                    transactions.len()
+                ));
+            }
+            Notification::TransactionHistory(history) => {
                 if self
                     .tx_to_front
-                    .send(SignalToFront::AccountTransactions(transactions))
+                    .send(SignalToFront::TransactionHistory(history))
                     .is_err()
                 {
-                    let _ = self.logger.log_error(
-                        "Failed to send error signal of transactions from account".to_string(),
-                    );
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send the transaction history to front".to_string());
+                }
+            }
+            Notification::AccountBalanceHistory(_, history) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::AccountBalanceHistory(history))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send balance history of account".to_string());
+                }
+            }
+            Notification::FaucetCoinsRequested(_, transaction_id) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::SuccessfullySentTransaction(hash::to_hex_be(
+                        &transaction_id,
+                    )))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send update after requesting coins".to_string());
+                }
+            }
+            Notification::FaucetRequestFailed(error) => {
+                let _ = self.logger.log_error(error.clone());
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::ErrorInTransaction(
+                        self.catalog.format("Faucet request failed: {0}", &[&error]),
+                    ))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send error signal to front".to_string());
+                }
+            }
+            Notification::FaucetTransactionConfirmed(block, transaction_id) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::BlockWithUnconfirmedTransactionReceived(
+                        block.to_string(),
+                        hash::to_hex_be(&transaction_id),
+                    ))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send faucet confirmation to front".to_string());
+                }
+            }
+            Notification::WalletChanged(wallet_name) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::WalletChanged(wallet_name))
+                    .is_err()
+                    || self.tx_to_front.send(SignalToFront::Update).is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send wallet changed to front".to_string());
+                }
+            }
+            Notification::WalletClosed(wallet_name) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::WalletClosed(wallet_name))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send wallet closed to front".to_string());
+                }
+            }
+            Notification::AvailableWallets(selected_wallet, wallet_names) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::AvailableWallets(
+                        selected_wallet,
+                        wallet_names,
+                    ))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send available wallets to front".to_string());
+                }
+            }
+            Notification::WalletChangeFailed(error) => {
+                let _ = self.logger.log_error(error.clone());
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::ErrorInTransaction(
+                        self.catalog.format("Wallet change failed: {0}", &[&error]),
+                    ))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send error signal to front".to_string());
+                }
+            }
+            Notification::FromWallet(_, notification) => self.notify(*notification),
+            Notification::RawBlock(block_hash, raw_block) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::RawData(
+                        format!("block {}", hash::to_hex_be(&block_hash)),
+                        raw_block,
+                    ))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send raw block to front".to_string());
+                }
+            }
+            Notification::RawTransaction(transaction_id, raw_transaction) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::RawData(
+                        format!("transaction {}", hash::to_hex_be(&transaction_id)),
+                        raw_transaction,
+                    ))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send raw transaction to front".to_string());
+                }
+            }
+            Notification::ScriptHashEvent(event) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::ScriptHashEvent(event))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send script hash event to front".to_string());
+                }
+            }
+            Notification::TransactionDescribed(description) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::DisplayTransactionDescription(
+                        description.to_string(),
+                    ))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send transaction description to front".to_string());
+                }
+            }
+            Notification::RawDataProblem(error) => {
+                let _ = self.logger.log_error(error.clone());
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::ErrorInTransaction(
+                        self.catalog.format("Raw data not obtained: {0}", &[&error]),
+                    ))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send error signal to front".to_string());
+                }
+            }
+            Notification::RecentBlocks(summaries) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::RecentBlocks(summaries))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send recent blocks to front".to_string());
+                }
+            }
+            Notification::BlockTransactions(summary, transactions) => {
+                let mut rows = Vec::new();
+                for transaction in transactions {
+                    let transaction_id = match transaction.get_tx_id() {
+                        Ok(transaction_id) => transaction_id,
+                        Err(error) => {
+                            let _ = self.logger.log_error(format!(
+                                "Error getting the id of a transaction, with error: {:?}",
+                                error
+                            ));
+                            continue;
+                        }
+                    };
+                    rows.push((
+                        transaction_id,
+                        transaction.tx_in.len(),
+                        transaction.tx_out.len(),
+                        transaction.tx_out.iter().map(|output| output.value).sum(),
+                    ));
+                }
+
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::BlockTransactions(summary.hash, rows))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send block transactions to front".to_string());
                 }
             }
             Notification::InvalidAddressEnter => {
-                let message = "Invalid address".to_string();
+                let message = self.catalog.text("Invalid address");
                 let _ = self.logger.log_error(message.clone());
                 if self
                     .tx_to_front
@@ -164,7 +555,7 @@ impl Notifier for NotifierGUI {
                 if self
                     .tx_to_front
                     .send(SignalToFront::ErrorInTransaction(
-                        "Error creating the transaction".to_string(),
+                        self.catalog.text("Error creating the transaction"),
                     ))
                     .is_err()
                 {
@@ -173,8 +564,39 @@ impl Notifier for NotifierGUI {
                         .log_error("Failed to send error signal to front".to_string());
                 };
             }
+            Notification::AmountIsDust(dust_threshold) => {
+                let message = self.catalog.format(
+                    "The amount is below the dust threshold of {0} satoshis, so the transaction would not be relayed",
+                    &[&dust_threshold],
+                );
+                let _ = self.logger.log_error(message.clone());
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::ErrorInTransaction(message))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send error signal to front".to_string());
+                }
+            }
+            Notification::RawTransactionRejected(reason) => {
+                let message = self
+                    .catalog
+                    .format("Raw transaction rejected: {0}", &[&reason]);
+                let _ = self.logger.log_error(message.clone());
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::ErrorInTransaction(message))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send error signal to front".to_string());
+                }
+            }
             Notification::InvalidPublicKeyEnter => {
-                let message = "Invalid public key".to_string();
+                let message = self.catalog.text("Invalid public key");
                 let _ = self.logger.log_error(message.clone());
                 if self
                     .tx_to_front
@@ -187,7 +609,7 @@ impl Notifier for NotifierGUI {
                 }
             }
             Notification::InvalidPrivateKeyEnter => {
-                let message = "Invalid private key".to_string();
+                let message = self.catalog.text("Invalid private key");
                 let _ = self.logger.log_error(message.clone());
                 if self
                     .tx_to_front
@@ -200,7 +622,7 @@ impl Notifier for NotifierGUI {
                 }
             }
             Notification::AccountCreationFail => {
-                let message = "Error in account creation".to_string();
+                let message = self.catalog.text("Error in account creation");
                 let _ = self.logger.log_error(message.clone());
                 if self
                     .tx_to_front
@@ -213,6 +635,173 @@ impl Notifier for NotifierGUI {
                     );
                 }
             }
+            Notification::AccountEdited(account_name, account) => {
+                let _ = self
+                    .logger
+                    .log_wallet(format!("The account {account_name} was edited: {account}"));
+                if account_name != account.account_name
+                    && self
+                        .tx_to_front
+                        .send(SignalToFront::AccountRenamed(
+                            account_name,
+                            account.account_name,
+                        ))
+                        .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send the renamed account to front".to_string());
+                }
+            }
+            Notification::TransactionLabelChanged(transaction_id, label) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::TransactionLabelChanged(
+                        transaction_id,
+                        label,
+                    ))
+                    .is_err()
+                {
+                    let _ = self.logger.log_error(
+                        "Failed to send the label of the transaction to front".to_string(),
+                    );
+                }
+            }
+            Notification::WalletExported(path) => {
+                let message = self
+                    .catalog
+                    .format("The wallet was exported to {0}", &[&path]);
+                let _ = self.logger.log_wallet(message.clone());
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::WalletBackupResult(message))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send the exported wallet to front".to_string());
+                }
+            }
+            Notification::WalletImported(path, accounts_added) => {
+                let message = self
+                    .catalog
+                    .format("Imported {0} accounts from {1}", &[&accounts_added, &path]);
+                let _ = self.logger.log_wallet(message.clone());
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::WalletBackupResult(message))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send the imported wallet to front".to_string());
+                }
+            }
+            Notification::WalletBackupFailed(reason) => {
+                let message = self
+                    .catalog
+                    .format("Backup of the wallet failed: {0}", &[&reason]);
+                let _ = self.logger.log_error(message.clone());
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::WalletBackupResult(message))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send the failed backup to front".to_string());
+                }
+            }
+            Notification::SpendingPasswordChanged(has_password) => {
+                let message = match has_password {
+                    true => self.catalog.text("The spending password was changed"),
+                    false => self.catalog.text("The spending password was removed"),
+                };
+                let _ = self.logger.log_wallet(message.clone());
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::SpendingPasswordResult(message))
+                    .is_err()
+                {
+                    let _ = self.logger.log_error(
+                        "Failed to send the change of the spending password to front".to_string(),
+                    );
+                }
+            }
+            Notification::WalletUnlocked(duration) => {
+                let message = self.catalog.format(
+                    "The wallet is unlocked for {0} seconds",
+                    &[&duration.as_secs()],
+                );
+                let _ = self.logger.log_wallet(message.clone());
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::SpendingPasswordResult(message))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send the unlocked wallet to front".to_string());
+                }
+            }
+            Notification::SpendingPasswordRejected(reason) => {
+                let message = self
+                    .catalog
+                    .format("The spending password was rejected: {0}", &[&reason]);
+                let _ = self.logger.log_error(message.clone());
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::ErrorInTransaction(message))
+                    .is_err()
+                {
+                    let _ = self.logger.log_error(
+                        "Failed to send the rejected spending password to front".to_string(),
+                    );
+                }
+            }
+            Notification::AccountEditFailed(reason) => {
+                let message = self
+                    .catalog
+                    .format("Error editing the account: {0}", &[&reason]);
+                let _ = self.logger.log_error(message.clone());
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::ErrorInAccountCreation(message))
+                    .is_err()
+                {
+                    let _ = self.logger.log_error(
+                        "Failed to send error signal for an error in edition of an account"
+                            .to_string(),
+                    );
+                }
+            }
+            Notification::TransactionPreview(preview) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::TransactionPreview(preview))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send the transaction preview to front".to_string());
+                }
+            }
+            Notification::TransactionConflicted(transaction_id, conflicting_id) => {
+                let message = self.catalog.format(
+                    "The transaction {0} was dropped, the transaction {1} of a new block spends the same outputs",
+                    &[&hash::to_hex_be(&transaction_id), &hash::to_hex_be(&conflicting_id)],
+                );
+                let _ = self.logger.log_error(message.clone());
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::ErrorInTransaction(message))
+                    .is_err()
+                {
+                    let _ = self
+                        .logger
+                        .log_error("Failed to send error signal to front".to_string());
+                }
+            }
             Notification::SuccessfullySentTransaction(transaction) => {
                 if self
                     .tx_to_front
@@ -225,17 +814,25 @@ impl Notifier for NotifierGUI {
                         .logger
                         .log_error("Failed to send update after sending transaction".to_string());
                 }
-                println!("Transaction sent: {transaction}", transaction = transaction);
+                println!(
+                    "{}",
+                    self.catalog
+                        .format("Transaction sent: {0}", &[&transaction])
+                );
             }
             Notification::HeadersReceived(headers) => {
-                println!("Received {headers} headers");
+                println!(
+                    "{}",
+                    self.catalog.format("Received {0} headers", &[&headers])
+                );
             }
-            Notification::ProgressDownloadingBlocks(blocks_downloaded, total_blocks) => {
+            Notification::ProgressDownloadingBlocks(progress) => {
                 if self
                     .tx_to_front
                     .send(SignalToFront::UpdateBlockProgressBar(
-                        blocks_downloaded,
-                        total_blocks,
+                        progress.blocks_done,
+                        progress.blocks_total,
+                        progress.to_string(),
                     ))
                     .is_err()
                 {
@@ -260,10 +857,28 @@ impl Notifier for NotifierGUI {
                     );
                 }
             }
-            Notification::ClosingPeers => println!("Closing peers"),
-            Notification::ClosingPeer => println!("Closing this peer"),
+            Notification::ProgressRescan(blocks_scanned, total_blocks) => {
+                if self
+                    .tx_to_front
+                    .send(SignalToFront::UpdateRescanProgressBar(
+                        blocks_scanned,
+                        total_blocks,
+                    ))
+                    .is_err()
+                {
+                    let _ = self.logger.log_error(
+                        "Failed to send signal for updating the rescan progress bar".to_string(),
+                    );
+                }
+            }
+            Notification::ClosingPeers => println!("{}", self.catalog.text("Closing peers")),
+            Notification::ClosingPeer => println!("{}", self.catalog.text("Closing this peer")),
             Notification::ReceivedMessage(message) => {
-                println!("Received message of type {:?}", message)
+                println!(
+                    "{}",
+                    self.catalog
+                        .format("Received message of type {0}", &[&format!("{:?}", message)])
+                )
             }
             Notification::ProblemVerifyingTransactionMerkleProofOfInclusion(error) => {
                 if self
@@ -292,27 +907,3 @@ impl Notifier for NotifierGUI {
         }
     }
 }
-
-/// Return the information of the transactions of an account
-fn get_account_transactions_information(
-    account: &Account,
-    transactions: Vec<Transaction>,
-) -> Vec<(u32, [u8; 32], i64)> {
-    transactions
-        .iter()
-        .filter_map(|transaction| {
-            let timestamp = transaction.time;
-            let label = match transaction.get_tx_id() {
-                Ok(txid) => txid,
-                Err(_) => return None,
-            };
-            let mut amount: i64 = 0;
-            for utxo in transaction.tx_out.clone() {
-                if account.verify_transaction_output_ownership(&utxo) {
-                    amount += utxo.value;
-                }
-            }
-            Some((timestamp, label, amount))
-        })
-        .collect()
-}
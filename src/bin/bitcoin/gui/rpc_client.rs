@@ -0,0 +1,201 @@
+use super::{
+    rpc_message::{decode_signal_to_front, encode_signal_to_back},
+    signal_to_back::SignalToBack,
+    signal_to_front::{FrontSender, SignalToFront},
+};
+
+use crate::{
+    process::{
+        reference::{get_reference, MutArc},
+        state::SharedState,
+    },
+    ui::error_ui::ErrorUI,
+};
+
+use cargosos_bitcoin::logs::logger_sender::LoggerSender;
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{Ipv4Addr, Shutdown, SocketAddr, TcpStream},
+    sync::mpsc::Receiver,
+    thread::{self, JoinHandle},
+};
+
+/// It's the client of the RPC of a daemon running in this computer. It sends the signals of the window
+/// to the daemon and shows the signals of the daemon, as an observer that cannot modify the node
+pub struct RpcClient {
+    stream: TcpStream,
+    selected_wallet: MutArc<Option<String>>,
+    logger: LoggerSender,
+}
+
+impl RpcClient {
+    /// Connects to the daemon listening in the given local port
+    ///
+    /// ### Error
+    ///  * `ErrorUI::ConnectionAborted`: It will appear when there is no daemon listening in the port
+    pub fn connect(rpc_port: u16, logger: LoggerSender) -> Result<Self, ErrorUI> {
+        let address = SocketAddr::from((Ipv4Addr::LOCALHOST, rpc_port));
+
+        let stream = match TcpStream::connect(address) {
+            Ok(stream) => stream,
+            Err(error) => {
+                let _ = logger.log_error(format!(
+                    "Cannot connect to the daemon at {address}: {:?}",
+                    error
+                ));
+                return Err(ErrorUI::ConnectionAborted);
+            }
+        };
+
+        let _ = logger.log_connection(format!("Observing the daemon at {address}"));
+
+        Ok(RpcClient {
+            stream,
+            selected_wallet: SharedState::new(None),
+            logger,
+        })
+    }
+
+    /// Creates a thread that reads the signals of the daemon and sends them to the window.
+    /// The thread ends when the connection with the daemon is closed
+    ///
+    /// ### Error
+    ///  * `ErrorUI::ErrorReading`: It will appear when the connection cannot be shared with the thread
+    pub fn spawn_receiver<S>(&self, tx_to_front: S) -> Result<JoinHandle<()>, ErrorUI>
+    where
+        S: FrontSender + 'static,
+    {
+        let stream = match self.stream.try_clone() {
+            Ok(stream) => stream,
+            Err(_) => {
+                return Err(ErrorUI::ErrorReading(
+                    "Cannot share the connection with the daemon".to_string(),
+                ))
+            }
+        };
+        let selected_wallet = self.selected_wallet.clone();
+        let logger = self.logger.clone();
+
+        Ok(thread::spawn(move || {
+            for line in BufReader::new(stream).lines() {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(_) => break,
+                };
+
+                let signal = match decode_signal_to_front(&line) {
+                    Some(signal) => signal,
+                    None => {
+                        let _ = logger.log_interface(format!("Unknown signal from daemon: {line}"));
+                        continue;
+                    }
+                };
+
+                remember_selected_wallet(&signal, &selected_wallet);
+
+                if tx_to_front.send(signal).is_err() {
+                    return;
+                }
+            }
+
+            let _ = logger.log_connection("The daemon closed the connection".to_string());
+            let _ = tx_to_front.send(SignalToFront::ErrorInTransaction(
+                "Lost connection with the daemon".to_string(),
+            ));
+        }))
+    }
+
+    /// Sends the signals of the window to the daemon until the window is closed. The signals that would
+    /// modify the node are rejected, letting the window know
+    ///
+    /// ### Error
+    ///  * `ErrorUI::ErrorWriting`: It will appear when a signal cannot be sent to the daemon
+    pub fn forward_signals<S: FrontSender>(
+        mut self,
+        rx_from_front: Receiver<SignalToBack>,
+        tx_to_front: S,
+    ) -> Result<(), ErrorUI> {
+        for signal in rx_from_front {
+            if let SignalToBack::ExitProgram = signal {
+                break;
+            }
+
+            if self.is_selected_wallet(&signal) {
+                continue;
+            }
+
+            match encode_signal_to_back(&signal) {
+                Some(line) => self.send(line)?,
+                None => {
+                    let rejection = match signal {
+                        SignalToBack::CreateAccount(..) | SignalToBack::GenerateAccount(_) => {
+                            SignalToFront::ErrorInAccountCreation(
+                                "Cannot create accounts while observing a daemon".to_string(),
+                            )
+                        }
+                        SignalToBack::CreateWallet(_)
+                        | SignalToBack::OpenWalletFile(_)
+                        | SignalToBack::CloseWallet => SignalToFront::ErrorInTransaction(
+                            "Cannot change the wallet while observing a daemon".to_string(),
+                        ),
+                        SignalToBack::ExportWallet(..) | SignalToBack::ImportWallet(..) => {
+                            SignalToFront::WalletBackupResult(
+                                "Cannot backup the wallet while observing a daemon".to_string(),
+                            )
+                        }
+                        SignalToBack::SetSpendingPassword(..) | SignalToBack::UnlockWallet(_) => {
+                            SignalToFront::SpendingPasswordResult(
+                                "Cannot change the spending password while observing a daemon"
+                                    .to_string(),
+                            )
+                        }
+                        _ => SignalToFront::ErrorInTransaction(
+                            "Cannot modify the node while observing a daemon".to_string(),
+                        ),
+                    };
+                    let _ = tx_to_front.send(rejection);
+                }
+            }
+        }
+
+        let _ = self.stream.shutdown(Shutdown::Both);
+        Ok(())
+    }
+
+    fn send(&mut self, line: String) -> Result<(), ErrorUI> {
+        match writeln!(self.stream, "{line}") {
+            Ok(_) => Ok(()),
+            Err(_) => Err(ErrorUI::ErrorWriting(
+                "Cannot send the signal to the daemon".to_string(),
+            )),
+        }
+    }
+
+    /// Returns true if the signal asks to change to the wallet the daemon is already using,
+    /// as the window does when it shows the available wallets
+    fn is_selected_wallet(&self, signal: &SignalToBack) -> bool {
+        let wallet_name = match signal {
+            SignalToBack::ChangeWallet(wallet_name) => wallet_name,
+            _ => return false,
+        };
+
+        match get_reference(&self.selected_wallet) {
+            Ok(selected_wallet) => selected_wallet.as_ref() == Some(wallet_name),
+            Err(_) => false,
+        }
+    }
+}
+
+/// Remembers the wallet used by the daemon, when the signal informs it
+fn remember_selected_wallet(signal: &SignalToFront, selected_wallet: &MutArc<Option<String>>) {
+    let wallet_name = match signal {
+        SignalToFront::WalletChanged(wallet_name)
+        | SignalToFront::AvailableWallets(wallet_name, _) => wallet_name,
+        _ => return,
+    };
+
+    if let Ok(mut selected_wallet) = get_reference(selected_wallet) {
+        *selected_wallet = Some(wallet_name.clone());
+    }
+}
@@ -0,0 +1,785 @@
+use super::{signal_to_back::SignalToBack, signal_to_front::SignalToFront};
+
+use cargosos_bitcoin::{
+    block_structure::{
+        block_summary::BlockSummary,
+        coinbase::CoinbaseInfo,
+        hash::{self, HashType, HASH_TYPE_SIZE},
+        mempool_info::MempoolInfo,
+        outpoint::Outpoint,
+    },
+    connections::p2p_protocol::ProtocolVersionP2P,
+    node_structure::{
+        connection_id::ConnectionId,
+        connection_type::ConnectionType,
+        peer_features::PeerFeatures,
+        peer_information::PeerInformation,
+        script_subscriptions::{ScriptEvent, ScriptEventKind},
+    },
+    wallet_structure::{
+        address::Address,
+        amount::Amount,
+        exchange_rate::ExchangeRate,
+        transaction_history::{Direction, HistoryEntry},
+        transaction_preview::TransactionPreview,
+    },
+};
+
+use std::{net::SocketAddr, time::Duration};
+
+pub const DEFAULT_RPC_PORT: u16 = 18400;
+
+const FIELD_SEPARATOR: char = '\t';
+const ITEM_SEPARATOR: char = ',';
+
+const GET_ACCOUNT_BALANCE: &str = "get_balance";
+const CHANGE_SELECTED_ACCOUNT: &str = "select_account";
+const GET_TRANSACTION_HISTORY: &str = "get_history";
+const GET_ACCOUNT_BALANCE_HISTORY: &str = "get_balance_history";
+const RESCAN_WALLET: &str = "rescan_wallet";
+const REQUEST_MERKLE_PROOF: &str = "get_merkle_proof";
+const REQUEST_RAW_BLOCK: &str = "get_raw_block";
+const REQUEST_RAW_TRANSACTION: &str = "get_raw_transaction";
+const DESCRIBE_RAW_TRANSACTION: &str = "describe_transaction";
+const REQUEST_RECENT_BLOCKS: &str = "get_recent_blocks";
+const REQUEST_BLOCK_TRANSACTIONS: &str = "get_block_transactions";
+const SUBSCRIBE_SCRIPT_HASH: &str = "subscribe_script";
+const UNSUBSCRIBE_SCRIPT_HASH: &str = "unsubscribe_script";
+
+const REGISTER_ACCOUNT: &str = "account";
+const ACCOUNT_RENAMED: &str = "account_renamed";
+const LOAD_AVAILABLE_BALANCE: &str = "balance";
+const BLOCKCHAIN_READY: &str = "ready";
+const ERROR_IN_TRANSACTION: &str = "transaction_error";
+const ERROR_IN_ACCOUNT_CREATION: &str = "account_error";
+const TRANSACTION_RECEIVED: &str = "transaction_received";
+const TRANSACTION_IN_BLOCK: &str = "transaction_in_block";
+const NEW_BLOCK: &str = "block";
+const TRANSACTION_HISTORY: &str = "history";
+const TRANSACTION_LABEL: &str = "transaction_label";
+const ACCOUNT_BALANCE_HISTORY: &str = "balance_history";
+const TRANSACTION_SENT: &str = "transaction_sent";
+const TRANSACTION_PREVIEW: &str = "transaction_preview";
+const ERROR_IN_MERKLE_PROOF: &str = "merkle_error";
+const MERKLE_PATH: &str = "merkle_path";
+const BLOCK_PROGRESS: &str = "block_progress";
+const BLOCKCHAIN_PROGRESS: &str = "blockchain_progress";
+const RESCAN_PROGRESS: &str = "rescan_progress";
+const CONNECTION: &str = "connection";
+const PEER_INFORMATION: &str = "peer_information";
+const PEER_DISCONNECTED: &str = "peer_disconnected";
+const WALLET_CHANGED: &str = "wallet";
+const WALLET_CLOSED: &str = "wallet_closed";
+const WALLET_BACKUP_RESULT: &str = "wallet_backup";
+const SPENDING_PASSWORD_RESULT: &str = "spending_password";
+const AVAILABLE_WALLETS: &str = "wallets";
+const UPDATE: &str = "update";
+const SELECTED_ADDRESS: &str = "selected_address";
+const RAW_DATA: &str = "raw";
+const TRANSACTION_DESCRIPTION: &str = "description";
+const SCRIPT_HASH_EVENT: &str = "script_event";
+const ACCOUNT_BALANCE_CHANGED: &str = "account_balance";
+const EXCHANGE_RATE: &str = "exchange_rate";
+const STATE_SAVED: &str = "state_saved";
+const NEW_BALANCE_CHECKPOINT: &str = "balance_checkpoint";
+const TIP_ADVANCED: &str = "tip";
+const MEMPOOL_INFO: &str = "mempool_info";
+const RECENT_BLOCKS: &str = "recent_blocks";
+const BLOCK_TRANSACTIONS: &str = "block_transactions";
+
+const OUTPUT_CREATED: &str = "created";
+const OUTPUT_SPENT: &str = "spent";
+
+const RECEIVED: &str = "received";
+const SENT: &str = "sent";
+
+const PEER: &str = "peer";
+const CLIENT: &str = "client";
+
+/// Turns the signal to the back into a line of the RPC. Only the signals that do not modify the node
+/// can be sent by an observer, so the rest have no representation
+pub fn encode_signal_to_back(signal: &SignalToBack) -> Option<String> {
+    let fields = match signal {
+        SignalToBack::GetAccountBalance => vec![GET_ACCOUNT_BALANCE.to_string()],
+        SignalToBack::ChangeSelectedAccount(account_name) => {
+            vec![CHANGE_SELECTED_ACCOUNT.to_string(), sanitize(account_name)]
+        }
+        SignalToBack::GetTransactionHistory => vec![GET_TRANSACTION_HISTORY.to_string()],
+        SignalToBack::GetAccountBalanceHistory(from_height) => vec![
+            GET_ACCOUNT_BALANCE_HISTORY.to_string(),
+            from_height.to_string(),
+        ],
+        SignalToBack::RescanWallet(from) => vec![RESCAN_WALLET.to_string(), from.to_string()],
+        SignalToBack::RequestMerkleProof(block_hash, transaction_id) => vec![
+            REQUEST_MERKLE_PROOF.to_string(),
+            sanitize(block_hash),
+            sanitize(transaction_id),
+        ],
+        SignalToBack::RequestRawBlock(block_hash) => {
+            vec![REQUEST_RAW_BLOCK.to_string(), sanitize(block_hash)]
+        }
+        SignalToBack::RequestRawTransaction(transaction_id) => {
+            vec![
+                REQUEST_RAW_TRANSACTION.to_string(),
+                sanitize(transaction_id),
+            ]
+        }
+        SignalToBack::RequestRecentBlocks(amount) => {
+            vec![REQUEST_RECENT_BLOCKS.to_string(), amount.to_string()]
+        }
+        SignalToBack::RequestBlockTransactions(block_hash) => {
+            vec![REQUEST_BLOCK_TRANSACTIONS.to_string(), sanitize(block_hash)]
+        }
+        SignalToBack::DescribeRawTransaction(raw_transaction) => vec![
+            DESCRIBE_RAW_TRANSACTION.to_string(),
+            sanitize(raw_transaction),
+        ],
+        SignalToBack::SubscribeScriptHash(script_hash) => {
+            vec![SUBSCRIBE_SCRIPT_HASH.to_string(), sanitize(script_hash)]
+        }
+        SignalToBack::UnsubscribeScriptHash(script_hash) => {
+            vec![UNSUBSCRIBE_SCRIPT_HASH.to_string(), sanitize(script_hash)]
+        }
+        _ => return None,
+    };
+
+    Some(join_fields(fields))
+}
+
+/// Turns a line of the RPC into the signal to the back it represents
+pub fn decode_signal_to_back(line: &str) -> Option<SignalToBack> {
+    let fields: Vec<&str> = split_fields(line);
+
+    match fields.as_slice() {
+        [GET_ACCOUNT_BALANCE] => Some(SignalToBack::GetAccountBalance),
+        [CHANGE_SELECTED_ACCOUNT, account_name] => Some(SignalToBack::ChangeSelectedAccount(
+            account_name.to_string(),
+        )),
+        [GET_TRANSACTION_HISTORY] => Some(SignalToBack::GetTransactionHistory),
+        [GET_ACCOUNT_BALANCE_HISTORY, from_height] => Some(SignalToBack::GetAccountBalanceHistory(
+            from_height.parse().ok()?,
+        )),
+        [RESCAN_WALLET, from] => Some(SignalToBack::RescanWallet(from.parse().ok()?)),
+        [REQUEST_MERKLE_PROOF, block_hash, transaction_id] => Some(
+            SignalToBack::RequestMerkleProof(block_hash.to_string(), transaction_id.to_string()),
+        ),
+        [REQUEST_RAW_BLOCK, block_hash] => {
+            Some(SignalToBack::RequestRawBlock(block_hash.to_string()))
+        }
+        [REQUEST_RAW_TRANSACTION, transaction_id] => Some(SignalToBack::RequestRawTransaction(
+            transaction_id.to_string(),
+        )),
+        [REQUEST_RECENT_BLOCKS, amount] => {
+            Some(SignalToBack::RequestRecentBlocks(amount.parse().ok()?))
+        }
+        [REQUEST_BLOCK_TRANSACTIONS, block_hash] => Some(SignalToBack::RequestBlockTransactions(
+            block_hash.to_string(),
+        )),
+        [DESCRIBE_RAW_TRANSACTION, raw_transaction] => Some(SignalToBack::DescribeRawTransaction(
+            raw_transaction.to_string(),
+        )),
+        [SUBSCRIBE_SCRIPT_HASH, script_hash] => {
+            Some(SignalToBack::SubscribeScriptHash(script_hash.to_string()))
+        }
+        [UNSUBSCRIBE_SCRIPT_HASH, script_hash] => {
+            Some(SignalToBack::UnsubscribeScriptHash(script_hash.to_string()))
+        }
+        _ => None,
+    }
+}
+
+/// Turns the signal to the front into a line of the RPC. The hashes of a script event are in the internal
+/// order, being the script hash the sha256 of the script
+pub fn encode_signal_to_front(signal: &SignalToFront) -> String {
+    let fields = match signal {
+        SignalToFront::RegisterAccount(account_name) => {
+            vec![REGISTER_ACCOUNT.to_string(), sanitize(account_name)]
+        }
+        SignalToFront::AccountRenamed(account_name, new_account_name) => vec![
+            ACCOUNT_RENAMED.to_string(),
+            sanitize(account_name),
+            sanitize(new_account_name),
+        ],
+        SignalToFront::LoadAvailableBalance((balance, pending)) => vec![
+            LOAD_AVAILABLE_BALANCE.to_string(),
+            balance.to_sat().to_string(),
+            pending.to_sat().to_string(),
+        ],
+        SignalToFront::NotifyBlockchainIsReady => vec![BLOCKCHAIN_READY.to_string()],
+        SignalToFront::ErrorInTransaction(error) => {
+            vec![ERROR_IN_TRANSACTION.to_string(), sanitize(error)]
+        }
+        SignalToFront::ErrorInAccountCreation(error) => {
+            vec![ERROR_IN_ACCOUNT_CREATION.to_string(), sanitize(error)]
+        }
+        SignalToFront::TransactionOfAccountReceived(account_name) => {
+            vec![TRANSACTION_RECEIVED.to_string(), sanitize(account_name)]
+        }
+        SignalToFront::BlockWithUnconfirmedTransactionReceived(block, transaction) => vec![
+            TRANSACTION_IN_BLOCK.to_string(),
+            sanitize(block),
+            sanitize(transaction),
+        ],
+        SignalToFront::NewBlockAdded(block, coinbase_info) => vec![
+            NEW_BLOCK.to_string(),
+            sanitize(block),
+            match coinbase_info.height {
+                Some(height) => height.to_string(),
+                None => String::new(),
+            },
+            match &coinbase_info.miner_tag {
+                Some(miner_tag) => sanitize(miner_tag),
+                None => String::new(),
+            },
+        ],
+        SignalToFront::TransactionHistory(history) => {
+            let mut fields = vec![TRANSACTION_HISTORY.to_string()];
+            fields.extend(history.iter().map(|entry| {
+                format!(
+                    "{}{ITEM_SEPARATOR}{}{ITEM_SEPARATOR}{}{ITEM_SEPARATOR}{}{ITEM_SEPARATOR}{}{ITEM_SEPARATOR}{}{ITEM_SEPARATOR}{}",
+                    entry.timestamp,
+                    encode_hash(&entry.transaction_id),
+                    encode_direction(entry.direction),
+                    entry.amount,
+                    entry.confirmations,
+                    match &entry.label {
+                        Some(label) => hash::to_hex(label.as_bytes()),
+                        None => String::new(),
+                    },
+                    sanitize(&entry.account_name),
+                )
+            }));
+            fields
+        }
+        SignalToFront::TransactionLabelChanged(transaction_id, label) => vec![
+            TRANSACTION_LABEL.to_string(),
+            encode_hash(transaction_id),
+            match label {
+                Some(label) => sanitize(label),
+                None => String::new(),
+            },
+        ],
+        SignalToFront::AccountBalanceHistory(history) => {
+            let mut fields = vec![ACCOUNT_BALANCE_HISTORY.to_string()];
+            fields.extend(
+                history
+                    .iter()
+                    .map(|(height, balance)| format!("{height}{ITEM_SEPARATOR}{balance}")),
+            );
+            fields
+        }
+        SignalToFront::TransactionPreview(preview) => vec![
+            TRANSACTION_PREVIEW.to_string(),
+            preview.recipient.to_string(),
+            preview.amount.to_string(),
+            preview.fee.to_string(),
+            preview.size.to_string(),
+            preview.change.to_string(),
+            preview.resulting_balance.to_string(),
+        ],
+        SignalToFront::SuccessfullySentTransaction(transaction_id) => {
+            vec![TRANSACTION_SENT.to_string(), sanitize(transaction_id)]
+        }
+        SignalToFront::ErrorInMerkleProof(error) => {
+            vec![ERROR_IN_MERKLE_PROOF.to_string(), sanitize(error)]
+        }
+        SignalToFront::DisplayMerklePath(merkle_path, root) => {
+            let mut fields = vec![MERKLE_PATH.to_string(), encode_hash(root)];
+            fields.extend(merkle_path.iter().map(encode_hash));
+            fields
+        }
+        SignalToFront::UpdateBlockProgressBar(downloaded, total, description) => vec![
+            BLOCK_PROGRESS.to_string(),
+            downloaded.to_string(),
+            total.to_string(),
+            sanitize(description),
+        ],
+        SignalToFront::UpdateBlockchainProgressBar(updated, total) => vec![
+            BLOCKCHAIN_PROGRESS.to_string(),
+            updated.to_string(),
+            total.to_string(),
+        ],
+        SignalToFront::UpdateRescanProgressBar(scanned, total) => vec![
+            RESCAN_PROGRESS.to_string(),
+            scanned.to_string(),
+            total.to_string(),
+        ],
+        SignalToFront::UpdateConnection(connection_id) => vec![
+            CONNECTION.to_string(),
+            connection_id.address.to_string(),
+            encode_connection_type(connection_id.connection_type),
+        ],
+        SignalToFront::UpdatePeerInformation(information) => {
+            let connection = information.connection;
+            vec![
+                PEER_INFORMATION.to_string(),
+                connection.address.to_string(),
+                encode_connection_type(connection.connection_type),
+                i32::from(connection.features.version).to_string(),
+                connection.features.services.to_string(),
+                connection.features.wtxid_relay.to_string(),
+                sanitize(&information.user_agent),
+                match information.latency {
+                    Some(latency) => latency.as_millis().to_string(),
+                    None => String::new(),
+                },
+                information.bytes_received.to_string(),
+                information.bytes_sent.to_string(),
+                information.queued_messages.to_string(),
+            ]
+        }
+        SignalToFront::RemovePeer(connection_id) => vec![
+            PEER_DISCONNECTED.to_string(),
+            connection_id.address.to_string(),
+            encode_connection_type(connection_id.connection_type),
+        ],
+        SignalToFront::WalletChanged(wallet_name) => {
+            vec![WALLET_CHANGED.to_string(), sanitize(wallet_name)]
+        }
+        SignalToFront::WalletClosed(wallet_name) => {
+            vec![WALLET_CLOSED.to_string(), sanitize(wallet_name)]
+        }
+        SignalToFront::WalletBackupResult(result) => {
+            vec![WALLET_BACKUP_RESULT.to_string(), sanitize(result)]
+        }
+        SignalToFront::SpendingPasswordResult(result) => {
+            vec![SPENDING_PASSWORD_RESULT.to_string(), sanitize(result)]
+        }
+        SignalToFront::AvailableWallets(selected_wallet, wallet_names) => {
+            let mut fields = vec![AVAILABLE_WALLETS.to_string(), sanitize(selected_wallet)];
+            fields.extend(wallet_names.iter().map(|name| sanitize(name)));
+            fields
+        }
+        SignalToFront::RawData(description, raw_data) => vec![
+            RAW_DATA.to_string(),
+            sanitize(description),
+            sanitize(raw_data),
+        ],
+        SignalToFront::DisplayTransactionDescription(description) => {
+            let mut fields = vec![TRANSACTION_DESCRIPTION.to_string()];
+            fields.extend(description.lines().map(sanitize));
+            fields
+        }
+        SignalToFront::ScriptHashEvent(event) => {
+            let (kind, detail) = match event.kind {
+                ScriptEventKind::Created(value) => (OUTPUT_CREATED, value.to_string()),
+                ScriptEventKind::Spent(transaction_id) => {
+                    (OUTPUT_SPENT, encode_hash(&transaction_id))
+                }
+            };
+            vec![
+                SCRIPT_HASH_EVENT.to_string(),
+                encode_hash(&event.script_hash),
+                encode_hash(&event.outpoint.get_transaction_id()),
+                event.outpoint.get_index().to_string(),
+                kind.to_string(),
+                detail,
+                match &event.block_hash {
+                    Some(block_hash) => encode_hash(block_hash),
+                    None => String::new(),
+                },
+            ]
+        }
+        SignalToFront::ExchangeRateUpdated(exchange_rate) => vec![
+            EXCHANGE_RATE.to_string(),
+            sanitize(&exchange_rate.currency),
+            exchange_rate.price.to_string(),
+        ],
+        SignalToFront::StateSaved(timestamp) => {
+            vec![STATE_SAVED.to_string(), timestamp.to_string()]
+        }
+        SignalToFront::MempoolInfo(mempool_info) => vec![
+            MEMPOOL_INFO.to_string(),
+            mempool_info.transaction_count.to_string(),
+            mempool_info.total_size.to_string(),
+            mempool_info.total_fees.to_string(),
+            match mempool_info.min_fee_rate {
+                Some(min_fee_rate) => min_fee_rate.to_string(),
+                None => String::new(),
+            },
+        ],
+        SignalToFront::AccountBalanceChanged(account_name, (balance, pending)) => vec![
+            ACCOUNT_BALANCE_CHANGED.to_string(),
+            sanitize(account_name),
+            balance.to_sat().to_string(),
+            pending.to_sat().to_string(),
+        ],
+        SignalToFront::NewBalanceCheckpoint(account_name, (height, balance)) => vec![
+            NEW_BALANCE_CHECKPOINT.to_string(),
+            sanitize(account_name),
+            height.to_string(),
+            balance.to_string(),
+        ],
+        SignalToFront::TipAdvanced(height, block_hash) => vec![
+            TIP_ADVANCED.to_string(),
+            height.to_string(),
+            sanitize(block_hash),
+        ],
+        SignalToFront::RecentBlocks(summaries) => {
+            let mut fields = vec![RECENT_BLOCKS.to_string()];
+            fields.extend(summaries.iter().map(|summary| {
+                format!(
+                    "{}{ITEM_SEPARATOR}{}{ITEM_SEPARATOR}{}{ITEM_SEPARATOR}{}{ITEM_SEPARATOR}{}{ITEM_SEPARATOR}{}{ITEM_SEPARATOR}{}",
+                    summary.height,
+                    encode_hash(&summary.hash),
+                    summary.time,
+                    summary.transaction_count,
+                    match summary.size {
+                        Some(size) => size.to_string(),
+                        None => String::new(),
+                    },
+                    summary.subsidy,
+                    match summary.fees {
+                        Some(fees) => fees.to_string(),
+                        None => String::new(),
+                    },
+                )
+            }));
+            fields
+        }
+        SignalToFront::BlockTransactions(block_hash, transactions) => {
+            let mut fields = vec![BLOCK_TRANSACTIONS.to_string(), encode_hash(block_hash)];
+            fields.extend(transactions.iter().map(
+                |(transaction_id, inputs, outputs, total_output)| {
+                    format!(
+                        "{}{ITEM_SEPARATOR}{inputs}{ITEM_SEPARATOR}{outputs}{ITEM_SEPARATOR}{total_output}",
+                        encode_hash(transaction_id)
+                    )
+                },
+            ));
+            fields
+        }
+        SignalToFront::SelectedAccountAddress(address) => {
+            vec![SELECTED_ADDRESS.to_string(), sanitize(address)]
+        }
+        SignalToFront::Update => vec![UPDATE.to_string()],
+    };
+
+    join_fields(fields)
+}
+
+/// Turns a line of the RPC into the signal to the front it represents
+pub fn decode_signal_to_front(line: &str) -> Option<SignalToFront> {
+    let fields: Vec<&str> = split_fields(line);
+
+    let signal = match fields.as_slice() {
+        [REGISTER_ACCOUNT, account_name] => {
+            SignalToFront::RegisterAccount(account_name.to_string())
+        }
+        [ACCOUNT_RENAMED, account_name, new_account_name] => {
+            SignalToFront::AccountRenamed(account_name.to_string(), new_account_name.to_string())
+        }
+        [LOAD_AVAILABLE_BALANCE, balance, pending] => SignalToFront::LoadAvailableBalance((
+            Amount::from_sat(balance.parse().ok()?),
+            Amount::from_sat(pending.parse().ok()?),
+        )),
+        [BLOCKCHAIN_READY] => SignalToFront::NotifyBlockchainIsReady,
+        [ERROR_IN_TRANSACTION, error] => SignalToFront::ErrorInTransaction(error.to_string()),
+        [ERROR_IN_ACCOUNT_CREATION, error] => {
+            SignalToFront::ErrorInAccountCreation(error.to_string())
+        }
+        [TRANSACTION_RECEIVED, account_name] => {
+            SignalToFront::TransactionOfAccountReceived(account_name.to_string())
+        }
+        [TRANSACTION_IN_BLOCK, block, transaction] => {
+            SignalToFront::BlockWithUnconfirmedTransactionReceived(
+                block.to_string(),
+                transaction.to_string(),
+            )
+        }
+        [NEW_BLOCK, block, height, miner_tag] => SignalToFront::NewBlockAdded(
+            block.to_string(),
+            CoinbaseInfo {
+                height: match height.is_empty() {
+                    true => None,
+                    false => Some(height.parse().ok()?),
+                },
+                miner_tag: match miner_tag.is_empty() {
+                    true => None,
+                    false => Some(miner_tag.to_string()),
+                },
+            },
+        ),
+        [TRANSACTION_HISTORY, history @ ..] => {
+            let mut entries = Vec::new();
+            for entry in history {
+                // The account name goes last, so it can have the item separator in it, while the
+                // label is in hexadecimal for the same reason
+                match entry
+                    .splitn(7, ITEM_SEPARATOR)
+                    .collect::<Vec<&str>>()
+                    .as_slice()
+                {
+                    [timestamp, transaction_id, direction, amount, confirmations, label, account_name] => {
+                        entries.push(HistoryEntry {
+                            account_name: account_name.to_string(),
+                            timestamp: timestamp.parse().ok()?,
+                            transaction_id: decode_hash(transaction_id)?,
+                            direction: decode_direction(direction)?,
+                            amount: amount.parse().ok()?,
+                            confirmations: confirmations.parse().ok()?,
+                            label: match label.is_empty() {
+                                true => None,
+                                false => Some(
+                                    String::from_utf8(hash::bytes_from_hex(label).ok()?).ok()?,
+                                ),
+                            },
+                        })
+                    }
+                    _ => return None,
+                }
+            }
+            SignalToFront::TransactionHistory(entries)
+        }
+        [TRANSACTION_LABEL, transaction_id, label] => SignalToFront::TransactionLabelChanged(
+            decode_hash(transaction_id)?,
+            match label.is_empty() {
+                true => None,
+                false => Some(label.to_string()),
+            },
+        ),
+        [ACCOUNT_BALANCE_HISTORY, history @ ..] => {
+            let mut checkpoints = Vec::new();
+            for checkpoint in history {
+                match split_items(checkpoint).as_slice() {
+                    [height, balance] => {
+                        checkpoints.push((height.parse().ok()?, balance.parse().ok()?))
+                    }
+                    _ => return None,
+                }
+            }
+            SignalToFront::AccountBalanceHistory(checkpoints)
+        }
+        [TRANSACTION_PREVIEW, recipient, amount, fee, size, change, resulting_balance] => {
+            SignalToFront::TransactionPreview(TransactionPreview {
+                recipient: Address::new(recipient).ok()?,
+                amount: amount.parse().ok()?,
+                fee: fee.parse().ok()?,
+                size: size.parse().ok()?,
+                change: change.parse().ok()?,
+                resulting_balance: resulting_balance.parse().ok()?,
+            })
+        }
+        [TRANSACTION_SENT, transaction_id] => {
+            SignalToFront::SuccessfullySentTransaction(transaction_id.to_string())
+        }
+        [ERROR_IN_MERKLE_PROOF, error] => SignalToFront::ErrorInMerkleProof(error.to_string()),
+        [MERKLE_PATH, root, merkle_path @ ..] => {
+            let mut path = Vec::new();
+            for hash in merkle_path {
+                path.push(decode_hash(hash)?);
+            }
+            SignalToFront::DisplayMerklePath(path, decode_hash(root)?)
+        }
+        [BLOCK_PROGRESS, downloaded, total, description] => SignalToFront::UpdateBlockProgressBar(
+            downloaded.parse().ok()?,
+            total.parse().ok()?,
+            description.to_string(),
+        ),
+        [BLOCKCHAIN_PROGRESS, updated, total] => {
+            SignalToFront::UpdateBlockchainProgressBar(updated.parse().ok()?, total.parse().ok()?)
+        }
+        [RESCAN_PROGRESS, scanned, total] => {
+            SignalToFront::UpdateRescanProgressBar(scanned.parse().ok()?, total.parse().ok()?)
+        }
+        [CONNECTION, address, connection_type] => {
+            let address: SocketAddr = address.parse().ok()?;
+            let connection_type = decode_connection_type(connection_type)?;
+            SignalToFront::UpdateConnection(ConnectionId::new(address, connection_type))
+        }
+        [PEER_INFORMATION, address, connection_type, version, services, wtxid_relay, user_agent, latency, bytes_received, bytes_sent, queued_messages] =>
+        {
+            let address: SocketAddr = address.parse().ok()?;
+            let features = PeerFeatures {
+                version: ProtocolVersionP2P::try_from(version.parse::<i32>().ok()?).ok()?,
+                services: services.parse().ok()?,
+                wtxid_relay: wtxid_relay.parse().ok()?,
+            };
+            SignalToFront::UpdatePeerInformation(PeerInformation {
+                connection: ConnectionId::new(address, decode_connection_type(connection_type)?)
+                    .with_features(features),
+                user_agent: user_agent.to_string(),
+                latency: match latency.is_empty() {
+                    true => None,
+                    false => Some(Duration::from_millis(latency.parse().ok()?)),
+                },
+                bytes_received: bytes_received.parse().ok()?,
+                bytes_sent: bytes_sent.parse().ok()?,
+                queued_messages: queued_messages.parse().ok()?,
+            })
+        }
+        [PEER_DISCONNECTED, address, connection_type] => {
+            let address: SocketAddr = address.parse().ok()?;
+            let connection_type = decode_connection_type(connection_type)?;
+            SignalToFront::RemovePeer(ConnectionId::new(address, connection_type))
+        }
+        [WALLET_CHANGED, wallet_name] => SignalToFront::WalletChanged(wallet_name.to_string()),
+        [WALLET_CLOSED, wallet_name] => SignalToFront::WalletClosed(wallet_name.to_string()),
+        [WALLET_BACKUP_RESULT, result] => SignalToFront::WalletBackupResult(result.to_string()),
+        [SPENDING_PASSWORD_RESULT, result] => {
+            SignalToFront::SpendingPasswordResult(result.to_string())
+        }
+        [AVAILABLE_WALLETS, selected_wallet, wallet_names @ ..] => SignalToFront::AvailableWallets(
+            selected_wallet.to_string(),
+            wallet_names.iter().map(|name| name.to_string()).collect(),
+        ),
+        [RAW_DATA, description, raw_data] => {
+            SignalToFront::RawData(description.to_string(), raw_data.to_string())
+        }
+        [TRANSACTION_DESCRIPTION, lines @ ..] => {
+            SignalToFront::DisplayTransactionDescription(lines.join("\n"))
+        }
+        [SCRIPT_HASH_EVENT, script_hash, transaction_id, index, kind, detail, block_hash] => {
+            SignalToFront::ScriptHashEvent(ScriptEvent {
+                script_hash: decode_hash(script_hash)?,
+                outpoint: Outpoint::new(decode_hash(transaction_id)?, index.parse().ok()?),
+                kind: match *kind {
+                    OUTPUT_CREATED => ScriptEventKind::Created(detail.parse().ok()?),
+                    OUTPUT_SPENT => ScriptEventKind::Spent(decode_hash(detail)?),
+                    _ => return None,
+                },
+                block_hash: match block_hash.is_empty() {
+                    true => None,
+                    false => Some(decode_hash(block_hash)?),
+                },
+            })
+        }
+        [ACCOUNT_BALANCE_CHANGED, account_name, balance, pending] => {
+            SignalToFront::AccountBalanceChanged(
+                account_name.to_string(),
+                (
+                    Amount::from_sat(balance.parse().ok()?),
+                    Amount::from_sat(pending.parse().ok()?),
+                ),
+            )
+        }
+        [NEW_BALANCE_CHECKPOINT, account_name, height, balance] => {
+            SignalToFront::NewBalanceCheckpoint(
+                account_name.to_string(),
+                (height.parse().ok()?, balance.parse().ok()?),
+            )
+        }
+        [TIP_ADVANCED, height, block_hash] => {
+            SignalToFront::TipAdvanced(height.parse().ok()?, block_hash.to_string())
+        }
+        [RECENT_BLOCKS, summaries @ ..] => {
+            let mut blocks = Vec::new();
+            for summary in summaries {
+                match split_items(summary).as_slice() {
+                    [height, hash, time, transaction_count, size, subsidy, fees] => {
+                        blocks.push(BlockSummary {
+                            height: height.parse().ok()?,
+                            hash: decode_hash(hash)?,
+                            time: time.parse().ok()?,
+                            transaction_count: transaction_count.parse().ok()?,
+                            size: match size.is_empty() {
+                                true => None,
+                                false => Some(size.parse().ok()?),
+                            },
+                            subsidy: subsidy.parse().ok()?,
+                            fees: match fees.is_empty() {
+                                true => None,
+                                false => Some(fees.parse().ok()?),
+                            },
+                        })
+                    }
+                    _ => return None,
+                }
+            }
+            SignalToFront::RecentBlocks(blocks)
+        }
+        [BLOCK_TRANSACTIONS, block_hash, transactions @ ..] => {
+            let mut rows = Vec::new();
+            for transaction in transactions {
+                match split_items(transaction).as_slice() {
+                    [transaction_id, inputs, outputs, total_output] => rows.push((
+                        decode_hash(transaction_id)?,
+                        inputs.parse().ok()?,
+                        outputs.parse().ok()?,
+                        total_output.parse().ok()?,
+                    )),
+                    _ => return None,
+                }
+            }
+            SignalToFront::BlockTransactions(decode_hash(block_hash)?, rows)
+        }
+        [SELECTED_ADDRESS, address] => SignalToFront::SelectedAccountAddress(address.to_string()),
+        [EXCHANGE_RATE, currency, price] => {
+            SignalToFront::ExchangeRateUpdated(ExchangeRate::new(currency, price.parse().ok()?))
+        }
+        [STATE_SAVED, timestamp] => SignalToFront::StateSaved(timestamp.parse().ok()?),
+        [MEMPOOL_INFO, transaction_count, total_size, total_fees, min_fee_rate] => {
+            SignalToFront::MempoolInfo(MempoolInfo {
+                transaction_count: transaction_count.parse().ok()?,
+                total_size: total_size.parse().ok()?,
+                total_fees: total_fees.parse().ok()?,
+                min_fee_rate: match min_fee_rate.is_empty() {
+                    true => None,
+                    false => Some(min_fee_rate.parse().ok()?),
+                },
+            })
+        }
+        [UPDATE] => SignalToFront::Update,
+        _ => return None,
+    };
+
+    Some(signal)
+}
+
+/// Removes the characters used by the RPC to separate the fields and the lines
+fn sanitize(value: &str) -> String {
+    value.replace([FIELD_SEPARATOR, '\n', '\r'], " ")
+}
+
+fn join_fields(fields: Vec<String>) -> String {
+    fields.join(&FIELD_SEPARATOR.to_string())
+}
+
+fn split_fields(line: &str) -> Vec<&str> {
+    line.trim_end_matches(['\n', '\r'])
+        .split(FIELD_SEPARATOR)
+        .collect()
+}
+
+fn split_items(field: &str) -> Vec<&str> {
+    field.split(ITEM_SEPARATOR).collect()
+}
+
+fn encode_connection_type(connection_type: ConnectionType) -> String {
+    match connection_type {
+        ConnectionType::Peer => PEER.to_string(),
+        ConnectionType::Client => CLIENT.to_string(),
+    }
+}
+
+fn decode_connection_type(value: &str) -> Option<ConnectionType> {
+    match value {
+        PEER => Some(ConnectionType::Peer),
+        CLIENT => Some(ConnectionType::Client),
+        _ => None,
+    }
+}
+
+/// Turns a hash into a string in the internal order
+fn encode_direction(direction: Direction) -> String {
+    match direction {
+        Direction::Received => RECEIVED.to_string(),
+        Direction::Sent => SENT.to_string(),
+    }
+}
+
+fn decode_direction(value: &str) -> Option<Direction> {
+    match value {
+        RECEIVED => Some(Direction::Received),
+        SENT => Some(Direction::Sent),
+        _ => None,
+    }
+}
+
+fn encode_hash(hash: &HashType) -> String {
+    hash::to_hex(hash)
+}
+
+fn decode_hash(value: &str) -> Option<HashType> {
+    hash::from_hex::<HASH_TYPE_SIZE>(value).ok()
+}
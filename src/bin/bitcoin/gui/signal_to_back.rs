@@ -1,10 +1,20 @@
+use cargosos_bitcoin::wallet_structure::amount::Amount;
+
 /// This enum represents the signal that the front sends to the back.
 pub enum SignalToBack {
     /// Signal to obtain the balance of an account.
     GetAccountBalance,
 
-    /// Signal to create a transaction.
-    CreateTransaction(String, f64, f64),
+    /// Signal to create a transaction, with the spending password to sign it when the wallet is locked.
+    CreateTransaction(String, Amount, Amount, Option<String>),
+    /// Signal to get the summary of a transaction (address, amount and fee) before creating it.
+    PreviewTransaction(String, Amount, Amount),
+
+    /// Signal to broadcast a transaction given in hexadecimal, checking it against the UTXO set first if asked.
+    BroadcastRawTransaction(String, bool),
+
+    /// Signal to send everything the private key given in WIF has, minus the fee, to the selected account.
+    SweepPrivateKey(String, Amount),
 
     /// Signal to change the selected account.
     ChangeSelectedAccount(String),
@@ -12,12 +22,81 @@ pub enum SignalToBack {
     /// Signal to create an account.
     CreateAccount(String, String, String),
 
-    /// Signal to get the transactions of an account.
-    GetAccountTransactions,
+    /// Signal to create an account with the given name and a new pair of keys.
+    GenerateAccount(String),
+
+    /// Signal to change the name, label and description of an account, keeping the values that are `None`.
+    EditAccount(String, Option<String>, Option<String>, Option<String>),
+
+    /// Signal to change the label of the transaction with the given id, removing it when it's empty.
+    SetTransactionLabel(String, String),
+
+    /// Signal to get the transactions of every account of the wallet.
+    GetTransactionHistory,
+
+    /// Signal to get the balance history of an account from the given height.
+    GetAccountBalanceHistory(u64),
+
+    /// Signal to rescan the blocks for the accounts of the wallet from the given height or timestamp.
+    RescanWallet(u64),
+
+    /// Signal to request coins from the faucet to the selected account.
+    RequestFaucetCoins,
+
+    /// Signal to change the wallet in use for the one with the given name.
+    ChangeWallet(String),
+
+    /// Signal to create a new empty wallet with the given name and start using it.
+    CreateWallet(String),
+
+    /// Signal to start using the wallet saved in the file of the given path.
+    OpenWalletFile(String),
+
+    /// Signal to save and close the wallet in use.
+    CloseWallet,
+
+    /// Signal to write a backup of the wallet in the given path, with the password to encrypt its keys.
+    ExportWallet(String, Option<String>),
+
+    /// Signal to add the accounts of the backup in the given path, with the password to decrypt its keys.
+    ImportWallet(String, Option<String>),
+
+    /// Signal to change the spending password of the wallet, from the current one to the new one, removing it when the new one is `None`.
+    SetSpendingPassword(Option<String>, Option<String>),
+
+    /// Signal to let the private keys be used for a while with the spending password.
+    UnlockWallet(String),
 
     /// Signal requesting the merkle proof of a transaction.
     RequestMerkleProof(String, String),
 
+    /// Signal requesting the raw data of the block with the given hash.
+    RequestRawBlock(String),
+
+    /// Signal requesting the raw data of the transaction with the given id.
+    RequestRawTransaction(String),
+
+    /// Signal requesting the summary of the given amount of the last blocks of the main chain.
+    RequestRecentBlocks(u32),
+
+    /// Signal requesting the transactions of the block with the given hash.
+    RequestBlockTransactions(String),
+
+    /// Signal requesting the breakdown of a transaction given in hexadecimal.
+    DescribeRawTransaction(String),
+
+    /// Signal to be notified of the outputs created or spent of the script with the given hash.
+    SubscribeScriptHash(String),
+
+    /// Signal to stop being notified of the script with the given hash.
+    UnsubscribeScriptHash(String),
+
+    /// Signal to close the connection with the peer of the given address.
+    DisconnectPeer(String),
+
+    /// Signal to close the connections with the peer of the given address and reject it from now on.
+    BanPeer(String),
+
     /// Signal to exit the program.
     ExitProgram,
 }
@@ -1,14 +1,38 @@
+use crate::ui::error_ui::ErrorUI;
+
 use cargosos_bitcoin::{
-    block_structure::hash::HashType, node_structure::connection_id::ConnectionId,
+    block_structure::{
+        block_summary::BlockSummary, coinbase::CoinbaseInfo, hash::HashType,
+        mempool_info::MempoolInfo,
+    },
+    node_structure::{
+        connection_id::ConnectionId, peer_information::PeerInformation,
+        script_subscriptions::ScriptEvent,
+    },
+    wallet_structure::{
+        amount::Amount, balance_history::BalanceCheckpoint, exchange_rate::ExchangeRate,
+        transaction_history::HistoryEntry, transaction_preview::TransactionPreview,
+    },
 };
 
+use gtk::glib;
+
 /// This enum represents the signal that the back sends to the front.
 pub enum SignalToFront {
     /// Signal to add an account to the list of accounts.
     RegisterAccount(String),
 
+    /// Signal to show the result of the export or import of a backup of the wallet.
+    WalletBackupResult(String),
+
+    /// Signal to show the result of changing the spending password of the wallet.
+    SpendingPasswordResult(String),
+
+    /// Signal to change the name of an account in the list of accounts, with its previous and new name.
+    AccountRenamed(String, String),
+
     /// Signal to update the available balance.
-    LoadAvailableBalance((f64, f64)),
+    LoadAvailableBalance((Amount, Amount)),
 
     /// Signal to notify that the blockchain is ready.
     NotifyBlockchainIsReady,
@@ -25,9 +49,20 @@ pub enum SignalToFront {
     /// Signal to notify that we received a transaction from one of our accounts in a block.
     BlockWithUnconfirmedTransactionReceived(String, String),
 
-    /// Signal to transmit the information of the transactions of an account.
-    AccountTransactions(Vec<(u32, HashType, i64)>),
+    /// Signal to transmit the hash of the last block added and the metadata of its coinbase.
+    NewBlockAdded(String, CoinbaseInfo),
+
+    /// Signal to transmit the transactions of the accounts of the wallet, from the newest one.
+    TransactionHistory(Vec<HistoryEntry>),
+
+    /// Signal to transmit the new label of the transaction with the given id, being `None` when it was removed.
+    TransactionLabelChanged(HashType, Option<String>),
+
+    /// Signal to transmit the balance history of an account.
+    AccountBalanceHistory(Vec<BalanceCheckpoint>),
 
+    /// Signal to transmit the summary of a transaction, to be confirmed before sending it.
+    TransactionPreview(TransactionPreview),
     /// Signal to transmit the success in sending a transaction.
     SuccessfullySentTransaction(String),
 
@@ -37,15 +72,90 @@ pub enum SignalToFront {
     /// SIgnal to transmit information about the merkle path of a transaction in a block.
     DisplayMerklePath(Vec<HashType>, HashType),
 
-    /// Signal to notify that we have to update the progress bar download of blocks.
-    UpdateBlockProgressBar(u32, u32),
+    /// Signal to notify that we have to update the progress bar download of blocks, with the
+    /// description of the progress (bytes, rate, time left and peer).
+    UpdateBlockProgressBar(u32, u32, String),
 
     /// Signal to notify that we have to update the progress bar update of the blockchain.
     UpdateBlockchainProgressBar(u32, u32),
 
+    /// Signal to update the progress of the rescan of the wallet, with the blocks walked and the total.
+    UpdateRescanProgressBar(u32, u32),
+
     /// Signal to notify to that we have to update the current connections
     UpdateConnection(ConnectionId),
 
-    /// Signal to notify that we have to update the front.
+    /// Signal to transmit the information of a connection, to be shown in the peers tab.
+    UpdatePeerInformation(PeerInformation),
+
+    /// Signal to notify that the connection with a peer was closed.
+    RemovePeer(ConnectionId),
+
+    /// Signal to notify that the wallet in use has changed, so its accounts have to be loaded.
+    WalletChanged(String),
+
+    /// Signal to notify that the wallet with the given name was closed, so its accounts have to be removed.
+    WalletClosed(String),
+
+    /// Signal to transmit the wallet in use and the wallets that can be used.
+    AvailableWallets(String, Vec<String>),
+
+    /// Signal to transmit the raw data in hexadecimal of a block or a transaction, with its description.
+    RawData(String, String),
+
+    /// Signal to transmit the breakdown of a transaction into its inputs and outputs, to be shown in a window.
+    DisplayTransactionDescription(String),
+
+    /// Signal to transmit an output of a subscribed script that was created or spent.
+    ScriptHashEvent(ScriptEvent),
+
+    /// Signal to transmit the price of a bitcoin in the fiat currency configured, to show the balances in it.
+    ExchangeRateUpdated(ExchangeRate),
+
+    /// Signal to transmit the time in seconds when the state of the node was last saved.
+    StateSaved(i64),
+
+    /// Signal to transmit the new available and pending balance of an account.
+    AccountBalanceChanged(String, (Amount, Amount)),
+
+    /// Signal to transmit a new balance checkpoint of an account.
+    NewBalanceCheckpoint(String, BalanceCheckpoint),
+
+    /// Signal to transmit the height and hash of the new tip of the blockchain.
+    TipAdvanced(u64, String),
+
+    /// Signal to transmit the statistics of the pending transactions, to be shown in the status of the overview.
+    MempoolInfo(MempoolInfo),
+
+    /// Signal to transmit the summary of the last blocks of the main chain, from the newest one.
+    RecentBlocks(Vec<BlockSummary>),
+
+    /// Signal to transmit the rows (transaction id, inputs, outputs, total output) of the transactions of a block.
+    BlockTransactions(HashType, Vec<(HashType, usize, usize, i64)>),
+
+    /// Signal to transmit the address of the account in use, to be shown in the receive tab.
+    SelectedAccountAddress(String),
+
+    /// Signal to notify that the account in use changed, so everything shown of it has to be requested again.
     Update,
 }
+
+/// It represents where the signals to the front are sent, being the window or the observers of the daemon
+pub trait FrontSender: Clone + Send {
+    /// Sends the signal to the front
+    ///
+    /// ### Error
+    ///  * `ErrorUI::FailedSignalToFront`: It will appear when the front is no longer receiving signals
+    fn send(&self, signal: SignalToFront) -> Result<(), ErrorUI>;
+}
+
+impl FrontSender for glib::Sender<SignalToFront> {
+    fn send(&self, signal: SignalToFront) -> Result<(), ErrorUI> {
+        match glib::Sender::send(self, signal) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(ErrorUI::FailedSignalToFront(
+                "The window is closed".to_string(),
+            )),
+        }
+    }
+}
@@ -1,3 +1,6 @@
+mod arguments;
+mod daemon;
+mod error_arguments;
 mod error_execution;
 mod error_initialization;
 mod gui;
@@ -6,27 +9,44 @@ mod tui;
 mod ui;
 
 use std::{
+    collections::HashMap,
     fs::{File, OpenOptions},
-    io::BufReader,
+    io::{self, BufReader, Write},
     path::Path,
     thread::{self, JoinHandle},
 };
 
+use arguments::Arguments;
 use error_execution::ErrorExecution;
 use error_initialization::ErrorInitialization;
+use gui::rpc_message::DEFAULT_RPC_PORT;
 use process::{configuration::Configuration, load_system::LoadSystem, save_system::SaveSystem};
 
 use cargosos_bitcoin::{
-    configurations::{interface::Interface, log_config::LogConfig, save_config::SaveConfig},
-    logs::{error_log::ErrorLog, logger, logger_sender::LoggerSender},
+    configurations::{
+        error_configuration::ErrorConfiguration, interface::Interface, log_config::LogConfig,
+        log_rotation_config::LogRotationConfig, save_config::SaveConfig,
+    },
+    logs::{
+        error_log::ErrorLog,
+        log_buffer::LogBuffer,
+        log_sink::LogSink,
+        logger,
+        logger_sender::LoggerSender,
+        rotating_file::RotatingFile,
+        severity::{Severity, SeverityFilter},
+    },
 };
 
+const TOML_EXTENSION: &str = "toml";
+const LOG_BUFFER_CAPACITY: usize = 1000;
+
 /// Get the configuration name given the arguments
 ///
 /// ### Errors
 ///  * `ErrorNoGivenFile`: It will appear when there is not argument pass that configuration declaration
-fn get_config_name(arguments: Vec<String>) -> Result<String, ErrorInitialization> {
-    let config_name: String = match arguments.get(1) {
+fn get_config_name(arguments: &Arguments) -> Result<String, ErrorInitialization> {
+    let config_name: String = match &arguments.config_name {
         Some(config_name) => config_name.to_owned(),
         None => return Err(ErrorInitialization::NoGivenConfigurationFile),
     };
@@ -47,12 +67,49 @@ fn open_config_file(config_name: String) -> Result<BufReader<File>, ErrorInitial
     Ok(BufReader::new(config_file))
 }
 
-/// Get the file given by its path. If the file does not exist, it will be created. Evrytime the file is opened, it will be truncated to set the file size to 0 and overwrite the previous content
+/// Reads the configuration of the file, in TOML if it has that extension. Every problem found
+/// in the configuration is shown before failing
+///
+/// ### Errors
+/// * `ErrorFileNotExist`: It will appear when the file does not exist
+/// * `ErrorInvalidConfiguration`: It will appear when the validation finds problems in the configuration
+fn read_configuration(config_name: String) -> Result<Configuration, ErrorExecution> {
+    let is_toml = Path::new(&config_name)
+        .extension()
+        .is_some_and(|extension| extension == TOML_EXTENSION);
+    let config_file = open_config_file(config_name)?;
+
+    let configuration = match is_toml {
+        true => Configuration::from_toml(config_file),
+        false => Configuration::new(config_file),
+    };
+
+    if let Err(ErrorConfiguration::ErrorInvalidConfiguration(diagnostics)) = &configuration {
+        println!("The configuration is not valid:");
+        for diagnostic in diagnostics {
+            println!("  {diagnostic}");
+        }
+    }
+
+    Ok(configuration?)
+}
+
+/// Get the file given by its path. If the file does not exist, it will be created. Without a rotation, evrytime the file is opened, it will be truncated to set the file size to 0 and overwrite the previous content. With a rotation, the new messages are appended and the file is rotated when it exceeds its maximum size
 ///
 /// ### Errors
 /// * `ErrorFileNotExist`: It will appear when the file does not exist
 /// * `CouldNotTruncateFile`: It will appear when the file could not be truncated
-fn open_log_file(log_path: &Path) -> Result<File, ErrorInitialization> {
+fn open_log_file(
+    log_path: &Path,
+    rotation: Option<LogRotationConfig>,
+) -> Result<Box<dyn Write + Send>, ErrorInitialization> {
+    if let Some(rotation) = rotation {
+        return match RotatingFile::new(log_path, rotation) {
+            Ok(log_file) => Ok(Box::new(log_file)),
+            _ => Err(ErrorInitialization::LogFileDoesntExist),
+        };
+    }
+
     let log_file = match OpenOptions::new()
         .create(true)
         .write(true)
@@ -63,10 +120,11 @@ fn open_log_file(log_path: &Path) -> Result<File, ErrorInitialization> {
         _ => return Err(ErrorInitialization::LogFileDoesntExist),
     };
 
-    Ok(log_file)
+    Ok(Box::new(log_file))
 }
 
-/// Initialize the logs ready for ejecution
+/// Initialize the logs ready for ejecution. The messages are written in the log file, in the console if it's configured,
+/// and in the buffer shown in the debug console of the graphical interface, up to `Severity::DEBUG`
 ///
 /// ### Errors
 ///  * `ErrorCouldNotFindReceiver`: No se encontro el receiver
@@ -75,12 +133,35 @@ fn open_log_file(log_path: &Path) -> Result<File, ErrorInitialization> {
 ///  * `CouldNotTruncateFile`: No se pudo truncar el archivo de logs
 fn initialize_logs(
     log_config: LogConfig,
+    log_buffer: LogBuffer,
 ) -> Result<(JoinHandle<Result<(), ErrorLog>>, LoggerSender), ErrorExecution> {
-    println!("Creating the logs system");
-
     let filepath_log = Path::new(&log_config.filepath_log);
-    let log_file = open_log_file(filepath_log)?;
-    let (logger, logger_receiver) = logger::initialize_logger(log_file, log_config.show_console);
+    let log_file = open_log_file(filepath_log, log_config.rotation)?;
+    let (logger, logger_receiver) = logger::initialize_logger(log_file, false);
+    let logger_receiver = match log_config.levels.clone() {
+        Some(levels) => logger_receiver.filter_levels(levels),
+        None => logger_receiver,
+    };
+    let logger_receiver = logger_receiver
+        .filter_severities(log_config.severities.clone())
+        .with_format(log_config.format);
+
+    let logger_receiver = match log_config.show_console {
+        true => {
+            let console = LogSink::new(io::stdout()).filter_severities(log_config.severities);
+            let console = match log_config.levels {
+                Some(levels) => console.filter_levels(levels),
+                None => console,
+            };
+            logger_receiver.with_sink(console)
+        }
+        false => logger_receiver,
+    };
+    let logger_receiver =
+        logger_receiver.with_sink(LogSink::new(log_buffer).filter_severities(SeverityFilter {
+            default: Severity::DEBUG,
+            levels: HashMap::new(),
+        }));
 
     let handle = thread::spawn(move || logger_receiver.receive_log());
 
@@ -102,41 +183,114 @@ fn end_program(
 }
 
 fn main() -> Result<(), ErrorExecution> {
-    let arguments: Vec<String> = std::env::args().collect();
+    let arguments =
+        Arguments::new(std::env::args().collect()).map_err(ErrorInitialization::from)?;
 
-    println!("\tInitialization");
-    println!("Reading the configuration file");
+    // The output of a script is only the result of its commands
+    let is_scripted = arguments.script.is_some();
+    if !is_scripted {
+        println!("\tInitialization");
+        println!("Reading the configuration file");
+    }
 
-    let config_name: String = get_config_name(arguments)?;
-    let config_file = open_config_file(config_name)?;
+    let config_name: String = get_config_name(&arguments)?;
+    let mut configuration = read_configuration(config_name.clone())?;
+    arguments.override_configuration(&mut configuration);
+    configuration.resolve_data_directory()?;
+    let (
+        log_config,
+        connection_config,
+        download_config,
+        save_config,
+        ui_config,
+        mode_config,
+        faucet_config,
+        exchange_rate_config,
+    ) = configuration.separate();
+    let backend_configs = (
+        connection_config,
+        download_config,
+        faucet_config,
+        exchange_rate_config,
+        save_config.clone(),
+    );
 
-    let configuration = Configuration::new(config_file)?;
-    let (log_config, connection_config, download_config, save_config, ui_config, mode_config) =
-        configuration.separate();
+    let mut log_config = log_config;
+    if ui_config.interface == Interface::Tui {
+        // The dashboard shows the logs in its own pane, and the output of a script must be only JSON
+        log_config.show_console = false;
+    }
 
-    let (handle, logger) = initialize_logs(log_config)?;
+    let log_buffer = LogBuffer::new(LOG_BUFFER_CAPACITY);
+    if !is_scripted {
+        println!("Creating the logs system");
+    }
+    let (handle, logger) = initialize_logs(log_config, log_buffer.clone())?;
+
+    process::shutdown::trap_signals();
 
-    let save_system = match ui_config.interface {
-        Interface::Tui => {
+    let save_system = match (ui_config.interface, ui_config.rpc_port) {
+        (Interface::Tui, _) => {
             let mut load_system = LoadSystem::new(save_config.clone(), logger.clone());
-            tui::execution::program_execution(
-                mode_config,
-                connection_config,
-                download_config,
-                &mut load_system,
-                logger.clone(),
-            )?
+            match arguments.script.clone() {
+                Some(script) => Some(tui::execution::script_execution(
+                    script,
+                    mode_config,
+                    backend_configs,
+                    (ui_config.rest_port, ui_config.publisher_port),
+                    &mut load_system,
+                    logger.clone(),
+                )?),
+                None => Some(tui::execution::program_execution(
+                    mode_config,
+                    backend_configs,
+                    (ui_config.rest_port, ui_config.publisher_port),
+                    &mut load_system,
+                    logger.clone(),
+                    (log_buffer.clone(), ui_config.language),
+                )?),
+            }
         }
-        Interface::Gui => gui::execution::program_execution(
+        (Interface::Gui, None) => Some(gui::execution::program_execution(
             mode_config,
-            connection_config,
-            download_config,
+            backend_configs,
+            (ui_config.rest_port, ui_config.publisher_port),
             save_config.clone(),
             logger.clone(),
-        )?,
+            (
+                log_buffer.clone(),
+                (ui_config.theme, ui_config.language, config_name),
+            ),
+        )?),
+        (Interface::Gui, Some(rpc_port)) => {
+            gui::execution::observer_execution(
+                rpc_port,
+                logger.clone(),
+                log_buffer.clone(),
+                (ui_config.theme, ui_config.language, config_name),
+            )?;
+            None
+        }
+        (Interface::Daemon, rpc_port) => {
+            let mut load_system = LoadSystem::new(save_config.clone(), logger.clone());
+            Some(daemon::execution::program_execution(
+                (
+                    rpc_port.unwrap_or(DEFAULT_RPC_PORT),
+                    ui_config.websocket_port,
+                    (ui_config.rest_port, ui_config.publisher_port),
+                ),
+                mode_config,
+                backend_configs,
+                &mut load_system,
+                logger.clone(),
+            )?)
+        }
     };
 
-    end_program(save_system, save_config, logger)?;
+    match save_system {
+        Some(save_system) => end_program(save_system, save_config, logger)?,
+        None => drop(logger),
+    }
 
     match handle.join() {
         Ok(result) => result?,
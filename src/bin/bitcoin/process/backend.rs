@@ -1,50 +1,77 @@
 use crate::{
     error_execution::ErrorExecution,
     process::{
-        broadcasting, connection, download, error_process::ErrorProcess, load_system::LoadSystem,
-        reference, reference::MutArc, save_system::SaveSystem,
+        broadcasting,
+        broadcasting::{ChainState, PeerRules, WalletState},
+        connection, download,
+        error_process::ErrorProcess,
+        exchange_rate,
+        load_system::LoadSystem,
+        reference,
+        reference::MutArc,
+        rest_server::RestServer,
+        save_system,
+        save_system::SaveSystem,
+        shutdown::{self, WORKER_JOIN_TIMEOUT},
+        state::SharedState,
+        supervisor::{RestartPolicy, Supervisor},
     },
-    ui::{error_ui::ErrorUI, input_handler::InputHandler},
+    ui::{account, error_ui::ErrorUI, input_handler::InputHandler},
 };
 
 use cargosos_bitcoin::{
-    block_structure::{block_chain::BlockChain, utxo_set::UTXOSet},
     concurrency::stop::Stop,
     configurations::{
         connection_config::ConnectionConfig, download_config::DownloadConfig,
-        mode_config::ModeConfig,
+        exchange_rate_config::ExchangeRateConfig, faucet_config::FaucetConfig,
+        mode_config::ModeConfig, save_config::SaveConfig,
     },
     logs::{level::Level, logger_sender::LoggerSender},
     node_structure::{
         broadcasting::Broadcasting, connection_event::ConnectionEvent, connection_id::ConnectionId,
-        message_response::MessageResponse,
+        message_response::MessageResponse, network_time::NetworkTime,
+        script_subscriptions::ScriptSubscriptions,
     },
-    notifications::{notification::Notification, notifier::Notifier},
-    wallet_structure::wallet::Wallet,
+    notifications::{
+        event_publisher::EventPublisher, notification::Notification, notifier::Notifier,
+    },
+    wallet_structure::{balance_history::BalanceHistory, faucet::Faucet},
 };
 
 use std::{
-    net::TcpStream,
+    net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream},
     sync::mpsc::{channel, Receiver},
-    sync::{Arc, Mutex},
-    thread::JoinHandle,
 };
 
-type HandlePeer = JoinHandle<Result<(), ErrorProcess>>;
+/// The names of the worker threads owned by the supervisor
+const PROCESS_CONNECTION_WORKER: &str = "process connection";
+const CONFIRMED_CONNECTION_WORKER: &str = "confirmed connection";
+const PEERS_HANDLER_WORKER: &str = "peers handler";
+
+/// The configurations of the node, of the services the wallet uses and of the autosave
+pub type BackendConfigs = (
+    ConnectionConfig,
+    DownloadConfig,
+    Option<FaucetConfig>,
+    Option<ExchangeRateConfig>,
+    SaveConfig,
+);
 
-/// The main function of the program for the terminal
+/// The main function of the program for the terminal. If a REST port is given, the blockchain
+/// can be queried through it, and if a publisher port is given the new blocks and the received
+/// transactions are published to the services subscribed to it
 ///
 /// ### Error
 ///  * `ErrorExecution::FailThread`: It will appear when the thread fails
-///  * `ErrorUI::CannotGetInner`: It will appear when we try to get the inner value of a mutex
-///  * `ErrorUI::CannotUnwrapArc`: It will appear when we try to unwrap an Arc
+///  * `ErrorUI::StillShared`: It will appear when a thread still has a value that has to be saved
 ///  * `UI::ErrorFromPeer`: It will appear when a conextion with a peer fails
 ///  * `ErrorProcess:CannotCreateDefault`: It will appear when can't create the default value
 ///  * `ErrorProcess:AlreadyLoaded`: It will appear when try to get a value that is already loadedError
+///  * `ErrorUI::ConnectionAborted`: It will appear when the REST or the publisher port cannot be used
 pub fn backend<N, I>(
     mode_config: ModeConfig,
-    connection_config: ConnectionConfig,
-    download_config: DownloadConfig,
+    configs: BackendConfigs,
+    local_ports: (Option<u16>, Option<u16>),
     load_system: &mut LoadSystem,
     input_handler: I,
     notifier: N,
@@ -54,56 +81,171 @@ where
     I: InputHandler<TcpStream>,
     N: Notifier + 'static,
 {
+    let (connection_config, download_config, faucet_config, exchange_rate_config, save_config) =
+        configs;
+    let (rest_port, publisher_port) = local_ports;
+
+    let notifier = EventPublisher::new(notifier, logger.clone());
+    if let Some(publisher_port) = publisher_port {
+        listen_subscribers(&notifier, publisher_port, logger.clone())?;
+    }
+
+    let chain_tip = load_system.get_chain_tip();
+    if let Some(chain_tip) = chain_tip {
+        notifier.notify(Notification::LastSyncedTip(chain_tip));
+    }
+    let last_tip = SharedState::new(chain_tip.map(|chain_tip| chain_tip.block_hash));
+
     let (sender_confirm_connection, receiver_confirm_connection) =
-        channel::<(TcpStream, ConnectionId)>();
+        channel::<(TcpStream, ConnectionId, String)>();
+
+    let network_time = SharedState::new(NetworkTime::default());
 
     let (handle_process_connection, sender_potential_connections) =
         connection::create_process_connection(
             connection_config.clone(),
             sender_confirm_connection,
+            network_time.clone(),
             notifier.clone(),
             logger.clone(),
         );
 
-    let wallet = load_system.get_wallet()?;
+    let supervisor = Supervisor::new(logger.clone());
+    supervisor.supervise(
+        PROCESS_CONNECTION_WORKER,
+        handle_process_connection,
+        RestartPolicy::Fatal,
+    )?;
+
+    let wallet = load_system
+        .get_wallet()?
+        .with_network(connection_config.network);
+    let wallet_directory = load_system.get_wallet_directory().map(SharedState::new);
 
     for account in wallet.get_accounts().iter() {
         notifier.notify(Notification::RegisterWalletAccount(account.clone()));
     }
 
-    let wallet = Arc::new(Mutex::new(wallet));
+    let wallet = SharedState::new(wallet);
+
+    account::give_available_wallets(&wallet_directory, notifier.clone(), logger.clone())?;
 
     let block_chain = load_system.get_block_chain()?;
 
-    let utxo_set = Arc::new(Mutex::new(download::get_utxo_set(
-        &block_chain,
+    let utxo_set = match load_system.get_utxo_set(&block_chain) {
+        Some(utxo_set) => utxo_set,
+        None => download::get_utxo_set(&block_chain, logger.clone()),
+    };
+    let utxo_set = SharedState::new(utxo_set);
+
+    let balance_history = match load_system.get_balance_history(&block_chain) {
+        Some(balance_history) => balance_history,
+        None => BalanceHistory::from_blockchain(
+            &block_chain,
+            reference::get_reference(&wallet)?.get_accounts(),
+        ),
+    };
+    let balance_history = SharedState::new(balance_history);
+
+    let block_chain = SharedState::new(block_chain);
+    let download_cursor = SharedState::new(load_system.get_download_cursor().unwrap_or_default());
+
+    let (sender_stop_block_store, receiver_stop_block_store) = channel::<Stop>();
+    let handle_block_store = save_system::persist_block_chain(
+        load_system.get_block_store(),
+        (block_chain.clone(), download_cursor.clone()),
+        &save_config,
+        receiver_stop_block_store,
         logger.clone(),
-    )));
+    );
 
-    let block_chain = Arc::new(Mutex::new(block_chain));
+    if let Some(rest_port) = rest_port {
+        RestServer::new(block_chain.clone(), utxo_set.clone(), logger.clone()).listen(rest_port)?;
+    }
+
+    let faucet = create_faucet(faucet_config, logger.clone());
+
+    let (sender_stop_autosave, receiver_stop_autosave) = channel::<Stop>();
+    let handle_autosave = save_system::autosave(
+        save_config,
+        (
+            wallet.clone(),
+            utxo_set.clone(),
+            balance_history.clone(),
+            block_chain.clone(),
+            wallet_directory.clone(),
+        ),
+        receiver_stop_autosave,
+        notifier.clone(),
+        logger.clone(),
+    );
+
+    let (sender_stop_exchange_rate, receiver_stop_exchange_rate) = channel::<Stop>();
+    let handle_exchange_rate = exchange_rate::poll_exchange_rate(
+        exchange_rate_config,
+        receiver_stop_exchange_rate,
+        notifier.clone(),
+        logger.clone(),
+    );
 
     notifier.notify(Notification::NotifyBlockchainIsReady);
 
+    let script_subscriptions = SharedState::new(ScriptSubscriptions::default());
+
     let (sender_response, receiver_response) = channel::<MessageResponse>();
 
-    let (handle_peers, broadcasting) = broadcasting(
-        (wallet.clone(), utxo_set.clone(), block_chain.clone()),
+    let broadcasting = broadcasting(
+        (
+            wallet.clone(),
+            utxo_set.clone(),
+            balance_history.clone(),
+            faucet.clone(),
+        ),
+        (
+            block_chain.clone(),
+            network_time.clone(),
+            script_subscriptions.clone(),
+        ),
         receiver_response,
+        (
+            connection_config.min_relay_fee as i64,
+            connection_config.network.soft_fork_heights(),
+        ),
+        &supervisor,
         notifier.clone(),
         logger.clone(),
-    );
+    )?;
+
+    let onion_peers = match &connection_config.proxy {
+        Some(proxy) => proxy.onion_peers.clone(),
+        None => Vec::new(),
+    };
 
     let handle_confirmed_connection = connection::update_from_connection(
         receiver_confirm_connection,
         sender_response,
-        (broadcasting.clone(), block_chain.clone(), utxo_set.clone()),
+        (
+            broadcasting.clone(),
+            block_chain.clone(),
+            utxo_set.clone(),
+            download_cursor.clone(),
+            wallet.clone(),
+            network_time,
+            last_tip,
+        ),
         (connection_config, download_config),
         notifier.clone(),
         logger.clone(),
     );
+    supervisor.supervise(
+        CONFIRMED_CONNECTION_WORKER,
+        handle_confirmed_connection,
+        RestartPolicy::Fatal,
+    )?;
 
     connection::establish_connection_to_peers(
         mode_config.clone(),
+        onion_peers,
         sender_potential_connections.clone(),
         logger.clone(),
     )?;
@@ -120,12 +262,25 @@ where
         ModeConfig::Client(_) => None,
     };
 
-    input_handler.handle_input(
+    match input_handler.handle_input(
         broadcasting.clone(),
-        wallet.clone(),
-        utxo_set,
+        (
+            wallet.clone(),
+            utxo_set.clone(),
+            balance_history.clone(),
+            faucet,
+        ),
         block_chain.clone(),
-    )?;
+        wallet_directory.clone(),
+        script_subscriptions,
+    ) {
+        Ok(()) => {}
+        Err(ErrorUI::Interrupted) => {
+            let _ =
+                logger.log_interface("The program was asked to stop, shutting down".to_string());
+        }
+        Err(error) => return Err(error.into()),
+    }
 
     if let Some(handle) = posible_handle {
         if sender_stop.send(Stop::Stop).is_err() {
@@ -133,7 +288,7 @@ where
                 Level::ERROR,
                 ErrorUI::ErrorFromPeer("Fail to stop potential connections".to_string()),
             );
-        } else if handle.join().is_err() {
+        } else if shutdown::join_with_timeout(handle, WORKER_JOIN_TIMEOUT).is_err() {
             let _ = logger.log_data(
                 Level::ERROR,
                 ErrorUI::ErrorFromPeer("Fail to close confirmed connections".to_string()),
@@ -141,6 +296,22 @@ where
         }
     }
 
+    if let Some(handle) = handle_autosave {
+        if sender_stop_autosave.send(Stop::Stop).is_err()
+            || shutdown::join_with_timeout(handle, WORKER_JOIN_TIMEOUT).is_err()
+        {
+            let _ = logger.log_error("Fail to stop the autosave".to_string());
+        }
+    }
+
+    if let Some(handle) = handle_exchange_rate {
+        if sender_stop_exchange_rate.send(Stop::Stop).is_err()
+            || shutdown::join_with_timeout(handle, WORKER_JOIN_TIMEOUT).is_err()
+        {
+            let _ = logger.log_error("Fail to stop asking for the exchange rate".to_string());
+        }
+    }
+
     if sender_potential_connections
         .send(ConnectionEvent::Stop)
         .is_err()
@@ -150,20 +321,14 @@ where
             ErrorUI::ErrorFromPeer("Failed to stop potential connections".to_string()),
         );
     } else {
-        match handle_process_connection.join() {
-            Ok(Ok(())) => {}
-            Ok(Err(error)) => {
-                let _ = logger.log_data(Level::ERROR, error);
-            }
-            Err(_) => {
-                let _ = logger.log_data(
-                    Level::ERROR,
-                    ErrorUI::ErrorFromPeer("Failed to close confirmed connections".to_string()),
-                );
-            }
+        if let Err(error) = supervisor.join(PROCESS_CONNECTION_WORKER, WORKER_JOIN_TIMEOUT) {
+            let _ = logger.log_data(Level::ERROR, error);
         }
 
-        if handle_confirmed_connection.join().is_err() {
+        if supervisor
+            .join(CONFIRMED_CONNECTION_WORKER, WORKER_JOIN_TIMEOUT)
+            .is_err()
+        {
             let _ = logger.log_data(
                 Level::ERROR,
                 ErrorUI::ErrorFromPeer("Failed to close confirmed connections".to_string()),
@@ -174,7 +339,9 @@ where
     if reference::get_reference(&broadcasting)?
         .close_connections(notifier)
         .is_ok()
-        && handle_peers.join().is_err()
+        && supervisor
+            .join(PEERS_HANDLER_WORKER, WORKER_JOIN_TIMEOUT)
+            .is_err()
     {
         let _ = logger.log_data(
             Level::ERROR,
@@ -182,36 +349,117 @@ where
         );
     }
 
+    if supervisor.stop().is_err() {
+        let _ = logger.log_error("Fail to stop the supervisor of the threads".to_string());
+    }
+
+    let block_store = match handle_block_store {
+        Some(handle) => match sender_stop_block_store.send(Stop::Stop) {
+            Ok(()) => shutdown::join_with_timeout(handle, WORKER_JOIN_TIMEOUT).ok(),
+            Err(_) => None,
+        },
+        None => None,
+    };
+    if block_store.is_none() {
+        let _ = logger.log_file("The block chain is only saved at shutdown".to_string());
+    }
+
+    let wallet_directory = match wallet_directory {
+        Some(wallet_directory) => Some(reference::get_inner(wallet_directory)?),
+        None => None,
+    };
+
+    let utxo_set = reference::get_reference(&utxo_set)?.clone();
+    let balance_history = reference::get_reference(&balance_history)?.clone();
+
     Ok(SaveSystem::new(
         reference::get_inner(block_chain)?,
+        block_store,
+        (utxo_set, balance_history),
+        reference::get_inner(download_cursor)?,
         reference::get_inner(wallet)?,
+        wallet_directory,
         logger,
     ))
 }
 
-/// Broadcasting blocks and transactions from and to the given peers
+/// Accepts the services subscribing to the chain events in the given local port
+///
+/// ### Error
+///  * `ErrorUI::ConnectionAborted`: It will appear when the port cannot be used
+fn listen_subscribers<N: Notifier + 'static>(
+    event_publisher: &EventPublisher<N>,
+    publisher_port: u16,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, publisher_port));
+
+    match TcpListener::bind(address) {
+        Ok(listener) => {
+            let _ = logger.log_connection(format!("Publishing the chain events at {address}"));
+            event_publisher.listen(listener);
+            Ok(())
+        }
+        Err(error) => {
+            let _ = logger.log_error(format!(
+                "Cannot publish the chain events at {address}: {:?}",
+                error
+            ));
+            Err(ErrorUI::ConnectionAborted)
+        }
+    }
+}
+
+/// Creates the faucet helper if it's configured
+fn create_faucet(
+    faucet_config: Option<FaucetConfig>,
+    logger: LoggerSender,
+) -> Option<MutArc<Faucet>> {
+    let faucet_config = faucet_config?;
+
+    match Faucet::new(&faucet_config.url) {
+        Ok(faucet) => Some(SharedState::new(faucet)),
+        Err(error) => {
+            let _ = logger.log_data(Level::ERROR, error);
+            None
+        }
+    }
+}
+
+/// Broadcasting blocks and transactions from and to the given peers. The thread handling the
+/// messages of the peers is restarted by the supervisor if it panics, receiving the messages that
+/// were left. The blocks and transactions of the peers must follow the given rules
 fn broadcasting<N: Notifier + 'static>(
-    data: (MutArc<Wallet>, MutArc<UTXOSet>, MutArc<BlockChain>),
+    wallet_state: WalletState,
+    chain_state: ChainState,
     receiver_response: Receiver<MessageResponse>,
+    peer_rules: PeerRules,
+    supervisor: &Supervisor,
     notifier: N,
     logger: LoggerSender,
-) -> (HandlePeer, MutArc<Broadcasting<TcpStream>>) {
-    let wallet: Arc<Mutex<Wallet>> = data.0;
-    let utxo_set: Arc<Mutex<UTXOSet>> = data.1;
-    let block_chain: Arc<Mutex<BlockChain>> = data.2;
-
+) -> Result<MutArc<Broadcasting<TcpStream>>, ErrorProcess> {
     let broadcasting = Broadcasting::<TcpStream>::new(logger.clone());
-    let broadcasting = Arc::new(Mutex::new(broadcasting));
+    let broadcasting = SharedState::new(broadcasting);
 
-    let handle = broadcasting::handle_peers(
-        receiver_response,
-        broadcasting.clone(),
-        wallet,
-        utxo_set,
-        block_chain,
-        notifier,
-        logger,
-    );
+    let receiver_response = SharedState::new(receiver_response);
+    let broadcasting_handler = broadcasting.clone();
+    let spawn_handler = move || {
+        broadcasting::handle_peers(
+            receiver_response.clone(),
+            broadcasting_handler.clone(),
+            wallet_state.clone(),
+            chain_state.clone(),
+            peer_rules,
+            notifier.clone(),
+            logger.clone(),
+        )
+    };
+
+    supervisor.supervise(
+        PEERS_HANDLER_WORKER,
+        spawn_handler(),
+        RestartPolicy::Restart(Box::new(spawn_handler)),
+    )?;
 
-    (handle, broadcasting)
+    Ok(broadcasting)
 }
@@ -5,46 +5,92 @@ use super::{
 
 use cargosos_bitcoin::{
     block_structure::{
-        block::Block, block_chain::BlockChain, error_block::ErrorBlock, transaction::Transaction,
+        block::Block,
+        block_chain::BlockChain,
+        coinbase::CoinbaseInfo,
+        error_block::ErrorBlock,
+        mempool_info::MempoolInfo,
+        relay_policy,
+        soft_fork::{self, SoftForkHeights},
+        transaction::Transaction,
         utxo_set::UTXOSet,
     },
     logs::logger_sender::LoggerSender,
     node_structure::{
-        broadcasting::Broadcasting, connection_id::ConnectionId, message_response::MessageResponse,
-        message_to_peer::MessageToPeer, peer_manager::PeerManager,
+        broadcasting::Broadcasting,
+        connection_id::ConnectionId,
+        connection_type::ConnectionType,
+        message_response::MessageResponse,
+        message_to_peer::MessageToPeer,
+        network_time::NetworkTime,
+        orphan_pool::{OrphanPool, MAX_ORPHAN_BLOCKS, MAX_ORPHAN_TRANSACTIONS, ORPHAN_EXPIRATION},
+        peer_manager::PeerManager,
+        peer_queue::{PeerQueue, QueuePush, PEER_QUEUE_CAPACITY, PEER_QUEUE_FULL_TIMEOUT},
+        script_subscriptions::ScriptSubscriptions,
     },
     notifications::{notification::Notification, notifier::Notifier},
-    wallet_structure::wallet::Wallet,
+    wallet_structure::{
+        amount::Amount, balance_history::BalanceHistory, faucet::Faucet, wallet::Wallet,
+    },
 };
 
 use std::{
     io::{Read, Write},
-    sync::mpsc::{channel, Receiver, Sender},
+    sync::mpsc::{Receiver, Sender},
     thread::{self, JoinHandle},
+    time::Instant,
 };
 
-/// Gives the broadcasting the peers to broadcast the blocks and transactions
+/// It's the state of the wallet shared between the threads: the wallet, its utxo set, the balance history and the faucet to watch
+pub type WalletState = (
+    MutArc<Wallet>,
+    MutArc<UTXOSet>,
+    MutArc<BalanceHistory>,
+    Option<MutArc<Faucet>>,
+);
+
+/// It's the state of the chain shared between the threads: the block chain, the time of the network and the script hashes subscribed
+pub type ChainState = (
+    MutArc<BlockChain>,
+    MutArc<NetworkTime>,
+    MutArc<ScriptSubscriptions>,
+);
+
+/// It's the rules the blocks and transactions of the peers must follow: the minimum relay fee, in satoshis per kilobyte, and the heights of the soft forks
+pub type PeerRules = (i64, SoftForkHeights);
+
+/// Gives the broadcasting the peers, with their user agent, to broadcast the blocks and transactions.
+/// The peers are asked for their mempool, to receive the transactions broadcasted while we were offline
 pub fn add_peer_to_broadcasting<N: Notifier + 'static, RW: Read + Write + Send + 'static>(
     broadcasting: &mut Broadcasting<RW>,
-    connection: (RW, ConnectionId),
+    connection: (RW, ConnectionId, String),
     sender_response: Sender<MessageResponse>,
     blockchain: MutArc<BlockChain>,
     magic_numbers: [u8; 4],
     notifier: N,
     logger: LoggerSender,
 ) {
+    let (stream, connection_id, user_agent) = connection;
+    let connection_type = connection_id.connection_type;
     let peer_manager = create_peer_manager(
-        connection,
+        (stream, connection_id),
         sender_response,
         blockchain,
         magic_numbers,
         notifier,
         logger.clone(),
-    );
+    )
+    .with_user_agent(user_agent);
+
+    let (mut queue, receiver) = PeerQueue::new(PEER_QUEUE_CAPACITY, PEER_QUEUE_FULL_TIMEOUT);
 
-    let (sender, receiver) = channel::<MessageToPeer>();
+    if connection_type == ConnectionType::Peer
+        && queue.push(MessageToPeer::RequestMempool, Instant::now()) != QueuePush::Queued
+    {
+        let _ = logger.log_node("Error requesting the mempool of the peer".to_string());
+    }
 
-    broadcasting.add_connection(peer_manager, (sender, receiver));
+    broadcasting.add_connection(peer_manager, (queue, receiver));
 
     let _ = logger.log_node("Adding new peer to bradcasting".to_string());
 }
@@ -63,20 +109,27 @@ fn create_peer_manager<N: Notifier + 'static, RW: Read + Write + Send + 'static>
         connection.1,
         connection.0,
         sender_response,
-        blockchain,
+        blockchain.as_mutex(),
         magic_numbers,
         notifier,
         logger,
     )
 }
 
-/// Create a thread for handling the blocks and transactions received
+/// Create a thread for handling the blocks and transactions received.
+/// The blocks with a time too far ahead of the time of the network are discarded. The receiver is
+/// shared so the messages can still be received by another thread if this one panics.
+/// The blocks whose previous block is unknown wait in an orphan pool until it arrives, and so do the
+/// transactions spending outputs of unknown transactions before being broadcasted. The transactions
+/// with dust outputs or paying less than the minimum relay fee, in satoshis per kilobyte, are discarded,
+/// and so are the ones whose lock time doesn't let them be in the next block. The blocks and transactions
+/// breaking the rules of the soft forks active at their height are discarded too
 pub fn handle_peers<RW, N>(
-    receiver_broadcasting: Receiver<MessageResponse>,
+    receiver_broadcasting: MutArc<Receiver<MessageResponse>>,
     broadcasting: MutArc<Broadcasting<RW>>,
-    wallet: MutArc<Wallet>,
-    utxo_set: MutArc<UTXOSet>,
-    block_chain: MutArc<BlockChain>,
+    wallet_state: WalletState,
+    chain_state: ChainState,
+    peer_rules: PeerRules,
     notifier: N,
     logger: LoggerSender,
 ) -> JoinHandle<Result<(), ErrorProcess>>
@@ -85,39 +138,183 @@ where
     N: Notifier + 'static,
 {
     thread::spawn(move || {
-        for message in receiver_broadcasting {
+        let (wallet, utxo_set, _, _) = &wallet_state;
+        let (block_chain, network_time, script_subscriptions) = &chain_state;
+        let (min_relay_fee, soft_fork_heights) = peer_rules;
+        let mut orphan_blocks = OrphanPool::new(MAX_ORPHAN_BLOCKS, ORPHAN_EXPIRATION);
+        let mut orphan_transactions = OrphanPool::new(MAX_ORPHAN_TRANSACTIONS, ORPHAN_EXPIRATION);
+
+        loop {
+            let message = match get_reference(&receiver_broadcasting)?.recv() {
+                Ok(message) => message,
+                Err(_) => break,
+            };
             let mut broadcasting_reference = get_reference(&broadcasting)?;
 
             match message {
                 MessageResponse::Block(block, from) => {
-                    receive_block(
-                        &utxo_set,
-                        &wallet,
-                        &block_chain,
-                        block.clone(),
-                        notifier.clone(),
-                    )?;
+                    let adjusted_time = get_reference(network_time)?.get_adjusted_time();
+                    if !block.header.is_time_valid(adjusted_time) {
+                        let _ = logger.log_node(format!(
+                            "Block {block} from {from} too far ahead of the network time"
+                        ));
+                        continue;
+                    }
+
+                    let previous_hash = block.header.previous_block_header_hash;
+                    if get_reference(block_chain)?
+                        .get_height_with_hash(&previous_hash)
+                        .is_none()
+                    {
+                        if orphan_blocks.add(
+                            block.clone(),
+                            from,
+                            vec![previous_hash],
+                            Instant::now(),
+                        ) {
+                            let _ = logger.log_node(format!(
+                                "Block {block} from {from} is waiting for its previous block"
+                            ));
+                        }
+                        continue;
+                    }
+
+                    let mut blocks = vec![(block, from)];
+                    while let Some((block, from)) = blocks.pop() {
+                        let height = get_reference(block_chain)?
+                            .get_height_with_hash(&block.header.previous_block_header_hash)
+                            .map(|height| height + 1);
+                        let soft_fork_check = {
+                            let utxo_set = get_reference(utxo_set)?;
+                            height.map(|height| {
+                                soft_fork::check_block(&block, height, soft_fork_heights, &utxo_set)
+                            })
+                        };
+
+                        if let Some(Err(error)) = soft_fork_check {
+                            let _ = logger.log_node(format!(
+                                "Block {block} from {from} breaks the rules of the soft forks: {:?}",
+                                error
+                            ));
+                            continue;
+                        }
+
+                        receive_block(
+                            &wallet_state,
+                            (block_chain, script_subscriptions),
+                            block.clone(),
+                            notifier.clone(),
+                        )?;
+
+                        let now = Instant::now();
+                        if let Ok(header_hash) = block.header.get_hash256d() {
+                            blocks.extend(orphan_blocks.resolve(&header_hash, now));
+                        }
+                        for transaction_id in
+                            Transaction::get_vec_txids(&block.transactions).unwrap_or_default()
+                        {
+                            for (transaction, from) in
+                                orphan_transactions.resolve(&transaction_id, now)
+                            {
+                                if broadcasting_reference
+                                    .broadcast_transaction(transaction, from)
+                                    .is_err()
+                                {
+                                    let _ = logger
+                                        .log_node("Error broadcasting transaction".to_string());
+                                    return Err(ErrorProcess::ErrorReading);
+                                }
+                            }
+                        }
 
-                    if broadcasting_reference.broadcast_block(block, from).is_err() {
-                        let _ = logger.log_node("Error broadcasting block".to_string());
-                        return Err(ErrorProcess::ErrorReading);
+                        if broadcasting_reference.broadcast_block(block, from).is_err() {
+                            let _ = logger.log_node("Error broadcasting block".to_string());
+                            return Err(ErrorProcess::ErrorReading);
+                        }
                     }
                 }
                 MessageResponse::Transaction(transaction, from) => {
+                    let (height, median_time_past) = {
+                        let block_chain = get_reference(block_chain)?;
+                        let height = block_chain.get_tip().map(|(_, height)| height as u32);
+                        (
+                            height.unwrap_or_default(),
+                            block_chain.get_median_time_past(),
+                        )
+                    };
+                    if !transaction.is_final(height + 1, median_time_past) {
+                        let _ = logger.log_node(format!(
+                            "Transaction {transaction} from {from} is not accepted: its lock time {} has not passed",
+                            transaction.lock_time
+                        ));
+                        continue;
+                    }
+
+                    if height as u64 + 1 >= soft_fork_heights.bip65 {
+                        let lock_time_verify = {
+                            let utxo_set = get_reference(utxo_set)?;
+                            soft_fork::check_lock_time_verify(&transaction, &utxo_set)
+                        };
+                        if let Err(error) = lock_time_verify {
+                            let _ = logger.log_node(format!(
+                                "Transaction {transaction} from {from} is not accepted: {:?}",
+                                error
+                            ));
+                            continue;
+                        }
+                    }
+
+                    let fee = get_reference(utxo_set)?
+                        .check_transaction(&transaction)
+                        .ok();
+                    if let Some(Err(error)) =
+                        fee.map(|fee| relay_policy::check_relay(&transaction, fee, min_relay_fee))
+                    {
+                        let _ = logger.log_node(format!(
+                            "Transaction {transaction} from {from} is not accepted: {:?}",
+                            error
+                        ));
+                        continue;
+                    }
+
                     receive_transaction(
-                        &wallet,
+                        (wallet, utxo_set, script_subscriptions),
                         transaction.clone(),
-                        &utxo_set,
                         logger.clone(),
                         notifier.clone(),
                     )?;
 
-                    if broadcasting_reference
-                        .broadcast_transaction(transaction, from)
-                        .is_err()
-                    {
-                        let _ = logger.log_node("Error broadcasting transaction".to_string());
-                        return Err(ErrorProcess::ErrorReading);
+                    let missing_parents =
+                        get_reference(utxo_set)?.get_missing_parents(&transaction);
+                    if !missing_parents.is_empty() {
+                        if orphan_transactions.add(
+                            transaction.clone(),
+                            from,
+                            missing_parents,
+                            Instant::now(),
+                        ) {
+                            let _ = logger.log_node(format!(
+                                "Transaction {transaction} from {from} is waiting for the transactions it spends"
+                            ));
+                        }
+                        continue;
+                    }
+
+                    let mut transactions = vec![(transaction, from)];
+                    while let Some((transaction, from)) = transactions.pop() {
+                        if let Ok(transaction_id) = transaction.get_tx_id() {
+                            transactions.extend(
+                                orphan_transactions.resolve(&transaction_id, Instant::now()),
+                            );
+                        }
+
+                        if broadcasting_reference
+                            .broadcast_transaction(transaction, from)
+                            .is_err()
+                        {
+                            let _ = logger.log_node("Error broadcasting transaction".to_string());
+                            return Err(ErrorProcess::ErrorReading);
+                        }
                     }
                 }
             }
@@ -127,17 +324,19 @@ where
     })
 }
 
-/// Manage receiving a transaction by updating the list of transactions seen so far if the transaction is from the selected account
-///
-/// ### Error
-///  * `ErrorUI::CannotUnwrapArc`: It will appear when we try to unwrap an Arc
+/// Manage receiving a transaction by updating the list of transactions seen so far if the transaction is from the selected account.
+/// It also notifies the outputs of the subscribed scripts created or spent by the transaction and the new statistics of the pending transactions
 fn receive_transaction<N: Notifier>(
-    wallet: &MutArc<Wallet>,
+    data: (
+        &MutArc<Wallet>,
+        &MutArc<UTXOSet>,
+        &MutArc<ScriptSubscriptions>,
+    ),
     transaction: Transaction,
-    utxo_set: &MutArc<UTXOSet>,
     logger: LoggerSender,
     notifier: N,
 ) -> Result<(), ErrorProcess> {
+    let (wallet, utxo_set, script_subscriptions) = data;
     let mut utxo_set = get_reference(utxo_set)?;
 
     if utxo_set.is_transaction_pending(&transaction) {
@@ -164,22 +363,42 @@ fn receive_transaction<N: Notifier>(
         ));
     }
 
+    for event in
+        get_reference(script_subscriptions)?.get_transaction_events(&transaction, &utxo_set, None)
+    {
+        notifier.notify(Notification::ScriptHashEvent(event));
+    }
+
+    let wallet = get_reference(wallet)?;
+    let balances = get_balances(&wallet, &utxo_set);
+
     utxo_set.append_pending_transaction(transaction);
+    notifier.notify(Notification::MempoolInfo(MempoolInfo::from_utxo_set(
+        &utxo_set,
+    )));
+
+    notify_balance_changes(&wallet, &utxo_set, balances, notifier);
     Ok(())
 }
 
-/// Manage receiving a block by updating the block chain and the utxo set
+/// Manage receiving a block by updating the block chain, the utxo set and the balance history.
+/// It notifies the new block with the metadata of its coinbase, the transactions requested to the faucet that are confirmed in the block,
+/// the outputs of the subscribed scripts created or spent by the block, the pending transactions of the wallet the block conflicts with
+/// and the new statistics of the pending transactions.
+/// For the accounts of the wallet it notifies only what changed: the balances, the new transactions of their history and
+/// the new balance checkpoints, alongside the new height of the tip
 ///
 /// ### Error
-///  * `ErrorUI::CannotUnwrapArc`: It will appear when we try to unwrap an Arc
 ///  * `ErrorUI::ErrorWriting`: It will appear when writing to the block chain
 fn receive_block<N: Notifier>(
-    utxo_set: &MutArc<UTXOSet>,
-    wallet: &MutArc<Wallet>,
-    block_chain: &MutArc<BlockChain>,
+    wallet_state: &WalletState,
+    chain: (&MutArc<BlockChain>, &MutArc<ScriptSubscriptions>),
     block: Block,
     notifier: N,
 ) -> Result<(), ErrorProcess> {
+    let (wallet, utxo_set, balance_history, faucet) = wallet_state;
+    let (block_chain, script_subscriptions) = chain;
+
     let mut utxo_set = get_reference(utxo_set)?;
     let wallet = get_reference(wallet)?;
 
@@ -197,12 +416,125 @@ fn receive_block<N: Notifier>(
         }
     }
 
+    for event in get_reference(script_subscriptions)?.get_block_events(&block, &utxo_set) {
+        notifier.notify(Notification::ScriptHashEvent(event));
+    }
+
+    let balances = get_balances(&wallet, &utxo_set);
+
+    for (transaction, conflicting_id) in utxo_set.remove_conflicting_transactions(&block) {
+        if !wallet
+            .get_accounts()
+            .iter()
+            .any(|account| account.verify_transaction_ownership(&transaction))
+        {
+            continue;
+        }
+        if let Ok(transaction_id) = transaction.get_tx_id() {
+            notifier.notify(Notification::TransactionConflicted(
+                transaction_id,
+                conflicting_id,
+            ));
+        }
+    }
+
     utxo_set.update_utxo_with_block(&block);
+    notifier.notify(Notification::MempoolInfo(MempoolInfo::from_utxo_set(
+        &utxo_set,
+    )));
+
+    for account in wallet.get_accounts() {
+        for transaction in block.transactions.iter() {
+            if account.verify_transaction_ownership(transaction) {
+                notifier.notify(Notification::AccountTransactionConfirmed(
+                    account.clone(),
+                    transaction.clone(),
+                ));
+            }
+        }
+    }
+
+    notify_balance_changes(&wallet, &utxo_set, balances, notifier.clone());
+
+    if let Some(faucet) = faucet {
+        for transaction_id in get_reference(faucet)?.confirmed_in_block(&block) {
+            notifier.notify(Notification::FaucetTransactionConfirmed(
+                block.clone(),
+                transaction_id,
+            ));
+        }
+    }
 
-    notifier.notify(Notification::NewBlockAddedToTheBlockchain(block.clone()));
+    notifier.notify(Notification::NewBlockAddedToTheBlockchain(
+        block.clone(),
+        CoinbaseInfo::from_block(&block),
+    ));
 
-    match get_reference(block_chain)?.append_block(block) {
-        Ok(_) | Err(ErrorBlock::TransactionAlreadyInBlock) => Ok(()),
-        _ => Err(ErrorProcess::ErrorWriting),
+    let mut block_chain = get_reference(block_chain)?;
+    match block_chain.append_block(block.clone()) {
+        Ok(_) | Err(ErrorBlock::TransactionAlreadyInBlock) => {}
+        _ => return Err(ErrorProcess::ErrorWriting),
+    }
+
+    let header_hash = match block.header.get_hash256d() {
+        Ok(header_hash) => header_hash,
+        Err(_) => return Ok(()),
+    };
+
+    if let Some(height) = block_chain.get_height_with_hash(&header_hash) {
+        let mut balance_history = get_reference(balance_history)?;
+        balance_history.update_with_utxo_set(height, wallet.get_accounts(), &utxo_set);
+
+        for account in wallet.get_accounts() {
+            if let Some(checkpoint) = balance_history.last_checkpoint(account) {
+                if checkpoint.0 == height {
+                    notifier.notify(Notification::AccountBalanceCheckpoint(
+                        account.clone(),
+                        checkpoint,
+                    ));
+                }
+            }
+        }
+
+        notifier.notify(Notification::TipAdvanced(height, header_hash));
+    }
+
+    Ok(())
+}
+
+/// Returns the available and pending balance of each account of the wallet
+fn get_balances(wallet: &Wallet, utxo_set: &UTXOSet) -> Vec<(Amount, Amount)> {
+    wallet
+        .get_accounts()
+        .iter()
+        .map(|account| {
+            (
+                utxo_set.get_balance(&account.address),
+                utxo_set.get_pending(&account.address),
+            )
+        })
+        .collect()
+}
+
+/// Notifies the balance of the accounts whose balance is different from the one given
+fn notify_balance_changes<N: Notifier>(
+    wallet: &Wallet,
+    utxo_set: &UTXOSet,
+    balances_before: Vec<(Amount, Amount)>,
+    notifier: N,
+) {
+    for (account, balance_before) in wallet.get_accounts().iter().zip(balances_before) {
+        let (balance, pending) = (
+            utxo_set.get_balance(&account.address),
+            utxo_set.get_pending(&account.address),
+        );
+
+        if (balance, pending) != balance_before {
+            notifier.notify(Notification::AccountBalanceChanged(
+                account.clone(),
+                balance,
+                pending,
+            ));
+        }
     }
 }
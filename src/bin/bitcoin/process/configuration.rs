@@ -1,14 +1,20 @@
 use cargosos_bitcoin::configurations::{
     client_config::ClientConfig,
     connection_config::ConnectionConfig,
+    data_dir::DataDir,
     download_config::DownloadConfig,
+    environment::{override_structures, ENVIRONMENT_PREFIX},
     error_configuration::ErrorConfiguration,
+    exchange_rate_config::ExchangeRateConfig,
+    faucet_config::FaucetConfig,
     log_config::LogConfig,
     mode_config::ModeConfig,
-    parsable::{parse_structure, Parsable},
+    parsable::{parse_structure, KeyValueMap, Parsable},
     save_config::SaveConfig,
     server_config::ServerConfig,
+    toml_structure::parse_toml_structure,
     ui_config::UIConfig,
+    validation::{validate_configuration, KeyRule},
 };
 
 use std::io::Read;
@@ -20,6 +26,8 @@ type Configurations = (
     SaveConfig,
     UIConfig,
     ModeConfig,
+    Option<FaucetConfig>,
+    Option<ExchangeRateConfig>,
 );
 
 const CONNECTION_CONFIG: &str = "Connection";
@@ -29,6 +37,20 @@ const SAVE_CONFIG: &str = "Save";
 const UI_CONFIG: &str = "UI";
 const UI_SERVER: &str = "Server";
 const UI_CLIENT: &str = "Client";
+const FAUCET_CONFIG: &str = "Faucet";
+const EXCHANGE_RATE_CONFIG: &str = "ExchangeRate";
+
+const CONFIGURATION_RULES: &[KeyRule] = &[
+    KeyRule::structure(CONNECTION_CONFIG, true, ConnectionConfig::RULES),
+    KeyRule::structure(LOGS_CONFIG, true, LogConfig::RULES),
+    KeyRule::structure(DOWNLOAD_CONFIG, true, DownloadConfig::RULES),
+    KeyRule::structure(SAVE_CONFIG, true, SaveConfig::RULES),
+    KeyRule::structure(UI_CONFIG, true, UIConfig::RULES),
+    KeyRule::structure(UI_SERVER, false, ServerConfig::RULES),
+    KeyRule::structure(UI_CLIENT, false, ClientConfig::RULES),
+    KeyRule::structure(FAUCET_CONFIG, false, FaucetConfig::RULES),
+    KeyRule::structure(EXCHANGE_RATE_CONFIG, false, ExchangeRateConfig::RULES),
+];
 
 /// Represents all the configuration needed to run the program
 #[derive(Debug, Clone)]
@@ -39,6 +61,8 @@ pub struct Configuration {
     pub save_config: SaveConfig,
     pub ui_config: UIConfig,
     pub mode_config: ModeConfig,
+    pub faucet_config: Option<FaucetConfig>,
+    pub exchange_rate_config: Option<ExchangeRateConfig>,
 }
 
 impl Configuration {
@@ -48,13 +72,37 @@ impl Configuration {
     ///  * `ErrorConfiguration::ValueNotFound`: It will appear when the value is not found
     ///  * `ErrorConfiguration::ErrorIncompleteConfiguration`: It will appear when the configuration cannot be accessed
     ///  * `ErrorConfiguration::ErrorCantParseValue`: It will appear when the value cannot be parsed
-    pub fn new<R: Read>(mut stream: R) -> Result<Self, ErrorConfiguration> {
-        let mut value = String::new();
-        if stream.read_to_string(&mut value).is_err() {
-            return Err(ErrorConfiguration::ValueNotFound);
-        }
+    ///  * `ErrorConfiguration::ErrorInvalidConfiguration`: It will appear when the validation finds problems in the configuration
+    pub fn new<R: Read>(stream: R) -> Result<Self, ErrorConfiguration> {
+        let value = read_value(stream)?;
+        Self::from_map(parse_structure(value.clone())?, Some(&value))
+    }
+
+    /// Creates a new configuration from a stream file written in TOML, with a table for each part
+    /// of the configuration
+    ///
+    /// ### Error
+    ///  * `ErrorConfiguration::ValueNotFound`: It will appear when the value is not found
+    ///  * `ErrorConfiguration::ErrorIncompleteConfiguration`: It will appear when the configuration cannot be accessed
+    ///  * `ErrorConfiguration::ErrorCantParseValue`: It will appear when the value cannot be parsed
+    ///  * `ErrorConfiguration::ErrorInvalidFormat`: It will appear when a line is not valid TOML
+    ///  * `ErrorConfiguration::ErrorEncounterFieldMoreThanOnes`: It will appear when a key or a table is defined more than once
+    ///  * `ErrorConfiguration::ErrorInvalidConfiguration`: It will appear when the validation finds problems in the configuration
+    pub fn from_toml<R: Read>(stream: R) -> Result<Self, ErrorConfiguration> {
+        Self::from_map(parse_toml_structure(read_value(stream)?)?, None)
+    }
+
+    /// Creates the configuration from its structures, after overriding them with the environment
+    /// variables and validating them. The text of the file is given to find the lines of the
+    /// problems, if it's in the legacy format
+    fn from_map(mut map: KeyValueMap, text: Option<&str>) -> Result<Self, ErrorConfiguration> {
+        let variables: Vec<(String, String)> = std::env::vars().collect();
+        override_structures(&mut map, ENVIRONMENT_PREFIX, &variables);
 
-        let map = parse_structure(value)?;
+        let diagnostics = validate_configuration(&map, CONFIGURATION_RULES, text);
+        if !diagnostics.is_empty() {
+            return Err(ErrorConfiguration::ErrorInvalidConfiguration(diagnostics));
+        }
 
         let possible_server_config = Option::<ServerConfig>::parse(UI_SERVER, &map)?;
         let possible_client_config = Option::<ClientConfig>::parse(UI_CLIENT, &map)?;
@@ -72,9 +120,43 @@ impl Configuration {
             save_config: SaveConfig::parse(SAVE_CONFIG, &map)?,
             ui_config: UIConfig::parse(UI_CONFIG, &map)?,
             mode_config,
+            faucet_config: Option::<FaucetConfig>::parse(FAUCET_CONFIG, &map)?,
+            exchange_rate_config: Option::<ExchangeRateConfig>::parse(EXCHANGE_RATE_CONFIG, &map)?,
         })
     }
 
+    /// Moves the relative paths of the saved files and the logs into the data directory of the
+    /// network, creating the directories they need. Without a configured data directory, the
+    /// one of the platform is used
+    ///
+    /// ### Error
+    ///  * `ErrorConfiguration::CannotCreateDataDirectory`: It will appear when a directory cannot be created
+    pub fn resolve_data_directory(&mut self) -> Result<(), ErrorConfiguration> {
+        let data_dir = DataDir::new(
+            self.save_config.data_directory.as_deref(),
+            self.connection_config.network,
+        );
+        data_dir.create()?;
+
+        self.log_config.filepath_log = data_dir.resolve(&self.log_config.filepath_log)?;
+
+        let save_config = &mut self.save_config;
+        for path in [
+            &mut save_config.read_block_chain,
+            &mut save_config.write_block_chain,
+            &mut save_config.read_wallet,
+            &mut save_config.write_wallet,
+            &mut save_config.wallet_directory,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            *path = data_dir.resolve(path)?;
+        }
+
+        Ok(())
+    }
+
     /// Separates the configuration into its parts to handle them separately
     pub fn separate(self) -> Configurations {
         (
@@ -84,6 +166,20 @@ impl Configuration {
             self.save_config,
             self.ui_config,
             self.mode_config,
+            self.faucet_config,
+            self.exchange_rate_config,
         )
     }
 }
+
+/// Reads all the content of the stream
+///
+/// ### Error
+///  * `ErrorConfiguration::ValueNotFound`: It will appear when the stream cannot be read
+fn read_value<R: Read>(mut stream: R) -> Result<String, ErrorConfiguration> {
+    let mut value = String::new();
+    if stream.read_to_string(&mut value).is_err() {
+        return Err(ErrorConfiguration::ValueNotFound);
+    }
+    Ok(value)
+}
@@ -6,24 +6,27 @@ use super::{
 use crate::error_execution::ErrorExecution;
 
 use cargosos_bitcoin::{
-    block_structure::{block_chain::BlockChain, utxo_set::UTXOSet},
+    block_structure::{
+        block_chain::BlockChain, download_cursor::DownloadCursor, hash::HashType, utxo_set::UTXOSet,
+    },
     concurrency::{listener::Listener, stop::Stop},
     configurations::{
         connection_config::ConnectionConfig, download_config::DownloadConfig,
         mode_config::ModeConfig, server_config::ServerConfig,
     },
-    connections::error_connection::ErrorConnection,
+    connections::{error_connection::ErrorConnection, onion_address::OnionAddress},
     logs::logger_sender::LoggerSender,
     node_structure::{
         broadcasting::Broadcasting,
         connection_event::ConnectionEvent,
-        connection_id::ConnectionId,
         connection_type::ConnectionType,
         error_node::ErrorNode,
         message_response::MessageResponse,
-        process_connection::{ProcessConnection, SenderPotential},
+        network_time::NetworkTime,
+        process_connection::{ProcessConnection, ReceiverConfirm, SenderConfirm, SenderPotential},
     },
     notifications::notifier::Notifier,
+    wallet_structure::wallet::Wallet,
 };
 
 use std::{
@@ -33,6 +36,16 @@ use std::{
     time::Duration,
 };
 
+type ConnectionData = (
+    MutArc<Broadcasting<TcpStream>>,
+    MutArc<BlockChain>,
+    MutArc<UTXOSet>,
+    MutArc<DownloadCursor>,
+    MutArc<Wallet>,
+    MutArc<NetworkTime>,
+    MutArc<Option<HashType>>,
+);
+
 /// Get the peers from the dns seeder
 ///
 /// ### Error
@@ -59,7 +72,8 @@ pub fn get_potential_peers(
 /// Crates the thread to manega the potential connections to establish a connection via a handshake
 pub fn create_process_connection<N: Notifier + Send + 'static>(
     connection_config: ConnectionConfig,
-    sender_confirm_connection: Sender<(TcpStream, ConnectionId)>,
+    sender_confirm_connection: SenderConfirm,
+    network_time: MutArc<NetworkTime>,
     notifier: N,
     logger: LoggerSender,
 ) -> (JoinHandle<Result<(), ErrorNode>>, SenderPotential) {
@@ -70,6 +84,7 @@ pub fn create_process_connection<N: Notifier + Send + 'static>(
         connection_config,
         sender_confirm_connection,
         receiver_potential_connections,
+        network_time.as_mutex(),
         notifier,
         logger,
     );
@@ -81,13 +96,9 @@ pub fn create_process_connection<N: Notifier + Send + 'static>(
 
 /// Creates a thread to manage the confirmed connections and update the block chain if the connection is a peer
 pub fn update_from_connection<N: Notifier + Send + 'static>(
-    receiver_confirm_connection: Receiver<(TcpStream, ConnectionId)>,
+    receiver_confirm_connection: ReceiverConfirm,
     sender_response: Sender<MessageResponse>,
-    data: (
-        MutArc<Broadcasting<TcpStream>>,
-        MutArc<BlockChain>,
-        MutArc<UTXOSet>,
-    ),
+    data: ConnectionData,
     config: (ConnectionConfig, DownloadConfig),
     notifier: N,
     logger: LoggerSender,
@@ -95,17 +106,42 @@ pub fn update_from_connection<N: Notifier + Send + 'static>(
     let broadcasting = data.0;
     let block_chain = data.1;
     let utxo_set = data.2;
+    let download_cursor = data.3;
+    let wallet = data.4;
+    let network_time = data.5;
+    let last_tip = data.6;
 
-    let magic_numbers = config.0.magic_numbers;
+    let magic_numbers = config.0.network.magic_numbers();
 
     thread::spawn(move || {
-        for (stream, connection_id) in receiver_confirm_connection {
+        for (stream, connection_id, user_agent) in receiver_confirm_connection {
+            match get_reference(&broadcasting) {
+                Ok(broadcasting_reference) => {
+                    if broadcasting_reference.is_banned(&connection_id.address.ip()) {
+                        let _ = logger.log_connection(format!(
+                            "Rejecting the connection with {connection_id}, it's banned"
+                        ));
+                        continue;
+                    }
+                }
+                Err(error) => {
+                    let _ = logger.log_connection(format!("Error: {:?}", error));
+                    continue;
+                }
+            }
+
             let (stream, connection_id) = match connection_id.connection_type {
                 ConnectionType::Peer => {
                     match download::update_block_chain_with_peer(
                         (stream, connection_id),
-                        block_chain.clone(),
-                        utxo_set.clone(),
+                        (
+                            block_chain.clone(),
+                            utxo_set.clone(),
+                            download_cursor.clone(),
+                            wallet.clone(),
+                            network_time.clone(),
+                            last_tip.clone(),
+                        ),
                         config.clone(),
                         notifier.clone(),
                         logger.clone(),
@@ -141,7 +177,7 @@ pub fn update_from_connection<N: Notifier + Send + 'static>(
 
             broadcasting::add_peer_to_broadcasting(
                 &mut broadcasting_reference,
-                (stream, connection_id),
+                (stream, connection_id, user_agent),
                 sender_response.clone(),
                 block_chain.clone(),
                 magic_numbers,
@@ -155,6 +191,7 @@ pub fn update_from_connection<N: Notifier + Send + 'static>(
 /// Establish the connection with the peers and the clients
 pub fn establish_connection_to_peers(
     mode_config: ModeConfig,
+    onion_peers: Vec<OnionAddress>,
     sender_potential_connections: Sender<ConnectionEvent>,
     logger: LoggerSender,
 ) -> Result<(), ErrorExecution> {
@@ -177,6 +214,15 @@ pub fn establish_connection_to_peers(
         }
     }
 
+    for onion_peer in onion_peers {
+        if sender_potential_connections
+            .send(ConnectionEvent::PotentialOnionPeer(onion_peer))
+            .is_err()
+        {
+            let _ = logger.log_connection("Could not send potential connection".to_string());
+        }
+    }
+
     Ok(())
 }
 
@@ -187,7 +233,7 @@ pub fn establish_connection_with_clients(
     logger: LoggerSender,
 ) -> Option<JoinHandle<()>> {
     let mut listener = match TcpListener::bind(SocketAddr::new(
-        IpAddr::V4(server_config.address),
+        server_config.address,
         server_config.own_port,
     )) {
         Ok(listener) => listener,
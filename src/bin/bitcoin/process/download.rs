@@ -1,28 +1,72 @@
 use super::{
     error_process::ErrorProcess,
     reference::{get_reference, MutArc},
+    supervisor,
 };
 
 use cargosos_bitcoin::{
-    block_structure::{block::Block, block_chain::BlockChain, hash::HashType, utxo_set::UTXOSet},
+    block_structure::{
+        block::Block,
+        block_chain::BlockChain,
+        download_cursor::DownloadCursor,
+        hash::HashType,
+        soft_fork::{self, SoftForkHeights},
+        utxo_set::UTXOSet,
+    },
     configurations::{connection_config::ConnectionConfig, download_config::DownloadConfig},
     connections::ibd_methods::IBDMethod,
     logs::logger_sender::LoggerSender,
     node_structure::{
-        block_download::BlockDownload, connection_id::ConnectionId, error_node::ErrorNode,
-        initial_headers_download::InitialHeaderDownload,
+        block_download::BlockDownload, compact_filter_client::CompactFilterClient,
+        connection_id::ConnectionId, error_node::ErrorNode,
+        initial_headers_download::InitialHeaderDownload, light_client::LightClient,
+        network_time::NetworkTime,
     },
     notifications::{notification::Notification, notifier::Notifier},
+    wallet_structure::wallet::Wallet,
 };
 
 use std::{
     fmt::Debug,
     io::{Read, Write},
+    sync::mpsc::{channel, Receiver, Sender},
     thread::{self, JoinHandle},
 };
 
+/// The amount of blocks asked to the peer at once, being added to the block chain before asking for the next ones
+const DOWNLOAD_CHUNK_SIZE: usize = 500;
+
+/// It's the state updated by the download, with the blocks already downloaded to skip them and the
+/// last known tip to locate the first headers
+pub type DownloadData = (
+    MutArc<BlockChain>,
+    MutArc<UTXOSet>,
+    MutArc<DownloadCursor>,
+    MutArc<Wallet>,
+    MutArc<NetworkTime>,
+    MutArc<Option<HashType>>,
+);
+
+/// It's a chunk of downloaded blocks, with the hashes of the blocks of the chunk that had nothing
+/// to download because their filter didn't match the wallet
+type DownloadedChunk = (Vec<HashType>, Vec<Block>);
+
+/// It's the way the blocks are downloaded after the headers
+enum BlockSource {
+    /// The blocks are downloaded with all their transactions
+    Full(BlockDownload),
+
+    /// Only the transactions that match the filter of the given elements are downloaded
+    Filtered(LightClient, Vec<Vec<u8>>),
+
+    /// Only the blocks whose compact filter matches the given scripts are downloaded
+    CompactFilters(CompactFilterClient, BlockDownload, Vec<Vec<u8>>),
+}
+
 /// Given the peers connection, updates the blockchain with the new blocks of the respected peers.
-/// The approch is to get the headers first and then the blocks.
+/// The approch is to get the headers first and then the blocks. The blocks already downloaded
+/// are skipped, and the rest are downloaded in chunks that are added to the block chain as they
+/// arrive, so an interrupted download keeps the chunks that were completed
 ///
 /// ### Error
 ///  * `ErrorMessage::InSerialization`: It will appear when the serialization of the message fails or the SHA(SHA(header)) fails
@@ -32,54 +76,57 @@ use std::{
 ///  * `ErrorProcess::FailThread`: It will appear when the thread fails
 fn headers_first<N: Notifier + 'static, RW: Read + Write + Send + Debug + 'static>(
     connection: (RW, ConnectionId),
-    block_chain: &mut BlockChain,
-    utxo_set: &mut UTXOSet,
-    connection_config: ConnectionConfig,
-    download_config: DownloadConfig,
+    data: &DownloadData,
+    config: (ConnectionConfig, DownloadConfig),
+    block_source: BlockSource,
     notifier: N,
     logger: LoggerSender,
 ) -> Result<(RW, ConnectionId), ErrorProcess> {
+    let (block_chain, utxo_set, download_cursor, _, network_time, last_tip) = data;
+    let (connection_config, download_config) = config;
+
     let header_download = InitialHeaderDownload::new(
         connection_config.p2p_protocol_version,
-        connection_config.magic_numbers,
+        connection_config.network.magic_numbers(),
         logger.clone(),
     );
 
-    let block_download = BlockDownload::new(connection_config.magic_numbers, logger.clone());
-
     let _ = logger.log_connection("Getting initial download headers first".to_string());
 
     let (mut peer_stream, id) = connection;
 
     let _ = logger.log_connection(format!("Connecting to peer: {}", id));
 
-    get_peer_header(
-        &mut peer_stream,
-        &header_download,
-        block_chain,
-        notifier.clone(),
-        &logger,
-    )?;
-
-    let mut list_of_blocks: Vec<Block> = Vec::new();
-    for block in block_chain.get_blocks_after_timestamp(download_config.timestamp) {
-        if block.transactions.len() as u64 == 0 {
-            list_of_blocks.push(block);
-        }
-    }
-
+    let list_of_blocks = {
+        let mut block_chain = get_reference(block_chain)?;
+        get_peer_header(
+            &mut peer_stream,
+            &header_download,
+            &mut block_chain,
+            (network_time, last_tip),
+            notifier.clone(),
+            &logger,
+        )?;
+
+        let download_cursor = get_reference(download_cursor)?;
+        get_blocks_to_download(&block_chain, &download_cursor, download_config.timestamp)
+    };
+
+    let total_blocks = list_of_blocks.len() as u32;
+    let (sender_chunk, receiver_chunk) = channel::<DownloadedChunk>();
     let peer_download_handle = get_blocks(
         peer_stream,
-        block_download,
+        block_source,
         list_of_blocks,
+        sender_chunk,
         notifier.clone(),
         logger.clone(),
     );
 
     let stream = updating_block_chain(
-        block_chain,
-        utxo_set,
-        peer_download_handle,
+        (block_chain, utxo_set, download_cursor),
+        (receiver_chunk, peer_download_handle, total_blocks),
+        connection_config.network.soft_fork_heights(),
         notifier,
         logger.clone(),
     )?;
@@ -87,31 +134,77 @@ fn headers_first<N: Notifier + 'static, RW: Read + Write + Send + Debug + 'stati
     Ok((stream, id))
 }
 
-/// It updates the blockchain with a specific peer headers until it reach the last header
+/// Returns the height and hash of the blocks after the timestamp whose body is missing, ordered by
+/// height. The blocks that were already downloaded are not included
+fn get_blocks_to_download(
+    block_chain: &BlockChain,
+    download_cursor: &DownloadCursor,
+    timestamp: u32,
+) -> Vec<(u32, HashType)> {
+    let mut list_of_blocks: Vec<(u32, HashType)> = Vec::new();
+    for block in block_chain.get_blocks_after_timestamp(timestamp) {
+        if block.transactions.len() as u64 != 0 {
+            continue;
+        }
+
+        let header_hash = match block.header.get_hash256d() {
+            Ok(header_hash) => header_hash,
+            Err(_) => continue,
+        };
+
+        if download_cursor.is_downloaded(&header_hash) {
+            continue;
+        }
+
+        if let Some(height) = block_chain.get_height_with_hash(&header_hash) {
+            list_of_blocks.push((height as u32, header_hash));
+        }
+    }
+    list_of_blocks.sort_by_key(|(height, _)| *height);
+
+    list_of_blocks
+}
+
+/// It updates the blockchain with a specific peer headers until it reach the last header.
+/// The first request is located from the last known tip if it was not used by another peer
 ///
 /// ### Error
 ///  * `ErrorMessage::InSerialization`: It will appear when the serialization of the message fails or the SHA(SHA(header)) fails
 ///  * `ErrorNode::NodeNotResponding`: It will appear when no message is received from the node
-///  * `ErrorNode::WhileValidating`: It will appear when a given header does not pass the proof of work to be added to the blockchain
+///  * `ErrorNode::WhileValidating`: It will appear when a given header does not pass the proof of work or its time is too far in the future
 fn get_peer_header<N: Notifier, RW: Read + Write>(
     peer_stream: &mut RW,
     header_download: &InitialHeaderDownload,
     block_chain: &mut BlockChain,
+    sync_state: (&MutArc<NetworkTime>, &MutArc<Option<HashType>>),
     notifier: N,
     logger: &LoggerSender,
 ) -> Result<(), ErrorProcess> {
+    let (network_time, last_tip) = sync_state;
+    let mut tip = get_reference(last_tip)?.take();
+
     loop {
-        let header_count: u32 = match header_download.get_headers(peer_stream, block_chain) {
+        let adjusted_time = get_reference(network_time)?.get_adjusted_time();
+
+        let headers = match tip.take() {
+            Some(tip) => {
+                header_download.get_headers_from_tip(peer_stream, block_chain, &tip, adjusted_time)
+            }
+            None => header_download.get_headers(peer_stream, block_chain, adjusted_time),
+        };
+
+        let header_count: u32 = match headers {
             Err(ErrorNode::NodeNotResponding(message)) => {
                 let _ = logger.log_connection(format!("Node not responding, send: {}", message));
                 break;
             }
             Ok(count) => count,
             Err(ErrorNode::WhileSerializing(_)) => return Err(ErrorProcess::ErrorWriting),
-            Err(_) => {
-                return Err(ErrorProcess::ErrorFromPeer(
-                    "Proof of work failed".to_string(),
-                ))
+            Err(error) => {
+                return Err(ErrorProcess::ErrorFromPeer(format!(
+                    "Headers not valid: {:?}",
+                    error
+                )))
             }
         };
 
@@ -126,33 +219,76 @@ fn get_peer_header<N: Notifier, RW: Read + Write>(
     Ok(())
 }
 
-/// It gets the blocks from a specific peer in a thread
+/// It gets the blocks from a specific peer in a thread, sending each chunk of blocks as soon as
+/// it's downloaded. It stops at the first chunk that cannot be downloaded
 fn get_blocks<N: Notifier + 'static, RW: Read + Write + Send + 'static>(
     mut peer_stream: RW,
-    block_download: BlockDownload,
-    list_of_blocks: Vec<Block>,
+    mut block_source: BlockSource,
+    list_of_blocks: Vec<(u32, HashType)>,
+    sender_chunk: Sender<DownloadedChunk>,
     notifier: N,
     logger: LoggerSender,
-) -> JoinHandle<(Vec<Block>, RW)> {
+) -> JoinHandle<RW> {
     thread::spawn(move || {
-        let mut headers: Vec<HashType> = Vec::new();
-
-        for block in list_of_blocks {
-            let header_hash = match block.header.get_hash256d() {
-                Ok(header_hash) => header_hash,
-                Err(_) => continue,
-            };
+        if let BlockSource::Full(block_download) = block_source {
+            block_source =
+                BlockSource::Full(block_download.with_blocks_total(list_of_blocks.len() as u32));
+        }
 
-            headers.push(header_hash);
+        if let BlockSource::Filtered(light_client, elements) = &block_source {
+            if let Err(error) = light_client.load_filter(&mut peer_stream, elements) {
+                let _ =
+                    logger.log_connection(format!("Cannot load the filter, we get {:?}", error));
+                return peer_stream;
+            }
         }
 
-        match block_download.get_data(&mut peer_stream, headers, notifier) {
-            Ok(blocks) => (blocks, peer_stream),
-            Err(error) => {
-                let _ = logger.log_connection(format!("Cannot get block, we get {:?}", error));
-                (vec![], peer_stream)
+        for chunk in list_of_blocks.chunks(DOWNLOAD_CHUNK_SIZE) {
+            let headers: Vec<HashType> =
+                chunk.iter().map(|(_, header_hash)| *header_hash).collect();
+
+            let downloaded_chunk = match &mut block_source {
+                BlockSource::Full(block_download) => block_download
+                    .get_data(&mut peer_stream, headers, notifier.clone())
+                    .map(|blocks| (vec![], blocks)),
+                BlockSource::Filtered(light_client, _) => light_client
+                    .get_data(&mut peer_stream, headers, notifier.clone())
+                    .map(|blocks| (vec![], blocks)),
+                BlockSource::CompactFilters(compact_filter_client, block_download, scripts) => {
+                    compact_filter_client
+                        .get_matching_blocks(
+                            &mut peer_stream,
+                            chunk.to_vec(),
+                            scripts,
+                            notifier.clone(),
+                        )
+                        .and_then(|matched_blocks| {
+                            let unmatched_blocks = headers
+                                .into_iter()
+                                .filter(|header_hash| !matched_blocks.contains(header_hash))
+                                .collect();
+
+                            block_download
+                                .get_data(&mut peer_stream, matched_blocks, notifier.clone())
+                                .map(|blocks| (unmatched_blocks, blocks))
+                        })
+                }
+            };
+
+            match downloaded_chunk {
+                Ok(downloaded_chunk) => {
+                    if sender_chunk.send(downloaded_chunk).is_err() {
+                        break;
+                    }
+                }
+                Err(error) => {
+                    let _ = logger.log_connection(format!("Cannot get block, we get {:?}", error));
+                    break;
+                }
             }
         }
+
+        peer_stream
     })
 }
 
@@ -169,32 +305,58 @@ pub fn update_block_chain_with_peer<
     RW: Read + Write + Send + Debug + 'static,
 >(
     connection: (RW, ConnectionId),
-    block_chain: MutArc<BlockChain>,
-    utxo_set: MutArc<UTXOSet>,
+    data: DownloadData,
     config: (ConnectionConfig, DownloadConfig),
     notifier: N,
     logger: LoggerSender,
 ) -> Result<(RW, ConnectionId), ErrorProcess> {
-    let connection_config = config.0;
-    let download_config = config.1;
-
-    let mut block_chain_reference = get_reference(&block_chain)?;
-    let mut utxo_set_reference = get_reference(&utxo_set)?;
+    let wallet = &data.3;
+    let network = config.0.network;
+    let magic_numbers = network.magic_numbers();
+    let peer = connection.1.address;
+    let batch_size = config.1.block_batch_size;
+
+    let block_source = match config.0.ibd_method {
+        IBDMethod::HeaderFirst => BlockSource::Full(
+            BlockDownload::new(network, logger.clone())
+                .with_peer(peer)
+                .with_batch_size(batch_size),
+        ),
+        IBDMethod::LightClient => BlockSource::Filtered(
+            LightClient::new(magic_numbers, logger.clone()),
+            get_filter_elements(wallet)?,
+        ),
+        IBDMethod::CompactFilters => BlockSource::CompactFilters(
+            CompactFilterClient::new(magic_numbers, logger.clone()),
+            BlockDownload::new(network, logger.clone())
+                .with_peer(peer)
+                .with_batch_size(batch_size),
+            get_wallet_scripts(wallet)?,
+        ),
+        IBDMethod::BlocksFirst => return Ok(blocks_first::<RW>()),
+    };
 
     let _ = logger.log_connection("Getting block chain".to_string());
 
-    Ok(match connection_config.ibd_method {
-        IBDMethod::HeaderFirst => headers_first(
-            connection,
-            &mut block_chain_reference,
-            &mut utxo_set_reference,
-            connection_config,
-            download_config,
-            notifier,
-            logger,
-        )?,
-        IBDMethod::BlocksFirst => blocks_first::<RW>(),
-    })
+    headers_first(connection, &data, config, block_source, notifier, logger)
+}
+
+/// Returns the public key hashes of the accounts of the wallet, to be loaded in the bloom filter of a light client
+fn get_filter_elements(wallet: &MutArc<Wallet>) -> Result<Vec<Vec<u8>>, ErrorProcess> {
+    Ok(get_reference(wallet)?
+        .get_accounts()
+        .iter()
+        .map(|account| account.address.extract_hashed_pk().to_vec())
+        .collect())
+}
+
+/// Returns the scripts of the accounts of the wallet, to be checked against the compact filters of the blocks
+fn get_wallet_scripts(wallet: &MutArc<Wallet>) -> Result<Vec<Vec<u8>>, ErrorProcess> {
+    Ok(get_reference(wallet)?
+        .get_accounts()
+        .iter()
+        .map(|account| account.address.generate_script_pubkey_p2pkh().into_bytes())
+        .collect())
 }
 
 /// Creates the UTXO set from the given block chain
@@ -207,55 +369,87 @@ pub fn get_utxo_set(block_chain: &BlockChain, logger: LoggerSender) -> UTXOSet {
     utxo_set
 }
 
-/// Updates the blockchain of the thread of a peer
+/// Updates the blockchain of the thread of a peer with each chunk of blocks as it arrives. The block
+/// chain and the UTXO set are only referenced while a chunk is added, marking its blocks as
+/// downloaded, so they can be saved between the chunks. The blocks breaking the rules of the soft forks
+/// active at their height are not added
 ///
 /// ### Error
 ///  * `ErrorBlock::CouldNotUpdate`: It will appear when the block is not in the blockchain.
 ///  * `ErrorExecution::FailThread`: It will appear when the thread fails
 fn updating_block_chain<N: Notifier, RW: Read + Write + Send>(
-    block_chain: &mut BlockChain,
-    utxo_set: &mut UTXOSet,
-    peer_download_handle: JoinHandle<(Vec<Block>, RW)>,
+    data: (
+        &MutArc<BlockChain>,
+        &MutArc<UTXOSet>,
+        &MutArc<DownloadCursor>,
+    ),
+    download: (Receiver<DownloadedChunk>, JoinHandle<RW>, u32),
+    soft_fork_heights: SoftForkHeights,
     notifier: N,
     logger: LoggerSender,
 ) -> Result<RW, ErrorProcess> {
-    let _ = logger.log_connection("Finish downloading, loading to blockchain".to_string());
-    match peer_download_handle.join() {
-        Ok((blocks, peer_stream)) => {
-            let total_blocks = blocks.len() as u32;
-            let _ = logger.log_connection(format!("Loading {total_blocks} blocks to blockchain"));
+    let (block_chain, utxo_set, download_cursor) = data;
+    let (receiver_chunk, peer_download_handle, total_blocks) = download;
 
-            for (i, block) in blocks.iter().enumerate() {
-                if block_chain.update_block(block.clone()).is_err() {
-                    continue;
-                }
+    let mut loaded_blocks: u32 = 0;
+    let mut finished_blocks: u32 = 0;
+    for (mut downloaded, blocks) in receiver_chunk {
+        let mut block_chain = get_reference(block_chain)?;
+        let mut utxo_set = get_reference(utxo_set)?;
 
-                utxo_set.update_utxo_with_block(block);
+        let _ = logger.log_connection(format!("Loading {} blocks to blockchain", blocks.len()));
 
-                if i % 50 == 0 {
-                    let _ = logger.log_connection(format!("Loading [{i}] blocks to blockchain",));
-                    notifier.notify(Notification::ProgressUpdatingBlockchain(
-                        i as u32,
-                        total_blocks,
-                    ));
-                }
-            }
+        for block in blocks.iter() {
+            let header_hash = match block.header.get_hash256d() {
+                Ok(header_hash) => header_hash,
+                Err(_) => continue,
+            };
 
-            if total_blocks == 0 {
-                notifier.notify(Notification::ProgressUpdatingBlockchain(1, 1));
-            } else {
-                notifier.notify(Notification::ProgressUpdatingBlockchain(
-                    total_blocks,
-                    total_blocks,
+            if let Some(Err(error)) = block_chain
+                .get_height_with_hash(&header_hash)
+                .map(|height| soft_fork::check_block(block, height, soft_fork_heights, &utxo_set))
+            {
+                let _ = logger.log_connection(format!(
+                    "Block {block} breaks the rules of the soft forks: {:?}",
+                    error
                 ));
+                continue;
             }
 
-            let _ =
-                logger.log_connection(format!("Loading [{}] blocks to blockchain", blocks.len()));
+            if block_chain.update_block(block.clone()).is_err() {
+                continue;
+            }
 
-            Ok(peer_stream)
+            utxo_set.update_utxo_with_block(block);
+            downloaded.push(header_hash);
+            loaded_blocks += 1;
+        }
+
+        finished_blocks += downloaded.len() as u32;
+        get_reference(download_cursor)?.mark_downloaded(&downloaded);
+        notifier.notify(Notification::ProgressUpdatingBlockchain(
+            finished_blocks,
+            total_blocks,
+        ));
+    }
+
+    if total_blocks == 0 {
+        notifier.notify(Notification::ProgressUpdatingBlockchain(1, 1));
+    }
+
+    let _ = logger.log_connection(format!(
+        "Finish downloading, loaded [{loaded_blocks}] blocks to blockchain"
+    ));
+
+    match peer_download_handle.join() {
+        Ok(peer_stream) => Ok(peer_stream),
+        Err(payload) => {
+            let _ = logger.log_error(format!(
+                "The download thread panicked: {}",
+                supervisor::get_panic_message(payload.as_ref())
+            ));
+            Err(ErrorProcess::FailThread)
         }
-        _ => Err(ErrorProcess::FailThread),
     }
 }
 
@@ -20,6 +20,9 @@ pub enum ErrorProcess {
     /// It will appear when a thread panics and fails
     FailThread,
 
+    /// It will appear when a thread doesn't stop in the time given to it
+    StopTimeout,
+
     /// It will appear when a conextion with a peer fails
     ErrorFromPeer(String),
 
@@ -29,17 +32,26 @@ pub enum ErrorProcess {
     /// It will appear when try to get a value that is already loaded
     AlreadyLoaded,
 
-    /// It will appear when we try to unwrap an Arc
-    CannotUnwrapArc,
+    /// It will appear when a shared value stays locked by another thread longer than the time
+    /// waited for it, being the name of the value
+    LockTimeout(String),
 
-    /// It will appear when we try to get the inner value of a mutex
-    CannotGetInner,
+    /// It will appear when a shared value is taken out while other threads still have it, being
+    /// the name of the value and the amount of threads
+    StillShared(String, usize),
 
     /// It will appear when trying to create a transaction of an amount and fee greater than the balance
     TransactionWithoutSufficientFunds,
 
+    /// It will appear when trying to create a transaction of an amount below the dust threshold, being the threshold in satoshis
+    TransactionWithDustAmount(i64),
+
     /// It will appear when trying to create a transaction and fails to create the signature script for it
     TransactionCreationFail,
+
+    /// It will appear when a saved file has a version of its format newer than the one supported
+    /// by this version of the program, being the path, the version of the file and the supported one
+    FileVersionNotSupported(String, u32, u32),
 }
 
 impl From<ErrorSerialization> for ErrorProcess {
@@ -51,6 +63,21 @@ impl From<ErrorSerialization> for ErrorProcess {
             ErrorSerialization::ErrorWhileReading => ErrorProcess::ErrorReading,
             ErrorSerialization::ConnectionAborted => ErrorProcess::ConnectionAborted,
             ErrorSerialization::InformationNotReady => ErrorProcess::InformationNotReady,
+            ErrorSerialization::DataNotFound(hashes) => {
+                ErrorProcess::ErrorFromPeer(format!("{} elements not found", hashes.len()))
+            }
+            ErrorSerialization::MessageRejected(reject) => ErrorProcess::ErrorFromPeer(format!(
+                "Message {} rejected: {}",
+                reject.message, reject.reason
+            )),
+            ErrorSerialization::PayloadTooLarge(command_name, payload_size) => {
+                ErrorProcess::ErrorFromPeer(format!(
+                    "Payload of {payload_size} bytes in {command_name:?} is too large"
+                ))
+            }
+            ErrorSerialization::FormatVersionNotSupported(path, version, supported) => {
+                ErrorProcess::FileVersionNotSupported(path, version, supported)
+            }
         }
     }
 }
@@ -0,0 +1,53 @@
+use cargosos_bitcoin::{
+    concurrency::stop::Stop,
+    configurations::exchange_rate_config::ExchangeRateConfig,
+    logs::{level::Level, logger_sender::LoggerSender},
+    notifications::{notification::Notification, notifier::Notifier},
+    wallet_structure::exchange_rate::ExchangeRateProvider,
+};
+
+use std::{
+    sync::mpsc::{Receiver, RecvTimeoutError},
+    thread::{self, JoinHandle},
+};
+
+/// Asks the provider configured for the price of a bitcoin every poll interval, notifying each new
+/// exchange rate until it's stopped. Nothing is done if the exchange rate is not configured
+pub fn poll_exchange_rate<N: Notifier + 'static>(
+    exchange_rate_config: Option<ExchangeRateConfig>,
+    receiver_stop: Receiver<Stop>,
+    notifier: N,
+    logger: LoggerSender,
+) -> Option<JoinHandle<()>> {
+    let exchange_rate_config = exchange_rate_config?;
+
+    let provider = match ExchangeRateProvider::new(
+        &exchange_rate_config.url,
+        &exchange_rate_config.currency,
+    ) {
+        Ok(provider) => provider,
+        Err(error) => {
+            let _ = logger.log_data(Level::ERROR, error);
+            return None;
+        }
+    };
+
+    let handle = thread::spawn(move || loop {
+        match provider.request_rate() {
+            Ok(exchange_rate) => {
+                let _ = logger.log_wallet(format!("Exchange rate updated to {exchange_rate}"));
+                notifier.notify(Notification::ExchangeRateUpdated(exchange_rate));
+            }
+            Err(error) => {
+                let _ = logger.log_data(Level::ERROR, error);
+            }
+        }
+
+        match receiver_stop.recv_timeout(exchange_rate_config.poll_interval) {
+            Err(RecvTimeoutError::Timeout) => continue,
+            _ => break,
+        }
+    });
+
+    Some(handle)
+}
@@ -1,16 +1,29 @@
 use super::error_process::ErrorProcess;
 
 use cargosos_bitcoin::{
-    block_structure::block_chain::BlockChain,
+    block_structure::{
+        block_chain::BlockChain,
+        block_store::{get_block_store_path, BlockStore},
+        chain_tip::ChainTip,
+        download_cursor::DownloadCursor,
+        utxo_set::{UTXOSet, UTXOSnapshot},
+    },
     configurations::{save_config::SaveConfig, try_default::TryDefault},
-    logs::logger_sender::LoggerSender,
-    serialization::deserializable_internal_order::DeserializableInternalOrder,
-    wallet_structure::wallet::Wallet,
+    logs::{level::Level, logger_sender::LoggerSender},
+    serialization::{
+        checked_file::{read_checked_file, CheckedRead},
+        deserializable_internal_order::DeserializableInternalOrder,
+        error_serialization::ErrorSerialization,
+        versioned_format::VersionedFormat,
+    },
+    wallet_structure::{
+        balance_history::{BalanceHistory, BalanceHistorySnapshot},
+        wallet::Wallet,
+        wallet_directory::{WalletDirectory, DEFAULT_WALLET_NAME},
+    },
 };
 
 use std::{
-    fs::OpenOptions,
-    io::BufReader,
     marker::Send,
     mem::replace,
     thread::{self, JoinHandle},
@@ -20,31 +33,129 @@ type Handle<T> = Option<JoinHandle<T>>;
 
 const BLOCKCHAIN_FILE: &str = "Blockchain";
 const WALLET_FILE: &str = "Wallet";
+const CHAIN_TIP_EXTENSION: &str = ".tip";
+const UTXO_SET_EXTENSION: &str = ".utxo";
+const DOWNLOAD_CURSOR_EXTENSION: &str = ".cursor";
+const BALANCE_HISTORY_EXTENSION: &str = ".balance";
+const CHAIN_TIP_FILE: &str = "Chain tip";
+const UTXO_SET_FILE: &str = "UTXO set";
+const DOWNLOAD_CURSOR_FILE: &str = "Download cursor";
+const BALANCE_HISTORY_FILE: &str = "Balance history";
+
+/// Returns the path of the file with the tip of the block chain saved in the given path
+pub fn get_chain_tip_path(block_chain_path: &str) -> String {
+    format!("{block_chain_path}{CHAIN_TIP_EXTENSION}")
+}
+
+/// Returns the path of the file with the UTXO set of the block chain saved in the given path
+pub fn get_utxo_set_path(block_chain_path: &str) -> String {
+    format!("{block_chain_path}{UTXO_SET_EXTENSION}")
+}
+
+/// Returns the path of the file with the blocks already downloaded of the block chain saved in the given path
+pub fn get_download_cursor_path(block_chain_path: &str) -> String {
+    format!("{block_chain_path}{DOWNLOAD_CURSOR_EXTENSION}")
+}
+
+/// Returns the path of the file with the balance history of the accounts saved next to the block chain in the given path
+pub fn get_balance_history_path(block_chain_path: &str) -> String {
+    format!("{block_chain_path}{BALANCE_HISTORY_EXTENSION}")
+}
 
 /// Represents the elements to load from files
 pub struct LoadSystem {
+    chain_tip: Option<ChainTip>,
+    download_cursor: Option<DownloadCursor>,
     block_chain: Handle<Result<BlockChain, ErrorProcess>>,
+    block_store_path: Option<String>,
+    block_store: Option<BlockStore>,
+    utxo_set_path: Option<String>,
+    balance_history_path: Option<String>,
     wallet: Handle<Result<Wallet, ErrorProcess>>,
+    wallet_directory: Option<WalletDirectory>,
+    logger: LoggerSender,
 }
 
 impl LoadSystem {
     pub fn new(save_config: SaveConfig, logger: LoggerSender) -> LoadSystem {
+        let wallet_directory = Self::create_wallet_directory(&save_config, logger.clone());
+
+        let wallet_path = match &wallet_directory {
+            Some(wallet_directory) => wallet_directory
+                .get_selected_path()
+                .map(|path| path.to_string_lossy().to_string()),
+            None => save_config.read_wallet,
+        };
+
+        let utxo_set_path = save_config
+            .read_block_chain
+            .as_ref()
+            .map(|path| get_utxo_set_path(path));
+        let balance_history_path = save_config
+            .read_block_chain
+            .as_ref()
+            .map(|path| get_balance_history_path(path));
+        let block_store_path = save_config
+            .write_block_chain
+            .as_ref()
+            .map(|path| get_block_store_path(path));
+
         LoadSystem {
+            chain_tip: Self::load_chain_tip(&save_config.read_block_chain, logger.clone()),
+            download_cursor: Self::load_download_cursor(
+                &save_config.read_block_chain,
+                logger.clone(),
+            ),
             block_chain: Some(Self::load_value(
                 BLOCKCHAIN_FILE.to_string(),
                 save_config.read_block_chain,
                 logger.clone(),
             )),
+            block_store_path,
+            block_store: None,
+            utxo_set_path,
+            balance_history_path,
             wallet: Some(Self::load_value(
                 WALLET_FILE.to_string(),
-                save_config.read_wallet,
-                logger,
+                wallet_path,
+                logger.clone(),
             )),
+            wallet_directory,
+            logger,
         }
     }
 
+    /// Get the directory of the wallets if it's configured, if already given it will return None
+    pub fn get_wallet_directory(&mut self) -> Option<WalletDirectory> {
+        self.wallet_directory.take()
+    }
+
+    /// Get the name of the wallet that is loaded. Without a wallet directory it's the default wallet
+    pub fn get_wallet_name(&self) -> String {
+        match &self.wallet_directory {
+            Some(wallet_directory) => wallet_directory
+                .get_selected_wallet()
+                .unwrap_or(DEFAULT_WALLET_NAME)
+                .to_string(),
+            None => DEFAULT_WALLET_NAME.to_string(),
+        }
+    }
+
+    /// Get the tip of the block chain saved the last time, without waiting for the block chain to load.
+    /// If already given or there is no saved tip it will return None
+    pub fn get_chain_tip(&mut self) -> Option<ChainTip> {
+        self.chain_tip.take()
+    }
+
+    /// Get the blocks already downloaded the last time, so an interrupted download doesn't ask for them again.
+    /// If already given or there is no saved cursor it will return None
+    pub fn get_download_cursor(&mut self) -> Option<DownloadCursor> {
+        self.download_cursor.take()
+    }
+
     /// Get the block chain from a file, if already loaded it will return the value immediately.
-    /// In the case of the file not existing, it will return the default value.
+    /// In the case of the file not existing, it will return the default value. The blocks kept
+    /// in the block store since the block chain was saved are added to it
     ///
     /// ### Error
     ///  * `ErrorProcess:FailThread`: It will appear when a thread panics and fails
@@ -54,10 +165,13 @@ impl LoadSystem {
         let block_chain_handle = replace(&mut self.block_chain, None);
 
         if let Some(block_chain_handle) = block_chain_handle {
-            return match block_chain_handle.join() {
-                Ok(block_chain) => block_chain,
-                _ => Err(ErrorProcess::FailThread),
+            let mut block_chain = match block_chain_handle.join() {
+                Ok(block_chain) => block_chain?,
+                _ => return Err(ErrorProcess::FailThread),
             };
+
+            self.block_store = self.open_block_store(&mut block_chain);
+            return Ok(block_chain);
         }
 
         Err(ErrorProcess::AlreadyLoaded)
@@ -83,30 +197,151 @@ impl LoadSystem {
         Err(ErrorProcess::AlreadyLoaded)
     }
 
-    /// Creates a thread to load a deserializable from a file, if the file does not exist or fail to read it will return the default value.
+    /// Get the block store opened with the block chain, if already given or there is no path to
+    /// save the block chain it will return None
+    pub fn get_block_store(&mut self) -> Option<BlockStore> {
+        self.block_store.take()
+    }
+
+    /// Get the UTXO set saved next to the block chain, if it was saved with the same tip as the
+    /// given block chain. Otherwise it will return None, and the UTXO set has to be built again
+    pub fn get_utxo_set(&self, block_chain: &BlockChain) -> Option<UTXOSet> {
+        let path = self.utxo_set_path.as_ref()?;
+        let (tip, _) = block_chain.get_tip()?;
+
+        let saved_utxo_set: UTXOSnapshot = match read_file(UTXO_SET_FILE, path, &self.logger) {
+            Ok(Some(saved_utxo_set)) => saved_utxo_set,
+            Ok(None) => return None,
+            Err(error) => {
+                let _ = self.logger.log_data(Level::ERROR, error);
+                return None;
+            }
+        };
+
+        if saved_utxo_set.tip != tip {
+            let _ = self
+                .logger
+                .log_file("The UTXO set file is not of the tip of the Blockchain".to_string());
+            return None;
+        }
+
+        Some(saved_utxo_set.utxo_set)
+    }
+
+    /// Get the balance history saved next to the block chain, if it was saved with the same tip as
+    /// the given block chain. Otherwise it will return None, and the balance history has to be built again
+    pub fn get_balance_history(&self, block_chain: &BlockChain) -> Option<BalanceHistory> {
+        let path = self.balance_history_path.as_ref()?;
+        let (tip, _) = block_chain.get_tip()?;
+
+        let saved_balance_history: BalanceHistorySnapshot =
+            match read_file(BALANCE_HISTORY_FILE, path, &self.logger) {
+                Ok(Some(saved_balance_history)) => saved_balance_history,
+                Ok(None) => return None,
+                Err(error) => {
+                    let _ = self.logger.log_data(Level::ERROR, error);
+                    return None;
+                }
+            };
+
+        if saved_balance_history.tip != tip {
+            let _ = self.logger.log_file(
+                "The balance history file is not of the tip of the Blockchain".to_string(),
+            );
+            return None;
+        }
+
+        Some(saved_balance_history.balance_history)
+    }
+
+    /// Opens the block store next to the saved block chain, adding its blocks to the block chain.
+    /// If it cannot be opened, the block chain is only saved at shutdown
+    fn open_block_store(&self, block_chain: &mut BlockChain) -> Option<BlockStore> {
+        let path = self.block_store_path.as_ref()?;
+
+        let _ = self.logger.log_file("Reading the Block store".to_string());
+        match BlockStore::open(path, block_chain) {
+            Ok(block_store) => Some(block_store),
+            Err(error) => {
+                let _ = self.logger.log_data(Level::ERROR, error);
+                None
+            }
+        }
+    }
+
+    /// Creates the directory of the wallets if it's configured. If it cannot be created, the wallet files are used
+    fn create_wallet_directory(
+        save_config: &SaveConfig,
+        logger: LoggerSender,
+    ) -> Option<WalletDirectory> {
+        let directory = save_config.wallet_directory.as_ref()?;
+
+        match WalletDirectory::new(directory, save_config.selected_wallet.clone()) {
+            Ok(wallet_directory) => Some(wallet_directory),
+            Err(error) => {
+                let _ = logger.log_data(Level::ERROR, error);
+                None
+            }
+        }
+    }
+
+    /// Reads the tip saved next to the block chain, it's small enough to not need a thread
+    fn load_chain_tip(block_chain_path: &Option<String>, logger: LoggerSender) -> Option<ChainTip> {
+        let path = get_chain_tip_path(block_chain_path.as_ref()?);
+
+        match read_file(CHAIN_TIP_FILE, &path, &logger) {
+            Ok(chain_tip) => chain_tip,
+            Err(error) => {
+                let _ = logger.log_data(Level::ERROR, error);
+                None
+            }
+        }
+    }
+
+    /// Reads the blocks already downloaded saved next to the block chain. It's read without a thread
+    /// because it's needed before the first peer starts downloading
+    fn load_download_cursor(
+        block_chain_path: &Option<String>,
+        logger: LoggerSender,
+    ) -> Option<DownloadCursor> {
+        let path = get_download_cursor_path(block_chain_path.as_ref()?);
+
+        match read_file(DOWNLOAD_CURSOR_FILE, &path, &logger) {
+            Ok(download_cursor) => download_cursor,
+            Err(error) => {
+                let _ = logger.log_data(Level::ERROR, error);
+                None
+            }
+        }
+    }
+
+    /// Creates a thread to load a deserializable from a file, if the file does not exist it will return the default value.
+    /// If the file is corrupted, the value is recovered from the backup of the previous save
     ///
     /// ### Error
     ///  * `ErrorProcess:CannotCreateDefault`: It will appear when can't create the default value
-    fn load_value<V: TryDefault + DeserializableInternalOrder + Send + 'static>(
+    ///  * `ErrorProcess:ErrorReading`: It will appear when the file is corrupted and there is no valid backup
+    ///  * `ErrorProcess:FileVersionNotSupported`: It will appear when the file was saved by a newer version of the program
+    fn load_value<
+        V: TryDefault + DeserializableInternalOrder + VersionedFormat + Send + 'static,
+    >(
         name: String,
         path: Option<String>,
         logger: LoggerSender,
     ) -> JoinHandle<Result<V, ErrorProcess>> {
         thread::spawn(move || {
             if let Some(path) = path {
-                if let Ok(file) = OpenOptions::new().read(true).open(path) {
-                    let mut file = BufReader::new(file);
-
-                    let _ = logger.log_file(format!("Reading the {name} from file"));
-
-                    let value = V::io_deserialize(&mut file)?;
-
-                    let _ = logger.log_file(format!("{name} loaded from file"));
-
-                    return Ok(value);
+                match read_file(&name, &path, &logger) {
+                    Ok(Some(value)) => {
+                        let _ = logger.log_file(format!("{name} loaded from file"));
+                        return Ok(value);
+                    }
+                    Ok(None) => {}
+                    Err(error) => {
+                        let _ = logger.log_data(Level::ERROR, &error);
+                        return Err(error.into());
+                    }
                 }
-
-                let _ = logger.log_file(format!("Could not open {name} file"));
             }
 
             match V::try_default() {
@@ -119,3 +354,35 @@ impl LoadSystem {
         })
     }
 }
+
+/// Reads the value saved in the given path, it returns None if it was never saved. If the file is
+/// corrupted or was not completely replaced, the value of the previous save is used
+///
+/// ### Error
+///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when the file is corrupted and there is no valid backup
+///  * `ErrorSerialization::FormatVersionNotSupported`: It will appear when the file was saved by a newer version of the program
+fn read_file<V: DeserializableInternalOrder + VersionedFormat>(
+    name: &str,
+    path: &str,
+    logger: &LoggerSender,
+) -> Result<Option<V>, ErrorSerialization> {
+    let _ = logger.log_file(format!("Reading the {name} from file"));
+
+    match read_checked_file(path)? {
+        CheckedRead::Value(value) => Ok(Some(value)),
+        CheckedRead::Recovered(value, error) => {
+            if let Some(error) = error {
+                let _ = logger.log_data(Level::ERROR, error);
+            }
+            let _ = logger.log_warning(
+                Level::FILE,
+                format!("The {name} was recovered from the backup of the previous save"),
+            );
+            Ok(Some(value))
+        }
+        CheckedRead::Missing => {
+            let _ = logger.log_file(format!("Could not open {name} file"));
+            Ok(None)
+        }
+    }
+}
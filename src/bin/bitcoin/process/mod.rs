@@ -3,12 +3,17 @@ pub mod backend;
 pub mod broadcasting;
 pub mod connection;
 pub mod download;
+pub mod exchange_rate;
+pub mod rest_server;
 pub mod transaction;
 
 pub mod configuration;
 pub mod load_system;
 pub mod save_system;
+pub mod shutdown;
+pub mod supervisor;
 
 pub mod reference;
+pub mod state;
 
 pub mod error_process;
@@ -1,31 +1,23 @@
-use super::error_process::ErrorProcess;
+use super::{error_process::ErrorProcess, state::SharedState};
 
-use std::sync::{Arc, Mutex, MutexGuard};
+use std::{sync::MutexGuard, time::Duration};
 
-pub type MutArc<T> = Arc<Mutex<T>>;
+pub type MutArc<T> = SharedState<T>;
 
-/// Get the value of a mutable reference given by Arc<Mutex<T>>
+/// The time waited for another thread to release a shared value where an answer is needed in
+/// time, as in the requests to the REST server
+pub const LOCK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Get the value of a shared reference, once the other threads don't have it anymore
 ///
 /// ### Error
-///  * `ErrorUI::CannotGetInner`: It will appear when we try to get the inner value of a mutex
-///  * `ErrorUI::CannotUnwrapArc`: It will appear when we try to unwrap an Arc
+///  * `ErrorProcess::StillShared`: It will appear when other threads still have the value
 pub fn get_inner<T>(reference: MutArc<T>) -> Result<T, ErrorProcess> {
-    match Arc::try_unwrap(reference) {
-        Ok(reference_unwrap) => match reference_unwrap.into_inner() {
-            Ok(reference) => Ok(reference),
-            Err(_) => Err(ErrorProcess::CannotGetInner),
-        },
-        Err(_) => Err(ErrorProcess::CannotUnwrapArc),
-    }
+    reference.into_inner()
 }
 
-/// Get a mutable guard to use the value inside the Arc<Mutex<T>>
-///
-/// ### Error
-///  * `ErrorUI::CannotUnwrapArc`: It will appear when we try to unwrap an Arc
+/// Get a mutable guard to use the value of the shared reference, once the other threads release it.
+/// A value left locked by a thread that panicked is used as it was left
 pub fn get_reference<T>(reference: &MutArc<T>) -> Result<MutexGuard<'_, T>, ErrorProcess> {
-    match reference.lock() {
-        Ok(reference) => Ok(reference),
-        Err(_) => Err(ErrorProcess::CannotUnwrapArc),
-    }
+    Ok(reference.lock())
 }
@@ -0,0 +1,354 @@
+use super::reference::{MutArc, LOCK_TIMEOUT};
+
+use crate::ui::{error_ui::ErrorUI, from_hexa};
+
+use cargosos_bitcoin::{
+    block_structure::{
+        block::Block, block_chain::BlockChain, hash, mempool_info::MempoolInfo,
+        transaction::Transaction, utxo_set::UTXOSet,
+    },
+    logs::logger_sender::LoggerSender,
+    notifications::notification_json::quote,
+    wallet_structure::address::Address,
+};
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+const GET_METHOD: &str = "GET";
+
+/// The time a client has to send its request and to receive the answer
+const REST_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_REQUEST_SIZE: u64 = 8 * 1024;
+
+const BLOCK_PATH: &str = "block";
+const TRANSACTION_PATH: &str = "tx";
+const ADDRESS_PATH: &str = "address";
+const UTXOS_PATH: &str = "utxos";
+const MEMPOOL_PATH: &str = "mempool";
+
+const STATUS_OK: &str = "200 OK";
+const STATUS_BAD_REQUEST: &str = "400 Bad Request";
+const STATUS_NOT_FOUND: &str = "404 Not Found";
+const STATUS_METHOD_NOT_ALLOWED: &str = "405 Method Not Allowed";
+const STATUS_INTERNAL_ERROR: &str = "500 Internal Server Error";
+
+/// It's the answer to a request, given by the status and the JSON body
+type Response = (&'static str, String);
+
+/// It's a read-only HTTP interface to query the local blockchain as JSON, for the dashboards and
+/// for debugging. It answers to:
+///  * `GET /block/<hash>`: the header of the block and the ids of its transactions
+///  * `GET /tx/<txid>`: the inputs and outputs of a transaction in a downloaded block
+///  * `GET /address/<address>/utxos`: the unspent outputs of the address
+///  * `GET /mempool`: the statistics of the pending transactions, for the metrics of the node
+#[derive(Clone)]
+pub struct RestServer {
+    block_chain: MutArc<BlockChain>,
+    utxo_set: MutArc<UTXOSet>,
+    logger: LoggerSender,
+}
+
+impl RestServer {
+    pub fn new(
+        block_chain: MutArc<BlockChain>,
+        utxo_set: MutArc<UTXOSet>,
+        logger: LoggerSender,
+    ) -> Self {
+        RestServer {
+            block_chain,
+            utxo_set,
+            logger,
+        }
+    }
+
+    /// Creates a thread that answers the requests made to the given local port
+    ///
+    /// ### Error
+    ///  * `ErrorUI::ConnectionAborted`: It will appear when the port cannot be used
+    pub fn listen(&self, rest_port: u16) -> Result<JoinHandle<()>, ErrorUI> {
+        let address = SocketAddr::from((Ipv4Addr::LOCALHOST, rest_port));
+
+        let listener = match TcpListener::bind(address) {
+            Ok(listener) => listener,
+            Err(error) => {
+                let _ = self.logger.log_error(format!(
+                    "Cannot listen for REST requests at {address}: {:?}",
+                    error
+                ));
+                return Err(ErrorUI::ConnectionAborted);
+            }
+        };
+
+        let _ = self
+            .logger
+            .log_connection(format!("Listening for REST requests at {address}"));
+
+        let rest_server = self.clone();
+        Ok(thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => continue,
+                };
+
+                if let Err(error) = rest_server.answer(stream) {
+                    let _ = rest_server
+                        .logger
+                        .log_connection(format!("Cannot answer a REST request: {:?}", error));
+                }
+            }
+        }))
+    }
+
+    /// Reads the request of the client and answers it, closing the connection after it. Reading the
+    /// request and sending the answer each wait up to a timeout, and at most `MAX_REQUEST_SIZE` bytes
+    /// of the request are read
+    ///
+    /// ### Error
+    ///  * `ErrorUI::ErrorReading`: It will appear when the request cannot be read
+    ///  * `ErrorUI::ErrorWriting`: It will appear when the answer cannot be sent to the client
+    fn answer(&self, mut stream: TcpStream) -> Result<(), ErrorUI> {
+        if stream.set_read_timeout(Some(REST_TIMEOUT)).is_err()
+            || stream.set_write_timeout(Some(REST_TIMEOUT)).is_err()
+        {
+            return Err(ErrorUI::ErrorReading(
+                "Cannot set the timeouts of the connection".to_string(),
+            ));
+        }
+
+        let mut request_line = String::new();
+        {
+            let mut reader = BufReader::new((&mut stream).take(MAX_REQUEST_SIZE));
+            if let Err(error) = reader.read_line(&mut request_line) {
+                return Err(ErrorUI::ErrorReading(format!(
+                    "Cannot read the request: {:?}",
+                    error
+                )));
+            }
+
+            for line in reader.lines() {
+                match line {
+                    Ok(line) if line.is_empty() => break,
+                    Ok(_) => continue,
+                    Err(_) => break,
+                }
+            }
+        }
+
+        let (status, body) = match request_line.ends_with('\n') {
+            true => self.route(&request_line),
+            false => error_response(STATUS_BAD_REQUEST, "Incomplete or too large request"),
+        };
+        let _ = self
+            .logger
+            .log_connection(format!("REST request {:?}: {status}", request_line.trim()));
+
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+
+        match stream.write_all(response.as_bytes()) {
+            Ok(_) => Ok(()),
+            Err(error) => Err(ErrorUI::ErrorWriting(format!(
+                "Cannot send the answer: {:?}",
+                error
+            ))),
+        }
+    }
+
+    /// Returns the answer to the request given by its first line, `<method> <path> <version>`
+    fn route(&self, request_line: &str) -> Response {
+        let mut parts = request_line.split_whitespace();
+        let (method, path) = match (parts.next(), parts.next()) {
+            (Some(method), Some(path)) => (method, path),
+            _ => return error_response(STATUS_BAD_REQUEST, "Invalid request"),
+        };
+
+        if method != GET_METHOD {
+            return error_response(STATUS_METHOD_NOT_ALLOWED, "Only GET requests are answered");
+        }
+
+        let segments: Vec<&str> = path
+            .trim_matches('/')
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect();
+
+        match segments.as_slice() {
+            [BLOCK_PATH, block_hash] => self.block(block_hash),
+            [TRANSACTION_PATH, transaction_id] => self.transaction(transaction_id),
+            [ADDRESS_PATH, address, UTXOS_PATH] => self.address_utxos(address),
+            [MEMPOOL_PATH] => self.mempool(),
+            _ => error_response(STATUS_NOT_FOUND, "Unknown path"),
+        }
+    }
+
+    /// Answers with the block of the given hash, in display order
+    fn block(&self, block_hash: &str) -> Response {
+        let block_hash = match from_hexa::hash_in_display_order(block_hash) {
+            Ok(block_hash) => block_hash,
+            Err(_) => return error_response(STATUS_BAD_REQUEST, "Invalid block hash"),
+        };
+
+        let block_chain = match self.block_chain.try_lock_for(LOCK_TIMEOUT) {
+            Ok(block_chain) => block_chain,
+            Err(_) => return error_response(STATUS_INTERNAL_ERROR, "Cannot read the blockchain"),
+        };
+
+        let block = match block_chain.get_block_with_hash(&block_hash) {
+            Some(block) => block,
+            None => return error_response(STATUS_NOT_FOUND, "Block not found"),
+        };
+        let height = block_chain.get_height_with_hash(&block_hash);
+
+        (STATUS_OK, block_json(&block, height))
+    }
+
+    /// Answers with the transaction of the given id, in display order
+    fn transaction(&self, transaction_id: &str) -> Response {
+        let transaction_id = match from_hexa::hash_in_display_order(transaction_id) {
+            Ok(transaction_id) => transaction_id,
+            Err(_) => return error_response(STATUS_BAD_REQUEST, "Invalid transaction id"),
+        };
+
+        let transaction = match self.block_chain.try_lock_for(LOCK_TIMEOUT) {
+            Ok(block_chain) => block_chain.get_transaction_with_id(&transaction_id),
+            Err(_) => return error_response(STATUS_INTERNAL_ERROR, "Cannot read the blockchain"),
+        };
+
+        match transaction {
+            Some(transaction) => (STATUS_OK, transaction_json(&transaction)),
+            None => error_response(STATUS_NOT_FOUND, "Transaction not found"),
+        }
+    }
+
+    /// Answers with the unspent outputs of the given address
+    fn address_utxos(&self, address: &str) -> Response {
+        let address = match Address::new(address) {
+            Ok(address) => address,
+            Err(_) => return error_response(STATUS_BAD_REQUEST, "Invalid address"),
+        };
+
+        let utxos = match self.utxo_set.try_lock_for(LOCK_TIMEOUT) {
+            Ok(utxo_set) => utxo_set.get_utxo_list_with_outpoints(Some(&address)),
+            Err(_) => return error_response(STATUS_INTERNAL_ERROR, "Cannot read the UTXO set"),
+        };
+
+        let utxos: Vec<String> = utxos
+            .iter()
+            .map(|(outpoint, output)| {
+                format!(
+                    "{{\"txid\":{},\"index\":{},\"value\":{}}}",
+                    quote(&hash::to_hex_be(&outpoint.get_transaction_id())),
+                    outpoint.get_index(),
+                    output.value
+                )
+            })
+            .collect();
+
+        (
+            STATUS_OK,
+            format!(
+                "{{\"address\":{},\"utxos\":[{}]}}",
+                quote(&address.to_string()),
+                utxos.join(",")
+            ),
+        )
+    }
+
+    /// Answers with the statistics of the pending transactions
+    fn mempool(&self) -> Response {
+        let mempool_info = match self.utxo_set.try_lock_for(LOCK_TIMEOUT) {
+            Ok(utxo_set) => MempoolInfo::from_utxo_set(&utxo_set),
+            Err(_) => return error_response(STATUS_INTERNAL_ERROR, "Cannot read the UTXO set"),
+        };
+
+        (
+            STATUS_OK,
+            format!(
+                "{{\"size\":{},\"bytes\":{},\"total_fee\":{},\"min_fee_rate\":{}}}",
+                mempool_info.transaction_count,
+                mempool_info.total_size,
+                mempool_info.total_fees,
+                match mempool_info.min_fee_rate {
+                    Some(min_fee_rate) => min_fee_rate.to_string(),
+                    None => "null".to_string(),
+                }
+            ),
+        )
+    }
+}
+
+/// Returns the block as a JSON object. The transactions are only given if the block was downloaded
+fn block_json(block: &Block, height: Option<u64>) -> String {
+    let transactions: Vec<String> = block
+        .transactions
+        .iter()
+        .map(|transaction| quote(&transaction.to_string()))
+        .collect();
+
+    format!(
+        "{{\"hash\":{},\"height\":{},\"previous_block\":{},\"merkle_root\":{},\"time\":{},\"nonce\":{},\"downloaded\":{},\"transactions\":[{}]}}",
+        quote(&block.to_string()),
+        match height {
+            Some(height) => height.to_string(),
+            None => "null".to_string(),
+        },
+        quote(&hash::to_hex_be(&block.header.previous_block_header_hash)),
+        quote(&hash::to_hex_be(&block.header.merkle_root_hash)),
+        block.header.time,
+        block.header.nonce,
+        block.is_complete(),
+        transactions.join(",")
+    )
+}
+
+/// Returns the transaction as a JSON object with its inputs and outputs
+fn transaction_json(transaction: &Transaction) -> String {
+    let inputs: Vec<String> = transaction
+        .tx_in
+        .iter()
+        .map(|input| {
+            format!(
+                "{{\"txid\":{},\"index\":{},\"sequence\":{}}}",
+                quote(&hash::to_hex_be(
+                    &input.previous_output.get_transaction_id()
+                )),
+                input.previous_output.get_index(),
+                input.sequence
+            )
+        })
+        .collect();
+
+    let outputs: Vec<String> = transaction
+        .tx_out
+        .iter()
+        .map(|output| {
+            format!(
+                "{{\"value\":{},\"script_pubkey\":{}}}",
+                output.value,
+                quote(&output.pk_script.to_string())
+            )
+        })
+        .collect();
+
+    format!(
+        "{{\"txid\":{},\"version\":{},\"inputs\":[{}],\"outputs\":[{}],\"locktime\":{}}}",
+        quote(&transaction.to_string()),
+        transaction.version,
+        inputs.join(","),
+        outputs.join(","),
+        transaction.lock_time
+    )
+}
+
+/// Returns the answer with the error as a JSON object
+fn error_response(status: &'static str, message: &str) -> Response {
+    (status, format!("{{\"error\":{}}}", quote(message)))
+}
@@ -1,63 +1,389 @@
-use crate::{error_execution::ErrorExecution, error_initialization::ErrorInitialization};
+use super::{
+    load_system::{
+        get_balance_history_path, get_chain_tip_path, get_download_cursor_path, get_utxo_set_path,
+    },
+    reference::{get_reference, MutArc},
+};
+
+use crate::error_execution::ErrorExecution;
 
 use cargosos_bitcoin::{
-    block_structure::block_chain::BlockChain, configurations::save_config::SaveConfig,
-    logs::logger_sender::LoggerSender,
-    serialization::serializable_internal_order::SerializableInternalOrder,
-    wallet_structure::wallet::Wallet,
+    block_structure::{
+        block_chain::BlockChain,
+        block_store::BlockStore,
+        chain_tip::ChainTip,
+        download_cursor::DownloadCursor,
+        utxo_set::{UTXOSet, UTXOSnapshot},
+    },
+    concurrency::stop::Stop,
+    configurations::save_config::SaveConfig,
+    logs::{level::Level, logger_sender::LoggerSender},
+    notifications::{notification::Notification, notifier::Notifier},
+    serialization::{
+        checked_file::write_checked_file, serializable_internal_order::SerializableInternalOrder,
+        versioned_format::VersionedFormat,
+    },
+    wallet_structure::{
+        balance_history::{BalanceHistory, BalanceHistorySnapshot},
+        wallet::Wallet,
+        wallet_directory::WalletDirectory,
+    },
 };
 
-use std::fs::OpenOptions;
+use chrono::offset::Utc;
+
+use std::{
+    sync::mpsc::{Receiver, RecvTimeoutError},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
 
 const BLOCKCHAIN_FILE: &str = "Blockchain";
+const CHAIN_TIP_FILE: &str = "Chain tip";
+const DOWNLOAD_CURSOR_FILE: &str = "Download cursor";
+const UTXO_SET_FILE: &str = "UTXO set";
+const BALANCE_HISTORY_FILE: &str = "Balance history";
 const WALLET_FILE: &str = "Wallet";
 
+/// The references to the state saved by the autosave: the wallet, the UTXO set, the balance history,
+/// the block chain and the directory of the wallets if it's configured
+pub type AutosaveState = (
+    MutArc<Wallet>,
+    MutArc<UTXOSet>,
+    MutArc<BalanceHistory>,
+    MutArc<BlockChain>,
+    Option<MutArc<WalletDirectory>>,
+);
+
+/// The time between the writes of the new blocks to the block store
+const BLOCK_STORE_FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Writes the blocks that arrive to the block store every flush interval, so they are not lost
+/// if the program ends without saving the block chain. After each write, the blocks already
+/// downloaded are saved if there are new ones, so an interrupted download continues from the
+/// blocks in the store. When it's stopped, it writes the last blocks and gives back the block
+/// store. Nothing is done if there is no block store
+pub fn persist_block_chain(
+    block_store: Option<BlockStore>,
+    state: (MutArc<BlockChain>, MutArc<DownloadCursor>),
+    save_config: &SaveConfig,
+    receiver_stop: Receiver<Stop>,
+    logger: LoggerSender,
+) -> Option<JoinHandle<BlockStore>> {
+    let mut block_store = block_store?;
+    let (block_chain, download_cursor) = state;
+    let download_cursor_path = save_config
+        .write_block_chain
+        .as_ref()
+        .map(|path| get_download_cursor_path(path));
+
+    let mut saved_downloads = 0;
+    let handle = thread::spawn(move || loop {
+        let stop = !matches!(
+            receiver_stop.recv_timeout(BLOCK_STORE_FLUSH_INTERVAL),
+            Err(RecvTimeoutError::Timeout)
+        );
+
+        let new_downloads = match get_reference(&block_chain) {
+            Ok(block_chain) => match block_store.flush(&block_chain) {
+                Ok(written) => {
+                    if written > 0 {
+                        let _ =
+                            logger.log_file(format!("Writing {written} blocks to the Block store"));
+                    }
+                    get_new_downloads(&download_cursor, saved_downloads)
+                }
+                Err(error) => {
+                    let _ = logger.log_data(Level::ERROR, error);
+                    None
+                }
+            },
+            Err(error) => {
+                let _ = logger.log_data(Level::ERROR, error);
+                None
+            }
+        };
+
+        if let Some(new_downloads) = new_downloads {
+            match SaveSystem::save_value(
+                &new_downloads,
+                DOWNLOAD_CURSOR_FILE,
+                download_cursor_path.clone(),
+                logger.clone(),
+            ) {
+                Ok(()) => saved_downloads = new_downloads.len(),
+                Err(error) => {
+                    let _ = logger.log_data(Level::ERROR, error);
+                }
+            }
+        }
+
+        if stop {
+            break block_store;
+        }
+    });
+
+    Some(handle)
+}
+
+/// Returns a copy of the blocks already downloaded if there are more than the ones saved. It's
+/// taken while the block chain is referenced, so it only has the blocks that are in the store
+fn get_new_downloads(
+    download_cursor: &MutArc<DownloadCursor>,
+    saved_downloads: usize,
+) -> Option<DownloadCursor> {
+    match get_reference(download_cursor) {
+        Ok(download_cursor) if download_cursor.len() != saved_downloads => {
+            Some(download_cursor.clone())
+        }
+        _ => None,
+    }
+}
+
+/// Saves the wallet, the UTXO set, the balance history and the tip of the block chain every autosave interval while
+/// running, notifying the time of each save, until it's stopped. Nothing is done if the autosave
+/// is not enabled
+pub fn autosave<N: Notifier + 'static>(
+    save_config: SaveConfig,
+    state: AutosaveState,
+    receiver_stop: Receiver<Stop>,
+    notifier: N,
+    logger: LoggerSender,
+) -> Option<JoinHandle<()>> {
+    if !save_config.autosave {
+        return None;
+    }
+
+    let handle = thread::spawn(move || {
+        while let Err(RecvTimeoutError::Timeout) =
+            receiver_stop.recv_timeout(save_config.autosave_interval)
+        {
+            let _ = logger.log_file("Autosaving the state of the node".to_string());
+            match save_state(&save_config, &state, logger.clone()) {
+                Ok(()) => notifier.notify(Notification::StateSaved(Utc::now().timestamp())),
+                Err(error) => {
+                    let _ = logger.log_data(Level::ERROR, error);
+                }
+            }
+        }
+    });
+
+    Some(handle)
+}
+
+/// Saves the state referenced while running. The UTXO set is copied only if it's not being
+/// updated, so it's the same as the one of the tip of the block chain
+///
+/// ### Error
+///  * `ErrorSerialization::ErrorInSerialization`: It will appear when the serialization of a value fails or a file could not be written
+///  * `ErrorWallet::CannotAccessWalletFile`: It will appear when the file of the wallet in the directory cannot be written
+fn save_state(
+    save_config: &SaveConfig,
+    state: &AutosaveState,
+    logger: LoggerSender,
+) -> Result<(), ErrorExecution> {
+    let (wallet, utxo_set, balance_history, block_chain, wallet_directory) = state;
+
+    let (chain_tip, utxo_set, balance_history) = {
+        let block_chain = get_reference(block_chain)?;
+        let utxo_set = match utxo_set.try_lock_for(Duration::ZERO) {
+            Ok(utxo_set) => Some(utxo_set.clone()),
+            Err(_) => None,
+        };
+        (
+            ChainTip::new(&block_chain, Utc::now().timestamp()),
+            utxo_set,
+            get_reference(balance_history)?.clone(),
+        )
+    };
+
+    if let Some(chain_tip) = chain_tip {
+        SaveSystem::save_chain_tip(
+            &chain_tip,
+            (utxo_set, balance_history),
+            save_config,
+            logger.clone(),
+        )?;
+    }
+
+    let wallet_directory = match wallet_directory {
+        Some(wallet_directory) => Some(get_reference(wallet_directory)?),
+        None => None,
+    };
+    let wallet = get_reference(wallet)?;
+    SaveSystem::save_wallet(&wallet, wallet_directory.as_deref(), save_config, logger)
+}
+
 /// Represents the elements to save to files
 pub struct SaveSystem {
     block_chain: BlockChain,
+    block_store: Option<BlockStore>,
+    utxo_set: UTXOSet,
+    balance_history: BalanceHistory,
+    download_cursor: DownloadCursor,
     wallet: Wallet,
+    wallet_directory: Option<WalletDirectory>,
     logger: LoggerSender,
 }
 
 impl SaveSystem {
-    pub fn new(block_chain: BlockChain, wallet: Wallet, logger: LoggerSender) -> SaveSystem {
+    pub fn new(
+        block_chain: BlockChain,
+        block_store: Option<BlockStore>,
+        (utxo_set, balance_history): (UTXOSet, BalanceHistory),
+        download_cursor: DownloadCursor,
+        wallet: Wallet,
+        wallet_directory: Option<WalletDirectory>,
+        logger: LoggerSender,
+    ) -> SaveSystem {
         SaveSystem {
             block_chain,
+            block_store,
+            utxo_set,
+            balance_history,
+            download_cursor,
             wallet,
+            wallet_directory,
             logger,
         }
     }
 
-    /// Saves the block chain and a wallet to there respective files if given
+    /// Saves the block chain and a wallet to there respective files if given.
+    /// Next to the block chain it saves its tip with the current time, to be shown at startup,
+    /// the UTXO set, the balance history and the blocks already downloaded. Once the block chain is saved, its block store is emptied.
+    /// If there is a wallet directory, the wallet is saved in the file of the wallet in use
     ///
     /// ### Error
-    ///  * `ErrorInitialization::ValueFileDoesntExist`: It will appear when the file could not be created
-    ///  * `ErrorSerialization::ErrorInSerialization`: It will appear when the serialization of the value fails
+    ///  * `ErrorSerialization::ErrorInSerialization`: It will appear when the serialization of the value fails or the file could not be written
+    ///  * `ErrorWallet::CannotAccessWalletFile`: It will appear when the file of the wallet in the directory cannot be written
     pub fn save_to_files(self, save_config: SaveConfig) -> Result<(), ErrorExecution> {
+        if let Some(chain_tip) = ChainTip::new(&self.block_chain, Utc::now().timestamp()) {
+            Self::save_chain_tip(
+                &chain_tip,
+                (Some(self.utxo_set), self.balance_history),
+                &save_config,
+                self.logger.clone(),
+            )?;
+        }
+
         Self::save_value(
-            self.block_chain,
+            &self.block_chain,
             BLOCKCHAIN_FILE,
-            save_config.write_block_chain,
+            save_config.write_block_chain.clone(),
             self.logger.clone(),
         )?;
 
         Self::save_value(
-            self.wallet,
-            WALLET_FILE,
-            save_config.write_wallet,
+            &self.download_cursor,
+            DOWNLOAD_CURSOR_FILE,
+            save_config
+                .write_block_chain
+                .as_ref()
+                .map(|path| get_download_cursor_path(path)),
+            self.logger.clone(),
+        )?;
+
+        if let Some(mut block_store) = self.block_store {
+            if let Err(error) = block_store.checkpoint(&self.block_chain) {
+                let _ = self.logger.log_data(Level::ERROR, error);
+            }
+        }
+
+        Self::save_wallet(
+            &self.wallet,
+            self.wallet_directory.as_ref(),
+            &save_config,
             self.logger,
+        )
+    }
+
+    /// Saves the tip of the block chain next to it with the balance history of that tip and, if
+    /// given, the UTXO set of that tip
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::ErrorInSerialization`: It will appear when the serialization of the value fails or the file could not be written
+    fn save_chain_tip(
+        chain_tip: &ChainTip,
+        (utxo_set, balance_history): (Option<UTXOSet>, BalanceHistory),
+        save_config: &SaveConfig,
+        logger: LoggerSender,
+    ) -> Result<(), ErrorExecution> {
+        Self::save_value(
+            chain_tip,
+            CHAIN_TIP_FILE,
+            save_config
+                .write_block_chain
+                .as_ref()
+                .map(|path| get_chain_tip_path(path)),
+            logger.clone(),
         )?;
 
-        Ok(())
+        if let Some(utxo_set) = utxo_set {
+            Self::save_value(
+                &UTXOSnapshot {
+                    tip: chain_tip.block_hash,
+                    utxo_set,
+                },
+                UTXO_SET_FILE,
+                save_config
+                    .write_block_chain
+                    .as_ref()
+                    .map(|path| get_utxo_set_path(path)),
+                logger.clone(),
+            )?;
+        }
+
+        Self::save_value(
+            &BalanceHistorySnapshot {
+                tip: chain_tip.block_hash,
+                balance_history,
+            },
+            BALANCE_HISTORY_FILE,
+            save_config
+                .write_block_chain
+                .as_ref()
+                .map(|path| get_balance_history_path(path)),
+            logger,
+        )
     }
 
-    /// Saves a serializable to a file
+    /// Saves the wallet in the file of the wallet in use of the directory, or in the wallet file
+    /// if there is no directory. If the wallet in use was closed, nothing is saved
     ///
     /// ### Error
-    ///  * `ErrorInitialization::ValueFileDoesntExist`: It will appear when the file could not be created
-    ///  * `ErrorSerialization::ErrorInSerialization`: It will appear when the serialization of the value fails
-    fn save_value<V: SerializableInternalOrder>(
-        value: V,
+    ///  * `ErrorSerialization::ErrorInSerialization`: It will appear when the serialization of the value fails or the file could not be written
+    ///  * `ErrorWallet::CannotAccessWalletFile`: It will appear when the file of the wallet in the directory cannot be written
+    fn save_wallet(
+        wallet: &Wallet,
+        wallet_directory: Option<&WalletDirectory>,
+        save_config: &SaveConfig,
+        logger: LoggerSender,
+    ) -> Result<(), ErrorExecution> {
+        if let Some(wallet_directory) = wallet_directory {
+            if let Some(path) = wallet_directory.get_selected_path() {
+                let _ = logger.log_file(format!("Writing the wallet to {}", path.display()));
+            }
+
+            wallet_directory.save_selected_wallet(wallet)?;
+            return Ok(());
+        }
+
+        Self::save_value(
+            wallet,
+            WALLET_FILE,
+            save_config.write_wallet.clone(),
+            logger,
+        )
+    }
+
+    /// Saves a serializable to a file with the version of its format, writing it first to a
+    /// temporary file that then replaces the previous one. The previous file is kept as a backup, to recover it if the new one
+    /// gets corrupted
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::ErrorInSerialization`: It will appear when the serialization of the value fails or the file could not be written
+    fn save_value<V: SerializableInternalOrder + VersionedFormat>(
+        value: &V,
         name: &str,
         path: Option<String>,
         logger: LoggerSender,
@@ -70,14 +396,9 @@ impl SaveSystem {
             }
         };
 
-        let mut file = match OpenOptions::new().create(true).write(true).open(path) {
-            Ok(file) => file,
-            _ => return Err(ErrorInitialization::ValueFileDoesntExist.into()),
-        };
-
         let _ = logger.log_file(format!("Writing the {name} to file"));
 
-        value.io_serialize(&mut file)?;
+        write_checked_file(&path, value)?;
 
         Ok(())
     }
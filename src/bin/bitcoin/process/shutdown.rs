@@ -0,0 +1,103 @@
+use super::error_process::ErrorProcess;
+
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+/// How often the threads waiting for a shutdown check if it was requested
+pub const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The time each worker thread has to finish after being told to stop
+pub const WORKER_JOIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+static SHUTDOWN_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(unix)]
+mod signals {
+    const SIGHUP: i32 = 1;
+    const SIGINT: i32 = 2;
+    const SIGTERM: i32 = 15;
+
+    const FORCED_EXIT_CODE: i32 = 130;
+
+    extern "C" {
+        fn signal(signum: i32, handler: extern "C" fn(i32)) -> usize;
+        fn _exit(status: i32) -> !;
+    }
+
+    /// Only sets the flag, being the only thing that is safe to do inside a signal handler. A
+    /// second signal ends the program right away, in case the shutdown gets stuck
+    extern "C" fn on_signal(_signum: i32) {
+        if super::SHUTDOWN_REQUESTED.swap(true, super::Ordering::SeqCst) {
+            unsafe { _exit(FORCED_EXIT_CODE) }
+        }
+    }
+
+    pub fn trap() {
+        for signum in [SIGHUP, SIGINT, SIGTERM] {
+            unsafe {
+                signal(signum, on_signal);
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod signals {
+    pub fn trap() {}
+}
+
+/// Makes the interrupt, the termination and the closing of the terminal request a shutdown instead
+/// of killing the program, so the workers are stopped and the state is saved before exiting.
+/// Only the Unix signals are trapped
+pub fn trap_signals() {
+    signals::trap();
+}
+
+/// Asks the program to stop as if a signal was received, used when the window is closed or a
+/// worker the program cannot continue without fails
+pub fn request_shutdown() {
+    SHUTDOWN_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+/// Returns if a signal or a fatal failure asked the program to stop
+pub fn is_shutdown_requested() -> bool {
+    SHUTDOWN_REQUESTED.load(Ordering::SeqCst)
+}
+
+/// Creates a thread that runs the action once a shutdown is requested, used to tell the input
+/// handler to stop waiting for the user
+pub fn on_shutdown<F>(action: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    thread::spawn(move || {
+        while !is_shutdown_requested() {
+            thread::sleep(SHUTDOWN_POLL_INTERVAL);
+        }
+        action();
+    });
+}
+
+/// Waits for the thread to finish and returns its result. If it doesn't finish in the given time
+/// it's left running, so a stuck worker cannot stop the state from being saved
+///
+/// ### Error
+///  * `ErrorProcess::FailThread`: It will appear when the thread panics
+///  * `ErrorProcess::StopTimeout`: It will appear when the thread doesn't finish in time
+pub fn join_with_timeout<T>(handle: JoinHandle<T>, timeout: Duration) -> Result<T, ErrorProcess> {
+    let start = Instant::now();
+    while !handle.is_finished() {
+        if start.elapsed() >= timeout {
+            return Err(ErrorProcess::StopTimeout);
+        }
+        thread::sleep(SHUTDOWN_POLL_INTERVAL);
+    }
+
+    match handle.join() {
+        Ok(result) => Ok(result),
+        Err(_) => Err(ErrorProcess::FailThread),
+    }
+}
@@ -0,0 +1,90 @@
+use super::error_process::ErrorProcess;
+
+use std::{
+    any,
+    sync::{Arc, Mutex, MutexGuard, TryLockError},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// How often the lock of a shared value is tried again while it's taken by another thread
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(5);
+
+/// It's a value shared between the threads of the program. A thread that panics while using it
+/// doesn't make it unusable for the others, they keep using the value as it was left
+#[derive(Debug)]
+pub struct SharedState<T> {
+    value: Arc<Mutex<T>>,
+}
+
+impl<T> SharedState<T> {
+    pub fn new(value: T) -> Self {
+        SharedState {
+            value: Arc::new(Mutex::new(value)),
+        }
+    }
+
+    /// Returns the name of the type of the value, used to describe the errors
+    fn name() -> String {
+        any::type_name::<T>().to_string()
+    }
+
+    /// Returns the mutex of the value, to share it with the parts of the library that lock it
+    /// themselves
+    pub fn as_mutex(&self) -> Arc<Mutex<T>> {
+        self.value.clone()
+    }
+
+    /// Waits for the other threads to release the value and locks it
+    pub fn lock(&self) -> MutexGuard<'_, T> {
+        match self.value.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Waits for the other threads to release the value and locks it, up to the given time
+    ///
+    /// ### Error
+    ///  * `ErrorProcess::LockTimeout`: It will appear when the value is still locked after the given time
+    pub fn try_lock_for(&self, timeout: Duration) -> Result<MutexGuard<'_, T>, ErrorProcess> {
+        let start = Instant::now();
+        loop {
+            match self.value.try_lock() {
+                Ok(guard) => return Ok(guard),
+                Err(TryLockError::Poisoned(poisoned)) => return Ok(poisoned.into_inner()),
+                Err(TryLockError::WouldBlock) if start.elapsed() < timeout => {
+                    thread::sleep(LOCK_RETRY_INTERVAL)
+                }
+                Err(TryLockError::WouldBlock) => {
+                    return Err(ErrorProcess::LockTimeout(Self::name()))
+                }
+            }
+        }
+    }
+
+    /// Takes the value out, when no other thread has it anymore
+    ///
+    /// ### Error
+    ///  * `ErrorProcess::StillShared`: It will appear when other threads still have the value
+    pub fn into_inner(self) -> Result<T, ErrorProcess> {
+        match Arc::try_unwrap(self.value) {
+            Ok(value) => match value.into_inner() {
+                Ok(value) => Ok(value),
+                Err(poisoned) => Ok(poisoned.into_inner()),
+            },
+            Err(value) => Err(ErrorProcess::StillShared(
+                Self::name(),
+                Arc::strong_count(&value) - 1,
+            )),
+        }
+    }
+}
+
+impl<T> Clone for SharedState<T> {
+    fn clone(&self) -> Self {
+        SharedState {
+            value: self.value.clone(),
+        }
+    }
+}
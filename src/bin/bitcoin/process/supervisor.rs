@@ -0,0 +1,240 @@
+use super::{
+    error_process::ErrorProcess,
+    reference::{get_reference, MutArc},
+    shutdown,
+    state::SharedState,
+};
+
+use cargosos_bitcoin::{concurrency::stop::Stop, logs::logger_sender::LoggerSender};
+
+use std::{
+    any::Any,
+    fmt::Debug,
+    sync::mpsc::{channel, RecvTimeoutError, Sender},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// How often the workers are checked for a panic
+const SUPERVISE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The times a worker can be restarted before its panics are considered fatal
+const MAX_RESTARTS: u32 = 5;
+
+/// It's what the supervisor does when a worker panics
+pub enum RestartPolicy<T> {
+    /// The worker is started again with the given function
+    Restart(Box<dyn FnMut() -> JoinHandle<T> + Send>),
+
+    /// The program is asked to stop, because it cannot work without the worker
+    Fatal,
+}
+
+/// It's the value returned by a worker thread, that is logged when it's an error
+pub trait WorkerResult: Send + 'static {
+    fn get_error(self) -> Option<String>;
+}
+
+impl WorkerResult for () {
+    fn get_error(self) -> Option<String> {
+        None
+    }
+}
+
+impl<E: Debug + Send + 'static> WorkerResult for Result<(), E> {
+    fn get_error(self) -> Option<String> {
+        self.err().map(|error| format!("{:?}", error))
+    }
+}
+
+/// Returns the message given to the panic, as a text
+pub fn get_panic_message(payload: &(dyn Any + Send)) -> String {
+    match (
+        payload.downcast_ref::<&str>(),
+        payload.downcast_ref::<String>(),
+    ) {
+        (Some(message), _) => message.to_string(),
+        (None, Some(message)) => message.clone(),
+        (None, None) => "unknown cause".to_string(),
+    }
+}
+
+/// It's a worker thread with the way to handle its panics
+struct Worker<T: WorkerResult> {
+    name: String,
+    handle: Option<JoinHandle<T>>,
+    policy: RestartPolicy<T>,
+    restarts: u32,
+}
+
+/// It's a worker of any result, to keep them together
+trait Supervised: Send {
+    fn get_name(&self) -> &str;
+
+    /// Handles the end of the worker if it has finished, restarting it if it panicked and it can
+    /// be restarted. Returns if the worker is still running
+    fn check(&mut self, logger: &LoggerSender) -> bool;
+
+    /// Waits for the worker to finish, up to the given time
+    fn join(self: Box<Self>, timeout: Duration, logger: &LoggerSender) -> Result<(), ErrorProcess>;
+}
+
+impl<T: WorkerResult> Supervised for Worker<T> {
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn check(&mut self, logger: &LoggerSender) -> bool {
+        let handle = match self.handle.take() {
+            Some(handle) if !handle.is_finished() => {
+                self.handle = Some(handle);
+                return true;
+            }
+            Some(handle) => handle,
+            None => return false,
+        };
+
+        let payload = match handle.join() {
+            Ok(result) => {
+                if let Some(error) = result.get_error() {
+                    let _ = logger.log_error(format!(
+                        "The {} thread ended with an error: {error}",
+                        self.name
+                    ));
+                }
+                return false;
+            }
+            Err(payload) => payload,
+        };
+
+        let _ = logger.log_error(format!(
+            "The {} thread panicked: {}",
+            self.name,
+            get_panic_message(payload.as_ref())
+        ));
+
+        match &mut self.policy {
+            RestartPolicy::Restart(spawn) if self.restarts < MAX_RESTARTS => {
+                self.restarts += 1;
+                let _ = logger.log_error(format!(
+                    "Restarting the {} thread ({}/{MAX_RESTARTS})",
+                    self.name, self.restarts
+                ));
+                self.handle = Some(spawn());
+                true
+            }
+            _ => {
+                let _ = logger.log_error(format!(
+                    "The program cannot continue without the {} thread, stopping it",
+                    self.name
+                ));
+                shutdown::request_shutdown();
+                false
+            }
+        }
+    }
+
+    fn join(self: Box<Self>, timeout: Duration, logger: &LoggerSender) -> Result<(), ErrorProcess> {
+        let handle = match self.handle {
+            Some(handle) => handle,
+            None => return Ok(()),
+        };
+
+        if let Some(error) = shutdown::join_with_timeout(handle, timeout)?.get_error() {
+            let _ = logger.log_error(format!(
+                "The {} thread ended with an error: {error}",
+                self.name
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// It owns the worker threads of the node and checks them periodically, so a panic doesn't end
+/// them silently. The panic is logged, and the worker is restarted or the program is stopped
+/// depending on its policy
+pub struct Supervisor {
+    workers: MutArc<Vec<Box<dyn Supervised>>>,
+    sender_stop: Sender<Stop>,
+    handle: JoinHandle<()>,
+    logger: LoggerSender,
+}
+
+impl Supervisor {
+    /// Creates the supervisor with the thread that checks the workers
+    pub fn new(logger: LoggerSender) -> Self {
+        let workers: MutArc<Vec<Box<dyn Supervised>>> = SharedState::new(Vec::new());
+        let (sender_stop, receiver_stop) = channel::<Stop>();
+
+        let supervised_workers = workers.clone();
+        let supervisor_logger = logger.clone();
+        let handle = thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) =
+                receiver_stop.recv_timeout(SUPERVISE_INTERVAL)
+            {
+                match get_reference(&supervised_workers) {
+                    Ok(mut workers) => {
+                        workers.retain_mut(|worker| worker.check(&supervisor_logger))
+                    }
+                    Err(error) => {
+                        let _ = supervisor_logger
+                            .log_error(format!("Cannot check the worker threads: {:?}", error));
+                    }
+                }
+            }
+        });
+
+        Supervisor {
+            workers,
+            sender_stop,
+            handle,
+            logger,
+        }
+    }
+
+    /// Starts supervising the worker thread with the given name
+    pub fn supervise<T: WorkerResult>(
+        &self,
+        name: &str,
+        handle: JoinHandle<T>,
+        policy: RestartPolicy<T>,
+    ) -> Result<(), ErrorProcess> {
+        get_reference(&self.workers)?.push(Box::new(Worker {
+            name: name.to_string(),
+            handle: Some(handle),
+            policy,
+            restarts: 0,
+        }));
+        Ok(())
+    }
+
+    /// Stops supervising the worker with the given name and waits for it to finish, up to the
+    /// given time. Its error is logged
+    ///
+    /// ### Error
+    ///  * `ErrorProcess::FailThread`: It will appear when the worker panics
+    ///  * `ErrorProcess::StopTimeout`: It will appear when the worker doesn't finish in time
+    pub fn join(&self, name: &str, timeout: Duration) -> Result<(), ErrorProcess> {
+        let worker = {
+            let mut workers = get_reference(&self.workers)?;
+            match workers.iter().position(|worker| worker.get_name() == name) {
+                Some(position) => workers.remove(position),
+                None => return Ok(()),
+            }
+        };
+
+        worker.join(timeout, &self.logger)
+    }
+
+    /// Stops checking the workers, leaving the ones that were not joined running
+    ///
+    /// ### Error
+    ///  * `ErrorProcess::FailThread`: It will appear when the thread of the supervisor fails
+    pub fn stop(self) -> Result<(), ErrorProcess> {
+        let _ = self.sender_stop.send(Stop::Stop);
+        match self.handle.join() {
+            Ok(()) => Ok(()),
+            Err(_) => Err(ErrorProcess::FailThread),
+        }
+    }
+}
@@ -4,77 +4,135 @@ use crate::ui::error_ui::ErrorUI;
 
 use cargosos_bitcoin::{
     block_structure::{
-        block_chain::BlockChain, hash::HashType, merkle_tree::MerkleTree, transaction::Transaction,
-        utxo_set::UTXOSet,
+        block_chain::BlockChain, error_block::ErrorBlock, hash::HashType, merkle_tree::MerkleTree,
+        relay_policy, transaction::Transaction, utxo_set::UTXOSet,
     },
+    connections::network::Network,
     logs::logger_sender::LoggerSender,
     node_structure::{broadcasting::Broadcasting, error_node::ErrorNode},
     notifications::{notification::Notification, notifier::Notifier},
     wallet_structure::{
-        account::Account, address::Address, error_wallet::ErrorWallet, wallet::Wallet,
+        account::Account, address::Address, amount::Amount, error_wallet::ErrorWallet,
+        private_key::PrivateKey, transaction_options::TransactionOptions,
+        transaction_preview::TransactionPreview, wallet::Wallet,
     },
 };
 
 use std::io::{Read, Write};
 
-/// FUnction that converts testnet bitcoins to satoshis
-pub fn fron_tbtc_to_satoshi(tbtc: f64) -> i64 {
-    (tbtc * 100_000_000.0) as i64
-}
+/// The name of the account of a private key being swept, which is never added to the wallet
+const SWEPT_ACCOUNT_NAME: &str = "Swept key";
 
-/// Creates a transaction given the user user_input
+/// Creates a transaction given the user user_input, with the lock time and replaceability of the options
 ///
 /// ### Error
-///  * `ErrorUI::ErrorInTransaction`: It will appear when the user does not have enough funds to make the transaction or the transaction is not valid
+///  * `ErrorProcess::TransactionWithoutSufficientFunds`: It will appear when the user does not have enough funds to make the transaction
+///  * `ErrorProcess::TransactionWithDustAmount`: It will appear when the amount is below the dust threshold
+///  * `ErrorProcess::TransactionCreationFail`: It will appear when the transaction is not valid
 fn create_transaction(
     utxo_set: &UTXOSet,
     account: &Account,
     logger: LoggerSender,
     address: &Address,
-    amount: f64,
-    fee: f64,
+    amount: Amount,
+    fee: Amount,
+    options: TransactionOptions,
 ) -> Result<Transaction, ErrorProcess> {
-    match account.create_transaction(
-        address.clone(),
-        fron_tbtc_to_satoshi(amount),
-        fron_tbtc_to_satoshi(fee),
+    account
+        .create_transaction(address.clone(), amount, fee, options, utxo_set)
+        .map_err(|error| to_creation_error(error, address, logger))
+}
+
+/// Returns why a transaction to the address could not be created, logging the error of the wallet
+fn to_creation_error(error: ErrorWallet, address: &Address, logger: LoggerSender) -> ErrorProcess {
+    let _ = logger.log_wallet(format!(
+        "Error creating transaction, with error: {:?}",
+        error
+    ));
+
+    match error {
+        ErrorWallet::NotEnoughFunds(_) => ErrorProcess::TransactionWithoutSufficientFunds,
+        ErrorWallet::DustOutput(_) => ErrorProcess::TransactionWithDustAmount(
+            relay_policy::get_dust_threshold(address.generate_script_pubkey().as_script()),
+        ),
+        _ => ErrorProcess::TransactionCreationFail,
+    }
+}
+
+/// Notifies the user why the transaction could not be created
+fn notify_creation_error<N: Notifier>(error: &ErrorProcess, notifier: N) {
+    match error {
+        ErrorProcess::TransactionWithDustAmount(dust_threshold) => {
+            notifier.notify(Notification::AmountIsDust(*dust_threshold))
+        }
+        _ => notifier.notify(Notification::NotEnoughFunds),
+    }
+}
+
+/// Creates the transaction of the selected account in the wallet without broadcasting it, and sends its
+/// summary to the front so the user can confirm it. The payment is the amount, the fee and the options
+/// of the transaction. When the transaction cannot be created the user is notified, without stopping
+/// the handling of the next inputs. The transaction is signed with the private key as it's kept in the
+/// wallet, even if it's encrypted, because only its size is needed
+pub fn preview_transaction<N: Notifier>(
+    wallet: &Wallet,
+    utxo_set: &UTXOSet,
+    address: Address,
+    payment: (Amount, Amount, TransactionOptions),
+    notifier: N,
+    logger: LoggerSender,
+) {
+    let (amount, fee, options) = payment;
+
+    let account = match wallet.get_selected_account() {
+        Some(account) => account,
+        None => {
+            let _ = logger.log_wallet("No account selected cannot preview transaction".to_string());
+            return notifier.notify(Notification::AccountNotSelected);
+        }
+    };
+
+    let transaction = match create_transaction(
         utxo_set,
+        account,
+        logger.clone(),
+        &address,
+        amount,
+        fee,
+        options,
     ) {
-        Ok(transaction) => Ok(transaction),
-        Err(ErrorWallet::NotEnoughFunds(error_string)) => {
-            let _ = logger.log_wallet(format!(
-                "Error creating transaction, with error: {:?}",
-                ErrorWallet::NotEnoughFunds(error_string)
-            ));
-            Err(ErrorProcess::TransactionWithoutSufficientFunds)
-        }
+        Ok(transaction) => transaction,
+        Err(error) => return notify_creation_error(&error, notifier),
+    };
+
+    match TransactionPreview::new(&transaction, account, &address, utxo_set) {
+        Ok(preview) => notifier.notify(Notification::TransactionPreview(preview)),
         Err(error) => {
-            let _ = logger.log_wallet(format!(
-                "Error creating transaction, with error: {:?}",
+            let _ = logger.log_transaction(format!(
+                "Cannot summarize the transaction, with error: {:?}",
                 error
             ));
-            Err(ErrorProcess::TransactionCreationFail)
         }
     }
 }
 
-/// Broadcast the transaction created by the user to the peers from the selected account in the wallet
+/// Broadcast the transaction created by the user to the peers from the selected account in the wallet.
+/// The payment is the amount, the fee and the options of the transaction. When the wallet is locked by
+/// its spending password the user is notified, without sending it
 ///
 /// ### Error
 ///  * `ErrorUI::FailedSignalToFront`: It will appear when the sender fails
-///  * `ErrorUI::CannotUnwrapArc`: It will appear when we try to unwrap an Arc
 ///  * `ErrorUI::ErrorFromPeer`: It will appear when a conextion with a peer fails
 pub fn sending_transaction<N: Notifier, RW: Read + Write + Send + 'static>(
     broadcasting: &mut Broadcasting<RW>,
     wallet: &Wallet,
     utxo_set: &mut UTXOSet,
     address: Address,
-    amount_fee: (f64, f64),
+    payment: (Amount, Amount, TransactionOptions),
     notifier: N,
     logger: LoggerSender,
 ) -> Result<(), ErrorUI> {
-    let amount = amount_fee.0;
-    let fee = amount_fee.1;
+    let (amount, fee, options) = payment;
 
     let account = match wallet.get_selected_account() {
         Some(account) => account,
@@ -85,16 +143,185 @@ pub fn sending_transaction<N: Notifier, RW: Read + Write + Send + 'static>(
         }
     };
 
-    let transaction =
-        match create_transaction(utxo_set, account, logger.clone(), &address, amount, fee) {
-            Ok(transaction) => transaction,
+    let account = match wallet.get_signing_account(account) {
+        Ok(account) => account,
+        Err(error) => {
+            let _ = logger.log_wallet(format!(
+                "Cannot sign the transaction, with error: {:?}",
+                error
+            ));
+            notifier.notify(Notification::SpendingPasswordRejected(format!(
+                "{:?}",
+                error
+            )));
+            return Ok(());
+        }
+    };
+
+    let transaction = match create_transaction(
+        utxo_set,
+        &account,
+        logger.clone(),
+        &address,
+        amount,
+        fee,
+        options,
+    ) {
+        Ok(transaction) => transaction,
+        Err(error) => {
+            notify_creation_error(&error, notifier);
+            return Err(error.into());
+        }
+    };
+
+    let _ = logger.log_transaction("Sending transaction".to_string());
+    utxo_set.append_pending_transaction(transaction.clone());
+
+    match broadcasting.send_transaction(transaction.clone()) {
+        Ok(()) => {
+            notifier.notify(Notification::SuccessfullySentTransaction(transaction));
+            Ok(())
+        }
+        Err(ErrorNode::WhileSendingMessage(message)) => Err(ErrorUI::ErrorFromPeer(message)),
+        _ => Err(ErrorUI::ErrorFromPeer(
+            "While sending transaction".to_string(),
+        )),
+    }
+}
+
+/// Sweeps the private key given in WIF: every unspent output of its address, minus the fee, is sent
+/// to the selected account of the wallet in a transaction signed with that key, which is not kept
+/// in the wallet. When the key is not valid or the transaction cannot be created the user is
+/// notified, without stopping the handling of the next inputs
+///
+/// ### Error
+///  * `ErrorUI::ErrorFromPeer`: It will appear when a conextion with a peer fails
+pub fn sweep_private_key<N: Notifier, RW: Read + Write + Send + 'static>(
+    broadcasting: &mut Broadcasting<RW>,
+    wallet: &Wallet,
+    utxo_set: &mut UTXOSet,
+    wif: &str,
+    fee: Amount,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let destination = match wallet.get_selected_account() {
+        Some(account) => account,
+        None => {
+            let _ = logger.log_wallet("No account selected cannot sweep the key".to_string());
+            notifier.notify(Notification::AccountNotSelected);
+            return Ok(());
+        }
+    };
+
+    let swept_account =
+        match PrivateKey::from_wif(wif, wallet.get_network()).and_then(|private_key| {
+            let public_key = private_key.public_key();
+            Account::from_keys(
+                SWEPT_ACCOUNT_NAME,
+                private_key,
+                public_key,
+                wallet.get_network(),
+            )
+        }) {
+            Ok(swept_account) => swept_account,
             Err(error) => {
-                notifier.notify(Notification::NotEnoughFunds);
-                return Err(error.into());
+                let _ = logger.log_wallet(format!(
+                    "Cannot read the private key to sweep, with error: {:?}",
+                    error
+                ));
+                notifier.notify(Notification::InvalidPrivateKeyEnter);
+                return Ok(());
             }
         };
 
-    let _ = logger.log_transaction("Sending transaction".to_string());
+    let transaction = match swept_account.create_sweep_transaction(
+        destination.address.clone(),
+        fee,
+        TransactionOptions::default(),
+        utxo_set,
+    ) {
+        Ok(transaction) => transaction,
+        Err(error) => {
+            let error = to_creation_error(error, &destination.address, logger);
+            notify_creation_error(&error, notifier);
+            return Ok(());
+        }
+    };
+
+    let _ = logger.log_transaction(format!(
+        "Sweeping the address {} into the account {}",
+        swept_account.address, destination.account_name
+    ));
+    utxo_set.append_pending_transaction(transaction.clone());
+
+    match broadcasting.send_transaction(transaction.clone()) {
+        Ok(()) => {
+            notifier.notify(Notification::SuccessfullySentTransaction(transaction));
+            Ok(())
+        }
+        Err(ErrorNode::WhileSendingMessage(message)) => Err(ErrorUI::ErrorFromPeer(message)),
+        _ => Err(ErrorUI::ErrorFromPeer(
+            "While sending transaction".to_string(),
+        )),
+    }
+}
+
+/// Broadcast a transaction given serialized in hexadecimal to the peers. If asked, the transaction is
+/// first checked against the UTXO set, so it's not sent when it spends outputs that are not available
+/// or when its outputs are worth more than its inputs
+///
+/// ### Error
+///  * `ErrorUI::ErrorFromPeer`: It will appear when a conextion with a peer fails
+pub fn broadcast_raw_transaction<N: Notifier, RW: Read + Write + Send + 'static>(
+    broadcasting: &mut Broadcasting<RW>,
+    utxo_set: &mut UTXOSet,
+    raw_transaction: &str,
+    validate: bool,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let transaction = match Transaction::from_hex(raw_transaction) {
+        Ok(transaction) => transaction,
+        Err(error) => {
+            let _ = logger.log_transaction(format!(
+                "Error reading the raw transaction, with error: {:?}",
+                error
+            ));
+            notifier.notify(Notification::RawTransactionRejected(
+                "It is not a transaction in hexadecimal".to_string(),
+            ));
+            return Ok(());
+        }
+    };
+
+    if validate {
+        let reason = match utxo_set.check_transaction(&transaction) {
+            Ok(fee) => {
+                let _ = logger.log_transaction(format!(
+                    "Raw transaction {transaction} is valid with a fee of {fee} satoshis"
+                ));
+                None
+            }
+            Err(ErrorBlock::OutputNotAvailable) => {
+                Some("It spends an output that is not available".to_string())
+            }
+            Err(ErrorBlock::OutputsExceedInputs) => {
+                Some("Its outputs are worth more than its inputs".to_string())
+            }
+            Err(error) => Some(format!("{:?}", error)),
+        };
+
+        if let Some(reason) = reason {
+            let _ = logger.log_transaction(format!(
+                "Raw transaction {transaction} rejected, with error: {reason}"
+            ));
+            notifier.notify(Notification::RawTransactionRejected(reason));
+            return Ok(());
+        }
+    }
+
+    let _ = logger.log_transaction("Sending raw transaction".to_string());
     utxo_set.append_pending_transaction(transaction.clone());
 
     match broadcasting.send_transaction(transaction.clone()) {
@@ -109,17 +336,52 @@ pub fn sending_transaction<N: Notifier, RW: Read + Write + Send + 'static>(
     }
 }
 
+/// Describes a transaction given serialized in hexadecimal, with the values of its inputs for the
+/// outputs spent that are in the UTXO set, and its addresses in the given network
+pub fn describe_raw_transaction<N: Notifier>(
+    utxo_set: &UTXOSet,
+    raw_transaction: &str,
+    network: Network,
+    notifier: N,
+    logger: LoggerSender,
+) {
+    let description = match Transaction::from_hex(raw_transaction) {
+        Ok(transaction) => transaction.describe(Some(utxo_set), network),
+        Err(error) => {
+            let _ = logger.log_transaction(format!(
+                "Error reading the raw transaction, with error: {:?}",
+                error
+            ));
+            notifier.notify(Notification::RawDataProblem(
+                "It is not a transaction in hexadecimal".to_string(),
+            ));
+            return;
+        }
+    };
+
+    match description {
+        Ok(description) => notifier.notify(Notification::TransactionDescribed(description)),
+        Err(error) => {
+            let _ = logger.log_transaction(format!(
+                "Error describing the raw transaction, with error: {:?}",
+                error
+            ));
+            notifier.notify(Notification::RawDataProblem(
+                "The transaction could not be described".to_string(),
+            ));
+        }
+    }
+}
+
 pub fn verify_transaction_merkle_proof_of_inclusion<N: Notifier>(
     block_chain: &BlockChain,
     block_hash: HashType,
-    mut transaction_id: HashType,
+    transaction_id: HashType,
     notifier: N,
     logger: LoggerSender,
 ) {
     let _ = logger.log_transaction("Verifying transaction merkle proof of inclusion".to_string());
 
-    transaction_id.reverse();
-
     let block = match block_chain.get_block_with_hash(&block_hash) {
         Some(block) => block,
         None => {
@@ -134,8 +396,9 @@ pub fn verify_transaction_merkle_proof_of_inclusion<N: Notifier>(
     };
 
     if let Ok(false) | Err(_) = MerkleTree::merkle_proof_of_inclusion(&block, &transaction_id) {
-        let _ =
-            logger.log_transaction("Transaction merkle proof of inclusion verified".to_string());
+        let _ = logger.log_transaction(
+            "Error verifying transaction merkle proof of inclusion, with error: The transaction is not in the block".to_string(),
+        );
         notifier.notify(
             Notification::ProblemVerifyingTransactionMerkleProofOfInclusion(
                 "Transaction merkle proof of inclusion failed".to_string(),
@@ -0,0 +1,654 @@
+use crate::{
+    process::shutdown::{self, SHUTDOWN_POLL_INTERVAL},
+    ui::error_ui::ErrorUI,
+};
+
+use cargosos_bitcoin::{
+    logs::log_buffer::LogBuffer,
+    node_structure::sync_progress::SyncProgress,
+    wallet_structure::{amount::Amount, exchange_rate::ExchangeRate},
+};
+
+use chrono::{offset::Utc, TimeZone};
+
+use std::{
+    collections::VecDeque,
+    io::{stdin, stdout, Write},
+    net::SocketAddr,
+    process::{Command, Stdio},
+    sync::{
+        mpsc::{channel, RecvTimeoutError},
+        Arc, Mutex, MutexGuard,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+const REFRESH_INTERVAL: Duration = Duration::from_millis(200);
+const SIZE_REFRESH_TICKS: u32 = 10;
+const DEFAULT_SIZE: (usize, usize) = (100, 32);
+const MIN_SIZE: (usize, usize) = (60, 24);
+
+const EVENTS_CAPACITY: usize = 100;
+const STATUS_PANES_HEIGHT: usize = 9;
+const FEED_PANES_HEIGHT: usize = 8;
+const MIN_OUTPUT_HEIGHT: usize = 3;
+const PROGRESS_BAR_WIDTH: usize = 20;
+const TAB_WIDTH: &str = "    ";
+
+const TITLE: &str = " CargoSOS Bitcoin Node Wallet ";
+const SELECTED_MARKER: &str = "★ ";
+const UNSELECTED_MARKER: &str = "  ";
+
+const ENTER_ALTERNATE_SCREEN: &str = "\x1b[?1049h";
+const LEAVE_ALTERNATE_SCREEN: &str = "\x1b[?1049l";
+const CLEAR_SCREEN: &str = "\x1b[2J";
+const CLEAR_LINE: &str = "\x1b[2K";
+const SAVE_CURSOR: &str = "\x1b7";
+const RESTORE_CURSOR: &str = "\x1b8";
+const REVERSE_VIDEO: &str = "\x1b[7m";
+const RESET_STYLE: &str = "\x1b[0m";
+
+/// It's a peer connected to the node, as shown in the peers pane
+#[derive(Debug, Clone)]
+struct PeerRow {
+    address: SocketAddr,
+    user_agent: String,
+    latency: Option<Duration>,
+}
+
+/// It's everything shown in the dashboard
+#[derive(Debug, Default)]
+struct DashboardState {
+    /// The width and the height of the terminal
+    size: (usize, usize),
+    is_running: bool,
+    needs_redraw: bool,
+    logs_registered: usize,
+
+    headers: u32,
+    download: Option<SyncProgress>,
+    update: Option<(u32, u32)>,
+    tip_height: Option<u64>,
+    is_blockchain_ready: bool,
+    last_saved: Option<i64>,
+
+    peers: Vec<PeerRow>,
+
+    wallet_name: Option<String>,
+    selected_account: Option<String>,
+    balances: Vec<(String, Amount, Amount)>,
+    exchange_rate: Option<ExchangeRate>,
+
+    events: VecDeque<String>,
+    output: (String, Vec<String>),
+    palette: Vec<String>,
+    question: String,
+}
+
+/// It's the full-screen terminal interface, with panes for the synchronization, the peers, the
+/// balances, the output of the last command, the events and the logs, and a command palette with
+/// the prompt at the bottom.
+///
+/// The panes are drawn again periodically when they change, keeping the cursor in the prompt, so
+/// the notifications do not mix with what the user is typing. It can be cloned to update it from
+/// the notifier while the input handler reads the commands
+#[derive(Debug, Clone)]
+pub struct Dashboard {
+    state: Arc<Mutex<DashboardState>>,
+    log_buffer: LogBuffer,
+}
+
+impl Dashboard {
+    /// Creates the dashboard, showing the last lines of the given buffer in the logs pane
+    pub fn new(log_buffer: LogBuffer) -> Self {
+        Dashboard {
+            state: Arc::new(Mutex::new(DashboardState {
+                size: DEFAULT_SIZE,
+                ..Default::default()
+            })),
+            log_buffer,
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, DashboardState> {
+        match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Changes the state and marks the panes to be drawn again
+    fn update<F: FnOnce(&mut DashboardState)>(&self, change: F) {
+        let mut state = self.state();
+        change(&mut state);
+        state.needs_redraw = true;
+    }
+
+    /// Switches to the alternate screen of the terminal and draws the dashboard periodically in
+    /// another thread, until it's stopped
+    pub fn start(&self) -> JoinHandle<()> {
+        {
+            let mut state = self.state();
+            state.is_running = true;
+            state.size = terminal_size();
+            print!("{ENTER_ALTERNATE_SCREEN}{CLEAR_SCREEN}");
+            self.draw(&mut state, true);
+        }
+
+        let dashboard = self.clone();
+        thread::spawn(move || {
+            let mut ticks: u32 = 0;
+            loop {
+                thread::sleep(REFRESH_INTERVAL);
+                ticks = ticks.wrapping_add(1);
+
+                let size = match ticks % SIZE_REFRESH_TICKS {
+                    0 => Some(terminal_size()),
+                    _ => None,
+                };
+                // Only the amount of lines registered is needed to know if the logs changed
+                let (_, logs_registered) = dashboard.log_buffer.lines_since(usize::MAX);
+
+                let mut state = dashboard.state();
+                if !state.is_running {
+                    break;
+                }
+
+                let is_resized = match size {
+                    Some(size) if size != state.size => {
+                        state.size = size;
+                        true
+                    }
+                    _ => false,
+                };
+                if is_resized || state.needs_redraw || state.logs_registered != logs_registered {
+                    state.logs_registered = logs_registered;
+                    dashboard.draw(&mut state, is_resized);
+                }
+            }
+        })
+    }
+
+    /// Stops drawing the dashboard and returns to the normal screen of the terminal
+    pub fn stop(&self) {
+        let mut state = self.state();
+        state.is_running = false;
+        print!("{LEAVE_ALTERNATE_SCREEN}");
+        let _ = stdout().flush();
+    }
+
+    /// Shows the question in the prompt and reads the answer of the user, without the surrounding spaces
+    ///
+    /// ### Error
+    ///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
+    ///  * `ErrorUI::Interrupted`: It will appear when a shutdown is requested before the user answers
+    pub fn read_line(&self, question: &str) -> Result<String, ErrorUI> {
+        {
+            let mut state = self.state();
+            state.question = question.to_string();
+            draw_prompt(&state);
+        }
+
+        let result = read_answer();
+
+        let mut state = self.state();
+        state.question.clear();
+        draw_prompt(&state);
+
+        result
+    }
+
+    /// Sets the commands shown in the command palette
+    pub fn set_palette(&self, commands: Vec<String>) {
+        self.update(|state| state.palette = commands);
+    }
+
+    /// Adds a message to the events pane, with the time it happened
+    pub fn add_event(&self, message: &str) {
+        let time = Utc::now().format("%H:%M:%S");
+        self.update(|state| {
+            for line in message.lines() {
+                state
+                    .events
+                    .push_back(format!("{time} {}", line.replace('\t', TAB_WIDTH)));
+            }
+            while state.events.len() > EVENTS_CAPACITY {
+                state.events.pop_front();
+            }
+        });
+    }
+
+    /// Replaces the content of the output pane, where the results of the commands are shown
+    pub fn show_output(&self, title: &str, body: &str) {
+        self.update(|state| {
+            state.output = (
+                title.to_string(),
+                body.lines()
+                    .map(|line| line.replace('\t', TAB_WIDTH))
+                    .collect(),
+            )
+        });
+    }
+
+    pub fn add_headers_received(&self, headers: u32) {
+        self.update(|state| state.headers += headers);
+    }
+
+    pub fn set_download_progress(&self, progress: SyncProgress) {
+        self.update(|state| state.download = Some(progress));
+    }
+
+    pub fn set_update_progress(&self, blocks_updated: u32, total_blocks: u32) {
+        self.update(|state| state.update = Some((blocks_updated, total_blocks)));
+    }
+
+    pub fn set_tip_height(&self, height: u64) {
+        self.update(|state| state.tip_height = Some(height));
+    }
+
+    pub fn set_blockchain_ready(&self) {
+        self.update(|state| state.is_blockchain_ready = true);
+    }
+
+    /// Sets the time in seconds when the state of the node was last saved
+    pub fn set_last_saved(&self, timestamp: i64) {
+        self.update(|state| state.last_saved = Some(timestamp));
+    }
+
+    /// Adds the peer to the peers pane, if it's not already there
+    pub fn add_peer(&self, address: SocketAddr) {
+        let address = SocketAddr::new(address.ip().to_canonical(), address.port());
+        self.update(|state| {
+            if !state.peers.iter().any(|peer| peer.address == address) {
+                state.peers.push(PeerRow {
+                    address,
+                    user_agent: String::new(),
+                    latency: None,
+                });
+            }
+        });
+    }
+
+    /// Updates the user agent and the latency of the peer, adding it if it's not already there
+    pub fn set_peer_information(
+        &self,
+        address: SocketAddr,
+        user_agent: &str,
+        latency: Option<Duration>,
+    ) {
+        self.add_peer(address);
+        self.update(|state| {
+            if let Some(peer) = state.peers.iter_mut().find(|peer| peer.address == address) {
+                peer.user_agent = user_agent.to_string();
+                peer.latency = latency.or(peer.latency);
+            }
+        });
+    }
+
+    pub fn set_peer_latency(&self, address: SocketAddr, latency: Duration) {
+        self.update(|state| {
+            if let Some(peer) = state.peers.iter_mut().find(|peer| peer.address == address) {
+                peer.latency = Some(latency);
+            }
+        });
+    }
+
+    pub fn remove_peer(&self, address: SocketAddr) {
+        self.update(|state| state.peers.retain(|peer| peer.address != address));
+    }
+
+    /// Sets the wallet in use, forgetting the balances of the previous one when it changes
+    pub fn set_wallet_name(&self, wallet_name: &str) {
+        self.update(|state| {
+            if state.wallet_name.as_deref() != Some(wallet_name) {
+                state.wallet_name = Some(wallet_name.to_string());
+                state.selected_account = None;
+                state.balances.clear();
+            }
+        });
+    }
+
+    pub fn set_selected_account(&self, account_name: &str) {
+        self.update(|state| state.selected_account = Some(account_name.to_string()));
+    }
+
+    /// Changes the name of an account in its balance and in the selected account
+    pub fn rename_account(&self, account_name: &str, new_account_name: &str) {
+        self.update(|state| {
+            if state.selected_account.as_deref() == Some(account_name) {
+                state.selected_account = Some(new_account_name.to_string());
+            }
+            for (name, _, _) in state.balances.iter_mut() {
+                if name == account_name {
+                    *name = new_account_name.to_string();
+                }
+            }
+        });
+    }
+
+    /// Sets the available and pending balance, in bitcoins, of the account
+    pub fn set_balance(&self, account_name: &str, balance: Amount, pending: Amount) {
+        self.update(|state| {
+            match state
+                .balances
+                .iter_mut()
+                .find(|(name, _, _)| name == account_name)
+            {
+                Some(account_balance) => {
+                    *account_balance = (account_name.to_string(), balance, pending)
+                }
+                None => state
+                    .balances
+                    .push((account_name.to_string(), balance, pending)),
+            }
+        });
+    }
+
+    pub fn set_exchange_rate(&self, exchange_rate: ExchangeRate) {
+        self.update(|state| state.exchange_rate = Some(exchange_rate));
+    }
+
+    /// Draws the panes and the command palette without moving the cursor from the prompt. The
+    /// whole screen is cleared and the prompt is drawn again when it's resized
+    fn draw(&self, state: &mut DashboardState, is_resized: bool) {
+        let logs = self.log_buffer.lines();
+        let mut screen = String::new();
+
+        if is_resized {
+            screen.push_str(CLEAR_SCREEN);
+        }
+        screen.push_str(SAVE_CURSOR);
+        for (index, row) in render(state, &logs).iter().enumerate() {
+            screen.push_str(&format!("\x1b[{};1H{CLEAR_LINE}{row}", index + 1));
+        }
+        screen.push_str(RESTORE_CURSOR);
+
+        print!("{screen}");
+        let _ = stdout().flush();
+        state.needs_redraw = false;
+
+        if is_resized {
+            draw_prompt(state);
+        }
+    }
+}
+
+/// Returns the width and the height of the terminal, as given by `stty`, or the default size if it
+/// cannot be known
+fn terminal_size() -> (usize, usize) {
+    let output = match Command::new("stty")
+        .arg("size")
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+    {
+        Ok(output) => String::from_utf8_lossy(&output.stdout).to_string(),
+        Err(_) => return DEFAULT_SIZE,
+    };
+
+    let mut values = output
+        .split_whitespace()
+        .filter_map(|value| value.parse::<usize>().ok());
+    match (values.next(), values.next()) {
+        (Some(height), Some(width)) => (width.max(MIN_SIZE.0), height.max(MIN_SIZE.1)),
+        _ => DEFAULT_SIZE,
+    }
+}
+
+/// Reads a line of the standard input in another thread, so the wait for the user ends when a
+/// shutdown is requested. The line is only read when it's asked for, leaving the terminal to the
+/// other commands in the meantime
+///
+/// ### Error
+///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
+///  * `ErrorUI::Interrupted`: It will appear when a shutdown is requested before the user answers
+fn read_answer() -> Result<String, ErrorUI> {
+    let (sender, receiver) = channel();
+    thread::spawn(move || {
+        let mut answer = String::new();
+        let _ = sender.send(stdin().read_line(&mut answer).map(|_| answer));
+    });
+
+    loop {
+        if shutdown::is_shutdown_requested() {
+            return Err(ErrorUI::Interrupted);
+        }
+
+        match receiver.recv_timeout(SHUTDOWN_POLL_INTERVAL) {
+            Ok(Ok(answer)) => return Ok(answer.trim().to_string()),
+            Ok(Err(_)) | Err(RecvTimeoutError::Disconnected) => {
+                return Err(ErrorUI::TerminalReadFail)
+            }
+            Err(RecvTimeoutError::Timeout) => continue,
+        }
+    }
+}
+
+/// Writes the question in the row of the prompt, leaving the cursor after it. The last row is
+/// left empty so the terminal does not scroll when the answer is entered
+fn draw_prompt(state: &DashboardState) {
+    let (_, height) = state.size;
+    print!(
+        "\x1b[{};1H{CLEAR_LINE}{}",
+        height - 1,
+        fit(&state.question, state.size.0, ' ').trim_end()
+    );
+    if !state.question.is_empty() {
+        print!(" ");
+    }
+    let _ = stdout().flush();
+}
+
+/// Returns the rows of the screen above the prompt
+fn render(state: &DashboardState, logs: &[String]) -> Vec<String> {
+    let (width, height) = state.size;
+    let palette = wrap_palette(&state.palette, width);
+    let output_height = height
+        .saturating_sub(1 + STATUS_PANES_HEIGHT + FEED_PANES_HEIGHT + palette.len() + 2)
+        .max(MIN_OUTPUT_HEIGHT);
+
+    let mut rows = vec![format!(
+        "{REVERSE_VIDEO}{}{RESET_STYLE}",
+        fit(TITLE, width, ' ')
+    )];
+
+    let third = width / 3;
+    let balances_title = match &state.wallet_name {
+        Some(wallet_name) => format!("Balances of {wallet_name}"),
+        None => "Balances".to_string(),
+    };
+    rows.extend(side_by_side(vec![
+        draw_pane("Sync", &sync_lines(state), third, STATUS_PANES_HEIGHT),
+        draw_pane(
+            &format!("Peers ({})", state.peers.len()),
+            &peer_lines(state),
+            third,
+            STATUS_PANES_HEIGHT,
+        ),
+        draw_pane(
+            &balances_title,
+            &balance_lines(state),
+            width - 2 * third,
+            STATUS_PANES_HEIGHT,
+        ),
+    ]));
+
+    let (output_title, output) = &state.output;
+    let output_title = match output_title.is_empty() {
+        true => "Output",
+        false => output_title,
+    };
+    rows.extend(draw_pane(output_title, output, width, output_height));
+
+    let half = width / 2;
+    let shown_lines = FEED_PANES_HEIGHT - 2;
+    let events: Vec<String> = state.events.iter().cloned().collect();
+    rows.extend(side_by_side(vec![
+        draw_pane(
+            "Events",
+            &events[events.len().saturating_sub(shown_lines)..],
+            half,
+            FEED_PANES_HEIGHT,
+        ),
+        draw_pane(
+            "Logs",
+            &logs[logs.len().saturating_sub(shown_lines)..],
+            width - half,
+            FEED_PANES_HEIGHT,
+        ),
+    ]));
+
+    rows.extend(palette);
+    rows
+}
+
+fn sync_lines(state: &DashboardState) -> Vec<String> {
+    let status = match state.is_blockchain_ready {
+        true => "Up to date",
+        false => "Synchronizing",
+    };
+
+    let mut lines = vec![
+        format!("Status: {status}"),
+        format!("Headers received: {}", state.headers),
+    ];
+    if let Some(tip_height) = state.tip_height {
+        lines.push(format!("Tip height: {tip_height}"));
+    }
+    if let Some(last_saved) = state
+        .last_saved
+        .and_then(|timestamp| Utc.timestamp_opt(timestamp, 0).single())
+    {
+        lines.push(format!("Last saved: {}", last_saved.format("%H:%M:%S")));
+    }
+    if let Some(progress) = &state.download {
+        lines.push(format!("Blocks {}", progress_bar(progress.fraction())));
+        lines.push(format!(
+            "  {}/{} at {:.1} blocks/s",
+            progress.blocks_done, progress.blocks_total, progress.blocks_per_second
+        ));
+    }
+    if let Some((blocks_updated, total_blocks)) = state.update {
+        lines.push(format!(
+            "Update {}",
+            progress_bar(blocks_updated as f64 / total_blocks.max(1) as f64)
+        ));
+    }
+    lines
+}
+
+fn peer_lines(state: &DashboardState) -> Vec<String> {
+    state
+        .peers
+        .iter()
+        .map(|peer| {
+            let latency = match peer.latency {
+                Some(latency) => format!("{} ms", latency.as_millis()),
+                None => "-".to_string(),
+            };
+            format!("{} {latency} {}", peer.address, peer.user_agent)
+        })
+        .collect()
+}
+
+fn balance_lines(state: &DashboardState) -> Vec<String> {
+    if state.balances.is_empty() {
+        return vec!["Show the balance to load it".to_string()];
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    for (account_name, balance, pending) in state.balances.iter() {
+        let marker = match state.selected_account.as_deref() == Some(account_name.as_str()) {
+            true => SELECTED_MARKER,
+            false => UNSELECTED_MARKER,
+        };
+        lines.push(format!("{marker}{account_name}: {}", balance.format_btc()));
+
+        let mut detail = format!("    pending: {}", pending.format_btc());
+        if let (Some(exchange_rate), Some(total)) =
+            (&state.exchange_rate, balance.checked_add(*pending))
+        {
+            detail.push_str(&format!(" {}", exchange_rate.format_fiat(total)));
+        }
+        lines.push(detail);
+    }
+    lines
+}
+
+fn progress_bar(fraction: f64) -> String {
+    let fraction = fraction.clamp(0.0, 1.0);
+    let filled = (fraction * PROGRESS_BAR_WIDTH as f64).round() as usize;
+    format!(
+        "[{}{}] {:.1}%",
+        "#".repeat(filled),
+        "-".repeat(PROGRESS_BAR_WIDTH - filled),
+        fraction * 100.0
+    )
+}
+
+/// Distributes the commands in rows of the given width
+fn wrap_palette(commands: &[String], width: usize) -> Vec<String> {
+    let mut rows: Vec<String> = Vec::new();
+    let mut row = String::new();
+    for command in commands {
+        if !row.is_empty() && row.chars().count() + command.chars().count() + 2 > width {
+            rows.push(row);
+            row = String::new();
+        }
+        if !row.is_empty() {
+            row.push_str("  ");
+        }
+        row.push_str(command);
+    }
+    if !row.is_empty() {
+        rows.push(row);
+    }
+    rows
+}
+
+/// Returns the rows of a pane with a border and the title in the top border, showing the first
+/// lines that fit
+fn draw_pane(title: &str, lines: &[String], width: usize, height: usize) -> Vec<String> {
+    let inner_width = width.saturating_sub(2);
+    let inner_height = height.saturating_sub(2);
+
+    let mut rows = vec![format!(
+        "┌{}┐",
+        fit(&format!("─ {title} "), inner_width, '─')
+    )];
+    for index in 0..inner_height {
+        let line = match lines.get(index) {
+            Some(_) if index + 1 == inner_height && lines.len() > inner_height => {
+                format!("... {} more lines", lines.len() - index)
+            }
+            Some(line) => line.to_string(),
+            None => String::new(),
+        };
+        rows.push(format!("│{}│", fit(&line, inner_width, ' ')));
+    }
+    rows.push(format!("└{}┘", "─".repeat(inner_width)));
+    rows
+}
+
+/// Joins the rows of the panes, one next to the other
+fn side_by_side(panes: Vec<Vec<String>>) -> Vec<String> {
+    let height = panes.iter().map(Vec::len).max().unwrap_or(0);
+    (0..height)
+        .map(|index| {
+            panes
+                .iter()
+                .filter_map(|pane| pane.get(index))
+                .cloned()
+                .collect::<String>()
+        })
+        .collect()
+}
+
+/// Cuts the text to the width, or fills it with the character until it has that width
+fn fit(text: &str, width: usize, fill: char) -> String {
+    let mut fitted: String = text.chars().take(width).collect();
+    let length = fitted.chars().count();
+    fitted.push_str(&fill.to_string().repeat(width - length));
+    fitted
+}
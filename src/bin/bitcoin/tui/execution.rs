@@ -1,17 +1,23 @@
-use super::{input_handler_tui::InputHandlerTUI, notifier_tui::NotifierTUI};
+use super::{
+    dashboard::Dashboard, input_handler_script::InputHandlerScript,
+    input_handler_tui::InputHandlerTUI, notifier_script::NotifierScript, notifier_tui::NotifierTUI,
+};
 
 use crate::{
     error_execution::ErrorExecution,
-    process::{backend, load_system::LoadSystem, save_system::SaveSystem},
+    process::{
+        backend::{self, BackendConfigs},
+        load_system::LoadSystem,
+        save_system::SaveSystem,
+    },
 };
 
 use cargosos_bitcoin::{
     block_structure::block_chain::BlockChain,
-    configurations::{
-        connection_config::ConnectionConfig, download_config::DownloadConfig,
-        mode_config::ModeConfig,
-    },
-    logs::logger_sender::LoggerSender,
+    configurations::{language::Language, mode_config::ModeConfig},
+    logs::{log_buffer::LogBuffer, logger_sender::LoggerSender},
+    notifications::wallet_notifier::WalletNotifier,
+    wallet_structure::transaction_options::TransactionOptions,
 };
 
 fn _show_merkle_path(block_chain: &BlockChain, logger: LoggerSender) -> Result<(), ErrorExecution> {
@@ -59,18 +65,63 @@ fn _show_merkle_path(block_chain: &BlockChain, logger: LoggerSender) -> Result<(
 
 pub fn program_execution(
     mode_config: ModeConfig,
-    connection_config: ConnectionConfig,
-    download_config: DownloadConfig,
+    configs: BackendConfigs,
+    local_ports: (Option<u16>, Option<u16>),
+    load_system: &mut LoadSystem,
+    logger: LoggerSender,
+    (log_buffer, language): (LogBuffer, Language),
+) -> Result<SaveSystem, ErrorExecution> {
+    let dashboard = Dashboard::new(log_buffer);
+    let handle = dashboard.start();
+
+    let notifier = WalletNotifier::new(
+        &load_system.get_wallet_name(),
+        NotifierTUI::new(dashboard.clone(), logger.clone()).with_language(language),
+    );
+    let input_handler = InputHandlerTUI::new(dashboard.clone(), notifier.clone(), logger.clone())
+        .with_transaction_options(TransactionOptions::new(configs.0.replaceable))
+        .with_language(language);
+
+    let result = backend::backend(
+        mode_config,
+        configs,
+        local_ports,
+        load_system,
+        input_handler,
+        notifier,
+        logger,
+    );
+
+    dashboard.stop();
+    if handle.join().is_err() {
+        return Err(ErrorExecution::FailThread);
+    }
+
+    result
+}
+
+/// The main function of the program without asking the user, running the commands of the given
+/// script, or of the standard input when it's `-`. The result of each command is written in the
+/// standard output as JSON, and the program ends when the script does
+pub fn script_execution(
+    script: String,
+    mode_config: ModeConfig,
+    configs: BackendConfigs,
+    local_ports: (Option<u16>, Option<u16>),
     load_system: &mut LoadSystem,
     logger: LoggerSender,
 ) -> Result<SaveSystem, ErrorExecution> {
-    let notifier = NotifierTUI::new(logger.clone());
-    let input_handler = InputHandlerTUI::new(notifier.clone(), logger.clone());
+    let notifier = WalletNotifier::new(
+        &load_system.get_wallet_name(),
+        NotifierScript::new(logger.clone()),
+    );
+    let input_handler = InputHandlerScript::new(script, notifier.clone(), logger.clone())
+        .with_transaction_options(TransactionOptions::new(configs.0.replaceable));
 
     backend::backend(
         mode_config,
-        connection_config,
-        download_config,
+        configs,
+        local_ports,
         load_system,
         input_handler,
         notifier,
@@ -1,35 +1,36 @@
-use crate::ui::{account, error_ui::ErrorUI, from_hexa};
+use super::dashboard::Dashboard;
+
+use crate::ui::{account, error_ui::ErrorUI, from_hexa, raw_dump};
 
 use crate::process::transaction;
 
 use cargosos_bitcoin::{
-    block_structure::utxo_set::UTXOSet,
     block_structure::{
-        block_chain::BlockChain,
-        hash::{HashType, HASH_TYPE_SIZE},
+        block_chain::BlockChain, hash::HashType, mempool_info::MempoolInfo, utxo_set::UTXOSet,
     },
+    connections::network::Network,
     logs::logger_sender::LoggerSender,
     node_structure::broadcasting::Broadcasting,
     notifications::{notification::Notification, notifier::Notifier},
     wallet_structure::{
-        account::Account, address::Address, private_key::PrivateKey, public_key::PublicKey,
-        wallet::Wallet,
+        account::Account, address::Address, amount::Amount, private_key::PrivateKey,
+        public_key::PublicKey, transaction_options::TransactionOptions, wallet::Wallet,
+        wallet_directory::validate_wallet_name,
     },
 };
 
-use std::io::{stdin, Read, Write};
+use std::io::{Read, Write};
 
 /// Get the private key from the terminal
 ///
 /// ### Error
 ///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
-fn get_private_key<N: Notifier>(notifier: N, logger: LoggerSender) -> Result<PrivateKey, ErrorUI> {
-    let mut private_key: String = String::new();
-
-    println!("Enter the private key: ");
-    if stdin().read_line(&mut private_key).is_err() {
-        return Err(ErrorUI::TerminalReadFail);
-    }
+fn get_private_key<N: Notifier>(
+    dashboard: &Dashboard,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<PrivateKey, ErrorUI> {
+    let mut private_key = dashboard.read_line("Enter the private key: ")?;
 
     loop {
         let _: PrivateKey = match PrivateKey::try_from(private_key.trim()) {
@@ -40,11 +41,7 @@ fn get_private_key<N: Notifier>(notifier: N, logger: LoggerSender) -> Result<Pri
             _ => {
                 notifier.notify(Notification::InvalidPrivateKeyEnter);
 
-                private_key.clear();
-                println!("Please enter a valid private key:");
-                if stdin().read_line(&mut private_key).is_err() {
-                    return Err(ErrorUI::TerminalReadFail);
-                }
+                private_key = dashboard.read_line("Please enter a valid private key:")?;
                 continue;
             }
         };
@@ -55,13 +52,12 @@ fn get_private_key<N: Notifier>(notifier: N, logger: LoggerSender) -> Result<Pri
 ///
 /// ### Error
 ///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
-fn get_public_key<N: Notifier>(notifier: N, logger: LoggerSender) -> Result<PublicKey, ErrorUI> {
-    let mut public_key: String = String::new();
-
-    println!("Enter the public key: ");
-    if stdin().read_line(&mut public_key).is_err() {
-        return Err(ErrorUI::TerminalReadFail);
-    }
+fn get_public_key<N: Notifier>(
+    dashboard: &Dashboard,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<PublicKey, ErrorUI> {
+    let mut public_key = dashboard.read_line("Enter the public key: ")?;
 
     loop {
         let _: PublicKey = match PublicKey::try_from(public_key.trim().to_string()) {
@@ -72,11 +68,7 @@ fn get_public_key<N: Notifier>(notifier: N, logger: LoggerSender) -> Result<Publ
             _ => {
                 notifier.notify(Notification::InvalidPublicKeyEnter);
 
-                public_key.clear();
-                println!("Please enter a valid public key:");
-                if stdin().read_line(&mut public_key).is_err() {
-                    return Err(ErrorUI::TerminalReadFail);
-                }
+                public_key = dashboard.read_line("Please enter a valid public key:")?;
 
                 continue;
             }
@@ -89,15 +81,11 @@ fn get_public_key<N: Notifier>(notifier: N, logger: LoggerSender) -> Result<Publ
 /// ### Error
 ///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
 pub(super) fn get_address<N: Notifier>(
+    dashboard: &Dashboard,
     notifier: N,
     logger: LoggerSender,
 ) -> Result<Address, ErrorUI> {
-    let mut address: String = String::new();
-
-    println!("Enter the address: ");
-    if stdin().read_line(&mut address).is_err() {
-        return Err(ErrorUI::TerminalReadFail);
-    }
+    let mut address = dashboard.read_line("Enter the address: ")?;
 
     loop {
         match Address::new(address.trim()) {
@@ -108,11 +96,7 @@ pub(super) fn get_address<N: Notifier>(
             _ => {
                 notifier.notify(Notification::InvalidAddressEnter);
 
-                address.clear();
-                println!("Error, please enter a valid address:");
-                if stdin().read_line(&mut address).is_err() {
-                    return Err(ErrorUI::TerminalReadFail);
-                }
+                address = dashboard.read_line("Error, please enter a valid address:")?;
                 continue;
             }
         };
@@ -123,59 +107,65 @@ pub(super) fn get_address<N: Notifier>(
 ///
 /// ### Error
 ///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
-fn get_account_name() -> Result<String, ErrorUI> {
-    let mut name: String = String::new();
-
-    println!("Enter the name: ");
-    match stdin().read_line(&mut name) {
-        Ok(_) => Ok(name.trim().to_string()),
-        Err(_) => Err(ErrorUI::TerminalReadFail),
-    }
+fn get_account_name(dashboard: &Dashboard) -> Result<String, ErrorUI> {
+    dashboard.read_line("Enter the name: ")
 }
 
+/// Get a hash from the terminal, notifying with the given notification when the hash is invalid
+///
+/// ### Error
+///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
 fn get_hash_id<N: Notifier>(
+    dashboard: &Dashboard,
     hash_type: &str,
+    invalid_notification: fn(String) -> Notification,
     notifier: N,
     logger: LoggerSender,
 ) -> Result<HashType, ErrorUI> {
-    let mut hash: String = String::new();
-
-    println!("Enter the {hash_type}: ");
-    if stdin().read_line(&mut hash).is_err() {
-        return Err(ErrorUI::TerminalReadFail);
-    }
+    let mut hash = dashboard.read_line("Enter the {hash_type}: ")?;
 
     loop {
-        match from_hexa::from::<HASH_TYPE_SIZE>(hash.trim()) {
+        match from_hexa::hash_in_display_order(&hash) {
             Ok(result) => {
                 let _ = logger.log_wallet(format!("Valid {hash_type} entered"));
                 return Ok(result);
             }
             _ => {
-                notifier.notify(
-                    Notification::ProblemVerifyingTransactionMerkleProofOfInclusion(format!(
-                        "Invalid {hash_type} entered"
-                    )),
-                );
-
-                hash.clear();
-                println!("Error, please enter a valid {hash_type}:");
-                if stdin().read_line(&mut hash).is_err() {
-                    return Err(ErrorUI::TerminalReadFail);
-                }
+                notifier.notify(invalid_notification(format!("Invalid {hash_type} entered")));
+
+                hash = dashboard.read_line("Error, please enter a valid {hash_type}:")?;
                 continue;
             }
         };
     }
 }
 
-pub fn create_merkle_proof_of_inclusion<N: Notifier>(
+/// Asks for the hash of a block and the id of one of its transactions, and shows the merkle path
+/// that proves the transaction is included in the block
+///
+/// ### Error
+///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
+pub fn verify_merkle_proof_of_inclusion<N: Notifier>(
+    dashboard: &Dashboard,
     block_chain: &BlockChain,
     notifier: N,
     logger: LoggerSender,
 ) -> Result<(), ErrorUI> {
-    let block_hash = get_hash_id("block hash", notifier.clone(), logger.clone())?;
-    let transaction_id = get_hash_id("transaction id", notifier.clone(), logger.clone())?;
+    let invalid_notification = Notification::ProblemVerifyingTransactionMerkleProofOfInclusion;
+    let block_hash = get_hash_id(
+        dashboard,
+        "block hash",
+        invalid_notification,
+        notifier.clone(),
+        logger.clone(),
+    )?;
+    let transaction_id = get_hash_id(
+        dashboard,
+        "transaction id",
+        invalid_notification,
+        notifier.clone(),
+        logger.clone(),
+    )?;
 
     transaction::verify_transaction_merkle_proof_of_inclusion(
         block_chain,
@@ -188,31 +178,168 @@ pub fn create_merkle_proof_of_inclusion<N: Notifier>(
     Ok(())
 }
 
+/// Shows the raw data of the block with the hash entered by the user
+///
+/// ### Error
+///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
+pub fn dump_block<N: Notifier>(
+    dashboard: &Dashboard,
+    block_chain: &BlockChain,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let block_hash = get_hash_id(
+        dashboard,
+        "block hash",
+        Notification::RawDataProblem,
+        notifier.clone(),
+        logger.clone(),
+    )?;
+
+    raw_dump::give_raw_block(block_chain, block_hash, notifier, logger);
+
+    Ok(())
+}
+
+/// Shows the raw data of the transaction with the id entered by the user
+///
+/// ### Error
+///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
+pub fn dump_transaction<N: Notifier>(
+    dashboard: &Dashboard,
+    block_chain: &BlockChain,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let transaction_id = get_hash_id(
+        dashboard,
+        "transaction id",
+        Notification::RawDataProblem,
+        notifier.clone(),
+        logger.clone(),
+    )?;
+
+    raw_dump::give_raw_transaction(block_chain, transaction_id, notifier, logger);
+
+    Ok(())
+}
+
 /// Creates a new account with the data entered by the user
 ///
 /// ### Error
 ///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
 pub fn create_account<N: Notifier>(
+    dashboard: &Dashboard,
     wallet: &mut Wallet,
     notifier: N,
     logger: LoggerSender,
 ) -> Result<(), ErrorUI> {
     let _ = logger.log_wallet("Creating a new account".to_string());
 
-    let private_key = get_private_key(notifier.clone(), logger.clone())?;
-    let public_key = get_public_key(notifier.clone(), logger)?;
-    let account_name = get_account_name()?;
+    let private_key = get_private_key(dashboard, notifier.clone(), logger.clone())?;
+    let public_key = get_public_key(dashboard, notifier.clone(), logger)?;
+    let account_name = get_account_name(dashboard)?;
 
     account::create_account(wallet, &account_name, private_key, public_key, notifier)
 }
 
+/// Creates a new account with the name entered by the user and a pair of keys generated by the wallet
+///
+/// ### Error
+///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
+pub fn generate_account<N: Notifier>(
+    dashboard: &Dashboard,
+    wallet: &mut Wallet,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let _ = logger.log_wallet("Generating a new account".to_string());
+
+    let account_name = get_account_name(dashboard)?;
+    account::generate_account(wallet, &account_name, notifier);
+
+    Ok(())
+}
+
+/// Changes the name, label and description of the account selected by the user. An empty answer
+/// keeps the value and a `-` removes the label or description
+///
+/// ### Error
+///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
+pub fn edit_account<N: Notifier>(
+    dashboard: &Dashboard,
+    wallet: &mut Wallet,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let account = select_account(dashboard, wallet, logger.clone())?;
+    let _ = logger.log_wallet(format!("Editing the account {}", account.account_name));
+
+    let new_name = get_edited_value(dashboard, "Enter the new name (empty keeps it): ")?;
+    let label = get_edited_value(
+        dashboard,
+        "Enter the label (empty keeps it, - removes it): ",
+    )?;
+    let description = get_edited_value(
+        dashboard,
+        "Enter the description (empty keeps it, - removes it): ",
+    )?;
+
+    account::edit_account(
+        wallet,
+        &account.account_name,
+        new_name,
+        label,
+        description,
+        notifier,
+    );
+
+    Ok(())
+}
+
+/// Changes the label of the transaction with the id entered by the user. An empty label removes it
+///
+/// ### Error
+///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
+pub fn label_transaction<N: Notifier>(
+    dashboard: &Dashboard,
+    wallet: &mut Wallet,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let transaction_id = get_hash_id(
+        dashboard,
+        "transaction id",
+        Notification::RawDataProblem,
+        notifier.clone(),
+        logger.clone(),
+    )?;
+    let label = dashboard.read_line("Enter the label (empty removes it): ")?;
+
+    account::label_transaction(wallet, transaction_id, Some(label), notifier, logger);
+
+    Ok(())
+}
+
+/// Get a new value for a field of an account from the terminal
+///
+/// ### Error
+///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
+fn get_edited_value(dashboard: &Dashboard, question: &str) -> Result<Option<String>, ErrorUI> {
+    let value = dashboard.read_line(question)?;
+    Ok(account::edited_value(&value))
+}
+
 /// Delete the selected account selected by the user
 ///
 /// ### Error
 ///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
-///  * `ErrorUI::CannotUnwrapArc`: It will appear when we try to unwrap an Arc
-pub fn remove_account(wallet: &mut Wallet, logger: LoggerSender) -> Result<(), ErrorUI> {
-    let account = select_account(wallet, logger)?;
+pub fn remove_account(
+    dashboard: &Dashboard,
+    wallet: &mut Wallet,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let account = select_account(dashboard, wallet, logger)?;
     wallet.remove_account(account);
 
     Ok(())
@@ -222,23 +349,17 @@ pub fn remove_account(wallet: &mut Wallet, logger: LoggerSender) -> Result<(), E
 ///
 /// ### Error
 ///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
-///  * `ErrorUI::CannotUnwrapArc`: It will appear when we try to unwrap an Arc
 pub fn change_account<N: Notifier>(
+    dashboard: &Dashboard,
     wallet: &mut Wallet,
     notifier: N,
     logger: LoggerSender,
 ) -> Result<(), ErrorUI> {
     let _ = logger.log_wallet("Selecting an account".to_string());
 
-    println!("Possible accounts: ");
-    show_accounts(wallet, logger.clone());
+    show_accounts(dashboard, wallet, logger.clone());
 
-    let mut account_name: String = String::new();
-
-    println!("Enter the name: ");
-    if stdin().read_line(&mut account_name).is_err() {
-        return Err(ErrorUI::TerminalReadFail);
-    }
+    let mut account_name = dashboard.read_line("Enter the name: ")?;
 
     while account::change_selected_account(
         account_name.trim().to_string(),
@@ -249,11 +370,7 @@ pub fn change_account<N: Notifier>(
     {
         let _ = logger.log_wallet("Invalid account name entered".to_string());
 
-        account_name.clear();
-        println!("Error, please enter a valid account name:");
-        if stdin().read_line(&mut account_name).is_err() {
-            return Err(ErrorUI::TerminalReadFail);
-        }
+        account_name = dashboard.read_line("Error, please enter a valid account name:")?;
     }
 
     Ok(())
@@ -274,18 +391,16 @@ fn get_account_from_name(account_name: &str, wallet: &Wallet) -> Option<Account>
 ///
 /// ### Error
 ///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
-pub fn select_account(wallet: &Wallet, logger: LoggerSender) -> Result<Account, ErrorUI> {
+pub fn select_account(
+    dashboard: &Dashboard,
+    wallet: &Wallet,
+    logger: LoggerSender,
+) -> Result<Account, ErrorUI> {
     let _ = logger.log_wallet("Selecting an account".to_string());
 
-    println!("Possible accounts: ");
-    show_accounts(wallet, logger.clone());
-
-    let mut account_name: String = String::new();
+    show_accounts(dashboard, wallet, logger.clone());
 
-    println!("Enter the name: ");
-    if stdin().read_line(&mut account_name).is_err() {
-        return Err(ErrorUI::TerminalReadFail);
-    }
+    let mut account_name = dashboard.read_line("Enter the name: ")?;
 
     loop {
         match get_account_from_name(account_name.trim(), wallet) {
@@ -296,23 +411,20 @@ pub fn select_account(wallet: &Wallet, logger: LoggerSender) -> Result<Account,
             None => {
                 let _ = logger.log_wallet("Invalid account name entered".to_string());
 
-                account_name.clear();
-                println!("Error, please enter a valid account name:");
-                if stdin().read_line(&mut account_name).is_err() {
-                    return Err(ErrorUI::TerminalReadFail);
-                }
+                account_name = dashboard.read_line("Error, please enter a valid account name:")?;
                 continue;
             }
         };
     }
 }
 
-/// Show all accounts from the wallet
-pub fn show_accounts(wallet: &Wallet, logger: LoggerSender) {
+/// Show all accounts from the wallet in the output of the dashboard
+pub fn show_accounts(dashboard: &Dashboard, wallet: &Wallet, logger: LoggerSender) {
     let _ = logger.log_wallet("Showing accounts".to_string());
 
     let possible_selected_account = wallet.get_selected_account();
 
+    let mut message_accounts = "".to_string();
     wallet.get_accounts().iter().for_each(|account| {
         let mut selected = "";
         if let Some(selected_account) = possible_selected_account {
@@ -321,25 +433,40 @@ pub fn show_accounts(wallet: &Wallet, logger: LoggerSender) {
             }
         }
 
-        println!("{selected} {account}\n");
+        message_accounts.push_str(&format!("{selected} {account}\n"));
     });
+    dashboard.show_output("Possible accounts", &message_accounts);
+}
+
+/// Show the statistics of the pending transactions in the output of the dashboard
+pub fn show_mempool_info(dashboard: &Dashboard, utxo_set: &UTXOSet, logger: LoggerSender) {
+    let _ = logger.log_node("Showing the mempool information".to_string());
+
+    let mempool_info = MempoolInfo::from_utxo_set(utxo_set);
+    let min_fee_rate = match mempool_info.min_fee_rate {
+        Some(min_fee_rate) => format!("{min_fee_rate} sat/kB"),
+        None => "-".to_string(),
+    };
+
+    dashboard.show_output(
+        "Mempool information",
+        &format!(
+            "Transactions: {}\nSize: {} bytes\nTotal fees: {} sat\nMinimum fee rate: {min_fee_rate}\n",
+            mempool_info.transaction_count, mempool_info.total_size, mempool_info.total_fees
+        ),
+    );
 }
 
 /// Get the amount for the transaction from the terminal
 ///
 /// ### Error
 ///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
-fn get_amount(logger: LoggerSender) -> Result<f64, ErrorUI> {
-    let mut amount: String = String::new();
-
-    println!("Enter an amount: ");
-    if stdin().read_line(&mut amount).is_err() {
-        return Err(ErrorUI::TerminalReadFail);
-    }
+fn get_amount(dashboard: &Dashboard, logger: LoggerSender) -> Result<Amount, ErrorUI> {
+    let mut amount = dashboard.read_line("Enter an amount (like 0.001 or 100000 sat): ")?;
 
     loop {
-        match amount.trim().parse::<f64>() {
-            Ok(result) => {
+        match amount.trim().parse::<Amount>() {
+            Ok(result) if result > Amount::ZERO => {
                 let _ = logger.log_wallet("Valid amount entered".to_string());
                 return Ok(result);
             }
@@ -347,11 +474,15 @@ fn get_amount(logger: LoggerSender) -> Result<f64, ErrorUI> {
                 let _ =
                     logger.log_wallet(format!("Invalid amount entered, with error: {:?}", error));
 
-                amount.clear();
-                println!("Error, please enter a valid amount:");
-                if stdin().read_line(&mut amount).is_err() {
-                    return Err(ErrorUI::TerminalReadFail);
-                }
+                amount = dashboard.read_line("Error, please enter a valid amount:")?;
+
+                continue;
+            }
+            Ok(_) => {
+                let _ =
+                    logger.log_wallet("Invalid amount entered, it must be positive".to_string());
+
+                amount = dashboard.read_line("Error, please enter a valid amount:")?;
 
                 continue;
             }
@@ -363,16 +494,11 @@ fn get_amount(logger: LoggerSender) -> Result<f64, ErrorUI> {
 ///
 /// ### Error
 ///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
-fn get_fee(logger: LoggerSender) -> Result<f64, ErrorUI> {
-    let mut fee: String = String::new();
-
-    println!("Enter a fee: ");
-    if stdin().read_line(&mut fee).is_err() {
-        return Err(ErrorUI::TerminalReadFail);
-    }
+fn get_fee(dashboard: &Dashboard, logger: LoggerSender) -> Result<Amount, ErrorUI> {
+    let mut fee = dashboard.read_line("Enter a fee (like 0.0001 or 10000 sat): ")?;
 
     loop {
-        match fee.trim().parse::<f64>() {
+        match fee.trim().parse::<Amount>() {
             Ok(result) => {
                 let _ = logger.log_wallet("Valid fee entered".to_string());
                 return Ok(result);
@@ -380,11 +506,60 @@ fn get_fee(logger: LoggerSender) -> Result<f64, ErrorUI> {
             Err(error) => {
                 let _ = logger.log_wallet(format!("Invalid fee entered, with error: {:?}", error));
 
-                fee.clear();
-                println!("Error, please enter a valid fee:");
-                if stdin().read_line(&mut fee).is_err() {
-                    return Err(ErrorUI::TerminalReadFail);
-                }
+                fee = dashboard.read_line("Error, please enter a valid fee:")?;
+
+                continue;
+            }
+        };
+    }
+}
+
+/// Get the height from which to show the balance history from the terminal
+///
+/// ### Error
+///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
+pub fn get_from_height(dashboard: &Dashboard, logger: LoggerSender) -> Result<u64, ErrorUI> {
+    let mut height = dashboard.read_line("Enter the height from which to show the balance: ")?;
+
+    loop {
+        match height.trim().parse::<u64>() {
+            Ok(result) => {
+                let _ = logger.log_wallet("Valid height entered".to_string());
+                return Ok(result);
+            }
+            Err(error) => {
+                let _ =
+                    logger.log_wallet(format!("Invalid height entered, with error: {:?}", error));
+
+                height = dashboard.read_line("Error, please enter a valid height:")?;
+
+                continue;
+            }
+        };
+    }
+}
+
+/// Get the height, or the timestamp of the first block, from which to rescan the wallet from the terminal
+///
+/// ### Error
+///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
+pub fn get_rescan_start(dashboard: &Dashboard, logger: LoggerSender) -> Result<u64, ErrorUI> {
+    let mut start = dashboard
+        .read_line("Enter the height, or the timestamp of the first block, to rescan from: ")?;
+
+    loop {
+        match start.trim().parse::<u64>() {
+            Ok(result) => {
+                let _ = logger.log_wallet("Valid rescan start entered".to_string());
+                return Ok(result);
+            }
+            Err(error) => {
+                let _ = logger.log_wallet(format!(
+                    "Invalid rescan start entered, with error: {:?}",
+                    error
+                ));
+
+                start = dashboard.read_line("Error, please enter a valid height or timestamp:")?;
 
                 continue;
             }
@@ -392,29 +567,255 @@ fn get_fee(logger: LoggerSender) -> Result<f64, ErrorUI> {
     }
 }
 
-/// Broadcast the transaction created by the user to the peers from the selected account in the wallet
+/// Get the name of the wallet to use from the terminal
+///
+/// ### Error
+///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
+pub fn get_wallet_name(dashboard: &Dashboard, logger: LoggerSender) -> Result<String, ErrorUI> {
+    let mut wallet_name = dashboard.read_line("Enter the name of the wallet: ")?;
+
+    while let Err(error) = validate_wallet_name(wallet_name.trim()) {
+        let _ = logger.log_wallet(format!(
+            "Invalid wallet name entered, with error: {:?}",
+            error
+        ));
+
+        wallet_name = dashboard.read_line("Error, please enter a valid wallet name:")?;
+    }
+
+    Ok(wallet_name.trim().to_string())
+}
+
+/// Writes a backup of the wallet in the path entered by the user, asking for the spending password
+/// when the wallet is locked
+///
+/// ### Error
+///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
+pub fn export_wallet<N: Notifier>(
+    dashboard: &Dashboard,
+    wallet: &mut Wallet,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let _ = logger.log_wallet("Exporting a backup of the wallet".to_string());
+
+    if wallet.is_locked() {
+        let password = get_spending_password(dashboard, "Enter the spending password: ")?;
+        if !account::unlock_wallet(wallet, password.as_deref(), notifier.clone()) {
+            return Ok(());
+        }
+    }
+
+    let path = get_backup_path(dashboard)?;
+    let password = get_backup_password(dashboard)?;
+    account::export_wallet(wallet, &path, password.as_deref(), notifier);
+
+    Ok(())
+}
+
+/// Adds to the wallet the accounts of the backup in the path entered by the user
+///
+/// ### Error
+///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
+pub fn import_wallet<N: Notifier>(
+    dashboard: &Dashboard,
+    wallet: &mut Wallet,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let _ = logger.log_wallet("Importing a backup of the wallet".to_string());
+
+    let path = get_backup_path(dashboard)?;
+    let password = get_backup_password(dashboard)?;
+    account::import_wallet(wallet, &path, password.as_deref(), notifier);
+
+    Ok(())
+}
+
+/// Changes the spending password of the wallet, asking for the current one when it has one
+///
+/// ### Error
+///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
+pub fn change_spending_password<N: Notifier>(
+    dashboard: &Dashboard,
+    wallet: &mut Wallet,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let _ = logger.log_wallet("Changing the spending password".to_string());
+
+    let password = match wallet.has_spending_password() {
+        true => get_spending_password(dashboard, "Enter the current spending password: ")?,
+        false => None,
+    };
+    let new_password = get_spending_password(
+        dashboard,
+        "Enter the new spending password (empty removes it): ",
+    )?;
+    account::set_spending_password(
+        wallet,
+        password.as_deref(),
+        new_password.as_deref(),
+        notifier,
+    );
+
+    Ok(())
+}
+
+/// Get a spending password from the terminal, being `None` when it's empty
+///
+/// ### Error
+///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
+fn get_spending_password(dashboard: &Dashboard, question: &str) -> Result<Option<String>, ErrorUI> {
+    let password = dashboard.read_line(question)?;
+    match password.trim() {
+        "" => Ok(None),
+        password => Ok(Some(password.to_string())),
+    }
+}
+
+/// Get the path of the backup of a wallet from the terminal
+///
+/// ### Error
+///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
+fn get_backup_path(dashboard: &Dashboard) -> Result<String, ErrorUI> {
+    let mut path = dashboard.read_line("Enter the path of the backup: ")?;
+
+    while path.trim().is_empty() {
+        path = dashboard.read_line("Error, please enter a path:")?;
+    }
+
+    Ok(path.trim().to_string())
+}
+
+/// Get the password of the backup of a wallet from the terminal, being `None` when it's empty
+///
+/// ### Error
+///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
+fn get_backup_password(dashboard: &Dashboard) -> Result<Option<String>, ErrorUI> {
+    let password = dashboard.read_line("Enter the password of the keys (empty for none): ")?;
+    match password.trim() {
+        "" => Ok(None),
+        password => Ok(Some(password.to_string())),
+    }
+}
+
+/// Get a transaction in hexadecimal from the terminal and show its inputs and outputs, with the
+/// addresses of the given network
+///
+/// ### Error
+///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
+pub fn describe_raw_transaction<N: Notifier>(
+    dashboard: &Dashboard,
+    utxo_set: &UTXOSet,
+    network: Network,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let raw_transaction = dashboard.read_line("Enter the raw transaction in hexadecimal: ")?;
+
+    transaction::describe_raw_transaction(utxo_set, &raw_transaction, network, notifier, logger);
+    Ok(())
+}
+
+/// Get a transaction in hexadecimal from the terminal and broadcast it to the peers, checking it
+/// against the UTXO set first if the user wants to
 ///
 /// ### Error
 ///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
-///  * `ErrorUI::CannotUnwrapArc`: It will appear when we try to unwrap an Arc
 ///  * `ErrorUI::ErrorFromPeer`: It will appear when a conextion with a peer fails
-pub fn sending_transaction<N: Notifier, RW: Read + Write + Send + 'static>(
+pub fn broadcast_raw_transaction<N: Notifier, RW: Read + Write + Send + 'static>(
+    dashboard: &Dashboard,
+    broadcasting: &mut Broadcasting<RW>,
+    utxo_set: &mut UTXOSet,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let raw_transaction = dashboard.read_line("Enter the raw transaction in hexadecimal: ")?;
+
+    let mut validate =
+        dashboard.read_line("Check it against the UTXO set before sending it? [y/n]: ")?;
+
+    while !matches!(validate.trim(), "y" | "n") {
+        validate = dashboard.read_line("Error, please enter y or n:")?;
+    }
+
+    transaction::broadcast_raw_transaction(
+        broadcasting,
+        utxo_set,
+        &raw_transaction,
+        validate.trim() == "y",
+        notifier,
+        logger,
+    )
+}
+
+/// Get a private key in WIF from the terminal and send everything its address has, minus the fee,
+/// to the selected account of the wallet
+///
+/// ### Error
+///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
+///  * `ErrorUI::ErrorFromPeer`: It will appear when a conextion with a peer fails
+pub fn sweep_private_key<N: Notifier, RW: Read + Write + Send + 'static>(
+    dashboard: &Dashboard,
     broadcasting: &mut Broadcasting<RW>,
     wallet: &Wallet,
     utxo_set: &mut UTXOSet,
     notifier: N,
     logger: LoggerSender,
 ) -> Result<(), ErrorUI> {
-    let address = get_address(notifier.clone(), logger.clone())?;
-    let amount = get_amount(logger.clone())?;
-    let fee = get_fee(logger.clone())?;
+    let mut wif = dashboard.read_line("Enter the private key to sweep in WIF: ")?;
+
+    while wif.trim().is_empty() {
+        wif = dashboard.read_line("Error, please enter a private key:")?;
+    }
+
+    let fee = get_fee(dashboard, logger.clone())?;
+
+    transaction::sweep_private_key(
+        broadcasting,
+        wallet,
+        utxo_set,
+        wif.trim(),
+        fee,
+        notifier,
+        logger,
+    )
+}
+
+/// Broadcast the transaction created by the user to the peers from the selected account in the wallet,
+/// with the lock time and replaceability of the options. When the wallet is locked, the spending
+/// password is asked to sign it
+///
+/// ### Error
+///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
+///  * `ErrorUI::ErrorFromPeer`: It will appear when a conextion with a peer fails
+pub fn sending_transaction<N: Notifier, RW: Read + Write + Send + 'static>(
+    dashboard: &Dashboard,
+    broadcasting: &mut Broadcasting<RW>,
+    wallet: &mut Wallet,
+    utxo_set: &mut UTXOSet,
+    options: TransactionOptions,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let address = get_address(dashboard, notifier.clone(), logger.clone())?;
+    let amount = get_amount(dashboard, logger.clone())?;
+    let fee = get_fee(dashboard, logger.clone())?;
+
+    if wallet.is_locked() {
+        let password = get_spending_password(dashboard, "Enter the spending password: ")?;
+        if !account::unlock_wallet(wallet, password.as_deref(), notifier.clone()) {
+            return Ok(());
+        }
+    }
 
     transaction::sending_transaction(
         broadcasting,
         wallet,
         utxo_set,
         address,
-        (amount, fee),
+        (amount, fee, options),
         notifier,
         logger,
     )
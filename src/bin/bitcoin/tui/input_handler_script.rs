@@ -0,0 +1,312 @@
+use super::{notifier_script::RecordingNotifier, script_command::ScriptCommand};
+
+use crate::{
+    process::{
+        broadcasting::WalletState,
+        reference::{get_reference, MutArc},
+        shutdown, transaction,
+    },
+    ui::{account, error_ui::ErrorUI, input_handler::InputHandler},
+};
+
+use cargosos_bitcoin::{
+    block_structure::{block_chain::BlockChain, hash, utxo_set::UTXOSet},
+    logs::logger_sender::LoggerSender,
+    node_structure::{broadcasting::Broadcasting, script_subscriptions::ScriptSubscriptions},
+    notifications::{notification::Notification, notification_json::quote, notifier::Notifier},
+    wallet_structure::{
+        address::Address, private_key::PrivateKey, public_key::PublicKey,
+        transaction_options::TransactionOptions, wallet::Wallet, wallet_directory::WalletDirectory,
+    },
+};
+
+use std::{
+    fs::File,
+    io::{stdin, stdout, BufRead, BufReader, Read, Write},
+};
+
+/// The name of the script to read the commands from the standard input
+pub const STDIN_SCRIPT: &str = "-";
+
+const COMMENT: char = '#';
+
+type Fields = Vec<(&'static str, String)>;
+
+/// Struct that handles the commands of a script, without asking the user for anything. The result
+/// of every command is written in the standard output as a JSON object per line, with the name of
+/// the command, its status and its values or the reason of the error. The failed commands don't
+/// stop the script, which ends with the `exit` command, when there are no more lines or when a
+/// signal stops the program
+pub struct InputHandlerScript<N>
+where
+    N: Notifier,
+{
+    script: String,
+    transaction_options: TransactionOptions,
+    notifier: N,
+    logger: LoggerSender,
+}
+
+impl<N: Notifier> InputHandlerScript<N> {
+    /// Creates the handler of the script in the given path, or of the standard input when it's `-`
+    pub fn new(script: String, notifier: N, logger: LoggerSender) -> Self {
+        Self {
+            script,
+            transaction_options: TransactionOptions::default(),
+            notifier,
+            logger,
+        }
+    }
+
+    /// The transactions sent by the script have the lock time and replaceability of the options
+    pub fn with_transaction_options(self, transaction_options: TransactionOptions) -> Self {
+        Self {
+            transaction_options,
+            ..self
+        }
+    }
+
+    /// Opens the script to read its lines
+    ///
+    /// ### Error
+    ///  * `ErrorUI::ErrorReading`: It will appear when the script cannot be opened
+    fn open_script(&self) -> Result<Box<dyn BufRead>, ErrorUI> {
+        match self.script.as_str() {
+            STDIN_SCRIPT => Ok(Box::new(BufReader::new(stdin()))),
+            path => match File::open(path) {
+                Ok(file) => Ok(Box::new(BufReader::new(file))),
+                Err(error) => Err(ErrorUI::ErrorReading(format!(
+                    "Cannot open the script {path}, with error: {:?}",
+                    error
+                ))),
+            },
+        }
+    }
+
+    /// Runs the command and returns the values of its result
+    ///
+    /// ### Error
+    ///  * `ErrorUI::ErrorReading`: It will appear when a value of the command is not valid
+    ///  * `ErrorUI::ErrorFromWallet`: It will appear when the wallet cannot do what the command asks
+    ///  * `ErrorUI::TransactionWithoutSufficientFunds`: It will appear when the selected account cannot pay the amount and the fee
+    ///  * `ErrorUI::ErrorFromPeer`: It will appear when the transaction cannot be sent to the peers
+    fn execute<RW: Read + Write + Send + 'static>(
+        &self,
+        command: ScriptCommand,
+        broadcasting: &MutArc<Broadcasting<RW>>,
+        wallet: &MutArc<Wallet>,
+        utxo_set: &MutArc<UTXOSet>,
+    ) -> Result<Fields, ErrorUI> {
+        let notifier = RecordingNotifier::new(self.notifier.clone());
+
+        match command {
+            ScriptCommand::CreateAccount {
+                private_key,
+                public_key,
+                name,
+            } => {
+                let private_key = match PrivateKey::try_from(private_key.as_str()) {
+                    Ok(private_key) => private_key,
+                    Err(_) => return Err(ErrorUI::ErrorReading("Invalid private key".to_string())),
+                };
+                let public_key = match PublicKey::try_from(public_key) {
+                    Ok(public_key) => public_key,
+                    Err(_) => return Err(ErrorUI::ErrorReading("Invalid public key".to_string())),
+                };
+
+                let mut wallet_reference = get_reference(wallet)?;
+                account::create_account(
+                    &mut wallet_reference,
+                    &name,
+                    private_key,
+                    public_key,
+                    notifier.clone(),
+                )?;
+            }
+            ScriptCommand::GenerateAccount(name) => {
+                let mut wallet_reference = get_reference(wallet)?;
+                account::generate_account(&mut wallet_reference, &name, notifier.clone());
+            }
+            ScriptCommand::SelectAccount(name) => {
+                let mut wallet_reference = get_reference(wallet)?;
+                account::change_selected_account(name, &mut wallet_reference, notifier.clone())?;
+            }
+            ScriptCommand::Send {
+                address,
+                amount,
+                fee,
+            } => {
+                let address = match Address::new(&address) {
+                    Ok(address) => address,
+                    Err(_) => return Err(ErrorUI::ErrorReading("Invalid address".to_string())),
+                };
+
+                let wallet_reference = get_reference(wallet)?;
+                let mut utxo_set_reference = get_reference(utxo_set)?;
+                let mut broadcasting_reference = get_reference(broadcasting)?;
+                transaction::sending_transaction(
+                    &mut broadcasting_reference,
+                    &wallet_reference,
+                    &mut utxo_set_reference,
+                    address,
+                    (amount, fee, self.transaction_options),
+                    notifier.clone(),
+                    self.logger.clone(),
+                )?;
+            }
+            ScriptCommand::Balance => {
+                let wallet_reference = get_reference(wallet)?;
+                let utxo_set_reference = get_reference(utxo_set)?;
+                account::give_account_balance(
+                    &wallet_reference,
+                    &utxo_set_reference,
+                    notifier.clone(),
+                );
+            }
+            ScriptCommand::Exit => return Ok(Vec::new()),
+        }
+
+        get_result(notifier.take_notifications())
+    }
+}
+
+/// Returns the values of the result of a command from the notifications it sent
+///
+/// ### Error
+///  * `ErrorUI::ErrorFromWallet`: It will appear when one of the notifications is a problem of the wallet
+fn get_result(notifications: Vec<Notification>) -> Result<Fields, ErrorUI> {
+    let mut fields: Fields = Vec::new();
+
+    for notification in notifications {
+        match notification {
+            Notification::RegisterWalletAccount(account)
+            | Notification::UpdatedSelectedAccount(account) => {
+                fields.push(("account", quote(&account.account_name)));
+                fields.push(("address", quote(&account.address.to_string())));
+            }
+            Notification::LoadAvailableBalance(account, balance, pending) => {
+                fields.push(("account", quote(&account.account_name)));
+                fields.push(("balance", balance.format_btc()));
+                fields.push(("pending", pending.format_btc()));
+            }
+            Notification::SuccessfullySentTransaction(transaction) => {
+                let transaction_id = match transaction.get_tx_id() {
+                    Ok(transaction_id) => quote(&hash::to_hex_be(&transaction_id)),
+                    Err(_) => "null".to_string(),
+                };
+                fields.push(("transaction_id", transaction_id));
+            }
+            Notification::AccountNotSelected => {
+                return Err(ErrorUI::ErrorFromWallet(
+                    "There is no account selected".to_string(),
+                ))
+            }
+            Notification::AccountCreationFail => {
+                return Err(ErrorUI::ErrorFromWallet(
+                    "The keys do not belong to the same account".to_string(),
+                ))
+            }
+            Notification::SpendingPasswordRejected(reason) => {
+                return Err(ErrorUI::ErrorFromWallet(reason))
+            }
+            _ => {}
+        }
+    }
+
+    Ok(fields)
+}
+
+/// Returns the reason of the error of a command, to be shown in its result
+fn error_reason(error: &ErrorUI) -> String {
+    match error {
+        ErrorUI::ErrorReading(message)
+        | ErrorUI::ErrorFromWallet(message)
+        | ErrorUI::ErrorFromPeer(message) => message.clone(),
+        ErrorUI::TransactionWithoutSufficientFunds => {
+            "The selected account does not have enough funds".to_string()
+        }
+        ErrorUI::TransactionWithDustAmount(dust_threshold) => {
+            format!("The amount is below the dust threshold of {dust_threshold} satoshis")
+        }
+        error => format!("{:?}", error),
+    }
+}
+
+/// Writes the result of the command as a JSON object in a line of the standard output
+fn write_result(command: &str, result: Result<Fields, String>) {
+    let mut fields: Fields = vec![("command", quote(command))];
+    match result {
+        Ok(values) => {
+            fields.push(("status", quote("ok")));
+            fields.extend(values);
+        }
+        Err(reason) => {
+            fields.push(("status", quote("error")));
+            fields.push(("error", quote(&reason)));
+        }
+    }
+
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(key, value)| format!("{}:{value}", quote(key)))
+        .collect();
+
+    let mut stdout = stdout();
+    let _ = writeln!(stdout, "{{{}}}", fields.join(","));
+    let _ = stdout.flush();
+}
+
+impl<RW, N> InputHandler<RW> for InputHandlerScript<N>
+where
+    RW: Read + Write + Send + 'static,
+    N: Notifier,
+{
+    fn handle_input(
+        &self,
+        broadcasting: MutArc<Broadcasting<RW>>,
+        wallet_state: WalletState,
+        _block_chain: MutArc<BlockChain>,
+        _wallet_directory: Option<MutArc<WalletDirectory>>,
+        _script_subscriptions: MutArc<ScriptSubscriptions>,
+    ) -> Result<(), ErrorUI> {
+        let (wallet, utxo_set, _, _) = wallet_state;
+
+        for line in self.open_script()?.lines() {
+            if shutdown::is_shutdown_requested() {
+                return Err(ErrorUI::Interrupted);
+            }
+
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => return Err(ErrorUI::TerminalReadFail),
+            };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(COMMENT) {
+                continue;
+            }
+
+            let _ = self.logger.log_wallet(format!("Running command: {line}"));
+            let command = match line.parse::<ScriptCommand>() {
+                Ok(command) => command,
+                Err(error) => {
+                    let name = line.split_whitespace().next().unwrap_or_default();
+                    write_result(name, Err(error_reason(&error)));
+                    continue;
+                }
+            };
+
+            let name = command.name();
+            let is_exit = command == ScriptCommand::Exit;
+            match self.execute(command, &broadcasting, &wallet, &utxo_set) {
+                Ok(fields) => write_result(name, Ok(fields)),
+                Err(error) => write_result(name, Err(error_reason(&error))),
+            }
+
+            if is_exit {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,16 +1,24 @@
-use super::{frontend, menu, menu_option::MenuOption};
+use super::{dashboard::Dashboard, frontend, menu, menu_option::MenuOption};
 
 use crate::{
-    process::reference::{get_reference, MutArc},
-    ui::{account, error_ui::ErrorUI, input_handler::InputHandler},
+    process::{
+        broadcasting::WalletState,
+        reference::{get_reference, MutArc},
+    },
+    ui::{
+        account, error_ui::ErrorUI, input_handler::InputHandler, message_catalog::MessageCatalog,
+    },
 };
 
 use cargosos_bitcoin::{
-    block_structure::{block_chain::BlockChain, utxo_set::UTXOSet},
+    block_structure::block_chain::BlockChain,
+    configurations::language::Language,
     logs::logger_sender::LoggerSender,
-    node_structure::broadcasting::Broadcasting,
+    node_structure::{broadcasting::Broadcasting, script_subscriptions::ScriptSubscriptions},
     notifications::notifier::Notifier,
-    wallet_structure::wallet::Wallet,
+    wallet_structure::{
+        transaction_options::TransactionOptions, wallet_directory::WalletDirectory,
+    },
 };
 
 use std::io::{Read, Write};
@@ -19,13 +27,39 @@ pub struct InputHandlerTUI<N>
 where
     N: Notifier,
 {
+    dashboard: Dashboard,
+    transaction_options: TransactionOptions,
+    catalog: MessageCatalog,
     notifier: N,
     logger: LoggerSender,
 }
 
 impl<N: Notifier> InputHandlerTUI<N> {
-    pub fn new(notifier: N, logger: LoggerSender) -> Self {
-        Self { notifier, logger }
+    pub fn new(dashboard: Dashboard, notifier: N, logger: LoggerSender) -> Self {
+        let catalog = MessageCatalog::default();
+        dashboard.set_palette(MenuOption::palette(catalog));
+        Self {
+            dashboard,
+            transaction_options: TransactionOptions::default(),
+            catalog,
+            notifier,
+            logger,
+        }
+    }
+
+    /// The transactions created by the user have the lock time and replaceability of the options
+    pub fn with_transaction_options(self, transaction_options: TransactionOptions) -> Self {
+        Self {
+            transaction_options,
+            ..self
+        }
+    }
+
+    /// The commands of the palette are shown and searched in the given language
+    pub fn with_language(self, language: Language) -> Self {
+        let catalog = MessageCatalog::new(language);
+        self.dashboard.set_palette(MenuOption::palette(catalog));
+        Self { catalog, ..self }
     }
 }
 
@@ -37,15 +71,28 @@ where
     fn handle_input(
         &self,
         broadcasting: MutArc<Broadcasting<RW>>,
-        wallet: MutArc<Wallet>,
-        utxo_set: MutArc<UTXOSet>,
+        wallet_state: WalletState,
         block_chain: MutArc<BlockChain>,
+        wallet_directory: Option<MutArc<WalletDirectory>>,
+        _script_subscriptions: MutArc<ScriptSubscriptions>,
     ) -> Result<(), ErrorUI> {
+        let (wallet, utxo_set, balance_history, faucet) = wallet_state;
+
         loop {
-            match menu::select_option(self.logger.clone())? {
+            match menu::select_option(&self.dashboard, self.catalog, self.logger.clone())? {
                 MenuOption::CreateAccount => {
                     let mut wallet_reference = get_reference(&wallet)?;
                     frontend::create_account(
+                        &self.dashboard,
+                        &mut wallet_reference,
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    )?
+                }
+                MenuOption::GenerateAccount => {
+                    let mut wallet_reference = get_reference(&wallet)?;
+                    frontend::generate_account(
+                        &self.dashboard,
                         &mut wallet_reference,
                         self.notifier.clone(),
                         self.logger.clone(),
@@ -54,6 +101,7 @@ where
                 MenuOption::ChangeAccount => {
                     let mut wallet_reference = get_reference(&wallet)?;
                     frontend::change_account(
+                        &self.dashboard,
                         &mut wallet_reference,
                         self.notifier.clone(),
                         self.logger.clone(),
@@ -61,13 +109,41 @@ where
                 }
                 MenuOption::RemoveAccount => {
                     let mut wallet_reference = get_reference(&wallet)?;
-                    frontend::remove_account(&mut wallet_reference, self.logger.clone())?
+                    frontend::remove_account(
+                        &self.dashboard,
+                        &mut wallet_reference,
+                        self.logger.clone(),
+                    )?
+                }
+                MenuOption::EditAccount => {
+                    let mut wallet_reference = get_reference(&wallet)?;
+                    frontend::edit_account(
+                        &self.dashboard,
+                        &mut wallet_reference,
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    )?
                 }
                 MenuOption::SendTransaction => {
-                    let wallet_reference = get_reference(&wallet)?;
+                    let mut wallet_reference = get_reference(&wallet)?;
                     let mut utxo_set_reference = get_reference(&utxo_set)?;
                     let mut broadcasting_reference = get_reference(&broadcasting)?;
                     frontend::sending_transaction(
+                        &self.dashboard,
+                        &mut broadcasting_reference,
+                        &mut wallet_reference,
+                        &mut utxo_set_reference,
+                        self.transaction_options,
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    )?
+                }
+                MenuOption::SweepPrivateKey => {
+                    let wallet_reference = get_reference(&wallet)?;
+                    let mut utxo_set_reference = get_reference(&utxo_set)?;
+                    let mut broadcasting_reference = get_reference(&broadcasting)?;
+                    frontend::sweep_private_key(
+                        &self.dashboard,
                         &mut broadcasting_reference,
                         &wallet_reference,
                         &mut utxo_set_reference,
@@ -77,7 +153,11 @@ where
                 }
                 MenuOption::ShowAccounts => {
                     let wallet_reference = get_reference(&wallet)?;
-                    frontend::show_accounts(&wallet_reference, self.logger.clone());
+                    frontend::show_accounts(
+                        &self.dashboard,
+                        &wallet_reference,
+                        self.logger.clone(),
+                    );
                 }
                 MenuOption::ShowBalance => {
                     let wallet_reference = get_reference(&wallet)?;
@@ -98,14 +178,150 @@ where
                         self.logger.clone(),
                     )?
                 }
-                MenuOption::MerkleProof => {
+                MenuOption::LabelTransaction => {
+                    let mut wallet_reference = get_reference(&wallet)?;
+                    frontend::label_transaction(
+                        &self.dashboard,
+                        &mut wallet_reference,
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    )?
+                }
+                MenuOption::VerifyMerkleProof => {
+                    let blockchain_reference = get_reference(&block_chain)?;
+                    frontend::verify_merkle_proof_of_inclusion(
+                        &self.dashboard,
+                        &blockchain_reference,
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    )?
+                }
+                MenuOption::BalanceHistory => {
+                    let from_height =
+                        frontend::get_from_height(&self.dashboard, self.logger.clone())?;
+                    let wallet_reference = get_reference(&wallet)?;
+                    let balance_history_reference = get_reference(&balance_history)?;
+                    account::give_account_balance_history(
+                        &wallet_reference,
+                        &balance_history_reference,
+                        from_height,
+                        self.notifier.clone(),
+                    )
+                }
+                MenuOption::RescanWallet => {
+                    let from = frontend::get_rescan_start(&self.dashboard, self.logger.clone())?;
+                    account::rescan_wallet(
+                        &wallet,
+                        &balance_history,
+                        &block_chain,
+                        from,
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    )?
+                }
+                MenuOption::RequestFaucetCoins => account::request_faucet_coins(
+                    &wallet,
+                    &faucet,
+                    self.notifier.clone(),
+                    self.logger.clone(),
+                )?,
+                MenuOption::SpendingPassword => {
+                    let mut wallet_reference = get_reference(&wallet)?;
+                    frontend::change_spending_password(
+                        &self.dashboard,
+                        &mut wallet_reference,
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    )?
+                }
+                MenuOption::ExportWallet => {
+                    let mut wallet_reference = get_reference(&wallet)?;
+                    frontend::export_wallet(
+                        &self.dashboard,
+                        &mut wallet_reference,
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    )?
+                }
+                MenuOption::ImportWallet => {
+                    let mut wallet_reference = get_reference(&wallet)?;
+                    frontend::import_wallet(
+                        &self.dashboard,
+                        &mut wallet_reference,
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    )?
+                }
+                MenuOption::ChangeWallet => {
+                    account::give_available_wallets(
+                        &wallet_directory,
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    )?;
+                    let wallet_name =
+                        frontend::get_wallet_name(&self.dashboard, self.logger.clone())?;
+
+                    let mut wallet_reference = get_reference(&wallet)?;
+                    let mut balance_history_reference = get_reference(&balance_history)?;
+                    let blockchain_reference = get_reference(&block_chain)?;
+                    account::change_wallet(
+                        &wallet_name,
+                        &mut wallet_reference,
+                        &mut balance_history_reference,
+                        &wallet_directory,
+                        &blockchain_reference,
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    )?
+                }
+                MenuOption::DumpBlock => {
+                    let blockchain_reference = get_reference(&block_chain)?;
+                    frontend::dump_block(
+                        &self.dashboard,
+                        &blockchain_reference,
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    )?
+                }
+                MenuOption::DumpTransaction => {
                     let blockchain_reference = get_reference(&block_chain)?;
-                    frontend::create_merkle_proof_of_inclusion(
+                    frontend::dump_transaction(
+                        &self.dashboard,
                         &blockchain_reference,
                         self.notifier.clone(),
                         self.logger.clone(),
                     )?
                 }
+                MenuOption::BroadcastRawTransaction => {
+                    let mut utxo_set_reference = get_reference(&utxo_set)?;
+                    let mut broadcasting_reference = get_reference(&broadcasting)?;
+                    frontend::broadcast_raw_transaction(
+                        &self.dashboard,
+                        &mut broadcasting_reference,
+                        &mut utxo_set_reference,
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    )?
+                }
+                MenuOption::DescribeRawTransaction => {
+                    let network = get_reference(&wallet)?.get_network();
+                    let utxo_set_reference = get_reference(&utxo_set)?;
+                    frontend::describe_raw_transaction(
+                        &self.dashboard,
+                        &utxo_set_reference,
+                        network,
+                        self.notifier.clone(),
+                        self.logger.clone(),
+                    )?
+                }
+                MenuOption::MempoolInfo => {
+                    let utxo_set_reference = get_reference(&utxo_set)?;
+                    frontend::show_mempool_info(
+                        &self.dashboard,
+                        &utxo_set_reference,
+                        self.logger.clone(),
+                    );
+                }
                 MenuOption::Exit => break,
             }
         }
@@ -1,39 +1,41 @@
-use super::menu_option::MenuOption;
+use super::{dashboard::Dashboard, menu_option::MenuOption};
 
-use crate::ui::error_ui::ErrorUI;
+use crate::ui::{error_ui::ErrorUI, message_catalog::MessageCatalog};
 
 use cargosos_bitcoin::logs::logger_sender::LoggerSender;
 
-use std::io::stdin;
-
-/// Get the option from the user via terminal
+/// Get the option from the user via the command palette, by its id or by a part of its name.
+/// When the command matches more than one option, they are shown to choose one of them. The questions
+/// and the options are in the language of the catalog
 ///
 /// ### Error
 ///  * `ErrorUI::TerminalReadFail`: It will appear when the terminal read fails
-pub fn select_option(logger: LoggerSender) -> Result<MenuOption, ErrorUI> {
-    println!("Select an option:");
-    MenuOption::print_all();
-
-    let mut option: String = String::new();
-    if stdin().read_line(&mut option).is_err() {
-        return Err(ErrorUI::TerminalReadFail);
-    }
+pub fn select_option(
+    dashboard: &Dashboard,
+    catalog: MessageCatalog,
+    logger: LoggerSender,
+) -> Result<MenuOption, ErrorUI> {
+    let mut command = dashboard.read_line(&catalog.text("Enter a command:"))?;
 
     loop {
-        let _: MenuOption = match MenuOption::try_from(option.trim()) {
-            Ok(result) => return Ok(result),
-            Err(error) => {
-                let _ =
-                    logger.log_wallet(format!("Put an invalid option, with error: {:?}", error));
-
-                option.clear();
-                println!("Error, please enter a valid option:");
-                MenuOption::print_all();
-                if stdin().read_line(&mut option).is_err() {
-                    return Err(ErrorUI::TerminalReadFail);
-                }
-                continue;
+        let options = MenuOption::search(&command, catalog);
+        match options.as_slice() {
+            [option] => return Ok(*option),
+            [] => {
+                let _ = logger.log_wallet(format!("Put an invalid command: {command}"));
+                command =
+                    dashboard.read_line(&catalog.text("Error, please enter a valid command:"))?;
+            }
+            options => {
+                let matching_options: Vec<String> =
+                    options.iter().map(|option| option.entry(catalog)).collect();
+                dashboard.show_output(
+                    &catalog.text("Matching commands"),
+                    &matching_options.join("\n"),
+                );
+                command =
+                    dashboard.read_line(&catalog.text("Enter one of the matching commands:"))?;
             }
-        };
+        }
     }
 }
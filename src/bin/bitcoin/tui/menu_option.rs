@@ -1,4 +1,4 @@
-use crate::ui::error_ui::ErrorUI;
+use crate::ui::{error_ui::ErrorUI, message_catalog::MessageCatalog};
 
 use std::{
     convert::{From, TryFrom},
@@ -12,43 +12,122 @@ const SEND_TRANSACTION: char = '4';
 const SHOW_ACCOUNTS: char = '5';
 const SHOW_BALANCE: char = '6';
 const LAST_TRANSACTIONS: char = '7';
-const MERKLE_PROOF: char = '8';
+const VERIFY_MERKLE_PROOF: char = '8';
+const REQUEST_FAUCET_COINS: char = 'a';
+const CHANGE_WALLET: char = 'b';
+const DUMP_BLOCK: char = 'c';
+const DUMP_TRANSACTION: char = 'd';
+const BROADCAST_RAW_TRANSACTION: char = 'e';
+const DESCRIBE_RAW_TRANSACTION: char = 'f';
+const GENERATE_ACCOUNT: char = 'g';
+const EDIT_ACCOUNT: char = 'h';
+const EXPORT_WALLET: char = 'i';
+const IMPORT_WALLET: char = 'j';
+const MEMPOOL_INFO: char = 'k';
+const LABEL_TRANSACTION: char = 'l';
+const SPENDING_PASSWORD: char = 'm';
+const SWEEP_PRIVATE_KEY: char = 'n';
+const RESCAN_WALLET: char = 'o';
+const BALANCE_HISTORY: char = 'p';
 const EXIT: char = '9';
 
 /// The options for the user in the menu
 #[derive(Debug, Clone, Copy)]
 pub enum MenuOption {
     CreateAccount,
+    GenerateAccount,
     ChangeAccount,
     RemoveAccount,
+    EditAccount,
     SendTransaction,
+    SweepPrivateKey,
     ShowAccounts,
     ShowBalance,
     LastTransactions,
-    MerkleProof,
+    LabelTransaction,
+    VerifyMerkleProof,
+    BalanceHistory,
+    RescanWallet,
+    RequestFaucetCoins,
+    ChangeWallet,
+    ExportWallet,
+    ImportWallet,
+    SpendingPassword,
+    DumpBlock,
+    DumpTransaction,
+    BroadcastRawTransaction,
+    DescribeRawTransaction,
+    MempoolInfo,
     Exit,
 }
 
 impl MenuOption {
-    pub fn print_all() {
-        let options: &[MenuOption] = &[
-            MenuOption::CreateAccount,
-            MenuOption::ChangeAccount,
-            MenuOption::RemoveAccount,
-            MenuOption::SendTransaction,
-            MenuOption::ShowAccounts,
-            MenuOption::ShowBalance,
-            MenuOption::LastTransactions,
-            MenuOption::MerkleProof,
-            MenuOption::Exit,
-        ];
+    /// Every option, in the order shown in the command palette
+    pub const OPTIONS: &'static [MenuOption] = &[
+        MenuOption::CreateAccount,
+        MenuOption::GenerateAccount,
+        MenuOption::ChangeAccount,
+        MenuOption::RemoveAccount,
+        MenuOption::EditAccount,
+        MenuOption::SendTransaction,
+        MenuOption::SweepPrivateKey,
+        MenuOption::ShowAccounts,
+        MenuOption::ShowBalance,
+        MenuOption::LastTransactions,
+        MenuOption::LabelTransaction,
+        MenuOption::VerifyMerkleProof,
+        MenuOption::BalanceHistory,
+        MenuOption::RescanWallet,
+        MenuOption::RequestFaucetCoins,
+        MenuOption::ChangeWallet,
+        MenuOption::ExportWallet,
+        MenuOption::ImportWallet,
+        MenuOption::SpendingPassword,
+        MenuOption::DumpBlock,
+        MenuOption::DumpTransaction,
+        MenuOption::BroadcastRawTransaction,
+        MenuOption::DescribeRawTransaction,
+        MenuOption::MempoolInfo,
+        MenuOption::Exit,
+    ];
 
-        let mut message = "".to_string();
-        for option in options {
-            let option_id: char = (*option).into();
-            message.push_str(&format!("\n{option} [{option_id}]"));
+    /// Returns the entries of the command palette, as `[id] name` with the name in the language of the catalog
+    pub fn palette(catalog: MessageCatalog) -> Vec<String> {
+        MenuOption::OPTIONS
+            .iter()
+            .map(|option| option.entry(catalog))
+            .collect()
+    }
+
+    /// Returns the entry of the option in the command palette, as `[id] name`
+    pub fn entry(&self, catalog: MessageCatalog) -> String {
+        let option_id: char = (*self).into();
+        format!("[{option_id}] {}", catalog.text(&self.to_string()))
+    }
+
+    /// Returns the options that match the command: the one with that id, or else the ones whose
+    /// name, in English or in the language of the catalog, contains it, without caring about the case
+    pub fn search(command: &str, catalog: MessageCatalog) -> Vec<MenuOption> {
+        if let Ok(option) = MenuOption::try_from(command) {
+            if command.chars().count() == 1 {
+                return vec![option];
+            }
         }
-        println!("{message}")
+
+        let command = command.to_lowercase();
+        if command.is_empty() {
+            return Vec::new();
+        }
+
+        MenuOption::OPTIONS
+            .iter()
+            .filter(|option| {
+                let name = option.to_string();
+                name.to_lowercase().contains(&command)
+                    || catalog.text(&name).to_lowercase().contains(&command)
+            })
+            .copied()
+            .collect()
     }
 }
 
@@ -56,13 +135,29 @@ impl Display for MenuOption {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             MenuOption::CreateAccount => write!(f, "Create account"),
+            MenuOption::GenerateAccount => write!(f, "Generate account"),
             MenuOption::ChangeAccount => write!(f, "Change account"),
             MenuOption::RemoveAccount => write!(f, "Remove account"),
+            MenuOption::EditAccount => write!(f, "Edit account"),
             MenuOption::SendTransaction => write!(f, "Send transaction"),
+            MenuOption::SweepPrivateKey => write!(f, "Sweep private key"),
             MenuOption::ShowAccounts => write!(f, "Show accounts"),
             MenuOption::ShowBalance => write!(f, "Show balance"),
             MenuOption::LastTransactions => write!(f, "Last transactions"),
-            MenuOption::MerkleProof => write!(f, "Merkle proof"),
+            MenuOption::LabelTransaction => write!(f, "Label transaction"),
+            MenuOption::VerifyMerkleProof => write!(f, "Verify merkle proof of inclusion"),
+            MenuOption::BalanceHistory => write!(f, "Balance history"),
+            MenuOption::RescanWallet => write!(f, "Rescan wallet"),
+            MenuOption::RequestFaucetCoins => write!(f, "Request faucet coins"),
+            MenuOption::ChangeWallet => write!(f, "Change wallet"),
+            MenuOption::ExportWallet => write!(f, "Export wallet backup"),
+            MenuOption::ImportWallet => write!(f, "Import wallet backup"),
+            MenuOption::SpendingPassword => write!(f, "Change spending password"),
+            MenuOption::DumpBlock => write!(f, "Dump block as hex"),
+            MenuOption::DumpTransaction => write!(f, "Dump transaction as hex"),
+            MenuOption::BroadcastRawTransaction => write!(f, "Broadcast raw transaction"),
+            MenuOption::DescribeRawTransaction => write!(f, "Describe raw transaction"),
+            MenuOption::MempoolInfo => write!(f, "Mempool information"),
             MenuOption::Exit => write!(f, "Exit"),
         }
     }
@@ -72,13 +167,29 @@ impl From<MenuOption> for char {
     fn from(value: MenuOption) -> Self {
         match value {
             MenuOption::CreateAccount => CREATE_ACCOUNT,
+            MenuOption::GenerateAccount => GENERATE_ACCOUNT,
             MenuOption::ChangeAccount => CHANGE_ACCOUNT,
             MenuOption::RemoveAccount => REMOVE_ACCOUNT,
+            MenuOption::EditAccount => EDIT_ACCOUNT,
             MenuOption::SendTransaction => SEND_TRANSACTION,
             MenuOption::ShowAccounts => SHOW_ACCOUNTS,
             MenuOption::ShowBalance => SHOW_BALANCE,
             MenuOption::LastTransactions => LAST_TRANSACTIONS,
-            MenuOption::MerkleProof => MERKLE_PROOF,
+            MenuOption::LabelTransaction => LABEL_TRANSACTION,
+            MenuOption::VerifyMerkleProof => VERIFY_MERKLE_PROOF,
+            MenuOption::BalanceHistory => BALANCE_HISTORY,
+            MenuOption::RescanWallet => RESCAN_WALLET,
+            MenuOption::RequestFaucetCoins => REQUEST_FAUCET_COINS,
+            MenuOption::ChangeWallet => CHANGE_WALLET,
+            MenuOption::ExportWallet => EXPORT_WALLET,
+            MenuOption::ImportWallet => IMPORT_WALLET,
+            MenuOption::SpendingPassword => SPENDING_PASSWORD,
+            MenuOption::SweepPrivateKey => SWEEP_PRIVATE_KEY,
+            MenuOption::DumpBlock => DUMP_BLOCK,
+            MenuOption::DumpTransaction => DUMP_TRANSACTION,
+            MenuOption::BroadcastRawTransaction => BROADCAST_RAW_TRANSACTION,
+            MenuOption::DescribeRawTransaction => DESCRIBE_RAW_TRANSACTION,
+            MenuOption::MempoolInfo => MEMPOOL_INFO,
             MenuOption::Exit => EXIT,
         }
     }
@@ -95,13 +206,29 @@ impl TryFrom<&str> for MenuOption {
 
         match value {
             CREATE_ACCOUNT => Ok(MenuOption::CreateAccount),
+            GENERATE_ACCOUNT => Ok(MenuOption::GenerateAccount),
             CHANGE_ACCOUNT => Ok(MenuOption::ChangeAccount),
             REMOVE_ACCOUNT => Ok(MenuOption::RemoveAccount),
+            EDIT_ACCOUNT => Ok(MenuOption::EditAccount),
             SEND_TRANSACTION => Ok(MenuOption::SendTransaction),
             SHOW_ACCOUNTS => Ok(MenuOption::ShowAccounts),
             SHOW_BALANCE => Ok(MenuOption::ShowBalance),
             LAST_TRANSACTIONS => Ok(MenuOption::LastTransactions),
-            MERKLE_PROOF => Ok(MenuOption::MerkleProof),
+            LABEL_TRANSACTION => Ok(MenuOption::LabelTransaction),
+            VERIFY_MERKLE_PROOF => Ok(MenuOption::VerifyMerkleProof),
+            BALANCE_HISTORY => Ok(MenuOption::BalanceHistory),
+            RESCAN_WALLET => Ok(MenuOption::RescanWallet),
+            REQUEST_FAUCET_COINS => Ok(MenuOption::RequestFaucetCoins),
+            CHANGE_WALLET => Ok(MenuOption::ChangeWallet),
+            EXPORT_WALLET => Ok(MenuOption::ExportWallet),
+            IMPORT_WALLET => Ok(MenuOption::ImportWallet),
+            SPENDING_PASSWORD => Ok(MenuOption::SpendingPassword),
+            SWEEP_PRIVATE_KEY => Ok(MenuOption::SweepPrivateKey),
+            DUMP_BLOCK => Ok(MenuOption::DumpBlock),
+            DUMP_TRANSACTION => Ok(MenuOption::DumpTransaction),
+            BROADCAST_RAW_TRANSACTION => Ok(MenuOption::BroadcastRawTransaction),
+            DESCRIBE_RAW_TRANSACTION => Ok(MenuOption::DescribeRawTransaction),
+            MEMPOOL_INFO => Ok(MenuOption::MempoolInfo),
             EXIT => Ok(MenuOption::Exit),
             _ => Err(ErrorUI::InvalidMenuOption),
         }
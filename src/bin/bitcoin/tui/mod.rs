@@ -1,3 +1,4 @@
+pub mod dashboard;
 pub mod execution;
 pub mod frontend;
 
@@ -6,3 +7,7 @@ pub mod menu_option;
 
 pub mod input_handler_tui;
 pub mod notifier_tui;
+
+pub mod input_handler_script;
+pub mod notifier_script;
+pub mod script_command;
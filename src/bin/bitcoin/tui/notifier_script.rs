@@ -0,0 +1,62 @@
+use cargosos_bitcoin::{
+    logs::logger_sender::LoggerSender,
+    notifications::{notification::Notification, notification_json, notifier::Notifier},
+};
+
+use std::sync::{Arc, Mutex};
+
+/// Struct that handles the notifications of the non-interactive mode, where the output is only the
+/// result of the commands. The notifications with a JSON representation are written in the logs
+#[derive(Clone)]
+pub struct NotifierScript {
+    logger: LoggerSender,
+}
+
+impl NotifierScript {
+    pub fn new(logger: LoggerSender) -> Self {
+        Self { logger }
+    }
+}
+
+impl Notifier for NotifierScript {
+    fn notify(&self, notification: Notification) {
+        if let Some(json) = notification_json::to_json(&notification) {
+            let _ = self.logger.log_notification(json);
+        }
+    }
+}
+
+/// It's a notifier that keeps the notifications of a command before passing them to the given
+/// notifier, so the result of the command can be obtained from them
+#[derive(Clone)]
+pub struct RecordingNotifier<N: Notifier> {
+    notifications: Arc<Mutex<Vec<Notification>>>,
+    notifier: N,
+}
+
+impl<N: Notifier> RecordingNotifier<N> {
+    pub fn new(notifier: N) -> Self {
+        RecordingNotifier {
+            notifications: Arc::new(Mutex::new(Vec::new())),
+            notifier,
+        }
+    }
+
+    /// Returns the notifications received until now, leaving none recorded
+    pub fn take_notifications(&self) -> Vec<Notification> {
+        match self.notifications.lock() {
+            Ok(mut notifications) => notifications.drain(..).collect(),
+            Err(poisoned) => poisoned.into_inner().drain(..).collect(),
+        }
+    }
+}
+
+impl<N: Notifier> Notifier for RecordingNotifier<N> {
+    fn notify(&self, notification: Notification) {
+        match self.notifications.lock() {
+            Ok(mut notifications) => notifications.push(notification.clone()),
+            Err(poisoned) => poisoned.into_inner().push(notification.clone()),
+        }
+        self.notifier.notify(notification);
+    }
+}
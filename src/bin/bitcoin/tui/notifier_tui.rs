@@ -1,19 +1,48 @@
+use super::dashboard::Dashboard;
+
+use crate::ui::message_catalog::MessageCatalog;
+
 use cargosos_bitcoin::{
+    block_structure::hash,
+    configurations::language::Language,
     logs::logger_sender::LoggerSender,
+    node_structure::script_subscriptions::ScriptEventKind,
     notifications::{notification::Notification, notifier::Notifier},
 };
 
-use std::cmp::max;
+use chrono::offset::Utc;
 
-/// Struct that handles the representation of the notifications for the TUI.
+/// Struct that handles the representation of the notifications for the TUI, updating the panes of
+/// the dashboard
 #[derive(Clone)]
 pub struct NotifierTUI {
+    dashboard: Dashboard,
+    catalog: MessageCatalog,
     logger: LoggerSender,
 }
 
 impl NotifierTUI {
-    pub fn new(logger: LoggerSender) -> Self {
-        Self { logger }
+    pub fn new(dashboard: Dashboard, logger: LoggerSender) -> Self {
+        Self {
+            dashboard,
+            catalog: MessageCatalog::default(),
+            logger,
+        }
+    }
+
+    /// The notifications are shown in the given language
+    pub fn with_language(self, language: Language) -> Self {
+        Self {
+            catalog: MessageCatalog::new(language),
+            ..self
+        }
+    }
+
+    /// Shows the notification in the output pane and adds its title to the events
+    fn show_notification(&self, title: &str, body: &str) {
+        self.dashboard.add_event(title);
+        self.dashboard.show_output(title, body);
+        let _ = self.logger.log_notification(body.to_string());
     }
 }
 
@@ -21,72 +50,159 @@ impl Notifier for NotifierTUI {
     fn notify(&self, notification: Notification) {
         match notification {
             Notification::AttemptingHandshakeWithPeer(socket_address) => {
-                println!("Attempting handshake with {socket_address}");
+                self.dashboard.add_event(&self.catalog.format("Attempting handshake with {0}", &[&socket_address]));
             }
             Notification::SuccessfulHandshakeWithPeer(socket_address) => {
-                println!("Successful handshake with {socket_address}");
+                self.dashboard.add_peer(socket_address);
+                self.dashboard.add_event(&self.catalog.format("Successful handshake with {0}", &[&socket_address]));
             }
             Notification::FailedHandshakeWithPeer(socket_address) => {
-                println!("Failed handshake with {socket_address}");
+                self.dashboard.add_event(&self.catalog.format("Failed handshake with {0}", &[&socket_address]));
             }
             Notification::ConnectionUpdated(connection_id) => {
-                println!("Connection updated: {connection_id}");
+                self.dashboard.add_event(&self.catalog.format("Connection updated: {0}", &[&connection_id]));
+            }
+            Notification::PeerLatency(connection_id, latency) => {
+                self.dashboard
+                    .set_peer_latency(connection_id.address, latency);
+                let _ = self.logger.log_connection(self.catalog.format(
+                    "Latency with {0}: {1} ms",
+                    &[&connection_id, &latency.as_millis()],
+                ));
+            }
+            Notification::PeerInformationUpdated(information) => {
+                self.dashboard.set_peer_information(
+                    information.connection.address,
+                    &information.user_agent,
+                    information.latency,
+                );
+                let _ = self.logger.log_connection(information.to_string());
+            }
+            Notification::PeerDisconnected(connection_id) => {
+                self.dashboard.remove_peer(connection_id.address);
+                self.dashboard.add_event(&self.catalog.format("Disconnected from {0}", &[&connection_id]));
+            }
+            Notification::ClockSkew(skew) => self.show_notification(
+                &self.catalog.text("Clock skew"),
+                &self.catalog.format("Our clock deviates {0} seconds from the time of the network,\n    check the date of the computer", &[&skew])
+            ),
+            Notification::StateSaved(timestamp) => {
+                self.dashboard.set_last_saved(timestamp);
+                let _ = self
+                    .logger
+                    .log_file(self.catalog.text("The state of the node was saved"));
             }
+            Notification::LastSyncedTip(chain_tip) => self.show_notification(
+                &self.catalog.text("Last synced tip"),
+                &self.catalog.format(
+                    "Last synced to height {0}, {1} hours ago",
+                    &[
+                        &chain_tip.height,
+                        &chain_tip.hours_since_sync(Utc::now().timestamp()),
+                    ],
+                )
+            ),
             Notification::TransactionOfAccountReceived(accounts, transaction) => {
                 for account in accounts {
-                    show_notification(
-                        "Transaction received",
-                        &format!(
-                            "The transaction: {transaction} was received\n    in the account: {account}",
-                            transaction = transaction.clone(),
-                        ),
-                        &self.logger,
+                    self.show_notification(
+                        &self.catalog.text("Transaction received"),
+                        &self.catalog.format(
+                            "The transaction: {0} was received\n    in the account: {1}",
+                            &[&transaction, &account],
+                        )
                     );
                 }
             }
-            Notification::TransactionOfAccountInNewBlock(block, transaction) => show_notification(
-                "Transaction in block",
-                &format!(
-                    "The transaction {transaction}was added\n    to a block with hash {block}"
-                ),
-                &self.logger,
+            Notification::TransactionOfAccountInNewBlock(block, transaction) => self.show_notification(
+                &self.catalog.text("Transaction in block"),
+                &self.catalog.format(
+                    "The transaction {0}was added\n    to a block with hash {1}",
+                    &[&transaction, &block],
+                )
             ),
-            Notification::NewBlockAddedToTheBlockchain(block) => {
-                show_notification(
-                    "New block added",
-                    &format!("The block {block}\n    was added to the blockchain"),
-                    &self.logger,
+            Notification::NewBlockAddedToTheBlockchain(block, coinbase_info) => {
+                self.show_notification(
+                    &self.catalog.text("New block added"),
+                    &self.catalog.format(
+                        "The block {0}\n    was added to the blockchain\n    with {1}",
+                        &[&block, &coinbase_info],
+                    )
                 );
             }
             Notification::UpdatedSelectedAccount(account) => {
-                let message = format!("Account selected: {account}");
-                println!("{message}");
+                self.dashboard.set_selected_account(&account.account_name);
+                let message = self.catalog.format("Account selected: {0}", &[&account]);
+                self.dashboard.add_event(&message);
                 let _ = self.logger.log_wallet(message);
             }
             Notification::RegisterWalletAccount(account) => {
-                let message = format!("New account {account} was added to the wallet");
-                println!("{message}");
+                let message = self
+                    .catalog
+                    .format("New account {0} was added to the wallet", &[&account]);
+                self.dashboard.add_event(&message);
                 let _ = self.logger.log_wallet(message);
             }
             Notification::NotifyBlockchainIsReady => {
-                let message = "Blockchain is up to date".to_string();
-                println!("{message}");
+                self.dashboard.set_blockchain_ready();
+                let message = self.catalog.text("Blockchain is up to date");
+                self.dashboard.add_event(&message);
                 let _ = self.logger.log_node(message);
             }
             Notification::LoadAvailableBalance(account, balance, pending) => {
-                let total = format!("{:.8}", balance + pending);
-                let balance = format!("{:.8}", balance);
-                let pending = format!("{:.8}", pending);
-
-                println!("Account: {account}\n    Balance: {balance}\n    Pending: {pending}\n    Total: {total}", account = account.account_name);
-                let _ = self.logger.log_wallet(format!(
-                    "Account: {account} with balance: {balance} and pending: {pending}",
-                    account = account.account_name
+                self.dashboard
+                    .set_balance(&account.account_name, balance, pending);
+                let _ = self.logger.log_wallet(self.catalog.format(
+                    "Account: {0} with balance: {1} and pending: {2}",
+                    &[
+                        &account.account_name,
+                        &balance.format_btc(),
+                        &pending.format_btc(),
+                    ],
+                ));
+            }
+            Notification::ExchangeRateUpdated(exchange_rate) => {
+                self.dashboard.set_exchange_rate(exchange_rate);
+            }
+            Notification::AccountBalanceChanged(account, balance, pending) => {
+                self.dashboard
+                    .set_balance(&account.account_name, balance, pending);
+                let _ = self.logger.log_wallet(self.catalog.format(
+                    "Account: {0} changed to balance: {1} and pending: {2}",
+                    &[
+                        &account.account_name,
+                        &balance.format_btc(),
+                        &pending.format_btc(),
+                    ],
+                ));
+            }
+            Notification::AccountTransactionConfirmed(account, transaction) => {
+                let _ = self.logger.log_wallet(self.catalog.format(
+                    "Transaction {0} of the account {1} was confirmed",
+                    &[&transaction, &account.account_name],
+                ));
+            }
+            Notification::AccountBalanceCheckpoint(account, (height, balance)) => {
+                let _ = self.logger.log_wallet(self.catalog.format(
+                    "Account: {0} with balance {1} at height {2}",
+                    &[&account.account_name, &balance, &height],
+                ));
+            }
+            Notification::TipAdvanced(height, _) => {
+                self.dashboard.set_tip_height(height);
+                let _ = self.logger.log_node(
+                    self.catalog
+                        .format("The tip of the blockchain advanced to height {0}", &[&height]),
+                );
+            }
+            Notification::MempoolInfo(mempool_info) => {
+                let _ = self.logger.log_node(self.catalog.format(
+                    "The mempool has {0} transactions with {1} sat in fees",
+                    &[&mempool_info.transaction_count, &mempool_info.total_fees],
                 ));
             }
             Notification::AccountNotSelected => {
-                let message = "Account not selected".to_string();
-                println!("{message}");
+                let message = self.catalog.text("Account not selected");
+                self.dashboard.add_event(&message);
                 let _ = self.logger.log_wallet(message);
             }
             Notification::AccountTransactions(account, transactions) => {
@@ -94,132 +210,396 @@ impl Notifier for NotifierTUI {
                 for transaction in transactions {
                     message_transaction.push_str(&format!("{transaction}\n"));
                 }
-                show_notification(
-                    &format!("In the account: {account}", account = account.account_name),
-                    &message_transaction,
-                    &self.logger,
+                self.show_notification(
+                    &self
+                        .catalog
+                        .format("In the account: {0}", &[&account.account_name]),
+                    &message_transaction
+                )
+            }
+            Notification::TransactionHistory(history) => {
+                let mut message_history = "".to_string();
+                for entry in history {
+                    message_history.push_str(&self.catalog.format(
+                        "{0}: {1} {2} satoshis in {3} ({4} confirmations)\n",
+                        &[
+                            &entry.account_name,
+                            &self.catalog.text(&entry.direction.to_string()),
+                            &entry.amount,
+                            &hash::to_hex_be(&entry.transaction_id),
+                            &entry.confirmations,
+                        ],
+                    ));
+                }
+                self.show_notification(
+                    &self.catalog.text("Transaction history"),
+                    &message_history,
+                )
+            }
+            Notification::AccountBalanceHistory(account, history) => {
+                let mut message_history = "".to_string();
+                for (height, balance) in history {
+                    message_history.push_str(
+                        &self
+                            .catalog
+                            .format("Height: {0}    Balance: {1}\n", &[&height, &balance]),
+                    );
+                }
+                self.show_notification(
+                    &self.catalog.format(
+                        "Balance history of the account: {0}",
+                        &[&account.account_name],
+                    ),
+                    &message_history
+                )
+            }
+            Notification::FaucetCoinsRequested(account, transaction_id) => self.show_notification(
+                &self.catalog.text("Faucet coins requested"),
+                &self.catalog.format(
+                    "The faucet sent the transaction {0}\n    to the account: {1}",
+                    &[&hash::to_hex_be(&transaction_id), &account.account_name],
+                )
+            ),
+            Notification::FaucetRequestFailed(error) => {
+                let message = self
+                    .catalog
+                    .format("Faucet request failed: {0}", &[&error]);
+                self.dashboard.add_event(&message);
+                let _ = self.logger.log_wallet(message);
+            }
+            Notification::WalletChanged(wallet_name) => {
+                self.dashboard.set_wallet_name(&wallet_name);
+                self.show_notification(
+                    &self.catalog.text("Wallet changed"),
+                    &self.catalog.format("Using the wallet {0}", &[&wallet_name]),
                 )
             }
+            Notification::WalletClosed(wallet_name) => {
+                self.show_notification(
+                    &self.catalog.text("Wallet closed"),
+                    &self.catalog.format("The wallet {0} was closed", &[&wallet_name]),
+                )
+            }
+            Notification::AvailableWallets(selected_wallet, wallet_names) => {
+                let mut message_wallets = "".to_string();
+                for wallet_name in wallet_names {
+                    if wallet_name == selected_wallet {
+                        message_wallets
+                            .push_str(&self.catalog.format("{0} (in use)\n", &[&wallet_name]));
+                    } else {
+                        message_wallets.push_str(&format!("{wallet_name}\n"));
+                    }
+                }
+                self.dashboard
+                    .show_output(&self.catalog.text("Available wallets"), &message_wallets);
+            }
+            Notification::WalletChangeFailed(error) => {
+                let message = self
+                    .catalog
+                    .format("Wallet change failed: {0}", &[&error]);
+                self.dashboard.add_event(&message);
+                let _ = self.logger.log_wallet(message);
+            }
+            Notification::RawBlock(block_hash, raw_block) => {
+                let title = self
+                    .catalog
+                    .format("Raw block {0}", &[&hash::to_hex_be(&block_hash)]);
+                let _ = self.logger.log_transaction(title.clone());
+                self.dashboard.show_output(&title, &raw_block);
+            }
+            Notification::RawTransaction(transaction_id, raw_transaction) => {
+                let title = self
+                    .catalog
+                    .format("Raw transaction {0}", &[&hash::to_hex_be(&transaction_id)]);
+                let _ = self.logger.log_transaction(title.clone());
+                self.dashboard.show_output(&title, &raw_transaction);
+            }
+            Notification::ScriptHashEvent(event) => {
+                let outpoint = format!(
+                    "{}:{}",
+                    hash::to_hex_be(&event.outpoint.get_transaction_id()),
+                    event.outpoint.get_index()
+                );
+                let action = match event.kind {
+                    ScriptEventKind::Created(value) => self
+                        .catalog
+                        .format("created with {0} satoshis", &[&value]),
+                    ScriptEventKind::Spent(transaction_id) => self
+                        .catalog
+                        .format("spent by {0}", &[&hash::to_hex_be(&transaction_id)]),
+                };
+                self.show_notification(
+                    &self.catalog.text("Subscribed script"),
+                    &self.catalog.format(
+                        "The output {0}\n    of the script {1}\n    was {2}",
+                        &[&outpoint, &hash::to_hex(&event.script_hash), &action],
+                    )
+                );
+            }
+            Notification::TransactionDescribed(description) => {
+                let _ = self.logger.log_transaction(self.catalog.format(
+                    "Transaction {0} described",
+                    &[&hash::to_hex_be(&description.transaction_id)],
+                ));
+                self.dashboard.show_output(
+                    &self.catalog.text("Transaction description"),
+                    &description.to_string(),
+                );
+            }
+            Notification::RawDataProblem(error) => {
+                self.show_notification(
+                    &self.catalog.text("Error while getting the raw data"),
+                    &self.catalog.format(
+                        "The raw data could not be obtained\n the error was: {0}",
+                        &[&error],
+                    )
+                );
+            }
+            Notification::RecentBlocks(summaries) => {
+                let mut message_blocks = "".to_string();
+                for summary in summaries {
+                    let fees = match summary.fees {
+                        Some(fees) => self.catalog.format("{0} satoshis", &[&fees]),
+                        None => self.catalog.text("unknown"),
+                    };
+                    message_blocks.push_str(&self.catalog.format(
+                        "{0} {1} with {2} transactions, subsidy of {3} satoshis and fees {4}\n",
+                        &[
+                            &summary.height,
+                            &hash::to_hex_be(&summary.hash),
+                            &summary.transaction_count,
+                            &summary.subsidy,
+                            &fees,
+                        ],
+                    ));
+                }
+                self.dashboard
+                    .show_output(&self.catalog.text("Recent blocks"), &message_blocks);
+            }
+            Notification::BlockTransactions(summary, transactions) => {
+                let mut message_transactions = "".to_string();
+                for transaction in transactions {
+                    if let Ok(transaction_id) = transaction.get_tx_id() {
+                        message_transactions
+                            .push_str(&format!("{}\n", hash::to_hex_be(&transaction_id)));
+                    }
+                }
+                self.dashboard.show_output(
+                    &self.catalog.format(
+                        "Transactions of the block {0} at height {1}",
+                        &[&hash::to_hex_be(&summary.hash), &summary.height],
+                    ),
+                    &message_transactions,
+                );
+            }
+            Notification::FromWallet(wallet_name, notification) => {
+                self.dashboard.set_wallet_name(&wallet_name);
+                self.notify(*notification);
+            }
+            Notification::FaucetTransactionConfirmed(block, transaction_id) => self.show_notification(
+                &self.catalog.text("Faucet transaction confirmed"),
+                &self.catalog.format(
+                    "The faucet transaction {0}\n    was confirmed in the block {1}",
+                    &[&hash::to_hex_be(&transaction_id), &block],
+                )
+            ),
             Notification::InvalidAddressEnter => {
-                let message = "Invalid address enter".to_string();
-                println!("{message}");
+                let message = self.catalog.text("Invalid address enter");
+                self.dashboard.add_event(&message);
                 let _ = self.logger.log_wallet(message);
             }
             Notification::InvalidPublicKeyEnter => {
-                let message = "Invalid public key enter".to_string();
-                println!("{message}");
+                let message = self.catalog.text("Invalid public key enter");
+                self.dashboard.add_event(&message);
                 let _ = self.logger.log_wallet(message);
             }
             Notification::InvalidPrivateKeyEnter => {
-                let message = "Invalid private key enter".to_string();
-                println!("{message}");
+                let message = self.catalog.text("Invalid private key enter");
+                self.dashboard.add_event(&message);
                 let _ = self.logger.log_wallet(message);
             }
             Notification::AccountCreationFail => {
-                let message = "Creation of the account fail".to_string();
-                println!("{message}");
+                let message = self.catalog.text("Creation of the account fail");
+                self.dashboard.add_event(&message);
+                let _ = self.logger.log_wallet(message);
+            }
+            Notification::AccountEdited(account_name, account) => {
+                self.dashboard
+                    .rename_account(&account_name, &account.account_name);
+                let message = self
+                    .catalog
+                    .format("The account {0} was edited: {1}", &[&account_name, &account]);
+                self.dashboard.add_event(&message);
+                let _ = self.logger.log_wallet(message);
+            }
+            Notification::AccountEditFailed(reason) => {
+                let message = self
+                    .catalog
+                    .format("Edition of the account fail: {0}", &[&reason]);
+                self.dashboard.add_event(&message);
+                let _ = self.logger.log_wallet(message);
+            }
+            Notification::TransactionLabelChanged(transaction_id, label) => {
+                let message = match label {
+                    Some(label) => self.catalog.format(
+                        "The transaction {0} is labelled: {1}",
+                        &[&hash::to_hex_be(&transaction_id), &label],
+                    ),
+                    None => self.catalog.format(
+                        "The label of the transaction {0} was removed",
+                        &[&hash::to_hex_be(&transaction_id)],
+                    ),
+                };
+                self.dashboard.add_event(&message);
+                let _ = self.logger.log_wallet(message);
+            }
+            Notification::WalletExported(path) => {
+                let message = self
+                    .catalog
+                    .format("The wallet was exported to {0}", &[&path]);
+                self.dashboard.add_event(&message);
+                let _ = self.logger.log_wallet(message);
+            }
+            Notification::WalletImported(path, accounts_added) => {
+                let message = self
+                    .catalog
+                    .format("Imported {0} accounts from {1}", &[&accounts_added, &path]);
+                self.dashboard.add_event(&message);
+                let _ = self.logger.log_wallet(message);
+            }
+            Notification::WalletBackupFailed(reason) => {
+                let message = self
+                    .catalog
+                    .format("Backup of the wallet failed: {0}", &[&reason]);
+                self.dashboard.add_event(&message);
+                let _ = self.logger.log_wallet(message);
+            }
+            Notification::SpendingPasswordChanged(has_password) => {
+                let message = match has_password {
+                    true => self.catalog.text("The spending password was changed"),
+                    false => self.catalog.text("The spending password was removed"),
+                };
+                self.dashboard.add_event(&message);
+                let _ = self.logger.log_wallet(message);
+            }
+            Notification::WalletUnlocked(duration) => {
+                let message = self.catalog.format(
+                    "The wallet is unlocked for {0} seconds",
+                    &[&duration.as_secs()],
+                );
+                self.dashboard.add_event(&message);
                 let _ = self.logger.log_wallet(message);
             }
+            Notification::SpendingPasswordRejected(reason) => {
+                let message = self
+                    .catalog
+                    .format("The spending password was rejected: {0}", &[&reason]);
+                self.dashboard.add_event(&message);
+                let _ = self.logger.log_wallet(message);
+            }
+            Notification::TransactionPreview(preview) => self.show_notification(
+                &self.catalog.text("Transaction to confirm"),
+                &self.catalog.format(
+                    "Recipient: {0}\nAmount: {1} satoshis\nFee: {2} satoshis ({3} satoshis/byte)\nChange: {4} satoshis\nResulting balance: {5} satoshis",
+                    &[
+                        &preview.recipient,
+                        &preview.amount,
+                        &preview.fee,
+                        &format!("{:.2}", preview.fee_rate()),
+                        &preview.change,
+                        &preview.resulting_balance,
+                    ],
+                )
+            ),
             Notification::NotEnoughFunds => {
-                let message = "Not enough founds to create transaction".to_string();
-                println!("{message}");
+                let message = self.catalog.text("Not enough founds to create transaction");
+                self.dashboard.add_event(&message);
                 let _ = self.logger.log_transaction(message);
             }
-            Notification::SuccessfullySentTransaction(transaction) => {
-                show_notification(
-                    "Transaction sent",
-                    &format!("The transaction {transaction} was sent"),
-                    &self.logger,
+            Notification::AmountIsDust(dust_threshold) => {
+                let message = self.catalog.format(
+                    "The amount is below the dust threshold of {0} satoshis, so the transaction would not be relayed",
+                    &[&dust_threshold],
                 );
+                self.dashboard.add_event(&message);
+                let _ = self.logger.log_transaction(message);
+            }
+            Notification::RawTransactionRejected(reason) => {
+                let message = self
+                    .catalog
+                    .format("The raw transaction was rejected: {0}", &[&reason]);
+                self.dashboard.add_event(&message);
+                let _ = self.logger.log_transaction(message);
             }
-            Notification::ProgressDownloadingBlocks(blocks_downloaded, total_blocks) => {
-                let percentage_downloaded =
-                    (blocks_downloaded as f32 / total_blocks as f32) * 100.0;
-                let message = format!(
-                    "Finished downloading {percentage}% of blocks",
-                    percentage = percentage_downloaded
+            Notification::TransactionConflicted(transaction_id, conflicting_id) => self
+                .show_notification(
+                    &self.catalog.text("Transaction conflicted"),
+                    &self.catalog.format(
+                        "The transaction {0} was dropped\n    the transaction {1} of a new block spends the same outputs",
+                        &[&hash::to_hex_be(&transaction_id), &hash::to_hex_be(&conflicting_id)],
+                    )
+                ),
+            Notification::SuccessfullySentTransaction(transaction) => {
+                self.show_notification(
+                    &self.catalog.text("Transaction sent"),
+                    &self
+                        .catalog
+                        .format("The transaction {0} was sent", &[&transaction])
                 );
-                println!("{message}");
+            }
+            Notification::ProgressDownloadingBlocks(progress) => {
+                self.dashboard.set_download_progress(progress);
             }
             Notification::ProgressUpdatingBlockchain(blocks_updated, total_blocks) => {
-                let percentage_updated = (blocks_updated as f32 / total_blocks as f32) * 100.0;
-                let message = format!(
-                    "Finished updating {percentage}% of the blockchain",
-                    percentage = percentage_updated
-                );
-                println!("{message}");
+                self.dashboard
+                    .set_update_progress(blocks_updated, total_blocks);
+            }
+            Notification::ProgressRescan(blocks_scanned, total_blocks) => {
+                self.dashboard.add_event(&self.catalog.format(
+                    "Rescanned {0} of {1} blocks for the wallet",
+                    &[&blocks_scanned, &total_blocks],
+                ))
             }
             Notification::HeadersReceived(headers) => {
-                println!("Received {headers} headers");
+                self.dashboard.add_headers_received(headers);
+            }
+            Notification::ClosingPeers => {
+                self.dashboard.add_event(&self.catalog.text("Closing peers"))
+            }
+            Notification::ClosingPeer => {
+                self.dashboard.add_event(&self.catalog.text("Closing this peer"))
             }
-            Notification::ClosingPeers => println!("Closing peers"),
-            Notification::ClosingPeer => println!("Closing this peer"),
             Notification::ReceivedMessage(message) => {
-                println!("Received message of type {:?}", message)
+                let _ = self
+                    .logger
+                    .log_connection(self.catalog.format("Received message of type {0}", &[&format!("{:?}", message)]));
             }
             Notification::ProblemVerifyingTransactionMerkleProofOfInclusion(error_message) => {
-                show_notification(
-                    "Error while verifying transaction merkle proof of inclusion",
-                    &format!("There was an error in the process of verifying the merkle proof\n the error was: {error_message}"),
-                    &self.logger,
+                self.show_notification(
+                    &self
+                        .catalog
+                        .text("Error while verifying transaction merkle proof of inclusion"),
+                    &self.catalog.format(
+                        "There was an error in the process of verifying the merkle proof\n the error was: {0}",
+                        &[&error_message],
+                    )
                 );
             }
             Notification::SuccessfulMerkleProof(path, root) => {
                 let mut message_path = "".to_string();
 
-                for hash in path {
-                    message_path.push_str(&format!("{}\n", from_hashtype_to_string(&hash)));
+                for node in path {
+                    message_path.push_str(&format!("{}\n", hash::to_hex_be(&node)));
                 }
 
-                show_notification(
-                    "Merkle proof successfully validated",
-                    &format!(
-                        "Merkle root:\n{root}\n Merkle path:\n{message_path}",
-                        root = from_hashtype_to_string(&root),
-                    ),
-                    &self.logger,
+                self.show_notification(
+                    &self.catalog.text("Merkle proof successfully validated"),
+                    &self.catalog.format(
+                        "Merkle root:\n{0}\n Merkle path:\n{1}",
+                        &[&hash::to_hex_be(&root), &message_path],
+                    )
                 );
             }
         }
     }
 }
-
-/// Notify the user in a clean way
-fn show_notification(title: &str, body: &str, logger: &LoggerSender) {
-    let len_message = max(calculate_body_len(title), calculate_body_len(body));
-    let border = "#".repeat(len_message + 4);
-
-    let mut message = format!("{border}\n");
-    for title_line in title.split('\n') {
-        let spaces = len_message - title_line.len();
-        message.push_str(&format!("# {}{} #\n", title_line, " ".repeat(spaces)));
-    }
-
-    for body_line in body.split('\n') {
-        let spaces = len_message - body_line.len();
-        message.push_str(&format!("# {}{} #\n", body_line, " ".repeat(spaces)));
-    }
-
-    message.push_str(border.as_str());
-
-    println!("{message}");
-    let _ = logger.log_notification(body.to_string());
-}
-
-/// Given a body of text, returns the length of the longest line
-fn calculate_body_len(body: &str) -> usize {
-    let mut len = 0;
-    for line in body.split('\n') {
-        len = max(len, line.len());
-    }
-    len
-}
-
-/// Turns a hashtype to a string
-fn from_hashtype_to_string(hash: &[u8]) -> String {
-    let mut hash_string = "".to_string();
-    for byte in hash.iter() {
-        hash_string.push_str(&format!("{:02x}", byte));
-    }
-    hash_string
-}
@@ -0,0 +1,108 @@
+use crate::ui::error_ui::ErrorUI;
+
+use cargosos_bitcoin::wallet_structure::amount::Amount;
+
+use std::str::FromStr;
+
+const CREATE_ACCOUNT: &str = "create-account";
+const GENERATE_ACCOUNT: &str = "generate-account";
+const SELECT_ACCOUNT: &str = "select-account";
+const SEND: &str = "send";
+const BALANCE: &str = "balance";
+const EXIT: &str = "exit";
+
+/// It represents the commands that can be written in a script for the non-interactive mode, one
+/// per line. The amounts are in tBTC, or in satoshis with the `sat` suffix, and the names of the accounts can have spaces:
+///  * `create-account <private key> <public key> <name>`
+///  * `generate-account <name>`, with a new pair of keys
+///  * `select-account <name>`
+///  * `send <address> <amount> <fee>`
+///  * `balance`
+///  * `exit`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptCommand {
+    CreateAccount {
+        private_key: String,
+        public_key: String,
+        name: String,
+    },
+    GenerateAccount(String),
+    SelectAccount(String),
+    Send {
+        address: String,
+        amount: Amount,
+        fee: Amount,
+    },
+    Balance,
+    Exit,
+}
+
+impl ScriptCommand {
+    /// Returns the name of the command, as written in the script
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScriptCommand::CreateAccount { .. } => CREATE_ACCOUNT,
+            ScriptCommand::GenerateAccount(_) => GENERATE_ACCOUNT,
+            ScriptCommand::SelectAccount(_) => SELECT_ACCOUNT,
+            ScriptCommand::Send { .. } => SEND,
+            ScriptCommand::Balance => BALANCE,
+            ScriptCommand::Exit => EXIT,
+        }
+    }
+}
+
+impl FromStr for ScriptCommand {
+    type Err = ErrorUI;
+
+    /// ### Error
+    ///  * `ErrorUI::ErrorReading`: It will appear when the command is not known or its values are missing or not valid
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let (command, values) = match words.split_first() {
+            Some((command, values)) => (*command, values),
+            None => return Err(ErrorUI::ErrorReading("The line is empty".to_string())),
+        };
+
+        let wrong_values = || {
+            ErrorUI::ErrorReading(format!(
+                "The values of the command {command} are missing or not valid"
+            ))
+        };
+
+        match (command.to_lowercase().as_str(), values) {
+            (CREATE_ACCOUNT, [private_key, public_key, name @ ..]) if !name.is_empty() => {
+                Ok(ScriptCommand::CreateAccount {
+                    private_key: private_key.to_string(),
+                    public_key: public_key.to_string(),
+                    name: name.join(" "),
+                })
+            }
+            (GENERATE_ACCOUNT, name) if !name.is_empty() => {
+                Ok(ScriptCommand::GenerateAccount(name.join(" ")))
+            }
+            (SELECT_ACCOUNT, name) if !name.is_empty() => {
+                Ok(ScriptCommand::SelectAccount(name.join(" ")))
+            }
+            (SEND, [address, amount, fee]) => {
+                match (amount.parse::<Amount>(), fee.parse::<Amount>()) {
+                    (Ok(amount), Ok(fee)) if amount > Amount::ZERO && !fee.is_negative() => {
+                        Ok(ScriptCommand::Send {
+                            address: address.to_string(),
+                            amount,
+                            fee,
+                        })
+                    }
+                    _ => Err(wrong_values()),
+                }
+            }
+            (BALANCE, []) => Ok(ScriptCommand::Balance),
+            (EXIT, []) => Ok(ScriptCommand::Exit),
+            (CREATE_ACCOUNT | GENERATE_ACCOUNT | SELECT_ACCOUNT | SEND | BALANCE | EXIT, _) => {
+                Err(wrong_values())
+            }
+            _ => Err(ErrorUI::ErrorReading(format!(
+                "The command {command} is not known"
+            ))),
+        }
+    }
+}
@@ -1,14 +1,36 @@
 use super::error_ui::ErrorUI;
 
+use crate::process::reference::{get_reference, MutArc};
+
 use cargosos_bitcoin::{
-    block_structure::{block_chain::BlockChain, transaction::Transaction, utxo_set::UTXOSet},
+    block_structure::{
+        block_chain::BlockChain,
+        hash::{self, HashType},
+        transaction::{Transaction, LOCKTIME_THRESHOLD},
+        utxo_set::UTXOSet,
+    },
     logs::logger_sender::LoggerSender,
     notifications::{notification::Notification, notifier::Notifier},
     wallet_structure::{
-        account::Account, private_key::PrivateKey, public_key::PublicKey, wallet::Wallet,
+        account::Account,
+        balance_history::BalanceHistory,
+        faucet::Faucet,
+        private_key::PrivateKey,
+        public_key::PublicKey,
+        rescan::{Rescan, RescanResult},
+        transaction_history,
+        wallet::Wallet,
+        wallet_directory::WalletDirectory,
     },
 };
 
+use std::{fs, time::Duration};
+
+const NO_WALLET_DIRECTORY: &str = "There is no wallet directory configured";
+
+/// The time the private keys can be used after giving the spending password, before the wallet locks again
+const SPENDING_UNLOCK_TIME: Duration = Duration::from_secs(5 * 60);
+
 /// Function that obtains the balance of the selected account and sends it to the front
 pub fn give_account_balance<N: Notifier>(wallet: &Wallet, utxo_set: &UTXOSet, notifier: N) {
     let account_to_check = match wallet.get_selected_account() {
@@ -16,8 +38,8 @@ pub fn give_account_balance<N: Notifier>(wallet: &Wallet, utxo_set: &UTXOSet, no
         None => return notifier.notify(Notification::AccountNotSelected),
     };
 
-    let balance = utxo_set.get_balance_in_tbtc(&account_to_check.address);
-    let pending = utxo_set.get_pending_in_tbtc(&account_to_check.address);
+    let balance = utxo_set.get_balance(&account_to_check.address);
+    let pending = utxo_set.get_pending(&account_to_check.address);
 
     notifier.notify(Notification::LoadAvailableBalance(
         account_to_check.clone(),
@@ -65,18 +87,210 @@ pub fn create_account<N: Notifier>(
     public_key: PublicKey,
     notifier: N,
 ) -> Result<(), ErrorUI> {
-    let account = match Account::from_keys(account_name, private_key, public_key) {
+    let account =
+        match Account::from_keys(account_name, private_key, public_key, wallet.get_network()) {
+            Ok(account) => account,
+            _ => {
+                notifier.notify(Notification::AccountCreationFail);
+                return Ok(());
+            }
+        };
+
+    wallet.add_account(account.clone());
+    notifier.notify(Notification::RegisterWalletAccount(account));
+
+    Ok(())
+}
+
+/// Function that creates an account with a new pair of keys and adds it to the wallet
+pub fn generate_account<N: Notifier>(wallet: &mut Wallet, account_name: &str, notifier: N) {
+    let account = match Account::generate(account_name, wallet.get_network()) {
         Ok(account) => account,
-        _ => {
-            notifier.notify(Notification::AccountCreationFail);
-            return Ok(());
-        }
+        Err(_) => return notifier.notify(Notification::AccountCreationFail),
     };
 
     wallet.add_account(account.clone());
     notifier.notify(Notification::RegisterWalletAccount(account));
+}
 
-    Ok(())
+/// Function that returns the new value of a field of an account from the text entered by the
+/// user. It's `None` when the text is empty, to keep the value, and empty when the text is `-`,
+/// to remove it
+pub fn edited_value(text: &str) -> Option<String> {
+    match text.trim() {
+        "" => None,
+        "-" => Some(String::new()),
+        text => Some(text.to_string()),
+    }
+}
+
+/// Function that changes the name, label and description of an account. The values that are
+/// `None` are kept, while an empty label or description removes it
+pub fn edit_account<N: Notifier>(
+    wallet: &mut Wallet,
+    account_name: &str,
+    new_name: Option<String>,
+    label: Option<String>,
+    description: Option<String>,
+    notifier: N,
+) {
+    let metadata = match wallet.get_account_with_name(account_name) {
+        Some(account) => account.metadata.clone(),
+        None => {
+            return notifier.notify(Notification::AccountEditFailed(format!(
+                "The account {account_name} does not exist"
+            )))
+        }
+    };
+
+    let label = match label {
+        Some(label) => Some(label).filter(|label| !label.trim().is_empty()),
+        None => metadata.label,
+    };
+    let description = match description {
+        Some(description) => Some(description).filter(|description| !description.trim().is_empty()),
+        None => metadata.description,
+    };
+
+    let new_name = new_name.unwrap_or(account_name.to_string());
+    if let Err(error) = wallet.rename_account(account_name, &new_name) {
+        return notifier.notify(Notification::AccountEditFailed(format!("{:?}", error)));
+    }
+
+    let new_name = new_name.trim();
+    if let Err(error) = wallet.set_account_description(new_name, label, description) {
+        return notifier.notify(Notification::AccountEditFailed(format!("{:?}", error)));
+    }
+
+    if let Some(account) = wallet.get_account_with_name(new_name) {
+        notifier.notify(Notification::AccountEdited(
+            account_name.to_string(),
+            account.clone(),
+        ));
+    }
+}
+
+/// Function that writes the wallet as a JSON backup in the given path, encrypting its private keys
+/// when there is a password
+pub fn export_wallet<N: Notifier>(
+    wallet: &Wallet,
+    path: &str,
+    password: Option<&str>,
+    notifier: N,
+) {
+    let backup = match wallet.export_json(password) {
+        Ok(backup) => backup,
+        Err(error) => {
+            return notifier.notify(Notification::WalletBackupFailed(format!("{:?}", error)))
+        }
+    };
+
+    match fs::write(path, backup) {
+        Ok(()) => notifier.notify(Notification::WalletExported(path.to_string())),
+        Err(error) => notifier.notify(Notification::WalletBackupFailed(format!(
+            "Cannot write the backup in {path}, with error: {:?}",
+            error
+        ))),
+    }
+}
+
+/// Function that changes the spending password of the wallet, removing it when there is no new password
+pub fn set_spending_password<N: Notifier>(
+    wallet: &mut Wallet,
+    password: Option<&str>,
+    new_password: Option<&str>,
+    notifier: N,
+) {
+    match wallet.set_spending_password(password, new_password) {
+        Ok(()) => notifier.notify(Notification::SpendingPasswordChanged(
+            new_password.is_some(),
+        )),
+        Err(error) => notifier.notify(Notification::SpendingPasswordRejected(format!(
+            "{:?}",
+            error
+        ))),
+    }
+}
+
+/// Function that lets the private keys of a locked wallet be used for a while with the spending
+/// password. It returns if the private keys can be used, notifying the user when they cannot
+pub fn unlock_wallet<N: Notifier>(
+    wallet: &mut Wallet,
+    password: Option<&str>,
+    notifier: N,
+) -> bool {
+    if !wallet.is_locked() {
+        return true;
+    }
+
+    let password = match password {
+        Some(password) => password,
+        None => {
+            notifier.notify(Notification::SpendingPasswordRejected(
+                "The wallet is locked, the spending password is needed to sign".to_string(),
+            ));
+            return false;
+        }
+    };
+
+    match wallet.unlock(password, SPENDING_UNLOCK_TIME) {
+        Ok(()) => {
+            notifier.notify(Notification::WalletUnlocked(SPENDING_UNLOCK_TIME));
+            true
+        }
+        Err(error) => {
+            notifier.notify(Notification::SpendingPasswordRejected(format!(
+                "{:?}",
+                error
+            )));
+            false
+        }
+    }
+}
+
+/// Function that adds to the wallet the accounts of the JSON backup in the given path. The accounts
+/// with the name of one already in the wallet are not added
+pub fn import_wallet<N: Notifier>(
+    wallet: &mut Wallet,
+    path: &str,
+    password: Option<&str>,
+    notifier: N,
+) {
+    let backup = match fs::read_to_string(path) {
+        Ok(backup) => backup,
+        Err(error) => {
+            return notifier.notify(Notification::WalletBackupFailed(format!(
+                "Cannot read the backup in {path}, with error: {:?}",
+                error
+            )))
+        }
+    };
+
+    let imported_wallet = match Wallet::import_json(&backup, password, wallet.get_network()) {
+        Ok(imported_wallet) => imported_wallet,
+        Err(error) => {
+            return notifier.notify(Notification::WalletBackupFailed(format!("{:?}", error)))
+        }
+    };
+
+    let mut accounts_added = 0;
+    for account in imported_wallet.get_accounts() {
+        if wallet
+            .get_account_with_name(&account.account_name)
+            .is_some()
+        {
+            continue;
+        }
+
+        wallet.add_account(account.clone());
+        notifier.notify(Notification::RegisterWalletAccount(account.clone()));
+        accounts_added += 1;
+    }
+
+    notifier.notify(Notification::WalletImported(
+        path.to_string(),
+        accounts_added,
+    ));
 }
 
 /// Function that gets the information of the transactions of the selected account
@@ -101,3 +315,379 @@ pub fn give_account_transactions<N: Notifier>(
 
     Ok(())
 }
+
+/// Function that gets the transactions of every account of the wallet, with the labels given to
+/// them, and sends them to the front
+pub fn give_transaction_history<N: Notifier>(
+    wallet: &Wallet,
+    blockchain: &BlockChain,
+    notifier: N,
+    logger: LoggerSender,
+) {
+    let mut history =
+        transaction_history::get_transaction_history(blockchain, wallet.get_accounts());
+    for entry in history.iter_mut() {
+        entry.label = wallet.get_transaction_label(&entry.transaction_id).cloned();
+    }
+    let _ = logger.log_wallet(format!(
+        "Found {} transactions of the accounts of the wallet",
+        history.len()
+    ));
+    notifier.notify(Notification::TransactionHistory(history));
+}
+
+/// Function that changes the label of a transaction in the wallet. An empty label removes it
+pub fn label_transaction<N: Notifier>(
+    wallet: &mut Wallet,
+    transaction_id: HashType,
+    label: Option<String>,
+    notifier: N,
+    logger: LoggerSender,
+) {
+    wallet.set_transaction_label(transaction_id, label);
+    let label = wallet.get_transaction_label(&transaction_id).cloned();
+
+    let _ = logger.log_wallet(format!(
+        "The label of the transaction {} is now {:?}",
+        hash::to_hex_be(&transaction_id),
+        label
+    ));
+    notifier.notify(Notification::TransactionLabelChanged(transaction_id, label));
+}
+
+/// Function that obtains the balance history of the selected account from the given height
+/// and sends it to the front
+pub fn give_account_balance_history<N: Notifier>(
+    wallet: &Wallet,
+    balance_history: &BalanceHistory,
+    from_height: u64,
+    notifier: N,
+) {
+    let account = match wallet.get_selected_account() {
+        Some(account) => account.clone(),
+        None => return notifier.notify(Notification::AccountNotSelected),
+    };
+
+    let history = balance_history.balance_history(&account, from_height);
+    notifier.notify(Notification::AccountBalanceHistory(account, history));
+}
+
+/// Function that rescans the blocks of the longest chain for the accounts of the wallet from the given
+/// height, rebuilding their transaction history, unspent outputs and balance history. As with a lock
+/// time, a value from 500000000 is the timestamp of the first block to rescan. The wallet and the
+/// block chain are only locked while the blocks to rescan are copied
+pub fn rescan_wallet<N: Notifier>(
+    wallet: &MutArc<Wallet>,
+    balance_history: &MutArc<BalanceHistory>,
+    block_chain: &MutArc<BlockChain>,
+    from: u64,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let rescan = {
+        let wallet = get_reference(wallet)?;
+        let block_chain = get_reference(block_chain)?;
+
+        let from_height = match from < LOCKTIME_THRESHOLD as u64 {
+            true => from,
+            false => u32::try_from(from)
+                .ok()
+                .and_then(|timestamp| block_chain.get_height_with_timestamp(timestamp))
+                .unwrap_or(u64::MAX),
+        };
+        Rescan::new(&block_chain, wallet.get_accounts(), from_height)
+    };
+
+    let from_height = rescan.from_height();
+    let _ = logger.log_wallet(format!(
+        "Rescanning the wallet from the height {from_height}"
+    ));
+    let RescanResult {
+        mut history,
+        owned_utxos,
+        balance_history: rescanned_balance_history,
+    } = rescan.run(notifier.clone());
+
+    get_reference(balance_history)?.replace_from(from_height, rescanned_balance_history);
+    {
+        let wallet = get_reference(wallet)?;
+        for entry in history.iter_mut() {
+            entry.label = wallet.get_transaction_label(&entry.transaction_id).cloned();
+        }
+    }
+
+    let _ = logger.log_wallet(format!(
+        "Rescan of the wallet finished, with {} transactions and {} unspent outputs found",
+        history.len(),
+        owned_utxos.len(),
+    ));
+    notifier.notify(Notification::TransactionHistory(history));
+    Ok(())
+}
+
+/// Function that requests coins from the faucet to the selected account and sends the result to the front.
+/// The wallet and the faucet are not locked while waiting for the answer of the faucet
+pub fn request_faucet_coins<N: Notifier>(
+    wallet: &MutArc<Wallet>,
+    faucet: &Option<MutArc<Faucet>>,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let selected_account = get_reference(wallet)?.get_selected_account().cloned();
+    let account = match selected_account {
+        Some(account) => account,
+        None => {
+            let _ = logger.log_wallet("No account selected cannot request coins".to_string());
+            notifier.notify(Notification::AccountNotSelected);
+            return Ok(());
+        }
+    };
+
+    let faucet = match faucet {
+        Some(faucet) => faucet,
+        None => {
+            notifier.notify(Notification::FaucetRequestFailed(
+                "There is no faucet configured".to_string(),
+            ));
+            return Ok(());
+        }
+    };
+
+    let requesting_faucet = get_reference(faucet)?.clone();
+    match requesting_faucet.request_coins(&account.address) {
+        Ok(transaction_id) => {
+            get_reference(faucet)?.add_pending_request(transaction_id);
+            let _ = logger.log_wallet(format!("Coins requested to the faucet for {account}"));
+            notifier.notify(Notification::FaucetCoinsRequested(account, transaction_id));
+        }
+        Err(error) => {
+            let _ = logger.log_wallet(format!("Fail to request coins, with error: {:?}", error));
+            notifier.notify(Notification::FaucetRequestFailed(format!("{:?}", error)));
+        }
+    }
+
+    Ok(())
+}
+
+/// Function that sends the names of the wallets in the wallet directory to the front
+pub fn give_available_wallets<N: Notifier>(
+    wallet_directory: &Option<MutArc<WalletDirectory>>,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let wallet_directory = match wallet_directory {
+        Some(wallet_directory) => wallet_directory,
+        None => return Ok(()),
+    };
+
+    let wallet_directory = get_reference(wallet_directory)?;
+    match wallet_directory.get_wallet_names() {
+        Ok(wallet_names) => notifier.notify(Notification::AvailableWallets(
+            wallet_directory
+                .get_selected_wallet()
+                .unwrap_or_default()
+                .to_string(),
+            wallet_names,
+        )),
+        Err(error) => {
+            let _ = logger.log_wallet(format!("Fail to read the wallets, with error: {:?}", error));
+        }
+    }
+
+    Ok(())
+}
+
+/// Function that changes the wallet in use for the one with the given name, saving the current one in its file.
+/// The accounts of the new wallet are sent to the front and its balance history is calculated.
+/// If the wallet is already in use nothing changes
+pub fn change_wallet<N: Notifier>(
+    wallet_name: &str,
+    wallet: &mut Wallet,
+    balance_history: &mut BalanceHistory,
+    wallet_directory: &Option<MutArc<WalletDirectory>>,
+    block_chain: &BlockChain,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let wallet_directory = match wallet_directory {
+        Some(wallet_directory) => wallet_directory,
+        None => {
+            notifier.notify(Notification::WalletChangeFailed(
+                NO_WALLET_DIRECTORY.to_string(),
+            ));
+            return Ok(());
+        }
+    };
+
+    let mut wallet_directory = get_reference(wallet_directory)?;
+    if wallet_directory.get_selected_wallet() == Some(wallet_name) {
+        let _ = logger.log_wallet(format!("The wallet {wallet_name} is already in use"));
+        return Ok(());
+    }
+
+    if let Err(error) = wallet_directory.switch_wallet(wallet, wallet_name) {
+        let _ = logger.log_wallet(format!(
+            "Fail to change the wallet, with error: {:?}",
+            error
+        ));
+        notifier.notify(Notification::WalletChangeFailed(format!("{:?}", error)));
+        return Ok(());
+    }
+
+    let _ = logger.log_wallet(format!("Changed to the wallet {wallet_name}"));
+    show_wallet_in_use(
+        wallet,
+        balance_history,
+        &wallet_directory,
+        block_chain,
+        notifier,
+    );
+    Ok(())
+}
+
+/// Function that creates a new empty wallet with the given name in the wallet directory and starts
+/// using it, saving the current one in its file
+pub fn create_wallet<N: Notifier>(
+    wallet_name: &str,
+    wallet: &mut Wallet,
+    balance_history: &mut BalanceHistory,
+    wallet_directory: &Option<MutArc<WalletDirectory>>,
+    block_chain: &BlockChain,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let wallet_directory = match wallet_directory {
+        Some(wallet_directory) => wallet_directory,
+        None => {
+            notifier.notify(Notification::WalletChangeFailed(
+                NO_WALLET_DIRECTORY.to_string(),
+            ));
+            return Ok(());
+        }
+    };
+
+    let mut wallet_directory = get_reference(wallet_directory)?;
+    if let Err(error) = wallet_directory.create_wallet(wallet, wallet_name) {
+        let _ = logger.log_wallet(format!(
+            "Fail to create the wallet, with error: {:?}",
+            error
+        ));
+        notifier.notify(Notification::WalletChangeFailed(format!("{:?}", error)));
+        return Ok(());
+    }
+
+    let _ = logger.log_wallet(format!("Created the wallet {wallet_name}"));
+    show_wallet_in_use(
+        wallet,
+        balance_history,
+        &wallet_directory,
+        block_chain,
+        notifier,
+    );
+    Ok(())
+}
+
+/// Function that starts using the wallet saved in the given file, that can be outside the wallet
+/// directory, saving the current one in its file. The wallet is saved back in the same file
+pub fn open_wallet_file<N: Notifier>(
+    path: &str,
+    wallet: &mut Wallet,
+    balance_history: &mut BalanceHistory,
+    wallet_directory: &Option<MutArc<WalletDirectory>>,
+    block_chain: &BlockChain,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let wallet_directory = match wallet_directory {
+        Some(wallet_directory) => wallet_directory,
+        None => {
+            notifier.notify(Notification::WalletChangeFailed(
+                NO_WALLET_DIRECTORY.to_string(),
+            ));
+            return Ok(());
+        }
+    };
+
+    let mut wallet_directory = get_reference(wallet_directory)?;
+    if let Err(error) = wallet_directory.open_wallet_file(wallet, path) {
+        let _ = logger.log_wallet(format!("Fail to open the wallet, with error: {:?}", error));
+        notifier.notify(Notification::WalletChangeFailed(format!("{:?}", error)));
+        return Ok(());
+    }
+
+    let _ = logger.log_wallet(format!("Opened the wallet in {path}"));
+    show_wallet_in_use(
+        wallet,
+        balance_history,
+        &wallet_directory,
+        block_chain,
+        notifier,
+    );
+    Ok(())
+}
+
+/// Function that saves the wallet in use and closes it, leaving an empty wallet until another one is used
+pub fn close_wallet<N: Notifier>(
+    wallet: &mut Wallet,
+    balance_history: &mut BalanceHistory,
+    wallet_directory: &Option<MutArc<WalletDirectory>>,
+    notifier: N,
+    logger: LoggerSender,
+) -> Result<(), ErrorUI> {
+    let wallet_directory = match wallet_directory {
+        Some(wallet_directory) => wallet_directory,
+        None => {
+            notifier.notify(Notification::WalletChangeFailed(
+                NO_WALLET_DIRECTORY.to_string(),
+            ));
+            return Ok(());
+        }
+    };
+
+    let mut wallet_directory = get_reference(wallet_directory)?;
+    let wallet_name = match wallet_directory.get_selected_wallet() {
+        Some(wallet_name) => wallet_name.to_string(),
+        None => return Ok(()),
+    };
+
+    if let Err(error) = wallet_directory.close_wallet(wallet) {
+        let _ = logger.log_wallet(format!("Fail to close the wallet, with error: {:?}", error));
+        notifier.notify(Notification::WalletChangeFailed(format!("{:?}", error)));
+        return Ok(());
+    }
+
+    let _ = logger.log_wallet(format!("Closed the wallet {wallet_name}"));
+    *balance_history = BalanceHistory::new();
+
+    notifier.notify(Notification::WalletClosed(wallet_name));
+    if let Ok(wallet_names) = wallet_directory.get_wallet_names() {
+        notifier.notify(Notification::AvailableWallets(String::new(), wallet_names));
+    }
+
+    Ok(())
+}
+
+/// Sends the wallet that started being used to the front, with its accounts, and calculates its
+/// balance history
+fn show_wallet_in_use<N: Notifier>(
+    wallet: &Wallet,
+    balance_history: &mut BalanceHistory,
+    wallet_directory: &WalletDirectory,
+    block_chain: &BlockChain,
+    notifier: N,
+) {
+    let wallet_name = wallet_directory
+        .get_selected_wallet()
+        .unwrap_or_default()
+        .to_string();
+    *balance_history = BalanceHistory::from_blockchain(block_chain, wallet.get_accounts());
+
+    notifier.notify(Notification::WalletChanged(wallet_name.clone()));
+    for account in wallet.get_accounts() {
+        notifier.notify(Notification::RegisterWalletAccount(account.clone()));
+    }
+
+    if let Ok(wallet_names) = wallet_directory.get_wallet_names() {
+        notifier.notify(Notification::AvailableWallets(wallet_name, wallet_names));
+    }
+}
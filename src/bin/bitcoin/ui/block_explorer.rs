@@ -0,0 +1,58 @@
+use cargosos_bitcoin::{
+    block_structure::{block_chain::BlockChain, block_summary::BlockSummary, hash::HashType},
+    logs::logger_sender::LoggerSender,
+    notifications::{notification::Notification, notifier::Notifier},
+};
+
+/// Function that sends to the front the summary of the last blocks of the main chain, from the newest one
+pub fn give_recent_blocks<N: Notifier>(
+    block_chain: &BlockChain,
+    amount: usize,
+    notifier: N,
+    logger: LoggerSender,
+) {
+    let mut summaries: Vec<BlockSummary> = Vec::new();
+    for (height, block) in block_chain.get_recent_blocks(amount) {
+        match BlockSummary::new(height, &block) {
+            Ok(summary) => summaries.push(summary),
+            Err(error) => {
+                let _ = logger.log_error(format!(
+                    "The block at height {height} could not be summarized, with error: {:?}",
+                    error
+                ));
+            }
+        }
+    }
+
+    notifier.notify(Notification::RecentBlocks(summaries));
+}
+
+/// Function that sends to the front the transactions of the block with the given hash
+pub fn give_block_transactions<N: Notifier>(
+    block_chain: &BlockChain,
+    block_hash: HashType,
+    notifier: N,
+    logger: LoggerSender,
+) {
+    let (block, height) = match (
+        block_chain.get_block_with_hash(&block_hash),
+        block_chain.get_height_with_hash(&block_hash),
+    ) {
+        (Some(block), Some(height)) => (block, height),
+        _ => {
+            let _ = logger.log_transaction("The block requested was not found".to_string());
+            return notifier.notify(Notification::RawDataProblem(
+                "Block not found in the local blockchain".to_string(),
+            ));
+        }
+    };
+
+    match BlockSummary::new(height, &block) {
+        Ok(summary) => {
+            notifier.notify(Notification::BlockTransactions(summary, block.transactions))
+        }
+        Err(_) => notifier.notify(Notification::RawDataProblem(
+            "The block could not be summarized".to_string(),
+        )),
+    }
+}
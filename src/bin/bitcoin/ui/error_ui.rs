@@ -14,6 +14,9 @@ pub enum ErrorUI {
     /// It will appear when trying to create a transaction of an amount and fee greater than the balance
     TransactionWithoutSufficientFunds,
 
+    /// It will appear when trying to create a transaction of an amount below the dust threshold, being the threshold in satoshis
+    TransactionWithDustAmount(i64),
+
     /// It will appear when trying to create a transaction and fails to create the signature script for it
     TransactionCreationFail,
 
@@ -35,17 +38,22 @@ pub enum ErrorUI {
     /// It will appear when a conextion with a peer fails
     ErrorFromPeer(String),
 
+    /// It will appear when the data of a wallet, as an address or a payment request, is not valid
+    ErrorFromWallet(String),
+
     /// It will appear when can't create the default value
     CannotCreateDefault,
 
     /// It will appear when try to get a value that is already loaded
     AlreadyLoaded,
 
-    /// It will appear when we try to unwrap an Arc
-    CannotUnwrapArc,
+    /// It will appear when a shared value stays locked by another thread longer than the time
+    /// waited for it, being the name of the value
+    LockTimeout(String),
 
-    /// It will appear when we try to get the inner value of a mutex
-    CannotGetInner,
+    /// It will appear when a shared value is taken out while other threads still have it, being
+    /// the name of the value and the amount of threads
+    StillShared(String, usize),
 
     /// It will appear when we try to send a signal to the front and it fails
     FailedSignalToFront(String),
@@ -55,6 +63,9 @@ pub enum ErrorUI {
 
     /// It will appear when a receiver is missing
     MissingReceiver,
+
+    /// It will appear when a shutdown is requested while waiting for the user
+    Interrupted,
 }
 
 impl From<ErrorProcess> for ErrorUI {
@@ -67,17 +78,28 @@ impl From<ErrorProcess> for ErrorUI {
                 ErrorUI::ErrorWriting("While processing data".to_string())
             }
             ErrorProcess::FailThread => ErrorUI::FailThread("While processing data".to_string()),
+            ErrorProcess::StopTimeout => {
+                ErrorUI::FailThread("The thread didn't stop in time".to_string())
+            }
             ErrorProcess::ErrorFromPeer(message) => ErrorUI::ErrorFromPeer(message),
             ErrorProcess::CannotCreateDefault => ErrorUI::CannotCreateDefault,
             ErrorProcess::AlreadyLoaded => ErrorUI::AlreadyLoaded,
-            ErrorProcess::CannotGetInner => ErrorUI::CannotGetInner,
-            ErrorProcess::CannotUnwrapArc => ErrorUI::CannotUnwrapArc,
+            ErrorProcess::LockTimeout(name) => ErrorUI::LockTimeout(name),
+            ErrorProcess::StillShared(name, amount) => ErrorUI::StillShared(name, amount),
             ErrorProcess::TransactionWithoutSufficientFunds => {
                 ErrorUI::TransactionWithoutSufficientFunds
             }
+            ErrorProcess::TransactionWithDustAmount(dust_threshold) => {
+                ErrorUI::TransactionWithDustAmount(dust_threshold)
+            }
             ErrorProcess::TransactionCreationFail => ErrorUI::TransactionCreationFail,
             ErrorProcess::ConnectionAborted => ErrorUI::ConnectionAborted,
             ErrorProcess::InformationNotReady => ErrorUI::InformationNotReady,
+            ErrorProcess::FileVersionNotSupported(path, version, supported) => {
+                ErrorUI::ErrorReading(format!(
+                    "The file {path} has the version {version}, newer than the supported {supported}"
+                ))
+            }
         }
     }
 }
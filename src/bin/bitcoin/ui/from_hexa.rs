@@ -1,5 +1,7 @@
 use super::error_ui::ErrorUI;
 
+use cargosos_bitcoin::block_structure::hash::{self, HashType};
+
 pub fn from<const N: usize>(value: &str) -> Result<[u8; N], ErrorUI> {
     let mut bytes: Vec<u8> = Vec::new();
 
@@ -35,3 +37,15 @@ pub fn from<const N: usize>(value: &str) -> Result<[u8; N], ErrorUI> {
 
     Ok(bytes)
 }
+
+/// Reads a hash in the order it's shown to the user, as the block explorers do, returning it in the
+/// order used in the protocol
+pub fn hash_in_display_order(value: &str) -> Result<HashType, ErrorUI> {
+    match hash::from_hex_be(value) {
+        Ok(hash) => Ok(hash),
+        Err(error) => Err(ErrorUI::ErrorReading(format!(
+            "Error while converting a string ({value}) into a hash: {:?}",
+            error
+        ))),
+    }
+}
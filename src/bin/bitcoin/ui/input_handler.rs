@@ -1,11 +1,11 @@
 use super::error_ui::ErrorUI;
 
-use crate::process::reference::MutArc;
+use crate::process::{broadcasting::WalletState, reference::MutArc};
 
 use cargosos_bitcoin::{
-    block_structure::{block_chain::BlockChain, utxo_set::UTXOSet},
-    node_structure::broadcasting::Broadcasting,
-    wallet_structure::wallet::Wallet,
+    block_structure::block_chain::BlockChain,
+    node_structure::{broadcasting::Broadcasting, script_subscriptions::ScriptSubscriptions},
+    wallet_structure::wallet_directory::WalletDirectory,
 };
 
 use std::io::{Read, Write};
@@ -17,8 +17,9 @@ where
     fn handle_input(
         &self,
         broadcasting: MutArc<Broadcasting<RW>>,
-        wallet: MutArc<Wallet>,
-        utxo_set: MutArc<UTXOSet>,
+        wallet_state: WalletState,
         block_chain: MutArc<BlockChain>,
+        wallet_directory: Option<MutArc<WalletDirectory>>,
+        script_subscriptions: MutArc<ScriptSubscriptions>,
     ) -> Result<(), ErrorUI>;
 }
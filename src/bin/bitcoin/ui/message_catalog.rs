@@ -0,0 +1,614 @@
+use cargosos_bitcoin::configurations::language::Language;
+
+use std::fmt::Display;
+
+const OPEN_ARGUMENT: char = '{';
+const CLOSE_ARGUMENT: char = '}';
+
+/// It's the texts shown by the interfaces in the language of the configuration. The texts are looked
+/// up by their English version, which is also what is shown when they have no translation
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MessageCatalog {
+    language: Language,
+}
+
+impl MessageCatalog {
+    pub fn new(language: Language) -> Self {
+        MessageCatalog { language }
+    }
+
+    /// Returns the text in the language of the catalog
+    pub fn text(&self, key: &str) -> String {
+        let messages = match self.language {
+            Language::English => return key.to_string(),
+            Language::Spanish => SPANISH,
+        };
+
+        match messages.iter().find(|(english, _)| *english == key) {
+            Some((_, translation)) => translation.to_string(),
+            None => key.to_string(),
+        }
+    }
+
+    /// Returns the text in the language of the catalog, with each `{n}` replaced by the argument in
+    /// the position `n`. The arguments are placed by position because the translations can change
+    /// their order
+    pub fn format(&self, key: &str, arguments: &[&dyn Display]) -> String {
+        let text = self.text(key);
+        let mut formatted = String::with_capacity(text.len());
+
+        let mut rest = text.as_str();
+        while let Some(start) = rest.find(OPEN_ARGUMENT) {
+            formatted.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            let argument = rest.find(CLOSE_ARGUMENT).and_then(|end| {
+                rest[1..end]
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|position| arguments.get(position))
+                    .map(|argument| (end, argument))
+            });
+
+            match argument {
+                Some((end, argument)) => {
+                    formatted.push_str(&argument.to_string());
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    formatted.push(OPEN_ARGUMENT);
+                    rest = &rest[1..];
+                }
+            }
+        }
+        formatted.push_str(rest);
+
+        formatted
+    }
+}
+
+/// The spanish translation of the texts of the menus, the notifications and the window
+const SPANISH: &[(&str, &str)] = &[
+    // Commands of the menu
+    ("Create account", "Crear cuenta"),
+    ("Generate account", "Generar cuenta"),
+    ("Change account", "Cambiar de cuenta"),
+    ("Remove account", "Eliminar cuenta"),
+    ("Edit account", "Editar cuenta"),
+    ("Send transaction", "Enviar transacción"),
+    ("Show accounts", "Mostrar cuentas"),
+    ("Show balance", "Mostrar saldo"),
+    ("Last transactions", "Últimas transacciones"),
+    ("Label transaction", "Etiquetar transacción"),
+    (
+        "Verify merkle proof of inclusion",
+        "Verificar la prueba de inclusión de merkle",
+    ),
+    ("Balance history", "Historial del saldo"),
+    ("Rescan wallet", "Reescanear la billetera"),
+    ("Request faucet coins", "Pedir monedas al faucet"),
+    ("Change wallet", "Cambiar de billetera"),
+    ("Export wallet backup", "Exportar copia de la billetera"),
+    ("Import wallet backup", "Importar copia de la billetera"),
+    ("Change spending password", "Cambiar la contraseña de gasto"),
+    ("Sweep private key", "Barrer clave privada"),
+    ("Dump block as hex", "Mostrar bloque en hexadecimal"),
+    (
+        "Dump transaction as hex",
+        "Mostrar transacción en hexadecimal",
+    ),
+    (
+        "Broadcast raw transaction",
+        "Difundir transacción en crudo",
+    ),
+    (
+        "Describe raw transaction",
+        "Describir transacción en crudo",
+    ),
+    ("Mempool information", "Información de la mempool"),
+    ("Exit", "Salir"),
+    ("Enter a command:", "Ingrese un comando:"),
+    (
+        "Error, please enter a valid command:",
+        "Error, por favor ingrese un comando válido:",
+    ),
+    ("Matching commands", "Comandos que coinciden"),
+    (
+        "Enter one of the matching commands:",
+        "Ingrese uno de los comandos que coinciden:",
+    ),
+    // Notifications
+    ("Attempting handshake with {0}", "Intentando el handshake con {0}"),
+    ("Successful handshake with {0}", "Handshake exitoso con {0}"),
+    ("Failed handshake with {0}", "Falló el handshake con {0}"),
+    (
+        "Attempting handshake with peer {0}",
+        "Intentando el handshake con el par {0}",
+    ),
+    (
+        "Successful handshake with peer {0}",
+        "Handshake exitoso con el par {0}",
+    ),
+    (
+        "Failed handshake with peer {0}",
+        "Falló el handshake con el par {0}",
+    ),
+    ("Connection updated: {0}", "Conexión actualizada: {0}"),
+    ("Latency with {0}: {1} ms", "Latencia con {0}: {1} ms"),
+    ("Latency with peer {0}: {1} ms", "Latencia con el par {0}: {1} ms"),
+    ("Disconnected from {0}", "Desconectado de {0}"),
+    ("Clock skew", "Desfase del reloj"),
+    (
+        "Our clock deviates {0} seconds from the time of the network,\n    check the date of the computer",
+        "Nuestro reloj se desvía {0} segundos de la hora de la red,\n    revise la fecha de la computadora",
+    ),
+    (
+        "Our clock deviates {0} seconds from the time of the network, check the date of the computer",
+        "Nuestro reloj se desvía {0} segundos de la hora de la red, revise la fecha de la computadora",
+    ),
+    ("The state of the node was saved", "Se guardó el estado del nodo"),
+    ("Last synced tip", "Última punta sincronizada"),
+    (
+        "Last synced to height {0}, {1} hours ago",
+        "Sincronizado por última vez a la altura {0}, hace {1} horas",
+    ),
+    ("Transaction received", "Transacción recibida"),
+    (
+        "The transaction: {0} was received\n    in the account: {1}",
+        "La transacción: {0} fue recibida\n    en la cuenta: {1}",
+    ),
+    ("Transaction in block", "Transacción en un bloque"),
+    (
+        "The transaction {0}was added\n    to a block with hash {1}",
+        "La transacción {0}fue agregada\n    a un bloque con hash {1}",
+    ),
+    ("New block added", "Nuevo bloque agregado"),
+    (
+        "The block {0}\n    was added to the blockchain\n    with {1}",
+        "El bloque {0}\n    fue agregado a la cadena de bloques\n    con {1}",
+    ),
+    ("Account selected: {0}", "Cuenta seleccionada: {0}"),
+    (
+        "New account {0} was added to the wallet",
+        "La nueva cuenta {0} fue agregada a la billetera",
+    ),
+    ("Blockchain is up to date", "La cadena de bloques está actualizada"),
+    (
+        "Account: {0} with balance: {1} and pending: {2}",
+        "Cuenta: {0} con saldo: {1} y pendiente: {2}",
+    ),
+    (
+        "Account: {0} changed to balance: {1} and pending: {2}",
+        "Cuenta: {0} cambió a saldo: {1} y pendiente: {2}",
+    ),
+    (
+        "Transaction {0} of the account {1} was confirmed",
+        "La transacción {0} de la cuenta {1} fue confirmada",
+    ),
+    (
+        "Account: {0} with balance {1} at height {2}",
+        "Cuenta: {0} con saldo {1} a la altura {2}",
+    ),
+    (
+        "The tip of the blockchain advanced to height {0}",
+        "La punta de la cadena de bloques avanzó a la altura {0}",
+    ),
+    (
+        "The mempool has {0} transactions with {1} sat in fees",
+        "La mempool tiene {0} transacciones con {1} sat en comisiones",
+    ),
+    ("Account not selected", "No hay una cuenta seleccionada"),
+    (
+        "No account selected cannot get transactions",
+        "No hay una cuenta seleccionada, no se pueden obtener las transacciones",
+    ),
+    ("In the account: {0}", "En la cuenta: {0}"),
+    ("Transaction history", "Historial de transacciones"),
+    (
+        "{0}: {1} {2} satoshis in {3} ({4} confirmations)\n",
+        "{0}: {1} {2} satoshis en {3} ({4} confirmaciones)\n",
+    ),
+    ("Height: {0}    Balance: {1}\n", "Altura: {0}    Saldo: {1}\n"),
+    (
+        "Balance history of the account: {0}",
+        "Historial del saldo de la cuenta: {0}",
+    ),
+    (
+        "Rescanned {0} of {1} blocks for the wallet",
+        "Se reescanearon {0} de {1} bloques para la billetera",
+    ),
+    ("Faucet coins requested", "Monedas pedidas al faucet"),
+    (
+        "The faucet sent the transaction {0}\n    to the account: {1}",
+        "El faucet envió la transacción {0}\n    a la cuenta: {1}",
+    ),
+    ("Faucet request failed: {0}", "Falló el pedido al faucet: {0}"),
+    ("Wallet changed", "Billetera cambiada"),
+    ("Using the wallet {0}", "Usando la billetera {0}"),
+    ("Wallet closed", "Billetera cerrada"),
+    ("The wallet {0} was closed", "Se cerró la billetera {0}"),
+    ("{0} (in use)\n", "{0} (en uso)\n"),
+    ("Available wallets", "Billeteras disponibles"),
+    (
+        "Wallet change failed: {0}",
+        "Falló el cambio de billetera: {0}",
+    ),
+    ("Raw block {0}", "Bloque en crudo {0}"),
+    ("Raw transaction {0}", "Transacción en crudo {0}"),
+    ("created with {0} satoshis", "creada con {0} satoshis"),
+    ("spent by {0}", "gastada por {0}"),
+    ("Subscribed script", "Script suscripto"),
+    (
+        "The output {0}\n    of the script {1}\n    was {2}",
+        "La salida {0}\n    del script {1}\n    fue {2}",
+    ),
+    ("Transaction {0} described", "Transacción {0} descripta"),
+    ("Transaction description", "Descripción de la transacción"),
+    (
+        "Error while getting the raw data",
+        "Error al obtener los datos en crudo",
+    ),
+    (
+        "The raw data could not be obtained\n the error was: {0}",
+        "No se pudieron obtener los datos en crudo\n el error fue: {0}",
+    ),
+    ("Raw data not obtained: {0}", "Datos en crudo no obtenidos: {0}"),
+    ("{0} satoshis", "{0} satoshis"),
+    ("unknown", "desconocidas"),
+    (
+        "{0} {1} with {2} transactions, subsidy of {3} satoshis and fees {4}\n",
+        "{0} {1} con {2} transacciones, subsidio de {3} satoshis y comisiones {4}\n",
+    ),
+    ("Recent blocks", "Bloques recientes"),
+    (
+        "Transactions of the block {0} at height {1}",
+        "Transacciones del bloque {0} a la altura {1}",
+    ),
+    ("Faucet transaction confirmed", "Transacción del faucet confirmada"),
+    (
+        "The faucet transaction {0}\n    was confirmed in the block {1}",
+        "La transacción del faucet {0}\n    fue confirmada en el bloque {1}",
+    ),
+    ("Invalid address enter", "Se ingresó una dirección inválida"),
+    ("Invalid public key enter", "Se ingresó una clave pública inválida"),
+    ("Invalid private key enter", "Se ingresó una clave privada inválida"),
+    ("Invalid address", "Dirección inválida"),
+    ("Invalid public key", "Clave pública inválida"),
+    ("Invalid private key", "Clave privada inválida"),
+    ("Creation of the account fail", "Falló la creación de la cuenta"),
+    ("Error in account creation", "Error en la creación de la cuenta"),
+    (
+        "The account {0} was edited: {1}",
+        "La cuenta {0} fue editada: {1}",
+    ),
+    (
+        "Edition of the account fail: {0}",
+        "Falló la edición de la cuenta: {0}",
+    ),
+    (
+        "Error editing the account: {0}",
+        "Error al editar la cuenta: {0}",
+    ),
+    (
+        "The transaction {0} is labelled: {1}",
+        "La transacción {0} tiene la etiqueta: {1}",
+    ),
+    (
+        "The label of the transaction {0} was removed",
+        "Se quitó la etiqueta de la transacción {0}",
+    ),
+    (
+        "The wallet was exported to {0}",
+        "La billetera fue exportada a {0}",
+    ),
+    ("Imported {0} accounts from {1}", "Se importaron {0} cuentas de {1}"),
+    (
+        "Backup of the wallet failed: {0}",
+        "Falló la copia de la billetera: {0}",
+    ),
+    (
+        "The spending password was changed",
+        "Se cambió la contraseña de gasto",
+    ),
+    (
+        "The spending password was removed",
+        "Se quitó la contraseña de gasto",
+    ),
+    (
+        "The wallet is unlocked for {0} seconds",
+        "La billetera está desbloqueada por {0} segundos",
+    ),
+    (
+        "The spending password was rejected: {0}",
+        "Se rechazó la contraseña de gasto: {0}",
+    ),
+    ("Transaction to confirm", "Transacción a confirmar"),
+    (
+        "Recipient: {0}\nAmount: {1} satoshis\nFee: {2} satoshis ({3} satoshis/byte)\nChange: {4} satoshis\nResulting balance: {5} satoshis",
+        "Destinatario: {0}\nMonto: {1} satoshis\nComisión: {2} satoshis ({3} satoshis/byte)\nVuelto: {4} satoshis\nSaldo resultante: {5} satoshis",
+    ),
+    (
+        "Not enough founds to create transaction",
+        "No hay fondos suficientes para crear la transacción",
+    ),
+    (
+        "Error creating the transaction",
+        "Error al crear la transacción",
+    ),
+    (
+        "The amount is below the dust threshold of {0} satoshis, so the transaction would not be relayed",
+        "El monto está debajo del umbral de polvo de {0} satoshis, por lo que la transacción no sería retransmitida",
+    ),
+    (
+        "The raw transaction was rejected: {0}",
+        "La transacción en crudo fue rechazada: {0}",
+    ),
+    (
+        "Raw transaction rejected: {0}",
+        "Transacción en crudo rechazada: {0}",
+    ),
+    ("Transaction conflicted", "Transacción en conflicto"),
+    (
+        "The transaction {0} was dropped\n    the transaction {1} of a new block spends the same outputs",
+        "La transacción {0} fue descartada\n    la transacción {1} de un nuevo bloque gasta las mismas salidas",
+    ),
+    (
+        "The transaction {0} was dropped, the transaction {1} of a new block spends the same outputs",
+        "La transacción {0} fue descartada, la transacción {1} de un nuevo bloque gasta las mismas salidas",
+    ),
+    ("Transaction sent", "Transacción enviada"),
+    ("The transaction {0} was sent", "La transacción {0} fue enviada"),
+    ("Transaction sent: {0}", "Transacción enviada: {0}"),
+    ("Received {0} headers", "Se recibieron {0} encabezados"),
+    ("Closing peers", "Cerrando los pares"),
+    ("Closing this peer", "Cerrando este par"),
+    (
+        "Received message of type {0}",
+        "Se recibió un mensaje de tipo {0}",
+    ),
+    (
+        "Error while verifying transaction merkle proof of inclusion",
+        "Error al verificar la prueba de inclusión de merkle de la transacción",
+    ),
+    (
+        "There was an error in the process of verifying the merkle proof\n the error was: {0}",
+        "Hubo un error en el proceso de verificar la prueba de merkle\n el error fue: {0}",
+    ),
+    (
+        "Merkle proof successfully validated",
+        "Prueba de merkle validada exitosamente",
+    ),
+    (
+        "Merkle root:\n{0}\n Merkle path:\n{1}",
+        "Raíz de merkle:\n{0}\n Camino de merkle:\n{1}",
+    ),
+    // Labels of the window
+    ("Account", "Cuenta"),
+    ("Add Wallet", "Agregar billetera"),
+    ("Address", "Dirección"),
+    (
+        "Address of the selected account:",
+        "Dirección de la cuenta seleccionada:",
+    ),
+    (
+        "Adds the accounts of the backup in the path to the wallet",
+        "Agrega a la billetera las cuentas de la copia en la ruta",
+    ),
+    ("All", "Todas"),
+    ("All accounts", "Todas las cuentas"),
+    ("Amount (BTC)", "Monto (BTC)"),
+    ("Amount:", "Monto:"),
+    ("Available", "Disponible"),
+    ("Backup of the wallet in JSON:", "Copia de la billetera en JSON:"),
+    (
+        "Balance history of the selected account",
+        "Historial del saldo de la cuenta seleccionada",
+    ),
+    ("Balances", "Saldos"),
+    ("Ban", "Bloquear"),
+    ("Block Download Progress", "Progreso de la descarga de bloques"),
+    ("Block:", "Bloque:"),
+    (
+        "Blockchain not up to date yet",
+        "La cadena de bloques todavía no está actualizada",
+    ),
+    ("Blocks", "Bloques"),
+    ("Broadcast", "Difundir"),
+    ("Cancel", "Cancelar"),
+    ("Change", "Cambiar"),
+    (
+        "Changes the name, label and description of the selected account",
+        "Cambia el nombre, la etiqueta y la descripción de la cuenta seleccionada",
+    ),
+    (
+        "Check against the UTXO set",
+        "Verificar contra el conjunto de UTXO",
+    ),
+    ("Clear all", "Limpiar todo"),
+    ("Confirm", "Confirmar"),
+    ("Confirm transaction", "Confirmar transacción"),
+    ("Confirmations", "Confirmaciones"),
+    ("Connection Type", "Tipo de conexión"),
+    ("Connections", "Conexiones"),
+    ("Current:", "Actual:"),
+    (
+        "Creates the account with the name and a new pair of keys",
+        "Crea la cuenta con el nombre y un nuevo par de claves",
+    ),
+    ("Dark", "Oscuro"),
+    ("Date", "Fecha"),
+    ("Debug", "Depuración"),
+    ("Describe", "Describir"),
+    ("Description:", "Descripción:"),
+    ("Direction", "Dirección"),
+    ("Disconnect", "Desconectar"),
+    (
+        "Do you want to send this transaction?",
+        "¿Desea enviar esta transacción?",
+    ),
+    ("Edit Account", "Editar cuenta"),
+    ("Choose a wallet", "Elegir una billetera"),
+    ("Choose the wallet to use:", "Elija la billetera a usar:"),
+    ("Create", "Crear"),
+    (
+        "Creates an empty wallet in the wallet directory and uses it",
+        "Crea una billetera vacía en el directorio de billeteras y la usa",
+    ),
+    ("Edit the selected account:", "Editar la cuenta seleccionada:"),
+    ("Empty keeps it", "Vacío lo mantiene"),
+    (
+        "Empty keeps it, - removes it",
+        "Vacío lo mantiene, - lo elimina",
+    ),
+    ("Empty if the wallet has none", "Vacía si la billetera no tiene"),
+    ("Empty to remove the password", "Vacía para quitar la contraseña"),
+    (
+        "Encrypts the private keys with the new password",
+        "Encripta las claves privadas con la nueva contraseña",
+    ),
+    (
+        "Enter a Bitcoin address (e.g. INS17iag9]JgTHDIVXjvICEnZuQ3rJDE9I)",
+        "Ingrese una dirección de Bitcoin (p. ej. INS17iag9]JgTHDIVXjvICEnZuQ3rJDE9I)",
+    ),
+    ("Enter a block header hash", "Ingrese el hash de un encabezado de bloque"),
+    ("Enter a new wallet:", "Ingrese una nueva billetera:"),
+    ("Enter a transaction ID", "Ingrese el ID de una transacción"),
+    (
+        "Enter transaction id to search",
+        "Ingrese el id de la transacción a buscar",
+    ),
+    ("Export", "Exportar"),
+    ("Fee:", "Comisión:"),
+    ("Fees (sats)", "Comisiones (sats)"),
+    ("File", "Archivo"),
+    ("File:", "Archivo:"),
+    (
+        "Find the history of the accounts in the blocks from:",
+        "Buscar el historial de las cuentas en los bloques desde:",
+    ),
+    ("Generate keys", "Generar claves"),
+    ("Height", "Altura"),
+    (
+        "Height, or timestamp of the first block",
+        "Altura, o marca de tiempo del primer bloque",
+    ),
+    ("Help", "Ayuda"),
+    ("IP Address", "Dirección IP"),
+    ("Import", "Importar"),
+    ("Inputs", "Entradas"),
+    ("Label", "Etiqueta"),
+    ("Label:", "Etiqueta:"),
+    ("Last month", "El último mes"),
+    ("Latency (ms)", "Latencia (ms)"),
+    ("Light", "Claro"),
+    ("M. Proof", "Prueba M."),
+    (
+        "Lets the private keys be used for a while with the current password",
+        "Permite usar las claves privadas por un tiempo con la contraseña actual",
+    ),
+    (
+        "Merkle Proof successfully validated",
+        "Prueba de Merkle validada exitosamente",
+    ),
+    ("Min amount", "Monto mínimo"),
+    ("Name of the new wallet", "Nombre de la nueva billetera"),
+    ("Name:", "Nombre:"),
+    ("New name:", "Nuevo nombre:"),
+    ("New:", "Nueva:"),
+    ("No account selected", "No hay una cuenta seleccionada"),
+    ("Ok", "Aceptar"),
+    ("Open", "Abrir"),
+    (
+        "Optional, to encrypt the keys",
+        "Opcional, para encriptar las claves",
+    ),
+    ("Outputs", "Salidas"),
+    ("Overview", "Resumen"),
+    ("Password:", "Contraseña:"),
+    (
+        "Password asked to sign the transactions:",
+        "Contraseña pedida para firmar las transacciones:",
+    ),
+    (
+        "Paste a private key in WIF to sweep it into the selected account",
+        "Pegue una clave privada en WIF para barrerla a la cuenta seleccionada",
+    ),
+    (
+        "Paste a raw transaction in hexadecimal",
+        "Pegue una transacción en crudo en hexadecimal",
+    ),
+    ("Path:", "Ruta:"),
+    ("Pay to:", "Pagar a:"),
+    ("Peers", "Pares"),
+    ("Pending:", "Pendiente:"),
+    ("Port", "Puerto"),
+    ("Private Key:", "Clave privada:"),
+    ("Public Key:", "Clave pública:"),
+    ("Queued", "En cola"),
+    ("Receive", "Recibir"),
+    ("Received", "Recibida"),
+    ("Refresh", "Actualizar"),
+    (
+        "Request coins from the configured faucet to the selected account",
+        "Pide monedas al faucet configurado para la cuenta seleccionada",
+    ),
+    ("Request testnet coins", "Pedir monedas de testnet"),
+    ("Rescan", "Reescanear"),
+    ("Save", "Guardar"),
+    ("Select a wallet", "Seleccione una billetera"),
+    ("Send", "Enviar"),
+    ("Sent", "Enviada"),
+    ("Settings", "Configuración"),
+    ("Size", "Tamaño"),
+    ("Spending password", "Contraseña de gasto"),
+    (
+        "Spending password, if the wallet is locked",
+        "Contraseña de gasto, si la billetera está bloqueada",
+    ),
+    ("Subsidy (sats)", "Subsidio (sats)"),
+    ("Sweep", "Barrer"),
+    ("System", "Sistema"),
+    ("Theme", "Tema"),
+    ("This month", "Este mes"),
+    ("This week", "Esta semana"),
+    ("This year", "Este año"),
+    ("Time", "Hora"),
+    ("Today", "Hoy"),
+    ("Total out (satoshis)", "Total de salida (satoshis)"),
+    ("Transaction", "Transacción"),
+    ("Transaction id", "Id de la transacción"),
+    ("Transaction successfully sent", "Transacción enviada exitosamente"),
+    ("Transaction:", "Transacción:"),
+    (
+        "Transactions seen that are not yet in a block",
+        "Transacciones vistas que todavía no están en un bloque",
+    ),
+    ("Unlock", "Desbloquear"),
+    ("Use", "Usar"),
+    ("User agent", "Agente de usuario"),
+    (
+        "Uses the wallet of the file in the path, saving it in the same file",
+        "Usa la billetera del archivo en la ruta, guardándola en el mismo archivo",
+    ),
+    (
+        "Uses the wallet selected of the wallet directory",
+        "Usa la billetera seleccionada del directorio de billeteras",
+    ),
+    ("Validate", "Validar"),
+    ("Version", "Versión"),
+    ("Wallet backup", "Copia de la billetera"),
+    ("Wallet:", "Billetera:"),
+    (
+        "Walks the stored blocks again to rebuild the history of the accounts",
+        "Recorre de nuevo los bloques guardados para reconstruir el historial de las cuentas",
+    ),
+    (
+        "Wallet in use, the wallets are the files of the configured wallet directory",
+        "Billetera en uso, las billeteras son los archivos del directorio de billeteras configurado",
+    ),
+    ("Window", "Ventana"),
+    (
+        "Writes the accounts and their keys in the path",
+        "Escribe las cuentas y sus claves en la ruta",
+    ),
+];
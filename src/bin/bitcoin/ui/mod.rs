@@ -1,4 +1,7 @@
 pub mod account;
+pub mod block_explorer;
 pub mod error_ui;
 pub mod from_hexa;
 pub mod input_handler;
+pub mod message_catalog;
+pub mod raw_dump;
@@ -0,0 +1,76 @@
+use cargosos_bitcoin::{
+    block_structure::{
+        block_chain::BlockChain,
+        hash::{self, HashType},
+    },
+    logs::logger_sender::LoggerSender,
+    notifications::{notification::Notification, notifier::Notifier},
+    serialization::serializable_internal_order::SerializableInternalOrder,
+};
+
+/// Function that sends to the front the raw data of the block with the given hash, as it's sent in the network
+pub fn give_raw_block<N: Notifier>(
+    block_chain: &BlockChain,
+    block_hash: HashType,
+    notifier: N,
+    logger: LoggerSender,
+) {
+    let block = match block_chain.get_block_with_hash(&block_hash) {
+        Some(block) if block.is_complete() => block,
+        Some(_) => {
+            let _ = logger.log_transaction("The block requested was not downloaded".to_string());
+            return notifier.notify(Notification::RawDataProblem(
+                "Only the header of the block is in the local blockchain".to_string(),
+            ));
+        }
+        None => {
+            let _ = logger.log_transaction("The block requested was not found".to_string());
+            return notifier.notify(Notification::RawDataProblem(
+                "Block not found in the local blockchain".to_string(),
+            ));
+        }
+    };
+
+    match to_hexa(&block) {
+        Some(raw_block) => notifier.notify(Notification::RawBlock(block_hash, raw_block)),
+        None => notifier.notify(Notification::RawDataProblem(
+            "The block could not be serialized".to_string(),
+        )),
+    }
+}
+
+/// Function that sends to the front the raw data of the transaction with the given id, as it's sent in the network.
+pub fn give_raw_transaction<N: Notifier>(
+    block_chain: &BlockChain,
+    transaction_id: HashType,
+    notifier: N,
+    logger: LoggerSender,
+) {
+    let transaction = match block_chain.get_transaction_with_id(&transaction_id) {
+        Some(transaction) => transaction,
+        None => {
+            let _ = logger.log_transaction("The transaction requested was not found".to_string());
+            return notifier.notify(Notification::RawDataProblem(
+                "Transaction not found in the blocks of the local blockchain".to_string(),
+            ));
+        }
+    };
+
+    match to_hexa(&transaction) {
+        Some(raw_transaction) => notifier.notify(Notification::RawTransaction(
+            transaction_id,
+            raw_transaction,
+        )),
+        None => notifier.notify(Notification::RawDataProblem(
+            "The transaction could not be serialized".to_string(),
+        )),
+    }
+}
+
+/// Serializes the value and turns its bytes into hexadecimal
+fn to_hexa<S: SerializableInternalOrder>(value: &S) -> Option<String> {
+    let mut bytes: Vec<u8> = Vec::new();
+    value.io_serialize(&mut bytes).ok()?;
+
+    Some(hash::to_hex(&bytes))
+}
@@ -1,6 +1,10 @@
 use super::{
-    block_header::BlockHeader, error_block::ErrorBlock, hash::HashType, merkle_tree::MerkleTree,
-    transaction::Transaction,
+    block_header::BlockHeader,
+    coinbase::CoinbaseInfo,
+    error_block::ErrorBlock,
+    hash::{self, HashType},
+    merkle_tree::MerkleTree,
+    transaction::{Transaction, MAX_TRANSACTION_SIZE},
 };
 
 use crate::serialization::{
@@ -29,6 +33,12 @@ impl Block {
         }
     }
 
+    /// Returns true if the block has all its transactions, and not only its header
+    pub fn is_complete(&self) -> bool {
+        !self.transactions.is_empty()
+            && self.transactions.len() as u64 == self.header.transaction_count.value
+    }
+
     /// Verifies that the merkle root hash is correct
     pub fn proof_of_inclusion(&self) -> bool {
         if self.transactions.is_empty() {
@@ -37,6 +47,25 @@ impl Block {
         self.header.proof_of_inclusion(&self.transactions)
     }
 
+    /// Returns the coinbase transaction of the block, being its first transaction
+    pub fn get_coinbase(&self) -> Option<&Transaction> {
+        match self.transactions.first() {
+            Some(transaction) if CoinbaseInfo::is_coinbase(transaction) => Some(transaction),
+            _ => None,
+        }
+    }
+
+    /// Verifies that the first transaction is a coinbase and that it's the only one.
+    /// A block without its transactions has nothing to verify
+    pub fn has_valid_coinbase(&self) -> bool {
+        match self.transactions.split_first() {
+            Some((first, rest)) => {
+                CoinbaseInfo::is_coinbase(first) && !rest.iter().any(CoinbaseInfo::is_coinbase)
+            }
+            None => true,
+        }
+    }
+
     /// Appends the transaction to the block if it's not already in the block
     ///
     /// ### Error
@@ -73,6 +102,33 @@ impl Block {
         let transaction_id = transaction.get_tx_id()?;
         MerkleTree::merkle_proof_of_inclusion(self, &transaction_id)
     }
+
+    /// Deserialize a block from a payload with the given amount of bytes left, so each transaction
+    /// is limited by the bytes left after the ones before it
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when a transaction has more inputs or outputs than the ones that fit in the payload
+    ///  * `ErrorSerialization::ErrorWhileReading`: It will appear when the block is bigger than the payload
+    pub fn io_deserialize_limited(
+        stream: &mut dyn Read,
+        remaining: u64,
+    ) -> Result<Self, ErrorSerialization> {
+        let mut stream = stream.take(remaining);
+        let header = BlockHeader::io_deserialize(&mut stream)?;
+        let length = header.transaction_count.value;
+
+        let mut transactions: Vec<Transaction> = Vec::new();
+        for _ in 0..length {
+            let remaining = stream.limit();
+            let transaction = Transaction::io_deserialize_limited(&mut stream, remaining)?;
+            transactions.push(transaction);
+        }
+
+        Ok(Block {
+            header,
+            transactions,
+        })
+    }
 }
 
 impl Display for Block {
@@ -82,12 +138,7 @@ impl Display for Block {
             Err(_) => return write!(f, "Block fail to get header hash"),
         };
 
-        let mut block_id_string = "".to_string();
-        for byte in block_id.iter() {
-            block_id_string.push_str(&format!("{:02x}", byte));
-        }
-
-        write!(f, "{block_id_string}")
+        write!(f, "{}", hash::to_hex_be(&block_id))
     }
 }
 
@@ -105,19 +156,7 @@ impl SerializableInternalOrder for Block {
 
 impl DeserializableInternalOrder for Block {
     fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
-        let header = BlockHeader::io_deserialize(stream)?;
-        let length = header.transaction_count.value;
-
-        let mut transactions: Vec<Transaction> = Vec::new();
-        for _ in 0..length {
-            let transaction = Transaction::io_deserialize(stream)?;
-            transactions.push(transaction);
-        }
-
-        Ok(Block {
-            header,
-            transactions,
-        })
+        Self::io_deserialize_limited(stream, MAX_TRANSACTION_SIZE)
     }
 }
 
@@ -127,7 +166,7 @@ mod tests {
     use super::*;
 
     use crate::block_structure::{
-        block_version, compact256::Compact256, outpoint::Outpoint,
+        block_version, compact256::Compact256, outpoint::Outpoint, script::ScriptBuf,
         transaction_input::TransactionInput, transaction_output::TransactionOutput,
     };
 
@@ -150,14 +189,14 @@ mod tests {
 
         let transaction_output = TransactionOutput {
             value: 10,
-            pk_script: vec![4, 5, 6],
+            pk_script: ScriptBuf::from(vec![4, 5, 6]),
         };
 
         let transaction_1 = Transaction {
             version: 1,
             tx_in: vec![transaction_input],
             tx_out: vec![transaction_output],
-            time: 0,
+            lock_time: 0,
         };
 
         let transaction_2 = transaction_1.clone();
@@ -198,14 +237,14 @@ mod tests {
 
         let transaction_output = TransactionOutput {
             value: 10,
-            pk_script: vec![4, 5, 6],
+            pk_script: ScriptBuf::from(vec![4, 5, 6]),
         };
 
         let transaction_1 = Transaction {
             version: 1,
             tx_in: vec![transaction_input],
             tx_out: vec![transaction_output],
-            time: 0,
+            lock_time: 0,
         };
 
         let transaction_2 = transaction_1.clone();
@@ -242,14 +281,14 @@ mod tests {
 
         let transaction_output = TransactionOutput {
             value: 10,
-            pk_script: vec![4, 5, 6],
+            pk_script: ScriptBuf::from(vec![4, 5, 6]),
         };
 
         let transaction_1 = Transaction {
             version: 1,
             tx_in: vec![transaction_input],
             tx_out: vec![transaction_output],
-            time: 0,
+            lock_time: 0,
         };
 
         let transaction_input =
@@ -257,14 +296,14 @@ mod tests {
 
         let transaction_output = TransactionOutput {
             value: 10,
-            pk_script: vec![4, 5, 6],
+            pk_script: ScriptBuf::from(vec![4, 5, 6]),
         };
 
         let transaction_2 = Transaction {
             version: 1,
             tx_in: vec![transaction_input],
             tx_out: vec![transaction_output],
-            time: 0,
+            lock_time: 0,
         };
 
         let transactions = vec![transaction_1];
@@ -296,14 +335,14 @@ mod tests {
 
         let transaction_output = TransactionOutput {
             value: 10,
-            pk_script: vec![4, 5, 6],
+            pk_script: ScriptBuf::from(vec![4, 5, 6]),
         };
 
         let transaction_1 = Transaction {
             version: 1,
             tx_in: vec![transaction_input],
             tx_out: vec![transaction_output],
-            time: 0,
+            lock_time: 0,
         };
 
         let transaction_2 = transaction_1.clone();
@@ -502,4 +541,47 @@ mod tests {
         let block = Block::io_deserialize(&mut block_bytes).unwrap();
         assert!(block.proof_of_inclusion());
     }
+
+    #[test]
+    fn test_06_first_transaction_must_be_the_only_coinbase() {
+        let mut block = Block::new(BlockHeader::new(
+            block_version::BlockVersion::version(1),
+            [0; 32],
+            [0; 32],
+            0,
+            Compact256::from(10),
+            0,
+            CompactSize::new(2),
+        ));
+        assert!(block.has_valid_coinbase());
+
+        let create_transaction = |previous_output: Outpoint, lock_time: u32| Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(previous_output, vec![1, 2, 3], 24)],
+            tx_out: vec![TransactionOutput {
+                value: 10,
+                pk_script: ScriptBuf::from(vec![4, 5, 6]),
+            }],
+            lock_time,
+        };
+        let coinbase = create_transaction(Outpoint::new([0; 32], u32::MAX), 0);
+        let transaction = create_transaction(Outpoint::new([1; 32], 23), 0);
+
+        let mut block_without_coinbase = block.clone();
+        block_without_coinbase
+            .append_transaction(transaction.clone())
+            .unwrap();
+        assert!(!block_without_coinbase.has_valid_coinbase());
+        assert_eq!(None, block_without_coinbase.get_coinbase());
+
+        block.append_transaction(coinbase.clone()).unwrap();
+        block.append_transaction(transaction).unwrap();
+        assert!(block.has_valid_coinbase());
+        assert_eq!(Some(&coinbase), block.get_coinbase());
+
+        block
+            .append_transaction(create_transaction(Outpoint::new([0; 32], u32::MAX), 1))
+            .unwrap();
+        assert!(!block.has_valid_coinbase());
+    }
 }
@@ -1,6 +1,6 @@
 use super::{
     block::Block, block_header::BlockHeader, error_block::ErrorBlock, hash::HashType,
-    node_chain::NodeChain,
+    node_chain::NodeChain, transaction::Transaction,
 };
 
 use crate::serialization::{
@@ -9,15 +9,27 @@ use crate::serialization::{
     error_serialization::ErrorSerialization,
     serializable_internal_order::SerializableInternalOrder,
     serializable_little_endian::SerializableLittleEndian,
+    versioned_format::{from_unversioned, Migration, VersionedFormat},
 };
 
 use crate::configurations::try_default::TryDefault;
 
+use crate::connections::network::Network;
+
 use std::{
     cmp,
+    collections::HashMap,
     io::{Read, Write},
 };
 
+pub const MAX_HEADERS_TO_SEND: usize = 2000;
+
+/// Amount of the last blocks whose time is used for the median time past
+pub const MEDIAN_TIME_SPAN: usize = 11;
+
+/// Amount of hashes of the locator that are consecutive blocks, before starting to double the step
+const LOCATOR_SINGLE_STEPS: usize = 10;
+
 /// It's the internal representation of the block chain
 #[derive(Debug, Clone, PartialEq)]
 pub struct BlockChain {
@@ -136,6 +148,15 @@ impl BlockChain {
         blocks_after_timestamp
     }
 
+    /// Get the height of the first block of the longest chain with a time after or equal to the given timestamp
+    pub fn get_height_with_timestamp(&self, timestamp: u32) -> Option<u64> {
+        self.get_main_chain()
+            .iter()
+            .map(|index| &self.blocks[*index])
+            .find(|node| node.block.header.time >= timestamp)
+            .map(|node| node.height)
+    }
+
     /// Get all completed blocks
     pub fn get_all_blocks(&self) -> Vec<Block> {
         self.blocks
@@ -147,6 +168,70 @@ impl BlockChain {
             .collect()
     }
 
+    /// Get all completed blocks with their height in the block chain, ordered by height
+    pub fn get_all_blocks_with_height(&self) -> Vec<(u64, Block)> {
+        let mut blocks: Vec<(u64, Block)> = self
+            .blocks
+            .iter()
+            .filter_map(|node| match !node.block.transactions.is_empty() {
+                true => Some((node.height, node.block.clone())),
+                false => None,
+            })
+            .collect();
+
+        blocks.sort_by_key(|(height, _)| *height);
+        blocks
+    }
+
+    /// Get the completed blocks of the longest chain with their height, from the first one
+    pub fn get_main_chain_blocks(&self) -> Vec<(u64, &Block)> {
+        self.get_main_chain()
+            .iter()
+            .map(|index| &self.blocks[*index])
+            .filter(|node| !node.block.transactions.is_empty())
+            .map(|node| (node.height, &node.block))
+            .collect()
+    }
+
+    /// Get the last blocks of the longest chain with their height, from the most recent one
+    pub fn get_recent_blocks(&self, amount: usize) -> Vec<(u64, Block)> {
+        self.get_main_chain()
+            .iter()
+            .rev()
+            .take(amount)
+            .map(|index| {
+                (
+                    self.blocks[*index].height,
+                    self.blocks[*index].block.clone(),
+                )
+            })
+            .collect()
+    }
+
+    /// Get the median of the times of the last blocks of the longest chain, which is the time a
+    /// lock time is compared to instead of the time of a single block (BIP113)
+    pub fn get_median_time_past(&self) -> u32 {
+        let mut times: Vec<u32> = Vec::with_capacity(MEDIAN_TIME_SPAN);
+
+        let mut current = self.get_main_tip();
+        while let Some(index) = current {
+            if times.len() >= MEDIAN_TIME_SPAN {
+                break;
+            }
+            times.push(self.blocks[index].block.header.time);
+            current = self.blocks[index].index_previous_node;
+        }
+
+        times.sort_unstable();
+        times.get(times.len() / 2).copied().unwrap_or_default()
+    }
+
+    /// Get the height of the block with the given hash
+    pub fn get_height_with_hash(&self, header_hash: &HashType) -> Option<u64> {
+        self.get_node_chain_with_hash(header_hash)
+            .map(|node| node.height)
+    }
+
     /// Get the block at the end of all forks
     pub fn latest(&self) -> Vec<Block> {
         let mut latest: Vec<Block> = Vec::new();
@@ -163,6 +248,52 @@ impl BlockChain {
         latest
     }
 
+    /// Get the hash and the height of the highest block at the end of all forks
+    pub fn get_tip(&self) -> Option<(HashType, u64)> {
+        self.last_blocks
+            .iter()
+            .filter_map(|index| self.blocks.get(*index))
+            .max_by_key(|node| node.height)
+            .map(|node| (node.header_hash, node.height))
+    }
+
+    /// Get the hashes to locate the given tip, going back one block at a time and then doubling the
+    /// step until the first block. It's empty if the tip is no longer at the end of a fork
+    pub fn get_locator(&self, tip: &HashType) -> Vec<HashType> {
+        let mut current = self.last_blocks.iter().copied().find(
+            |index| matches!(self.blocks.get(*index), Some(node) if node.header_hash == *tip),
+        );
+
+        let mut locator: Vec<HashType> = Vec::new();
+        let mut step: usize = 1;
+        let mut distance: usize = 0;
+        let mut next_distance: usize = 0;
+
+        while let Some(node) = current.and_then(|index| self.blocks.get(index)) {
+            if distance == next_distance || node.index_previous_node.is_none() {
+                locator.push(node.header_hash);
+                if locator.len() >= LOCATOR_SINGLE_STEPS {
+                    step *= 2;
+                }
+                next_distance += step;
+            }
+
+            distance += 1;
+            current = node.index_previous_node;
+        }
+
+        locator
+    }
+
+    /// Get the hashes to locate the highest tip, the last 10 blocks of its chain and then exponentially
+    /// spaced blocks back to the first one. A peer on another fork finds in it the last block we have in common
+    pub fn build_locator(&self) -> Vec<HashType> {
+        match self.get_tip() {
+            Some((tip, _)) => self.get_locator(&tip),
+            None => Vec::new(),
+        }
+    }
+
     pub fn headers_to_update(&self, go_back: usize) -> Vec<Block> {
         let mut latest: Vec<Block> = Vec::new();
 
@@ -352,6 +483,74 @@ impl BlockChain {
         Ok(headers)
     }
 
+    /// Gets the headers of the main chain following the first hash of the locator that is in the main chain,
+    /// up to the stop hash or a maximum of 2000 headers. If none of the hashes is known, the headers after the first block are given
+    pub fn get_headers_from_locator(
+        &self,
+        locator: &[HashType],
+        stop_hash: &HashType,
+    ) -> Vec<BlockHeader> {
+        let main_chain = self.get_main_chain();
+
+        let positions: HashMap<HashType, usize> = main_chain
+            .iter()
+            .enumerate()
+            .map(|(position, index)| (self.blocks[*index].header_hash, position))
+            .collect();
+
+        let start = locator
+            .iter()
+            .find_map(|hash| positions.get(hash))
+            .map_or(0, |position| *position);
+
+        let mut headers: Vec<BlockHeader> = Vec::new();
+        for index in main_chain.iter().skip(start + 1) {
+            let node = &self.blocks[*index];
+            headers.push(node.block.header);
+
+            if node.header_hash == *stop_hash || headers.len() >= MAX_HEADERS_TO_SEND {
+                break;
+            }
+        }
+
+        headers
+    }
+
+    /// Returns the indexes of the nodes of the longest chain, from the first block to the last one
+    fn get_main_chain(&self) -> Vec<usize> {
+        let mut main_chain: Vec<usize> = Vec::new();
+
+        let mut current = self.get_main_tip();
+        while let Some(index) = current {
+            main_chain.push(index);
+            current = self.blocks[index].index_previous_node;
+        }
+
+        main_chain.reverse();
+        main_chain
+    }
+
+    /// Returns the index of the node at the end of the longest chain
+    fn get_main_tip(&self) -> Option<usize> {
+        self.last_blocks
+            .iter()
+            .filter(|index| **index < self.blocks.len())
+            .max_by_key(|index| self.blocks[**index].height)
+            .copied()
+    }
+
+    /// Gets the transaction with the given id from the blocks downloaded
+    pub fn get_transaction_with_id(&self, transaction_id: &HashType) -> Option<Transaction> {
+        self.blocks
+            .iter()
+            .flat_map(|node| node.block.transactions.iter())
+            .find(|transaction| match transaction.get_tx_id() {
+                Ok(id) => id == *transaction_id,
+                Err(_) => false,
+            })
+            .cloned()
+    }
+
     /// Gets a block with the given hash
     pub fn get_block_with_hash(&self, header_hash: &HashType) -> Option<Block> {
         if let Some(node) = self.get_node_chain_with_hash(header_hash) {
@@ -359,16 +558,59 @@ impl BlockChain {
         };
         None
     }
+
+    /// Returns the nodes and the indexes of the last block of each branch, to store them
+    pub(super) fn get_nodes(&self) -> (&[NodeChain], &[usize]) {
+        (&self.blocks, &self.last_blocks)
+    }
+
+    /// Replaces the nodes at the given positions, in increasing order, which can also be the next
+    /// positions of the block chain. Then it keeps only the given amount of nodes, with the given
+    /// last block of each branch
+    ///
+    /// ### Error
+    ///  * `ErrorBlock::NodeChainReferenceNotFound`: It will appear when a position or a last block is after the end of the block chain
+    pub(super) fn replace_nodes(
+        &mut self,
+        node_count: usize,
+        nodes: Vec<(usize, NodeChain)>,
+        last_blocks: Vec<usize>,
+    ) -> Result<(), ErrorBlock> {
+        let mut final_len = self.blocks.len();
+        for (index, _) in nodes.iter() {
+            match (*index).cmp(&final_len) {
+                cmp::Ordering::Less => {}
+                cmp::Ordering::Equal => final_len += 1,
+                cmp::Ordering::Greater => return Err(ErrorBlock::NodeChainReferenceNotFound),
+            }
+        }
+
+        if node_count == 0
+            || node_count > final_len
+            || last_blocks.is_empty()
+            || last_blocks.iter().any(|index| *index >= node_count)
+        {
+            return Err(ErrorBlock::NodeChainReferenceNotFound);
+        }
+
+        for (index, node) in nodes {
+            match self.blocks.get_mut(index) {
+                Some(current_node) => *current_node = node,
+                None => self.blocks.push(node),
+            }
+        }
+        self.blocks.truncate(node_count);
+        self.last_blocks = last_blocks;
+
+        Ok(())
+    }
 }
 
 impl TryDefault for BlockChain {
     type Error = ErrorBlock;
 
     fn try_default() -> Result<Self, Self::Error> {
-        let genesis_header: BlockHeader = BlockHeader::generate_genesis_block_header();
-        let genesis_block: Block = Block::new(genesis_header);
-
-        BlockChain::new(genesis_block)
+        BlockChain::new(Network::default().genesis_block())
     }
 }
 
@@ -417,11 +659,15 @@ impl DeserializableInternalOrder for BlockChain {
     }
 }
 
+impl VersionedFormat for BlockChain {
+    const MIGRATIONS: &'static [Migration] = &[from_unversioned];
+}
+
 #[cfg(test)]
 mod tests {
     use crate::block_structure::{
         block_version, compact256::Compact256, hash::HashType, outpoint::Outpoint,
-        transaction::Transaction, transaction_input::TransactionInput,
+        script::ScriptBuf, transaction_input::TransactionInput,
         transaction_output::TransactionOutput,
     };
 
@@ -434,14 +680,14 @@ mod tests {
 
         let transaction_output = TransactionOutput {
             value: 10,
-            pk_script: vec![4, 5, 6],
+            pk_script: ScriptBuf::from(vec![4, 5, 6]),
         };
 
         Transaction {
             version: 1,
             tx_in: vec![transaction_input.clone()],
             tx_out: vec![transaction_output.clone()],
-            time,
+            lock_time: time,
         }
     }
 
@@ -497,14 +743,14 @@ mod tests {
 
         let transaction_output = TransactionOutput {
             value: 10,
-            pk_script: "Prueba out".as_bytes().to_vec(),
+            pk_script: ScriptBuf::from("Prueba out".as_bytes().to_vec()),
         };
 
         let transaction = Transaction {
             version: 1,
             tx_in: vec![transaction_input.clone()],
             tx_out: vec![transaction_output.clone()],
-            time: 0,
+            lock_time: 0,
         };
 
         let empty_block = Block::new(BlockHeader::new(
@@ -688,4 +934,237 @@ mod tests {
         let most_recent_hash = blockchain.get_most_recent_hash(hashes).unwrap();
         assert_eq!(most_recent_hash, block_6.header.get_hash256d().unwrap());
     }
+
+    #[test]
+    fn test_08_correct_blocks_with_height() {
+        let mut block_1 = create_block([0; 32], 1, 1);
+        block_1
+            .append_transaction(create_transaction(10, 0))
+            .unwrap();
+
+        let block_2 = create_block(block_1.header.get_hash256d().unwrap(), 1, 2);
+
+        let mut block_3 = create_block(block_2.header.get_hash256d().unwrap(), 1, 3);
+        block_3
+            .append_transaction(create_transaction(30, 2))
+            .unwrap();
+
+        let mut blockchain = BlockChain::new(block_1.clone()).unwrap();
+        blockchain.append_block(block_2.clone()).unwrap();
+        blockchain.append_block(block_3.clone()).unwrap();
+
+        let blocks = blockchain.get_all_blocks_with_height();
+        assert_eq!(blocks, vec![(0, block_1), (2, block_3.clone())]);
+
+        assert_eq!(
+            blockchain.get_height_with_hash(&block_2.header.get_hash256d().unwrap()),
+            Some(1)
+        );
+        assert_eq!(blockchain.get_height_with_hash(&[7; 32]), None);
+    }
+
+    #[test]
+    fn test_09_correct_headers_from_locator() {
+        let block_1 = create_block([0; 32], 0, 1);
+        let block_2 = create_block(block_1.header.get_hash256d().unwrap(), 0, 2);
+        let block_3 = create_block(block_2.header.get_hash256d().unwrap(), 0, 3);
+        let block_4 = create_block(block_3.header.get_hash256d().unwrap(), 0, 4);
+        let block_5 = create_block(block_4.header.get_hash256d().unwrap(), 0, 5);
+        let fork_block = create_block(block_3.header.get_hash256d().unwrap(), 0, 6);
+
+        let mut blockchain = BlockChain::new(block_1).unwrap();
+        blockchain.append_block(block_2.clone()).unwrap();
+        blockchain.append_block(block_3.clone()).unwrap();
+        blockchain.append_block(block_4.clone()).unwrap();
+        blockchain.append_block(block_5.clone()).unwrap();
+        blockchain.append_block(fork_block.clone()).unwrap();
+
+        let locator = vec![
+            [7; 32],
+            fork_block.header.get_hash256d().unwrap(),
+            block_3.header.get_hash256d().unwrap(),
+        ];
+
+        assert_eq!(
+            blockchain.get_headers_from_locator(&locator, &[0; 32]),
+            vec![block_4.header, block_5.header]
+        );
+        assert_eq!(
+            blockchain.get_headers_from_locator(&locator, &block_4.header.get_hash256d().unwrap()),
+            vec![block_4.header]
+        );
+        assert_eq!(
+            blockchain.get_headers_from_locator(&[], &[0; 32]),
+            vec![
+                block_2.header,
+                block_3.header,
+                block_4.header,
+                block_5.header
+            ]
+        );
+    }
+
+    #[test]
+    fn test_10_correct_get_transaction_with_id() {
+        let transaction = create_transaction(20, 1);
+
+        let mut block_1 = create_block([0; 32], 1, 1);
+        block_1
+            .append_transaction(create_transaction(10, 0))
+            .unwrap();
+
+        let mut block_2 = create_block(block_1.header.get_hash256d().unwrap(), 1, 2);
+        block_2.append_transaction(transaction.clone()).unwrap();
+
+        let mut blockchain = BlockChain::new(block_1).unwrap();
+        blockchain.append_block(block_2).unwrap();
+
+        assert_eq!(
+            blockchain.get_transaction_with_id(&transaction.get_tx_id().unwrap()),
+            Some(transaction)
+        );
+        assert_eq!(blockchain.get_transaction_with_id(&[7; 32]), None);
+    }
+
+    #[test]
+    fn test_11_correct_locator_from_tip() {
+        let first_block = create_block([0; 32], 0, 0);
+        let mut hashes = vec![first_block.header.get_hash256d().unwrap()];
+        let mut blockchain = BlockChain::new(first_block).unwrap();
+
+        for time in 1..20 {
+            let block = create_block(*hashes.last().unwrap(), 0, time);
+            hashes.push(block.header.get_hash256d().unwrap());
+            blockchain.append_block(block).unwrap();
+        }
+
+        let tip = hashes[19];
+        assert_eq!(blockchain.get_tip(), Some((tip, 19)));
+
+        let locator = blockchain.get_locator(&tip);
+        let mut expected: Vec<HashType> = (10..20).rev().map(|height| hashes[height]).collect();
+        expected.extend([hashes[8], hashes[4], hashes[0]]);
+        assert_eq!(locator, expected);
+
+        assert!(blockchain.get_locator(&hashes[10]).is_empty());
+    }
+
+    #[test]
+    fn test_12_correct_recent_blocks_of_the_longest_chain() {
+        let first_block = create_block([0; 32], 0, 0);
+        let first_hash = first_block.header.get_hash256d().unwrap();
+        let mut blockchain = BlockChain::new(first_block).unwrap();
+
+        let fork = create_block(first_hash, 0, 10);
+        blockchain.append_block(fork).unwrap();
+
+        let mut previous_hash = first_hash;
+        for time in 1..4 {
+            let block = create_block(previous_hash, 0, time);
+            previous_hash = block.header.get_hash256d().unwrap();
+            blockchain.append_block(block).unwrap();
+        }
+
+        let recent_blocks = blockchain.get_recent_blocks(2);
+        assert_eq!(
+            vec![(3, 3), (2, 2)],
+            recent_blocks
+                .iter()
+                .map(|(height, block)| (*height, block.header.time))
+                .collect::<Vec<(u64, u32)>>()
+        );
+        assert_eq!(4, blockchain.get_recent_blocks(10).len());
+    }
+
+    #[test]
+    fn test_13_correct_locator_of_the_highest_tip() {
+        let first_block = create_block([0; 32], 0, 0);
+        let first_hash = first_block.header.get_hash256d().unwrap();
+        let mut blockchain = BlockChain::new(first_block).unwrap();
+
+        let mut hashes = vec![first_hash];
+        for time in 1..15 {
+            let block = create_block(*hashes.last().unwrap(), 0, time);
+            hashes.push(block.header.get_hash256d().unwrap());
+            blockchain.append_block(block).unwrap();
+        }
+
+        let fork = create_block(hashes[3], 0, 100);
+        blockchain.append_block(fork).unwrap();
+
+        let mut expected: Vec<HashType> = (5..15).rev().map(|height| hashes[height]).collect();
+        expected.extend([hashes[3], hashes[0]]);
+        assert_eq!(blockchain.build_locator(), expected);
+    }
+
+    #[test]
+    fn test_14_correct_median_time_past_of_the_last_blocks() {
+        let first_block = create_block([0; 32], 0, 0);
+        let mut previous_hash = first_block.header.get_hash256d().unwrap();
+        let mut blockchain = BlockChain::new(first_block).unwrap();
+        assert_eq!(0, blockchain.get_median_time_past());
+
+        for time in [50, 10, 40, 20, 30] {
+            let block = create_block(previous_hash, 0, time);
+            previous_hash = block.header.get_hash256d().unwrap();
+            blockchain.append_block(block).unwrap();
+        }
+        assert_eq!(30, blockchain.get_median_time_past());
+
+        for time in 100..110 {
+            let block = create_block(previous_hash, 0, time);
+            previous_hash = block.header.get_hash256d().unwrap();
+            blockchain.append_block(block).unwrap();
+        }
+        assert_eq!(104, blockchain.get_median_time_past());
+    }
+
+    #[test]
+    fn test_15_correct_height_of_the_first_block_after_a_timestamp() {
+        let first_block = create_block([0; 32], 0, 10);
+        let mut previous_hash = first_block.header.get_hash256d().unwrap();
+        let mut blockchain = BlockChain::new(first_block).unwrap();
+
+        for time in [20, 30, 40] {
+            let block = create_block(previous_hash, 0, time);
+            previous_hash = block.header.get_hash256d().unwrap();
+            blockchain.append_block(block).unwrap();
+        }
+
+        assert_eq!(Some(0), blockchain.get_height_with_timestamp(0));
+        assert_eq!(Some(2), blockchain.get_height_with_timestamp(25));
+        assert_eq!(Some(3), blockchain.get_height_with_timestamp(40));
+        assert_eq!(None, blockchain.get_height_with_timestamp(41));
+    }
+
+    #[test]
+    fn test_16_correct_completed_blocks_of_the_longest_chain() {
+        let first_block = create_block([0; 32], 0, 0);
+        let first_hash = first_block.header.get_hash256d().unwrap();
+        let mut blockchain = BlockChain::new(first_block).unwrap();
+
+        let mut fork = create_block(first_hash, 1, 10);
+        fork.append_transaction(create_transaction(10, 0)).unwrap();
+        blockchain.append_block(fork).unwrap();
+
+        let mut previous_hash = first_hash;
+        for time in 1..4 {
+            let mut block = create_block(previous_hash, 1, time);
+            block
+                .append_transaction(create_transaction(time, 0))
+                .unwrap();
+            previous_hash = block.header.get_hash256d().unwrap();
+            blockchain.append_block(block).unwrap();
+        }
+
+        assert_eq!(
+            vec![(1, 1), (2, 2), (3, 3)],
+            blockchain
+                .get_main_chain_blocks()
+                .iter()
+                .map(|(height, block)| (*height, block.header.time))
+                .collect::<Vec<(u64, u32)>>()
+        );
+        assert_eq!(Some(2), blockchain.get_height_with_timestamp(2));
+    }
 }
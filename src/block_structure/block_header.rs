@@ -20,16 +20,19 @@ use crate::{
 
 use std::io::{Read, Write};
 
-const GENESIS_BLOCK_VERSION: BlockVersion = BlockVersion::version(1);
-const GENESIS_PREVIOUS_BLOCK_HEADER_HASH: HashType = [0; 32];
-const GENESIS_MERKLE_ROOT_HASH: HashType = [
+pub const GENESIS_BLOCK_VERSION: BlockVersion = BlockVersion::version(1);
+pub const GENESIS_PREVIOUS_BLOCK_HEADER_HASH: HashType = [0; 32];
+pub const GENESIS_MERKLE_ROOT_HASH: HashType = [
     0x3b, 0xa3, 0xed, 0xfd, 0x7a, 0x7b, 0x12, 0xb2, 0x7a, 0xc7, 0x2c, 0x3e, 0x67, 0x76, 0x8f, 0x61,
     0x7f, 0xc8, 0x1b, 0xc3, 0x88, 0x8a, 0x51, 0x32, 0x3a, 0x9f, 0xb8, 0xaa, 0x4b, 0x1e, 0x5e, 0x4a,
 ];
 const GENESIS_TIME: u32 = 0x4d49e5da;
 const GENESIS_N_BITS: u32 = 0x1d00ffff;
 const GENESIS_NONCE: u32 = 0x18aea41a;
-const GENESIS_TRANSACTION_COUNT: u64 = 0;
+pub const GENESIS_TRANSACTION_COUNT: u64 = 0;
+
+/// Maximum amount of seconds that the time of a header can be ahead of the time of the network
+pub const MAX_FUTURE_BLOCK_TIME: i64 = 2 * 60 * 60;
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct BlockHeader {
@@ -63,7 +66,7 @@ impl BlockHeader {
         }
     }
 
-    /// Generates the genesis block header
+    /// Generates the genesis block header of the test network
     pub fn generate_genesis_block_header() -> Self {
         BlockHeader::new(
             GENESIS_BLOCK_VERSION,
@@ -91,6 +94,11 @@ impl BlockHeader {
         self.n_bits > compact_hash
     }
 
+    /// Returns true if the time of the header is not too far ahead of the given network adjusted time
+    pub fn is_time_valid(&self, adjusted_time: i64) -> bool {
+        self.time as i64 <= adjusted_time + MAX_FUTURE_BLOCK_TIME
+    }
+
     /// Verifies that the merkle root hash is correct
     pub fn proof_of_inclusion(&self, transactions: &[Transaction]) -> bool {
         let merkle_tree: MerkleTree = match MerkleTree::new(transactions) {
@@ -103,19 +111,50 @@ impl BlockHeader {
         merkle_tree.root == self.merkle_root_hash
     }
 
+    /// Serializes the 80 bytes of the header, without the transaction count
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::ErrorInSerialization`: It will appear when there is an error in the serialization
+    pub fn serialize_without_transaction_count(
+        &self,
+        stream: &mut dyn Write,
+    ) -> Result<(), ErrorSerialization> {
+        self.version.le_serialize(stream)?;
+        self.previous_block_header_hash.le_serialize(stream)?;
+        self.merkle_root_hash.be_serialize(stream)?;
+        self.time.le_serialize(stream)?;
+        self.n_bits.le_serialize(stream)?;
+        self.nonce.le_serialize(stream)?;
+
+        Ok(())
+    }
+
+    /// Deserializes the 80 bytes of a header, with the given transaction count
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when there is an error in the deserialization
+    pub fn deserialize_without_transaction_count(
+        stream: &mut dyn Read,
+        transaction_count: CompactSize,
+    ) -> Result<Self, ErrorSerialization> {
+        Ok(BlockHeader {
+            version: BlockVersion::le_deserialize(stream)?,
+            previous_block_header_hash: HashType::le_deserialize(stream)?,
+            merkle_root_hash: HashType::be_deserialize(stream)?,
+            time: u32::le_deserialize(stream)?,
+            n_bits: Compact256::le_deserialize(stream)?,
+            nonce: u32::le_deserialize(stream)?,
+            transaction_count,
+        })
+    }
+
     /// Get the hash 256 double of the block header
     ///
     /// ### Error
     ///  * `ErrorSerialization::ErrorInSerialization`: It will appear when there is an error in the serialization
     pub fn get_hash256d(&self) -> Result<HashType, ErrorSerialization> {
         let mut buffer = vec![];
-
-        self.version.le_serialize(&mut buffer)?;
-        self.previous_block_header_hash.le_serialize(&mut buffer)?;
-        self.merkle_root_hash.be_serialize(&mut buffer)?;
-        self.time.le_serialize(&mut buffer)?;
-        self.n_bits.le_serialize(&mut buffer)?;
-        self.nonce.le_serialize(&mut buffer)?;
+        self.serialize_without_transaction_count(&mut buffer)?;
 
         let buffer = {
             let mut temp: Vec<u8> = Vec::new();
@@ -249,4 +288,14 @@ mod tests {
         genesis_hash.reverse();
         assert_eq!(genesis_block_header.get_hash256d().unwrap(), genesis_hash);
     }
+
+    #[test]
+    fn test_06_header_too_far_in_the_future_is_not_valid() {
+        let genesis_block_header = BlockHeader::generate_genesis_block_header();
+        let time = genesis_block_header.time as i64;
+
+        assert!(genesis_block_header.is_time_valid(time));
+        assert!(genesis_block_header.is_time_valid(time - MAX_FUTURE_BLOCK_TIME));
+        assert!(!genesis_block_header.is_time_valid(time - MAX_FUTURE_BLOCK_TIME - 1));
+    }
 }
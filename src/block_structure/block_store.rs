@@ -0,0 +1,451 @@
+use super::{
+    block_chain::BlockChain,
+    error_block::ErrorBlock,
+    hash::{hash256d_reduce, HashType, HashTypeReduced},
+    node_chain::NodeChain,
+};
+
+use crate::serialization::{
+    deserializable_internal_order::DeserializableInternalOrder,
+    deserializable_little_endian::DeserializableLittleEndian,
+    error_serialization::ErrorSerialization,
+    serializable_internal_order::SerializableInternalOrder,
+    serializable_little_endian::SerializableLittleEndian,
+};
+
+use std::{
+    fmt::Debug,
+    fs::{File, OpenOptions},
+    io::{BufReader, Read, Seek, SeekFrom, Write},
+};
+
+const BLOCK_STORE_EXTENSION: &str = ".journal";
+
+/// The amount of bytes of the amount of nodes and the hash of the last node the store continues
+const BASE_SIZE: u64 = 8 + 32;
+
+/// The amount of bytes of an entry besides its content, being its length and its checksum
+const ENTRY_OVERHEAD: u64 = 8 + 4;
+
+/// Returns the path of the block store of the block chain saved in the given path
+pub fn get_block_store_path(block_chain_path: &str) -> String {
+    format!("{block_chain_path}{BLOCK_STORE_EXTENSION}")
+}
+
+/// It's an append-only file next to the saved block chain, with the changes of the block chain
+/// since it was saved. Each entry has the nodes added or updated since the previous one and a
+/// checksum, so an entry that was not completely written is discarded when the store is opened.
+/// The store starts with the amount of nodes and the hash of the last node of the saved block
+/// chain, so it's ignored if that block chain is not the one loaded
+#[derive(Debug)]
+pub struct BlockStore {
+    file: File,
+    len: u64,
+    fingerprints: Vec<u64>,
+    last_blocks: Vec<usize>,
+}
+
+impl BlockStore {
+    /// Opens the block store in the given path, adding its changes to the loaded block chain.
+    /// An incomplete entry at the end is removed, and if the store doesn't continue the loaded
+    /// block chain it starts again from it
+    ///
+    /// ### Error
+    ///  * `ErrorBlock::CannotAccessBlockStore`: It will appear when the file cannot be opened or written
+    pub fn open(path: &str, block_chain: &mut BlockChain) -> Result<Self, ErrorBlock> {
+        let file = match OpenOptions::new()
+            .create(true)
+            .read(true)
+            .write(true)
+            .truncate(false)
+            .open(path)
+        {
+            Ok(file) => file,
+            Err(error) => return Err(cannot_access("open", error)),
+        };
+
+        let mut block_store = BlockStore {
+            file,
+            len: 0,
+            fingerprints: Vec::new(),
+            last_blocks: Vec::new(),
+        };
+
+        match replay(&block_store.file, block_chain) {
+            Some(len) => {
+                block_store.truncate(len)?;
+                block_store.remember(block_chain);
+            }
+            None => block_store.checkpoint(block_chain)?,
+        }
+
+        Ok(block_store)
+    }
+
+    /// Appends an entry with the nodes of the block chain that were added or updated since the
+    /// last one, returning the amount of nodes written. Nothing is written if there are no changes
+    ///
+    /// ### Error
+    ///  * `ErrorBlock::CannotAccessBlockStore`: It will appear when the entry cannot be written
+    pub fn flush(&mut self, block_chain: &BlockChain) -> Result<usize, ErrorBlock> {
+        let (nodes, last_blocks) = block_chain.get_nodes();
+
+        let changed: Vec<usize> = nodes
+            .iter()
+            .enumerate()
+            .filter(|(index, node)| self.fingerprints.get(*index) != Some(&fingerprint(node)))
+            .map(|(index, _)| index)
+            .collect();
+
+        if changed.is_empty()
+            && nodes.len() == self.fingerprints.len()
+            && last_blocks == self.last_blocks.as_slice()
+        {
+            return Ok(0);
+        }
+
+        let mut content: Vec<u8> = Vec::new();
+        if let Err(error) = write_entry(&mut content, nodes, &changed, last_blocks) {
+            return Err(cannot_access("serialize an entry of", error));
+        }
+
+        let mut entry: Vec<u8> = Vec::new();
+        let serialized = (content.len() as u64)
+            .le_serialize(&mut entry)
+            .and_then(|_| content.io_serialize(&mut entry))
+            .and_then(|_| hash256d_reduce(&content))
+            .and_then(|checksum| checksum.io_serialize(&mut entry));
+        if let Err(error) = serialized {
+            return Err(cannot_access("serialize an entry of", error));
+        }
+
+        if let Err(error) = self
+            .file
+            .write_all(&entry)
+            .and_then(|_| self.file.sync_data())
+        {
+            let _ = self.truncate(self.len);
+            return Err(cannot_access("write an entry to", error));
+        }
+        self.len += entry.len() as u64;
+
+        self.remember(block_chain);
+        Ok(changed.len())
+    }
+
+    /// Empties the store once the block chain is saved, starting it again from it
+    ///
+    /// ### Error
+    ///  * `ErrorBlock::CannotAccessBlockStore`: It will appear when the file cannot be written
+    pub fn checkpoint(&mut self, block_chain: &BlockChain) -> Result<(), ErrorBlock> {
+        let (count, hash) = get_base(block_chain);
+
+        let mut base: Vec<u8> = Vec::new();
+        if let Err(error) = count
+            .le_serialize(&mut base)
+            .and_then(|_| hash.io_serialize(&mut base))
+        {
+            return Err(cannot_access("serialize the base of", error));
+        }
+
+        self.truncate(0)?;
+        if let Err(error) = self
+            .file
+            .write_all(&base)
+            .and_then(|_| self.file.sync_data())
+        {
+            return Err(cannot_access("write the base of", error));
+        }
+        self.len = BASE_SIZE;
+
+        self.remember(block_chain);
+        Ok(())
+    }
+
+    /// Keeps the state of the block chain that is already in the store
+    fn remember(&mut self, block_chain: &BlockChain) {
+        let (nodes, last_blocks) = block_chain.get_nodes();
+        self.fingerprints = nodes.iter().map(fingerprint).collect();
+        self.last_blocks = last_blocks.to_vec();
+    }
+
+    /// Cuts the file to the given length, to continue writing from there
+    ///
+    /// ### Error
+    ///  * `ErrorBlock::CannotAccessBlockStore`: It will appear when the file cannot be cut
+    fn truncate(&mut self, len: u64) -> Result<(), ErrorBlock> {
+        if let Err(error) = self
+            .file
+            .set_len(len)
+            .and_then(|_| self.file.seek(SeekFrom::Start(len)))
+        {
+            return Err(cannot_access("cut", error));
+        }
+        self.len = len;
+        Ok(())
+    }
+}
+
+fn cannot_access<E: Debug>(action: &str, error: E) -> ErrorBlock {
+    ErrorBlock::CannotAccessBlockStore(format!(
+        "Cannot {action} the block store, with error: {:?}",
+        error
+    ))
+}
+
+/// Identifies the content of a node, changing when its block is replaced or gets its transactions
+fn fingerprint(node: &NodeChain) -> u64 {
+    let mut hash_start: [u8; 8] = [0; 8];
+    hash_start.copy_from_slice(&node.header_hash[..8]);
+    u64::from_le_bytes(hash_start) ^ node.block.transactions.len() as u64
+}
+
+/// Returns the amount of nodes and the hash of the last node of the block chain
+fn get_base(block_chain: &BlockChain) -> (u64, HashType) {
+    let (nodes, _) = block_chain.get_nodes();
+    let hash = match nodes.last() {
+        Some(node) => node.header_hash,
+        None => [0; 32],
+    };
+    (nodes.len() as u64, hash)
+}
+
+/// Adds the entries of the store to the block chain, returning the length of the valid part of
+/// the store. If the store doesn't continue the block chain, it returns None
+fn replay(file: &File, block_chain: &mut BlockChain) -> Option<u64> {
+    let mut stream = BufReader::new(file);
+
+    let base_count = u64::le_deserialize(&mut stream).ok()?;
+    let base_hash = HashType::io_deserialize(&mut stream).ok()?;
+    if (base_count, base_hash) != get_base(block_chain) {
+        return None;
+    }
+
+    let mut len = BASE_SIZE;
+    while let Some(entry_len) = replay_entry(&mut stream, block_chain) {
+        len += entry_len;
+    }
+    Some(len)
+}
+
+/// Adds the next entry of the store to the block chain, returning its length. If the entry is
+/// incomplete or not valid, it returns None
+fn replay_entry(stream: &mut dyn Read, block_chain: &mut BlockChain) -> Option<u64> {
+    let content_len = u64::le_deserialize(stream).ok()?;
+
+    let mut content: Vec<u8> = Vec::new();
+    let read_len = stream.take(content_len).read_to_end(&mut content).ok()?;
+    if read_len as u64 != content_len {
+        return None;
+    }
+
+    let checksum = HashTypeReduced::io_deserialize(stream).ok()?;
+    if hash256d_reduce(&content).ok()? != checksum {
+        return None;
+    }
+
+    let (node_count, nodes, last_blocks) = read_entry(&mut content.as_slice()).ok()?;
+    block_chain
+        .replace_nodes(node_count, nodes, last_blocks)
+        .ok()?;
+
+    Some(content_len + ENTRY_OVERHEAD)
+}
+
+/// Serializes the amount of nodes, the changed nodes with their position and the last block of each branch
+fn write_entry(
+    stream: &mut dyn Write,
+    nodes: &[NodeChain],
+    changed: &[usize],
+    last_blocks: &[usize],
+) -> Result<(), ErrorSerialization> {
+    (nodes.len() as u64).le_serialize(stream)?;
+
+    (changed.len() as u64).le_serialize(stream)?;
+    for index in changed {
+        (*index as u64).le_serialize(stream)?;
+        nodes[*index].io_serialize(stream)?;
+    }
+
+    (last_blocks.len() as u64).le_serialize(stream)?;
+    for index in last_blocks {
+        (*index as u64).le_serialize(stream)?;
+    }
+
+    Ok(())
+}
+
+type Entry = (usize, Vec<(usize, NodeChain)>, Vec<usize>);
+
+/// Deserializes an entry written by `write_entry`
+fn read_entry(stream: &mut dyn Read) -> Result<Entry, ErrorSerialization> {
+    let node_count = u64::le_deserialize(stream)? as usize;
+
+    let changed_count = u64::le_deserialize(stream)?;
+    let mut nodes: Vec<(usize, NodeChain)> = Vec::new();
+    for _ in 0..changed_count {
+        let index = u64::le_deserialize(stream)? as usize;
+        nodes.push((index, NodeChain::io_deserialize(stream)?));
+    }
+
+    let last_blocks_count = u64::le_deserialize(stream)?;
+    let mut last_blocks: Vec<usize> = Vec::new();
+    for _ in 0..last_blocks_count {
+        last_blocks.push(u64::le_deserialize(stream)? as usize);
+    }
+
+    Ok((node_count, nodes, last_blocks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::block_structure::{
+        block::Block, block_header::BlockHeader, block_version::BlockVersion,
+        compact256::Compact256, outpoint::Outpoint, script::ScriptBuf, transaction::Transaction,
+        transaction_input::TransactionInput, transaction_output::TransactionOutput,
+    };
+    use crate::messages::compact_size::CompactSize;
+
+    use std::{env, fs};
+
+    fn create_path(test_name: &str) -> String {
+        let path = env::temp_dir().join(format!("cargosos_block_store_{test_name}"));
+        let _ = fs::remove_file(&path);
+        path.to_string_lossy().to_string()
+    }
+
+    fn create_block(previous_header: HashType, time: u32) -> Block {
+        Block::new(BlockHeader::new(
+            BlockVersion::version(1),
+            previous_header,
+            [0; 32],
+            time,
+            Compact256::from(u32::MAX),
+            0,
+            CompactSize::new(0),
+        ))
+    }
+
+    fn create_chain(length: u32) -> (BlockChain, Vec<Block>) {
+        let mut blocks = vec![create_block([0; 32], 0)];
+        for time in 1..length {
+            let previous_hash = blocks[blocks.len() - 1].header.get_hash256d().unwrap();
+            blocks.push(create_block(previous_hash, time));
+        }
+
+        let block_chain = BlockChain::new(blocks.remove(0)).unwrap();
+        (block_chain, blocks)
+    }
+
+    #[test]
+    fn test01_flushed_blocks_are_added_when_opened() {
+        let path = create_path("test01");
+        let (saved_block_chain, blocks) = create_chain(3);
+
+        let mut block_chain = saved_block_chain.clone();
+        let mut block_store = BlockStore::open(&path, &mut block_chain).unwrap();
+        assert_eq!(0, block_store.flush(&block_chain).unwrap());
+
+        for block in blocks {
+            block_chain.append_block(block).unwrap();
+        }
+        assert_eq!(2, block_store.flush(&block_chain).unwrap());
+        assert_eq!(0, block_store.flush(&block_chain).unwrap());
+
+        let mut loaded_block_chain = saved_block_chain;
+        BlockStore::open(&path, &mut loaded_block_chain).unwrap();
+
+        assert_eq!(block_chain, loaded_block_chain);
+    }
+
+    #[test]
+    fn test02_blocks_updated_with_transactions_are_written_again() {
+        let path = create_path("test02");
+        let (saved_block_chain, blocks) = create_chain(2);
+
+        let mut block_chain = saved_block_chain.clone();
+        let mut block_store = BlockStore::open(&path, &mut block_chain).unwrap();
+        block_chain.append_block(blocks[0].clone()).unwrap();
+        block_store.flush(&block_chain).unwrap();
+
+        let mut block = blocks[0].clone();
+        block.transactions.push(Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(
+                Outpoint::new([1; 32], 0),
+                vec![1, 2, 3],
+                24,
+            )],
+            tx_out: vec![TransactionOutput {
+                value: 10,
+                pk_script: ScriptBuf::from(vec![4, 5, 6]),
+            }],
+            lock_time: 0,
+        });
+        block_chain.update_block(block).unwrap();
+        assert_eq!(1, block_store.flush(&block_chain).unwrap());
+
+        let mut loaded_block_chain = saved_block_chain;
+        BlockStore::open(&path, &mut loaded_block_chain).unwrap();
+
+        assert_eq!(block_chain, loaded_block_chain);
+    }
+
+    #[test]
+    fn test03_incomplete_entry_is_discarded() {
+        let path = create_path("test03");
+        let (saved_block_chain, blocks) = create_chain(3);
+
+        let mut block_chain = saved_block_chain.clone();
+        let mut block_store = BlockStore::open(&path, &mut block_chain).unwrap();
+        block_chain.append_block(blocks[0].clone()).unwrap();
+        block_store.flush(&block_chain).unwrap();
+        let expected_block_chain = block_chain.clone();
+
+        block_chain.append_block(blocks[1].clone()).unwrap();
+        block_store.flush(&block_chain).unwrap();
+        drop(block_store);
+
+        let len = fs::metadata(&path).unwrap().len();
+        OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_len(len - 5)
+            .unwrap();
+
+        let mut loaded_block_chain = saved_block_chain.clone();
+        let mut block_store = BlockStore::open(&path, &mut loaded_block_chain).unwrap();
+        assert_eq!(expected_block_chain, loaded_block_chain);
+
+        loaded_block_chain.append_block(blocks[1].clone()).unwrap();
+        assert_eq!(1, block_store.flush(&loaded_block_chain).unwrap());
+
+        let mut reloaded_block_chain = saved_block_chain;
+        BlockStore::open(&path, &mut reloaded_block_chain).unwrap();
+        assert_eq!(block_chain, reloaded_block_chain);
+    }
+
+    #[test]
+    fn test04_store_of_another_block_chain_is_ignored() {
+        let path = create_path("test04");
+        let (saved_block_chain, blocks) = create_chain(3);
+
+        let mut block_chain = saved_block_chain.clone();
+        let mut block_store = BlockStore::open(&path, &mut block_chain).unwrap();
+        block_chain.append_block(blocks[0].clone()).unwrap();
+        block_store.flush(&block_chain).unwrap();
+        block_store.checkpoint(&block_chain).unwrap();
+
+        block_chain.append_block(blocks[1].clone()).unwrap();
+        block_store.flush(&block_chain).unwrap();
+
+        let mut loaded_block_chain = saved_block_chain.clone();
+        BlockStore::open(&path, &mut loaded_block_chain).unwrap();
+
+        assert_eq!(saved_block_chain, loaded_block_chain);
+        assert_eq!(BASE_SIZE, fs::metadata(&path).unwrap().len());
+    }
+}
@@ -0,0 +1,108 @@
+use super::{
+    block::Block,
+    coinbase::{get_block_fees, get_block_subsidy},
+    error_block::ErrorBlock,
+    hash::HashType,
+};
+
+use crate::serialization::serializable_internal_order::SerializableInternalOrder;
+
+/// It's the summary of a block of the block chain, as the block explorers list them
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockSummary {
+    pub height: u64,
+    pub hash: HashType,
+    pub time: u32,
+    pub transaction_count: u64,
+
+    /// The size in bytes of the serialized block, only known when all its transactions were downloaded
+    pub size: Option<usize>,
+
+    /// The new coins, in satoshis, the block can create given its height
+    pub subsidy: i64,
+
+    /// The fees, in satoshis, collected by the block, only known when all its transactions were downloaded
+    pub fees: Option<i64>,
+}
+
+impl BlockSummary {
+    /// Summarizes the block that is at the given height
+    ///
+    /// ### Error
+    ///  * `ErrorBlock::CouldNotHash`: It will appear when the header of the block could not be hashed
+    pub fn new(height: u64, block: &Block) -> Result<Self, ErrorBlock> {
+        let hash = match block.header.get_hash256d() {
+            Ok(hash) => hash,
+            Err(_) => return Err(ErrorBlock::CouldNotHash),
+        };
+
+        let size = match block.is_complete() {
+            true => {
+                let mut serialized_block: Vec<u8> = Vec::new();
+                match block.io_serialize(&mut serialized_block) {
+                    Ok(()) => Some(serialized_block.len()),
+                    Err(_) => None,
+                }
+            }
+            false => None,
+        };
+
+        Ok(BlockSummary {
+            height,
+            hash,
+            time: block.header.time,
+            transaction_count: block.header.transaction_count.value,
+            size,
+            subsidy: get_block_subsidy(height),
+            fees: get_block_fees(block, height),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        block_structure::{
+            block_header::BlockHeader, block_version::BlockVersion, compact256::Compact256,
+            outpoint::Outpoint, transaction::Transaction, transaction_input::TransactionInput,
+        },
+        messages::compact_size::CompactSize,
+    };
+
+    #[test]
+    fn test01_size_is_only_known_for_complete_blocks() {
+        let mut block = Block::new(BlockHeader::new(
+            BlockVersion::version(1),
+            [0; 32],
+            [0; 32],
+            1234,
+            Compact256::from(u32::MAX),
+            0,
+            CompactSize::new(1),
+        ));
+
+        let summary = BlockSummary::new(7, &block).unwrap();
+        assert_eq!(7, summary.height);
+        assert_eq!(block.header.get_hash256d().unwrap(), summary.hash);
+        assert_eq!(1234, summary.time);
+        assert_eq!(1, summary.transaction_count);
+        assert_eq!(None, summary.size);
+        assert_eq!(5_000_000_000, summary.subsidy);
+        assert_eq!(None, summary.fees);
+
+        block
+            .append_transaction(Transaction {
+                version: 1,
+                tx_in: vec![TransactionInput::new(Outpoint::new([0; 32], 0), vec![], 0)],
+                tx_out: vec![],
+                lock_time: 0,
+            })
+            .unwrap();
+
+        let summary = BlockSummary::new(7, &block).unwrap();
+        assert_eq!(Some(81 + 51), summary.size);
+        assert_eq!(None, summary.fees);
+    }
+}
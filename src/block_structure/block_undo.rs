@@ -0,0 +1,102 @@
+use super::{hash::HashType, outpoint::Outpoint, transaction_output::TransactionOutput};
+
+use crate::serialization::{
+    deserializable_internal_order::DeserializableInternalOrder,
+    deserializable_little_endian::DeserializableLittleEndian,
+    error_serialization::ErrorSerialization,
+    serializable_internal_order::SerializableInternalOrder,
+    serializable_little_endian::SerializableLittleEndian,
+};
+
+use std::io::{Read, Write};
+
+/// It's the changes a block made to the UTXO set, to revert them when the block is disconnected
+/// from the chain. The outputs spent by the block are restored and the outputs created by it are removed
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockUndo {
+    pub block_hash: HashType,
+    pub spent: Vec<(Outpoint, TransactionOutput)>,
+    pub created: Vec<Outpoint>,
+}
+
+impl BlockUndo {
+    pub fn new(block_hash: HashType) -> Self {
+        BlockUndo {
+            block_hash,
+            spent: Vec::new(),
+            created: Vec::new(),
+        }
+    }
+}
+
+impl SerializableInternalOrder for BlockUndo {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        self.block_hash.io_serialize(stream)?;
+
+        (self.spent.len() as u64).le_serialize(stream)?;
+        for (outpoint, output) in self.spent.iter() {
+            outpoint.io_serialize(stream)?;
+            output.io_serialize(stream)?;
+        }
+
+        (self.created.len() as u64).le_serialize(stream)?;
+        for outpoint in self.created.iter() {
+            outpoint.io_serialize(stream)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl DeserializableInternalOrder for BlockUndo {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        let block_hash = HashType::io_deserialize(stream)?;
+
+        let spent_count = u64::le_deserialize(stream)?;
+        let mut spent: Vec<(Outpoint, TransactionOutput)> = Vec::new();
+        for _ in 0..spent_count {
+            let outpoint = Outpoint::io_deserialize(stream)?;
+            spent.push((outpoint, TransactionOutput::io_deserialize(stream)?));
+        }
+
+        let created_count = u64::le_deserialize(stream)?;
+        let mut created: Vec<Outpoint> = Vec::new();
+        for _ in 0..created_count {
+            created.push(Outpoint::io_deserialize(stream)?);
+        }
+
+        Ok(BlockUndo {
+            block_hash,
+            spent,
+            created,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::block_structure::script::ScriptBuf;
+
+    #[test]
+    fn test01_undo_data_is_serialized_and_deserialized() {
+        let mut block_undo = BlockUndo::new([1; 32]);
+        block_undo.spent.push((
+            Outpoint::new([2; 32], 0),
+            TransactionOutput {
+                value: 10,
+                pk_script: ScriptBuf::from(vec![0x51]),
+            },
+        ));
+        block_undo.created.push(Outpoint::new([3; 32], 1));
+
+        let mut stream: Vec<u8> = Vec::new();
+        block_undo.io_serialize(&mut stream).unwrap();
+
+        assert_eq!(
+            BlockUndo::io_deserialize(&mut stream.as_slice()).unwrap(),
+            block_undo
+        );
+    }
+}
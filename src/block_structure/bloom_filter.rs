@@ -0,0 +1,230 @@
+use crate::serialization::{
+    deserializable_fix_size::DeserializableFixSize,
+    deserializable_internal_order::DeserializableInternalOrder,
+    deserializable_little_endian::DeserializableLittleEndian,
+    error_serialization::ErrorSerialization, serializable_big_endian::SerializableBigEndian,
+    serializable_internal_order::SerializableInternalOrder,
+    serializable_little_endian::SerializableLittleEndian,
+};
+
+use crate::messages::compact_size::CompactSize;
+
+use std::io::{Read, Write};
+
+pub const MAX_BLOOM_FILTER_SIZE: usize = 36_000;
+pub const MAX_HASH_FUNCS: u32 = 50;
+
+const HASH_FUNCTION_SEED: u32 = 0xFBA4C795;
+
+/// It's the representation of what the peer does with the filter when a transaction matches
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BloomFlags {
+    UpdateNone,
+    UpdateAll,
+    UpdateP2PubkeyOnly,
+}
+
+impl From<BloomFlags> for u8 {
+    fn from(flags: BloomFlags) -> u8 {
+        match flags {
+            BloomFlags::UpdateNone => 0,
+            BloomFlags::UpdateAll => 1,
+            BloomFlags::UpdateP2PubkeyOnly => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for BloomFlags {
+    type Error = ErrorSerialization;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(BloomFlags::UpdateNone),
+            1 => Ok(BloomFlags::UpdateAll),
+            2 => Ok(BloomFlags::UpdateP2PubkeyOnly),
+            _ => Err(ErrorSerialization::ErrorInDeserialization(format!(
+                "Invalid bloom filter flags, we get: {:?}",
+                value
+            ))),
+        }
+    }
+}
+
+/// It's the bloom filter of BIP37, used by a light client to tell its peers which transactions it wants
+#[derive(Debug, Clone, PartialEq)]
+pub struct BloomFilter {
+    pub filter: Vec<u8>,
+    pub hash_functions: u32,
+    pub tweak: u32,
+    pub flags: BloomFlags,
+}
+
+impl BloomFilter {
+    /// Creates a filter sized for the amount of elements and the false positive rate, with all the
+    /// elements inserted
+    pub fn new(elements: &[Vec<u8>], false_positive_rate: f64, tweak: u32) -> Self {
+        let element_count = elements.len().max(1) as f64;
+        let ln2 = std::f64::consts::LN_2;
+
+        let size = (-element_count * false_positive_rate.ln() / (ln2 * ln2) / 8.0) as usize;
+        let size = size.clamp(1, MAX_BLOOM_FILTER_SIZE);
+
+        let hash_functions = (size as f64 * 8.0 / element_count * ln2) as u32;
+        let hash_functions = hash_functions.clamp(1, MAX_HASH_FUNCS);
+
+        let mut bloom_filter = BloomFilter {
+            filter: vec![0; size],
+            hash_functions,
+            tweak,
+            flags: BloomFlags::UpdateAll,
+        };
+
+        for element in elements {
+            bloom_filter.insert(element);
+        }
+
+        bloom_filter
+    }
+
+    /// Inserts the element in the filter
+    pub fn insert(&mut self, element: &[u8]) {
+        for hash_number in 0..self.hash_functions {
+            let index = self.get_bit_index(hash_number, element);
+            self.filter[index >> 3] |= 1 << (7 & index);
+        }
+    }
+
+    /// Returns true if the element may be in the filter, and false if it's not for sure
+    pub fn contains(&self, element: &[u8]) -> bool {
+        if self.filter.is_empty() {
+            return false;
+        }
+
+        (0..self.hash_functions).all(|hash_number| {
+            let index = self.get_bit_index(hash_number, element);
+            self.filter[index >> 3] & (1 << (7 & index)) != 0
+        })
+    }
+
+    /// Returns the position of the bit in the filter for the element with the given hash function
+    fn get_bit_index(&self, hash_number: u32, element: &[u8]) -> usize {
+        let seed = hash_number
+            .wrapping_mul(HASH_FUNCTION_SEED)
+            .wrapping_add(self.tweak);
+        murmur3(seed, element) as usize % (self.filter.len() * 8)
+    }
+}
+
+/// Calculates the 32 bits murmur3 hash of the bytes
+pub fn murmur3(seed: u32, bytes: &[u8]) -> u32 {
+    const C1: u32 = 0xcc9e2d51;
+    const C2: u32 = 0x1b873593;
+
+    let mut hash = seed;
+
+    let mut blocks = bytes.chunks_exact(4);
+    for block in blocks.by_ref() {
+        let mut k = u32::from_le_bytes([block[0], block[1], block[2], block[3]]);
+        k = k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+
+        hash ^= k;
+        hash = hash
+            .rotate_left(13)
+            .wrapping_mul(5)
+            .wrapping_add(0xe6546b64);
+    }
+
+    let tail = blocks.remainder();
+    let mut k: u32 = 0;
+    for (position, byte) in tail.iter().enumerate() {
+        k ^= (*byte as u32) << (8 * position);
+    }
+    if !tail.is_empty() {
+        hash ^= k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+    }
+
+    hash ^= bytes.len() as u32;
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x85ebca6b);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(0xc2b2ae35);
+    hash ^= hash >> 16;
+
+    hash
+}
+
+impl SerializableInternalOrder for BloomFilter {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        CompactSize::new(self.filter.len() as u64).le_serialize(stream)?;
+        self.filter.be_serialize(stream)?;
+        self.hash_functions.le_serialize(stream)?;
+        self.tweak.le_serialize(stream)?;
+        u8::from(self.flags).le_serialize(stream)?;
+
+        Ok(())
+    }
+}
+
+impl DeserializableInternalOrder for BloomFilter {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        let size = CompactSize::le_deserialize(stream)?.value as usize;
+        if size > MAX_BLOOM_FILTER_SIZE {
+            return Err(ErrorSerialization::ErrorInDeserialization(format!(
+                "Bloom filter bigger than the maximum, we get: {size}"
+            )));
+        }
+
+        let filter = Vec::<u8>::deserialize_fix_size(stream, size)?;
+
+        let hash_functions = u32::le_deserialize(stream)?;
+        if hash_functions > MAX_HASH_FUNCS {
+            return Err(ErrorSerialization::ErrorInDeserialization(format!(
+                "Too many hash functions in the bloom filter, we get: {hash_functions}"
+            )));
+        }
+
+        Ok(BloomFilter {
+            filter,
+            hash_functions,
+            tweak: u32::le_deserialize(stream)?,
+            flags: BloomFlags::try_from(u8::le_deserialize(stream)?)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_murmur3_known_values() {
+        assert_eq!(murmur3(0, &[]), 0);
+        assert_eq!(murmur3(0xFBA4C795, &[]), 0x6a396f08);
+        assert_eq!(murmur3(0, &[0x00]), 0x514E28B7);
+        assert_eq!(murmur3(0, &[0x21, 0x43, 0x65, 0x87]), 0xF55B516B);
+    }
+
+    #[test]
+    fn test02_inserted_elements_are_contained() {
+        let elements: Vec<Vec<u8>> = vec![vec![1; 20], vec![2; 20], vec![3; 20]];
+        let bloom_filter = BloomFilter::new(&elements, 0.0001, 5);
+
+        for element in elements.iter() {
+            assert!(bloom_filter.contains(element));
+        }
+        assert!(!bloom_filter.contains(&[4; 20]));
+    }
+
+    #[test]
+    fn test03_bloom_filter_is_serialized_with_its_size() {
+        let bloom_filter = BloomFilter::new(&[vec![1; 20]], 0.01, 5);
+
+        let mut stream: Vec<u8> = Vec::new();
+        bloom_filter.io_serialize(&mut stream).unwrap();
+
+        assert_eq!(stream.len(), 1 + bloom_filter.filter.len() + 4 + 4 + 1);
+
+        let deserialized = BloomFilter::io_deserialize(&mut stream.as_slice()).unwrap();
+        assert_eq!(deserialized, bloom_filter);
+    }
+}
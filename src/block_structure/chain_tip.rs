@@ -0,0 +1,125 @@
+use super::{block_chain::BlockChain, hash::HashType};
+
+use crate::serialization::{
+    deserializable_internal_order::DeserializableInternalOrder,
+    deserializable_little_endian::DeserializableLittleEndian,
+    error_serialization::ErrorSerialization,
+    serializable_internal_order::SerializableInternalOrder,
+    serializable_little_endian::SerializableLittleEndian,
+    versioned_format::{from_unversioned, Migration, VersionedFormat},
+};
+
+use std::io::{Read, Write};
+
+const SECONDS_PER_HOUR: i64 = 60 * 60;
+
+/// It's the last known tip of the block chain, recorded at shutdown with the time of the sync,
+/// so it can be shown at startup before loading the block chain
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChainTip {
+    pub block_hash: HashType,
+    pub height: u64,
+    pub sync_time: i64,
+}
+
+impl ChainTip {
+    /// Creates the tip with the highest block of the block chain, synced at the given time in seconds
+    pub fn new(block_chain: &BlockChain, sync_time: i64) -> Option<Self> {
+        let (block_hash, height) = block_chain.get_tip()?;
+
+        Some(ChainTip {
+            block_hash,
+            height,
+            sync_time,
+        })
+    }
+
+    /// Returns the amount of whole hours since the sync, given the current time in seconds
+    pub fn hours_since_sync(&self, now: i64) -> i64 {
+        (now - self.sync_time).max(0) / SECONDS_PER_HOUR
+    }
+}
+
+impl SerializableInternalOrder for ChainTip {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        self.block_hash.io_serialize(stream)?;
+        self.height.le_serialize(stream)?;
+        self.sync_time.le_serialize(stream)?;
+
+        Ok(())
+    }
+}
+
+impl DeserializableInternalOrder for ChainTip {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        Ok(ChainTip {
+            block_hash: HashType::io_deserialize(stream)?,
+            height: u64::le_deserialize(stream)?,
+            sync_time: i64::le_deserialize(stream)?,
+        })
+    }
+}
+
+impl VersionedFormat for ChainTip {
+    const MIGRATIONS: &'static [Migration] = &[from_unversioned];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::block_structure::{
+        block::Block, block_header::BlockHeader, block_version::BlockVersion,
+        compact256::Compact256,
+    };
+    use crate::messages::compact_size::CompactSize;
+
+    fn create_block(previous_header: HashType, time: u32) -> Block {
+        Block::new(BlockHeader::new(
+            BlockVersion::version(1),
+            previous_header,
+            [0; 32],
+            time,
+            Compact256::from(u32::MAX),
+            0,
+            CompactSize::new(0),
+        ))
+    }
+
+    #[test]
+    fn test01_tip_is_the_highest_block() {
+        let first_block = create_block([0; 32], 0);
+        let second_block = create_block(first_block.header.get_hash256d().unwrap(), 1);
+        let second_hash = second_block.header.get_hash256d().unwrap();
+
+        let mut block_chain = BlockChain::new(first_block).unwrap();
+        block_chain.append_block(second_block).unwrap();
+
+        let chain_tip = ChainTip::new(&block_chain, 1_000).unwrap();
+
+        assert_eq!(chain_tip.block_hash, second_hash);
+        assert_eq!(chain_tip.height, 1);
+        assert_eq!(
+            chain_tip.hours_since_sync(1_000 + 3 * SECONDS_PER_HOUR + 59),
+            3
+        );
+        assert_eq!(chain_tip.hours_since_sync(0), 0);
+    }
+
+    #[test]
+    fn test02_tip_is_serialized_and_deserialized() {
+        let chain_tip = ChainTip {
+            block_hash: [3; 32],
+            height: 2_437_000,
+            sync_time: 1_700_000_000,
+        };
+
+        let mut stream: Vec<u8> = Vec::new();
+        chain_tip.io_serialize(&mut stream).unwrap();
+
+        assert_eq!(
+            ChainTip::io_deserialize(&mut stream.as_slice()).unwrap(),
+            chain_tip
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use super::{
+    block::Block,
+    outpoint::Outpoint,
+    script::{OP_0, OP_1, OP_16},
+    transaction::Transaction,
+};
+
+use std::fmt::{Display, Formatter};
+
+const MAX_HEIGHT_BYTES: usize = 8;
+const MIN_MINER_TAG_LEN: usize = 4;
+const MINER_TAG_DELIMITER: char = '/';
+
+/// The subsidy, in satoshis, of the blocks before the first halving
+pub const INITIAL_SUBSIDY: i64 = 50 * 100_000_000;
+
+/// The amount of blocks after which the subsidy is halved
+pub const HALVING_INTERVAL: u64 = 210_000;
+
+/// After this many halvings the subsidy is zero
+const MAX_HALVINGS: u64 = 64;
+
+/// It's the metadata left by the miner in the coinbase transaction of a block
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CoinbaseInfo {
+    pub height: Option<u64>,
+    pub miner_tag: Option<String>,
+}
+
+impl CoinbaseInfo {
+    /// Extracts the metadata from the coinbase transaction of the block.
+    /// If the block does not have a coinbase transaction, then there is no metadata
+    pub fn from_block(block: &Block) -> Self {
+        match block.transactions.first() {
+            Some(transaction) => CoinbaseInfo::from_transaction(transaction),
+            None => CoinbaseInfo::default(),
+        }
+    }
+
+    /// Extracts the metadata from the given transaction if it's a coinbase transaction
+    pub fn from_transaction(transaction: &Transaction) -> Self {
+        if !CoinbaseInfo::is_coinbase(transaction) {
+            return CoinbaseInfo::default();
+        }
+
+        match transaction.tx_in.first() {
+            Some(input) => CoinbaseInfo::from_signature_script(&input.signature_script),
+            None => CoinbaseInfo::default(),
+        }
+    }
+
+    /// Returns true if the transaction only spends the null outpoint
+    pub fn is_coinbase(transaction: &Transaction) -> bool {
+        match transaction.tx_in.as_slice() {
+            [input] => input.previous_output == Outpoint::new([0; 32], u32::MAX),
+            _ => false,
+        }
+    }
+
+    /// Parses the signature script of a coinbase input.
+    /// The height is the first push of the script (BIP34) and the miner tag is the text found after it
+    pub fn from_signature_script(signature_script: &[u8]) -> Self {
+        let (height, rest) = match signature_script.split_first() {
+            Some((&OP_0, rest)) => (Some(0), rest),
+            Some((&opcode, rest)) if (OP_1..=OP_16).contains(&opcode) => {
+                (Some((opcode - OP_1 + 1) as u64), rest)
+            }
+            Some((&length, rest))
+                if (1..=MAX_HEIGHT_BYTES).contains(&(length as usize))
+                    && rest.len() >= length as usize =>
+            {
+                let (height_bytes, rest) = rest.split_at(length as usize);
+                (Some(from_little_endian(height_bytes)), rest)
+            }
+            _ => (None, signature_script),
+        };
+
+        CoinbaseInfo {
+            height,
+            miner_tag: find_miner_tag(rest),
+        }
+    }
+}
+
+/// Reads the bytes as an unsigned number in little endian
+fn from_little_endian(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .rev()
+        .fold(0, |value, byte| (value << 8) | *byte as u64)
+}
+
+/// Finds the text that identifies the miner in the script.
+/// A tag between delimiters (like `/slush/`) is preferred over the longest readable text
+fn find_miner_tag(script: &[u8]) -> Option<String> {
+    let mut candidates: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for byte in script.iter() {
+        if byte.is_ascii_graphic() || *byte == b' ' {
+            current.push(*byte as char);
+            continue;
+        }
+
+        if current.trim().len() >= MIN_MINER_TAG_LEN {
+            candidates.push(current.trim().to_string());
+        }
+        current.clear();
+    }
+
+    if current.trim().len() >= MIN_MINER_TAG_LEN {
+        candidates.push(current.trim().to_string());
+    }
+
+    for candidate in candidates.iter() {
+        let mut parts = candidate.split(MINER_TAG_DELIMITER);
+        if let (Some(_), Some(tag), Some(_)) = (parts.next(), parts.next(), parts.next()) {
+            if !tag.is_empty() {
+                return Some(format!("{MINER_TAG_DELIMITER}{tag}{MINER_TAG_DELIMITER}"));
+            }
+        }
+    }
+
+    candidates
+        .into_iter()
+        .max_by_key(|candidate| candidate.len())
+}
+
+/// Returns the subsidy, in satoshis, of the block at the given height: the new coins its coinbase can
+/// create, halved every `HALVING_INTERVAL` blocks
+pub fn get_block_subsidy(height: u64) -> i64 {
+    match height / HALVING_INTERVAL {
+        halvings if halvings >= MAX_HALVINGS => 0,
+        halvings => INITIAL_SUBSIDY >> halvings,
+    }
+}
+
+/// Returns the fees, in satoshis, collected by the block at the given height: what its coinbase claims
+/// above the subsidy. It's only known when all the transactions of the block were downloaded
+pub fn get_block_fees(block: &Block, height: u64) -> Option<i64> {
+    if !block.is_complete() {
+        return None;
+    }
+
+    let coinbase = match block.transactions.first() {
+        Some(transaction) if CoinbaseInfo::is_coinbase(transaction) => transaction,
+        _ => return None,
+    };
+
+    let claimed: i64 = coinbase.tx_out.iter().map(|output| output.value).sum();
+    Some((claimed - get_block_subsidy(height)).max(0))
+}
+
+impl Display for CoinbaseInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let height = match self.height {
+            Some(height) => height.to_string(),
+            None => "unknown".to_string(),
+        };
+
+        let miner_tag = match &self.miner_tag {
+            Some(miner_tag) => miner_tag.clone(),
+            None => "unknown".to_string(),
+        };
+
+        write!(f, "height: {height}, miner: {miner_tag}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        block_structure::{
+            block_header::BlockHeader, block_version::BlockVersion, compact256::Compact256,
+            script::ScriptBuf, transaction_input::TransactionInput,
+            transaction_output::TransactionOutput,
+        },
+        messages::compact_size::CompactSize,
+    };
+
+    fn create_coinbase(signature_script: Vec<u8>) -> Transaction {
+        Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(
+                Outpoint::new([0; 32], u32::MAX),
+                signature_script,
+                u32::MAX,
+            )],
+            tx_out: vec![TransactionOutput {
+                value: 625000000,
+                pk_script: ScriptBuf::new(),
+            }],
+            lock_time: 0,
+        }
+    }
+
+    #[test]
+    fn test_01_correct_height_parsing() {
+        let coinbase = create_coinbase(vec![0x03, 0x4e, 0x01, 0x05, 0x00, 0x01]);
+
+        let coinbase_info = CoinbaseInfo::from_transaction(&coinbase);
+
+        assert_eq!(coinbase_info.height, Some(328014));
+        assert_eq!(coinbase_info.miner_tag, None);
+    }
+
+    #[test]
+    fn test_02_correct_small_height_parsing() {
+        let coinbase_info = CoinbaseInfo::from_signature_script(&[0x55, 0x00]);
+
+        assert_eq!(coinbase_info.height, Some(5));
+    }
+
+    #[test]
+    fn test_03_correct_miner_tag_parsing() {
+        let mut signature_script = vec![0x03, 0x4e, 0x01, 0x05, 0x10, 0xff];
+        signature_script.extend(b"Mined by /slush/ pool");
+        signature_script.extend([0x00, 0x01]);
+
+        let coinbase_info = CoinbaseInfo::from_signature_script(&signature_script);
+
+        assert_eq!(coinbase_info.miner_tag, Some("/slush/".to_string()));
+
+        let mut signature_script = vec![0x01, 0x10];
+        signature_script.extend([0x00, 0x02, 0x03]);
+        signature_script.extend(b"ViaBTC");
+
+        let coinbase_info = CoinbaseInfo::from_signature_script(&signature_script);
+
+        assert_eq!(coinbase_info.miner_tag, Some("ViaBTC".to_string()));
+    }
+
+    #[test]
+    fn test_04_no_metadata_for_non_coinbase_transaction() {
+        let mut transaction = create_coinbase(vec![0x03, 0x4e, 0x01, 0x05]);
+        transaction.tx_in[0] = TransactionInput::new(Outpoint::new([1; 32], 0), vec![], 0);
+
+        assert!(!CoinbaseInfo::is_coinbase(&transaction));
+        assert_eq!(
+            CoinbaseInfo::from_transaction(&transaction),
+            CoinbaseInfo::default()
+        );
+    }
+
+    #[test]
+    fn test_05_subsidy_is_halved_every_interval() {
+        assert_eq!(5_000_000_000, get_block_subsidy(0));
+        assert_eq!(5_000_000_000, get_block_subsidy(HALVING_INTERVAL - 1));
+        assert_eq!(2_500_000_000, get_block_subsidy(HALVING_INTERVAL));
+        assert_eq!(625_000_000, get_block_subsidy(3 * HALVING_INTERVAL));
+        assert_eq!(0, get_block_subsidy(64 * HALVING_INTERVAL));
+    }
+
+    #[test]
+    fn test_06_fees_are_what_the_coinbase_claims_above_the_subsidy() {
+        let mut block = Block::new(BlockHeader::new(
+            BlockVersion::version(1),
+            [0; 32],
+            [0; 32],
+            0,
+            Compact256::from(u32::MAX),
+            0,
+            CompactSize::new(1),
+        ));
+        let height = 3 * HALVING_INTERVAL;
+        assert_eq!(None, get_block_fees(&block, height));
+
+        let mut coinbase = create_coinbase(vec![0x03, 0x4e, 0x01, 0x05]);
+        coinbase.tx_out[0].value = 625_012_345;
+        block.append_transaction(coinbase).unwrap();
+
+        assert_eq!(Some(12_345), get_block_fees(&block, height));
+        assert_eq!(Some(0), get_block_fees(&block, 0));
+    }
+}
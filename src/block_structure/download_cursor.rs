@@ -0,0 +1,109 @@
+use super::hash::HashType;
+
+use crate::serialization::{
+    deserializable_internal_order::DeserializableInternalOrder,
+    deserializable_little_endian::DeserializableLittleEndian,
+    error_serialization::ErrorSerialization,
+    serializable_internal_order::SerializableInternalOrder,
+    serializable_little_endian::SerializableLittleEndian,
+    versioned_format::{Migration, VersionedFormat},
+};
+
+use std::{
+    collections::HashSet,
+    io::{Read, Write},
+};
+
+/// It's the blocks whose download after the headers already finished, saved next to the block
+/// chain so an interrupted download continues from where it was instead of asking for the same
+/// blocks again. A block is finished when its body was added to the block chain, or when there
+/// was nothing to download for it because its filter didn't match the wallet
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DownloadCursor {
+    downloaded: HashSet<HashType>,
+}
+
+impl DownloadCursor {
+    pub fn new() -> Self {
+        DownloadCursor::default()
+    }
+
+    /// Marks the blocks with the given hashes as downloaded
+    pub fn mark_downloaded(&mut self, block_hashes: &[HashType]) {
+        self.downloaded.extend(block_hashes.iter().copied());
+    }
+
+    /// Returns true if the block with the given hash was already downloaded
+    pub fn is_downloaded(&self, block_hash: &HashType) -> bool {
+        self.downloaded.contains(block_hash)
+    }
+
+    pub fn len(&self) -> usize {
+        self.downloaded.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.downloaded.is_empty()
+    }
+}
+
+impl SerializableInternalOrder for DownloadCursor {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        (self.downloaded.len() as u64).le_serialize(stream)?;
+        for block_hash in self.downloaded.iter() {
+            block_hash.io_serialize(stream)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl DeserializableInternalOrder for DownloadCursor {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        let length = u64::le_deserialize(stream)?;
+
+        let mut downloaded = HashSet::new();
+        for _ in 0..length {
+            downloaded.insert(HashType::io_deserialize(stream)?);
+        }
+
+        Ok(DownloadCursor { downloaded })
+    }
+}
+
+impl VersionedFormat for DownloadCursor {
+    const MIGRATIONS: &'static [Migration] = &[];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_only_the_marked_blocks_are_downloaded() {
+        let mut download_cursor = DownloadCursor::new();
+        assert!(download_cursor.is_empty());
+
+        download_cursor.mark_downloaded(&[[1; 32], [2; 32]]);
+        download_cursor.mark_downloaded(&[[2; 32]]);
+
+        assert_eq!(2, download_cursor.len());
+        assert!(download_cursor.is_downloaded(&[1; 32]));
+        assert!(download_cursor.is_downloaded(&[2; 32]));
+        assert!(!download_cursor.is_downloaded(&[3; 32]));
+    }
+
+    #[test]
+    fn test02_cursor_is_serialized_and_deserialized() {
+        let mut download_cursor = DownloadCursor::new();
+        download_cursor.mark_downloaded(&[[1; 32], [2; 32], [3; 32]]);
+
+        let mut stream: Vec<u8> = Vec::new();
+        download_cursor.io_serialize(&mut stream).unwrap();
+
+        assert_eq!(
+            DownloadCursor::io_deserialize(&mut stream.as_slice()).unwrap(),
+            download_cursor
+        );
+    }
+}
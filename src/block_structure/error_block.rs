@@ -42,4 +42,37 @@ pub enum ErrorBlock {
 
     /// It will appear when there is an error disposing of branches
     ErrorCleansingBlockChain,
+
+    /// It will appear when a partial merkle tree is malformed or does not match the merkle root
+    InvalidMerkleProof,
+
+    /// It will appear when a compact block filter is malformed
+    InvalidCompactFilter,
+
+    /// It will appear when a transaction spends an output that is not in the UTXO set or is already spent by a pending transaction
+    OutputNotAvailable,
+
+    /// It will appear when the outputs of a transaction are worth more than its inputs
+    OutputsExceedInputs,
+
+    /// It will appear when an output of a transaction is worth less than its dust threshold
+    DustOutput,
+
+    /// It will appear when the fee of a transaction doesn't pay the minimum relay fee rate
+    FeeBelowMinimum,
+
+    /// It will appear when the UTXO set has no undo data of the block to disconnect, or the block is not its last block
+    UndoDataNotFound,
+
+    /// It will appear when the file of the block store cannot be read or written
+    CannotAccessBlockStore(String),
+
+    /// It will appear when the version of a block is below the one required by the soft forks active at its height
+    ObsoleteBlockVersion,
+
+    /// It will appear when the coinbase of a block doesn't start with the height of the block (BIP34)
+    CoinbaseHeightMismatch,
+
+    /// It will appear when the lock time required by an OP_CHECKLOCKTIMEVERIFY is not met by the transaction (BIP65)
+    LockTimeVerifyFailed,
 }
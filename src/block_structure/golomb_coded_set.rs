@@ -0,0 +1,332 @@
+use super::{
+    error_block::ErrorBlock,
+    hash::{hash256d, siphash24, HashType},
+};
+
+use crate::messages::compact_size::CompactSize;
+
+use crate::serialization::{
+    deserializable_little_endian::DeserializableLittleEndian,
+    serializable_little_endian::SerializableLittleEndian,
+};
+
+pub const BASIC_FILTER_TYPE: u8 = 0x00;
+
+/// Amount of bits of the remainder of each element in the basic filter
+pub const BASIC_FILTER_P: u8 = 19;
+
+/// Inverse of the false positive rate of the basic filter
+pub const BASIC_FILTER_M: u64 = 784_931;
+
+/// It's the Golomb-coded set of BIP158, a compact probabilistic set of the scripts of a block
+#[derive(Debug, Clone, PartialEq)]
+pub struct GolombCodedSet {
+    pub element_count: u64,
+    pub data: Vec<u8>,
+    keys: (u64, u64),
+}
+
+impl GolombCodedSet {
+    /// Creates the basic filter of the block with the given hash, with all the given elements
+    pub fn new(block_hash: &HashType, elements: &[Vec<u8>]) -> Self {
+        let keys = get_keys(block_hash);
+
+        let mut unique_elements: Vec<&Vec<u8>> = elements.iter().collect();
+        unique_elements.sort();
+        unique_elements.dedup();
+
+        let element_count = unique_elements.len() as u64;
+        let mut values: Vec<u64> = unique_elements
+            .iter()
+            .map(|element| hash_to_range(keys, element_count, element))
+            .collect();
+        values.sort_unstable();
+
+        let mut writer = BitWriter::default();
+        let mut last_value: u64 = 0;
+        for value in values {
+            writer.write_golomb_rice(value - last_value);
+            last_value = value;
+        }
+
+        GolombCodedSet {
+            element_count,
+            data: writer.bytes,
+            keys,
+        }
+    }
+
+    /// Reads the basic filter of the block with the given hash, as it's sent in a cfilter message
+    ///
+    /// ### Error
+    ///  * `ErrorBlock::InvalidCompactFilter`: It will appear when the filter does not start with the amount of elements
+    pub fn from_filter(block_hash: &HashType, filter: &[u8]) -> Result<Self, ErrorBlock> {
+        let mut stream = filter;
+        let element_count = match CompactSize::le_deserialize(&mut stream) {
+            Ok(element_count) => element_count.value,
+            Err(_) => return Err(ErrorBlock::InvalidCompactFilter),
+        };
+
+        Ok(GolombCodedSet {
+            element_count,
+            data: stream.to_vec(),
+            keys: get_keys(block_hash),
+        })
+    }
+
+    /// Returns the filter as it's sent in a cfilter message
+    ///
+    /// ### Error
+    ///  * `ErrorBlock::InvalidCompactFilter`: It will appear when the amount of elements could not be serialized
+    pub fn get_filter(&self) -> Result<Vec<u8>, ErrorBlock> {
+        let mut filter: Vec<u8> = Vec::new();
+        if CompactSize::new(self.element_count)
+            .le_serialize(&mut filter)
+            .is_err()
+        {
+            return Err(ErrorBlock::InvalidCompactFilter);
+        }
+        filter.extend_from_slice(&self.data);
+
+        Ok(filter)
+    }
+
+    /// Returns true if any of the elements may be in the set, and false if none of them is for sure
+    ///
+    /// ### Error
+    ///  * `ErrorBlock::InvalidCompactFilter`: It will appear when the data ends before reading all the elements
+    pub fn match_any(&self, elements: &[Vec<u8>]) -> Result<bool, ErrorBlock> {
+        if self.element_count == 0 || elements.is_empty() {
+            return Ok(false);
+        }
+
+        let mut queries: Vec<u64> = elements
+            .iter()
+            .map(|element| hash_to_range(self.keys, self.element_count, element))
+            .collect();
+        queries.sort_unstable();
+
+        let mut reader = BitReader::new(&self.data);
+        let mut queries = queries.iter().peekable();
+        let mut value: u64 = 0;
+
+        for _ in 0..self.element_count {
+            value += reader.read_golomb_rice()?;
+
+            while let Some(query) = queries.peek() {
+                match (**query).cmp(&value) {
+                    std::cmp::Ordering::Equal => return Ok(true),
+                    std::cmp::Ordering::Less => {
+                        queries.next();
+                    }
+                    std::cmp::Ordering::Greater => break,
+                }
+            }
+
+            if queries.peek().is_none() {
+                break;
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+/// Returns the header of the filter, which chains the hash of the filter with the header of the previous one
+///
+/// ### Error
+///  * `ErrorBlock::CouldNotHash`: It will appear when the filter or the header could not be hashed
+pub fn get_filter_header(
+    filter_hash: &HashType,
+    previous_header: &HashType,
+) -> Result<HashType, ErrorBlock> {
+    let mut bytes = filter_hash.to_vec();
+    bytes.extend_from_slice(previous_header);
+
+    match hash256d(&bytes) {
+        Ok(header) => Ok(header),
+        Err(_) => Err(ErrorBlock::CouldNotHash),
+    }
+}
+
+/// Returns the hash of the filter, as it's sent in a cfheaders message
+///
+/// ### Error
+///  * `ErrorBlock::CouldNotHash`: It will appear when the filter could not be hashed
+pub fn get_filter_hash(filter: &[u8]) -> Result<HashType, ErrorBlock> {
+    match hash256d(filter) {
+        Ok(filter_hash) => Ok(filter_hash),
+        Err(_) => Err(ErrorBlock::CouldNotHash),
+    }
+}
+
+/// The SipHash keys are the first 16 bytes of the block hash
+fn get_keys(block_hash: &HashType) -> (u64, u64) {
+    let mut first_key = [0; 8];
+    let mut second_key = [0; 8];
+    first_key.copy_from_slice(&block_hash[0..8]);
+    second_key.copy_from_slice(&block_hash[8..16]);
+
+    (
+        u64::from_le_bytes(first_key),
+        u64::from_le_bytes(second_key),
+    )
+}
+
+/// Maps the element uniformly to the range [0, element_count * M)
+fn hash_to_range(keys: (u64, u64), element_count: u64, element: &[u8]) -> u64 {
+    let hash = siphash24(keys.0, keys.1, element) as u128;
+    let range = (element_count as u128) * (BASIC_FILTER_M as u128);
+
+    ((hash * range) >> 64) as u64
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    bits_used: usize,
+}
+
+impl BitWriter {
+    fn write_bit(&mut self, bit: bool) {
+        if self.bits_used.is_multiple_of(8) {
+            self.bytes.push(0);
+        }
+        if bit {
+            if let Some(byte) = self.bytes.last_mut() {
+                *byte |= 0x80 >> (self.bits_used % 8);
+            }
+        }
+        self.bits_used += 1;
+    }
+
+    fn write_golomb_rice(&mut self, value: u64) {
+        for _ in 0..(value >> BASIC_FILTER_P) {
+            self.write_bit(true);
+        }
+        self.write_bit(false);
+
+        for position in (0..BASIC_FILTER_P).rev() {
+            self.write_bit((value >> position) & 1 == 1);
+        }
+    }
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    bits_used: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        BitReader {
+            bytes,
+            bits_used: 0,
+        }
+    }
+
+    fn read_bit(&mut self) -> Result<bool, ErrorBlock> {
+        let byte = match self.bytes.get(self.bits_used / 8) {
+            Some(byte) => *byte,
+            None => return Err(ErrorBlock::InvalidCompactFilter),
+        };
+
+        let bit = byte & (0x80 >> (self.bits_used % 8)) != 0;
+        self.bits_used += 1;
+        Ok(bit)
+    }
+
+    fn read_golomb_rice(&mut self) -> Result<u64, ErrorBlock> {
+        let mut quotient: u64 = 0;
+        while self.read_bit()? {
+            quotient += 1;
+        }
+
+        let mut remainder: u64 = 0;
+        for _ in 0..BASIC_FILTER_P {
+            remainder = (remainder << 1) | self.read_bit()? as u64;
+        }
+
+        Ok((quotient << BASIC_FILTER_P) | remainder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn testnet_genesis_hash() -> HashType {
+        let mut block_hash: HashType = [
+            0x00, 0x00, 0x00, 0x00, 0x09, 0x33, 0xea, 0x01, 0xad, 0x0e, 0xe9, 0x84, 0x20, 0x97,
+            0x79, 0xba, 0xae, 0xc3, 0xce, 0xd9, 0x0f, 0xa3, 0xf4, 0x08, 0x71, 0x95, 0x26, 0xf8,
+            0xd7, 0x7f, 0x49, 0x43,
+        ];
+        block_hash.reverse();
+        block_hash
+    }
+
+    fn genesis_output_script() -> Vec<u8> {
+        let mut script = vec![0x41];
+        script.extend_from_slice(&[
+            0x04, 0x67, 0x8a, 0xfd, 0xb0, 0xfe, 0x55, 0x48, 0x27, 0x19, 0x67, 0xf1, 0xa6, 0x71,
+            0x30, 0xb7, 0x10, 0x5c, 0xd6, 0xa8, 0x28, 0xe0, 0x39, 0x09, 0xa6, 0x79, 0x62, 0xe0,
+            0xea, 0x1f, 0x61, 0xde, 0xb6, 0x49, 0xf6, 0xbc, 0x3f, 0x4c, 0xef, 0x38, 0xc4, 0xf3,
+            0x55, 0x04, 0xe5, 0x1e, 0xc1, 0x12, 0xde, 0x5c, 0x38, 0x4d, 0xf7, 0xba, 0x0b, 0x8d,
+            0x57, 0x8a, 0x4c, 0x70, 0x2b, 0x6b, 0xf1, 0x1d, 0x5f,
+        ]);
+        script.push(0xac);
+        script
+    }
+
+    #[test]
+    fn test01_basic_filter_of_the_testnet_genesis_block() {
+        let filter = GolombCodedSet::new(&testnet_genesis_hash(), &[genesis_output_script()]);
+
+        assert_eq!(filter.get_filter().unwrap(), vec![0x01, 0x9d, 0xfc, 0xa8]);
+    }
+
+    #[test]
+    fn test02_filter_header_of_the_testnet_genesis_block() {
+        let filter_hash = get_filter_hash(&[0x01, 0x9d, 0xfc, 0xa8]).unwrap();
+        let mut filter_header = get_filter_header(&filter_hash, &[0; 32]).unwrap();
+        filter_header.reverse();
+
+        let expected_header: HashType = [
+            0x21, 0x58, 0x45, 0x79, 0xb7, 0xeb, 0x08, 0x99, 0x77, 0x73, 0xe5, 0xae, 0xff, 0x3a,
+            0x7f, 0x93, 0x27, 0x00, 0x04, 0x2d, 0x0e, 0xd2, 0xa6, 0x12, 0x90, 0x12, 0xb7, 0xd7,
+            0xae, 0x81, 0xb7, 0x50,
+        ];
+        assert_eq!(filter_header, expected_header);
+    }
+
+    #[test]
+    fn test03_received_filter_matches_its_elements() {
+        let block_hash: HashType = [7; 32];
+        let elements: Vec<Vec<u8>> = (0..50).map(|number| vec![number; 25]).collect();
+        let filter = GolombCodedSet::new(&block_hash, &elements)
+            .get_filter()
+            .unwrap();
+
+        let received = GolombCodedSet::from_filter(&block_hash, &filter).unwrap();
+
+        assert_eq!(received.element_count, 50);
+        assert!(received.match_any(&[vec![3; 25]]).unwrap());
+        assert!(received.match_any(&[vec![200; 25], vec![49; 25]]).unwrap());
+        assert!(!received.match_any(&[vec![200; 25]]).unwrap());
+        assert!(!received.match_any(&[]).unwrap());
+    }
+
+    #[test]
+    fn test04_truncated_filter_is_rejected() {
+        let block_hash: HashType = [7; 32];
+        let elements: Vec<Vec<u8>> = (0..10).map(|number| vec![number; 25]).collect();
+        let filter = GolombCodedSet::new(&block_hash, &elements)
+            .get_filter()
+            .unwrap();
+
+        let received = GolombCodedSet::from_filter(&block_hash, &filter[..1]).unwrap();
+
+        assert!(received.match_any(&elements).is_err());
+        assert!(GolombCodedSet::from_filter(&block_hash, &[]).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::serialization::error_serialization::ErrorSerialization;
 
-use bitcoin_hashes::{hash160, sha256, sha256d, Hash};
+use bitcoin_hashes::{hash160, sha256, sha256d, siphash24, Hash};
 
 pub const HASH_TYPE_SIZE: usize = 32;
 pub const HASH_TYPE_REDUCE_SIZE: usize = 4;
@@ -84,6 +84,79 @@ pub fn hash256d_reduce(bytes: &[u8]) -> Result<HashTypeReduced, ErrorSerializati
     Ok(hash_byte_4)
 }
 
+/// It hashes a byte array using SipHash-2-4 with the given keys
+pub fn siphash24(first_key: u64, second_key: u64, bytes: &[u8]) -> u64 {
+    siphash24::Hash::hash_to_u64_with_keys(first_key, second_key, bytes)
+}
+
+/// Returns the bytes in hexadecimal, in the same order
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Returns the hash in hexadecimal in the order it's shown to the user, as the block explorers do,
+/// which is the reverse of the order used in the protocol
+pub fn to_hex_be(hash: &HashType) -> String {
+    hash.iter()
+        .rev()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Reads the bytes written in hexadecimal of any length, in the same order
+///
+/// ### Error
+///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when the value is not hexadecimal
+pub fn bytes_from_hex(value: &str) -> Result<Vec<u8>, ErrorSerialization> {
+    let value = value.trim();
+    if !value.len().is_multiple_of(2) || !value.is_ascii() {
+        return Err(ErrorSerialization::ErrorInDeserialization(format!(
+            "Expected an even amount of hexadecimal digits, we get: {value}"
+        )));
+    }
+
+    let mut bytes = Vec::with_capacity(value.len() / 2);
+    for i in (0..value.len()).step_by(2) {
+        match u8::from_str_radix(&value[i..i + 2], 16) {
+            Ok(byte) => bytes.push(byte),
+            Err(_) => {
+                return Err(ErrorSerialization::ErrorInDeserialization(format!(
+                    "Invalid hexadecimal: {value}"
+                )))
+            }
+        };
+    }
+
+    Ok(bytes)
+}
+
+/// Reads the bytes written in hexadecimal, in the same order
+///
+/// ### Error
+///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when the value is not hexadecimal of the expected length
+pub fn from_hex<const N: usize>(value: &str) -> Result<[u8; N], ErrorSerialization> {
+    let bytes = bytes_from_hex(value)?;
+    match bytes.try_into() {
+        Ok(bytes) => Ok(bytes),
+        Err(_) => Err(ErrorSerialization::ErrorInDeserialization(format!(
+            "Expected {} hexadecimal digits, we get: {}",
+            N * 2,
+            value.trim()
+        ))),
+    }
+}
+
+/// Reads a hash written in hexadecimal in the order it's shown to the user, as the block explorers
+/// do, returning it in the order used in the protocol
+///
+/// ### Error
+///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when the value is not the hexadecimal of a hash
+pub fn from_hex_be(value: &str) -> Result<HashType, ErrorSerialization> {
+    let mut hash = from_hex::<HASH_TYPE_SIZE>(value)?;
+    hash.reverse();
+    Ok(hash)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,4 +218,29 @@ mod tests {
         let hash_expected = [0xC7, 0xF1, 0x8F, 0xE8];
         assert_eq!(hash, hash_expected);
     }
+
+    #[test]
+    fn test_05_correct_siphash24() {
+        let hash = siphash24(0x0706050403020100, 0x0f0e0d0c0b0a0908, &[]);
+        assert_eq!(hash, 0x726fdb47dd0e0e31);
+    }
+
+    #[test]
+    fn test_06_hex_in_display_order() {
+        let genesis = "000000000933ea01ad0ee984209779baaec3ced90fa3f408719526f8d77f4943";
+        let hash = from_hex_be(genesis).unwrap();
+
+        assert_eq!(0x43, hash[0]);
+        assert_eq!(0x00, hash[31]);
+        assert_eq!(genesis, to_hex_be(&hash));
+        assert_eq!(hash, from_hex::<32>(&to_hex(&hash)).unwrap());
+    }
+
+    #[test]
+    fn test_07_invalid_hex_is_an_error() {
+        assert!(from_hex_be("0011").is_err());
+        assert!(from_hex::<2>("00zz").is_err());
+        assert!(from_hex::<2>("00ñ").is_err());
+        assert_eq!([0xab, 0xcd], from_hex::<2>(" ABcd\n").unwrap());
+    }
 }
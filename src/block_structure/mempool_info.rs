@@ -0,0 +1,176 @@
+use super::{outpoint::Outpoint, utxo_set::UTXOSet};
+
+use crate::serialization::serializable_internal_order::SerializableInternalOrder;
+
+use std::collections::HashMap;
+
+/// It's the statistics of the pending transactions, the transactions seen that are not yet in a block
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MempoolInfo {
+    pub transaction_count: usize,
+
+    /// The size in bytes of the serialized pending transactions. Without segwit it's also their virtual size
+    pub total_size: usize,
+
+    /// The fees, in satoshis, of the pending transactions whose spent outputs are known
+    pub total_fees: i64,
+
+    /// The least fee rate, in satoshis per kilobyte, of the pending transactions whose fee is known
+    pub min_fee_rate: Option<i64>,
+}
+
+impl MempoolInfo {
+    /// Computes the statistics of the pending transactions of the UTXO set. The outputs spent by a pending
+    /// transaction can be unspent outputs or outputs of another pending transaction, otherwise its fee is unknown
+    pub fn from_utxo_set(utxo_set: &UTXOSet) -> Self {
+        let pending = utxo_set.pending_transactions();
+
+        let mut pending_outputs: HashMap<Outpoint, i64> = HashMap::new();
+        for transaction in pending.iter() {
+            if let Ok(transaction_id) = transaction.get_tx_id() {
+                for (index, output) in transaction.tx_out.iter().enumerate() {
+                    pending_outputs
+                        .insert(Outpoint::new(transaction_id, index as u32), output.value);
+                }
+            }
+        }
+
+        let mut mempool_info = MempoolInfo {
+            transaction_count: pending.len(),
+            ..MempoolInfo::default()
+        };
+
+        for transaction in pending.iter() {
+            let mut serialized_transaction: Vec<u8> = Vec::new();
+            if transaction
+                .io_serialize(&mut serialized_transaction)
+                .is_err()
+            {
+                continue;
+            }
+            let size = serialized_transaction.len();
+            mempool_info.total_size += size;
+
+            let mut input_value: Option<i64> = Some(0);
+            for input in transaction.tx_in.iter() {
+                let value = match utxo_set.get(&input.previous_output) {
+                    Some(output) => Some(output.value),
+                    None => pending_outputs.get(&input.previous_output).copied(),
+                };
+                input_value = match (input_value, value) {
+                    (Some(input_value), Some(value)) => Some(input_value + value),
+                    _ => None,
+                };
+            }
+
+            let input_value = match input_value {
+                Some(input_value) => input_value,
+                None => continue,
+            };
+            let output_value: i64 = transaction.tx_out.iter().map(|output| output.value).sum();
+
+            let fee = input_value - output_value;
+            mempool_info.total_fees += fee;
+
+            if size > 0 {
+                let fee_rate = fee * 1_000 / size as i64;
+                mempool_info.min_fee_rate = Some(match mempool_info.min_fee_rate {
+                    Some(min_fee_rate) => min_fee_rate.min(fee_rate),
+                    None => fee_rate,
+                });
+            }
+        }
+
+        mempool_info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        block_structure::{
+            block::Block, block_header::BlockHeader, block_version::BlockVersion,
+            compact256::Compact256, script::ScriptBuf, transaction::Transaction,
+            transaction_input::TransactionInput, transaction_output::TransactionOutput,
+        },
+        messages::compact_size::CompactSize,
+    };
+
+    fn create_transaction(previous_output: Outpoint, value: i64) -> Transaction {
+        Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(
+                previous_output,
+                vec![0; 107],
+                0xffffffff,
+            )],
+            tx_out: vec![TransactionOutput::new(
+                value,
+                ScriptBuf::new_p2pkh(&[7; 20]),
+            )],
+            lock_time: 0,
+        }
+    }
+
+    fn create_utxo_set(funding: &Transaction) -> UTXOSet {
+        let mut block = Block::new(BlockHeader::new(
+            BlockVersion::version(1),
+            [0; 32],
+            [0; 32],
+            0,
+            Compact256::from(u32::MAX),
+            0,
+            CompactSize::new(1),
+        ));
+        block.append_transaction(funding.clone()).unwrap();
+
+        UTXOSet::new(vec![block])
+    }
+
+    fn get_size(transaction: &Transaction) -> usize {
+        let mut serialized_transaction: Vec<u8> = Vec::new();
+        transaction
+            .io_serialize(&mut serialized_transaction)
+            .unwrap();
+        serialized_transaction.len()
+    }
+
+    #[test]
+    fn test01_empty_mempool_has_no_fee_rate() {
+        let funding = create_transaction(Outpoint::new([1; 32], 0), 10_000);
+        let utxo_set = create_utxo_set(&funding);
+
+        assert_eq!(
+            MempoolInfo::default(),
+            MempoolInfo::from_utxo_set(&utxo_set)
+        );
+    }
+
+    #[test]
+    fn test02_fees_are_computed_from_unspent_and_pending_outputs() {
+        let funding = create_transaction(Outpoint::new([1; 32], 0), 10_000);
+        let mut utxo_set = create_utxo_set(&funding);
+
+        let parent = create_transaction(Outpoint::new(funding.get_tx_id().unwrap(), 0), 9_000);
+        let child = create_transaction(Outpoint::new(parent.get_tx_id().unwrap(), 0), 8_500);
+        let unknown = create_transaction(Outpoint::new([2; 32], 0), 1_000);
+        utxo_set.append_pending_transaction(parent.clone());
+        utxo_set.append_pending_transaction(child.clone());
+        utxo_set.append_pending_transaction(unknown.clone());
+
+        let mempool_info = MempoolInfo::from_utxo_set(&utxo_set);
+
+        assert_eq!(3, mempool_info.transaction_count);
+        assert_eq!(
+            get_size(&parent) + get_size(&child) + get_size(&unknown),
+            mempool_info.total_size
+        );
+        assert_eq!(1_500, mempool_info.total_fees);
+        assert_eq!(
+            Some(500 * 1_000 / get_size(&child) as i64),
+            mempool_info.min_fee_rate
+        );
+    }
+}
@@ -0,0 +1,360 @@
+use super::{
+    error_block::ErrorBlock,
+    hash::{hash256d, HashType},
+};
+
+use crate::messages::compact_size::CompactSize;
+
+use crate::serialization::{
+    deserializable_big_endian::DeserializableBigEndian,
+    deserializable_fix_size::DeserializableFixSize,
+    deserializable_internal_order::DeserializableInternalOrder,
+    deserializable_little_endian::DeserializableLittleEndian,
+    error_serialization::ErrorSerialization, serializable_big_endian::SerializableBigEndian,
+    serializable_internal_order::SerializableInternalOrder,
+    serializable_little_endian::SerializableLittleEndian,
+};
+
+use std::io::{Read, Write};
+
+/// It's the partial merkle tree of BIP37. It proves that some transactions are in a block
+/// without sending all the transactions of the block
+#[derive(Debug, Clone, PartialEq)]
+pub struct MerkleProof {
+    pub total_transactions: u32,
+    pub hashes: Vec<HashType>,
+    pub flags: Vec<u8>,
+}
+
+impl MerkleProof {
+    /// Creates the proof for the transactions ids of a block, where only the ones marked in matches
+    /// can be extracted
+    ///
+    /// ### Error
+    ///  * `ErrorBlock::InvalidMerkleProof`: It will appear when there are no transactions or the amount of matches is not the amount of transactions
+    pub fn new(transaction_ids: &[HashType], matches: &[bool]) -> Result<Self, ErrorBlock> {
+        if transaction_ids.is_empty() || transaction_ids.len() != matches.len() {
+            return Err(ErrorBlock::InvalidMerkleProof);
+        }
+
+        let mut builder = MerkleProofBuilder {
+            transaction_ids,
+            matches,
+            hashes: Vec::new(),
+            bits: Vec::new(),
+        };
+        builder.traverse(tree_height(transaction_ids.len()), 0)?;
+
+        let mut flags: Vec<u8> = vec![0; builder.bits.len().div_ceil(8)];
+        for (position, bit) in builder.bits.iter().enumerate() {
+            if *bit {
+                flags[position / 8] |= 1 << (position % 8);
+            }
+        }
+
+        Ok(MerkleProof {
+            total_transactions: transaction_ids.len() as u32,
+            hashes: builder.hashes,
+            flags,
+        })
+    }
+
+    /// Returns the ids of the transactions that matched, in the order of the block,
+    /// after validating that the proof gives the merkle root
+    ///
+    /// ### Error
+    ///  * `ErrorBlock::InvalidMerkleProof`: It will appear when the proof is malformed or its root is not the merkle root
+    pub fn get_matched_transactions(
+        &self,
+        merkle_root: &HashType,
+    ) -> Result<Vec<HashType>, ErrorBlock> {
+        let total_transactions = self.total_transactions as usize;
+        if total_transactions == 0
+            || self.hashes.len() > total_transactions
+            || self.flags.len() * 8 < self.hashes.len()
+        {
+            return Err(ErrorBlock::InvalidMerkleProof);
+        }
+
+        let mut extractor = MerkleProofExtractor {
+            proof: self,
+            bits_used: 0,
+            hashes_used: 0,
+            matches: Vec::new(),
+        };
+        let root = extractor.traverse(tree_height(total_transactions), 0)?;
+
+        if extractor.bits_used.div_ceil(8) != self.flags.len()
+            || extractor.hashes_used != self.hashes.len()
+            || root != *merkle_root
+        {
+            return Err(ErrorBlock::InvalidMerkleProof);
+        }
+
+        Ok(extractor.matches)
+    }
+}
+
+/// Returns the amount of nodes in the level of the tree with the given height
+fn tree_width(total_transactions: usize, height: u32) -> usize {
+    (total_transactions + (1 << height) - 1) >> height
+}
+
+/// Returns the height of the root of the tree
+fn tree_height(total_transactions: usize) -> u32 {
+    let mut height = 0;
+    while tree_width(total_transactions, height) > 1 {
+        height += 1;
+    }
+    height
+}
+
+/// Hashes two nodes of the tree to get their parent
+fn hash_children(left: &HashType, right: &HashType) -> Result<HashType, ErrorBlock> {
+    let mut combined = left.to_vec();
+    combined.extend_from_slice(right);
+
+    match hash256d(&combined) {
+        Ok(hash) => Ok(hash),
+        Err(_) => Err(ErrorBlock::CouldNotCalculateMerklePath),
+    }
+}
+
+struct MerkleProofBuilder<'a> {
+    transaction_ids: &'a [HashType],
+    matches: &'a [bool],
+    hashes: Vec<HashType>,
+    bits: Vec<bool>,
+}
+
+impl<'a> MerkleProofBuilder<'a> {
+    fn calculate_hash(&self, height: u32, position: usize) -> Result<HashType, ErrorBlock> {
+        if height == 0 {
+            return Ok(self.transaction_ids[position]);
+        }
+
+        let left = self.calculate_hash(height - 1, position * 2)?;
+        let right = match position * 2 + 1 < tree_width(self.transaction_ids.len(), height - 1) {
+            true => self.calculate_hash(height - 1, position * 2 + 1)?,
+            false => left,
+        };
+
+        hash_children(&left, &right)
+    }
+
+    fn traverse(&mut self, height: u32, position: usize) -> Result<(), ErrorBlock> {
+        let start = position << height;
+        let end = ((position + 1) << height).min(self.transaction_ids.len());
+        let is_parent_of_match = self.matches[start..end].iter().any(|is_match| *is_match);
+
+        self.bits.push(is_parent_of_match);
+
+        if height == 0 || !is_parent_of_match {
+            let hash = self.calculate_hash(height, position)?;
+            self.hashes.push(hash);
+            return Ok(());
+        }
+
+        self.traverse(height - 1, position * 2)?;
+        if position * 2 + 1 < tree_width(self.transaction_ids.len(), height - 1) {
+            self.traverse(height - 1, position * 2 + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+struct MerkleProofExtractor<'a> {
+    proof: &'a MerkleProof,
+    bits_used: usize,
+    hashes_used: usize,
+    matches: Vec<HashType>,
+}
+
+impl<'a> MerkleProofExtractor<'a> {
+    fn next_bit(&mut self) -> Result<bool, ErrorBlock> {
+        let byte = match self.proof.flags.get(self.bits_used / 8) {
+            Some(byte) => *byte,
+            None => return Err(ErrorBlock::InvalidMerkleProof),
+        };
+
+        let bit = byte & (1 << (self.bits_used % 8)) != 0;
+        self.bits_used += 1;
+        Ok(bit)
+    }
+
+    fn next_hash(&mut self) -> Result<HashType, ErrorBlock> {
+        let hash = match self.proof.hashes.get(self.hashes_used) {
+            Some(hash) => *hash,
+            None => return Err(ErrorBlock::InvalidMerkleProof),
+        };
+
+        self.hashes_used += 1;
+        Ok(hash)
+    }
+
+    fn traverse(&mut self, height: u32, position: usize) -> Result<HashType, ErrorBlock> {
+        let is_parent_of_match = self.next_bit()?;
+
+        if height == 0 || !is_parent_of_match {
+            let hash = self.next_hash()?;
+            if height == 0 && is_parent_of_match {
+                self.matches.push(hash);
+            }
+            return Ok(hash);
+        }
+
+        let left = self.traverse(height - 1, position * 2)?;
+        let total_transactions = self.proof.total_transactions as usize;
+        let right = match position * 2 + 1 < tree_width(total_transactions, height - 1) {
+            true => {
+                let right = self.traverse(height - 1, position * 2 + 1)?;
+                if right == left {
+                    return Err(ErrorBlock::InvalidMerkleProof);
+                }
+                right
+            }
+            false => left,
+        };
+
+        hash_children(&left, &right)
+    }
+}
+
+impl SerializableInternalOrder for MerkleProof {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        self.total_transactions.le_serialize(stream)?;
+
+        CompactSize::new(self.hashes.len() as u64).le_serialize(stream)?;
+        for hash in self.hashes.iter() {
+            hash.be_serialize(stream)?;
+        }
+
+        CompactSize::new(self.flags.len() as u64).le_serialize(stream)?;
+        self.flags.be_serialize(stream)?;
+
+        Ok(())
+    }
+}
+
+impl DeserializableInternalOrder for MerkleProof {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        let total_transactions = u32::le_deserialize(stream)?;
+
+        let hash_count = CompactSize::le_deserialize(stream)?.value;
+        if hash_count > total_transactions as u64 {
+            return Err(ErrorSerialization::ErrorInDeserialization(format!(
+                "More hashes than transactions in the merkle proof, we get: {hash_count}"
+            )));
+        }
+
+        let mut hashes: Vec<HashType> = Vec::new();
+        for _ in 0..hash_count {
+            hashes.push(HashType::be_deserialize(stream)?);
+        }
+
+        let total_nodes: usize = (0..=tree_height(total_transactions as usize))
+            .map(|height| tree_width(total_transactions as usize, height))
+            .sum();
+
+        let flag_count = CompactSize::le_deserialize(stream)?.value;
+        if flag_count > total_nodes.div_ceil(8) as u64 {
+            return Err(ErrorSerialization::ErrorInDeserialization(format!(
+                "Too many flags in the merkle proof, we get: {flag_count}"
+            )));
+        }
+        let flags = Vec::<u8>::deserialize_fix_size(stream, flag_count as usize)?;
+
+        Ok(MerkleProof {
+            total_transactions,
+            hashes,
+            flags,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::block_structure::{
+        merkle_tree::MerkleTree, outpoint::Outpoint, script::ScriptBuf, transaction::Transaction,
+        transaction_input::TransactionInput, transaction_output::TransactionOutput,
+    };
+
+    fn create_transaction(time: u32) -> Transaction {
+        Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(
+                Outpoint::new([1; 32], 23),
+                vec![1, 2, 3],
+                24,
+            )],
+            tx_out: vec![TransactionOutput {
+                value: 10,
+                pk_script: ScriptBuf::from(vec![4, 5, 6]),
+            }],
+            lock_time: time,
+        }
+    }
+
+    #[test]
+    fn test01_extracts_the_matched_transactions() {
+        let transactions: Vec<Transaction> = (0..7).map(create_transaction).collect();
+        let transaction_ids = Transaction::get_vec_txids(&transactions).unwrap();
+        let merkle_root = MerkleTree::new(&transactions).unwrap().root;
+
+        let matches = vec![false, true, false, false, false, true, false];
+        let merkle_proof = MerkleProof::new(&transaction_ids, &matches).unwrap();
+
+        assert_eq!(
+            merkle_proof.get_matched_transactions(&merkle_root).unwrap(),
+            vec![transaction_ids[1], transaction_ids[5]]
+        );
+    }
+
+    #[test]
+    fn test02_proof_without_matches_gives_the_root() {
+        let transactions: Vec<Transaction> = (0..4).map(create_transaction).collect();
+        let transaction_ids = Transaction::get_vec_txids(&transactions).unwrap();
+        let merkle_root = MerkleTree::new(&transactions).unwrap().root;
+
+        let merkle_proof = MerkleProof::new(&transaction_ids, &[false; 4]).unwrap();
+
+        assert_eq!(merkle_proof.hashes, vec![merkle_root]);
+        assert!(merkle_proof
+            .get_matched_transactions(&merkle_root)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test03_proof_with_wrong_root_is_rejected() {
+        let transactions: Vec<Transaction> = (0..3).map(create_transaction).collect();
+        let transaction_ids = Transaction::get_vec_txids(&transactions).unwrap();
+
+        let merkle_proof = MerkleProof::new(&transaction_ids, &[true, false, false]).unwrap();
+
+        assert!(merkle_proof.get_matched_transactions(&[0; 32]).is_err());
+    }
+
+    #[test]
+    fn test04_merkle_proof_serialization() {
+        let transactions: Vec<Transaction> = (0..5).map(create_transaction).collect();
+        let transaction_ids = Transaction::get_vec_txids(&transactions).unwrap();
+        let merkle_root = MerkleTree::new(&transactions).unwrap().root;
+
+        let merkle_proof =
+            MerkleProof::new(&transaction_ids, &[false, false, false, false, true]).unwrap();
+
+        let mut stream: Vec<u8> = Vec::new();
+        merkle_proof.io_serialize(&mut stream).unwrap();
+        let deserialized = MerkleProof::io_deserialize(&mut stream.as_slice()).unwrap();
+
+        assert_eq!(deserialized, merkle_proof);
+        assert_eq!(
+            deserialized.get_matched_transactions(&merkle_root).unwrap(),
+            vec![transaction_ids[4]]
+        );
+    }
+}
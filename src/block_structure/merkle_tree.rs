@@ -174,7 +174,7 @@ mod tests {
     use crate::{
         block_structure::{
             block_header::BlockHeader, block_version::BlockVersion, compact256::Compact256,
-            outpoint::Outpoint, transaction_input::TransactionInput,
+            outpoint::Outpoint, script::ScriptBuf, transaction_input::TransactionInput,
             transaction_output::TransactionOutput,
         },
         messages::compact_size::CompactSize,
@@ -383,14 +383,14 @@ mod tests {
 
         let transaction_output_1 = TransactionOutput {
             value: 10,
-            pk_script: vec![4, 5, 6],
+            pk_script: ScriptBuf::from(vec![4, 5, 6]),
         };
 
         let transaction_1 = Transaction {
             version: 1,
             tx_in: vec![transaction_input_1],
             tx_out: vec![transaction_output_1],
-            time: 0,
+            lock_time: 0,
         };
 
         let transaction_input_2 =
@@ -398,14 +398,14 @@ mod tests {
 
         let transaction_output_2 = TransactionOutput {
             value: 10,
-            pk_script: vec![4, 5, 6],
+            pk_script: ScriptBuf::from(vec![4, 5, 6]),
         };
 
         let transaction_2 = Transaction {
             version: 1,
             tx_in: vec![transaction_input_2],
             tx_out: vec![transaction_output_2],
-            time: 0,
+            lock_time: 0,
         };
 
         let transaction_input_3 =
@@ -413,14 +413,14 @@ mod tests {
 
         let transaction_output_3 = TransactionOutput {
             value: 10,
-            pk_script: vec![4, 5, 21],
+            pk_script: ScriptBuf::from(vec![4, 5, 21]),
         };
 
         let transaction_3 = Transaction {
             version: 1,
             tx_in: vec![transaction_input_3],
             tx_out: vec![transaction_output_3],
-            time: 0,
+            lock_time: 0,
         };
 
         let transaction_input_4 =
@@ -428,14 +428,14 @@ mod tests {
 
         let transaction_output_4 = TransactionOutput {
             value: 13,
-            pk_script: vec![4, 5, 21],
+            pk_script: ScriptBuf::from(vec![4, 5, 21]),
         };
 
         let transaction_4 = Transaction {
             version: 1,
             tx_in: vec![transaction_input_4],
             tx_out: vec![transaction_output_4],
-            time: 0,
+            lock_time: 0,
         };
 
         let transaction_input_5 =
@@ -443,14 +443,14 @@ mod tests {
 
         let transaction_output_5 = TransactionOutput {
             value: 199,
-            pk_script: vec![4, 1, 21],
+            pk_script: ScriptBuf::from(vec![4, 1, 21]),
         };
 
         let transaction_5 = Transaction {
             version: 1,
             tx_in: vec![transaction_input_5],
             tx_out: vec![transaction_output_5],
-            time: 0,
+            lock_time: 0,
         };
 
         let transactions = vec![
@@ -1,15 +1,29 @@
 pub mod block;
 pub mod block_chain;
 pub mod block_header;
+pub mod block_store;
+pub mod block_summary;
+pub mod block_undo;
 pub mod block_version;
+pub mod chain_tip;
+pub mod coinbase;
+pub mod download_cursor;
+pub mod mempool_info;
 pub mod node_chain;
 
 pub mod compact256;
 pub mod hash;
 
+pub mod bloom_filter;
+pub mod golomb_coded_set;
+pub mod merkle_proof;
 pub mod merkle_tree;
 pub mod outpoint;
+pub mod relay_policy;
+pub mod script;
+pub mod soft_fork;
 pub mod transaction;
+pub mod transaction_description;
 pub mod transaction_input;
 pub mod transaction_output;
 
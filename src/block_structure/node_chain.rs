@@ -149,7 +149,7 @@ impl DeserializableInternalOrder for NodeChain {
 mod tests {
     use super::*;
     use crate::block_structure::{
-        block_version::BlockVersion, compact256::Compact256, outpoint::Outpoint,
+        block_version::BlockVersion, compact256::Compact256, outpoint::Outpoint, script::ScriptBuf,
         transaction::Transaction, transaction_input::TransactionInput,
         transaction_output::TransactionOutput,
     };
@@ -251,14 +251,14 @@ mod tests {
 
         let transaction_output = TransactionOutput {
             value: 10,
-            pk_script: vec![4, 5, 6],
+            pk_script: ScriptBuf::from(vec![4, 5, 6]),
         };
 
         let transaction_1 = Transaction {
             version: 1,
             tx_in: vec![transaction_input],
             tx_out: vec![transaction_output],
-            time: 0,
+            lock_time: 0,
         };
 
         let transaction_2 = transaction_1.clone();
@@ -311,14 +311,14 @@ mod tests {
 
         let transaction_output = TransactionOutput {
             value: 10,
-            pk_script: vec![4, 5, 6],
+            pk_script: ScriptBuf::from(vec![4, 5, 6]),
         };
 
         let transaction_1 = Transaction {
             version: 1,
             tx_in: vec![transaction_input],
             tx_out: vec![transaction_output],
-            time: 0,
+            lock_time: 0,
         };
 
         let transaction_input =
@@ -326,14 +326,14 @@ mod tests {
 
         let transaction_output = TransactionOutput {
             value: 10,
-            pk_script: vec![4, 5, 6],
+            pk_script: ScriptBuf::from(vec![4, 5, 6]),
         };
 
         let transaction_2 = Transaction {
             version: 1,
             tx_in: vec![transaction_input],
             tx_out: vec![transaction_output],
-            time: 0,
+            lock_time: 0,
         };
 
         let transactions = vec![transaction_1, transaction_2];
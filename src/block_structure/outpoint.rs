@@ -24,6 +24,16 @@ impl Outpoint {
     pub fn new(hash: HashType, index: u32) -> Self {
         Outpoint { hash, index }
     }
+
+    /// Returns the id of the transaction that created the output
+    pub fn get_transaction_id(&self) -> HashType {
+        self.hash
+    }
+
+    /// Returns the position of the output in its transaction
+    pub fn get_index(&self) -> u32 {
+        self.index
+    }
 }
 
 impl SerializableInternalOrder for Outpoint {
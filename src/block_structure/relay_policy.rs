@@ -0,0 +1,140 @@
+use super::{
+    error_block::ErrorBlock, script::Script, transaction::Transaction,
+    transaction_output::TransactionOutput,
+};
+
+use crate::serialization::serializable_internal_order::SerializableInternalOrder;
+
+/// The fee rate, in satoshis per kilobyte, used to decide if an output is dust
+pub const DUST_RELAY_FEE: i64 = 3_000;
+
+/// The least fee rate, in satoshis per kilobyte, of the transactions accepted from the peers
+pub const MIN_RELAY_FEE: i64 = 1_000;
+
+/// The size of the input that spends a P2PKH output, with its signature and public key
+const SPENDING_INPUT_SIZE: i64 = 148;
+
+/// Returns the least value, in satoshis, of an output with the given script so it's not dust. An output
+/// is dust when spending it costs more than a third of its value, so the peers don't relay it
+pub fn get_dust_threshold(pk_script: Script) -> i64 {
+    let mut serialized_output: Vec<u8> = Vec::new();
+    let output_size = match TransactionOutput::new(0, pk_script.to_script_buf())
+        .io_serialize(&mut serialized_output)
+    {
+        Ok(()) => serialized_output.len() as i64,
+        Err(_) => pk_script.len() as i64 + 9,
+    };
+
+    (output_size + SPENDING_INPUT_SIZE) * DUST_RELAY_FEE / 1_000
+}
+
+/// Returns true if the output is worth less than its dust threshold. The outputs that cannot be
+/// spent, carrying data, are never dust
+pub fn is_dust(output: &TransactionOutput) -> bool {
+    if output.pk_script.as_script().is_op_return() {
+        return false;
+    }
+
+    output.value < get_dust_threshold(output.pk_script.as_script())
+}
+
+/// Returns the least fee, in satoshis, of a transaction of the given size in bytes to pay the fee rate
+pub fn get_minimum_fee(size: usize, fee_rate: i64) -> i64 {
+    size as i64 * fee_rate / 1_000
+}
+
+/// Checks that the transaction can be relayed to the peers: none of its outputs is dust and its fee
+/// pays at least the minimum relay fee rate, in satoshis per kilobyte
+///
+/// ### Error
+///  * `ErrorBlock::DustOutput`: It will appear when an output of the transaction is dust
+///  * `ErrorBlock::FeeBelowMinimum`: It will appear when the fee doesn't pay the minimum relay fee rate
+///  * `ErrorBlock::CouldNotWriteTxId`: It will appear when the transaction cannot be serialized
+pub fn check_relay(
+    transaction: &Transaction,
+    fee: i64,
+    min_relay_fee: i64,
+) -> Result<(), ErrorBlock> {
+    if transaction.tx_out.iter().any(is_dust) {
+        return Err(ErrorBlock::DustOutput);
+    }
+
+    let mut serialized_transaction: Vec<u8> = Vec::new();
+    if let Err(error) = transaction.io_serialize(&mut serialized_transaction) {
+        return Err(ErrorBlock::CouldNotWriteTxId(format!("{:?}", error)));
+    }
+
+    match fee >= get_minimum_fee(serialized_transaction.len(), min_relay_fee) {
+        true => Ok(()),
+        false => Err(ErrorBlock::FeeBelowMinimum),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::block_structure::{
+        outpoint::Outpoint, script::ScriptBuf, transaction_input::TransactionInput,
+    };
+
+    fn create_p2pkh_script() -> ScriptBuf {
+        ScriptBuf::new_p2pkh(&[7; 20])
+    }
+
+    #[test]
+    fn test01_p2pkh_output_is_dust_below_546_satoshis() {
+        assert_eq!(546, get_dust_threshold(create_p2pkh_script().as_script()));
+
+        assert!(is_dust(&TransactionOutput::new(545, create_p2pkh_script())));
+        assert!(!is_dust(&TransactionOutput::new(
+            546,
+            create_p2pkh_script()
+        )));
+        assert!(!is_dust(&TransactionOutput::new(
+            0,
+            ScriptBuf::new_op_return(&[0x01])
+        )));
+    }
+
+    #[test]
+    fn test02_transaction_is_relayed_only_paying_the_minimum_fee() {
+        let transaction = Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(
+                Outpoint::new([1; 32], 0),
+                vec![0; 107],
+                0xffffffff,
+            )],
+            tx_out: vec![TransactionOutput::new(10_000, create_p2pkh_script())],
+            lock_time: 0,
+        };
+
+        let mut serialized_transaction: Vec<u8> = Vec::new();
+        transaction
+            .io_serialize(&mut serialized_transaction)
+            .unwrap();
+        let minimum_fee = get_minimum_fee(serialized_transaction.len(), MIN_RELAY_FEE);
+
+        assert!(check_relay(&transaction, minimum_fee, MIN_RELAY_FEE).is_ok());
+        assert!(matches!(
+            check_relay(&transaction, minimum_fee - 1, MIN_RELAY_FEE),
+            Err(ErrorBlock::FeeBelowMinimum)
+        ));
+    }
+
+    #[test]
+    fn test03_transaction_with_dust_is_not_relayed() {
+        let transaction = Transaction {
+            version: 1,
+            tx_in: vec![],
+            tx_out: vec![TransactionOutput::new(100, create_p2pkh_script())],
+            lock_time: 0,
+        };
+
+        assert!(matches!(
+            check_relay(&transaction, 10_000, MIN_RELAY_FEE),
+            Err(ErrorBlock::DustOutput)
+        ));
+    }
+}
@@ -0,0 +1,453 @@
+use super::hash;
+
+use crate::serialization::error_serialization::ErrorSerialization;
+
+use std::{
+    fmt::{Display, Formatter},
+    ops::Deref,
+};
+
+pub const OP_0: u8 = 0x00;
+pub const OP_PUSHDATA1: u8 = 0x4c;
+pub const OP_PUSHDATA2: u8 = 0x4d;
+pub const OP_PUSHDATA4: u8 = 0x4e;
+pub const OP_1NEGATE: u8 = 0x4f;
+pub const OP_1: u8 = 0x51;
+pub const OP_16: u8 = 0x60;
+pub const OP_IF: u8 = 0x63;
+pub const OP_NOTIF: u8 = 0x64;
+pub const OP_ELSE: u8 = 0x67;
+pub const OP_ENDIF: u8 = 0x68;
+pub const OP_RETURN: u8 = 0x6a;
+pub const OP_DROP: u8 = 0x75;
+pub const OP_DUP: u8 = 0x76;
+pub const OP_EQUAL: u8 = 0x87;
+pub const OP_EQUALVERIFY: u8 = 0x88;
+pub const OP_HASH160: u8 = 0xa9;
+pub const OP_CHECKSIG: u8 = 0xac;
+pub const OP_CHECKMULTISIG: u8 = 0xae;
+pub const OP_CHECKLOCKTIMEVERIFY: u8 = 0xb1;
+
+const PUSH_20: u8 = 0x14;
+const PUSH_32: u8 = 0x20;
+const PUSH_33: u8 = 0x21;
+const PUSH_65: u8 = 0x41;
+
+/// The most bytes pushed by the operation that is the length itself
+const MAX_DIRECT_PUSH: usize = 0x4b;
+
+/// It represents the standard forms of the script of an output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptType {
+    PayToPublicKey,
+    PayToPublicKeyHash,
+    PayToScriptHash,
+    PayToWitnessPublicKeyHash,
+    PayToWitnessScriptHash,
+    NullData,
+    NonStandard,
+}
+
+impl Display for ScriptType {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            ScriptType::PayToPublicKey => write!(f, "pubkey"),
+            ScriptType::PayToPublicKeyHash => write!(f, "pubkeyhash"),
+            ScriptType::PayToScriptHash => write!(f, "scripthash"),
+            ScriptType::PayToWitnessPublicKeyHash => write!(f, "witness_v0_keyhash"),
+            ScriptType::PayToWitnessScriptHash => write!(f, "witness_v0_scripthash"),
+            ScriptType::NullData => write!(f, "nulldata"),
+            ScriptType::NonStandard => write!(f, "nonstandard"),
+        }
+    }
+}
+
+/// It's an operation of a script: the data it pushes to the stack, or any other opcode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction<'a> {
+    PushBytes(&'a [u8]),
+    Op(u8),
+}
+
+/// It's a script borrowed from the bytes of an output or an input, to read its form without copying it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Script<'a>(&'a [u8]);
+
+impl<'a> Script<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Script(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+
+    /// Returns the standard form of the script, being `NonStandard` when it does not follow any of them
+    pub fn get_type(&self) -> ScriptType {
+        match self.0 {
+            [PUSH_33, key @ .., OP_CHECKSIG] if key.len() == 33 => ScriptType::PayToPublicKey,
+            [PUSH_65, key @ .., OP_CHECKSIG] if key.len() == 65 => ScriptType::PayToPublicKey,
+            [OP_DUP, OP_HASH160, PUSH_20, hash @ .., OP_EQUALVERIFY, OP_CHECKSIG]
+                if hash.len() == 20 =>
+            {
+                ScriptType::PayToPublicKeyHash
+            }
+            [OP_HASH160, PUSH_20, hash @ .., OP_EQUAL] if hash.len() == 20 => {
+                ScriptType::PayToScriptHash
+            }
+            [OP_0, PUSH_20, hash @ ..] if hash.len() == 20 => ScriptType::PayToWitnessPublicKeyHash,
+            [OP_0, PUSH_32, hash @ ..] if hash.len() == 32 => ScriptType::PayToWitnessScriptHash,
+            [OP_RETURN, ..] => ScriptType::NullData,
+            _ => ScriptType::NonStandard,
+        }
+    }
+
+    /// Returns true if the output cannot be spent because the script only carries data
+    pub fn is_op_return(&self) -> bool {
+        self.get_type() == ScriptType::NullData
+    }
+
+    /// Returns the public key the script pays to, if it's a pay to public key script
+    pub fn get_public_key(&self) -> Option<&'a [u8]> {
+        match self.get_type() {
+            ScriptType::PayToPublicKey => Some(&self.0[1..self.0.len() - 1]),
+            _ => None,
+        }
+    }
+
+    /// Returns the hash of the public key or of the script the script pays to, if it has one
+    pub fn get_hash(&self) -> Option<&'a [u8]> {
+        match self.get_type() {
+            ScriptType::PayToPublicKeyHash => Some(&self.0[3..23]),
+            ScriptType::PayToScriptHash => Some(&self.0[2..22]),
+            ScriptType::PayToWitnessPublicKeyHash | ScriptType::PayToWitnessScriptHash => {
+                Some(&self.0[2..])
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the operations of the script in order, or `None` if a push goes past the end of the script
+    pub fn instructions(&self) -> Option<Vec<Instruction<'a>>> {
+        let mut instructions: Vec<Instruction<'a>> = Vec::new();
+        let mut rest: &'a [u8] = self.0;
+
+        while let Some((&opcode, after_opcode)) = rest.split_first() {
+            let (length, after_length) = match opcode {
+                0x01..=0x4b => (opcode as usize, after_opcode),
+                OP_PUSHDATA1 => read_push_length(after_opcode, 1)?,
+                OP_PUSHDATA2 => read_push_length(after_opcode, 2)?,
+                OP_PUSHDATA4 => read_push_length(after_opcode, 4)?,
+                OP_0 => (0, after_opcode),
+                _ => {
+                    instructions.push(Instruction::Op(opcode));
+                    rest = after_opcode;
+                    continue;
+                }
+            };
+
+            if after_length.len() < length {
+                return None;
+            }
+            let (data, after_data) = after_length.split_at(length);
+            instructions.push(Instruction::PushBytes(data));
+            rest = after_data;
+        }
+
+        Some(instructions)
+    }
+
+    pub fn to_script_buf(&self) -> ScriptBuf {
+        ScriptBuf(self.0.to_vec())
+    }
+}
+
+/// Reads the length, in little endian, of a push with the given size of length
+fn read_push_length(bytes: &[u8], size: usize) -> Option<(usize, &[u8])> {
+    if bytes.len() < size {
+        return None;
+    }
+
+    let (length_bytes, rest) = bytes.split_at(size);
+    let length = length_bytes
+        .iter()
+        .rev()
+        .fold(0, |length, byte| (length << 8) | *byte as usize);
+    Some((length, rest))
+}
+
+impl Deref for Script<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.0
+    }
+}
+
+impl Display for Script<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", hash::to_hex(self.0))
+    }
+}
+
+/// It's a script owning its bytes, as the one locking an output, with the constructors of the
+/// standard forms
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ScriptBuf(Vec<u8>);
+
+impl ScriptBuf {
+    pub fn new() -> Self {
+        ScriptBuf::default()
+    }
+
+    /// Creates the script that pays to the given public key
+    pub fn new_p2pk(public_key: &[u8]) -> Self {
+        let mut script = vec![public_key.len() as u8];
+        script.extend_from_slice(public_key);
+        script.push(OP_CHECKSIG);
+        ScriptBuf(script)
+    }
+
+    /// Creates the script that pays to the hash of a public key
+    pub fn new_p2pkh(public_key_hash: &[u8; 20]) -> Self {
+        let mut script = vec![OP_DUP, OP_HASH160, PUSH_20];
+        script.extend_from_slice(public_key_hash);
+        script.extend_from_slice(&[OP_EQUALVERIFY, OP_CHECKSIG]);
+        ScriptBuf(script)
+    }
+
+    /// Creates the script that pays to the hash of a redeem script
+    pub fn new_p2sh(script_hash: &[u8; 20]) -> Self {
+        let mut script = vec![OP_HASH160, PUSH_20];
+        script.extend_from_slice(script_hash);
+        script.push(OP_EQUAL);
+        ScriptBuf(script)
+    }
+
+    /// Creates the script that needs the signatures of `threshold` of the given public keys, used as
+    /// the redeem script of a multisig P2SH output. There can be at most 16 public keys
+    pub fn new_multisig(threshold: u8, public_keys: &[[u8; 33]]) -> Self {
+        let mut script = ScriptBuf::new();
+        script.push_opcode(OP_1 + threshold - 1);
+        for public_key in public_keys {
+            script.push_slice(public_key);
+        }
+        script.push_opcode(OP_1 + public_keys.len() as u8 - 1);
+        script.push_opcode(OP_CHECKMULTISIG);
+        script
+    }
+
+    /// Creates the segwit version 0 script that pays to the hash of a public key
+    pub fn new_p2wpkh(public_key_hash: &[u8; 20]) -> Self {
+        let mut script = vec![OP_0, PUSH_20];
+        script.extend_from_slice(public_key_hash);
+        ScriptBuf(script)
+    }
+
+    /// Creates the segwit version 0 script that pays to the hash of a witness script
+    pub fn new_p2wsh(script_hash: &[u8; 32]) -> Self {
+        let mut script = vec![OP_0, PUSH_32];
+        script.extend_from_slice(script_hash);
+        ScriptBuf(script)
+    }
+
+    /// Creates the script of an output that cannot be spent, carrying the given data
+    pub fn new_op_return(data: &[u8]) -> Self {
+        let mut script = vec![OP_RETURN];
+        if data.len() > MAX_DIRECT_PUSH {
+            script.push(OP_PUSHDATA1);
+        }
+        script.push(data.len() as u8);
+        script.extend_from_slice(data);
+        ScriptBuf(script)
+    }
+
+    /// Reads a script written in hexadecimal
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when the value is not hexadecimal
+    pub fn from_hex(value: &str) -> Result<Self, ErrorSerialization> {
+        Ok(ScriptBuf(hash::bytes_from_hex(value)?))
+    }
+
+    /// Adds an operation that is not a push at the end of the script
+    pub fn push_opcode(&mut self, opcode: u8) {
+        self.0.push(opcode);
+    }
+
+    /// Adds the push of the data at the end of the script, with the smallest push operation for its size
+    pub fn push_slice(&mut self, data: &[u8]) {
+        match data.len() {
+            0 => self.0.push(OP_0),
+            length if length <= MAX_DIRECT_PUSH => self.0.push(length as u8),
+            length if length <= u8::MAX as usize => self.0.extend([OP_PUSHDATA1, length as u8]),
+            length if length <= u16::MAX as usize => {
+                self.0.push(OP_PUSHDATA2);
+                self.0.extend((length as u16).to_le_bytes());
+            }
+            length => {
+                self.0.push(OP_PUSHDATA4);
+                self.0.extend((length as u32).to_le_bytes());
+            }
+        }
+        self.0.extend_from_slice(data);
+    }
+
+    pub fn as_script(&self) -> Script<'_> {
+        Script(&self.0)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl From<Vec<u8>> for ScriptBuf {
+    fn from(bytes: Vec<u8>) -> Self {
+        ScriptBuf(bytes)
+    }
+}
+
+impl Deref for ScriptBuf {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Display for ScriptBuf {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_script())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_standard_scripts_are_recognized() {
+        let p2pkh = ScriptBuf::new_p2pkh(&[7; 20]);
+        let p2sh = ScriptBuf::new_p2sh(&[8; 20]);
+        let p2wpkh = ScriptBuf::new_p2wpkh(&[9; 20]);
+        let p2wsh = ScriptBuf::new_p2wsh(&[10; 32]);
+        let p2pk = ScriptBuf::new_p2pk(&[2; 33]);
+
+        assert_eq!(25, p2pkh.len());
+        assert_eq!(ScriptType::PayToPublicKeyHash, p2pkh.as_script().get_type());
+        assert_eq!(Some(&[7; 20][..]), p2pkh.as_script().get_hash());
+        assert_eq!(ScriptType::PayToScriptHash, p2sh.as_script().get_type());
+        assert_eq!(Some(&[8; 20][..]), p2sh.as_script().get_hash());
+        assert_eq!(
+            ScriptType::PayToWitnessPublicKeyHash,
+            p2wpkh.as_script().get_type()
+        );
+        assert_eq!(
+            ScriptType::PayToWitnessScriptHash,
+            p2wsh.as_script().get_type()
+        );
+        assert_eq!(Some(&[10; 32][..]), p2wsh.as_script().get_hash());
+        assert_eq!(ScriptType::PayToPublicKey, p2pk.as_script().get_type());
+        assert_eq!(Some(&[2; 33][..]), p2pk.as_script().get_public_key());
+        assert_eq!(
+            ScriptType::NonStandard,
+            ScriptBuf::new().as_script().get_type()
+        );
+    }
+
+    #[test]
+    fn test02_op_return_pushes_its_data() {
+        let short = ScriptBuf::new_op_return(&[1, 2, 3]);
+        assert_eq!(vec![OP_RETURN, 3, 1, 2, 3], short.clone().into_bytes());
+        assert!(short.as_script().is_op_return());
+        assert_eq!(None, short.as_script().get_hash());
+
+        let long = ScriptBuf::new_op_return(&[0; 80]);
+        assert_eq!(&[OP_RETURN, OP_PUSHDATA1, 80], &long[..3]);
+        assert_eq!(83, long.len());
+    }
+
+    #[test]
+    fn test03_script_is_written_and_read_in_hexadecimal() {
+        let script = ScriptBuf::new_p2pkh(&[0xab; 20]);
+        let hex = script.to_string();
+
+        assert!(hex.starts_with("76a914abab"));
+        assert!(hex.ends_with("88ac"));
+        assert_eq!(script, ScriptBuf::from_hex(&hex).unwrap());
+        assert_eq!(script.as_script().to_script_buf(), script);
+        assert!(ScriptBuf::from_hex("7g").is_err());
+    }
+
+    #[test]
+    fn test04_instructions_of_a_script() {
+        let script = ScriptBuf::from(vec![
+            0x03,
+            0x80,
+            0x1a,
+            0x06,
+            OP_CHECKLOCKTIMEVERIFY,
+            OP_0,
+            OP_PUSHDATA1,
+            0x02,
+            0xab,
+            0xcd,
+        ]);
+
+        assert_eq!(
+            Some(vec![
+                Instruction::PushBytes(&[0x80, 0x1a, 0x06]),
+                Instruction::Op(OP_CHECKLOCKTIMEVERIFY),
+                Instruction::PushBytes(&[]),
+                Instruction::PushBytes(&[0xab, 0xcd]),
+            ]),
+            script.as_script().instructions()
+        );
+        assert_eq!(None, Script::new(&[0x03, 0x01]).instructions());
+        assert_eq!(None, Script::new(&[OP_PUSHDATA2, 0x01]).instructions());
+    }
+
+    #[test]
+    fn test05_pushes_use_the_smallest_operation() {
+        let mut script = ScriptBuf::new();
+        script.push_slice(&[]);
+        script.push_slice(&[1; 75]);
+        script.push_slice(&[2; 76]);
+        script.push_slice(&[3; 256]);
+
+        assert_eq!(
+            Some(vec![
+                Instruction::PushBytes(&[]),
+                Instruction::PushBytes(&[1; 75]),
+                Instruction::PushBytes(&[2; 76]),
+                Instruction::PushBytes(&[3; 256]),
+            ]),
+            script.as_script().instructions()
+        );
+        assert_eq!(OP_PUSHDATA1, script[77]);
+        assert_eq!(OP_PUSHDATA2, script[155]);
+    }
+
+    #[test]
+    fn test06_multisig_script_has_its_threshold_and_keys() {
+        let script = ScriptBuf::new_multisig(2, &[[2; 33], [3; 33], [4; 33]]);
+
+        assert_eq!(
+            Some(vec![
+                Instruction::Op(OP_1 + 1),
+                Instruction::PushBytes(&[2; 33]),
+                Instruction::PushBytes(&[3; 33]),
+                Instruction::PushBytes(&[4; 33]),
+                Instruction::Op(OP_1 + 2),
+                Instruction::Op(OP_CHECKMULTISIG),
+            ]),
+            script.as_script().instructions()
+        );
+        assert_eq!(ScriptType::NonStandard, script.as_script().get_type());
+    }
+}
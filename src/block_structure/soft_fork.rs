@@ -0,0 +1,476 @@
+use super::{
+    block::Block,
+    coinbase::CoinbaseInfo,
+    error_block::ErrorBlock,
+    outpoint::Outpoint,
+    script::{
+        Instruction, Script, ScriptType, OP_1, OP_16, OP_1NEGATE, OP_CHECKLOCKTIMEVERIFY, OP_DROP,
+        OP_ELSE, OP_ENDIF, OP_IF, OP_NOTIF,
+    },
+    transaction::{Transaction, LOCKTIME_THRESHOLD},
+    transaction_input::SEQUENCE_FINAL,
+    transaction_output::TransactionOutput,
+    utxo_set::UTXOSet,
+};
+
+use std::collections::HashMap;
+
+/// The most bytes of the number checked by OP_CHECKLOCKTIMEVERIFY
+const MAX_LOCK_TIME_BYTES: usize = 5;
+
+/// It's the heights from which the soft forks signalled by the version of the blocks are enforced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SoftForkHeights {
+    /// From it the blocks have version 2 and their coinbase starts with their height (BIP34)
+    pub bip34: u64,
+
+    /// From it the blocks have version 3 and their signatures are strict DER (BIP66)
+    pub bip66: u64,
+
+    /// From it the blocks have version 4 and OP_CHECKLOCKTIMEVERIFY is enforced (BIP65)
+    pub bip65: u64,
+}
+
+impl SoftForkHeights {
+    /// Returns the least version of a block at the given height
+    pub fn get_minimum_version(&self, height: u64) -> i32 {
+        if height >= self.bip65 {
+            4
+        } else if height >= self.bip66 {
+            3
+        } else if height >= self.bip34 {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+/// Checks the rules of the soft forks active at the height of the block: its version, the height in its
+/// coinbase and the lock time of the transactions using OP_CHECKLOCKTIMEVERIFY. The transactions are
+/// only checked when they were downloaded, and only their inputs spending a P2SH output found in the
+/// utxo set or in the block itself
+///
+/// ### Error
+///  * `ErrorBlock::ObsoleteBlockVersion`: It will appear when the version of the block is below the one required at its height
+///  * `ErrorBlock::CoinbaseHeightMismatch`: It will appear when the coinbase doesn't start with the height of the block
+///  * `ErrorBlock::LockTimeVerifyFailed`: It will appear when a transaction doesn't meet the lock time it's required
+pub fn check_block(
+    block: &Block,
+    height: u64,
+    heights: SoftForkHeights,
+    utxo_set: &UTXOSet,
+) -> Result<(), ErrorBlock> {
+    if block.header.version.value < heights.get_minimum_version(height) {
+        return Err(ErrorBlock::ObsoleteBlockVersion);
+    }
+
+    if height >= heights.bip34
+        && block.get_coinbase().is_some()
+        && CoinbaseInfo::from_block(block).height != Some(height)
+    {
+        return Err(ErrorBlock::CoinbaseHeightMismatch);
+    }
+
+    if height >= heights.bip65 {
+        let block_outputs = get_block_outputs(block);
+        for transaction in block.transactions.iter() {
+            check_inputs_lock_time_verify(transaction, |outpoint| {
+                utxo_set
+                    .get(outpoint)
+                    .or_else(|| block_outputs.get(outpoint))
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks every OP_CHECKLOCKTIMEVERIFY in the redeem scripts revealed by the inputs of the transaction
+/// (BIP65). The lock time required must be of the same kind, height or time, as the lock time of the
+/// transaction and not after it, and the input spending it cannot be final. Only the inputs spending
+/// a P2SH output of the utxo set reveal a redeem script, for the rest the last push is not a script.
+/// Only the OP_CHECKLOCKTIMEVERIFY in the branches known to run are checked
+///
+/// ### Error
+///  * `ErrorBlock::LockTimeVerifyFailed`: It will appear when the transaction doesn't meet a lock time it's required
+pub fn check_lock_time_verify(
+    transaction: &Transaction,
+    utxo_set: &UTXOSet,
+) -> Result<(), ErrorBlock> {
+    check_inputs_lock_time_verify(transaction, |outpoint| utxo_set.get(outpoint))
+}
+
+/// Checks the lock time required by the redeem scripts of the inputs of the transaction whose spent
+/// output, given by `get_spent_output`, is a P2SH output. The pushes of the input and the redeem script
+/// are followed to know which branches of OP_IF and OP_NOTIF run; after any other operation the stack
+/// is unknown, so the branches that follow and their OP_CHECKLOCKTIMEVERIFY are not checked
+///
+/// ### Error
+///  * `ErrorBlock::LockTimeVerifyFailed`: It will appear when the transaction doesn't meet a lock time it's required
+fn check_inputs_lock_time_verify<'a, F>(
+    transaction: &Transaction,
+    get_spent_output: F,
+) -> Result<(), ErrorBlock>
+where
+    F: Fn(&Outpoint) -> Option<&'a TransactionOutput>,
+{
+    for tx_in in transaction.tx_in.iter() {
+        let spends_script_hash = get_spent_output(&tx_in.previous_output)
+            .map(|output| output.pk_script.as_script().get_type() == ScriptType::PayToScriptHash);
+        if spends_script_hash != Some(true) {
+            continue;
+        }
+
+        let signature_instructions = match Script::new(&tx_in.signature_script).instructions() {
+            Some(signature_instructions) => signature_instructions,
+            None => continue,
+        };
+
+        let (redeem_script, arguments) = match signature_instructions.split_last() {
+            Some((Instruction::PushBytes(redeem_script), arguments)) => (*redeem_script, arguments),
+            _ => continue,
+        };
+
+        let instructions = match Script::new(redeem_script).instructions() {
+            Some(instructions) => instructions,
+            None => continue,
+        };
+
+        let mut stack: Option<Vec<Instruction>> = match arguments.iter().all(is_data) {
+            true => Some(arguments.to_vec()),
+            false => None,
+        };
+        let mut conditions: Vec<Option<bool>> = Vec::new();
+
+        for instruction in instructions.iter() {
+            let skipped = conditions.contains(&Some(false));
+            let executed = conditions.iter().all(|condition| *condition == Some(true));
+
+            match instruction {
+                Instruction::Op(OP_IF | OP_NOTIF) => {
+                    let condition = match (skipped, executed, stack.as_mut()) {
+                        (true, _, _) => Some(false),
+                        (false, true, Some(stack)) => stack
+                            .pop()
+                            .map(|top| is_true(&top) == (*instruction == Instruction::Op(OP_IF))),
+                        _ => None,
+                    };
+                    if condition.is_none() {
+                        stack = None;
+                    }
+                    conditions.push(condition);
+                }
+                Instruction::Op(OP_ELSE) => match conditions.last_mut() {
+                    Some(condition) => *condition = condition.map(|condition| !condition),
+                    None => break,
+                },
+                Instruction::Op(OP_ENDIF) => {
+                    if conditions.pop().is_none() {
+                        break;
+                    }
+                }
+                _ if !executed => {}
+                Instruction::Op(OP_CHECKLOCKTIMEVERIFY) => {
+                    let required_lock_time = match stack.as_ref().map(|stack| stack.last()) {
+                        Some(Some(argument)) => get_number(argument),
+                        Some(None) => None,
+                        None => continue,
+                    };
+
+                    if !meets_lock_time(transaction, tx_in.sequence, required_lock_time) {
+                        return Err(ErrorBlock::LockTimeVerifyFailed);
+                    }
+                }
+                Instruction::Op(OP_DROP) => {
+                    if let Some(stack) = stack.as_mut() {
+                        stack.pop();
+                    }
+                }
+                data if is_data(data) => {
+                    if let Some(stack) = stack.as_mut() {
+                        stack.push(*data);
+                    }
+                }
+                _ => stack = None,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns true if the transaction meets the lock time required by an OP_CHECKLOCKTIMEVERIFY of the
+/// input with the given sequence
+fn meets_lock_time(
+    transaction: &Transaction,
+    sequence: u32,
+    required_lock_time: Option<i64>,
+) -> bool {
+    match required_lock_time {
+        Some(required_lock_time) if required_lock_time >= 0 => {
+            let transaction_lock_time = transaction.lock_time as i64;
+            let threshold = LOCKTIME_THRESHOLD as i64;
+            (required_lock_time < threshold) == (transaction_lock_time < threshold)
+                && required_lock_time <= transaction_lock_time
+                && sequence != SEQUENCE_FINAL
+        }
+        _ => false,
+    }
+}
+
+/// Returns the outputs created by the transactions of the block, so they can be found when they
+/// are spent in the same block
+fn get_block_outputs(block: &Block) -> HashMap<Outpoint, TransactionOutput> {
+    let mut block_outputs: HashMap<Outpoint, TransactionOutput> = HashMap::new();
+    for transaction in block.transactions.iter() {
+        let transaction_id = match transaction.get_tx_id() {
+            Ok(transaction_id) => transaction_id,
+            Err(_) => continue,
+        };
+
+        for (index, output) in transaction.tx_out.iter().enumerate() {
+            block_outputs.insert(Outpoint::new(transaction_id, index as u32), output.clone());
+        }
+    }
+    block_outputs
+}
+
+/// Returns true if the instruction only pushes data to the stack
+fn is_data(instruction: &Instruction) -> bool {
+    match instruction {
+        Instruction::PushBytes(_) => true,
+        Instruction::Op(opcode) => *opcode == OP_1NEGATE || (OP_1..=OP_16).contains(opcode),
+    }
+}
+
+/// Returns true if the data pushed by the instruction is true as a condition, which is any number
+/// but zero and negative zero
+fn is_true(instruction: &Instruction) -> bool {
+    match instruction {
+        Instruction::PushBytes(bytes) => match bytes.split_last() {
+            Some((last, rest)) => rest.iter().any(|byte| *byte != 0) || last & 0x7f != 0,
+            None => false,
+        },
+        Instruction::Op(_) => true,
+    }
+}
+
+/// Returns the number pushed by the instruction, in little endian with the sign in the highest bit
+fn get_number(instruction: &Instruction) -> Option<i64> {
+    let bytes = match instruction {
+        Instruction::PushBytes(bytes) if bytes.len() <= MAX_LOCK_TIME_BYTES => bytes,
+        Instruction::Op(OP_1NEGATE) => return Some(-1),
+        Instruction::Op(opcode) if (OP_1..=OP_16).contains(opcode) => {
+            return Some((opcode - OP_1 + 1) as i64)
+        }
+        _ => return None,
+    };
+
+    let (last, _) = match bytes.split_last() {
+        Some(split) => split,
+        None => return Some(0),
+    };
+
+    let magnitude = bytes
+        .iter()
+        .rev()
+        .fold(0, |value, byte| (value << 8) | *byte as i64)
+        & !(0x80 << (8 * (bytes.len() - 1)));
+
+    match last & 0x80 != 0 {
+        true => Some(-magnitude),
+        false => Some(magnitude),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        block_structure::{
+            block_header::BlockHeader,
+            block_version::BlockVersion,
+            compact256::Compact256,
+            outpoint::Outpoint,
+            script::{ScriptBuf, OP_CHECKSIG, OP_EQUALVERIFY, OP_HASH160},
+            transaction_input::TransactionInput,
+            transaction_output::TransactionOutput,
+        },
+        messages::compact_size::CompactSize,
+    };
+
+    const HEIGHTS: SoftForkHeights = SoftForkHeights {
+        bip34: 10,
+        bip66: 20,
+        bip65: 30,
+    };
+
+    fn create_block(version: i32, coinbase_script: Vec<u8>) -> Block {
+        let mut block = Block::new(BlockHeader::new(
+            BlockVersion::version(version),
+            [0; 32],
+            [0; 32],
+            0,
+            Compact256::from(u32::MAX),
+            0,
+            CompactSize::new(1),
+        ));
+        block
+            .append_transaction(Transaction {
+                version: 1,
+                tx_in: vec![TransactionInput::new(
+                    Outpoint::new([0; 32], u32::MAX),
+                    coinbase_script,
+                    u32::MAX,
+                )],
+                tx_out: vec![TransactionOutput::new(10, ScriptBuf::new())],
+                lock_time: 0,
+            })
+            .unwrap();
+        block
+    }
+
+    fn create_funding(pk_script: ScriptBuf) -> (UTXOSet, Outpoint) {
+        let mut funding = create_block(1, vec![0x01, 0x01]);
+        funding.transactions[0].tx_out = vec![TransactionOutput::new(10, pk_script)];
+        let outpoint = Outpoint::new(funding.transactions[0].get_tx_id().unwrap(), 0);
+
+        (UTXOSet::new(vec![funding]), outpoint)
+    }
+
+    fn create_cltv_spend(
+        previous_output: Outpoint,
+        required_lock_time: Vec<u8>,
+        lock_time: u32,
+        sequence: u32,
+    ) -> Transaction {
+        let mut redeem_script = vec![required_lock_time.len() as u8];
+        redeem_script.extend(required_lock_time);
+        redeem_script.push(OP_CHECKLOCKTIMEVERIFY);
+
+        let mut signature_script = vec![redeem_script.len() as u8];
+        signature_script.extend(redeem_script);
+
+        Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(
+                previous_output,
+                signature_script,
+                sequence,
+            )],
+            tx_out: vec![],
+            lock_time,
+        }
+    }
+
+    #[test]
+    fn test01_version_required_grows_with_each_soft_fork() {
+        let utxo_set = UTXOSet::new(Vec::new());
+        assert_eq!(1, HEIGHTS.get_minimum_version(9));
+        assert_eq!(2, HEIGHTS.get_minimum_version(10));
+        assert_eq!(3, HEIGHTS.get_minimum_version(20));
+        assert_eq!(4, HEIGHTS.get_minimum_version(30));
+
+        assert!(check_block(&create_block(1, vec![0x01, 0x05]), 5, HEIGHTS, &utxo_set).is_ok());
+        assert!(matches!(
+            check_block(&create_block(2, vec![0x01, 0x14]), 20, HEIGHTS, &utxo_set),
+            Err(ErrorBlock::ObsoleteBlockVersion)
+        ));
+    }
+
+    #[test]
+    fn test02_coinbase_starts_with_the_height_after_bip34() {
+        let utxo_set = UTXOSet::new(Vec::new());
+        assert!(check_block(&create_block(2, vec![0x01, 0x0c]), 12, HEIGHTS, &utxo_set).is_ok());
+        assert!(matches!(
+            check_block(&create_block(2, vec![0x01, 0x0b]), 12, HEIGHTS, &utxo_set),
+            Err(ErrorBlock::CoinbaseHeightMismatch)
+        ));
+        assert!(check_block(&create_block(1, vec![0x01, 0x0b]), 5, HEIGHTS, &utxo_set).is_ok());
+    }
+
+    #[test]
+    fn test03_lock_time_verify_requires_the_lock_time_of_the_transaction() {
+        let (utxo_set, outpoint) = create_funding(ScriptBuf::new_p2sh(&[3; 20]));
+        let spend = |required_lock_time: Vec<u8>, lock_time: u32, sequence: u32| {
+            check_lock_time_verify(
+                &create_cltv_spend(outpoint.clone(), required_lock_time, lock_time, sequence),
+                &utxo_set,
+            )
+        };
+        let height_500 = vec![0xf4, 0x01];
+
+        assert!(spend(height_500.clone(), 500, 0).is_ok());
+        assert!(spend(height_500.clone(), 600, 0).is_ok());
+        assert!(matches!(
+            spend(height_500.clone(), 499, 0),
+            Err(ErrorBlock::LockTimeVerifyFailed)
+        ));
+        assert!(spend(height_500.clone(), LOCKTIME_THRESHOLD, 0).is_err());
+        assert!(spend(height_500, 500, SEQUENCE_FINAL).is_err());
+        assert!(spend(vec![0x81], 500, 0).is_err());
+    }
+
+    #[test]
+    fn test04_only_inputs_spending_a_script_hash_reveal_a_redeem_script() {
+        let height_500 = vec![0xf4, 0x01];
+
+        let (utxo_set, outpoint) = create_funding(ScriptBuf::new_p2pkh(&[3; 20]));
+        let spend = create_cltv_spend(outpoint, height_500.clone(), 0, SEQUENCE_FINAL);
+        assert!(check_lock_time_verify(&spend, &utxo_set).is_ok());
+
+        let spend = create_cltv_spend(Outpoint::new([1; 32], 0), height_500, 0, SEQUENCE_FINAL);
+        assert!(check_lock_time_verify(&spend, &utxo_set).is_ok());
+    }
+
+    #[test]
+    fn test05_lock_time_verify_is_only_required_in_the_branch_that_runs() {
+        let (utxo_set, outpoint) = create_funding(ScriptBuf::new_p2sh(&[3; 20]));
+        let spend = |else_lock_time: Vec<u8>, condition: Vec<u8>, lock_time: u32, sequence: u32| {
+            let mut redeem_script = ScriptBuf::new();
+            redeem_script.push_opcode(OP_IF);
+            redeem_script.push_opcode(OP_HASH160);
+            redeem_script.push_slice(&[4; 20]);
+            redeem_script.push_opcode(OP_EQUALVERIFY);
+            redeem_script.push_slice(&[2; 33]);
+            redeem_script.push_opcode(OP_ELSE);
+            redeem_script.push_slice(&else_lock_time);
+            redeem_script.push_opcode(OP_CHECKLOCKTIMEVERIFY);
+            redeem_script.push_opcode(OP_DROP);
+            redeem_script.push_slice(&[2; 33]);
+            redeem_script.push_opcode(OP_ENDIF);
+            redeem_script.push_opcode(OP_CHECKSIG);
+
+            let mut signature_script = ScriptBuf::new();
+            signature_script.push_slice(&[5; 71]);
+            signature_script.push_slice(&[6; 32]);
+            signature_script.push_slice(&condition);
+            signature_script.push_slice(redeem_script.as_bytes());
+
+            let transaction = Transaction {
+                version: 1,
+                tx_in: vec![TransactionInput::new(
+                    outpoint.clone(),
+                    signature_script.into_bytes(),
+                    sequence,
+                )],
+                tx_out: vec![],
+                lock_time,
+            };
+            check_lock_time_verify(&transaction, &utxo_set)
+        };
+        let height_500 = vec![0xf4, 0x01];
+
+        assert!(spend(height_500.clone(), vec![1], 0, SEQUENCE_FINAL).is_ok());
+        assert!(spend(vec![1; 9], vec![1], 0, SEQUENCE_FINAL).is_ok());
+
+        assert!(spend(height_500.clone(), vec![], 500, 0).is_ok());
+        assert!(matches!(
+            spend(height_500.clone(), vec![], 0, SEQUENCE_FINAL),
+            Err(ErrorBlock::LockTimeVerifyFailed)
+        ));
+        assert!(spend(height_500, vec![0x80], 499, 0).is_err());
+    }
+}
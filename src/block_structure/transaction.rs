@@ -1,12 +1,16 @@
 use super::{
     error_block::ErrorBlock,
-    hash::{hash256d, HashType},
+    hash::{self, hash256d, HashType},
     outpoint::Outpoint,
-    transaction_input::TransactionInput,
+    relay_policy,
+    transaction_description::TransactionDescription,
+    transaction_input::{TransactionInput, SEQUENCE_FINAL},
     transaction_output::TransactionOutput,
+    utxo_set::UTXOSet,
 };
 
 use crate::{
+    connections::network::Network,
     messages::compact_size::CompactSize,
     serialization::{
         deserializable_internal_order::DeserializableInternalOrder,
@@ -15,11 +19,12 @@ use crate::{
         serializable_internal_order::SerializableInternalOrder,
         serializable_little_endian::SerializableLittleEndian,
     },
-    wallet_structure::{account::Account, address::Address, error_wallet::ErrorWallet},
+    wallet_structure::{
+        account::Account, address::Address, error_wallet::ErrorWallet,
+        transaction_options::TransactionOptions,
+    },
 };
 
-use chrono::offset::Utc;
-
 use std::{
     cmp::PartialEq,
     collections::HashMap,
@@ -27,13 +32,25 @@ use std::{
     io::{Read, Write},
 };
 
+/// The biggest payload a transaction can be in, given by the maximum size of a block
+pub const MAX_TRANSACTION_SIZE: u64 = 4_000_000;
+
+/// The smallest serialized input: the outpoint, an empty script and the sequence
+const MIN_INPUT_SIZE: u64 = 36 + 1 + 4;
+
+/// The smallest serialized output: the value and an empty script
+const MIN_OUTPUT_SIZE: u64 = 8 + 1;
+
+/// The lock times below this value are block heights, and the others are Unix times
+pub const LOCKTIME_THRESHOLD: u32 = 500_000_000;
+
 /// It's the representation of a transaction in the block chain
 #[derive(Debug, Clone, PartialEq)]
 pub struct Transaction {
     pub version: i32,
     pub tx_in: Vec<TransactionInput>,
     pub tx_out: Vec<TransactionOutput>,
-    pub time: u32,
+    pub lock_time: u32,
 }
 
 impl Transaction {
@@ -52,6 +69,12 @@ impl Transaction {
         }
     }
 
+    /// It create the witness id for this transaction (BIP141). While the transactions are serialized
+    /// without witness data, it's the same as the id
+    pub fn get_wtx_id(&self) -> Result<HashType, ErrorBlock> {
+        self.get_tx_id()
+    }
+
     /// It create the id for all the transaction
     ///
     /// ### Error
@@ -64,6 +87,39 @@ impl Transaction {
         Ok(tx_ids)
     }
 
+    /// Reads a transaction serialized in hexadecimal, as the block explorers and other wallets show
+    /// the raw transactions
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when the value is not hexadecimal, is not a transaction or has more bytes after it
+    pub fn from_hex(value: &str) -> Result<Transaction, ErrorSerialization> {
+        let bytes = hash::bytes_from_hex(value)?;
+        let mut stream = bytes.as_slice();
+        let transaction = Transaction::io_deserialize(&mut stream)?;
+
+        match stream.is_empty() {
+            true => Ok(transaction),
+            false => Err(ErrorSerialization::ErrorInDeserialization(format!(
+                "There are {} bytes left after the transaction",
+                stream.len()
+            ))),
+        }
+    }
+
+    /// Returns the breakdown of the transaction into its inputs and outputs, with the addresses of
+    /// the given network. The values of the inputs, and so the fee, are known only for the outputs
+    /// spent that are in the UTXO set
+    ///
+    /// ### Error
+    ///  * `ErrorBlock::CouldNotGetTxId`: It will appear when the transaction could not be serialized
+    pub fn describe(
+        &self,
+        utxo_set: Option<&UTXOSet>,
+        network: Network,
+    ) -> Result<TransactionDescription, ErrorBlock> {
+        TransactionDescription::new(self, utxo_set, network)
+    }
+
     /// Returns true if the address owns any of transaction output (works for P2PKH) and false otherwise
     pub fn verify_transaction_ownership(&self, address: &Address) -> bool {
         self.tx_out
@@ -71,7 +127,33 @@ impl Transaction {
             .any(|tx_out| address.verify_transaction_ownership(tx_out))
     }
 
-    /// Returns a transaction given the amount and to whom it is sent
+    /// Returns true if the transaction can be in the block at the given height, whose previous blocks
+    /// have the given median time past. It's the case when the transaction has no lock time, when
+    /// every input is final, or when the lock time already passed
+    pub fn is_final(&self, height: u32, median_time_past: u32) -> bool {
+        if self.lock_time == 0
+            || self
+                .tx_in
+                .iter()
+                .all(|tx_in| tx_in.sequence == SEQUENCE_FINAL)
+        {
+            return true;
+        }
+
+        match self.lock_time < LOCKTIME_THRESHOLD {
+            true => self.lock_time < height,
+            false => self.lock_time < median_time_past,
+        }
+    }
+
+    /// Returns true if any input of the transaction signals that it can be replaced (BIP125)
+    pub fn signals_replacement(&self) -> bool {
+        self.tx_in.iter().any(TransactionInput::signals_replacement)
+    }
+
+    /// Returns a transaction given the amount and to whom it is sent. The change goes back to the
+    /// account, unless it's dust, in which case it's left to the fee. The options give the lock time
+    /// of the transaction and the sequence of its inputs
     ///
     /// ### Error
     ///  * `ErrorWallet::CannotCreateNewTransaction`: It will appear when a transaction cannot be created
@@ -82,10 +164,36 @@ impl Transaction {
         account_to: &Address,
         amount: i64,
         fee: i64,
+        options: TransactionOptions,
     ) -> Result<Transaction, ErrorWallet> {
+        let mut unsigned_transaction = Transaction::unsigned_to_address(
+            outputs_to_spend,
+            account_to,
+            &account_from.address,
+            amount,
+            fee,
+            options,
+        );
+
+        unsigned_transaction.get_signed_by_account(account_from)?;
+
+        Ok(unsigned_transaction)
+    }
+
+    /// Returns the transaction, with its inputs not yet signed, that spends the outputs to send the
+    /// amount to the address. The change goes back to the change address, unless it's dust
+    pub fn unsigned_to_address(
+        outputs_to_spend: &HashMap<Outpoint, TransactionOutput>,
+        address_to: &Address,
+        change_address: &Address,
+        amount: i64,
+        fee: i64,
+        options: TransactionOptions,
+    ) -> Transaction {
         let mut tx_in: Vec<TransactionInput> = Vec::new();
         for outpoint in outputs_to_spend.keys() {
-            let new_transaction_input = TransactionInput::from_outpoint_unsigned(outpoint);
+            let new_transaction_input =
+                TransactionInput::from_outpoint_unsigned(outpoint, options.get_sequence());
             tx_in.push(new_transaction_input);
         }
 
@@ -98,25 +206,21 @@ impl Transaction {
 
         let mut tx_out: Vec<TransactionOutput> = Vec::new();
         let transaction_output_to_address =
-            TransactionOutput::new(amount, account_to.generate_script_pubkey_p2pkh());
+            TransactionOutput::new(amount, address_to.generate_script_pubkey());
         let transaction_output_change =
-            TransactionOutput::new(change, account_from.address.generate_script_pubkey_p2pkh());
+            TransactionOutput::new(change, change_address.generate_script_pubkey());
 
         tx_out.push(transaction_output_to_address);
-        tx_out.push(transaction_output_change);
-
-        let time: u32 = Utc::now().timestamp() as u32;
+        if !relay_policy::is_dust(&transaction_output_change) {
+            tx_out.push(transaction_output_change);
+        }
 
-        let mut unsigned_transaction = Transaction {
+        Transaction {
             version: 1,
             tx_in,
             tx_out,
-            time,
-        };
-
-        unsigned_transaction.get_signed_by_account(account_from)?;
-
-        Ok(unsigned_transaction)
+            lock_time: options.lock_time,
+        }
     }
 
     /// Sign the transaction with the given account
@@ -127,15 +231,61 @@ impl Transaction {
         let unsigned_transaction = self.clone();
 
         for (index, tx_in) in self.tx_in.iter_mut().enumerate() {
-            let script_sig = TransactionInput::create_signature_script(
-                account,
-                unsigned_transaction.clone(),
-                index,
-            )?;
+            let script_sig =
+                TransactionInput::create_signature_script(account, &unsigned_transaction, index)?;
             tx_in.signature_script = script_sig;
         }
         Ok(())
     }
+
+    /// Deserialize a transaction from a payload with the given amount of bytes left. The amount of
+    /// inputs and outputs is limited by how many of them fit in the bytes that are left
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when there are more inputs or outputs than the ones that fit in the payload
+    ///  * `ErrorSerialization::ErrorWhileReading`: It will appear when the transaction is bigger than the payload
+    pub fn io_deserialize_limited(
+        stream: &mut dyn Read,
+        remaining: u64,
+    ) -> Result<Self, ErrorSerialization> {
+        let mut stream = stream.take(remaining);
+        let version = i32::le_deserialize(&mut stream)?;
+
+        let length_tx_in = CompactSize::le_deserialize(&mut stream)?.value;
+        if length_tx_in.saturating_mul(MIN_INPUT_SIZE) > stream.limit() {
+            return Err(ErrorSerialization::ErrorInDeserialization(format!(
+                "Too many inputs in the transaction for the {} bytes left, we get: {length_tx_in}",
+                stream.limit()
+            )));
+        }
+
+        let mut tx_in: Vec<TransactionInput> = Vec::new();
+        for _ in 0..length_tx_in {
+            tx_in.push(TransactionInput::io_deserialize(&mut stream)?);
+        }
+
+        let length_tx_out = CompactSize::le_deserialize(&mut stream)?.value;
+        if length_tx_out.saturating_mul(MIN_OUTPUT_SIZE) > stream.limit() {
+            return Err(ErrorSerialization::ErrorInDeserialization(format!(
+                "Too many outputs in the transaction for the {} bytes left, we get: {length_tx_out}",
+                stream.limit()
+            )));
+        }
+
+        let mut tx_out: Vec<TransactionOutput> = Vec::new();
+        for _ in 0..length_tx_out {
+            tx_out.push(TransactionOutput::io_deserialize(&mut stream)?);
+        }
+
+        let lock_time = u32::le_deserialize(&mut stream)?;
+
+        Ok(Transaction {
+            version,
+            tx_in,
+            tx_out,
+            lock_time,
+        })
+    }
 }
 
 impl Display for Transaction {
@@ -145,12 +295,7 @@ impl Display for Transaction {
             Err(_) => return write!(f, "Transaction fail at get tx id"),
         };
 
-        let mut transaction_id_string = "".to_string();
-        for byte in transaction_id.iter().rev() {
-            transaction_id_string.push_str(&format!("{:02x}", byte));
-        }
-
-        write!(f, "{transaction_id_string}")
+        write!(f, "{}", hash::to_hex_be(&transaction_id))
     }
 }
 
@@ -168,35 +313,14 @@ impl SerializableInternalOrder for Transaction {
             tx_out.io_serialize(stream)?;
         }
 
-        self.time.le_serialize(stream)?;
+        self.lock_time.le_serialize(stream)?;
         Ok(())
     }
 }
 
 impl DeserializableInternalOrder for Transaction {
     fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
-        let version = i32::le_deserialize(stream)?;
-
-        let length_tx_in = CompactSize::le_deserialize(stream)?.value;
-        let mut tx_in: Vec<TransactionInput> = Vec::new();
-        for _ in 0..length_tx_in {
-            tx_in.push(TransactionInput::io_deserialize(stream)?);
-        }
-
-        let length_tx_out = CompactSize::le_deserialize(stream)?.value;
-        let mut tx_out: Vec<TransactionOutput> = Vec::new();
-        for _ in 0..length_tx_out {
-            tx_out.push(TransactionOutput::io_deserialize(stream)?);
-        }
-
-        let time = u32::le_deserialize(stream)?;
-
-        Ok(Transaction {
-            version,
-            tx_in,
-            tx_out,
-            time,
-        })
+        Self::io_deserialize_limited(stream, MAX_TRANSACTION_SIZE)
     }
 }
 
@@ -205,6 +329,8 @@ impl DeserializableInternalOrder for Transaction {
 mod tests {
     use super::*;
 
+    use crate::block_structure::script::ScriptBuf;
+
     #[test]
     fn test_01_correct_transaction_serialization() {
         let transaction_input =
@@ -212,14 +338,14 @@ mod tests {
 
         let transaction_output = TransactionOutput {
             value: 10,
-            pk_script: vec![4, 5, 6],
+            pk_script: ScriptBuf::from(vec![4, 5, 6]),
         };
 
         let transaction = Transaction {
             version: 1,
             tx_in: vec![transaction_input.clone()],
             tx_out: vec![transaction_output.clone()],
-            time: 0,
+            lock_time: 0,
         };
 
         let mut buffer: Vec<u8> = Vec::new();
@@ -253,14 +379,14 @@ mod tests {
 
         let transaction_output = TransactionOutput {
             value: 10,
-            pk_script: vec![4, 5, 6],
+            pk_script: ScriptBuf::from(vec![4, 5, 6]),
         };
 
         let transaction = Transaction {
             version: 1,
             tx_in: vec![transaction_input.clone()],
             tx_out: vec![transaction_output.clone()],
-            time: 0,
+            lock_time: 0,
         };
 
         let mut buffer: Vec<u8> = Vec::new();
@@ -278,14 +404,14 @@ mod tests {
 
         let transaction_output = TransactionOutput {
             value: 10,
-            pk_script: vec![4, 5, 6],
+            pk_script: ScriptBuf::from(vec![4, 5, 6]),
         };
 
         let transaction = Transaction {
             version: 1,
             tx_in: vec![transaction_input.clone()],
             tx_out: vec![transaction_output.clone()],
-            time: 0,
+            lock_time: 0,
         };
 
         let tx_id = transaction.get_tx_id().unwrap();
@@ -332,6 +458,7 @@ mod tests {
                 0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
                 0x35, 0x14, 0x92, 0x4A, 0x22,
             ],
+            Network::Testnet,
         )
         .unwrap();
 
@@ -377,4 +504,118 @@ mod tests {
 
         assert_eq!(transaction_to_be_signed, signed_transaction);
     }
+
+    #[test]
+    fn test_06_transaction_with_too_many_inputs_or_outputs_is_rejected() {
+        let mut buffer: Vec<u8> = Vec::new();
+        1i32.le_serialize(&mut buffer).unwrap();
+        CompactSize::new(u32::MAX as u64)
+            .le_serialize(&mut buffer)
+            .unwrap();
+
+        assert!(Transaction::io_deserialize(&mut buffer.as_slice()).is_err());
+
+        let mut buffer: Vec<u8> = Vec::new();
+        1i32.le_serialize(&mut buffer).unwrap();
+        CompactSize::new(0).le_serialize(&mut buffer).unwrap();
+        CompactSize::new(u32::MAX as u64)
+            .le_serialize(&mut buffer)
+            .unwrap();
+
+        assert!(Transaction::io_deserialize(&mut buffer.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_07_transaction_is_read_from_hexadecimal() {
+        let transaction = Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(
+                Outpoint::new([1; 32], 0),
+                vec![1, 2],
+                0xffffffff,
+            )],
+            tx_out: vec![TransactionOutput {
+                value: 1000,
+                pk_script: ScriptBuf::from(vec![3, 4, 5]),
+            }],
+            lock_time: 0,
+        };
+
+        let mut buffer: Vec<u8> = Vec::new();
+        transaction.io_serialize(&mut buffer).unwrap();
+        let raw = hash::to_hex(&buffer);
+
+        assert_eq!(
+            transaction,
+            Transaction::from_hex(&format!("{raw}\n")).unwrap()
+        );
+        assert!(Transaction::from_hex(&format!("{raw}00")).is_err());
+        assert!(Transaction::from_hex(&raw[..raw.len() - 2]).is_err());
+        assert!(Transaction::from_hex("not a transaction").is_err());
+    }
+
+    #[test]
+    fn test_08_transaction_is_final_once_its_lock_time_passed() {
+        let mut transaction = Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(
+                Outpoint::new([1; 32], 0),
+                vec![],
+                SEQUENCE_FINAL - 1,
+            )],
+            tx_out: vec![],
+            lock_time: 0,
+        };
+        assert!(transaction.is_final(1, 0));
+
+        transaction.lock_time = 100;
+        assert!(!transaction.is_final(100, u32::MAX));
+        assert!(transaction.is_final(101, 0));
+
+        transaction.lock_time = LOCKTIME_THRESHOLD + 1_000;
+        assert!(!transaction.is_final(u32::MAX, LOCKTIME_THRESHOLD + 1_000));
+        assert!(transaction.is_final(0, LOCKTIME_THRESHOLD + 1_001));
+
+        transaction.tx_in[0].sequence = SEQUENCE_FINAL;
+        assert!(transaction.is_final(0, 0));
+    }
+
+    #[test]
+    fn test_09_inputs_and_outputs_are_limited_by_the_bytes_left_in_the_payload() {
+        let mut buffer: Vec<u8> = Vec::new();
+        1i32.le_serialize(&mut buffer).unwrap();
+        CompactSize::new(1_000).le_serialize(&mut buffer).unwrap();
+        buffer.resize(100, 0);
+
+        let remaining = buffer.len() as u64;
+        assert!(matches!(
+            Transaction::io_deserialize_limited(&mut buffer.as_slice(), remaining),
+            Err(ErrorSerialization::ErrorInDeserialization(_))
+        ));
+
+        let transaction = Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(
+                Outpoint::new([1; 32], 0),
+                vec![1, 2],
+                0xffffffff,
+            )],
+            tx_out: vec![TransactionOutput {
+                value: 1000,
+                pk_script: ScriptBuf::from(vec![3, 4, 5]),
+            }],
+            lock_time: 0,
+        };
+
+        let mut buffer: Vec<u8> = Vec::new();
+        transaction.io_serialize(&mut buffer).unwrap();
+        let remaining = buffer.len() as u64;
+        assert_eq!(
+            transaction,
+            Transaction::io_deserialize_limited(&mut buffer.as_slice(), remaining).unwrap()
+        );
+        assert!(
+            Transaction::io_deserialize_limited(&mut buffer.as_slice(), remaining - 1).is_err()
+        );
+    }
 }
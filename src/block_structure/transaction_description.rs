@@ -0,0 +1,298 @@
+use super::{
+    coinbase::CoinbaseInfo,
+    error_block::ErrorBlock,
+    hash::{self, hash160, HashType},
+    outpoint::Outpoint,
+    script::{Script, ScriptType},
+    transaction::Transaction,
+    utxo_set::UTXOSet,
+};
+
+use crate::{
+    connections::network::Network,
+    serialization::serializable_internal_order::SerializableInternalOrder,
+    wallet_structure::address::Address,
+};
+
+use std::fmt::{Display, Formatter};
+
+/// Returns the address the script pays to in the given network. Only the scripts that pay to a
+/// public key, the hash of a public key or the hash of a script have one
+pub fn script_address(pk_script: Script, network: Network) -> Option<Address> {
+    let (version_byte, hash) = match pk_script.get_type() {
+        ScriptType::PayToPublicKey => (
+            network.address_version_byte(),
+            hash160(pk_script.get_public_key()?).ok()?,
+        ),
+        ScriptType::PayToPublicKeyHash => (
+            network.address_version_byte(),
+            pk_script.get_hash()?.try_into().ok()?,
+        ),
+        ScriptType::PayToScriptHash => (
+            network.script_version_byte(),
+            pk_script.get_hash()?.try_into().ok()?,
+        ),
+        _ => return None,
+    };
+
+    Address::from_hash(version_byte, &hash).ok()
+}
+
+/// It's the description of an input of a transaction
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputDescription {
+    pub previous_output: Outpoint,
+    pub sequence: u32,
+
+    /// The value of the output spent, if it's in the UTXO set
+    pub value: Option<i64>,
+
+    /// The address of the output spent, if it's in the UTXO set and has one
+    pub address: Option<Address>,
+}
+
+/// It's the description of an output of a transaction
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputDescription {
+    pub value: i64,
+    pub script_type: ScriptType,
+    pub address: Option<Address>,
+}
+
+/// It's the breakdown of a transaction into its inputs and outputs, as the `decoderawtransaction`
+/// command of the reference client shows it
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionDescription {
+    pub transaction_id: HashType,
+    pub version: i32,
+    pub lock_time: u32,
+    pub size: usize,
+    pub is_coinbase: bool,
+    pub inputs: Vec<InputDescription>,
+    pub outputs: Vec<OutputDescription>,
+}
+
+impl TransactionDescription {
+    /// Describes the transaction, looking for the outputs it spends in the UTXO set if given
+    ///
+    /// ### Error
+    ///  * `ErrorBlock::CouldNotGetTxId`: It will appear when the transaction could not be serialized
+    pub fn new(
+        transaction: &Transaction,
+        utxo_set: Option<&UTXOSet>,
+        network: Network,
+    ) -> Result<Self, ErrorBlock> {
+        let mut serialized_transaction: Vec<u8> = Vec::new();
+        if transaction
+            .io_serialize(&mut serialized_transaction)
+            .is_err()
+        {
+            return Err(ErrorBlock::CouldNotGetTxId);
+        }
+
+        let is_coinbase = CoinbaseInfo::is_coinbase(transaction);
+        let inputs = transaction
+            .tx_in
+            .iter()
+            .map(|input| {
+                let spent_output = match (is_coinbase, utxo_set) {
+                    (false, Some(utxo_set)) => utxo_set.get(&input.previous_output),
+                    _ => None,
+                };
+
+                InputDescription {
+                    previous_output: input.previous_output.clone(),
+                    sequence: input.sequence,
+                    value: spent_output.map(|output| output.value),
+                    address: spent_output
+                        .and_then(|output| script_address(output.pk_script.as_script(), network)),
+                }
+            })
+            .collect();
+
+        let outputs = transaction
+            .tx_out
+            .iter()
+            .map(|output| OutputDescription {
+                value: output.value,
+                script_type: output.pk_script.as_script().get_type(),
+                address: script_address(output.pk_script.as_script(), network),
+            })
+            .collect();
+
+        Ok(TransactionDescription {
+            transaction_id: transaction.get_tx_id()?,
+            version: transaction.version,
+            lock_time: transaction.lock_time,
+            size: serialized_transaction.len(),
+            is_coinbase,
+            inputs,
+            outputs,
+        })
+    }
+
+    /// Returns the value of all the inputs, if the value of every output spent is known
+    pub fn total_input(&self) -> Option<i64> {
+        if self.is_coinbase {
+            return None;
+        }
+
+        self.inputs.iter().map(|input| input.value).sum()
+    }
+
+    /// Returns the value of all the outputs
+    pub fn total_output(&self) -> i64 {
+        self.outputs.iter().map(|output| output.value).sum()
+    }
+
+    /// Returns the fee paid by the transaction, if the value of every output spent is known
+    pub fn fee(&self) -> Option<i64> {
+        self.total_input()
+            .map(|total_input| total_input - self.total_output())
+    }
+}
+
+/// Returns the amount in satoshis, or that it's unknown
+fn format_value(value: Option<i64>) -> String {
+    match value {
+        Some(value) => format!("{value} satoshis"),
+        None => "unknown".to_string(),
+    }
+}
+
+impl Display for TransactionDescription {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        writeln!(f, "Transaction {}", hash::to_hex_be(&self.transaction_id))?;
+        writeln!(
+            f,
+            "  Version: {}, lock time: {}, size: {} bytes",
+            self.version, self.lock_time, self.size
+        )?;
+
+        writeln!(f, "  Inputs ({}):", self.inputs.len())?;
+        for (index, input) in self.inputs.iter().enumerate() {
+            if self.is_coinbase {
+                writeln!(f, "    {index}: coinbase")?;
+                continue;
+            }
+
+            write!(
+                f,
+                "    {index}: {}:{} (sequence {:08x}), {}",
+                hash::to_hex_be(&input.previous_output.get_transaction_id()),
+                input.previous_output.get_index(),
+                input.sequence,
+                format_value(input.value),
+            )?;
+            match &input.address {
+                Some(address) => writeln!(f, " from {address}")?,
+                None => writeln!(f)?,
+            }
+        }
+
+        writeln!(f, "  Outputs ({}):", self.outputs.len())?;
+        for (index, output) in self.outputs.iter().enumerate() {
+            write!(f, "    {index}: {} satoshis", output.value)?;
+            match &output.address {
+                Some(address) => writeln!(f, " to {address} ({})", output.script_type)?,
+                None => writeln!(f, " ({})", output.script_type)?,
+            }
+        }
+
+        write!(
+            f,
+            "  Total in: {}, total out: {} satoshis, fee: {}",
+            format_value(self.total_input()),
+            self.total_output(),
+            format_value(self.fee()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        block_structure::{
+            block::Block, block_header::BlockHeader, block_version::BlockVersion,
+            compact256::Compact256, script::ScriptBuf, transaction_input::TransactionInput,
+            transaction_output::TransactionOutput,
+        },
+        messages::compact_size::CompactSize,
+    };
+
+    const TESTNET_ADDRESS: &str = "mnQLoVaZ3w1NLVmUhfG8hh6WoG3iu7cnNw";
+
+    fn spend(previous_output: Outpoint, outputs: Vec<TransactionOutput>) -> Transaction {
+        Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(previous_output, vec![], 0xffffffff)],
+            tx_out: outputs,
+            lock_time: 0,
+        }
+    }
+
+    #[test]
+    fn test01_addresses_of_the_standard_scripts() {
+        let address = Address::new(TESTNET_ADDRESS).unwrap();
+        let p2pkh = address.generate_script_pubkey_p2pkh();
+        let p2sh = ScriptBuf::new_p2sh(address.extract_hashed_pk().try_into().unwrap());
+
+        assert_eq!(
+            Some(address),
+            script_address(p2pkh.as_script(), Network::Testnet)
+        );
+        assert!(script_address(p2sh.as_script(), Network::Testnet)
+            .unwrap()
+            .to_string()
+            .starts_with('2'));
+        assert_eq!(
+            None,
+            script_address(ScriptBuf::new_op_return(&[1]).as_script(), Network::Testnet)
+        );
+    }
+
+    #[test]
+    fn test02_fee_is_known_when_the_outputs_spent_are_in_the_utxo_set() {
+        let address = Address::new(TESTNET_ADDRESS).unwrap();
+        let funding = spend(
+            Outpoint::new([1; 32], 0),
+            vec![TransactionOutput::new(
+                1000,
+                address.generate_script_pubkey_p2pkh(),
+            )],
+        );
+
+        let mut block = Block::new(BlockHeader::new(
+            BlockVersion::version(1),
+            [0; 32],
+            [0; 32],
+            0,
+            Compact256::from(u32::MAX),
+            0,
+            CompactSize::new(1),
+        ));
+        block.append_transaction(funding.clone()).unwrap();
+        let utxo_set = UTXOSet::new(vec![block]);
+
+        let transaction = spend(
+            Outpoint::new(funding.get_tx_id().unwrap(), 0),
+            vec![TransactionOutput::new(900, ScriptBuf::new_op_return(&[]))],
+        );
+
+        let description =
+            TransactionDescription::new(&transaction, Some(&utxo_set), Network::Testnet).unwrap();
+        assert_eq!(Some(1000), description.total_input());
+        assert_eq!(900, description.total_output());
+        assert_eq!(Some(100), description.fee());
+        assert_eq!(Some(address), description.inputs[0].address);
+        assert_eq!(ScriptType::NullData, description.outputs[0].script_type);
+        assert!(description.to_string().ends_with("fee: 100 satoshis"));
+
+        let description =
+            TransactionDescription::new(&transaction, None, Network::Testnet).unwrap();
+        assert_eq!(None, description.fee());
+        assert!(description.to_string().ends_with("fee: unknown"));
+    }
+}
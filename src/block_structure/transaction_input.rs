@@ -1,4 +1,7 @@
-use super::{hash::hash256d, outpoint::Outpoint, transaction::Transaction};
+use super::{
+    outpoint::Outpoint,
+    transaction::{Transaction, MAX_TRANSACTION_SIZE},
+};
 
 use crate::serialization::{
     deserializable_internal_order::DeserializableInternalOrder,
@@ -8,7 +11,11 @@ use crate::serialization::{
     serializable_little_endian::SerializableLittleEndian,
 };
 
-use crate::wallet_structure::{account::Account, error_wallet::ErrorWallet};
+use crate::wallet_structure::{
+    account::Account,
+    error_wallet::ErrorWallet,
+    sighash::{self, SIGHASH_ALL},
+};
 
 use crate::messages::compact_size::CompactSize;
 
@@ -17,9 +24,15 @@ use std::{
     io::{Read, Write},
 };
 
-const DEFAULT_SEQUENCE: u32 = 0xFFFFFFFF;
-const SIGHASH_ALL_MESSAGE: [u8; 4] = [0x01, 0x00, 0x00, 0x00];
-const SIGHASH_ALL_SIG_SCRIPT: u8 = 1;
+/// The sequence of an input that is final: it doesn't signal replacement and, if every input is
+/// final, the lock time of the transaction is ignored
+pub const SEQUENCE_FINAL: u32 = 0xFFFFFFFF;
+
+/// The sequence of an input that enforces the lock time of the transaction without signaling replacement
+pub const SEQUENCE_LOCK_TIME: u32 = 0xFFFFFFFE;
+
+/// The sequence of an input that signals the transaction can be replaced by one paying a higher fee (BIP125)
+pub const SEQUENCE_REPLACEABLE: u32 = 0xFFFFFFFD;
 
 /// It's the representation of a transaction input
 #[derive(Debug, Clone, PartialEq)]
@@ -42,42 +55,26 @@ impl TransactionInput {
         }
     }
 
-    /// It create the signature script from the given transaction
+    /// It create the signature script from the given transaction, signing with SIGHASH_ALL the legacy
+    /// digest of the whole transaction for the P2PKH output of the account spent by the input
     ///
     /// ### Error
     ///  * `ErrorWallet::CannotCreateNewTransaction`: It will appear when a transaction cannot be created
     pub fn create_signature_script(
         account: &Account,
-        unsigned_transaction: Transaction,
+        unsigned_transaction: &Transaction,
         input_index: usize,
     ) -> Result<Vec<u8>, ErrorWallet> {
-        let mut unsigned_transaction = unsigned_transaction;
-        unsigned_transaction.tx_in[input_index].signature_script =
-            account.address.generate_script_pubkey_p2pkh();
-
-        let mut message: Vec<u8> = Vec::new();
-        if let Err(e) = unsigned_transaction.io_serialize(&mut message) {
-            return Err(ErrorWallet::CannotCreateNewTransaction(format!(
-                "Error serializing the transaction to sign: {:?}",
-                e
-            )));
-        };
-
-        message.extend(SIGHASH_ALL_MESSAGE);
-
-        let hashed_message = match hash256d(&message) {
-            Ok(hashed_message) => hashed_message,
-            Err(e) => {
-                return Err(ErrorWallet::CannotCreateNewTransaction(format!(
-                    "Error hashing the transaction to sign: {:?}",
-                    e
-                )))
-            }
-        };
+        let hashed_message = sighash::legacy_sighash(
+            unsigned_transaction,
+            input_index,
+            account.address.generate_script_pubkey_p2pkh().as_script(),
+            SIGHASH_ALL,
+        )?;
 
         let mut signed_message = account.sign(&hashed_message)?;
 
-        signed_message.push(SIGHASH_ALL_SIG_SCRIPT);
+        signed_message.push(SIGHASH_ALL as u8);
 
         let mut final_script_signature = vec![];
         final_script_signature.push(signed_message.len() as u8);
@@ -89,12 +86,16 @@ impl TransactionInput {
         Ok(final_script_signature)
     }
 
-    /// It create a new transaction input from the given outpoint
-    pub fn from_outpoint_unsigned(outpoint: &Outpoint) -> TransactionInput {
+    /// It create a new transaction input from the given outpoint and sequence
+    pub fn from_outpoint_unsigned(outpoint: &Outpoint, sequence: u32) -> TransactionInput {
         let signature_script = vec![];
-        let sequence = DEFAULT_SEQUENCE;
         TransactionInput::new(outpoint.clone(), signature_script, sequence)
     }
+
+    /// Returns true if the input signals that its transaction can be replaced (BIP125)
+    pub fn signals_replacement(&self) -> bool {
+        self.sequence < SEQUENCE_LOCK_TIME
+    }
 }
 
 impl SerializableInternalOrder for TransactionInput {
@@ -114,6 +115,11 @@ impl DeserializableInternalOrder for TransactionInput {
     fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
         let previous_output = Outpoint::io_deserialize(stream)?;
         let length_sginature = CompactSize::le_deserialize(stream)?.value;
+        if length_sginature > MAX_TRANSACTION_SIZE {
+            return Err(ErrorSerialization::ErrorInDeserialization(format!(
+                "Signature script bigger than a transaction, we get: {length_sginature}"
+            )));
+        }
 
         let mut signature_script: Vec<u8> = Vec::new();
         for _ in 0..length_sginature {
@@ -134,6 +140,8 @@ impl DeserializableInternalOrder for TransactionInput {
 mod tests {
     use super::*;
 
+    use crate::connections::network::Network;
+
     #[test]
     fn test_01_correct_transaction_input_from_outpoint() {
         let previous_output = Outpoint::new(
@@ -145,12 +153,12 @@ mod tests {
             1,
         );
         let signature_script = vec![];
-        let sequence = DEFAULT_SEQUENCE;
+        let sequence = SEQUENCE_FINAL;
         let transaction_input =
             TransactionInput::new(previous_output.clone(), signature_script, sequence);
 
         let transaction_input_from_outpoint =
-            TransactionInput::from_outpoint_unsigned(&previous_output);
+            TransactionInput::from_outpoint_unsigned(&previous_output, SEQUENCE_FINAL);
 
         assert_eq!(transaction_input, transaction_input_from_outpoint);
     }
@@ -169,6 +177,7 @@ mod tests {
                 0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
                 0x35, 0x14, 0x92, 0x4A, 0x22,
             ],
+            Network::Testnet,
         )
         .unwrap();
 
@@ -187,7 +196,7 @@ mod tests {
         let transaction_to_sign =
             Transaction::io_deserialize(&mut transaction_to_sign_bytes).unwrap();
         let sig_script =
-            TransactionInput::create_signature_script(&account, transaction_to_sign, 0).unwrap();
+            TransactionInput::create_signature_script(&account, &transaction_to_sign, 0).unwrap();
         let actual_sig_script = vec![
             0x48, 0x30, 0x45, 0x02, 0x21, 0x00, 0xBB, 0xDB, 0xD2, 0x5E, 0x68, 0x06, 0xDC, 0x4F,
             0x82, 0x3F, 0xB9, 0x1B, 0x3C, 0xFB, 0xC2, 0xF6, 0xEC, 0xDE, 0x9D, 0x36, 0x67, 0x7C,
@@ -212,7 +221,7 @@ mod tests {
             1,
         );
         let signature_script = vec![1, 2, 3];
-        let sequence = DEFAULT_SEQUENCE;
+        let sequence = SEQUENCE_FINAL;
         let input = TransactionInput {
             previous_output,
             signature_script,
@@ -250,7 +259,7 @@ mod tests {
             1,
         );
         let signature_script = vec![1, 2, 3];
-        let sequence = DEFAULT_SEQUENCE;
+        let sequence = SEQUENCE_FINAL;
         let input = TransactionInput {
             previous_output,
             signature_script,
@@ -1,3 +1,5 @@
+use super::{script::ScriptBuf, transaction::MAX_TRANSACTION_SIZE};
+
 use crate::messages::compact_size::CompactSize;
 
 use crate::serialization::{
@@ -17,11 +19,11 @@ use std::{
 #[derive(Debug, Clone, PartialEq)]
 pub struct TransactionOutput {
     pub value: i64,
-    pub pk_script: Vec<u8>,
+    pub pk_script: ScriptBuf,
 }
 
 impl TransactionOutput {
-    pub fn new(value: i64, pk_script: Vec<u8>) -> TransactionOutput {
+    pub fn new(value: i64, pk_script: ScriptBuf) -> TransactionOutput {
         TransactionOutput { value, pk_script }
     }
 }
@@ -31,7 +33,7 @@ impl SerializableInternalOrder for TransactionOutput {
         self.value.le_serialize(stream)?;
 
         CompactSize::new(self.pk_script.len() as u64).le_serialize(stream)?;
-        self.pk_script.io_serialize(stream)?;
+        self.pk_script.as_bytes().io_serialize(stream)?;
 
         Ok(())
     }
@@ -41,13 +43,21 @@ impl DeserializableInternalOrder for TransactionOutput {
     fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
         let value = i64::le_deserialize(stream)?;
         let length_pk_script = CompactSize::le_deserialize(stream)?.value;
+        if length_pk_script > MAX_TRANSACTION_SIZE {
+            return Err(ErrorSerialization::ErrorInDeserialization(format!(
+                "Public key script bigger than a transaction, we get: {length_pk_script}"
+            )));
+        }
 
         let mut pk_script: Vec<u8> = Vec::new();
         for _ in 0..length_pk_script {
             pk_script.push(u8::le_deserialize(stream)?);
         }
 
-        Ok(TransactionOutput { value, pk_script })
+        Ok(TransactionOutput {
+            value,
+            pk_script: ScriptBuf::from(pk_script),
+        })
     }
 }
 
@@ -60,7 +70,7 @@ mod tests {
     fn test_01_correct_transaction_output_serialization() {
         let value: i64 = 10;
         let pk_script: Vec<u8> = vec![1, 2, 3, 4, 5, 6, 7, 8];
-        let transaction_output = TransactionOutput::new(value, pk_script.clone());
+        let transaction_output = TransactionOutput::new(value, ScriptBuf::from(pk_script.clone()));
 
         let mut fields_serialized: Vec<u8> = Vec::new();
         value.le_serialize(&mut fields_serialized).unwrap();
@@ -80,7 +90,8 @@ mod tests {
     #[test]
     fn test_02_correct_transaction_output_deserialization() {
         let transaction_output_bytes = [10, 0, 0, 0, 0, 0, 0, 0, 8, 1, 2, 3, 4, 5, 6, 7, 8];
-        let actual_transaction_output = TransactionOutput::new(10, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+        let actual_transaction_output =
+            TransactionOutput::new(10, ScriptBuf::from(vec![1, 2, 3, 4, 5, 6, 7, 8]));
         let deserialized_transaction_output =
             TransactionOutput::io_deserialize(&mut &transaction_output_bytes[..]).unwrap();
         assert_eq!(actual_transaction_output, deserialized_transaction_output);
@@ -1,21 +1,51 @@
 use super::{
-    block::Block, block_chain::BlockChain, hash::hash256d, outpoint::Outpoint,
-    transaction::Transaction, transaction_output::TransactionOutput,
+    block::Block,
+    block_chain::BlockChain,
+    block_undo::BlockUndo,
+    error_block::ErrorBlock,
+    hash::{hash256, hash256d, HashType},
+    outpoint::Outpoint,
+    script::Script,
+    transaction::Transaction,
+    transaction_output::TransactionOutput,
 };
 
 use crate::{
-    serialization::serializable_internal_order::SerializableInternalOrder,
-    wallet_structure::address::Address,
+    serialization::{
+        deserializable_internal_order::DeserializableInternalOrder,
+        deserializable_little_endian::DeserializableLittleEndian,
+        error_serialization::ErrorSerialization,
+        serializable_internal_order::SerializableInternalOrder,
+        serializable_little_endian::SerializableLittleEndian,
+        versioned_format::{from_unversioned, Migration, VersionedFormat},
+    },
+    wallet_structure::{address::Address, amount::Amount},
 };
 
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    io::{Read, Write},
+};
+
+/// The amount of the last blocks whose undo data is kept, being the deepest reorganization that can be reverted
+pub const MAX_UNDO_BLOCKS: usize = 100;
 
-const FROM_SATOSHIS_TO_TBTC: f64 = 100_000_000.0;
+/// It's the outputs spent by the inputs of a transaction, in the order of the inputs, with the sum
+/// of their values in satoshis
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedInputs {
+    pub spent: Vec<(Outpoint, TransactionOutput)>,
+    pub total_value: i64,
+}
 
+/// It's the unspent outputs of the block chain. Next to them, the outpoints are indexed by the hash
+/// of the script of their output, so the outputs of an address are found without going through all of them
 #[derive(Debug, Clone)]
 pub struct UTXOSet {
     utxo: HashMap<Outpoint, TransactionOutput>,
+    by_script: HashMap<HashType, HashSet<Outpoint>>,
     pending: Vec<Transaction>,
+    undo: VecDeque<BlockUndo>,
 }
 
 impl UTXOSet {
@@ -23,7 +53,9 @@ impl UTXOSet {
     pub fn new(blocks: Vec<Block>) -> UTXOSet {
         let mut utxo_set = UTXOSet {
             utxo: HashMap::new(),
+            by_script: HashMap::new(),
             pending: Vec::new(),
+            undo: VecDeque::new(),
         };
 
         blocks
@@ -51,31 +83,107 @@ impl UTXOSet {
         &self,
         possible_address: Option<&Address>,
     ) -> Vec<(Outpoint, TransactionOutput)> {
-        let mut utxo_without_pending = self.utxo.clone();
+        let spent_by_pending = self.get_spent_by_pending();
+
+        let outpoints: Vec<&Outpoint> = match possible_address {
+            Some(address) => {
+                let script_hash = get_script_hash(address.generate_script_pubkey().as_script());
+                match script_hash.and_then(|script_hash| self.by_script.get(&script_hash)) {
+                    Some(outpoints) => outpoints.iter().collect(),
+                    None => Vec::new(),
+                }
+            }
+            None => self.utxo.keys().collect(),
+        };
 
-        for transaction in self.pending.iter() {
-            for input in &transaction.tx_in {
-                utxo_without_pending.remove(&input.previous_output);
+        outpoints
+            .into_iter()
+            .filter(|outpoint| !spent_by_pending.contains(outpoint))
+            .filter_map(|outpoint| {
+                self.utxo
+                    .get(outpoint)
+                    .map(|output| (outpoint.clone(), output.clone()))
+            })
+            .collect()
+    }
+
+    /// Returns the outpoints spent by the pending transactions
+    fn get_spent_by_pending(&self) -> HashSet<&Outpoint> {
+        self.pending
+            .iter()
+            .flat_map(|transaction| transaction.tx_in.iter())
+            .map(|input| &input.previous_output)
+            .collect()
+    }
+
+    /// Returns the unspent output of the given outpoint, even if a pending transaction spends it
+    pub fn get(&self, outpoint: &Outpoint) -> Option<&TransactionOutput> {
+        self.utxo.get(outpoint)
+    }
+
+    /// Returns true if the output of the given outpoint is unspent and no pending transaction spends it
+    pub fn is_unspent(&self, outpoint: &Outpoint) -> bool {
+        self.utxo.contains_key(outpoint) && !self.get_spent_by_pending().contains(outpoint)
+    }
+
+    /// Returns the unspent outputs spent by the inputs of the transaction and their total value, even
+    /// if a pending transaction also spends them
+    ///
+    /// ### Error
+    ///  * `ErrorBlock::OutputNotAvailable`: It will appear when an input spends an output that is not unspent
+    pub fn resolve_inputs(&self, transaction: &Transaction) -> Result<ResolvedInputs, ErrorBlock> {
+        let mut resolved_inputs = ResolvedInputs {
+            spent: Vec::new(),
+            total_value: 0,
+        };
+
+        for input in transaction.tx_in.iter() {
+            match self.utxo.get(&input.previous_output) {
+                Some(output) => {
+                    resolved_inputs.total_value += output.value;
+                    resolved_inputs
+                        .spent
+                        .push((input.previous_output.clone(), output.clone()));
+                }
+                None => return Err(ErrorBlock::OutputNotAvailable),
             }
         }
 
-        utxo_without_pending
-            .iter()
-            .filter_map(|(outpoint, output)| {
-                if let Some(address) = possible_address {
-                    match address.verify_transaction_ownership(output) {
-                        true => Some((outpoint.clone(), output.clone())),
-                        false => None,
-                    }
-                } else {
-                    Some((outpoint.clone(), output.clone()))
+        Ok(resolved_inputs)
+    }
+
+    /// Adds the unspent output, indexing it by the hash of its script
+    fn insert_output(&mut self, outpoint: Outpoint, output: TransactionOutput) {
+        if let Some(script_hash) = get_script_hash(output.pk_script.as_script()) {
+            self.by_script
+                .entry(script_hash)
+                .or_default()
+                .insert(outpoint.clone());
+        }
+        self.utxo.insert(outpoint, output);
+    }
+
+    /// Removes the unspent output and its index, returning it if it was unspent
+    fn remove_output(&mut self, outpoint: &Outpoint) -> Option<TransactionOutput> {
+        let output = self.utxo.remove(outpoint)?;
+        if let Some(script_hash) = get_script_hash(output.pk_script.as_script()) {
+            if let Some(outpoints) = self.by_script.get_mut(&script_hash) {
+                outpoints.remove(outpoint);
+                if outpoints.is_empty() {
+                    self.by_script.remove(&script_hash);
                 }
-            })
-            .collect()
+            }
+        }
+
+        Some(output)
     }
 
-    /// Updates the UTXOSet with the transaction outputs of a new block
-    fn update_utxo_with_transaction_output(&mut self, transactions: &Vec<Transaction>) {
+    /// Updates the UTXOSet with the transaction outputs of a new block, returning the outpoints created
+    fn update_utxo_with_transaction_output(
+        &mut self,
+        transactions: &Vec<Transaction>,
+    ) -> Vec<Outpoint> {
+        let mut created: Vec<Outpoint> = Vec::new();
         for transaction in transactions {
             let mut serialized_transaction: Vec<u8> = Vec::new();
             match transaction.io_serialize(&mut serialized_transaction) {
@@ -89,25 +197,79 @@ impl UTXOSet {
 
             for (index_utxo, output) in transaction.tx_out.iter().enumerate() {
                 let outpoint = Outpoint::new(hashed_transaction, index_utxo as u32);
-                self.utxo.insert(outpoint, output.clone());
+                self.insert_output(outpoint.clone(), output.clone());
+                created.push(outpoint);
             }
         }
+
+        created
     }
 
-    /// Updates the UTXOSet with the transaction inputs of a new block
-    fn update_utxo_with_transaction_input(&mut self, transactions: &Vec<Transaction>) {
+    /// Updates the UTXOSet with the transaction inputs of a new block, returning the outputs spent
+    fn update_utxo_with_transaction_input(
+        &mut self,
+        transactions: &Vec<Transaction>,
+    ) -> Vec<(Outpoint, TransactionOutput)> {
+        let mut spent: Vec<(Outpoint, TransactionOutput)> = Vec::new();
         for transaction in transactions {
             for input in &transaction.tx_in {
-                self.utxo.remove(&input.previous_output);
+                if let Some(output) = self.remove_output(&input.previous_output) {
+                    spent.push((input.previous_output.clone(), output));
+                }
             }
         }
+
+        spent
     }
 
-    /// Updates de UTXOSet with the information of a block
+    /// Updates de UTXOSet with the information of a block, keeping its undo data so the block can
+    /// be disconnected later
     pub fn update_utxo_with_block(&mut self, block: &Block) {
-        self.update_utxo_with_transaction_output(&block.transactions);
-        self.update_utxo_with_transaction_input(&block.transactions);
+        let created = self.update_utxo_with_transaction_output(&block.transactions);
+        let spent = self.update_utxo_with_transaction_input(&block.transactions);
         self.pending.clear();
+
+        if let Ok(block_hash) = block.header.get_hash256d() {
+            self.undo.push_back(BlockUndo {
+                block_hash,
+                spent,
+                created,
+            });
+            while self.undo.len() > MAX_UNDO_BLOCKS {
+                self.undo.pop_front();
+            }
+        }
+    }
+
+    /// Reverts the changes of the last block the UTXOSet was updated with, restoring the outputs it
+    /// spent and removing the outputs it created. The blocks are disconnected from the last one
+    ///
+    /// ### Error
+    ///  * `ErrorBlock::CouldNotHash`: It will appear when the header of the block could not be hashed
+    ///  * `ErrorBlock::UndoDataNotFound`: It will appear when the block is not the last block with undo data
+    pub fn disconnect_block(&mut self, block: &Block) -> Result<(), ErrorBlock> {
+        let block_hash = match block.header.get_hash256d() {
+            Ok(block_hash) => block_hash,
+            Err(_) => return Err(ErrorBlock::CouldNotHash),
+        };
+
+        let block_undo = match self.undo.back() {
+            Some(block_undo) if block_undo.block_hash == block_hash => self.undo.pop_back(),
+            _ => None,
+        };
+        let block_undo = match block_undo {
+            Some(block_undo) => block_undo,
+            None => return Err(ErrorBlock::UndoDataNotFound),
+        };
+
+        for (outpoint, output) in block_undo.spent {
+            self.insert_output(outpoint, output);
+        }
+        for outpoint in block_undo.created.iter() {
+            self.remove_output(outpoint);
+        }
+
+        Ok(())
     }
 
     /// Add a new transaction to the pending transactions removing its influence in the balance
@@ -117,6 +279,94 @@ impl UTXOSet {
         }
     }
 
+    /// Checks that the transaction only spends unspent outputs not spent by a pending transaction,
+    /// and that its outputs are not worth more than its inputs. Returns the fee of the transaction
+    ///
+    /// ### Error
+    ///  * `ErrorBlock::OutputNotAvailable`: It will appear when an input spends an output that is not available
+    ///  * `ErrorBlock::OutputsExceedInputs`: It will appear when the outputs are worth more than the inputs
+    pub fn check_transaction(&self, transaction: &Transaction) -> Result<i64, ErrorBlock> {
+        let spent_by_pending = self.get_spent_by_pending();
+        if transaction
+            .tx_in
+            .iter()
+            .any(|input| spent_by_pending.contains(&input.previous_output))
+        {
+            return Err(ErrorBlock::OutputNotAvailable);
+        }
+
+        let total_input = self.resolve_inputs(transaction)?.total_value;
+
+        let total_output: i64 = transaction.tx_out.iter().map(|output| output.value).sum();
+        match total_input >= total_output {
+            true => Ok(total_input - total_output),
+            false => Err(ErrorBlock::OutputsExceedInputs),
+        }
+    }
+
+    /// Returns the ids of the transactions whose outputs are spent by the transaction but are unknown:
+    /// they are neither unspent outputs nor outputs of a pending transaction
+    pub fn get_missing_parents(&self, transaction: &Transaction) -> Vec<HashType> {
+        let pending_ids: Vec<HashType> = self
+            .pending
+            .iter()
+            .filter_map(|pending| pending.get_tx_id().ok())
+            .collect();
+
+        let mut missing_parents: Vec<HashType> = Vec::new();
+        for input in transaction.tx_in.iter() {
+            let parent = input.previous_output.get_transaction_id();
+            if self.utxo.contains_key(&input.previous_output)
+                || pending_ids.contains(&parent)
+                || missing_parents.contains(&parent)
+            {
+                continue;
+            }
+            missing_parents.push(parent);
+        }
+
+        missing_parents
+    }
+
+    /// Removes the pending transactions that spend an output also spent by a transaction of the block
+    /// they are not in, as the block makes them invalid. Returns each of them with the id of the
+    /// transaction of the block it conflicts with
+    pub fn remove_conflicting_transactions(
+        &mut self,
+        block: &Block,
+    ) -> Vec<(Transaction, HashType)> {
+        let mut spent_by_block: HashMap<&Outpoint, HashType> = HashMap::new();
+        for transaction in block.transactions.iter() {
+            let transaction_id = match transaction.get_tx_id() {
+                Ok(transaction_id) => transaction_id,
+                Err(_) => continue,
+            };
+            for input in transaction.tx_in.iter() {
+                spent_by_block.insert(&input.previous_output, transaction_id);
+            }
+        }
+
+        let mut conflicts: Vec<(Transaction, HashType)> = Vec::new();
+        let mut remaining: Vec<Transaction> = Vec::new();
+        for transaction in self.pending.iter() {
+            let conflicting_id = match block.transactions.contains(transaction) {
+                true => None,
+                false => transaction
+                    .tx_in
+                    .iter()
+                    .find_map(|input| spent_by_block.get(&input.previous_output)),
+            };
+
+            match conflicting_id {
+                Some(conflicting_id) => conflicts.push((transaction.clone(), *conflicting_id)),
+                None => remaining.push(transaction.clone()),
+            }
+        }
+
+        self.pending = remaining;
+        conflicts
+    }
+
     /// Return true if the transaction is pending
     pub fn is_transaction_pending(&self, transaction: &Transaction) -> bool {
         self.pending.contains(transaction)
@@ -136,9 +386,9 @@ impl UTXOSet {
         balance
     }
 
-    /// Returns the balance of the UTXOSet in TBTC.
-    pub fn get_balance_in_tbtc(&self, address: &Address) -> f64 {
-        self.get_balance_in_satoshis(address) as f64 / FROM_SATOSHIS_TO_TBTC
+    /// Returns the balance of the address, being the value of its unspent outputs
+    pub fn get_balance(&self, address: &Address) -> Amount {
+        Amount::from_sat(self.get_balance_in_satoshis(address))
     }
 
     pub fn get_pending_in_satoshis(&self, address: &Address) -> i64 {
@@ -153,11 +403,97 @@ impl UTXOSet {
         pending
     }
 
-    pub fn get_pending_in_tbtc(&self, address: &Address) -> f64 {
-        self.get_pending_in_satoshis(address) as f64 / FROM_SATOSHIS_TO_TBTC
+    /// Returns the value of the outputs to the address of the transactions that are not yet in a block
+    pub fn get_pending(&self, address: &Address) -> Amount {
+        Amount::from_sat(self.get_pending_in_satoshis(address))
+    }
+}
+
+impl SerializableInternalOrder for UTXOSet {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        (self.utxo.len() as u64).le_serialize(stream)?;
+        for (outpoint, output) in self.utxo.iter() {
+            outpoint.io_serialize(stream)?;
+            output.io_serialize(stream)?;
+        }
+
+        (self.pending.len() as u64).le_serialize(stream)?;
+        for transaction in self.pending.iter() {
+            transaction.io_serialize(stream)?;
+        }
+
+        (self.undo.len() as u64).le_serialize(stream)?;
+        for block_undo in self.undo.iter() {
+            block_undo.io_serialize(stream)?;
+        }
+
+        Ok(())
     }
 }
 
+impl DeserializableInternalOrder for UTXOSet {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        let mut utxo_set = UTXOSet::new(Vec::new());
+
+        let utxo_count = u64::le_deserialize(stream)?;
+        for _ in 0..utxo_count {
+            let outpoint = Outpoint::io_deserialize(stream)?;
+            utxo_set.insert_output(outpoint, TransactionOutput::io_deserialize(stream)?);
+        }
+
+        let pending_count = u64::le_deserialize(stream)?;
+        for _ in 0..pending_count {
+            utxo_set.pending.push(Transaction::io_deserialize(stream)?);
+        }
+
+        let undo_count = u64::le_deserialize(stream)?;
+        for _ in 0..undo_count {
+            utxo_set.undo.push_back(BlockUndo::io_deserialize(stream)?);
+        }
+
+        Ok(utxo_set)
+    }
+}
+
+/// Returns the hash of the script of an output, used to index the outputs of the same address
+fn get_script_hash(pk_script: Script) -> Option<HashType> {
+    hash256(pk_script.as_bytes()).ok()
+}
+
+/// It's the UTXO set with the hash of the tip of the block chain it was built with, to save it
+/// next to the block chain
+#[derive(Debug, Clone)]
+pub struct UTXOSnapshot {
+    pub tip: HashType,
+    pub utxo_set: UTXOSet,
+}
+
+impl SerializableInternalOrder for UTXOSnapshot {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        self.tip.io_serialize(stream)?;
+        self.utxo_set.io_serialize(stream)
+    }
+}
+
+impl DeserializableInternalOrder for UTXOSnapshot {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        Ok(UTXOSnapshot {
+            tip: HashType::io_deserialize(stream)?,
+            utxo_set: UTXOSet::io_deserialize(stream)?,
+        })
+    }
+}
+
+/// The migration of the UTXO sets saved before they had undo data, adding an empty list of undo data
+fn without_undo_data(mut content: Vec<u8>) -> Result<Vec<u8>, ErrorSerialization> {
+    content.extend_from_slice(&0u64.to_le_bytes());
+    Ok(content)
+}
+
+impl VersionedFormat for UTXOSnapshot {
+    const MIGRATIONS: &'static [Migration] = &[from_unversioned, without_undo_data];
+}
+
 #[cfg(test)]
 
 mod tests {
@@ -165,8 +501,8 @@ mod tests {
 
     use crate::block_structure::{
         block::Block, block_header::BlockHeader, block_version, compact256::Compact256,
-        outpoint::Outpoint, transaction::Transaction, transaction_input::TransactionInput,
-        transaction_output::TransactionOutput,
+        outpoint::Outpoint, script::ScriptBuf, transaction::Transaction,
+        transaction_input::TransactionInput, transaction_output::TransactionOutput,
     };
 
     use crate::messages::compact_size::CompactSize;
@@ -180,17 +516,17 @@ mod tests {
 
         let transaction_output = TransactionOutput {
             value: 10,
-            pk_script: vec![
+            pk_script: ScriptBuf::from(vec![
                 0x76, 0xa9, 0x14, 0x7a, 0xa8, 0x18, 0x46, 0x85, 0xca, 0x1f, 0x06, 0xf5, 0x43, 0xb6,
                 0x4a, 0x50, 0x2e, 0xb3, 0xb6, 0x13, 0x5d, 0x67, 0x20, 0x88, 0xac,
-            ],
+            ]),
         };
 
         Transaction {
             version: 1,
             tx_in: vec![transaction_input.clone()],
             tx_out: vec![transaction_output.clone()],
-            time,
+            lock_time: time,
         }
     }
 
@@ -246,25 +582,25 @@ mod tests {
 
         let transaction_output_1 = TransactionOutput {
             value: 10,
-            pk_script: vec![
+            pk_script: ScriptBuf::from(vec![
                 0x76, 0xa9, 0x14, 0x7a, 0xa8, 0x18, 0x46, 0x85, 0xca, 0x1f, 0x06, 0xf5, 0x43, 0xb6,
                 0x4a, 0x50, 0x2e, 0xb3, 0xb6, 0x13, 0x5d, 0x67, 0x20, 0x88, 0xac,
-            ],
+            ]),
         };
 
         let transaction_output_2 = TransactionOutput {
             value: 20,
-            pk_script: vec![
+            pk_script: ScriptBuf::from(vec![
                 0x76, 0xa9, 0x14, 0x7a, 0xa8, 0x18, 0x46, 0x85, 0xca, 0x1f, 0x06, 0xf5, 0x43, 0xb6,
                 0x4a, 0x50, 0x2e, 0xb3, 0xb6, 0x13, 0x5d, 0x67, 0x20, 0x88, 0xac,
-            ],
+            ]),
         };
 
         let transaction_output = Transaction {
             version: 1,
             tx_in: vec![],
             tx_out: vec![transaction_output_1.clone(), transaction_output_2.clone()],
-            time: 0,
+            lock_time: 0,
         };
 
         block_1
@@ -295,7 +631,7 @@ mod tests {
             version: 1,
             tx_in: vec![transaction_input_1.clone()],
             tx_out: vec![],
-            time: 0,
+            lock_time: 0,
         };
 
         let mut block_transaction_input = Block::new(BlockHeader::new(
@@ -328,8 +664,8 @@ mod tests {
         let utxo_set_blockchain = UTXOSet::from_blockchain(&blockchain);
         let address = Address::new(&"mrhW6tcF2LDetj3kJvaDTvatrVxNK64NXk".to_string()).unwrap();
         assert_eq!(
-            utxo_set_blockchain.get_balance_in_tbtc(&address),
-            (10.0 / FROM_SATOSHIS_TO_TBTC)
+            utxo_set_blockchain.get_balance(&address),
+            Amount::from_btc("0.0000001").unwrap()
         );
     }
 
@@ -345,17 +681,17 @@ mod tests {
 
         let transaction_output = TransactionOutput {
             value: 10,
-            pk_script: vec![
+            pk_script: ScriptBuf::from(vec![
                 0x76, 0xa9, 0x14, 0x7a, 0xa8, 0x18, 0x46, 0x85, 0xca, 0x1f, 0x06, 0xf5, 0x43, 0xb6,
                 0x4a, 0x50, 0x2e, 0xb3, 0xb6, 0x13, 0x5d, 0x67, 0x20, 0x88, 0xac,
-            ],
+            ]),
         };
 
         let transaction = Transaction {
             version: 1,
             tx_in: vec![transaction_input.clone()],
             tx_out: vec![transaction_output.clone()],
-            time: 0,
+            lock_time: 0,
         };
 
         block.append_transaction(transaction.clone()).unwrap();
@@ -377,29 +713,455 @@ mod tests {
 
         let transaction_output = TransactionOutput {
             value: 5,
-            pk_script: vec![
+            pk_script: ScriptBuf::from(vec![
                 0x76, 0xa9, 0x14, 0x7a, 0xa8, 0x18, 0x46, 0x85, 0xca, 0x1f, 0x06, 0xf5, 0x43, 0xb6,
                 0x4a, 0x50, 0x2e, 0xb3, 0xb6, 0x13, 0x5d, 0x67, 0x20, 0x88, 0xac,
-            ],
+            ]),
         };
 
         let new_transaction = Transaction {
             version: 1,
             tx_in: vec![transaction_input.clone()],
             tx_out: vec![transaction_output.clone()],
-            time: 0,
+            lock_time: 0,
         };
 
         utxo_set_blockchain.append_pending_transaction(new_transaction);
 
+        assert_eq!(utxo_set_blockchain.get_balance(&address), Amount::ZERO);
+
+        assert_eq!(
+            utxo_set_blockchain.get_pending(&address),
+            Amount::from_btc("0.00000005").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_06_transaction_is_checked_against_the_unspent_outputs() {
+        let mut block = create_block(1);
+        let funding_transaction = create_transaction(0);
+        block
+            .append_transaction(funding_transaction.clone())
+            .unwrap();
+
+        let blockchain = BlockChain::new(block).unwrap();
+        let mut utxo_set = UTXOSet::from_blockchain(&blockchain);
+        let outpoint = Outpoint::new(funding_transaction.get_tx_id().unwrap(), 0);
+
+        let spend = |value: i64, outpoint: Outpoint| Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(outpoint, vec![], 0xffffffff)],
+            tx_out: vec![TransactionOutput {
+                value,
+                pk_script: ScriptBuf::new(),
+            }],
+            lock_time: 0,
+        };
+
         assert_eq!(
-            utxo_set_blockchain.get_balance_in_tbtc(&address),
-            (0.0 / FROM_SATOSHIS_TO_TBTC)
+            3,
+            utxo_set
+                .check_transaction(&spend(7, outpoint.clone()))
+                .unwrap()
         );
+        assert!(matches!(
+            utxo_set.check_transaction(&spend(11, outpoint.clone())),
+            Err(ErrorBlock::OutputsExceedInputs)
+        ));
+        assert!(matches!(
+            utxo_set.check_transaction(&spend(1, Outpoint::new([2; 32], 0))),
+            Err(ErrorBlock::OutputNotAvailable)
+        ));
 
+        utxo_set.append_pending_transaction(spend(7, outpoint.clone()));
+        assert!(matches!(
+            utxo_set.check_transaction(&spend(5, outpoint)),
+            Err(ErrorBlock::OutputNotAvailable)
+        ));
+    }
+
+    #[test]
+    fn test_07_correct_utxo_set_serialization() {
+        let mut block = create_block(2);
+        block.append_transaction(create_transaction(0)).unwrap();
+        block.append_transaction(create_transaction(1)).unwrap();
+
+        let blockchain = BlockChain::new(block).unwrap();
+        let mut utxo_set = UTXOSet::from_blockchain(&blockchain);
+        utxo_set.append_pending_transaction(create_transaction(2));
+
+        let mut stream: Vec<u8> = Vec::new();
+        utxo_set.io_serialize(&mut stream).unwrap();
+        let deserialized_utxo_set = UTXOSet::io_deserialize(&mut stream.as_slice()).unwrap();
+
+        assert_eq!(utxo_set.utxo, deserialized_utxo_set.utxo);
+        assert_eq!(
+            utxo_set.pending_transactions(),
+            deserialized_utxo_set.pending_transactions()
+        );
+    }
+
+    #[test]
+    fn test_08_parents_of_a_transaction_not_known_are_missing() {
+        let mut block = create_block(1);
+        let funding_transaction = create_transaction(0);
+        block
+            .append_transaction(funding_transaction.clone())
+            .unwrap();
+
+        let blockchain = BlockChain::new(block).unwrap();
+        let mut utxo_set = UTXOSet::from_blockchain(&blockchain);
+
+        let spend = |outpoints: Vec<Outpoint>| Transaction {
+            version: 1,
+            tx_in: outpoints
+                .into_iter()
+                .map(|outpoint| TransactionInput::new(outpoint, vec![], 0xffffffff))
+                .collect(),
+            tx_out: vec![TransactionOutput {
+                value: 1,
+                pk_script: ScriptBuf::new(),
+            }],
+            lock_time: 0,
+        };
+
+        let confirmed = Outpoint::new(funding_transaction.get_tx_id().unwrap(), 0);
+        let pending_transaction = spend(vec![confirmed.clone()]);
+        let pending = Outpoint::new(pending_transaction.get_tx_id().unwrap(), 0);
+        let unknown = Outpoint::new([2; 32], 0);
+
+        let transaction = spend(vec![confirmed, pending.clone(), unknown.clone(), unknown]);
+        assert_eq!(
+            vec![pending.get_transaction_id(), [2; 32]],
+            utxo_set.get_missing_parents(&transaction)
+        );
+
+        utxo_set.append_pending_transaction(pending_transaction);
+        assert_eq!(vec![[2; 32]], utxo_set.get_missing_parents(&transaction));
+    }
+
+    #[test]
+    fn test_09_disconnecting_a_block_reverts_its_changes() {
+        let mut funding_block = create_block(1);
+        let funding_transaction = create_transaction(0);
+        funding_block
+            .append_transaction(funding_transaction.clone())
+            .unwrap();
+
+        let mut utxo_set = UTXOSet::new(vec![funding_block]);
+        let utxo_before = utxo_set.utxo.clone();
+
+        let funded = Outpoint::new(funding_transaction.get_tx_id().unwrap(), 0);
+        let spending_transaction = Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(funded, vec![], 0xffffffff)],
+            tx_out: vec![TransactionOutput {
+                value: 5,
+                pk_script: ScriptBuf::new(),
+            }],
+            lock_time: 0,
+        };
+        let spending_child = Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(
+                Outpoint::new(spending_transaction.get_tx_id().unwrap(), 0),
+                vec![],
+                0xffffffff,
+            )],
+            tx_out: vec![TransactionOutput {
+                value: 4,
+                pk_script: ScriptBuf::new(),
+            }],
+            lock_time: 0,
+        };
+
+        let mut spending_block = create_block(2);
+        spending_block.header.time = 1;
+        spending_block
+            .append_transaction(spending_transaction)
+            .unwrap();
+        spending_block.append_transaction(spending_child).unwrap();
+
+        utxo_set.update_utxo_with_block(&spending_block);
+        assert_ne!(utxo_before, utxo_set.utxo);
+
+        utxo_set.disconnect_block(&spending_block).unwrap();
+        assert_eq!(utxo_before, utxo_set.utxo);
+    }
+
+    #[test]
+    fn test_10_only_the_last_block_can_be_disconnected() {
+        let mut first_block = create_block(1);
+        first_block
+            .append_transaction(create_transaction(0))
+            .unwrap();
+        let mut second_block = create_block(1);
+        second_block.header.time = 1;
+        second_block
+            .append_transaction(create_transaction(1))
+            .unwrap();
+
+        let mut utxo_set = UTXOSet::new(vec![first_block.clone(), second_block.clone()]);
+
+        assert!(matches!(
+            utxo_set.disconnect_block(&first_block),
+            Err(ErrorBlock::UndoDataNotFound)
+        ));
+        assert!(utxo_set.disconnect_block(&second_block).is_ok());
+        assert!(utxo_set.disconnect_block(&first_block).is_ok());
+        assert!(utxo_set.utxo.is_empty());
+        assert!(matches!(
+            utxo_set.disconnect_block(&first_block),
+            Err(ErrorBlock::UndoDataNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_11_undo_data_is_kept_when_serialized() {
+        let mut block = create_block(1);
+        block.append_transaction(create_transaction(0)).unwrap();
+
+        let utxo_set = UTXOSet::new(vec![block.clone()]);
+
+        let mut stream: Vec<u8> = Vec::new();
+        utxo_set.io_serialize(&mut stream).unwrap();
+        let mut deserialized_utxo_set = UTXOSet::io_deserialize(&mut stream.as_slice()).unwrap();
+
+        assert_eq!(utxo_set.undo, deserialized_utxo_set.undo);
+        assert!(deserialized_utxo_set.disconnect_block(&block).is_ok());
+    }
+
+    #[test]
+    fn test_12_snapshot_saved_without_undo_data_is_migrated() {
+        let mut block = create_block(1);
+        block.append_transaction(create_transaction(0)).unwrap();
+        let utxo_set = UTXOSet::new(vec![block]);
+
+        let mut content: Vec<u8> = Vec::new();
+        [3; 32].io_serialize(&mut content).unwrap();
+        (utxo_set.utxo.len() as u64)
+            .le_serialize(&mut content)
+            .unwrap();
+        for (outpoint, output) in utxo_set.utxo.iter() {
+            outpoint.io_serialize(&mut content).unwrap();
+            output.io_serialize(&mut content).unwrap();
+        }
+        0u64.le_serialize(&mut content).unwrap();
+
+        let migrated = UTXOSnapshot::migrate(1, content).unwrap();
+        let snapshot = UTXOSnapshot::io_deserialize(&mut migrated.as_slice()).unwrap();
+
+        assert_eq!([3; 32], snapshot.tip);
+        assert_eq!(utxo_set.utxo, snapshot.utxo_set.utxo);
+        assert!(snapshot.utxo_set.undo.is_empty());
+    }
+
+    #[test]
+    fn test_13_outputs_of_an_address_are_indexed_by_their_script() {
+        let first_address = Address::new("mrhW6tcF2LDetj3kJvaDTvatrVxNK64NXk").unwrap();
+        let second_address = Address::new("mnQLoVaZ3w1NLVmUhfG8hh6WoG3iu7cnNw").unwrap();
+
+        let funding_transaction = Transaction {
+            version: 1,
+            tx_in: vec![],
+            tx_out: vec![
+                TransactionOutput {
+                    value: 10,
+                    pk_script: first_address.generate_script_pubkey_p2pkh(),
+                },
+                TransactionOutput {
+                    value: 20,
+                    pk_script: second_address.generate_script_pubkey_p2pkh(),
+                },
+                TransactionOutput {
+                    value: 30,
+                    pk_script: first_address.generate_script_pubkey_p2pkh(),
+                },
+            ],
+            lock_time: 0,
+        };
+        let mut funding_block = create_block(1);
+        funding_block
+            .append_transaction(funding_transaction.clone())
+            .unwrap();
+
+        let mut utxo_set = UTXOSet::new(vec![funding_block]);
+        assert_eq!(2, utxo_set.by_script.len());
+        assert_eq!(40, utxo_set.get_balance_in_satoshis(&first_address));
+        assert_eq!(20, utxo_set.get_balance_in_satoshis(&second_address));
+
+        let spending_transaction = Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(
+                Outpoint::new(funding_transaction.get_tx_id().unwrap(), 1),
+                vec![],
+                0xffffffff,
+            )],
+            tx_out: vec![TransactionOutput {
+                value: 15,
+                pk_script: ScriptBuf::new(),
+            }],
+            lock_time: 0,
+        };
+        let mut spending_block = create_block(1);
+        spending_block.header.time = 1;
+        spending_block
+            .append_transaction(spending_transaction)
+            .unwrap();
+
+        utxo_set.update_utxo_with_block(&spending_block);
+        assert_eq!(0, utxo_set.get_balance_in_satoshis(&second_address));
+        assert_eq!(
+            2,
+            utxo_set
+                .get_utxo_list_with_outpoints(Some(&first_address))
+                .len()
+        );
+
+        utxo_set.disconnect_block(&spending_block).unwrap();
+        assert_eq!(20, utxo_set.get_balance_in_satoshis(&second_address));
+        assert_eq!(2, utxo_set.by_script.len());
+    }
+
+    #[test]
+    fn test_14_index_is_rebuilt_when_deserialized() {
+        let mut block = create_block(1);
+        block.append_transaction(create_transaction(0)).unwrap();
+        let utxo_set = UTXOSet::new(vec![block]);
+
+        let mut stream: Vec<u8> = Vec::new();
+        utxo_set.io_serialize(&mut stream).unwrap();
+        let deserialized_utxo_set = UTXOSet::io_deserialize(&mut stream.as_slice()).unwrap();
+
+        let address = Address::new("mrhW6tcF2LDetj3kJvaDTvatrVxNK64NXk").unwrap();
+        assert_eq!(utxo_set.by_script, deserialized_utxo_set.by_script);
+        assert_eq!(10, deserialized_utxo_set.get_balance_in_satoshis(&address));
+    }
+
+    #[test]
+    fn test_15_spent_status_of_an_outpoint() {
+        let mut block = create_block(1);
+        let funding_transaction = create_transaction(0);
+        block
+            .append_transaction(funding_transaction.clone())
+            .unwrap();
+
+        let mut utxo_set = UTXOSet::new(vec![block]);
+        let outpoint = Outpoint::new(funding_transaction.get_tx_id().unwrap(), 0);
+        let unknown_outpoint = Outpoint::new([2; 32], 0);
+
+        assert!(utxo_set.is_unspent(&outpoint));
+        assert_eq!(
+            Some(&funding_transaction.tx_out[0]),
+            utxo_set.get(&outpoint)
+        );
+        assert!(!utxo_set.is_unspent(&unknown_outpoint));
+        assert_eq!(None, utxo_set.get(&unknown_outpoint));
+
+        utxo_set.append_pending_transaction(Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(outpoint.clone(), vec![], 0xffffffff)],
+            tx_out: vec![],
+            lock_time: 0,
+        });
+        assert!(!utxo_set.is_unspent(&outpoint));
+        assert!(utxo_set.get(&outpoint).is_some());
+    }
+
+    #[test]
+    fn test_16_inputs_of_a_transaction_are_resolved() {
+        let mut block = create_block(2);
+        let first_transaction = create_transaction(0);
+        let second_transaction = create_transaction(1);
+        block.append_transaction(first_transaction.clone()).unwrap();
+        block
+            .append_transaction(second_transaction.clone())
+            .unwrap();
+
+        let utxo_set = UTXOSet::new(vec![block]);
+        let first_outpoint = Outpoint::new(first_transaction.get_tx_id().unwrap(), 0);
+        let second_outpoint = Outpoint::new(second_transaction.get_tx_id().unwrap(), 0);
+
+        let spending = |outpoints: Vec<Outpoint>| Transaction {
+            version: 1,
+            tx_in: outpoints
+                .into_iter()
+                .map(|outpoint| TransactionInput::new(outpoint, vec![], 0xffffffff))
+                .collect(),
+            tx_out: vec![],
+            lock_time: 0,
+        };
+
+        let resolved_inputs = utxo_set
+            .resolve_inputs(&spending(vec![
+                second_outpoint.clone(),
+                first_outpoint.clone(),
+            ]))
+            .unwrap();
+        assert_eq!(
+            vec![
+                (
+                    second_outpoint.clone(),
+                    second_transaction.tx_out[0].clone()
+                ),
+                (first_outpoint, first_transaction.tx_out[0].clone()),
+            ],
+            resolved_inputs.spent
+        );
+        assert_eq!(
+            first_transaction.tx_out[0].value + second_transaction.tx_out[0].value,
+            resolved_inputs.total_value
+        );
+
+        assert!(matches!(
+            utxo_set.resolve_inputs(&spending(vec![second_outpoint, Outpoint::new([2; 32], 0)])),
+            Err(ErrorBlock::OutputNotAvailable)
+        ));
+    }
+
+    #[test]
+    fn test_17_pending_transactions_in_conflict_with_a_block_are_removed() {
+        let mut block = create_block(1);
+        let funding_transaction = create_transaction(0);
+        block
+            .append_transaction(funding_transaction.clone())
+            .unwrap();
+
+        let mut utxo_set = UTXOSet::new(vec![block]);
+        let outpoint = Outpoint::new(funding_transaction.get_tx_id().unwrap(), 0);
+
+        let spend = |lock_time: u32, outpoint: Outpoint| Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(outpoint, vec![], 0xffffffff)],
+            tx_out: vec![],
+            lock_time,
+        };
+        let pending_transaction = spend(1, outpoint.clone());
+        let confirmed_transaction = spend(2, Outpoint::new([2; 32], 0));
+        let conflicting_transaction = spend(3, outpoint);
+
+        utxo_set.append_pending_transaction(pending_transaction.clone());
+        utxo_set.append_pending_transaction(confirmed_transaction.clone());
+
+        let mut spending_block = create_block(2);
+        spending_block.header.time = 1;
+        spending_block
+            .append_transaction(confirmed_transaction.clone())
+            .unwrap();
+        spending_block
+            .append_transaction(conflicting_transaction.clone())
+            .unwrap();
+
+        assert_eq!(
+            vec![(
+                pending_transaction,
+                conflicting_transaction.get_tx_id().unwrap()
+            )],
+            utxo_set.remove_conflicting_transactions(&spending_block)
+        );
         assert_eq!(
-            utxo_set_blockchain.get_pending_in_tbtc(&address),
-            (5.0 / FROM_SATOSHIS_TO_TBTC)
+            &vec![confirmed_transaction],
+            utxo_set.pending_transactions()
         );
     }
 }
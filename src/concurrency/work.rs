@@ -6,6 +6,7 @@ use std::{
     convert::Into,
     io::{Read, Write},
     sync::mpsc::{Receiver, TryRecvError},
+    time::{Duration, Instant},
 };
 
 #[derive(Debug)]
@@ -21,17 +22,45 @@ impl<I> Work<I> {
         receiver: &Receiver<M>,
     ) -> Self {
         loop {
-            match MessageHeader::deserialize_header(stream) {
-                Ok(header) => return Work::Message(header),
-                Err(ErrorSerialization::InformationNotReady) => {}
-                _ => return Work::Stop,
+            if let Some(work) = Self::poll(stream, receiver) {
+                return work;
+            }
+        }
+    }
+
+    /// Listens like `listen`, but returns None if neither a message nor an information arrives before the timeout
+    pub fn listen_with_timeout<RW: Read + Write, M: Into<Work<I>>>(
+        stream: &mut RW,
+        receiver: &Receiver<M>,
+        timeout: Duration,
+    ) -> Option<Self> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(work) = Self::poll(stream, receiver) {
+                return Some(work);
             }
 
-            match receiver.try_recv() {
-                Ok(message) => return message.into(),
-                Err(TryRecvError::Disconnected) => return Work::Stop,
-                Err(_) => {}
+            if Instant::now() >= deadline {
+                return None;
             }
         }
     }
+
+    /// Tries once to read a message from the stream and then an information from the receiver
+    fn poll<RW: Read + Write, M: Into<Work<I>>>(
+        stream: &mut RW,
+        receiver: &Receiver<M>,
+    ) -> Option<Self> {
+        match MessageHeader::deserialize_header(stream) {
+            Ok(header) => return Some(Work::Message(header)),
+            Err(ErrorSerialization::InformationNotReady) => {}
+            _ => return Some(Work::Stop),
+        }
+
+        match receiver.try_recv() {
+            Ok(message) => Some(message.into()),
+            Err(TryRecvError::Disconnected) => Some(Work::Stop),
+            Err(_) => None,
+        }
+    }
 }
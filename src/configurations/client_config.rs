@@ -1,6 +1,7 @@
 use super::{
     error_configuration::ErrorConfiguration,
     parsable::{parse_structure, value_from_map, KeyValueMap, Parsable},
+    validation::{is_valid, KeyRule},
 };
 
 use std::{cmp::PartialEq, net::Ipv4Addr};
@@ -18,6 +19,14 @@ pub struct ClientConfig {
     pub address: Ipv4Addr,
 }
 
+impl ClientConfig {
+    /// It's the keys that can be given in the structure
+    pub const RULES: &'static [KeyRule] = &[
+        KeyRule::value(PORT, true, is_valid::<u16>),
+        KeyRule::value(ADDRESS, true, is_valid::<Ipv4Addr>),
+    ];
+}
+
 impl Parsable for ClientConfig {
     fn parse(name: &str, map: &KeyValueMap) -> Result<Self, ErrorConfiguration> {
         let structure = value_from_map(name.to_string(), map)?;
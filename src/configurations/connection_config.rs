@@ -1,22 +1,39 @@
 use super::{
     error_configuration::ErrorConfiguration,
     parsable::{parse_structure, value_from_map, KeyValueMap, Parsable},
+    proxy_config::ProxyConfig,
+    validation::{is_positive_duration, is_valid, is_valid_list, KeyRule},
 };
 
-use crate::connections::{ibd_methods::IBDMethod, p2p_protocol::ProtocolVersionP2P};
+use crate::block_structure::relay_policy::MIN_RELAY_FEE as DEFAULT_MIN_RELAY_FEE;
 
-use crate::messages::{bitfield_services::BitfieldServices, message_header::MagicType};
+use crate::connections::{
+    connection_timeouts::ConnectionTimeouts, ibd_methods::IBDMethod, network::Network,
+    p2p_protocol::ProtocolVersionP2P, supported_services::SupportedServices,
+};
+
+use crate::messages::bitfield_services::BitfieldServices;
 
-use std::cmp::PartialEq;
+use std::{cmp::PartialEq, time::Duration};
 
 const P2P_PROTOCOL_VERSION: &str = "p2p_protocol_version";
 const IBD_METHOD: &str = "ibd_method";
 const BLOCK_HEIGHT: &str = "block_height";
 const SERVICES: &str = "services";
-const MAGIC_NUMBERS: &str = "magic_numbers";
+const NETWORK: &str = "network";
 const NONCE: &str = "nonce";
 const USER_AGENT: &str = "user_agent";
 const RELAY: &str = "relay";
+const PROXY: &str = "proxy";
+const CONNECT_TIMEOUT: &str = "connect_timeout";
+const READ_TIMEOUT: &str = "read_timeout";
+const WRITE_TIMEOUT: &str = "write_timeout";
+const MIN_RELAY_FEE: &str = "min_relay_fee";
+const REPLACEABLE: &str = "replaceable";
+
+const DEFAULT_CONNECT_TIMEOUT_SECONDS: u64 = 10;
+const DEFAULT_READ_TIMEOUT_SECONDS: u64 = 60;
+const DEFAULT_WRITE_TIMEOUT_SECONDS: u64 = 30;
 
 /// It represents all the data needed to establish a connection
 #[derive(Debug, PartialEq, Clone)]
@@ -33,17 +50,80 @@ pub struct ConnectionConfig {
     /// It's the services that this node will offer
     pub services: BitfieldServices,
 
-    /// It's the magic numbers that will be used to identify the network
-    pub magic_numbers: MagicType,
+    /// It's the network the node connects to, which gives the magic numbers of the messages
+    pub network: Network,
 
     /// It's used to detect connections to self
     pub nonce: u64,
 
-    /// It's used in the version message
+    /// It's used in the version message. It can be a full user agent (BIP14) or a comment to add to the default one
     pub user_agent: String,
 
     /// It's the flag that indicates if the node will relay transactions
     pub relay: bool,
+
+    /// It's the SOCKS5 proxy used to connect to the peers, if there is one
+    pub proxy: Option<ProxyConfig>,
+
+    /// It's the most time waited to establish a connection, given in seconds
+    pub connect_timeout: Duration,
+
+    /// It's the most time waited without receiving anything from a peer, given in seconds
+    pub read_timeout: Duration,
+
+    /// It's the most time waited to send a message to a peer, given in seconds
+    pub write_timeout: Duration,
+
+    /// It's the least fee rate of the transactions accepted from the peers, given in satoshis per kilobyte
+    pub min_relay_fee: u64,
+
+    /// It's the flag that indicates if the transactions created by the wallet signal they can be
+    /// replaced by one paying a higher fee (BIP125)
+    pub replaceable: bool,
+}
+
+impl ConnectionConfig {
+    /// It's the keys that can be given in the structure
+    pub const RULES: &'static [KeyRule] = &[
+        KeyRule::value(P2P_PROTOCOL_VERSION, true, is_valid::<ProtocolVersionP2P>),
+        KeyRule::value(IBD_METHOD, true, is_valid::<IBDMethod>),
+        KeyRule::value(BLOCK_HEIGHT, true, is_valid::<i32>),
+        KeyRule::value(SERVICES, true, is_valid_list::<SupportedServices>),
+        KeyRule::value(NETWORK, true, is_valid::<Network>),
+        KeyRule::value(NONCE, true, is_valid::<u64>),
+        KeyRule::value(USER_AGENT, false, is_valid::<String>),
+        KeyRule::value(RELAY, true, is_valid::<bool>),
+        KeyRule::structure(PROXY, false, ProxyConfig::RULES),
+        KeyRule::value(CONNECT_TIMEOUT, false, is_positive_duration),
+        KeyRule::value(READ_TIMEOUT, false, is_positive_duration),
+        KeyRule::value(WRITE_TIMEOUT, false, is_positive_duration),
+        KeyRule::value(MIN_RELAY_FEE, false, is_valid::<u64>),
+        KeyRule::value(REPLACEABLE, false, is_valid::<bool>),
+    ];
+
+    pub fn timeouts(&self) -> ConnectionTimeouts {
+        ConnectionTimeouts {
+            connect: self.connect_timeout,
+            read: self.read_timeout,
+            write: self.write_timeout,
+        }
+    }
+}
+
+/// Parses a timeout given in seconds or with its unit, which must be positive. If it's not given,
+/// the default value is used
+fn parse_timeout(
+    name: &str,
+    map: &KeyValueMap,
+    default: u64,
+) -> Result<Duration, ErrorConfiguration> {
+    match Option::<Duration>::parse(name, map)? {
+        Some(timeout) if timeout.is_zero() => Err(ErrorConfiguration::ErrorCantParseValue(
+            format!("{name} must be positive"),
+        )),
+        Some(timeout) => Ok(timeout),
+        None => Ok(Duration::from_secs(default)),
+    }
 }
 
 impl Parsable for ConnectionConfig {
@@ -56,10 +136,17 @@ impl Parsable for ConnectionConfig {
             ibd_method: IBDMethod::parse(IBD_METHOD, &map)?,
             block_height: i32::parse(BLOCK_HEIGHT, &map)?,
             services: BitfieldServices::parse(SERVICES, &map)?,
-            magic_numbers: MagicType::parse(MAGIC_NUMBERS, &map)?,
+            network: Network::parse(NETWORK, &map)?,
             nonce: u64::parse(NONCE, &map)?,
             user_agent: Option::<String>::parse(USER_AGENT, &map)?.unwrap_or_default(),
             relay: bool::parse(RELAY, &map)?,
+            proxy: Option::<ProxyConfig>::parse(PROXY, &map)?,
+            connect_timeout: parse_timeout(CONNECT_TIMEOUT, &map, DEFAULT_CONNECT_TIMEOUT_SECONDS)?,
+            read_timeout: parse_timeout(READ_TIMEOUT, &map, DEFAULT_READ_TIMEOUT_SECONDS)?,
+            write_timeout: parse_timeout(WRITE_TIMEOUT, &map, DEFAULT_WRITE_TIMEOUT_SECONDS)?,
+            min_relay_fee: Option::<u64>::parse(MIN_RELAY_FEE, &map)?
+                .unwrap_or(DEFAULT_MIN_RELAY_FEE as u64),
+            replaceable: Option::<bool>::parse(REPLACEABLE, &map)?.unwrap_or(true),
         })
     }
 }
@@ -77,7 +164,7 @@ mod tests {
             ibd_method = HeaderFirst
             block_height = 0
             services = [Unname]
-            magic_numbers = [1, 2, 3, 4]
+            network = testnet
             nonce = 0
             user_agent = Tanto tiempo
             relay = true
@@ -95,10 +182,16 @@ mod tests {
             services: BitfieldServices {
                 elements: vec![SupportedServices::Unname],
             },
-            magic_numbers: [1, 2, 3, 4],
+            network: Network::Testnet,
             nonce: 0,
             user_agent: "Tanto tiempo".to_string(),
             relay: true,
+            proxy: None,
+            connect_timeout: Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECONDS),
+            read_timeout: Duration::from_secs(DEFAULT_READ_TIMEOUT_SECONDS),
+            write_timeout: Duration::from_secs(DEFAULT_WRITE_TIMEOUT_SECONDS),
+            min_relay_fee: DEFAULT_MIN_RELAY_FEE as u64,
+            replaceable: true,
         };
 
         assert_eq!(Ok(config_connection), connection_result);
@@ -111,7 +204,7 @@ mod tests {
             ibd_method=                                 HeaderFirst
             block_height = 0
             services = [Unname]
-            magic_numbers = [1, 2, 3, 4]
+            network = testnet
             nonce = 0
             user_agent = Tanto tiempo
             relay = true
@@ -129,10 +222,16 @@ mod tests {
             services: BitfieldServices {
                 elements: vec![SupportedServices::Unname],
             },
-            magic_numbers: [1, 2, 3, 4],
+            network: Network::Testnet,
             nonce: 0,
             user_agent: "Tanto tiempo".to_string(),
             relay: true,
+            proxy: None,
+            connect_timeout: Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECONDS),
+            read_timeout: Duration::from_secs(DEFAULT_READ_TIMEOUT_SECONDS),
+            write_timeout: Duration::from_secs(DEFAULT_WRITE_TIMEOUT_SECONDS),
+            min_relay_fee: DEFAULT_MIN_RELAY_FEE as u64,
+            replaceable: true,
         };
 
         assert_eq!(Ok(config_connection), connection_result);
@@ -144,7 +243,7 @@ mod tests {
             p2p_protocol_version = V70015
             block_height = 0
             services = [Unname]
-            magic_numbers = [1, 2, 3, 4]
+            network = testnet
             nonce = 0
             user_agent = Tanto tiempo
             relay = true
@@ -166,7 +265,7 @@ mod tests {
             ibd_method = HeaderFirst
             block_height = 0
             services = [Unname]
-            magic_numbers = [1, 2, 3, 4]
+            network = testnet
             nonce = 0
             user_agent = Tanto tiempo
             relay = true
@@ -184,10 +283,16 @@ mod tests {
             services: BitfieldServices {
                 elements: vec![SupportedServices::Unname],
             },
-            magic_numbers: [1, 2, 3, 4],
+            network: Network::Testnet,
             nonce: 0,
             user_agent: "Tanto tiempo".to_string(),
             relay: true,
+            proxy: None,
+            connect_timeout: Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECONDS),
+            read_timeout: Duration::from_secs(DEFAULT_READ_TIMEOUT_SECONDS),
+            write_timeout: Duration::from_secs(DEFAULT_WRITE_TIMEOUT_SECONDS),
+            min_relay_fee: DEFAULT_MIN_RELAY_FEE as u64,
+            replaceable: true,
         };
 
         assert_eq!(Ok(config_connection), connection_result);
@@ -204,4 +309,154 @@ mod tests {
 
         assert_eq!(Err(ErrorConfiguration::ValueNotFound), connection_result);
     }
+
+    #[test]
+    fn test06_accept_input_with_a_proxy() {
+        let configuration = "connection {
+            p2p_protocol_version = V70015
+            ibd_method = HeaderFirst
+            block_height = 0
+            services = [Unname]
+            network = testnet
+            nonce = 0
+            relay = true
+            proxy {
+                address = 127.0.0.1
+                port = 9050
+            }
+        }";
+
+        let name = "connection";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let connection_result = ConnectionConfig::parse(name, &map).unwrap();
+
+        let expected_proxy = ProxyConfig {
+            address: std::net::IpAddr::V4(std::net::Ipv4Addr::new(127, 0, 0, 1)),
+            port: 9050,
+            onion_peers: Vec::new(),
+        };
+
+        assert_eq!(Some(expected_proxy), connection_result.proxy);
+    }
+
+    #[test]
+    fn test07_accept_input_with_timeouts() {
+        let configuration = "connection {
+            p2p_protocol_version = V70015
+            ibd_method = HeaderFirst
+            block_height = 0
+            services = [Unname]
+            network = testnet
+            nonce = 0
+            relay = true
+            connect_timeout = 3
+            read_timeout = 20
+            write_timeout = 5
+        }";
+
+        let name = "connection";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let connection_result = ConnectionConfig::parse(name, &map).unwrap();
+
+        let expected_timeouts = ConnectionTimeouts {
+            connect: Duration::from_secs(3),
+            read: Duration::from_secs(20),
+            write: Duration::from_secs(5),
+        };
+
+        assert_eq!(expected_timeouts, connection_result.timeouts());
+    }
+
+    #[test]
+    fn test08_does_not_accept_a_timeout_of_zero() {
+        let configuration = "connection {
+            p2p_protocol_version = V70015
+            ibd_method = HeaderFirst
+            block_height = 0
+            services = [Unname]
+            network = testnet
+            nonce = 0
+            relay = true
+            read_timeout = 0
+        }";
+
+        let name = "connection";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let connection_result = ConnectionConfig::parse(name, &map);
+
+        assert!(connection_result.is_err());
+    }
+
+    #[test]
+    fn test09_accept_timeouts_with_their_unit() {
+        let configuration = "connection {
+            p2p_protocol_version = V70015
+            ibd_method = HeaderFirst
+            block_height = 0
+            services = [Unname]
+            network = testnet
+            nonce = 0
+            relay = true
+            connect_timeout = 1500ms
+            read_timeout = 2m
+        }";
+
+        let name = "connection";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let connection_result = ConnectionConfig::parse(name, &map).unwrap();
+
+        let expected_timeouts = ConnectionTimeouts {
+            connect: Duration::from_millis(1500),
+            read: Duration::from_secs(120),
+            write: Duration::from_secs(DEFAULT_WRITE_TIMEOUT_SECONDS),
+        };
+
+        assert_eq!(expected_timeouts, connection_result.timeouts());
+    }
+
+    #[test]
+    fn test10_accept_input_with_a_min_relay_fee() {
+        let configuration = "connection {
+            p2p_protocol_version = V70015
+            ibd_method = HeaderFirst
+            block_height = 0
+            services = [Unname]
+            network = testnet
+            nonce = 0
+            relay = true
+            min_relay_fee = 2000
+        }";
+
+        let name = "connection";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let connection_result = ConnectionConfig::parse(name, &map).unwrap();
+
+        assert_eq!(2000, connection_result.min_relay_fee);
+    }
+
+    #[test]
+    fn test11_accept_input_with_transactions_not_replaceable() {
+        let configuration = "connection {
+            p2p_protocol_version = V70015
+            ibd_method = HeaderFirst
+            block_height = 0
+            services = [Unname]
+            network = testnet
+            nonce = 0
+            relay = true
+            replaceable = false
+        }";
+
+        let name = "connection";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let connection_result = ConnectionConfig::parse(name, &map).unwrap();
+
+        assert!(!connection_result.replaceable);
+    }
 }
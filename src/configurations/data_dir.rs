@@ -0,0 +1,164 @@
+use super::error_configuration::ErrorConfiguration;
+
+use crate::connections::network::Network;
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// The name of the directory of the program inside the directory of the data of the platform
+const PROGRAM_DIRECTORY: &str = "cargosos";
+
+/// It's the directory where the saved files and the logs are kept. The files of each network are
+/// in their own directory inside it, except the ones of the mainnet that are at its root, so the
+/// block chains and wallets of different networks are never mixed
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataDir {
+    root: PathBuf,
+    network: Network,
+}
+
+impl DataDir {
+    /// Creates the data directory of the network in the given root, or in the directory of the
+    /// data of the platform if no root is given. If the platform has none, the current directory
+    /// is used
+    pub fn new(root: Option<&str>, network: Network) -> Self {
+        let root = match root {
+            Some(root) => PathBuf::from(root),
+            None => platform_data_directory(|variable| env::var(variable).ok())
+                .unwrap_or_else(|| PathBuf::from(".")),
+        };
+
+        DataDir { root, network }
+    }
+
+    /// Returns the directory where the files of the network are kept
+    pub fn get_network_directory(&self) -> PathBuf {
+        match self.network {
+            Network::Mainnet => self.root.clone(),
+            network => self.root.join(network.to_string()),
+        }
+    }
+
+    /// Returns the path of the file inside the directory of the network, creating the directories
+    /// it needs. The absolute paths are kept as they are
+    ///
+    /// ### Error
+    ///  * `ErrorConfiguration::CannotCreateDataDirectory`: It will appear when a directory cannot be created
+    pub fn resolve(&self, path: &str) -> Result<String, ErrorConfiguration> {
+        let path = self.get_network_directory().join(path);
+
+        if let Some(directory) = path.parent() {
+            create_directory(directory)?;
+        }
+
+        Ok(path.to_string_lossy().to_string())
+    }
+
+    /// Creates the directory of the network if it doesn't exist, which happens the first time the
+    /// program is run
+    ///
+    /// ### Error
+    ///  * `ErrorConfiguration::CannotCreateDataDirectory`: It will appear when the directory cannot be created
+    pub fn create(&self) -> Result<(), ErrorConfiguration> {
+        create_directory(&self.get_network_directory())
+    }
+}
+
+/// Creates the directory and its parents if they don't exist
+///
+/// ### Error
+///  * `ErrorConfiguration::CannotCreateDataDirectory`: It will appear when the directory cannot be created
+fn create_directory(directory: &Path) -> Result<(), ErrorConfiguration> {
+    match fs::create_dir_all(directory) {
+        Ok(()) => Ok(()),
+        Err(error) => Err(ErrorConfiguration::CannotCreateDataDirectory(format!(
+            "{}, error : {:?}",
+            directory.display(),
+            error
+        ))),
+    }
+}
+
+/// Returns the directory of the program inside the directory of the data of the platform, given
+/// the way to read the environment variables:
+///  * Windows: `%APPDATA%\cargosos`
+///  * macOS: `$HOME/Library/Application Support/cargosos`
+///  * Others: `$XDG_DATA_HOME/cargosos` or `$HOME/.local/share/cargosos`
+fn platform_data_directory<F: Fn(&str) -> Option<String>>(variable: F) -> Option<PathBuf> {
+    let data_directory = if cfg!(target_os = "windows") {
+        PathBuf::from(variable("APPDATA")?)
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from(variable("HOME")?).join("Library/Application Support")
+    } else {
+        match variable("XDG_DATA_HOME").filter(|directory| !directory.is_empty()) {
+            Some(directory) => PathBuf::from(directory),
+            None => PathBuf::from(variable("HOME")?).join(".local/share"),
+        }
+    };
+
+    Some(data_directory.join(PROGRAM_DIRECTORY))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_networks_have_their_own_directory() {
+        let mainnet = DataDir::new(Some("data"), Network::Mainnet);
+        let testnet = DataDir::new(Some("data"), Network::Testnet);
+        let regtest = DataDir::new(Some("data"), Network::Regtest);
+
+        assert_eq!(PathBuf::from("data"), mainnet.get_network_directory());
+        assert_eq!(
+            PathBuf::from("data").join("testnet"),
+            testnet.get_network_directory()
+        );
+        assert_eq!(
+            PathBuf::from("data").join("regtest"),
+            regtest.get_network_directory()
+        );
+    }
+
+    #[test]
+    fn test02_relative_paths_are_inside_the_network_directory() {
+        let root = env::temp_dir().join("cargosos_data_dir_test02");
+        let _ = fs::remove_dir_all(&root);
+        let data_dir = DataDir::new(root.to_str(), Network::Regtest);
+
+        let wallet = data_dir.resolve("wallets/personal.raw").unwrap();
+        let log = data_dir.resolve("/tmp/logs.txt").unwrap();
+
+        assert_eq!(
+            root.join("regtest").join("wallets/personal.raw"),
+            PathBuf::from(wallet)
+        );
+        assert!(root.join("regtest").join("wallets").is_dir());
+        assert_eq!("/tmp/logs.txt", log);
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn test03_default_directory_is_the_one_of_the_platform() {
+        let variables = |variable: &str| match variable {
+            "HOME" => Some("/home/ana".to_string()),
+            "XDG_DATA_HOME" => Some("".to_string()),
+            "APPDATA" => Some("C:\\Users\\ana\\AppData".to_string()),
+            _ => None,
+        };
+
+        let expected = if cfg!(target_os = "windows") {
+            PathBuf::from("C:\\Users\\ana\\AppData").join(PROGRAM_DIRECTORY)
+        } else if cfg!(target_os = "macos") {
+            PathBuf::from("/home/ana/Library/Application Support").join(PROGRAM_DIRECTORY)
+        } else {
+            PathBuf::from("/home/ana/.local/share").join(PROGRAM_DIRECTORY)
+        };
+
+        assert_eq!(Some(expected), platform_data_directory(variables));
+        assert_eq!(None, platform_data_directory(|_| None));
+    }
+}
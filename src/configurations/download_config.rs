@@ -1,15 +1,45 @@
 use super::{
     error_configuration::ErrorConfiguration,
     parsable::{parse_structure, value_from_map, KeyValueMap, Parsable},
+    validation::{is_positive, is_valid_timestamp, KeyRule},
 };
 
+use crate::node_structure::block_download::BLOCK_BATCH_SIZE as DEFAULT_BLOCK_BATCH_SIZE;
+
+use chrono::{offset::Utc, NaiveDate, NaiveDateTime};
+
 const TIMESTAMP: &str = "timestamp";
+const DOWNLOAD_FROM: &str = "download_from";
+const BLOCK_BATCH_SIZE: &str = "block_batch_size";
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const DATE_TIME_FORMATS: [&str; 2] = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S"];
+
+const HOURS_SUFFIX: char = 'h';
+const DAYS_SUFFIX: char = 'd';
+const WEEKS_SUFFIX: char = 'w';
+
+const SECONDS_IN_HOUR: i64 = 60 * 60;
+const SECONDS_IN_DAY: i64 = 24 * SECONDS_IN_HOUR;
+const SECONDS_IN_WEEK: i64 = 7 * SECONDS_IN_DAY;
 
 /// It represents all the data needed in the download process
 #[derive(Debug, PartialEq, Clone)]
 pub struct DownloadConfig {
     /// It's the time from where the block download will start
     pub timestamp: u32,
+
+    /// It's the amount of blocks asked to a peer in each request
+    pub block_batch_size: usize,
+}
+
+impl DownloadConfig {
+    /// It's the keys that can be given in the structure
+    pub const RULES: &'static [KeyRule] = &[
+        KeyRule::value(TIMESTAMP, false, is_valid_timestamp),
+        KeyRule::value(DOWNLOAD_FROM, false, is_valid_download_from),
+        KeyRule::value(BLOCK_BATCH_SIZE, false, is_positive),
+    ];
 }
 
 impl Parsable for DownloadConfig {
@@ -17,17 +47,88 @@ impl Parsable for DownloadConfig {
         let structure = value_from_map(name.to_string(), map)?;
         let map = parse_structure(structure)?;
 
+        let timestamp = match (
+            Option::<u32>::parse(TIMESTAMP, &map)?,
+            Option::<String>::parse(DOWNLOAD_FROM, &map)?,
+        ) {
+            (Some(timestamp), None) => timestamp,
+            (None, Some(download_from)) => {
+                match timestamp_from_date(&download_from, Utc::now().timestamp()) {
+                    Some(timestamp) => timestamp,
+                    None => {
+                        return Err(ErrorConfiguration::ErrorCantParseValue(format!(
+                            "download_from of {:?}",
+                            download_from
+                        )))
+                    }
+                }
+            }
+            (Some(_), Some(_)) => {
+                return Err(ErrorConfiguration::ErrorCantParseValue(format!(
+                    "only one of {TIMESTAMP} and {DOWNLOAD_FROM} can be given"
+                )))
+            }
+            (None, None) => return Err(ErrorConfiguration::ValueNotFound),
+        };
+
         Ok(DownloadConfig {
-            timestamp: u32::parse(TIMESTAMP, &map)?,
+            timestamp,
+            block_batch_size: Option::<usize>::parse(BLOCK_BATCH_SIZE, &map)?
+                .unwrap_or(DEFAULT_BLOCK_BATCH_SIZE),
         })
     }
 }
 
+/// Returns the timestamp of a date given by `2023-01-01` or `2023-01-01 12:30:00` (in UTC), or
+/// relative to the current time in hours, days or weeks, as in `30d`
+fn timestamp_from_date(value: &str, now: i64) -> Option<u32> {
+    let value = value.trim();
+
+    let relative = [
+        (HOURS_SUFFIX, SECONDS_IN_HOUR),
+        (DAYS_SUFFIX, SECONDS_IN_DAY),
+        (WEEKS_SUFFIX, SECONDS_IN_WEEK),
+    ]
+    .iter()
+    .find_map(|(suffix, unit)| {
+        let amount = value.strip_suffix(*suffix)?.parse::<u32>().ok()?;
+        Some(now - amount as i64 * unit)
+    });
+
+    let timestamp = match relative {
+        Some(timestamp) => timestamp,
+        None => match NaiveDate::parse_from_str(value, DATE_FORMAT) {
+            Ok(date) => date.and_hms_opt(0, 0, 0)?.and_utc().timestamp(),
+            Err(_) => DATE_TIME_FORMATS
+                .iter()
+                .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())?
+                .and_utc()
+                .timestamp(),
+        },
+    };
+
+    u32::try_from(timestamp).ok()
+}
+
+/// Checks that the value is a date or a relative time that gives a valid timestamp
+fn is_valid_download_from(value: &str) -> Result<(), String> {
+    match timestamp_from_date(value, Utc::now().timestamp()) {
+        Some(timestamp) => is_valid_timestamp(&timestamp.to_string()),
+        None => Err(format!(
+            "{:?} is not a date as 2023-01-01 or a relative time as 30d",
+            value
+        )),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const CONFIG_CONNECTION: DownloadConfig = DownloadConfig { timestamp: 0 };
+    const CONFIG_CONNECTION: DownloadConfig = DownloadConfig {
+        timestamp: 0,
+        block_batch_size: DEFAULT_BLOCK_BATCH_SIZE,
+    };
 
     #[test]
     fn test01_accept_valid_input() {
@@ -96,4 +197,84 @@ mod tests {
 
         assert_eq!(Err(ErrorConfiguration::ValueNotFound), connection_result);
     }
+
+    #[test]
+    fn test06_accept_a_date_instead_of_a_timestamp() {
+        let configuration = "download {
+            download_from = 2023-01-01
+        }";
+
+        let name = "download";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let download_result = DownloadConfig::parse(name, &map);
+
+        assert_eq!(
+            Ok(DownloadConfig {
+                timestamp: 1672531200,
+                block_batch_size: DEFAULT_BLOCK_BATCH_SIZE,
+            }),
+            download_result
+        );
+        assert_eq!(
+            Some(1672576200),
+            timestamp_from_date("2023-01-01 12:30:00", 0)
+        );
+    }
+
+    #[test]
+    fn test07_accept_a_time_relative_to_now() {
+        let now: i64 = 1685264160;
+
+        assert_eq!(
+            Some(1685264160 - 30 * 24 * 60 * 60),
+            timestamp_from_date("30d", now)
+        );
+        assert_eq!(
+            Some(1685264160 - 2 * 60 * 60),
+            timestamp_from_date("2h", now)
+        );
+        assert_eq!(
+            Some(1685264160 - 7 * 24 * 60 * 60),
+            timestamp_from_date("1w", now)
+        );
+        assert_eq!(None, timestamp_from_date("100000w", now));
+    }
+
+    #[test]
+    fn test08_does_not_accept_both_a_timestamp_and_a_date() {
+        let configuration = "download {
+            timestamp = 0
+            download_from = 2023-01-01
+        }";
+
+        let name = "download";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let download_result = DownloadConfig::parse(name, &map);
+
+        assert!(download_result.is_err());
+        assert!(is_valid_download_from("yesterday").is_err());
+    }
+
+    #[test]
+    fn test09_accept_the_size_of_the_block_batches() {
+        let configuration = "download {
+            timestamp = 0
+            block_batch_size = 16
+        }";
+
+        let name = "download";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let download_result = DownloadConfig::parse(name, &map);
+
+        assert_eq!(
+            Ok(DownloadConfig {
+                timestamp: 0,
+                block_batch_size: 16,
+            }),
+            download_result
+        );
+    }
 }
@@ -0,0 +1,206 @@
+use super::parsable::{parse_structure, Key, KeyValueMap, Value};
+
+/// It's the prefix of the environment variables that override the configuration
+pub const ENVIRONMENT_PREFIX: &str = "CARGOSOS";
+
+const END_LINE: char = '\n';
+const ASSIGNMENT: char = '=';
+const OPEN_GROUP: char = '{';
+const CLOSE_GROUP: char = '}';
+const NAME_SEPARATOR: char = '_';
+
+/// It's an environment variable, given by its name and its value
+pub type Variable = (String, String);
+
+/// Overrides the values of the configuration with the environment variables named
+/// `<prefix>_<STRUCTURE>_<KEY>`, for example `CARGOSOS_CONNECTION_PEER_COUNT_MAX=16`. The names
+/// are not case sensitive and the inner structures are given in the same way, as in
+/// `CARGOSOS_CONNECTION_DNS_SEEDER_PORT=18444`.
+///
+/// A variable that does not match a key of the structure adds that key to it, but the structures
+/// that are not in the configuration are not created
+pub fn override_structures(map: &mut KeyValueMap, prefix: &str, variables: &[Variable]) {
+    let variables = with_prefix(variables, prefix);
+
+    for (key, value) in map.iter_mut() {
+        *value = match is_structure(value) {
+            true => override_structure(value, &with_prefix(&variables, key)),
+            false => match find_variable(&variables, key) {
+                Some(variable_value) => variable_value,
+                None => continue,
+            },
+        };
+    }
+}
+
+/// Returns the structure with its values overridden by the variables, whose names are already
+/// given without the name of the structure
+fn override_structure(structure: &str, variables: &[Variable]) -> Value {
+    if variables.is_empty() {
+        return structure.to_string();
+    }
+
+    let map = match parse_structure(structure.to_string()) {
+        Ok(map) => map,
+        Err(_) => return structure.to_string(),
+    };
+
+    let mut keys: Vec<(&Key, &Value)> = map.iter().collect();
+    keys.sort();
+
+    let mut overridden = String::new();
+    for (key, value) in keys.iter() {
+        match is_structure(value) {
+            true => overridden.push_str(&format!(
+                "{key} {OPEN_GROUP}{END_LINE}{}{END_LINE}{CLOSE_GROUP}{END_LINE}",
+                override_structure(value, &with_prefix(variables, key))
+            )),
+            false => {
+                let value = find_variable(variables, key).unwrap_or(value.to_string());
+                overridden.push_str(&format!("{key} {ASSIGNMENT} {value}{END_LINE}"));
+            }
+        }
+    }
+
+    for (name, value) in variables {
+        let is_defined = keys.iter().any(|(key, value)| match is_structure(value) {
+            true => !with_prefix(&[(name.clone(), String::new())], key).is_empty(),
+            false => name.eq_ignore_ascii_case(key),
+        });
+
+        if !is_defined {
+            overridden.push_str(&format!(
+                "{} {ASSIGNMENT} {}{END_LINE}",
+                name.to_lowercase(),
+                value.trim()
+            ));
+        }
+    }
+
+    overridden
+}
+
+/// Returns the variables whose name starts with the given name, without it
+fn with_prefix(variables: &[Variable], name: &str) -> Vec<Variable> {
+    let prefix = format!("{}{NAME_SEPARATOR}", name.to_uppercase());
+    variables
+        .iter()
+        .filter_map(|(variable_name, value)| {
+            variable_name
+                .to_uppercase()
+                .strip_prefix(&prefix)
+                .map(|rest| (rest.to_string(), value.clone()))
+        })
+        .collect()
+}
+
+/// Returns the value of the last variable with the given name
+fn find_variable(variables: &[Variable], name: &str) -> Option<Value> {
+    variables
+        .iter()
+        .rev()
+        .find(|(variable_name, _)| variable_name.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.trim().to_string())
+}
+
+/// The values of the structures are the only ones with assignments
+fn is_structure(value: &str) -> bool {
+    value.contains(ASSIGNMENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::configurations::{
+        download_config::DownloadConfig, parsable::Parsable, save_config::SaveConfig,
+    };
+    use crate::node_structure::block_download::BLOCK_BATCH_SIZE;
+
+    fn variable(name: &str, value: &str) -> Variable {
+        (name.to_string(), value.to_string())
+    }
+
+    #[test]
+    fn test01_variables_override_the_values_of_the_structures() {
+        let configuration = "Download {
+            timestamp = 0
+        }
+        Save {
+            read_wallet = wallet.raw
+        }";
+        let mut map = parse_structure(configuration.to_string()).unwrap();
+
+        override_structures(
+            &mut map,
+            ENVIRONMENT_PREFIX,
+            &[
+                variable("CARGOSOS_DOWNLOAD_TIMESTAMP", "1685264160"),
+                variable("CARGOSOS_SAVE_WRITE_WALLET", "other_wallet.raw"),
+                variable("PATH", "/usr/bin"),
+            ],
+        );
+
+        assert_eq!(
+            DownloadConfig::parse("Download", &map),
+            Ok(DownloadConfig {
+                timestamp: 1685264160,
+                block_batch_size: BLOCK_BATCH_SIZE,
+            })
+        );
+        let save_config = SaveConfig::parse("Save", &map).unwrap();
+        assert_eq!(save_config.read_wallet, Some("wallet.raw".to_string()));
+        assert_eq!(
+            save_config.write_wallet,
+            Some("other_wallet.raw".to_string())
+        );
+    }
+
+    #[test]
+    fn test02_variables_override_the_inner_structures() {
+        let configuration = "Connection {
+            peer_count_max = 5
+            dns_seeder {
+                seed = seed.testnet.bitcoin.sprovoost.nl
+                port = 18333
+            }
+        }";
+        let mut map = parse_structure(configuration.to_string()).unwrap();
+
+        override_structures(
+            &mut map,
+            ENVIRONMENT_PREFIX,
+            &[
+                variable("CARGOSOS_CONNECTION_PEER_COUNT_MAX", "16"),
+                variable("cargosos_connection_dns_seeder_port", "18444"),
+            ],
+        );
+
+        let connection = parse_structure(map["Connection"].clone()).unwrap();
+        assert_eq!(usize::parse("peer_count_max", &connection), Ok(16));
+
+        let dns_seeder = parse_structure(connection["dns_seeder"].clone()).unwrap();
+        assert_eq!(u16::parse("port", &dns_seeder), Ok(18444));
+        assert_eq!(
+            String::parse("seed", &dns_seeder),
+            Ok("seed.testnet.bitcoin.sprovoost.nl".to_string())
+        );
+    }
+
+    #[test]
+    fn test03_structures_without_variables_are_not_changed() {
+        let configuration = "Download {
+            timestamp = 0
+        }";
+        let mut map = parse_structure(configuration.to_string()).unwrap();
+        let expected = map.clone();
+
+        override_structures(
+            &mut map,
+            ENVIRONMENT_PREFIX,
+            &[variable("OTHER_DOWNLOAD_TIMESTAMP", "1")],
+        );
+
+        assert_eq!(expected, map);
+    }
+}
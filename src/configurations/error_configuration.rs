@@ -1,3 +1,5 @@
+use super::validation::Diagnostic;
+
 /// It represents all the possible error that can appear in the parsing process
 #[derive(Debug, std::cmp::PartialEq)]
 pub enum ErrorConfiguration {
@@ -21,4 +23,10 @@ pub enum ErrorConfiguration {
 
     /// It will appear when there isn't a structure with a given property name
     ErrorConfigurationNotFound,
+
+    /// It will appear when the validation of the configuration finds problems, given by every one of them
+    ErrorInvalidConfiguration(Vec<Diagnostic>),
+
+    /// It will appear when the data directory, or a directory inside it, cannot be created
+    CannotCreateDataDirectory(String),
 }
@@ -0,0 +1,103 @@
+use super::{
+    error_configuration::ErrorConfiguration,
+    parsable::{parse_structure, value_from_map, KeyValueMap, Parsable},
+    validation::{is_positive_duration, is_valid, KeyRule},
+};
+
+use std::{cmp::PartialEq, time::Duration};
+
+const URL: &str = "url";
+const CURRENCY: &str = "currency";
+const POLL_INTERVAL: &str = "poll_interval";
+
+const DEFAULT_CURRENCY: &str = "USD";
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// It represents all the data needed to ask for the price of a bitcoin in a fiat currency
+#[derive(Debug, PartialEq, Clone)]
+pub struct ExchangeRateConfig {
+    /// It's the url of the provider of the exchange rate
+    pub url: String,
+
+    /// It's the code of the fiat currency, as `USD` or `EUR`
+    pub currency: String,
+
+    /// It's the time to wait between the requests to the provider
+    pub poll_interval: Duration,
+}
+
+impl ExchangeRateConfig {
+    /// It's the keys that can be given in the structure
+    pub const RULES: &'static [KeyRule] = &[
+        KeyRule::value(URL, true, is_valid::<String>),
+        KeyRule::value(CURRENCY, false, is_valid::<String>),
+        KeyRule::value(POLL_INTERVAL, false, is_positive_duration),
+    ];
+}
+
+impl Parsable for ExchangeRateConfig {
+    fn parse(name: &str, map: &KeyValueMap) -> Result<Self, ErrorConfiguration> {
+        let structure = value_from_map(name.to_string(), map)?;
+        let map = parse_structure(structure)?;
+
+        Ok(ExchangeRateConfig {
+            url: String::parse(URL, &map)?,
+            currency: Option::<String>::parse(CURRENCY, &map)?
+                .unwrap_or(DEFAULT_CURRENCY.to_string())
+                .to_uppercase(),
+            poll_interval: Option::<Duration>::parse(POLL_INTERVAL, &map)?
+                .unwrap_or(DEFAULT_POLL_INTERVAL),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_accept_valid_input() {
+        let configuration = "ExchangeRate {
+            url = http://rates.test:8080/price
+            currency = eur
+            poll_interval = 1m
+        }";
+        let name = "ExchangeRate";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let exchange_rate_result = ExchangeRateConfig::parse(name, &map);
+
+        let exchange_rate_config = ExchangeRateConfig {
+            url: "http://rates.test:8080/price".to_string(),
+            currency: "EUR".to_string(),
+            poll_interval: Duration::from_secs(60),
+        };
+
+        assert_eq!(Ok(exchange_rate_config), exchange_rate_result);
+    }
+
+    #[test]
+    fn test02_currency_and_poll_interval_have_default_values() {
+        let configuration = "ExchangeRate {
+            url = http://rates.test/price
+        }";
+        let name = "ExchangeRate";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let exchange_rate_config = ExchangeRateConfig::parse(name, &map).unwrap();
+
+        assert_eq!(DEFAULT_CURRENCY, exchange_rate_config.currency);
+        assert_eq!(DEFAULT_POLL_INTERVAL, exchange_rate_config.poll_interval);
+    }
+
+    #[test]
+    fn test03_exchange_rate_is_optional() {
+        let configuration = "";
+        let name = "ExchangeRate";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let exchange_rate_result = Option::<ExchangeRateConfig>::parse(name, &map);
+
+        assert_eq!(Ok(None), exchange_rate_result);
+    }
+}
@@ -0,0 +1,77 @@
+use super::{
+    error_configuration::ErrorConfiguration,
+    parsable::{parse_structure, value_from_map, KeyValueMap, Parsable},
+    validation::{is_valid, KeyRule},
+};
+
+use std::cmp::PartialEq;
+
+const URL: &str = "url";
+
+/// It represents all the data needed to request coins from a testnet faucet
+#[derive(Debug, PartialEq, Clone)]
+pub struct FaucetConfig {
+    /// It's the url of the faucet to request the coins
+    pub url: String,
+}
+
+impl FaucetConfig {
+    /// It's the keys that can be given in the structure
+    pub const RULES: &'static [KeyRule] = &[KeyRule::value(URL, true, is_valid::<String>)];
+}
+
+impl Parsable for FaucetConfig {
+    fn parse(name: &str, map: &KeyValueMap) -> Result<Self, ErrorConfiguration> {
+        let structure = value_from_map(name.to_string(), map)?;
+        let map = parse_structure(structure)?;
+
+        Ok(FaucetConfig {
+            url: String::parse(URL, &map)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_accept_valid_input() {
+        let configuration = "Faucet {
+            url = http://faucet.test:8080/send
+        }";
+        let name = "Faucet";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let faucet_result = FaucetConfig::parse(name, &map);
+
+        let faucet_config = FaucetConfig {
+            url: "http://faucet.test:8080/send".to_string(),
+        };
+
+        assert_eq!(Ok(faucet_config), faucet_result);
+    }
+
+    #[test]
+    fn test02_does_not_accept_input_with_missing_values() {
+        let configuration = "Faucet {
+        }";
+        let name = "Faucet";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let faucet_result = FaucetConfig::parse(name, &map);
+
+        assert_eq!(Err(ErrorConfiguration::ValueNotFound), faucet_result);
+    }
+
+    #[test]
+    fn test03_faucet_is_optional() {
+        let configuration = "";
+        let name = "Faucet";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let faucet_result = Option::<FaucetConfig>::parse(name, &map);
+
+        assert_eq!(Ok(None), faucet_result);
+    }
+}
@@ -13,6 +13,9 @@ pub enum Interface {
 
     /// To use the terminal interface
     Tui,
+
+    /// To run the node without interface, serving the observers connected to the local RPC port
+    Daemon,
 }
 
 ///Implementación del trait que permite hacer parse
@@ -23,6 +26,7 @@ impl FromStr for Interface {
         match s {
             "GUI" => Ok(Interface::Gui),
             "TUI" => Ok(Interface::Tui),
+            "DAEMON" => Ok(Interface::Daemon),
             _ => Err(ErrorConfiguration::ErrorCantParseValue(format!(
                 "interface of {:?}",
                 s
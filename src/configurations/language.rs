@@ -0,0 +1,60 @@
+use super::{
+    error_configuration::ErrorConfiguration,
+    parsable::{value_from_map, KeyValueMap, Parsable},
+};
+
+use std::{cmp::PartialEq, fmt::Display, str::FromStr};
+
+/// It represents the language of the texts shown by the interfaces
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    English,
+
+    Spanish,
+}
+
+impl FromStr for Language {
+    type Err = ErrorConfiguration;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "english" | "en" => Ok(Language::English),
+            "spanish" | "es" | "español" => Ok(Language::Spanish),
+            _ => Err(ErrorConfiguration::ErrorCantParseValue(format!(
+                "language of {:?}",
+                value
+            ))),
+        }
+    }
+}
+
+impl Parsable for Language {
+    fn parse(name: &str, map: &KeyValueMap) -> Result<Self, ErrorConfiguration> {
+        let value = value_from_map(name.to_string(), map)?;
+        value.parse::<Language>()
+    }
+}
+
+impl Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Language::English => write!(f, "english"),
+            Language::Spanish => write!(f, "spanish"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_language_is_read_by_its_name_or_its_code() {
+        assert_eq!(Ok(Language::English), "English".parse::<Language>());
+        assert_eq!(Ok(Language::English), "en".parse::<Language>());
+        assert_eq!(Ok(Language::Spanish), "SPANISH".parse::<Language>());
+        assert_eq!(Ok(Language::Spanish), "es".parse::<Language>());
+        assert!("klingon".parse::<Language>().is_err());
+    }
+}
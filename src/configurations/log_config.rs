@@ -1,12 +1,39 @@
 use super::{
     error_configuration::ErrorConfiguration,
+    log_rotation_config::LogRotationConfig,
     parsable::{parse_structure, value_from_map, KeyValueMap, Parsable},
+    validation::{is_valid, is_valid_list, KeyRule},
+};
+
+use crate::logs::{
+    level::Level,
+    log_format::LogFormat,
+    severity::{Severity, SeverityFilter},
 };
 
 use std::cmp::PartialEq;
 
 const FILEPATH_LOG: &str = "filepath_log";
 const SHOW_CONSOLE: &str = "show_console";
+const LEVELS: &str = "levels";
+const SEVERITIES: &str = "severities";
+const ROTATION: &str = "rotation";
+const FORMAT: &str = "format";
+
+/// It's the keys of the severities, one for each level and the default one
+const SEVERITY_RULES: &[KeyRule] = &[
+    KeyRule::value("default", false, is_valid::<Severity>),
+    KeyRule::value("node", false, is_valid::<Severity>),
+    KeyRule::value("wallet", false, is_valid::<Severity>),
+    KeyRule::value("transaction", false, is_valid::<Severity>),
+    KeyRule::value("configuration", false, is_valid::<Severity>),
+    KeyRule::value("connection", false, is_valid::<Severity>),
+    KeyRule::value("file", false, is_valid::<Severity>),
+    KeyRule::value("interface", false, is_valid::<Severity>),
+    KeyRule::value("notification", false, is_valid::<Severity>),
+    KeyRule::value("broadcasting", false, is_valid::<Severity>),
+    KeyRule::value("error", false, is_valid::<Severity>),
+];
 
 /// Configuration for the logs process
 #[derive(Debug, PartialEq, Clone)]
@@ -16,6 +43,30 @@ pub struct LogConfig {
 
     /// If the logs message are shown in the terminal
     pub show_console: bool,
+
+    /// The levels of the messages that are registered. If not given, all of them are registered
+    pub levels: Option<Vec<Level>>,
+
+    /// The least important severity registered for each level. If not given, the messages up to `INFO` are registered
+    pub severities: SeverityFilter,
+
+    /// How the log file is rotated when it grows. If not given, the file is emptied on every execution
+    pub rotation: Option<LogRotationConfig>,
+
+    /// How each message is written. If not given, they are written as text
+    pub format: LogFormat,
+}
+
+impl LogConfig {
+    /// It's the keys that can be given in the structure
+    pub const RULES: &'static [KeyRule] = &[
+        KeyRule::value(FILEPATH_LOG, true, is_valid::<String>),
+        KeyRule::value(SHOW_CONSOLE, true, is_valid::<bool>),
+        KeyRule::value(LEVELS, false, is_valid_list::<Level>),
+        KeyRule::structure(SEVERITIES, false, SEVERITY_RULES),
+        KeyRule::structure(ROTATION, false, LogRotationConfig::RULES),
+        KeyRule::value(FORMAT, false, is_valid::<LogFormat>),
+    ];
 }
 
 impl Parsable for LogConfig {
@@ -26,6 +77,10 @@ impl Parsable for LogConfig {
         Ok(LogConfig {
             filepath_log: String::parse(FILEPATH_LOG, &map)?,
             show_console: bool::parse(SHOW_CONSOLE, &map)?,
+            levels: Option::<Vec<Level>>::parse(LEVELS, &map)?,
+            severities: Option::<SeverityFilter>::parse(SEVERITIES, &map)?.unwrap_or_default(),
+            rotation: Option::<LogRotationConfig>::parse(ROTATION, &map)?,
+            format: Option::<LogFormat>::parse(FORMAT, &map)?.unwrap_or_default(),
         })
     }
 }
@@ -48,6 +103,10 @@ mod tests {
         let config_log = LogConfig {
             filepath_log: "log_test.txt".to_string(),
             show_console: true,
+            levels: None,
+            severities: SeverityFilter::default(),
+            rotation: None,
+            format: LogFormat::Text,
         };
 
         assert_eq!(Ok(config_log), log_result);
@@ -67,6 +126,10 @@ mod tests {
         let config_log = LogConfig {
             filepath_log: "log_test.txt".to_string(),
             show_console: true,
+            levels: None,
+            severities: SeverityFilter::default(),
+            rotation: None,
+            format: LogFormat::Text,
         };
 
         assert_eq!(Ok(config_log), log_result);
@@ -100,6 +163,10 @@ mod tests {
         let config_log = LogConfig {
             filepath_log: "log_test.txt".to_string(),
             show_console: true,
+            levels: None,
+            severities: SeverityFilter::default(),
+            rotation: None,
+            format: LogFormat::Text,
         };
 
         assert_eq!(Ok(config_log), log_result);
@@ -115,4 +182,91 @@ mod tests {
 
         assert_eq!(Err(ErrorConfiguration::ValueNotFound), log_result);
     }
+
+    #[test]
+    fn test06_accept_input_with_levels() {
+        let configuration = "logs {
+            filepath_log = log_test.txt
+            show_console = false
+            levels = [NODE, connection, unknown]
+        }";
+        let name = "logs";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let log_result = LogConfig::parse(name, &map);
+
+        let config_log = LogConfig {
+            filepath_log: "log_test.txt".to_string(),
+            show_console: false,
+            levels: Some(vec![Level::NODE, Level::CONNECTION]),
+            severities: SeverityFilter::default(),
+            rotation: None,
+            format: LogFormat::Text,
+        };
+
+        assert_eq!(Ok(config_log), log_result);
+    }
+
+    #[test]
+    fn test07_accept_input_with_severities() {
+        let configuration = "logs {
+            filepath_log = log_test.txt
+            show_console = false
+            severities {
+                default = warn
+                connection = debug
+            }
+        }";
+        let name = "logs";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let log_result = LogConfig::parse(name, &map).unwrap();
+
+        let expected_severities = SeverityFilter {
+            default: Severity::WARN,
+            levels: std::collections::HashMap::from([(Level::CONNECTION, Severity::DEBUG)]),
+        };
+
+        assert_eq!(expected_severities, log_result.severities);
+    }
+
+    #[test]
+    fn test08_accept_input_with_rotation() {
+        let configuration = "logs {
+            filepath_log = log_test.txt
+            show_console = false
+            rotation {
+                max_size = 1MB
+                archives = 2
+                compress = true
+            }
+        }";
+        let name = "logs";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let log_result = LogConfig::parse(name, &map).unwrap();
+
+        let expected_rotation = LogRotationConfig {
+            max_size: 1024 * 1024,
+            archives: 2,
+            compress: true,
+        };
+
+        assert_eq!(Some(expected_rotation), log_result.rotation);
+    }
+
+    #[test]
+    fn test09_accept_input_with_json_format() {
+        let configuration = "logs {
+            filepath_log = log_test.json
+            show_console = false
+            format = json
+        }";
+        let name = "logs";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let log_result = LogConfig::parse(name, &map).unwrap();
+
+        assert_eq!(LogFormat::Json, log_result.format);
+    }
 }
@@ -0,0 +1,147 @@
+use super::{
+    error_configuration::ErrorConfiguration,
+    parsable::{parse_structure, value_from_map, KeyValueMap, Parsable},
+    validation::{is_valid, KeyRule},
+};
+
+use std::cmp::PartialEq;
+
+const MAX_SIZE: &str = "max_size";
+const ARCHIVES: &str = "archives";
+const COMPRESS: &str = "compress";
+
+const DEFAULT_ARCHIVES: usize = 5;
+
+const SIZE_UNITS: [(&str, u64); 4] = [
+    ("GB", 1024 * 1024 * 1024),
+    ("MB", 1024 * 1024),
+    ("KB", 1024),
+    ("B", 1),
+];
+
+/// Configuration of the rotation of the log file
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogRotationConfig {
+    /// It's the most bytes the log file can have before it's rotated
+    pub max_size: u64,
+
+    /// It's the number of old log files that are kept
+    pub archives: usize,
+
+    /// If the old log files are compressed with gzip
+    pub compress: bool,
+}
+
+impl LogRotationConfig {
+    /// It's the keys that can be given in the structure
+    pub const RULES: &'static [KeyRule] = &[
+        KeyRule::value(MAX_SIZE, true, is_valid_size),
+        KeyRule::value(ARCHIVES, false, is_valid::<usize>),
+        KeyRule::value(COMPRESS, false, is_valid::<bool>),
+    ];
+}
+
+/// Returns the size given in bytes, or with one of the units `B`, `KB`, `MB` or `GB`
+fn parse_size(value: &str) -> Option<u64> {
+    let value = value.trim().to_uppercase();
+    let (amount, unit) = SIZE_UNITS
+        .iter()
+        .find_map(|(suffix, unit)| Some((value.strip_suffix(suffix)?, *unit)))
+        .unwrap_or((&value, 1));
+
+    amount.trim().parse::<u64>().ok()?.checked_mul(unit)
+}
+
+/// Checks that the value is a size greater than zero
+fn is_valid_size(value: &str) -> Result<(), String> {
+    match parse_size(value) {
+        Some(0) => Err("must be greater than 0".to_string()),
+        Some(_) => Ok(()),
+        None => Err(format!(
+            "{:?} is not a valid size, as 1048576, 512KB or 10MB",
+            value
+        )),
+    }
+}
+
+impl Parsable for LogRotationConfig {
+    fn parse(name: &str, map: &KeyValueMap) -> Result<Self, ErrorConfiguration> {
+        let structure = value_from_map(name.to_string(), map)?;
+        let map = parse_structure(structure)?;
+
+        let max_size = value_from_map(MAX_SIZE.to_string(), &map)?;
+        let max_size = match parse_size(&max_size) {
+            Some(max_size) if max_size > 0 => max_size,
+            _ => {
+                return Err(ErrorConfiguration::ErrorCantParseValue(format!(
+                    "size of {:?}",
+                    max_size
+                )))
+            }
+        };
+
+        Ok(LogRotationConfig {
+            max_size,
+            archives: Option::<usize>::parse(ARCHIVES, &map)?.unwrap_or(DEFAULT_ARCHIVES),
+            compress: Option::<bool>::parse(COMPRESS, &map)?.unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_accept_valid_input() {
+        let rotation = "rotation {
+            max_size = 10MB
+            archives = 3
+            compress = true
+        }";
+
+        let name = "rotation";
+        let map = parse_structure(rotation.to_string()).unwrap();
+
+        let rotation_result = LogRotationConfig::parse(name, &map);
+
+        let config_rotation = LogRotationConfig {
+            max_size: 10 * 1024 * 1024,
+            archives: 3,
+            compress: true,
+        };
+
+        assert_eq!(Ok(config_rotation), rotation_result);
+    }
+
+    #[test]
+    fn test02_accept_input_with_only_the_size() {
+        let rotation = "rotation {
+            max_size = 4096
+        }";
+
+        let name = "rotation";
+        let map = parse_structure(rotation.to_string()).unwrap();
+
+        let rotation_result = LogRotationConfig::parse(name, &map);
+
+        let config_rotation = LogRotationConfig {
+            max_size: 4096,
+            archives: DEFAULT_ARCHIVES,
+            compress: false,
+        };
+
+        assert_eq!(Ok(config_rotation), rotation_result);
+    }
+
+    #[test]
+    fn test03_does_not_accept_invalid_sizes() {
+        for max_size in ["0", "10 TB", "-5KB"] {
+            let rotation = format!("rotation {{\nmax_size = {max_size}\n}}");
+            let map = parse_structure(rotation).unwrap();
+
+            assert!(LogRotationConfig::parse("rotation", &map).is_err());
+            assert!(is_valid_size(max_size).is_err());
+        }
+    }
+}
@@ -1,14 +1,24 @@
 pub mod client_config;
 pub mod connection_config;
 pub mod download_config;
+pub mod exchange_rate_config;
+pub mod faucet_config;
 pub mod log_config;
+pub mod log_rotation_config;
 pub mod mode_config;
+pub mod proxy_config;
 pub mod save_config;
 pub mod server_config;
 pub mod ui_config;
 
+pub mod data_dir;
+pub mod environment;
 pub mod interface;
+pub mod language;
 pub mod parsable;
+pub mod theme;
+pub mod toml_structure;
 pub mod try_default;
+pub mod validation;
 
 pub mod error_configuration;
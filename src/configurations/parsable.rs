@@ -1,6 +1,11 @@
 use super::error_configuration::ErrorConfiguration;
 
-use std::{collections::HashMap, net::Ipv4Addr, str::FromStr};
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    str::FromStr,
+    time::Duration,
+};
 
 pub type Key = String;
 pub type Value = String;
@@ -12,6 +17,14 @@ const ASSIGNMENT: char = '=';
 const OPEN_GROUP: char = '{';
 const CLOSE_GROUP: char = '}';
 
+/// It's the key used to parse each element of a list as any other value
+const ELEMENT: &str = "element";
+
+const MILLISECONDS_SUFFIX: &str = "ms";
+const SECONDS_SUFFIX: &str = "s";
+const MINUTES_SUFFIX: &str = "m";
+const HOURS_SUFFIX: &str = "h";
+
 /// Trait that allows to parse from a configuration file
 pub trait Parsable: Sized {
     fn parse(name: &str, map: &KeyValueMap) -> Result<Self, ErrorConfiguration>;
@@ -100,22 +113,25 @@ impl<V: Parsable> Parsable for Option<V> {
     }
 }
 
-impl<V: FromStr> Parsable for Vec<V> {
+/// Parses a list given by `[a, b, ...]`, where each element is parsed as any other value of its
+/// type. The elements that cannot be parsed are ignored
+impl<V: Parsable> Parsable for Vec<V> {
     fn parse(name: &str, map: &KeyValueMap) -> Result<Self, ErrorConfiguration> {
         let value = value_from_map(name.to_string(), map)?;
 
-        if let (Some(primero), Some(ultimo)) = (value.find('['), value.find(']')) {
+        if let (Some(primero), Some(ultimo)) = (value.find('['), value.rfind(']')) {
             let value: &str = &value[primero + 1..ultimo];
             let values: Vec<String> = value
                 .split(',')
                 .map(|service| service.trim().to_string())
+                .filter(|service| !service.is_empty())
                 .collect();
 
             let values: Vec<V> = values
-                .iter()
-                .filter_map(|value| match value.parse::<V>() {
-                    Ok(value) => Some(value),
-                    _ => None,
+                .into_iter()
+                .filter_map(|value| {
+                    let element = HashMap::from([(ELEMENT.to_string(), value)]);
+                    V::parse(ELEMENT, &element).ok()
                 })
                 .collect();
 
@@ -129,11 +145,34 @@ impl<V: FromStr> Parsable for Vec<V> {
     }
 }
 
+/// Parses a structure where every key has a value of the same type, as in
+/// ```text
+/// seeders {
+///     main {
+///         seed = seed.testnet.bitcoin.sprovoost.nl
+///         port = 18333
+///     }
+/// }
+/// ```
+impl<V: Parsable> Parsable for HashMap<Key, V> {
+    fn parse(name: &str, map: &KeyValueMap) -> Result<Self, ErrorConfiguration> {
+        let structure = value_from_map(name.to_string(), map)?;
+        let map = parse_structure(structure)?;
+
+        let mut values: HashMap<Key, V> = HashMap::new();
+        for key in map.keys() {
+            values.insert(key.clone(), V::parse(key, &map)?);
+        }
+
+        Ok(values)
+    }
+}
+
 impl<const N: usize, V: FromStr> Parsable for [V; N] {
     fn parse(name: &str, map: &KeyValueMap) -> Result<Self, ErrorConfiguration> {
         let value = value_from_map(name.to_string(), map)?;
 
-        if let (Some(primero), Some(ultimo)) = (value.find('['), value.find(']')) {
+        if let (Some(primero), Some(ultimo)) = (value.find('['), value.rfind(']')) {
             let value: &str = &value[primero + 1..ultimo];
             let values: Vec<String> = value
                 .split(',')
@@ -252,6 +291,19 @@ impl Parsable for String {
     }
 }
 
+impl Parsable for IpAddr {
+    fn parse(name: &str, map: &KeyValueMap) -> Result<Self, ErrorConfiguration> {
+        let value = value_from_map(name.to_string(), map)?;
+        match value.parse::<IpAddr>() {
+            Ok(parse_value) => Ok(parse_value),
+            _ => Err(ErrorConfiguration::ErrorCantParseValue(format!(
+                "IpAddr of {:?}",
+                value
+            ))),
+        }
+    }
+}
+
 impl Parsable for Ipv4Addr {
     fn parse(name: &str, map: &KeyValueMap) -> Result<Self, ErrorConfiguration> {
         let value = value_from_map(name.to_string(), map)?;
@@ -264,3 +316,125 @@ impl Parsable for Ipv4Addr {
         }
     }
 }
+
+impl Parsable for SocketAddr {
+    fn parse(name: &str, map: &KeyValueMap) -> Result<Self, ErrorConfiguration> {
+        let value = value_from_map(name.to_string(), map)?;
+        match value.parse::<SocketAddr>() {
+            Ok(parse_value) => Ok(parse_value),
+            _ => Err(ErrorConfiguration::ErrorCantParseValue(format!(
+                "SocketAddr of {:?}",
+                value
+            ))),
+        }
+    }
+}
+
+/// Parses a duration given in seconds, or with one of the units `ms`, `s`, `m` or `h`, as in `500ms`
+impl Parsable for Duration {
+    fn parse(name: &str, map: &KeyValueMap) -> Result<Self, ErrorConfiguration> {
+        let value = value_from_map(name.to_string(), map)?;
+        match parse_duration(&value) {
+            Some(parse_value) => Ok(parse_value),
+            None => Err(ErrorConfiguration::ErrorCantParseValue(format!(
+                "Duration of {:?}",
+                value
+            ))),
+        }
+    }
+}
+
+/// Returns the duration given in seconds, or with one of the units `ms`, `s`, `m` or `h`
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let (amount, unit_in_milliseconds): (&str, u64) =
+        if let Some(amount) = value.strip_suffix(MILLISECONDS_SUFFIX) {
+            (amount, 1)
+        } else if let Some(amount) = value.strip_suffix(SECONDS_SUFFIX) {
+            (amount, 1000)
+        } else if let Some(amount) = value.strip_suffix(MINUTES_SUFFIX) {
+            (amount, 60 * 1000)
+        } else if let Some(amount) = value.strip_suffix(HOURS_SUFFIX) {
+            (amount, 60 * 60 * 1000)
+        } else {
+            (value, 1000)
+        };
+
+    let amount = amount.trim().parse::<u64>().ok()?;
+    Some(Duration::from_millis(
+        amount.checked_mul(unit_in_milliseconds)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::Ipv6Addr;
+
+    #[test]
+    fn test01_lists_are_parsed_by_the_type_of_their_elements() {
+        let map = parse_structure(
+            "peers = [127.0.0.1:18333, [::1]:18444, not_a_peer]
+            ports = []"
+                .to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            Vec::<SocketAddr>::parse("peers", &map),
+            Ok(vec![
+                SocketAddr::from((Ipv4Addr::LOCALHOST, 18333)),
+                SocketAddr::from((Ipv6Addr::LOCALHOST, 18444)),
+            ])
+        );
+        assert_eq!(Vec::<u16>::parse("ports", &map), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn test02_nested_structures_are_parsed_by_the_type_of_their_values() {
+        let map = parse_structure(
+            "seeders {
+                main {
+                    port = 18333
+                }
+                backup {
+                    port = 18444
+                }
+            }"
+            .to_string(),
+        )
+        .unwrap();
+
+        let seeders = HashMap::<Key, HashMap<Key, u16>>::parse("seeders", &map).unwrap();
+
+        assert_eq!(seeders["main"]["port"], 18333);
+        assert_eq!(seeders["backup"]["port"], 18444);
+    }
+
+    #[test]
+    fn test03_durations_are_parsed_with_their_unit() {
+        let map = parse_structure(
+            "default = 30
+            milliseconds = 500ms
+            minutes = 2m
+            invalid = 2 days"
+                .to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            Duration::parse("default", &map),
+            Ok(Duration::from_secs(30))
+        );
+        assert_eq!(
+            Duration::parse("milliseconds", &map),
+            Ok(Duration::from_millis(500))
+        );
+        assert_eq!(
+            Duration::parse("minutes", &map),
+            Ok(Duration::from_secs(120))
+        );
+        assert!(Duration::parse("invalid", &map).is_err());
+    }
+}
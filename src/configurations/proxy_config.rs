@@ -0,0 +1,126 @@
+use super::{
+    error_configuration::ErrorConfiguration,
+    parsable::{parse_structure, value_from_map, KeyValueMap, Parsable},
+    validation::{is_valid, is_valid_list, KeyRule},
+};
+
+use crate::connections::onion_address::OnionAddress;
+
+use std::{
+    cmp::PartialEq,
+    net::{IpAddr, SocketAddr},
+};
+
+const ADDRESS: &str = "address";
+const PORT: &str = "port";
+const ONION_PEERS: &str = "onion_peers";
+
+/// Configuration of the SOCKS5 proxy (like Tor) used for the outbound connections
+#[derive(Debug, PartialEq, Clone)]
+pub struct ProxyConfig {
+    /// It's the address where the proxy is listening
+    pub address: IpAddr,
+
+    /// It's the port number where the proxy is listening
+    pub port: u16,
+
+    /// It's the onion services to connect to through the proxy
+    pub onion_peers: Vec<OnionAddress>,
+}
+
+impl ProxyConfig {
+    /// It's the keys that can be given in the structure
+    pub const RULES: &'static [KeyRule] = &[
+        KeyRule::value(ADDRESS, true, is_valid::<IpAddr>),
+        KeyRule::value(PORT, true, is_valid::<u16>),
+        KeyRule::value(ONION_PEERS, false, is_valid_list::<OnionAddress>),
+    ];
+
+    /// Returns the socket where the proxy is listening
+    pub fn socket(&self) -> SocketAddr {
+        SocketAddr::new(self.address, self.port)
+    }
+}
+
+impl Parsable for ProxyConfig {
+    fn parse(name: &str, map: &KeyValueMap) -> Result<Self, ErrorConfiguration> {
+        let structure = value_from_map(name.to_string(), map)?;
+        let map = parse_structure(structure)?;
+
+        Ok(ProxyConfig {
+            address: IpAddr::parse(ADDRESS, &map)?,
+            port: u16::parse(PORT, &map)?,
+            onion_peers: Option::<Vec<OnionAddress>>::parse(ONION_PEERS, &map)?.unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn test01_accept_valid_input() {
+        let proxy = "proxy {
+            address = 127.0.0.1
+            port = 9050
+            onion_peers = [2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion:18333]
+        }";
+
+        let name = "proxy";
+        let map = parse_structure(proxy.to_string()).unwrap();
+
+        let proxy_result = ProxyConfig::parse(name, &map);
+
+        let config_proxy = ProxyConfig {
+            address: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
+            port: 9050,
+            onion_peers: vec![
+                "2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion:18333"
+                    .parse::<OnionAddress>()
+                    .unwrap(),
+            ],
+        };
+
+        assert_eq!(Ok(config_proxy), proxy_result);
+    }
+
+    #[test]
+    fn test02_onion_peers_are_optional() {
+        let proxy = "proxy {
+            address = 127.0.0.1
+            port = 9050
+        }";
+
+        let name = "proxy";
+        let map = parse_structure(proxy.to_string()).unwrap();
+
+        let proxy_result = ProxyConfig::parse(name, &map).unwrap();
+
+        assert!(proxy_result.onion_peers.is_empty());
+        assert_eq!(
+            proxy_result.socket(),
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9050)
+        );
+    }
+
+    #[test]
+    fn test03_accept_a_proxy_listening_in_an_ipv6_address() {
+        let proxy = "proxy {
+            address = ::1
+            port = 9150
+        }";
+
+        let name = "proxy";
+        let map = parse_structure(proxy.to_string()).unwrap();
+
+        let proxy_result = ProxyConfig::parse(name, &map).unwrap();
+
+        assert_eq!(
+            proxy_result.socket(),
+            SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 9150)
+        );
+    }
+}
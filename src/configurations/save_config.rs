@@ -1,9 +1,10 @@
 use super::{
     error_configuration::ErrorConfiguration,
     parsable::{parse_structure, value_from_map, KeyValueMap, Parsable},
+    validation::{is_positive_duration, is_valid, KeyRule},
 };
 
-use std::cmp::PartialEq;
+use std::{cmp::PartialEq, time::Duration};
 
 const READ_BLOCK_CHAIN: &str = "read_block_chain";
 const WRITE_BLOCK_CHAIN: &str = "write_block_chain";
@@ -11,6 +12,16 @@ const WRITE_BLOCK_CHAIN: &str = "write_block_chain";
 const READ_WALLET: &str = "read_wallet";
 const WRITE_WALLET: &str = "write_wallet";
 
+const WALLET_DIRECTORY: &str = "wallet_directory";
+const SELECTED_WALLET: &str = "selected_wallet";
+
+const DATA_DIRECTORY: &str = "datadir";
+
+const AUTOSAVE: &str = "autosave";
+const AUTOSAVE_INTERVAL: &str = "autosave_interval";
+
+const DEFAULT_AUTOSAVE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
 /// It represents all the data needed to load and save the data of the program
 #[derive(Debug, PartialEq, Clone)]
 pub struct SaveConfig {
@@ -25,6 +36,37 @@ pub struct SaveConfig {
 
     /// It's the file name where the wallet will be saved
     pub write_wallet: Option<String>,
+
+    /// It's the directory where each wallet has its own file. If given, it's used instead of the wallet files
+    pub wallet_directory: Option<String>,
+
+    /// It's the name of the wallet of the directory to use at startup
+    pub selected_wallet: Option<String>,
+
+    /// It's the directory where the relative paths of the saved files and the logs are. If not
+    /// given, the directory of the data of the platform is used
+    pub data_directory: Option<String>,
+
+    /// If the wallet, the UTXO set and the tip of the block chain are saved periodically while running
+    pub autosave: bool,
+
+    /// It's the time to wait between the autosaves
+    pub autosave_interval: Duration,
+}
+
+impl SaveConfig {
+    /// It's the keys that can be given in the structure
+    pub const RULES: &'static [KeyRule] = &[
+        KeyRule::value(READ_BLOCK_CHAIN, false, is_valid::<String>),
+        KeyRule::value(WRITE_BLOCK_CHAIN, false, is_valid::<String>),
+        KeyRule::value(READ_WALLET, false, is_valid::<String>),
+        KeyRule::value(WRITE_WALLET, false, is_valid::<String>),
+        KeyRule::value(WALLET_DIRECTORY, false, is_valid::<String>),
+        KeyRule::value(SELECTED_WALLET, false, is_valid::<String>),
+        KeyRule::value(DATA_DIRECTORY, false, is_valid::<String>),
+        KeyRule::value(AUTOSAVE, false, is_valid::<bool>),
+        KeyRule::value(AUTOSAVE_INTERVAL, false, is_positive_duration),
+    ];
 }
 
 impl Parsable for SaveConfig {
@@ -37,6 +79,12 @@ impl Parsable for SaveConfig {
             write_block_chain: Option::<String>::parse(WRITE_BLOCK_CHAIN, &map)?,
             read_wallet: Option::<String>::parse(READ_WALLET, &map)?,
             write_wallet: Option::<String>::parse(WRITE_WALLET, &map)?,
+            wallet_directory: Option::<String>::parse(WALLET_DIRECTORY, &map)?,
+            selected_wallet: Option::<String>::parse(SELECTED_WALLET, &map)?,
+            data_directory: Option::<String>::parse(DATA_DIRECTORY, &map)?,
+            autosave: Option::<bool>::parse(AUTOSAVE, &map)?.unwrap_or_default(),
+            autosave_interval: Option::<Duration>::parse(AUTOSAVE_INTERVAL, &map)?
+                .unwrap_or(DEFAULT_AUTOSAVE_INTERVAL),
         })
     }
 }
@@ -63,6 +111,11 @@ mod tests {
             write_block_chain: Some("save_test2.txt".to_string()),
             read_wallet: Some("save_w_test.txt".to_string()),
             write_wallet: Some("save_w_test2.txt".to_string()),
+            wallet_directory: None,
+            selected_wallet: None,
+            data_directory: None,
+            autosave: false,
+            autosave_interval: DEFAULT_AUTOSAVE_INTERVAL,
         };
 
         assert_eq!(Ok(config_save), log_result);
@@ -86,6 +139,11 @@ mod tests {
             write_block_chain: Some("save_test2.txt".to_string()),
             read_wallet: Some("save_w_test.txt".to_string()),
             write_wallet: Some("save_w_test2.txt".to_string()),
+            wallet_directory: None,
+            selected_wallet: None,
+            data_directory: None,
+            autosave: false,
+            autosave_interval: DEFAULT_AUTOSAVE_INTERVAL,
         };
 
         assert_eq!(Ok(config_save), log_result);
@@ -108,6 +166,11 @@ mod tests {
             write_block_chain: None,
             read_wallet: Some("save_w_test.txt".to_string()),
             write_wallet: Some("save_w_test2.txt".to_string()),
+            wallet_directory: None,
+            selected_wallet: None,
+            data_directory: None,
+            autosave: false,
+            autosave_interval: DEFAULT_AUTOSAVE_INTERVAL,
         };
 
         assert_eq!(Ok(config_missing), log_result);
@@ -132,6 +195,11 @@ mod tests {
             write_block_chain: Some("save_test2.txt".to_string()),
             read_wallet: Some("save_w_test.txt".to_string()),
             write_wallet: Some("save_w_test2.txt".to_string()),
+            wallet_directory: None,
+            selected_wallet: None,
+            data_directory: None,
+            autosave: false,
+            autosave_interval: DEFAULT_AUTOSAVE_INTERVAL,
         };
 
         assert_eq!(Ok(config_save), log_result);
@@ -147,4 +215,83 @@ mod tests {
 
         assert_eq!(Err(ErrorConfiguration::ValueNotFound), log_result);
     }
+
+    #[test]
+    fn test06_accept_input_with_wallet_directory() {
+        let configuration = "save {
+            wallet_directory = wallets
+            selected_wallet = personal
+        }";
+        let name = "save";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let log_result = SaveConfig::parse(name, &map);
+
+        let config_save = SaveConfig {
+            read_block_chain: None,
+            write_block_chain: None,
+            read_wallet: None,
+            write_wallet: None,
+            wallet_directory: Some("wallets".to_string()),
+            selected_wallet: Some("personal".to_string()),
+            data_directory: None,
+            autosave: false,
+            autosave_interval: DEFAULT_AUTOSAVE_INTERVAL,
+        };
+
+        assert_eq!(Ok(config_save), log_result);
+    }
+
+    #[test]
+    fn test07_accept_input_with_autosave() {
+        let configuration = "save {
+            write_block_chain = save_test2.txt
+            autosave = true
+            autosave_interval = 30s
+        }";
+        let name = "save";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let log_result = SaveConfig::parse(name, &map);
+
+        let config_save = SaveConfig {
+            read_block_chain: None,
+            write_block_chain: Some("save_test2.txt".to_string()),
+            read_wallet: None,
+            write_wallet: None,
+            wallet_directory: None,
+            selected_wallet: None,
+            data_directory: None,
+            autosave: true,
+            autosave_interval: Duration::from_secs(30),
+        };
+
+        assert_eq!(Ok(config_save), log_result);
+    }
+
+    #[test]
+    fn test08_accept_input_with_data_directory() {
+        let configuration = "save {
+            write_block_chain = blockchain.raw
+            datadir = /var/lib/cargosos
+        }";
+        let name = "save";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let log_result = SaveConfig::parse(name, &map);
+
+        let config_save = SaveConfig {
+            read_block_chain: None,
+            write_block_chain: Some("blockchain.raw".to_string()),
+            read_wallet: None,
+            write_wallet: None,
+            wallet_directory: None,
+            selected_wallet: None,
+            data_directory: Some("/var/lib/cargosos".to_string()),
+            autosave: false,
+            autosave_interval: DEFAULT_AUTOSAVE_INTERVAL,
+        };
+
+        assert_eq!(Ok(config_save), log_result);
+    }
 }
@@ -1,11 +1,12 @@
 use super::{
     error_configuration::ErrorConfiguration,
     parsable::{parse_structure, value_from_map, KeyValueMap, Parsable},
+    validation::{is_positive, is_valid, KeyRule},
 };
 
 use crate::connections::dns_seeder::DNSSeeder;
 
-use std::{cmp::PartialEq, net::Ipv4Addr};
+use std::{cmp::PartialEq, net::IpAddr};
 
 const DNS_SEEDER: &str = "dns_seeder";
 const PEER_COUNT_MAX: &str = "peer_count_max";
@@ -25,7 +26,17 @@ pub struct ServerConfig {
     pub own_port: u16,
 
     /// The address' where the server will be listening
-    pub address: Ipv4Addr,
+    pub address: IpAddr,
+}
+
+impl ServerConfig {
+    /// It's the keys that can be given in the structure
+    pub const RULES: &'static [KeyRule] = &[
+        KeyRule::structure(DNS_SEEDER, true, DNSSeeder::RULES),
+        KeyRule::value(PEER_COUNT_MAX, true, is_positive),
+        KeyRule::value(PORT, true, is_valid::<u16>),
+        KeyRule::value(ADDRESS, true, is_valid::<IpAddr>),
+    ];
 }
 
 impl Parsable for ServerConfig {
@@ -37,7 +48,7 @@ impl Parsable for ServerConfig {
             dns_seeder: DNSSeeder::parse(DNS_SEEDER, &map)?,
             peer_count_max: usize::parse(PEER_COUNT_MAX, &map)?,
             own_port: u16::parse(PORT, &map)?,
-            address: Ipv4Addr::parse(ADDRESS, &map)?,
+            address: IpAddr::parse(ADDRESS, &map)?,
         })
     }
 }
@@ -69,7 +80,7 @@ mod tests {
             dns_seeder: DNSSeeder::new("seed.testnet.bitcoin.sprovoost.nl", 18333),
             peer_count_max: 8,
             own_port: 18333,
-            address: Ipv4Addr::new(127, 0, 0, 1),
+            address: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
         };
 
         assert_eq!(Ok(config_server), server_result);
@@ -96,7 +107,7 @@ mod tests {
             dns_seeder: DNSSeeder::new("seed.testnet.bitcoin.sprovoost.nl", 18333),
             peer_count_max: 8,
             own_port: 18333,
-            address: Ipv4Addr::new(127, 0, 0, 1),
+            address: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
         };
 
         assert_eq!(Ok(server_config), server_result);
@@ -143,7 +154,7 @@ mod tests {
             dns_seeder: DNSSeeder::new("seed.testnet.bitcoin.sprovoost.nl", 18333),
             peer_count_max: 8,
             own_port: 18333,
-            address: Ipv4Addr::new(127, 0, 0, 1),
+            address: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
         };
 
         assert_eq!(Ok(server_config), server_result);
@@ -0,0 +1,180 @@
+use super::{
+    error_configuration::ErrorConfiguration,
+    parsable::{value_from_map, KeyValueMap, Parsable},
+};
+
+use std::{cmp::PartialEq, fmt::Display, str::FromStr};
+
+const THEME: &str = "theme";
+const ASSIGNMENT: char = '=';
+const OPEN_GROUP: &str = "{";
+const CLOSE_GROUP: &str = "}";
+const OPEN_TABLE: char = '[';
+const CLOSE_TABLE: char = ']';
+const INDENTATION: &str = "    ";
+
+/// It represents the colors used by the graphical interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Theme {
+    /// Dark text over light backgrounds
+    Light,
+
+    /// Light text over dark backgrounds
+    Dark,
+
+    /// The one preferred by the desktop
+    #[default]
+    System,
+}
+
+impl FromStr for Theme {
+    type Err = ErrorConfiguration;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "light" => Ok(Theme::Light),
+            "dark" => Ok(Theme::Dark),
+            "system" => Ok(Theme::System),
+            _ => Err(ErrorConfiguration::ErrorCantParseValue(format!(
+                "theme of {:?}",
+                value
+            ))),
+        }
+    }
+}
+
+impl Parsable for Theme {
+    fn parse(name: &str, map: &KeyValueMap) -> Result<Self, ErrorConfiguration> {
+        let value = value_from_map(name.to_string(), map)?;
+        value.parse::<Theme>()
+    }
+}
+
+impl Display for Theme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Theme::Light => write!(f, "light"),
+            Theme::Dark => write!(f, "dark"),
+            Theme::System => write!(f, "system"),
+        }
+    }
+}
+
+/// Returns the text of the configuration with the theme written in the given structure, replacing
+/// the previous one or adding it at the end of the structure. With TOML, the structure is a table
+/// and the theme is written between quotes. The rest of the lines are kept as they are
+///
+/// ### Error
+///  * `ErrorConfiguration::ErrorConfigurationNotFound`: It will appear when the structure is not in the configuration
+pub fn write_theme(
+    configuration: &str,
+    structure: &str,
+    theme: Theme,
+    is_toml: bool,
+) -> Result<String, ErrorConfiguration> {
+    let mut lines: Vec<String> = configuration.lines().map(str::to_string).collect();
+
+    let is_start = |line: &str| match is_toml {
+        true => line.trim() == format!("{OPEN_TABLE}{structure}{CLOSE_TABLE}"),
+        false => line.trim().strip_suffix(OPEN_GROUP).map(str::trim_end) == Some(structure),
+    };
+    let is_end = |line: &str| match is_toml {
+        true => line.trim_start().starts_with(OPEN_TABLE),
+        false => line.trim() == CLOSE_GROUP,
+    };
+
+    let start = match lines.iter().position(|line| is_start(line)) {
+        Some(start) => start,
+        None => return Err(ErrorConfiguration::ErrorConfigurationNotFound),
+    };
+    let end = match lines.iter().skip(start + 1).position(|line| is_end(line)) {
+        Some(position) => start + 1 + position,
+        None if is_toml => lines.len(),
+        None => return Err(ErrorConfiguration::ErrorConfigurationNotFound),
+    };
+
+    let theme_line = match is_toml {
+        true => format!("{THEME} {ASSIGNMENT} \"{theme}\""),
+        false => format!("{INDENTATION}{THEME} {ASSIGNMENT} {theme}"),
+    };
+
+    let previous_theme = lines[start + 1..end].iter().position(|line| {
+        line.split_once(ASSIGNMENT)
+            .is_some_and(|(key, _)| key.trim() == THEME)
+    });
+
+    match previous_theme {
+        Some(position) => lines[start + 1 + position] = theme_line,
+        None => {
+            let last_line = lines[start..end]
+                .iter()
+                .rposition(|line| !line.trim().is_empty())
+                .unwrap_or(0);
+            lines.insert(start + last_line + 1, theme_line);
+        }
+    }
+
+    let mut configuration = lines.join("\n");
+    configuration.push('\n');
+    Ok(configuration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_theme_is_read_without_caring_about_the_case() {
+        assert_eq!(Ok(Theme::Light), "light".parse::<Theme>());
+        assert_eq!(Ok(Theme::Dark), "Dark".parse::<Theme>());
+        assert_eq!(Ok(Theme::System), "SYSTEM".parse::<Theme>());
+        assert!("blue".parse::<Theme>().is_err());
+    }
+
+    #[test]
+    fn test02_theme_is_added_at_the_end_of_the_structure() {
+        let configuration = "Logs {\n    show_console = true\n}\n\nUI {\n    interface = GUI\n}\n";
+
+        let configuration = write_theme(configuration, "UI", Theme::Dark, false).unwrap();
+
+        assert_eq!(
+            "Logs {\n    show_console = true\n}\n\nUI {\n    interface = GUI\n    theme = dark\n}\n",
+            configuration
+        );
+    }
+
+    #[test]
+    fn test03_previous_theme_is_replaced() {
+        let configuration = "UI {\n    theme = dark\n    interface = GUI\n}";
+
+        let configuration = write_theme(configuration, "UI", Theme::Light, false).unwrap();
+
+        assert_eq!(
+            "UI {\n    theme = light\n    interface = GUI\n}\n",
+            configuration
+        );
+    }
+
+    #[test]
+    fn test04_theme_is_written_in_the_toml_table() {
+        let configuration = "[UI]\ninterface = \"GUI\"\n\n[Faucet]\nurl = \"http://faucet\"\n";
+
+        let configuration = write_theme(configuration, "UI", Theme::System, true).unwrap();
+        assert_eq!(
+            "[UI]\ninterface = \"GUI\"\ntheme = \"system\"\n\n[Faucet]\nurl = \"http://faucet\"\n",
+            configuration
+        );
+
+        let configuration = write_theme(&configuration, "UI", Theme::Dark, true).unwrap();
+        assert!(configuration.contains("theme = \"dark\"\n"));
+        assert!(!configuration.contains("system"));
+    }
+
+    #[test]
+    fn test05_structure_must_be_in_the_configuration() {
+        assert_eq!(
+            Err(ErrorConfiguration::ErrorConfigurationNotFound),
+            write_theme("Logs {\n}\n", "UI", Theme::Dark, false)
+        );
+    }
+}
@@ -0,0 +1,471 @@
+use super::{
+    error_configuration::ErrorConfiguration,
+    parsable::{Key, KeyValueMap, Value},
+};
+
+use std::collections::HashMap;
+
+const END_LINE: char = '\n';
+const ASSIGNMENT: char = '=';
+const COMMENT: char = '#';
+const KEY_SEPARATOR: char = '.';
+const OPEN_TABLE: char = '[';
+const CLOSE_TABLE: char = ']';
+const OPEN_ARRAY: char = '[';
+const CLOSE_ARRAY: char = ']';
+const ARRAY_SEPARATOR: char = ',';
+const BASIC_STRING: char = '"';
+const LITERAL_STRING: char = '\'';
+const ESCAPE: char = '\\';
+const INLINE_TABLE: char = '{';
+
+const OPEN_GROUP: &str = "{";
+const CLOSE_GROUP: &str = "}";
+
+/// It's a value of a TOML file, where the tables keep the order of their keys
+#[derive(Debug, PartialEq)]
+enum TomlValue {
+    Value(Value),
+    Table(Vec<(Key, TomlValue)>),
+}
+
+/// Parses a configuration written in TOML into the same map given by `parse_structure`, so every
+/// structure can be parsed in the same way from both formats. The tables are given as their
+/// content in the legacy format, and the strings and arrays as the legacy values.
+///
+/// It accepts the tables (`[Logs]`, `[Connection.dns_seeder]`), the dotted keys, the basic and
+/// literal strings, the integers, the booleans and the arrays of them. The inline tables are not
+/// accepted
+///
+/// ### Error
+///  * `ErrorConfiguration::ErrorInvalidFormat`: It will appear when a line is not a table header or a `key = value` assignment
+///  * `ErrorConfiguration::ErrorCantParseValue`: It will appear when a value is not valid TOML
+///  * `ErrorConfiguration::ErrorEncounterFieldMoreThanOnes`: It will appear when a key or a table is defined more than once
+pub fn parse_toml_structure(value: Value) -> Result<KeyValueMap, ErrorConfiguration> {
+    let mut root: Vec<(Key, TomlValue)> = Vec::new();
+    let mut defined_tables: Vec<Vec<Key>> = Vec::new();
+    let mut current_table: Vec<Key> = Vec::new();
+
+    let mut lines = value.split(END_LINE);
+    while let Some(line) = lines.next() {
+        let mut line = strip_comment(line).trim().to_string();
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with(OPEN_TABLE) {
+            current_table = parse_table_header(&line)?;
+            if defined_tables.contains(&current_table) {
+                return Err(ErrorConfiguration::ErrorEncounterFieldMoreThanOnes);
+            }
+            defined_tables.push(current_table.clone());
+            get_table(&mut root, &current_table)?;
+            continue;
+        }
+
+        while !is_array_closed(&line) {
+            match lines.next() {
+                Some(next_line) => {
+                    line.push(' ');
+                    line.push_str(strip_comment(next_line).trim());
+                }
+                None => return Err(ErrorConfiguration::ErrorCantParseValue(line)),
+            }
+        }
+
+        let (key, value) = match line.split_once(ASSIGNMENT) {
+            Some((key, value)) => (parse_key(key)?, parse_value(value.trim())?),
+            None => return Err(ErrorConfiguration::ErrorInvalidFormat),
+        };
+
+        let (name, path) = match key.split_last() {
+            Some((name, path)) => (name.clone(), path),
+            None => return Err(ErrorConfiguration::ErrorInvalidFormat),
+        };
+
+        let mut full_path = current_table.clone();
+        full_path.extend_from_slice(path);
+
+        let table = get_table(&mut root, &full_path)?;
+        if table.iter().any(|(defined, _)| *defined == name) {
+            return Err(ErrorConfiguration::ErrorEncounterFieldMoreThanOnes);
+        }
+        table.push((name, TomlValue::Value(value)));
+    }
+
+    let mut map: KeyValueMap = HashMap::new();
+    for (key, value) in root {
+        let value = match value {
+            TomlValue::Value(value) => value,
+            TomlValue::Table(table) => to_legacy_structure(&table),
+        };
+        map.insert(key, value);
+    }
+
+    Ok(map)
+}
+
+/// Returns the table given by its path, creating the ones that do not exist yet
+///
+/// ### Error
+///  * `ErrorConfiguration::ErrorEncounterFieldMoreThanOnes`: It will appear when a key of the path is already a value
+fn get_table<'a>(
+    table: &'a mut Vec<(Key, TomlValue)>,
+    path: &[Key],
+) -> Result<&'a mut Vec<(Key, TomlValue)>, ErrorConfiguration> {
+    let (name, rest) = match path.split_first() {
+        Some((name, rest)) => (name, rest),
+        None => return Ok(table),
+    };
+
+    let position = match table.iter().position(|(key, _)| key == name) {
+        Some(position) => position,
+        None => {
+            table.push((name.clone(), TomlValue::Table(Vec::new())));
+            table.len() - 1
+        }
+    };
+
+    match &mut table[position].1 {
+        TomlValue::Table(inner) => get_table(inner, rest),
+        TomlValue::Value(_) => Err(ErrorConfiguration::ErrorEncounterFieldMoreThanOnes),
+    }
+}
+
+/// Writes the table in the legacy format, where the inner tables are given as `key { ... }`
+fn to_legacy_structure(table: &[(Key, TomlValue)]) -> Value {
+    let mut structure = String::new();
+    for (key, value) in table {
+        match value {
+            TomlValue::Value(value) => {
+                structure.push_str(&format!("{key} {ASSIGNMENT} {value}{END_LINE}"))
+            }
+            TomlValue::Table(inner) => structure.push_str(&format!(
+                "{key} {OPEN_GROUP}{END_LINE}{}{CLOSE_GROUP}{END_LINE}",
+                to_legacy_structure(inner)
+            )),
+        }
+    }
+    structure
+}
+
+/// Removes the comment at the end of the line, ignoring the ones inside a string
+fn strip_comment(line: &str) -> &str {
+    let mut in_string: Option<char> = None;
+    let mut escaped = false;
+
+    for (index, character) in line.char_indices() {
+        match in_string {
+            Some(BASIC_STRING) if escaped => escaped = false,
+            Some(BASIC_STRING) if character == ESCAPE => escaped = true,
+            Some(quote) if character == quote => in_string = None,
+            Some(_) => {}
+            None => match character {
+                BASIC_STRING | LITERAL_STRING => in_string = Some(character),
+                COMMENT => return &line[..index],
+                _ => {}
+            },
+        }
+    }
+
+    line
+}
+
+/// Returns if every array opened in the assignment is already closed
+fn is_array_closed(line: &str) -> bool {
+    let value = match line.split_once(ASSIGNMENT) {
+        Some((_, value)) => value,
+        None => return true,
+    };
+
+    let mut depth: i32 = 0;
+    let mut in_string: Option<char> = None;
+    let mut escaped = false;
+    for character in value.chars() {
+        match in_string {
+            Some(BASIC_STRING) if escaped => escaped = false,
+            Some(BASIC_STRING) if character == ESCAPE => escaped = true,
+            Some(quote) if character == quote => in_string = None,
+            Some(_) => {}
+            None => match character {
+                BASIC_STRING | LITERAL_STRING => in_string = Some(character),
+                OPEN_ARRAY => depth += 1,
+                CLOSE_ARRAY => depth -= 1,
+                _ => {}
+            },
+        }
+    }
+
+    depth <= 0
+}
+
+/// Parses the header of a table given by `[name]` or `[name.inner]`
+///
+/// ### Error
+///  * `ErrorConfiguration::ErrorInvalidFormat`: It will appear when the header is not closed or it's an array of tables
+fn parse_table_header(line: &str) -> Result<Vec<Key>, ErrorConfiguration> {
+    match line
+        .strip_prefix(OPEN_TABLE)
+        .and_then(|line| line.strip_suffix(CLOSE_TABLE))
+    {
+        Some(name) if !name.starts_with(OPEN_TABLE) => parse_key(name),
+        _ => Err(ErrorConfiguration::ErrorInvalidFormat),
+    }
+}
+
+/// Parses a key, which can be dotted to give the tables where the value is
+///
+/// ### Error
+///  * `ErrorConfiguration::ErrorInvalidFormat`: It will appear when a part of the key is empty or has invalid characters
+fn parse_key(key: &str) -> Result<Vec<Key>, ErrorConfiguration> {
+    key.split(KEY_SEPARATOR)
+        .map(|part| {
+            let part = part.trim();
+            let part = match (
+                part.strip_prefix(BASIC_STRING)
+                    .and_then(|part| part.strip_suffix(BASIC_STRING)),
+                part.strip_prefix(LITERAL_STRING)
+                    .and_then(|part| part.strip_suffix(LITERAL_STRING)),
+            ) {
+                (Some(quoted), _) | (_, Some(quoted)) => quoted,
+                _ => part,
+            };
+
+            let is_bare = part
+                .chars()
+                .all(|character| character.is_ascii_alphanumeric() || "_-".contains(character));
+
+            match !part.is_empty() && is_bare {
+                true => Ok(part.to_string()),
+                false => Err(ErrorConfiguration::ErrorInvalidFormat),
+            }
+        })
+        .collect()
+}
+
+/// Parses a value, giving it as it's written in the legacy format
+///
+/// ### Error
+///  * `ErrorConfiguration::ErrorCantParseValue`: It will appear when the value is empty, an inline table or an invalid string or array
+fn parse_value(value: &str) -> Result<Value, ErrorConfiguration> {
+    let error = || ErrorConfiguration::ErrorCantParseValue(value.to_string());
+
+    match value.chars().next() {
+        Some(BASIC_STRING) => parse_basic_string(value).ok_or_else(error),
+        Some(LITERAL_STRING) => match value
+            .strip_prefix(LITERAL_STRING)
+            .and_then(|value| value.strip_suffix(LITERAL_STRING))
+        {
+            Some(string) if !string.contains(LITERAL_STRING) => Ok(string.to_string()),
+            _ => Err(error()),
+        },
+        Some(OPEN_ARRAY) => {
+            let elements = match value
+                .strip_prefix(OPEN_ARRAY)
+                .and_then(|value| value.strip_suffix(CLOSE_ARRAY))
+            {
+                Some(elements) => elements,
+                None => return Err(error()),
+            };
+
+            let mut values: Vec<Value> = Vec::new();
+            for element in split_array(elements) {
+                let element = element.trim();
+                if element.is_empty() {
+                    continue;
+                }
+                if element.starts_with(OPEN_ARRAY) {
+                    return Err(error());
+                }
+                values.push(parse_value(element)?);
+            }
+
+            Ok(format!(
+                "{OPEN_ARRAY}{}{CLOSE_ARRAY}",
+                values.join(&format!("{ARRAY_SEPARATOR} "))
+            ))
+        }
+        Some(INLINE_TABLE) | None => Err(error()),
+        Some(_) => match value.contains(char::is_whitespace) {
+            true => Err(error()),
+            false => Ok(value.replace('_', "")),
+        },
+    }
+}
+
+/// Parses a string between double quotes, resolving its escaped characters
+fn parse_basic_string(value: &str) -> Option<Value> {
+    let content = value
+        .strip_prefix(BASIC_STRING)?
+        .strip_suffix(BASIC_STRING)?;
+
+    let mut string = String::new();
+    let mut characters = content.chars();
+    while let Some(character) = characters.next() {
+        match character {
+            ESCAPE => match characters.next()? {
+                'n' => string.push('\n'),
+                't' => string.push('\t'),
+                'r' => string.push('\r'),
+                BASIC_STRING => string.push(BASIC_STRING),
+                ESCAPE => string.push(ESCAPE),
+                _ => return None,
+            },
+            BASIC_STRING => return None,
+            _ => string.push(character),
+        }
+    }
+
+    Some(string)
+}
+
+/// Separates the elements of an array, ignoring the separators inside the strings
+fn split_array(elements: &str) -> Vec<&str> {
+    let mut parts: Vec<&str> = Vec::new();
+    let mut in_string: Option<char> = None;
+    let mut escaped = false;
+    let mut start = 0;
+
+    for (index, character) in elements.char_indices() {
+        match in_string {
+            Some(BASIC_STRING) if escaped => escaped = false,
+            Some(BASIC_STRING) if character == ESCAPE => escaped = true,
+            Some(quote) if character == quote => in_string = None,
+            Some(_) => {}
+            None => match character {
+                BASIC_STRING | LITERAL_STRING => in_string = Some(character),
+                ARRAY_SEPARATOR => {
+                    parts.push(&elements[start..index]);
+                    start = index + 1;
+                }
+                _ => {}
+            },
+        }
+    }
+    parts.push(&elements[start..]);
+
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::configurations::{
+        connection_config::ConnectionConfig,
+        download_config::DownloadConfig,
+        parsable::{parse_structure, Parsable},
+        save_config::SaveConfig,
+    };
+
+    #[test]
+    fn test01_tables_are_given_in_the_legacy_format() {
+        let configuration = "
+            # The files of the node
+            [Save]
+            read_block_chain = \"blockchain.raw\" # It's loaded at the start
+            write_wallet = 'wallet.raw'
+
+            [Download]
+            timestamp = 1_685_264_160
+        ";
+
+        let map = parse_toml_structure(configuration.to_string()).unwrap();
+
+        let save_config = SaveConfig::parse("Save", &map).unwrap();
+        assert_eq!(
+            save_config.read_block_chain,
+            Some("blockchain.raw".to_string())
+        );
+        assert_eq!(save_config.write_wallet, Some("wallet.raw".to_string()));
+        assert_eq!(save_config.read_wallet, None);
+
+        let download_config = DownloadConfig::parse("Download", &map).unwrap();
+        assert_eq!(download_config.timestamp, 1685264160);
+    }
+
+    #[test]
+    fn test02_gives_the_same_configuration_as_the_legacy_format() {
+        let legacy = "Connection {
+            p2p_protocol_version = V70015
+            ibd_method = HeaderFirst
+            block_height = 0
+            services = [Unname]
+            network = testnet
+            nonce = 0
+            user_agent = Tanto tiempo
+            relay = true
+            proxy {
+                address = 127.0.0.1
+                port = 9050
+            }
+        }";
+        let toml = "
+            [Connection]
+            p2p_protocol_version = \"V70015\"
+            ibd_method = \"HeaderFirst\"
+            block_height = 0
+            services = [
+                \"Unname\",
+            ]
+            network = \"testnet\"
+            nonce = 0
+            user_agent = \"Tanto tiempo\"
+            relay = true
+
+            [Connection.proxy]
+            address = \"127.0.0.1\"
+            port = 9050
+        ";
+
+        let legacy_map = parse_structure(legacy.to_string()).unwrap();
+        let toml_map = parse_toml_structure(toml.to_string()).unwrap();
+
+        assert_eq!(
+            ConnectionConfig::parse("Connection", &legacy_map),
+            ConnectionConfig::parse("Connection", &toml_map)
+        );
+    }
+
+    #[test]
+    fn test03_does_not_accept_duplicated_keys_or_tables() {
+        let duplicated_key = "
+            [Download]
+            timestamp = 0
+            timestamp = 1
+        ";
+        let duplicated_table = "
+            [Download]
+            timestamp = 0
+            [Download]
+            timestamp = 1
+        ";
+        let value_as_table = "
+            Download = 0
+            [Download]
+            timestamp = 1
+        ";
+
+        for configuration in [duplicated_key, duplicated_table, value_as_table] {
+            assert_eq!(
+                Err(ErrorConfiguration::ErrorEncounterFieldMoreThanOnes),
+                parse_toml_structure(configuration.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn test04_does_not_accept_invalid_values_or_lines() {
+        assert_eq!(
+            Err(ErrorConfiguration::ErrorInvalidFormat),
+            parse_toml_structure("[Download\ntimestamp = 0".to_string())
+        );
+        assert_eq!(
+            Err(ErrorConfiguration::ErrorInvalidFormat),
+            parse_toml_structure("[Download]\ntimestamp".to_string())
+        );
+        assert!(parse_toml_structure("[Save]\nread_wallet = \"wallet.raw".to_string()).is_err());
+        assert!(parse_toml_structure("[Save]\nread_wallet = {a = 1}".to_string()).is_err());
+        assert!(parse_toml_structure("[Save]\nread_wallet = wallet raw".to_string()).is_err());
+    }
+}
@@ -1,18 +1,61 @@
 use super::{
     error_configuration::ErrorConfiguration,
     interface::Interface,
+    language::Language,
     parsable::{parse_structure, value_from_map, KeyValueMap, Parsable},
+    theme::Theme,
+    validation::{is_valid, KeyRule},
 };
 
 use std::cmp::PartialEq;
 
 const INTERFACE: &str = "interface";
+const RPC_PORT: &str = "rpc_port";
+const WEBSOCKET_PORT: &str = "websocket_port";
+const REST_PORT: &str = "rest_port";
+const PUBLISHER_PORT: &str = "publisher_port";
+const THEME: &str = "theme";
+const LANGUAGE: &str = "language";
 
 /// It represents all the data needed for the UI
 #[derive(Debug, PartialEq, Clone)]
 pub struct UIConfig {
     /// It's which interface will be used
     pub interface: Interface,
+
+    /// It's the local port of the RPC of the daemon. With the graphical interface, it connects
+    /// to the running daemon as an observer instead of running its own node
+    pub rpc_port: Option<u16>,
+
+    /// It's the local port where the daemon streams the notifications as JSON to the WebSocket clients
+    pub websocket_port: Option<u16>,
+
+    /// It's the local port of the read-only REST interface, which answers queries about the
+    /// blocks, the transactions and the unspent outputs of an address as JSON
+    pub rest_port: Option<u16>,
+
+    /// It's the local port where the new blocks and the received transactions are published to
+    /// the subscribed services
+    pub publisher_port: Option<u16>,
+
+    /// It's the theme of the graphical interface, following the one of the desktop if it's not given
+    pub theme: Theme,
+
+    /// It's the language of the texts of the interfaces, English if it's not given
+    pub language: Language,
+}
+
+impl UIConfig {
+    /// It's the keys that can be given in the structure
+    pub const RULES: &'static [KeyRule] = &[
+        KeyRule::value(INTERFACE, true, is_valid::<Interface>),
+        KeyRule::value(RPC_PORT, false, is_valid::<u16>),
+        KeyRule::value(WEBSOCKET_PORT, false, is_valid::<u16>),
+        KeyRule::value(REST_PORT, false, is_valid::<u16>),
+        KeyRule::value(PUBLISHER_PORT, false, is_valid::<u16>),
+        KeyRule::value(THEME, false, is_valid::<Theme>),
+        KeyRule::value(LANGUAGE, false, is_valid::<Language>),
+    ];
 }
 
 impl Parsable for UIConfig {
@@ -22,6 +65,12 @@ impl Parsable for UIConfig {
 
         Ok(UIConfig {
             interface: Interface::parse(INTERFACE, &map)?,
+            rpc_port: Option::<u16>::parse(RPC_PORT, &map)?,
+            websocket_port: Option::<u16>::parse(WEBSOCKET_PORT, &map)?,
+            rest_port: Option::<u16>::parse(REST_PORT, &map)?,
+            publisher_port: Option::<u16>::parse(PUBLISHER_PORT, &map)?,
+            theme: Option::<Theme>::parse(THEME, &map)?.unwrap_or_default(),
+            language: Option::<Language>::parse(LANGUAGE, &map)?.unwrap_or_default(),
         })
     }
 }
@@ -42,6 +91,12 @@ mod tests {
 
         let ui_log = UIConfig {
             interface: Interface::Gui,
+            rpc_port: None,
+            websocket_port: None,
+            rest_port: None,
+            publisher_port: None,
+            theme: Theme::System,
+            language: Language::English,
         };
 
         assert_eq!(Ok(ui_log), ui_result);
@@ -59,6 +114,12 @@ mod tests {
 
         let ui_log = UIConfig {
             interface: Interface::Gui,
+            rpc_port: None,
+            websocket_port: None,
+            rest_port: None,
+            publisher_port: None,
+            theme: Theme::System,
+            language: Language::English,
         };
 
         assert_eq!(Ok(ui_log), ui_result);
@@ -89,6 +150,12 @@ mod tests {
 
         let ui_log = UIConfig {
             interface: Interface::Gui,
+            rpc_port: None,
+            websocket_port: None,
+            rest_port: None,
+            publisher_port: None,
+            theme: Theme::System,
+            language: Language::English,
         };
 
         assert_eq!(Ok(ui_log), ui_result);
@@ -104,4 +171,154 @@ mod tests {
 
         assert_eq!(Err(ErrorConfiguration::ValueNotFound), ui_result);
     }
+
+    #[test]
+    fn test06_accept_input_with_rpc_port() {
+        let configuration = "UI {
+            interface = DAEMON
+            rpc_port = 18400
+        }";
+
+        let name = "UI";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let ui_result = UIConfig::parse(name, &map);
+
+        let ui_log = UIConfig {
+            interface: Interface::Daemon,
+            rpc_port: Some(18400),
+            websocket_port: None,
+            rest_port: None,
+            publisher_port: None,
+            theme: Theme::System,
+            language: Language::English,
+        };
+
+        assert_eq!(Ok(ui_log), ui_result);
+    }
+
+    #[test]
+    fn test07_accept_input_with_websocket_port() {
+        let configuration = "UI {
+            interface = DAEMON
+            websocket_port = 18401
+        }";
+
+        let name = "UI";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let ui_result = UIConfig::parse(name, &map);
+
+        let ui_log = UIConfig {
+            interface: Interface::Daemon,
+            rpc_port: None,
+            websocket_port: Some(18401),
+            rest_port: None,
+            publisher_port: None,
+            theme: Theme::System,
+            language: Language::English,
+        };
+
+        assert_eq!(Ok(ui_log), ui_result);
+    }
+
+    #[test]
+    fn test08_accept_input_with_rest_port() {
+        let configuration = "UI {
+            interface = TUI
+            rest_port = 18402
+        }";
+
+        let name = "UI";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let ui_result = UIConfig::parse(name, &map);
+
+        let ui_log = UIConfig {
+            interface: Interface::Tui,
+            rpc_port: None,
+            websocket_port: None,
+            rest_port: Some(18402),
+            publisher_port: None,
+            theme: Theme::System,
+            language: Language::English,
+        };
+
+        assert_eq!(Ok(ui_log), ui_result);
+    }
+
+    #[test]
+    fn test09_accept_input_with_publisher_port() {
+        let configuration = "UI {
+            interface = DAEMON
+            publisher_port = 28332
+        }";
+
+        let name = "UI";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let ui_result = UIConfig::parse(name, &map);
+
+        let ui_log = UIConfig {
+            interface: Interface::Daemon,
+            rpc_port: None,
+            websocket_port: None,
+            rest_port: None,
+            publisher_port: Some(28332),
+            theme: Theme::System,
+            language: Language::English,
+        };
+
+        assert_eq!(Ok(ui_log), ui_result);
+    }
+
+    #[test]
+    fn test10_accept_input_with_theme() {
+        let configuration = "UI {
+            interface = GUI
+            theme = dark
+        }";
+
+        let name = "UI";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let ui_result = UIConfig::parse(name, &map);
+
+        let ui_log = UIConfig {
+            interface: Interface::Gui,
+            rpc_port: None,
+            websocket_port: None,
+            rest_port: None,
+            publisher_port: None,
+            theme: Theme::Dark,
+            language: Language::English,
+        };
+
+        assert_eq!(Ok(ui_log), ui_result);
+    }
+
+    #[test]
+    fn test11_accept_input_with_language() {
+        let configuration = "UI {
+            interface = TUI
+            language = es
+        }";
+
+        let name = "UI";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let ui_result = UIConfig::parse(name, &map);
+
+        let ui_log = UIConfig {
+            interface: Interface::Tui,
+            rpc_port: None,
+            websocket_port: None,
+            rest_port: None,
+            publisher_port: None,
+            theme: Theme::System,
+            language: Language::Spanish,
+        };
+
+        assert_eq!(Ok(ui_log), ui_result);
+    }
 }
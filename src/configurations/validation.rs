@@ -0,0 +1,430 @@
+use super::parsable::{parse_duration, parse_structure, KeyValueMap};
+
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display, Error, Formatter},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const END_LINE: char = '\n';
+const ASSIGNMENT: char = '=';
+const OPEN_GROUP: char = '{';
+const CLOSE_GROUP: char = '}';
+const OPEN_LIST: char = '[';
+const CLOSE_LIST: char = ']';
+const LIST_SEPARATOR: char = ',';
+const PATH_SEPARATOR: char = '.';
+
+/// It's the most a timestamp can be in the future, given in seconds
+const MAX_FUTURE_TIMESTAMP: u64 = 2 * 60 * 60;
+
+/// It's a check of a value of the configuration, which explains why the value is not valid
+pub type Check = fn(&str) -> Result<(), String>;
+
+/// It represents what is expected for a key of a structure
+pub enum Rule {
+    /// The key has a value that must pass the check
+    Value(Check),
+
+    /// The key has an inner structure with its own keys
+    Structure(&'static [KeyRule]),
+}
+
+/// It represents a key that can appear in a structure of the configuration
+pub struct KeyRule {
+    pub name: &'static str,
+    pub required: bool,
+    pub rule: Rule,
+}
+
+impl KeyRule {
+    pub const fn value(name: &'static str, required: bool, check: Check) -> Self {
+        KeyRule {
+            name,
+            required,
+            rule: Rule::Value(check),
+        }
+    }
+
+    pub const fn structure(name: &'static str, required: bool, rules: &'static [KeyRule]) -> Self {
+        KeyRule {
+            name,
+            required,
+            rule: Rule::Structure(rules),
+        }
+    }
+}
+
+/// It represents a problem found in the configuration, given by the key where it was found (as
+/// `Structure.key`) and the line of the file, if it's known
+#[derive(PartialEq, Clone)]
+pub struct Diagnostic {
+    pub line: Option<usize>,
+    pub key: String,
+    pub message: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        if let Some(line) = self.line {
+            write!(f, "line {line}: ")?;
+        }
+        match self.key.is_empty() {
+            true => write!(f, "{}", self.message),
+            false => write!(f, "{}: {}", self.key, self.message),
+        }
+    }
+}
+
+impl Debug for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "{self}")
+    }
+}
+
+/// Validates the configuration before it's parsed, reporting every problem found instead of only
+/// the first one:
+///  * the lines that are not `key = value`, `key {` or `}`, and the keys given more than once
+///  * the keys that are not known and the required keys that are missing
+///  * the values that are not of the expected type or are out of range
+///
+/// The lines are only checked if the text of the configuration file is given, which must be in
+/// the legacy format, and in that case the diagnostics have the line where they were found
+pub fn validate_configuration(
+    map: &KeyValueMap,
+    rules: &[KeyRule],
+    text: Option<&str>,
+) -> Vec<Diagnostic> {
+    let (mut diagnostics, lines) = match text {
+        Some(text) => validate_lines(text),
+        None => (Vec::new(), HashMap::new()),
+    };
+
+    let mut structure_diagnostics: Vec<Diagnostic> = Vec::new();
+    validate_structure(map, rules, "", &mut structure_diagnostics);
+
+    for mut diagnostic in structure_diagnostics {
+        diagnostic.line = lines.get(&diagnostic.key).copied();
+        diagnostics.push(diagnostic);
+    }
+
+    diagnostics
+}
+
+/// Checks the keys of the structure against the rules, and the inner structures recursively
+fn validate_structure(
+    map: &KeyValueMap,
+    rules: &[KeyRule],
+    path: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let mut keys: Vec<&String> = map.keys().collect();
+    keys.sort();
+
+    for key in keys {
+        let full_key = join_path(path, key);
+        let value = &map[key];
+
+        match rules.iter().find(|rule| rule.name == key) {
+            Some(KeyRule {
+                rule: Rule::Value(check),
+                ..
+            }) => {
+                if let Err(message) = check(value) {
+                    diagnostics.push(diagnostic(full_key, message));
+                }
+            }
+            Some(KeyRule {
+                rule: Rule::Structure(inner_rules),
+                ..
+            }) => match parse_structure(value.to_string()) {
+                Ok(inner_map) if value.contains(ASSIGNMENT) => {
+                    validate_structure(&inner_map, inner_rules, &full_key, diagnostics)
+                }
+                _ => diagnostics.push(diagnostic(full_key, "must be a structure".to_string())),
+            },
+            None => diagnostics.push(diagnostic(full_key, "is not a known key".to_string())),
+        }
+    }
+
+    for rule in rules {
+        if rule.required && !map.contains_key(rule.name) {
+            diagnostics.push(diagnostic(
+                join_path(path, rule.name),
+                "is missing".to_string(),
+            ));
+        }
+    }
+}
+
+/// Checks every line of a configuration in the legacy format, returning the problems found and
+/// the line where each key is given
+fn validate_lines(text: &str) -> (Vec<Diagnostic>, HashMap<String, usize>) {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    let mut lines: HashMap<String, usize> = HashMap::new();
+    let mut structures: Vec<(String, usize)> = Vec::new();
+
+    for (index, line) in text.split(END_LINE).enumerate() {
+        let number = index + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let path = match structures.last() {
+            Some((path, _)) => path.clone(),
+            None => String::new(),
+        };
+
+        let (key, opens_structure) = if line == CLOSE_GROUP.to_string() {
+            if structures.pop().is_none() {
+                diagnostics.push(line_diagnostic(number, "there is no structure to close"));
+            }
+            continue;
+        } else if let Some(key) = line.strip_suffix(OPEN_GROUP) {
+            (key.trim(), true)
+        } else if let Some((key, _)) = line.split_once(ASSIGNMENT) {
+            (key.trim(), false)
+        } else {
+            diagnostics.push(line_diagnostic(
+                number,
+                "expected `key = value`, `key {` or `}`",
+            ));
+            continue;
+        };
+
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            diagnostics.push(line_diagnostic(number, "the key is not valid"));
+            continue;
+        }
+
+        let full_key = join_path(&path, key);
+        match lines.get(&full_key) {
+            Some(first_line) => diagnostics.push(Diagnostic {
+                line: Some(number),
+                key: full_key.clone(),
+                message: format!("is already given in line {first_line}"),
+            }),
+            None => {
+                lines.insert(full_key.clone(), number);
+            }
+        }
+
+        if opens_structure {
+            structures.push((full_key, number));
+        }
+    }
+
+    for (key, line) in structures {
+        diagnostics.push(Diagnostic {
+            line: Some(line),
+            key,
+            message: "is not closed".to_string(),
+        });
+    }
+
+    (diagnostics, lines)
+}
+
+fn join_path(path: &str, key: &str) -> String {
+    match path.is_empty() {
+        true => key.to_string(),
+        false => format!("{path}{PATH_SEPARATOR}{key}"),
+    }
+}
+
+fn diagnostic(key: String, message: String) -> Diagnostic {
+    Diagnostic {
+        line: None,
+        key,
+        message,
+    }
+}
+
+fn line_diagnostic(line: usize, message: &str) -> Diagnostic {
+    Diagnostic {
+        line: Some(line),
+        key: String::new(),
+        message: message.to_string(),
+    }
+}
+
+/// Checks that the value can be parsed as the given type
+pub fn is_valid<T: FromStr>(value: &str) -> Result<(), String> {
+    match value.parse::<T>() {
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!("{:?} is not a valid {}", value, type_name::<T>())),
+    }
+}
+
+/// Checks that the value is a list given by `[a, b, ...]` where each element can be parsed as the
+/// given type
+pub fn is_valid_list<T: FromStr>(value: &str) -> Result<(), String> {
+    let elements = match value
+        .strip_prefix(OPEN_LIST)
+        .and_then(|value| value.strip_suffix(CLOSE_LIST))
+    {
+        Some(elements) => elements,
+        None => return Err(format!("{:?} is not a list given by [a, b, ...]", value)),
+    };
+
+    elements
+        .split(LIST_SEPARATOR)
+        .map(str::trim)
+        .filter(|element| !element.is_empty())
+        .try_for_each(is_valid::<T>)
+}
+
+/// Checks that the value is a number greater than zero
+pub fn is_positive(value: &str) -> Result<(), String> {
+    match value.parse::<u64>() {
+        Ok(0) => Err("must be greater than 0".to_string()),
+        Ok(_) => Ok(()),
+        Err(_) => is_valid::<u64>(value),
+    }
+}
+
+/// Checks that the value is a duration greater than zero, given in seconds or with its unit
+pub fn is_positive_duration(value: &str) -> Result<(), String> {
+    match parse_duration(value) {
+        Some(duration) if duration.is_zero() => Err("must be greater than 0".to_string()),
+        Some(_) => Ok(()),
+        None => Err(format!(
+            "{:?} is not a valid duration, as 30, 500ms, 30s, 5m or 1h",
+            value
+        )),
+    }
+}
+
+/// Checks that the value is a Unix timestamp that is not in the future
+pub fn is_valid_timestamp(value: &str) -> Result<(), String> {
+    let timestamp = match value.parse::<u32>() {
+        Ok(timestamp) => timestamp as u64,
+        Err(_) => return Err(format!("{:?} is not a valid timestamp", value)),
+    };
+
+    let now = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(now) => now.as_secs(),
+        Err(_) => return Ok(()),
+    };
+
+    match timestamp > now + MAX_FUTURE_TIMESTAMP {
+        true => Err(format!("the timestamp {timestamp} is in the future")),
+        false => Ok(()),
+    }
+}
+
+/// Returns the name of the type without its path
+fn type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    match name.rsplit_once("::") {
+        Some((_, name)) => name,
+        None => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INNER_RULES: &[KeyRule] = &[
+        KeyRule::value("seed", true, is_valid::<String>),
+        KeyRule::value("port", false, is_valid::<u16>),
+    ];
+
+    const RULES: &[KeyRule] = &[KeyRule::structure(
+        "Server",
+        true,
+        &[
+            KeyRule::value("peer_count_max", true, is_positive),
+            KeyRule::value("timestamp", false, is_valid_timestamp),
+            KeyRule::value("peers", false, is_valid_list::<u16>),
+            KeyRule::structure("dns_seeder", true, INNER_RULES),
+        ],
+    )];
+
+    fn validate(text: &str) -> Vec<String> {
+        let map = parse_structure(text.to_string()).unwrap();
+        validate_configuration(&map, RULES, Some(text))
+            .iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test01_valid_configuration_has_no_diagnostics() {
+        let text = "Server {
+            peer_count_max = 8
+            timestamp = 1685264160
+            peers = [18333, 18444]
+            dns_seeder {
+                seed = seed.testnet.bitcoin.sprovoost.nl
+            }
+        }";
+
+        assert!(validate(text).is_empty());
+    }
+
+    #[test]
+    fn test02_unknown_and_missing_keys_are_reported_by_name() {
+        let text = "Server {
+            peer_count = 8
+            dns_seeder {
+                port = 18333
+            }
+        }";
+
+        assert_eq!(
+            validate(text),
+            vec![
+                "Server.dns_seeder.seed: is missing".to_string(),
+                "line 2: Server.peer_count: is not a known key".to_string(),
+                "Server.peer_count_max: is missing".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test03_invalid_values_are_reported_with_their_line() {
+        let text = "Server {
+            peer_count_max = 0
+            timestamp = 4000000000
+            peers = [18333, port]
+            dns_seeder {
+                seed = seed.testnet.bitcoin.sprovoost.nl
+                port = 70000
+            }
+        }";
+
+        assert_eq!(
+            validate(text),
+            vec![
+                "line 7: Server.dns_seeder.port: \"70000\" is not a valid u16".to_string(),
+                "line 2: Server.peer_count_max: must be greater than 0".to_string(),
+                "line 4: Server.peers: \"port\" is not a valid u16".to_string(),
+                "line 3: Server.timestamp: the timestamp 4000000000 is in the future".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test04_malformed_lines_and_duplicated_keys_are_reported() {
+        let text = "Server {
+            peer_count_max = 8
+            peer_count_max = 9
+            dns_seeder
+            dns_seeder {
+                seed = seed.testnet.bitcoin.sprovoost.nl
+            }
+        }";
+
+        assert_eq!(
+            validate(text)[..2],
+            [
+                "line 3: Server.peer_count_max: is already given in line 2".to_string(),
+                "line 4: expected `key = value`, `key {` or `}`".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use super::error_connection::ErrorConnection;
+
+use std::{
+    cmp::PartialEq,
+    io::ErrorKind,
+    net::{SocketAddr, TcpStream},
+    time::Duration,
+};
+
+/// It's the most time a read waits before checking if the connection has to stop
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// It's the timeouts applied to every stream with a peer or a client
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ConnectionTimeouts {
+    /// It's the most time waited to establish a connection
+    pub connect: Duration,
+
+    /// It's the most time waited without receiving anything from the other end
+    pub read: Duration,
+
+    /// It's the most time waited to send a message
+    pub write: Duration,
+}
+
+impl ConnectionTimeouts {
+    /// Opens a stream to the given address, waiting at most the connect timeout, and applies the other timeouts to it
+    ///
+    /// ### Error
+    ///  * `ErrorConnection::ErrorConnectTimeout`: It will appear when the connection is not established before the connect timeout
+    ///  * `ErrorConnection::ErrorCannotConnectToAddress`: It will appear when the connection is refused
+    ///  * `ErrorConnection::ErrorCannotSetStreamProperties`: It will appear when the timeouts cannot be applied to the stream
+    pub fn connect(&self, address: &SocketAddr) -> Result<TcpStream, ErrorConnection> {
+        let stream = match TcpStream::connect_timeout(address, self.connect) {
+            Ok(stream) => stream,
+            Err(error) => {
+                return Err(match error.kind() {
+                    ErrorKind::TimedOut | ErrorKind::WouldBlock => {
+                        ErrorConnection::ErrorConnectTimeout
+                    }
+                    _ => ErrorConnection::ErrorCannotConnectToAddress,
+                })
+            }
+        };
+
+        self.configure(&stream)?;
+        Ok(stream)
+    }
+
+    /// Applies the read and write timeouts to the stream and disables the Nagle's algorithm, so the small
+    /// messages are sent right away. The reads wake up at least every second to check if the connection
+    /// has to stop, so the read timeout is counted while listening
+    ///
+    /// ### Error
+    ///  * `ErrorConnection::ErrorCannotSetStreamProperties`: It will appear when the timeouts cannot be applied to the stream
+    pub fn configure(&self, stream: &TcpStream) -> Result<(), ErrorConnection> {
+        if stream
+            .set_read_timeout(Some(self.read.min(POLL_INTERVAL)))
+            .is_err()
+            || stream.set_write_timeout(Some(self.write)).is_err()
+            || stream.set_nodelay(true).is_err()
+        {
+            return Err(ErrorConnection::ErrorCannotSetStreamProperties);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::{IpAddr, Ipv4Addr, TcpListener};
+
+    fn timeouts() -> ConnectionTimeouts {
+        ConnectionTimeouts {
+            connect: Duration::from_secs(1),
+            read: Duration::from_secs(10),
+            write: Duration::from_secs(5),
+        }
+    }
+
+    #[test]
+    fn test01_timeouts_are_applied_to_the_stream() {
+        let listener =
+            TcpListener::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0)).unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let stream = timeouts().connect(&address).unwrap();
+
+        assert_eq!(stream.read_timeout().unwrap(), Some(POLL_INTERVAL));
+        assert_eq!(
+            stream.write_timeout().unwrap(),
+            Some(Duration::from_secs(5))
+        );
+        assert!(stream.nodelay().unwrap());
+    }
+
+    #[test]
+    fn test02_refused_connection_is_not_a_timeout() {
+        let listener =
+            TcpListener::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0)).unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+
+        assert_eq!(
+            Err(ErrorConnection::ErrorCannotConnectToAddress),
+            timeouts().connect(&address).map(|_| ())
+        );
+    }
+}
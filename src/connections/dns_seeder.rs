@@ -3,47 +3,135 @@ use super::error_connection::ErrorConnection;
 use crate::configurations::{
     error_configuration::ErrorConfiguration,
     parsable::{parse_structure, value_from_map, KeyValueMap, Parsable},
+    validation::{is_positive_duration, is_valid, is_valid_list, KeyRule},
 };
 
 use std::{
     cmp::PartialEq,
-    net::{SocketAddr, ToSocketAddrs},
+    collections::HashSet,
+    net::{IpAddr, Ipv4Addr, SocketAddr, ToSocketAddrs},
+    sync::mpsc::channel,
+    thread,
+    time::{Duration, Instant},
 };
 
 const SEED: &str = "seed";
+const SEEDS: &str = "seeds";
 const PORT: &str = "port";
+const TIMEOUT: &str = "timeout";
+const FIXED_PEERS: &str = "fixed_peers";
 
-/// It connects to a DNS server and gets the peers
+const DEFAULT_TIMEOUT_SECONDS: u64 = 5;
+
+/// It connects to a list of DNS servers and gets the peers. When none of them answers,
+/// it falls back to a fixed list of peers. Both the seeds and the fixed peers can have their
+/// own port, otherwise the port of the seeder is used
 #[derive(Debug, PartialEq, Clone)]
 pub struct DNSSeeder {
-    dns_addr: String,
+    dns_addrs: Vec<String>,
     port_number: u16,
+    timeout: Duration,
+    fixed_peers: Vec<SocketAddr>,
 }
 
 impl DNSSeeder {
+    /// It's the keys that can be given in the structure. At least one of `seed` and `seeds` must be given
+    pub const RULES: &'static [KeyRule] = &[
+        KeyRule::value(SEED, false, is_valid::<String>),
+        KeyRule::value(SEEDS, false, is_valid_list::<String>),
+        KeyRule::value(PORT, true, is_valid::<u16>),
+        KeyRule::value(TIMEOUT, false, is_positive_duration),
+        KeyRule::value(FIXED_PEERS, false, is_valid_list::<String>),
+    ];
+
     pub fn new(dns_addr: &str, port_number: u16) -> Self {
+        Self::with_seeds(vec![dns_addr.to_string()], port_number)
+    }
+
+    pub fn with_seeds(dns_addrs: Vec<String>, port_number: u16) -> Self {
         DNSSeeder {
-            dns_addr: dns_addr.to_string(),
+            dns_addrs,
             port_number,
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECONDS),
+            fixed_peers: vec![SocketAddr::new(
+                IpAddr::V4(Ipv4Addr::LOCALHOST),
+                port_number,
+            )],
         }
     }
 
-    /// It return the peers that the DNS server has
+    /// It return the peers that the DNS servers have, asking all of them at the same time and
+    /// waiting each one at most the timeout. The repeated peers are only returned once and if
+    /// no DNS server answers the fixed peers are returned
     ///
     /// ### Error
-    ///  * `ErrorConnection::ErrorInvalidIPOrPortNumber`: It will appear if the IP or the port number its not valid
+    ///  * `ErrorConnection::ErrorInvalidIPOrPortNumber`: It will appear if no DNS server answers and there are no fixed peers
     pub fn discover_peers(&self) -> Result<Vec<SocketAddr>, ErrorConnection> {
+        let (sender, receiver) = channel::<Vec<SocketAddr>>();
+
+        for dns_addr in self.dns_addrs.iter() {
+            let sender = sender.clone();
+            let dns_addr = dns_addr.clone();
+            let port_number = self.port_number;
+            thread::spawn(move || {
+                if let Some(peer_addrs) = Self::resolve(&dns_addr, port_number) {
+                    let _ = sender.send(peer_addrs);
+                }
+            });
+        }
+        drop(sender);
+
+        let deadline = Instant::now() + self.timeout;
+        let mut seen: HashSet<SocketAddr> = HashSet::new();
         let mut peer_addrs: Vec<SocketAddr> = Vec::new();
 
-        if let Ok(iter) = (self.dns_addr.clone(), self.port_number).to_socket_addrs() {
-            for peer_addr in iter {
-                peer_addrs.push(peer_addr);
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match receiver.recv_timeout(remaining) {
+                Ok(addresses) => {
+                    for peer_addr in addresses {
+                        if seen.insert(peer_addr) {
+                            peer_addrs.push(peer_addr);
+                        }
+                    }
+                }
+                Err(_) => break,
             }
-        } else {
-            return Err(ErrorConnection::ErrorInvalidIPOrPortNumber);
         }
 
-        Ok(peer_addrs)
+        if !peer_addrs.is_empty() {
+            return Ok(peer_addrs);
+        }
+
+        match self.fixed_peers.is_empty() {
+            true => Err(ErrorConnection::ErrorInvalidIPOrPortNumber),
+            false => Ok(self.fixed_peers.clone()),
+        }
+    }
+
+    /// Resolves a seed given by `host:port` or only by the host, in which case the given port is used
+    fn resolve(dns_addr: &str, port_number: u16) -> Option<Vec<SocketAddr>> {
+        if let Ok(iter) = dns_addr.to_socket_addrs() {
+            return Some(iter.collect());
+        }
+
+        match (dns_addr, port_number).to_socket_addrs() {
+            Ok(iter) => Some(iter.collect()),
+            Err(_) => None,
+        }
+    }
+
+    /// Parses a peer given by `ip:port` (`[ip]:port` for IPv6) or only by the ip, in which case
+    /// the given port is used
+    fn parse_peer(peer: &str, port_number: u16) -> Option<SocketAddr> {
+        if let Ok(peer_addr) = peer.parse::<SocketAddr>() {
+            return Some(peer_addr);
+        }
+
+        match peer.parse::<IpAddr>() {
+            Ok(ip) => Some(SocketAddr::new(ip, port_number)),
+            Err(_) => None,
+        }
     }
 }
 
@@ -52,9 +140,37 @@ impl Parsable for DNSSeeder {
         let structure = value_from_map(name.to_string(), map)?;
         let map = parse_structure(structure)?;
 
+        let mut dns_addrs: Vec<String> = Vec::new();
+        if let Some(seed) = Option::<String>::parse(SEED, &map)? {
+            dns_addrs.push(seed);
+        }
+        if let Some(seeds) = Option::<Vec<String>>::parse(SEEDS, &map)? {
+            dns_addrs.extend(seeds);
+        }
+
+        if dns_addrs.is_empty() {
+            return Err(ErrorConfiguration::ValueNotFound);
+        }
+
+        let port_number = u16::parse(PORT, &map)?;
+        let timeout = Option::<Duration>::parse(TIMEOUT, &map)?
+            .unwrap_or(Duration::from_secs(DEFAULT_TIMEOUT_SECONDS));
+        let fixed_peers = match Option::<Vec<String>>::parse(FIXED_PEERS, &map)? {
+            Some(fixed_peers) => fixed_peers
+                .iter()
+                .filter_map(|peer| Self::parse_peer(peer, port_number))
+                .collect(),
+            None => vec![SocketAddr::new(
+                IpAddr::V4(Ipv4Addr::LOCALHOST),
+                port_number,
+            )],
+        };
+
         Ok(DNSSeeder {
-            dns_addr: String::parse(SEED, &map)?,
-            port_number: u16::parse(PORT, &map)?,
+            dns_addrs,
+            port_number,
+            timeout,
+            fixed_peers,
         })
     }
 }
@@ -64,6 +180,8 @@ mod tests {
 
     use super::*;
 
+    use std::net::Ipv6Addr;
+
     #[test]
     fn test01_accept_valid_input() {
         let configuration = "dns_seeder {
@@ -76,11 +194,128 @@ mod tests {
 
         let dns_result = DNSSeeder::parse(name, &map);
 
+        let expected_dns = DNSSeeder::new("seed.testnet.bitcoin.sprovoost.nl", 18333);
+
+        assert_eq!(Ok(expected_dns), dns_result);
+    }
+
+    #[test]
+    fn test02_accept_a_list_of_seeds() {
+        let configuration = "dns_seeder {
+            seed = seed.testnet.bitcoin.sprovoost.nl
+            seeds = [testnet-seed.bitcoin.jonasschnelli.ch, seed.tbtc.petertodd.org]
+            port = 18333
+            timeout = 2
+            fixed_peers = [127.0.0.1, 127.0.0.2]
+        }";
+
+        let name = "dns_seeder";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let dns_result = DNSSeeder::parse(name, &map);
+
         let expected_dns = DNSSeeder {
-            dns_addr: "seed.testnet.bitcoin.sprovoost.nl".to_string(),
+            dns_addrs: vec![
+                "seed.testnet.bitcoin.sprovoost.nl".to_string(),
+                "testnet-seed.bitcoin.jonasschnelli.ch".to_string(),
+                "seed.tbtc.petertodd.org".to_string(),
+            ],
             port_number: 18333,
+            timeout: Duration::from_secs(2),
+            fixed_peers: vec![
+                SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 18333),
+                SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2)), 18333),
+            ],
         };
 
         assert_eq!(Ok(expected_dns), dns_result);
     }
+
+    #[test]
+    fn test03_does_not_accept_input_without_seeds() {
+        let configuration = "dns_seeder {
+            port = 18333
+        }";
+
+        let name = "dns_seeder";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let dns_result = DNSSeeder::parse(name, &map);
+
+        assert_eq!(Err(ErrorConfiguration::ValueNotFound), dns_result);
+    }
+
+    #[test]
+    fn test04_repeated_peers_are_returned_once() {
+        let dns_seeder = DNSSeeder::with_seeds(
+            vec!["127.0.0.2".to_string(), "127.0.0.2".to_string()],
+            18333,
+        );
+
+        let peers = dns_seeder.discover_peers().unwrap();
+
+        assert_eq!(
+            vec![SocketAddr::new(
+                IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2)),
+                18333
+            )],
+            peers
+        );
+    }
+
+    #[test]
+    fn test05_falls_back_to_the_fixed_peers() {
+        let mut dns_seeder = DNSSeeder::with_seeds(vec!["not a seed".to_string()], 18333);
+        dns_seeder.timeout = Duration::from_secs(1);
+
+        let peers = dns_seeder.discover_peers().unwrap();
+
+        assert_eq!(
+            vec![SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 18333)],
+            peers
+        );
+
+        dns_seeder.fixed_peers = Vec::new();
+        assert_eq!(
+            Err(ErrorConnection::ErrorInvalidIPOrPortNumber),
+            dns_seeder.discover_peers()
+        );
+    }
+
+    #[test]
+    fn test06_accept_ipv6_peers_with_their_own_port() {
+        let configuration = "dns_seeder {
+            seeds = [127.0.0.2:18444, ::1]
+            port = 18333
+            fixed_peers = [::1, [2001:db8::1]:18444, 127.0.0.1:8333]
+        }";
+
+        let name = "dns_seeder";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let dns_seeder = DNSSeeder::parse(name, &map).unwrap();
+
+        assert_eq!(
+            vec![
+                SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 18333),
+                SocketAddr::new(
+                    IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)),
+                    18444
+                ),
+                SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 8333),
+            ],
+            dns_seeder.fixed_peers
+        );
+
+        let mut peers = dns_seeder.discover_peers().unwrap();
+        peers.sort();
+
+        assert_eq!(
+            vec![
+                SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2)), 18444),
+                SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 18333),
+            ],
+            peers
+        );
+    }
 }
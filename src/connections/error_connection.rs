@@ -1,3 +1,5 @@
+use crate::serialization::error_serialization::ErrorSerialization;
+
 /// It represents all posible errors that can occur in the connection to a peer
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ErrorConnection {
@@ -15,4 +17,32 @@ pub enum ErrorConnection {
 
     /// It will appear when we are unable to set the properties of a stream
     ErrorCannotSetStreamProperties,
+
+    /// It will appear when the user agent does not follow the format of the BIP14
+    ErrorInvalidUserAgent,
+
+    /// It will appear when the connection is not established before the connect timeout
+    ErrorConnectTimeout,
+
+    /// It will appear when nothing is received from the other end before the read timeout
+    ErrorReadTimeout,
+
+    /// It will appear when a message cannot be sent before the write timeout
+    ErrorWriteTimeout,
+
+    /// It will appear when the other end breaks the rules of the protocol, so it must be disconnected
+    ErrorMisbehavingPeer,
+}
+
+impl From<ErrorSerialization> for ErrorConnection {
+    fn from(value: ErrorSerialization) -> Self {
+        match value {
+            ErrorSerialization::ErrorInSerialization(_) | ErrorSerialization::ErrorWhileWriting => {
+                ErrorConnection::ErrorCannotSendMessage
+            }
+            ErrorSerialization::InformationNotReady => ErrorConnection::ErrorReadTimeout,
+            ErrorSerialization::PayloadTooLarge(_, _) => ErrorConnection::ErrorMisbehavingPeer,
+            _ => ErrorConnection::ErrorCannotReceiveMessage,
+        }
+    }
 }
@@ -7,12 +7,16 @@ use std::{cmp::PartialEq, str::FromStr};
 
 const BLOCKS_FIRST: &str = "BlocksFirst";
 const HEADER_FIRST: &str = "HeaderFirst";
+const LIGHT_CLIENT: &str = "LightClient";
+const COMPACT_FILTERS: &str = "CompactFilters";
 
 /// It's the representation of Initial Block Download method
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum IBDMethod {
     BlocksFirst,
     HeaderFirst,
+    LightClient,
+    CompactFilters,
 }
 
 impl FromStr for IBDMethod {
@@ -22,6 +26,8 @@ impl FromStr for IBDMethod {
         match value {
             BLOCKS_FIRST => Ok(IBDMethod::BlocksFirst),
             HEADER_FIRST => Ok(IBDMethod::HeaderFirst),
+            LIGHT_CLIENT => Ok(IBDMethod::LightClient),
+            COMPACT_FILTERS => Ok(IBDMethod::CompactFilters),
             _ => Err(ErrorConfiguration::ErrorCantParseValue(format!(
                 "ibd method of {:?}",
                 value
@@ -57,4 +63,28 @@ mod tests {
 
         assert_eq!(Ok(expected_ibd_methods), ibd_methods_result);
     }
+
+    #[test]
+    fn test02_accept_light_client() {
+        let configuration = "ibd_methods = LightClient";
+
+        let name = "ibd_methods";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let ibd_methods_result = IBDMethod::parse(name, &map);
+
+        assert_eq!(Ok(IBDMethod::LightClient), ibd_methods_result);
+    }
+
+    #[test]
+    fn test03_accept_compact_filters() {
+        let configuration = "ibd_methods = CompactFilters";
+
+        let name = "ibd_methods";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let ibd_methods_result = IBDMethod::parse(name, &map);
+
+        assert_eq!(Ok(IBDMethod::CompactFilters), ibd_methods_result);
+    }
 }
@@ -13,3 +13,13 @@ pub mod socket_conversion;
 pub mod type_identifier;
 
 pub mod network_ip_address;
+
+pub mod user_agent;
+
+pub mod network;
+
+pub mod onion_address;
+
+pub mod socks5;
+
+pub mod connection_timeouts;
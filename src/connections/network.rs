@@ -0,0 +1,293 @@
+use crate::{
+    block_structure::{
+        block::Block,
+        block_header::{
+            BlockHeader, GENESIS_BLOCK_VERSION, GENESIS_MERKLE_ROOT_HASH,
+            GENESIS_PREVIOUS_BLOCK_HEADER_HASH, GENESIS_TRANSACTION_COUNT,
+        },
+        compact256::Compact256,
+        soft_fork::SoftForkHeights,
+    },
+    configurations::{
+        error_configuration::ErrorConfiguration,
+        parsable::{value_from_map, KeyValueMap, Parsable},
+    },
+    messages::{compact_size::CompactSize, message_header::MagicType},
+};
+
+use std::{cmp::PartialEq, fmt::Display, str::FromStr};
+
+const MAINNET: &str = "mainnet";
+const TESTNET: &str = "testnet";
+const SIGNET: &str = "signet";
+const REGTEST: &str = "regtest";
+
+const MAINNET_MAGIC_NUMBERS: MagicType = [0xf9, 0xbe, 0xb4, 0xd9];
+const TESTNET_MAGIC_NUMBERS: MagicType = [0x0b, 0x11, 0x09, 0x07];
+const SIGNET_MAGIC_NUMBERS: MagicType = [0x0a, 0x03, 0xcf, 0x40];
+const REGTEST_MAGIC_NUMBERS: MagicType = [0xfa, 0xbf, 0xb5, 0xda];
+
+const MAINNET_PORT: u16 = 8333;
+const TESTNET_PORT: u16 = 18333;
+const SIGNET_PORT: u16 = 38333;
+const REGTEST_PORT: u16 = 18444;
+
+const MAINNET_ADDRESS_VERSION_BYTE: u8 = 0x00;
+const TESTNET_ADDRESS_VERSION_BYTE: u8 = 0x6f;
+
+const MAINNET_SCRIPT_VERSION_BYTE: u8 = 0x05;
+const TESTNET_SCRIPT_VERSION_BYTE: u8 = 0xc4;
+
+const MAINNET_WIF_VERSION_BYTE: u8 = 0x80;
+const TESTNET_WIF_VERSION_BYTE: u8 = 0xef;
+
+const MAINNET_SOFT_FORK_HEIGHTS: SoftForkHeights = SoftForkHeights {
+    bip34: 227_931,
+    bip66: 363_725,
+    bip65: 388_381,
+};
+const TESTNET_SOFT_FORK_HEIGHTS: SoftForkHeights = SoftForkHeights {
+    bip34: 21_111,
+    bip66: 330_776,
+    bip65: 581_885,
+};
+const ALWAYS_ACTIVE_SOFT_FORK_HEIGHTS: SoftForkHeights = SoftForkHeights {
+    bip34: 1,
+    bip66: 1,
+    bip65: 1,
+};
+
+const MAINNET_GENESIS_TIME: u32 = 0x495fab29;
+const MAINNET_GENESIS_N_BITS: u32 = 0x1d00ffff;
+const MAINNET_GENESIS_NONCE: u32 = 0x7c2bac1d;
+
+const SIGNET_GENESIS_TIME: u32 = 0x5f4d8f00;
+const SIGNET_GENESIS_N_BITS: u32 = 0x1e0377ae;
+const SIGNET_GENESIS_NONCE: u32 = 0x0322d28a;
+
+const REGTEST_GENESIS_TIME: u32 = 0x4d49e5da;
+const REGTEST_GENESIS_N_BITS: u32 = 0x207fffff;
+const REGTEST_GENESIS_NONCE: u32 = 0x00000002;
+
+/// It's the representation of the Bitcoin network the node connects to. It bundles the values
+/// that differ between them: the magic numbers, the default port, the address version byte, the heights
+/// of the soft forks and the genesis block
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Signet,
+    Regtest,
+}
+
+impl Network {
+    /// Returns the magic numbers that start every message in the network
+    pub fn magic_numbers(&self) -> MagicType {
+        match self {
+            Network::Mainnet => MAINNET_MAGIC_NUMBERS,
+            Network::Testnet => TESTNET_MAGIC_NUMBERS,
+            Network::Signet => SIGNET_MAGIC_NUMBERS,
+            Network::Regtest => REGTEST_MAGIC_NUMBERS,
+        }
+    }
+
+    /// Returns the port the nodes of the network listen to by default
+    pub fn default_port(&self) -> u16 {
+        match self {
+            Network::Mainnet => MAINNET_PORT,
+            Network::Testnet => TESTNET_PORT,
+            Network::Signet => SIGNET_PORT,
+            Network::Regtest => REGTEST_PORT,
+        }
+    }
+
+    /// Returns the byte that prefixes the P2PKH addresses of the network
+    pub fn address_version_byte(&self) -> u8 {
+        match self {
+            Network::Mainnet => MAINNET_ADDRESS_VERSION_BYTE,
+            Network::Testnet | Network::Signet | Network::Regtest => TESTNET_ADDRESS_VERSION_BYTE,
+        }
+    }
+
+    /// Returns the byte that prefixes the P2SH addresses of the network
+    pub fn script_version_byte(&self) -> u8 {
+        match self {
+            Network::Mainnet => MAINNET_SCRIPT_VERSION_BYTE,
+            Network::Testnet | Network::Signet | Network::Regtest => TESTNET_SCRIPT_VERSION_BYTE,
+        }
+    }
+
+    /// Returns the byte that prefixes the private keys of the network written in WIF
+    pub fn wif_version_byte(&self) -> u8 {
+        match self {
+            Network::Mainnet => MAINNET_WIF_VERSION_BYTE,
+            Network::Testnet | Network::Signet | Network::Regtest => TESTNET_WIF_VERSION_BYTE,
+        }
+    }
+
+    /// Returns the heights from which the soft forks BIP34, BIP66 and BIP65 are enforced in the network
+    pub fn soft_fork_heights(&self) -> SoftForkHeights {
+        match self {
+            Network::Mainnet => MAINNET_SOFT_FORK_HEIGHTS,
+            Network::Testnet => TESTNET_SOFT_FORK_HEIGHTS,
+            Network::Signet | Network::Regtest => ALWAYS_ACTIVE_SOFT_FORK_HEIGHTS,
+        }
+    }
+
+    /// Returns the header of the first block of the network
+    pub fn genesis_block_header(&self) -> BlockHeader {
+        let (time, n_bits, nonce) = match self {
+            Network::Testnet => return BlockHeader::generate_genesis_block_header(),
+            Network::Mainnet => (
+                MAINNET_GENESIS_TIME,
+                MAINNET_GENESIS_N_BITS,
+                MAINNET_GENESIS_NONCE,
+            ),
+            Network::Signet => (
+                SIGNET_GENESIS_TIME,
+                SIGNET_GENESIS_N_BITS,
+                SIGNET_GENESIS_NONCE,
+            ),
+            Network::Regtest => (
+                REGTEST_GENESIS_TIME,
+                REGTEST_GENESIS_N_BITS,
+                REGTEST_GENESIS_NONCE,
+            ),
+        };
+
+        BlockHeader::new(
+            GENESIS_BLOCK_VERSION,
+            GENESIS_PREVIOUS_BLOCK_HEADER_HASH,
+            GENESIS_MERKLE_ROOT_HASH,
+            time,
+            Compact256::from(n_bits),
+            nonce,
+            CompactSize::new(GENESIS_TRANSACTION_COUNT),
+        )
+    }
+
+    /// Returns the first block of the network
+    pub fn genesis_block(&self) -> Block {
+        Block::new(self.genesis_block_header())
+    }
+}
+
+impl Default for Network {
+    /// The network used by the node before it could be configured
+    fn default() -> Self {
+        Network::Testnet
+    }
+}
+
+impl FromStr for Network {
+    type Err = ErrorConfiguration;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            MAINNET => Ok(Network::Mainnet),
+            TESTNET => Ok(Network::Testnet),
+            SIGNET => Ok(Network::Signet),
+            REGTEST => Ok(Network::Regtest),
+            _ => Err(ErrorConfiguration::ErrorCantParseValue(format!(
+                "network of {:?}",
+                value
+            ))),
+        }
+    }
+}
+
+impl Display for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Network::Mainnet => write!(f, "{MAINNET}"),
+            Network::Testnet => write!(f, "{TESTNET}"),
+            Network::Signet => write!(f, "{SIGNET}"),
+            Network::Regtest => write!(f, "{REGTEST}"),
+        }
+    }
+}
+
+impl Parsable for Network {
+    fn parse(name: &str, map: &KeyValueMap) -> Result<Self, ErrorConfiguration> {
+        let value = value_from_map(name.to_string(), map)?;
+        value.parse::<Network>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::configurations::parsable::parse_structure;
+
+    #[test]
+    fn test01_accept_valid_input() {
+        let configuration = "network = testnet";
+
+        let name = "network";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let network_result = Network::parse(name, &map);
+
+        assert_eq!(Ok(Network::Testnet), network_result);
+    }
+
+    #[test]
+    fn test02_does_not_accept_unknown_networks() {
+        let configuration = "network = testnet4";
+
+        let name = "network";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let network_result = Network::parse(name, &map);
+
+        assert!(network_result.is_err());
+    }
+
+    #[test]
+    fn test03_testnet_keeps_the_values_used_by_the_node() {
+        let network = Network::Testnet;
+
+        assert_eq!(network.magic_numbers(), [11, 17, 9, 7]);
+        assert_eq!(network.default_port(), 18333);
+        assert_eq!(network.address_version_byte(), 0x6f);
+        assert_eq!(network.soft_fork_heights().bip34, 21_111);
+        assert_eq!(
+            network.genesis_block_header(),
+            BlockHeader::generate_genesis_block_header()
+        );
+    }
+
+    #[test]
+    fn test04_correct_hash_of_the_genesis_blocks() {
+        let mut mainnet_hash = [
+            0x6f, 0xe2, 0x8c, 0x0a, 0xb6, 0xf1, 0xb3, 0x72, 0xc1, 0xa6, 0xa2, 0x46, 0xae, 0x63,
+            0xf7, 0x4f, 0x93, 0x1e, 0x83, 0x65, 0xe1, 0x5a, 0x08, 0x9c, 0x68, 0xd6, 0x19, 0x00,
+            0x00, 0x00, 0x00, 0x00,
+        ];
+        mainnet_hash.reverse();
+
+        let mut signet_hash = [
+            0xf6, 0x1e, 0xee, 0x3b, 0x63, 0xa3, 0x80, 0xa4, 0x77, 0xa0, 0x63, 0xaf, 0x32, 0xb2,
+            0xbb, 0xc9, 0x7c, 0x9f, 0xf9, 0xf0, 0x1f, 0x2c, 0x42, 0x25, 0xe9, 0x73, 0x98, 0x81,
+            0x08, 0x00, 0x00, 0x00,
+        ];
+        signet_hash.reverse();
+
+        assert_eq!(
+            Network::Mainnet
+                .genesis_block_header()
+                .get_hash256d()
+                .unwrap(),
+            mainnet_hash
+        );
+        assert_eq!(
+            Network::Signet
+                .genesis_block_header()
+                .get_hash256d()
+                .unwrap(),
+            signet_hash
+        );
+    }
+}
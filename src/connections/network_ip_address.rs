@@ -5,11 +5,14 @@ use crate::serialization::{
     serializable_little_endian::SerializableLittleEndian,
 };
 
-use crate::messages::bitfield_services::BitfieldServices;
+use crate::{
+    connections::socket_conversion::ipv6_port_to_socket,
+    messages::bitfield_services::BitfieldServices,
+};
 
 use std::{
     io::{Read, Write},
-    net::Ipv6Addr,
+    net::{Ipv6Addr, SocketAddr},
 };
 
 /// It's the representation of a new potential peer to connect to
@@ -21,6 +24,13 @@ pub struct NetworkIpAddress {
     port: u16,
 }
 
+impl NetworkIpAddress {
+    /// Returns the socket address of the potential peer, with its own port
+    pub fn socket_addr(&self) -> SocketAddr {
+        ipv6_port_to_socket(&self.ip_address, self.port)
+    }
+}
+
 impl SerializableLittleEndian for NetworkIpAddress {
     fn le_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
         self.time.le_serialize(stream)?;
@@ -94,4 +104,30 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test03_ipv6_address_and_port_are_serialized_in_big_endian() -> Result<(), ErrorSerialization>
+    {
+        let network_ip_address: NetworkIpAddress = NetworkIpAddress {
+            time: 1234,
+            services: BitfieldServices::new(vec![SupportedServices::Unname]),
+            ip_address: Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1),
+            port: 18444,
+        };
+
+        let mut stream: Vec<u8> = Vec::new();
+        network_ip_address.le_serialize(&mut stream)?;
+
+        let mut expected_end: Vec<u8> = vec![0x20, 0x01, 0x0d, 0xb8];
+        expected_end.extend_from_slice(&[0; 11]);
+        expected_end.extend_from_slice(&[0x01, 0x48, 0x0c]);
+
+        assert_eq!(stream[12..], expected_end);
+        assert_eq!(
+            network_ip_address.socket_addr(),
+            "[2001:db8::1]:18444".parse::<SocketAddr>().unwrap()
+        );
+
+        Ok(())
+    }
 }
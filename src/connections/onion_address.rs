@@ -0,0 +1,177 @@
+use crate::configurations::{
+    error_configuration::ErrorConfiguration,
+    parsable::{value_from_map, KeyValueMap, Parsable},
+};
+
+use std::{
+    cmp::PartialEq,
+    fmt::Display,
+    net::{IpAddr, Ipv6Addr, SocketAddr},
+    str::FromStr,
+};
+
+const ONION_SUFFIX: &str = ".onion";
+const ONION_LENGTH: usize = 56;
+const ONION_DECODED_LENGTH: usize = 35;
+const ONION_VERSION: u8 = 0x03;
+const PUBLIC_KEY_LENGTH: usize = 32;
+
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// It's the range of IPv6 addresses (OnionCat) used to identify the onion services as any other peer
+const ONION_CAT_PREFIX: [u8; 6] = [0xfd, 0x87, 0xd8, 0x7e, 0xeb, 0x43];
+
+/// It's the representation of a Tor onion service (version 3) that runs a node, which can only be
+/// reached through a SOCKS5 proxy
+#[derive(Debug, PartialEq, Clone)]
+pub struct OnionAddress {
+    host: String,
+    public_key: [u8; PUBLIC_KEY_LENGTH],
+    port: u16,
+}
+
+impl OnionAddress {
+    /// Returns the host name of the onion service, which the proxy resolves
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Returns the address in the OnionCat range that identifies the onion service
+    pub fn to_socket_addr(&self) -> SocketAddr {
+        let mut octets = [0; 16];
+        octets[..6].copy_from_slice(&ONION_CAT_PREFIX);
+        octets[6..].copy_from_slice(&self.public_key[..10]);
+
+        SocketAddr::new(IpAddr::V6(Ipv6Addr::from(octets)), self.port)
+    }
+
+    /// Decodes the base32 name of the onion service, without padding
+    fn decode_base32(name: &str) -> Option<Vec<u8>> {
+        let mut decoded: Vec<u8> = Vec::new();
+        let mut buffer: u32 = 0;
+        let mut bits: u32 = 0;
+
+        for character in name.bytes() {
+            let value = BASE32_ALPHABET
+                .iter()
+                .position(|letter| *letter == character)? as u32;
+
+            buffer = (buffer << 5) | value;
+            bits += 5;
+            if bits >= 8 {
+                bits -= 8;
+                decoded.push((buffer >> bits) as u8);
+                buffer &= (1 << bits) - 1;
+            }
+        }
+
+        Some(decoded)
+    }
+}
+
+impl FromStr for OnionAddress {
+    type Err = ErrorConfiguration;
+
+    /// Parses an onion service given by `<name>.onion:<port>`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let error =
+            ErrorConfiguration::ErrorCantParseValue(format!("onion address of {:?}", value));
+
+        let (host, port) = match value.trim().rsplit_once(':') {
+            Some((host, port)) => (host.to_lowercase(), port),
+            None => return Err(error),
+        };
+
+        let port = match port.parse::<u16>() {
+            Ok(port) => port,
+            Err(_) => return Err(error),
+        };
+
+        let name = match host.strip_suffix(ONION_SUFFIX) {
+            Some(name) if name.len() == ONION_LENGTH => name,
+            _ => return Err(error),
+        };
+
+        let decoded = match Self::decode_base32(name) {
+            Some(decoded) if decoded.len() == ONION_DECODED_LENGTH => decoded,
+            _ => return Err(error),
+        };
+
+        if decoded[ONION_DECODED_LENGTH - 1] != ONION_VERSION {
+            return Err(error);
+        }
+
+        let mut public_key = [0; PUBLIC_KEY_LENGTH];
+        public_key.copy_from_slice(&decoded[..PUBLIC_KEY_LENGTH]);
+
+        Ok(OnionAddress {
+            host,
+            public_key,
+            port,
+        })
+    }
+}
+
+impl Parsable for OnionAddress {
+    fn parse(name: &str, map: &KeyValueMap) -> Result<Self, ErrorConfiguration> {
+        let value = value_from_map(name.to_string(), map)?;
+        value.parse::<OnionAddress>()
+    }
+}
+
+impl Display for OnionAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.host, self.port)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONION_HOST: &str = "2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion";
+
+    #[test]
+    fn test01_parses_an_onion_address() {
+        let onion_address = format!("{ONION_HOST}:18333")
+            .parse::<OnionAddress>()
+            .unwrap();
+
+        assert_eq!(onion_address.host(), ONION_HOST);
+        assert_eq!(onion_address.port(), 18333);
+        assert_eq!(onion_address.to_string(), format!("{ONION_HOST}:18333"));
+    }
+
+    #[test]
+    fn test02_does_not_accept_invalid_onion_addresses() {
+        assert!("2gzyxa5ihm7nsggf.onion:18333"
+            .parse::<OnionAddress>()
+            .is_err());
+        assert!(ONION_HOST.parse::<OnionAddress>().is_err());
+        assert!("seed.testnet.bitcoin.sprovoost.nl:18333"
+            .parse::<OnionAddress>()
+            .is_err());
+        assert!(format!("{}:18333", ONION_HOST.replace('2', "1"))
+            .parse::<OnionAddress>()
+            .is_err());
+    }
+
+    #[test]
+    fn test03_onion_addresses_are_identified_in_the_onion_cat_range() {
+        let onion_address = format!("{ONION_HOST}:18333")
+            .parse::<OnionAddress>()
+            .unwrap();
+
+        let socket_address = onion_address.to_socket_addr();
+
+        match socket_address.ip() {
+            IpAddr::V6(ip) => assert_eq!(ip.octets()[..6], ONION_CAT_PREFIX),
+            IpAddr::V4(_) => panic!("The onion address must be identified with an IPv6 address"),
+        }
+        assert_eq!(socket_address.port(), 18333);
+    }
+}
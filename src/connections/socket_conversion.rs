@@ -8,3 +8,44 @@ pub fn socket_to_ipv6_port(socket_addr: &SocketAddr) -> (Ipv6Addr, u16) {
     };
     (ip, socket_addr.port())
 }
+
+/// Converts an IPv6 address and a port, as they are sent in the messages, to a socket address.
+/// The IPv4-mapped addresses are converted back to IPv4
+pub fn ipv6_port_to_socket(ip: &Ipv6Addr, port: u16) -> SocketAddr {
+    let ip = match ip.to_ipv4_mapped() {
+        Some(v4) => IpAddr::V4(v4),
+        None => IpAddr::V6(*ip),
+    };
+    SocketAddr::new(ip, port)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test01_ipv4_sockets_are_mapped_to_ipv6() {
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(192, 10, 2, 255)), 18444);
+
+        let (ip, port) = socket_to_ipv6_port(&socket_addr);
+
+        assert_eq!(ip, Ipv6Addr::new(0, 0, 0, 0, 0, 0xffff, 0xc00a, 0x02ff));
+        assert_eq!(port, 18444);
+        assert_eq!(ipv6_port_to_socket(&ip, port), socket_addr);
+    }
+
+    #[test]
+    fn test02_ipv6_sockets_keep_their_address_and_port() {
+        let socket_addr = SocketAddr::new(
+            IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)),
+            8333,
+        );
+
+        let (ip, port) = socket_to_ipv6_port(&socket_addr);
+
+        assert_eq!(IpAddr::V6(ip), socket_addr.ip());
+        assert_eq!(port, 8333);
+        assert_eq!(ipv6_port_to_socket(&ip, port), socket_addr);
+    }
+}
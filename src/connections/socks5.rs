@@ -0,0 +1,215 @@
+use super::error_connection::ErrorConnection;
+
+use std::{
+    io::{ErrorKind, Read, Write},
+    net::{IpAddr, SocketAddr},
+};
+
+const SOCKS_VERSION: u8 = 0x05;
+const NO_AUTHENTICATION: u8 = 0x00;
+const CONNECT_COMMAND: u8 = 0x01;
+const RESERVED: u8 = 0x00;
+const SUCCEEDED: u8 = 0x00;
+
+const ADDRESS_TYPE_IPV4: u8 = 0x01;
+const ADDRESS_TYPE_DOMAIN: u8 = 0x03;
+const ADDRESS_TYPE_IPV6: u8 = 0x04;
+
+const IPV4_LENGTH: usize = 4;
+const IPV6_LENGTH: usize = 16;
+const PORT_LENGTH: usize = 2;
+
+/// Asks the SOCKS5 proxy in the other end of the stream to connect to the given address.
+/// After it, the stream is connected to that address
+///
+/// ### Error
+///  * `ErrorConnection::ErrorCannotSendMessage`: It will appear when a message to the proxy cannot be sent
+///  * `ErrorConnection::ErrorCannotReceiveMessage`: It will appear when the answer of the proxy cannot be read or it's not a valid SOCKS5 answer
+///  * `ErrorConnection::ErrorCannotConnectToAddress`: It will appear when the proxy cannot connect to the address
+///  * `ErrorConnection::ErrorWriteTimeout`: It will appear when the proxy does not accept the request before the write timeout
+///  * `ErrorConnection::ErrorReadTimeout`: It will appear when the proxy does not answer before the read timeout
+pub fn connect_to_socket<RW: Read + Write>(
+    stream: &mut RW,
+    address: &SocketAddr,
+) -> Result<(), ErrorConnection> {
+    let mut destination: Vec<u8> = Vec::new();
+    match address.ip() {
+        IpAddr::V4(ip) => {
+            destination.push(ADDRESS_TYPE_IPV4);
+            destination.extend_from_slice(&ip.octets());
+        }
+        IpAddr::V6(ip) => {
+            destination.push(ADDRESS_TYPE_IPV6);
+            destination.extend_from_slice(&ip.octets());
+        }
+    }
+
+    connect(stream, destination, address.port())
+}
+
+/// Asks the SOCKS5 proxy in the other end of the stream to connect to the given host name, which
+/// is resolved by the proxy. It's the only way to reach an onion service.
+/// After it, the stream is connected to that host
+///
+/// ### Error
+///  * `ErrorConnection::ErrorInvalidIPOrPortNumber`: It will appear when the host name is too long
+///  * `ErrorConnection::ErrorCannotSendMessage`: It will appear when a message to the proxy cannot be sent
+///  * `ErrorConnection::ErrorCannotReceiveMessage`: It will appear when the answer of the proxy cannot be read or it's not a valid SOCKS5 answer
+///  * `ErrorConnection::ErrorCannotConnectToAddress`: It will appear when the proxy cannot connect to the host
+///  * `ErrorConnection::ErrorWriteTimeout`: It will appear when the proxy does not accept the request before the write timeout
+///  * `ErrorConnection::ErrorReadTimeout`: It will appear when the proxy does not answer before the read timeout
+pub fn connect_to_domain<RW: Read + Write>(
+    stream: &mut RW,
+    host: &str,
+    port: u16,
+) -> Result<(), ErrorConnection> {
+    let host_length = match u8::try_from(host.len()) {
+        Ok(host_length) => host_length,
+        Err(_) => return Err(ErrorConnection::ErrorInvalidIPOrPortNumber),
+    };
+
+    let mut destination: Vec<u8> = vec![ADDRESS_TYPE_DOMAIN, host_length];
+    destination.extend_from_slice(host.as_bytes());
+
+    connect(stream, destination, port)
+}
+
+/// Greets the proxy without authentication and sends the connect request to the destination
+fn connect<RW: Read + Write>(
+    stream: &mut RW,
+    destination: Vec<u8>,
+    port: u16,
+) -> Result<(), ErrorConnection> {
+    send(stream, &[SOCKS_VERSION, 1, NO_AUTHENTICATION])?;
+
+    let mut method = [0; 2];
+    receive(stream, &mut method)?;
+    if method != [SOCKS_VERSION, NO_AUTHENTICATION] {
+        return Err(ErrorConnection::ErrorCannotReceiveMessage);
+    }
+
+    let mut request: Vec<u8> = vec![SOCKS_VERSION, CONNECT_COMMAND, RESERVED];
+    request.extend(destination);
+    request.extend_from_slice(&port.to_be_bytes());
+    send(stream, &request)?;
+
+    let mut reply = [0; 4];
+    receive(stream, &mut reply)?;
+    if reply[0] != SOCKS_VERSION {
+        return Err(ErrorConnection::ErrorCannotReceiveMessage);
+    }
+    if reply[1] != SUCCEEDED {
+        return Err(ErrorConnection::ErrorCannotConnectToAddress);
+    }
+
+    let bound_address_length = match reply[3] {
+        ADDRESS_TYPE_IPV4 => IPV4_LENGTH,
+        ADDRESS_TYPE_IPV6 => IPV6_LENGTH,
+        ADDRESS_TYPE_DOMAIN => {
+            let mut length = [0; 1];
+            receive(stream, &mut length)?;
+            length[0] as usize
+        }
+        _ => return Err(ErrorConnection::ErrorCannotReceiveMessage),
+    };
+
+    let mut bound_address = vec![0; bound_address_length + PORT_LENGTH];
+    receive(stream, &mut bound_address)
+}
+
+fn send<W: Write>(stream: &mut W, message: &[u8]) -> Result<(), ErrorConnection> {
+    match stream.write_all(message) {
+        Ok(()) => Ok(()),
+        Err(error) => match error.kind() {
+            ErrorKind::TimedOut | ErrorKind::WouldBlock => Err(ErrorConnection::ErrorWriteTimeout),
+            _ => Err(ErrorConnection::ErrorCannotSendMessage),
+        },
+    }
+}
+
+fn receive<R: Read>(stream: &mut R, buffer: &mut [u8]) -> Result<(), ErrorConnection> {
+    match stream.read_exact(buffer) {
+        Ok(()) => Ok(()),
+        Err(error) => match error.kind() {
+            ErrorKind::TimedOut | ErrorKind::WouldBlock => Err(ErrorConnection::ErrorReadTimeout),
+            _ => Err(ErrorConnection::ErrorCannotReceiveMessage),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::testing::stream::Stream;
+
+    use std::net::Ipv4Addr;
+
+    fn proxy_answer(reply: u8) -> Vec<u8> {
+        vec![
+            SOCKS_VERSION,
+            NO_AUTHENTICATION,
+            SOCKS_VERSION,
+            reply,
+            RESERVED,
+            ADDRESS_TYPE_IPV4,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+        ]
+    }
+
+    #[test]
+    fn test01_connects_to_a_socket_through_the_proxy() {
+        let mut stream = Stream::new(proxy_answer(SUCCEEDED));
+        let address = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2)), 18333);
+
+        connect_to_socket(&mut stream, &address).unwrap();
+
+        let mut expected: Vec<u8> = vec![SOCKS_VERSION, 1, NO_AUTHENTICATION];
+        expected.extend_from_slice(&[
+            SOCKS_VERSION,
+            CONNECT_COMMAND,
+            RESERVED,
+            ADDRESS_TYPE_IPV4,
+            127,
+            0,
+            0,
+            2,
+        ]);
+        expected.extend_from_slice(&18333_u16.to_be_bytes());
+
+        let mut written = vec![0; expected.len()];
+        stream.get_write_stream().read_exact(&mut written).unwrap();
+        assert_eq!(expected, written);
+    }
+
+    #[test]
+    fn test02_connects_to_a_domain_through_the_proxy() {
+        let mut stream = Stream::new(proxy_answer(SUCCEEDED));
+        let host = "2gzyxa5ihm7nsggfxnu52rck2vv4rvmdlkiu3zzui5du4xyclen53wid.onion";
+
+        connect_to_domain(&mut stream, host, 18333).unwrap();
+
+        let mut written = vec![0; 3 + 5 + host.len() + 2];
+        stream.get_write_stream().read_exact(&mut written).unwrap();
+
+        assert_eq!(written[6], ADDRESS_TYPE_DOMAIN);
+        assert_eq!(written[7] as usize, host.len());
+        assert_eq!(&written[8..8 + host.len()], host.as_bytes());
+    }
+
+    #[test]
+    fn test03_proxy_refusing_the_connection_is_an_error() {
+        let mut stream = Stream::new(proxy_answer(0x05));
+        let address = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2)), 18333);
+
+        assert_eq!(
+            Err(ErrorConnection::ErrorCannotConnectToAddress),
+            connect_to_socket(&mut stream, &address)
+        );
+    }
+}
@@ -18,6 +18,7 @@ const NODE_GET_UTXO: u64 = 0x02;
 const NODE_BLOOM: u64 = 0x04;
 const NODE_WITNESS: u64 = 0x08;
 const NODE_XTHIN: u64 = 0x10;
+const NODE_COMPACT_FILTERS: u64 = 0x40;
 const NODE_NETWORK_LIMITED: u64 = 0x0400;
 
 /// It's the representation of the supported services of a node
@@ -29,6 +30,7 @@ pub enum SupportedServices {
     NodeBloom,
     NodeWitness,
     NodeXThin,
+    NodeCompactFilters,
     NodeNetworkLimited,
 }
 
@@ -43,6 +45,7 @@ impl FromStr for SupportedServices {
             "NodeBloom" => Ok(SupportedServices::NodeBloom),
             "NodeWitness" => Ok(SupportedServices::NodeWitness),
             "NodeXThin" => Ok(SupportedServices::NodeXThin),
+            "NodeCompactFilters" => Ok(SupportedServices::NodeCompactFilters),
             "NodeNetworkLimited" => Ok(SupportedServices::NodeNetworkLimited),
             _ => Err(ErrorConfiguration::ErrorCantParseValue(format!(
                 "Supported services of {:?}",
@@ -70,6 +73,7 @@ impl TryFrom<u64> for SupportedServices {
             NODE_BLOOM => Ok(SupportedServices::NodeBloom),
             NODE_WITNESS => Ok(SupportedServices::NodeWitness),
             NODE_XTHIN => Ok(SupportedServices::NodeXThin),
+            NODE_COMPACT_FILTERS => Ok(SupportedServices::NodeCompactFilters),
             NODE_NETWORK_LIMITED => Ok(SupportedServices::NodeNetworkLimited),
             _ => Err(ErrorConfiguration::ErrorCantParseValue(format!(
                 "Supported services of {:?}",
@@ -90,6 +94,7 @@ impl TryInto<u64> for SupportedServices {
             SupportedServices::NodeBloom => Ok(NODE_BLOOM),
             SupportedServices::NodeWitness => Ok(NODE_WITNESS),
             SupportedServices::NodeXThin => Ok(NODE_XTHIN),
+            SupportedServices::NodeCompactFilters => Ok(NODE_COMPACT_FILTERS),
             SupportedServices::NodeNetworkLimited => Ok(NODE_NETWORK_LIMITED),
         }
     }
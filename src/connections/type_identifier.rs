@@ -13,6 +13,7 @@ const TRANSACTION_ID_VALUE: u32 = 0x01;
 const BLOCK_VALUE: u32 = 0x02;
 const FILTERED_BLOCK_VALUE: u32 = 0x03;
 const COMPACT_BLOCK_VALUE: u32 = 0x04;
+const WITNESS_TRANSACTION_ID_VALUE: u32 = 0x05;
 const WITNESS_TRANSACTION_VALUE: u32 = 0x40000001;
 const WITNESS_BLOCK_VALUE: u32 = 0x40000002;
 const FILTERED_WITNESS_BLOCK_VALUE: u32 = 0x40000003;
@@ -26,6 +27,7 @@ pub enum TypeIdentifier {
     Block,
     FilteredBlock,
     CompactBlock,
+    WitnessTransactionId,
     WitnessTransaction,
     WitnessBlock,
     FilteredWitnessBlock,
@@ -40,6 +42,7 @@ impl SerializableLittleEndian for TypeIdentifier {
             TypeIdentifier::Block => BLOCK_VALUE,
             TypeIdentifier::FilteredBlock => FILTERED_BLOCK_VALUE,
             TypeIdentifier::CompactBlock => COMPACT_BLOCK_VALUE,
+            TypeIdentifier::WitnessTransactionId => WITNESS_TRANSACTION_ID_VALUE,
             TypeIdentifier::WitnessTransaction => WITNESS_TRANSACTION_VALUE,
             TypeIdentifier::WitnessBlock => WITNESS_BLOCK_VALUE,
             TypeIdentifier::FilteredWitnessBlock => FILTERED_WITNESS_BLOCK_VALUE,
@@ -67,6 +70,7 @@ impl DeserializableLittleEndian for TypeIdentifier {
             BLOCK_VALUE => Ok(TypeIdentifier::Block),
             FILTERED_BLOCK_VALUE => Ok(TypeIdentifier::FilteredBlock),
             COMPACT_BLOCK_VALUE => Ok(TypeIdentifier::CompactBlock),
+            WITNESS_TRANSACTION_ID_VALUE => Ok(TypeIdentifier::WitnessTransactionId),
             WITNESS_TRANSACTION_VALUE => Ok(TypeIdentifier::WitnessTransaction),
             WITNESS_BLOCK_VALUE => Ok(TypeIdentifier::WitnessBlock),
             FILTERED_WITNESS_BLOCK_VALUE => Ok(TypeIdentifier::FilteredWitnessBlock),
@@ -0,0 +1,205 @@
+use super::error_connection::ErrorConnection;
+
+pub const CLIENT_NAME: &str = "CargoSOS";
+pub const CLIENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const MAX_USER_AGENT_LENGTH: usize = 256;
+
+const SEPARATOR: char = '/';
+const VERSION_SEPARATOR: char = ':';
+const COMMENT_START: char = '(';
+const COMMENT_END: char = ')';
+const COMMENT_SEPARATOR: &str = "; ";
+
+const RESERVED_IN_NAME: [char; 4] = [SEPARATOR, VERSION_SEPARATOR, COMMENT_START, COMMENT_END];
+const RESERVED_IN_COMMENT: [char; 4] = [SEPARATOR, COMMENT_START, COMMENT_END, ';'];
+
+/// It's the builder of a user agent following the format of the BIP14: `/Name:Version(comment; comment)/`
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserAgentBuilder {
+    name: String,
+    version: String,
+    comments: Vec<String>,
+}
+
+impl UserAgentBuilder {
+    /// Creates the builder with the name of this client and the version of the crate
+    pub fn new() -> Self {
+        UserAgentBuilder {
+            name: CLIENT_NAME.to_string(),
+            version: CLIENT_VERSION.to_string(),
+            comments: Vec::new(),
+        }
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn version(mut self, version: &str) -> Self {
+        self.version = version.to_string();
+        self
+    }
+
+    pub fn comment(mut self, comment: &str) -> Self {
+        self.comments.push(comment.to_string());
+        self
+    }
+
+    /// Creates the user agent and validates it
+    ///
+    /// ### Error
+    ///  * `ErrorConnection::ErrorInvalidUserAgent`: It will appear when a part of the user agent has reserved characters or the user agent is too long
+    pub fn build(self) -> Result<String, ErrorConnection> {
+        if self.name.is_empty()
+            || !is_valid_part(&self.name, &RESERVED_IN_NAME)
+            || !is_valid_part(&self.version, &RESERVED_IN_NAME)
+            || self
+                .comments
+                .iter()
+                .any(|comment| comment.is_empty() || !is_valid_part(comment, &RESERVED_IN_COMMENT))
+        {
+            return Err(ErrorConnection::ErrorInvalidUserAgent);
+        }
+
+        let mut user_agent = format!("{SEPARATOR}{}", self.name);
+        if !self.version.is_empty() {
+            user_agent.push_str(&format!("{VERSION_SEPARATOR}{}", self.version));
+        }
+        if !self.comments.is_empty() {
+            user_agent.push_str(&format!(
+                "{COMMENT_START}{}{COMMENT_END}",
+                self.comments.join(COMMENT_SEPARATOR)
+            ));
+        }
+        user_agent.push(SEPARATOR);
+
+        validate_user_agent(&user_agent)?;
+        Ok(user_agent)
+    }
+}
+
+impl Default for UserAgentBuilder {
+    fn default() -> Self {
+        UserAgentBuilder::new()
+    }
+}
+
+/// Validates that the user agent has only printable characters, its length is inside the limit
+/// and it's delimited as the BIP14 specifies
+///
+/// ### Error
+///  * `ErrorConnection::ErrorInvalidUserAgent`: It will appear when the user agent is not valid
+pub fn validate_user_agent(user_agent: &str) -> Result<(), ErrorConnection> {
+    let is_delimited = user_agent.len() > 1
+        && user_agent.starts_with(SEPARATOR)
+        && user_agent.ends_with(SEPARATOR);
+
+    if !is_delimited
+        || user_agent.len() > MAX_USER_AGENT_LENGTH
+        || !user_agent
+            .chars()
+            .all(|character| character.is_ascii_graphic() || character == ' ')
+    {
+        return Err(ErrorConnection::ErrorInvalidUserAgent);
+    }
+
+    Ok(())
+}
+
+/// Creates the user agent from the configured value. If nothing is configured the default user agent is used,
+/// if a full user agent is configured it's validated, and otherwise the value is added as a comment
+///
+/// ### Error
+///  * `ErrorConnection::ErrorInvalidUserAgent`: It will appear when the resulting user agent is not valid
+pub fn from_configuration(configured: &str) -> Result<String, ErrorConnection> {
+    let configured = configured.trim();
+
+    if configured.is_empty() {
+        return UserAgentBuilder::new().build();
+    }
+
+    if configured.starts_with(SEPARATOR) {
+        validate_user_agent(configured)?;
+        return Ok(configured.to_string());
+    }
+
+    UserAgentBuilder::new().comment(configured).build()
+}
+
+/// Returns true if the part of the user agent is printable and does not have reserved characters
+fn is_valid_part(part: &str, reserved: &[char]) -> bool {
+    part.chars().all(|character| {
+        (character.is_ascii_graphic() || character == ' ') && !reserved.contains(&character)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_correct_default_user_agent() {
+        let user_agent = UserAgentBuilder::new().build().unwrap();
+
+        assert_eq!(user_agent, format!("/CargoSOS:{CLIENT_VERSION}/"));
+    }
+
+    #[test]
+    fn test02_correct_user_agent_with_comments() {
+        let user_agent = UserAgentBuilder::new()
+            .version("0.1.0")
+            .comment("testnet")
+            .comment("wallet")
+            .build()
+            .unwrap();
+
+        assert_eq!(user_agent, "/CargoSOS:0.1.0(testnet; wallet)/");
+    }
+
+    #[test]
+    fn test03_does_not_accept_reserved_characters() {
+        assert_eq!(
+            UserAgentBuilder::new().name("Cargo/SOS").build(),
+            Err(ErrorConnection::ErrorInvalidUserAgent)
+        );
+        assert_eq!(
+            UserAgentBuilder::new().comment("a; b").build(),
+            Err(ErrorConnection::ErrorInvalidUserAgent)
+        );
+        assert_eq!(
+            UserAgentBuilder::new().comment("\u{1b}[31m").build(),
+            Err(ErrorConnection::ErrorInvalidUserAgent)
+        );
+    }
+
+    #[test]
+    fn test04_does_not_accept_user_agent_too_long() {
+        let comment = "a".repeat(MAX_USER_AGENT_LENGTH);
+
+        assert_eq!(
+            UserAgentBuilder::new().comment(&comment).build(),
+            Err(ErrorConnection::ErrorInvalidUserAgent)
+        );
+    }
+
+    #[test]
+    fn test05_correct_user_agent_from_configuration() {
+        assert_eq!(
+            from_configuration(""),
+            Ok(format!("/CargoSOS:{CLIENT_VERSION}/"))
+        );
+        assert_eq!(
+            from_configuration("/Satoshi:25.0.0/"),
+            Ok("/Satoshi:25.0.0/".to_string())
+        );
+        assert_eq!(
+            from_configuration("Tanto tiempo"),
+            Ok(format!("/CargoSOS:{CLIENT_VERSION}(Tanto tiempo)/"))
+        );
+        assert_eq!(
+            from_configuration("/Satoshi"),
+            Err(ErrorConnection::ErrorInvalidUserAgent)
+        );
+    }
+}
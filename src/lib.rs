@@ -17,3 +17,5 @@ pub mod block_structure;
 pub mod serialization;
 
 pub mod wallet_structure;
+
+pub mod testing;
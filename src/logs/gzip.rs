@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+
+const GZIP_HEADER: [u8; 10] = [0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff];
+
+const WINDOW_SIZE: usize = 32 * 1024;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+const END_OF_BLOCK: u16 = 256;
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA_BITS: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA_BITS: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+const CRC32_POLYNOMIAL: u32 = 0xedb88320;
+
+/// Writes the bits from the least significant one, as the deflate format expects
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        BitWriter {
+            bytes: Vec::new(),
+            buffer: 0,
+            bits: 0,
+        }
+    }
+
+    fn write_bits(&mut self, value: u32, count: u8) {
+        for bit in 0..count {
+            self.buffer |= ((value >> bit) & 1) << self.bits;
+            self.bits += 1;
+            if self.bits == 8 {
+                self.bytes.push(self.buffer as u8);
+                self.buffer = 0;
+                self.bits = 0;
+            }
+        }
+    }
+
+    /// The Huffman codes are written from the most significant bit
+    fn write_code(&mut self, code: u32, length: u8) {
+        for bit in (0..length).rev() {
+            self.write_bits((code >> bit) & 1, 1);
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+
+    /// Writes the symbol of a literal, a length or the end of the block with the fixed Huffman codes
+    fn write_symbol(&mut self, symbol: u16) {
+        let symbol = symbol as u32;
+        match symbol {
+            0..=143 => self.write_code(0x30 + symbol, 8),
+            144..=255 => self.write_code(0x190 + symbol - 144, 9),
+            256..=279 => self.write_code(symbol - 256, 7),
+            _ => self.write_code(0xc0 + symbol - 280, 8),
+        }
+    }
+
+    fn write_match(&mut self, length: usize, distance: usize) {
+        let length_index = LENGTH_BASE
+            .iter()
+            .rposition(|base| *base as usize <= length)
+            .unwrap_or(0);
+        self.write_symbol(257 + length_index as u16);
+        self.write_bits(
+            (length - LENGTH_BASE[length_index] as usize) as u32,
+            LENGTH_EXTRA_BITS[length_index],
+        );
+
+        let distance_index = DISTANCE_BASE
+            .iter()
+            .rposition(|base| *base as usize <= distance)
+            .unwrap_or(0);
+        self.write_code(distance_index as u32, 5);
+        self.write_bits(
+            (distance - DISTANCE_BASE[distance_index] as usize) as u32,
+            DISTANCE_EXTRA_BITS[distance_index],
+        );
+    }
+}
+
+/// Compresses the data in the gzip format, using a single deflate block with the fixed Huffman
+/// codes. It's enough for text as the logs, without depending on an external library
+pub fn compress(data: &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter::new();
+    writer.write_bits(1, 1);
+    writer.write_bits(1, 2);
+
+    let mut last_positions: HashMap<[u8; MIN_MATCH], usize> = HashMap::new();
+    let mut position = 0;
+    while position < data.len() {
+        let (length, distance) = find_match(data, position, &last_positions);
+
+        let step = match length >= MIN_MATCH {
+            true => {
+                writer.write_match(length, distance);
+                length
+            }
+            false => {
+                writer.write_symbol(data[position] as u16);
+                1
+            }
+        };
+
+        for index in position..position + step {
+            if let Some(key) = key_at(data, index) {
+                last_positions.insert(key, index);
+            }
+        }
+        position += step;
+    }
+    writer.write_symbol(END_OF_BLOCK);
+
+    let mut compressed = GZIP_HEADER.to_vec();
+    compressed.extend(writer.finish());
+    compressed.extend_from_slice(&crc32(data).to_le_bytes());
+    compressed.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    compressed
+}
+
+/// Returns the length and the distance of the last previous occurrence of the data at the position
+fn find_match(
+    data: &[u8],
+    position: usize,
+    last_positions: &HashMap<[u8; MIN_MATCH], usize>,
+) -> (usize, usize) {
+    let previous = match key_at(data, position).and_then(|key| last_positions.get(&key)) {
+        Some(previous) if position - previous <= WINDOW_SIZE => *previous,
+        _ => return (0, 0),
+    };
+
+    let max_length = MAX_MATCH.min(data.len() - position);
+    let length = (0..max_length)
+        .take_while(|offset| data[previous + offset] == data[position + offset])
+        .count();
+
+    (length, position - previous)
+}
+
+fn key_at(data: &[u8], position: usize) -> Option<[u8; MIN_MATCH]> {
+    data.get(position..position + MIN_MATCH)?.try_into().ok()
+}
+
+/// Returns the CRC-32 checksum used by the gzip format
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc: u32 = !0;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = match crc & 1 {
+                1 => (crc >> 1) ^ CRC32_POLYNOMIAL,
+                _ => crc >> 1,
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_crc32_of_the_check_value() {
+        assert_eq!(0xcbf43926, crc32(b"123456789"));
+        assert_eq!(0, crc32(b""));
+    }
+
+    #[test]
+    fn test02_compressed_data_has_the_gzip_header_and_trailer() {
+        let data = b"Time: [1685264160s]: [INFO] [NODE] A block\n".repeat(100);
+
+        let compressed = compress(&data);
+
+        assert_eq!(compressed[..10], GZIP_HEADER);
+        assert_eq!(
+            compressed[compressed.len() - 8..compressed.len() - 4],
+            crc32(&data).to_le_bytes()
+        );
+        assert_eq!(
+            compressed[compressed.len() - 4..],
+            (data.len() as u32).to_le_bytes()
+        );
+        assert!(compressed.len() < data.len() / 10);
+    }
+
+    #[test]
+    fn test03_empty_data_is_a_single_end_of_block() {
+        let compressed = compress(b"");
+
+        assert_eq!(compressed[10..compressed.len() - 8], [0x03, 0x00]);
+    }
+}
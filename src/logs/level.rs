@@ -1,5 +1,12 @@
+use crate::configurations::{
+    error_configuration::ErrorConfiguration,
+    parsable::{value_from_map, KeyValueMap, Parsable},
+};
+
+use std::{cmp::PartialEq, str::FromStr};
+
 /// It represents all the locations from where a log can be called
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Level {
     NODE,
     WALLET,
@@ -29,3 +36,33 @@ impl std::fmt::Display for Level {
         }
     }
 }
+
+impl FromStr for Level {
+    type Err = ErrorConfiguration;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_uppercase().as_str() {
+            "NODE" => Ok(Level::NODE),
+            "WALLET" => Ok(Level::WALLET),
+            "TRANSACTION" => Ok(Level::TRANSACTION),
+            "CONFIGURATION" => Ok(Level::CONFIGURATION),
+            "CONNECTION" => Ok(Level::CONNECTION),
+            "FILE" => Ok(Level::FILE),
+            "INTERFACE" => Ok(Level::INTERFACE),
+            "NOTIFICATION" => Ok(Level::NOTIFICATION),
+            "BROADCASTING" => Ok(Level::BROADCASTING),
+            "ERROR" => Ok(Level::ERROR),
+            _ => Err(ErrorConfiguration::ErrorCantParseValue(format!(
+                "level of {:?}",
+                value
+            ))),
+        }
+    }
+}
+
+impl Parsable for Level {
+    fn parse(name: &str, map: &KeyValueMap) -> Result<Self, ErrorConfiguration> {
+        let value = value_from_map(name.to_string(), map)?;
+        value.parse::<Level>()
+    }
+}
@@ -0,0 +1,127 @@
+use std::{
+    collections::VecDeque,
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+
+#[derive(Debug, Default)]
+struct BufferedLines {
+    lines: VecDeque<String>,
+    registered: usize,
+    partial: String,
+}
+
+/// It's an in-memory output of the logs that keeps only the last lines registered, so they can be
+/// shown while the program is running. It can be cloned to read the lines from another thread
+#[derive(Debug, Clone)]
+pub struct LogBuffer {
+    buffered: Arc<Mutex<BufferedLines>>,
+    capacity: usize,
+}
+
+impl LogBuffer {
+    /// Creates a buffer that keeps up to the given amount of lines
+    pub fn new(capacity: usize) -> Self {
+        LogBuffer {
+            buffered: Arc::new(Mutex::new(BufferedLines::default())),
+            capacity,
+        }
+    }
+
+    /// Returns the most lines that are kept
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the lines kept, from the oldest one
+    pub fn lines(&self) -> Vec<String> {
+        self.lines_since(0).0
+    }
+
+    /// Returns the lines registered after the first `registered` ones that are still kept, and the
+    /// amount of lines registered until now, to be used in the next call
+    pub fn lines_since(&self, registered: usize) -> (Vec<String>, usize) {
+        let buffered = match self.buffered.lock() {
+            Ok(buffered) => buffered,
+            Err(_) => return (Vec::new(), registered),
+        };
+
+        let discarded = buffered.registered - buffered.lines.len();
+        let skip = registered.saturating_sub(discarded);
+        let lines = buffered.lines.iter().skip(skip).cloned().collect();
+
+        (lines, buffered.registered)
+    }
+}
+
+impl Write for LogBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut buffered = match self.buffered.lock() {
+            Ok(buffered) => buffered,
+            Err(_) => return Err(io::Error::other("Poisoned log buffer")),
+        };
+
+        buffered.partial.push_str(&String::from_utf8_lossy(buf));
+        while let Some(position) = buffered.partial.find('\n') {
+            let line: String = buffered.partial.drain(..=position).collect();
+            buffered.lines.push_back(line.trim_end().to_string());
+            buffered.registered += 1;
+
+            if buffered.lines.len() > self.capacity {
+                buffered.lines.pop_front();
+            }
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_buffer_keeps_only_the_last_lines() {
+        let mut buffer = LogBuffer::new(2);
+
+        buffer.write_all(b"first\nsecond\n").unwrap();
+        buffer.write_all(b"third\n").unwrap();
+
+        assert_eq!(
+            vec!["second".to_string(), "third".to_string()],
+            buffer.lines()
+        );
+    }
+
+    #[test]
+    fn test02_lines_are_registered_when_they_are_complete() {
+        let mut buffer = LogBuffer::new(10);
+
+        buffer.write_all(b"a bl").unwrap();
+        assert!(buffer.lines().is_empty());
+
+        buffer.write_all(b"ock\n").unwrap();
+        assert_eq!(vec!["a block".to_string()], buffer.lines());
+    }
+
+    #[test]
+    fn test03_only_the_new_lines_are_returned() {
+        let mut buffer = LogBuffer::new(2);
+        let reader = buffer.clone();
+
+        buffer.write_all(b"first\n").unwrap();
+        let (lines, registered) = reader.lines_since(0);
+        assert_eq!(vec!["first".to_string()], lines);
+
+        buffer.write_all(b"second\nthird\nfourth\n").unwrap();
+        let (lines, registered) = reader.lines_since(registered);
+        assert_eq!(vec!["third".to_string(), "fourth".to_string()], lines);
+
+        let (lines, _) = reader.lines_since(registered);
+        assert!(lines.is_empty());
+    }
+}
@@ -0,0 +1,115 @@
+use super::{level::Level, severity::Severity};
+
+use crate::{
+    configurations::{
+        error_configuration::ErrorConfiguration,
+        parsable::{value_from_map, KeyValueMap, Parsable},
+    },
+    notifications::notification_json::quote,
+};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use std::{cmp::PartialEq, str::FromStr};
+
+/// It represents how each log message is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// A line readable by a person, as `Time: [1685264160s]: [INFO] [NODE] A block`
+    #[default]
+    Text,
+
+    /// A JSON object per line, with the fields `timestamp`, `level`, `category`, `message` and
+    /// `thread`, so it can be read by tools as Loki or Elasticsearch
+    Json,
+}
+
+impl LogFormat {
+    /// Returns the line of the message to be registered, ending with a new line
+    pub fn format(
+        &self,
+        time: DateTime<Utc>,
+        level: &Level,
+        severity: Severity,
+        thread: &str,
+        message: &str,
+    ) -> String {
+        match self {
+            LogFormat::Text => format!(
+                "Time: [{}s]: [{severity}] [{level}] {message}\n",
+                time.timestamp()
+            ),
+            LogFormat::Json => format!(
+                "{{\"timestamp\":{},\"level\":{},\"category\":{},\"message\":{},\"thread\":{}}}\n",
+                quote(&time.to_rfc3339_opts(SecondsFormat::Millis, true)),
+                quote(&severity.to_string()),
+                quote(&level.to_string()),
+                quote(message),
+                quote(thread),
+            ),
+        }
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = ErrorConfiguration;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(ErrorConfiguration::ErrorCantParseValue(format!(
+                "log format of {:?}",
+                value
+            ))),
+        }
+    }
+}
+
+impl Parsable for LogFormat {
+    fn parse(name: &str, map: &KeyValueMap) -> Result<Self, ErrorConfiguration> {
+        let value = value_from_map(name.to_string(), map)?;
+        value.parse::<LogFormat>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::TimeZone;
+
+    #[test]
+    fn test01_text_format_keeps_the_previous_line() {
+        let time = Utc.timestamp_opt(1685264160, 0).unwrap();
+
+        let line = LogFormat::Text.format(time, &Level::NODE, Severity::INFO, "main", "A block");
+
+        assert_eq!("Time: [1685264160s]: [INFO] [NODE] A block\n", line);
+    }
+
+    #[test]
+    fn test02_json_format_is_an_object_per_line() {
+        let time = Utc.timestamp_opt(1685264160, 250_000_000).unwrap();
+
+        let line = LogFormat::Json.format(
+            time,
+            &Level::CONNECTION,
+            Severity::WARN,
+            "peer-1",
+            "Peer \"127.0.0.1\"\nclosed",
+        );
+
+        assert_eq!(
+            "{\"timestamp\":\"2023-05-28T08:56:00.250Z\",\"level\":\"WARN\",\"category\":\"CONNECTION\",\"message\":\"Peer \\\"127.0.0.1\\\"\\nclosed\",\"thread\":\"peer-1\"}\n",
+            line
+        );
+    }
+
+    #[test]
+    fn test03_format_is_parsed_without_case() {
+        assert_eq!(Ok(LogFormat::Json), "JSON".parse::<LogFormat>());
+        assert_eq!(Ok(LogFormat::Text), "text".parse::<LogFormat>());
+        assert!("xml".parse::<LogFormat>().is_err());
+    }
+}
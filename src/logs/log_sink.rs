@@ -0,0 +1,99 @@
+use super::{
+    error_log::ErrorLog,
+    level::Level,
+    log_format::LogFormat,
+    severity::{Severity, SeverityFilter},
+};
+
+use chrono::{DateTime, Utc};
+
+use std::{fmt::Debug, io::Write};
+
+/// Returns if a message has to be registered with the given filters. The errors are always
+/// registered when the levels are filtered
+pub(crate) fn is_registered(
+    levels: &Option<Vec<Level>>,
+    severities: &SeverityFilter,
+    level: &Level,
+    severity: Severity,
+) -> bool {
+    if let Some(levels) = levels {
+        if *level != Level::ERROR && !levels.contains(level) {
+            return false;
+        }
+    }
+
+    severities.allows(level, severity)
+}
+
+/// It's an additional output for the log messages, with its own filters and format, as the
+/// console or a `LogBuffer` besides the log file
+pub struct LogSink {
+    output: Box<dyn Write + Send>,
+    levels: Option<Vec<Level>>,
+    severities: SeverityFilter,
+    format: LogFormat,
+}
+
+impl LogSink {
+    /// Creates the output where every message up to `Severity::INFO` is written as text
+    pub fn new<W: Write + Send + 'static>(output: W) -> Self {
+        LogSink {
+            output: Box::new(output),
+            levels: None,
+            severities: SeverityFilter::default(),
+            format: LogFormat::default(),
+        }
+    }
+
+    /// Only the messages of the given levels will be written. The errors are always written
+    pub fn filter_levels(self, levels: Vec<Level>) -> Self {
+        LogSink {
+            levels: Some(levels),
+            ..self
+        }
+    }
+
+    /// Only the messages at least as important as the severity of their level will be written
+    pub fn filter_severities(self, severities: SeverityFilter) -> Self {
+        LogSink { severities, ..self }
+    }
+
+    /// The messages will be written in the given format
+    pub fn with_format(self, format: LogFormat) -> Self {
+        LogSink { format, ..self }
+    }
+
+    /// Writes the message if it passes the filters of this output
+    ///
+    /// ### Error
+    ///  * `ErrorLog::CouldNotWriteInFile`: It will appear when the message can't be written in the output
+    pub(crate) fn write(
+        &mut self,
+        time: DateTime<Utc>,
+        level: &Level,
+        severity: Severity,
+        thread: &str,
+        message: &str,
+    ) -> Result<(), ErrorLog> {
+        if !is_registered(&self.levels, &self.severities, level, severity) {
+            return Ok(());
+        }
+
+        let text = self.format.format(time, level, severity, thread, message);
+        match self.output.write_all(text.as_bytes()) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(ErrorLog::CouldNotWriteInFile),
+        }
+    }
+}
+
+impl Debug for LogSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LogSink")
+            .field("levels", &self.levels)
+            .field("severities", &self.severities)
+            .field("format", &self.format)
+            .finish()
+    }
+}
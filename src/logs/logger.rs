@@ -1,10 +1,12 @@
 use super::level::Level;
 use super::logger_receiver::LoggerReceiver;
 use super::logger_sender::LoggerSender;
+use super::severity::Severity;
 use std::io::Write;
 use std::sync::mpsc;
 
-pub(crate) type MessageLog = (Level, String);
+/// It's the level, the severity, the message and the name of the thread that sent it
+pub(crate) type MessageLog = (Level, Severity, String, String);
 /// We create the sender and the receiver for the logger, receiving the path of the file where we want to write the logs
 pub fn initialize_logger<W: Write>(
     output: W,
@@ -22,7 +24,12 @@ pub fn initialize_logger<W: Write>(
 mod tests {
 
     use super::*;
-    use crate::logs::error_log::ErrorLog;
+    use crate::logs::{
+        error_log::ErrorLog, log_buffer::LogBuffer, log_format::LogFormat, log_sink::LogSink,
+        severity::SeverityFilter,
+    };
+
+    use std::{collections::HashMap, thread};
 
     #[test]
     fn test01_correct_log_creation() {
@@ -58,4 +65,112 @@ mod tests {
 
         assert_eq!(error_message, ErrorLog::ReceiverNotFound);
     }
+
+    #[test]
+    fn test03_only_the_filtered_levels_are_registered() {
+        let mut vec: Vec<u8> = Vec::new();
+        let (logger_sender, logger_receiver) = initialize_logger(&mut vec, false);
+        let logger_receiver = logger_receiver.filter_levels(vec![Level::WALLET]);
+
+        logger_sender.log_node("A block".to_string()).unwrap();
+        logger_sender.log_wallet("An account".to_string()).unwrap();
+        logger_sender.log_error("A failure".to_string()).unwrap();
+        std::mem::drop(logger_sender);
+
+        logger_receiver.receive_log().unwrap();
+
+        let contents = String::from_utf8(vec).unwrap();
+
+        assert!(!contents.contains("[NODE] A block"));
+        assert!(contents.contains("[WALLET] An account"));
+        assert!(contents.contains("[ERROR] A failure"));
+    }
+
+    #[test]
+    fn test04_only_the_messages_as_important_as_their_severity_are_registered() {
+        let mut vec: Vec<u8> = Vec::new();
+        let (logger_sender, logger_receiver) = initialize_logger(&mut vec, false);
+        let logger_receiver = logger_receiver.filter_severities(SeverityFilter {
+            default: Severity::WARN,
+            levels: HashMap::from([(Level::CONNECTION, Severity::DEBUG)]),
+        });
+
+        logger_sender.log_node("A block".to_string()).unwrap();
+        logger_sender
+            .log_warning(Level::NODE, "A stale block".to_string())
+            .unwrap();
+        logger_sender
+            .log_debug(Level::CONNECTION, "A ping".to_string())
+            .unwrap();
+        logger_sender
+            .log_trace(Level::CONNECTION, "A byte".to_string())
+            .unwrap();
+        std::mem::drop(logger_sender);
+
+        logger_receiver.receive_log().unwrap();
+
+        let contents = String::from_utf8(vec).unwrap();
+
+        assert!(!contents.contains("A block"));
+        assert!(contents.contains("[WARN] [NODE] A stale block"));
+        assert!(contents.contains("[DEBUG] [CONNECTION] A ping"));
+        assert!(!contents.contains("A byte"));
+    }
+
+    #[test]
+    fn test05_messages_can_be_written_as_json() {
+        let mut vec: Vec<u8> = Vec::new();
+        let (logger_sender, logger_receiver) = initialize_logger(&mut vec, false);
+        let logger_receiver = logger_receiver.with_format(LogFormat::Json);
+
+        let handle = thread::Builder::new()
+            .name("peer".to_string())
+            .spawn(move || logger_sender.log_node("A block".to_string()))
+            .unwrap();
+        handle.join().unwrap().unwrap();
+
+        logger_receiver.receive_log().unwrap();
+
+        let contents = String::from_utf8(vec).unwrap();
+
+        assert!(contents.starts_with("{\"timestamp\":\""));
+        assert!(contents.ends_with(
+            "\"level\":\"INFO\",\"category\":\"NODE\",\"message\":\"A block\",\"thread\":\"peer\"}\n"
+        ));
+    }
+
+    #[test]
+    fn test06_each_output_uses_its_own_filters() {
+        let mut vec: Vec<u8> = Vec::new();
+        let buffer = LogBuffer::new(10);
+        let (logger_sender, logger_receiver) = initialize_logger(&mut vec, false);
+        let logger_receiver = logger_receiver
+            .filter_levels(vec![Level::WALLET])
+            .with_sink(
+                LogSink::new(buffer.clone())
+                    .filter_levels(vec![Level::NODE])
+                    .filter_severities(SeverityFilter {
+                        default: Severity::DEBUG,
+                        levels: HashMap::new(),
+                    }),
+            );
+
+        logger_sender.log_node("A block".to_string()).unwrap();
+        logger_sender
+            .log_debug(Level::NODE, "A header".to_string())
+            .unwrap();
+        logger_sender.log_wallet("An account".to_string()).unwrap();
+        std::mem::drop(logger_sender);
+
+        logger_receiver.receive_log().unwrap();
+
+        let contents = String::from_utf8(vec).unwrap();
+        let lines = buffer.lines();
+
+        assert!(!contents.contains("A block"));
+        assert!(contents.contains("[WALLET] An account"));
+        assert_eq!(2, lines.len());
+        assert!(lines[0].ends_with("[INFO] [NODE] A block"));
+        assert!(lines[1].ends_with("[DEBUG] [NODE] A header"));
+    }
 }
@@ -1,4 +1,11 @@
-use super::{error_log::ErrorLog, level::Level, logger::MessageLog};
+use super::{
+    error_log::ErrorLog,
+    level::Level,
+    log_format::LogFormat,
+    log_sink::{is_registered, LogSink},
+    logger::MessageLog,
+    severity::SeverityFilter,
+};
 
 use chrono::offset::Utc;
 
@@ -10,6 +17,10 @@ pub struct LoggerReceiver<W: Write> {
     receiver: Receiver<MessageLog>,
     output: W,
     display_in_terminal: bool,
+    levels: Option<Vec<Level>>,
+    severities: SeverityFilter,
+    format: LogFormat,
+    sinks: Vec<LogSink>,
 }
 
 impl<W: Write> LoggerReceiver<W> {
@@ -23,18 +34,60 @@ impl<W: Write> LoggerReceiver<W> {
             receiver,
             output,
             display_in_terminal,
+            levels: None,
+            severities: SeverityFilter::default(),
+            format: LogFormat::default(),
+            sinks: Vec::new(),
         }
     }
 
-    /// Receive the messages sent by `LoggerSender`
+    /// Only the messages of the given levels will be registered. The errors are always registered
+    pub fn filter_levels(self, levels: Vec<Level>) -> Self {
+        LoggerReceiver {
+            levels: Some(levels),
+            ..self
+        }
+    }
+
+    /// Only the messages at least as important as the severity of their level will be registered
+    pub fn filter_severities(self, severities: SeverityFilter) -> Self {
+        LoggerReceiver { severities, ..self }
+    }
+
+    /// The messages will be written in the given format
+    pub fn with_format(self, format: LogFormat) -> Self {
+        LoggerReceiver { format, ..self }
+    }
+
+    /// The messages will also be written in the given output, with its own filters and format
+    pub fn with_sink(mut self, sink: LogSink) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Receive the messages sent by `LoggerSender`, writing them in the given file and in every
+    /// other output added
     ///
     /// ### Errores
-    ///  * `ErrorLog::CouldNotWriteInFile`: It will appear when no more lines can be added to the given file
+    ///  * `ErrorLog::CouldNotWriteInFile`: It will appear when no more lines can be added to the given file or to one of the outputs
     pub fn receive_log(self) -> Result<(), ErrorLog> {
         let mut file = self.output;
+        let mut sinks = self.sinks;
+
+        for (level, severity, message, thread) in self.receiver {
+            let time = Utc::now();
 
-        for (level, message) in self.receiver {
-            let text = Self::format_message(level, message);
+            for sink in sinks.iter_mut() {
+                sink.write(time, &level, severity, &thread, &message)?;
+            }
+
+            if !is_registered(&self.levels, &self.severities, &level, severity) {
+                continue;
+            }
+
+            let text = self
+                .format
+                .format(time, &level, severity, &thread, &message);
 
             if file.write(text.as_bytes()).is_err() {
                 return Err(ErrorLog::CouldNotWriteInFile);
@@ -47,13 +100,4 @@ impl<W: Write> LoggerReceiver<W> {
 
         Ok(())
     }
-
-    /// Format in which the message will be written in the file
-    /// Includes the time in which the message is received
-    fn format_message(level: Level, message: String) -> String {
-        let datetime = Utc::now();
-        let timestamp = datetime.timestamp();
-
-        format!("Time: [{timestamp}s]: [{level}] {message}\n")
-    }
 }
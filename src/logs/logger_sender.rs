@@ -1,9 +1,11 @@
 use super::logger::MessageLog;
 use std::fmt::Debug;
 use std::sync::mpsc::Sender;
+use std::thread;
 
 use super::error_log::ErrorLog;
 use super::level::Level;
+use super::severity::Severity;
 
 /// Manages the log messages. This can be cloned to have multiple senders
 #[derive(Debug, Clone)]
@@ -16,17 +18,70 @@ impl LoggerSender {
         LoggerSender { sender }
     }
 
-    /// Sends the message with the desired level
+    /// Sends the message with the desired level. The severity is `Severity::ERROR` for the
+    /// `Level::ERROR` and `Severity::INFO` for the rest
     ///
     /// ### Errores
     ///  * `ErrorLog::ReceiverNotFound`: It will appear when the receiver it's drop and can't send the message
     pub fn log(&self, level: Level, message: String) -> Result<(), ErrorLog> {
-        if self.sender.send((level, message)).is_err() {
+        let severity = match level {
+            Level::ERROR => Severity::ERROR,
+            _ => Severity::INFO,
+        };
+        self.log_with_severity(level, severity, message)
+    }
+
+    /// Sends the message with the desired level and severity, along with the name of the current
+    /// thread, or its id if it does not have a name
+    ///
+    /// ### Errores
+    ///  * `ErrorLog::ReceiverNotFound`: It will appear when the receiver it's drop and can't send the message
+    pub fn log_with_severity(
+        &self,
+        level: Level,
+        severity: Severity,
+        message: String,
+    ) -> Result<(), ErrorLog> {
+        let current = thread::current();
+        let thread = match current.name() {
+            Some(name) => name.to_string(),
+            None => format!("{:?}", current.id()),
+        };
+
+        if self
+            .sender
+            .send((level, severity, message, thread))
+            .is_err()
+        {
             return Err(ErrorLog::ReceiverNotFound);
         }
         Ok(())
     }
 
+    /// Sends the message with the desired level and severity: `Severity::WARN`
+    ///
+    /// ### Errores
+    ///  * `ErrorLog::ReceiverNotFound`: It will appear when the receiver it's drop and can't send the message
+    pub fn log_warning(&self, level: Level, message: String) -> Result<(), ErrorLog> {
+        self.log_with_severity(level, Severity::WARN, message)
+    }
+
+    /// Sends the message with the desired level and severity: `Severity::DEBUG`
+    ///
+    /// ### Errores
+    ///  * `ErrorLog::ReceiverNotFound`: It will appear when the receiver it's drop and can't send the message
+    pub fn log_debug(&self, level: Level, message: String) -> Result<(), ErrorLog> {
+        self.log_with_severity(level, Severity::DEBUG, message)
+    }
+
+    /// Sends the message with the desired level and severity: `Severity::TRACE`
+    ///
+    /// ### Errores
+    ///  * `ErrorLog::ReceiverNotFound`: It will appear when the receiver it's drop and can't send the message
+    pub fn log_trace(&self, level: Level, message: String) -> Result<(), ErrorLog> {
+        self.log_with_severity(level, Severity::TRACE, message)
+    }
+
     pub fn log_data<D: Debug>(&self, level: Level, data: D) -> Result<(), ErrorLog> {
         self.log(level, format!("{:?}", data))
     }
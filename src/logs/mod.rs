@@ -1,5 +1,11 @@
 pub mod error_log;
+pub mod gzip;
 pub mod level;
+pub mod log_buffer;
+pub mod log_format;
+pub mod log_sink;
 pub mod logger;
 pub mod logger_receiver;
 pub mod logger_sender;
+pub mod rotating_file;
+pub mod severity;
@@ -0,0 +1,183 @@
+use super::gzip;
+
+use crate::configurations::log_rotation_config::LogRotationConfig;
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+const COMPRESSED_EXTENSION: &str = "gz";
+
+/// It's a log file that is rotated when it exceeds the maximum size given in the configuration.
+/// The old files are kept as `<file>.1`, `<file>.2`, ... (with `.gz` at the end if they are
+/// compressed), where `<file>.1` is the most recent one, and the oldest ones are removed
+#[derive(Debug)]
+pub struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    rotation: LogRotationConfig,
+}
+
+impl RotatingFile {
+    /// Opens the log file to continue writing at its end, creating it if it does not exist
+    ///
+    /// ### Error
+    ///  * `io::Error`: It will appear when the file cannot be opened
+    pub fn new(path: &Path, rotation: LogRotationConfig) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+
+        Ok(RotatingFile {
+            path: path.to_path_buf(),
+            file,
+            size,
+            rotation,
+        })
+    }
+
+    /// Returns the path of the old log file of the given position
+    fn archive_path(&self, position: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{position}"));
+        if self.rotation.compress {
+            name.push(format!(".{COMPRESSED_EXTENSION}"));
+        }
+        PathBuf::from(name)
+    }
+
+    /// Moves the current file to the first archive, moving every archive one position and removing
+    /// the oldest one. After it, the log file is empty
+    ///
+    /// ### Error
+    ///  * `io::Error`: It will appear when the files cannot be moved, compressed or created
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+
+        if self.rotation.archives > 0 {
+            let _ = fs::remove_file(self.archive_path(self.rotation.archives));
+            for position in (1..self.rotation.archives).rev() {
+                let archive = self.archive_path(position);
+                if archive.exists() {
+                    fs::rename(archive, self.archive_path(position + 1))?;
+                }
+            }
+
+            match self.rotation.compress {
+                true => fs::write(self.archive_path(1), gzip::compress(&fs::read(&self.path)?))?,
+                false => fs::copy(&self.path, self.archive_path(1)).map(|_| ())?,
+            }
+        }
+
+        self.file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        self.size = 0;
+
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.size > 0 && self.size + buf.len() as u64 > self.rotation.max_size {
+            self.rotate()?;
+        }
+
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+
+    fn log_directory(test_name: &str) -> PathBuf {
+        let directory = env::temp_dir().join(format!("cargosos_logs_{test_name}"));
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    #[test]
+    fn test01_file_is_rotated_when_it_exceeds_the_size() {
+        let directory = log_directory("rotation");
+        let path = directory.join("logs.txt");
+        let rotation = LogRotationConfig {
+            max_size: 10,
+            archives: 2,
+            compress: false,
+        };
+
+        let mut file = RotatingFile::new(&path, rotation).unwrap();
+        for line in ["first\n", "second\n", "third\n", "fourth\n"] {
+            file.write_all(line.as_bytes()).unwrap();
+        }
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "fourth\n");
+        assert_eq!(
+            fs::read_to_string(directory.join("logs.txt.1")).unwrap(),
+            "third\n"
+        );
+        assert_eq!(
+            fs::read_to_string(directory.join("logs.txt.2")).unwrap(),
+            "second\n"
+        );
+        assert!(!directory.join("logs.txt.3").exists());
+
+        let _ = fs::remove_dir_all(directory);
+    }
+
+    #[test]
+    fn test02_old_files_can_be_compressed() {
+        let directory = log_directory("compression");
+        let path = directory.join("logs.txt");
+        let rotation = LogRotationConfig {
+            max_size: 8,
+            archives: 1,
+            compress: true,
+        };
+
+        let mut file = RotatingFile::new(&path, rotation).unwrap();
+        file.write_all(b"a block\n").unwrap();
+        file.write_all(b"another\n").unwrap();
+
+        let compressed = fs::read(directory.join("logs.txt.1.gz")).unwrap();
+        assert_eq!(compressed, gzip::compress(b"a block\n"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "another\n");
+
+        let _ = fs::remove_dir_all(directory);
+    }
+
+    #[test]
+    fn test03_without_archives_the_file_is_only_emptied() {
+        let directory = log_directory("without_archives");
+        let path = directory.join("logs.txt");
+        fs::write(&path, "previous\n").unwrap();
+        let rotation = LogRotationConfig {
+            max_size: 12,
+            archives: 0,
+            compress: false,
+        };
+
+        let mut file = RotatingFile::new(&path, rotation).unwrap();
+        file.write_all(b"new line\n").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new line\n");
+        assert!(!directory.join("logs.txt.1").exists());
+
+        let _ = fs::remove_dir_all(directory);
+    }
+}
@@ -0,0 +1,154 @@
+use super::level::Level;
+
+use crate::configurations::{
+    error_configuration::ErrorConfiguration,
+    parsable::{value_from_map, Key, KeyValueMap, Parsable},
+};
+
+use std::{cmp::PartialEq, collections::HashMap, fmt::Display, str::FromStr};
+
+const DEFAULT_SEVERITY: &str = "default";
+
+/// It represents how important a log message is, from the most important to the least one.
+/// It's independent of the level, which is the location from where the log is called
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    ERROR,
+    WARN,
+    INFO,
+    DEBUG,
+    TRACE,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::ERROR => write!(f, "ERROR"),
+            Severity::WARN => write!(f, "WARN"),
+            Severity::INFO => write!(f, "INFO"),
+            Severity::DEBUG => write!(f, "DEBUG"),
+            Severity::TRACE => write!(f, "TRACE"),
+        }
+    }
+}
+
+impl FromStr for Severity {
+    type Err = ErrorConfiguration;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_uppercase().as_str() {
+            "ERROR" => Ok(Severity::ERROR),
+            "WARN" | "WARNING" => Ok(Severity::WARN),
+            "INFO" => Ok(Severity::INFO),
+            "DEBUG" => Ok(Severity::DEBUG),
+            "TRACE" => Ok(Severity::TRACE),
+            _ => Err(ErrorConfiguration::ErrorCantParseValue(format!(
+                "severity of {:?}",
+                value
+            ))),
+        }
+    }
+}
+
+impl Parsable for Severity {
+    fn parse(name: &str, map: &KeyValueMap) -> Result<Self, ErrorConfiguration> {
+        let value = value_from_map(name.to_string(), map)?;
+        value.parse::<Severity>()
+    }
+}
+
+/// It represents the least important severity registered for each level. The levels that are
+/// not given use the default severity
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeverityFilter {
+    pub default: Severity,
+    pub levels: HashMap<Level, Severity>,
+}
+
+impl SeverityFilter {
+    /// Returns if a message of the given level and severity has to be registered
+    pub fn allows(&self, level: &Level, severity: Severity) -> bool {
+        let minimum = match self.levels.get(level) {
+            Some(minimum) => *minimum,
+            None => self.default,
+        };
+
+        severity <= minimum
+    }
+}
+
+impl Default for SeverityFilter {
+    /// Every message up to `INFO` is registered
+    fn default() -> Self {
+        SeverityFilter {
+            default: Severity::INFO,
+            levels: HashMap::new(),
+        }
+    }
+}
+
+/// Parses a structure where each key is a level and its value the severity for that level, as in
+/// ```text
+/// severities {
+///     default = warn
+///     connection = debug
+/// }
+/// ```
+impl Parsable for SeverityFilter {
+    fn parse(name: &str, map: &KeyValueMap) -> Result<Self, ErrorConfiguration> {
+        let severities = HashMap::<Key, Severity>::parse(name, map)?;
+
+        let mut filter = SeverityFilter::default();
+        for (key, severity) in severities {
+            match key.as_str() {
+                DEFAULT_SEVERITY => filter.default = severity,
+                _ => {
+                    filter.levels.insert(key.parse::<Level>()?, severity);
+                }
+            }
+        }
+
+        Ok(filter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::configurations::parsable::parse_structure;
+
+    #[test]
+    fn test01_severities_are_ordered_by_importance() {
+        assert!(Severity::ERROR < Severity::WARN);
+        assert!(Severity::INFO < Severity::DEBUG);
+        assert_eq!(Ok(Severity::WARN), "warning".parse::<Severity>());
+        assert!("verbose".parse::<Severity>().is_err());
+    }
+
+    #[test]
+    fn test02_filter_uses_the_severity_of_each_level() {
+        let configuration = "severities {
+            default = warn
+            connection = debug
+        }";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        let filter = SeverityFilter::parse("severities", &map).unwrap();
+
+        assert!(filter.allows(&Level::CONNECTION, Severity::DEBUG));
+        assert!(!filter.allows(&Level::CONNECTION, Severity::TRACE));
+        assert!(filter.allows(&Level::NODE, Severity::WARN));
+        assert!(!filter.allows(&Level::NODE, Severity::INFO));
+    }
+
+    #[test]
+    fn test03_does_not_accept_unknown_levels() {
+        let configuration = "severities {
+            peers = debug
+        }";
+        let map = parse_structure(configuration.to_string()).unwrap();
+
+        assert!(SeverityFilter::parse("severities", &map).is_err());
+    }
+}
@@ -106,6 +106,7 @@ impl DeserializableLittleEndian for BitfieldServices {
             SupportedServices::NodeBloom,
             SupportedServices::NodeWitness,
             SupportedServices::NodeXThin,
+            SupportedServices::NodeCompactFilters,
             SupportedServices::NodeNetworkLimited,
         ];
 
@@ -20,6 +20,15 @@ impl Message for BlockMessage {
     fn get_command_name() -> CommandName {
         CommandName::Block
     }
+
+    fn io_deserialize_limited(
+        stream: &mut dyn Read,
+        payload_size: u64,
+    ) -> Result<Self, ErrorSerialization> {
+        Ok(BlockMessage {
+            block: Block::io_deserialize_limited(stream, payload_size)?,
+        })
+    }
 }
 
 impl SerializableInternalOrder for BlockMessage {
@@ -43,7 +52,7 @@ mod tests {
 
     use crate::block_structure::{
         block_header::BlockHeader, block_version, compact256::Compact256, outpoint::Outpoint,
-        transaction::Transaction, transaction_input::TransactionInput,
+        script::ScriptBuf, transaction::Transaction, transaction_input::TransactionInput,
         transaction_output::TransactionOutput,
     };
 
@@ -66,14 +75,14 @@ mod tests {
 
         let transaction_output = TransactionOutput {
             value: 10,
-            pk_script: vec![4, 5, 6],
+            pk_script: ScriptBuf::from(vec![4, 5, 6]),
         };
 
         let transaction_1 = Transaction {
             version: 1,
             tx_in: vec![transaction_input],
             tx_out: vec![transaction_output],
-            time: 0,
+            lock_time: 0,
         };
 
         let transaction_2 = transaction_1.clone();
@@ -120,14 +129,14 @@ mod tests {
 
         let transaction_output = TransactionOutput {
             value: 10,
-            pk_script: vec![4, 5, 6],
+            pk_script: ScriptBuf::from(vec![4, 5, 6]),
         };
 
         let transaction_1 = Transaction {
             version: 1,
             tx_in: vec![transaction_input],
             tx_out: vec![transaction_output],
-            time: 0,
+            lock_time: 0,
         };
 
         let transaction_2 = transaction_1.clone();
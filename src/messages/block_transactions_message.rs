@@ -0,0 +1,70 @@
+use super::{
+    command_name::{CommandName, MAX_PAYLOAD_SIZE},
+    compact_size::CompactSize,
+    message::Message,
+};
+
+use crate::block_structure::{hash::HashType, transaction::Transaction};
+
+use crate::serialization::{
+    deserializable_internal_order::DeserializableInternalOrder,
+    deserializable_little_endian::DeserializableLittleEndian,
+    error_serialization::ErrorSerialization,
+    serializable_internal_order::SerializableInternalOrder,
+    serializable_little_endian::SerializableLittleEndian,
+};
+
+use std::io::{Read, Write};
+
+/// It's the blocktxn message, with the transactions requested by a get block transactions message
+/// in the same order they were requested
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockTransactionsMessage {
+    pub block_hash: HashType,
+    pub transactions: Vec<Transaction>,
+}
+
+impl Message for BlockTransactionsMessage {
+    fn get_command_name() -> CommandName {
+        CommandName::BlockTransactions
+    }
+
+    fn io_deserialize_limited(
+        stream: &mut dyn Read,
+        payload_size: u64,
+    ) -> Result<Self, ErrorSerialization> {
+        let mut stream = stream.take(payload_size);
+        let block_hash = HashType::le_deserialize(&mut stream)?;
+
+        let count = CompactSize::le_deserialize(&mut stream)?.value;
+        let mut transactions: Vec<Transaction> = Vec::new();
+        for _ in 0..count {
+            let remaining = stream.limit();
+            transactions.push(Transaction::io_deserialize_limited(&mut stream, remaining)?);
+        }
+
+        Ok(BlockTransactionsMessage {
+            block_hash,
+            transactions,
+        })
+    }
+}
+
+impl SerializableInternalOrder for BlockTransactionsMessage {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        self.block_hash.le_serialize(stream)?;
+
+        CompactSize::new(self.transactions.len() as u64).le_serialize(stream)?;
+        for transaction in self.transactions.iter() {
+            transaction.io_serialize(stream)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl DeserializableInternalOrder for BlockTransactionsMessage {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        Self::io_deserialize_limited(stream, MAX_PAYLOAD_SIZE as u64)
+    }
+}
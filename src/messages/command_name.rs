@@ -4,12 +4,26 @@ use crate::serialization::{
     serializable_internal_order::SerializableInternalOrder,
 };
 
+use crate::{
+    block_structure::{block_chain::MAX_HEADERS_TO_SEND, bloom_filter::MAX_BLOOM_FILTER_SIZE},
+    connections::user_agent::MAX_USER_AGENT_LENGTH,
+    messages::filter_add_message::MAX_FILTER_ADD_SIZE,
+};
+
 use std::io::{Read, Write};
 
 use std::convert::{TryFrom, TryInto};
 
 type CommandNameType = [u8; 12];
 
+/// The maximum size of the payload of any message
+pub const MAX_PAYLOAD_SIZE: u32 = 4_000_000;
+
+const MAX_INVENTORY_ENTRIES: u32 = 50_000;
+const MAX_ADDRESSES: u32 = 1_000;
+const MAX_LOCATOR_HASHES: u32 = 101;
+const MAX_COMPACT_SIZE_LENGTH: u32 = 9;
+
 const VERSION_NAME: CommandNameType = [
     b'v', b'e', b'r', b's', b'i', b'o', b'n', b'\0', b'\0', b'\0', b'\0', b'\0',
 ];
@@ -55,9 +69,60 @@ const ALERT_NAME: CommandNameType = [
 const TX_NAME: CommandNameType = [
     b't', b'x', b'\0', b'\0', b'\0', b'\0', b'\0', b'\0', b'\0', b'\0', b'\0', b'\0',
 ];
+const COMPACT_BLOCK_NAME: CommandNameType = [
+    b'c', b'm', b'p', b'c', b't', b'b', b'l', b'o', b'c', b'k', b'\0', b'\0',
+];
+const GET_BLOCK_TRANSACTIONS_NAME: CommandNameType = [
+    b'g', b'e', b't', b'b', b'l', b'o', b'c', b'k', b't', b'x', b'n', b'\0',
+];
+const BLOCK_TRANSACTIONS_NAME: CommandNameType = [
+    b'b', b'l', b'o', b'c', b'k', b't', b'x', b'n', b'\0', b'\0', b'\0', b'\0',
+];
+const FILTER_LOAD_NAME: CommandNameType = [
+    b'f', b'i', b'l', b't', b'e', b'r', b'l', b'o', b'a', b'd', b'\0', b'\0',
+];
+const FILTER_ADD_NAME: CommandNameType = [
+    b'f', b'i', b'l', b't', b'e', b'r', b'a', b'd', b'd', b'\0', b'\0', b'\0',
+];
+const FILTER_CLEAR_NAME: CommandNameType = [
+    b'f', b'i', b'l', b't', b'e', b'r', b'c', b'l', b'e', b'a', b'r', b'\0',
+];
+const MERKLE_BLOCK_NAME: CommandNameType = [
+    b'm', b'e', b'r', b'k', b'l', b'e', b'b', b'l', b'o', b'c', b'k', b'\0',
+];
+const GET_COMPACT_FILTERS_NAME: CommandNameType = [
+    b'g', b'e', b't', b'c', b'f', b'i', b'l', b't', b'e', b'r', b's', b'\0',
+];
+const COMPACT_FILTER_NAME: CommandNameType = [
+    b'c', b'f', b'i', b'l', b't', b'e', b'r', b'\0', b'\0', b'\0', b'\0', b'\0',
+];
+const GET_COMPACT_FILTER_HEADERS_NAME: CommandNameType = [
+    b'g', b'e', b't', b'c', b'f', b'h', b'e', b'a', b'd', b'e', b'r', b's',
+];
+const COMPACT_FILTER_HEADERS_NAME: CommandNameType = [
+    b'c', b'f', b'h', b'e', b'a', b'd', b'e', b'r', b's', b'\0', b'\0', b'\0',
+];
+const GET_COMPACT_FILTER_CHECKPOINT_NAME: CommandNameType = [
+    b'g', b'e', b't', b'c', b'f', b'c', b'h', b'e', b'c', b'k', b'p', b't',
+];
+const COMPACT_FILTER_CHECKPOINT_NAME: CommandNameType = [
+    b'c', b'f', b'c', b'h', b'e', b'c', b'k', b'p', b't', b'\0', b'\0', b'\0',
+];
+const MEMPOOL_NAME: CommandNameType = [
+    b'm', b'e', b'm', b'p', b'o', b'o', b'l', b'\0', b'\0', b'\0', b'\0', b'\0',
+];
+const NOT_FOUND_NAME: CommandNameType = [
+    b'n', b'o', b't', b'f', b'o', b'u', b'n', b'd', b'\0', b'\0', b'\0', b'\0',
+];
+const REJECT_NAME: CommandNameType = [
+    b'r', b'e', b'j', b'e', b'c', b't', b'\0', b'\0', b'\0', b'\0', b'\0', b'\0',
+];
+const WTXID_RELAY_NAME: CommandNameType = [
+    b'w', b't', b'x', b'i', b'd', b'r', b'e', b'l', b'a', b'y', b'\0', b'\0',
+];
 
 /// It's the representation of the type of message received or sent
-#[derive(Debug, Copy, Clone, std::cmp::PartialEq)]
+#[derive(Debug, Clone, std::cmp::PartialEq)]
 pub enum CommandName {
     Version,
     Verack,
@@ -74,6 +139,64 @@ pub enum CommandName {
     GetData,
     Alert,
     Tx,
+    CompactBlock,
+    GetBlockTransactions,
+    BlockTransactions,
+    FilterLoad,
+    FilterAdd,
+    FilterClear,
+    MerkleBlock,
+    GetCompactFilters,
+    CompactFilter,
+    GetCompactFilterHeaders,
+    CompactFilterHeaders,
+    GetCompactFilterCheckpoint,
+    CompactFilterCheckpoint,
+    Mempool,
+    NotFound,
+    Reject,
+    WtxidRelay,
+    Unknown(String),
+}
+
+impl CommandName {
+    /// Returns the maximum size of the payload of the message. A peer that announces a bigger payload
+    /// is misbehaving, so the payload must not be read
+    pub fn max_payload_size(&self) -> u32 {
+        match self {
+            CommandName::Verack
+            | CommandName::SendHeaders
+            | CommandName::FilterClear
+            | CommandName::Mempool
+            | CommandName::WtxidRelay => 0,
+            CommandName::Ping | CommandName::Pong | CommandName::FeeFilter => 8,
+            CommandName::SendCmpct => 9,
+            CommandName::Version => 86 + MAX_COMPACT_SIZE_LENGTH + MAX_USER_AGENT_LENGTH as u32,
+            CommandName::GetHeaders => 4 + MAX_COMPACT_SIZE_LENGTH + (MAX_LOCATOR_HASHES + 1) * 32,
+            CommandName::Headers => MAX_COMPACT_SIZE_LENGTH + MAX_HEADERS_TO_SEND as u32 * 81,
+            CommandName::Inventory | CommandName::GetData | CommandName::NotFound => {
+                MAX_COMPACT_SIZE_LENGTH + MAX_INVENTORY_ENTRIES * 36
+            }
+            CommandName::Addr => MAX_COMPACT_SIZE_LENGTH + MAX_ADDRESSES * 30,
+            CommandName::FilterLoad => MAX_COMPACT_SIZE_LENGTH + MAX_BLOOM_FILTER_SIZE as u32 + 9,
+            CommandName::FilterAdd => MAX_COMPACT_SIZE_LENGTH + MAX_FILTER_ADD_SIZE as u32,
+            CommandName::Block
+            | CommandName::Tx
+            | CommandName::Alert
+            | CommandName::CompactBlock
+            | CommandName::GetBlockTransactions
+            | CommandName::BlockTransactions
+            | CommandName::MerkleBlock
+            | CommandName::GetCompactFilters
+            | CommandName::CompactFilter
+            | CommandName::GetCompactFilterHeaders
+            | CommandName::CompactFilterHeaders
+            | CommandName::GetCompactFilterCheckpoint
+            | CommandName::CompactFilterCheckpoint
+            | CommandName::Reject
+            | CommandName::Unknown(_) => MAX_PAYLOAD_SIZE,
+        }
+    }
 }
 
 impl From<CommandName> for CommandNameType {
@@ -94,6 +217,30 @@ impl From<CommandName> for CommandNameType {
             CommandName::GetData => GET_DATA_NAME,
             CommandName::Alert => ALERT_NAME,
             CommandName::Tx => TX_NAME,
+            CommandName::CompactBlock => COMPACT_BLOCK_NAME,
+            CommandName::GetBlockTransactions => GET_BLOCK_TRANSACTIONS_NAME,
+            CommandName::BlockTransactions => BLOCK_TRANSACTIONS_NAME,
+            CommandName::FilterLoad => FILTER_LOAD_NAME,
+            CommandName::FilterAdd => FILTER_ADD_NAME,
+            CommandName::FilterClear => FILTER_CLEAR_NAME,
+            CommandName::MerkleBlock => MERKLE_BLOCK_NAME,
+            CommandName::GetCompactFilters => GET_COMPACT_FILTERS_NAME,
+            CommandName::CompactFilter => COMPACT_FILTER_NAME,
+            CommandName::GetCompactFilterHeaders => GET_COMPACT_FILTER_HEADERS_NAME,
+            CommandName::CompactFilterHeaders => COMPACT_FILTER_HEADERS_NAME,
+            CommandName::GetCompactFilterCheckpoint => GET_COMPACT_FILTER_CHECKPOINT_NAME,
+            CommandName::CompactFilterCheckpoint => COMPACT_FILTER_CHECKPOINT_NAME,
+            CommandName::Mempool => MEMPOOL_NAME,
+            CommandName::NotFound => NOT_FOUND_NAME,
+            CommandName::Reject => REJECT_NAME,
+            CommandName::WtxidRelay => WTXID_RELAY_NAME,
+            CommandName::Unknown(name) => {
+                let mut command_name: CommandNameType = [b'\0'; 12];
+                name.bytes()
+                    .zip(command_name.iter_mut())
+                    .for_each(|(byte, command_byte)| *command_byte = byte);
+                command_name
+            }
         }
     }
 }
@@ -118,17 +265,49 @@ impl TryFrom<CommandNameType> for CommandName {
             GET_DATA_NAME => Ok(CommandName::GetData),
             ALERT_NAME => Ok(CommandName::Alert),
             TX_NAME => Ok(CommandName::Tx),
-            _ => Err(ErrorSerialization::ErrorInDeserialization(format!(
-                "Invalid command name, we get: {:?}",
-                value
-            ))),
+            COMPACT_BLOCK_NAME => Ok(CommandName::CompactBlock),
+            GET_BLOCK_TRANSACTIONS_NAME => Ok(CommandName::GetBlockTransactions),
+            BLOCK_TRANSACTIONS_NAME => Ok(CommandName::BlockTransactions),
+            FILTER_LOAD_NAME => Ok(CommandName::FilterLoad),
+            FILTER_ADD_NAME => Ok(CommandName::FilterAdd),
+            FILTER_CLEAR_NAME => Ok(CommandName::FilterClear),
+            MERKLE_BLOCK_NAME => Ok(CommandName::MerkleBlock),
+            GET_COMPACT_FILTERS_NAME => Ok(CommandName::GetCompactFilters),
+            COMPACT_FILTER_NAME => Ok(CommandName::CompactFilter),
+            GET_COMPACT_FILTER_HEADERS_NAME => Ok(CommandName::GetCompactFilterHeaders),
+            COMPACT_FILTER_HEADERS_NAME => Ok(CommandName::CompactFilterHeaders),
+            GET_COMPACT_FILTER_CHECKPOINT_NAME => Ok(CommandName::GetCompactFilterCheckpoint),
+            COMPACT_FILTER_CHECKPOINT_NAME => Ok(CommandName::CompactFilterCheckpoint),
+            MEMPOOL_NAME => Ok(CommandName::Mempool),
+            NOT_FOUND_NAME => Ok(CommandName::NotFound),
+            REJECT_NAME => Ok(CommandName::Reject),
+            WTXID_RELAY_NAME => Ok(CommandName::WtxidRelay),
+            _ => {
+                let name: Vec<u8> = value
+                    .iter()
+                    .take_while(|byte| **byte != b'\0')
+                    .copied()
+                    .collect();
+                let is_padded =
+                    !name.is_empty() && value[name.len()..].iter().all(|byte| *byte == b'\0');
+
+                match String::from_utf8(name) {
+                    Ok(name) if is_padded && name.chars().all(|c| c.is_ascii_graphic()) => {
+                        Ok(CommandName::Unknown(name))
+                    }
+                    _ => Err(ErrorSerialization::ErrorInDeserialization(format!(
+                        "Invalid command name, we get: {:?}",
+                        value
+                    ))),
+                }
+            }
         }
     }
 }
 
 impl SerializableInternalOrder for CommandName {
     fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
-        let command_name: CommandNameType = (*self).into();
+        let command_name: CommandNameType = self.clone().into();
         command_name.io_serialize(stream)?;
 
         Ok(())
@@ -174,4 +353,29 @@ mod tests {
 
         assert_eq!(deserialized_command, command_name_alert);
     }
+
+    #[test]
+    fn test_04_unknown_command_name_is_kept() {
+        let command_name_type: CommandNameType = [
+            b's', b'e', b'n', b'd', b'a', b'd', b'd', b'r', b'v', b'2', b'\0', b'\0',
+        ];
+        let command_name = CommandName::io_deserialize(&mut command_name_type.as_slice()).unwrap();
+
+        assert_eq!(command_name, CommandName::Unknown("sendaddrv2".to_string()));
+
+        let mut vec = Vec::new();
+        command_name.io_serialize(&mut vec).unwrap();
+
+        assert_eq!(vec, command_name_type);
+    }
+
+    #[test]
+    fn test_05_malformed_command_name_is_an_error() {
+        let command_name_type: CommandNameType = [
+            b'p', b'i', b'n', b'g', b'\0', b'x', b'\0', b'\0', b'\0', b'\0', b'\0', b'\0',
+        ];
+
+        assert!(CommandName::try_from(command_name_type).is_err());
+        assert!(CommandName::try_from([b'\0'; 12]).is_err());
+    }
 }
@@ -0,0 +1,317 @@
+use super::{
+    command_name::{CommandName, MAX_PAYLOAD_SIZE},
+    compact_size::CompactSize,
+    message::Message,
+};
+
+use crate::block_structure::{
+    block::Block,
+    block_header::BlockHeader,
+    hash::{hash256, siphash24, HashType},
+    transaction::Transaction,
+};
+
+use crate::serialization::{
+    deserializable_fix_size::DeserializableFixSize,
+    deserializable_internal_order::DeserializableInternalOrder,
+    deserializable_little_endian::DeserializableLittleEndian,
+    error_serialization::ErrorSerialization, serializable_big_endian::SerializableBigEndian,
+    serializable_internal_order::SerializableInternalOrder,
+    serializable_little_endian::SerializableLittleEndian,
+};
+
+use std::io::{Read, Write};
+
+pub const SHORT_TRANSACTION_ID_SIZE: usize = 6;
+const SHORT_TRANSACTION_ID_MASK: u64 = 0xFFFF_FFFF_FFFF;
+
+pub type ShortTransactionId = u64;
+
+/// It's a transaction sent complete in a compact block, with its position in the block
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrefilledTransaction {
+    pub index: u64,
+    pub transaction: Transaction,
+}
+
+/// It's the cmpctblock message, where the transactions of the block are replaced by short ids
+/// so the peer can rebuild the block with the transactions it already has
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompactBlockMessage {
+    pub header: BlockHeader,
+    pub nonce: u64,
+    pub short_ids: Vec<ShortTransactionId>,
+    pub prefilled_transactions: Vec<PrefilledTransaction>,
+}
+
+impl CompactBlockMessage {
+    /// Creates the compact block of a block, sending complete only the coinbase transaction
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::ErrorInSerialization`: It will appear when a transaction id cannot be calculated
+    pub fn new(block: &Block, nonce: u64) -> Result<Self, ErrorSerialization> {
+        let mut compact_block = CompactBlockMessage {
+            header: block.header,
+            nonce,
+            short_ids: Vec::new(),
+            prefilled_transactions: Vec::new(),
+        };
+
+        let mut transactions = block.transactions.iter();
+        if let Some(coinbase) = transactions.next() {
+            compact_block
+                .prefilled_transactions
+                .push(PrefilledTransaction {
+                    index: 0,
+                    transaction: coinbase.clone(),
+                });
+        }
+
+        let keys = compact_block.get_short_id_keys()?;
+        for transaction in transactions {
+            let transaction_id = match transaction.get_tx_id() {
+                Ok(transaction_id) => transaction_id,
+                Err(_) => {
+                    return Err(ErrorSerialization::ErrorInSerialization(
+                        "While getting the id of a transaction of the compact block".to_string(),
+                    ))
+                }
+            };
+            compact_block
+                .short_ids
+                .push(calculate_short_id(keys, &transaction_id));
+        }
+
+        Ok(compact_block)
+    }
+
+    /// Returns the amount of transactions in the block
+    pub fn transaction_count(&self) -> usize {
+        self.short_ids.len() + self.prefilled_transactions.len()
+    }
+
+    /// Calculates the keys used to get the short ids of the transactions, from the header and the nonce
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::ErrorInSerialization`: It will appear when the header cannot be hashed
+    pub fn get_short_id_keys(&self) -> Result<(u64, u64), ErrorSerialization> {
+        let mut buffer: Vec<u8> = Vec::new();
+        self.header
+            .serialize_without_transaction_count(&mut buffer)?;
+        self.nonce.le_serialize(&mut buffer)?;
+
+        let hash = hash256(&buffer)?;
+
+        let mut first_key: &[u8] = &hash[0..8];
+        let mut second_key: &[u8] = &hash[8..16];
+        Ok((
+            u64::le_deserialize(&mut first_key)?,
+            u64::le_deserialize(&mut second_key)?,
+        ))
+    }
+}
+
+/// Calculates the short id of a transaction with the keys of a compact block
+pub fn calculate_short_id(keys: (u64, u64), transaction_id: &HashType) -> ShortTransactionId {
+    siphash24(keys.0, keys.1, transaction_id) & SHORT_TRANSACTION_ID_MASK
+}
+
+impl Message for CompactBlockMessage {
+    fn get_command_name() -> CommandName {
+        CommandName::CompactBlock
+    }
+
+    fn io_deserialize_limited(
+        stream: &mut dyn Read,
+        payload_size: u64,
+    ) -> Result<Self, ErrorSerialization> {
+        let mut stream = stream.take(payload_size);
+        let mut header =
+            BlockHeader::deserialize_without_transaction_count(&mut stream, CompactSize::new(0))?;
+        let nonce = u64::le_deserialize(&mut stream)?;
+
+        let short_ids_count = CompactSize::le_deserialize(&mut stream)?.value;
+        let mut short_ids: Vec<ShortTransactionId> = Vec::new();
+        for _ in 0..short_ids_count {
+            let mut bytes =
+                Vec::<u8>::deserialize_fix_size(&mut stream, SHORT_TRANSACTION_ID_SIZE)?;
+            bytes.resize(8, 0);
+
+            let mut bytes: &[u8] = &bytes;
+            short_ids.push(u64::le_deserialize(&mut bytes)?);
+        }
+
+        let prefilled_count = CompactSize::le_deserialize(&mut stream)?.value;
+        let mut prefilled_transactions: Vec<PrefilledTransaction> = Vec::new();
+        let mut next_index: u64 = 0;
+        for _ in 0..prefilled_count {
+            let difference = CompactSize::le_deserialize(&mut stream)?.value;
+            let index = match next_index.checked_add(difference) {
+                Some(index) if index < short_ids_count.saturating_add(prefilled_count) => index,
+                _ => {
+                    return Err(ErrorSerialization::ErrorInDeserialization(
+                        "Index of a prefilled transaction out of the block".to_string(),
+                    ))
+                }
+            };
+
+            let remaining = stream.limit();
+            prefilled_transactions.push(PrefilledTransaction {
+                index,
+                transaction: Transaction::io_deserialize_limited(&mut stream, remaining)?,
+            });
+            next_index = index + 1;
+        }
+
+        header.transaction_count =
+            CompactSize::new(short_ids_count.saturating_add(prefilled_count));
+
+        Ok(CompactBlockMessage {
+            header,
+            nonce,
+            short_ids,
+            prefilled_transactions,
+        })
+    }
+}
+
+impl SerializableInternalOrder for CompactBlockMessage {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        self.header.serialize_without_transaction_count(stream)?;
+        self.nonce.le_serialize(stream)?;
+
+        CompactSize::new(self.short_ids.len() as u64).le_serialize(stream)?;
+        for short_id in self.short_ids.iter() {
+            short_id.to_le_bytes()[..SHORT_TRANSACTION_ID_SIZE].be_serialize(stream)?;
+        }
+
+        CompactSize::new(self.prefilled_transactions.len() as u64).le_serialize(stream)?;
+        let mut next_index: u64 = 0;
+        for prefilled in self.prefilled_transactions.iter() {
+            let difference = match prefilled.index.checked_sub(next_index) {
+                Some(difference) => difference,
+                None => {
+                    return Err(ErrorSerialization::ErrorInSerialization(
+                        "The prefilled transactions are not in order".to_string(),
+                    ))
+                }
+            };
+            CompactSize::new(difference).le_serialize(stream)?;
+            prefilled.transaction.io_serialize(stream)?;
+            next_index = prefilled.index + 1;
+        }
+
+        Ok(())
+    }
+}
+
+impl DeserializableInternalOrder for CompactBlockMessage {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        Self::io_deserialize_limited(stream, MAX_PAYLOAD_SIZE as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::block_structure::{
+        block_version::BlockVersion, compact256::Compact256, outpoint::Outpoint, script::ScriptBuf,
+        transaction_input::TransactionInput, transaction_output::TransactionOutput,
+    };
+
+    fn create_transaction(time: u32) -> Transaction {
+        Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(
+                Outpoint::new([1; 32], 23),
+                vec![1, 2, 3],
+                24,
+            )],
+            tx_out: vec![TransactionOutput {
+                value: 10,
+                pk_script: ScriptBuf::from(vec![4, 5, 6]),
+            }],
+            lock_time: time,
+        }
+    }
+
+    fn create_block(transactions: Vec<Transaction>) -> Block {
+        Block {
+            header: BlockHeader::new(
+                BlockVersion::version(1),
+                [0; 32],
+                [0; 32],
+                0,
+                Compact256::from(u32::MAX),
+                0,
+                CompactSize::new(transactions.len() as u64),
+            ),
+            transactions,
+        }
+    }
+
+    #[test]
+    fn test01_correct_compact_block_creation() {
+        let transactions: Vec<Transaction> = (0..3).map(create_transaction).collect();
+        let block = create_block(transactions.clone());
+
+        let compact_block = CompactBlockMessage::new(&block, 5).unwrap();
+        let keys = compact_block.get_short_id_keys().unwrap();
+
+        assert_eq!(compact_block.transaction_count(), 3);
+        assert_eq!(
+            compact_block.prefilled_transactions,
+            vec![PrefilledTransaction {
+                index: 0,
+                transaction: transactions[0].clone(),
+            }]
+        );
+        assert_eq!(
+            compact_block.short_ids,
+            vec![
+                calculate_short_id(keys, &transactions[1].get_tx_id().unwrap()),
+                calculate_short_id(keys, &transactions[2].get_tx_id().unwrap()),
+            ]
+        );
+        assert!(compact_block
+            .short_ids
+            .iter()
+            .all(|short_id| *short_id <= SHORT_TRANSACTION_ID_MASK));
+    }
+
+    #[test]
+    fn test02_correct_compact_block_serialization() {
+        let block = create_block((0..3).map(create_transaction).collect());
+        let compact_block = CompactBlockMessage::new(&block, 5).unwrap();
+
+        let mut stream: Vec<u8> = Vec::new();
+        compact_block.io_serialize(&mut stream).unwrap();
+
+        assert_eq!(
+            stream.len(),
+            80 + 8 + 1 + 2 * SHORT_TRANSACTION_ID_SIZE + 1 + 1 + {
+                let mut transaction: Vec<u8> = Vec::new();
+                block.transactions[0]
+                    .io_serialize(&mut transaction)
+                    .unwrap();
+                transaction.len()
+            }
+        );
+
+        let deserialized = CompactBlockMessage::io_deserialize(&mut stream.as_slice()).unwrap();
+        assert_eq!(deserialized, compact_block);
+    }
+
+    #[test]
+    fn test03_prefilled_transaction_out_of_the_block_is_rejected() {
+        let block = create_block(vec![create_transaction(0)]);
+        let mut compact_block = CompactBlockMessage::new(&block, 5).unwrap();
+        compact_block.prefilled_transactions[0].index = 1;
+
+        let mut stream: Vec<u8> = Vec::new();
+        compact_block.io_serialize(&mut stream).unwrap();
+
+        assert!(CompactBlockMessage::io_deserialize(&mut stream.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,74 @@
+use super::{command_name::CommandName, compact_size::CompactSize, message::Message};
+
+use crate::block_structure::hash::HashType;
+
+use crate::serialization::{
+    deserializable_big_endian::DeserializableBigEndian,
+    deserializable_internal_order::DeserializableInternalOrder,
+    deserializable_little_endian::DeserializableLittleEndian,
+    error_serialization::ErrorSerialization, serializable_big_endian::SerializableBigEndian,
+    serializable_internal_order::SerializableInternalOrder,
+    serializable_little_endian::SerializableLittleEndian,
+};
+
+use std::io::{Read, Write};
+
+pub const MAX_FILTER_HASHES: u64 = 2_000;
+
+/// It's the compact filter headers message, with the hashes of the filters until the block with the
+/// stop hash and the header of the filter before them
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompactFilterHeadersMessage {
+    pub filter_type: u8,
+    pub stop_hash: HashType,
+    pub previous_filter_header: HashType,
+    pub filter_hashes: Vec<HashType>,
+}
+
+impl Message for CompactFilterHeadersMessage {
+    fn get_command_name() -> CommandName {
+        CommandName::CompactFilterHeaders
+    }
+}
+
+impl SerializableInternalOrder for CompactFilterHeadersMessage {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        self.filter_type.le_serialize(stream)?;
+        self.stop_hash.be_serialize(stream)?;
+        self.previous_filter_header.be_serialize(stream)?;
+
+        CompactSize::new(self.filter_hashes.len() as u64).le_serialize(stream)?;
+        for filter_hash in self.filter_hashes.iter() {
+            filter_hash.be_serialize(stream)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl DeserializableInternalOrder for CompactFilterHeadersMessage {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        let filter_type = u8::le_deserialize(stream)?;
+        let stop_hash = HashType::be_deserialize(stream)?;
+        let previous_filter_header = HashType::be_deserialize(stream)?;
+
+        let hash_count = CompactSize::le_deserialize(stream)?.value;
+        if hash_count > MAX_FILTER_HASHES {
+            return Err(ErrorSerialization::ErrorInDeserialization(format!(
+                "More filter hashes than the maximum, we get: {hash_count}"
+            )));
+        }
+
+        let mut filter_hashes: Vec<HashType> = Vec::new();
+        for _ in 0..hash_count {
+            filter_hashes.push(HashType::be_deserialize(stream)?);
+        }
+
+        Ok(CompactFilterHeadersMessage {
+            filter_type,
+            stop_hash,
+            previous_filter_header,
+            filter_hashes,
+        })
+    }
+}
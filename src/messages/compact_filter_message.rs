@@ -0,0 +1,62 @@
+use super::{command_name::CommandName, compact_size::CompactSize, message::Message};
+
+use crate::block_structure::hash::HashType;
+
+use crate::serialization::{
+    deserializable_big_endian::DeserializableBigEndian,
+    deserializable_fix_size::DeserializableFixSize,
+    deserializable_internal_order::DeserializableInternalOrder,
+    deserializable_little_endian::DeserializableLittleEndian,
+    error_serialization::ErrorSerialization, serializable_big_endian::SerializableBigEndian,
+    serializable_internal_order::SerializableInternalOrder,
+    serializable_little_endian::SerializableLittleEndian,
+};
+
+use std::io::{Read, Write};
+
+pub const MAX_COMPACT_FILTER_SIZE: usize = 4_000_000;
+
+/// It's the compact filter message, with the filter of a block
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompactFilterMessage {
+    pub filter_type: u8,
+    pub block_hash: HashType,
+    pub filter: Vec<u8>,
+}
+
+impl Message for CompactFilterMessage {
+    fn get_command_name() -> CommandName {
+        CommandName::CompactFilter
+    }
+}
+
+impl SerializableInternalOrder for CompactFilterMessage {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        self.filter_type.le_serialize(stream)?;
+        self.block_hash.be_serialize(stream)?;
+        CompactSize::new(self.filter.len() as u64).le_serialize(stream)?;
+        self.filter.be_serialize(stream)?;
+
+        Ok(())
+    }
+}
+
+impl DeserializableInternalOrder for CompactFilterMessage {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        let filter_type = u8::le_deserialize(stream)?;
+        let block_hash = HashType::be_deserialize(stream)?;
+
+        let size = CompactSize::le_deserialize(stream)?.value as usize;
+        if size > MAX_COMPACT_FILTER_SIZE {
+            return Err(ErrorSerialization::ErrorInDeserialization(format!(
+                "Compact filter bigger than the maximum, we get: {size}"
+            )));
+        }
+
+        Ok(CompactFilterMessage {
+            filter_type,
+            block_hash,
+            filter: Vec::<u8>::deserialize_fix_size(stream, size)?,
+        })
+    }
+}
@@ -0,0 +1,50 @@
+use super::{command_name::CommandName, compact_size::CompactSize, message::Message};
+
+use crate::serialization::{
+    deserializable_fix_size::DeserializableFixSize,
+    deserializable_internal_order::DeserializableInternalOrder,
+    deserializable_little_endian::DeserializableLittleEndian,
+    error_serialization::ErrorSerialization, serializable_big_endian::SerializableBigEndian,
+    serializable_internal_order::SerializableInternalOrder,
+    serializable_little_endian::SerializableLittleEndian,
+};
+
+use std::io::{Read, Write};
+
+pub const MAX_FILTER_ADD_SIZE: usize = 520;
+
+/// It's the filter add message, with an element to add to the bloom filter already loaded
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterAddMessage {
+    pub data: Vec<u8>,
+}
+
+impl Message for FilterAddMessage {
+    fn get_command_name() -> CommandName {
+        CommandName::FilterAdd
+    }
+}
+
+impl SerializableInternalOrder for FilterAddMessage {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        CompactSize::new(self.data.len() as u64).le_serialize(stream)?;
+        self.data.be_serialize(stream)?;
+
+        Ok(())
+    }
+}
+
+impl DeserializableInternalOrder for FilterAddMessage {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        let size = CompactSize::le_deserialize(stream)?.value as usize;
+        if size > MAX_FILTER_ADD_SIZE {
+            return Err(ErrorSerialization::ErrorInDeserialization(format!(
+                "Element of the filter add bigger than the maximum, we get: {size}"
+            )));
+        }
+
+        Ok(FilterAddMessage {
+            data: Vec::<u8>::deserialize_fix_size(stream, size)?,
+        })
+    }
+}
@@ -0,0 +1,37 @@
+use super::{command_name::CommandName, message::Message};
+
+use crate::block_structure::bloom_filter::BloomFilter;
+
+use crate::serialization::{
+    deserializable_internal_order::DeserializableInternalOrder,
+    error_serialization::ErrorSerialization,
+    serializable_internal_order::SerializableInternalOrder,
+};
+
+use std::io::{Read, Write};
+
+/// It's the filter load message, with the bloom filter the peer has to use to relay transactions and blocks
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterLoadMessage {
+    pub bloom_filter: BloomFilter,
+}
+
+impl Message for FilterLoadMessage {
+    fn get_command_name() -> CommandName {
+        CommandName::FilterLoad
+    }
+}
+
+impl SerializableInternalOrder for FilterLoadMessage {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        self.bloom_filter.io_serialize(stream)
+    }
+}
+
+impl DeserializableInternalOrder for FilterLoadMessage {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        Ok(FilterLoadMessage {
+            bloom_filter: BloomFilter::io_deserialize(stream)?,
+        })
+    }
+}
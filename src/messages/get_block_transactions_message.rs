@@ -0,0 +1,112 @@
+use super::{command_name::CommandName, compact_size::CompactSize, message::Message};
+
+use crate::block_structure::hash::HashType;
+
+use crate::serialization::{
+    deserializable_internal_order::DeserializableInternalOrder,
+    deserializable_little_endian::DeserializableLittleEndian,
+    error_serialization::ErrorSerialization,
+    serializable_internal_order::SerializableInternalOrder,
+    serializable_little_endian::SerializableLittleEndian,
+};
+
+use std::io::{Read, Write};
+
+/// It's the getblocktxn message, requesting the transactions of a compact block that could not be rebuilt.
+/// The indexes are the positions of the transactions in the block, in increasing order
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetBlockTransactionsMessage {
+    pub block_hash: HashType,
+    pub indexes: Vec<u64>,
+}
+
+impl Message for GetBlockTransactionsMessage {
+    fn get_command_name() -> CommandName {
+        CommandName::GetBlockTransactions
+    }
+}
+
+impl SerializableInternalOrder for GetBlockTransactionsMessage {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        self.block_hash.le_serialize(stream)?;
+
+        CompactSize::new(self.indexes.len() as u64).le_serialize(stream)?;
+        let mut next_index: u64 = 0;
+        for index in self.indexes.iter() {
+            let difference = match index.checked_sub(next_index) {
+                Some(difference) => difference,
+                None => {
+                    return Err(ErrorSerialization::ErrorInSerialization(
+                        "The indexes of the transactions are not in order".to_string(),
+                    ))
+                }
+            };
+            CompactSize::new(difference).le_serialize(stream)?;
+            next_index = index + 1;
+        }
+
+        Ok(())
+    }
+}
+
+impl DeserializableInternalOrder for GetBlockTransactionsMessage {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        let block_hash = HashType::le_deserialize(stream)?;
+
+        let count = CompactSize::le_deserialize(stream)?.value;
+        let mut indexes: Vec<u64> = Vec::new();
+        let mut next_index: u64 = 0;
+        for _ in 0..count {
+            let difference = CompactSize::le_deserialize(stream)?.value;
+            let index = match next_index.checked_add(difference) {
+                Some(index) => index,
+                None => {
+                    return Err(ErrorSerialization::ErrorInDeserialization(
+                        "Index of a transaction out of range".to_string(),
+                    ))
+                }
+            };
+
+            indexes.push(index);
+            next_index = index + 1;
+        }
+
+        Ok(GetBlockTransactionsMessage {
+            block_hash,
+            indexes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_indexes_are_serialized_as_differences() {
+        let message = GetBlockTransactionsMessage {
+            block_hash: [1; 32],
+            indexes: vec![1, 2, 5],
+        };
+
+        let mut stream: Vec<u8> = Vec::new();
+        message.io_serialize(&mut stream).unwrap();
+
+        assert_eq!(stream[32..], [3, 1, 0, 2]);
+
+        let deserialized =
+            GetBlockTransactionsMessage::io_deserialize(&mut stream.as_slice()).unwrap();
+        assert_eq!(deserialized, message);
+    }
+
+    #[test]
+    fn test02_indexes_out_of_order_cannot_be_serialized() {
+        let message = GetBlockTransactionsMessage {
+            block_hash: [1; 32],
+            indexes: vec![3, 3],
+        };
+
+        let mut stream: Vec<u8> = Vec::new();
+        assert!(message.io_serialize(&mut stream).is_err());
+    }
+}
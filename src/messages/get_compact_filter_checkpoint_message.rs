@@ -0,0 +1,46 @@
+use super::{command_name::CommandName, message::Message};
+
+use crate::block_structure::hash::HashType;
+
+use crate::serialization::{
+    deserializable_big_endian::DeserializableBigEndian,
+    deserializable_internal_order::DeserializableInternalOrder,
+    deserializable_little_endian::DeserializableLittleEndian,
+    error_serialization::ErrorSerialization, serializable_big_endian::SerializableBigEndian,
+    serializable_internal_order::SerializableInternalOrder,
+    serializable_little_endian::SerializableLittleEndian,
+};
+
+use std::io::{Read, Write};
+
+/// It's the get compact filter checkpoint message, to ask for the filter headers of every
+/// thousand blocks until the block with the stop hash
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetCompactFilterCheckpointMessage {
+    pub filter_type: u8,
+    pub stop_hash: HashType,
+}
+
+impl Message for GetCompactFilterCheckpointMessage {
+    fn get_command_name() -> CommandName {
+        CommandName::GetCompactFilterCheckpoint
+    }
+}
+
+impl SerializableInternalOrder for GetCompactFilterCheckpointMessage {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        self.filter_type.le_serialize(stream)?;
+        self.stop_hash.be_serialize(stream)?;
+
+        Ok(())
+    }
+}
+
+impl DeserializableInternalOrder for GetCompactFilterCheckpointMessage {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        Ok(GetCompactFilterCheckpointMessage {
+            filter_type: u8::le_deserialize(stream)?,
+            stop_hash: HashType::be_deserialize(stream)?,
+        })
+    }
+}
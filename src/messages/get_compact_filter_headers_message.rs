@@ -0,0 +1,49 @@
+use super::{command_name::CommandName, message::Message};
+
+use crate::block_structure::hash::HashType;
+
+use crate::serialization::{
+    deserializable_big_endian::DeserializableBigEndian,
+    deserializable_internal_order::DeserializableInternalOrder,
+    deserializable_little_endian::DeserializableLittleEndian,
+    error_serialization::ErrorSerialization, serializable_big_endian::SerializableBigEndian,
+    serializable_internal_order::SerializableInternalOrder,
+    serializable_little_endian::SerializableLittleEndian,
+};
+
+use std::io::{Read, Write};
+
+/// It's the get compact filter headers message, to ask for the hashes of the filters of the blocks
+/// from the start height until the block with the stop hash
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetCompactFilterHeadersMessage {
+    pub filter_type: u8,
+    pub start_height: u32,
+    pub stop_hash: HashType,
+}
+
+impl Message for GetCompactFilterHeadersMessage {
+    fn get_command_name() -> CommandName {
+        CommandName::GetCompactFilterHeaders
+    }
+}
+
+impl SerializableInternalOrder for GetCompactFilterHeadersMessage {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        self.filter_type.le_serialize(stream)?;
+        self.start_height.le_serialize(stream)?;
+        self.stop_hash.be_serialize(stream)?;
+
+        Ok(())
+    }
+}
+
+impl DeserializableInternalOrder for GetCompactFilterHeadersMessage {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        Ok(GetCompactFilterHeadersMessage {
+            filter_type: u8::le_deserialize(stream)?,
+            start_height: u32::le_deserialize(stream)?,
+            stop_hash: HashType::be_deserialize(stream)?,
+        })
+    }
+}
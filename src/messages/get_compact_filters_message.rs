@@ -0,0 +1,49 @@
+use super::{command_name::CommandName, message::Message};
+
+use crate::block_structure::hash::HashType;
+
+use crate::serialization::{
+    deserializable_big_endian::DeserializableBigEndian,
+    deserializable_internal_order::DeserializableInternalOrder,
+    deserializable_little_endian::DeserializableLittleEndian,
+    error_serialization::ErrorSerialization, serializable_big_endian::SerializableBigEndian,
+    serializable_internal_order::SerializableInternalOrder,
+    serializable_little_endian::SerializableLittleEndian,
+};
+
+use std::io::{Read, Write};
+
+/// It's the get compact filters message, to ask for the filters of the blocks from the start height
+/// until the block with the stop hash
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetCompactFiltersMessage {
+    pub filter_type: u8,
+    pub start_height: u32,
+    pub stop_hash: HashType,
+}
+
+impl Message for GetCompactFiltersMessage {
+    fn get_command_name() -> CommandName {
+        CommandName::GetCompactFilters
+    }
+}
+
+impl SerializableInternalOrder for GetCompactFiltersMessage {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        self.filter_type.le_serialize(stream)?;
+        self.start_height.le_serialize(stream)?;
+        self.stop_hash.be_serialize(stream)?;
+
+        Ok(())
+    }
+}
+
+impl DeserializableInternalOrder for GetCompactFiltersMessage {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        Ok(GetCompactFiltersMessage {
+            filter_type: u8::le_deserialize(stream)?,
+            start_height: u32::le_deserialize(stream)?,
+            stop_hash: HashType::be_deserialize(stream)?,
+        })
+    }
+}
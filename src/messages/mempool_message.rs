@@ -0,0 +1,38 @@
+use super::{
+    command_name::CommandName,
+    message::{Message, CHECKSUM_EMPTY_PAYLOAD},
+};
+
+use crate::serialization::{
+    deserializable_internal_order::DeserializableInternalOrder,
+    error_serialization::ErrorSerialization,
+    serializable_internal_order::SerializableInternalOrder,
+};
+
+use std::io::{Read, Write};
+
+/// It's the mempool message, requesting the peer to announce the transactions in its mempool
+#[derive(Debug, std::cmp::PartialEq)]
+pub struct MempoolMessage;
+
+impl Message for MempoolMessage {
+    fn calculate_checksum(_: &[u8]) -> Result<[u8; 4], ErrorSerialization> {
+        Ok(CHECKSUM_EMPTY_PAYLOAD)
+    }
+
+    fn get_command_name() -> CommandName {
+        CommandName::Mempool
+    }
+}
+
+impl SerializableInternalOrder for MempoolMessage {
+    fn io_serialize(&self, _: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        Ok(())
+    }
+}
+
+impl DeserializableInternalOrder for MempoolMessage {
+    fn io_deserialize(_: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        Ok(MempoolMessage)
+    }
+}
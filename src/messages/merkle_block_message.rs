@@ -0,0 +1,49 @@
+use super::{command_name::CommandName, compact_size::CompactSize, message::Message};
+
+use crate::block_structure::{block_header::BlockHeader, merkle_proof::MerkleProof};
+
+use crate::serialization::{
+    deserializable_internal_order::DeserializableInternalOrder,
+    error_serialization::ErrorSerialization,
+    serializable_internal_order::SerializableInternalOrder,
+};
+
+use std::io::{Read, Write};
+
+/// It's the merkle block message, with the header of a block and the proof of the transactions
+/// that matched the bloom filter
+#[derive(Debug, Clone, PartialEq)]
+pub struct MerkleBlockMessage {
+    pub header: BlockHeader,
+    pub merkle_proof: MerkleProof,
+}
+
+impl Message for MerkleBlockMessage {
+    fn get_command_name() -> CommandName {
+        CommandName::MerkleBlock
+    }
+}
+
+impl SerializableInternalOrder for MerkleBlockMessage {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        self.header.serialize_without_transaction_count(stream)?;
+        self.merkle_proof.io_serialize(stream)?;
+
+        Ok(())
+    }
+}
+
+impl DeserializableInternalOrder for MerkleBlockMessage {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        let mut header =
+            BlockHeader::deserialize_without_transaction_count(stream, CompactSize::new(0))?;
+        let merkle_proof = MerkleProof::io_deserialize(stream)?;
+
+        header.transaction_count = CompactSize::new(merkle_proof.total_transactions as u64);
+
+        Ok(MerkleBlockMessage {
+            header,
+            merkle_proof,
+        })
+    }
+}
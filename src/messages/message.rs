@@ -10,23 +10,40 @@ use super::{
     addr_message::AddrMessage,
     alert_message::AlertMessage,
     block_message::BlockMessage,
+    block_transactions_message::BlockTransactionsMessage,
     command_name::CommandName,
+    compact_block_message::CompactBlockMessage,
+    compact_filter_checkpoint_message::CompactFilterCheckpointMessage,
+    compact_filter_headers_message::CompactFilterHeadersMessage,
+    compact_filter_message::CompactFilterMessage,
     fee_filter_message::FeeFilterMessage,
+    filter_add_message::FilterAddMessage,
+    filter_clear_message::FilterClearMessage,
+    filter_load_message::FilterLoadMessage,
+    get_block_transactions_message::GetBlockTransactionsMessage,
+    get_compact_filter_checkpoint_message::GetCompactFilterCheckpointMessage,
+    get_compact_filter_headers_message::GetCompactFilterHeadersMessage,
+    get_compact_filters_message::GetCompactFiltersMessage,
     get_data_message::GetDataMessage,
     get_headers_message::GetHeadersMessage,
     headers_message::HeadersMessage,
     inventory_message::InventoryMessage,
+    mempool_message::MempoolMessage,
+    merkle_block_message::MerkleBlockMessage,
     message_header::{MagicType, MessageHeader},
+    not_found_message::NotFoundMessage,
     ping_message::PingMessage,
     pong_message::PongMessage,
+    reject_message::RejectMessage,
     send_cmpct_message::SendCmpctMessage,
     send_headers_message::SendHeadersMessage,
     tx_message::TxMessage,
     verack_message::VerackMessage,
     version_message::VersionMessage,
+    wtxid_relay_message::WtxidRelayMessage,
 };
 
-use std::io::{ErrorKind, Read, Write};
+use std::io::{self, ErrorKind, Read, Write};
 
 pub const CHECKSUM_EMPTY_PAYLOAD: MagicType = [0x5d, 0xf6, 0xe0, 0xe2];
 
@@ -56,9 +73,12 @@ pub trait Message: SerializableInternalOrder + DeserializableInternalOrder {
         Ok(())
     }
 
-    /// Deserialize a message given the header of it
+    /// Deserialize a message given the header of it. The payload is only read if its size is not
+    /// bigger than the maximum of the command, and it's read as it arrives instead of reserving the
+    /// size announced by the peer
     ///
     /// ### Error
+    ///  * `ErrorSerialization::PayloadTooLarge`: It will appear when the payload is bigger than the maximum of the command
     ///  * `ErrorSerialization::ErrorSerialization`: It will appear when there is an error in the serialization
     ///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when there is an error in the deserialization
     ///  * `ErrorSerialization::ErrorWhileReading`: It will appear when there is an error in the reading from a stream
@@ -66,31 +86,71 @@ pub trait Message: SerializableInternalOrder + DeserializableInternalOrder {
         stream: &mut R,
         message_header: MessageHeader,
     ) -> Result<Self, ErrorSerialization> {
-        let mut buffer: Vec<u8> = vec![0; message_header.payload_size as usize];
-        read_exact(stream, &mut buffer)?;
-        let mut buffer: &[u8] = &buffer[..];
-        let message = Self::io_deserialize(&mut buffer)?;
+        message_header.check_payload_size()?;
 
-        let mut serialized_message: Vec<u8> = Vec::new();
-        message.io_serialize(&mut serialized_message)?;
+        let mut buffer: Vec<u8> = Vec::new();
+        read_payload(stream, &message_header, &mut buffer)?;
+        Self::deserialize_payload(&buffer, &message_header)
+    }
 
-        let length = serialized_message.len();
-        if length != message_header.payload_size as usize {
+    /// Deserialize the payload of a message already received. The checksum is calculated over the
+    /// bytes received before deserializing them, so a corrupted payload is never deserialized
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::ErrorSerialization`: It will appear when there is an error in the serialization
+    ///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when the checksum does not match, or the payload has more bytes than the message
+    fn deserialize_payload(
+        payload: &[u8],
+        message_header: &MessageHeader,
+    ) -> Result<Self, ErrorSerialization> {
+        let checksum = Self::calculate_checksum(payload)?;
+        if !checksum.eq(&message_header.checksum) {
             return Err(ErrorSerialization::ErrorInDeserialization(format!(
-                "Payload size {:?} in {:?} isn't the same as receive: {:?}",
-                length,
+                "Checksum {:?} in {:?}  isn't the same as receive: {:?}",
+                checksum,
                 Self::get_command_name(),
-                message_header.payload_size
+                message_header.checksum
             )));
         }
 
-        let checksum = Self::calculate_checksum(&serialized_message)?;
-        if !checksum.eq(&message_header.checksum) {
+        let mut remaining: &[u8] = payload;
+        let message = Self::io_deserialize_limited(&mut remaining, payload.len() as u64)?;
+        if !remaining.is_empty() {
             return Err(ErrorSerialization::ErrorInDeserialization(format!(
-                "Checksum {:?} in {:?}  isn't the same as receive: {:?}",
-                checksum,
+                "Payload size {:?} in {:?} has {:?} bytes after the message",
+                payload.len(),
+                Self::get_command_name(),
+                remaining.len()
+            )));
+        }
+
+        Ok(message)
+    }
+
+    /// Deserialize a message like `deserialize_message`, but also checks that the message is
+    /// serialized back to the same bytes, so any non-canonical encoding is rejected
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::PayloadTooLarge`: It will appear when the payload is bigger than the maximum of the command
+    ///  * `ErrorSerialization::ErrorSerialization`: It will appear when there is an error in the serialization
+    ///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when there is an error in the deserialization or the encoding is not canonical
+    ///  * `ErrorSerialization::ErrorWhileReading`: It will appear when there is an error in the reading from a stream
+    fn deserialize_message_strict<R: Read>(
+        stream: &mut R,
+        message_header: MessageHeader,
+    ) -> Result<Self, ErrorSerialization> {
+        message_header.check_payload_size()?;
+
+        let mut buffer: Vec<u8> = Vec::new();
+        read_payload(stream, &message_header, &mut buffer)?;
+        let message = Self::deserialize_payload(&buffer, &message_header)?;
+
+        let mut serialized_message: Vec<u8> = Vec::new();
+        message.io_serialize(&mut serialized_message)?;
+        if serialized_message != buffer {
+            return Err(ErrorSerialization::ErrorInDeserialization(format!(
+                "Payload of {:?} isn't serialized back to the bytes received",
                 Self::get_command_name(),
-                message_header.checksum
             )));
         }
 
@@ -102,25 +162,63 @@ pub trait Message: SerializableInternalOrder + DeserializableInternalOrder {
         hash256d_reduce(serialized_message)
     }
 
+    /// Deserialize the message from a payload of the given size. The messages with transactions
+    /// override it to limit the inputs and outputs of each one by the bytes left in the payload
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when there is an error in the deserialization
+    ///  * `ErrorSerialization::ErrorWhileReading`: It will appear when there is an error in the reading from a stream
+    fn io_deserialize_limited(
+        stream: &mut dyn Read,
+        _payload_size: u64,
+    ) -> Result<Self, ErrorSerialization> {
+        Self::io_deserialize(stream)
+    }
+
     /// Get the command name of the message to know the type of message
     fn get_command_name() -> CommandName;
 }
 
 pub fn read_exact<R: Read>(stream: &mut R, buffer: &mut [u8]) -> Result<(), ErrorSerialization> {
-    if let Err(error) = stream.read_exact(buffer) {
-        let error = match error.kind() {
-            ErrorKind::ConnectionAborted => ErrorSerialization::ConnectionAborted,
-            ErrorKind::WouldBlock => ErrorSerialization::InformationNotReady,
-            _ => ErrorSerialization::ErrorWhileReading,
-        };
-        return Err(error);
+    match stream.read_exact(buffer) {
+        Ok(_) => Ok(()),
+        Err(error) => Err(from_io_error(error)),
     }
-    Ok(())
 }
 
-/// Ignores any message that is not the one that is being searched for
+/// Reads the payload of the message into the buffer, which grows as the bytes arrive instead of
+/// reserving the size announced by the peer
 ///
 /// ### Error
+///  * `ErrorSerialization::ErrorWhileReading`: It will appear when the stream ends before the whole payload
+pub fn read_payload<R: Read>(
+    stream: &mut R,
+    header: &MessageHeader,
+    buffer: &mut Vec<u8>,
+) -> Result<(), ErrorSerialization> {
+    buffer.clear();
+    let payload_size = header.payload_size as u64;
+    match stream.take(payload_size).read_to_end(buffer) {
+        Ok(read) if read as u64 == payload_size => Ok(()),
+        Ok(_) => Err(ErrorSerialization::ErrorWhileReading),
+        Err(error) => Err(from_io_error(error)),
+    }
+}
+
+fn from_io_error(error: io::Error) -> ErrorSerialization {
+    match error.kind() {
+        ErrorKind::ConnectionAborted => ErrorSerialization::ConnectionAborted,
+        ErrorKind::WouldBlock | ErrorKind::TimedOut => ErrorSerialization::InformationNotReady,
+        _ => ErrorSerialization::ErrorWhileReading,
+    }
+}
+
+/// Ignores any message that is not the one that is being searched for. If the peer answers that it
+/// cannot serve the data or rejects our message, the search is stopped
+///
+/// ### Error
+///  * `ErrorSerialization::DataNotFound`: It will appear when a notfound message is received while searching for other message
+///  * `ErrorSerialization::MessageRejected`: It will appear when a reject message is received while searching for other message
 ///  * `ErrorSerialization::ErrorSerialization`: It will appear when there is an error in the serialization
 ///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when there is an error in the deserialization
 ///  * `ErrorSerialization::ErrorWhileReading`: It will appear when there is an error in the reading from a stream
@@ -162,6 +260,46 @@ pub fn deserialize_until_found<RW: Read + Write>(
             CommandName::GetData => ignore_message::<RW, GetDataMessage>(stream, header)?,
             CommandName::Alert => ignore_message::<RW, AlertMessage>(stream, header)?,
             CommandName::Tx => ignore_message::<RW, TxMessage>(stream, header)?,
+            CommandName::CompactBlock => ignore_message::<RW, CompactBlockMessage>(stream, header)?,
+            CommandName::GetBlockTransactions => {
+                ignore_message::<RW, GetBlockTransactionsMessage>(stream, header)?
+            }
+            CommandName::BlockTransactions => {
+                ignore_message::<RW, BlockTransactionsMessage>(stream, header)?
+            }
+            CommandName::FilterLoad => ignore_message::<RW, FilterLoadMessage>(stream, header)?,
+            CommandName::FilterAdd => ignore_message::<RW, FilterAddMessage>(stream, header)?,
+            CommandName::FilterClear => ignore_message::<RW, FilterClearMessage>(stream, header)?,
+            CommandName::MerkleBlock => ignore_message::<RW, MerkleBlockMessage>(stream, header)?,
+            CommandName::GetCompactFilters => {
+                ignore_message::<RW, GetCompactFiltersMessage>(stream, header)?
+            }
+            CommandName::CompactFilter => {
+                ignore_message::<RW, CompactFilterMessage>(stream, header)?
+            }
+            CommandName::GetCompactFilterHeaders => {
+                ignore_message::<RW, GetCompactFilterHeadersMessage>(stream, header)?
+            }
+            CommandName::CompactFilterHeaders => {
+                ignore_message::<RW, CompactFilterHeadersMessage>(stream, header)?
+            }
+            CommandName::GetCompactFilterCheckpoint => {
+                ignore_message::<RW, GetCompactFilterCheckpointMessage>(stream, header)?
+            }
+            CommandName::CompactFilterCheckpoint => {
+                ignore_message::<RW, CompactFilterCheckpointMessage>(stream, header)?
+            }
+            CommandName::Mempool => ignore_message::<RW, MempoolMessage>(stream, header)?,
+            CommandName::NotFound => {
+                let not_found = NotFoundMessage::deserialize_message(stream, header)?;
+                return Err(ErrorSerialization::DataNotFound(not_found.get_hashes()));
+            }
+            CommandName::Reject => {
+                let reject = RejectMessage::deserialize_message(stream, header)?;
+                return Err(ErrorSerialization::MessageRejected(reject));
+            }
+            CommandName::WtxidRelay => ignore_message::<RW, WtxidRelayMessage>(stream, header)?,
+            CommandName::Unknown(_) => ignore_unknown_message(stream, header)?,
         }
     }
 }
@@ -174,3 +312,85 @@ pub fn ignore_message<R: Read, M: Message>(
     let _ = M::deserialize_message(stream, header)?;
     Ok(())
 }
+
+/// Ignores a message with a command name that is not known, by skipping the bytes of its payload
+///
+/// ### Error
+///  * `ErrorSerialization::PayloadTooLarge`: It will appear when the payload is bigger than the maximum of the command
+///  * `ErrorSerialization::ErrorWhileReading`: It will appear when there is an error in the reading from a stream
+pub fn ignore_unknown_message<R: Read>(
+    stream: &mut R,
+    header: MessageHeader,
+) -> Result<(), ErrorSerialization> {
+    header.check_payload_size()?;
+
+    let payload_size = header.payload_size as u64;
+    match io::copy(&mut stream.take(payload_size), &mut io::sink()) {
+        Ok(read) if read == payload_size => Ok(()),
+        Ok(_) => Err(ErrorSerialization::ErrorWhileReading),
+        Err(error) => Err(from_io_error(error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAGIC_NUMBERS: MagicType = [0x0b, 0x11, 0x09, 0x07];
+
+    fn header_for(payload: &[u8], command_name: CommandName) -> MessageHeader {
+        MessageHeader {
+            magic_numbers: MAGIC_NUMBERS,
+            command_name,
+            payload_size: payload.len() as u32,
+            checksum: hash256d_reduce(payload).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test01_checksum_is_verified_before_deserializing() {
+        let payload: Vec<u8> = vec![0x01, 0x02];
+        let mut header = header_for(&payload, CommandName::Inventory);
+        header.checksum = [0; 4];
+
+        let error = InventoryMessage::deserialize_message(&mut payload.as_slice(), header);
+        assert!(matches!(
+            error,
+            Err(ErrorSerialization::ErrorInDeserialization(message)) if message.starts_with("Checksum")
+        ));
+    }
+
+    #[test]
+    fn test02_non_canonical_encoding_is_only_rejected_in_strict_mode() {
+        let payload: Vec<u8> = vec![0xFD, 0x00, 0x00];
+
+        let message = InventoryMessage::deserialize_message(
+            &mut payload.as_slice(),
+            header_for(&payload, CommandName::Inventory),
+        )
+        .unwrap();
+        assert!(message.inventory_vectors.is_empty());
+
+        assert!(InventoryMessage::deserialize_message_strict(
+            &mut payload.as_slice(),
+            header_for(&payload, CommandName::Inventory),
+        )
+        .is_err());
+        assert!(InventoryMessage::deserialize_message_strict(
+            &mut [0x00].as_slice(),
+            header_for(&[0x00], CommandName::Inventory),
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test03_bytes_after_the_message_are_an_error() {
+        let payload: Vec<u8> = vec![0x00, 0x00];
+
+        assert!(InventoryMessage::deserialize_message(
+            &mut payload.as_slice(),
+            header_for(&payload, CommandName::Inventory),
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,252 @@
+use super::{
+    message::Message,
+    message_header::{MagicType, MessageHeader, HEADER_SIZE},
+};
+
+use crate::serialization::{
+    deserializable_internal_order::DeserializableInternalOrder,
+    error_serialization::ErrorSerialization,
+};
+
+use std::io::{ErrorKind, Read};
+
+const READ_CHUNK_SIZE: usize = 4096;
+
+/// It's a complete message received from a stream, with its payload still serialized
+#[derive(Debug, PartialEq)]
+pub struct Frame {
+    pub header: MessageHeader,
+    pub payload: Vec<u8>,
+}
+
+impl Frame {
+    /// Deserialize the payload as the message of the frame, checking its checksum first
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when there is an error in the deserialization
+    pub fn message<M: Message>(&self) -> Result<M, ErrorSerialization> {
+        M::deserialize_payload(&self.payload, &self.header)
+    }
+}
+
+/// It separates the bytes received from a stream into frames, one for each message, without
+/// blocking while waiting for the rest of a message. The bytes can be given by any source, so it can
+/// be used with non-blocking sockets or with the buffers of an async runtime
+#[derive(Debug, Default)]
+pub struct MessageCodec {
+    buffer: Vec<u8>,
+}
+
+impl MessageCodec {
+    pub fn new() -> Self {
+        MessageCodec::default()
+    }
+
+    /// Adds the bytes received to the ones waiting to complete a frame
+    pub fn extend(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Returns the amount of bytes waiting to complete a frame
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns the next frame if all of its bytes were received, removing them from the buffer. The
+    /// payload announced is checked before waiting for it, so a peer can't make the buffer grow
+    /// past the maximum of the command
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when the header can't be deserialized
+    ///  * `ErrorSerialization::PayloadTooLarge`: It will appear when the payload is bigger than the maximum of the command
+    pub fn decode(&mut self) -> Result<Option<Frame>, ErrorSerialization> {
+        if self.buffer.len() < HEADER_SIZE {
+            return Ok(None);
+        }
+
+        let mut header_bytes: &[u8] = &self.buffer[..HEADER_SIZE];
+        let header = MessageHeader::io_deserialize(&mut header_bytes)?;
+        header.check_payload_size()?;
+
+        let frame_size = HEADER_SIZE + header.payload_size as usize;
+        if self.buffer.len() < frame_size {
+            return Ok(None);
+        }
+
+        let payload = self.buffer[HEADER_SIZE..frame_size].to_vec();
+        self.buffer.drain(..frame_size);
+
+        Ok(Some(Frame { header, payload }))
+    }
+
+    /// Reads the bytes available in the stream and returns the next frame if it's complete. If the
+    /// stream is non-blocking and has nothing to read, it returns `None` instead of waiting
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::ConnectionAborted`: It will appear when the stream is closed
+    ///  * `ErrorSerialization::ErrorWhileReading`: It will appear when there is an error in the reading from a stream
+    ///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when the header can't be deserialized
+    pub fn poll_frame<R: Read>(
+        &mut self,
+        stream: &mut R,
+    ) -> Result<Option<Frame>, ErrorSerialization> {
+        if let Some(frame) = self.decode()? {
+            return Ok(Some(frame));
+        }
+
+        let mut chunk = [0; READ_CHUNK_SIZE];
+        match stream.read(&mut chunk) {
+            Ok(0) => return Err(ErrorSerialization::ConnectionAborted),
+            Ok(read) => self.extend(&chunk[..read]),
+            Err(error) => match error.kind() {
+                ErrorKind::WouldBlock | ErrorKind::TimedOut | ErrorKind::Interrupted => {
+                    return Ok(None)
+                }
+                ErrorKind::ConnectionAborted | ErrorKind::ConnectionReset => {
+                    return Err(ErrorSerialization::ConnectionAborted)
+                }
+                _ => return Err(ErrorSerialization::ErrorWhileReading),
+            },
+        }
+
+        self.decode()
+    }
+
+    /// Reads from the stream until a frame is complete, as the blocking deserialization does
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::ConnectionAborted`: It will appear when the stream is closed
+    ///  * `ErrorSerialization::InformationNotReady`: It will appear when the stream has nothing to read
+    ///  * `ErrorSerialization::ErrorWhileReading`: It will appear when there is an error in the reading from a stream
+    ///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when the header can't be deserialized
+    pub fn read_frame<R: Read>(&mut self, stream: &mut R) -> Result<Frame, ErrorSerialization> {
+        loop {
+            let buffered = self.buffered();
+            match self.poll_frame(stream)? {
+                Some(frame) => return Ok(frame),
+                None if self.buffered() == buffered => {
+                    return Err(ErrorSerialization::InformationNotReady)
+                }
+                None => continue,
+            }
+        }
+    }
+}
+
+/// Serialize the message with its header, to be sent as a whole frame
+///
+/// ### Error
+///  * `ErrorSerialization::ErrorInSerialization`: It will appear when there is an error in the serialization
+pub fn encode<M: Message>(
+    magic_numbers: MagicType,
+    message: &M,
+) -> Result<Vec<u8>, ErrorSerialization> {
+    let mut bytes: Vec<u8> = Vec::new();
+    M::serialize_message(&mut bytes, magic_numbers, message)?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::messages::{
+        command_name::CommandName, ping_message::PingMessage, verack_message::VerackMessage,
+    };
+
+    use crate::serialization::serializable_internal_order::SerializableInternalOrder;
+
+    use std::io;
+
+    const MAGIC_NUMBERS: MagicType = [0x0b, 0x11, 0x09, 0x07];
+
+    struct NonBlockingStream {
+        chunks: Vec<Vec<u8>>,
+    }
+
+    impl Read for NonBlockingStream {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.chunks.is_empty() {
+                return Err(io::Error::from(ErrorKind::WouldBlock));
+            }
+            let chunk = self.chunks.remove(0);
+            buf[..chunk.len()].copy_from_slice(&chunk);
+            Ok(chunk.len())
+        }
+    }
+
+    #[test]
+    fn test01_frame_is_decoded_once_all_its_bytes_arrived() {
+        let bytes = encode(MAGIC_NUMBERS, &PingMessage { nonce: 42 }).unwrap();
+        let mut codec = MessageCodec::new();
+
+        codec.extend(&bytes[..10]);
+        assert_eq!(None, codec.decode().unwrap());
+        codec.extend(&bytes[10..bytes.len() - 1]);
+        assert_eq!(None, codec.decode().unwrap());
+        codec.extend(&bytes[bytes.len() - 1..]);
+
+        let frame = codec.decode().unwrap().unwrap();
+        assert_eq!(CommandName::Ping, frame.header.command_name);
+        assert_eq!(0, codec.buffered());
+        assert_eq!(42, frame.message::<PingMessage>().unwrap().nonce);
+    }
+
+    #[test]
+    fn test02_non_blocking_stream_does_not_wait_for_the_rest_of_the_frame() {
+        let ping = encode(MAGIC_NUMBERS, &PingMessage { nonce: 7 }).unwrap();
+        let verack = encode(MAGIC_NUMBERS, &VerackMessage).unwrap();
+
+        let mut both = ping[20..].to_vec();
+        both.extend_from_slice(&verack);
+        let mut stream = NonBlockingStream {
+            chunks: vec![ping[..20].to_vec(), both],
+        };
+        let mut codec = MessageCodec::new();
+
+        assert_eq!(None, codec.poll_frame(&mut stream).unwrap());
+        let ping = codec.poll_frame(&mut stream).unwrap().unwrap();
+        assert_eq!(7, ping.message::<PingMessage>().unwrap().nonce);
+
+        let verack = codec.poll_frame(&mut stream).unwrap().unwrap();
+        assert_eq!(CommandName::Verack, verack.header.command_name);
+        assert_eq!(None, codec.poll_frame(&mut stream).unwrap());
+    }
+
+    #[test]
+    fn test03_blocking_read_reads_until_the_frame_is_complete() {
+        let bytes = encode(MAGIC_NUMBERS, &PingMessage { nonce: 3 }).unwrap();
+        let mut stream: &[u8] = &bytes;
+        let mut codec = MessageCodec::new();
+
+        let frame = codec.read_frame(&mut stream).unwrap();
+        assert_eq!(3, frame.message::<PingMessage>().unwrap().nonce);
+        assert!(matches!(
+            codec.read_frame(&mut stream),
+            Err(ErrorSerialization::ConnectionAborted)
+        ));
+    }
+
+    #[test]
+    fn test04_frame_bigger_than_the_maximum_is_rejected_before_its_payload() {
+        let header = MessageHeader {
+            magic_numbers: MAGIC_NUMBERS,
+            command_name: CommandName::Verack,
+            payload_size: 1_000_000,
+            checksum: [0; 4],
+        };
+        let mut bytes: Vec<u8> = Vec::new();
+        header.io_serialize(&mut bytes).unwrap();
+
+        let mut codec = MessageCodec::new();
+        codec.extend(&bytes);
+
+        assert!(matches!(
+            codec.decode(),
+            Err(ErrorSerialization::PayloadTooLarge(
+                CommandName::Verack,
+                1_000_000
+            ))
+        ));
+    }
+}
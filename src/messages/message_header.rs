@@ -17,7 +17,7 @@ const MASSAGE_TYPE_SIZE: usize = 12;
 const PAYLOAD_SIZE: usize = 4;
 const CHECKSUM_SIZE: usize = 4;
 
-const HEADER_SIZE: usize = MAGIC_BYTES_SIZE + MASSAGE_TYPE_SIZE + PAYLOAD_SIZE + CHECKSUM_SIZE;
+pub const HEADER_SIZE: usize = MAGIC_BYTES_SIZE + MASSAGE_TYPE_SIZE + PAYLOAD_SIZE + CHECKSUM_SIZE;
 
 pub type MagicType = [u8; 4];
 
@@ -46,6 +46,20 @@ impl MessageHeader {
 
         MessageHeader::io_deserialize(&mut buffer)
     }
+
+    /// Checks that the payload announced is not bigger than the maximum of the command
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::PayloadTooLarge`: It will appear when the payload is bigger than the maximum of the command
+    pub fn check_payload_size(&self) -> Result<(), ErrorSerialization> {
+        if self.payload_size > self.command_name.max_payload_size() {
+            return Err(ErrorSerialization::PayloadTooLarge(
+                self.command_name.clone(),
+                self.payload_size,
+            ));
+        }
+        Ok(())
+    }
 }
 
 impl SerializableInternalOrder for MessageHeader {
@@ -126,4 +140,38 @@ mod tests {
 
         assert_eq!(header, expected_message_header);
     }
+
+    #[test]
+    fn test03_payload_bigger_than_the_maximum_of_the_command_is_not_read() {
+        use crate::messages::{
+            block_message::BlockMessage, message::Message, ping_message::PingMessage,
+        };
+
+        let header = MessageHeader {
+            magic_numbers: [0x55, 0x66, 0xee, 0xee],
+            command_name: CommandName::Ping,
+            payload_size: 9,
+            checksum: [0; 4],
+        };
+        let mut stream: &[u8] = &[0; 9];
+        assert!(matches!(
+            PingMessage::deserialize_message(&mut stream, header),
+            Err(ErrorSerialization::PayloadTooLarge(CommandName::Ping, 9))
+        ));
+        assert_eq!(9, stream.len());
+
+        let header = MessageHeader {
+            magic_numbers: [0x55, 0x66, 0xee, 0xee],
+            command_name: CommandName::Block,
+            payload_size: u32::MAX,
+            checksum: [0; 4],
+        };
+        assert!(matches!(
+            BlockMessage::deserialize_message(&mut stream, header),
+            Err(ErrorSerialization::PayloadTooLarge(
+                CommandName::Block,
+                u32::MAX
+            ))
+        ));
+    }
 }
@@ -1,9 +1,11 @@
 pub mod command_name;
 pub mod message;
+pub mod message_codec;
 pub mod message_header;
 
 pub mod verack_message;
 pub mod version_message;
+pub mod wtxid_relay_message;
 
 pub mod get_data_message;
 pub mod get_headers_message;
@@ -12,11 +14,30 @@ pub mod block_message;
 pub mod headers_message;
 pub mod tx_message;
 
+pub mod block_transactions_message;
+pub mod compact_block_message;
+pub mod get_block_transactions_message;
+
+pub mod filter_add_message;
+pub mod filter_clear_message;
+pub mod filter_load_message;
+pub mod merkle_block_message;
+
+pub mod compact_filter_checkpoint_message;
+pub mod compact_filter_headers_message;
+pub mod compact_filter_message;
+pub mod get_compact_filter_checkpoint_message;
+pub mod get_compact_filter_headers_message;
+pub mod get_compact_filters_message;
+
 pub mod addr_message;
 pub mod fee_filter_message;
 pub mod inventory_message;
+pub mod mempool_message;
+pub mod not_found_message;
 pub mod ping_message;
 pub mod pong_message;
+pub mod reject_message;
 pub mod send_cmpct_message;
 pub mod send_headers_message;
 
@@ -0,0 +1,88 @@
+use super::{
+    command_name::CommandName, compact_size::CompactSize, inventory_vector::InventoryVector,
+    message::Message,
+};
+
+use crate::serialization::{
+    deserializable_internal_order::DeserializableInternalOrder,
+    deserializable_little_endian::DeserializableLittleEndian,
+    error_serialization::ErrorSerialization,
+    serializable_internal_order::SerializableInternalOrder,
+    serializable_little_endian::SerializableLittleEndian,
+};
+
+use crate::block_structure::hash::HashType;
+
+use std::io::{Read, Write};
+
+/// It's the message a peer answers with when it cannot serve the data requested
+#[derive(Debug, Clone, std::cmp::PartialEq)]
+pub struct NotFoundMessage {
+    pub inventory_vectors: Vec<InventoryVector>,
+}
+
+impl NotFoundMessage {
+    pub fn new(inventory_vectors: Vec<InventoryVector>) -> NotFoundMessage {
+        NotFoundMessage { inventory_vectors }
+    }
+
+    /// Returns the hashes of the data that the peer could not serve
+    pub fn get_hashes(&self) -> Vec<HashType> {
+        self.inventory_vectors
+            .iter()
+            .map(|inventory_vector| inventory_vector.hash_value)
+            .collect()
+    }
+}
+
+impl Message for NotFoundMessage {
+    fn get_command_name() -> CommandName {
+        CommandName::NotFound
+    }
+}
+
+impl SerializableInternalOrder for NotFoundMessage {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        CompactSize::new(self.inventory_vectors.len() as u64).le_serialize(stream)?;
+        for inventory_vector in &self.inventory_vectors {
+            inventory_vector.io_serialize(stream)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl DeserializableInternalOrder for NotFoundMessage {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        let count = CompactSize::le_deserialize(stream)?.value;
+        let mut inventory_vectors = Vec::new();
+        for _ in 0..count {
+            inventory_vectors.push(InventoryVector::io_deserialize(stream)?);
+        }
+
+        Ok(NotFoundMessage { inventory_vectors })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::connections::type_identifier::TypeIdentifier;
+
+    #[test]
+    fn test01_correct_not_found_message_serialization() {
+        let message = NotFoundMessage::new(vec![
+            InventoryVector::new(TypeIdentifier::Block, [1; 32]),
+            InventoryVector::new(TypeIdentifier::TransactionId, [2; 32]),
+        ]);
+
+        let mut stream: Vec<u8> = Vec::new();
+        message.io_serialize(&mut stream).unwrap();
+
+        let deserialized = NotFoundMessage::io_deserialize(&mut stream.as_slice()).unwrap();
+
+        assert_eq!(deserialized, message);
+        assert_eq!(deserialized.get_hashes(), vec![[1; 32], [2; 32]]);
+    }
+}
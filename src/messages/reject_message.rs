@@ -0,0 +1,105 @@
+use super::{command_name::CommandName, compact_size::CompactSize, message::Message};
+
+use crate::serialization::{
+    deserializable_fix_size::DeserializableFixSize,
+    deserializable_internal_order::DeserializableInternalOrder,
+    deserializable_little_endian::DeserializableLittleEndian,
+    error_serialization::ErrorSerialization,
+    serializable_internal_order::SerializableInternalOrder,
+    serializable_little_endian::SerializableLittleEndian,
+};
+
+use crate::block_structure::hash::HashType;
+
+use std::io::{Read, Write};
+
+/// It's the message old peers send when they reject a message received from us. The data is the
+/// hash of the rejected transaction or block, if the rejected message was one of them
+#[derive(Debug, Clone, std::cmp::PartialEq)]
+pub struct RejectMessage {
+    pub message: String,
+    pub code: u8,
+    pub reason: String,
+    pub data: Option<HashType>,
+}
+
+impl Message for RejectMessage {
+    fn get_command_name() -> CommandName {
+        CommandName::Reject
+    }
+}
+
+impl SerializableInternalOrder for RejectMessage {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        CompactSize::new(self.message.len() as u64).le_serialize(stream)?;
+        self.message.le_serialize(stream)?;
+        self.code.le_serialize(stream)?;
+        CompactSize::new(self.reason.len() as u64).le_serialize(stream)?;
+        self.reason.le_serialize(stream)?;
+
+        if let Some(data) = &self.data {
+            data.io_serialize(stream)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl DeserializableInternalOrder for RejectMessage {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        let message_length = CompactSize::le_deserialize(stream)?.value;
+        let message = String::deserialize_fix_size(stream, message_length as usize)?;
+        let code = u8::le_deserialize(stream)?;
+        let reason_length = CompactSize::le_deserialize(stream)?.value;
+        let reason = String::deserialize_fix_size(stream, reason_length as usize)?;
+
+        Ok(RejectMessage {
+            message,
+            code,
+            reason,
+            data: HashType::io_deserialize(stream).ok(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_correct_reject_message_with_data_serialization() {
+        let message = RejectMessage {
+            message: "tx".to_string(),
+            code: 0x42,
+            reason: "insufficient fee".to_string(),
+            data: Some([7; 32]),
+        };
+
+        let mut stream: Vec<u8> = Vec::new();
+        message.io_serialize(&mut stream).unwrap();
+
+        assert_eq!(stream.len(), 1 + 2 + 1 + 1 + 16 + 32);
+        assert_eq!(
+            RejectMessage::io_deserialize(&mut stream.as_slice()).unwrap(),
+            message
+        );
+    }
+
+    #[test]
+    fn test02_correct_reject_message_without_data_serialization() {
+        let message = RejectMessage {
+            message: "version".to_string(),
+            code: 0x11,
+            reason: "obsolete".to_string(),
+            data: None,
+        };
+
+        let mut stream: Vec<u8> = Vec::new();
+        message.io_serialize(&mut stream).unwrap();
+
+        assert_eq!(
+            RejectMessage::io_deserialize(&mut stream.as_slice()).unwrap(),
+            message
+        );
+    }
+}
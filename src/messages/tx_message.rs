@@ -19,6 +19,15 @@ impl Message for TxMessage {
     fn get_command_name() -> CommandName {
         CommandName::Tx
     }
+
+    fn io_deserialize_limited(
+        stream: &mut dyn Read,
+        payload_size: u64,
+    ) -> Result<Self, ErrorSerialization> {
+        Ok(TxMessage {
+            transaction: Transaction::io_deserialize_limited(stream, payload_size)?,
+        })
+    }
 }
 
 impl SerializableInternalOrder for TxMessage {
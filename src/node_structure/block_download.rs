@@ -1,38 +1,85 @@
-use super::error_node::ErrorNode;
+use super::{
+    error_node::ErrorNode,
+    misbehavior::{
+        Misbehavior, INVALID_COINBASE_SCORE, INVALID_MERKLE_ROOT_SCORE, UNREQUESTED_BLOCK_SCORE,
+    },
+    sync_progress::ProgressTracker,
+};
 
 use crate::messages::{
     block_message::BlockMessage,
     command_name::CommandName,
     get_data_message::GetDataMessage,
     message::{self, Message},
-    message_header::MagicType,
 };
 
-use crate::notifications::{notification::Notification, notifier::Notifier};
+use crate::notifications::notifier::Notifier;
+
+use crate::serialization::error_serialization::ErrorSerialization;
 
 use crate::logs::logger_sender::LoggerSender;
 
 use crate::block_structure::{block::Block, hash::HashType};
 
-use std::io::{Read, Write};
+use crate::connections::network::Network;
 
-const MAX_HEADERS_COUNT: usize = 50_000;
+use std::{
+    collections::HashSet,
+    io::{Read, Write},
+    net::SocketAddr,
+};
+
+/// The amount of blocks asked to the peer in each get data message
+pub const BLOCK_BATCH_SIZE: usize = 128;
 
 /// It represents the download of blocks given the headers to the block to download
 #[derive(Debug, Clone)]
 pub struct BlockDownload {
-    magic_numbers: MagicType,
+    network: Network,
+    peer: Option<SocketAddr>,
+    batch_size: usize,
+    blocks_total: u32,
+    tracker: Option<ProgressTracker>,
     sender_log: LoggerSender,
 }
 
 impl BlockDownload {
-    pub fn new(magic_numbers: MagicType, sender_log: LoggerSender) -> Self {
+    pub fn new(network: Network, sender_log: LoggerSender) -> Self {
         BlockDownload {
-            magic_numbers,
+            network,
+            peer: None,
+            batch_size: BLOCK_BATCH_SIZE,
+            blocks_total: 0,
+            tracker: None,
             sender_log,
         }
     }
 
+    /// The progress of the download will show the address of the peer
+    pub fn with_peer(self, peer: SocketAddr) -> Self {
+        BlockDownload {
+            peer: Some(peer),
+            ..self
+        }
+    }
+
+    /// The progress of the download will count the given amount of blocks, for a download split in
+    /// many requests. Otherwise the total grows with the blocks of each request
+    pub fn with_blocks_total(self, blocks_total: u32) -> Self {
+        BlockDownload {
+            blocks_total,
+            ..self
+        }
+    }
+
+    /// The blocks will be asked to the peer in get data messages of the given amount of blocks
+    pub fn with_batch_size(self, batch_size: usize) -> Self {
+        BlockDownload {
+            batch_size: batch_size.max(1),
+            ..self
+        }
+    }
+
     /// It sends a get data message to the peer given the hashed headers
     ///
     /// ### Error
@@ -46,81 +93,163 @@ impl BlockDownload {
 
         let get_data_message = GetDataMessage::get_blocks(hashed_headers);
 
-        GetDataMessage::serialize_message(peer_stream, self.magic_numbers, &get_data_message)?;
+        GetDataMessage::serialize_message(
+            peer_stream,
+            self.network.magic_numbers(),
+            &get_data_message,
+        )?;
 
         Ok(())
     }
 
-    /// It receives the blocks from the peer
+    /// It receives the blocks of a batch from the peer, until every requested block is received or
+    /// the peer says it cannot serve it, notifying the progress periodically. The blocks that the
+    /// peer cannot serve are skipped, so they stay to be requested to another peer. The blocks that
+    /// were not requested or whose transactions don't match their merkle root are ignored, adding to
+    /// the misbehavior score of the peer
     ///
     /// ### Error
     ///  * `ErrorNode::WhileSerializing`: It will appear when there is an error in the serialization
     ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
+    ///  * `ErrorNode::PeerMisbehaving`: It will appear when the peer sends too many blocks that are not valid
     fn receive_blocks<N: Notifier, RW: Read + Write>(
         &self,
         peer_stream: &mut RW,
-        headers_count: usize,
-        notifier: N,
+        requested: &[HashType],
+        progress: (&mut ProgressTracker, &N),
+        misbehavior: &mut Misbehavior,
     ) -> Result<Vec<Block>, ErrorNode> {
+        let (tracker, notifier) = progress;
+        let mut pending: HashSet<HashType> = requested.iter().copied().collect();
         let mut blocks: Vec<Block> = Vec::new();
-        for i in 0..headers_count {
-            if i % 100 == 0 {
+        while !pending.is_empty() {
+            let i = blocks.len();
+            if i.is_multiple_of(100) {
                 let _ = self
                     .sender_log
                     .log_connection(format!("Getting blocks [{i}]"));
-                notifier.notify(Notification::ProgressDownloadingBlocks(
-                    i as u32,
-                    headers_count as u32,
-                ));
             }
-
-            let header = message::deserialize_until_found(peer_stream, CommandName::Block)?;
+            tracker.notify_if_due(notifier);
+
+            let header = match message::deserialize_until_found(peer_stream, CommandName::Block) {
+                Ok(header) => header,
+                Err(ErrorSerialization::DataNotFound(hashes)) => {
+                    let not_found = hashes.iter().filter(|hash| pending.remove(*hash)).count();
+                    let _ = self.sender_log.log_connection(format!(
+                        "The peer could not find {not_found} blocks, they will be requested to another peer"
+                    ));
+                    tracker.skip_blocks(not_found as u32);
+                    continue;
+                }
+                Err(error) => return Err(error.into()),
+            };
+            let bytes = header.payload_size as u64;
             let block_message = BlockMessage::deserialize_message(peer_stream, header)?;
 
-            if !block_message.block.proof_of_inclusion() {
-                return Err(ErrorNode::WhileValidating(
-                    "Failed proof of inclusion".to_string(),
+            if !pending.remove(&block_message.block.header.get_hash256d()?) {
+                let _ = self.sender_log.log_connection(format!(
+                    "The peer sent the block {} that was not requested, it's ignored",
+                    block_message.block
                 ));
+                misbehavior.add(
+                    UNREQUESTED_BLOCK_SCORE,
+                    "Sent a block that was not requested",
+                )?;
+                continue;
+            }
+
+            if !block_message.block.proof_of_inclusion() {
+                misbehavior.add(
+                    INVALID_MERKLE_ROOT_SCORE,
+                    "Sent a block with transactions that don't match its merkle root",
+                )?;
+                continue;
+            }
+
+            if !block_message.block.has_valid_coinbase() {
+                misbehavior.add(
+                    INVALID_COINBASE_SCORE,
+                    "Sent a block without a valid coinbase",
+                )?;
+                continue;
             }
 
             blocks.push(block_message.block);
+            tracker.add_blocks(1, bytes);
         }
-        notifier.notify(Notification::ProgressDownloadingBlocks(
-            headers_count as u32,
-            headers_count as u32,
-        ));
 
         Ok(blocks)
     }
 
-    /// Get the blocks from the peer given the hashed headers
+    /// Get the blocks from the peer given the hashed headers, notifying the progress of every block
+    /// received at most once a second. The progress continues from the previous calls, so a download
+    /// split in many calls is shown as a single one
     ///
     /// ### Error
     ///  * `ErrorNode::WhileSerializing`: It will appear when there is an error in the serialization
     ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
-    ///  * `ErrorNode::RequestedDataTooBig`: It will appear when the headers count is bigger than the maximum headers count of 50_000
+    ///  * `ErrorNode::PeerMisbehaving`: It will appear when the peer sends too many blocks that are not valid
     pub fn get_data<N: Notifier, RW: Read + Write>(
-        &self,
+        &mut self,
         peer_stream: &mut RW,
         hashed_headers: Vec<HashType>,
         notifier: N,
+    ) -> Result<Vec<Block>, ErrorNode> {
+        let mut tracker = match self.tracker.take() {
+            Some(tracker) => tracker,
+            None => ProgressTracker::new(self.blocks_total, self.peer),
+        };
+        tracker.expect_blocks(hashed_headers.len() as u32);
+
+        let blocks = self.download_batches(peer_stream, &hashed_headers, &mut tracker, &notifier);
+        tracker.notify(&notifier);
+        self.tracker = Some(tracker);
+
+        blocks
+    }
+
+    /// Asks for the blocks in batches, asking for the next batch while the current one is received
+    /// so the peer is never waiting for a request
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileSerializing`: It will appear when there is an error in the serialization
+    ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
+    ///  * `ErrorNode::PeerMisbehaving`: It will appear when the peer sends too many blocks that are not valid
+    fn download_batches<N: Notifier, RW: Read + Write>(
+        &self,
+        peer_stream: &mut RW,
+        hashed_headers: &[HashType],
+        tracker: &mut ProgressTracker,
+        notifier: &N,
     ) -> Result<Vec<Block>, ErrorNode> {
         let headers_count = hashed_headers.len();
+        let batches: Vec<&[HashType]> = hashed_headers.chunks(self.batch_size).collect();
+
+        let _ = self.sender_log.log_connection(format!(
+            "Downloading {headers_count} blocks in {} batches",
+            batches.len()
+        ));
 
-        if headers_count >= MAX_HEADERS_COUNT {
-            let _ = self
-                .sender_log
-                .log_connection("More headers than possible".to_string());
-            return Err(ErrorNode::RequestedDataTooBig);
+        if let Some(first_batch) = batches.first() {
+            self.send_get_data_message(peer_stream, first_batch.to_vec())?;
         }
 
-        self.send_get_data_message(peer_stream, hashed_headers)?;
+        let mut misbehavior = Misbehavior::default();
+        let mut blocks: Vec<Block> = Vec::new();
+        for (i, batch) in batches.iter().enumerate() {
+            if let Some(next_batch) = batches.get(i + 1) {
+                self.send_get_data_message(peer_stream, next_batch.to_vec())?;
+            }
 
-        let _ = self
-            .sender_log
-            .log_connection(format!("Downloading {headers_count} blocks",));
+            blocks.extend(self.receive_blocks(
+                peer_stream,
+                batch,
+                (tracker, notifier),
+                &mut misbehavior,
+            )?);
+        }
 
-        self.receive_blocks(peer_stream, headers_count, notifier)
+        Ok(blocks)
     }
 }
 
@@ -132,16 +261,22 @@ mod tests {
     use crate::{
         block_structure::{
             block::Block, block_header::BlockHeader, block_version::BlockVersion,
-            compact256::Compact256, merkle_tree::MerkleTree, outpoint::Outpoint,
+            compact256::Compact256, merkle_tree::MerkleTree, outpoint::Outpoint, script::ScriptBuf,
             transaction::Transaction, transaction_input::TransactionInput,
             transaction_output::TransactionOutput,
         },
         connections::type_identifier::TypeIdentifier,
         logs::logger,
-        messages::{compact_size::CompactSize, inventory_vector::InventoryVector},
-        serialization::error_serialization::ErrorSerialization,
+        messages::{
+            compact_size::CompactSize, inventory_vector::InventoryVector,
+            not_found_message::NotFoundMessage,
+        },
+        node_structure::sync_progress::SyncProgress,
+        notifications::notification::Notification,
     };
 
+    use std::sync::{Arc, Mutex};
+
     struct Stream {
         stream: Vec<u8>,
         pointer: usize,
@@ -205,14 +340,30 @@ mod tests {
 
         let transaction_output = TransactionOutput {
             value: 10,
-            pk_script: vec![4, 5, 6],
+            pk_script: ScriptBuf::from(vec![4, 5, 6]),
         };
 
         Transaction {
             version: 1,
             tx_in: vec![transaction_input.clone()],
             tx_out: vec![transaction_output.clone()],
-            time,
+            lock_time: time,
+        }
+    }
+
+    fn create_coinbase(time: u32) -> Transaction {
+        Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(
+                Outpoint::new([0; 32], u32::MAX),
+                vec![1, 2, 3],
+                u32::MAX,
+            )],
+            tx_out: vec![TransactionOutput {
+                value: 10,
+                pk_script: ScriptBuf::from(vec![4, 5, 6]),
+            }],
+            lock_time: time,
         }
     }
 
@@ -251,10 +402,7 @@ mod tests {
         let magic_numbers = [11, 17, 9, 7];
 
         let mut first_block = create_empty_block(3);
-        let first_block_header_hash = first_block.header.get_hash256d().unwrap();
-        first_block
-            .append_transaction(create_transaction(0))
-            .unwrap();
+        first_block.append_transaction(create_coinbase(0)).unwrap();
         first_block
             .append_transaction(create_transaction(1))
             .unwrap();
@@ -263,12 +411,10 @@ mod tests {
             .unwrap();
 
         update_merkle_root_hash(&mut first_block);
+        let first_block_header_hash = first_block.header.get_hash256d().unwrap();
 
         let mut second_block = create_block(first_block_header_hash, 3);
-        let second_block_header_hash = second_block.header.get_hash256d().unwrap();
-        second_block
-            .append_transaction(create_transaction(1))
-            .unwrap();
+        second_block.append_transaction(create_coinbase(1)).unwrap();
         second_block
             .append_transaction(create_transaction(2))
             .unwrap();
@@ -277,6 +423,7 @@ mod tests {
             .unwrap();
 
         update_merkle_root_hash(&mut second_block);
+        let second_block_header_hash = second_block.header.get_hash256d().unwrap();
 
         serialize_block_message(&mut stream, magic_numbers.clone(), first_block.clone()).unwrap();
         serialize_block_message(&mut stream, magic_numbers.clone(), second_block.clone()).unwrap();
@@ -285,7 +432,7 @@ mod tests {
 
         let logger_text: Vec<u8> = Vec::new();
         let (sender, _) = logger::initialize_logger(logger_text, false);
-        let block_download = BlockDownload::new(magic_numbers, sender);
+        let mut block_download = BlockDownload::new(Network::Testnet, sender);
 
         let notifier = NotificationMock {};
 
@@ -311,4 +458,249 @@ mod tests {
             expected_inventory_vectors
         );
     }
+
+    #[test]
+    fn test02_blocks_not_found_are_skipped() {
+        let mut stream = Stream::new();
+        let magic_numbers = [11, 17, 9, 7];
+
+        let mut first_block = create_empty_block(1);
+        first_block.append_transaction(create_coinbase(0)).unwrap();
+        update_merkle_root_hash(&mut first_block);
+        let first_block_header_hash = first_block.header.get_hash256d().unwrap();
+
+        let missing_hash: HashType = [5; 32];
+
+        NotFoundMessage::serialize_message(
+            &mut stream,
+            magic_numbers,
+            &NotFoundMessage::new(vec![InventoryVector::new(
+                TypeIdentifier::Block,
+                missing_hash,
+            )]),
+        )
+        .unwrap();
+        serialize_block_message(&mut stream, magic_numbers, first_block.clone()).unwrap();
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+        let mut block_download = BlockDownload::new(Network::Testnet, sender);
+
+        let blocks = block_download
+            .get_data(
+                &mut stream,
+                vec![missing_hash, first_block_header_hash],
+                NotificationMock {},
+            )
+            .unwrap();
+
+        assert_eq!(blocks, vec![first_block]);
+    }
+
+    #[test]
+    fn test03_progress_has_the_bytes_downloaded_and_the_peer() {
+        #[derive(Clone)]
+        struct ProgressNotifier(Arc<Mutex<Vec<SyncProgress>>>);
+
+        impl Notifier for ProgressNotifier {
+            fn notify(&self, notification: Notification) {
+                if let Notification::ProgressDownloadingBlocks(progress) = notification {
+                    self.0.lock().unwrap().push(progress);
+                }
+            }
+        }
+
+        let mut stream = Stream::new();
+        let magic_numbers = [11, 17, 9, 7];
+
+        let mut block = create_empty_block(1);
+        block.append_transaction(create_coinbase(0)).unwrap();
+        update_merkle_root_hash(&mut block);
+        let block_hash = block.header.get_hash256d().unwrap();
+
+        serialize_block_message(&mut stream, magic_numbers, block).unwrap();
+        let bytes = stream.stream.len() as u64 - 24;
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+        let peer: SocketAddr = "127.0.0.1:18333".parse().unwrap();
+        let mut block_download = BlockDownload::new(Network::Testnet, sender).with_peer(peer);
+
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        block_download
+            .get_data(
+                &mut stream,
+                vec![block_hash],
+                ProgressNotifier(recorded.clone()),
+            )
+            .unwrap();
+
+        let recorded = recorded.lock().unwrap();
+        let last = recorded.last().unwrap();
+        assert_eq!((1, 1), (last.blocks_done, last.blocks_total));
+        assert_eq!(bytes, last.bytes_downloaded);
+        assert_eq!(Some(peer), last.peer);
+    }
+
+    fn create_block_with_transaction(time: u32) -> Block {
+        let mut block = create_empty_block(1);
+        block.append_transaction(create_coinbase(time)).unwrap();
+        update_merkle_root_hash(&mut block);
+        block
+    }
+
+    #[test]
+    fn test04_blocks_are_requested_in_batches() {
+        let mut stream = Stream::new();
+        let magic_numbers = [11, 17, 9, 7];
+
+        let blocks: Vec<Block> = (0..3).map(create_block_with_transaction).collect();
+        let hashed_headers: Vec<HashType> = blocks
+            .iter()
+            .map(|block| block.header.get_hash256d().unwrap())
+            .collect();
+        for block in blocks.iter() {
+            serialize_block_message(&mut stream, magic_numbers, block.clone()).unwrap();
+        }
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+        let mut block_download = BlockDownload::new(Network::Testnet, sender).with_batch_size(2);
+
+        let received = block_download
+            .get_data(&mut stream, hashed_headers.clone(), NotificationMock {})
+            .unwrap();
+        assert_eq!(blocks, received);
+
+        let mut requested: Vec<Vec<HashType>> = Vec::new();
+        while let Ok(header) = message::deserialize_until_found(&mut stream, CommandName::GetData) {
+            let get_data_message =
+                GetDataMessage::deserialize_message(&mut stream, header).unwrap();
+            requested.push(
+                get_data_message
+                    .inventory_vectors
+                    .iter()
+                    .map(|inventory_vector| inventory_vector.hash_value)
+                    .collect(),
+            );
+        }
+
+        assert_eq!(
+            vec![hashed_headers[0..2].to_vec(), hashed_headers[2..3].to_vec()],
+            requested
+        );
+    }
+
+    #[test]
+    fn test05_blocks_not_requested_are_ignored() {
+        let mut stream = Stream::new();
+        let magic_numbers = [11, 17, 9, 7];
+
+        let requested_block = create_block_with_transaction(0);
+        let other_block = create_block_with_transaction(1);
+
+        serialize_block_message(&mut stream, magic_numbers, other_block).unwrap();
+        serialize_block_message(&mut stream, magic_numbers, requested_block.clone()).unwrap();
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+        let mut block_download = BlockDownload::new(Network::Testnet, sender);
+
+        let received = block_download
+            .get_data(
+                &mut stream,
+                vec![requested_block.header.get_hash256d().unwrap()],
+                NotificationMock {},
+            )
+            .unwrap();
+
+        assert_eq!(vec![requested_block], received);
+    }
+
+    #[test]
+    fn test06_progress_continues_between_requests() {
+        #[derive(Clone)]
+        struct ProgressNotifier(Arc<Mutex<Vec<SyncProgress>>>);
+
+        impl Notifier for ProgressNotifier {
+            fn notify(&self, notification: Notification) {
+                if let Notification::ProgressDownloadingBlocks(progress) = notification {
+                    self.0.lock().unwrap().push(progress);
+                }
+            }
+        }
+
+        let mut stream = Stream::new();
+        let magic_numbers = [11, 17, 9, 7];
+
+        let first_block = create_block_with_transaction(0);
+        let second_block = create_block_with_transaction(1);
+        let missing_hash: HashType = [5; 32];
+
+        serialize_block_message(&mut stream, magic_numbers, first_block.clone()).unwrap();
+        NotFoundMessage::serialize_message(
+            &mut stream,
+            magic_numbers,
+            &NotFoundMessage::new(vec![InventoryVector::new(
+                TypeIdentifier::Block,
+                missing_hash,
+            )]),
+        )
+        .unwrap();
+        serialize_block_message(&mut stream, magic_numbers, second_block.clone()).unwrap();
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+        let mut block_download = BlockDownload::new(Network::Testnet, sender).with_blocks_total(3);
+
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        block_download
+            .get_data(
+                &mut stream,
+                vec![first_block.header.get_hash256d().unwrap()],
+                ProgressNotifier(recorded.clone()),
+            )
+            .unwrap();
+
+        let first = recorded.lock().unwrap().last().cloned().unwrap();
+        assert_eq!((1, 3), (first.blocks_done, first.blocks_total));
+
+        block_download
+            .get_data(
+                &mut stream,
+                vec![missing_hash, second_block.header.get_hash256d().unwrap()],
+                ProgressNotifier(recorded.clone()),
+            )
+            .unwrap();
+
+        let recorded = recorded.lock().unwrap();
+        let last = recorded.last().unwrap();
+        assert_eq!((2, 2), (last.blocks_done, last.blocks_total));
+        assert_eq!(1, last.blocks_to_retry);
+    }
+
+    #[test]
+    fn test07_block_without_coinbase_is_rejected() {
+        let mut stream = Stream::new();
+        let magic_numbers = [11, 17, 9, 7];
+
+        let mut block = create_empty_block(1);
+        block.append_transaction(create_transaction(0)).unwrap();
+        update_merkle_root_hash(&mut block);
+
+        serialize_block_message(&mut stream, magic_numbers, block.clone()).unwrap();
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+        let mut block_download = BlockDownload::new(Network::Testnet, sender);
+
+        assert!(matches!(
+            block_download.get_data(
+                &mut stream,
+                vec![block.header.get_hash256d().unwrap()],
+                NotificationMock {},
+            ),
+            Err(ErrorNode::PeerMisbehaving(_))
+        ));
+    }
 }
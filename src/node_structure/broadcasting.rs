@@ -1,22 +1,30 @@
 use super::{
-    connection_id::ConnectionId, error_node::ErrorNode, message_to_peer::MessageToPeer,
+    connection_id::ConnectionId,
+    error_node::ErrorNode,
+    keep_alive::{self, KEEP_ALIVE_TICK},
+    message_to_peer::MessageToPeer,
     peer_manager::PeerManager,
+    peer_queue::{PeerQueue, QueuePush},
+    recent_inventory::RecentInventory,
 };
 
 use crate::{
     block_structure::{block::Block, transaction::Transaction},
-    logs::logger_sender::LoggerSender,
+    logs::{level::Level, logger_sender::LoggerSender},
     notifications::{notification::Notification, notifier::Notifier},
 };
 
 use std::{
+    collections::HashSet,
     io::{Read, Write},
-    sync::mpsc::{Receiver, Sender},
+    net::{IpAddr, SocketAddr},
+    sync::{mpsc::Receiver, Arc, Mutex, MutexGuard},
     thread::{self, JoinHandle},
+    time::Instant,
 };
 
-type HandleSender<T> = (JoinHandle<Result<T, ErrorNode>>, Sender<MessageToPeer>);
-type SenderReceiver<T> = (Sender<T>, Receiver<T>);
+type HandleSender<T> = (ConnectionId, JoinHandle<Result<T, ErrorNode>>, PeerQueue);
+type QueueReceiver<T> = (PeerQueue, Receiver<T>);
 
 // It represents the broadcasting of the transactions and blocks to the peers
 pub struct Broadcasting<RW>
@@ -24,6 +32,8 @@ where
     RW: Read + Write + Send + 'static,
 {
     peers: Vec<HandleSender<(RW, ConnectionId)>>,
+    banned: Arc<Mutex<HashSet<IpAddr>>>,
+    recent_inventory: Arc<Mutex<RecentInventory>>,
     logger: LoggerSender,
 }
 
@@ -34,21 +44,32 @@ where
     pub fn new(logger: LoggerSender) -> Self {
         Broadcasting {
             peers: Vec::new(),
+            banned: Arc::new(Mutex::new(HashSet::new())),
+            recent_inventory: Arc::new(Mutex::new(RecentInventory::default())),
             logger,
         }
     }
 
-    /// It adds a connection to a peer to the broadcasting
+    /// It adds a connection to a peer to the broadcasting, the peer manager drains the queue of the peer.
+    /// The transactions and blocks recently received are shared between the peers, so they are processed once,
+    /// and so is the ban list, so a peer that misbehaves is rejected by the next connections
     pub fn add_connection<N: Notifier>(
         &mut self,
         peer_manager: PeerManager<RW, N>,
-        sender_receiver: SenderReceiver<MessageToPeer>,
+        queue_receiver: QueueReceiver<MessageToPeer>,
     ) {
-        let handle = thread::spawn(move || peer_manager.connecting_to_peer(sender_receiver.1));
-        self.peers.push((handle, sender_receiver.0));
+        let (queue, receiver) = queue_receiver;
+        let peer_manager = peer_manager
+            .with_queue_status(queue.get_status())
+            .with_recent_inventory(self.recent_inventory.clone())
+            .with_ban_list(self.banned.clone());
+        let id = peer_manager.peer_information().connection;
+        let handle = thread::spawn(move || peer_manager.connecting_to_peer(receiver));
+        keep_alive::spawn_keep_alive_scheduler(queue.clone(), KEEP_ALIVE_TICK);
+        self.peers.push((id, handle, queue));
     }
 
-    /// It sends a transaction to all the peers
+    /// It announces a transaction to all the peers, the transaction is sent to the peers that request it
     ///
     /// ### Error
     ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending a message to a peer
@@ -56,21 +77,15 @@ where
         let _ = self
             .logger
             .log_broadcasting(format!("Broadcasting own transaction: {transaction}"));
-        for (_, sender) in self.peers.iter() {
-            if sender
-                .send(MessageToPeer::SendTransaction(transaction.clone(), None))
-                .is_err()
-            {
-                let _ = self
-                    .logger
-                    .log_error("Sending transaction message to peer".to_string());
-            }
-        }
+        self.push_to_peers(
+            MessageToPeer::SendTransaction(transaction, None),
+            "transaction",
+        );
 
         Ok(())
     }
 
-    /// It broadcast a transaction to all the peers except the peer that sent the transaction
+    /// It announces a transaction to all the peers except the peer that sent the transaction
     ///
     /// ### Error
     ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending a message to a peer
@@ -82,24 +97,15 @@ where
         let _ = self
             .logger
             .log_broadcasting("Broadcasting a transaction".to_string());
-        for (_, sender) in self.peers.iter() {
-            if sender
-                .send(MessageToPeer::SendTransaction(
-                    transaction.clone(),
-                    Some(from),
-                ))
-                .is_err()
-            {
-                let _ = self
-                    .logger
-                    .log_error("Sending transaction message to peer".to_string());
-            }
-        }
+        self.push_to_peers(
+            MessageToPeer::SendTransaction(transaction, Some(from)),
+            "transaction",
+        );
 
         Ok(())
     }
 
-    /// It broadcast a block to all the peers except the peer that sent the block
+    /// It announces a block to all the peers except the peer that sent the block
     ///
     /// ### Error
     ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending a message to a peer
@@ -107,20 +113,87 @@ where
         let _ = self
             .logger
             .log_broadcasting("Broadcasting a block".to_string());
-        for (_, sender) in self.peers.iter() {
-            if sender
-                .send(MessageToPeer::SendBlock(block.clone(), from))
-                .is_err()
-            {
-                let _ = self
-                    .logger
-                    .log_error("Sending block message to peer".to_string());
-            }
-        }
+        self.push_to_peers(MessageToPeer::SendBlock(block, from), "block");
 
         Ok(())
     }
 
+    /// It pushes the message to the queue of every peer without waiting for them. The message is dropped
+    /// for the peers whose queue is full, and the peers whose queue stays full are disconnected for being too slow
+    fn push_to_peers(&mut self, message: MessageToPeer, kind: &str) {
+        let now = Instant::now();
+        let logger = self.logger.clone();
+        self.peers
+            .retain_mut(|(id, _, queue)| match queue.push(message.clone(), now) {
+                QueuePush::Queued => true,
+                QueuePush::Dropped => {
+                    let _ = logger.log_warning(
+                        Level::BROADCASTING,
+                        format!("The queue of the peer {id} is full, dropping the {kind} message"),
+                    );
+                    true
+                }
+                QueuePush::Stuck => {
+                    let _ = logger.log_connection(format!(
+                        "Disconnecting the peer {id} for not keeping up with its queue"
+                    ));
+                    queue.close();
+                    false
+                }
+                QueuePush::Disconnected => {
+                    let _ = logger.log_error(format!("Sending {kind} message to peer"));
+                    true
+                }
+            });
+    }
+
+    /// It stops the connection with the peer of the given address, returning false if there is no such connection
+    pub fn disconnect_peer(&mut self, address: SocketAddr) -> bool {
+        let address = SocketAddr::new(address.ip().to_canonical(), address.port());
+        self.stop_peers(|id| id.address == address)
+    }
+
+    /// It stops the connections with the peers of the given ip, and rejects the next connections from it
+    pub fn ban_peer(&mut self, ip: IpAddr) {
+        let ip = ip.to_canonical();
+        let _ = self.logger.log_connection(format!("Banning the peer {ip}"));
+
+        self.lock_banned().insert(ip);
+        self.stop_peers(|id| id.address.ip() == ip);
+    }
+
+    /// Returns true if the peers of the given ip were banned
+    pub fn is_banned(&self, ip: &IpAddr) -> bool {
+        self.lock_banned().contains(&ip.to_canonical())
+    }
+
+    fn lock_banned(&self) -> MutexGuard<'_, HashSet<IpAddr>> {
+        match self.banned.lock() {
+            Ok(banned) => banned,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// It stops the peers whose connection matches the condition, returning false if there was none.
+    /// The peers are forgotten without waiting for their threads to finish
+    fn stop_peers<F: Fn(&ConnectionId) -> bool>(&mut self, condition: F) -> bool {
+        let mut stopped = false;
+        self.peers.retain(|(id, _, queue)| {
+            if !condition(id) {
+                return true;
+            }
+
+            let _ = self
+                .logger
+                .log_connection(format!("Disconnecting the peer {id}"));
+            queue.close();
+            stopped = true;
+            false
+        });
+
+        stopped
+    }
+
     /// It stops all the peers and returns their streams
     ///
     /// ### Error
@@ -128,12 +201,8 @@ where
     pub fn close_connections<N: Notifier>(&mut self, notifier: N) -> Result<Vec<RW>, ErrorNode> {
         let _ = self.logger.log_configuration("Closing peers".to_string());
         notifier.notify(Notification::ClosingPeers);
-        for (_, sender) in self.peers.iter() {
-            if sender.send(MessageToPeer::Stop).is_err() {
-                let _ = self
-                    .logger
-                    .log_error("Sending closing message to peer".to_string());
-            }
+        for (_, _, queue) in self.peers.iter() {
+            queue.close();
         }
 
         let mut peers: Vec<HandleSender<(RW, ConnectionId)>> = Vec::new();
@@ -143,7 +212,7 @@ where
         }
 
         let mut peers_streams = Vec::new();
-        for (handle, _) in peers {
+        for (_, handle, _) in peers {
             match handle.join() {
                 Ok(Ok((peer_stream, _))) => peers_streams.push(peer_stream),
                 Ok(Err(error)) => return Err(error),
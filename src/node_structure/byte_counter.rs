@@ -0,0 +1,77 @@
+use std::io::{Read, Result, Write};
+
+/// It's a stream that counts the bytes read from it and written to it
+#[derive(Debug)]
+pub struct ByteCounter<RW: Read + Write> {
+    stream: RW,
+    received: u64,
+    sent: u64,
+}
+
+impl<RW: Read + Write> ByteCounter<RW> {
+    pub fn new(stream: RW) -> Self {
+        ByteCounter {
+            stream,
+            received: 0,
+            sent: 0,
+        }
+    }
+
+    /// Returns the amount of bytes read from the stream
+    pub fn received(&self) -> u64 {
+        self.received
+    }
+
+    /// Returns the amount of bytes written to the stream
+    pub fn sent(&self) -> u64 {
+        self.sent
+    }
+
+    /// Returns the stream being counted
+    pub fn into_inner(self) -> RW {
+        self.stream
+    }
+}
+
+impl<RW: Read + Write> Read for ByteCounter<RW> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let read = self.stream.read(buf)?;
+        self.received += read as u64;
+        Ok(read)
+    }
+}
+
+impl<RW: Read + Write> Write for ByteCounter<RW> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let written = self.stream.write(buf)?;
+        self.sent += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.stream.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Cursor;
+
+    #[test]
+    fn test01_counts_the_bytes_read_and_written() {
+        let mut counter = ByteCounter::new(Cursor::new(vec![1, 2, 3, 4, 5]));
+
+        let mut buffer = [0; 3];
+        counter.read_exact(&mut buffer).unwrap();
+        counter.write_all(&[9, 9, 9, 9]).unwrap();
+
+        let mut rest = Vec::new();
+        counter.read_to_end(&mut rest).unwrap();
+
+        assert_eq!(3, counter.received());
+        assert_eq!(4, counter.sent());
+        assert_eq!(vec![1, 2, 3, 9, 9, 9, 9], counter.into_inner().into_inner());
+    }
+}
@@ -0,0 +1,340 @@
+use crate::{
+    block_structure::{
+        block::Block, block_header::BlockHeader, hash::HashType, transaction::Transaction,
+    },
+    messages::{
+        compact_block_message::{calculate_short_id, CompactBlockMessage, ShortTransactionId},
+        send_cmpct_message::SendCmpctMessage,
+    },
+    serialization::error_serialization::ErrorSerialization,
+};
+
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub const COMPACT_BLOCK_VERSION: u64 = 1;
+pub const MAX_PARTIAL_BLOCKS: usize = 16;
+
+/// It's a block being rebuilt from a compact block, with the positions of the transactions still missing
+#[derive(Debug, Clone)]
+pub struct PartialBlock {
+    header: BlockHeader,
+    transactions: Vec<Option<Transaction>>,
+}
+
+impl PartialBlock {
+    /// Places the prefilled transactions and the transactions of the mempool that match the short ids.
+    /// Returns None if the compact block cannot be used, as when two of its transactions share a short id
+    pub fn new<'t, I>(compact_block: CompactBlockMessage, mempool: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = &'t Transaction>,
+    {
+        let keys = compact_block.get_short_id_keys().ok()?;
+        let mut transactions: Vec<Option<Transaction>> =
+            vec![None; compact_block.transaction_count()];
+
+        for prefilled in compact_block.prefilled_transactions {
+            let position = transactions.get_mut(prefilled.index as usize)?;
+            *position = Some(prefilled.transaction);
+        }
+
+        let missing_positions = transactions.iter().enumerate().filter_map(
+            |(position, transaction)| match transaction {
+                Some(_) => None,
+                None => Some(position),
+            },
+        );
+
+        let mut short_ids: HashMap<ShortTransactionId, usize> = HashMap::new();
+        for (short_id, position) in compact_block.short_ids.iter().zip(missing_positions) {
+            if short_ids.insert(*short_id, position).is_some() {
+                return None;
+            }
+        }
+
+        for transaction in mempool {
+            let transaction_id = match transaction.get_tx_id() {
+                Ok(transaction_id) => transaction_id,
+                Err(_) => continue,
+            };
+
+            if let Some(position) = short_ids.remove(&calculate_short_id(keys, &transaction_id)) {
+                transactions[position] = Some(transaction.clone());
+            }
+        }
+
+        Some(PartialBlock {
+            header: compact_block.header,
+            transactions,
+        })
+    }
+
+    /// Returns the positions in the block of the transactions that are missing
+    pub fn get_missing_indexes(&self) -> Vec<u64> {
+        self.transactions
+            .iter()
+            .enumerate()
+            .filter_map(|(index, transaction)| match transaction {
+                Some(_) => None,
+                None => Some(index as u64),
+            })
+            .collect()
+    }
+
+    /// Places the given transactions in the missing positions, in order.
+    /// Returns false if the amount of transactions is not the amount missing
+    pub fn fill(&mut self, transactions: Vec<Transaction>) -> bool {
+        let missing_indexes = self.get_missing_indexes();
+        if missing_indexes.len() != transactions.len() {
+            return false;
+        }
+
+        for (index, transaction) in missing_indexes.into_iter().zip(transactions) {
+            self.transactions[index as usize] = Some(transaction);
+        }
+
+        true
+    }
+
+    /// Returns the block if it has all its transactions and they match the merkle root
+    pub fn get_block(&self) -> Option<Block> {
+        let mut block = Block::new(self.header);
+        for transaction in self.transactions.iter() {
+            block.transactions.push(transaction.clone()?);
+        }
+
+        match block.proof_of_inclusion() {
+            true => Some(block),
+            false => None,
+        }
+    }
+}
+
+/// It represents the compact blocks negotiated with a peer, and the blocks waiting for the transactions
+/// requested to rebuild them
+#[derive(Debug, Clone)]
+pub struct CompactBlockRelay {
+    is_supported: bool,
+    should_announce: bool,
+    partial_blocks: HashMap<HashType, PartialBlock>,
+    waiting: VecDeque<HashType>,
+    capacity: usize,
+    nonce_counter: u64,
+}
+
+impl CompactBlockRelay {
+    pub fn new(capacity: usize) -> Self {
+        CompactBlockRelay {
+            is_supported: false,
+            should_announce: false,
+            partial_blocks: HashMap::new(),
+            waiting: VecDeque::new(),
+            capacity,
+            nonce_counter: 0,
+        }
+    }
+
+    /// Receives the send compact message of the peer. Only the version without segregated witness is supported
+    pub fn receive_send_compact(&mut self, send_compact: &SendCmpctMessage) {
+        if send_compact.version == COMPACT_BLOCK_VERSION {
+            self.is_supported = true;
+            self.should_announce = send_compact.announce;
+        }
+    }
+
+    /// Returns true if the peer can send and receive compact blocks
+    pub fn is_supported(&self) -> bool {
+        self.is_supported
+    }
+
+    /// Returns true if the peer wants new blocks to be announced directly with a compact block
+    pub fn should_announce(&self) -> bool {
+        self.is_supported && self.should_announce
+    }
+
+    /// Creates the compact block of the given block, with a new nonce
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::ErrorInSerialization`: It will appear when the short ids cannot be calculated
+    pub fn create_compact_block(
+        &mut self,
+        block: &Block,
+    ) -> Result<CompactBlockMessage, ErrorSerialization> {
+        let nonce = self.generate_nonce();
+        CompactBlockMessage::new(block, nonce)
+    }
+
+    /// Keeps the partial block until its missing transactions arrive, forgetting the oldest one
+    /// when the capacity is exceeded
+    pub fn wait_transactions(&mut self, block_hash: HashType, partial_block: PartialBlock) {
+        if self
+            .partial_blocks
+            .insert(block_hash, partial_block)
+            .is_none()
+        {
+            self.waiting.push_back(block_hash);
+        }
+
+        while self.waiting.len() > self.capacity {
+            if let Some(oldest) = self.waiting.pop_front() {
+                self.partial_blocks.remove(&oldest);
+            }
+        }
+    }
+
+    /// Returns the partial block waiting for transactions with the given hash
+    pub fn take_partial_block(&mut self, block_hash: &HashType) -> Option<PartialBlock> {
+        let partial_block = self.partial_blocks.remove(block_hash)?;
+        self.waiting
+            .retain(|waiting_hash| waiting_hash != block_hash);
+        Some(partial_block)
+    }
+
+    /// Generates a nonce that is different for every compact block of the connection
+    fn generate_nonce(&mut self) -> u64 {
+        self.nonce_counter = self.nonce_counter.wrapping_add(1);
+
+        let time = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(time) => time.as_nanos() as u64,
+            Err(_) => 0,
+        };
+
+        time ^ self.nonce_counter.rotate_left(32)
+    }
+}
+
+impl Default for CompactBlockRelay {
+    fn default() -> Self {
+        CompactBlockRelay::new(MAX_PARTIAL_BLOCKS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        block_structure::{
+            block_version::BlockVersion, compact256::Compact256, merkle_tree::MerkleTree,
+            outpoint::Outpoint, script::ScriptBuf, transaction_input::TransactionInput,
+            transaction_output::TransactionOutput,
+        },
+        messages::compact_size::CompactSize,
+    };
+
+    fn create_transaction(time: u32) -> Transaction {
+        Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(
+                Outpoint::new([1; 32], 23),
+                vec![1, 2, 3],
+                24,
+            )],
+            tx_out: vec![TransactionOutput {
+                value: 10,
+                pk_script: ScriptBuf::from(vec![4, 5, 6]),
+            }],
+            lock_time: time,
+        }
+    }
+
+    fn create_block(transactions: Vec<Transaction>) -> Block {
+        let merkle_root_hash = MerkleTree::new(&transactions).unwrap().root;
+        Block {
+            header: BlockHeader::new(
+                BlockVersion::version(1),
+                [0; 32],
+                merkle_root_hash,
+                0,
+                Compact256::from(u32::MAX),
+                0,
+                CompactSize::new(transactions.len() as u64),
+            ),
+            transactions,
+        }
+    }
+
+    #[test]
+    fn test01_rebuilds_block_with_the_mempool() {
+        let transactions: Vec<Transaction> = (0..4).map(create_transaction).collect();
+        let block = create_block(transactions.clone());
+
+        let mut compact_block_relay = CompactBlockRelay::default();
+        let compact_block = compact_block_relay.create_compact_block(&block).unwrap();
+
+        let mempool = [
+            transactions[2].clone(),
+            create_transaction(10),
+            transactions[1].clone(),
+            transactions[3].clone(),
+        ];
+        let partial_block = PartialBlock::new(compact_block, mempool.iter()).unwrap();
+
+        assert!(partial_block.get_missing_indexes().is_empty());
+        assert_eq!(partial_block.get_block(), Some(block));
+    }
+
+    #[test]
+    fn test02_requests_missing_transactions() {
+        let transactions: Vec<Transaction> = (0..4).map(create_transaction).collect();
+        let block = create_block(transactions.clone());
+
+        let compact_block = CompactBlockMessage::new(&block, 7).unwrap();
+        let mempool = [transactions[2].clone()];
+        let mut partial_block = PartialBlock::new(compact_block, mempool.iter()).unwrap();
+
+        assert_eq!(partial_block.get_missing_indexes(), vec![1, 3]);
+        assert_eq!(partial_block.get_block(), None);
+
+        assert!(!partial_block.fill(vec![transactions[1].clone()]));
+        assert!(partial_block.fill(vec![transactions[1].clone(), transactions[3].clone()]));
+        assert_eq!(partial_block.get_block(), Some(block));
+    }
+
+    #[test]
+    fn test03_wrong_transactions_do_not_rebuild_the_block() {
+        let transactions: Vec<Transaction> = (0..2).map(create_transaction).collect();
+        let block = create_block(transactions);
+
+        let compact_block = CompactBlockMessage::new(&block, 7).unwrap();
+        let mut partial_block = PartialBlock::new(compact_block, Vec::new().iter()).unwrap();
+
+        assert!(partial_block.fill(vec![create_transaction(10)]));
+        assert_eq!(partial_block.get_block(), None);
+    }
+
+    #[test]
+    fn test04_only_supported_version_is_negotiated() {
+        let mut compact_block_relay = CompactBlockRelay::default();
+
+        compact_block_relay.receive_send_compact(&SendCmpctMessage {
+            announce: true,
+            version: 2,
+        });
+        assert!(!compact_block_relay.is_supported());
+
+        compact_block_relay.receive_send_compact(&SendCmpctMessage {
+            announce: true,
+            version: COMPACT_BLOCK_VERSION,
+        });
+        assert!(compact_block_relay.is_supported());
+        assert!(compact_block_relay.should_announce());
+    }
+
+    #[test]
+    fn test05_forgets_oldest_partial_block() {
+        let mut compact_block_relay = CompactBlockRelay::new(1);
+        let block = create_block(vec![create_transaction(0), create_transaction(1)]);
+        let compact_block = CompactBlockMessage::new(&block, 7).unwrap();
+        let partial_block = PartialBlock::new(compact_block, Vec::new().iter()).unwrap();
+
+        compact_block_relay.wait_transactions([1; 32], partial_block.clone());
+        compact_block_relay.wait_transactions([2; 32], partial_block);
+
+        assert!(compact_block_relay.take_partial_block(&[1; 32]).is_none());
+        assert!(compact_block_relay.take_partial_block(&[2; 32]).is_some());
+        assert!(compact_block_relay.take_partial_block(&[2; 32]).is_none());
+    }
+}
@@ -0,0 +1,535 @@
+use super::{error_node::ErrorNode, sync_progress::ProgressTracker};
+
+use crate::messages::{
+    command_name::CommandName,
+    compact_filter_checkpoint_message::{CompactFilterCheckpointMessage, CHECKPOINT_INTERVAL},
+    compact_filter_headers_message::CompactFilterHeadersMessage,
+    compact_filter_message::CompactFilterMessage,
+    get_compact_filter_checkpoint_message::GetCompactFilterCheckpointMessage,
+    get_compact_filter_headers_message::GetCompactFilterHeadersMessage,
+    get_compact_filters_message::GetCompactFiltersMessage,
+    message::{self, Message},
+    message_header::MagicType,
+};
+
+use crate::notifications::notifier::Notifier;
+
+use crate::logs::logger_sender::LoggerSender;
+
+use crate::block_structure::{
+    golomb_coded_set::{self, GolombCodedSet, BASIC_FILTER_TYPE},
+    hash::HashType,
+};
+
+use std::io::{Read, Write};
+
+pub const MAX_FILTERS_PER_REQUEST: usize = 1_000;
+
+/// It represents the download of blocks with compact block filters (BIP157/158). The filters of the blocks
+/// are checked locally against the scripts of the wallet, so the peer never knows which ones are ours
+#[derive(Debug, Clone)]
+pub struct CompactFilterClient {
+    magic_numbers: MagicType,
+    sender_log: LoggerSender,
+}
+
+impl CompactFilterClient {
+    pub fn new(magic_numbers: MagicType, sender_log: LoggerSender) -> Self {
+        CompactFilterClient {
+            magic_numbers,
+            sender_log,
+        }
+    }
+
+    /// Get the filter headers of every thousand blocks until the block with the stop hash
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileSerializing`: It will appear when there is an error in the serialization
+    ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
+    ///  * `ErrorNode::WhileValidating`: It will appear when the checkpoints are not the ones requested
+    pub fn get_checkpoints<RW: Read + Write>(
+        &self,
+        peer_stream: &mut RW,
+        stop_hash: &HashType,
+    ) -> Result<Vec<HashType>, ErrorNode> {
+        let get_checkpoint_message = GetCompactFilterCheckpointMessage {
+            filter_type: BASIC_FILTER_TYPE,
+            stop_hash: *stop_hash,
+        };
+        GetCompactFilterCheckpointMessage::serialize_message(
+            peer_stream,
+            self.magic_numbers,
+            &get_checkpoint_message,
+        )?;
+
+        let header =
+            message::deserialize_until_found(peer_stream, CommandName::CompactFilterCheckpoint)?;
+        let checkpoint_message =
+            CompactFilterCheckpointMessage::deserialize_message(peer_stream, header)?;
+
+        if checkpoint_message.filter_type != BASIC_FILTER_TYPE
+            || checkpoint_message.stop_hash != *stop_hash
+        {
+            return Err(ErrorNode::WhileValidating(
+                "Filter checkpoints not requested".to_string(),
+            ));
+        }
+
+        Ok(checkpoint_message.filter_headers)
+    }
+
+    /// Get the hashes of the filters of the blocks from the start height until the block with the stop hash,
+    /// validating the chain of filter headers against the checkpoints
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileSerializing`: It will appear when there is an error in the serialization
+    ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
+    ///  * `ErrorNode::WhileValidating`: It will appear when the filter headers are not the ones requested or do not match the checkpoints
+    fn get_filter_hashes<RW: Read + Write>(
+        &self,
+        peer_stream: &mut RW,
+        batch: &[(u32, HashType)],
+        checkpoints: &[HashType],
+    ) -> Result<Vec<HashType>, ErrorNode> {
+        let (start_height, stop_hash) = match (batch.first(), batch.last()) {
+            (Some((start_height, _)), Some((_, stop_hash))) => (*start_height, *stop_hash),
+            _ => return Ok(vec![]),
+        };
+
+        let get_headers_message = GetCompactFilterHeadersMessage {
+            filter_type: BASIC_FILTER_TYPE,
+            start_height,
+            stop_hash,
+        };
+        GetCompactFilterHeadersMessage::serialize_message(
+            peer_stream,
+            self.magic_numbers,
+            &get_headers_message,
+        )?;
+
+        let header =
+            message::deserialize_until_found(peer_stream, CommandName::CompactFilterHeaders)?;
+        let headers_message =
+            CompactFilterHeadersMessage::deserialize_message(peer_stream, header)?;
+
+        if headers_message.filter_type != BASIC_FILTER_TYPE
+            || headers_message.stop_hash != stop_hash
+            || headers_message.filter_hashes.len() != batch.len()
+        {
+            return Err(ErrorNode::WhileValidating(
+                "Filter headers not requested".to_string(),
+            ));
+        }
+
+        let mut filter_header = headers_message.previous_filter_header;
+        for (filter_hash, (height, _)) in headers_message.filter_hashes.iter().zip(batch.iter()) {
+            filter_header = match golomb_coded_set::get_filter_header(filter_hash, &filter_header) {
+                Ok(filter_header) => filter_header,
+                Err(_) => {
+                    return Err(ErrorNode::WhileValidating(
+                        "Could not hash the filter header".to_string(),
+                    ))
+                }
+            };
+
+            if *height == 0 || height % CHECKPOINT_INTERVAL != 0 {
+                continue;
+            }
+
+            let checkpoint_index = (height / CHECKPOINT_INTERVAL - 1) as usize;
+            if let Some(checkpoint) = checkpoints.get(checkpoint_index) {
+                if *checkpoint != filter_header {
+                    return Err(ErrorNode::WhileValidating(format!(
+                        "Filter header at height {height} does not match the checkpoint"
+                    )));
+                }
+            }
+        }
+
+        Ok(headers_message.filter_hashes)
+    }
+
+    /// Get the filters of the blocks of the batch and returns the hashes of the blocks that match any of the scripts
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileSerializing`: It will appear when there is an error in the serialization
+    ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
+    ///  * `ErrorNode::WhileValidating`: It will appear when a filter is not the one requested or does not match its hash
+    fn get_matching_filters<RW: Read + Write>(
+        &self,
+        peer_stream: &mut RW,
+        batch: &[(u32, HashType)],
+        filter_hashes: &[HashType],
+        scripts: &[Vec<u8>],
+    ) -> Result<Vec<HashType>, ErrorNode> {
+        let (start_height, stop_hash) = match (batch.first(), batch.last()) {
+            (Some((start_height, _)), Some((_, stop_hash))) => (*start_height, *stop_hash),
+            _ => return Ok(vec![]),
+        };
+
+        let get_filters_message = GetCompactFiltersMessage {
+            filter_type: BASIC_FILTER_TYPE,
+            start_height,
+            stop_hash,
+        };
+        GetCompactFiltersMessage::serialize_message(
+            peer_stream,
+            self.magic_numbers,
+            &get_filters_message,
+        )?;
+
+        let mut matched_blocks: Vec<HashType> = Vec::new();
+        for ((_, block_hash), filter_hash) in batch.iter().zip(filter_hashes.iter()) {
+            let header = message::deserialize_until_found(peer_stream, CommandName::CompactFilter)?;
+            let filter_message = CompactFilterMessage::deserialize_message(peer_stream, header)?;
+
+            if filter_message.filter_type != BASIC_FILTER_TYPE
+                || filter_message.block_hash != *block_hash
+            {
+                return Err(ErrorNode::WhileValidating(
+                    "Filter not requested".to_string(),
+                ));
+            }
+
+            match golomb_coded_set::get_filter_hash(&filter_message.filter) {
+                Ok(received_hash) if received_hash == *filter_hash => {}
+                _ => {
+                    return Err(ErrorNode::WhileValidating(
+                        "Filter does not match its header".to_string(),
+                    ))
+                }
+            }
+
+            let is_match = match GolombCodedSet::from_filter(block_hash, &filter_message.filter)
+                .and_then(|filter| filter.match_any(scripts))
+            {
+                Ok(is_match) => is_match,
+                Err(_) => {
+                    return Err(ErrorNode::WhileValidating(
+                        "Filter is malformed".to_string(),
+                    ))
+                }
+            };
+
+            if is_match {
+                matched_blocks.push(*block_hash);
+            }
+        }
+
+        Ok(matched_blocks)
+    }
+
+    /// Get the hashes of the blocks whose filter matches any of the scripts of the wallet. The headers are
+    /// the height and the hash of each block, and must be ordered by height
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileSerializing`: It will appear when there is an error in the serialization
+    ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
+    ///  * `ErrorNode::WhileValidating`: It will appear when a filter or its header is not valid
+    pub fn get_matching_blocks<N: Notifier, RW: Read + Write>(
+        &self,
+        peer_stream: &mut RW,
+        headers: Vec<(u32, HashType)>,
+        scripts: &[Vec<u8>],
+        notifier: N,
+    ) -> Result<Vec<HashType>, ErrorNode> {
+        let stop_hash = match headers.last() {
+            Some((_, stop_hash)) => *stop_hash,
+            None => return Ok(vec![]),
+        };
+        let headers_count = headers.len() as u32;
+
+        let _ = self
+            .sender_log
+            .log_connection(format!("Checking {headers_count} compact filters"));
+
+        let checkpoints = self.get_checkpoints(peer_stream, &stop_hash)?;
+
+        let mut matched_blocks: Vec<HashType> = Vec::new();
+        let mut tracker = ProgressTracker::new(headers_count, None);
+        for batch in get_batches(&headers) {
+            tracker.notify_if_due(&notifier);
+
+            let filter_hashes = self.get_filter_hashes(peer_stream, batch, &checkpoints)?;
+            matched_blocks.extend(self.get_matching_filters(
+                peer_stream,
+                batch,
+                &filter_hashes,
+                scripts,
+            )?);
+
+            tracker.add_blocks(batch.len() as u32, 0);
+        }
+        tracker.notify(&notifier);
+
+        let _ = self.sender_log.log_connection(format!(
+            "{} blocks matched the compact filters",
+            matched_blocks.len()
+        ));
+
+        Ok(matched_blocks)
+    }
+}
+
+/// Splits the headers in ranges of consecutive heights, each one small enough for a single request
+fn get_batches(headers: &[(u32, HashType)]) -> Vec<&[(u32, HashType)]> {
+    let mut batches: Vec<&[(u32, HashType)]> = Vec::new();
+    let mut start = 0;
+
+    for index in 1..=headers.len() {
+        let is_end = index == headers.len()
+            || headers[index].0 != headers[index - 1].0 + 1
+            || index - start == MAX_FILTERS_PER_REQUEST;
+
+        if is_end {
+            batches.push(&headers[start..index]);
+            start = index;
+        }
+    }
+
+    batches
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::logs::logger;
+    use crate::notifications::notification::Notification;
+
+    struct Stream {
+        stream: Vec<u8>,
+        pointer: usize,
+    }
+
+    impl Stream {
+        pub fn new() -> Stream {
+            Stream {
+                stream: Vec::new(),
+                pointer: 0,
+            }
+        }
+    }
+
+    impl Read for Stream {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let mut i = 0;
+            while i < buf.len() && self.pointer < self.stream.len() {
+                buf[i] = self.stream[self.pointer];
+                self.pointer += 1;
+                i += 1;
+            }
+            Ok(i)
+        }
+    }
+
+    impl Write for Stream {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.stream.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[derive(Clone)]
+    struct NotificationMock {}
+
+    impl Notifier for NotificationMock {
+        fn notify(&self, _notification: Notification) {}
+    }
+
+    const WALLET_SCRIPT: [u8; 25] = [7; 25];
+
+    fn create_headers() -> Vec<(u32, HashType)> {
+        (998..1002)
+            .map(|height| (height, [height as u8; 32]))
+            .collect()
+    }
+
+    fn create_filters(headers: &[(u32, HashType)]) -> Vec<Vec<u8>> {
+        headers
+            .iter()
+            .map(|(height, block_hash)| {
+                let mut scripts: Vec<Vec<u8>> = vec![vec![1; 25], vec![2; 25]];
+                if *height == 1000 {
+                    scripts.push(WALLET_SCRIPT.to_vec());
+                }
+                GolombCodedSet::new(block_hash, &scripts)
+                    .get_filter()
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    fn serialize_responses(
+        stream: &mut Stream,
+        magic_numbers: MagicType,
+        headers: &[(u32, HashType)],
+        filters: (&[Vec<u8>], &[Vec<u8>]),
+        checkpoints: Vec<HashType>,
+    ) {
+        let (hashed_filters, sent_filters) = filters;
+        let stop_hash = headers.last().unwrap().1;
+
+        let checkpoint_message = CompactFilterCheckpointMessage {
+            filter_type: BASIC_FILTER_TYPE,
+            stop_hash,
+            filter_headers: checkpoints,
+        };
+        CompactFilterCheckpointMessage::serialize_message(
+            stream,
+            magic_numbers,
+            &checkpoint_message,
+        )
+        .unwrap();
+
+        let headers_message = CompactFilterHeadersMessage {
+            filter_type: BASIC_FILTER_TYPE,
+            stop_hash,
+            previous_filter_header: [0; 32],
+            filter_hashes: hashed_filters
+                .iter()
+                .map(|filter| golomb_coded_set::get_filter_hash(filter).unwrap())
+                .collect(),
+        };
+        CompactFilterHeadersMessage::serialize_message(stream, magic_numbers, &headers_message)
+            .unwrap();
+
+        for ((_, block_hash), filter) in headers.iter().zip(sent_filters.iter()) {
+            let filter_message = CompactFilterMessage {
+                filter_type: BASIC_FILTER_TYPE,
+                block_hash: *block_hash,
+                filter: filter.clone(),
+            };
+            CompactFilterMessage::serialize_message(stream, magic_numbers, &filter_message)
+                .unwrap();
+        }
+    }
+
+    fn get_filter_header_at_height_1000(filters: &[Vec<u8>]) -> HashType {
+        let mut filter_header: HashType = [0; 32];
+        for filter in filters.iter().take(3) {
+            let filter_hash = golomb_coded_set::get_filter_hash(filter).unwrap();
+            filter_header =
+                golomb_coded_set::get_filter_header(&filter_hash, &filter_header).unwrap();
+        }
+        filter_header
+    }
+
+    #[test]
+    fn test01_only_the_blocks_that_match_the_filter_are_returned() {
+        let mut stream = Stream::new();
+        let magic_numbers = [11, 17, 9, 7];
+
+        let headers = create_headers();
+        let filters = create_filters(&headers);
+        let checkpoints = vec![get_filter_header_at_height_1000(&filters)];
+        serialize_responses(
+            &mut stream,
+            magic_numbers,
+            &headers,
+            (&filters, &filters),
+            checkpoints,
+        );
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+        let client = CompactFilterClient::new(magic_numbers, sender);
+
+        let matched_blocks = client
+            .get_matching_blocks(
+                &mut stream,
+                headers.clone(),
+                &[WALLET_SCRIPT.to_vec()],
+                NotificationMock {},
+            )
+            .unwrap();
+
+        assert_eq!(matched_blocks, vec![headers[2].1]);
+    }
+
+    #[test]
+    fn test02_filter_headers_that_do_not_match_the_checkpoint_are_rejected() {
+        let mut stream = Stream::new();
+        let magic_numbers = [11, 17, 9, 7];
+
+        let headers = create_headers();
+        let filters = create_filters(&headers);
+        serialize_responses(
+            &mut stream,
+            magic_numbers,
+            &headers,
+            (&filters, &filters),
+            vec![[1; 32]],
+        );
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+        let client = CompactFilterClient::new(magic_numbers, sender);
+
+        assert!(matches!(
+            client.get_matching_blocks(
+                &mut stream,
+                headers,
+                &[WALLET_SCRIPT.to_vec()],
+                NotificationMock {}
+            ),
+            Err(ErrorNode::WhileValidating(_))
+        ));
+    }
+
+    #[test]
+    fn test03_filter_that_does_not_match_its_hash_is_rejected() {
+        let mut stream = Stream::new();
+        let magic_numbers = [11, 17, 9, 7];
+
+        let headers = create_headers();
+        let filters = create_filters(&headers);
+        let checkpoints = vec![get_filter_header_at_height_1000(&filters)];
+
+        let mut sent_filters = filters.clone();
+        sent_filters[1] = filters[0].clone();
+
+        serialize_responses(
+            &mut stream,
+            magic_numbers,
+            &headers,
+            (&filters, &sent_filters),
+            checkpoints,
+        );
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+        let client = CompactFilterClient::new(magic_numbers, sender);
+
+        assert!(matches!(
+            client.get_matching_blocks(
+                &mut stream,
+                headers,
+                &[WALLET_SCRIPT.to_vec()],
+                NotificationMock {}
+            ),
+            Err(ErrorNode::WhileValidating(_))
+        ));
+    }
+
+    #[test]
+    fn test04_headers_are_split_in_consecutive_batches() {
+        let mut headers: Vec<(u32, HashType)> = (0..1500).map(|height| (height, [0; 32])).collect();
+        headers.push((2000, [0; 32]));
+
+        let batches = get_batches(&headers);
+
+        assert_eq!(
+            batches
+                .iter()
+                .map(|batch| batch.len())
+                .collect::<Vec<usize>>(),
+            vec![1000, 500, 1]
+        );
+    }
+}
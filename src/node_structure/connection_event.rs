@@ -1,8 +1,11 @@
+use crate::connections::onion_address::OnionAddress;
+
 use std::net::{SocketAddr, TcpStream};
 
 #[derive(Debug)]
 pub enum ConnectionEvent {
     PotentialClient(TcpStream, SocketAddr),
     PotentialPeer(SocketAddr),
+    PotentialOnionPeer(OnionAddress),
     Stop,
 }
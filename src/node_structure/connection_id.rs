@@ -1,21 +1,31 @@
-use super::connection_type::ConnectionType;
+use super::{connection_type::ConnectionType, peer_features::PeerFeatures};
 
 use std::{cmp::PartialEq, fmt::Display, net::SocketAddr};
 
-/// The identifier of a connection given by the address and the type of connection
+/// The identifier of a connection given by the address and the type of connection, with the
+/// features negotiated in the handshake
 #[derive(Debug, Clone, Copy)]
 pub struct ConnectionId {
     pub connection_type: ConnectionType,
     pub address: SocketAddr,
+    pub features: PeerFeatures,
 }
 
 impl ConnectionId {
+    /// Creates the identifier of the connection. An IPv4-mapped address is kept as IPv4, so the
+    /// same peer is identified the same way in both kinds of sockets
     pub fn new(address: SocketAddr, connection_type: ConnectionType) -> Self {
         ConnectionId {
             connection_type,
-            address,
+            address: SocketAddr::new(address.ip().to_canonical(), address.port()),
+            features: PeerFeatures::default(),
         }
     }
+
+    /// Returns the same identifier with the features negotiated with the peer
+    pub fn with_features(self, features: PeerFeatures) -> Self {
+        ConnectionId { features, ..self }
+    }
 }
 
 impl PartialEq for ConnectionId {
@@ -29,3 +39,35 @@ impl Display for ConnectionId {
         write!(f, "{:?} - {:?}", self.connection_type, self.address)
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn test01_ipv4_mapped_addresses_identify_the_same_peer() {
+        let ipv4 = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(192, 10, 2, 255)), 18444);
+        let ipv4_mapped = SocketAddr::new(
+            IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0xffff, 0xc00a, 0x02ff)),
+            18444,
+        );
+
+        let connection = ConnectionId::new(ipv4_mapped, ConnectionType::Peer);
+
+        assert_eq!(connection.address, ipv4);
+        assert_eq!(connection, ConnectionId::new(ipv4, ConnectionType::Client));
+    }
+
+    #[test]
+    fn test02_peers_in_other_ports_are_different_connections() {
+        let ip = IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+
+        let connection = ConnectionId::new(SocketAddr::new(ip, 18333), ConnectionType::Peer);
+        let other_connection = ConnectionId::new(SocketAddr::new(ip, 18444), ConnectionType::Peer);
+
+        assert_ne!(connection, other_connection);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::serialization::error_serialization::ErrorSerialization;
+use crate::{
+    block_structure::hash::HashType, messages::reject_message::RejectMessage,
+    serialization::error_serialization::ErrorSerialization,
+};
 
 /// It represents all posible errors that can occur while making the protocols of a node
 #[derive(Debug, PartialEq)]
@@ -35,6 +38,15 @@ pub enum ErrorNode {
 
     /// It will appear when the headers count is bigger than the maximum headers count
     RequestedDataTooBig,
+
+    /// It will appear when the peer cannot serve the data with the given hashes, so it must be requested to another peer
+    DataNotFound(Vec<HashType>),
+
+    /// It will appear when the peer rejects a message that we sent
+    MessageRejected(RejectMessage),
+
+    /// It will appear when the peer breaks the rules of the protocol, so it must be disconnected
+    PeerMisbehaving(String),
 }
 
 impl From<ErrorSerialization> for ErrorNode {
@@ -52,6 +64,19 @@ impl From<ErrorSerialization> for ErrorNode {
             }
             ErrorSerialization::ConnectionAborted => ErrorNode::ConnectionAborted,
             ErrorSerialization::InformationNotReady => ErrorNode::InformationNotReady,
+            ErrorSerialization::DataNotFound(hashes) => ErrorNode::DataNotFound(hashes),
+            ErrorSerialization::MessageRejected(reject) => ErrorNode::MessageRejected(reject),
+            ErrorSerialization::PayloadTooLarge(command_name, payload_size) => {
+                ErrorNode::PeerMisbehaving(format!(
+                    "Payload of {payload_size} bytes in {command_name:?} is bigger than {}",
+                    command_name.max_payload_size()
+                ))
+            }
+            ErrorSerialization::FormatVersionNotSupported(path, version, supported) => {
+                ErrorNode::WhileDeserializing(format!(
+                    "The file {path} has the version {version}, newer than {supported}"
+                ))
+            }
         }
     }
 }
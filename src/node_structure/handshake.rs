@@ -1,10 +1,14 @@
-use super::handshake_data::HandshakeData;
+use super::{
+    compact_block_relay::COMPACT_BLOCK_VERSION, handshake_data::HandshakeData,
+    peer_features::PeerFeatures,
+};
 
 use crate::{
     messages::{
         bitfield_services::BitfieldServices, message::Message, message_header::MessageHeader,
-        send_headers_message::SendHeadersMessage, verack_message::VerackMessage,
-        version_message::VersionMessage,
+        send_cmpct_message::SendCmpctMessage, send_headers_message::SendHeadersMessage,
+        verack_message::VerackMessage, version_message::VersionMessage,
+        wtxid_relay_message::WtxidRelayMessage,
     },
     serialization::error_serialization::ErrorSerialization,
 };
@@ -80,9 +84,11 @@ impl Handshake {
             relay: self.data.relay,
         };
 
-        if let Err(error) =
-            VersionMessage::serialize_message(peer_stream, self.data.magic_number, &version_message)
-        {
+        if let Err(error) = VersionMessage::serialize_message(
+            peer_stream,
+            self.data.network.magic_numbers(),
+            &version_message,
+        ) {
             let _ = self.sender_log.log_connection(format!(
                 "Error while sending version message to peer {}: {:?}",
                 potential_peer, error
@@ -97,7 +103,7 @@ impl Handshake {
         }
     }
 
-    /// Function that receives a version message from the given potential peer.
+    /// Function that receives a version message from the given potential peer, and returns it.
     ///
     /// ### Error
     ///  * `ErrorSerialization::ErrorSerialization`: It will appear when there is an error in the serialization
@@ -108,15 +114,26 @@ impl Handshake {
         peer_stream: &mut RW,
         header: MessageHeader,
         potential_peer: &SocketAddr,
-    ) -> Result<(), ErrorSerialization> {
-        if let Err(error) = VersionMessage::deserialize_message(peer_stream, header) {
-            let _ = self.sender_log.log_connection(format!(
-                "Error while receiving version message from peer {}: {:?}",
-                potential_peer, error
-            ));
-            return Err(error);
+    ) -> Result<VersionMessage, ErrorSerialization> {
+        match VersionMessage::deserialize_message(peer_stream, header) {
+            Ok(version_message) => Ok(version_message),
+            Err(error) => {
+                let _ = self.sender_log.log_connection(format!(
+                    "Error while receiving version message from peer {}: {:?}",
+                    potential_peer, error
+                ));
+                Err(error)
+            }
         }
-        Ok(())
+    }
+
+    /// Negotiates the features to use with the peer given its version message
+    pub fn negotiate_features(&self, version_message: &VersionMessage) -> PeerFeatures {
+        PeerFeatures::negotiate(
+            self.protocol_version,
+            version_message.version,
+            &version_message.services,
+        )
     }
 
     /// Function that sends a verack message to the given potential peer.
@@ -128,9 +145,11 @@ impl Handshake {
         peer_stream: &mut RW,
         potential_peer: &SocketAddr,
     ) -> Result<(), ErrorSerialization> {
-        if let Err(error) =
-            VerackMessage::serialize_message(peer_stream, self.data.magic_number, &VerackMessage)
-        {
+        if let Err(error) = VerackMessage::serialize_message(
+            peer_stream,
+            self.data.network.magic_numbers(),
+            &VerackMessage,
+        ) {
             let _ = self.sender_log.log_connection(format!(
                 "Error while sending verack message to peer {}: {:?}",
                 potential_peer, error
@@ -167,6 +186,28 @@ impl Handshake {
         Ok(())
     }
 
+    /// Function that receives a wtxidrelay message from the given potential peer.
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when there is an error in the deserialization
+    ///  * `ErrorSerialization::ErrorWhileReading`: It will appear when there is an error in the reading from a stream
+    pub fn receive_wtxidrelay_message<RW: Read + Write>(
+        &self,
+        peer_stream: &mut RW,
+        header: MessageHeader,
+        potential_peer: &SocketAddr,
+    ) -> Result<(), ErrorSerialization> {
+        if let Err(error) = WtxidRelayMessage::deserialize_message(peer_stream, header) {
+            let _ = self.sender_log.log_connection(format!(
+                "Error while receiving wtxidrelay message from peer {}: {:?}",
+                potential_peer, error
+            ));
+            return Err(error);
+        }
+
+        Ok(())
+    }
+
     /// Sends a send header message to the peer.
     ///
     /// ### Error
@@ -177,8 +218,27 @@ impl Handshake {
     ) -> Result<(), ErrorSerialization> {
         SendHeadersMessage::serialize_message(
             peer_stream,
-            self.data.magic_number,
+            self.data.network.magic_numbers(),
             &SendHeadersMessage,
         )
     }
+
+    /// Sends a send compact message to the peer, letting it know that we can rebuild blocks from compact blocks.
+    /// The blocks are still announced with headers or inventory messages
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileSerializing`: It will appear when there is an error in the serialization
+    pub fn send_sendcmpct_message<RW: Read + Write>(
+        &self,
+        peer_stream: &mut RW,
+    ) -> Result<(), ErrorSerialization> {
+        SendCmpctMessage::serialize_message(
+            peer_stream,
+            self.data.network.magic_numbers(),
+            &SendCmpctMessage {
+                announce: false,
+                version: COMPACT_BLOCK_VERSION,
+            },
+        )
+    }
 }
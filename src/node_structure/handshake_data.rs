@@ -1,4 +1,4 @@
-use crate::messages::message_header::MagicType;
+use crate::connections::network::Network;
 
 /// It represents the data from ourself to give in the handshake to the peers
 #[derive(Debug, Clone)]
@@ -6,5 +6,5 @@ pub struct HandshakeData {
     pub nonce: u64,
     pub user_agent: String,
     pub relay: bool,
-    pub magic_number: MagicType,
+    pub network: Network,
 }
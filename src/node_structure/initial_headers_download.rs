@@ -1,7 +1,7 @@
 use super::error_node::ErrorNode;
 
 use crate::{
-    block_structure::{block_chain::BlockChain, block_header::BlockHeader, hash::HashType},
+    block_structure::{block_chain::BlockChain, hash::HashType},
     connections::p2p_protocol::ProtocolVersionP2P,
     logs::logger_sender::LoggerSender,
 };
@@ -17,7 +17,6 @@ use crate::messages::{
 use std::io::{Read, Write};
 
 const NO_STOP_HASH: HashType = [0; 32];
-const GO_BACK: usize = 50;
 
 /// It represents the download of the headers from a peer
 #[derive(Debug, Clone)]
@@ -40,26 +39,15 @@ impl InitialHeaderDownload {
         }
     }
 
-    /// It sends a get headers message to the peer given the latest headers from the blockchain
+    /// It sends a get headers message to the peer with the given locator
     ///
     /// ### Error
-    ///  * `ErrorNode::InSerialization`: It will appear when the serialization of the message fails or the SHA(SHA(header)) fails
+    ///  * `ErrorNode::InSerialization`: It will appear when the serialization of the message fails
     fn send_get_headers_message<RW: Read + Write>(
         &self,
         peer_stream: &mut RW,
-        block_chain: &BlockChain,
+        header_locator_hashes: Vec<HashType>,
     ) -> Result<(), ErrorNode> {
-        let _ = self
-            .sender_log
-            .log_connection("Serializing last headers from blockchain".to_string());
-
-        let mut header_locator_hashes: Vec<HashType> = Vec::new();
-
-        for block in block_chain.headers_to_update(GO_BACK).iter() {
-            let last_header: &BlockHeader = &block.header;
-            header_locator_hashes.push(last_header.get_hash256d()?);
-        }
-
         let get_headers_message =
             GetHeadersMessage::new(self.protocol_version, header_locator_hashes, NO_STOP_HASH);
 
@@ -72,22 +60,77 @@ impl InitialHeaderDownload {
         Ok(())
     }
 
-    /// Updates the block chain with the headers received from the peer
+    /// Updates the block chain with the headers received from the peer. The headers cannot be
+    /// too far ahead of the given network adjusted time
     ///
     /// ### Error
     ///  * `ErrorNode::InSerialization`: It will appear when the serialization of the message fails or the SHA(SHA(header)) fails
     ///  * `ErrorNode::NodeNotResponding`: It will appear when no message is received from the node
-    ///  * `ErrorNode::WhileValidating`: It will appear when a given header does not pass the proof of work to be added to the blockchain
+    ///  * `ErrorNode::WhileValidating`: It will appear when a given header does not pass the proof of work or its time is too far in the future
     pub fn get_headers<RW: Read + Write>(
         &self,
         peer_stream: &mut RW,
         block_chain: &mut BlockChain,
+        adjusted_time: i64,
+    ) -> Result<u32, ErrorNode> {
+        let header_locator_hashes = block_chain.build_locator();
+        self.receive_headers(
+            peer_stream,
+            block_chain,
+            header_locator_hashes,
+            adjusted_time,
+        )
+    }
+
+    /// Updates the block chain with the headers received from the peer, locating them from the given
+    /// tip instead of the highest one. If the tip is no longer at the end of a fork, the highest tip is used
+    ///
+    /// ### Error
+    ///  * `ErrorNode::InSerialization`: It will appear when the serialization of the message fails or the SHA(SHA(header)) fails
+    ///  * `ErrorNode::NodeNotResponding`: It will appear when no message is received from the node
+    ///  * `ErrorNode::WhileValidating`: It will appear when a given header does not pass the proof of work or its time is too far in the future
+    pub fn get_headers_from_tip<RW: Read + Write>(
+        &self,
+        peer_stream: &mut RW,
+        block_chain: &mut BlockChain,
+        tip: &HashType,
+        adjusted_time: i64,
+    ) -> Result<u32, ErrorNode> {
+        let header_locator_hashes = match block_chain.get_locator(tip) {
+            locator if locator.is_empty() => block_chain.build_locator(),
+            locator => {
+                let _ = self
+                    .sender_log
+                    .log_connection("Locating the headers from the last known tip".to_string());
+                locator
+            }
+        };
+        self.receive_headers(
+            peer_stream,
+            block_chain,
+            header_locator_hashes,
+            adjusted_time,
+        )
+    }
+
+    /// Sends the get headers message with the locator and appends the headers received to the block chain
+    ///
+    /// ### Error
+    ///  * `ErrorNode::InSerialization`: It will appear when the serialization of the message fails
+    ///  * `ErrorNode::NodeNotResponding`: It will appear when no message is received from the node
+    ///  * `ErrorNode::WhileValidating`: It will appear when a given header does not pass the proof of work or its time is too far in the future
+    fn receive_headers<RW: Read + Write>(
+        &self,
+        peer_stream: &mut RW,
+        block_chain: &mut BlockChain,
+        header_locator_hashes: Vec<HashType>,
+        adjusted_time: i64,
     ) -> Result<u32, ErrorNode> {
         let _ = self
             .sender_log
             .log_connection("Sending get headers message".to_string());
 
-        self.send_get_headers_message(peer_stream, block_chain)?;
+        self.send_get_headers_message(peer_stream, header_locator_hashes)?;
 
         let header_headers_message =
             match message::deserialize_until_found(peer_stream, CommandName::Headers) {
@@ -115,6 +158,17 @@ impl InitialHeaderDownload {
                 }
             };
 
+        if let Some(header) = received_headers_message
+            .headers
+            .iter()
+            .find(|header| !header.is_time_valid(adjusted_time))
+        {
+            return Err(ErrorNode::WhileValidating(format!(
+                "Header with time {} too far ahead of the network time {}",
+                header.time, adjusted_time
+            )));
+        }
+
         match block_chain.append_headers(received_headers_message.headers) {
             Ok(count) => Ok(count),
             Err(error) => Err(ErrorNode::WhileValidating(format!(
@@ -131,7 +185,10 @@ mod tests {
     use super::*;
 
     use crate::{
-        block_structure::{block::Block, block_version::BlockVersion, compact256::Compact256},
+        block_structure::{
+            block::Block, block_header::BlockHeader, block_version::BlockVersion,
+            compact256::Compact256,
+        },
         logs::logger,
         messages::compact_size::CompactSize,
         serialization::error_serialization::ErrorSerialization,
@@ -235,7 +292,7 @@ mod tests {
             InitialHeaderDownload::new(ProtocolVersionP2P::V70016, magic_numbers, sender);
 
         initial_headers_download
-            .get_headers(&mut stream, &mut blockchain)
+            .get_headers(&mut stream, &mut blockchain, 0)
             .unwrap();
 
         assert_eq!(expected_blockchain, blockchain);
@@ -248,11 +305,109 @@ mod tests {
         let get_headers_message =
             GetHeadersMessage::deserialize_message(&mut stream, header).unwrap();
 
-        assert_eq!(get_headers_message.header_locator_hashes.len(), 2);
+        assert_eq!(get_headers_message.header_locator_hashes.len(), 1);
         assert_eq!(get_headers_message.version, ProtocolVersionP2P::V70016);
         assert_eq!(
             get_headers_message.header_locator_hashes,
-            vec![hash_of_first_block_header, hash_of_first_block_header]
+            vec![hash_of_first_block_header]
+        );
+    }
+
+    #[test]
+    fn test02_header_too_far_in_the_future_is_rejected() {
+        let mut stream = Stream::new();
+        let magic_numbers = [11, 17, 9, 7];
+
+        let block = Block::new(BlockHeader::new(
+            BlockVersion::version(1),
+            [0; 32],
+            [0; 32],
+            0,
+            Compact256::from(10),
+            0,
+            CompactSize::new(0),
+        ));
+
+        let header_to_append = BlockHeader::new(
+            BlockVersion::version(1),
+            block.header.get_hash256d().unwrap(),
+            [3; 32],
+            u32::MAX,
+            Compact256::from(u32::MAX),
+            21,
+            CompactSize::new(0),
+        );
+
+        let mut blockchain = BlockChain::new(block).unwrap();
+        let expected_blockchain = blockchain.clone();
+
+        serialize_headers_message(&mut stream, magic_numbers, vec![header_to_append]).unwrap();
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+
+        let initial_headers_download =
+            InitialHeaderDownload::new(ProtocolVersionP2P::V70016, magic_numbers, sender);
+
+        assert!(matches!(
+            initial_headers_download.get_headers(&mut stream, &mut blockchain, 0),
+            Err(ErrorNode::WhileValidating(_))
+        ));
+        assert_eq!(expected_blockchain, blockchain);
+    }
+
+    #[test]
+    fn test03_headers_are_located_from_the_given_tip() {
+        let mut stream = Stream::new();
+        let magic_numbers = [11, 17, 9, 7];
+
+        let block = Block::new(BlockHeader::new(
+            BlockVersion::version(1),
+            [0; 32],
+            [0; 32],
+            0,
+            Compact256::from(10),
+            0,
+            CompactSize::new(0),
+        ));
+
+        let hash_of_first_block_header = block.header.get_hash256d().unwrap();
+
+        let mut blockchain = BlockChain::new(block).unwrap();
+
+        let header_to_append = BlockHeader::new(
+            BlockVersion::version(1),
+            hash_of_first_block_header,
+            [3; 32],
+            5,
+            Compact256::from(u32::MAX),
+            21,
+            CompactSize::new(0),
+        );
+
+        serialize_headers_message(&mut stream, magic_numbers, vec![header_to_append]).unwrap();
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+
+        let initial_headers_download =
+            InitialHeaderDownload::new(ProtocolVersionP2P::V70016, magic_numbers, sender);
+
+        assert_eq!(
+            initial_headers_download
+                .get_headers_from_tip(&mut stream, &mut blockchain, &hash_of_first_block_header, 0)
+                .unwrap(),
+            1
+        );
+
+        let header =
+            message::deserialize_until_found(&mut stream, CommandName::GetHeaders).unwrap();
+        let get_headers_message =
+            GetHeadersMessage::deserialize_message(&mut stream, header).unwrap();
+
+        assert_eq!(
+            get_headers_message.header_locator_hashes,
+            vec![hash_of_first_block_header]
         );
     }
 }
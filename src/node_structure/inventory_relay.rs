@@ -0,0 +1,259 @@
+use crate::{
+    block_structure::{block::Block, hash::HashType, transaction::Transaction},
+    connections::type_identifier::TypeIdentifier,
+    messages::inventory_vector::InventoryVector,
+};
+
+use std::collections::{HashMap, VecDeque};
+
+pub const MAX_ANNOUNCED_INVENTORY: usize = 1000;
+
+/// It represents the transactions and blocks announced to a peer with an inventory message,
+/// kept until the peer requests them with a get data message
+#[derive(Debug, Clone)]
+pub struct InventoryRelay {
+    transactions: HashMap<HashType, Transaction>,
+    blocks: HashMap<HashType, Block>,
+    announced: VecDeque<HashType>,
+    capacity: usize,
+}
+
+impl InventoryRelay {
+    pub fn new(capacity: usize) -> Self {
+        InventoryRelay {
+            transactions: HashMap::new(),
+            blocks: HashMap::new(),
+            announced: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Keeps the transaction to be requested and returns the inventory vector to announce it.
+    /// If the transaction was already announced there is nothing new to announce
+    pub fn announce_transaction(&mut self, transaction: Transaction) -> Option<InventoryVector> {
+        let transaction_id = transaction.get_tx_id().ok()?;
+
+        if self.transactions.contains_key(&transaction_id) {
+            return None;
+        }
+
+        self.transactions.insert(transaction_id, transaction);
+        self.remember(transaction_id);
+
+        Some(InventoryVector::new(
+            TypeIdentifier::TransactionId,
+            transaction_id,
+        ))
+    }
+
+    /// Keeps the block to be requested and returns the inventory vector to announce it.
+    /// If the block was already announced there is nothing new to announce
+    pub fn announce_block(&mut self, block: Block) -> Option<InventoryVector> {
+        let block_hash = block.header.get_hash256d().ok()?;
+
+        if self.blocks.contains_key(&block_hash) {
+            return None;
+        }
+
+        self.blocks.insert(block_hash, block);
+        self.remember(block_hash);
+
+        Some(InventoryVector::new(TypeIdentifier::Block, block_hash))
+    }
+
+    /// Keeps a transaction received from the peer, so it's not announced back to it
+    /// and it can be used to rebuild compact blocks
+    pub fn receive_transaction(&mut self, transaction: Transaction) {
+        let transaction_id = match transaction.get_tx_id() {
+            Ok(transaction_id) => transaction_id,
+            Err(_) => return,
+        };
+
+        if self
+            .transactions
+            .insert(transaction_id, transaction)
+            .is_none()
+        {
+            self.remember(transaction_id);
+        }
+    }
+
+    /// Returns the transactions known by the peer
+    pub fn get_transactions(&self) -> impl Iterator<Item = &Transaction> {
+        self.transactions.values()
+    }
+
+    /// Returns the transaction announced with the given id
+    pub fn get_transaction(&self, transaction_id: &HashType) -> Option<&Transaction> {
+        self.transactions.get(transaction_id)
+    }
+
+    /// Returns the transaction announced with the given witness id
+    pub fn get_transaction_with_wtx_id(&self, wtx_id: &HashType) -> Option<&Transaction> {
+        self.transactions
+            .values()
+            .find(|transaction| match transaction.get_wtx_id() {
+                Ok(transaction_wtx_id) => transaction_wtx_id == *wtx_id,
+                Err(_) => false,
+            })
+    }
+
+    /// Returns the block announced with the given hash
+    pub fn get_block(&self, block_hash: &HashType) -> Option<&Block> {
+        self.blocks.get(block_hash)
+    }
+
+    /// Remembers the announced hash, forgetting the oldest announcement when the capacity is exceeded
+    fn remember(&mut self, hash: HashType) {
+        self.announced.push_back(hash);
+
+        while self.announced.len() > self.capacity {
+            if let Some(oldest) = self.announced.pop_front() {
+                self.transactions.remove(&oldest);
+                self.blocks.remove(&oldest);
+            }
+        }
+    }
+}
+
+impl Default for InventoryRelay {
+    fn default() -> Self {
+        InventoryRelay::new(MAX_ANNOUNCED_INVENTORY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        block_structure::{
+            block_header::BlockHeader, block_version::BlockVersion, compact256::Compact256,
+            outpoint::Outpoint, script::ScriptBuf, transaction_input::TransactionInput,
+            transaction_output::TransactionOutput,
+        },
+        messages::compact_size::CompactSize,
+    };
+
+    fn create_transaction(time: u32) -> Transaction {
+        Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(
+                Outpoint::new([1; 32], 23),
+                vec![1, 2, 3],
+                24,
+            )],
+            tx_out: vec![TransactionOutput {
+                value: 10,
+                pk_script: ScriptBuf::from(vec![4, 5, 6]),
+            }],
+            lock_time: time,
+        }
+    }
+
+    fn create_block(nonce: u32) -> Block {
+        Block::new(BlockHeader::new(
+            BlockVersion::version(1),
+            [0; 32],
+            [0; 32],
+            0,
+            Compact256::from(u32::MAX),
+            nonce,
+            CompactSize::new(0),
+        ))
+    }
+
+    #[test]
+    fn test01_correct_announcement_of_transactions_and_blocks() {
+        let mut inventory_relay = InventoryRelay::default();
+
+        let transaction = create_transaction(0);
+        let transaction_id = transaction.get_tx_id().unwrap();
+        let block = create_block(0);
+        let block_hash = block.header.get_hash256d().unwrap();
+
+        assert_eq!(
+            inventory_relay.announce_transaction(transaction.clone()),
+            Some(InventoryVector::new(
+                TypeIdentifier::TransactionId,
+                transaction_id
+            ))
+        );
+        assert_eq!(
+            inventory_relay.announce_block(block.clone()),
+            Some(InventoryVector::new(TypeIdentifier::Block, block_hash))
+        );
+
+        assert_eq!(
+            inventory_relay.get_transaction(&transaction_id),
+            Some(&transaction)
+        );
+        assert_eq!(inventory_relay.get_block(&block_hash), Some(&block));
+        assert_eq!(inventory_relay.get_transaction(&block_hash), None);
+    }
+
+    #[test]
+    fn test02_does_not_announce_twice() {
+        let mut inventory_relay = InventoryRelay::default();
+
+        assert!(inventory_relay
+            .announce_transaction(create_transaction(0))
+            .is_some());
+        assert!(inventory_relay
+            .announce_transaction(create_transaction(0))
+            .is_none());
+        assert!(inventory_relay.announce_block(create_block(0)).is_some());
+        assert!(inventory_relay.announce_block(create_block(0)).is_none());
+    }
+
+    #[test]
+    fn test03_forgets_oldest_announcement() {
+        let mut inventory_relay = InventoryRelay::new(2);
+
+        let transactions: Vec<Transaction> = (0..3).map(create_transaction).collect();
+        for transaction in transactions.iter() {
+            inventory_relay.announce_transaction(transaction.clone());
+        }
+
+        let first_id = transactions[0].get_tx_id().unwrap();
+        let last_id = transactions[2].get_tx_id().unwrap();
+
+        assert_eq!(inventory_relay.get_transaction(&first_id), None);
+        assert_eq!(
+            inventory_relay.get_transaction(&last_id),
+            Some(&transactions[2])
+        );
+    }
+
+    #[test]
+    fn test04_received_transaction_is_not_announced_back() {
+        let mut inventory_relay = InventoryRelay::default();
+
+        inventory_relay.receive_transaction(create_transaction(0));
+
+        assert!(inventory_relay
+            .announce_transaction(create_transaction(0))
+            .is_none());
+        assert_eq!(
+            inventory_relay.get_transactions().collect::<Vec<_>>(),
+            vec![&create_transaction(0)]
+        );
+    }
+
+    #[test]
+    fn test05_announced_transaction_is_found_by_its_witness_id() {
+        let mut inventory_relay = InventoryRelay::default();
+        let transaction = create_transaction(0);
+        let wtx_id = transaction.get_wtx_id().unwrap();
+
+        inventory_relay.announce_transaction(transaction.clone());
+
+        assert_eq!(
+            inventory_relay.get_transaction_with_wtx_id(&wtx_id),
+            Some(&transaction)
+        );
+        assert!(inventory_relay
+            .get_transaction_with_wtx_id(&[0; 32])
+            .is_none());
+    }
+}
@@ -0,0 +1,189 @@
+use super::{
+    message_to_peer::MessageToPeer,
+    peer_queue::{PeerQueue, QueuePush},
+};
+
+use crate::messages::{ping_message::PingMessage, pong_message::PongMessage};
+
+use std::{
+    thread::{self, JoinHandle},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+pub const PING_INTERVAL: Duration = Duration::from_secs(120);
+pub const PING_TIMEOUT: Duration = Duration::from_secs(300);
+pub const KEEP_ALIVE_TICK: Duration = Duration::from_secs(10);
+
+/// It represents the keepalive of a connection, knowing when to ping the peer and the ping that is waiting for a pong
+#[derive(Debug, Clone)]
+pub struct KeepAlive {
+    ping_interval: Duration,
+    timeout: Duration,
+    last_ping: Option<Instant>,
+    pending_ping: Option<(u64, Instant)>,
+    nonce_counter: u64,
+}
+
+impl KeepAlive {
+    pub fn new(ping_interval: Duration, timeout: Duration) -> Self {
+        KeepAlive {
+            ping_interval,
+            timeout,
+            last_ping: None,
+            pending_ping: None,
+            nonce_counter: 0,
+        }
+    }
+
+    /// Returns true if there is no ping waiting for an answer and the interval since the last ping has passed
+    pub fn should_ping(&self, now: Instant) -> bool {
+        if self.pending_ping.is_some() {
+            return false;
+        }
+
+        match self.last_ping {
+            Some(last_ping) => now.saturating_duration_since(last_ping) >= self.ping_interval,
+            None => true,
+        }
+    }
+
+    /// Creates a ping message with a new nonce and waits for its pong
+    pub fn ping(&mut self, now: Instant) -> PingMessage {
+        let nonce = self.generate_nonce();
+
+        self.last_ping = Some(now);
+        self.pending_ping = Some((nonce, now));
+
+        PingMessage { nonce }
+    }
+
+    /// Receives a pong and returns the latency of the connection if it answers the pending ping
+    pub fn receive_pong(&mut self, pong: &PongMessage, now: Instant) -> Option<Duration> {
+        match self.pending_ping {
+            Some((nonce, sent_at)) if nonce == pong.nonce => {
+                self.pending_ping = None;
+                Some(now.saturating_duration_since(sent_at))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns true if the pending ping was not answered within the timeout
+    pub fn is_timed_out(&self, now: Instant) -> bool {
+        match self.pending_ping {
+            Some((_, sent_at)) => now.saturating_duration_since(sent_at) > self.timeout,
+            None => false,
+        }
+    }
+
+    /// Generates a nonce that is different for every ping of the connection
+    fn generate_nonce(&mut self) -> u64 {
+        self.nonce_counter = self.nonce_counter.wrapping_add(1);
+
+        let time = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(time) => time.as_nanos() as u64,
+            Err(_) => 0,
+        };
+
+        time ^ self.nonce_counter.rotate_left(32)
+    }
+}
+
+impl Default for KeepAlive {
+    fn default() -> Self {
+        KeepAlive::new(PING_INTERVAL, PING_TIMEOUT)
+    }
+}
+
+/// Creates a thread that periodically tells the peer manager to check its keepalive. The tick is
+/// skipped when the queue of the peer is full, and the thread ends when the peer manager is no
+/// longer listening
+pub fn spawn_keep_alive_scheduler(mut queue: PeerQueue, tick: Duration) -> JoinHandle<()> {
+    thread::spawn(move || loop {
+        thread::sleep(tick);
+
+        if queue.push(MessageToPeer::KeepAlive, Instant::now()) == QueuePush::Disconnected {
+            break;
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::node_structure::peer_queue::PEER_QUEUE_FULL_TIMEOUT;
+
+    #[test]
+    fn test01_correct_ping_schedule() {
+        let now = Instant::now();
+        let mut keep_alive = KeepAlive::new(Duration::from_secs(10), Duration::from_secs(20));
+
+        assert!(keep_alive.should_ping(now));
+
+        let ping = keep_alive.ping(now);
+
+        assert!(!keep_alive.should_ping(now + Duration::from_secs(15)));
+
+        keep_alive.receive_pong(
+            &PongMessage { nonce: ping.nonce },
+            now + Duration::from_secs(1),
+        );
+
+        assert!(!keep_alive.should_ping(now + Duration::from_secs(5)));
+        assert!(keep_alive.should_ping(now + Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn test02_correct_latency_with_nonce_matching() {
+        let now = Instant::now();
+        let mut keep_alive = KeepAlive::default();
+
+        let ping = keep_alive.ping(now);
+        let latency = Duration::from_millis(250);
+
+        assert_eq!(
+            keep_alive.receive_pong(
+                &PongMessage {
+                    nonce: ping.nonce.wrapping_add(1)
+                },
+                now + latency
+            ),
+            None
+        );
+        assert_eq!(
+            keep_alive.receive_pong(&PongMessage { nonce: ping.nonce }, now + latency),
+            Some(latency)
+        );
+        assert_eq!(
+            keep_alive.receive_pong(&PongMessage { nonce: ping.nonce }, now + latency),
+            None
+        );
+    }
+
+    #[test]
+    fn test03_ping_without_answer_times_out() {
+        let now = Instant::now();
+        let mut keep_alive = KeepAlive::new(Duration::from_secs(10), Duration::from_secs(20));
+
+        assert!(!keep_alive.is_timed_out(now + Duration::from_secs(30)));
+
+        let first_nonce = keep_alive.ping(now).nonce;
+
+        assert!(!keep_alive.is_timed_out(now + Duration::from_secs(20)));
+        assert!(keep_alive.is_timed_out(now + Duration::from_secs(21)));
+        assert_ne!(first_nonce, keep_alive.ping(now).nonce);
+    }
+
+    #[test]
+    fn test04_scheduler_sends_keep_alive() {
+        let (queue, receiver) = PeerQueue::new(1, PEER_QUEUE_FULL_TIMEOUT);
+
+        let handle = spawn_keep_alive_scheduler(queue, Duration::from_millis(1));
+
+        assert!(matches!(receiver.recv(), Ok(MessageToPeer::KeepAlive)));
+
+        drop(receiver);
+        handle.join().unwrap();
+    }
+}
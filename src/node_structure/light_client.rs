@@ -0,0 +1,384 @@
+use super::{error_node::ErrorNode, sync_progress::ProgressTracker};
+
+use crate::messages::{
+    command_name::CommandName,
+    filter_load_message::FilterLoadMessage,
+    get_data_message::GetDataMessage,
+    inventory_vector::InventoryVector,
+    merkle_block_message::MerkleBlockMessage,
+    message::{self, Message},
+    message_header::MagicType,
+    tx_message::TxMessage,
+};
+
+use crate::notifications::notifier::Notifier;
+
+use crate::logs::logger_sender::LoggerSender;
+
+use crate::block_structure::{
+    block::Block, bloom_filter::BloomFilter, hash::HashType, transaction::Transaction,
+};
+
+use crate::connections::type_identifier::TypeIdentifier;
+
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const MAX_HEADERS_COUNT: usize = 50_000;
+pub const FALSE_POSITIVE_RATE: f64 = 0.0001;
+
+/// It represents the download of blocks as a light client (BIP37). The peer only sends the merkle blocks
+/// and the transactions that match the bloom filter of the wallet
+#[derive(Debug, Clone)]
+pub struct LightClient {
+    magic_numbers: MagicType,
+    sender_log: LoggerSender,
+}
+
+impl LightClient {
+    pub fn new(magic_numbers: MagicType, sender_log: LoggerSender) -> Self {
+        LightClient {
+            magic_numbers,
+            sender_log,
+        }
+    }
+
+    /// It sends to the peer the bloom filter with the given elements, as the public key hashes of the wallet
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileSerializing`: It will appear when there is an error in the serialization
+    pub fn load_filter<RW: Read + Write>(
+        &self,
+        peer_stream: &mut RW,
+        elements: &[Vec<u8>],
+    ) -> Result<(), ErrorNode> {
+        let _ = self.sender_log.log_connection(format!(
+            "Loading bloom filter with {} elements",
+            elements.len()
+        ));
+
+        let tweak = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(time) => time.subsec_nanos(),
+            Err(_) => 0,
+        };
+
+        let filter_load_message = FilterLoadMessage {
+            bloom_filter: BloomFilter::new(elements, FALSE_POSITIVE_RATE, tweak),
+        };
+
+        FilterLoadMessage::serialize_message(
+            peer_stream,
+            self.magic_numbers,
+            &filter_load_message,
+        )?;
+
+        Ok(())
+    }
+
+    /// It sends a get data message to the peer asking for the merkle blocks of the hashed headers
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileSerializing`: It will appear when there is an error in the serialization
+    fn send_get_data_message<RW: Read + Write>(
+        &self,
+        peer_stream: &mut RW,
+        hashed_headers: &[HashType],
+    ) -> Result<(), ErrorNode> {
+        let _ = self
+            .sender_log
+            .log_connection("Getting filtered data".to_string());
+
+        let get_data_message = GetDataMessage::new(
+            hashed_headers
+                .iter()
+                .map(|hash| InventoryVector::new(TypeIdentifier::FilteredBlock, *hash))
+                .collect(),
+        );
+
+        GetDataMessage::serialize_message(peer_stream, self.magic_numbers, &get_data_message)?;
+
+        Ok(())
+    }
+
+    /// It receives a merkle block and its matched transactions, and returns the block with only those transactions,
+    /// along with the amount of bytes received
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileSerializing`: It will appear when there is an error in the serialization
+    ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
+    ///  * `ErrorNode::WhileValidating`: It will appear when the merkle block is not the one requested or its proof is not valid
+    fn receive_merkle_block<RW: Read + Write>(
+        &self,
+        peer_stream: &mut RW,
+        hashed_header: &HashType,
+    ) -> Result<(Block, u64), ErrorNode> {
+        let header = message::deserialize_until_found(peer_stream, CommandName::MerkleBlock)?;
+        let mut bytes = header.payload_size as u64;
+        let merkle_block = MerkleBlockMessage::deserialize_message(peer_stream, header)?;
+
+        if merkle_block.header.get_hash256d()? != *hashed_header {
+            return Err(ErrorNode::WhileValidating(
+                "Merkle block not requested".to_string(),
+            ));
+        }
+
+        let matched_ids = match merkle_block
+            .merkle_proof
+            .get_matched_transactions(&merkle_block.header.merkle_root_hash)
+        {
+            Ok(matched_ids) => matched_ids,
+            Err(_) => {
+                return Err(ErrorNode::WhileValidating(
+                    "Failed merkle proof".to_string(),
+                ))
+            }
+        };
+
+        let mut matched_transactions: HashMap<HashType, Option<Transaction>> = matched_ids
+            .iter()
+            .map(|transaction_id| (*transaction_id, None))
+            .collect();
+        let mut missing = matched_transactions.len();
+
+        while missing > 0 {
+            let header = message::deserialize_until_found(peer_stream, CommandName::Tx)?;
+            bytes += header.payload_size as u64;
+            let transaction = TxMessage::deserialize_message(peer_stream, header)?.transaction;
+
+            let transaction_id = match transaction.get_tx_id() {
+                Ok(transaction_id) => transaction_id,
+                Err(_) => continue,
+            };
+
+            if let Some(position @ None) = matched_transactions.get_mut(&transaction_id) {
+                *position = Some(transaction);
+                missing -= 1;
+            }
+        }
+
+        let mut block = Block::new(merkle_block.header);
+        for transaction_id in matched_ids {
+            if let Some(Some(transaction)) = matched_transactions.remove(&transaction_id) {
+                block.transactions.push(transaction);
+            }
+        }
+
+        Ok((block, bytes))
+    }
+
+    /// Get the blocks from the peer given the hashed headers, with only the transactions that matched the filter
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileSerializing`: It will appear when there is an error in the serialization
+    ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
+    ///  * `ErrorNode::WhileValidating`: It will appear when a merkle block is not the one requested or its proof is not valid
+    ///  * `ErrorNode::RequestedDataTooBig`: It will appear when the headers count is bigger than the maximum headers count of 50_000
+    pub fn get_data<N: Notifier, RW: Read + Write>(
+        &self,
+        peer_stream: &mut RW,
+        hashed_headers: Vec<HashType>,
+        notifier: N,
+    ) -> Result<Vec<Block>, ErrorNode> {
+        let headers_count = hashed_headers.len();
+
+        if headers_count >= MAX_HEADERS_COUNT {
+            let _ = self
+                .sender_log
+                .log_connection("More headers than possible".to_string());
+            return Err(ErrorNode::RequestedDataTooBig);
+        }
+
+        self.send_get_data_message(peer_stream, &hashed_headers)?;
+
+        let _ = self
+            .sender_log
+            .log_connection(format!("Downloading {headers_count} merkle blocks",));
+
+        let mut blocks: Vec<Block> = Vec::new();
+        let mut tracker = ProgressTracker::new(headers_count as u32, None);
+        for (i, hashed_header) in hashed_headers.iter().enumerate() {
+            if i % 100 == 0 {
+                let _ = self
+                    .sender_log
+                    .log_connection(format!("Getting merkle blocks [{i}]"));
+            }
+            tracker.notify_if_due(&notifier);
+
+            let (block, bytes) = self.receive_merkle_block(peer_stream, hashed_header)?;
+            blocks.push(block);
+            tracker.add_blocks(1, bytes);
+        }
+        tracker.notify(&notifier);
+
+        Ok(blocks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::{
+        block_structure::{
+            block_header::BlockHeader, block_version::BlockVersion, compact256::Compact256,
+            merkle_proof::MerkleProof, merkle_tree::MerkleTree, outpoint::Outpoint,
+            script::ScriptBuf, transaction_input::TransactionInput,
+            transaction_output::TransactionOutput,
+        },
+        logs::logger,
+        messages::compact_size::CompactSize,
+        notifications::notification::Notification,
+    };
+
+    struct Stream {
+        stream: Vec<u8>,
+        pointer: usize,
+    }
+
+    impl Stream {
+        pub fn new() -> Stream {
+            Stream {
+                stream: Vec::new(),
+                pointer: 0,
+            }
+        }
+    }
+
+    impl Read for Stream {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let mut i = 0;
+            while i < buf.len() && self.pointer < self.stream.len() {
+                buf[i] = self.stream[self.pointer];
+                self.pointer += 1;
+                i += 1;
+            }
+            Ok(i)
+        }
+    }
+
+    impl Write for Stream {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.stream.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[derive(Clone)]
+    struct NotificationMock {}
+
+    impl Notifier for NotificationMock {
+        fn notify(&self, _notification: Notification) {}
+    }
+
+    fn create_transaction(time: u32) -> Transaction {
+        Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(
+                Outpoint::new([1; 32], 23),
+                vec![1, 2, 3],
+                24,
+            )],
+            tx_out: vec![TransactionOutput {
+                value: 10,
+                pk_script: ScriptBuf::from(vec![4, 5, 6]),
+            }],
+            lock_time: time,
+        }
+    }
+
+    fn create_block(transactions: Vec<Transaction>) -> Block {
+        let merkle_root_hash = MerkleTree::new(&transactions).unwrap().root;
+        Block {
+            header: BlockHeader::new(
+                BlockVersion::version(1),
+                [0; 32],
+                merkle_root_hash,
+                0,
+                Compact256::from(u32::MAX),
+                0,
+                CompactSize::new(transactions.len() as u64),
+            ),
+            transactions,
+        }
+    }
+
+    fn serialize_merkle_block(stream: &mut Stream, magic_numbers: MagicType, block: &Block) {
+        let transaction_ids = Transaction::get_vec_txids(&block.transactions).unwrap();
+        let matches = vec![false, true, false, true];
+
+        let merkle_block = MerkleBlockMessage {
+            header: block.header,
+            merkle_proof: MerkleProof::new(&transaction_ids, &matches).unwrap(),
+        };
+        MerkleBlockMessage::serialize_message(stream, magic_numbers, &merkle_block).unwrap();
+
+        for index in [3, 1] {
+            let tx_message = TxMessage {
+                transaction: block.transactions[index].clone(),
+            };
+            TxMessage::serialize_message(stream, magic_numbers, &tx_message).unwrap();
+        }
+    }
+
+    #[test]
+    fn test01_light_client_gets_only_the_matched_transactions() {
+        let mut stream = Stream::new();
+        let magic_numbers = [11, 17, 9, 7];
+
+        let block = create_block((0..4).map(create_transaction).collect());
+        let block_hash = block.header.get_hash256d().unwrap();
+        serialize_merkle_block(&mut stream, magic_numbers, &block);
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+        let light_client = LightClient::new(magic_numbers, sender);
+
+        let blocks = light_client
+            .get_data(&mut stream, vec![block_hash], NotificationMock {})
+            .unwrap();
+
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].header, block.header);
+        assert_eq!(
+            blocks[0].transactions,
+            vec![block.transactions[1].clone(), block.transactions[3].clone()]
+        );
+        assert!(!blocks[0].is_complete());
+
+        let header = message::deserialize_until_found(&mut stream, CommandName::GetData).unwrap();
+        let get_data_message = GetDataMessage::deserialize_message(&mut stream, header).unwrap();
+        assert_eq!(
+            get_data_message.inventory_vectors,
+            vec![InventoryVector::new(
+                TypeIdentifier::FilteredBlock,
+                block_hash
+            )]
+        );
+    }
+
+    #[test]
+    fn test02_merkle_block_not_requested_is_rejected() {
+        let mut stream = Stream::new();
+        let magic_numbers = [11, 17, 9, 7];
+
+        let block = create_block((0..4).map(create_transaction).collect());
+        serialize_merkle_block(&mut stream, magic_numbers, &block);
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+        let light_client = LightClient::new(magic_numbers, sender);
+
+        assert!(matches!(
+            light_client.get_data(&mut stream, vec![[1; 32]], NotificationMock {}),
+            Err(ErrorNode::WhileValidating(_))
+        ));
+    }
+}
@@ -9,4 +9,6 @@ use std::cmp::PartialEq;
 pub enum MessageBroadcast {
     Transaction(Transaction, Option<ConnectionId>),
     Block(Block, ConnectionId),
+    RequestMempool,
+    KeepAlive,
 }
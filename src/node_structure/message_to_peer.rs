@@ -8,10 +8,12 @@ use crate::{
 use std::convert::From;
 
 /// Messages to send to the peer
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum MessageToPeer {
     SendTransaction(Transaction, Option<ConnectionId>),
     SendBlock(Block, ConnectionId),
+    RequestMempool,
+    KeepAlive,
     Stop,
 }
 
@@ -24,6 +26,8 @@ impl From<MessageToPeer> for Work<MessageBroadcast> {
             MessageToPeer::SendBlock(block, id) => {
                 Work::Information(MessageBroadcast::Block(block, id))
             }
+            MessageToPeer::RequestMempool => Work::Information(MessageBroadcast::RequestMempool),
+            MessageToPeer::KeepAlive => Work::Information(MessageBroadcast::KeepAlive),
             MessageToPeer::Stop => Work::Stop,
         }
     }
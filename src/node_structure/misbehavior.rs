@@ -0,0 +1,87 @@
+use super::error_node::ErrorNode;
+
+/// The score at which a peer is disconnected for misbehaving
+pub const MISBEHAVIOR_THRESHOLD: u32 = 100;
+
+/// The score of sending a block that was not requested or whose hash is not the one requested
+pub const UNREQUESTED_BLOCK_SCORE: u32 = 20;
+
+/// The score of sending a block whose transactions don't match the merkle root of its header
+pub const INVALID_MERKLE_ROOT_SCORE: u32 = MISBEHAVIOR_THRESHOLD;
+
+/// The score of sending a block whose first transaction is not a coinbase, or with more than one coinbase
+pub const INVALID_COINBASE_SCORE: u32 = MISBEHAVIOR_THRESHOLD;
+
+/// It represents how much a peer broke the rules of the protocol. Each fault adds to the score, and
+/// the peer is disconnected once the score reaches the threshold, so an occasional mistake is
+/// tolerated but a peer that keeps sending wrong data is not
+#[derive(Debug, Clone, PartialEq)]
+pub struct Misbehavior {
+    score: u32,
+    threshold: u32,
+}
+
+impl Misbehavior {
+    pub fn new(threshold: u32) -> Self {
+        Misbehavior {
+            score: 0,
+            threshold,
+        }
+    }
+
+    /// Adds the score of a fault of the peer
+    ///
+    /// ### Error
+    ///  * `ErrorNode::PeerMisbehaving`: It will appear when the score reaches the threshold
+    pub fn add(&mut self, score: u32, reason: &str) -> Result<(), ErrorNode> {
+        self.score = self.score.saturating_add(score);
+
+        if self.score >= self.threshold {
+            return Err(ErrorNode::PeerMisbehaving(format!(
+                "{reason}, reaching a misbehavior score of {}",
+                self.score
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub fn get_score(&self) -> u32 {
+        self.score
+    }
+}
+
+impl Default for Misbehavior {
+    fn default() -> Self {
+        Misbehavior::new(MISBEHAVIOR_THRESHOLD)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_peer_misbehaves_when_the_score_reaches_the_threshold() {
+        let mut misbehavior = Misbehavior::default();
+
+        for _ in 0..4 {
+            assert_eq!(Ok(()), misbehavior.add(UNREQUESTED_BLOCK_SCORE, "fault"));
+        }
+        assert_eq!(80, misbehavior.get_score());
+
+        assert!(matches!(
+            misbehavior.add(UNREQUESTED_BLOCK_SCORE, "fault"),
+            Err(ErrorNode::PeerMisbehaving(_))
+        ));
+    }
+
+    #[test]
+    fn test02_invalid_merkle_root_is_enough_to_misbehave() {
+        let mut misbehavior = Misbehavior::default();
+
+        assert!(misbehavior
+            .add(INVALID_MERKLE_ROOT_SCORE, "invalid block")
+            .is_err());
+    }
+}
@@ -1,17 +1,32 @@
 pub mod block_download;
+pub mod compact_filter_client;
 pub mod handshake;
 pub mod handshake_data;
 pub mod initial_headers_download;
+pub mod light_client;
 pub mod process_connection;
 
 pub mod broadcasting;
+pub mod byte_counter;
+pub mod compact_block_relay;
+pub mod inventory_relay;
+pub mod keep_alive;
 pub mod message_broadcast;
 pub mod message_response;
 pub mod message_to_peer;
+pub mod misbehavior;
+pub mod network_time;
+pub mod orphan_pool;
 pub mod peer_manager;
+pub mod peer_queue;
+pub mod recent_inventory;
+pub mod script_subscriptions;
+pub mod sync_progress;
 
 pub mod connection_event;
 pub mod connection_id;
 pub mod connection_type;
+pub mod peer_features;
+pub mod peer_information;
 
 pub mod error_node;
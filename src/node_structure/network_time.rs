@@ -0,0 +1,123 @@
+use chrono::offset::Utc;
+
+use std::{collections::HashMap, net::IpAddr};
+
+pub const MAX_TIME_SAMPLES: usize = 200;
+pub const MIN_TIME_SAMPLES: usize = 5;
+
+/// Maximum offset in seconds that the network can adjust our clock
+pub const MAX_TIME_ADJUSTMENT: i64 = 70 * 60;
+
+/// Offset in seconds from which our clock is considered wrong
+pub const CLOCK_SKEW_WARNING: i64 = 5 * 60;
+
+/// It represents the time of the network, calculated with the median of the offsets between the
+/// timestamps of the version messages of the peers and our clock
+#[derive(Debug, Clone, Default)]
+pub struct NetworkTime {
+    offsets: HashMap<IpAddr, i64>,
+    offset: i64,
+    is_skewed: bool,
+}
+
+impl NetworkTime {
+    /// Adds the offset of a peer, counting each address only once. Returns the median offset of the network
+    /// when our clock starts to deviate from it more than the warning threshold
+    pub fn add_sample(&mut self, peer: IpAddr, offset: i64) -> Option<i64> {
+        if self.offsets.len() >= MAX_TIME_SAMPLES || self.offsets.contains_key(&peer) {
+            return None;
+        }
+        self.offsets.insert(peer, offset);
+
+        if self.offsets.len() < MIN_TIME_SAMPLES {
+            return None;
+        }
+
+        let mut offsets: Vec<i64> = self.offsets.values().copied().collect();
+        offsets.sort_unstable();
+        let median = offsets[offsets.len() / 2];
+
+        self.offset = match median.abs() <= MAX_TIME_ADJUSTMENT {
+            true => median,
+            false => 0,
+        };
+
+        let was_skewed = self.is_skewed;
+        self.is_skewed = median.abs() > CLOCK_SKEW_WARNING;
+
+        match self.is_skewed && !was_skewed {
+            true => Some(median),
+            false => None,
+        }
+    }
+
+    /// Returns the offset in seconds used to adjust our clock
+    pub fn get_offset(&self) -> i64 {
+        self.offset
+    }
+
+    /// Returns the current time in seconds adjusted with the time of the network
+    pub fn get_adjusted_time(&self) -> i64 {
+        Utc::now().timestamp() + self.offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::Ipv4Addr;
+
+    fn peer(number: u8) -> IpAddr {
+        IpAddr::V4(Ipv4Addr::new(10, 0, 0, number))
+    }
+
+    #[test]
+    fn test01_offset_needs_minimum_samples() {
+        let mut network_time = NetworkTime::default();
+
+        for number in 0..(MIN_TIME_SAMPLES as u8 - 1) {
+            assert_eq!(network_time.add_sample(peer(number), 60), None);
+        }
+        assert_eq!(network_time.get_offset(), 0);
+
+        network_time.add_sample(peer(10), 60);
+        assert_eq!(network_time.get_offset(), 60);
+    }
+
+    #[test]
+    fn test02_offset_is_the_median() {
+        let mut network_time = NetworkTime::default();
+
+        for (number, offset) in [-30, 10, 20, 4000, 15].iter().enumerate() {
+            network_time.add_sample(peer(number as u8), *offset);
+        }
+
+        assert_eq!(network_time.get_offset(), 15);
+    }
+
+    #[test]
+    fn test03_big_skew_is_warned_once_and_not_adjusted() {
+        let mut network_time = NetworkTime::default();
+        let offset = MAX_TIME_ADJUSTMENT + 1;
+
+        for number in 0..(MIN_TIME_SAMPLES as u8 - 1) {
+            network_time.add_sample(peer(number), offset);
+        }
+
+        assert_eq!(network_time.add_sample(peer(10), offset), Some(offset));
+        assert_eq!(network_time.add_sample(peer(11), offset), None);
+        assert_eq!(network_time.get_offset(), 0);
+    }
+
+    #[test]
+    fn test04_each_peer_is_counted_once() {
+        let mut network_time = NetworkTime::default();
+
+        for _ in 0..MIN_TIME_SAMPLES {
+            network_time.add_sample(peer(1), 60);
+        }
+
+        assert_eq!(network_time.get_offset(), 0);
+    }
+}
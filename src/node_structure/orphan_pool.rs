@@ -0,0 +1,177 @@
+use super::connection_id::ConnectionId;
+
+use crate::block_structure::hash::HashType;
+
+use std::time::{Duration, Instant};
+
+/// The time an orphan waits for its parents before being dropped
+pub const ORPHAN_EXPIRATION: Duration = Duration::from_secs(20 * 60);
+
+/// The amount of blocks that can wait for their previous block
+pub const MAX_ORPHAN_BLOCKS: usize = 100;
+
+/// The amount of transactions that can wait for the transactions they spend
+pub const MAX_ORPHAN_TRANSACTIONS: usize = 500;
+
+/// It's an element received before the elements it depends on
+#[derive(Debug, Clone)]
+struct Orphan<T> {
+    element: T,
+    from: ConnectionId,
+    missing: Vec<HashType>,
+    received: Instant,
+}
+
+/// It holds the blocks whose previous block is unknown, or the transactions spending outputs of
+/// unknown transactions, until the missing parents arrive. The pool is bounded in size, dropping the
+/// oldest orphan when it's full, and in time, dropping the orphans that wait for longer than the expiration
+#[derive(Debug, Clone)]
+pub struct OrphanPool<T: PartialEq> {
+    orphans: Vec<Orphan<T>>,
+    capacity: usize,
+    expiration: Duration,
+}
+
+impl<T: PartialEq> OrphanPool<T> {
+    pub fn new(capacity: usize, expiration: Duration) -> Self {
+        OrphanPool {
+            orphans: Vec::new(),
+            capacity,
+            expiration,
+        }
+    }
+
+    /// Holds the element received from the peer until the missing parents arrive, returning false if
+    /// it was already being held
+    pub fn add(
+        &mut self,
+        element: T,
+        from: ConnectionId,
+        missing: Vec<HashType>,
+        now: Instant,
+    ) -> bool {
+        self.remove_expired(now);
+        if self.contains(&element) {
+            return false;
+        }
+
+        if self.orphans.len() >= self.capacity && !self.orphans.is_empty() {
+            self.orphans.remove(0);
+        }
+
+        self.orphans.push(Orphan {
+            element,
+            from,
+            missing,
+            received: now,
+        });
+        true
+    }
+
+    /// Marks the parent as arrived, returning the orphans that are no longer missing any parent with
+    /// the peer they came from, to be inserted again. They are removed from the pool
+    pub fn resolve(&mut self, parent: &HashType, now: Instant) -> Vec<(T, ConnectionId)> {
+        self.remove_expired(now);
+
+        let mut resolved = Vec::new();
+        let mut waiting = Vec::new();
+        for mut orphan in self.orphans.drain(..) {
+            orphan.missing.retain(|missing| missing != parent);
+            match orphan.missing.is_empty() {
+                true => resolved.push((orphan.element, orphan.from)),
+                false => waiting.push(orphan),
+            }
+        }
+        self.orphans = waiting;
+
+        resolved
+    }
+
+    /// Drops the orphans that waited for their parents for longer than the expiration, returning how many were dropped
+    pub fn remove_expired(&mut self, now: Instant) -> usize {
+        let before = self.orphans.len();
+        let expiration = self.expiration;
+        self.orphans
+            .retain(|orphan| now.saturating_duration_since(orphan.received) < expiration);
+
+        before - self.orphans.len()
+    }
+
+    /// Returns true if the element is waiting for its parents
+    pub fn contains(&self, element: &T) -> bool {
+        self.orphans.iter().any(|orphan| orphan.element == *element)
+    }
+
+    pub fn len(&self) -> usize {
+        self.orphans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.orphans.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::node_structure::connection_type::ConnectionType;
+
+    fn create_connection() -> ConnectionId {
+        ConnectionId::new("127.0.0.1:18333".parse().unwrap(), ConnectionType::Peer)
+    }
+
+    #[test]
+    fn test01_orphan_is_resolved_when_all_its_parents_arrive() {
+        let now = Instant::now();
+        let mut pool: OrphanPool<u32> = OrphanPool::new(10, ORPHAN_EXPIRATION);
+
+        assert!(pool.add(1, create_connection(), vec![[1; 32], [2; 32]], now));
+        assert!(!pool.add(1, create_connection(), vec![[1; 32]], now));
+        assert!(pool.add(2, create_connection(), vec![[2; 32]], now));
+
+        assert!(pool.resolve(&[1; 32], now).is_empty());
+
+        let resolved: Vec<u32> = pool
+            .resolve(&[2; 32], now)
+            .into_iter()
+            .map(|(element, _)| element)
+            .collect();
+        assert_eq!(vec![1, 2], resolved);
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn test02_oldest_orphan_is_dropped_when_the_pool_is_full() {
+        let now = Instant::now();
+        let mut pool: OrphanPool<u32> = OrphanPool::new(2, ORPHAN_EXPIRATION);
+
+        pool.add(1, create_connection(), vec![[1; 32]], now);
+        pool.add(2, create_connection(), vec![[1; 32]], now);
+        pool.add(3, create_connection(), vec![[1; 32]], now);
+
+        assert_eq!(2, pool.len());
+        assert!(!pool.contains(&1));
+        assert!(pool.contains(&3));
+    }
+
+    #[test]
+    fn test03_orphan_expires_if_its_parents_do_not_arrive() {
+        let now = Instant::now();
+        let mut pool: OrphanPool<u32> = OrphanPool::new(10, Duration::from_secs(60));
+
+        pool.add(1, create_connection(), vec![[1; 32]], now);
+        pool.add(
+            2,
+            create_connection(),
+            vec![[1; 32]],
+            now + Duration::from_secs(30),
+        );
+
+        assert_eq!(1, pool.remove_expired(now + Duration::from_secs(60)));
+
+        let resolved = pool.resolve(&[1; 32], now + Duration::from_secs(61));
+        assert_eq!(1, resolved.len());
+        assert_eq!(2, resolved[0].0);
+    }
+}
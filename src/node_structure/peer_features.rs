@@ -0,0 +1,174 @@
+use crate::{
+    connections::{p2p_protocol::ProtocolVersionP2P, supported_services::SupportedServices},
+    messages::bitfield_services::BitfieldServices,
+};
+
+use std::convert::TryInto;
+
+const SEND_HEADERS_VERSION: i32 = 70012;
+const FEE_FILTER_VERSION: i32 = 70013;
+const SEND_COMPACT_VERSION: i32 = 70014;
+const WTXID_RELAY_VERSION: i32 = 70016;
+
+/// It's the features agreed with a peer in the handshake, given by the lowest protocol version
+/// of both nodes, the services the peer advertises and if the transactions are announced by their witness id
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PeerFeatures {
+    pub version: ProtocolVersionP2P,
+    pub services: u64,
+    pub wtxid_relay: bool,
+}
+
+impl PeerFeatures {
+    /// Negotiates the features with the protocol version of our node and the one of the peer,
+    /// keeping the services the peer advertises
+    pub fn negotiate(
+        own_version: ProtocolVersionP2P,
+        peer_version: ProtocolVersionP2P,
+        peer_services: &BitfieldServices,
+    ) -> Self {
+        let version = match i32::from(own_version) <= i32::from(peer_version) {
+            true => own_version,
+            false => peer_version,
+        };
+
+        let services = peer_services
+            .elements
+            .iter()
+            .filter_map(|service| TryInto::<u64>::try_into(*service).ok())
+            .fold(0, |services, service| services | service);
+
+        PeerFeatures {
+            version,
+            services,
+            wtxid_relay: false,
+        }
+    }
+
+    /// Returns true if the peer advertises the given service
+    pub fn has_service(&self, service: SupportedServices) -> bool {
+        match TryInto::<u64>::try_into(service) {
+            Ok(service) => service != 0 && self.services & service == service,
+            Err(_) => false,
+        }
+    }
+
+    /// Returns true if the peer can be asked to announce the blocks with headers messages
+    pub fn supports_send_headers(&self) -> bool {
+        i32::from(self.version) >= SEND_HEADERS_VERSION
+    }
+
+    /// Returns true if the peer understands the fee filter message
+    pub fn supports_fee_filter(&self) -> bool {
+        i32::from(self.version) >= FEE_FILTER_VERSION
+    }
+
+    /// Returns true if compact blocks can be negotiated with the peer
+    pub fn supports_send_compact(&self) -> bool {
+        i32::from(self.version) >= SEND_COMPACT_VERSION
+    }
+
+    /// Returns true if the transactions can be announced by their witness id with the peer
+    pub fn supports_wtxid_relay(&self) -> bool {
+        i32::from(self.version) >= WTXID_RELAY_VERSION
+    }
+}
+
+impl Default for PeerFeatures {
+    /// The features assumed before the handshake, with the latest protocol version and no services
+    fn default() -> Self {
+        PeerFeatures {
+            version: ProtocolVersionP2P::V70016,
+            services: 0,
+            wtxid_relay: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_negotiates_the_lowest_version() {
+        let services = BitfieldServices::new(vec![SupportedServices::NodeNetwork]);
+
+        let features = PeerFeatures::negotiate(
+            ProtocolVersionP2P::V70015,
+            ProtocolVersionP2P::V70016,
+            &services,
+        );
+        assert_eq!(features.version, ProtocolVersionP2P::V70015);
+
+        let features = PeerFeatures::negotiate(
+            ProtocolVersionP2P::V70015,
+            ProtocolVersionP2P::V70012,
+            &services,
+        );
+        assert_eq!(features.version, ProtocolVersionP2P::V70012);
+    }
+
+    #[test]
+    fn test02_optional_messages_depend_on_the_version() {
+        let services = BitfieldServices::new(vec![SupportedServices::NodeNetwork]);
+
+        let features = PeerFeatures::negotiate(
+            ProtocolVersionP2P::V70016,
+            ProtocolVersionP2P::V70013,
+            &services,
+        );
+
+        assert!(features.supports_send_headers());
+        assert!(features.supports_fee_filter());
+        assert!(!features.supports_send_compact());
+
+        let features = PeerFeatures::negotiate(
+            ProtocolVersionP2P::V70016,
+            ProtocolVersionP2P::V70002,
+            &services,
+        );
+
+        assert!(!features.supports_send_headers());
+        assert!(!features.supports_fee_filter());
+        assert!(!features.supports_send_compact());
+    }
+
+    #[test]
+    fn test03_keeps_the_services_of_the_peer() {
+        let services = BitfieldServices::new(vec![
+            SupportedServices::NodeNetworkLimited,
+            SupportedServices::NodeWitness,
+        ]);
+
+        let features = PeerFeatures::negotiate(
+            ProtocolVersionP2P::V70016,
+            ProtocolVersionP2P::V70016,
+            &services,
+        );
+
+        assert!(features.has_service(SupportedServices::NodeNetworkLimited));
+        assert!(features.has_service(SupportedServices::NodeWitness));
+        assert!(!features.has_service(SupportedServices::NodeNetwork));
+        assert!(!features.has_service(SupportedServices::Unname));
+    }
+
+    #[test]
+    fn test04_wtxid_relay_needs_the_latest_version() {
+        let services = BitfieldServices::new(vec![SupportedServices::NodeNetwork]);
+
+        let features = PeerFeatures::negotiate(
+            ProtocolVersionP2P::V70016,
+            ProtocolVersionP2P::V70016,
+            &services,
+        );
+        assert!(features.supports_wtxid_relay());
+        assert!(!features.wtxid_relay);
+
+        let features = PeerFeatures::negotiate(
+            ProtocolVersionP2P::V70016,
+            ProtocolVersionP2P::V70015,
+            &services,
+        );
+        assert!(!features.supports_wtxid_relay());
+    }
+}
@@ -0,0 +1,98 @@
+use super::{connection_id::ConnectionId, connection_type::ConnectionType};
+
+use std::{fmt::Display, time::Duration};
+
+const OUTBOUND: &str = "Outbound";
+const INBOUND: &str = "Inbound";
+
+/// It's the information of a connection to show: who the peer is, how long it takes to answer a ping
+/// and the amount of bytes transferred with it
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeerInformation {
+    pub connection: ConnectionId,
+
+    /// The user agent the peer sent in its version message
+    pub user_agent: String,
+
+    /// The time the peer took to answer the last ping, if it answered one
+    pub latency: Option<Duration>,
+
+    pub bytes_received: u64,
+    pub bytes_sent: u64,
+
+    /// The amount of messages waiting in the queue to be sent to the peer
+    pub queued_messages: usize,
+}
+
+impl PeerInformation {
+    /// Returns if we connected to the peer (outbound) or the peer connected to us (inbound)
+    pub fn direction(&self) -> &'static str {
+        match self.connection.connection_type {
+            ConnectionType::Peer => OUTBOUND,
+            ConnectionType::Client => INBOUND,
+        }
+    }
+}
+
+impl Display for PeerInformation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({}) {} with version {}",
+            self.connection.address,
+            self.direction(),
+            self.user_agent,
+            i32::from(self.connection.features.version),
+        )?;
+
+        if let Some(latency) = self.latency {
+            write!(f, ", latency of {} ms", latency.as_millis())?;
+        }
+        write!(
+            f,
+            ", {} bytes received and {} bytes sent",
+            self.bytes_received, self.bytes_sent
+        )?;
+
+        if self.queued_messages > 0 {
+            write!(f, ", {} messages queued", self.queued_messages)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_peer_information_is_shown_with_its_direction() {
+        let address = "127.0.0.1:18333".parse().unwrap();
+        let mut information = PeerInformation {
+            connection: ConnectionId::new(address, ConnectionType::Peer),
+            user_agent: "/Satoshi:25.0.0/".to_string(),
+            latency: None,
+            bytes_received: 1024,
+            bytes_sent: 256,
+            queued_messages: 0,
+        };
+
+        assert_eq!(
+            "127.0.0.1:18333 (Outbound) /Satoshi:25.0.0/ with version 70016, 1024 bytes received and 256 bytes sent",
+            information.to_string()
+        );
+
+        information.connection = ConnectionId::new(address, ConnectionType::Client);
+        information.latency = Some(Duration::from_millis(42));
+        assert_eq!(
+            "127.0.0.1:18333 (Inbound) /Satoshi:25.0.0/ with version 70016, latency of 42 ms, 1024 bytes received and 256 bytes sent",
+            information.to_string()
+        );
+
+        information.queued_messages = 3;
+        assert_eq!(
+            "127.0.0.1:18333 (Inbound) /Satoshi:25.0.0/ with version 70016, latency of 42 ms, 1024 bytes received and 256 bytes sent, 3 messages queued",
+            information.to_string()
+        );
+    }
+}
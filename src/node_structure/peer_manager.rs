@@ -1,6 +1,19 @@
 use super::{
-    connection_id::ConnectionId, error_node::ErrorNode, message_broadcast::MessageBroadcast,
-    message_response::MessageResponse, message_to_peer::MessageToPeer,
+    byte_counter::ByteCounter,
+    compact_block_relay::{CompactBlockRelay, PartialBlock},
+    connection_id::ConnectionId,
+    error_node::ErrorNode,
+    inventory_relay::InventoryRelay,
+    keep_alive::KeepAlive,
+    message_broadcast::MessageBroadcast,
+    message_response::MessageResponse,
+    message_to_peer::MessageToPeer,
+    misbehavior::{
+        Misbehavior, INVALID_COINBASE_SCORE, INVALID_MERKLE_ROOT_SCORE, UNREQUESTED_BLOCK_SCORE,
+    },
+    peer_information::PeerInformation,
+    peer_queue::QueueStatus,
+    recent_inventory::RecentInventory,
 };
 
 use crate::{
@@ -9,37 +22,58 @@ use crate::{
     },
     concurrency::work::Work,
     connections::type_identifier::TypeIdentifier,
-    logs::logger_sender::LoggerSender,
+    logs::{level::Level, logger_sender::LoggerSender},
     messages::{
         addr_message::AddrMessage,
         alert_message::AlertMessage,
         block_message::BlockMessage,
+        block_transactions_message::BlockTransactionsMessage,
         command_name::CommandName,
+        compact_block_message::CompactBlockMessage,
+        compact_filter_checkpoint_message::CompactFilterCheckpointMessage,
+        compact_filter_headers_message::CompactFilterHeadersMessage,
+        compact_filter_message::CompactFilterMessage,
+        compact_size::CompactSize,
         fee_filter_message::FeeFilterMessage,
+        filter_add_message::FilterAddMessage,
+        filter_clear_message::FilterClearMessage,
+        filter_load_message::FilterLoadMessage,
+        get_block_transactions_message::GetBlockTransactionsMessage,
+        get_compact_filter_checkpoint_message::GetCompactFilterCheckpointMessage,
+        get_compact_filter_headers_message::GetCompactFilterHeadersMessage,
+        get_compact_filters_message::GetCompactFiltersMessage,
         get_data_message::GetDataMessage,
         get_headers_message::GetHeadersMessage,
         headers_message::HeadersMessage,
         inventory_message::InventoryMessage,
         inventory_vector::InventoryVector,
-        message::{ignore_message, Message},
+        mempool_message::MempoolMessage,
+        merkle_block_message::MerkleBlockMessage,
+        message::{ignore_message, ignore_unknown_message, Message},
         message_header::MessageHeader,
+        not_found_message::NotFoundMessage,
         ping_message::PingMessage,
         pong_message::PongMessage,
+        reject_message::RejectMessage,
         send_cmpct_message::SendCmpctMessage,
         send_headers_message::SendHeadersMessage,
         tx_message::TxMessage,
         verack_message::VerackMessage,
         version_message::VersionMessage,
+        wtxid_relay_message::WtxidRelayMessage,
     },
     notifications::{notification::Notification, notifier::Notifier},
 };
 
 use std::{
+    collections::HashSet,
     io::{Read, Write},
+    net::IpAddr,
     sync::{
         mpsc::{Receiver, Sender},
         Arc, Mutex,
     },
+    time::{Duration, Instant},
 };
 
 /// It represents how to manage the the peer, listening to the there messages and sending them transactions
@@ -49,10 +83,21 @@ where
     N: Notifier + 'static,
 {
     id: ConnectionId,
-    peer: RW,
+    peer: ByteCounter<RW>,
+    user_agent: String,
+    latency: Option<Duration>,
+    queue_status: QueueStatus,
     sender: Sender<MessageResponse>,
     blockchain: Arc<Mutex<BlockChain>>,
     magic_numbers: [u8; 4],
+    keep_alive: KeepAlive,
+    inventory_relay: InventoryRelay,
+    recent_inventory: Arc<Mutex<RecentInventory>>,
+    banned: Arc<Mutex<HashSet<IpAddr>>>,
+    requested_blocks: HashSet<HashType>,
+    misbehavior: Misbehavior,
+    compact_block_relay: CompactBlockRelay,
+    wtxid_relay: bool,
     notifier: N,
     logger: LoggerSender,
 }
@@ -73,18 +118,68 @@ where
     ) -> Self {
         PeerManager {
             id,
-            peer,
+            peer: ByteCounter::new(peer),
+            user_agent: String::new(),
+            latency: None,
+            queue_status: QueueStatus::default(),
             sender,
             blockchain,
             magic_numbers,
+            keep_alive: KeepAlive::default(),
+            inventory_relay: InventoryRelay::default(),
+            recent_inventory: Arc::new(Mutex::new(RecentInventory::default())),
+            banned: Arc::new(Mutex::new(HashSet::new())),
+            requested_blocks: HashSet::new(),
+            misbehavior: Misbehavior::default(),
+            compact_block_relay: CompactBlockRelay::default(),
+            wtxid_relay: id.features.wtxid_relay,
             notifier,
             logger,
         }
     }
 
-    /// Listens and send messages to the peer
+    /// Sets the user agent the peer sent in the handshake, to be shown with the information of the connection
+    pub fn with_user_agent(mut self, user_agent: String) -> Self {
+        self.user_agent = user_agent;
+        self
+    }
+
+    /// Sets the status of the queue the messages to the peer come from, to show its depth and to know when to stop
+    pub fn with_queue_status(mut self, queue_status: QueueStatus) -> Self {
+        self.queue_status = queue_status;
+        self
+    }
+
+    /// Sets the transactions and blocks recently received, shared with the managers of the other peers
+    pub fn with_recent_inventory(mut self, recent_inventory: Arc<Mutex<RecentInventory>>) -> Self {
+        self.recent_inventory = recent_inventory;
+        self
+    }
+
+    /// Sets the ips of the banned peers, shared with the broadcasting, so a misbehaving peer is not accepted again
+    pub fn with_ban_list(mut self, banned: Arc<Mutex<HashSet<IpAddr>>>) -> Self {
+        self.banned = banned;
+        self
+    }
+
+    /// Returns the information of the connection until now
+    pub fn peer_information(&self) -> PeerInformation {
+        PeerInformation {
+            connection: self.id,
+            user_agent: self.user_agent.clone(),
+            latency: self.latency,
+            bytes_received: self.peer.received(),
+            bytes_sent: self.peer.sent(),
+            queued_messages: self.queue_status.get_depth(),
+        }
+    }
+
+    /// Listens and send messages to the peer. The peer is disconnected if it does not answer the pings in time
+    /// or if it misbehaves. The information of the connection is notified every time the peer is kept alive,
+    /// and its disconnection when it stops
     ///
     /// ### Error
+    ///  * `ErrorNode::PeerMisbehaving`: It will appear when the peer breaks the rules of the protocol
     ///  * `ErrorNode::WhileSerializing`: It will appear when there is an error in the serialization
     ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
     ///  * `ErrorNode::NodeNotResponding`: It will appear when the node is not responding to the messages
@@ -92,22 +187,75 @@ where
         mut self,
         receiver: Receiver<MessageToPeer>,
     ) -> Result<(RW, ConnectionId), ErrorNode> {
+        self.notifier.notify(Notification::PeerInformationUpdated(
+            self.peer_information(),
+        ));
+
+        let result = self.manage_peer(&receiver);
+        self.notifier
+            .notify(Notification::PeerDisconnected(self.id));
+        result?;
+
+        Ok((self.peer.into_inner(), self.id))
+    }
+
+    /// Listens and send messages to the peer until it's stopped or disconnected
+    fn manage_peer(&mut self, receiver: &Receiver<MessageToPeer>) -> Result<(), ErrorNode> {
         loop {
-            match Work::listen(&mut self.peer, &receiver) {
-                Work::Message(header) => self.manage_message(header)?,
+            if self.queue_status.is_closed() {
+                let _ = self
+                    .logger
+                    .log_configuration("Closing this peer".to_string());
+                self.notifier.notify(Notification::ClosingPeer);
+                break;
+            }
+
+            let work = Work::listen(&mut self.peer, receiver);
+            if let Work::Information(_) = work {
+                self.queue_status.received();
+            }
+
+            match work {
+                Work::Message(header) => {
+                    if let Err(error) = self.manage_message(header) {
+                        if let ErrorNode::PeerMisbehaving(reason) = &error {
+                            let _ = self.logger.log_connection(format!(
+                                "Banning peer {} for misbehaving: {reason}",
+                                self.id
+                            ));
+                            self.ban();
+                            self.notifier.notify(Notification::ClosingPeer);
+                        }
+                        return Err(error);
+                    }
+                }
                 Work::Information(MessageBroadcast::Transaction(transaction, None)) => {
-                    self.send_transaction(transaction)?
+                    self.announce_transaction(transaction)?
                 }
                 Work::Information(MessageBroadcast::Transaction(transaction, Some(from))) => {
                     if from != self.id {
-                        self.send_transaction(transaction)?
+                        self.announce_transaction(transaction)?
                     }
                 }
                 Work::Information(MessageBroadcast::Block(block, from)) => {
                     if from != self.id {
-                        self.send_block(block)?
+                        self.announce_block(block)?
                     }
                 }
+                Work::Information(MessageBroadcast::RequestMempool) => self.request_mempool()?,
+                Work::Information(MessageBroadcast::KeepAlive) => {
+                    if !self.keep_alive()? {
+                        let _ = self.logger.log_connection(format!(
+                            "Disconnecting peer {} for not answering the ping",
+                            self.id
+                        ));
+                        self.notifier.notify(Notification::ClosingPeer);
+                        break;
+                    }
+                    self.notifier.notify(Notification::PeerInformationUpdated(
+                        self.peer_information(),
+                    ));
+                }
                 Work::Stop => {
                     let _ = self
                         .logger
@@ -118,7 +266,7 @@ where
             }
         }
 
-        Ok((self.peer, self.id))
+        Ok(())
     }
 
     /// Receives the message from the peer and manages it by sending to the peer or others threads via the sender
@@ -130,16 +278,17 @@ where
     fn manage_message(&mut self, header: MessageHeader) -> Result<(), ErrorNode> {
         let magic_numbers = header.magic_numbers;
 
-        let _ = self
-            .logger
-            .log_connection(format!("Receive message of type {:?}", header.command_name));
+        let _ = self.logger.log_debug(
+            Level::CONNECTION,
+            format!("Receive message of type {:?}", header.command_name),
+        );
 
         self.notifier
-            .notify(Notification::ReceivedMessage(header.command_name));
+            .notify(Notification::ReceivedMessage(header.command_name.clone()));
 
-        match header.command_name {
-            CommandName::Version => ignore_message::<RW, VersionMessage>(&mut self.peer, header)?,
-            CommandName::Verack => ignore_message::<RW, VerackMessage>(&mut self.peer, header)?,
+        match header.command_name.clone() {
+            CommandName::Version => ignore_message::<_, VersionMessage>(&mut self.peer, header)?,
+            CommandName::Verack => ignore_message::<_, VerackMessage>(&mut self.peer, header)?,
             CommandName::Ping => {
                 let ping = PingMessage::deserialize_message(&mut self.peer, header)?;
 
@@ -147,30 +296,118 @@ where
 
                 PongMessage::serialize_message(&mut self.peer, magic_numbers, &pong)?;
             }
-            CommandName::Pong => ignore_message::<RW, PongMessage>(&mut self.peer, header)?,
+            CommandName::Pong => self.receive_pong(header)?,
             CommandName::GetHeaders => self.replay_to_get_headers_message(header)?,
             CommandName::Headers => self.receive_headers(header)?,
             CommandName::GetData => self.reply_to_get_data_message(header)?,
             CommandName::Block => self.receive_blocks(header)?,
             CommandName::Inventory => self.receive_inventory_message(header)?,
             CommandName::SendHeaders => {
-                ignore_message::<RW, SendHeadersMessage>(&mut self.peer, header)?
+                ignore_message::<_, SendHeadersMessage>(&mut self.peer, header)?
             }
-            CommandName::SendCmpct => {
-                ignore_message::<RW, SendCmpctMessage>(&mut self.peer, header)?
+            CommandName::SendCmpct => self.receive_send_compact(header)?,
+            CommandName::Addr => ignore_message::<_, AddrMessage>(&mut self.peer, header)?,
+            CommandName::FeeFilter => self.receive_fee_filter(header)?,
+            CommandName::Alert => ignore_message::<_, AlertMessage>(&mut self.peer, header)?,
+            CommandName::Tx => self.receive_transaction(header)?,
+            CommandName::CompactBlock => self.receive_compact_block(header)?,
+            CommandName::GetBlockTransactions => {
+                self.reply_to_get_block_transactions_message(header)?
             }
-            CommandName::Addr => ignore_message::<RW, AddrMessage>(&mut self.peer, header)?,
-            CommandName::FeeFilter => {
-                ignore_message::<RW, FeeFilterMessage>(&mut self.peer, header)?
+            CommandName::BlockTransactions => self.receive_block_transactions(header)?,
+            CommandName::FilterLoad => {
+                ignore_message::<_, FilterLoadMessage>(&mut self.peer, header)?
             }
-            CommandName::Alert => ignore_message::<RW, AlertMessage>(&mut self.peer, header)?,
-            CommandName::Tx => self.receive_transaction(header)?,
+            CommandName::FilterAdd => {
+                ignore_message::<_, FilterAddMessage>(&mut self.peer, header)?
+            }
+            CommandName::FilterClear => {
+                ignore_message::<_, FilterClearMessage>(&mut self.peer, header)?
+            }
+            CommandName::MerkleBlock => {
+                ignore_message::<_, MerkleBlockMessage>(&mut self.peer, header)?
+            }
+            CommandName::GetCompactFilters => {
+                ignore_message::<_, GetCompactFiltersMessage>(&mut self.peer, header)?
+            }
+            CommandName::CompactFilter => {
+                ignore_message::<_, CompactFilterMessage>(&mut self.peer, header)?
+            }
+            CommandName::GetCompactFilterHeaders => {
+                ignore_message::<_, GetCompactFilterHeadersMessage>(&mut self.peer, header)?
+            }
+            CommandName::CompactFilterHeaders => {
+                ignore_message::<_, CompactFilterHeadersMessage>(&mut self.peer, header)?
+            }
+            CommandName::GetCompactFilterCheckpoint => {
+                ignore_message::<_, GetCompactFilterCheckpointMessage>(&mut self.peer, header)?
+            }
+            CommandName::CompactFilterCheckpoint => {
+                ignore_message::<_, CompactFilterCheckpointMessage>(&mut self.peer, header)?
+            }
+            CommandName::Mempool => ignore_message::<_, MempoolMessage>(&mut self.peer, header)?,
+            CommandName::NotFound => self.receive_not_found(header)?,
+            CommandName::Reject => self.receive_reject(header)?,
+            CommandName::WtxidRelay => {
+                let _ = self.logger.log_connection(format!(
+                    "Ignoring wtxidrelay message of peer {}, it's only valid before the verack message",
+                    self.id
+                ));
+                ignore_message::<_, WtxidRelayMessage>(&mut self.peer, header)?
+            }
+            CommandName::Unknown(_) => ignore_unknown_message(&mut self.peer, header)?,
+        }
+
+        Ok(())
+    }
+
+    /// Sends a ping to the peer when it's time to do it, and returns false if the peer did not answer the last ping in time
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending the ping to the peer
+    fn keep_alive(&mut self) -> Result<bool, ErrorNode> {
+        let now = Instant::now();
+
+        if self.keep_alive.is_timed_out(now) {
+            return Ok(false);
+        }
+
+        if !self.keep_alive.should_ping(now) {
+            return Ok(true);
+        }
+
+        let ping = self.keep_alive.ping(now);
+        if PingMessage::serialize_message(&mut self.peer, self.magic_numbers, &ping).is_err() {
+            return Err(ErrorNode::WhileSendingMessage(
+                "Sending ping message to peer".to_string(),
+            ));
+        }
+
+        Ok(true)
+    }
+
+    /// Receives the answer of a ping, and notifies the latency of the connection if it matches the ping sent
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
+    fn receive_pong(&mut self, header: MessageHeader) -> Result<(), ErrorNode> {
+        let pong = PongMessage::deserialize_message(&mut self.peer, header)?;
+
+        if let Some(latency) = self.keep_alive.receive_pong(&pong, Instant::now()) {
+            self.latency = Some(latency);
+            let _ = self.logger.log_connection(format!(
+                "Peer {} answered the ping in {} ms",
+                self.id,
+                latency.as_millis()
+            ));
+            self.notifier
+                .notify(Notification::PeerLatency(self.id, latency));
         }
 
         Ok(())
     }
 
-    /// Receives the message of a new header, and request its corresponding block
+    /// Receives the message of a new header, and request its corresponding block if no peer sent it recently
     ///
     /// ### Error
     ///  * `ErrorNode::WhileSerializing`: It will appear when there is an error in the serialization
@@ -187,9 +424,21 @@ where
                 Ok(header_hash) => Some(header_hash),
                 Err(_) => None,
             })
+            .filter(|header_hash| !self.is_recently_seen(header_hash))
             .collect();
 
-        let get_data_message = GetDataMessage::get_blocks(headers);
+        if headers.is_empty() {
+            return Ok(());
+        }
+
+        self.requested_blocks.extend(headers.iter().copied());
+        let block_type = self.get_block_type_identifier();
+        let get_data_message = GetDataMessage::new(
+            headers
+                .into_iter()
+                .map(|header_hash| InventoryVector::new(block_type.clone(), header_hash))
+                .collect(),
+        );
 
         let _ = self
             .logger
@@ -206,19 +455,73 @@ where
         Ok(())
     }
 
-    /// Receives the message of a new block, and send it to others threads via the sender
+    /// Receives the message of a new block, and send it to others threads via the sender unless a peer sent it recently.
+    /// The block is dropped if it was not requested or its transactions don't match its merkle root, adding to the
+    /// misbehavior score of the peer
     ///
     /// ### Error
     ///  * `ErrorNode::WhileSerializing`: It will appear when there is an error in the serialization
     ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
     ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending a message to others threads
+    ///  * `ErrorNode::PeerMisbehaving`: It will appear when the peer sends too many blocks that are not valid
     fn receive_blocks(&mut self, header: MessageHeader) -> Result<(), ErrorNode> {
         let block_message = BlockMessage::deserialize_message(&mut self.peer, header)?;
+        let block_hash = block_message.block.header.get_hash256d()?;
 
         let _ = self
             .logger
             .log_connection(format!("Receiving a block: {}", block_message.block));
 
+        if !self.requested_blocks.remove(&block_hash) {
+            let _ = self.logger.log_warning(
+                Level::CONNECTION,
+                format!(
+                    "Peer {} sent the block {} that was not requested",
+                    self.id, block_message.block
+                ),
+            );
+            return self.misbehavior.add(
+                UNREQUESTED_BLOCK_SCORE,
+                "Sent a block that was not requested",
+            );
+        }
+
+        if !block_message.block.proof_of_inclusion() {
+            let _ = self.logger.log_warning(
+                Level::CONNECTION,
+                format!(
+                    "Peer {} sent the block {} with transactions that don't match its merkle root",
+                    self.id, block_message.block
+                ),
+            );
+            return self.misbehavior.add(
+                INVALID_MERKLE_ROOT_SCORE,
+                "Sent a block with transactions that don't match its merkle root",
+            );
+        }
+
+        if !block_message.block.has_valid_coinbase() {
+            let _ = self.logger.log_warning(
+                Level::CONNECTION,
+                format!(
+                    "Peer {} sent the block {} without a valid coinbase",
+                    self.id, block_message.block
+                ),
+            );
+            return self.misbehavior.add(
+                INVALID_COINBASE_SCORE,
+                "Sent a block without a valid coinbase",
+            );
+        }
+
+        if !self.mark_as_seen(block_hash) {
+            let _ = self.logger.log_debug(
+                Level::CONNECTION,
+                format!("Block {} was already received", block_message.block),
+            );
+            return Ok(());
+        }
+
         if self
             .sender
             .send(MessageResponse::Block(block_message.block, self.id))
@@ -232,7 +535,42 @@ where
         Ok(())
     }
 
-    /// Receives the message of a new transaction, and send it to others threads via the sender
+    /// Receives the message of the data that the peer cannot serve, which is left to be announced by other peers
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
+    fn receive_not_found(&mut self, header: MessageHeader) -> Result<(), ErrorNode> {
+        let not_found = NotFoundMessage::deserialize_message(&mut self.peer, header)?;
+
+        let _ = self.logger.log_connection(format!(
+            "Peer {} could not find {} of the elements requested",
+            self.id,
+            not_found.inventory_vectors.len()
+        ));
+
+        for inventory_vector in not_found.inventory_vectors.iter() {
+            self.requested_blocks.remove(&inventory_vector.hash_value);
+        }
+
+        Ok(())
+    }
+
+    /// Receives the message of a peer rejecting one of our messages
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
+    fn receive_reject(&mut self, header: MessageHeader) -> Result<(), ErrorNode> {
+        let reject = RejectMessage::deserialize_message(&mut self.peer, header)?;
+
+        let _ = self.logger.log_connection(format!(
+            "Peer {} rejected the message {} with code {}: {}",
+            self.id, reject.message, reject.code, reject.reason
+        ));
+
+        Ok(())
+    }
+
+    /// Receives the message of a new transaction, and send it to others threads via the sender unless a peer sent it recently
     ///
     /// ### Error
     ///  * `ErrorNode::WhileSerializing`: It will appear when there is an error in the serialization
@@ -246,6 +584,22 @@ where
             tx_message.transaction
         ));
 
+        self.inventory_relay
+            .receive_transaction(tx_message.transaction.clone());
+
+        if let Ok(transaction_id) = tx_message.transaction.get_tx_id() {
+            if !self.mark_as_seen(transaction_id) {
+                let _ = self.logger.log_debug(
+                    Level::CONNECTION,
+                    format!(
+                        "Transaction {} was already received",
+                        tx_message.transaction
+                    ),
+                );
+                return Ok(());
+            }
+        }
+
         if self
             .sender
             .send(MessageResponse::Transaction(
@@ -269,17 +623,29 @@ where
     ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
     ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending a message to others threads
     fn receive_inventory_message(&mut self, header: MessageHeader) -> Result<(), ErrorNode> {
-        let _ = self
-            .logger
-            .log_connection("Receiving a inventory message".to_string());
+        let _ = self.logger.log_debug(
+            Level::CONNECTION,
+            "Receiving a inventory message".to_string(),
+        );
         let inventory_message = InventoryMessage::deserialize_message(&mut self.peer, header)?;
 
         let mut inventory_vectors: Vec<InventoryVector> = Vec::new();
         for inventory_vector in inventory_message.inventory_vectors {
+            if self.is_recently_seen(&inventory_vector.hash_value) {
+                continue;
+            }
+
             match inventory_vector.type_identifier.clone() {
-                TypeIdentifier::TransactionId | TypeIdentifier::Block => {
+                TypeIdentifier::TransactionId => {
                     inventory_vectors.push(inventory_vector);
                 }
+                TypeIdentifier::Block => {
+                    self.requested_blocks.insert(inventory_vector.hash_value);
+                    inventory_vectors.push(InventoryVector::new(
+                        self.get_block_type_identifier(),
+                        inventory_vector.hash_value,
+                    ));
+                }
                 _ => {}
             }
         }
@@ -305,16 +671,33 @@ where
         Ok(())
     }
 
-    /// Creates a response to a get headers message
+    /// Creates a response to a get headers message, sending the headers of our blockchain that follow the locator
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
+    ///  * `ErrorNode::WhileCreatingMessage`: It will appear when the blockchain cannot be locked
+    ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending the message to the peer
     fn replay_to_get_headers_message(&mut self, header: MessageHeader) -> Result<(), ErrorNode> {
-        let magic_numbers = header.magic_numbers;
         let get_headers = GetHeadersMessage::deserialize_message(&mut self.peer, header)?;
         let headers = self.generate_headers_message(get_headers)?;
-        HeadersMessage::serialize_message(&mut self.peer, magic_numbers, &headers)?;
+
+        let _ = self.logger.log_connection(format!(
+            "Sending {} headers to peer {}",
+            headers.headers.len(),
+            self.id
+        ));
+
+        if HeadersMessage::serialize_message(&mut self.peer, self.magic_numbers, &headers).is_err()
+        {
+            return Err(ErrorNode::WhileSendingMessage(
+                "Sending headers message to peer".to_string(),
+            ));
+        }
+
         Ok(())
     }
 
-    /// Creates a response to a get headers message
+    /// Creates a response to a get headers message. The headers of a headers message do not have transactions
     ///
     /// ### Error
     /// * `ErrorNode::WhileCreatingMessage`: It will appear when there is an error while creating the message
@@ -322,7 +705,7 @@ where
         &self,
         get_headers_message: GetHeadersMessage,
     ) -> Result<HeadersMessage, ErrorNode> {
-        let mut blockchain = match self.blockchain.lock() {
+        let blockchain = match self.blockchain.lock() {
             Ok(blockchain) => blockchain,
             Err(_) => {
                 return Err(ErrorNode::WhileCreatingMessage(
@@ -330,135 +713,564 @@ where
                 ))
             }
         };
-        let most_recent_hash = match blockchain
-            .get_most_recent_hash(get_headers_message.header_locator_hashes)
-        {
-            Ok(most_recent_hash) => most_recent_hash,
-            Err(_) => {
-                return Err(ErrorNode::WhileCreatingMessage(
-                    "While getting the most recent hash to create the headers message".to_string(),
-                ))
-            }
-        };
-        let headers_to_send = match blockchain
-            .get_headers_from_header_hash(&most_recent_hash, &get_headers_message.stop_hash)
-        {
-            Ok(headers_to_send) => headers_to_send,
-            Err(_) => {
-                return Err(ErrorNode::WhileCreatingMessage(
-                    "While getting the headers to send to create the headers message".to_string(),
-                ))
-            }
-        };
-        Ok(HeadersMessage {
-            headers: headers_to_send,
-        })
+
+        let headers = blockchain
+            .get_headers_from_locator(
+                &get_headers_message.header_locator_hashes,
+                &get_headers_message.stop_hash,
+            )
+            .into_iter()
+            .map(|mut header| {
+                header.transaction_count = CompactSize::new(0);
+                header
+            })
+            .collect();
+
+        Ok(HeadersMessage { headers })
     }
 
-    /// Creates a response to a get data message
+    /// Creates a response to a get data message, sending the transactions announced to the peer
+    /// and the blocks announced or downloaded in our blockchain
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
+    ///  * `ErrorNode::WhileCreatingMessage`: It will appear when the blockchain cannot be locked
+    ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending a message to the peer
     fn reply_to_get_data_message(&mut self, header: MessageHeader) -> Result<(), ErrorNode> {
-        let magic_numbers = header.magic_numbers;
         let get_data_message = GetDataMessage::deserialize_message(&mut self.peer, header)?;
 
         for inventory_vector in get_data_message.inventory_vectors.iter() {
-            if let TypeIdentifier::Block = inventory_vector.type_identifier {
-                let blockchain = match self.blockchain.lock() {
-                    Ok(blockchain) => blockchain,
-                    Err(_) => {
-                        return Err(ErrorNode::WhileCreatingMessage(
-                            "While locking the blockchain to create the get data message"
-                                .to_string(),
-                        ))
+            match inventory_vector.type_identifier {
+                TypeIdentifier::TransactionId => {
+                    if let Some(transaction) = self
+                        .inventory_relay
+                        .get_transaction(&inventory_vector.hash_value)
+                        .cloned()
+                    {
+                        self.send_transaction(transaction)?;
                     }
-                };
-                if let Some(block) = blockchain.get_block_with_hash(&inventory_vector.hash_value) {
-                    BlockMessage::serialize_message(&mut self.peer, magic_numbers, &block)?;
                 }
+                TypeIdentifier::WitnessTransactionId => {
+                    if let Some(transaction) = self
+                        .inventory_relay
+                        .get_transaction_with_wtx_id(&inventory_vector.hash_value)
+                        .cloned()
+                    {
+                        self.send_transaction(transaction)?;
+                    }
+                }
+                TypeIdentifier::Block => {
+                    if let Some(block) = self.get_block_to_send(&inventory_vector.hash_value)? {
+                        self.send_block(block)?;
+                    }
+                }
+                TypeIdentifier::CompactBlock => {
+                    if let Some(block) = self.get_block_to_send(&inventory_vector.hash_value)? {
+                        self.send_compact_block(&block)?;
+                    }
+                }
+                _ => {}
             }
         }
         Ok(())
     }
 
-    /// Sends a transaction to the peer
+    /// Returns the block announced to the peer or downloaded in our blockchain with the given hash
     ///
     /// ### Error
-    ///  * `ErrorNode::WhileSerializing`: It will appear when there is an error in the serialization
-    ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
-    ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending a message to others threads
-    fn send_transaction(&mut self, transaction: Transaction) -> Result<(), ErrorNode> {
-        let tx_message = TxMessage { transaction };
+    ///  * `ErrorNode::WhileCreatingMessage`: It will appear when the blockchain cannot be locked
+    fn get_block_to_send(&self, block_hash: &HashType) -> Result<Option<Block>, ErrorNode> {
+        if let Some(block) = self.inventory_relay.get_block(block_hash) {
+            return Ok(Some(block.clone()));
+        }
 
-        if TxMessage::serialize_message(&mut self.peer, self.magic_numbers, &tx_message).is_err() {
-            return Err(ErrorNode::WhileSendingMessage(
-                "Sending transaction to peers".to_string(),
+        match self.blockchain.lock() {
+            Ok(blockchain) => Ok(blockchain
+                .get_block_with_hash(block_hash)
+                .filter(|block| block.is_complete())),
+            Err(_) => Err(ErrorNode::WhileCreatingMessage(
+                "While locking the blockchain to get the block to send".to_string(),
+            )),
+        }
+    }
+
+    /// Returns the type used to request blocks, asking for compact blocks if the peer supports them
+    fn get_block_type_identifier(&self) -> TypeIdentifier {
+        match self.compact_block_relay.is_supported() {
+            true => TypeIdentifier::CompactBlock,
+            false => TypeIdentifier::Block,
+        }
+    }
+
+    /// Receives the send compact message of the peer, to know if compact blocks can be used with it
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
+    fn receive_send_compact(&mut self, header: MessageHeader) -> Result<(), ErrorNode> {
+        let send_compact = SendCmpctMessage::deserialize_message(&mut self.peer, header)?;
+        if !self.id.features.supports_send_compact() {
+            let _ = self.logger.log_connection(format!(
+                "Ignoring send compact message of peer {}, the negotiated version {:?} does not support it",
+                self.id, self.id.features.version
             ));
+            return Ok(());
         }
 
+        self.compact_block_relay.receive_send_compact(&send_compact);
+
+        let _ = self.logger.log_connection(format!(
+            "Peer {} supports compact blocks: {}",
+            self.id,
+            self.compact_block_relay.is_supported()
+        ));
+
         Ok(())
     }
 
-    /// Sends a block to the peer
+    /// Receives the fee filter of the peer, which is only expected if the negotiated version supports it
     ///
     /// ### Error
-    ///  * `ErrorNode::WhileSerializing`: It will appear when there is an error in the serialization
     ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
-    ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending a message to others threads
-    fn send_block(&mut self, block: Block) -> Result<(), ErrorNode> {
-        let block_message = BlockMessage { block };
+    fn receive_fee_filter(&mut self, header: MessageHeader) -> Result<(), ErrorNode> {
+        let fee_filter = FeeFilterMessage::deserialize_message(&mut self.peer, header)?;
+
+        let _ = match self.id.features.supports_fee_filter() {
+            true => self.logger.log_connection(format!(
+                "Peer {} only wants transactions with a fee rate of at least {}",
+                self.id, fee_filter.feerate
+            )),
+            false => self.logger.log_connection(format!(
+                "Ignoring fee filter message of peer {}, the negotiated version {:?} does not support it",
+                self.id, self.id.features.version
+            )),
+        };
 
-        if BlockMessage::serialize_message(&mut self.peer, self.magic_numbers, &block_message)
-            .is_err()
+        Ok(())
+    }
+
+    /// Receives a compact block and rebuilds it with the transactions we know, requesting the missing ones.
+    /// If the block cannot be rebuilt it's requested complete
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
+    ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending a message to the peer or others threads
+    fn receive_compact_block(&mut self, header: MessageHeader) -> Result<(), ErrorNode> {
+        let compact_block = CompactBlockMessage::deserialize_message(&mut self.peer, header)?;
+        let block_hash = compact_block.header.get_hash256d()?;
+        if self.is_recently_seen(&block_hash) {
+            return Ok(());
+        }
+
+        let partial_block =
+            match PartialBlock::new(compact_block, self.inventory_relay.get_transactions()) {
+                Some(partial_block) => partial_block,
+                None => return self.request_block(block_hash),
+            };
+
+        let missing_indexes = partial_block.get_missing_indexes();
+        if missing_indexes.is_empty() {
+            return self.rebuild_block(block_hash, partial_block);
+        }
+
+        let _ = self.logger.log_connection(format!(
+            "Requesting {} transactions of a compact block to peer {}",
+            missing_indexes.len(),
+            self.id
+        ));
+
+        self.compact_block_relay
+            .wait_transactions(block_hash, partial_block);
+
+        let get_block_transactions = GetBlockTransactionsMessage {
+            block_hash,
+            indexes: missing_indexes,
+        };
+
+        if GetBlockTransactionsMessage::serialize_message(
+            &mut self.peer,
+            self.magic_numbers,
+            &get_block_transactions,
+        )
+        .is_err()
         {
             return Err(ErrorNode::WhileSendingMessage(
-                "Sending transaction to peers".to_string(),
+                "Sending get block transactions message to peer".to_string(),
             ));
         }
 
         Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
+    /// Receives the transactions missing to rebuild a compact block
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
+    ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending a message to the peer or others threads
+    fn receive_block_transactions(&mut self, header: MessageHeader) -> Result<(), ErrorNode> {
+        let block_transactions =
+            BlockTransactionsMessage::deserialize_message(&mut self.peer, header)?;
+        let block_hash = block_transactions.block_hash;
+
+        let mut partial_block = match self.compact_block_relay.take_partial_block(&block_hash) {
+            Some(partial_block) => partial_block,
+            None => {
+                let _ = self.logger.log_connection(format!(
+                    "Peer {} sent transactions of a block that was not requested",
+                    self.id
+                ));
+                return Ok(());
+            }
+        };
 
-    use super::*;
+        match partial_block.fill(block_transactions.transactions) {
+            true => self.rebuild_block(block_hash, partial_block),
+            false => self.request_block(block_hash),
+        }
+    }
 
-    use crate::{
-        block_structure::{
-            block::Block, block_header::BlockHeader, block_version::BlockVersion,
-            compact256::Compact256, outpoint::Outpoint, transaction::Transaction,
-            transaction_input::TransactionInput, transaction_output::TransactionOutput,
-        },
-        connections::type_identifier::TypeIdentifier,
-        logs::logger,
-        messages::{compact_size::CompactSize, inventory_vector::InventoryVector, message},
-        node_structure::connection_type::ConnectionType,
-        notifications::{notification::Notification, notifier::Notifier},
-        serialization::error_serialization::ErrorSerialization,
-    };
+    /// Sends the rebuilt block to others threads, or requests it complete if the transactions do not match the header
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending a message to the peer or others threads
+    fn rebuild_block(
+        &mut self,
+        block_hash: HashType,
+        partial_block: PartialBlock,
+    ) -> Result<(), ErrorNode> {
+        let block = match partial_block.get_block() {
+            Some(block) => block,
+            None => return self.request_block(block_hash),
+        };
 
-    use std::{
-        net::{IpAddr, Ipv4Addr, SocketAddr},
-        sync::mpsc::channel,
-    };
+        let _ = self
+            .logger
+            .log_connection(format!("Rebuilt a block from a compact block: {}", block));
 
-    #[derive(Clone)]
-    struct NotificationMock {}
+        if !self.mark_as_seen(block_hash) {
+            return Ok(());
+        }
 
-    impl Notifier for NotificationMock {
-        fn notify(&self, _notification: Notification) {}
+        if self
+            .sender
+            .send(MessageResponse::Block(block, self.id))
+            .is_err()
+        {
+            return Err(ErrorNode::WhileSendingMessage(
+                "Sending block back".to_string(),
+            ));
+        }
+
+        Ok(())
     }
 
-    struct Stream {
-        write_stream: Vec<u8>,
-        read_stream: Vec<u8>,
-        pointer: usize,
+    /// Adds the ip of the peer to the ban list, so the next connections from it are rejected
+    fn ban(&self) {
+        let mut banned = match self.banned.lock() {
+            Ok(banned) => banned,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        banned.insert(self.id.address.ip().to_canonical());
     }
 
-    impl Stream {
-        pub fn new(read_stream: Vec<u8>) -> Stream {
-            Stream {
-                read_stream,
+    /// Remembers the transaction or block as received, returning false if any peer sent it recently
+    fn mark_as_seen(&self, hash: HashType) -> bool {
+        let mut recent_inventory = match self.recent_inventory.lock() {
+            Ok(recent_inventory) => recent_inventory,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        recent_inventory.insert(hash, Instant::now())
+    }
+
+    /// Returns true if the transaction or block was received recently from any peer
+    fn is_recently_seen(&self, hash: &HashType) -> bool {
+        let recent_inventory = match self.recent_inventory.lock() {
+            Ok(recent_inventory) => recent_inventory,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        recent_inventory.contains(hash, Instant::now())
+    }
+
+    /// Requests the complete block to the peer
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending the message to the peer
+    fn request_block(&mut self, block_hash: HashType) -> Result<(), ErrorNode> {
+        let _ = self.logger.log_connection(format!(
+            "Compact block could not be rebuilt, requesting the complete block to peer {}",
+            self.id
+        ));
+
+        self.requested_blocks.insert(block_hash);
+        let get_data_message = GetDataMessage::get_blocks(vec![block_hash]);
+
+        if GetDataMessage::serialize_message(&mut self.peer, self.magic_numbers, &get_data_message)
+            .is_err()
+        {
+            return Err(ErrorNode::WhileSendingMessage(
+                "Sending get data message to peer".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Creates a response to a get block transactions message, sending the transactions of the block
+    /// in the requested positions
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
+    ///  * `ErrorNode::WhileCreatingMessage`: It will appear when the blockchain cannot be locked
+    ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending the message to the peer
+    fn reply_to_get_block_transactions_message(
+        &mut self,
+        header: MessageHeader,
+    ) -> Result<(), ErrorNode> {
+        let get_block_transactions =
+            GetBlockTransactionsMessage::deserialize_message(&mut self.peer, header)?;
+
+        let block = match self.get_block_to_send(&get_block_transactions.block_hash)? {
+            Some(block) => block,
+            None => return Ok(()),
+        };
+
+        let mut transactions: Vec<Transaction> = Vec::new();
+        for index in get_block_transactions.indexes {
+            match block.transactions.get(index as usize) {
+                Some(transaction) => transactions.push(transaction.clone()),
+                None => {
+                    let _ = self.logger.log_connection(format!(
+                        "Peer {} requested a transaction out of the block",
+                        self.id
+                    ));
+                    return Ok(());
+                }
+            }
+        }
+
+        let block_transactions = BlockTransactionsMessage {
+            block_hash: get_block_transactions.block_hash,
+            transactions,
+        };
+
+        if BlockTransactionsMessage::serialize_message(
+            &mut self.peer,
+            self.magic_numbers,
+            &block_transactions,
+        )
+        .is_err()
+        {
+            return Err(ErrorNode::WhileSendingMessage(
+                "Sending block transactions message to peer".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Announces a transaction to the peer with an inventory message, the transaction is sent when the peer requests it.
+    /// If the peer asked for it in the handshake, the transaction is announced by its witness id
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending the message to the peer
+    fn announce_transaction(&mut self, transaction: Transaction) -> Result<(), ErrorNode> {
+        let wtx_id = match transaction.get_wtx_id() {
+            Ok(wtx_id) => wtx_id,
+            Err(error) => {
+                return Err(ErrorNode::WhileCreatingMessage(format!(
+                    "Could not get the witness id of the transaction to announce: {:?}",
+                    error
+                )))
+            }
+        };
+
+        match self.inventory_relay.announce_transaction(transaction) {
+            Some(_) if self.wtxid_relay => self.send_inventory(InventoryVector::new(
+                TypeIdentifier::WitnessTransactionId,
+                wtx_id,
+            )),
+            Some(inventory_vector) => self.send_inventory(inventory_vector),
+            None => Ok(()),
+        }
+    }
+
+    /// Announces a block to the peer with an inventory message, the block is sent when the peer requests it.
+    /// If the peer asked for it, the block is announced with a compact block instead
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending the message to the peer
+    fn announce_block(&mut self, block: Block) -> Result<(), ErrorNode> {
+        match self.inventory_relay.announce_block(block.clone()) {
+            Some(_) if self.compact_block_relay.should_announce() => {
+                self.send_compact_block(&block)
+            }
+            Some(inventory_vector) => self.send_inventory(inventory_vector),
+            None => Ok(()),
+        }
+    }
+
+    /// Sends an inventory message to the peer
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending the message to the peer
+    fn send_inventory(&mut self, inventory_vector: InventoryVector) -> Result<(), ErrorNode> {
+        let _ = self.logger.log_connection(format!(
+            "Announcing {:?} to peer {}",
+            inventory_vector.type_identifier, self.id
+        ));
+
+        let inventory_message = InventoryMessage::new(vec![inventory_vector]);
+
+        if InventoryMessage::serialize_message(
+            &mut self.peer,
+            self.magic_numbers,
+            &inventory_message,
+        )
+        .is_err()
+        {
+            return Err(ErrorNode::WhileSendingMessage(
+                "Sending inventory message to peer".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Asks the peer for the transactions in its mempool. The peer answers with inventory messages, so the
+    /// transactions are received as any other announced transaction
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending the message to the peer
+    fn request_mempool(&mut self) -> Result<(), ErrorNode> {
+        let _ = self
+            .logger
+            .log_connection(format!("Requesting the mempool of peer {}", self.id));
+
+        if MempoolMessage::serialize_message(&mut self.peer, self.magic_numbers, &MempoolMessage)
+            .is_err()
+        {
+            return Err(ErrorNode::WhileSendingMessage(
+                "Sending mempool message to peer".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Sends a transaction to the peer
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileSerializing`: It will appear when there is an error in the serialization
+    ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
+    ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending a message to others threads
+    fn send_transaction(&mut self, transaction: Transaction) -> Result<(), ErrorNode> {
+        let tx_message = TxMessage { transaction };
+
+        if TxMessage::serialize_message(&mut self.peer, self.magic_numbers, &tx_message).is_err() {
+            return Err(ErrorNode::WhileSendingMessage(
+                "Sending transaction to peers".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Sends a block to the peer
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileSerializing`: It will appear when there is an error in the serialization
+    ///  * `ErrorNode::WhileDeserialization`: It will appear when there is an error in the deserialization
+    ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending a message to others threads
+    fn send_block(&mut self, block: Block) -> Result<(), ErrorNode> {
+        let block_message = BlockMessage { block };
+
+        if BlockMessage::serialize_message(&mut self.peer, self.magic_numbers, &block_message)
+            .is_err()
+        {
+            return Err(ErrorNode::WhileSendingMessage(
+                "Sending transaction to peers".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Sends the compact block of a block to the peer
+    ///
+    /// ### Error
+    ///  * `ErrorNode::WhileSerializing`: It will appear when the short ids of the block cannot be calculated
+    ///  * `ErrorNode::WhileSendingMessage`: It will appear when there is an error while sending the message to the peer
+    fn send_compact_block(&mut self, block: &Block) -> Result<(), ErrorNode> {
+        let compact_block = self.compact_block_relay.create_compact_block(block)?;
+
+        let _ = self.logger.log_connection(format!(
+            "Sending compact block {} to peer {}",
+            block, self.id
+        ));
+
+        if CompactBlockMessage::serialize_message(
+            &mut self.peer,
+            self.magic_numbers,
+            &compact_block,
+        )
+        .is_err()
+        {
+            return Err(ErrorNode::WhileSendingMessage(
+                "Sending compact block to peer".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use crate::{
+        block_structure::{
+            block::Block, block_header::BlockHeader, block_version::BlockVersion,
+            compact256::Compact256, merkle_tree::MerkleTree, outpoint::Outpoint, script::ScriptBuf,
+            transaction::Transaction, transaction_input::TransactionInput,
+            transaction_output::TransactionOutput,
+        },
+        connections::{p2p_protocol::ProtocolVersionP2P, type_identifier::TypeIdentifier},
+        logs::logger,
+        messages::{inventory_vector::InventoryVector, message},
+        node_structure::{
+            compact_block_relay::COMPACT_BLOCK_VERSION, connection_type::ConnectionType,
+            peer_features::PeerFeatures,
+        },
+        notifications::{notification::Notification, notifier::Notifier},
+        serialization::{
+            error_serialization::ErrorSerialization,
+            serializable_internal_order::SerializableInternalOrder,
+        },
+    };
+
+    use std::{
+        net::{IpAddr, Ipv4Addr, SocketAddr},
+        sync::mpsc::channel,
+    };
+
+    #[derive(Clone)]
+    struct NotificationMock {}
+
+    impl Notifier for NotificationMock {
+        fn notify(&self, _notification: Notification) {}
+    }
+
+    struct Stream {
+        write_stream: Vec<u8>,
+        read_stream: Vec<u8>,
+        pointer: usize,
+    }
+
+    impl Stream {
+        pub fn new(read_stream: Vec<u8>) -> Stream {
+            Stream {
+                read_stream,
                 write_stream: Vec::new(),
                 pointer: 0,
             }
@@ -542,6 +1354,25 @@ mod tests {
         InventoryMessage::serialize_message(stream, magic_numbers, &inventory_message)
     }
 
+    fn serialize_get_data_message<W: Write>(
+        stream: &mut W,
+        magic_numbers: [u8; 4],
+        inventory_vectors: Vec<InventoryVector>,
+    ) -> Result<(), ErrorSerialization> {
+        let get_data_message = GetDataMessage::new(inventory_vectors);
+        GetDataMessage::serialize_message(stream, magic_numbers, &get_data_message)
+    }
+
+    fn serialize_get_headers_message<W: Write>(
+        stream: &mut W,
+        magic_numbers: [u8; 4],
+        header_locator_hashes: Vec<HashType>,
+    ) -> Result<(), ErrorSerialization> {
+        let get_headers_message =
+            GetHeadersMessage::new(ProtocolVersionP2P::V70015, header_locator_hashes, [0; 32]);
+        GetHeadersMessage::serialize_message(stream, magic_numbers, &get_headers_message)
+    }
+
     fn serialize_ping_message<W: Write>(
         stream: &mut W,
         magic_numbers: [u8; 4],
@@ -556,14 +1387,30 @@ mod tests {
 
         let transaction_output = TransactionOutput {
             value: 10,
-            pk_script: vec![4, 5, 6],
+            pk_script: ScriptBuf::from(vec![4, 5, 6]),
         };
 
         Transaction {
             version: 1,
             tx_in: vec![transaction_input.clone()],
             tx_out: vec![transaction_output.clone()],
-            time,
+            lock_time: time,
+        }
+    }
+
+    fn create_coinbase(time: u32) -> Transaction {
+        Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(
+                Outpoint::new([0; 32], u32::MAX),
+                vec![1, 2, 3],
+                u32::MAX,
+            )],
+            tx_out: vec![TransactionOutput {
+                value: 10,
+                pk_script: ScriptBuf::from(vec![4, 5, 6]),
+            }],
+            lock_time: time,
         }
     }
 
@@ -592,14 +1439,14 @@ mod tests {
 
         let transaction_output = TransactionOutput {
             value: 10,
-            pk_script: "Prueba out".as_bytes().to_vec(),
+            pk_script: ScriptBuf::from("Prueba out".as_bytes().to_vec()),
         };
 
         let transaction = Transaction {
             version: 1,
             tx_in: vec![transaction_input.clone()],
             tx_out: vec![transaction_output.clone()],
-            time: 0,
+            lock_time: 0,
         };
 
         let empty_block = Block::new(BlockHeader::new(
@@ -675,10 +1522,12 @@ mod tests {
         let magic_numbers = [11, 17, 9, 7];
 
         let mut block = create_empty_block(3);
-        block.append_transaction(create_transaction(0)).unwrap();
+        block.append_transaction(create_coinbase(0)).unwrap();
         block.append_transaction(create_transaction(1)).unwrap();
         block.append_transaction(create_transaction(2)).unwrap();
+        block.header.merkle_root_hash = MerkleTree::new(&block.transactions).unwrap().root;
 
+        serialize_headers_message(&mut stream, magic_numbers, vec![block.header]).unwrap();
         serialize_block_message(&mut stream, magic_numbers.clone(), block.clone()).unwrap();
 
         let stream = Stream::new(stream);
@@ -840,7 +1689,7 @@ mod tests {
     }
 
     #[test]
-    fn test05_peer_manager_sends_transaction_successfully() {
+    fn test05_peer_manager_announces_transaction_successfully() {
         let mut stream = Vec::new();
         let magic_numbers = [11, 17, 9, 7];
 
@@ -886,12 +1735,603 @@ mod tests {
 
         let _ = PongMessage::deserialize_message(&mut stream, header).unwrap();
 
-        let header = message::deserialize_until_found(&mut stream, CommandName::Tx).unwrap();
+        let header = message::deserialize_until_found(&mut stream, CommandName::Inventory).unwrap();
+
+        assert_eq!(header.command_name, CommandName::Inventory);
+
+        let inventory_message = InventoryMessage::deserialize_message(&mut stream, header).unwrap();
+
+        assert_eq!(
+            vec![InventoryVector::new(
+                TypeIdentifier::TransactionId,
+                transaction.get_tx_id().unwrap()
+            )],
+            inventory_message.inventory_vectors
+        );
+        assert!(message::deserialize_until_found(&mut stream, CommandName::Tx).is_err());
+    }
+
+    #[test]
+    fn test06_peer_manager_sends_ping_to_keep_alive() {
+        let stream = Stream::new(Vec::new());
+        let magic_numbers = [11, 17, 9, 7];
+
+        let (sender_message, _) = channel::<MessageResponse>();
+        let (sender_to_peer, receiver_to_peer) = channel::<MessageToPeer>();
+        let notifier = NotificationMock {};
+        let blockchain: Arc<Mutex<BlockChain>> = Arc::new(Mutex::new(create_mock_blockchain()));
+
+        let id_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8333);
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+        let peer_manager = PeerManager::new(
+            ConnectionId::new(id_address, ConnectionType::Peer),
+            stream,
+            sender_message,
+            blockchain,
+            magic_numbers,
+            notifier,
+            sender,
+        );
+
+        sender_to_peer.send(MessageToPeer::KeepAlive).unwrap();
+        sender_to_peer.send(MessageToPeer::KeepAlive).unwrap();
+        sender_to_peer.send(MessageToPeer::Stop).unwrap();
+
+        let (stream, _) = peer_manager.connecting_to_peer(receiver_to_peer).unwrap();
+        let mut stream = stream.get_write_stream();
+
+        let header = message::deserialize_until_found(&mut stream, CommandName::Ping).unwrap();
+        let _ = PingMessage::deserialize_message(&mut stream, header).unwrap();
 
-        assert_eq!(header.command_name, CommandName::Tx);
+        assert!(message::deserialize_until_found(&mut stream, CommandName::Ping).is_err());
+    }
+
+    #[test]
+    fn test07_peer_manager_replies_to_get_data_message() {
+        let mut stream = Vec::new();
+        let magic_numbers = [11, 17, 9, 7];
+
+        let block_hash = create_header(1).get_hash256d().unwrap();
+        let transaction_id = create_transaction(0).get_tx_id().unwrap();
+
+        serialize_get_data_message(
+            &mut stream,
+            magic_numbers,
+            vec![
+                InventoryVector::new(TypeIdentifier::TransactionId, transaction_id),
+                InventoryVector::new(TypeIdentifier::Block, block_hash),
+            ],
+        )
+        .unwrap();
+
+        let stream = Stream::new(stream);
+
+        let (sender_message, _) = channel::<MessageResponse>();
+        let (sender_to_peer, receiver_to_peer) = channel::<MessageToPeer>();
+        let notifier = NotificationMock {};
+        let mut expected_block = create_empty_block(1);
+        expected_block
+            .append_transaction(create_transaction(1))
+            .unwrap();
+        let blockchain = BlockChain::new(expected_block.clone()).unwrap();
+        let blockchain: Arc<Mutex<BlockChain>> = Arc::new(Mutex::new(blockchain));
 
-        let transaction_message = TxMessage::deserialize_message(&mut stream, header).unwrap();
+        let id_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8333);
 
-        assert_eq!(transaction, transaction_message.transaction);
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+        let peer_manager = PeerManager::new(
+            ConnectionId::new(id_address, ConnectionType::Peer),
+            stream,
+            sender_message,
+            blockchain,
+            magic_numbers,
+            notifier,
+            sender,
+        );
+
+        sender_to_peer.send(MessageToPeer::Stop).unwrap();
+
+        let (peer_stream, _) = peer_manager.connecting_to_peer(receiver_to_peer).unwrap();
+        let mut stream = peer_stream.get_write_stream();
+
+        let header = message::deserialize_until_found(&mut stream, CommandName::Block).unwrap();
+        let block_message = BlockMessage::deserialize_message(&mut stream, header).unwrap();
+
+        assert_eq!(expected_block, block_message.block);
+
+        let mut stream = peer_stream.get_write_stream();
+        assert!(message::deserialize_until_found(&mut stream, CommandName::Tx).is_err());
+    }
+
+    #[test]
+    fn test08_peer_manager_replies_to_get_headers_message() {
+        let mut stream = Vec::new();
+        let magic_numbers = [11, 17, 9, 7];
+
+        let first_block = create_empty_block(0);
+        let mut second_block = Block::new(BlockHeader::new(
+            BlockVersion::version(1),
+            first_block.header.get_hash256d().unwrap(),
+            [0; 32],
+            0,
+            Compact256::from(u32::MAX),
+            0,
+            CompactSize::new(1),
+        ));
+        second_block
+            .append_transaction(create_transaction(0))
+            .unwrap();
+
+        serialize_get_headers_message(
+            &mut stream,
+            magic_numbers,
+            vec![first_block.header.get_hash256d().unwrap()],
+        )
+        .unwrap();
+
+        let stream = Stream::new(stream);
+
+        let (sender_message, _) = channel::<MessageResponse>();
+        let (sender_to_peer, receiver_to_peer) = channel::<MessageToPeer>();
+        let notifier = NotificationMock {};
+        let mut blockchain = BlockChain::new(first_block).unwrap();
+        blockchain.append_block(second_block.clone()).unwrap();
+        let blockchain: Arc<Mutex<BlockChain>> = Arc::new(Mutex::new(blockchain));
+
+        let id_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8333);
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+        let peer_manager = PeerManager::new(
+            ConnectionId::new(id_address, ConnectionType::Peer),
+            stream,
+            sender_message,
+            blockchain,
+            magic_numbers,
+            notifier,
+            sender,
+        );
+
+        sender_to_peer.send(MessageToPeer::Stop).unwrap();
+
+        let (peer_stream, _) = peer_manager.connecting_to_peer(receiver_to_peer).unwrap();
+        let mut stream = peer_stream.get_write_stream();
+
+        let header = message::deserialize_until_found(&mut stream, CommandName::Headers).unwrap();
+        let headers_message = HeadersMessage::deserialize_message(&mut stream, header).unwrap();
+
+        let mut expected_header = second_block.header;
+        expected_header.transaction_count = CompactSize::new(0);
+
+        assert_eq!(headers_message.headers, vec![expected_header]);
+    }
+
+    #[test]
+    fn test09_peer_manager_rebuilds_compact_block() {
+        let mut stream = Vec::new();
+        let magic_numbers = [11, 17, 9, 7];
+
+        let transactions: Vec<Transaction> = (0..3).map(create_transaction).collect();
+        let block = Block {
+            header: BlockHeader::new(
+                BlockVersion::version(1),
+                [0; 32],
+                MerkleTree::new(&transactions).unwrap().root,
+                0,
+                Compact256::from(u32::MAX),
+                0,
+                CompactSize::new(3),
+            ),
+            transactions: transactions.clone(),
+        };
+        let block_hash = block.header.get_hash256d().unwrap();
+
+        SendCmpctMessage::serialize_message(
+            &mut stream,
+            magic_numbers,
+            &SendCmpctMessage {
+                announce: false,
+                version: COMPACT_BLOCK_VERSION,
+            },
+        )
+        .unwrap();
+        serialize_tx_message(&mut stream, magic_numbers, transactions[1].clone()).unwrap();
+        CompactBlockMessage::serialize_message(
+            &mut stream,
+            magic_numbers,
+            &CompactBlockMessage::new(&block, 3).unwrap(),
+        )
+        .unwrap();
+        BlockTransactionsMessage::serialize_message(
+            &mut stream,
+            magic_numbers,
+            &BlockTransactionsMessage {
+                block_hash,
+                transactions: vec![transactions[2].clone()],
+            },
+        )
+        .unwrap();
+
+        let stream = Stream::new(stream);
+
+        let (sender_message, receiver_message) = channel::<MessageResponse>();
+        let (sender_to_peer, receiver_to_peer) = channel::<MessageToPeer>();
+        let notifier = NotificationMock {};
+        let blockchain: Arc<Mutex<BlockChain>> =
+            Arc::new(Mutex::new(BlockChain::new(create_empty_block(0)).unwrap()));
+
+        let id_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8333);
+        let id = ConnectionId::new(id_address, ConnectionType::Peer);
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+        let peer_manager = PeerManager::new(
+            id,
+            stream,
+            sender_message,
+            blockchain,
+            magic_numbers,
+            notifier,
+            sender,
+        );
+
+        sender_to_peer.send(MessageToPeer::Stop).unwrap();
+
+        let (peer_stream, _) = peer_manager.connecting_to_peer(receiver_to_peer).unwrap();
+        let mut stream = peer_stream.get_write_stream();
+
+        let header =
+            message::deserialize_until_found(&mut stream, CommandName::GetBlockTransactions)
+                .unwrap();
+        let get_block_transactions =
+            GetBlockTransactionsMessage::deserialize_message(&mut stream, header).unwrap();
+
+        assert_eq!(
+            get_block_transactions,
+            GetBlockTransactionsMessage {
+                block_hash,
+                indexes: vec![2],
+            }
+        );
+
+        assert_eq!(
+            receiver_message.try_recv().unwrap(),
+            MessageResponse::Transaction(transactions[1].clone(), id)
+        );
+        assert_eq!(
+            receiver_message.try_recv().unwrap(),
+            MessageResponse::Block(block, id)
+        );
+    }
+
+    #[test]
+    fn test10_peer_manager_requests_the_mempool_and_the_announced_transactions() {
+        let mut stream = Vec::new();
+        let magic_numbers = [11, 17, 9, 7];
+
+        let transaction = create_transaction(0);
+        let transaction_id = transaction.get_tx_id().unwrap();
+
+        serialize_inv_message(
+            &mut stream,
+            magic_numbers,
+            vec![InventoryVector::new(
+                TypeIdentifier::TransactionId,
+                transaction_id,
+            )],
+        )
+        .unwrap();
+
+        let stream = Stream::new(stream);
+
+        let (sender_message, _) = channel::<MessageResponse>();
+        let (sender_to_peer, receiver_to_peer) = channel::<MessageToPeer>();
+        let notifier = NotificationMock {};
+        let blockchain: Arc<Mutex<BlockChain>> = Arc::new(Mutex::new(create_mock_blockchain()));
+
+        let id_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8333);
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+        let peer_manager = PeerManager::new(
+            ConnectionId::new(id_address, ConnectionType::Peer),
+            stream,
+            sender_message,
+            blockchain,
+            magic_numbers,
+            notifier,
+            sender,
+        );
+
+        sender_to_peer.send(MessageToPeer::RequestMempool).unwrap();
+        sender_to_peer.send(MessageToPeer::Stop).unwrap();
+
+        let (stream, _) = peer_manager.connecting_to_peer(receiver_to_peer).unwrap();
+
+        let mut write_stream = stream.get_write_stream();
+        let header =
+            message::deserialize_until_found(&mut write_stream, CommandName::Mempool).unwrap();
+        assert_eq!(
+            MempoolMessage::deserialize_message(&mut write_stream, header).unwrap(),
+            MempoolMessage
+        );
+
+        let mut write_stream = stream.get_write_stream();
+        let header =
+            message::deserialize_until_found(&mut write_stream, CommandName::GetData).unwrap();
+        let get_data_message =
+            GetDataMessage::deserialize_message(&mut write_stream, header).unwrap();
+
+        assert_eq!(
+            get_data_message.inventory_vectors,
+            vec![InventoryVector::new(
+                TypeIdentifier::TransactionId,
+                transaction_id
+            )]
+        );
+    }
+
+    #[test]
+    fn test11_peer_manager_announces_transaction_by_witness_id() {
+        let magic_numbers = [11, 17, 9, 7];
+        let transaction = create_transaction(0);
+
+        let stream = Stream::new(Vec::new());
+
+        let (sender_message, _) = channel::<MessageResponse>();
+        let (sender_transaction, receiver_transaction) = channel::<MessageToPeer>();
+        let notifier = NotificationMock {};
+        let blockchain: Arc<Mutex<BlockChain>> = Arc::new(Mutex::new(create_mock_blockchain()));
+
+        let id_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8333);
+        let id = ConnectionId::new(id_address, ConnectionType::Peer).with_features(PeerFeatures {
+            wtxid_relay: true,
+            ..PeerFeatures::default()
+        });
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+        let peer_manager = PeerManager::new(
+            id,
+            stream,
+            sender_message,
+            blockchain,
+            magic_numbers,
+            notifier,
+            sender,
+        );
+
+        sender_transaction
+            .send(MessageToPeer::SendTransaction(transaction.clone(), None))
+            .unwrap();
+        sender_transaction.send(MessageToPeer::Stop).unwrap();
+
+        let (stream, _) = peer_manager
+            .connecting_to_peer(receiver_transaction)
+            .unwrap();
+        let mut stream = stream.get_write_stream();
+
+        let header = message::deserialize_until_found(&mut stream, CommandName::Inventory).unwrap();
+        let inventory_message = InventoryMessage::deserialize_message(&mut stream, header).unwrap();
+
+        assert_eq!(
+            vec![InventoryVector::new(
+                TypeIdentifier::WitnessTransactionId,
+                transaction.get_wtx_id().unwrap()
+            )],
+            inventory_message.inventory_vectors
+        );
+    }
+
+    #[test]
+    fn test12_peer_manager_ignores_transaction_received_by_another_peer() {
+        let magic_numbers = [11, 17, 9, 7];
+        let transaction = create_transaction(0);
+        let recent_inventory = Arc::new(Mutex::new(RecentInventory::default()));
+
+        let (sender_message, receiver_message) = channel::<MessageResponse>();
+        let blockchain: Arc<Mutex<BlockChain>> = Arc::new(Mutex::new(create_mock_blockchain()));
+
+        for port in [8333, 8334] {
+            let mut stream: Vec<u8> = Vec::new();
+            serialize_tx_message(&mut stream, magic_numbers, transaction.clone()).unwrap();
+            let stream = Stream::new(stream);
+
+            let (sender_transaction, receiver_transaction) = channel::<MessageToPeer>();
+            let id_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), port);
+
+            let logger_text: Vec<u8> = Vec::new();
+            let (sender, _) = logger::initialize_logger(logger_text, false);
+            let peer_manager = PeerManager::new(
+                ConnectionId::new(id_address, ConnectionType::Peer),
+                stream,
+                sender_message.clone(),
+                blockchain.clone(),
+                magic_numbers,
+                NotificationMock {},
+                sender,
+            )
+            .with_recent_inventory(recent_inventory.clone());
+
+            sender_transaction.send(MessageToPeer::Stop).unwrap();
+            let _ = peer_manager
+                .connecting_to_peer(receiver_transaction)
+                .unwrap();
+        }
+
+        assert!(matches!(
+            receiver_message.try_recv(),
+            Ok(MessageResponse::Transaction(_, _))
+        ));
+        assert!(receiver_message.try_recv().is_err());
+    }
+
+    fn create_valid_block(time: u32) -> Block {
+        let mut block = create_empty_block(1);
+        block.append_transaction(create_coinbase(time)).unwrap();
+        block.header.merkle_root_hash = MerkleTree::new(&block.transactions).unwrap().root;
+        block
+    }
+
+    fn receive_from_peer(
+        stream: Vec<u8>,
+        magic_numbers: [u8; 4],
+    ) -> (
+        Result<(Stream, ConnectionId), ErrorNode>,
+        Receiver<MessageResponse>,
+    ) {
+        let (sender_message, receiver_message) = channel::<MessageResponse>();
+        let (sender_transaction, receiver_transaction) = channel::<MessageToPeer>();
+        let blockchain: Arc<Mutex<BlockChain>> = Arc::new(Mutex::new(create_mock_blockchain()));
+        let id_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8333);
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+        let peer_manager = PeerManager::new(
+            ConnectionId::new(id_address, ConnectionType::Peer),
+            Stream::new(stream),
+            sender_message,
+            blockchain,
+            magic_numbers,
+            NotificationMock {},
+            sender,
+        );
+
+        sender_transaction.send(MessageToPeer::Stop).unwrap();
+        (
+            peer_manager.connecting_to_peer(receiver_transaction),
+            receiver_message,
+        )
+    }
+
+    #[test]
+    fn test13_peer_manager_disconnects_peer_sending_blocks_not_requested() {
+        let magic_numbers = [11, 17, 9, 7];
+
+        let mut stream = Vec::new();
+        for time in 0..4 {
+            serialize_block_message(&mut stream, magic_numbers, create_valid_block(time)).unwrap();
+        }
+        let (result, receiver_message) = receive_from_peer(stream.clone(), magic_numbers);
+        assert!(result.is_ok());
+        assert!(receiver_message.try_recv().is_err());
+
+        serialize_block_message(&mut stream, magic_numbers, create_valid_block(4)).unwrap();
+        let (result, _) = receive_from_peer(stream, magic_numbers);
+        assert!(matches!(result, Err(ErrorNode::PeerMisbehaving(_))));
+    }
+
+    #[test]
+    fn test14_peer_manager_disconnects_peer_sending_block_with_wrong_merkle_root() {
+        let magic_numbers = [11, 17, 9, 7];
+
+        let mut block = create_empty_block(2);
+        block.append_transaction(create_transaction(0)).unwrap();
+        block.append_transaction(create_transaction(1)).unwrap();
+
+        let mut stream = Vec::new();
+        serialize_headers_message(&mut stream, magic_numbers, vec![block.header]).unwrap();
+        serialize_block_message(&mut stream, magic_numbers, block).unwrap();
+
+        let (result, receiver_message) = receive_from_peer(stream, magic_numbers);
+        assert!(matches!(result, Err(ErrorNode::PeerMisbehaving(_))));
+        assert!(receiver_message.try_recv().is_err());
+    }
+
+    #[test]
+    fn test15_peer_manager_bans_peer_sending_payload_too_large() {
+        let magic_numbers = [11, 17, 9, 7];
+
+        let mut stream = Vec::new();
+        MessageHeader {
+            magic_numbers,
+            command_name: CommandName::Ping,
+            payload_size: 9,
+            checksum: [0; 4],
+        }
+        .io_serialize(&mut stream)
+        .unwrap();
+        stream.extend_from_slice(&[0; 9]);
+
+        let (sender_message, _) = channel::<MessageResponse>();
+        let (sender_transaction, receiver_transaction) = channel::<MessageToPeer>();
+        let blockchain: Arc<Mutex<BlockChain>> = Arc::new(Mutex::new(create_mock_blockchain()));
+        let id_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8333);
+        let banned: Arc<Mutex<HashSet<IpAddr>>> = Arc::new(Mutex::new(HashSet::new()));
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+        let peer_manager = PeerManager::new(
+            ConnectionId::new(id_address, ConnectionType::Peer),
+            Stream::new(stream),
+            sender_message,
+            blockchain,
+            magic_numbers,
+            NotificationMock {},
+            sender,
+        )
+        .with_ban_list(banned.clone());
+
+        sender_transaction.send(MessageToPeer::Stop).unwrap();
+        let result = peer_manager.connecting_to_peer(receiver_transaction);
+        assert!(matches!(result, Err(ErrorNode::PeerMisbehaving(_))));
+        assert!(banned.lock().unwrap().contains(&id_address.ip()));
+    }
+
+    #[test]
+    fn test16_peer_manager_does_not_request_transactions_by_witness_id() {
+        let mut stream = Vec::new();
+        let magic_numbers = [11, 17, 9, 7];
+
+        let transaction = create_transaction(0);
+        let transaction_id = transaction.get_tx_id().unwrap();
+        let other_transaction_id = create_transaction(1).get_tx_id().unwrap();
+
+        serialize_inv_message(
+            &mut stream,
+            magic_numbers,
+            vec![
+                InventoryVector::new(TypeIdentifier::WitnessTransactionId, other_transaction_id),
+                InventoryVector::new(TypeIdentifier::TransactionId, transaction_id),
+            ],
+        )
+        .unwrap();
+
+        let (sender_message, _) = channel::<MessageResponse>();
+        let (sender_transaction, receiver_transaction) = channel::<MessageToPeer>();
+        let blockchain: Arc<Mutex<BlockChain>> = Arc::new(Mutex::new(create_mock_blockchain()));
+        let id_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8333);
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+        let peer_manager = PeerManager::new(
+            ConnectionId::new(id_address, ConnectionType::Peer),
+            Stream::new(stream),
+            sender_message,
+            blockchain,
+            magic_numbers,
+            NotificationMock {},
+            sender,
+        );
+
+        sender_transaction.send(MessageToPeer::Stop).unwrap();
+        let (stream, _) = peer_manager
+            .connecting_to_peer(receiver_transaction)
+            .unwrap();
+        let mut stream = stream.get_write_stream();
+
+        let header = message::deserialize_until_found(&mut stream, CommandName::GetData).unwrap();
+        let get_data_message = GetDataMessage::deserialize_message(&mut stream, header).unwrap();
+
+        assert_eq!(
+            vec![InventoryVector::new(
+                TypeIdentifier::TransactionId,
+                transaction_id
+            )],
+            get_data_message.inventory_vectors
+        );
     }
 }
@@ -0,0 +1,192 @@
+use super::message_to_peer::MessageToPeer;
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc::{sync_channel, Receiver, SyncSender, TrySendError},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// The amount of messages that can wait to be sent to a peer
+pub const PEER_QUEUE_CAPACITY: usize = 200;
+
+/// The time the queue of a peer can stay full before the peer is disconnected
+pub const PEER_QUEUE_FULL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// It's what happened with a message pushed to the queue of a peer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QueuePush {
+    /// The message waits to be sent to the peer
+    Queued,
+
+    /// The queue is full, so the message is not sent
+    Dropped,
+
+    /// The queue is full for longer than its timeout, so the peer is too slow to keep it
+    Stuck,
+
+    /// The peer is no longer listening
+    Disconnected,
+}
+
+/// It's the state of a queue shared with the peer manager draining it
+#[derive(Debug, Clone, Default)]
+pub struct QueueStatus {
+    depth: Arc<AtomicUsize>,
+    is_closed: Arc<AtomicBool>,
+}
+
+impl QueueStatus {
+    /// Returns the amount of messages waiting to be sent to the peer
+    pub fn get_depth(&self) -> usize {
+        self.depth.load(Ordering::SeqCst)
+    }
+
+    /// Marks that a message of the queue was taken to be sent
+    pub fn received(&self) {
+        let _ = self
+            .depth
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |depth| {
+                Some(depth.saturating_sub(1))
+            });
+    }
+
+    /// Returns if the peer has to stop, even when its queue is full
+    pub fn is_closed(&self) -> bool {
+        self.is_closed.load(Ordering::SeqCst)
+    }
+}
+
+/// It's the bounded queue of the messages to send to a peer, drained by the thread of its peer
+/// manager. The messages are pushed without waiting, so a slow peer never blocks the thread
+/// broadcasting to all of them, and it's disconnected when its queue stays full for too long
+#[derive(Debug, Clone)]
+pub struct PeerQueue {
+    sender: SyncSender<MessageToPeer>,
+    status: QueueStatus,
+    full_timeout: Duration,
+    full_since: Option<Instant>,
+}
+
+impl PeerQueue {
+    /// Creates the queue with the given capacity and the receiver to drain it
+    pub fn new(capacity: usize, full_timeout: Duration) -> (PeerQueue, Receiver<MessageToPeer>) {
+        let (sender, receiver) = sync_channel::<MessageToPeer>(capacity);
+        let queue = PeerQueue {
+            sender,
+            status: QueueStatus::default(),
+            full_timeout,
+            full_since: None,
+        };
+
+        (queue, receiver)
+    }
+
+    /// Returns the state of the queue, to be shared with the peer manager
+    pub fn get_status(&self) -> QueueStatus {
+        self.status.clone()
+    }
+
+    /// Pushes the message without waiting. When the queue is full the message is dropped, and the
+    /// queue is stuck once it has been full for longer than its timeout
+    pub fn push(&mut self, message: MessageToPeer, now: Instant) -> QueuePush {
+        self.status.depth.fetch_add(1, Ordering::SeqCst);
+        let result = self.sender.try_send(message);
+        if result.is_err() {
+            self.status.received();
+        }
+
+        match result {
+            Ok(()) => {
+                self.full_since = None;
+                QueuePush::Queued
+            }
+            Err(TrySendError::Full(_)) => match self.full_since {
+                Some(full_since) if now.duration_since(full_since) >= self.full_timeout => {
+                    QueuePush::Stuck
+                }
+                Some(_) => QueuePush::Dropped,
+                None => {
+                    self.full_since = Some(now);
+                    QueuePush::Dropped
+                }
+            },
+            Err(TrySendError::Disconnected(_)) => QueuePush::Disconnected,
+        }
+    }
+
+    /// Tells the peer manager to stop. The stop message is only sent if there is room for it,
+    /// otherwise the peer manager stops when it takes the next message of the queue
+    pub fn close(&self) {
+        self.status.is_closed.store(true, Ordering::SeqCst);
+        let _ = self.sender.try_send(MessageToPeer::Stop);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_depth_counts_the_messages_not_taken() {
+        let (mut queue, receiver) = PeerQueue::new(2, PEER_QUEUE_FULL_TIMEOUT);
+        let status = queue.get_status();
+        let now = Instant::now();
+
+        assert_eq!(QueuePush::Queued, queue.push(MessageToPeer::KeepAlive, now));
+        assert_eq!(QueuePush::Queued, queue.push(MessageToPeer::KeepAlive, now));
+        assert_eq!(2, status.get_depth());
+
+        assert!(matches!(receiver.recv(), Ok(MessageToPeer::KeepAlive)));
+        status.received();
+        assert_eq!(1, status.get_depth());
+    }
+
+    #[test]
+    fn test02_full_queue_drops_messages_until_it_is_stuck() {
+        let (mut queue, receiver) = PeerQueue::new(1, Duration::from_secs(30));
+        let status = queue.get_status();
+        let now = Instant::now();
+
+        assert_eq!(QueuePush::Queued, queue.push(MessageToPeer::KeepAlive, now));
+        assert_eq!(
+            QueuePush::Dropped,
+            queue.push(MessageToPeer::KeepAlive, now)
+        );
+        assert_eq!(
+            QueuePush::Dropped,
+            queue.push(MessageToPeer::KeepAlive, now + Duration::from_secs(29))
+        );
+        assert_eq!(
+            QueuePush::Stuck,
+            queue.push(MessageToPeer::KeepAlive, now + Duration::from_secs(30))
+        );
+        assert_eq!(1, status.get_depth());
+
+        let _ = receiver.recv();
+        status.received();
+        assert_eq!(
+            QueuePush::Queued,
+            queue.push(MessageToPeer::KeepAlive, now + Duration::from_secs(31))
+        );
+    }
+
+    #[test]
+    fn test03_closed_queue_stops_the_peer() {
+        let (mut queue, receiver) = PeerQueue::new(1, PEER_QUEUE_FULL_TIMEOUT);
+        let status = queue.get_status();
+
+        queue.close();
+
+        assert!(status.is_closed());
+        assert!(matches!(receiver.recv(), Ok(MessageToPeer::Stop)));
+
+        drop(receiver);
+        assert_eq!(
+            QueuePush::Disconnected,
+            queue.push(MessageToPeer::KeepAlive, Instant::now())
+        );
+    }
+}
@@ -1,37 +1,54 @@
 use super::{
     connection_event::ConnectionEvent, connection_id::ConnectionId,
     connection_type::ConnectionType, error_node::ErrorNode, handshake::Handshake,
-    handshake_data::HandshakeData,
+    handshake_data::HandshakeData, network_time::NetworkTime, peer_features::PeerFeatures,
 };
 
 use crate::{
     concurrency::{stop::Stop, work::Work},
-    configurations::connection_config::ConnectionConfig,
-    logs::logger_sender::LoggerSender,
+    configurations::{connection_config::ConnectionConfig, proxy_config::ProxyConfig},
+    connections::{
+        connection_timeouts::ConnectionTimeouts,
+        error_connection::ErrorConnection,
+        onion_address::OnionAddress,
+        socks5,
+        user_agent::{self, UserAgentBuilder},
+    },
+    logs::{level::Level, logger_sender::LoggerSender},
+    messages::{command_name::CommandName, version_message::VersionMessage},
     notifications::{notification::Notification, notifier::Notifier},
-    serialization::error_serialization::ErrorSerialization,
 };
 
+use chrono::offset::Utc;
+
 use std::{
     net::SocketAddr,
     net::TcpStream,
-    sync::mpsc::{channel, Receiver, Sender},
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Arc, Mutex,
+    },
     thread::{self, JoinHandle},
     time::Duration,
 };
 
-pub type SenderConfirm = Sender<(TcpStream, ConnectionId)>;
-pub type ReceiverConfirm = Receiver<(TcpStream, ConnectionId)>;
+/// The connections established are sent with the user agent of the peer
+pub type SenderConfirm = Sender<(TcpStream, ConnectionId, String)>;
+pub type ReceiverConfirm = Receiver<(TcpStream, ConnectionId, String)>;
 
 pub type SenderPotential = Sender<ConnectionEvent>;
 pub type ReceiverPotential = Receiver<ConnectionEvent>;
 
 pub struct ProcessConnection<N: Notifier + Send + 'static> {
     handshake: Handshake,
+    proxy: Option<ProxyConfig>,
+    timeouts: ConnectionTimeouts,
 
     sender_confirm_connection: SenderConfirm,
     receiver_potential_connections: ReceiverPotential,
 
+    network_time: Arc<Mutex<NetworkTime>>,
+
     notifier: N,
     logger: LoggerSender,
 }
@@ -41,26 +58,39 @@ impl<N: Notifier + Send + 'static> ProcessConnection<N> {
         connection_config: ConnectionConfig,
         sender_confirm_connection: SenderConfirm,
         receiver_potential_connections: ReceiverPotential,
+        network_time: Arc<Mutex<NetworkTime>>,
         notifier: N,
         logger: LoggerSender,
     ) -> Self {
+        let user_agent = match user_agent::from_configuration(&connection_config.user_agent) {
+            Ok(user_agent) => user_agent,
+            Err(error) => {
+                let _ = logger.log_data(Level::ERROR, error);
+                UserAgentBuilder::new().build().unwrap_or_default()
+            }
+        };
+
+        let timeouts = connection_config.timeouts();
         let handshake = Handshake::new(
             connection_config.p2p_protocol_version,
             connection_config.services,
             connection_config.block_height,
             HandshakeData {
                 nonce: connection_config.nonce,
-                user_agent: connection_config.user_agent,
+                user_agent,
                 relay: connection_config.relay,
-                magic_number: connection_config.magic_numbers,
+                network: connection_config.network,
             },
             logger.clone(),
         );
 
         Self {
             handshake,
+            proxy: connection_config.proxy,
+            timeouts,
             sender_confirm_connection,
             receiver_potential_connections,
+            network_time,
             notifier,
             logger,
         }
@@ -79,7 +109,12 @@ impl<N: Notifier + Send + 'static> ProcessConnection<N> {
                 ConnectionEvent::PotentialPeer(socket_address) => {
                     let (sender, receiver) = channel::<Stop>();
 
-                    let stream = match Self::create_stream(socket_address, self.logger.clone()) {
+                    let stream = match Self::create_stream(
+                        socket_address,
+                        self.proxy.as_ref(),
+                        self.timeouts,
+                        self.logger.clone(),
+                    ) {
                         Some(stream) => stream,
                         None => {
                             let _ = self
@@ -97,9 +132,43 @@ impl<N: Notifier + Send + 'static> ProcessConnection<N> {
 
                     pending_connection_handlers.push((handler, sender));
                 }
+                ConnectionEvent::PotentialOnionPeer(onion_address) => {
+                    let (sender, receiver) = channel::<Stop>();
+
+                    let stream = match Self::create_onion_stream(
+                        &onion_address,
+                        self.proxy.as_ref(),
+                        self.timeouts,
+                        self.logger.clone(),
+                    ) {
+                        Some(stream) => stream,
+                        None => {
+                            let _ = self
+                                .logger
+                                .log_connection(format!("Cannot connect to {onion_address}"));
+                            continue;
+                        }
+                    };
+
+                    let handler = self.handle_connection_event(
+                        stream,
+                        ConnectionId::new(onion_address.to_socket_addr(), ConnectionType::Peer),
+                        receiver,
+                    );
+
+                    pending_connection_handlers.push((handler, sender));
+                }
                 ConnectionEvent::PotentialClient(stream, socket_address) => {
                     let (sender, receiver) = channel::<Stop>();
 
+                    if let Err(error) = self.timeouts.configure(&stream) {
+                        let _ = self.logger.log_connection(format!(
+                            "Cannot configure the stream of {socket_address}, it appear {:?}",
+                            error
+                        ));
+                        continue;
+                    }
+
                     let handler = self.handle_connection_event(
                         stream,
                         ConnectionId::new(socket_address, ConnectionType::Client),
@@ -135,7 +204,9 @@ impl<N: Notifier + Send + 'static> ProcessConnection<N> {
         let handshake = self.handshake.clone();
         let logger = self.logger.clone();
         let sender_confirm_connection = self.sender_confirm_connection.clone();
+        let network_time = self.network_time.clone();
         let notifier = self.notifier.clone();
+        let read_timeout = self.timeouts.read;
 
         thread::spawn(move || {
             notifier.notify(Notification::AttemptingHandshakeWithPeer(
@@ -155,30 +226,54 @@ impl<N: Notifier + Send + 'static> ProcessConnection<N> {
                 ConnectionId {
                     address,
                     connection_type: ConnectionType::Peer,
+                    ..
                 } => Self::connect_to_peer(
                     &mut stream,
                     &local_socket,
                     &address,
                     &handshake,
-                    &receiver,
+                    (&receiver, read_timeout),
                 ),
                 ConnectionId {
                     address,
                     connection_type: ConnectionType::Client,
+                    ..
                 } => Self::connect_to_client(
                     &mut stream,
                     &local_socket,
                     &address,
                     &handshake,
-                    &receiver,
+                    (&receiver, read_timeout),
                 ),
             };
 
             match result {
-                Ok(true) => {
+                Ok(Some((version_message, features))) => {
+                    let connection = connection.with_features(features);
+                    let _ = logger.log_connection(format!(
+                        "Negotiated with {} the protocol version {:?}, services {:#x} and wtxid relay {}",
+                        connection.address,
+                        connection.features.version,
+                        connection.features.services,
+                        connection.features.wtxid_relay
+                    ));
+
+                    if connection.connection_type == ConnectionType::Peer {
+                        Self::add_time_sample(
+                            &network_time,
+                            &connection,
+                            &version_message,
+                            notifier.clone(),
+                            &logger,
+                        );
+                    }
+
                     let _ = logger
                         .log_connection(format!("Connection established with {:?}", connection));
-                    if sender_confirm_connection.send((stream, connection)).is_ok() {
+                    if sender_confirm_connection
+                        .send((stream, connection, version_message.user_agent))
+                        .is_ok()
+                    {
                         notifier.notify(Notification::SuccessfulHandshakeWithPeer(
                             connection.address,
                         ));
@@ -187,129 +282,256 @@ impl<N: Notifier + Send + 'static> ProcessConnection<N> {
                         notifier.notify(Notification::FailedHandshakeWithPeer(connection.address));
                     }
                 }
-                Ok(false) => {}
-                Err(_) => {
+                Ok(None) => {}
+                Err(error) => {
+                    let _ = logger.log_connection(format!(
+                        "Handshake with {} failed, it appear {:?}",
+                        connection.address, error
+                    ));
                     notifier.notify(Notification::FailedHandshakeWithPeer(connection.address));
                 }
             }
         })
     }
 
-    /// Establish the handshake with a peer
+    /// Compares the time of the version message of a peer we connected to with our clock, and notifies
+    /// if our clock deviates from the time of the network
+    fn add_time_sample(
+        network_time: &Mutex<NetworkTime>,
+        connection: &ConnectionId,
+        version_message: &VersionMessage,
+        notifier: N,
+        logger: &LoggerSender,
+    ) {
+        let offset = version_message.timestamp.timestamp() - Utc::now().timestamp();
+
+        let skew = match network_time.lock() {
+            Ok(mut network_time) => network_time.add_sample(connection.address.ip(), offset),
+            Err(_) => return,
+        };
+
+        if let Some(skew) = skew {
+            let _ = logger.log_connection(format!(
+                "Our clock deviates {skew} seconds from the time of the network"
+            ));
+            notifier.notify(Notification::ClockSkew(skew));
+        }
+    }
+
+    /// Establish the handshake with a peer, returning the version message of the peer and the negotiated features.
+    /// The optional messages are only sent if the negotiated features allow them.
+    /// Returns None if the handshake was stopped
     ///
     /// ### Error
-    ///  * `ErrorSerialization::ErrorSerialization`: It will appear when there is an error in the serialization
-    ///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when there is an error in the deserialization
-    ///  * `ErrorSerialization::ErrorWhileReading`: It will appear when there is an error in the reading from a stream
+    ///  * `ErrorConnection::ErrorCannotSendMessage`: It will appear when a message cannot be sent
+    ///  * `ErrorConnection::ErrorCannotReceiveMessage`: It will appear when a message cannot be received
+    ///  * `ErrorConnection::ErrorReadTimeout`: It will appear when nothing is received before the read timeout
     fn connect_to_peer(
         stream: &mut TcpStream,
         local_socket: &SocketAddr,
         potential_socket: &SocketAddr,
         handshake: &Handshake,
-        receiver: &Receiver<Stop>,
-    ) -> Result<bool, ErrorSerialization> {
+        listening: (&Receiver<Stop>, Duration),
+    ) -> Result<Option<(VersionMessage, PeerFeatures)>, ErrorConnection> {
         handshake.send_version_message(stream, local_socket, potential_socket)?;
 
-        loop {
-            match Work::listen(stream, receiver) {
+        let version_message = loop {
+            match Self::listen(stream, listening)? {
                 Work::Message(header) => {
-                    handshake.receive_version_message(stream, header, potential_socket)?;
-                    break;
+                    break handshake.receive_version_message(stream, header, potential_socket)?;
                 }
                 Work::Information(()) => continue,
                 Work::Stop => {
-                    return Ok(false);
+                    return Ok(None);
                 }
             }
-        }
+        };
 
+        let features = handshake.negotiate_features(&version_message);
         handshake.send_verack_message(stream, potential_socket)?;
 
-        loop {
-            match Work::listen(stream, receiver) {
-                Work::Message(header) => {
-                    handshake.receive_verack_message(stream, header, potential_socket)?;
-                    break;
-                }
-                Work::Information(()) => continue,
-                Work::Stop => {
-                    return Ok(false);
-                }
-            }
+        if Self::receive_until_verack(stream, potential_socket, handshake, listening)?.is_none() {
+            return Ok(None);
         }
 
-        handshake.send_sendheaders_message(stream)?;
+        if features.supports_send_headers() {
+            handshake.send_sendheaders_message(stream)?;
+        }
+        if features.supports_send_compact() {
+            handshake.send_sendcmpct_message(stream)?;
+        }
 
-        Ok(true)
+        Ok(Some((version_message, features)))
     }
 
-    /// Establish the handshake with a client
+    /// Establish the handshake with a client, returning the version message of the client and the negotiated features.
+    /// The optional messages are only sent if the negotiated features allow them.
+    /// Returns None if the handshake was stopped
     ///
     /// ### Error
-    ///  * `ErrorSerialization::ErrorSerialization`: It will appear when there is an error in the serialization
-    ///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when there is an error in the deserialization
-    ///  * `ErrorSerialization::ErrorWhileReading`: It will appear when there is an error in the reading from a stream
+    ///  * `ErrorConnection::ErrorCannotSendMessage`: It will appear when a message cannot be sent
+    ///  * `ErrorConnection::ErrorCannotReceiveMessage`: It will appear when a message cannot be received
+    ///  * `ErrorConnection::ErrorReadTimeout`: It will appear when nothing is received before the read timeout
     fn connect_to_client(
         stream: &mut TcpStream,
         local_socket: &SocketAddr,
         potential_socket: &SocketAddr,
         handshake: &Handshake,
-        receiver: &Receiver<Stop>,
-    ) -> Result<bool, ErrorSerialization> {
-        loop {
-            match Work::listen(stream, receiver) {
+        listening: (&Receiver<Stop>, Duration),
+    ) -> Result<Option<(VersionMessage, PeerFeatures)>, ErrorConnection> {
+        let version_message = loop {
+            match Self::listen(stream, listening)? {
                 Work::Message(header) => {
-                    handshake.receive_version_message(stream, header, potential_socket)?;
-                    break;
+                    break handshake.receive_version_message(stream, header, potential_socket)?;
                 }
                 Work::Information(()) => continue,
                 Work::Stop => {
-                    return Ok(false);
+                    return Ok(None);
                 }
             }
-        }
+        };
 
         handshake.send_version_message(stream, local_socket, potential_socket)?;
 
+        let features = handshake.negotiate_features(&version_message);
+        if Self::receive_until_verack(stream, potential_socket, handshake, listening)?.is_none() {
+            return Ok(None);
+        }
+
+        handshake.send_verack_message(stream, potential_socket)?;
+        if features.supports_send_compact() {
+            handshake.send_sendcmpct_message(stream)?;
+        }
+
+        Ok(Some((version_message, features)))
+    }
+
+    /// Receives the messages of the handshake until the verack message. The wtxidrelay message of the other
+    /// node is read but not answered, because the transactions are serialized without their witness, so
+    /// the relay by witness id is never agreed. Returns None if the handshake was stopped
+    ///
+    /// ### Error
+    ///  * `ErrorConnection::ErrorCannotReceiveMessage`: It will appear when a message cannot be received
+    ///  * `ErrorConnection::ErrorReadTimeout`: It will appear when nothing is received before the read timeout
+    fn receive_until_verack(
+        stream: &mut TcpStream,
+        potential_socket: &SocketAddr,
+        handshake: &Handshake,
+        listening: (&Receiver<Stop>, Duration),
+    ) -> Result<Option<()>, ErrorConnection> {
         loop {
-            match Work::listen(stream, receiver) {
-                Work::Message(header) => {
-                    handshake.receive_verack_message(stream, header, potential_socket)?;
-                    break;
-                }
+            match Self::listen(stream, listening)? {
+                Work::Message(header) => match header.command_name {
+                    CommandName::WtxidRelay => {
+                        handshake.receive_wtxidrelay_message(stream, header, potential_socket)?;
+                    }
+                    _ => {
+                        handshake.receive_verack_message(stream, header, potential_socket)?;
+                        return Ok(Some(()));
+                    }
+                },
                 Work::Information(()) => continue,
                 Work::Stop => {
-                    return Ok(false);
+                    return Ok(None);
                 }
             }
         }
+    }
 
-        handshake.send_verack_message(stream, potential_socket)?;
+    /// Listens to the stream and the receiver until a message or the signal to stop arrives
+    ///
+    /// ### Error
+    ///  * `ErrorConnection::ErrorReadTimeout`: It will appear when nothing is received before the read timeout
+    fn listen(
+        stream: &mut TcpStream,
+        listening: (&Receiver<Stop>, Duration),
+    ) -> Result<Work<()>, ErrorConnection> {
+        let (receiver, read_timeout) = listening;
+        match Work::listen_with_timeout(stream, receiver, read_timeout) {
+            Some(work) => Ok(work),
+            None => Err(ErrorConnection::ErrorReadTimeout),
+        }
+    }
 
-        Ok(true)
+    /// Create a stream to connect to a potential connection, through the proxy if there is one
+    fn create_stream(
+        potential_address: SocketAddr,
+        proxy: Option<&ProxyConfig>,
+        timeouts: ConnectionTimeouts,
+        logger: LoggerSender,
+    ) -> Option<TcpStream> {
+        match proxy {
+            Some(proxy) => {
+                let mut stream = Self::connect_to_proxy(proxy, timeouts, &logger)?;
+                if let Err(error) = socks5::connect_to_socket(&mut stream, &potential_address) {
+                    let _ = logger.log_connection(format!(
+                        "The proxy cannot connect to address: {:?}, it appear {:?}",
+                        potential_address, error
+                    ));
+                    return None;
+                }
+                Some(stream)
+            }
+            None => match timeouts.connect(&potential_address) {
+                Ok(stream) => Some(stream),
+                Err(error) => {
+                    let _ = logger.log_connection(format!(
+                        "Cannot connect to address: {:?}, it appear {:?}",
+                        potential_address, error
+                    ));
+                    None
+                }
+            },
+        }
     }
 
-    /// Create a stream to connect to a potential connection
-    fn create_stream(potential_address: SocketAddr, logger: LoggerSender) -> Option<TcpStream> {
-        let stream = match TcpStream::connect(potential_address) {
-            Ok(stream) => stream,
-            Err(error) => {
+    /// Create a stream to connect to an onion service, which can only be reached through the proxy
+    fn create_onion_stream(
+        onion_address: &OnionAddress,
+        proxy: Option<&ProxyConfig>,
+        timeouts: ConnectionTimeouts,
+        logger: LoggerSender,
+    ) -> Option<TcpStream> {
+        let proxy = match proxy {
+            Some(proxy) => proxy,
+            None => {
                 let _ = logger.log_connection(format!(
-                    "Cannot connect to address: {:?}, it appear {:?}",
-                    potential_address, error
+                    "Cannot connect to the onion service {onion_address} without a proxy"
                 ));
                 return None;
             }
         };
 
-        if let Err(error) = stream.set_read_timeout(Some(Duration::from_secs(1))) {
+        let mut stream = Self::connect_to_proxy(proxy, timeouts, &logger)?;
+        if let Err(error) =
+            socks5::connect_to_domain(&mut stream, onion_address.host(), onion_address.port())
+        {
             let _ = logger.log_connection(format!(
-                "Cannot connect to address: {:?}, it appear {:?}",
-                potential_address, error
+                "The proxy cannot connect to the onion service {onion_address}, it appear {:?}",
+                error
             ));
             return None;
-        };
+        }
 
         Some(stream)
     }
+
+    /// Create a stream to the SOCKS5 proxy
+    fn connect_to_proxy(
+        proxy: &ProxyConfig,
+        timeouts: ConnectionTimeouts,
+        logger: &LoggerSender,
+    ) -> Option<TcpStream> {
+        match timeouts.connect(&proxy.socket()) {
+            Ok(stream) => Some(stream),
+            Err(error) => {
+                let _ = logger.log_connection(format!(
+                    "Cannot connect to the proxy: {:?}, it appear {:?}",
+                    proxy.socket(),
+                    error
+                ));
+                None
+            }
+        }
+    }
 }
@@ -0,0 +1,124 @@
+use crate::block_structure::hash::HashType;
+
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+/// The time a transaction or block is remembered after being received
+pub const RECENT_INVENTORY_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// The amount of transactions and blocks remembered at the same time
+pub const MAX_RECENT_INVENTORY: usize = 20_000;
+
+/// It represents the transactions and blocks recently received from any peer, shared by the peer managers
+/// so the same transaction or block is not requested, processed and relayed again when another peer sends it.
+/// They are forgotten after the time to live, or when the capacity is exceeded starting by the oldest
+#[derive(Debug, Clone)]
+pub struct RecentInventory {
+    seen: HashMap<HashType, Instant>,
+    order: VecDeque<HashType>,
+    capacity: usize,
+    time_to_live: Duration,
+}
+
+impl RecentInventory {
+    pub fn new(capacity: usize, time_to_live: Duration) -> Self {
+        RecentInventory {
+            seen: HashMap::new(),
+            order: VecDeque::new(),
+            capacity,
+            time_to_live,
+        }
+    }
+
+    /// Remembers the hash of the transaction or block as seen, returning false if it was already seen recently
+    pub fn insert(&mut self, hash: HashType, now: Instant) -> bool {
+        self.remove_expired(now);
+        if self.seen.contains_key(&hash) {
+            return false;
+        }
+
+        self.seen.insert(hash, now);
+        self.order.push_back(hash);
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+
+        true
+    }
+
+    /// Returns true if the transaction or block with the given hash was seen recently
+    pub fn contains(&self, hash: &HashType, now: Instant) -> bool {
+        match self.seen.get(hash) {
+            Some(seen_at) => now.saturating_duration_since(*seen_at) < self.time_to_live,
+            None => false,
+        }
+    }
+
+    /// Forgets the hashes seen for longer than the time to live
+    fn remove_expired(&mut self, now: Instant) {
+        while let Some(oldest) = self.order.front() {
+            match self.seen.get(oldest) {
+                Some(seen_at) if now.saturating_duration_since(*seen_at) < self.time_to_live => {
+                    break
+                }
+                _ => {
+                    if let Some(oldest) = self.order.pop_front() {
+                        self.seen.remove(&oldest);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Default for RecentInventory {
+    fn default() -> Self {
+        RecentInventory::new(MAX_RECENT_INVENTORY, RECENT_INVENTORY_TTL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_hash_is_seen_only_once() {
+        let now = Instant::now();
+        let mut recent_inventory = RecentInventory::default();
+
+        assert!(!recent_inventory.contains(&[1; 32], now));
+        assert!(recent_inventory.insert([1; 32], now));
+        assert!(recent_inventory.contains(&[1; 32], now));
+        assert!(!recent_inventory.insert([1; 32], now));
+    }
+
+    #[test]
+    fn test02_hash_is_forgotten_after_the_time_to_live() {
+        let now = Instant::now();
+        let mut recent_inventory = RecentInventory::new(10, Duration::from_secs(60));
+
+        recent_inventory.insert([1; 32], now);
+
+        let later = now + Duration::from_secs(60);
+        assert!(!recent_inventory.contains(&[1; 32], later));
+        assert!(recent_inventory.insert([1; 32], later));
+    }
+
+    #[test]
+    fn test03_oldest_hash_is_forgotten_when_the_capacity_is_exceeded() {
+        let now = Instant::now();
+        let mut recent_inventory = RecentInventory::new(2, RECENT_INVENTORY_TTL);
+
+        recent_inventory.insert([1; 32], now);
+        recent_inventory.insert([2; 32], now);
+        recent_inventory.insert([3; 32], now);
+
+        assert!(!recent_inventory.contains(&[1; 32], now));
+        assert!(recent_inventory.contains(&[2; 32], now));
+        assert!(recent_inventory.contains(&[3; 32], now));
+    }
+}
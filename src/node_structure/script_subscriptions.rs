@@ -0,0 +1,269 @@
+use crate::block_structure::{
+    block::Block,
+    hash::{hash256, HashType},
+    outpoint::Outpoint,
+    script::Script,
+    transaction::Transaction,
+    utxo_set::UTXOSet,
+};
+
+use std::collections::{HashMap, HashSet};
+
+/// It's what happened to an output paying to a subscribed script
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptEventKind {
+    /// The output was created with the given value in satoshis
+    Created(i64),
+
+    /// The output was spent by the transaction with the given id
+    Spent(HashType),
+}
+
+/// It's the event pushed to the subscribers of a script hash. The block hash is `None` while the
+/// transaction is only in the mempool
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptEvent {
+    pub script_hash: HashType,
+    pub outpoint: Outpoint,
+    pub kind: ScriptEventKind,
+    pub block_hash: Option<HashType>,
+}
+
+/// Returns the hash used to subscribe to a script, the sha256 of the script as in Electrum
+pub fn get_script_hash(script: Script) -> Option<HashType> {
+    hash256(script.as_bytes()).ok()
+}
+
+/// It represents the script hashes that external applications are subscribed to, and finds the
+/// outputs of those scripts created or spent by the blocks and transactions received
+#[derive(Debug, Clone, Default)]
+pub struct ScriptSubscriptions {
+    script_hashes: HashSet<HashType>,
+}
+
+impl ScriptSubscriptions {
+    /// Adds the script hash to the subscriptions, and returns false if it was already subscribed
+    pub fn subscribe(&mut self, script_hash: HashType) -> bool {
+        self.script_hashes.insert(script_hash)
+    }
+
+    /// Removes the script hash from the subscriptions, and returns false if it was not subscribed
+    pub fn unsubscribe(&mut self, script_hash: &HashType) -> bool {
+        self.script_hashes.remove(script_hash)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.script_hashes.is_empty()
+    }
+
+    /// Returns the events of the subscribed scripts in the transaction. The spent outputs are looked up
+    /// in the UTXO set, so it must not be updated with the transaction yet
+    pub fn get_transaction_events(
+        &self,
+        transaction: &Transaction,
+        utxo_set: &UTXOSet,
+        block_hash: Option<HashType>,
+    ) -> Vec<ScriptEvent> {
+        self.get_events(transaction, utxo_set, &HashMap::new(), block_hash)
+    }
+
+    /// Returns the events of the subscribed scripts in all the transactions of the block, including the
+    /// outputs created and spent in the same block. The UTXO set must not be updated with the block yet
+    pub fn get_block_events(&self, block: &Block, utxo_set: &UTXOSet) -> Vec<ScriptEvent> {
+        if self.is_empty() {
+            return vec![];
+        }
+
+        let block_hash = block.header.get_hash256d().ok();
+        let mut created_in_block: HashMap<Outpoint, HashType> = HashMap::new();
+        let mut events: Vec<ScriptEvent> = Vec::new();
+
+        for transaction in block.transactions.iter() {
+            let transaction_events =
+                self.get_events(transaction, utxo_set, &created_in_block, block_hash);
+
+            for event in transaction_events.iter() {
+                if let ScriptEventKind::Created(_) = event.kind {
+                    created_in_block.insert(event.outpoint.clone(), event.script_hash);
+                }
+            }
+            events.extend(transaction_events);
+        }
+
+        events
+    }
+
+    fn get_events(
+        &self,
+        transaction: &Transaction,
+        utxo_set: &UTXOSet,
+        created_in_block: &HashMap<Outpoint, HashType>,
+        block_hash: Option<HashType>,
+    ) -> Vec<ScriptEvent> {
+        if self.is_empty() {
+            return vec![];
+        }
+
+        let transaction_id = match transaction.get_tx_id() {
+            Ok(transaction_id) => transaction_id,
+            Err(_) => return vec![],
+        };
+
+        let mut events: Vec<ScriptEvent> = Vec::new();
+
+        for input in transaction.tx_in.iter() {
+            let outpoint = &input.previous_output;
+            let script_hash = match utxo_set.get(outpoint) {
+                Some(output) => get_script_hash(output.pk_script.as_script()),
+                None => created_in_block.get(outpoint).copied(),
+            };
+
+            if let Some(script_hash) = script_hash {
+                if self.script_hashes.contains(&script_hash) {
+                    events.push(ScriptEvent {
+                        script_hash,
+                        outpoint: outpoint.clone(),
+                        kind: ScriptEventKind::Spent(transaction_id),
+                        block_hash,
+                    });
+                }
+            }
+        }
+
+        for (index, output) in transaction.tx_out.iter().enumerate() {
+            if let Some(script_hash) = get_script_hash(output.pk_script.as_script()) {
+                if self.script_hashes.contains(&script_hash) {
+                    events.push(ScriptEvent {
+                        script_hash,
+                        outpoint: Outpoint::new(transaction_id, index as u32),
+                        kind: ScriptEventKind::Created(output.value),
+                        block_hash,
+                    });
+                }
+            }
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::block_structure::{
+        block_header::BlockHeader, block_version::BlockVersion, compact256::Compact256,
+        script::ScriptBuf, transaction_input::TransactionInput,
+        transaction_output::TransactionOutput,
+    };
+    use crate::messages::compact_size::CompactSize;
+
+    fn create_transaction(previous_output: Outpoint, pk_script: ScriptBuf) -> Transaction {
+        Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(previous_output, vec![1, 2, 3], 24)],
+            tx_out: vec![TransactionOutput {
+                value: 10,
+                pk_script,
+            }],
+            lock_time: 0,
+        }
+    }
+
+    fn create_block(transactions: Vec<Transaction>) -> Block {
+        Block {
+            header: BlockHeader::new(
+                BlockVersion::version(1),
+                [0; 32],
+                [0; 32],
+                0,
+                Compact256::from(u32::MAX),
+                0,
+                CompactSize::new(transactions.len() as u64),
+            ),
+            transactions,
+        }
+    }
+
+    #[test]
+    fn test01_only_the_subscribed_scripts_are_notified() {
+        let script = ScriptBuf::from(vec![4, 5, 6]);
+        let script_hash = get_script_hash(script.as_script()).unwrap();
+
+        let mut subscriptions = ScriptSubscriptions::default();
+        assert!(subscriptions.subscribe(script_hash));
+        assert!(!subscriptions.subscribe(script_hash));
+
+        let utxo_set = UTXOSet::new(vec![]);
+        let paying = create_transaction(Outpoint::new([1; 32], 0), script);
+        let not_paying =
+            create_transaction(Outpoint::new([1; 32], 1), ScriptBuf::from(vec![7, 8, 9]));
+
+        let events = subscriptions.get_transaction_events(&paying, &utxo_set, None);
+        assert_eq!(
+            events,
+            vec![ScriptEvent {
+                script_hash,
+                outpoint: Outpoint::new(paying.get_tx_id().unwrap(), 0),
+                kind: ScriptEventKind::Created(10),
+                block_hash: None,
+            }]
+        );
+        assert!(subscriptions
+            .get_transaction_events(&not_paying, &utxo_set, None)
+            .is_empty());
+
+        assert!(subscriptions.unsubscribe(&script_hash));
+        assert!(subscriptions
+            .get_transaction_events(&paying, &utxo_set, None)
+            .is_empty());
+    }
+
+    #[test]
+    fn test02_spent_outputs_are_found_in_the_utxo_set() {
+        let script = ScriptBuf::from(vec![4, 5, 6]);
+        let script_hash = get_script_hash(script.as_script()).unwrap();
+
+        let funding = create_transaction(Outpoint::new([1; 32], 0), script);
+        let funding_outpoint = Outpoint::new(funding.get_tx_id().unwrap(), 0);
+        let utxo_set = UTXOSet::new(vec![create_block(vec![funding])]);
+
+        let mut subscriptions = ScriptSubscriptions::default();
+        subscriptions.subscribe(script_hash);
+
+        let spending = create_transaction(funding_outpoint.clone(), ScriptBuf::from(vec![7, 8, 9]));
+        let events = subscriptions.get_transaction_events(&spending, &utxo_set, None);
+
+        assert_eq!(
+            events,
+            vec![ScriptEvent {
+                script_hash,
+                outpoint: funding_outpoint,
+                kind: ScriptEventKind::Spent(spending.get_tx_id().unwrap()),
+                block_hash: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test03_outputs_created_and_spent_in_the_same_block_are_notified() {
+        let script = ScriptBuf::from(vec![4, 5, 6]);
+        let script_hash = get_script_hash(script.as_script()).unwrap();
+
+        let funding = create_transaction(Outpoint::new([1; 32], 0), script);
+        let funding_outpoint = Outpoint::new(funding.get_tx_id().unwrap(), 0);
+        let spending = create_transaction(funding_outpoint, ScriptBuf::from(vec![7, 8, 9]));
+        let block = create_block(vec![funding, spending]);
+
+        let mut subscriptions = ScriptSubscriptions::default();
+        subscriptions.subscribe(script_hash);
+
+        let events = subscriptions.get_block_events(&block, &UTXOSet::new(vec![]));
+        let block_hash = block.header.get_hash256d().ok();
+
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0].kind, ScriptEventKind::Created(10)));
+        assert!(matches!(events[1].kind, ScriptEventKind::Spent(_)));
+        assert!(events.iter().all(|event| event.block_hash == block_hash));
+    }
+}
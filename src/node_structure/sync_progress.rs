@@ -0,0 +1,303 @@
+use crate::notifications::{notification::Notification, notifier::Notifier};
+
+use std::{
+    fmt::Display,
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
+
+const REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
+const BYTE_UNITS: [(&str, u64); 3] = [
+    ("GB", 1024 * 1024 * 1024),
+    ("MB", 1024 * 1024),
+    ("KB", 1024),
+];
+
+/// It represents the progress of the download of the blocks from a peer
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncProgress {
+    /// The amount of blocks already downloaded
+    pub blocks_done: u32,
+
+    /// The amount of blocks to download
+    pub blocks_total: u32,
+
+    /// The amount of bytes of the blocks downloaded
+    pub bytes_downloaded: u64,
+
+    /// The amount of blocks downloaded per second since the download started
+    pub blocks_per_second: f64,
+
+    /// The estimated time until the download finishes, if it can be estimated
+    pub eta: Option<Duration>,
+
+    /// The address of the peer from where the blocks are downloaded, if known
+    pub peer: Option<SocketAddr>,
+
+    /// The amount of blocks the peer could not serve, left to retry with another peer
+    pub blocks_to_retry: u32,
+}
+
+impl SyncProgress {
+    /// Creates the progress given what was downloaded in the elapsed time, estimating the rest
+    /// with the same rate
+    pub fn new(
+        blocks_done: u32,
+        blocks_total: u32,
+        bytes_downloaded: u64,
+        elapsed: Duration,
+        peer: Option<SocketAddr>,
+    ) -> Self {
+        let seconds = elapsed.as_secs_f64();
+        let blocks_per_second = match seconds > 0.0 {
+            true => blocks_done as f64 / seconds,
+            false => 0.0,
+        };
+
+        let remaining = blocks_total.saturating_sub(blocks_done);
+        let eta = match (remaining, blocks_per_second > 0.0) {
+            (0, _) => Some(Duration::ZERO),
+            (remaining, true) => Some(Duration::from_secs_f64(
+                remaining as f64 / blocks_per_second,
+            )),
+            (_, false) => None,
+        };
+
+        SyncProgress {
+            blocks_done,
+            blocks_total,
+            bytes_downloaded,
+            blocks_per_second,
+            eta,
+            peer,
+            blocks_to_retry: 0,
+        }
+    }
+
+    /// The progress will show the blocks left to retry with another peer
+    pub fn with_blocks_to_retry(self, blocks_to_retry: u32) -> Self {
+        SyncProgress {
+            blocks_to_retry,
+            ..self
+        }
+    }
+
+    /// Returns the fraction of the blocks downloaded, between 0 and 1
+    pub fn fraction(&self) -> f64 {
+        match self.blocks_total {
+            0 => 1.0,
+            total => self.blocks_done as f64 / total as f64,
+        }
+    }
+}
+
+/// Returns the amount of bytes with the largest unit where it's at least 1
+fn format_bytes(bytes: u64) -> String {
+    for (unit, size) in BYTE_UNITS {
+        if bytes >= size {
+            return format!("{:.1} {unit}", bytes as f64 / size as f64);
+        }
+    }
+    format!("{bytes} B")
+}
+
+impl Display for SyncProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{} blocks ({:.1}%), {} at {:.1} blocks/s",
+            self.blocks_done,
+            self.blocks_total,
+            self.fraction() * 100.0,
+            format_bytes(self.bytes_downloaded),
+            self.blocks_per_second,
+        )?;
+
+        if let Some(eta) = self.eta {
+            write!(f, ", {}s left", eta.as_secs())?;
+        }
+        if self.blocks_to_retry > 0 {
+            write!(f, ", {} to retry", self.blocks_to_retry)?;
+        }
+        if let Some(peer) = self.peer {
+            write!(f, ", from {peer}")?;
+        }
+        Ok(())
+    }
+}
+
+/// It keeps the amount of blocks and bytes downloaded, notifying the progress at most once every
+/// second while the download goes on
+#[derive(Debug, Clone)]
+pub struct ProgressTracker {
+    start: Instant,
+    last_report: Option<Instant>,
+    blocks_done: u32,
+    blocks_total: u32,
+    blocks_to_retry: u32,
+    bytes_downloaded: u64,
+    peer: Option<SocketAddr>,
+}
+
+impl ProgressTracker {
+    pub fn new(blocks_total: u32, peer: Option<SocketAddr>) -> Self {
+        ProgressTracker {
+            start: Instant::now(),
+            last_report: None,
+            blocks_done: 0,
+            blocks_total,
+            blocks_to_retry: 0,
+            bytes_downloaded: 0,
+            peer,
+        }
+    }
+
+    /// Registers the blocks downloaded with their size in bytes
+    pub fn add_blocks(&mut self, blocks: u32, bytes: u64) {
+        self.blocks_done += blocks;
+        self.bytes_downloaded += bytes;
+    }
+
+    /// Removes the blocks that will not be downloaded from the total, leaving them to retry with another peer
+    pub fn skip_blocks(&mut self, blocks: u32) {
+        self.blocks_total = self.blocks_total.saturating_sub(blocks);
+        self.blocks_to_retry += blocks;
+    }
+
+    /// Makes the total count the given amount of blocks about to be requested, for the downloads
+    /// whose total was not known when they started
+    pub fn expect_blocks(&mut self, blocks: u32) {
+        self.blocks_total = self.blocks_total.max(self.blocks_done + blocks);
+    }
+
+    /// Returns the progress until now
+    pub fn progress(&self) -> SyncProgress {
+        SyncProgress::new(
+            self.blocks_done,
+            self.blocks_total,
+            self.bytes_downloaded,
+            self.start.elapsed(),
+            self.peer,
+        )
+        .with_blocks_to_retry(self.blocks_to_retry)
+    }
+
+    /// Notifies the progress if it was not notified in the last second
+    pub fn notify_if_due<N: Notifier>(&mut self, notifier: &N) {
+        let is_due = match self.last_report {
+            Some(last_report) => last_report.elapsed() >= REPORT_INTERVAL,
+            None => true,
+        };
+
+        if is_due {
+            self.notify(notifier);
+        }
+    }
+
+    /// Notifies the progress until now
+    pub fn notify<N: Notifier>(&mut self, notifier: &N) {
+        self.last_report = Some(Instant::now());
+        notifier.notify(Notification::ProgressDownloadingBlocks(self.progress()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test01_rate_and_eta_are_estimated_from_the_elapsed_time() {
+        let progress = SyncProgress::new(50, 150, 2048, Duration::from_secs(10), None);
+
+        assert_eq!(5.0, progress.blocks_per_second);
+        assert_eq!(Some(Duration::from_secs(20)), progress.eta);
+        assert_eq!(
+            "50/150 blocks (33.3%), 2.0 KB at 5.0 blocks/s, 20s left",
+            progress.to_string()
+        );
+    }
+
+    #[test]
+    fn test02_eta_is_unknown_before_the_first_block() {
+        let peer: SocketAddr = "127.0.0.1:18333".parse().unwrap();
+        let progress = SyncProgress::new(0, 10, 0, Duration::from_secs(3), Some(peer));
+
+        assert_eq!(None, progress.eta);
+        assert_eq!(
+            "0/10 blocks (0.0%), 0 B at 0.0 blocks/s, from 127.0.0.1:18333",
+            progress.to_string()
+        );
+        assert_eq!(
+            Some(Duration::ZERO),
+            SyncProgress::new(10, 10, 0, Duration::ZERO, None).eta
+        );
+    }
+
+    #[test]
+    fn test03_tracker_notifies_at_most_once_every_second() {
+        #[derive(Clone)]
+        struct RecordNotifier(Arc<Mutex<Vec<SyncProgress>>>);
+
+        impl Notifier for RecordNotifier {
+            fn notify(&self, notification: Notification) {
+                if let Notification::ProgressDownloadingBlocks(progress) = notification {
+                    self.0.lock().unwrap().push(progress);
+                }
+            }
+        }
+
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let notifier = RecordNotifier(recorded.clone());
+        let mut tracker = ProgressTracker::new(3, None);
+
+        tracker.notify_if_due(&notifier);
+        tracker.add_blocks(1, 100);
+        tracker.notify_if_due(&notifier);
+        tracker.skip_blocks(1);
+        tracker.add_blocks(1, 50);
+        tracker.notify(&notifier);
+
+        let recorded = recorded.lock().unwrap();
+        assert_eq!(2, recorded.len());
+        assert_eq!(
+            (0, 3, 0),
+            (
+                recorded[0].blocks_done,
+                recorded[0].blocks_total,
+                recorded[0].bytes_downloaded
+            )
+        );
+        assert_eq!(
+            (2, 2, 150),
+            (
+                recorded[1].blocks_done,
+                recorded[1].blocks_total,
+                recorded[1].bytes_downloaded
+            )
+        );
+        assert_eq!(1, recorded[1].blocks_to_retry);
+    }
+
+    #[test]
+    fn test04_total_grows_with_the_blocks_expected() {
+        let mut tracker = ProgressTracker::new(0, None);
+
+        tracker.expect_blocks(2);
+        tracker.add_blocks(2, 0);
+        tracker.expect_blocks(3);
+        assert_eq!(5, tracker.progress().blocks_total);
+
+        let mut tracker = ProgressTracker::new(10, None);
+        tracker.expect_blocks(3);
+        assert_eq!(10, tracker.progress().blocks_total);
+
+        tracker.skip_blocks(2);
+        assert_eq!(
+            "0/8 blocks (0.0%), 0 B at 0.0 blocks/s, 2 to retry",
+            tracker.progress().to_string()
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use super::{notification::Notification, notifier::Notifier};
+
+use crate::{
+    logs::logger_sender::LoggerSender,
+    serialization::serializable_internal_order::SerializableInternalOrder,
+};
+
+use std::{
+    collections::HashMap,
+    io::Write,
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+pub const HASH_BLOCK_TOPIC: &str = "hashblock";
+pub const RAW_BLOCK_TOPIC: &str = "rawblock";
+pub const HASH_TRANSACTION_TOPIC: &str = "hashtx";
+pub const RAW_TRANSACTION_TOPIC: &str = "rawtx";
+
+/// The time a subscriber has to receive each event before it's dropped
+const SUBSCRIBER_WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// It's a chain event to publish, given by its topic and its body
+type Event = (&'static str, Vec<u8>);
+
+/// It's a notifier that publishes the chain events to every subscriber connected, in the style of
+/// the ZeroMQ interface of Bitcoin Core, while giving all the notifications to the inner notifier.
+///
+/// For every new block it publishes the `hashblock` and `rawblock` events, and for every
+/// transaction received the `hashtx` and `rawtx` events. Each event is sent as three frames,
+/// the topic, the body and the sequence number of the topic (4 bytes in little endian), and each
+/// frame is preceded by its length (4 bytes in little endian). The hashes are sent in the order
+/// they are shown to the user
+#[derive(Clone)]
+pub struct EventPublisher<N: Notifier> {
+    subscribers: Arc<Mutex<Vec<TcpStream>>>,
+    sequences: Arc<Mutex<HashMap<&'static str, u32>>>,
+    notifier: N,
+    logger: LoggerSender,
+}
+
+impl<N: Notifier> EventPublisher<N> {
+    pub fn new(notifier: N, logger: LoggerSender) -> Self {
+        EventPublisher {
+            subscribers: Arc::new(Mutex::new(Vec::new())),
+            sequences: Arc::new(Mutex::new(HashMap::new())),
+            notifier,
+            logger,
+        }
+    }
+
+    /// Creates a thread that accepts the subscribers connecting to the listener
+    pub fn listen(&self, listener: TcpListener) -> JoinHandle<()> {
+        let subscribers = self.subscribers.clone();
+        let logger = self.logger.clone();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => continue,
+                };
+
+                if stream
+                    .set_write_timeout(Some(SUBSCRIBER_WRITE_TIMEOUT))
+                    .is_err()
+                {
+                    continue;
+                }
+
+                let _ = logger.log_connection("Event subscriber connected".to_string());
+                match subscribers.lock() {
+                    Ok(mut subscribers) => subscribers.push(stream),
+                    Err(poisoned) => poisoned.into_inner().push(stream),
+                }
+            }
+        })
+    }
+
+    /// Sends the event to every subscriber, forgetting the ones that are no longer connected or that
+    /// don't receive it before the write timeout
+    fn publish(&self, event: Event) {
+        let (topic, body) = event;
+
+        let sequence = {
+            let mut sequences = match self.sequences.lock() {
+                Ok(sequences) => sequences,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            let sequence = sequences.entry(topic).or_insert(0);
+            let current = *sequence;
+            *sequence = sequence.wrapping_add(1);
+            current
+        };
+
+        let message = get_message(topic, &body, sequence);
+
+        let mut subscribers = match self.subscribers.lock() {
+            Ok(subscribers) => subscribers,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        subscribers.retain_mut(|subscriber| subscriber.write_all(&message).is_ok());
+    }
+}
+
+impl<N: Notifier> Notifier for EventPublisher<N> {
+    fn notify(&self, notification: Notification) {
+        for event in get_events(&notification) {
+            self.publish(event);
+        }
+        self.notifier.notify(notification);
+    }
+}
+
+/// Returns the events to publish for the notification. Only the new blocks and the received
+/// transactions are published
+fn get_events(notification: &Notification) -> Vec<Event> {
+    match notification {
+        Notification::FromWallet(_, notification) => get_events(notification),
+        Notification::NewBlockAddedToTheBlockchain(block, _) => {
+            let mut events: Vec<Event> = Vec::new();
+            if let Ok(mut hash) = block.header.get_hash256d() {
+                hash.reverse();
+                events.push((HASH_BLOCK_TOPIC, hash.to_vec()));
+            }
+            if let Some(raw_block) = serialize(block) {
+                events.push((RAW_BLOCK_TOPIC, raw_block));
+            }
+            events
+        }
+        Notification::TransactionOfAccountReceived(_, transaction) => {
+            let mut events: Vec<Event> = Vec::new();
+            if let Ok(mut transaction_id) = transaction.get_tx_id() {
+                transaction_id.reverse();
+                events.push((HASH_TRANSACTION_TOPIC, transaction_id.to_vec()));
+            }
+            if let Some(raw_transaction) = serialize(transaction) {
+                events.push((RAW_TRANSACTION_TOPIC, raw_transaction));
+            }
+            events
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn serialize<S: SerializableInternalOrder>(value: &S) -> Option<Vec<u8>> {
+    let mut bytes: Vec<u8> = Vec::new();
+    value.io_serialize(&mut bytes).ok()?;
+    Some(bytes)
+}
+
+/// Returns the frames of the event as they are sent to the subscribers
+fn get_message(topic: &str, body: &[u8], sequence: u32) -> Vec<u8> {
+    let mut message: Vec<u8> = Vec::new();
+    for frame in [topic.as_bytes(), body, &sequence.to_le_bytes()] {
+        message.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+        message.extend_from_slice(frame);
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        block_structure::coinbase::CoinbaseInfo,
+        testing::creation::{create_block, create_transaction},
+    };
+
+    #[test]
+    fn test01_new_blocks_are_published_by_hash_and_raw() {
+        let block = create_block([0; 32], 0);
+        let notification = Notification::FromWallet(
+            "personal".to_string(),
+            Box::new(Notification::NewBlockAddedToTheBlockchain(
+                block.clone(),
+                CoinbaseInfo {
+                    height: None,
+                    miner_tag: None,
+                },
+            )),
+        );
+
+        let events = get_events(&notification);
+
+        let mut hash = block.header.get_hash256d().unwrap();
+        hash.reverse();
+        assert_eq!(
+            events,
+            vec![
+                (HASH_BLOCK_TOPIC, hash.to_vec()),
+                (RAW_BLOCK_TOPIC, serialize(&block).unwrap()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test02_received_transactions_are_published_by_id_and_raw() {
+        let transaction = create_transaction(10);
+        let notification =
+            Notification::TransactionOfAccountReceived(Vec::new(), transaction.clone());
+
+        let events = get_events(&notification);
+
+        let mut transaction_id = transaction.get_tx_id().unwrap();
+        transaction_id.reverse();
+        assert_eq!(
+            events,
+            vec![
+                (HASH_TRANSACTION_TOPIC, transaction_id.to_vec()),
+                (RAW_TRANSACTION_TOPIC, serialize(&transaction).unwrap()),
+            ]
+        );
+        assert!(get_events(&Notification::ClosingPeers).is_empty());
+    }
+
+    #[test]
+    fn test03_events_are_sent_as_frames_with_their_length() {
+        let message = get_message(HASH_BLOCK_TOPIC, &[1, 2], 3);
+
+        let mut expected: Vec<u8> = vec![9, 0, 0, 0];
+        expected.extend_from_slice(b"hashblock");
+        expected.extend_from_slice(&[2, 0, 0, 0, 1, 2]);
+        expected.extend_from_slice(&[4, 0, 0, 0, 3, 0, 0, 0]);
+
+        assert_eq!(message, expected);
+    }
+}
@@ -1,2 +1,9 @@
+pub mod event_publisher;
 pub mod notification;
+pub mod notification_history;
+pub mod notification_hub;
+pub mod notification_json;
+pub mod notification_kind;
 pub mod notifier;
+pub mod wallet_notifier;
+pub mod websocket_notifier;
@@ -1,13 +1,25 @@
-use std::net::SocketAddr;
+use std::{net::SocketAddr, time::Duration};
 
 use crate::{
-    block_structure::{block::Block, hash::HashType, transaction::Transaction},
+    block_structure::{
+        block::Block, block_summary::BlockSummary, chain_tip::ChainTip, coinbase::CoinbaseInfo,
+        hash::HashType, mempool_info::MempoolInfo, transaction::Transaction,
+        transaction_description::TransactionDescription,
+    },
     messages::command_name::CommandName,
-    node_structure::connection_id::ConnectionId,
-    wallet_structure::account::Account,
+    node_structure::{
+        connection_id::ConnectionId, peer_information::PeerInformation,
+        script_subscriptions::ScriptEvent, sync_progress::SyncProgress,
+    },
+    wallet_structure::{
+        account::Account, amount::Amount, balance_history::BalanceCheckpoint,
+        exchange_rate::ExchangeRate, transaction_history::HistoryEntry,
+        transaction_preview::TransactionPreview,
+    },
 };
 
 /// The different types of notifications that the notifier can send.
+#[derive(Debug, Clone)]
 pub enum Notification {
     /// Notifies that we are trying to establish a connection with a peer.
     AttemptingHandshakeWithPeer(SocketAddr),
@@ -21,6 +33,24 @@ pub enum Notification {
     /// Notifies that we have to update a connection
     ConnectionUpdated(ConnectionId),
 
+    /// Notifies the latency of a connection, measured with a ping and its pong.
+    PeerLatency(ConnectionId, Duration),
+
+    /// Notifies the information of a connection, with its user agent, latency and bytes transferred.
+    PeerInformationUpdated(PeerInformation),
+
+    /// Notifies that the connection with a peer was closed.
+    PeerDisconnected(ConnectionId),
+
+    /// Notifies that our clock deviates from the time of the network by the given amount of seconds.
+    ClockSkew(i64),
+
+    /// Notifies the tip of the block chain recorded the last time the program was closed, before any network activity.
+    LastSyncedTip(ChainTip),
+
+    /// Notifies that the wallet, the UTXO set and the tip of the block chain were saved while running, with the time in seconds.
+    StateSaved(i64),
+
     /// Notifies that we have received a transaction for an account in the wallet.
     TransactionOfAccountReceived(Vec<Account>, Transaction),
 
@@ -30,6 +60,9 @@ pub enum Notification {
     /// Notifies that we have received a transaction for an account in the wallet in a block.
     TransactionOfAccountInNewBlock(Block, Transaction),
 
+    /// Notifies that a pending transaction of the wallet was dropped because a transaction of a new block spends the same outputs, with the ids of both.
+    TransactionConflicted(HashType, HashType),
+
     /// Notifies that we have successfully sent a transaction.
     SuccessfullySentTransaction(Transaction),
 
@@ -39,14 +72,17 @@ pub enum Notification {
     /// Notifies that we have received an amount of headers.
     HeadersReceived(u32),
 
-    /// Notifies the amount of blocks that we have downloaded.
-    ProgressDownloadingBlocks(u32, u32),
+    /// Notifies the progress of the download of the blocks, with the bytes downloaded and the estimated time left.
+    ProgressDownloadingBlocks(SyncProgress),
 
     /// Notifies the amount of blocks added to the blockchain.
     ProgressUpdatingBlockchain(u32, u32),
 
-    /// Notifies that we have received a block.
-    NewBlockAddedToTheBlockchain(Block),
+    /// Notifies that we have received a block, with the metadata of its coinbase.
+    NewBlockAddedToTheBlockchain(Block, CoinbaseInfo),
+
+    /// Notifies that an output of a subscribed script was created or spent.
+    ScriptHashEvent(ScriptEvent),
 
     /// Notifies that we have updated the selected account.
     UpdatedSelectedAccount(Account),
@@ -58,7 +94,22 @@ pub enum Notification {
     NotifyBlockchainIsReady,
 
     /// Notifies the balance of an account
-    LoadAvailableBalance(Account, f64, f64),
+    LoadAvailableBalance(Account, Amount, Amount),
+
+    /// Notifies that the balance of an account changed, with its available and pending balance.
+    AccountBalanceChanged(Account, Amount, Amount),
+
+    /// Notifies that a transaction of an account was added to the blockchain, to be added to its history.
+    AccountTransactionConfirmed(Account, Transaction),
+
+    /// Notifies the balance checkpoint (height, balance in satoshis) recorded for an account with a new block.
+    AccountBalanceCheckpoint(Account, BalanceCheckpoint),
+
+    /// Notifies that the tip of the blockchain advanced to the given height, with the hash of the block.
+    TipAdvanced(u64, HashType),
+
+    /// Notifies the statistics of the pending transactions, when they change.
+    MempoolInfo(MempoolInfo),
 
     /// Notifies that there is no account currently selected.
     AccountNotSelected,
@@ -66,6 +117,63 @@ pub enum Notification {
     /// Notifies the information of the transactions of an account.
     AccountTransactions(Account, Vec<Transaction>),
 
+    /// Notifies the transactions of the accounts of the wallet, from the newest one.
+    TransactionHistory(Vec<HistoryEntry>),
+
+    /// Notifies the summary of a transaction of the selected account, to be confirmed before sending it.
+    TransactionPreview(TransactionPreview),
+
+    /// Notifies the balance checkpoints (height, balance in satoshis) of an account.
+    AccountBalanceHistory(Account, Vec<BalanceCheckpoint>),
+
+    /// Notifies the amount of blocks walked while rescanning the wallet, with the amount to walk.
+    ProgressRescan(u32, u32),
+
+    /// Notifies that we have requested coins to the faucet for an account, with the id of the transaction.
+    FaucetCoinsRequested(Account, HashType),
+
+    /// Notifies that the request of coins to the faucet has failed.
+    FaucetRequestFailed(String),
+
+    /// Notifies that a transaction requested to the faucet was confirmed in a block.
+    FaucetTransactionConfirmed(Block, HashType),
+
+    /// Notifies the new price of a bitcoin in the fiat currency configured.
+    ExchangeRateUpdated(ExchangeRate),
+
+    /// Notifies that the wallet in use has changed, with the name of the new wallet.
+    WalletChanged(String),
+
+    /// Notifies that the wallet with the given name was saved and closed, leaving no wallet in use.
+    WalletClosed(String),
+
+    /// Notifies the name of the wallet in use and the names of the wallets that can be used.
+    AvailableWallets(String, Vec<String>),
+
+    /// Notifies that the wallet could not be changed.
+    WalletChangeFailed(String),
+
+    /// Notifies a notification that occurred while using the wallet with the given name.
+    FromWallet(String, Box<Notification>),
+
+    /// Notifies the raw data of a block in hexadecimal, serialized as in the network, with its hash.
+    RawBlock(HashType, String),
+
+    /// Notifies the raw data of a transaction in hexadecimal, serialized as in the network, with its id.
+    RawTransaction(HashType, String),
+
+    /// Notifies the breakdown of a transaction into its inputs and outputs.
+    TransactionDescribed(TransactionDescription),
+
+    /// Notifies that there was a problem while getting the raw data of a block or a transaction.
+    RawDataProblem(String),
+
+    /// Notifies the summary of the last blocks of the main chain, from the newest one.
+    RecentBlocks(Vec<BlockSummary>),
+
+    /// Notifies the transactions of a block, with its summary.
+    BlockTransactions(BlockSummary, Vec<Transaction>),
+
     /// Notifies that we have entered an invalid address.
     InvalidAddressEnter,
 
@@ -78,9 +186,42 @@ pub enum Notification {
     /// Notifies that we have failed to create an account.
     AccountCreationFail,
 
+    /// Notifies that we have changed the name, label or description of an account, with its previous name.
+    AccountEdited(String, Account),
+
+    /// Notifies that we have failed to edit an account, with the reason.
+    AccountEditFailed(String),
+
+    /// Notifies that we have changed the label of the transaction with the given id, being `None` when it was removed.
+    TransactionLabelChanged(HashType, Option<String>),
+
+    /// Notifies that the wallet was exported as a backup to the given path.
+    WalletExported(String),
+
+    /// Notifies that the backup in the given path was imported, with the amount of accounts added to the wallet.
+    WalletImported(String, usize),
+
+    /// Notifies that a backup of the wallet could not be exported or imported, with the reason.
+    WalletBackupFailed(String),
+
+    /// Notifies that the spending password of the wallet was changed, and if the wallet has one now.
+    SpendingPasswordChanged(bool),
+
+    /// Notifies that the private keys can be used for the given time, after giving the spending password.
+    WalletUnlocked(Duration),
+
+    /// Notifies that the spending password was missing or not correct, so the private keys could not be used, with the reason.
+    SpendingPasswordRejected(String),
+
     /// Notifies that we do not have enough funds to create a transaction.
     NotEnoughFunds,
 
+    /// Notifies that the amount to send is below the dust threshold, in satoshis, so the peers would not relay the transaction.
+    AmountIsDust(i64),
+
+    /// Notifies that a raw transaction entered could not be read or is not valid, and why.
+    RawTransactionRejected(String),
+
     /// Notifies that we have received a message.
     ReceivedMessage(CommandName),
 
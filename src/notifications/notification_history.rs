@@ -0,0 +1,162 @@
+use super::{notification::Notification, notifier::Notifier};
+
+use std::{
+    collections::VecDeque,
+    mem::{self, Discriminant},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// It's a notifier that keeps the last notifications received, so they can be given again to the
+/// interfaces that start listening after they were sent.
+///
+/// The notifications that represent the current state of the node, as the progress of the download
+/// or the tip of the blockchain, replace the previous one of the same type, so only the last state
+/// is given again. The rest are kept in order, discarding the oldest ones when the capacity is reached
+#[derive(Debug, Clone)]
+pub struct NotificationHistory {
+    notifications: Arc<Mutex<VecDeque<Notification>>>,
+    capacity: usize,
+}
+
+impl NotificationHistory {
+    pub fn new(capacity: usize) -> Self {
+        NotificationHistory {
+            notifications: Arc::new(Mutex::new(VecDeque::new())),
+            capacity,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, VecDeque<Notification>> {
+        match self.notifications.lock() {
+            Ok(notifications) => notifications,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Returns the notifications kept, from the oldest one
+    pub fn notifications(&self) -> Vec<Notification> {
+        self.lock().iter().cloned().collect()
+    }
+
+    /// Gives every notification kept to the notifier, from the oldest one
+    pub fn replay_to<N: Notifier>(&self, notifier: &N) {
+        for notification in self.notifications() {
+            notifier.notify(notification);
+        }
+    }
+}
+
+impl Notifier for NotificationHistory {
+    fn notify(&self, notification: Notification) {
+        let mut notifications = self.lock();
+
+        if let Some(state) = get_state(&notification) {
+            notifications.retain(|previous| get_state(previous) != Some(state));
+        }
+
+        notifications.push_back(notification);
+        while notifications.len() > self.capacity {
+            notifications.pop_front();
+        }
+    }
+}
+
+/// Returns the type of the notification if it represents the current state of the node, being the
+/// same for the notifications of a wallet
+fn get_state(notification: &Notification) -> Option<Discriminant<Notification>> {
+    match notification {
+        Notification::FromWallet(_, notification) => get_state(notification),
+        Notification::LastSyncedTip(_)
+        | Notification::StateSaved(_)
+        | Notification::ClockSkew(_)
+        | Notification::ProgressDownloadingBlocks(_)
+        | Notification::ProgressUpdatingBlockchain(_, _)
+        | Notification::ProgressRescan(_, _)
+        | Notification::NotifyBlockchainIsReady
+        | Notification::TipAdvanced(_, _)
+        | Notification::MempoolInfo(_)
+        | Notification::ExchangeRateUpdated(_)
+        | Notification::AvailableWallets(_, _) => Some(mem::discriminant(notification)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::node_structure::sync_progress::SyncProgress;
+
+    use std::time::Duration;
+
+    fn progress(blocks_done: u32) -> SyncProgress {
+        SyncProgress::new(blocks_done, 100, 0, Duration::from_secs(1), None)
+    }
+
+    #[derive(Clone)]
+    struct RecordNotifier(Arc<Mutex<Vec<Notification>>>);
+
+    impl Notifier for RecordNotifier {
+        fn notify(&self, notification: Notification) {
+            self.0.lock().unwrap().push(notification);
+        }
+    }
+
+    #[test]
+    fn test01_only_the_last_notifications_are_kept() {
+        let history = NotificationHistory::new(2);
+
+        history.notify(Notification::ClosingPeer);
+        history.notify(Notification::NotEnoughFunds);
+        history.notify(Notification::ClosingPeers);
+
+        assert!(matches!(
+            history.notifications().as_slice(),
+            [Notification::NotEnoughFunds, Notification::ClosingPeers]
+        ));
+    }
+
+    #[test]
+    fn test02_only_the_last_state_is_kept() {
+        let history = NotificationHistory::new(10);
+
+        history.notify(Notification::ProgressDownloadingBlocks(progress(10)));
+        history.notify(Notification::ClosingPeer);
+        history.notify(Notification::FromWallet(
+            "personal".to_string(),
+            Box::new(Notification::ProgressDownloadingBlocks(progress(50))),
+        ));
+        history.notify(Notification::NotifyBlockchainIsReady);
+
+        let notifications = history.notifications();
+        assert_eq!(3, notifications.len());
+        assert!(matches!(notifications[0], Notification::ClosingPeer));
+        assert!(matches!(
+            &notifications[1],
+            Notification::FromWallet(_, notification)
+                if matches!(notification.as_ref(), Notification::ProgressDownloadingBlocks(progress) if progress.blocks_done == 50)
+        ));
+        assert!(matches!(
+            notifications[2],
+            Notification::NotifyBlockchainIsReady
+        ));
+    }
+
+    #[test]
+    fn test03_late_notifiers_receive_the_notifications_kept() {
+        let history = NotificationHistory::new(10);
+        history.notify(Notification::NotifyBlockchainIsReady);
+        history.notify(Notification::ProgressUpdatingBlockchain(5, 5));
+
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        history.replay_to(&RecordNotifier(recorded.clone()));
+
+        assert!(matches!(
+            recorded.lock().unwrap().as_slice(),
+            [
+                Notification::NotifyBlockchainIsReady,
+                Notification::ProgressUpdatingBlockchain(5, 5)
+            ]
+        ));
+    }
+}
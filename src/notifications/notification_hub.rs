@@ -0,0 +1,308 @@
+use super::{
+    notification::Notification, notification_history::NotificationHistory,
+    notification_kind::NotificationKind, notifier::Notifier,
+};
+
+use std::{
+    sync::{
+        mpsc::{sync_channel, Receiver, SyncSender, TrySendError},
+        Arc, Mutex, MutexGuard,
+    },
+    thread::{self, JoinHandle},
+};
+
+/// It's the identifier given to each subscriber, used to unsubscribe it
+pub type SubscriptionId = u64;
+
+/// It represents which notifications a subscriber receives
+#[derive(Debug, Clone)]
+pub enum NotificationFilter {
+    /// Every notification
+    All,
+
+    /// Only the notifications of the given kinds
+    Only(Vec<NotificationKind>),
+
+    /// Only the notifications for which the function returns true
+    Matching(fn(&Notification) -> bool),
+}
+
+impl NotificationFilter {
+    /// Returns if the notification has to be given to the subscriber
+    pub fn accepts(&self, notification: &Notification) -> bool {
+        match self {
+            NotificationFilter::All => true,
+            NotificationFilter::Only(kinds) => {
+                kinds.contains(&NotificationKind::from(notification))
+            }
+            NotificationFilter::Matching(accepts) => accepts(notification),
+        }
+    }
+}
+
+struct Subscriber {
+    id: SubscriptionId,
+    filter: NotificationFilter,
+    sender: SyncSender<Notification>,
+    dropped: usize,
+}
+
+#[derive(Default)]
+struct Subscribers {
+    subscribers: Vec<Subscriber>,
+    next_id: SubscriptionId,
+}
+
+/// It's a notifier that gives each notification to every subscriber whose filter accepts it.
+/// Each subscriber receives its notifications through its own bounded channel, so a subscriber
+/// that does not keep up loses the notifications that do not fit in it instead of blocking the
+/// rest of the program. The subscribers can be added and removed at any time, and with a history
+/// the new subscribers first receive the notifications kept in it
+#[derive(Clone, Default)]
+pub struct NotificationHub {
+    subscribers: Arc<Mutex<Subscribers>>,
+    history: Option<NotificationHistory>,
+}
+
+impl NotificationHub {
+    pub fn new() -> Self {
+        NotificationHub::default()
+    }
+
+    /// The notifications will be kept in the history, to be given to the subscribers added later
+    pub fn with_history(self, history: NotificationHistory) -> Self {
+        NotificationHub {
+            history: Some(history),
+            ..self
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Subscribers> {
+        match self.subscribers.lock() {
+            Ok(subscribers) => subscribers,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Adds a subscriber that receives the notifications accepted by the filter, keeping up to
+    /// `capacity` of them until they are received. If there is a history, the notifications kept in
+    /// it that are accepted by the filter are received first
+    pub fn subscribe(
+        &self,
+        filter: NotificationFilter,
+        capacity: usize,
+    ) -> (SubscriptionId, Receiver<Notification>) {
+        let (sender, receiver) = sync_channel::<Notification>(capacity);
+        if let Some(history) = &self.history {
+            for notification in history.notifications() {
+                if filter.accepts(&notification) {
+                    let _ = sender.try_send(notification);
+                }
+            }
+        }
+
+        let mut subscribers = self.lock();
+        let id = subscribers.next_id;
+        subscribers.next_id += 1;
+        subscribers.subscribers.push(Subscriber {
+            id,
+            filter,
+            sender,
+            dropped: 0,
+        });
+
+        (id, receiver)
+    }
+
+    /// Removes the subscriber, returning if it was subscribed
+    pub fn unsubscribe(&self, id: SubscriptionId) -> bool {
+        let mut subscribers = self.lock();
+        let count = subscribers.subscribers.len();
+        subscribers
+            .subscribers
+            .retain(|subscriber| subscriber.id != id);
+
+        count != subscribers.subscribers.len()
+    }
+
+    /// Returns the amount of notifications the subscriber lost because its channel was full
+    pub fn dropped(&self, id: SubscriptionId) -> Option<usize> {
+        self.lock()
+            .subscribers
+            .iter()
+            .find(|subscriber| subscriber.id == id)
+            .map(|subscriber| subscriber.dropped)
+    }
+
+    /// Returns the amount of subscribers
+    pub fn subscriber_count(&self) -> usize {
+        self.lock().subscribers.len()
+    }
+}
+
+impl Notifier for NotificationHub {
+    /// Gives the notification to the subscribers that accept it, forgetting the ones whose
+    /// receiver was dropped
+    fn notify(&self, notification: Notification) {
+        if let Some(history) = &self.history {
+            history.notify(notification.clone());
+        }
+
+        let mut subscribers = self.lock();
+        subscribers.subscribers.retain_mut(|subscriber| {
+            if !subscriber.filter.accepts(&notification) {
+                return true;
+            }
+
+            match subscriber.sender.try_send(notification.clone()) {
+                Ok(_) => true,
+                Err(TrySendError::Full(_)) => {
+                    subscriber.dropped += 1;
+                    true
+                }
+                Err(TrySendError::Disconnected(_)) => false,
+            }
+        });
+    }
+}
+
+/// Creates a thread that gives every notification of the subscription to the notifier, until the
+/// subscriber is removed from the hub
+pub fn forward<N: Notifier + 'static>(
+    receiver: Receiver<Notification>,
+    notifier: N,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        for notification in receiver {
+            notifier.notify(notification);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+    fn peer_notification() -> Notification {
+        Notification::AttemptingHandshakeWithPeer(SocketAddr::new(
+            IpAddr::V4(Ipv4Addr::LOCALHOST),
+            18333,
+        ))
+    }
+
+    #[test]
+    fn test01_each_subscriber_receives_the_notifications_of_its_filter() {
+        let hub = NotificationHub::new();
+        let (_, peers) = hub.subscribe(NotificationFilter::Only(vec![NotificationKind::Peer]), 10);
+        let (_, wallets) =
+            hub.subscribe(NotificationFilter::Only(vec![NotificationKind::Wallet]), 10);
+        let (_, everything) = hub.subscribe(NotificationFilter::All, 10);
+
+        hub.notify(peer_notification());
+        hub.notify(Notification::FromWallet(
+            "personal".to_string(),
+            Box::new(Notification::NotEnoughFunds),
+        ));
+
+        assert!(matches!(
+            peers.try_recv(),
+            Ok(Notification::AttemptingHandshakeWithPeer(_))
+        ));
+        assert!(peers.try_recv().is_err());
+        assert!(matches!(
+            wallets.try_recv(),
+            Ok(Notification::FromWallet(_, _))
+        ));
+        assert!(wallets.try_recv().is_err());
+        assert_eq!(2, everything.try_iter().count());
+    }
+
+    #[test]
+    fn test02_full_subscribers_lose_notifications_without_blocking() {
+        let hub = NotificationHub::new();
+        let (id, receiver) = hub.subscribe(NotificationFilter::All, 1);
+
+        hub.notify(Notification::NotifyBlockchainIsReady);
+        hub.notify(Notification::ClosingPeers);
+        hub.notify(Notification::ClosingPeers);
+
+        assert_eq!(Some(2), hub.dropped(id));
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(Notification::NotifyBlockchainIsReady)
+        ));
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn test03_subscribers_can_leave_at_any_time() {
+        let hub = NotificationHub::new();
+        let (id, receiver) = hub.subscribe(NotificationFilter::All, 10);
+        let (_, dropped_receiver) = hub.subscribe(
+            NotificationFilter::Matching(|notification| {
+                matches!(notification, Notification::ClosingPeers)
+            }),
+            10,
+        );
+        drop(dropped_receiver);
+
+        hub.notify(Notification::ClosingPeers);
+        assert_eq!(1, hub.subscriber_count());
+
+        assert!(hub.unsubscribe(id));
+        assert!(!hub.unsubscribe(id));
+        hub.notify(Notification::ClosingPeers);
+
+        assert_eq!(1, receiver.try_iter().count());
+    }
+
+    #[test]
+    fn test04_subscription_can_be_forwarded_to_a_notifier() {
+        #[derive(Clone)]
+        struct CountNotifier(Arc<Mutex<usize>>);
+
+        impl Notifier for CountNotifier {
+            fn notify(&self, _: Notification) {
+                *self.0.lock().unwrap() += 1;
+            }
+        }
+
+        let hub = NotificationHub::new();
+        let count = Arc::new(Mutex::new(0));
+        let (id, receiver) = hub.subscribe(NotificationFilter::All, 10);
+        let handle = forward(receiver, CountNotifier(count.clone()));
+
+        hub.notify(Notification::NotifyBlockchainIsReady);
+        hub.notify(peer_notification());
+        hub.unsubscribe(id);
+        handle.join().unwrap();
+
+        assert_eq!(2, *count.lock().unwrap());
+    }
+
+    #[test]
+    fn test05_late_subscribers_receive_the_history() {
+        let hub = NotificationHub::new().with_history(NotificationHistory::new(10));
+
+        hub.notify(Notification::NotifyBlockchainIsReady);
+        hub.notify(peer_notification());
+
+        let (_, receiver) = hub.subscribe(
+            NotificationFilter::Only(vec![NotificationKind::Blockchain]),
+            10,
+        );
+        hub.notify(Notification::ProgressUpdatingBlockchain(1, 1));
+
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(Notification::NotifyBlockchainIsReady)
+        ));
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(Notification::ProgressUpdatingBlockchain(1, 1))
+        ));
+        assert!(receiver.try_recv().is_err());
+    }
+}
@@ -0,0 +1,241 @@
+use super::notification::Notification;
+
+/// Returns the notification as a JSON object, for the notifications mirrored to the web dashboards:
+/// the new blocks, the transactions of the wallet, the progress of the sync and the statistics of the pending transactions.
+/// The rest of the notifications have no representation
+pub fn to_json(notification: &Notification) -> Option<String> {
+    let fields: Vec<(&str, String)> = match notification {
+        Notification::FromWallet(wallet_name, notification) => {
+            let json = to_json(notification)?;
+            return Some(format!(
+                "{{\"wallet\":{},{}",
+                quote(wallet_name),
+                json.strip_prefix('{')?
+            ));
+        }
+        Notification::NewBlockAddedToTheBlockchain(block, coinbase_info) => vec![
+            ("event", quote("new_block")),
+            ("block", quote(&block.to_string())),
+            (
+                "height",
+                match coinbase_info.height {
+                    Some(height) => height.to_string(),
+                    None => "null".to_string(),
+                },
+            ),
+            (
+                "miner_tag",
+                match &coinbase_info.miner_tag {
+                    Some(miner_tag) => quote(miner_tag),
+                    None => "null".to_string(),
+                },
+            ),
+        ],
+        Notification::TransactionOfAccountReceived(accounts, transaction) => vec![
+            ("event", quote("transaction_received")),
+            ("transaction", quote(&transaction.to_string())),
+            (
+                "accounts",
+                format!(
+                    "[{}]",
+                    accounts
+                        .iter()
+                        .map(|account| quote(&account.account_name))
+                        .collect::<Vec<String>>()
+                        .join(",")
+                ),
+            ),
+        ],
+        Notification::TransactionOfAccountInNewBlock(block, transaction) => vec![
+            ("event", quote("transaction_in_block")),
+            ("transaction", quote(&transaction.to_string())),
+            ("block", quote(&block.to_string())),
+        ],
+        Notification::SuccessfullySentTransaction(transaction) => vec![
+            ("event", quote("transaction_sent")),
+            ("transaction", quote(&transaction.to_string())),
+        ],
+        Notification::HeadersReceived(amount) => vec![
+            ("event", quote("headers_received")),
+            ("amount", amount.to_string()),
+        ],
+        Notification::ProgressDownloadingBlocks(progress) => {
+            let mut fields = vec![
+                ("event", quote("blocks_downloaded")),
+                ("progress", progress.blocks_done.to_string()),
+                ("total", progress.blocks_total.to_string()),
+                ("bytes", progress.bytes_downloaded.to_string()),
+                (
+                    "blocks_per_second",
+                    format!("{:.2}", progress.blocks_per_second),
+                ),
+            ];
+            if let Some(eta) = progress.eta {
+                fields.push(("eta_seconds", eta.as_secs().to_string()));
+            }
+            if let Some(peer) = progress.peer {
+                fields.push(("peer", quote(&peer.to_string())));
+            }
+            if progress.blocks_to_retry > 0 {
+                fields.push(("to_retry", progress.blocks_to_retry.to_string()));
+            }
+            fields
+        }
+        Notification::ProgressUpdatingBlockchain(updated, total) => vec![
+            ("event", quote("blockchain_updated")),
+            ("progress", updated.to_string()),
+            ("total", total.to_string()),
+        ],
+        Notification::ProgressRescan(scanned, total) => vec![
+            ("event", quote("rescan_progress")),
+            ("progress", scanned.to_string()),
+            ("total", total.to_string()),
+        ],
+        Notification::NotifyBlockchainIsReady => vec![("event", quote("blockchain_ready"))],
+        Notification::StateSaved(timestamp) => vec![
+            ("event", quote("state_saved")),
+            ("timestamp", timestamp.to_string()),
+        ],
+        Notification::MempoolInfo(mempool_info) => vec![
+            ("event", quote("mempool_info")),
+            ("size", mempool_info.transaction_count.to_string()),
+            ("bytes", mempool_info.total_size.to_string()),
+            ("total_fee", mempool_info.total_fees.to_string()),
+            (
+                "min_fee_rate",
+                match mempool_info.min_fee_rate {
+                    Some(min_fee_rate) => min_fee_rate.to_string(),
+                    None => "null".to_string(),
+                },
+            ),
+        ],
+        _ => return None,
+    };
+
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(key, value)| format!("{}:{value}", quote(key)))
+        .collect();
+
+    Some(format!("{{{}}}", fields.join(",")))
+}
+
+/// Returns the text as a JSON string, escaping the characters that cannot appear in it
+pub fn quote(text: &str) -> String {
+    let mut quoted = String::from('"');
+    for character in text.chars() {
+        match character {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            character if (character as u32) < 0x20 => {
+                quoted.push_str(&format!("\\u{:04x}", character as u32))
+            }
+            character => quoted.push(character),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::block_structure::{
+        block::Block, block_header::BlockHeader, block_version::BlockVersion,
+        coinbase::CoinbaseInfo, compact256::Compact256, mempool_info::MempoolInfo,
+    };
+    use crate::messages::compact_size::CompactSize;
+    use crate::node_structure::sync_progress::SyncProgress;
+
+    use std::time::Duration;
+
+    fn create_block() -> Block {
+        Block {
+            header: BlockHeader::new(
+                BlockVersion::version(1),
+                [0; 32],
+                [0; 32],
+                0,
+                Compact256::from(u32::MAX),
+                0,
+                CompactSize::new(0),
+            ),
+            transactions: vec![],
+        }
+    }
+
+    #[test]
+    fn test01_new_block_is_represented_with_its_coinbase() {
+        let block = create_block();
+        let notification = Notification::NewBlockAddedToTheBlockchain(
+            block.clone(),
+            CoinbaseInfo {
+                height: Some(12),
+                miner_tag: Some("pool \"a\"".to_string()),
+            },
+        );
+
+        assert_eq!(
+            to_json(&notification),
+            Some(format!(
+                "{{\"event\":\"new_block\",\"block\":\"{block}\",\"height\":12,\"miner_tag\":\"pool \\\"a\\\"\"}}"
+            ))
+        );
+    }
+
+    #[test]
+    fn test02_notifications_of_a_wallet_are_tagged() {
+        let notification = Notification::FromWallet(
+            "personal".to_string(),
+            Box::new(Notification::ProgressDownloadingBlocks(SyncProgress::new(
+                3,
+                10,
+                512,
+                Duration::from_secs(2),
+                None,
+            ))),
+        );
+
+        assert_eq!(
+            to_json(&notification),
+            Some(
+                "{\"wallet\":\"personal\",\"event\":\"blocks_downloaded\",\"progress\":3,\"total\":10,\"bytes\":512,\"blocks_per_second\":1.50,\"eta_seconds\":4}"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test03_not_mirrored_notifications_have_no_representation() {
+        assert_eq!(to_json(&Notification::ClosingPeers), None);
+        assert_eq!(
+            to_json(&Notification::FromWallet(
+                "personal".to_string(),
+                Box::new(Notification::NotEnoughFunds)
+            )),
+            None
+        );
+    }
+
+    #[test]
+    fn test04_mempool_info_is_represented_with_its_counters() {
+        let notification = Notification::MempoolInfo(MempoolInfo {
+            transaction_count: 2,
+            total_size: 450,
+            total_fees: 900,
+            min_fee_rate: None,
+        });
+
+        assert_eq!(
+            to_json(&notification),
+            Some(
+                "{\"event\":\"mempool_info\",\"size\":2,\"bytes\":450,\"total_fee\":900,\"min_fee_rate\":null}"
+                    .to_string()
+            )
+        );
+    }
+}
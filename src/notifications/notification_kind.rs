@@ -0,0 +1,97 @@
+use super::notification::Notification;
+
+/// It represents the part of the program a notification comes from, so the subscribers can choose
+/// which notifications to receive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NotificationKind {
+    /// The connections with the peers and the messages received from them
+    Peer,
+
+    /// The download and the state of the blockchain
+    Blockchain,
+
+    /// The wallets, their accounts and their transactions
+    Wallet,
+}
+
+impl From<&Notification> for NotificationKind {
+    fn from(notification: &Notification) -> Self {
+        match notification {
+            Notification::AttemptingHandshakeWithPeer(_)
+            | Notification::SuccessfulHandshakeWithPeer(_)
+            | Notification::FailedHandshakeWithPeer(_)
+            | Notification::ConnectionUpdated(_)
+            | Notification::PeerLatency(_, _)
+            | Notification::PeerInformationUpdated(_)
+            | Notification::PeerDisconnected(_)
+            | Notification::ClockSkew(_)
+            | Notification::ReceivedMessage(_)
+            | Notification::ClosingPeer
+            | Notification::ClosingPeers => NotificationKind::Peer,
+
+            Notification::LastSyncedTip(_)
+            | Notification::StateSaved(_)
+            | Notification::ProblemVerifyingTransactionMerkleProofOfInclusion(_)
+            | Notification::SuccessfulMerkleProof(_, _)
+            | Notification::HeadersReceived(_)
+            | Notification::ProgressDownloadingBlocks(_)
+            | Notification::ProgressUpdatingBlockchain(_, _)
+            | Notification::NewBlockAddedToTheBlockchain(_, _)
+            | Notification::ScriptHashEvent(_)
+            | Notification::NotifyBlockchainIsReady
+            | Notification::TipAdvanced(_, _)
+            | Notification::MempoolInfo(_)
+            | Notification::RawBlock(_, _)
+            | Notification::RawTransaction(_, _)
+            | Notification::TransactionDescribed(_)
+            | Notification::RecentBlocks(_)
+            | Notification::BlockTransactions(_, _)
+            | Notification::RawDataProblem(_) => NotificationKind::Blockchain,
+
+            Notification::TransactionOfAccountReceived(_, _)
+            | Notification::TransactionOfAccountInNewBlock(_, _)
+            | Notification::TransactionConflicted(_, _)
+            | Notification::SuccessfullySentTransaction(_)
+            | Notification::UpdatedSelectedAccount(_)
+            | Notification::RegisterWalletAccount(_)
+            | Notification::LoadAvailableBalance(_, _, _)
+            | Notification::AccountBalanceChanged(_, _, _)
+            | Notification::AccountTransactionConfirmed(_, _)
+            | Notification::AccountBalanceCheckpoint(_, _)
+            | Notification::AccountNotSelected
+            | Notification::AccountTransactions(_, _)
+            | Notification::TransactionHistory(_)
+            | Notification::TransactionPreview(_)
+            | Notification::AccountBalanceHistory(_, _)
+            | Notification::ProgressRescan(_, _)
+            | Notification::FaucetCoinsRequested(_, _)
+            | Notification::FaucetRequestFailed(_)
+            | Notification::FaucetTransactionConfirmed(_, _)
+            | Notification::ExchangeRateUpdated(_)
+            | Notification::WalletChanged(_)
+            | Notification::WalletClosed(_)
+            | Notification::AvailableWallets(_, _)
+            | Notification::WalletChangeFailed(_)
+            | Notification::InvalidAddressEnter
+            | Notification::InvalidPublicKeyEnter
+            | Notification::InvalidPrivateKeyEnter
+            | Notification::AccountCreationFail
+            | Notification::AccountEdited(_, _)
+            | Notification::TransactionLabelChanged(_, _)
+            | Notification::AccountEditFailed(_)
+            | Notification::WalletExported(_)
+            | Notification::WalletImported(_, _)
+            | Notification::WalletBackupFailed(_)
+            | Notification::SpendingPasswordChanged(_)
+            | Notification::WalletUnlocked(_)
+            | Notification::SpendingPasswordRejected(_)
+            | Notification::NotEnoughFunds
+            | Notification::AmountIsDust(_)
+            | Notification::RawTransactionRejected(_) => NotificationKind::Wallet,
+
+            Notification::FromWallet(_, notification) => {
+                NotificationKind::from(notification.as_ref())
+            }
+        }
+    }
+}
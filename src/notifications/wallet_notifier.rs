@@ -0,0 +1,95 @@
+use super::{notification::Notification, notifier::Notifier};
+
+use std::sync::{Arc, Mutex};
+
+/// It's a notifier that tags every notification with the name of the wallet in use.
+/// When the wallet changes, the new name is used for the following notifications
+#[derive(Clone)]
+pub struct WalletNotifier<N: Notifier> {
+    wallet_name: Arc<Mutex<String>>,
+    notifier: N,
+}
+
+impl<N: Notifier> WalletNotifier<N> {
+    pub fn new(wallet_name: &str, notifier: N) -> Self {
+        WalletNotifier {
+            wallet_name: Arc::new(Mutex::new(wallet_name.to_string())),
+            notifier,
+        }
+    }
+
+    /// Returns the name of the wallet in use
+    pub fn get_wallet_name(&self) -> String {
+        match self.wallet_name.lock() {
+            Ok(wallet_name) => wallet_name.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    fn set_wallet_name(&self, name: &str) {
+        match self.wallet_name.lock() {
+            Ok(mut wallet_name) => *wallet_name = name.to_string(),
+            Err(poisoned) => *poisoned.into_inner() = name.to_string(),
+        }
+    }
+}
+
+impl<N: Notifier> Notifier for WalletNotifier<N> {
+    fn notify(&self, notification: Notification) {
+        match notification {
+            Notification::WalletChanged(name) => {
+                self.set_wallet_name(&name);
+                self.notifier.notify(Notification::WalletChanged(name));
+            }
+            Notification::FromWallet(name, notification) => {
+                self.notifier
+                    .notify(Notification::FromWallet(name, notification));
+            }
+            notification => {
+                self.notifier.notify(Notification::FromWallet(
+                    self.get_wallet_name(),
+                    Box::new(notification),
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc::{channel, Sender};
+
+    #[derive(Clone)]
+    struct TagNotifier {
+        sender: Sender<Option<String>>,
+    }
+
+    impl Notifier for TagNotifier {
+        fn notify(&self, notification: Notification) {
+            let tag = match notification {
+                Notification::FromWallet(name, _) => Some(name),
+                _ => None,
+            };
+            let _ = self.sender.send(tag);
+        }
+    }
+
+    #[test]
+    fn test01_notifications_are_tagged_with_the_wallet_in_use() {
+        let (sender, receiver) = channel::<Option<String>>();
+        let notifier = WalletNotifier::new("personal", TagNotifier { sender });
+
+        notifier.notify(Notification::AccountNotSelected);
+        notifier
+            .clone()
+            .notify(Notification::WalletChanged("testing".to_string()));
+        notifier.notify(Notification::NotEnoughFunds);
+
+        assert_eq!(receiver.recv(), Ok(Some("personal".to_string())));
+        assert_eq!(receiver.recv(), Ok(None));
+        assert_eq!(receiver.recv(), Ok(Some("testing".to_string())));
+        assert_eq!(notifier.get_wallet_name(), "testing");
+    }
+}
@@ -0,0 +1,329 @@
+use super::{
+    notification::Notification, notification_history::NotificationHistory, notification_json,
+    notifier::Notifier,
+};
+
+use crate::{connections::error_connection::ErrorConnection, logs::logger_sender::LoggerSender};
+
+use bitcoin_hashes::{sha1, Hash};
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// Value appended to the key of the client to accept the handshake, as defined in the RFC6455
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+const WEBSOCKET_KEY_HEADER: &str = "sec-websocket-key";
+
+/// The time a client has to send its handshake, and to receive each notification before it's dropped
+const WEBSOCKET_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_HANDSHAKE_SIZE: u64 = 8 * 1024;
+
+const TEXT_FRAME: u8 = 0x81;
+const MEDIUM_PAYLOAD: u8 = 126;
+const LARGE_PAYLOAD: u8 = 127;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// It's a notifier that pushes the notifications mirrored to the web dashboards as JSON to every
+/// WebSocket client connected, while giving all of them to the inner notifier. With a history, the
+/// clients receive the notifications kept in it when they connect
+#[derive(Clone)]
+pub struct WebSocketNotifier<N: Notifier> {
+    clients: Arc<Mutex<Vec<TcpStream>>>,
+    history: Option<NotificationHistory>,
+    notifier: N,
+    logger: LoggerSender,
+}
+
+impl<N: Notifier> WebSocketNotifier<N> {
+    pub fn new(notifier: N, logger: LoggerSender) -> Self {
+        WebSocketNotifier {
+            clients: Arc::new(Mutex::new(Vec::new())),
+            history: None,
+            notifier,
+            logger,
+        }
+    }
+
+    /// The notifications will be kept in the history, to be sent to the clients that connect later
+    pub fn with_history(self, history: NotificationHistory) -> Self {
+        WebSocketNotifier {
+            history: Some(history),
+            ..self
+        }
+    }
+
+    /// Creates a thread that accepts the WebSocket clients connecting to the listener
+    pub fn listen(&self, listener: TcpListener) -> JoinHandle<()> {
+        let clients = self.clients.clone();
+        let history = self.history.clone();
+        let logger = self.logger.clone();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => continue,
+                };
+
+                if stream.set_read_timeout(Some(WEBSOCKET_TIMEOUT)).is_err()
+                    || stream.set_write_timeout(Some(WEBSOCKET_TIMEOUT)).is_err()
+                {
+                    continue;
+                }
+
+                if accept_handshake(&mut stream).is_err() {
+                    let _ = logger.log_connection(
+                        "Rejecting a WebSocket client with an invalid handshake".to_string(),
+                    );
+                    continue;
+                }
+
+                let _ = logger.log_connection("WebSocket client connected".to_string());
+                if let Some(history) = &history {
+                    if replay(&mut stream, history).is_err() {
+                        continue;
+                    }
+                }
+
+                match clients.lock() {
+                    Ok(mut clients) => clients.push(stream),
+                    Err(poisoned) => poisoned.into_inner().push(stream),
+                }
+            }
+        })
+    }
+
+    /// Sends the JSON to every client, forgetting the ones that are no longer connected or that
+    /// don't receive it before the write timeout
+    fn push(&self, json: &str) {
+        let frame = get_text_frame(json);
+
+        let mut clients = match self.clients.lock() {
+            Ok(clients) => clients,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        clients.retain_mut(|client| client.write_all(&frame).is_ok());
+    }
+}
+
+impl<N: Notifier> Notifier for WebSocketNotifier<N> {
+    fn notify(&self, notification: Notification) {
+        if let Some(json) = notification_json::to_json(&notification) {
+            self.push(&json);
+        }
+        if let Some(history) = &self.history {
+            history.notify(notification.clone());
+        }
+        self.notifier.notify(notification);
+    }
+}
+
+/// Sends the notifications kept in the history to a client that has just connected
+///
+/// ### Error
+///  * `std::io::Error`: It will appear when the client is no longer connected
+fn replay<W: Write>(stream: &mut W, history: &NotificationHistory) -> std::io::Result<()> {
+    for notification in history.notifications() {
+        if let Some(json) = notification_json::to_json(&notification) {
+            stream.write_all(&get_text_frame(&json))?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads the opening handshake of the client and answers it, switching the connection to the WebSocket protocol.
+/// At most `MAX_HANDSHAKE_SIZE` bytes of the request are read
+///
+/// ### Error
+///  * `ErrorConnection::ErrorCannotReceiveMessage`: It will appear when the request is not a WebSocket handshake or it's too large
+///  * `ErrorConnection::ErrorCannotSendMessage`: It will appear when the answer cannot be sent to the client
+fn accept_handshake<RW: Read + Write>(stream: &mut RW) -> Result<(), ErrorConnection> {
+    let mut key: Option<String> = None;
+    let mut is_complete = false;
+
+    {
+        let reader = BufReader::new((&mut *stream).take(MAX_HANDSHAKE_SIZE));
+        for line in reader.lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => return Err(ErrorConnection::ErrorCannotReceiveMessage),
+            };
+
+            if line.is_empty() {
+                is_complete = true;
+                break;
+            }
+
+            if let Some((name, value)) = line.split_once(':') {
+                if name.trim().to_lowercase() == WEBSOCKET_KEY_HEADER {
+                    key = Some(value.trim().to_string());
+                }
+            }
+        }
+    }
+
+    let key = match key {
+        Some(key) if is_complete => key,
+        _ => return Err(ErrorConnection::ErrorCannotReceiveMessage),
+    };
+
+    let response = format!(
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        get_accept_key(&key)
+    );
+
+    match stream.write_all(response.as_bytes()) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(ErrorConnection::ErrorCannotSendMessage),
+    }
+}
+
+/// Returns the key that proves to the client that the handshake was understood
+fn get_accept_key(key: &str) -> String {
+    let hash = sha1::Hash::hash(format!("{key}{WEBSOCKET_GUID}").as_bytes());
+    to_base64(hash.as_ref())
+}
+
+/// Returns the text in a single unmasked frame, as the server sends them
+fn get_text_frame(text: &str) -> Vec<u8> {
+    let payload = text.as_bytes();
+    let mut frame = vec![TEXT_FRAME];
+
+    match payload.len() {
+        length if length < MEDIUM_PAYLOAD as usize => frame.push(length as u8),
+        length if length <= u16::MAX as usize => {
+            frame.push(MEDIUM_PAYLOAD);
+            frame.extend_from_slice(&(length as u16).to_be_bytes());
+        }
+        length => {
+            frame.push(LARGE_PAYLOAD);
+            frame.extend_from_slice(&(length as u64).to_be_bytes());
+        }
+    }
+
+    frame.extend_from_slice(payload);
+    frame
+}
+
+fn to_base64(bytes: &[u8]) -> String {
+    let mut encoded = String::new();
+
+    for chunk in bytes.chunks(3) {
+        let group = match chunk {
+            [first, second, third] => [*first, *second, *third],
+            [first, second] => [*first, *second, 0],
+            [first] => [*first, 0, 0],
+            _ => continue,
+        };
+        let value = u32::from_be_bytes([0, group[0], group[1], group[2]]);
+
+        for position in 0..4 {
+            match position <= chunk.len() {
+                true => {
+                    let index = (value >> (18 - 6 * position)) & 0x3f;
+                    encoded.push(BASE64_ALPHABET[index as usize] as char);
+                }
+                false => encoded.push('='),
+            }
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Cursor;
+
+    #[test]
+    fn test01_accept_key_of_the_rfc_example() {
+        assert_eq!(
+            get_accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[test]
+    fn test02_handshake_is_answered_with_the_accept_key() {
+        let request = "GET /notifications HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n\r\n";
+        let mut stream = Cursor::new(request.as_bytes().to_vec());
+
+        assert!(accept_handshake(&mut stream).is_ok());
+
+        let written = String::from_utf8(stream.into_inner()[request.len()..].to_vec()).unwrap();
+        assert!(written.starts_with("HTTP/1.1 101 Switching Protocols\r\n"));
+        assert!(written.contains("Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo=\r\n"));
+    }
+
+    #[test]
+    fn test03_request_without_key_is_rejected() {
+        let request = "GET / HTTP/1.1\r\nHost: localhost\r\n\r\n";
+        let mut stream = Cursor::new(request.as_bytes().to_vec());
+
+        assert_eq!(
+            accept_handshake(&mut stream),
+            Err(ErrorConnection::ErrorCannotReceiveMessage)
+        );
+    }
+
+    #[test]
+    fn test04_frames_encode_the_length_of_the_payload() {
+        assert_eq!(get_text_frame("Hello"), b"\x81\x05Hello".to_vec());
+
+        let medium = "a".repeat(300);
+        assert_eq!(get_text_frame(&medium)[..4], [0x81, 126, 0x01, 0x2c]);
+
+        let large = "a".repeat(70_000);
+        let frame = get_text_frame(&large);
+        assert_eq!(frame[..2], [0x81, 127]);
+        assert_eq!(frame[2..10], 70_000u64.to_be_bytes());
+        assert_eq!(frame.len(), 70_010);
+    }
+
+    #[test]
+    fn test05_base64_pads_the_last_group() {
+        assert_eq!(to_base64(b"Man"), "TWFu");
+        assert_eq!(to_base64(b"Ma"), "TWE=");
+        assert_eq!(to_base64(b"M"), "TQ==");
+    }
+
+    #[test]
+    fn test06_new_clients_receive_the_history() {
+        let history = NotificationHistory::new(10);
+        history.notify(Notification::ClosingPeers);
+        history.notify(Notification::NotifyBlockchainIsReady);
+
+        let mut stream: Vec<u8> = Vec::new();
+        replay(&mut stream, &history).unwrap();
+
+        assert_eq!(
+            stream,
+            get_text_frame(
+                &notification_json::to_json(&Notification::NotifyBlockchainIsReady).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test07_handshake_larger_than_the_limit_is_rejected() {
+        let request = format!(
+            "GET / HTTP/1.1\r\nCookie: {}\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n",
+            "a".repeat(MAX_HANDSHAKE_SIZE as usize)
+        );
+        let mut stream = Cursor::new(request.as_bytes().to_vec());
+
+        assert_eq!(
+            accept_handshake(&mut stream),
+            Err(ErrorConnection::ErrorCannotReceiveMessage)
+        );
+    }
+}
@@ -0,0 +1,374 @@
+use super::{
+    deserializable_internal_order::DeserializableInternalOrder,
+    error_serialization::ErrorSerialization,
+    serializable_internal_order::SerializableInternalOrder, versioned_format::VersionedFormat,
+};
+
+use crate::block_structure::hash::{hash256d_reduce, HashTypeReduced};
+
+use std::{
+    fs::{self, File},
+    io::{ErrorKind, Read, Write},
+    path::Path,
+};
+
+/// The bytes at the start of a checked file, to recognize it from the files saved without a header
+pub const CHECKED_FILE_MAGIC: [u8; 4] = *b"CSOS";
+
+/// The amount of bytes of the header, being the magic, the checksum and the length of the content
+const HEADER_SIZE: usize = 4 + 4 + 8;
+
+/// The amount of bytes of the version of the format, at the start of the content
+const VERSION_SIZE: usize = 4;
+
+const TEMPORARY_EXTENSION: &str = ".tmp";
+const BACKUP_EXTENSION: &str = ".bak";
+
+/// Returns the path of the backup of the file in the given path, being its previous version
+pub fn get_backup_path(path: &str) -> String {
+    format!("{path}{BACKUP_EXTENSION}")
+}
+
+/// It's the result of reading a checked file
+#[derive(Debug)]
+pub enum CheckedRead<V> {
+    /// Neither the file nor its backup exist
+    Missing,
+
+    /// The value saved in the file
+    Value(V),
+
+    /// The value saved in the backup, because the file is corrupted or missing. The error says
+    /// why the file was not used, being None if it was missing
+    Recovered(V, Option<ErrorSerialization>),
+}
+
+/// Writes the value to a temporary file next to the given path, with a header that has a magic,
+/// the checksum and the length of its content, and then it replaces the file with it. The
+/// content starts with the version of the format of the value. The previous file is kept as its
+/// backup, so a crash while writing never leaves a half-written file
+///
+/// ### Error
+///  * `ErrorSerialization::ErrorInSerialization`: It will appear when the value cannot be serialized or the files cannot be written
+pub fn write_checked_file<V: SerializableInternalOrder + VersionedFormat>(
+    path: &str,
+    value: &V,
+) -> Result<(), ErrorSerialization> {
+    let mut content: Vec<u8> = V::format_version().to_le_bytes().to_vec();
+    value.io_serialize(&mut content)?;
+    let checksum = hash256d_reduce(&content)?;
+
+    let temporary_path = format!("{path}{TEMPORARY_EXTENSION}");
+    let written = File::create(&temporary_path).and_then(|mut file| {
+        file.write_all(&CHECKED_FILE_MAGIC)?;
+        file.write_all(&checksum)?;
+        file.write_all(&(content.len() as u64).to_le_bytes())?;
+        file.write_all(&content)?;
+        file.sync_all()
+    });
+    if let Err(error) = written {
+        let _ = fs::remove_file(&temporary_path);
+        return Err(cannot_write(&temporary_path, error));
+    }
+
+    if Path::new(path).exists() {
+        if let Err(error) = fs::rename(path, get_backup_path(path)) {
+            return Err(cannot_write(path, error));
+        }
+    }
+
+    match fs::rename(&temporary_path, path) {
+        Ok(()) => Ok(()),
+        Err(error) => Err(cannot_write(path, error)),
+    }
+}
+
+/// Reads the value of a checked file, upgrading it if it was saved with an older version of its
+/// format. If the file is missing or corrupted, the value is read from its backup. The files saved
+/// without a header are read as the version 0
+///
+/// ### Error
+///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when the file is corrupted and there is no valid backup
+///  * `ErrorSerialization::FormatVersionNotSupported`: It will appear when the file was saved with a newer version of its format
+pub fn read_checked_file<V: DeserializableInternalOrder + VersionedFormat>(
+    path: &str,
+) -> Result<CheckedRead<V>, ErrorSerialization> {
+    let error = match read_file(path) {
+        Ok(Some(value)) => return Ok(CheckedRead::Value(value)),
+        Ok(None) => None,
+        Err(error @ ErrorSerialization::FormatVersionNotSupported(..)) => return Err(error),
+        Err(error) => Some(error),
+    };
+
+    match (read_file(&get_backup_path(path)), error) {
+        (Ok(Some(value)), error) => Ok(CheckedRead::Recovered(value, error)),
+        (Ok(None), None) => Ok(CheckedRead::Missing),
+        (_, Some(error)) | (Err(error), None) => Err(error),
+    }
+}
+
+/// Reads the value of the file, checking its header if it has one. It returns None if the file
+/// doesn't exist
+///
+/// ### Error
+///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when the file cannot be read or is corrupted
+///  * `ErrorSerialization::FormatVersionNotSupported`: It will appear when the file was saved with a newer version of its format
+fn read_file<V: DeserializableInternalOrder + VersionedFormat>(
+    path: &str,
+) -> Result<Option<V>, ErrorSerialization> {
+    let mut bytes: Vec<u8> = Vec::new();
+    match File::open(path).and_then(|mut file| file.read_to_end(&mut bytes)) {
+        Ok(_) => {}
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(error) => {
+            return Err(corrupted(
+                path,
+                &format!("it cannot be read, error : {:?}", error),
+            ))
+        }
+    }
+
+    let (version, content) = match bytes.starts_with(&CHECKED_FILE_MAGIC) {
+        true => checked_content(path, bytes)?,
+        false => (0, bytes),
+    };
+
+    if version > V::format_version() {
+        return Err(ErrorSerialization::FormatVersionNotSupported(
+            path.to_string(),
+            version,
+            V::format_version(),
+        ));
+    }
+
+    let content = V::migrate(version, content)?;
+    let mut stream = content.as_slice();
+    let value = match V::io_deserialize(&mut stream) {
+        Ok(value) => value,
+        Err(error) => {
+            return Err(corrupted(
+                path,
+                &format!("its value is not valid, error : {:?}", error),
+            ))
+        }
+    };
+
+    Ok(Some(value))
+}
+
+/// Returns the version of the format and the content of the bytes of a checked file, after
+/// checking its length and checksum
+///
+/// ### Error
+///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when the content is incomplete or its checksum doesn't match
+fn checked_content(path: &str, mut bytes: Vec<u8>) -> Result<(u32, Vec<u8>), ErrorSerialization> {
+    if bytes.len() < HEADER_SIZE + VERSION_SIZE {
+        return Err(corrupted(path, "its header is incomplete"));
+    }
+
+    let (header, content) = bytes.split_at(HEADER_SIZE);
+    let mut len_bytes: [u8; 8] = [0; 8];
+    len_bytes.copy_from_slice(&header[8..]);
+    if u64::from_le_bytes(len_bytes) != content.len() as u64 {
+        return Err(corrupted(path, "its content is incomplete"));
+    }
+
+    let checksum: HashTypeReduced = hash256d_reduce(content)?;
+    if header[4..8] != checksum {
+        return Err(corrupted(path, "its checksum doesn't match"));
+    }
+
+    let mut version_bytes: [u8; VERSION_SIZE] = [0; VERSION_SIZE];
+    version_bytes.copy_from_slice(&content[..VERSION_SIZE]);
+
+    bytes.drain(..HEADER_SIZE + VERSION_SIZE);
+    Ok((u32::from_le_bytes(version_bytes), bytes))
+}
+
+fn cannot_write(path: &str, error: std::io::Error) -> ErrorSerialization {
+    ErrorSerialization::ErrorInSerialization(format!(
+        "Cannot write the file {path}, error : {:?}",
+        error
+    ))
+}
+
+fn corrupted(path: &str, reason: &str) -> ErrorSerialization {
+    ErrorSerialization::ErrorInDeserialization(format!("The file {path} is corrupted, {reason}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::serialization::versioned_format::{from_unversioned, Migration};
+
+    use std::env;
+
+    /// A value whose format doubles its byte in the version 2
+    #[derive(Debug, PartialEq)]
+    struct Value(u8);
+
+    fn double(content: Vec<u8>) -> Result<Vec<u8>, ErrorSerialization> {
+        Ok(content.iter().map(|byte| byte * 2).collect())
+    }
+
+    impl SerializableInternalOrder for Value {
+        fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+            [self.0].io_serialize(stream)
+        }
+    }
+
+    impl DeserializableInternalOrder for Value {
+        fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+            let [byte] = <[u8; 1]>::io_deserialize(stream)?;
+            Ok(Value(byte))
+        }
+    }
+
+    impl VersionedFormat for Value {
+        const MIGRATIONS: &'static [Migration] = &[from_unversioned, double];
+    }
+
+    fn create_path(test_name: &str) -> String {
+        let path = env::temp_dir().join(format!("cargosos_checked_file_{test_name}"));
+        let path = path.to_string_lossy().to_string();
+        remove_files(&path);
+        path
+    }
+
+    fn remove_files(path: &str) {
+        let _ = fs::remove_file(path);
+        let _ = fs::remove_file(get_backup_path(path));
+    }
+
+    /// Writes a checked file with the given version of the format
+    fn write_version(path: &str, version: u32, byte: u8) {
+        let content = [&version.to_le_bytes()[..], &[byte]].concat();
+        let checksum = hash256d_reduce(&content).unwrap();
+        let bytes = [
+            &CHECKED_FILE_MAGIC[..],
+            &checksum,
+            &(content.len() as u64).to_le_bytes(),
+            &content,
+        ]
+        .concat();
+        fs::write(path, bytes).unwrap();
+    }
+
+    #[test]
+    fn test01_value_is_read_after_writing_it() {
+        let path = create_path("test01");
+
+        write_checked_file(&path, &Value(4)).unwrap();
+        let bytes = fs::read(&path).unwrap();
+
+        assert_eq!(CHECKED_FILE_MAGIC, bytes[..4]);
+        assert!(matches!(
+            read_checked_file::<Value>(&path),
+            Ok(CheckedRead::Value(Value(4)))
+        ));
+        assert!(!Path::new(&format!("{path}{TEMPORARY_EXTENSION}")).exists());
+
+        remove_files(&path);
+    }
+
+    #[test]
+    fn test02_previous_value_is_kept_as_backup() {
+        let path = create_path("test02");
+
+        write_checked_file(&path, &Value(1)).unwrap();
+        write_checked_file(&path, &Value(2)).unwrap();
+
+        assert!(matches!(
+            read_checked_file::<Value>(&path),
+            Ok(CheckedRead::Value(Value(2)))
+        ));
+        assert!(matches!(
+            read_checked_file::<Value>(&get_backup_path(&path)),
+            Ok(CheckedRead::Value(Value(1)))
+        ));
+
+        remove_files(&path);
+    }
+
+    #[test]
+    fn test03_corrupted_file_is_recovered_from_backup() {
+        let path = create_path("test03");
+
+        write_checked_file(&path, &Value(1)).unwrap();
+        write_checked_file(&path, &Value(2)).unwrap();
+        let mut bytes = fs::read(&path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+        fs::write(&path, bytes).unwrap();
+
+        assert!(matches!(
+            read_checked_file::<Value>(&path),
+            Ok(CheckedRead::Recovered(Value(1), Some(_)))
+        ));
+
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            read_checked_file::<Value>(&path),
+            Ok(CheckedRead::Recovered(Value(1), None))
+        ));
+
+        remove_files(&path);
+    }
+
+    #[test]
+    fn test04_corrupted_file_without_backup_is_an_error() {
+        let path = create_path("test04");
+
+        write_checked_file(&path, &Value(1)).unwrap();
+        let bytes = fs::read(&path).unwrap();
+        fs::write(&path, &bytes[..bytes.len() - 2]).unwrap();
+
+        assert!(matches!(
+            read_checked_file::<Value>(&path),
+            Err(ErrorSerialization::ErrorInDeserialization(_))
+        ));
+
+        remove_files(&path);
+    }
+
+    #[test]
+    fn test05_older_versions_are_migrated() {
+        let path = create_path("test05");
+
+        fs::write(&path, [5]).unwrap();
+        assert!(matches!(
+            read_checked_file::<Value>(&path),
+            Ok(CheckedRead::Value(Value(10)))
+        ));
+
+        write_version(&path, 1, 3);
+        assert!(matches!(
+            read_checked_file::<Value>(&path),
+            Ok(CheckedRead::Value(Value(6)))
+        ));
+
+        assert!(matches!(
+            read_checked_file::<Value>(&create_path("test05_missing")),
+            Ok(CheckedRead::Missing)
+        ));
+
+        remove_files(&path);
+    }
+
+    #[test]
+    fn test06_newer_version_is_not_replaced_by_backup() {
+        let path = create_path("test06");
+
+        write_checked_file(&path, &Value(1)).unwrap();
+        write_checked_file(&path, &Value(2)).unwrap();
+        write_version(&path, 3, 1);
+
+        assert!(matches!(
+            read_checked_file::<Value>(&path),
+            Err(ErrorSerialization::FormatVersionNotSupported(_, 3, 2))
+        ));
+
+        remove_files(&path);
+    }
+}
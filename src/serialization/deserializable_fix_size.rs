@@ -31,6 +31,22 @@ impl DeserializableFixSize for String {
     }
 }
 
+impl DeserializableFixSize for Vec<u8> {
+    fn deserialize_fix_size(
+        stream: &mut dyn Read,
+        sizes: usize,
+    ) -> Result<Self, ErrorSerialization> {
+        let mut buffer: Vec<u8> = vec![0; sizes];
+        if stream.read_exact(&mut buffer).is_err() {
+            return Err(ErrorSerialization::ErrorInDeserialization(
+                "Deserializing bytes".to_string(),
+            ));
+        }
+
+        Ok(buffer)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{DeserializableFixSize, ErrorSerialization};
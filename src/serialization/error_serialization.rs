@@ -1,3 +1,8 @@
+use crate::{
+    block_structure::hash::HashType,
+    messages::{command_name::CommandName, reject_message::RejectMessage},
+};
+
 /// It represents all posible errors that can occur in the process of serializing and deserializing
 #[derive(Debug)]
 pub enum ErrorSerialization {
@@ -18,4 +23,18 @@ pub enum ErrorSerialization {
 
     /// It will appear when there is an error in the reading from a stream
     ErrorWhileReading,
+
+    /// It will appear when the peer answers that it cannot serve the data with the given hashes
+    DataNotFound(Vec<HashType>),
+
+    /// It will appear when the peer rejects a message that we sent
+    MessageRejected(RejectMessage),
+
+    /// It will appear when a message announces a payload bigger than the maximum of its command,
+    /// which is a reason to disconnect the peer
+    PayloadTooLarge(CommandName, u32),
+
+    /// It will appear when the file in the path was saved by a newer version of the program, being
+    /// the version of its format and the newest version supported
+    FormatVersionNotSupported(String, u32, u32),
 }
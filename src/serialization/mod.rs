@@ -7,4 +7,7 @@ pub mod deserializable_fix_size;
 pub mod deserializable_internal_order;
 pub mod deserializable_little_endian;
 
+pub mod checked_file;
+pub mod versioned_format;
+
 pub mod error_serialization;
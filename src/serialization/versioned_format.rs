@@ -0,0 +1,62 @@
+use super::error_serialization::ErrorSerialization;
+
+/// It upgrades the content of a saved file from a version of its format to the next one
+pub type Migration = fn(Vec<u8>) -> Result<Vec<u8>, ErrorSerialization>;
+
+/// This trait is used to save a value in a file with the version of its format, so a change in
+/// the format doesn't break the files saved by older versions of the program
+pub trait VersionedFormat {
+    /// The migrations of the older formats, where the one in the position `i` upgrades the
+    /// content of the version `i` to the version `i + 1`. The files saved before the format had
+    /// a version have the version 0
+    const MIGRATIONS: &'static [Migration];
+
+    /// Returns the version of the format written by this program, being the amount of migrations
+    fn format_version() -> u32 {
+        Self::MIGRATIONS.len() as u32
+    }
+
+    /// Upgrades the content saved with the given version to the current format
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::ErrorInDeserialization`: It will appear when a migration cannot upgrade the content
+    fn migrate(version: u32, content: Vec<u8>) -> Result<Vec<u8>, ErrorSerialization> {
+        Self::MIGRATIONS
+            .iter()
+            .skip(version as usize)
+            .try_fold(content, |content, migration| migration(content))
+    }
+}
+
+/// The migration of the files saved before the format had a version. Their content has the same
+/// layout as the first version, the only difference is the header of the file
+pub fn from_unversioned(content: Vec<u8>) -> Result<Vec<u8>, ErrorSerialization> {
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Doubled;
+
+    fn double(content: Vec<u8>) -> Result<Vec<u8>, ErrorSerialization> {
+        Ok(content.iter().map(|byte| byte * 2).collect())
+    }
+
+    impl VersionedFormat for Doubled {
+        const MIGRATIONS: &'static [Migration] = &[from_unversioned, double];
+    }
+
+    #[test]
+    fn test01_version_is_the_amount_of_migrations() {
+        assert_eq!(2, Doubled::format_version());
+    }
+
+    #[test]
+    fn test02_only_the_migrations_after_the_version_are_applied() {
+        assert_eq!(vec![2, 4], Doubled::migrate(0, vec![1, 2]).unwrap());
+        assert_eq!(vec![2, 4], Doubled::migrate(1, vec![1, 2]).unwrap());
+        assert_eq!(vec![1, 2], Doubled::migrate(2, vec![1, 2]).unwrap());
+    }
+}
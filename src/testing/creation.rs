@@ -0,0 +1,107 @@
+use crate::{
+    block_structure::{
+        block::Block, block_header::BlockHeader, block_version::BlockVersion,
+        compact256::Compact256, hash::HashType, merkle_tree::MerkleTree, outpoint::Outpoint,
+        script::ScriptBuf, transaction::Transaction, transaction_input::TransactionInput,
+        transaction_output::TransactionOutput,
+    },
+    messages::compact_size::CompactSize,
+    wallet_structure::address::Address,
+};
+
+/// Creates a transaction with one input and one output, distinguished by its time
+pub fn create_transaction(time: u32) -> Transaction {
+    let transaction_input = TransactionInput::new(Outpoint::new([1; 32], 23), vec![1, 2, 3], 24);
+
+    let transaction_output = TransactionOutput {
+        value: 10,
+        pk_script: ScriptBuf::from(vec![4, 5, 6]),
+    };
+
+    Transaction {
+        version: 1,
+        tx_in: vec![transaction_input],
+        tx_out: vec![transaction_output],
+        lock_time: time,
+    }
+}
+
+/// Creates the coinbase transaction of the block at the given height, with the height as the first
+/// push of its signature script (BIP34)
+pub fn create_coinbase(height: u64) -> Transaction {
+    let mut height_bytes: Vec<u8> = height.to_le_bytes().to_vec();
+    while height_bytes.last() == Some(&0) {
+        height_bytes.pop();
+    }
+    if height_bytes.last().is_some_and(|byte| byte & 0x80 != 0) {
+        height_bytes.push(0);
+    }
+
+    let mut signature_script = vec![height_bytes.len() as u8];
+    signature_script.extend(height_bytes);
+
+    Transaction {
+        version: 1,
+        tx_in: vec![TransactionInput::new(
+            Outpoint::new([0; 32], u32::MAX),
+            signature_script,
+            u32::MAX,
+        )],
+        tx_out: vec![TransactionOutput {
+            value: 5_000_000_000,
+            pk_script: ScriptBuf::from(vec![4, 5, 6]),
+        }],
+        lock_time: 0,
+    }
+}
+
+/// Creates a transaction that pays the given value in satoshis to the address, distinguished by its time
+pub fn create_payment(address: &Address, value: i64, time: u32) -> Transaction {
+    let transaction_input = TransactionInput::new(Outpoint::new([2; 32], time), vec![1, 2, 3], 24);
+
+    let transaction_output = TransactionOutput {
+        value,
+        pk_script: address.generate_script_pubkey_p2pkh(),
+    };
+
+    Transaction {
+        version: 1,
+        tx_in: vec![transaction_input],
+        tx_out: vec![transaction_output],
+        lock_time: time,
+    }
+}
+
+pub fn create_header(previous_header: HashType, transaction_count: u64) -> BlockHeader {
+    BlockHeader::new(
+        BlockVersion::version(1),
+        previous_header,
+        [0; 32],
+        0,
+        Compact256::from(u32::MAX),
+        0,
+        CompactSize::new(transaction_count),
+    )
+}
+
+pub fn create_genesis_block() -> Block {
+    Block::new(BlockHeader::generate_genesis_block_header())
+}
+
+pub fn create_block(previous_header: HashType, transaction_count: u64) -> Block {
+    Block::new(create_header(previous_header, transaction_count))
+}
+
+/// Creates a complete block after the given header with the transactions and the merkle root of them
+pub fn create_block_with_transactions(
+    previous_header: HashType,
+    transactions: Vec<Transaction>,
+) -> Option<Block> {
+    let mut header = create_header(previous_header, transactions.len() as u64);
+    header.merkle_root_hash = MerkleTree::new(&transactions).ok()?.root;
+
+    Some(Block {
+        header,
+        transactions,
+    })
+}
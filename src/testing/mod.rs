@@ -1,4 +1,4 @@
-pub mod stream;
-
 pub mod creation;
+pub mod scenario;
 pub mod serialize_message;
+pub mod stream;
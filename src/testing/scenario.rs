@@ -0,0 +1,353 @@
+use super::{creation, serialize_message, stream::Stream};
+
+use crate::{
+    block_structure::{
+        block::Block, block_chain::BlockChain, error_block::ErrorBlock, hash::HashType,
+        transaction::Transaction, utxo_set::UTXOSet,
+    },
+    connections::{
+        network::Network, p2p_protocol::ProtocolVersionP2P, socket_conversion::socket_to_ipv6_port,
+        supported_services::SupportedServices,
+    },
+    logs::logger_sender::LoggerSender,
+    messages::{
+        bitfield_services::BitfieldServices, message::Message, version_message::VersionMessage,
+    },
+    node_structure::{
+        connection_id::ConnectionId, connection_type::ConnectionType, handshake::Handshake,
+        handshake_data::HandshakeData,
+    },
+    serialization::error_serialization::ErrorSerialization,
+    wallet_structure::account::Account,
+};
+
+use chrono::{offset::Utc, TimeZone};
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+const LOCAL_PORT: u16 = 8333;
+
+/// It's the builder of a scenario: the chain of blocks after the genesis block, the peers that serve it
+/// and the accounts of the wallet
+#[derive(Debug, Clone)]
+pub struct ScenarioBuilder {
+    network: Network,
+    protocol_version: ProtocolVersionP2P,
+    blocks: Vec<Vec<Transaction>>,
+    peers: Vec<SocketAddr>,
+    accounts: Vec<Account>,
+}
+
+impl ScenarioBuilder {
+    pub fn new() -> Self {
+        ScenarioBuilder {
+            network: Network::Testnet,
+            protocol_version: ProtocolVersionP2P::V70016,
+            blocks: Vec::new(),
+            peers: Vec::new(),
+            accounts: Vec::new(),
+        }
+    }
+
+    pub fn network(mut self, network: Network) -> Self {
+        self.network = network;
+        self
+    }
+
+    pub fn protocol_version(mut self, protocol_version: ProtocolVersionP2P) -> Self {
+        self.protocol_version = protocol_version;
+        self
+    }
+
+    /// Appends a block with the given transactions at the end of the chain
+    pub fn block(mut self, transactions: Vec<Transaction>) -> Self {
+        self.blocks.push(transactions);
+        self
+    }
+
+    /// Appends the given amount of blocks, each one with a transaction that does not belong to the wallet
+    pub fn empty_blocks(mut self, count: usize) -> Self {
+        for _ in 0..count {
+            let time = self.next_time();
+            self.blocks.push(vec![creation::create_transaction(time)]);
+        }
+        self
+    }
+
+    /// Appends a block with a transaction that pays the given value in satoshis to the account
+    pub fn payment(mut self, account: &Account, value: i64) -> Self {
+        let time = self.next_time();
+        self.blocks.push(vec![creation::create_payment(
+            &account.address,
+            value,
+            time,
+        )]);
+        self
+    }
+
+    pub fn peer(mut self, address: SocketAddr) -> Self {
+        self.peers.push(address);
+        self
+    }
+
+    pub fn account(mut self, account: Account) -> Self {
+        self.accounts.push(account);
+        self
+    }
+
+    /// Creates the blocks of the scenario, each one linked to the previous one and starting with the
+    /// coinbase of its height
+    ///
+    /// ### Error
+    ///  * `ErrorBlock::CouldNotHash`: It will appear when the header of a block cannot be hashed
+    ///  * `ErrorBlock::CouldNotGetTxId`: It will appear when the merkle root of a block cannot be calculated
+    pub fn build(self) -> Result<Scenario, ErrorBlock> {
+        let genesis = self.network.genesis_block();
+
+        let mut previous_hash = match genesis.header.get_hash256d() {
+            Ok(hash) => hash,
+            Err(_) => return Err(ErrorBlock::CouldNotHash),
+        };
+
+        let mut blocks: Vec<Block> = Vec::new();
+        for (index, block_transactions) in self.blocks.into_iter().enumerate() {
+            let mut transactions = vec![creation::create_coinbase(index as u64 + 1)];
+            transactions.extend(block_transactions);
+
+            let block = match creation::create_block_with_transactions(previous_hash, transactions)
+            {
+                Some(block) => block,
+                None => return Err(ErrorBlock::CouldNotGetTxId),
+            };
+
+            previous_hash = match block.header.get_hash256d() {
+                Ok(hash) => hash,
+                Err(_) => return Err(ErrorBlock::CouldNotHash),
+            };
+            blocks.push(block);
+        }
+
+        let peers = self
+            .peers
+            .into_iter()
+            .map(|address| ConnectionId::new(address, ConnectionType::Peer))
+            .collect();
+
+        Ok(Scenario {
+            network: self.network,
+            protocol_version: self.protocol_version,
+            genesis,
+            blocks,
+            peers,
+            accounts: self.accounts,
+        })
+    }
+
+    /// Returns a time not used by the transactions of the previous blocks, so every transaction is different
+    fn next_time(&self) -> u32 {
+        self.blocks.iter().map(Vec::len).sum::<usize>() as u32 + 1
+    }
+}
+
+impl Default for ScenarioBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// It's a chain of blocks, the peers that serve it and the accounts of the wallet. It assembles the
+/// stream each peer answers with in the initial block download and the outcomes expected from it
+#[derive(Debug, Clone)]
+pub struct Scenario {
+    pub network: Network,
+    pub protocol_version: ProtocolVersionP2P,
+    pub genesis: Block,
+    pub blocks: Vec<Block>,
+    pub peers: Vec<ConnectionId>,
+    pub accounts: Vec<Account>,
+}
+
+impl Scenario {
+    /// Returns the address of our node in the scenario
+    pub fn local_socket(&self) -> SocketAddr {
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), LOCAL_PORT)
+    }
+
+    pub fn handshake_data(&self) -> HandshakeData {
+        HandshakeData {
+            nonce: 0,
+            user_agent: String::new(),
+            relay: false,
+            network: self.network,
+        }
+    }
+
+    /// Returns the handshake of our node, starting with only the genesis block
+    pub fn handshake(&self, logger: LoggerSender) -> Handshake {
+        Handshake::new(
+            self.protocol_version,
+            BitfieldServices::new(vec![SupportedServices::Unname]),
+            0,
+            self.handshake_data(),
+            logger,
+        )
+    }
+
+    /// Returns the block chain our node starts with, which only has the genesis block
+    ///
+    /// ### Error
+    ///  * `ErrorBlock::CouldNotHash`: It will appear when the genesis block cannot be hashed
+    pub fn initial_block_chain(&self) -> Result<BlockChain, ErrorBlock> {
+        BlockChain::new(self.genesis.clone())
+    }
+
+    /// Returns the hashes of the blocks after the genesis block, in the order of the chain
+    pub fn block_hashes(&self) -> Vec<HashType> {
+        self.blocks
+            .iter()
+            .filter_map(|block| block.header.get_hash256d().ok())
+            .collect()
+    }
+
+    /// Returns the stream with the answers of the peer: the handshake, the headers of the whole chain
+    /// and every block after the genesis block
+    ///
+    /// ### Error
+    ///  * `ErrorSerialization::ErrorInSerialization`: It will appear when a message cannot be serialized
+    pub fn peer_stream(&self, peer: &ConnectionId) -> Result<Stream, ErrorSerialization> {
+        let mut stream: Vec<u8> = Vec::new();
+
+        self.serialize_version_message(&mut stream, peer)?;
+        if serialize_message::serialize_verack_message(&mut stream, self.network.magic_numbers())
+            .is_err()
+        {
+            return Err(ErrorSerialization::ErrorInSerialization(
+                "While serializing the verack message".to_string(),
+            ));
+        }
+
+        serialize_message::serialize_headers_message(
+            &mut stream,
+            self.network.magic_numbers(),
+            self.blocks.iter().map(|block| block.header).collect(),
+        )?;
+
+        for block in self.blocks.iter() {
+            serialize_message::serialize_block_message(
+                &mut stream,
+                self.network.magic_numbers(),
+                block.clone(),
+            )?;
+        }
+
+        Ok(Stream::new(stream))
+    }
+
+    /// Returns the block chain expected after downloading the headers
+    ///
+    /// ### Error
+    ///  * `ErrorBlock::CouldNotAppendBlock`: It will appear when a header cannot be appended
+    pub fn expected_headers(&self) -> Result<BlockChain, ErrorBlock> {
+        let mut block_chain = self.initial_block_chain()?;
+        for block in self.blocks.iter() {
+            block_chain.append_header(block.header)?;
+        }
+        Ok(block_chain)
+    }
+
+    /// Returns the block chain expected after downloading the blocks
+    ///
+    /// ### Error
+    ///  * `ErrorBlock::CouldNotAppendBlock`: It will appear when a block cannot be appended
+    pub fn expected_block_chain(&self) -> Result<BlockChain, ErrorBlock> {
+        let mut block_chain = self.initial_block_chain()?;
+        for block in self.blocks.iter() {
+            block_chain.append_block(block.clone())?;
+        }
+        Ok(block_chain)
+    }
+
+    /// Returns the balance in satoshis expected for the account after downloading the blocks
+    pub fn expected_balance(&self, account: &Account) -> i64 {
+        UTXOSet::new(self.blocks.clone()).get_balance_in_satoshis(&account.address)
+    }
+
+    /// Serializes the version message of the peer, announcing the height of the whole chain
+    fn serialize_version_message(
+        &self,
+        stream: &mut Vec<u8>,
+        peer: &ConnectionId,
+    ) -> Result<(), ErrorSerialization> {
+        let timestamp = match Utc.timestamp_opt(1234, 0).single() {
+            Some(timestamp) => timestamp,
+            None => {
+                return Err(ErrorSerialization::ErrorInSerialization(
+                    "Invalid timestamp for the version message".to_string(),
+                ))
+            }
+        };
+
+        let (recv_addr, recv_port) = socket_to_ipv6_port(&self.local_socket());
+        let (trans_addr, trans_port) = socket_to_ipv6_port(&peer.address);
+
+        let version_message = VersionMessage {
+            version: self.protocol_version,
+            services: BitfieldServices::new(vec![SupportedServices::NodeNetwork]),
+            timestamp,
+            recv_services: BitfieldServices::new(vec![SupportedServices::Unname]),
+            recv_addr,
+            recv_port,
+            trans_addr,
+            trans_port,
+            nonce: 0,
+            user_agent: String::new(),
+            start_height: self.blocks.len() as i32,
+            relay: false,
+        };
+
+        VersionMessage::serialize_message(stream, self.network.magic_numbers(), &version_message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::messages::{command_name::CommandName, message};
+
+    #[test]
+    fn test01_blocks_are_linked_after_the_genesis_block() {
+        let scenario = ScenarioBuilder::new().empty_blocks(3).build().unwrap();
+
+        let mut previous_hash = scenario.genesis.header.get_hash256d().unwrap();
+        for block in scenario.blocks.iter() {
+            assert_eq!(block.header.previous_block_header_hash, previous_hash);
+            assert!(block.is_complete());
+            assert!(block.proof_of_inclusion());
+            assert!(block.has_valid_coinbase());
+
+            previous_hash = block.header.get_hash256d().unwrap();
+        }
+
+        assert_eq!(scenario.block_hashes().len(), 3);
+        assert_eq!(scenario.block_hashes().last(), Some(&previous_hash));
+    }
+
+    #[test]
+    fn test02_peer_stream_starts_with_the_handshake() {
+        let address = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2)), 8333);
+        let scenario = ScenarioBuilder::new()
+            .empty_blocks(2)
+            .peer(address)
+            .build()
+            .unwrap();
+
+        let mut stream = scenario.peer_stream(&scenario.peers[0]).unwrap();
+
+        let header = message::deserialize_until_found(&mut stream, CommandName::Version).unwrap();
+        let version_message = VersionMessage::deserialize_message(&mut stream, header).unwrap();
+        assert_eq!(version_message.start_height, 2);
+
+        assert!(message::deserialize_until_found(&mut stream, CommandName::Verack).is_ok());
+    }
+}
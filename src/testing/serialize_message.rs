@@ -1,4 +1,4 @@
-use cargosos_bitcoin::{
+use crate::{
     block_structure::{block::Block, block_header::BlockHeader, transaction::Transaction},
     connections::{p2p_protocol::ProtocolVersionP2P, supported_services::SupportedServices},
     messages::{
@@ -12,7 +12,7 @@ use cargosos_bitcoin::{
 
 use std::{io::Write, net::Ipv4Addr};
 
-use chrono::{offset::Utc, DateTime, NaiveDateTime};
+use chrono::{offset::Utc, TimeZone};
 
 pub fn serialize_verack_message<W: Write>(
     stream: &mut W,
@@ -31,12 +31,18 @@ pub fn serialize_version_message<W: Write>(
     local_ip: (Ipv4Addr, u16),
     remote_ip: (Ipv4Addr, u16),
 ) -> Result<(), ErrorNode> {
-    let naive = NaiveDateTime::from_timestamp_opt(1234 as i64, 0).unwrap();
-    let timestamp: DateTime<Utc> = DateTime::from_utc(naive, Utc);
+    let timestamp = match Utc.timestamp_opt(1234, 0).single() {
+        Some(timestamp) => timestamp,
+        None => {
+            return Err(ErrorNode::WhileSerializing(
+                "Invalid timestamp for the version message".to_string(),
+            ))
+        }
+    };
 
     let version_message = VersionMessage {
         version: protocol_version,
-        services: services,
+        services,
         timestamp,
         recv_services: BitfieldServices::new(vec![SupportedServices::Unname]),
         recv_addr: Ipv4Addr::to_ipv6_mapped(&local_ip.0),
@@ -49,7 +55,11 @@ pub fn serialize_version_message<W: Write>(
         relay: handshake_data.relay,
     };
 
-    VersionMessage::serialize_message(stream, handshake_data.magic_number, &version_message)?;
+    VersionMessage::serialize_message(
+        stream,
+        handshake_data.network.magic_numbers(),
+        &version_message,
+    )?;
 
     Ok(())
 }
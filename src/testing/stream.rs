@@ -1,5 +1,8 @@
 use std::io::{Read, Write};
 
+/// It's a stream in memory that reads from the given bytes and keeps what is written to it.
+/// When there is nothing left to read it answers as a non blocking socket would
+#[derive(Debug, Clone, Default)]
 pub struct Stream {
     write_stream: Vec<u8>,
     read_stream: Vec<u8>,
@@ -15,41 +18,34 @@ impl Stream {
         }
     }
 
+    /// Returns a new stream to read what was written to this one
     pub fn get_write_stream(&self) -> Stream {
-        Stream {
-            read_stream: self.write_stream.clone(),
-            write_stream: Vec::new(),
-            pointer: 0,
-        }
+        Stream::new(self.write_stream.clone())
     }
 }
 
 impl Read for Stream {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        let mut i = 0;
-        while i < buf.len() && self.pointer < self.read_stream.len() {
-            buf[i] = self.read_stream[self.pointer];
-            self.pointer += 1;
-            i += 1;
-        }
-        if i == 0 {
+        let remaining = &self.read_stream[self.pointer..];
+        if remaining.is_empty() && !buf.is_empty() {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::WouldBlock,
                 "Error reading the stream",
             ));
         }
-        Ok(i)
+
+        let length = buf.len().min(remaining.len());
+        buf[..length].copy_from_slice(&remaining[..length]);
+        self.pointer += length;
+
+        Ok(length)
     }
 }
 
 impl Write for Stream {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        let mut i = 0;
-        while i < buf.len() {
-            self.write_stream.push(buf[i]);
-            i += 1;
-        }
-        Ok(i)
+        self.write_stream.extend_from_slice(buf);
+        Ok(buf.len())
     }
 
     fn flush(&mut self) -> std::io::Result<()> {
@@ -1,8 +1,11 @@
 use super::{
+    account_metadata::AccountMetadata,
     address::Address,
+    amount::Amount,
     error_wallet::ErrorWallet,
     private_key::{PrivateKey, PrivateKeyType},
     public_key::{PublicKey, PublicKeyType},
+    transaction_options::TransactionOptions,
 };
 
 use crate::serialization::{
@@ -14,9 +17,11 @@ use crate::serialization::{
     serializable_little_endian::SerializableLittleEndian,
 };
 
+use crate::connections::network::Network;
+
 use crate::block_structure::{
-    outpoint::Outpoint, transaction::Transaction, transaction_output::TransactionOutput,
-    utxo_set::UTXOSet,
+    outpoint::Outpoint, relay_policy, transaction::Transaction,
+    transaction_output::TransactionOutput, utxo_set::UTXOSet,
 };
 
 use std::{
@@ -33,43 +38,61 @@ pub struct Account {
     pub private_key: PrivateKey,
     pub public_key: PublicKey,
     pub address: Address,
+    pub metadata: AccountMetadata,
 }
 
 impl Account {
+    /// Creates the account of the given keys, with its address in the given network
     pub fn new(
         name: &str,
         private_key_bytes: &PrivateKeyType,
         public_key_bytes: &PublicKeyType,
+        network: Network,
     ) -> Result<Account, ErrorWallet> {
         let account_name = name.to_string();
         let private_key = PrivateKey::new(private_key_bytes)?;
         let public_key = PublicKey::new(public_key_bytes);
-        let address = Address::from_public_key(&public_key)?;
+        let address = Address::from_public_key(&public_key, network)?;
 
         Ok(Account {
             account_name,
             private_key,
             public_key,
             address,
+            metadata: AccountMetadata::new(),
         })
     }
 
+    /// Creates the account of the given keys, with its address in the given network
     pub fn from_keys(
         name: &str,
         private_key: PrivateKey,
         public_key: PublicKey,
+        network: Network,
     ) -> Result<Account, ErrorWallet> {
         let account_name = name.to_string();
-        let address = Address::from_public_key(&public_key)?;
+        let address = Address::from_public_key(&public_key, network)?;
 
         Ok(Account {
             account_name,
             private_key,
             public_key,
             address,
+            metadata: AccountMetadata::new(),
         })
     }
 
+    /// Creates an account with a new pair of keys, so they don't have to be generated elsewhere,
+    /// with its address in the given network
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotGeneratePrivateKey`: It will appear when the private key cannot be generated
+    pub fn generate(name: &str, network: Network) -> Result<Account, ErrorWallet> {
+        let private_key = PrivateKey::generate()?;
+        let public_key = private_key.public_key();
+        Account::from_keys(name, private_key, public_key, network)
+    }
+
     /// Returns true if the account owns the given transaction output (works for P2PKH) and false otherwise.
     pub fn verify_transaction_output_ownership(&self, txo: &TransactionOutput) -> bool {
         self.address.verify_transaction_ownership(txo)
@@ -85,47 +108,108 @@ impl Account {
         utxo_set.get_balance_in_satoshis(&self.address)
     }
 
-    /// Returns the balance of the account in tbtc
-    pub fn get_balance_in_tbtc(&self, utxo_set: UTXOSet) -> f64 {
-        utxo_set.get_balance_in_tbtc(&self.address)
+    /// Returns the balance of the account
+    pub fn get_balance(&self, utxo_set: UTXOSet) -> Amount {
+        utxo_set.get_balance(&self.address)
     }
 
-    /// Returns a transaction given the amount and to whom it is sent
+    /// Returns a transaction given the amount and to whom it is sent, with the lock time and
+    /// replaceability of the options
     ///
     /// ### Error
     ///  * `ErrorWallet::CannotCreateNewTransaction`: It will appear when a transaction cannot be created
     ///  * `ErrorWallet::NotEnoughFunds`: It will appear when an account does not have enough funds to create a transaction for the amount requested
+    ///  * `ErrorWallet::DustOutput`: It will appear when the amount is below the dust threshold of the output to the address
+    ///  * `ErrorWallet::InvalidAmount`: It will appear when the fee is negative or the amount and the fee overflow
     pub fn create_transaction(
         &self,
         to: Address,
-        amount: i64,
-        fee: i64,
+        amount: Amount,
+        fee: Amount,
+        options: TransactionOptions,
         utxo_set: &UTXOSet,
     ) -> Result<Transaction, ErrorWallet> {
-        let mut available_outputs = utxo_set.get_utxo_list_with_outpoints(Some(&self.address));
-        available_outputs.sort_by(|(_, a), (_, b)| b.value.cmp(&a.value));
-
-        let mut input_amount = 0;
-        let mut outputs_to_spend: Vec<(Outpoint, TransactionOutput)> = vec![];
-        for (available_outpoint, available_transaction) in available_outputs.iter() {
-            input_amount += available_transaction.value;
-            outputs_to_spend.push((available_outpoint.clone(), available_transaction.clone()));
-            if input_amount >= (amount + fee) {
-                break;
-            }
+        let outputs_to_spend = select_outputs(&self.address, &to, amount, fee, utxo_set)?;
+
+        match Transaction::from_account_to_address(
+            self,
+            &outputs_to_spend,
+            &to,
+            amount.to_sat(),
+            fee.to_sat(),
+            options,
+        ) {
+            Ok(transaction) => Ok(transaction),
+            Err(error) => Err(ErrorWallet::CannotCreateNewTransaction(format!(
+                "Error while trying to create a new transaction. Error: {:?}",
+                error
+            ))),
         }
+    }
 
-        if input_amount < (amount + fee) {
-            return Err(ErrorWallet::NotEnoughFunds(format!("Not enough funds to create the transaction. Input amount: {}. Output amount: {}. Fee: {}", input_amount, amount, fee)));
+    /// Returns a transaction sending every unspent output of the account, minus the fee, to the address.
+    /// It's used to sweep a private key that is not kept in the wallet into one of its accounts
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotCreateNewTransaction`: It will appear when a transaction cannot be created
+    ///  * `ErrorWallet::NotEnoughFunds`: It will appear when the account has no unspent outputs or they don't pay the fee
+    ///  * `ErrorWallet::DustOutput`: It will appear when what is left after the fee is below the dust threshold of the output to the address
+    ///  * `ErrorWallet::InvalidAmount`: It will appear when the fee is negative or the outputs overflow
+    pub fn create_sweep_transaction(
+        &self,
+        to: Address,
+        fee: Amount,
+        options: TransactionOptions,
+        utxo_set: &UTXOSet,
+    ) -> Result<Transaction, ErrorWallet> {
+        let outputs_to_spend: HashMap<Outpoint, TransactionOutput> = utxo_set
+            .get_utxo_list_with_outpoints(Some(&self.address))
+            .into_iter()
+            .collect();
+
+        let total = match Amount::checked_sum(
+            outputs_to_spend
+                .values()
+                .map(|output| Amount::from_sat(output.value)),
+        ) {
+            Some(total) if !fee.is_negative() => total,
+            _ => {
+                return Err(ErrorWallet::InvalidAmount(format!(
+                    "The outputs of the account {} cannot be swept with a fee of {fee}",
+                    self.account_name
+                )))
+            }
+        };
+
+        let amount = match total.checked_sub(fee) {
+            Some(amount) if !outputs_to_spend.is_empty() && !amount.is_negative() => amount,
+            _ => {
+                return Err(ErrorWallet::NotEnoughFunds(format!(
+                    "Not enough funds to sweep the account. Input amount: {total}. Fee: {fee}"
+                )))
+            }
+        };
+
+        let dust_threshold =
+            relay_policy::get_dust_threshold(to.generate_script_pubkey().as_script());
+        if amount.to_sat() < dust_threshold {
+            return Err(ErrorWallet::DustOutput(format!(
+                "The amount of {} satoshis is below the dust threshold of {dust_threshold} satoshis",
+                amount.to_sat()
+            )));
         }
 
-        let outputs_to_spend: HashMap<Outpoint, TransactionOutput> =
-            outputs_to_spend.into_iter().collect();
-
-        match Transaction::from_account_to_address(self, &outputs_to_spend, &to, amount, fee) {
+        match Transaction::from_account_to_address(
+            self,
+            &outputs_to_spend,
+            &to,
+            amount.to_sat(),
+            fee.to_sat(),
+            options,
+        ) {
             Ok(transaction) => Ok(transaction),
             Err(error) => Err(ErrorWallet::CannotCreateNewTransaction(format!(
-                "Error while trying to create a new transaction. Error: {:?}",
+                "Error while trying to create the sweep transaction. Error: {:?}",
                 error
             ))),
         }
@@ -140,6 +224,61 @@ impl Account {
     }
 }
 
+/// Returns the unspent outputs of the address to spend to pay the amount and the fee, taking the
+/// biggest ones first
+///
+/// ### Error
+///  * `ErrorWallet::NotEnoughFunds`: It will appear when the address does not have enough funds for the amount requested
+///  * `ErrorWallet::DustOutput`: It will appear when the amount is below the dust threshold of the output to the address
+///  * `ErrorWallet::InvalidAmount`: It will appear when the fee is negative or the amount and the fee overflow
+pub fn select_outputs(
+    from: &Address,
+    to: &Address,
+    amount: Amount,
+    fee: Amount,
+    utxo_set: &UTXOSet,
+) -> Result<HashMap<Outpoint, TransactionOutput>, ErrorWallet> {
+    let dust_threshold = relay_policy::get_dust_threshold(to.generate_script_pubkey().as_script());
+    if amount.to_sat() < dust_threshold {
+        return Err(ErrorWallet::DustOutput(format!(
+            "The amount of {} satoshis is below the dust threshold of {dust_threshold} satoshis",
+            amount.to_sat()
+        )));
+    }
+
+    let total = match amount.checked_add(fee) {
+        Some(total) if !fee.is_negative() => total,
+        _ => {
+            return Err(ErrorWallet::InvalidAmount(format!(
+                "The amount of {amount} and the fee of {fee} cannot be paid"
+            )))
+        }
+    };
+
+    let mut available_outputs = utxo_set.get_utxo_list_with_outpoints(Some(from));
+    available_outputs.sort_by(|(_, a), (_, b)| b.value.cmp(&a.value));
+
+    let mut input_amount = Amount::ZERO;
+    let mut outputs_to_spend: Vec<(Outpoint, TransactionOutput)> = vec![];
+    for (available_outpoint, available_transaction) in available_outputs.iter() {
+        input_amount = match input_amount.checked_add(Amount::from_sat(available_transaction.value))
+        {
+            Some(input_amount) => input_amount,
+            None => break,
+        };
+        outputs_to_spend.push((available_outpoint.clone(), available_transaction.clone()));
+        if input_amount >= total {
+            break;
+        }
+    }
+
+    if input_amount < total {
+        return Err(ErrorWallet::NotEnoughFunds(format!("Not enough funds to create the transaction. Input amount: {}. Output amount: {}. Fee: {}", input_amount, amount, fee)));
+    }
+
+    Ok(outputs_to_spend.into_iter().collect())
+}
+
 impl PartialEq for Account {
     fn eq(&self, other: &Self) -> bool {
         self.account_name == other.account_name
@@ -168,6 +307,7 @@ impl DeserializableInternalOrder for Account {
             private_key: PrivateKey::io_deserialize(stream)?,
             public_key: PublicKey::io_deserialize(stream)?,
             address: Address::io_deserialize(stream)?,
+            metadata: AccountMetadata::default(),
         })
     }
 }
@@ -176,8 +316,8 @@ impl Display for Account {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Account Name: {}\n    with address: {}",
-            self.account_name, self.address
+            "Account Name: {}\n    with address: {}{}",
+            self.account_name, self.address, self.metadata
         )
     }
 }
@@ -187,6 +327,8 @@ impl Display for Account {
 mod tests {
     use super::*;
 
+    use crate::testing::creation;
+
     #[test]
     fn test_1_correct_account_creation() {
         let priv_key_bytes: [u8; 32] = [
@@ -199,7 +341,8 @@ mod tests {
             0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
             0x35, 0x14, 0x92, 0x4A, 0x22,
         ];
-        let account = Account::new("test", &priv_key_bytes, &pubkey_bytes).unwrap();
+        let account =
+            Account::new("test", &priv_key_bytes, &pubkey_bytes, Network::Testnet).unwrap();
         assert_eq!(account.account_name, "test");
         assert_eq!(account.public_key.as_bytes(), pubkey_bytes);
     }
@@ -220,7 +363,7 @@ mod tests {
         let account_name = "test".to_string();
         let private_key = PrivateKey::new(&priv_key_bytes).unwrap();
         let public_key = PublicKey::new(&pubkey_bytes);
-        let address = Address::from_public_key(&public_key).unwrap();
+        let address = Address::from_public_key(&public_key, Network::Testnet).unwrap();
 
         let mut serialized_fields: Vec<u8> = Vec::new();
         (account_name.len() as u64)
@@ -231,7 +374,8 @@ mod tests {
         public_key.io_serialize(&mut serialized_fields).unwrap();
         address.io_serialize(&mut serialized_fields).unwrap();
 
-        let account = Account::new("test", &priv_key_bytes, &pubkey_bytes).unwrap();
+        let account =
+            Account::new("test", &priv_key_bytes, &pubkey_bytes, Network::Testnet).unwrap();
 
         let mut serialized_account: Vec<u8> = Vec::new();
         account.io_serialize(&mut serialized_account).unwrap();
@@ -252,7 +396,8 @@ mod tests {
             0x35, 0x14, 0x92, 0x4A, 0x22,
         ];
 
-        let account = Account::new("test", &priv_key_bytes, &pubkey_bytes).unwrap();
+        let account =
+            Account::new("test", &priv_key_bytes, &pubkey_bytes, Network::Testnet).unwrap();
 
         let mut serialized_transaction: Vec<u8> = Vec::new();
         account.io_serialize(&mut serialized_transaction).unwrap();
@@ -277,6 +422,7 @@ mod tests {
                 0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
                 0x35, 0x14, 0x92, 0x4A, 0x22,
             ],
+            Network::Testnet,
         )
         .unwrap();
 
@@ -310,6 +456,7 @@ mod tests {
                 0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
                 0x35, 0x14, 0x92, 0x4A, 0x22,
             ],
+            Network::Testnet,
         )
         .unwrap();
 
@@ -330,4 +477,79 @@ mod tests {
 
         assert!(account_old.verify_transaction_output_ownership(&transaction_output));
     }
+
+    #[test]
+    fn test_06_generated_account_has_the_address_of_its_keys() {
+        let account = Account::generate("Generated", Network::Testnet).unwrap();
+
+        assert_eq!("Generated", account.account_name);
+        assert_eq!(account.private_key.public_key(), account.public_key);
+        assert_eq!(
+            Address::from_public_key(&account.public_key, Network::Testnet).unwrap(),
+            account.address
+        );
+
+        let account = Account::generate("Generated", Network::Mainnet).unwrap();
+        assert_eq!(
+            Address::from_public_key(&account.public_key, Network::Mainnet).unwrap(),
+            account.address
+        );
+    }
+
+    #[test]
+    fn test_07_sweep_sends_every_unspent_output_minus_the_fee() {
+        let swept = Account::generate("Swept", Network::Testnet).unwrap();
+        let destination = Account::generate("Destination", Network::Testnet).unwrap();
+
+        let payments = vec![
+            creation::create_payment(&swept.address, 30_000, 1),
+            creation::create_payment(&swept.address, 20_000, 2),
+            creation::create_payment(&destination.address, 70_000, 3),
+        ];
+        let block = creation::create_block_with_transactions([0; 32], payments).unwrap();
+        let utxo_set = UTXOSet::new(vec![block]);
+
+        let transaction = swept
+            .create_sweep_transaction(
+                destination.address.clone(),
+                Amount::from_sat(1_000),
+                TransactionOptions::default(),
+                &utxo_set,
+            )
+            .unwrap();
+
+        assert_eq!(2, transaction.tx_in.len());
+        assert_eq!(
+            vec![TransactionOutput::new(
+                49_000,
+                destination.address.generate_script_pubkey()
+            )],
+            transaction.tx_out
+        );
+        assert!(transaction
+            .tx_in
+            .iter()
+            .all(|tx_in| !tx_in.signature_script.is_empty()));
+
+        assert!(matches!(
+            swept.create_sweep_transaction(
+                destination.address.clone(),
+                Amount::from_sat(50_000),
+                TransactionOptions::default(),
+                &utxo_set,
+            ),
+            Err(ErrorWallet::DustOutput(_))
+        ));
+        assert!(matches!(
+            Account::generate("Empty", Network::Testnet)
+                .unwrap()
+                .create_sweep_transaction(
+                    destination.address,
+                    Amount::from_sat(1_000),
+                    TransactionOptions::default(),
+                    &utxo_set,
+                ),
+            Err(ErrorWallet::NotEnoughFunds(_))
+        ));
+    }
 }
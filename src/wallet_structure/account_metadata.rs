@@ -0,0 +1,161 @@
+use crate::serialization::{
+    deserializable_fix_size::DeserializableFixSize,
+    deserializable_internal_order::DeserializableInternalOrder,
+    deserializable_little_endian::DeserializableLittleEndian,
+    error_serialization::ErrorSerialization,
+    serializable_internal_order::SerializableInternalOrder,
+    serializable_little_endian::SerializableLittleEndian,
+};
+
+use chrono::{offset::Utc, TimeZone};
+
+use std::{
+    fmt::Display,
+    io::{Read, Write},
+};
+
+/// It's the information of an account given by the user, that is not needed to use it
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AccountMetadata {
+    /// A short text to recognize the account
+    pub label: Option<String>,
+
+    /// Notes about the account
+    pub description: Option<String>,
+
+    /// The seconds since the epoch when the account was added to the wallet. The accounts saved
+    /// before it was registered don't have it
+    pub creation_timestamp: Option<i64>,
+}
+
+impl AccountMetadata {
+    /// Creates the metadata of an account added now
+    pub fn new() -> Self {
+        AccountMetadata {
+            creation_timestamp: Some(Utc::now().timestamp()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Serializes the text, with a flag saying if there is one
+fn serialize_text(text: &Option<String>, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+    text.is_some().le_serialize(stream)?;
+    if let Some(text) = text {
+        (text.len() as u64).le_serialize(stream)?;
+        text.le_serialize(stream)?;
+    }
+    Ok(())
+}
+
+/// Deserializes a text serialized with its flag
+fn deserialize_text(stream: &mut dyn Read) -> Result<Option<String>, ErrorSerialization> {
+    if !bool::le_deserialize(stream)? {
+        return Ok(None);
+    }
+    let text_len = u64::le_deserialize(stream)? as usize;
+    Ok(Some(String::deserialize_fix_size(stream, text_len)?))
+}
+
+impl SerializableInternalOrder for AccountMetadata {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        serialize_text(&self.label, stream)?;
+        serialize_text(&self.description, stream)?;
+
+        self.creation_timestamp.is_some().le_serialize(stream)?;
+        if let Some(creation_timestamp) = self.creation_timestamp {
+            creation_timestamp.le_serialize(stream)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl DeserializableInternalOrder for AccountMetadata {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        let label = deserialize_text(stream)?;
+        let description = deserialize_text(stream)?;
+        let creation_timestamp = match bool::le_deserialize(stream)? {
+            true => Some(i64::le_deserialize(stream)?),
+            false => None,
+        };
+
+        Ok(AccountMetadata {
+            label,
+            description,
+            creation_timestamp,
+        })
+    }
+}
+
+impl Display for AccountMetadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(label) = &self.label {
+            write!(f, "\n    with label: {label}")?;
+        }
+        if let Some(description) = &self.description {
+            write!(f, "\n    with description: {description}")?;
+        }
+        if let Some(creation_timestamp) = self.creation_timestamp {
+            if let Some(creation_date) = Utc.timestamp_opt(creation_timestamp, 0).single() {
+                write!(
+                    f,
+                    "\n    created on: {}",
+                    creation_date.format("%Y-%m-%d %H:%M")
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_metadata_is_serialized_and_deserialized() {
+        let metadata = AccountMetadata {
+            label: Some("Savings".to_string()),
+            description: Some("Coins for the holidays".to_string()),
+            creation_timestamp: Some(1_690_000_000),
+        };
+
+        let mut stream: Vec<u8> = Vec::new();
+        metadata.io_serialize(&mut stream).unwrap();
+
+        assert_eq!(
+            metadata,
+            AccountMetadata::io_deserialize(&mut stream.as_slice()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test02_metadata_without_values_is_three_flags() {
+        let mut stream: Vec<u8> = Vec::new();
+        AccountMetadata::default()
+            .io_serialize(&mut stream)
+            .unwrap();
+
+        assert_eq!(vec![0x00, 0x00, 0x00], stream);
+        assert_eq!(
+            AccountMetadata::default(),
+            AccountMetadata::io_deserialize(&mut stream.as_slice()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test03_metadata_is_shown_only_with_its_values() {
+        let metadata = AccountMetadata {
+            label: Some("Savings".to_string()),
+            description: None,
+            creation_timestamp: Some(0),
+        };
+
+        assert_eq!(
+            "\n    with label: Savings\n    created on: 1970-01-01 00:00",
+            metadata.to_string()
+        );
+        assert_eq!("", AccountMetadata::default().to_string());
+    }
+}
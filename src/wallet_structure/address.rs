@@ -9,7 +9,13 @@ use crate::serialization::{
     serializable_little_endian::SerializableLittleEndian,
 };
 
-use crate::block_structure::{hash::hash256d_reduce, transaction_output::TransactionOutput};
+use crate::block_structure::{
+    hash::hash256d_reduce,
+    script::{ScriptBuf, ScriptType},
+    transaction_output::TransactionOutput,
+};
+
+use crate::connections::network::Network;
 
 use std::{
     convert::TryInto,
@@ -20,7 +26,6 @@ use std::{
 use bs58::decode;
 
 pub const ADDRESS_SIZE: usize = 25;
-pub const ADDRESS_TESTNET_VERSION_BYTE: u8 = 0x6f;
 
 pub type AddressType = [u8; ADDRESS_SIZE];
 
@@ -37,9 +42,9 @@ impl Address {
     /// ### Error
     ///  * `ErrorWallet::CannotDecodeAddress`: It will appear when address for an account cannot be generated
     pub fn new(address: &str) -> Result<Address, ErrorWallet> {
-        if address.len() != 34 {
+        if !(34..=35).contains(&address.len()) {
             return Err(ErrorWallet::CannotDecodeAddress(format!(
-                "Invalid address length, expected 34, got {}",
+                "Invalid address length, expected 34 or 35, got {}",
                 address.len()
             )));
         }
@@ -67,10 +72,13 @@ impl Address {
         })
     }
 
-    /// Generates an Address from a public key
+    /// Generates an Address from a public key, with the version byte of the given network
     /// ### Error
     ///  * `ErrorWallet::CannotCreateAccount`: It will appear when there was a problem hashing
-    pub fn from_public_key(public_key: &PublicKey) -> Result<Address, ErrorWallet> {
+    pub fn from_public_key(
+        public_key: &PublicKey,
+        network: Network,
+    ) -> Result<Address, ErrorWallet> {
         let hashed_pk = match public_key.get_hashed_160() {
             Ok(hashed_pk) => hashed_pk,
             Err(e) => {
@@ -80,20 +88,27 @@ impl Address {
                 )))
             }
         };
-        let mut extended_hashed_pk = Vec::new();
-        extended_hashed_pk.push(ADDRESS_TESTNET_VERSION_BYTE);
-        extended_hashed_pk.extend_from_slice(&hashed_pk);
-        let checksum = match hash256d_reduce(&extended_hashed_pk) {
+        Address::from_hash(network.address_version_byte(), &hashed_pk)
+    }
+
+    /// Generates an Address from the hash of a public key or of a script, prefixed with the given version byte
+    /// ### Error
+    ///  * `ErrorWallet::CannotCreateAddress`: It will appear when there was a problem hashing
+    pub fn from_hash(version_byte: u8, hash: &[u8; 20]) -> Result<Address, ErrorWallet> {
+        let mut extended_hash = Vec::new();
+        extended_hash.push(version_byte);
+        extended_hash.extend_from_slice(hash);
+        let checksum = match hash256d_reduce(&extended_hash) {
             Ok(checksum) => checksum,
             Err(e) => {
                 return Err(ErrorWallet::CannotCreateAddress(format!(
-                    "Cannot hash public key, error : {:?}",
+                    "Cannot hash the address, error : {:?}",
                     e
                 )))
             }
         };
         let mut address_bytes = [0; 25];
-        address_bytes[..21].clone_from_slice(&extended_hashed_pk);
+        address_bytes[..21].clone_from_slice(&extended_hash);
         address_bytes[21..25].clone_from_slice(&checksum);
         let address_string = bs58::encode(address_bytes.to_vec()).into_string();
         Ok(Address {
@@ -102,35 +117,44 @@ impl Address {
         })
     }
 
+    /// Returns true if the address pays to the hash of a redeem script (P2SH) instead of a public key
+    pub fn is_script_hash(&self) -> bool {
+        [Network::Mainnet, Network::Testnet]
+            .iter()
+            .any(|network| network.script_version_byte() == self.address_bytes[0])
+    }
+
     /// Extracts the hashed public key from the address
-    fn extract_hashed_pk(&self) -> &[u8] {
+    pub fn extract_hashed_pk(&self) -> &[u8] {
         &self.address_bytes[1..21] as &[u8]
     }
 
     /// Generates the script pubkey for P2PKH from this address
-    pub fn generate_script_pubkey_p2pkh(&self) -> Vec<u8> {
-        let mut script_pubkey = vec![0x76, 0xa9, 0x14];
-        script_pubkey.extend_from_slice(self.extract_hashed_pk());
-        script_pubkey.extend_from_slice(&[0x88, 0xac]);
-        script_pubkey
+    pub fn generate_script_pubkey_p2pkh(&self) -> ScriptBuf {
+        let mut hashed_pk = [0; 20];
+        hashed_pk.copy_from_slice(self.extract_hashed_pk());
+        ScriptBuf::new_p2pkh(&hashed_pk)
     }
 
-    /// Returns true if the address owns the given transaction output (works for P2PKH) and false otherwise.
-    pub fn verify_transaction_ownership(&self, txo: &TransactionOutput) -> bool {
-        let pk_script = txo.pk_script.clone();
-        if pk_script.len() != 25 {
-            return false;
-        }
-        if pk_script[0] != 0x76
-            || pk_script[1] != 0xa9
-            || pk_script[2] != 0x14
-            || pk_script[23] != 0x88
-            || pk_script[24] != 0xac
-        {
-            return false;
+    /// Generates the script pubkey that pays to this address, being P2SH or P2PKH depending on its version byte
+    pub fn generate_script_pubkey(&self) -> ScriptBuf {
+        let mut hash = [0; 20];
+        hash.copy_from_slice(self.extract_hashed_pk());
+        match self.is_script_hash() {
+            true => ScriptBuf::new_p2sh(&hash),
+            false => ScriptBuf::new_p2pkh(&hash),
         }
-        let hashed_pk = &pk_script[3..23];
-        hashed_pk == self.extract_hashed_pk()
+    }
+
+    /// Returns true if the address owns the given transaction output (works for P2PKH and P2SH) and false otherwise.
+    pub fn verify_transaction_ownership(&self, txo: &TransactionOutput) -> bool {
+        let script_type = match self.is_script_hash() {
+            true => ScriptType::PayToScriptHash,
+            false => ScriptType::PayToPublicKeyHash,
+        };
+        let pk_script = txo.pk_script.as_script();
+        pk_script.get_type() == script_type
+            && pk_script.get_hash() == Some(self.extract_hashed_pk())
     }
 }
 
@@ -197,7 +221,7 @@ mod tests {
             0x35, 0x14, 0x92, 0x4A, 0x22,
         ];
         let pubkey = PublicKey::new(&pubkey_bytes);
-        let address = Address::from_public_key(&pubkey).unwrap();
+        let address = Address::from_public_key(&pubkey, Network::Testnet).unwrap();
         let actual_address = Address::new("mnQLoVaZ3w1NLVmUhfG8hh6WoG3iu7cnNw").unwrap();
         assert_eq!(address, actual_address);
     }
@@ -223,4 +247,58 @@ mod tests {
 
         assert!(address.verify_transaction_ownership(&transaction_output));
     }
+
+    #[test]
+    fn test_05_correct_address_creation_in_mainnet() {
+        let pubkey_bytes: [u8; 33] = [
+            0x03, 0xBC, 0x6D, 0x45, 0xD2, 0x10, 0x1E, 0x91, 0x28, 0xDE, 0x14, 0xB5, 0xB6, 0x68,
+            0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
+            0x35, 0x14, 0x92, 0x4A, 0x22,
+        ];
+        let pubkey = PublicKey::new(&pubkey_bytes);
+        let address = Address::from_public_key(&pubkey, Network::Mainnet).unwrap();
+        let actual_address = Address::new("17tPWSVaEua7ZPHrz6HksmtBwGT1wv1tFs").unwrap();
+        assert_eq!(address, actual_address);
+
+        let testnet_address = Address::from_public_key(&pubkey, Network::Testnet).unwrap();
+        assert_eq!(
+            address.extract_hashed_pk(),
+            testnet_address.extract_hashed_pk()
+        );
+    }
+
+    #[test]
+    fn test_06_script_hash_address_pays_to_p2sh() {
+        let script_hash = [8; 20];
+        let address =
+            Address::from_hash(Network::Testnet.script_version_byte(), &script_hash).unwrap();
+        assert_eq!(35, address.to_string().len());
+        assert!(address.to_string().starts_with('2'));
+        assert_eq!(address, Address::new(&address.to_string()).unwrap());
+
+        assert!(address.is_script_hash());
+        assert_eq!(
+            ScriptBuf::new_p2sh(&script_hash),
+            address.generate_script_pubkey()
+        );
+        assert!(
+            address.verify_transaction_ownership(&TransactionOutput::new(
+                1000,
+                ScriptBuf::new_p2sh(&script_hash)
+            ))
+        );
+        assert!(
+            !address.verify_transaction_ownership(&TransactionOutput::new(
+                1000,
+                ScriptBuf::new_p2pkh(&script_hash)
+            ))
+        );
+
+        let public_key_address = Address::new("mnQLoVaZ3w1NLVmUhfG8hh6WoG3iu7cnNw").unwrap();
+        assert!(!public_key_address.is_script_hash());
+        assert_eq!(
+            public_key_address.generate_script_pubkey_p2pkh(),
+            public_key_address.generate_script_pubkey()
+        );
+    }
 }
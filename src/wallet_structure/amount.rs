@@ -0,0 +1,227 @@
+use super::error_wallet::ErrorWallet;
+
+use std::{cmp::PartialEq, fmt::Display, str::FromStr};
+
+/// The amount of satoshis in a bitcoin
+pub const SATOSHIS_PER_BITCOIN: i64 = 100_000_000;
+
+/// The most satoshis there can ever be, 21 million bitcoins
+pub const MAX_MONEY: i64 = 21_000_000 * SATOSHIS_PER_BITCOIN;
+
+const DECIMAL_POINT: char = '.';
+const DECIMALS: usize = 8;
+const BITCOIN_UNITS: [&str; 2] = ["btc", "tbtc"];
+const SATOSHI_UNITS: [&str; 4] = ["sat", "sats", "satoshi", "satoshis"];
+const UNIT: &str = "tBTC";
+
+/// It's an amount of bitcoins, kept in satoshis so it doesn't lose precision. It can be negative, as
+/// the change of a balance, and the operations between amounts are checked so they never overflow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Amount(i64);
+
+impl Amount {
+    pub const ZERO: Amount = Amount(0);
+
+    pub fn from_sat(satoshis: i64) -> Self {
+        Amount(satoshis)
+    }
+
+    pub fn to_sat(self) -> i64 {
+        self.0
+    }
+
+    /// Reads an amount of bitcoins written with digits and at most 8 decimals, like `0.001`, without
+    /// going through a floating point number
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::InvalidAmount`: It will appear when the value is not a valid amount of bitcoins
+    pub fn from_btc(value: &str) -> Result<Self, ErrorWallet> {
+        let error = || ErrorWallet::InvalidAmount(format!("Invalid amount of bitcoins {value}"));
+
+        let (integer, decimals) = match value.split_once(DECIMAL_POINT) {
+            Some((integer, decimals)) => (integer, decimals),
+            None => (value, ""),
+        };
+
+        let is_digits = |part: &str| part.chars().all(|char| char.is_ascii_digit());
+        if (integer.is_empty() && decimals.is_empty())
+            || !is_digits(integer)
+            || !is_digits(decimals)
+            || decimals.len() > DECIMALS
+        {
+            return Err(error());
+        }
+
+        let integer = match integer {
+            "" => 0,
+            integer => integer.parse::<i64>().map_err(|_| error())?,
+        };
+        let decimals = match decimals {
+            "" => 0,
+            decimals => {
+                decimals.parse::<i64>().map_err(|_| error())?
+                    * 10_i64.pow((DECIMALS - decimals.len()) as u32)
+            }
+        };
+
+        match integer
+            .checked_mul(SATOSHIS_PER_BITCOIN)
+            .and_then(|satoshis| satoshis.checked_add(decimals))
+        {
+            Some(satoshis) if satoshis <= MAX_MONEY => Ok(Amount(satoshis)),
+            _ => Err(error()),
+        }
+    }
+
+    /// Returns the amount in bitcoins. It's only meant to be shown or converted to other currencies,
+    /// because it can lose precision
+    pub fn to_btc(self) -> f64 {
+        self.0 as f64 / SATOSHIS_PER_BITCOIN as f64
+    }
+
+    /// Returns the amount in bitcoins with its 8 decimals, without the unit
+    pub fn format_btc(&self) -> String {
+        let sign = match self.0 < 0 {
+            true => "-",
+            false => "",
+        };
+        let satoshis = self.0.unsigned_abs();
+        let satoshis_per_bitcoin = SATOSHIS_PER_BITCOIN as u64;
+
+        format!(
+            "{sign}{}{DECIMAL_POINT}{:0width$}",
+            satoshis / satoshis_per_bitcoin,
+            satoshis % satoshis_per_bitcoin,
+            width = DECIMALS
+        )
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.0 < 0
+    }
+
+    /// Returns the sum of both amounts, or None if it overflows
+    pub fn checked_add(self, other: Amount) -> Option<Amount> {
+        self.0.checked_add(other.0).map(Amount)
+    }
+
+    /// Returns the difference of both amounts, or None if it overflows
+    pub fn checked_sub(self, other: Amount) -> Option<Amount> {
+        self.0.checked_sub(other.0).map(Amount)
+    }
+
+    /// Returns the sum of every amount, or None if it overflows
+    pub fn checked_sum<I: IntoIterator<Item = Amount>>(amounts: I) -> Option<Amount> {
+        amounts
+            .into_iter()
+            .try_fold(Amount::ZERO, |total, amount| total.checked_add(amount))
+    }
+}
+
+impl FromStr for Amount {
+    type Err = ErrorWallet;
+
+    /// Reads an amount in bitcoins, like `0.001` or `0.001 tBTC`, or in satoshis, like `100000 sat`
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::InvalidAmount`: It will appear when the value is not a valid amount
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let (number, unit) = match value.find(|char: char| char.is_alphabetic()) {
+            Some(position) => (value[..position].trim(), value[position..].to_lowercase()),
+            None => (value, String::new()),
+        };
+
+        if unit.is_empty() || BITCOIN_UNITS.contains(&unit.as_str()) {
+            return Amount::from_btc(number);
+        }
+
+        if !SATOSHI_UNITS.contains(&unit.as_str()) {
+            return Err(ErrorWallet::InvalidAmount(format!(
+                "Unknown unit of the amount {value}"
+            )));
+        }
+
+        match number.parse::<u64>() {
+            Ok(satoshis) if satoshis <= MAX_MONEY as u64 => Ok(Amount(satoshis as i64)),
+            _ => Err(ErrorWallet::InvalidAmount(format!(
+                "Invalid amount of satoshis {value}"
+            ))),
+        }
+    }
+}
+
+impl Display for Amount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {UNIT}", self.format_btc())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_amount_is_read_in_bitcoins_without_losing_precision() {
+        assert_eq!(Ok(Amount::from_sat(100_000)), "0.001".parse::<Amount>());
+        assert_eq!(Ok(Amount::from_sat(100_000)), ".001 tBTC".parse::<Amount>());
+        assert_eq!(
+            Ok(Amount::from_sat(2_900_000_000)),
+            "29 BTC".parse::<Amount>()
+        );
+        assert_eq!(Ok(Amount::from_sat(1)), "0.00000001".parse::<Amount>());
+        assert_eq!(Ok(Amount::from_sat(29)), Amount::from_btc("0.00000029"));
+
+        assert!("0.000000001".parse::<Amount>().is_err());
+        assert!("1e3".parse::<Amount>().is_err());
+        assert!("-1".parse::<Amount>().is_err());
+        assert!(".".parse::<Amount>().is_err());
+        assert!("21000000.00000001".parse::<Amount>().is_err());
+    }
+
+    #[test]
+    fn test02_amount_is_read_in_satoshis() {
+        assert_eq!(
+            Ok(Amount::from_sat(100_000)),
+            "100000 sat".parse::<Amount>()
+        );
+        assert_eq!(Ok(Amount::from_sat(546)), "546sats".parse::<Amount>());
+        assert_eq!(Ok(Amount::from_sat(10)), "10 Satoshis".parse::<Amount>());
+
+        assert!("0.5 sat".parse::<Amount>().is_err());
+        assert!("10 dollars".parse::<Amount>().is_err());
+    }
+
+    #[test]
+    fn test03_amount_is_shown_in_tbtc_with_its_8_decimals() {
+        assert_eq!("0.00100000 tBTC", Amount::from_sat(100_000).to_string());
+        assert_eq!("21.00000001", Amount::from_sat(2_100_000_001).format_btc());
+        assert_eq!("-0.00000546", Amount::from_sat(-546).format_btc());
+        assert_eq!(0.001, Amount::from_sat(100_000).to_btc());
+    }
+
+    #[test]
+    fn test04_arithmetic_does_not_overflow() {
+        let amount = Amount::from_sat(10);
+
+        assert_eq!(
+            Some(Amount::from_sat(15)),
+            amount.checked_add(Amount::from_sat(5))
+        );
+        assert_eq!(
+            Some(Amount::from_sat(-5)),
+            Amount::from_sat(5).checked_sub(amount)
+        );
+        assert_eq!(None, Amount::from_sat(i64::MAX).checked_add(amount));
+        assert_eq!(None, Amount::from_sat(i64::MIN).checked_sub(amount));
+
+        assert_eq!(
+            Some(Amount::from_sat(30)),
+            Amount::checked_sum(vec![amount, amount, amount])
+        );
+        assert_eq!(
+            None,
+            Amount::checked_sum(vec![amount, Amount::from_sat(i64::MAX)])
+        );
+    }
+}
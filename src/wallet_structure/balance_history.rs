@@ -0,0 +1,422 @@
+use super::account::Account;
+
+use crate::{
+    block_structure::{block_chain::BlockChain, hash::HashType, utxo_set::UTXOSet},
+    serialization::{
+        deserializable_fix_size::DeserializableFixSize,
+        deserializable_internal_order::DeserializableInternalOrder,
+        deserializable_little_endian::DeserializableLittleEndian,
+        error_serialization::ErrorSerialization,
+        serializable_internal_order::SerializableInternalOrder,
+        serializable_little_endian::SerializableLittleEndian,
+        versioned_format::{Migration, VersionedFormat},
+    },
+};
+
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+};
+
+/// It represents the balance of an account at a given height of the block chain
+pub type BalanceCheckpoint = (u64, i64);
+
+/// It's the history of the balance of each account in the wallet, recorded as blocks are connected
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BalanceHistory {
+    /// The checkpoints of each account, by its address
+    checkpoints: HashMap<String, Vec<BalanceCheckpoint>>,
+}
+
+impl BalanceHistory {
+    pub fn new() -> Self {
+        BalanceHistory {
+            checkpoints: HashMap::new(),
+        }
+    }
+
+    /// Creates the balance history of the given accounts by walking the blocks of the longest chain
+    pub fn from_blockchain(block_chain: &BlockChain, accounts: &[Account]) -> Self {
+        let mut balance_history = BalanceHistory::new();
+        let mut utxo_set = UTXOSet::new(Vec::new());
+
+        for (height, block) in block_chain.get_main_chain_blocks() {
+            utxo_set.update_utxo_with_block(block);
+            balance_history.update_with_utxo_set(height, accounts, &utxo_set);
+        }
+
+        balance_history
+    }
+
+    /// Records the balance of each account given the utxo set at the given height
+    pub fn update_with_utxo_set(&mut self, height: u64, accounts: &[Account], utxo_set: &UTXOSet) {
+        for account in accounts {
+            let balance = utxo_set.get_balance_in_satoshis(&account.address);
+            self.record_checkpoint(account, height, balance);
+        }
+    }
+
+    /// Records the balance of an account at the given height.
+    /// It only stores a new checkpoint when the balance changes with respect of the last one
+    pub fn record_checkpoint(&mut self, account: &Account, height: u64, balance: i64) {
+        self.record(get_key(account), height, balance);
+    }
+
+    /// Records the balance at the given height in the checkpoints with the given key
+    fn record(&mut self, key: String, height: u64, balance: i64) {
+        let checkpoints = self.checkpoints.entry(key).or_default();
+
+        match checkpoints.last_mut() {
+            Some((last_height, last_balance)) if *last_height == height => {
+                *last_balance = balance;
+            }
+            Some((_, last_balance)) if *last_balance == balance => {}
+            None if balance == 0 => {}
+            _ => checkpoints.push((height, balance)),
+        }
+    }
+
+    /// Returns the balance checkpoints of the account from the given height.
+    /// The first checkpoint always represents the balance at the given height
+    pub fn balance_history(&self, account: &Account, from_height: u64) -> Vec<BalanceCheckpoint> {
+        let checkpoints = match self.checkpoints.get(&get_key(account)) {
+            Some(checkpoints) => checkpoints,
+            None => return Vec::new(),
+        };
+
+        let mut history: Vec<BalanceCheckpoint> = Vec::new();
+        let mut balance_before: Option<i64> = None;
+
+        for (height, balance) in checkpoints.iter() {
+            if *height < from_height {
+                balance_before = Some(*balance);
+                continue;
+            }
+
+            if history.is_empty() && *height > from_height {
+                if let Some(balance_before) = balance_before {
+                    history.push((from_height, balance_before));
+                }
+            }
+
+            history.push((*height, *balance));
+        }
+
+        if history.is_empty() {
+            if let Some(balance_before) = balance_before {
+                history.push((from_height, balance_before));
+            }
+        }
+
+        history
+    }
+
+    /// Returns the last balance checkpoint recorded for the account
+    pub fn last_checkpoint(&self, account: &Account) -> Option<BalanceCheckpoint> {
+        self.checkpoints.get(&get_key(account))?.last().copied()
+    }
+
+    /// Removes the checkpoints of every account from the given height, so they can be recorded again
+    pub fn remove_from(&mut self, from_height: u64) {
+        for checkpoints in self.checkpoints.values_mut() {
+            checkpoints.retain(|(height, _)| *height < from_height);
+        }
+        self.checkpoints
+            .retain(|_, checkpoints| !checkpoints.is_empty());
+    }
+
+    /// Replaces the checkpoints of every account from the given height with the ones of the given
+    /// history, as the ones recorded again by a rescan
+    pub fn replace_from(&mut self, from_height: u64, balance_history: BalanceHistory) {
+        self.remove_from(from_height);
+        for (key, checkpoints) in balance_history.checkpoints {
+            for (height, balance) in checkpoints {
+                if height >= from_height {
+                    self.record(key.clone(), height, balance);
+                }
+            }
+        }
+    }
+
+    /// Removes the history of the given account
+    pub fn remove_account(&mut self, account: &Account) {
+        self.checkpoints.remove(&get_key(account));
+    }
+
+    /// Returns the addresses with checkpoints, sorted so the history is always saved the same way
+    fn get_sorted_keys(&self) -> Vec<&String> {
+        let mut keys: Vec<&String> = self.checkpoints.keys().collect();
+        keys.sort();
+        keys
+    }
+}
+
+/// Returns the key of the checkpoints of the account. It's its address, because the names of the
+/// accounts can be repeated
+fn get_key(account: &Account) -> String {
+    account.address.to_string()
+}
+
+impl SerializableInternalOrder for BalanceHistory {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        (self.checkpoints.len() as u64).le_serialize(stream)?;
+        for key in self.get_sorted_keys() {
+            (key.len() as u64).le_serialize(stream)?;
+            key.le_serialize(stream)?;
+
+            let checkpoints = &self.checkpoints[key];
+            (checkpoints.len() as u64).le_serialize(stream)?;
+            for (height, balance) in checkpoints.iter() {
+                height.le_serialize(stream)?;
+                balance.le_serialize(stream)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl DeserializableInternalOrder for BalanceHistory {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        let mut balance_history = BalanceHistory::new();
+
+        let keys_len = u64::le_deserialize(stream)?;
+        for _ in 0..keys_len {
+            let key_len = u64::le_deserialize(stream)? as usize;
+            let key = String::deserialize_fix_size(stream, key_len)?;
+
+            let checkpoints_len = u64::le_deserialize(stream)?;
+            let mut checkpoints: Vec<BalanceCheckpoint> = Vec::new();
+            for _ in 0..checkpoints_len {
+                checkpoints.push((u64::le_deserialize(stream)?, i64::le_deserialize(stream)?));
+            }
+            balance_history.checkpoints.insert(key, checkpoints);
+        }
+
+        Ok(balance_history)
+    }
+}
+
+/// It's the balance history with the hash of the tip of the block chain it was recorded with, to
+/// save it next to the block chain
+#[derive(Debug, Clone)]
+pub struct BalanceHistorySnapshot {
+    pub tip: HashType,
+    pub balance_history: BalanceHistory,
+}
+
+impl SerializableInternalOrder for BalanceHistorySnapshot {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        self.tip.io_serialize(stream)?;
+        self.balance_history.io_serialize(stream)
+    }
+}
+
+impl DeserializableInternalOrder for BalanceHistorySnapshot {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        Ok(BalanceHistorySnapshot {
+            tip: HashType::io_deserialize(stream)?,
+            balance_history: BalanceHistory::io_deserialize(stream)?,
+        })
+    }
+}
+
+impl VersionedFormat for BalanceHistorySnapshot {
+    const MIGRATIONS: &'static [Migration] = &[];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        block_structure::{
+            block::Block, block_header::BlockHeader, block_version, compact256::Compact256,
+            hash::HashType, outpoint::Outpoint, transaction::Transaction,
+            transaction_input::TransactionInput, transaction_output::TransactionOutput,
+        },
+        connections::network::Network,
+        messages::compact_size::CompactSize,
+    };
+
+    fn create_account(name: &str) -> Account {
+        let priv_key_bytes: [u8; 32] = [
+            0x0a, 0x52, 0x65, 0x08, 0x2e, 0x24, 0x11, 0x5f, 0x77, 0x54, 0x0a, 0xb3, 0xb8, 0xc2,
+            0xb9, 0x20, 0x60, 0xaa, 0x30, 0xd6, 0xd2, 0xb8, 0x1a, 0x08, 0x5d, 0x71, 0xab, 0x37,
+            0xed, 0xa7, 0x68, 0x91,
+        ];
+        let pubkey_bytes: [u8; 33] = [
+            0x03, 0xBC, 0x6D, 0x45, 0xD2, 0x10, 0x1E, 0x91, 0x28, 0xDE, 0x14, 0xB5, 0xB6, 0x68,
+            0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
+            0x35, 0x14, 0x92, 0x4A, 0x22,
+        ];
+        Account::new(name, &priv_key_bytes, &pubkey_bytes, Network::Testnet).unwrap()
+    }
+
+    fn create_block(previous_header: HashType, transaction: Transaction) -> Block {
+        let mut block = Block::new(BlockHeader::new(
+            block_version::BlockVersion::version(1),
+            previous_header,
+            [0; 32],
+            0,
+            Compact256::from(u32::MAX),
+            0,
+            CompactSize::new(1),
+        ));
+        block.append_transaction(transaction).unwrap();
+        block
+    }
+
+    #[test]
+    fn test_01_correct_record_of_checkpoints() {
+        let account = create_account("test");
+        let mut balance_history = BalanceHistory::new();
+
+        balance_history.record_checkpoint(&account, 0, 0);
+        balance_history.record_checkpoint(&account, 1, 10);
+        balance_history.record_checkpoint(&account, 2, 10);
+        balance_history.record_checkpoint(&account, 3, 5);
+        balance_history.record_checkpoint(&account, 3, 7);
+
+        assert_eq!(
+            balance_history.balance_history(&account, 0),
+            vec![(1, 10), (3, 7)]
+        );
+    }
+
+    #[test]
+    fn test_02_correct_history_from_height() {
+        let account = create_account("test");
+        let mut balance_history = BalanceHistory::new();
+
+        balance_history.record_checkpoint(&account, 1, 10);
+        balance_history.record_checkpoint(&account, 4, 20);
+        balance_history.record_checkpoint(&account, 8, 15);
+
+        assert_eq!(
+            balance_history.balance_history(&account, 5),
+            vec![(5, 20), (8, 15)]
+        );
+        assert_eq!(
+            balance_history.balance_history(&account, 10),
+            vec![(10, 15)]
+        );
+        assert_eq!(
+            balance_history
+                .balance_history(&Account::generate("other", Network::Testnet).unwrap(), 0),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_03_correct_history_from_blockchain() {
+        let account = create_account("test");
+        let pk_script = account.address.generate_script_pubkey_p2pkh();
+
+        let transaction_1 = Transaction {
+            version: 1,
+            tx_in: vec![],
+            tx_out: vec![TransactionOutput {
+                value: 30,
+                pk_script: pk_script.clone(),
+            }],
+            lock_time: 0,
+        };
+        let transaction_2 = Transaction {
+            version: 1,
+            tx_in: vec![TransactionInput::new(
+                Outpoint::new(transaction_1.get_tx_id().unwrap(), 0),
+                vec![],
+                24,
+            )],
+            tx_out: vec![TransactionOutput {
+                value: 20,
+                pk_script,
+            }],
+            lock_time: 0,
+        };
+
+        let block_1 = create_block([0; 32], transaction_1);
+        let block_2 = create_block(block_1.header.get_hash256d().unwrap(), transaction_2);
+
+        let mut block_chain = BlockChain::new(block_1).unwrap();
+        block_chain.append_block(block_2).unwrap();
+
+        let balance_history =
+            BalanceHistory::from_blockchain(&block_chain, std::slice::from_ref(&account));
+
+        assert_eq!(
+            balance_history.balance_history(&account, 0),
+            vec![(0, 30), (1, 20)]
+        );
+    }
+
+    #[test]
+    fn test_04_correct_last_checkpoint() {
+        let account = create_account("test");
+        let mut balance_history = BalanceHistory::new();
+
+        assert_eq!(balance_history.last_checkpoint(&account), None);
+
+        balance_history.record_checkpoint(&account, 1, 10);
+        balance_history.record_checkpoint(&account, 4, 20);
+        balance_history.record_checkpoint(&account, 6, 20);
+
+        assert_eq!(balance_history.last_checkpoint(&account), Some((4, 20)));
+    }
+
+    #[test]
+    fn test_06_correct_history_of_accounts_with_the_same_name() {
+        let account = Account::generate("test", Network::Testnet).unwrap();
+        let other_account = Account::generate("test", Network::Testnet).unwrap();
+        let mut balance_history = BalanceHistory::new();
+
+        balance_history.record_checkpoint(&account, 1, 10);
+        balance_history.record_checkpoint(&other_account, 2, 20);
+
+        assert_eq!(balance_history.balance_history(&account, 0), vec![(1, 10)]);
+        assert_eq!(
+            balance_history.balance_history(&other_account, 0),
+            vec![(2, 20)]
+        );
+    }
+
+    #[test]
+    fn test_07_correct_serialization_of_the_snapshot() {
+        let account = create_account("test");
+        let mut balance_history = BalanceHistory::new();
+        balance_history.record_checkpoint(&account, 1, 10);
+        balance_history.record_checkpoint(&account, 4, -20);
+
+        let snapshot = BalanceHistorySnapshot {
+            tip: [3; 32],
+            balance_history,
+        };
+        let mut stream: Vec<u8> = Vec::new();
+        snapshot.io_serialize(&mut stream).unwrap();
+
+        let deserialized = BalanceHistorySnapshot::io_deserialize(&mut stream.as_slice()).unwrap();
+
+        assert_eq!([3; 32], deserialized.tip);
+        assert_eq!(snapshot.balance_history, deserialized.balance_history);
+    }
+
+    #[test]
+    fn test_05_correct_replace_of_checkpoints_from_height() {
+        let account = create_account("test");
+        let mut balance_history = BalanceHistory::new();
+        balance_history.record_checkpoint(&account, 1, 10);
+        balance_history.record_checkpoint(&account, 4, 20);
+
+        let mut rescanned_balance_history = BalanceHistory::new();
+        rescanned_balance_history.record_checkpoint(&account, 3, 10);
+        rescanned_balance_history.record_checkpoint(&account, 5, 30);
+
+        balance_history.replace_from(3, rescanned_balance_history);
+
+        assert_eq!(
+            balance_history.balance_history(&account, 0),
+            vec![(1, 10), (5, 30)]
+        );
+    }
+}
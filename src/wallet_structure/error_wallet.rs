@@ -19,6 +19,55 @@ pub enum ErrorWallet {
     /// It will appear when an account does not have enough funds to create a transaction for the amount requested
     NotEnoughFunds(String),
 
+    /// It will appear when the amount of a transaction is below the dust threshold, so the peers would not relay it
+    DustOutput(String),
+
     /// It will appear when a problem appears when trying to create an address from a public key
     CannotCreateAddress(String),
+
+    /// It will appear when the coins cannot be requested to the faucet
+    CannotRequestFaucet(String),
+
+    /// It will appear when the exchange rate cannot be requested to its provider
+    CannotRequestExchangeRate(String),
+
+    /// It will appear when the name of a wallet cannot be used as a file name
+    InvalidWalletName(String),
+
+    /// It will appear when a wallet file or the wallet directory cannot be read or written
+    CannotAccessWalletFile(String),
+
+    /// It will appear when a payment URI does not follow the BIP21 format
+    InvalidPaymentUri(String),
+
+    /// It will appear when the data is too long to be encoded in a QR code
+    CannotEncodeQrCode(String),
+
+    /// It will appear when there is no account with the given name in the wallet
+    AccountNotFound(String),
+
+    /// It will appear when the name given to an account is empty or already used by another account
+    InvalidAccountName(String),
+
+    /// It will appear when a backup of a wallet is not valid or its password is not the correct one
+    InvalidWalletBackup(String),
+
+    /// It will appear when the keys of a backup cannot be encrypted or decrypted
+    CannotEncryptKeys(String),
+
+    /// It will appear when an amount is not a valid amount of bitcoins or satoshis
+    InvalidAmount(String),
+
+    /// It will appear when creating a wallet with the name of a wallet that already exists
+    WalletAlreadyExists(String),
+
+    /// It will appear when the private keys are needed and the spending password was not given or its time ran out
+    WalletLocked(String),
+
+    /// It will appear when the spending password given is missing or not the correct one
+    InvalidSpendingPassword(String),
+
+    /// It will appear when the public keys or the threshold of a multisig account are not valid, or
+    /// a transaction cannot be signed by its cosigners
+    InvalidMultisig(String),
 }
@@ -0,0 +1,232 @@
+use super::{amount::Amount, error_wallet::ErrorWallet, http_url::HttpUrl};
+
+use std::{
+    fmt::Display,
+    io::{Read, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+const EXCHANGE_RATE_TIMEOUT: Duration = Duration::from_secs(10);
+const HEADER_SEPARATOR: &str = "\r\n\r\n";
+
+/// It's the price of one bitcoin in a fiat currency
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExchangeRate {
+    /// The code of the fiat currency, as `USD` or `EUR`
+    pub currency: String,
+
+    /// The amount of the fiat currency that one bitcoin is worth
+    pub price: f64,
+}
+
+impl ExchangeRate {
+    pub fn new(currency: &str, price: f64) -> Self {
+        ExchangeRate {
+            currency: currency.to_uppercase(),
+            price,
+        }
+    }
+
+    /// Returns the value in the fiat currency of the amount
+    pub fn to_fiat(&self, amount: Amount) -> f64 {
+        amount.to_btc() * self.price
+    }
+
+    /// Returns the approximate value in the fiat currency of the amount, as `≈ 12.34 USD`
+    pub fn format_fiat(&self, amount: Amount) -> String {
+        format!("≈ {:.2} {}", self.to_fiat(amount), self.currency)
+    }
+}
+
+impl Display for ExchangeRate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "1 BTC = {:.2} {}", self.price, self.currency)
+    }
+}
+
+/// It's the helper to ask for the price of a bitcoin to a provider over http. The price is expected
+/// as the value of the key of the currency, as in `{"bitcoin":{"usd":67000.5}}`, or as the whole body
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExchangeRateProvider {
+    host: String,
+    port: u16,
+    path: String,
+    currency: String,
+}
+
+impl ExchangeRateProvider {
+    /// Creates a provider from an url of the form `http://host[:port][/path]`
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotRequestExchangeRate`: It will appear when the url is not a valid http url
+    pub fn new(url: &str, currency: &str) -> Result<Self, ErrorWallet> {
+        let HttpUrl { host, port, path } =
+            HttpUrl::new(url, ErrorWallet::CannotRequestExchangeRate)?;
+
+        Ok(ExchangeRateProvider {
+            host,
+            port,
+            path,
+            currency: currency.to_uppercase(),
+        })
+    }
+
+    /// Asks the provider for the current price of a bitcoin
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotRequestExchangeRate`: It will appear when the provider cannot be reach or it does not answer with a price
+    pub fn request_rate(&self) -> Result<ExchangeRate, ErrorWallet> {
+        let mut stream = match TcpStream::connect((self.host.as_str(), self.port)) {
+            Ok(stream) => stream,
+            Err(error) => {
+                return Err(ErrorWallet::CannotRequestExchangeRate(format!(
+                    "Cannot connect to the provider, with error: {:?}",
+                    error
+                )))
+            }
+        };
+
+        if stream
+            .set_read_timeout(Some(EXCHANGE_RATE_TIMEOUT))
+            .is_err()
+            || stream.write_all(self.build_request().as_bytes()).is_err()
+        {
+            return Err(ErrorWallet::CannotRequestExchangeRate(
+                "Cannot send the request to the provider".to_string(),
+            ));
+        }
+
+        let mut response = String::new();
+        if stream.read_to_string(&mut response).is_err() {
+            return Err(ErrorWallet::CannotRequestExchangeRate(
+                "Cannot read the response of the provider".to_string(),
+            ));
+        }
+
+        let price = Self::parse_response(&response, &self.currency)?;
+        Ok(ExchangeRate::new(&self.currency, price))
+    }
+
+    /// Creates the http request for the price
+    fn build_request(&self) -> String {
+        format!(
+            "GET {path} HTTP/1.1\r\nHost: {host}\r\nAccept: application/json\r\nConnection: close\r\n\r\n",
+            path = self.path,
+            host = self.host,
+        )
+    }
+
+    /// Gets the price of the currency from the response of the provider
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotRequestExchangeRate`: It will appear when the response is an error or it does not have the price
+    fn parse_response(response: &str, currency: &str) -> Result<f64, ErrorWallet> {
+        let (head, body) = match response.split_once(HEADER_SEPARATOR) {
+            Some((head, body)) => (head, body),
+            None => (response, ""),
+        };
+
+        let status_code = head
+            .lines()
+            .next()
+            .and_then(|status_line| status_line.split_whitespace().nth(1))
+            .and_then(|status_code| status_code.parse::<u16>().ok());
+
+        match status_code {
+            Some(status_code) if (200..300).contains(&status_code) => {}
+            _ => {
+                return Err(ErrorWallet::CannotRequestExchangeRate(format!(
+                    "The provider answered with an error: {}",
+                    head.lines().next().unwrap_or_default()
+                )))
+            }
+        }
+
+        if let Ok(price) = body.trim().parse::<f64>() {
+            return Ok(price);
+        }
+
+        let key = format!("\"{}\"", currency.to_lowercase());
+        let lowercase_body = body.to_ascii_lowercase();
+        let after_key = match lowercase_body.find(&key) {
+            Some(position) => &body[position + key.len()..],
+            None => {
+                return Err(ErrorWallet::CannotRequestExchangeRate(format!(
+                    "The provider did not answer with the price in {currency}"
+                )))
+            }
+        };
+
+        let value = after_key
+            .trim_start()
+            .strip_prefix(':')
+            .unwrap_or_default()
+            .trim_start()
+            .trim_start_matches('"');
+        let end = value
+            .find(|character: char| !character.is_ascii_digit() && character != '.')
+            .unwrap_or(value.len());
+
+        match value[..end].parse::<f64>() {
+            Ok(price) => Ok(price),
+            Err(_) => Err(ErrorWallet::CannotRequestExchangeRate(format!(
+                "The price in {currency} is not a number"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_correct_request_creation() {
+        let provider = ExchangeRateProvider::new("http://rates.test/price", "usd").unwrap();
+
+        assert_eq!("USD", provider.currency);
+        assert_eq!(
+            "GET /price HTTP/1.1\r\nHost: rates.test\r\nAccept: application/json\r\nConnection: close\r\n\r\n",
+            provider.build_request()
+        );
+    }
+
+    #[test]
+    fn test02_correct_response_parsing() {
+        let response = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n{\"bitcoin\":{\"eur\":61234.5,\"usd\": 67000.25}}";
+        assert_eq!(
+            Ok(67000.25),
+            ExchangeRateProvider::parse_response(response, "USD")
+        );
+
+        let response = "HTTP/1.1 200 OK\r\n\r\n 25000.5\n";
+        assert_eq!(
+            Ok(25000.5),
+            ExchangeRateProvider::parse_response(response, "EUR")
+        );
+    }
+
+    #[test]
+    fn test03_does_not_accept_error_response() {
+        let response = "HTTP/1.1 503 Service Unavailable\r\n\r\n{\"usd\":1}";
+        assert!(ExchangeRateProvider::parse_response(response, "USD").is_err());
+
+        let response = "HTTP/1.1 200 OK\r\n\r\n{\"bitcoin\":{\"eur\":61234.5}}";
+        assert!(ExchangeRateProvider::parse_response(response, "USD").is_err());
+
+        let response = "HTTP/1.1 200 OK\r\n\r\n{\"usd\":null}";
+        assert!(ExchangeRateProvider::parse_response(response, "USD").is_err());
+    }
+
+    #[test]
+    fn test04_amounts_are_converted_to_the_currency() {
+        let exchange_rate = ExchangeRate::new("eur", 20000.0);
+
+        let amount = Amount::from_sat(2_500_000);
+
+        assert_eq!(500.0, exchange_rate.to_fiat(amount));
+        assert_eq!("≈ 500.00 EUR", exchange_rate.format_fiat(amount));
+        assert_eq!("1 BTC = 20000.00 EUR", exchange_rate.to_string());
+    }
+}
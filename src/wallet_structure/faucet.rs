@@ -0,0 +1,284 @@
+use super::{address::Address, error_wallet::ErrorWallet, http_url::HttpUrl};
+
+use crate::block_structure::{
+    block::Block,
+    hash::{HashType, HASH_TYPE_SIZE},
+};
+
+use std::{
+    io::{Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+const FAUCET_TIMEOUT: Duration = Duration::from_secs(10);
+const HEADER_SEPARATOR: &str = "\r\n\r\n";
+
+/// It's the helper to request coins from a testnet faucet and watch for the confirmation of the returned transactions
+#[derive(Debug, Clone, PartialEq)]
+pub struct Faucet {
+    host: String,
+    port: u16,
+    path: String,
+    pending_requests: Vec<HashType>,
+}
+
+impl Faucet {
+    /// Creates a faucet from an url of the form `http://host[:port][/path]`
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotRequestFaucet`: It will appear when the url is not a valid http url
+    pub fn new(url: &str) -> Result<Self, ErrorWallet> {
+        let HttpUrl { host, port, path } = HttpUrl::new(url, ErrorWallet::CannotRequestFaucet)?;
+
+        Ok(Faucet {
+            host,
+            port,
+            path,
+            pending_requests: Vec::new(),
+        })
+    }
+
+    /// Requests coins from the faucet to the given address, and returns the id of the transaction.
+    /// Connecting, sending the request and reading the answer each wait up to a timeout. The transaction
+    /// is not watched until it's added with `add_pending_request`, so the faucet doesn't need to be
+    /// borrowed mutably while waiting for the answer
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotRequestFaucet`: It will appear when the faucet cannot be reach or it answers with an error
+    pub fn request_coins(&self, address: &Address) -> Result<HashType, ErrorWallet> {
+        let mut stream = self.connect()?;
+
+        if stream.set_read_timeout(Some(FAUCET_TIMEOUT)).is_err()
+            || stream.set_write_timeout(Some(FAUCET_TIMEOUT)).is_err()
+            || stream
+                .write_all(self.build_request(address).as_bytes())
+                .is_err()
+        {
+            return Err(ErrorWallet::CannotRequestFaucet(
+                "Cannot send the request to the faucet".to_string(),
+            ));
+        }
+
+        let mut response = String::new();
+        if stream.read_to_string(&mut response).is_err() {
+            return Err(ErrorWallet::CannotRequestFaucet(
+                "Cannot read the response of the faucet".to_string(),
+            ));
+        }
+
+        Faucet::parse_response(&response)
+    }
+
+    /// Watches the transaction requested to the faucet until it's confirmed in a block
+    pub fn add_pending_request(&mut self, transaction_id: HashType) {
+        self.pending_requests.push(transaction_id);
+    }
+
+    /// Returns the transactions requested to the faucet that are not yet confirmed
+    pub fn pending_requests(&self) -> &Vec<HashType> {
+        &self.pending_requests
+    }
+
+    /// Removes the requested transactions that are confirmed in the given block and returns them
+    pub fn confirmed_in_block(&mut self, block: &Block) -> Vec<HashType> {
+        let mut confirmed: Vec<HashType> = Vec::new();
+
+        for transaction in block.transactions.iter() {
+            let transaction_id = match transaction.get_tx_id() {
+                Ok(transaction_id) => transaction_id,
+                Err(_) => continue,
+            };
+
+            if self.pending_requests.contains(&transaction_id) {
+                confirmed.push(transaction_id);
+            }
+        }
+
+        self.pending_requests
+            .retain(|transaction_id| !confirmed.contains(transaction_id));
+
+        confirmed
+    }
+
+    /// Connects to the first address of the host of the faucet that answers before the timeout
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotRequestFaucet`: It will appear when the host cannot be resolved or none of its addresses answers
+    fn connect(&self) -> Result<TcpStream, ErrorWallet> {
+        let socket_addresses = match (self.host.as_str(), self.port).to_socket_addrs() {
+            Ok(socket_addresses) => socket_addresses,
+            Err(error) => {
+                return Err(ErrorWallet::CannotRequestFaucet(format!(
+                    "Cannot resolve the host of the faucet, with error: {:?}",
+                    error
+                )))
+            }
+        };
+
+        let mut last_error = None;
+        for socket_address in socket_addresses {
+            match TcpStream::connect_timeout(&socket_address, FAUCET_TIMEOUT) {
+                Ok(stream) => return Ok(stream),
+                Err(error) => last_error = Some(error),
+            }
+        }
+
+        Err(ErrorWallet::CannotRequestFaucet(format!(
+            "Cannot connect to the faucet, with error: {:?}",
+            last_error
+        )))
+    }
+
+    /// Creates the http request for the faucet with the address as a form parameter
+    fn build_request(&self, address: &Address) -> String {
+        let body = format!("address={address}");
+
+        format!(
+            "POST {path} HTTP/1.1\r\nHost: {host}\r\nContent-Type: application/x-www-form-urlencoded\r\nContent-Length: {length}\r\nConnection: close\r\n\r\n{body}",
+            path = self.path,
+            host = self.host,
+            length = body.len(),
+        )
+    }
+
+    /// Gets the transaction id from the response of the faucet. The transaction id is expected in display order (reversed)
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotRequestFaucet`: It will appear when the response is an error or it does not have a transaction id
+    fn parse_response(response: &str) -> Result<HashType, ErrorWallet> {
+        let (head, body) = match response.split_once(HEADER_SEPARATOR) {
+            Some((head, body)) => (head, body),
+            None => (response, ""),
+        };
+
+        let status_code = head
+            .lines()
+            .next()
+            .and_then(|status_line| status_line.split_whitespace().nth(1))
+            .and_then(|status_code| status_code.parse::<u16>().ok());
+
+        match status_code {
+            Some(status_code) if (200..300).contains(&status_code) => {}
+            _ => {
+                return Err(ErrorWallet::CannotRequestFaucet(format!(
+                    "The faucet answered with an error: {}",
+                    head.lines().next().unwrap_or_default()
+                )))
+            }
+        }
+
+        for word in body.split(|character: char| !character.is_ascii_hexdigit()) {
+            if word.len() != HASH_TYPE_SIZE * 2 {
+                continue;
+            }
+
+            let mut transaction_id: HashType = [0; HASH_TYPE_SIZE];
+            for (i, byte) in transaction_id.iter_mut().rev().enumerate() {
+                *byte = u8::from_str_radix(&word[i * 2..i * 2 + 2], 16).unwrap_or_default();
+            }
+
+            return Ok(transaction_id);
+        }
+
+        Err(ErrorWallet::CannotRequestFaucet(
+            "The faucet did not answer with a transaction id".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        block_structure::{
+            block_header::BlockHeader, block_version, compact256::Compact256, script::ScriptBuf,
+            transaction::Transaction, transaction_output::TransactionOutput,
+        },
+        messages::compact_size::CompactSize,
+    };
+
+    #[test]
+    fn test01_correct_url_parsing() {
+        let faucet = Faucet::new("http://faucet.test:8080/api/send").unwrap();
+
+        assert_eq!(faucet.host, "faucet.test");
+        assert_eq!(faucet.port, 8080);
+        assert_eq!(faucet.path, "/api/send");
+
+        let faucet = Faucet::new("http://faucet.test").unwrap();
+
+        assert_eq!(faucet.port, 80);
+        assert_eq!(faucet.path, "/");
+    }
+
+    #[test]
+    fn test02_does_not_accept_invalid_url() {
+        assert!(Faucet::new("https://faucet.test").is_err());
+        assert!(Faucet::new("http://faucet.test:port/").is_err());
+        assert!(Faucet::new("http:///send").is_err());
+    }
+
+    #[test]
+    fn test03_correct_request_creation() {
+        let faucet = Faucet::new("http://faucet.test/send").unwrap();
+        let address = Address::new("mrhW6tcF2LDetj3kJvaDTvatrVxNK64NXk").unwrap();
+
+        let request = faucet.build_request(&address);
+
+        assert!(request.starts_with("POST /send HTTP/1.1\r\nHost: faucet.test\r\n"));
+        assert!(request.ends_with("\r\n\r\naddress=mrhW6tcF2LDetj3kJvaDTvatrVxNK64NXk"));
+    }
+
+    #[test]
+    fn test04_correct_response_parsing() {
+        let response = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n{\"txid\":\"00000000000000000000000000000000000000000000000000000000000000ff\"}";
+
+        let transaction_id = Faucet::parse_response(response).unwrap();
+
+        let mut expected: HashType = [0; 32];
+        expected[0] = 0xff;
+        assert_eq!(transaction_id, expected);
+    }
+
+    #[test]
+    fn test05_does_not_accept_error_response() {
+        let response = "HTTP/1.1 429 Too Many Requests\r\n\r\nTry again later";
+        assert!(Faucet::parse_response(response).is_err());
+
+        let response = "HTTP/1.1 200 OK\r\n\r\nNo coins for you";
+        assert!(Faucet::parse_response(response).is_err());
+    }
+
+    #[test]
+    fn test06_correct_confirmation_of_requested_transactions() {
+        let transaction = Transaction {
+            version: 1,
+            tx_in: vec![],
+            tx_out: vec![TransactionOutput {
+                value: 10,
+                pk_script: ScriptBuf::from(vec![1, 2, 3]),
+            }],
+            lock_time: 0,
+        };
+        let transaction_id = transaction.get_tx_id().unwrap();
+
+        let mut block = Block::new(BlockHeader::new(
+            block_version::BlockVersion::version(1),
+            [0; 32],
+            [0; 32],
+            0,
+            Compact256::from(u32::MAX),
+            0,
+            CompactSize::new(1),
+        ));
+        block.append_transaction(transaction).unwrap();
+
+        let mut faucet = Faucet::new("http://faucet.test").unwrap();
+        faucet.pending_requests = vec![transaction_id, [1; 32]];
+
+        assert_eq!(faucet.confirmed_in_block(&block), vec![transaction_id]);
+        assert_eq!(faucet.pending_requests(), &vec![[1; 32]]);
+    }
+}
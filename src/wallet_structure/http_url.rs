@@ -0,0 +1,84 @@
+use super::error_wallet::ErrorWallet;
+
+const HTTP_SCHEME: &str = "http://";
+const DEFAULT_HTTP_PORT: u16 = 80;
+
+/// It's an url of the form `http://host[:port][/path]` of the services the wallet asks to, as the
+/// faucet or the exchange rate provider
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpUrl {
+    pub host: String,
+    pub port: u16,
+    pub path: String,
+}
+
+impl HttpUrl {
+    /// Reads the url, creating the error of the service with the description of the problem
+    ///
+    /// ### Error
+    ///  * The error given: It will appear when the url is not a valid http url
+    pub fn new(url: &str, error: fn(String) -> ErrorWallet) -> Result<Self, ErrorWallet> {
+        let without_scheme = match url.trim().strip_prefix(HTTP_SCHEME) {
+            Some(without_scheme) => without_scheme,
+            None => return Err(error(format!("The url {url} is not an http url"))),
+        };
+
+        let (authority, path) = match without_scheme.find('/') {
+            Some(position) => without_scheme.split_at(position),
+            None => (without_scheme, "/"),
+        };
+
+        let (host, port) = match authority.split_once(':') {
+            Some((host, port)) => match port.parse::<u16>() {
+                Ok(port) => (host, port),
+                Err(_) => return Err(error(format!("The port {port} is not valid"))),
+            },
+            None => (authority, DEFAULT_HTTP_PORT),
+        };
+
+        if host.is_empty() {
+            return Err(error(format!("The url {url} does not have a host")));
+        }
+
+        Ok(HttpUrl {
+            host: host.to_string(),
+            port,
+            path: path.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_correct_url_parsing() {
+        let url = HttpUrl::new(
+            "http://rates.test:8080/api/price",
+            ErrorWallet::CannotRequestFaucet,
+        )
+        .unwrap();
+
+        assert_eq!("rates.test", url.host);
+        assert_eq!(8080, url.port);
+        assert_eq!("/api/price", url.path);
+
+        let url = HttpUrl::new("http://rates.test", ErrorWallet::CannotRequestFaucet).unwrap();
+
+        assert_eq!(DEFAULT_HTTP_PORT, url.port);
+        assert_eq!("/", url.path);
+    }
+
+    #[test]
+    fn test02_invalid_url_gives_the_error_of_the_service() {
+        assert!(matches!(
+            HttpUrl::new("https://rates.test", ErrorWallet::CannotRequestExchangeRate),
+            Err(ErrorWallet::CannotRequestExchangeRate(_))
+        ));
+        assert!(matches!(
+            HttpUrl::new("http://rates.test:port/", ErrorWallet::CannotRequestFaucet),
+            Err(ErrorWallet::CannotRequestFaucet(_))
+        ));
+    }
+}
@@ -0,0 +1,353 @@
+use super::error_wallet::ErrorWallet;
+
+use crate::notifications::notification_json::quote;
+
+use std::{fmt::Display, iter::Peekable, str::Chars};
+
+/// It's a value of a JSON document, keeping the order of the fields of its objects
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// Parses a JSON document, that can only have white spaces after its value
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::InvalidWalletBackup`: It will appear when the text is not a valid JSON document
+    pub fn parse(text: &str) -> Result<JsonValue, ErrorWallet> {
+        let mut chars = text.chars().peekable();
+        let value = parse_value(&mut chars)?;
+
+        skip_whitespaces(&mut chars);
+        match chars.next() {
+            None => Ok(value),
+            Some(character) => Err(invalid_json(&format!(
+                "Unexpected {character} after the end of the document"
+            ))),
+        }
+    }
+
+    /// Returns the value of the field with the given name, if it's an object that has it
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(fields) => fields
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<JsonValue>> {
+        match self {
+            JsonValue::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, JsonValue::Null)
+    }
+}
+
+impl From<Option<String>> for JsonValue {
+    fn from(value: Option<String>) -> Self {
+        match value {
+            Some(text) => JsonValue::String(text),
+            None => JsonValue::Null,
+        }
+    }
+}
+
+/// Returns the error of a document that is not valid
+fn invalid_json(reason: &str) -> ErrorWallet {
+    ErrorWallet::InvalidWalletBackup(format!("The JSON document is not valid: {reason}"))
+}
+
+fn skip_whitespaces(chars: &mut Peekable<Chars>) {
+    while matches!(chars.peek(), Some(character) if character.is_whitespace()) {
+        chars.next();
+    }
+}
+
+/// Consumes the next character, that has to be the expected one
+///
+/// ### Error
+///  * `ErrorWallet::InvalidWalletBackup`: It will appear when the next character is another one
+fn expect(chars: &mut Peekable<Chars>, expected: char) -> Result<(), ErrorWallet> {
+    match chars.next() {
+        Some(character) if character == expected => Ok(()),
+        Some(character) => Err(invalid_json(&format!(
+            "Expected {expected} but found {character}"
+        ))),
+        None => Err(invalid_json(&format!("Expected {expected} but it ended"))),
+    }
+}
+
+/// Consumes the given word, used for the literals `true`, `false` and `null`
+///
+/// ### Error
+///  * `ErrorWallet::InvalidWalletBackup`: It will appear when the next characters are not the word
+fn expect_word(chars: &mut Peekable<Chars>, word: &str) -> Result<(), ErrorWallet> {
+    for expected in word.chars() {
+        expect(chars, expected)?;
+    }
+    Ok(())
+}
+
+/// Parses the value that starts in the next character that is not a white space
+///
+/// ### Error
+///  * `ErrorWallet::InvalidWalletBackup`: It will appear when the value is not valid
+fn parse_value(chars: &mut Peekable<Chars>) -> Result<JsonValue, ErrorWallet> {
+    skip_whitespaces(chars);
+
+    match chars.peek() {
+        Some('n') => expect_word(chars, "null").map(|_| JsonValue::Null),
+        Some('t') => expect_word(chars, "true").map(|_| JsonValue::Bool(true)),
+        Some('f') => expect_word(chars, "false").map(|_| JsonValue::Bool(false)),
+        Some('"') => Ok(JsonValue::String(parse_string(chars)?)),
+        Some('[') => parse_array(chars),
+        Some('{') => parse_object(chars),
+        Some(character) if *character == '-' || character.is_ascii_digit() => parse_number(chars),
+        Some(character) => Err(invalid_json(&format!("Unexpected {character}"))),
+        None => Err(invalid_json("A value is missing")),
+    }
+}
+
+/// Parses a string, with its escaped characters
+///
+/// ### Error
+///  * `ErrorWallet::InvalidWalletBackup`: It will appear when the string is not closed or has an invalid escape
+fn parse_string(chars: &mut Peekable<Chars>) -> Result<String, ErrorWallet> {
+    expect(chars, '"')?;
+
+    let mut text = String::new();
+    loop {
+        match chars.next() {
+            Some('"') => return Ok(text),
+            Some('\\') => match chars.next() {
+                Some('"') => text.push('"'),
+                Some('\\') => text.push('\\'),
+                Some('/') => text.push('/'),
+                Some('b') => text.push('\u{8}'),
+                Some('f') => text.push('\u{c}'),
+                Some('n') => text.push('\n'),
+                Some('r') => text.push('\r'),
+                Some('t') => text.push('\t'),
+                Some('u') => text.push(parse_unicode_escape(chars)?),
+                _ => return Err(invalid_json("Invalid escape in a string")),
+            },
+            Some(character) => text.push(character),
+            None => return Err(invalid_json("A string is not closed")),
+        }
+    }
+}
+
+/// Parses the four hexadecimal digits of an escaped character, joining the surrogate pairs
+///
+/// ### Error
+///  * `ErrorWallet::InvalidWalletBackup`: It will appear when the digits are not a valid character
+fn parse_unicode_escape(chars: &mut Peekable<Chars>) -> Result<char, ErrorWallet> {
+    fn parse_code(chars: &mut Peekable<Chars>) -> Result<u32, ErrorWallet> {
+        let digits: String = chars.by_ref().take(4).collect();
+        match u32::from_str_radix(&digits, 16) {
+            Ok(code) if digits.len() == 4 => Ok(code),
+            _ => Err(invalid_json("Invalid unicode escape in a string")),
+        }
+    }
+
+    let mut code = parse_code(chars)?;
+    if (0xD800..0xDC00).contains(&code) {
+        expect_word(chars, "\\u")?;
+        let low_code = parse_code(chars)?;
+        code = 0x10000 + ((code - 0xD800) << 10) + (low_code.wrapping_sub(0xDC00) & 0x3FF);
+    }
+
+    match char::from_u32(code) {
+        Some(character) => Ok(character),
+        None => Err(invalid_json("Invalid unicode escape in a string")),
+    }
+}
+
+/// Parses a number, as a floating point value
+///
+/// ### Error
+///  * `ErrorWallet::InvalidWalletBackup`: It will appear when the number is not valid
+fn parse_number(chars: &mut Peekable<Chars>) -> Result<JsonValue, ErrorWallet> {
+    let mut number = String::new();
+    while let Some(character) = chars.peek() {
+        match character {
+            '-' | '+' | '.' | 'e' | 'E' | '0'..='9' => number.push(*character),
+            _ => break,
+        }
+        chars.next();
+    }
+
+    match number.parse::<f64>() {
+        Ok(number) => Ok(JsonValue::Number(number)),
+        Err(_) => Err(invalid_json(&format!("{number} is not a number"))),
+    }
+}
+
+/// Parses an array, with its values separated by commas
+///
+/// ### Error
+///  * `ErrorWallet::InvalidWalletBackup`: It will appear when the array or one of its values is not valid
+fn parse_array(chars: &mut Peekable<Chars>) -> Result<JsonValue, ErrorWallet> {
+    expect(chars, '[')?;
+
+    let mut values: Vec<JsonValue> = Vec::new();
+    skip_whitespaces(chars);
+    if chars.peek() == Some(&']') {
+        chars.next();
+        return Ok(JsonValue::Array(values));
+    }
+
+    loop {
+        values.push(parse_value(chars)?);
+
+        skip_whitespaces(chars);
+        match chars.next() {
+            Some(',') => continue,
+            Some(']') => return Ok(JsonValue::Array(values)),
+            _ => return Err(invalid_json("An array is not closed")),
+        }
+    }
+}
+
+/// Parses an object, with its fields separated by commas
+///
+/// ### Error
+///  * `ErrorWallet::InvalidWalletBackup`: It will appear when the object or one of its fields is not valid
+fn parse_object(chars: &mut Peekable<Chars>) -> Result<JsonValue, ErrorWallet> {
+    expect(chars, '{')?;
+
+    let mut fields: Vec<(String, JsonValue)> = Vec::new();
+    skip_whitespaces(chars);
+    if chars.peek() == Some(&'}') {
+        chars.next();
+        return Ok(JsonValue::Object(fields));
+    }
+
+    loop {
+        skip_whitespaces(chars);
+        let name = parse_string(chars)?;
+
+        skip_whitespaces(chars);
+        expect(chars, ':')?;
+        fields.push((name, parse_value(chars)?));
+
+        skip_whitespaces(chars);
+        match chars.next() {
+            Some(',') => continue,
+            Some('}') => return Ok(JsonValue::Object(fields)),
+            _ => return Err(invalid_json("An object is not closed")),
+        }
+    }
+}
+
+impl Display for JsonValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonValue::Null => write!(f, "null"),
+            JsonValue::Bool(value) => write!(f, "{value}"),
+            JsonValue::Number(number) => write!(f, "{number}"),
+            JsonValue::String(text) => write!(f, "{}", quote(text)),
+            JsonValue::Array(values) => {
+                let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+                write!(f, "[{}]", values.join(","))
+            }
+            JsonValue::Object(fields) => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|(name, value)| format!("{}:{value}", quote(name)))
+                    .collect();
+                write!(f, "{{{}}}", fields.join(","))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_correct_document_parsing() {
+        let document = r#" {"name": "Savings", "amount": -1.5e2, "keys": [true, null, "a\"bé"], "empty": {}} "#;
+
+        let value = JsonValue::parse(document).unwrap();
+
+        assert_eq!(
+            Some("Savings"),
+            value.get("name").and_then(JsonValue::as_str)
+        );
+        assert_eq!(
+            Some(-150.0),
+            value.get("amount").and_then(JsonValue::as_f64)
+        );
+        assert_eq!(
+            Some(&vec![
+                JsonValue::Bool(true),
+                JsonValue::Null,
+                JsonValue::String("a\"bé".to_string())
+            ]),
+            value.get("keys").and_then(JsonValue::as_array)
+        );
+        assert_eq!(Some(&JsonValue::Object(Vec::new())), value.get("empty"));
+    }
+
+    #[test]
+    fn test02_value_is_the_same_after_writing_it() {
+        let value = JsonValue::Object(vec![
+            ("name".to_string(), JsonValue::String("a\nb".to_string())),
+            ("timestamp".to_string(), JsonValue::Number(1690000000.0)),
+            (
+                "accounts".to_string(),
+                JsonValue::Array(vec![JsonValue::Null, JsonValue::Bool(false)]),
+            ),
+        ]);
+
+        let document = value.to_string();
+
+        assert_eq!(
+            r#"{"name":"a\nb","timestamp":1690000000,"accounts":[null,false]}"#,
+            document
+        );
+        assert_eq!(value, JsonValue::parse(&document).unwrap());
+    }
+
+    #[test]
+    fn test03_invalid_documents_are_rejected() {
+        for document in ["", "{", "[1,]", r#"{"a" 1}"#, r#""open"#, "nul", "{} {}"] {
+            assert!(
+                matches!(
+                    JsonValue::parse(document),
+                    Err(ErrorWallet::InvalidWalletBackup(_))
+                ),
+                "{document} should not be valid"
+            );
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use super::{
+    error_wallet::ErrorWallet,
+    private_key::{PrivateKeyType, PRIVATE_KEY_SIZE},
+    public_key::PublicKeyType,
+};
+
+use crate::block_structure::hash::{hash256, hash256d, HashType};
+
+use std::{fs::File, io::Read};
+
+pub const SALT_SIZE: usize = 16;
+pub const CHECK_SIZE: usize = 4;
+
+const RANDOM_SOURCE: &str = "/dev/urandom";
+const KEY_DERIVATION_ROUNDS: u32 = 20_000;
+
+pub type SaltType = [u8; SALT_SIZE];
+pub type CheckType = [u8; CHECK_SIZE];
+
+/// It's the key derived from a password to encrypt the private keys of a backup or of a wallet. Each
+/// private key is mixed with a different stream of bytes obtained from the key, the salt and its
+/// position or public key, so the same key is needed to recover them
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyEncryption {
+    salt: SaltType,
+    key: HashType,
+}
+
+impl KeyEncryption {
+    /// Creates the key of the password with a new salt, given by the operating system
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotEncryptKeys`: It will appear when the random bytes cannot be read or the key cannot be derived
+    pub fn new(password: &str) -> Result<Self, ErrorWallet> {
+        let mut salt: SaltType = [0; SALT_SIZE];
+        let read_salt = File::open(RANDOM_SOURCE)
+            .and_then(|mut random_source| random_source.read_exact(&mut salt));
+        if let Err(error) = read_salt {
+            return Err(ErrorWallet::CannotEncryptKeys(format!(
+                "Cannot read random bytes for the salt, error : {:?}",
+                error
+            )));
+        }
+
+        Self::with_salt(password, salt)
+    }
+
+    /// Creates the key of the password with the salt used when it was created
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotEncryptKeys`: It will appear when the key cannot be derived
+    pub fn with_salt(password: &str, salt: SaltType) -> Result<Self, ErrorWallet> {
+        let mut key: HashType = [0; 32];
+        for _ in 0..KEY_DERIVATION_ROUNDS {
+            let bytes = [&key[..], &salt[..], password.as_bytes()].concat();
+            key = match hash256(&bytes) {
+                Ok(key) => key,
+                Err(error) => {
+                    return Err(ErrorWallet::CannotEncryptKeys(format!(
+                        "Cannot derive the key of the password, error : {:?}",
+                        error
+                    )))
+                }
+            };
+        }
+
+        Ok(KeyEncryption { salt, key })
+    }
+
+    pub fn get_salt(&self) -> SaltType {
+        self.salt
+    }
+
+    /// Returns the bytes that identify the key, to know if the password is the correct one
+    /// without revealing it
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotEncryptKeys`: It will appear when the key cannot be hashed
+    pub fn check(&self) -> Result<CheckType, ErrorWallet> {
+        match hash256d(&self.key) {
+            Ok(hash) => Ok([hash[0], hash[1], hash[2], hash[3]]),
+            Err(error) => Err(ErrorWallet::CannotEncryptKeys(format!(
+                "Cannot hash the key of the password, error : {:?}",
+                error
+            ))),
+        }
+    }
+
+    /// Encrypts or decrypts the private key in the given position, being the same operation
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotEncryptKeys`: It will appear when the stream of bytes cannot be obtained
+    pub fn apply(
+        &self,
+        position: u32,
+        private_key: &PrivateKeyType,
+    ) -> Result<PrivateKeyType, ErrorWallet> {
+        self.mix(&position.to_le_bytes(), private_key)
+    }
+
+    /// Encrypts or decrypts the private key of the given public key, being the same operation. Unlike the
+    /// position, the public key doesn't change when other accounts are added or removed
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotEncryptKeys`: It will appear when the stream of bytes cannot be obtained
+    pub fn apply_with_public_key(
+        &self,
+        public_key: &PublicKeyType,
+        private_key: &PrivateKeyType,
+    ) -> Result<PrivateKeyType, ErrorWallet> {
+        self.mix(public_key, private_key)
+    }
+
+    /// Mixes the private key with the stream of bytes obtained from the key, the salt and the nonce
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotEncryptKeys`: It will appear when the stream of bytes cannot be obtained
+    fn mix(
+        &self,
+        nonce: &[u8],
+        private_key: &PrivateKeyType,
+    ) -> Result<PrivateKeyType, ErrorWallet> {
+        let bytes = [&self.key[..], &self.salt[..], nonce].concat();
+        let stream = match hash256(&bytes) {
+            Ok(stream) => stream,
+            Err(error) => {
+                return Err(ErrorWallet::CannotEncryptKeys(format!(
+                    "Cannot obtain the bytes to encrypt the key, error : {:?}",
+                    error
+                )))
+            }
+        };
+
+        let mut result: PrivateKeyType = [0; PRIVATE_KEY_SIZE];
+        for (i, byte) in result.iter_mut().enumerate() {
+            *byte = private_key[i] ^ stream[i];
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_key_is_recovered_with_the_same_password() {
+        let private_key: PrivateKeyType = [0x0A; PRIVATE_KEY_SIZE];
+        let encryption = KeyEncryption::new("password").unwrap();
+
+        let encrypted = encryption.apply(0, &private_key).unwrap();
+        let decryption = KeyEncryption::with_salt("password", encryption.get_salt()).unwrap();
+
+        assert_ne!(private_key, encrypted);
+        assert_eq!(encryption.check().unwrap(), decryption.check().unwrap());
+        assert_eq!(private_key, decryption.apply(0, &encrypted).unwrap());
+    }
+
+    #[test]
+    fn test02_different_password_or_position_gives_other_bytes() {
+        let private_key: PrivateKeyType = [0x0A; PRIVATE_KEY_SIZE];
+        let salt: SaltType = [0x01; SALT_SIZE];
+        let encryption = KeyEncryption::with_salt("password", salt).unwrap();
+        let other_encryption = KeyEncryption::with_salt("other", salt).unwrap();
+
+        assert_ne!(
+            encryption.check().unwrap(),
+            other_encryption.check().unwrap()
+        );
+        assert_ne!(
+            encryption.apply(0, &private_key).unwrap(),
+            other_encryption.apply(0, &private_key).unwrap()
+        );
+        assert_ne!(
+            encryption.apply(0, &private_key).unwrap(),
+            encryption.apply(1, &private_key).unwrap()
+        );
+    }
+
+    #[test]
+    fn test03_key_is_recovered_with_its_public_key() {
+        let private_key: PrivateKeyType = [0x0A; PRIVATE_KEY_SIZE];
+        let encryption = KeyEncryption::with_salt("password", [0x01; SALT_SIZE]).unwrap();
+
+        let encrypted = encryption
+            .apply_with_public_key(&[0x02; 33], &private_key)
+            .unwrap();
+
+        assert_ne!(private_key, encrypted);
+        assert_ne!(
+            encrypted,
+            encryption
+                .apply_with_public_key(&[0x03; 33], &private_key)
+                .unwrap()
+        );
+        assert_eq!(
+            private_key,
+            encryption
+                .apply_with_public_key(&[0x02; 33], &encrypted)
+                .unwrap()
+        );
+    }
+}
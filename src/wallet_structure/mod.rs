@@ -1,8 +1,26 @@
 pub mod wallet;
+pub mod wallet_directory;
 
 pub mod account;
+pub mod account_metadata;
 
 pub mod address;
+pub mod amount;
+pub mod balance_history;
 pub mod error_wallet;
+pub mod exchange_rate;
+pub mod faucet;
+pub mod http_url;
+pub mod json_value;
+pub mod key_encryption;
+pub mod multisig_account;
+pub mod payment_uri;
 pub mod private_key;
 pub mod public_key;
+pub mod qr_code;
+pub mod rescan;
+pub mod sighash;
+pub mod spending_lock;
+pub mod transaction_history;
+pub mod transaction_options;
+pub mod transaction_preview;
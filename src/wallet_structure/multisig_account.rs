@@ -0,0 +1,492 @@
+use super::{
+    account::{self, Account},
+    address::Address,
+    amount::Amount,
+    error_wallet::ErrorWallet,
+    public_key::{PublicKey, PublicKeyType},
+    sighash::{self, SIGHASH_ALL},
+    transaction_options::TransactionOptions,
+};
+
+use crate::block_structure::{
+    hash,
+    script::{Instruction, Script, ScriptBuf, OP_0},
+    transaction::Transaction,
+    utxo_set::UTXOSet,
+};
+
+use crate::connections::network::Network;
+
+/// The most public keys of a standard multisig redeem script
+pub const MAX_MULTISIG_KEYS: usize = 15;
+
+/// It's an account shared by some cosigners, whose outputs need the signatures of `threshold` of its
+/// public keys to be spent. It's paid through the P2SH address of its redeem script.
+///
+/// The signature script of an input still missing signatures has one push for each public key, empty
+/// for the cosigners that did not sign yet, so the transaction can be passed to the next cosigner
+/// as is. Once it has enough signatures, only the needed ones are kept
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultisigAccount {
+    pub account_name: String,
+    pub public_keys: Vec<PublicKey>,
+    pub threshold: usize,
+    pub address: Address,
+}
+
+impl MultisigAccount {
+    /// Creates the account that needs `threshold` signatures of the given public keys, with its address
+    /// in the given network
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::InvalidMultisig`: It will appear when the threshold is not between 1 and the amount of public keys, there are more than 15 keys or a key is repeated
+    ///  * `ErrorWallet::CannotCreateAddress`: It will appear when the address cannot be derived from the redeem script
+    pub fn new(
+        name: &str,
+        public_keys: Vec<PublicKey>,
+        threshold: usize,
+        network: Network,
+    ) -> Result<MultisigAccount, ErrorWallet> {
+        if public_keys.len() > MAX_MULTISIG_KEYS {
+            return Err(ErrorWallet::InvalidMultisig(format!(
+                "A multisig account can have at most {MAX_MULTISIG_KEYS} public keys, got {}",
+                public_keys.len()
+            )));
+        }
+
+        if threshold == 0 || threshold > public_keys.len() {
+            return Err(ErrorWallet::InvalidMultisig(format!(
+                "The threshold must be between 1 and {}, got {threshold}",
+                public_keys.len()
+            )));
+        }
+
+        for (index, public_key) in public_keys.iter().enumerate() {
+            if public_keys[..index].contains(public_key) {
+                return Err(ErrorWallet::InvalidMultisig(format!(
+                    "The public key {} is repeated",
+                    hash::to_hex(&public_key.as_bytes())
+                )));
+            }
+        }
+
+        let redeem_script = create_redeem_script(&public_keys, threshold);
+        let script_hash = match hash::hash160(redeem_script.as_bytes()) {
+            Ok(script_hash) => script_hash,
+            Err(error) => {
+                return Err(ErrorWallet::CannotCreateAddress(format!(
+                    "Cannot hash the redeem script, error : {:?}",
+                    error
+                )))
+            }
+        };
+        let address = Address::from_hash(network.script_version_byte(), &script_hash)?;
+
+        Ok(MultisigAccount {
+            account_name: name.to_string(),
+            public_keys,
+            threshold,
+            address,
+        })
+    }
+
+    /// Returns the script whose hash the address pays to, which has to be given to spend its outputs
+    pub fn redeem_script(&self) -> ScriptBuf {
+        create_redeem_script(&self.public_keys, self.threshold)
+    }
+
+    /// Returns a transaction given the amount and to whom it is sent, without any signature yet. The
+    /// change goes back to the multisig address
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::NotEnoughFunds`: It will appear when the account does not have enough funds to create a transaction for the amount requested
+    ///  * `ErrorWallet::DustOutput`: It will appear when the amount is below the dust threshold of the output to the address
+    ///  * `ErrorWallet::InvalidAmount`: It will appear when the fee is negative or the amount and the fee overflow
+    pub fn create_transaction(
+        &self,
+        to: Address,
+        amount: Amount,
+        fee: Amount,
+        options: TransactionOptions,
+        utxo_set: &UTXOSet,
+    ) -> Result<Transaction, ErrorWallet> {
+        let outputs_to_spend = account::select_outputs(&self.address, &to, amount, fee, utxo_set)?;
+
+        let mut transaction = Transaction::unsigned_to_address(
+            &outputs_to_spend,
+            &to,
+            &self.address,
+            amount.to_sat(),
+            fee.to_sat(),
+            options,
+        );
+
+        let signature_script = self.create_signature_script(&vec![vec![]; self.public_keys.len()]);
+        for tx_in in transaction.tx_in.iter_mut() {
+            tx_in.signature_script = signature_script.clone().into_bytes();
+        }
+
+        Ok(transaction)
+    }
+
+    /// Adds the signature of the cosigner to every input of the transaction, signing with SIGHASH_ALL
+    /// the legacy digest of the redeem script
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::InvalidMultisig`: It will appear when the account is not a cosigner, an input does not spend from this account or it already has all the signatures
+    ///  * `ErrorWallet::CannotCreateNewTransaction`: It will appear when the digest of an input cannot be created
+    ///  * `ErrorWallet::CannotSignMessage`: It will appear when the digest cannot be signed
+    pub fn sign_transaction(
+        &self,
+        transaction: &mut Transaction,
+        cosigner: &Account,
+    ) -> Result<(), ErrorWallet> {
+        let position = match self
+            .public_keys
+            .iter()
+            .position(|public_key| *public_key == cosigner.public_key)
+        {
+            Some(position) => position,
+            None => {
+                return Err(ErrorWallet::InvalidMultisig(format!(
+                    "The account {} is not a cosigner of {}",
+                    cosigner.account_name, self.account_name
+                )))
+            }
+        };
+
+        let redeem_script = self.redeem_script();
+        let unsigned_transaction = transaction.clone();
+        for (index, tx_in) in transaction.tx_in.iter_mut().enumerate() {
+            let mut signatures = self.get_signatures(&tx_in.signature_script)?;
+            if signatures.len() != self.public_keys.len() {
+                return Err(ErrorWallet::InvalidMultisig(format!(
+                    "The input {index} already has the {} signatures it needs",
+                    self.threshold
+                )));
+            }
+
+            let hashed_message = sighash::legacy_sighash(
+                &unsigned_transaction,
+                index,
+                redeem_script.as_script(),
+                SIGHASH_ALL,
+            )?;
+            let mut signature = cosigner.sign(&hashed_message)?;
+            signature.push(SIGHASH_ALL as u8);
+
+            signatures[position] = signature;
+            tx_in.signature_script = self.create_signature_script(&signatures).into_bytes();
+        }
+
+        Ok(())
+    }
+
+    /// Returns the least amount of signatures of the inputs of the transaction
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::InvalidMultisig`: It will appear when an input does not spend from this account
+    pub fn count_signatures(&self, transaction: &Transaction) -> Result<usize, ErrorWallet> {
+        let mut least_signatures: Option<usize> = None;
+        for tx_in in transaction.tx_in.iter() {
+            let signatures = self
+                .get_signatures(&tx_in.signature_script)?
+                .iter()
+                .filter(|signature| !signature.is_empty())
+                .count();
+            least_signatures =
+                Some(least_signatures.map_or(signatures, |least| least.min(signatures)));
+        }
+
+        Ok(least_signatures.unwrap_or_default())
+    }
+
+    /// Returns true if every input of the transaction has the signatures it needs, so it can be broadcast
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::InvalidMultisig`: It will appear when an input does not spend from this account
+    pub fn is_complete(&self, transaction: &Transaction) -> Result<bool, ErrorWallet> {
+        Ok(!transaction.tx_in.is_empty() && self.count_signatures(transaction)? >= self.threshold)
+    }
+
+    /// Returns the signature script of an input with the given signatures, one for each public key.
+    /// With enough signatures only the first ones needed are kept, otherwise the missing ones are left empty
+    fn create_signature_script(&self, signatures: &[Vec<u8>]) -> ScriptBuf {
+        let given_signatures: Vec<&Vec<u8>> = signatures
+            .iter()
+            .filter(|signature| !signature.is_empty())
+            .collect();
+
+        let mut signature_script = ScriptBuf::new();
+        signature_script.push_opcode(OP_0);
+        match given_signatures.len() >= self.threshold {
+            true => given_signatures
+                .iter()
+                .take(self.threshold)
+                .for_each(|signature| signature_script.push_slice(signature)),
+            false => signatures
+                .iter()
+                .for_each(|signature| signature_script.push_slice(signature)),
+        }
+        signature_script.push_slice(self.redeem_script().as_bytes());
+
+        signature_script
+    }
+
+    /// Returns the signatures of the signature script of an input spending from this account
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::InvalidMultisig`: It will appear when the signature script does not spend from this account
+    fn get_signatures(&self, signature_script: &[u8]) -> Result<Vec<Vec<u8>>, ErrorWallet> {
+        let redeem_script = self.redeem_script();
+        let instructions = Script::new(signature_script).instructions();
+
+        match instructions.as_deref() {
+            Some([Instruction::PushBytes([]), signatures @ .., Instruction::PushBytes(script)])
+                if *script == redeem_script.as_bytes()
+                    && (signatures.len() == self.public_keys.len()
+                        || signatures.len() == self.threshold) =>
+            {
+                signatures
+                    .iter()
+                    .map(|signature| match signature {
+                        Instruction::PushBytes(signature) => Ok(signature.to_vec()),
+                        Instruction::Op(_) => Err(ErrorWallet::InvalidMultisig(
+                            "The signature script has an operation instead of a signature"
+                                .to_string(),
+                        )),
+                    })
+                    .collect()
+            }
+            _ => Err(ErrorWallet::InvalidMultisig(format!(
+                "The input does not spend from the multisig account {}",
+                self.account_name
+            ))),
+        }
+    }
+}
+
+/// Returns the redeem script that needs `threshold` signatures of the public keys, in their order
+fn create_redeem_script(public_keys: &[PublicKey], threshold: usize) -> ScriptBuf {
+    let public_keys: Vec<PublicKeyType> = public_keys
+        .iter()
+        .map(|public_key| public_key.as_bytes())
+        .collect();
+    ScriptBuf::new_multisig(threshold as u8, &public_keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        block_structure::{
+            block::Block, block_header::BlockHeader, block_version::BlockVersion,
+            compact256::Compact256, outpoint::Outpoint, script::ScriptType,
+            transaction_input::TransactionInput, transaction_output::TransactionOutput,
+        },
+        messages::compact_size::CompactSize,
+        serialization::serializable_internal_order::SerializableInternalOrder,
+    };
+
+    use secp256k1::{ecdsa::Signature, Message, Secp256k1};
+
+    fn create_cosigners() -> Vec<Account> {
+        vec![
+            Account::generate("First", Network::Testnet).unwrap(),
+            Account::generate("Second", Network::Testnet).unwrap(),
+            Account::generate("Third", Network::Testnet).unwrap(),
+        ]
+    }
+
+    fn create_multisig(cosigners: &[Account], threshold: usize) -> MultisigAccount {
+        let public_keys = cosigners
+            .iter()
+            .map(|cosigner| cosigner.public_key.clone())
+            .collect();
+        MultisigAccount::new("Shared", public_keys, threshold, Network::Testnet).unwrap()
+    }
+
+    fn create_utxo_set(address: &Address, value: i64) -> UTXOSet {
+        let mut block = Block::new(BlockHeader::new(
+            BlockVersion::version(1),
+            [0; 32],
+            [0; 32],
+            0,
+            Compact256::from(u32::MAX),
+            0,
+            CompactSize::new(1),
+        ));
+        block
+            .append_transaction(Transaction {
+                version: 1,
+                tx_in: vec![TransactionInput::new(
+                    Outpoint::new([1; 32], 0),
+                    vec![],
+                    0xffffffff,
+                )],
+                tx_out: vec![TransactionOutput::new(
+                    value,
+                    address.generate_script_pubkey(),
+                )],
+                lock_time: 0,
+            })
+            .unwrap();
+
+        UTXOSet::new(vec![block])
+    }
+
+    fn is_signed_by(
+        multisig: &MultisigAccount,
+        transaction: &Transaction,
+        signature: &[u8],
+        cosigner: &Account,
+    ) -> bool {
+        let hashed_message = sighash::legacy_sighash(
+            transaction,
+            0,
+            multisig.redeem_script().as_script(),
+            SIGHASH_ALL,
+        )
+        .unwrap();
+
+        let message = Message::from_slice(&hashed_message).unwrap();
+        let signature = Signature::from_der(&signature[..signature.len() - 1]).unwrap();
+        let public_key = secp256k1::PublicKey::from_slice(&cosigner.public_key.as_bytes()).unwrap();
+        Secp256k1::verification_only()
+            .verify_ecdsa(&message, &signature, &public_key)
+            .is_ok()
+    }
+
+    #[test]
+    fn test01_threshold_and_keys_are_validated() {
+        let cosigners = create_cosigners();
+        let public_keys: Vec<PublicKey> = cosigners
+            .iter()
+            .map(|cosigner| cosigner.public_key.clone())
+            .collect();
+
+        assert!(MultisigAccount::new("Shared", public_keys.clone(), 0, Network::Testnet).is_err());
+        assert!(MultisigAccount::new("Shared", public_keys.clone(), 4, Network::Testnet).is_err());
+        assert!(MultisigAccount::new(
+            "Shared",
+            vec![public_keys[0].clone(), public_keys[0].clone()],
+            1,
+            Network::Testnet
+        )
+        .is_err());
+        assert!(MultisigAccount::new(
+            "Shared",
+            vec![public_keys[0].clone(); 16],
+            1,
+            Network::Testnet
+        )
+        .is_err());
+        assert!(MultisigAccount::new("Shared", public_keys, 3, Network::Testnet).is_ok());
+    }
+
+    #[test]
+    fn test02_address_pays_to_the_hash_of_the_redeem_script() {
+        let multisig = create_multisig(&create_cosigners(), 2);
+        let redeem_script = multisig.redeem_script();
+        let script_hash = hash::hash160(redeem_script.as_bytes()).unwrap();
+
+        assert_eq!(Some(&[0x52][..]), redeem_script.get(..1));
+        assert!(multisig.address.is_script_hash());
+        assert_eq!(&script_hash, multisig.address.extract_hashed_pk());
+        assert_eq!(
+            ScriptType::PayToScriptHash,
+            multisig
+                .address
+                .generate_script_pubkey()
+                .as_script()
+                .get_type()
+        );
+
+        let public_keys = multisig.public_keys.clone();
+        let multisig = MultisigAccount::new("Shared", public_keys, 2, Network::Mainnet).unwrap();
+        assert_eq!(
+            Address::from_hash(Network::Mainnet.script_version_byte(), &script_hash).unwrap(),
+            multisig.address
+        );
+    }
+
+    #[test]
+    fn test03_transaction_is_completed_by_the_cosigners() {
+        let cosigners = create_cosigners();
+        let multisig = create_multisig(&cosigners, 2);
+        let utxo_set = create_utxo_set(&multisig.address, 100_000);
+        let to = cosigners[0].address.clone();
+
+        let mut transaction = multisig
+            .create_transaction(
+                to,
+                Amount::from_sat(50_000),
+                Amount::from_sat(1_000),
+                TransactionOptions::default(),
+                &utxo_set,
+            )
+            .unwrap();
+        assert_eq!(2, transaction.tx_out.len());
+        assert_eq!(Ok(0), multisig.count_signatures(&transaction));
+
+        multisig
+            .sign_transaction(&mut transaction, &cosigners[2])
+            .unwrap();
+        assert_eq!(Ok(1), multisig.count_signatures(&transaction));
+        assert_eq!(Ok(false), multisig.is_complete(&transaction));
+
+        let mut partially_signed: Vec<u8> = Vec::new();
+        transaction.io_serialize(&mut partially_signed).unwrap();
+        let mut transaction = Transaction::from_hex(&hash::to_hex(&partially_signed)).unwrap();
+        multisig
+            .sign_transaction(&mut transaction, &cosigners[0])
+            .unwrap();
+        assert_eq!(Ok(true), multisig.is_complete(&transaction));
+
+        let instructions = Script::new(&transaction.tx_in[0].signature_script)
+            .instructions()
+            .unwrap();
+        assert_eq!(4, instructions.len());
+        match (instructions[1], instructions[2]) {
+            (Instruction::PushBytes(first), Instruction::PushBytes(second)) => {
+                assert!(is_signed_by(&multisig, &transaction, first, &cosigners[0]));
+                assert!(is_signed_by(&multisig, &transaction, second, &cosigners[2]));
+            }
+            _ => panic!("The signatures were expected"),
+        }
+
+        assert!(multisig
+            .sign_transaction(&mut transaction, &cosigners[1])
+            .is_err());
+    }
+
+    #[test]
+    fn test04_only_cosigners_can_sign() {
+        let cosigners = create_cosigners();
+        let multisig = create_multisig(&cosigners, 2);
+        let utxo_set = create_utxo_set(&multisig.address, 100_000);
+
+        let mut transaction = multisig
+            .create_transaction(
+                cosigners[0].address.clone(),
+                Amount::from_sat(50_000),
+                Amount::from_sat(1_000),
+                TransactionOptions::default(),
+                &utxo_set,
+            )
+            .unwrap();
+
+        let outsider = Account::generate("Outsider", Network::Testnet).unwrap();
+        assert!(matches!(
+            multisig.sign_transaction(&mut transaction, &outsider),
+            Err(ErrorWallet::InvalidMultisig(_))
+        ));
+
+        let other_multisig = create_multisig(&cosigners[..2], 1);
+        assert!(matches!(
+            other_multisig.sign_transaction(&mut transaction, &cosigners[0]),
+            Err(ErrorWallet::InvalidMultisig(_))
+        ));
+    }
+}
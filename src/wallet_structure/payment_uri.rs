@@ -0,0 +1,240 @@
+use super::{address::Address, amount::Amount, error_wallet::ErrorWallet};
+
+use std::{convert::TryFrom, fmt::Display};
+
+const SCHEME: &str = "bitcoin:";
+const QUERY_START: char = '?';
+const PARAMETER_SEPARATOR: char = '&';
+const VALUE_SEPARATOR: char = '=';
+const REQUIRED_PREFIX: &str = "req-";
+
+const AMOUNT: &str = "amount";
+const LABEL: &str = "label";
+const MESSAGE: &str = "message";
+
+/// It's a payment request following the BIP21: `bitcoin:<address>?amount=<amount>&label=<label>&message=<message>`,
+/// with the amount in bitcoins
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaymentUri {
+    pub address: Address,
+    pub amount: Option<Amount>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+}
+
+impl PaymentUri {
+    /// Creates the payment request to the given address, without amount
+    pub fn new(address: Address) -> Self {
+        PaymentUri {
+            address,
+            amount: None,
+            label: None,
+            message: None,
+        }
+    }
+
+    pub fn with_amount(mut self, amount: Amount) -> Self {
+        self.amount = Some(amount);
+        self
+    }
+
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    pub fn with_message(mut self, message: &str) -> Self {
+        self.message = Some(message.to_string());
+        self
+    }
+}
+
+impl TryFrom<&str> for PaymentUri {
+    type Error = ErrorWallet;
+
+    /// Reads a payment request, ignoring the parameters that are not known unless they are required
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::InvalidPaymentUri`: It will appear when the URI does not follow the BIP21 format
+    ///  * `ErrorWallet::CannotDecodeAddress`: It will appear when the address is not valid
+    fn try_from(uri: &str) -> Result<Self, Self::Error> {
+        let uri = uri.trim();
+        let rest = match uri.get(..SCHEME.len()) {
+            Some(scheme) if scheme.eq_ignore_ascii_case(SCHEME) => &uri[SCHEME.len()..],
+            _ => {
+                return Err(ErrorWallet::InvalidPaymentUri(format!(
+                    "The URI {uri} does not start with {SCHEME}"
+                )))
+            }
+        };
+
+        let (address, query) = match rest.split_once(QUERY_START) {
+            Some((address, query)) => (address, query),
+            None => (rest, ""),
+        };
+        let mut payment_uri = PaymentUri::new(Address::new(address)?);
+
+        for parameter in query.split(PARAMETER_SEPARATOR) {
+            if parameter.is_empty() {
+                continue;
+            }
+
+            let (key, value) = match parameter.split_once(VALUE_SEPARATOR) {
+                Some((key, value)) => (key, percent_decode(value)?),
+                None => (parameter, String::new()),
+            };
+
+            match key {
+                AMOUNT => payment_uri.amount = Some(parse_amount(&value)?),
+                LABEL => payment_uri.label = Some(value),
+                MESSAGE => payment_uri.message = Some(value),
+                key if key.starts_with(REQUIRED_PREFIX) => {
+                    return Err(ErrorWallet::InvalidPaymentUri(format!(
+                        "The required parameter {key} is not supported"
+                    )))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(payment_uri)
+    }
+}
+
+impl Display for PaymentUri {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{SCHEME}{}", self.address)?;
+
+        let mut parameters: Vec<String> = Vec::new();
+        if let Some(amount) = self.amount {
+            let amount = amount.format_btc();
+            parameters.push(format!(
+                "{AMOUNT}{VALUE_SEPARATOR}{}",
+                amount.trim_end_matches('0').trim_end_matches('.')
+            ));
+        }
+        if let Some(label) = &self.label {
+            parameters.push(format!("{LABEL}{VALUE_SEPARATOR}{}", percent_encode(label)));
+        }
+        if let Some(message) = &self.message {
+            parameters.push(format!(
+                "{MESSAGE}{VALUE_SEPARATOR}{}",
+                percent_encode(message)
+            ));
+        }
+
+        if !parameters.is_empty() {
+            write!(
+                f,
+                "{QUERY_START}{}",
+                parameters.join(&PARAMETER_SEPARATOR.to_string())
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads an amount in bitcoins written only with digits and a decimal point
+fn parse_amount(value: &str) -> Result<Amount, ErrorWallet> {
+    match Amount::from_btc(value) {
+        Ok(amount) => Ok(amount),
+        Err(_) => Err(ErrorWallet::InvalidPaymentUri(format!(
+            "Invalid amount {value}"
+        ))),
+    }
+}
+
+/// Replaces every `%XX` of the value by the byte it represents
+fn percent_decode(value: &str) -> Result<String, ErrorWallet> {
+    let error = || ErrorWallet::InvalidPaymentUri(format!("Invalid encoding of {value}"));
+
+    let mut bytes: Vec<u8> = Vec::new();
+    let mut rest = value.as_bytes();
+    while let Some((byte, next)) = rest.split_first() {
+        match byte {
+            b'%' => {
+                let hex = match next.get(..2) {
+                    Some(hex) => std::str::from_utf8(hex).map_err(|_| error())?,
+                    None => return Err(error()),
+                };
+                bytes.push(u8::from_str_radix(hex, 16).map_err(|_| error())?);
+                rest = &next[2..];
+            }
+            byte => {
+                bytes.push(*byte);
+                rest = next;
+            }
+        }
+    }
+
+    String::from_utf8(bytes).map_err(|_| error())
+}
+
+/// Replaces every byte that is not a letter, a digit or one of `-._~` by its `%XX`
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(
+            |byte| match byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+                true => (byte as char).to_string(),
+                false => format!("%{:02X}", byte),
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "mnEvYsxexfDEkCx2YLEfzhjrwKKcyAhMqV";
+
+    #[test]
+    fn test01_payment_uri_is_read_with_its_parameters() {
+        let uri = format!(
+            "BITCOIN:{ADDRESS}?amount=0.015&label=Luke%20Jr&message=Donation&future=ignored"
+        );
+
+        let payment_uri = PaymentUri::try_from(uri.as_str()).unwrap();
+
+        assert_eq!(ADDRESS, payment_uri.address.to_string());
+        assert_eq!(Some(Amount::from_sat(1_500_000)), payment_uri.amount);
+        assert_eq!(Some("Luke Jr".to_string()), payment_uri.label);
+        assert_eq!(Some("Donation".to_string()), payment_uri.message);
+    }
+
+    #[test]
+    fn test02_invalid_payment_uris_are_rejected() {
+        for uri in [
+            ADDRESS.to_string(),
+            format!("bitcoin:{ADDRESS}?amount=1e3"),
+            format!("bitcoin:{ADDRESS}?req-somethingyoudontunderstand=50"),
+            format!("bitcoin:{ADDRESS}?label=%2"),
+        ] {
+            assert!(matches!(
+                PaymentUri::try_from(uri.as_str()),
+                Err(ErrorWallet::InvalidPaymentUri(_))
+            ));
+        }
+
+        assert!(matches!(
+            PaymentUri::try_from("bitcoin:notanaddress"),
+            Err(ErrorWallet::CannotDecodeAddress(_))
+        ));
+    }
+
+    #[test]
+    fn test03_payment_uri_is_written_as_it_is_read() {
+        let payment_uri = PaymentUri::new(Address::new(ADDRESS).unwrap())
+            .with_amount(Amount::from_sat(50_000_000))
+            .with_label("Café & co");
+
+        let uri = payment_uri.to_string();
+
+        assert_eq!(
+            format!("bitcoin:{ADDRESS}?amount=0.5&label=Caf%C3%A9%20%26%20co"),
+            uri
+        );
+        assert_eq!(payment_uri, PaymentUri::try_from(uri.as_str()).unwrap());
+    }
+}
@@ -1,4 +1,8 @@
-use super::error_wallet::ErrorWallet;
+use super::{error_wallet::ErrorWallet, public_key::PublicKey};
+
+use crate::block_structure::hash::hash256d_reduce;
+
+use crate::connections::network::Network;
 
 use crate::serialization::{
     deserializable_internal_order::DeserializableInternalOrder,
@@ -7,13 +11,20 @@ use crate::serialization::{
 };
 
 use std::{
+    fs::File,
     io::{Read, Write},
     str::FromStr,
 };
 
+use bs58::decode;
 use secp256k1::{Secp256k1, SecretKey};
 
 pub const PRIVATE_KEY_SIZE: usize = 32;
+const RANDOM_SOURCE: &str = "/dev/urandom";
+
+/// The byte after the key in the WIF of a private key whose public key is compressed
+const WIF_COMPRESSED_FLAG: u8 = 0x01;
+const WIF_CHECKSUM_SIZE: usize = 4;
 pub type PrivateKeyType = [u8; PRIVATE_KEY_SIZE];
 
 /// It's the internal representation of a private key for an account
@@ -23,7 +34,7 @@ pub struct PrivateKey {
 }
 
 impl PrivateKey {
-    /// Creates a private key object from a string with a WIF (uncompressed)
+    /// Creates a private key object from its bytes
     ///
     /// ### Error
     ///  * `ErrorWallet::CannotGeneratePrivateKey`: It will appear when private key for an account cannot be generated
@@ -41,8 +52,111 @@ impl PrivateKey {
         Ok(PrivateKey { key })
     }
 
+    /// Reads a private key written in WIF (Wallet Import Format) for the given network. Only the keys
+    /// of compressed public keys are accepted, as those are the public keys of the accounts. The
+    /// errors never contain the WIF, because it's the secret of the key
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotGeneratePrivateKey`: It will appear when the value is not a valid WIF of a compressed key of the network
+    pub fn from_wif(wif: &str, network: Network) -> Result<PrivateKey, ErrorWallet> {
+        let decoded_wif = match decode(wif.trim()).into_vec() {
+            Ok(decoded_wif) => decoded_wif,
+            Err(_) => {
+                return Err(ErrorWallet::CannotGeneratePrivateKey(
+                    "Cannot decode the WIF from base 58".to_string(),
+                ))
+            }
+        };
+
+        if decoded_wif.len() == 1 + PRIVATE_KEY_SIZE + WIF_CHECKSUM_SIZE {
+            return Err(ErrorWallet::CannotGeneratePrivateKey(
+                "The private keys of uncompressed public keys are not supported".to_string(),
+            ));
+        }
+
+        let (payload, checksum) = match decoded_wif.len() {
+            length if length == 2 + PRIVATE_KEY_SIZE + WIF_CHECKSUM_SIZE => {
+                decoded_wif.split_at(length - WIF_CHECKSUM_SIZE)
+            }
+            length => {
+                return Err(ErrorWallet::CannotGeneratePrivateKey(format!(
+                    "Invalid WIF length, expected {}, got {length}",
+                    2 + PRIVATE_KEY_SIZE + WIF_CHECKSUM_SIZE
+                )))
+            }
+        };
+
+        match hash256d_reduce(payload) {
+            Ok(expected_checksum) if expected_checksum == checksum => {}
+            _ => {
+                return Err(ErrorWallet::CannotGeneratePrivateKey(
+                    "The checksum of the WIF is not valid".to_string(),
+                ))
+            }
+        }
+
+        if payload[0] != network.wif_version_byte() {
+            return Err(ErrorWallet::CannotGeneratePrivateKey(format!(
+                "The WIF is not of the {network} network"
+            )));
+        }
+
+        if payload[1 + PRIVATE_KEY_SIZE] != WIF_COMPRESSED_FLAG {
+            return Err(ErrorWallet::CannotGeneratePrivateKey(
+                "The WIF does not end with the flag of a compressed key".to_string(),
+            ));
+        }
+
+        let mut private_key_bytes: PrivateKeyType = [0; PRIVATE_KEY_SIZE];
+        private_key_bytes.copy_from_slice(&payload[1..1 + PRIVATE_KEY_SIZE]);
+        match PrivateKey::new(&private_key_bytes) {
+            Ok(private_key) => Ok(private_key),
+            Err(_) => Err(ErrorWallet::CannotGeneratePrivateKey(
+                "The WIF does not contain a valid private key".to_string(),
+            )),
+        }
+    }
+
+    /// Creates a new private key from the random bytes given by the operating system
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotGeneratePrivateKey`: It will appear when the random bytes cannot be read
+    pub fn generate() -> Result<PrivateKey, ErrorWallet> {
+        let mut random_source = match File::open(RANDOM_SOURCE) {
+            Ok(random_source) => random_source,
+            Err(e) => {
+                return Err(ErrorWallet::CannotGeneratePrivateKey(format!(
+                    "Cannot open the source of random bytes, error : {:?}",
+                    e
+                )))
+            }
+        };
+
+        loop {
+            let mut private_key_bytes: PrivateKeyType = [0; PRIVATE_KEY_SIZE];
+            if let Err(e) = random_source.read_exact(&mut private_key_bytes) {
+                return Err(ErrorWallet::CannotGeneratePrivateKey(format!(
+                    "Cannot read random bytes, error : {:?}",
+                    e
+                )));
+            }
+
+            // Almost every value is valid, the rest are outside the order of the curve
+            if let Ok(key) = SecretKey::from_slice(&private_key_bytes) {
+                return Ok(PrivateKey { key });
+            }
+        }
+    }
+
+    /// Returns the compressed public key that corresponds to the private key
+    pub fn public_key(&self) -> PublicKey {
+        let secp = Secp256k1::new();
+        let public_key = secp256k1::PublicKey::from_secret_key(&secp, &self.key);
+        PublicKey::new(&public_key.serialize())
+    }
+
     /// Returns the private key as a byte array
-    fn as_bytes(&self) -> PrivateKeyType {
+    pub fn as_bytes(&self) -> PrivateKeyType {
         self.key.secret_bytes()
     }
 
@@ -125,4 +239,96 @@ mod tests {
         let signing_bytes = private_key.as_bytes();
         assert!(signing_bytes == private_key_bytes);
     }
+
+    #[test]
+    fn test_02_public_key_is_derived_from_the_private_key() {
+        let private_key_bytes: [u8; 32] = [
+            0x0a, 0x52, 0x65, 0x08, 0x2e, 0x24, 0x11, 0x5f, 0x77, 0x54, 0x0a, 0xb3, 0xb8, 0xc2,
+            0xb9, 0x20, 0x60, 0xaa, 0x30, 0xd6, 0xd2, 0xb8, 0x1a, 0x08, 0x5d, 0x71, 0xab, 0x37,
+            0xed, 0xa7, 0x68, 0x91,
+        ];
+        let public_key_bytes: [u8; 33] = [
+            0x03, 0xBC, 0x6D, 0x45, 0xD2, 0x10, 0x1E, 0x91, 0x28, 0xDE, 0x14, 0xB5, 0xB6, 0x68,
+            0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
+            0x35, 0x14, 0x92, 0x4A, 0x22,
+        ];
+
+        let private_key = PrivateKey::new(&private_key_bytes).unwrap();
+
+        assert_eq!(PublicKey::new(&public_key_bytes), private_key.public_key());
+    }
+
+    #[test]
+    fn test_03_generated_keys_are_different() {
+        let private_key = PrivateKey::generate().unwrap();
+        let other_private_key = PrivateKey::generate().unwrap();
+
+        assert_ne!(private_key, other_private_key);
+        assert_eq!(33, private_key.public_key().as_bytes().len());
+    }
+
+    #[test]
+    fn test_04_private_key_is_read_from_its_wif() {
+        let mut private_key_bytes: PrivateKeyType = [0; PRIVATE_KEY_SIZE];
+        private_key_bytes[PRIVATE_KEY_SIZE - 1] = 1;
+        let private_key = PrivateKey::new(&private_key_bytes).unwrap();
+
+        assert_eq!(
+            Ok(private_key.clone()),
+            PrivateKey::from_wif(
+                "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn",
+                Network::Mainnet
+            )
+        );
+        assert_eq!(
+            Ok(private_key),
+            PrivateKey::from_wif(
+                "cMahea7zqjxrtgAbB7LSGbcQUr1uX1ojuat9jZodMN87JcbXMTcA",
+                Network::Testnet
+            )
+        );
+
+        assert!(PrivateKey::from_wif(
+            "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn",
+            Network::Testnet
+        )
+        .is_err());
+        assert!(PrivateKey::from_wif(
+            "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWm",
+            Network::Mainnet
+        )
+        .is_err());
+        assert!(PrivateKey::from_wif(
+            "5HpHagT65TZzG1PH3CSu63k8DbpvD8s5ip4nEB3kEsreAnchuDf",
+            Network::Mainnet
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_05_errors_of_the_wif_do_not_contain_it() {
+        let wifs = [
+            (
+                "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn",
+                Network::Testnet,
+            ),
+            (
+                "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWm",
+                Network::Mainnet,
+            ),
+            (
+                "5HpHagT65TZzG1PH3CSu63k8DbpvD8s5ip4nEB3kEsreAnchuDf",
+                Network::Mainnet,
+            ),
+            (
+                "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoW0",
+                Network::Mainnet,
+            ),
+        ];
+
+        for (wif, network) in wifs {
+            let error = PrivateKey::from_wif(wif, network).unwrap_err();
+            assert!(!format!("{:?}", error).contains(wif));
+        }
+    }
 }
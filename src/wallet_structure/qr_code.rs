@@ -0,0 +1,355 @@
+use super::error_wallet::ErrorWallet;
+
+/// The data codewords per block, the amount of blocks and the error correction codewords per block
+/// of the versions 1 to 6 with the medium error correction level
+const VERSIONS: [(usize, usize, usize); 6] = [
+    (16, 1, 10),
+    (28, 1, 16),
+    (44, 1, 26),
+    (32, 2, 18),
+    (43, 2, 24),
+    (27, 4, 16),
+];
+
+const BYTE_MODE: u8 = 0b0100;
+const PAD_BYTES: [u8; 2] = [0xEC, 0x11];
+
+const FORMAT_GENERATOR: u32 = 0x537;
+const FORMAT_MASK: u32 = 0x5412;
+const MEDIUM_ERROR_CORRECTION: u32 = 0b00;
+const MASK_PATTERN: u32 = 0;
+
+/// It's a QR code with the medium error correction level, encoding the data in byte mode.
+///
+/// Only the versions 1 to 6 are supported, which is enough for an address or a payment request,
+/// and the mask 0 is always used, given that the readers accept any of them
+#[derive(Debug, Clone, PartialEq)]
+pub struct QrCode {
+    size: usize,
+    modules: Vec<Vec<bool>>,
+    is_function: Vec<Vec<bool>>,
+}
+
+impl QrCode {
+    /// Creates the QR code of the smallest version where the data fits
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotEncodeQrCode`: It will appear when the data does not fit in any supported version
+    pub fn encode(data: &[u8]) -> Result<QrCode, ErrorWallet> {
+        let (version, (data_per_block, blocks, error_correction_per_block)) = match VERSIONS
+            .iter()
+            .enumerate()
+            .find(|(_, (data_per_block, blocks, _))| data_per_block * blocks - 2 >= data.len())
+        {
+            Some((index, version)) => (index + 1, *version),
+            None => {
+                return Err(ErrorWallet::CannotEncodeQrCode(format!(
+                    "The data of {} bytes is too long",
+                    data.len()
+                )))
+            }
+        };
+
+        let size = version * 4 + 17;
+        let mut qr_code = QrCode {
+            size,
+            modules: vec![vec![false; size]; size],
+            is_function: vec![vec![false; size]; size],
+        };
+
+        let data_codewords = get_data_codewords(data, data_per_block * blocks);
+        let codewords = add_error_correction(&data_codewords, blocks, error_correction_per_block);
+
+        qr_code.draw_function_patterns(version);
+        qr_code.draw_codewords(&codewords);
+        qr_code.apply_mask();
+        Ok(qr_code)
+    }
+
+    /// Returns the amount of modules of each side
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns if the module in the column `x` and the row `y` is dark, being light outside the code
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        match self.modules.get(y) {
+            Some(row) => row.get(x).copied().unwrap_or(false),
+            None => false,
+        }
+    }
+
+    fn set_function_module(&mut self, x: usize, y: usize, is_dark: bool) {
+        self.modules[y][x] = is_dark;
+        self.is_function[y][x] = true;
+    }
+
+    fn draw_function_patterns(&mut self, version: usize) {
+        for i in 0..self.size {
+            self.set_function_module(6, i, i.is_multiple_of(2));
+            self.set_function_module(i, 6, i.is_multiple_of(2));
+        }
+
+        let last = self.size - 4;
+        for (x, y) in [(3, 3), (last, 3), (3, last)] {
+            self.draw_finder_pattern(x, y);
+        }
+
+        if version > 1 {
+            let position = self.size - 7;
+            self.draw_alignment_pattern(position, position);
+        }
+
+        self.draw_format_bits();
+    }
+
+    fn draw_finder_pattern(&mut self, x: usize, y: usize) {
+        for dy in -4..=4_isize {
+            for dx in -4..=4_isize {
+                let distance = dx.abs().max(dy.abs());
+                let (x, y) = (x as isize + dx, y as isize + dy);
+                if (0..self.size as isize).contains(&x) && (0..self.size as isize).contains(&y) {
+                    self.set_function_module(
+                        x as usize,
+                        y as usize,
+                        distance != 2 && distance != 4,
+                    );
+                }
+            }
+        }
+    }
+
+    fn draw_alignment_pattern(&mut self, x: usize, y: usize) {
+        for dy in 0..5 {
+            for dx in 0..5 {
+                let distance = (dx as isize - 2).abs().max((dy as isize - 2).abs());
+                self.set_function_module(x + dx - 2, y + dy - 2, distance != 1);
+            }
+        }
+    }
+
+    fn draw_format_bits(&mut self) {
+        let data = (MEDIUM_ERROR_CORRECTION << 3) | MASK_PATTERN;
+        let mut remainder = data;
+        for _ in 0..10 {
+            remainder = (remainder << 1) ^ ((remainder >> 9) * FORMAT_GENERATOR);
+        }
+        let bits = ((data << 10) | remainder) ^ FORMAT_MASK;
+        let bit = |i: usize| (bits >> i) & 1 == 1;
+
+        for i in 0..=5 {
+            self.set_function_module(8, i, bit(i));
+        }
+        self.set_function_module(8, 7, bit(6));
+        self.set_function_module(8, 8, bit(7));
+        self.set_function_module(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function_module(14 - i, 8, bit(i));
+        }
+
+        for i in 0..8 {
+            self.set_function_module(self.size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function_module(8, self.size - 15 + i, bit(i));
+        }
+        self.set_function_module(8, self.size - 8, true);
+    }
+
+    /// Places the bits of the codewords in zigzag, two columns at a time from the bottom right corner
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let mut index = 0;
+        let mut right = self.size - 1;
+        while right >= 1 {
+            if right == 6 {
+                right = 5;
+            }
+
+            let upward = (right + 1) & 2 == 0;
+            for vertical in 0..self.size {
+                for column in 0..2 {
+                    let x = right - column;
+                    let y = match upward {
+                        true => self.size - 1 - vertical,
+                        false => vertical,
+                    };
+
+                    if !self.is_function[y][x] && index < codewords.len() * 8 {
+                        self.modules[y][x] = (codewords[index / 8] >> (7 - index % 8)) & 1 == 1;
+                        index += 1;
+                    }
+                }
+            }
+
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                if !self.is_function[y][x] && (x + y).is_multiple_of(2) {
+                    self.modules[y][x] = !self.modules[y][x];
+                }
+            }
+        }
+    }
+}
+
+/// Returns the mode, the length and the data, followed by the terminator and the padding
+/// to fill the capacity
+fn get_data_codewords(data: &[u8], capacity: usize) -> Vec<u8> {
+    let mut bits: Vec<bool> = Vec::new();
+    append_bits(&mut bits, BYTE_MODE as u32, 4);
+    append_bits(&mut bits, data.len() as u32, 8);
+    data.iter()
+        .for_each(|byte| append_bits(&mut bits, *byte as u32, 8));
+
+    let terminator = (capacity * 8 - bits.len()).min(4);
+    append_bits(&mut bits, 0, terminator);
+    let padding = (8 - bits.len() % 8) % 8;
+    append_bits(&mut bits, 0, padding);
+
+    let mut codewords: Vec<u8> = bits
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0, |value, bit| (value << 1) | *bit as u8))
+        .collect();
+
+    let mut pad_bytes = PAD_BYTES.iter().cycle();
+    while codewords.len() < capacity {
+        if let Some(pad_byte) = pad_bytes.next() {
+            codewords.push(*pad_byte);
+        }
+    }
+    codewords
+}
+
+fn append_bits(bits: &mut Vec<bool>, value: u32, length: usize) {
+    for i in (0..length).rev() {
+        bits.push((value >> i) & 1 == 1);
+    }
+}
+
+/// Splits the data in blocks, adds the error correction of each one and interleaves them
+fn add_error_correction(data: &[u8], blocks: usize, error_correction_per_block: usize) -> Vec<u8> {
+    let divisor = reed_solomon_divisor(error_correction_per_block);
+    let data_blocks: Vec<&[u8]> = data.chunks(data.len() / blocks).collect();
+    let error_correction_blocks: Vec<Vec<u8>> = data_blocks
+        .iter()
+        .map(|block| reed_solomon_remainder(block, &divisor))
+        .collect();
+
+    let mut codewords: Vec<u8> = Vec::new();
+    for i in 0..data_blocks[0].len() {
+        data_blocks
+            .iter()
+            .for_each(|block| codewords.push(block[i]));
+    }
+    for i in 0..error_correction_per_block {
+        error_correction_blocks
+            .iter()
+            .for_each(|block| codewords.push(block[i]));
+    }
+    codewords
+}
+
+/// Returns the coefficients of the generator polynomial of the given degree, from the highest
+/// power without the leading one
+fn reed_solomon_divisor(degree: usize) -> Vec<u8> {
+    let mut result = vec![0; degree];
+    result[degree - 1] = 1;
+
+    let mut root: u8 = 1;
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = gf_multiply(result[j], root);
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+        root = gf_multiply(root, 0x02);
+    }
+    result
+}
+
+fn reed_solomon_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut result = vec![0; divisor.len()];
+    for byte in data {
+        let factor = byte ^ result.remove(0);
+        result.push(0);
+        for (coefficient, divisor) in result.iter_mut().zip(divisor) {
+            *coefficient ^= gf_multiply(*divisor, factor);
+        }
+    }
+    result
+}
+
+/// Multiplies in the field GF(2^8) with the polynomial 0x11D
+fn gf_multiply(x: u8, y: u8) -> u8 {
+    let mut z: u8 = 0;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x1D);
+        z ^= ((y >> i) & 1) * x;
+    }
+    z
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_error_correction_follows_the_reed_solomon_code() {
+        let data: [u8; 16] = [
+            32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17,
+        ];
+
+        let error_correction = reed_solomon_remainder(&data, &reed_solomon_divisor(10));
+
+        assert_eq!(
+            vec![196, 35, 39, 119, 235, 215, 231, 226, 93, 23],
+            error_correction
+        );
+    }
+
+    #[test]
+    fn test02_data_is_padded_to_the_capacity() {
+        let codewords = get_data_codewords(b"a", 16);
+
+        assert_eq!(16, codewords.len());
+        assert_eq!([0x40, 0x16, 0x10, 0xEC, 0x11, 0xEC], codewords[..6]);
+    }
+
+    #[test]
+    fn test03_qr_code_has_the_finder_patterns_and_the_format() {
+        let qr_code = QrCode::encode(b"bitcoin:mnEvYsxexfDEkCx2YLEfzhjrwKKcyAhMqV").unwrap();
+        let size = qr_code.size();
+        let format: u32 = 0b101010000010010;
+
+        assert_eq!(29, size);
+        for (x, y) in [(0, 0), (size - 7, 0), (0, size - 7)] {
+            assert!(qr_code.is_dark(x, y));
+            assert!(qr_code.is_dark(x + 6, y + 6));
+            assert!(!qr_code.is_dark(x + 1, y + 1));
+            assert!(qr_code.is_dark(x + 3, y + 3));
+        }
+        for i in 0..=5 {
+            assert_eq!((format >> i) & 1 == 1, qr_code.is_dark(8, i));
+        }
+        assert!(qr_code.is_dark(8, size - 8));
+        assert!(qr_code.is_dark(size - 7, size - 7));
+        assert!(!qr_code.is_dark(size, 0));
+    }
+
+    #[test]
+    fn test04_data_too_long_cannot_be_encoded() {
+        assert!(matches!(
+            QrCode::encode(&[0; 107]),
+            Err(ErrorWallet::CannotEncodeQrCode(_))
+        ));
+        assert!(QrCode::encode(&[0; 106]).is_ok());
+    }
+}
@@ -0,0 +1,261 @@
+use super::{
+    account::Account,
+    balance_history::BalanceHistory,
+    transaction_history::{Direction, HistoryEntry},
+};
+
+use crate::{
+    block_structure::{
+        block::Block, block_chain::BlockChain, outpoint::Outpoint, transaction::Transaction,
+        transaction_output::TransactionOutput,
+    },
+    notifications::{notification::Notification, notifier::Notifier},
+};
+
+use std::collections::HashMap;
+
+/// The amount of blocks walked between each notification of the progress of a rescan
+const RESCAN_PROGRESS_STEP: u32 = 500;
+
+/// It's a rescan of the blocks of the longest chain for the accounts of a wallet, from a given height.
+/// It copies what it needs from the block chain when created, so the blocks can be walked without
+/// keeping the block chain or the wallet
+#[derive(Debug, Clone)]
+pub struct Rescan {
+    accounts: Vec<Account>,
+    from_height: u64,
+    tip_height: u64,
+
+    /// The blocks of the longest chain from the given height
+    blocks: Vec<(u64, Block)>,
+
+    /// The unspent outputs of the accounts, with the blocks walked so far
+    owned_utxos: HashMap<Outpoint, TransactionOutput>,
+}
+
+/// It's what a rescan found for the accounts of the wallet
+#[derive(Debug, Clone)]
+pub struct RescanResult {
+    /// The transactions that change the balance of the accounts from the given height, from the newest one
+    pub history: Vec<HistoryEntry>,
+
+    /// The unspent outputs of the accounts at the last block
+    pub owned_utxos: HashMap<Outpoint, TransactionOutput>,
+
+    /// The balance checkpoints of the accounts from the given height
+    pub balance_history: BalanceHistory,
+}
+
+impl Rescan {
+    /// Creates the rescan of the given accounts from the given height. The blocks before it are only
+    /// walked by reference to find the unspent outputs of the accounts, and the ones after it are copied
+    pub fn new(block_chain: &BlockChain, accounts: &[Account], from_height: u64) -> Self {
+        let mut rescan = Rescan {
+            accounts: accounts.to_vec(),
+            from_height,
+            tip_height: 0,
+            blocks: Vec::new(),
+            owned_utxos: HashMap::new(),
+        };
+
+        for (height, block) in block_chain.get_main_chain_blocks() {
+            rescan.tip_height = height;
+            match height < from_height {
+                true => {
+                    for transaction in block.transactions.iter() {
+                        rescan.connect_transaction(transaction);
+                    }
+                }
+                false => rescan.blocks.push((height, block.clone())),
+            }
+        }
+
+        rescan
+    }
+
+    /// Walks the blocks from the given height, rebuilding the history, the unspent outputs and the
+    /// balance checkpoints of the accounts. The progress is notified as the blocks are walked
+    pub fn run<N: Notifier>(mut self, notifier: N) -> RescanResult {
+        let blocks = std::mem::take(&mut self.blocks);
+        let total = blocks.len() as u32;
+
+        let mut history: Vec<HistoryEntry> = Vec::new();
+        let mut balance_history = BalanceHistory::new();
+        let mut scanned: u32 = 0;
+
+        for (height, block) in blocks.iter() {
+            for transaction in block.transactions.iter() {
+                let net_amounts = self.connect_transaction(transaction);
+                let transaction_id = match transaction.get_tx_id() {
+                    Ok(transaction_id) => transaction_id,
+                    Err(_) => continue,
+                };
+
+                for (account, net_amount) in self.accounts.iter().zip(net_amounts) {
+                    if net_amount == 0 {
+                        continue;
+                    }
+
+                    history.push(HistoryEntry {
+                        account_name: account.account_name.clone(),
+                        timestamp: block.header.time,
+                        transaction_id,
+                        direction: match net_amount > 0 {
+                            true => Direction::Received,
+                            false => Direction::Sent,
+                        },
+                        amount: net_amount.abs(),
+                        confirmations: self.tip_height - height + 1,
+                        label: None,
+                    });
+                }
+            }
+
+            for account in self.accounts.iter() {
+                balance_history.record_checkpoint(account, *height, self.get_balance(account));
+            }
+
+            scanned += 1;
+            if scanned.is_multiple_of(RESCAN_PROGRESS_STEP) && scanned < total {
+                notifier.notify(Notification::ProgressRescan(scanned, total));
+            }
+        }
+
+        notifier.notify(Notification::ProgressRescan(scanned, total));
+
+        history.reverse();
+        RescanResult {
+            history,
+            owned_utxos: self.owned_utxos,
+            balance_history,
+        }
+    }
+
+    /// Returns the height from which the blocks are rescanned
+    pub fn from_height(&self) -> u64 {
+        self.from_height
+    }
+
+    /// Updates the unspent outputs of the accounts with the transaction.
+    ///
+    /// Returns the satoshis that each account receives in the transaction minus the ones it spends
+    fn connect_transaction(&mut self, transaction: &Transaction) -> Vec<i64> {
+        let mut net_amounts: Vec<i64> = vec![0; self.accounts.len()];
+
+        for input in transaction.tx_in.iter() {
+            if let Some(output) = self.owned_utxos.remove(&input.previous_output) {
+                for (account, net_amount) in self.accounts.iter().zip(net_amounts.iter_mut()) {
+                    if account.verify_transaction_output_ownership(&output) {
+                        *net_amount -= output.value;
+                    }
+                }
+            }
+        }
+
+        let transaction_id = match transaction.get_tx_id() {
+            Ok(transaction_id) => transaction_id,
+            Err(_) => return net_amounts,
+        };
+
+        for (index, output) in transaction.tx_out.iter().enumerate() {
+            let mut owned = false;
+            for (account, net_amount) in self.accounts.iter().zip(net_amounts.iter_mut()) {
+                if account.verify_transaction_output_ownership(output) {
+                    *net_amount += output.value;
+                    owned = true;
+                }
+            }
+
+            if owned {
+                self.owned_utxos
+                    .insert(Outpoint::new(transaction_id, index as u32), output.clone());
+            }
+        }
+
+        net_amounts
+    }
+
+    /// Returns the satoshis of the unspent outputs of the account
+    fn get_balance(&self, account: &Account) -> i64 {
+        self.owned_utxos
+            .values()
+            .filter(|output| account.verify_transaction_output_ownership(output))
+            .map(|output| output.value)
+            .sum()
+    }
+}
+
+impl RescanResult {
+    /// Returns the unspent outputs of the given account found by the rescan
+    pub fn get_utxo_list(&self, account: &Account) -> Vec<TransactionOutput> {
+        self.owned_utxos
+            .values()
+            .filter(|output| account.verify_transaction_output_ownership(output))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        connections::network::Network, notifications::notification_history::NotificationHistory,
+        testing::scenario::ScenarioBuilder,
+    };
+
+    #[test]
+    fn test_01_rescan_finds_the_history_of_an_imported_account() {
+        let account = Account::generate("Imported", Network::Testnet).unwrap();
+        let scenario = ScenarioBuilder::new()
+            .payment(&account, 1_000)
+            .empty_blocks(1)
+            .payment(&account, 2_000)
+            .build()
+            .unwrap();
+        let block_chain = scenario.expected_block_chain().unwrap();
+        let notifier = NotificationHistory::new(10);
+
+        let result =
+            Rescan::new(&block_chain, std::slice::from_ref(&account), 0).run(notifier.clone());
+        assert_eq!(2, result.history.len());
+        assert_eq!(2, result.get_utxo_list(&account).len());
+        assert_eq!(
+            vec![(1, 1_000), (3, 3_000)],
+            result.balance_history.balance_history(&account, 0)
+        );
+        assert!(matches!(
+            notifier.notifications().as_slice(),
+            [Notification::ProgressRescan(3, 3)]
+        ));
+    }
+
+    #[test]
+    fn test_02_rescan_from_a_height_keeps_the_outputs_before_it() {
+        let account = Account::generate("Imported", Network::Testnet).unwrap();
+        let scenario = ScenarioBuilder::new()
+            .payment(&account, 1_000)
+            .empty_blocks(1)
+            .payment(&account, 2_000)
+            .build()
+            .unwrap();
+        let block_chain = scenario.expected_block_chain().unwrap();
+        let notifier = NotificationHistory::new(10);
+
+        let result =
+            Rescan::new(&block_chain, std::slice::from_ref(&account), 3).run(notifier.clone());
+        assert_eq!(1, result.history.len());
+        assert_eq!(Direction::Received, result.history[0].direction);
+        assert_eq!(2_000, result.history[0].amount);
+        assert_eq!(2, result.get_utxo_list(&account).len());
+        assert_eq!(
+            vec![(3, 3_000)],
+            result.balance_history.balance_history(&account, 0)
+        );
+        assert!(matches!(
+            notifier.notifications().as_slice(),
+            [Notification::ProgressRescan(1, 1)]
+        ));
+    }
+}
@@ -0,0 +1,281 @@
+use super::error_wallet::ErrorWallet;
+
+use crate::{
+    block_structure::{
+        hash::{hash256d, HashType},
+        script::{Script, ScriptBuf},
+        transaction::Transaction,
+        transaction_output::TransactionOutput,
+    },
+    messages::compact_size::CompactSize,
+    serialization::{
+        error_serialization::ErrorSerialization,
+        serializable_internal_order::SerializableInternalOrder,
+        serializable_little_endian::SerializableLittleEndian,
+    },
+};
+
+/// The signature commits to every input and every output
+pub const SIGHASH_ALL: u32 = 0x01;
+
+/// The signature commits to every input and to none of the outputs
+pub const SIGHASH_NONE: u32 = 0x02;
+
+/// The signature commits to every input and to the output with the same index as the signed input
+pub const SIGHASH_SINGLE: u32 = 0x03;
+
+/// The signature commits only to the signed input, letting others add more inputs
+pub const SIGHASH_ANYONECANPAY: u32 = 0x80;
+
+/// The digest signed when using SIGHASH_SINGLE on an input without an output of the same index
+const SIGHASH_SINGLE_WITHOUT_OUTPUT: HashType = {
+    let mut hash = [0; 32];
+    hash[0] = 1;
+    hash
+};
+
+const SIGHASH_BASE_MASK: u32 = 0x1f;
+
+/// Returns the digest signed by the input of the given index of a legacy transaction. The script code
+/// is the script of the output spent, which takes the place of the signature script of the input while
+/// the other inputs have theirs empty. The inputs and outputs left out by the sighash type are removed
+///
+/// ### Error
+///  * `ErrorWallet::CannotCreateNewTransaction`: It will appear when the input doesn't exist or the transaction cannot be serialized
+pub fn legacy_sighash(
+    transaction: &Transaction,
+    input_index: usize,
+    script_code: Script,
+    sighash_type: u32,
+) -> Result<HashType, ErrorWallet> {
+    if input_index >= transaction.tx_in.len() {
+        return Err(ErrorWallet::CannotCreateNewTransaction(format!(
+            "The transaction has no input {input_index} to sign"
+        )));
+    }
+
+    let base_type = sighash_type & SIGHASH_BASE_MASK;
+    if base_type == SIGHASH_SINGLE && input_index >= transaction.tx_out.len() {
+        return Ok(SIGHASH_SINGLE_WITHOUT_OUTPUT);
+    }
+
+    let mut transaction_copy = transaction.clone();
+    for (index, tx_in) in transaction_copy.tx_in.iter_mut().enumerate() {
+        if index == input_index {
+            tx_in.signature_script = script_code.as_bytes().to_vec();
+            continue;
+        }
+
+        tx_in.signature_script.clear();
+        if base_type == SIGHASH_NONE || base_type == SIGHASH_SINGLE {
+            tx_in.sequence = 0;
+        }
+    }
+
+    match base_type {
+        SIGHASH_NONE => transaction_copy.tx_out.clear(),
+        SIGHASH_SINGLE => {
+            transaction_copy.tx_out.truncate(input_index + 1);
+            for tx_out in transaction_copy.tx_out.iter_mut().take(input_index) {
+                *tx_out = TransactionOutput::new(-1, ScriptBuf::new());
+            }
+        }
+        _ => {}
+    }
+
+    if sighash_type & SIGHASH_ANYONECANPAY != 0 {
+        transaction_copy.tx_in = vec![transaction_copy.tx_in[input_index].clone()];
+    }
+
+    let mut preimage: Vec<u8> = Vec::new();
+    if let Err(error) = transaction_copy.io_serialize(&mut preimage) {
+        return Err(to_wallet_error(error));
+    }
+    if let Err(error) = sighash_type.le_serialize(&mut preimage) {
+        return Err(to_wallet_error(error));
+    }
+
+    hash256d(&preimage).map_err(to_wallet_error)
+}
+
+/// Returns the digest signed by the input of the given index spending a segwit version 0 output (BIP143).
+/// Unlike the legacy digest, it commits to the amount of the output spent and reuses the hashes of the
+/// inputs and outputs, so its cost doesn't grow with the square of the amount of inputs
+///
+/// ### Error
+///  * `ErrorWallet::CannotCreateNewTransaction`: It will appear when the input doesn't exist or the transaction cannot be serialized
+pub fn segwit_v0_sighash(
+    transaction: &Transaction,
+    input_index: usize,
+    script_code: Script,
+    amount: i64,
+    sighash_type: u32,
+) -> Result<HashType, ErrorWallet> {
+    if input_index >= transaction.tx_in.len() {
+        return Err(ErrorWallet::CannotCreateNewTransaction(format!(
+            "The transaction has no input {input_index} to sign"
+        )));
+    }
+
+    match segwit_v0_preimage(transaction, input_index, script_code, amount, sighash_type) {
+        Ok(preimage) => hash256d(&preimage).map_err(to_wallet_error),
+        Err(error) => Err(to_wallet_error(error)),
+    }
+}
+
+/// Returns the message hashed for the BIP143 digest: the version, the hashes of the outpoints, of the
+/// sequences and of the outputs, the input signed with its script code and amount, the lock time and
+/// the sighash type
+fn segwit_v0_preimage(
+    transaction: &Transaction,
+    input_index: usize,
+    script_code: Script,
+    amount: i64,
+    sighash_type: u32,
+) -> Result<Vec<u8>, ErrorSerialization> {
+    let base_type = sighash_type & SIGHASH_BASE_MASK;
+    let anyone_can_pay = sighash_type & SIGHASH_ANYONECANPAY != 0;
+
+    let hash_prevouts = match anyone_can_pay {
+        true => [0; 32],
+        false => {
+            let mut stream: Vec<u8> = Vec::new();
+            for tx_in in transaction.tx_in.iter() {
+                tx_in.previous_output.io_serialize(&mut stream)?;
+            }
+            hash256d(&stream)?
+        }
+    };
+
+    let hash_sequence =
+        match anyone_can_pay || base_type == SIGHASH_SINGLE || base_type == SIGHASH_NONE {
+            true => [0; 32],
+            false => {
+                let mut stream: Vec<u8> = Vec::new();
+                for tx_in in transaction.tx_in.iter() {
+                    tx_in.sequence.le_serialize(&mut stream)?;
+                }
+                hash256d(&stream)?
+            }
+        };
+
+    let hash_outputs = match base_type {
+        SIGHASH_NONE => [0; 32],
+        SIGHASH_SINGLE => match transaction.tx_out.get(input_index) {
+            Some(tx_out) => {
+                let mut stream: Vec<u8> = Vec::new();
+                tx_out.io_serialize(&mut stream)?;
+                hash256d(&stream)?
+            }
+            None => [0; 32],
+        },
+        _ => {
+            let mut stream: Vec<u8> = Vec::new();
+            for tx_out in transaction.tx_out.iter() {
+                tx_out.io_serialize(&mut stream)?;
+            }
+            hash256d(&stream)?
+        }
+    };
+
+    let tx_in = &transaction.tx_in[input_index];
+
+    let mut preimage: Vec<u8> = Vec::new();
+    transaction.version.le_serialize(&mut preimage)?;
+    hash_prevouts.io_serialize(&mut preimage)?;
+    hash_sequence.io_serialize(&mut preimage)?;
+    tx_in.previous_output.io_serialize(&mut preimage)?;
+    CompactSize::new(script_code.len() as u64).le_serialize(&mut preimage)?;
+    script_code.as_bytes().io_serialize(&mut preimage)?;
+    amount.le_serialize(&mut preimage)?;
+    tx_in.sequence.le_serialize(&mut preimage)?;
+    hash_outputs.io_serialize(&mut preimage)?;
+    transaction.lock_time.le_serialize(&mut preimage)?;
+    sighash_type.le_serialize(&mut preimage)?;
+
+    Ok(preimage)
+}
+
+fn to_wallet_error(error: ErrorSerialization) -> ErrorWallet {
+    ErrorWallet::CannotCreateNewTransaction(format!(
+        "Error computing the digest to sign: {:?}",
+        error
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::block_structure::hash;
+
+    #[test]
+    fn test01_legacy_digest_of_a_p2pkh_input() {
+        let transaction = Transaction::from_hex(
+            "0100000001813f79011acb80925dfe69b3def355fe914bd1d96a3f5f71bf8303c6a989c7d1000000006b483045022100ed81ff192e75a3fd2304004dcadb746fa5e24c5031ccfcf21320b0277457c98f02207a986d955c6e0cb35d446a89d3f56100f4d7f67801c31967743a9c8e10615bed01210349fc4e631e3624a545de3f89f5d8684c7b8138bd94bdd531d2e213bf016b278afeffffff02a135ef01000000001976a914bc3b654dca7e56b04dca18f2566cdaf02e8d9ada88ac99c39800000000001976a9141c4bc762dd5423e332166702cb75f40df79fea1288ac19430600",
+        )
+        .unwrap();
+        let script_code =
+            ScriptBuf::from_hex("76a914a802fc56c704ce87c42d7c92eb75e7896bdc41ae88ac").unwrap();
+
+        assert_eq!(
+            hash::from_hex::<32>(
+                "27e0c5994dec7824e56dec6b2fcb342eb7cdb0d0957c2fce9882f715e85d81a6"
+            )
+            .unwrap(),
+            legacy_sighash(&transaction, 0, script_code.as_script(), SIGHASH_ALL).unwrap()
+        );
+    }
+
+    #[test]
+    fn test02_segwit_digest_of_a_p2wpkh_input() {
+        let transaction = Transaction::from_hex(
+            "0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000",
+        )
+        .unwrap();
+        let script_code =
+            ScriptBuf::from_hex("76a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188ac").unwrap();
+
+        assert_eq!(
+            hash::from_hex::<32>(
+                "c37af31116d1b27caf68aae9e3ac82f1477929014d5b917657d0eb49478cb670"
+            )
+            .unwrap(),
+            segwit_v0_sighash(
+                &transaction,
+                1,
+                script_code.as_script(),
+                600_000_000,
+                SIGHASH_ALL
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test03_legacy_digest_ignores_the_signature_scripts_of_the_other_inputs() {
+        let mut transaction = Transaction::from_hex(
+            "0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000",
+        )
+        .unwrap();
+        let script_code = ScriptBuf::new_p2pkh(&[1; 20]);
+
+        let digest = legacy_sighash(&transaction, 1, script_code.as_script(), SIGHASH_ALL).unwrap();
+        transaction.tx_in[0].signature_script = vec![1, 2, 3];
+        assert_eq!(
+            digest,
+            legacy_sighash(&transaction, 1, script_code.as_script(), SIGHASH_ALL).unwrap()
+        );
+
+        transaction.tx_out.truncate(1);
+        assert_ne!(
+            digest,
+            legacy_sighash(&transaction, 1, script_code.as_script(), SIGHASH_ALL).unwrap()
+        );
+        assert_eq!(
+            SIGHASH_SINGLE_WITHOUT_OUTPUT,
+            legacy_sighash(&transaction, 1, script_code.as_script(), SIGHASH_SINGLE).unwrap()
+        );
+        assert!(legacy_sighash(&transaction, 2, script_code.as_script(), SIGHASH_ALL).is_err());
+    }
+}
@@ -0,0 +1,235 @@
+use super::{
+    account::Account,
+    error_wallet::ErrorWallet,
+    key_encryption::{CheckType, KeyEncryption, SaltType},
+    private_key::PrivateKey,
+    public_key::PublicKeyType,
+};
+
+use crate::serialization::{
+    deserializable_internal_order::DeserializableInternalOrder,
+    deserializable_little_endian::DeserializableLittleEndian,
+    error_serialization::ErrorSerialization,
+    serializable_internal_order::SerializableInternalOrder,
+    serializable_little_endian::SerializableLittleEndian,
+};
+
+use std::{
+    io::{Read, Write},
+    time::{Duration, Instant},
+};
+
+/// It's the password asked before signing a transaction. While a wallet has one, the private keys
+/// of its accounts are kept encrypted, and they can only be used for a while after the password is given
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpendingLock {
+    salt: SaltType,
+    check: CheckType,
+
+    /// The public keys of the accounts added while the wallet was locked, whose private keys are
+    /// encrypted the next time the password is given
+    unencrypted_keys: Vec<PublicKeyType>,
+
+    /// The key of the password given and until when it can be used
+    unlocked: Option<(KeyEncryption, Instant)>,
+}
+
+impl SpendingLock {
+    /// Creates the lock of the private keys encrypted with the given key, starting locked
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotEncryptKeys`: It will appear when the key cannot be hashed
+    pub fn new(encryption: &KeyEncryption) -> Result<Self, ErrorWallet> {
+        Ok(SpendingLock {
+            salt: encryption.get_salt(),
+            check: encryption.check()?,
+            unencrypted_keys: Vec::new(),
+            unlocked: None,
+        })
+    }
+
+    /// Returns the key of the password, checking it's the one used to encrypt the private keys
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::InvalidSpendingPassword`: It will appear when the password is not the correct one
+    ///  * `ErrorWallet::CannotEncryptKeys`: It will appear when the key cannot be derived from the password
+    pub fn verify(&self, password: &str) -> Result<KeyEncryption, ErrorWallet> {
+        let encryption = KeyEncryption::with_salt(password, self.salt)?;
+        if encryption.check()? != self.check {
+            return Err(ErrorWallet::InvalidSpendingPassword(
+                "The spending password is not correct".to_string(),
+            ));
+        }
+
+        Ok(encryption)
+    }
+
+    /// Keeps the key of the password for the given duration, so the private keys can be used
+    pub fn unlock(&mut self, encryption: KeyEncryption, duration: Duration) {
+        self.unlocked = Some((encryption, Instant::now() + duration));
+    }
+
+    /// Forgets the key of the password, so it has to be given again to use the private keys
+    pub fn lock(&mut self) {
+        self.unlocked = None;
+    }
+
+    /// Returns the key of the password if it was given and its time has not run out
+    pub fn get_key(&self) -> Option<&KeyEncryption> {
+        match &self.unlocked {
+            Some((encryption, until)) if Instant::now() < *until => Some(encryption),
+            _ => None,
+        }
+    }
+
+    /// Returns if the private key of the account is encrypted
+    pub fn is_encrypted(&self, account: &Account) -> bool {
+        !self
+            .unencrypted_keys
+            .contains(&account.public_key.as_bytes())
+    }
+
+    /// Marks the private key of the account as not encrypted, until the password is given again
+    pub fn add_unencrypted(&mut self, account: &Account) {
+        if self.is_encrypted(account) {
+            self.unencrypted_keys.push(account.public_key.as_bytes());
+        }
+    }
+
+    /// Marks the private key of the account as encrypted, or forgets it when the account is removed
+    pub fn remove_unencrypted(&mut self, account: &Account) {
+        let public_key = account.public_key.as_bytes();
+        self.unencrypted_keys.retain(|key| *key != public_key);
+    }
+
+    /// Returns the account with its private key decrypted, to sign with it
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::WalletLocked`: It will appear when the password was not given or its time ran out
+    ///  * `ErrorWallet::CannotEncryptKeys`: It will appear when the private key cannot be decrypted
+    pub fn decrypt(&self, account: &Account) -> Result<Account, ErrorWallet> {
+        if !self.is_encrypted(account) {
+            return Ok(account.clone());
+        }
+
+        match self.get_key() {
+            Some(encryption) => apply(encryption, account),
+            None => Err(ErrorWallet::WalletLocked(
+                "The spending password is needed to use the private keys".to_string(),
+            )),
+        }
+    }
+}
+
+/// Returns the account with its private key encrypted or decrypted with the key, being the same operation
+///
+/// ### Error
+///  * `ErrorWallet::CannotEncryptKeys`: It will appear when the private key cannot be encrypted or decrypted
+pub fn apply(encryption: &KeyEncryption, account: &Account) -> Result<Account, ErrorWallet> {
+    let private_key_bytes = encryption.apply_with_public_key(
+        &account.public_key.as_bytes(),
+        &account.private_key.as_bytes(),
+    )?;
+
+    let private_key = match PrivateKey::new(&private_key_bytes) {
+        Ok(private_key) => private_key,
+        Err(error) => {
+            return Err(ErrorWallet::CannotEncryptKeys(format!(
+                "The private key of the account {} cannot be encrypted, error : {:?}",
+                account.account_name, error
+            )))
+        }
+    };
+
+    let mut account = account.clone();
+    account.private_key = private_key;
+    Ok(account)
+}
+
+impl SerializableInternalOrder for SpendingLock {
+    fn io_serialize(&self, stream: &mut dyn Write) -> Result<(), ErrorSerialization> {
+        self.salt.io_serialize(stream)?;
+        self.check.io_serialize(stream)?;
+
+        (self.unencrypted_keys.len() as u64).le_serialize(stream)?;
+        for public_key in self.unencrypted_keys.iter() {
+            public_key.io_serialize(stream)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl DeserializableInternalOrder for SpendingLock {
+    fn io_deserialize(stream: &mut dyn Read) -> Result<Self, ErrorSerialization> {
+        let salt = SaltType::io_deserialize(stream)?;
+        let check = CheckType::io_deserialize(stream)?;
+
+        let unencrypted_keys_len = u64::le_deserialize(stream)?;
+        let mut unencrypted_keys: Vec<PublicKeyType> = Vec::new();
+        for _ in 0..unencrypted_keys_len {
+            unencrypted_keys.push(PublicKeyType::io_deserialize(stream)?);
+        }
+
+        Ok(SpendingLock {
+            salt,
+            check,
+            unencrypted_keys,
+            unlocked: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::wallet_structure::key_encryption::SALT_SIZE;
+
+    #[test]
+    fn test01_password_is_checked_before_unlocking() {
+        let encryption = KeyEncryption::with_salt("password", [0x01; SALT_SIZE]).unwrap();
+        let spending_lock = SpendingLock::new(&encryption).unwrap();
+
+        assert_eq!(Ok(encryption), spending_lock.verify("password"));
+        assert!(matches!(
+            spending_lock.verify("other"),
+            Err(ErrorWallet::InvalidSpendingPassword(_))
+        ));
+    }
+
+    #[test]
+    fn test02_key_can_only_be_used_while_unlocked() {
+        let encryption = KeyEncryption::with_salt("password", [0x01; SALT_SIZE]).unwrap();
+        let mut spending_lock = SpendingLock::new(&encryption).unwrap();
+        assert_eq!(None, spending_lock.get_key());
+
+        spending_lock.unlock(encryption.clone(), Duration::from_secs(60));
+        assert_eq!(Some(&encryption), spending_lock.get_key());
+
+        spending_lock.lock();
+        assert_eq!(None, spending_lock.get_key());
+
+        spending_lock.unlock(encryption, Duration::ZERO);
+        assert_eq!(None, spending_lock.get_key());
+    }
+
+    #[test]
+    fn test03_lock_is_serialized_without_its_key() {
+        let encryption = KeyEncryption::with_salt("password", [0x01; SALT_SIZE]).unwrap();
+        let mut spending_lock = SpendingLock::new(&encryption).unwrap();
+        spending_lock.unencrypted_keys.push([0x02; 33]);
+
+        let mut stream: Vec<u8> = Vec::new();
+        spending_lock.io_serialize(&mut stream).unwrap();
+        spending_lock.unlock(encryption, Duration::from_secs(60));
+
+        let deserialized = SpendingLock::io_deserialize(&mut stream.as_slice()).unwrap();
+        assert_eq!(None, deserialized.get_key());
+        assert_eq!(
+            spending_lock.unencrypted_keys,
+            deserialized.unencrypted_keys
+        );
+        assert!(deserialized.verify("password").is_ok());
+    }
+}
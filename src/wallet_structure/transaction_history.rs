@@ -0,0 +1,286 @@
+use super::account::Account;
+
+use crate::block_structure::{
+    block::Block, block_chain::BlockChain, hash::HashType, outpoint::Outpoint,
+    transaction::Transaction, transaction_output::TransactionOutput, utxo_set::UTXOSet,
+};
+
+use std::{collections::HashMap, fmt::Display};
+
+/// It represents if the balance of an account increased or decreased with a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Received,
+    Sent,
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Direction::Received => write!(f, "Received"),
+            Direction::Sent => write!(f, "Sent"),
+        }
+    }
+}
+
+/// It's a transaction of the block chain that involves an account of the wallet
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    /// The name of the account involved
+    pub account_name: String,
+
+    /// The time of the block where the transaction was included
+    pub timestamp: u32,
+
+    pub transaction_id: HashType,
+
+    pub direction: Direction,
+
+    /// The amount of satoshis that the balance of the account changed, without its sign
+    pub amount: i64,
+
+    /// The amount of blocks that include the transaction or were built on top of it
+    pub confirmations: u64,
+
+    /// The note given by the user to the transaction, kept in the wallet
+    pub label: Option<String>,
+}
+
+/// Returns the transactions that change the balance of the given accounts by walking the blocks
+/// of the block chain, from the newest one
+pub fn get_transaction_history(
+    block_chain: &BlockChain,
+    accounts: &[Account],
+) -> Vec<HistoryEntry> {
+    let mut history: Vec<HistoryEntry> = Vec::new();
+    let mut utxo_set = UTXOSet::new(Vec::new());
+
+    let blocks = block_chain.get_all_blocks_with_height();
+    let tip_height = match blocks.iter().map(|(height, _)| *height).max() {
+        Some(tip_height) => tip_height,
+        None => return history,
+    };
+
+    for (height, block) in blocks {
+        let block_outputs = get_block_outputs(&block);
+
+        for transaction in block.transactions.iter() {
+            let transaction_id = match transaction.get_tx_id() {
+                Ok(transaction_id) => transaction_id,
+                Err(_) => continue,
+            };
+
+            for account in accounts {
+                let net_amount = get_net_amount(account, transaction, &utxo_set, &block_outputs);
+                if net_amount == 0 {
+                    continue;
+                }
+
+                history.push(HistoryEntry {
+                    account_name: account.account_name.clone(),
+                    timestamp: block.header.time,
+                    transaction_id,
+                    direction: match net_amount > 0 {
+                        true => Direction::Received,
+                        false => Direction::Sent,
+                    },
+                    amount: net_amount.abs(),
+                    confirmations: tip_height - height + 1,
+                    label: None,
+                });
+            }
+        }
+
+        utxo_set.update_utxo_with_block(&block);
+    }
+
+    history.reverse();
+    history
+}
+
+/// Returns the outputs created by the transactions of the block, so they can be found when they
+/// are spent in the same block
+fn get_block_outputs(block: &Block) -> HashMap<Outpoint, TransactionOutput> {
+    let mut block_outputs: HashMap<Outpoint, TransactionOutput> = HashMap::new();
+    for transaction in block.transactions.iter() {
+        let transaction_id = match transaction.get_tx_id() {
+            Ok(transaction_id) => transaction_id,
+            Err(_) => continue,
+        };
+
+        for (index, output) in transaction.tx_out.iter().enumerate() {
+            block_outputs.insert(Outpoint::new(transaction_id, index as u32), output.clone());
+        }
+    }
+    block_outputs
+}
+
+/// Returns the satoshis that the account receives in the transaction minus the ones it spends
+fn get_net_amount(
+    account: &Account,
+    transaction: &Transaction,
+    utxo_set: &UTXOSet,
+    block_outputs: &HashMap<Outpoint, TransactionOutput>,
+) -> i64 {
+    let received: i64 = transaction
+        .tx_out
+        .iter()
+        .filter(|output| account.verify_transaction_output_ownership(output))
+        .map(|output| output.value)
+        .sum();
+
+    let spent: i64 = transaction
+        .tx_in
+        .iter()
+        .filter_map(|input| match utxo_set.get(&input.previous_output) {
+            Some(output) => Some(output),
+            None => block_outputs.get(&input.previous_output),
+        })
+        .filter(|output| account.verify_transaction_output_ownership(output))
+        .map(|output| output.value)
+        .sum();
+
+    received - spent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        block_structure::{
+            block_header::BlockHeader, block_version, compact256::Compact256, script::ScriptBuf,
+            transaction_input::TransactionInput,
+        },
+        connections::network::Network,
+        messages::compact_size::CompactSize,
+    };
+
+    fn create_account(name: &str) -> Account {
+        let priv_key_bytes: [u8; 32] = [
+            0x0a, 0x52, 0x65, 0x08, 0x2e, 0x24, 0x11, 0x5f, 0x77, 0x54, 0x0a, 0xb3, 0xb8, 0xc2,
+            0xb9, 0x20, 0x60, 0xaa, 0x30, 0xd6, 0xd2, 0xb8, 0x1a, 0x08, 0x5d, 0x71, 0xab, 0x37,
+            0xed, 0xa7, 0x68, 0x91,
+        ];
+        let pubkey_bytes: [u8; 33] = [
+            0x03, 0xBC, 0x6D, 0x45, 0xD2, 0x10, 0x1E, 0x91, 0x28, 0xDE, 0x14, 0xB5, 0xB6, 0x68,
+            0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
+            0x35, 0x14, 0x92, 0x4A, 0x22,
+        ];
+        Account::new(name, &priv_key_bytes, &pubkey_bytes, Network::Testnet).unwrap()
+    }
+
+    fn create_block(previous_header: HashType, time: u32, transactions: Vec<Transaction>) -> Block {
+        let mut block = Block::new(BlockHeader::new(
+            block_version::BlockVersion::version(1),
+            previous_header,
+            [0; 32],
+            time,
+            Compact256::from(u32::MAX),
+            0,
+            CompactSize::new(transactions.len() as u64),
+        ));
+        for transaction in transactions {
+            block.append_transaction(transaction).unwrap();
+        }
+        block
+    }
+
+    fn create_transaction(inputs: Vec<Outpoint>, outputs: Vec<(i64, ScriptBuf)>) -> Transaction {
+        Transaction {
+            version: 1,
+            tx_in: inputs
+                .into_iter()
+                .map(|outpoint| TransactionInput::new(outpoint, vec![], 0))
+                .collect(),
+            tx_out: outputs
+                .into_iter()
+                .map(|(value, pk_script)| TransactionOutput { value, pk_script })
+                .collect(),
+            lock_time: 0,
+        }
+    }
+
+    #[test]
+    fn test01_correct_history_from_blockchain() {
+        let account = create_account("test");
+        let pk_script = account.address.generate_script_pubkey_p2pkh();
+
+        let transaction_1 = create_transaction(vec![], vec![(30, pk_script.clone())]);
+        let transaction_2 = create_transaction(
+            vec![Outpoint::new(transaction_1.get_tx_id().unwrap(), 0)],
+            vec![(20, pk_script), (9, ScriptBuf::from(vec![0x6a]))],
+        );
+
+        let block_1 = create_block([0; 32], 100, vec![transaction_1.clone()]);
+        let block_2 = create_block(
+            block_1.header.get_hash256d().unwrap(),
+            200,
+            vec![transaction_2.clone()],
+        );
+
+        let mut block_chain = BlockChain::new(block_1).unwrap();
+        block_chain.append_block(block_2).unwrap();
+
+        let history = get_transaction_history(&block_chain, &[account]);
+
+        assert_eq!(
+            history,
+            vec![
+                HistoryEntry {
+                    account_name: "test".to_string(),
+                    timestamp: 200,
+                    transaction_id: transaction_2.get_tx_id().unwrap(),
+                    direction: Direction::Sent,
+                    amount: 10,
+                    confirmations: 1,
+                    label: None,
+                },
+                HistoryEntry {
+                    account_name: "test".to_string(),
+                    timestamp: 100,
+                    transaction_id: transaction_1.get_tx_id().unwrap(),
+                    direction: Direction::Received,
+                    amount: 30,
+                    confirmations: 2,
+                    label: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test02_outputs_spent_in_the_same_block_are_found() {
+        let account = create_account("test");
+        let pk_script = account.address.generate_script_pubkey_p2pkh();
+
+        let transaction_1 = create_transaction(vec![], vec![(30, pk_script)]);
+        let transaction_2 = create_transaction(
+            vec![Outpoint::new(transaction_1.get_tx_id().unwrap(), 0)],
+            vec![(25, ScriptBuf::from(vec![0x6a]))],
+        );
+
+        let block = create_block([0; 32], 100, vec![transaction_1, transaction_2]);
+        let block_chain = BlockChain::new(block).unwrap();
+
+        let history = get_transaction_history(&block_chain, &[account]);
+
+        assert_eq!(2, history.len());
+        assert_eq!(
+            (Direction::Sent, 30),
+            (history[0].direction, history[0].amount)
+        );
+        assert_eq!(
+            (Direction::Received, 30),
+            (history[1].direction, history[1].amount)
+        );
+    }
+
+    #[test]
+    fn test03_transactions_of_other_addresses_are_not_in_the_history() {
+        let transaction = create_transaction(vec![], vec![(30, ScriptBuf::from(vec![0x6a]))]);
+        let block_chain = BlockChain::new(create_block([0; 32], 100, vec![transaction])).unwrap();
+
+        assert!(get_transaction_history(&block_chain, &[create_account("test")]).is_empty());
+    }
+}
@@ -0,0 +1,76 @@
+use crate::block_structure::transaction_input::{
+    SEQUENCE_FINAL, SEQUENCE_LOCK_TIME, SEQUENCE_REPLACEABLE,
+};
+
+/// It's how a transaction created by the wallet can be confirmed and replaced
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransactionOptions {
+    /// The height, or the Unix time, before which the transaction cannot be in a block. With 0 it
+    /// can be in a block at any time
+    pub lock_time: u32,
+
+    /// If the transaction signals that it can be replaced by one paying a higher fee (BIP125)
+    pub replaceable: bool,
+}
+
+impl TransactionOptions {
+    pub fn new(replaceable: bool) -> Self {
+        TransactionOptions {
+            lock_time: 0,
+            replaceable,
+        }
+    }
+
+    pub fn with_lock_time(self, lock_time: u32) -> Self {
+        TransactionOptions { lock_time, ..self }
+    }
+
+    /// Returns the sequence of the inputs of the transaction. A replaceable transaction signals it in
+    /// every input, otherwise the inputs are final unless there is a lock time to enforce
+    pub fn get_sequence(&self) -> u32 {
+        match (self.replaceable, self.lock_time) {
+            (true, _) => SEQUENCE_REPLACEABLE,
+            (false, 0) => SEQUENCE_FINAL,
+            (false, _) => SEQUENCE_LOCK_TIME,
+        }
+    }
+}
+
+impl Default for TransactionOptions {
+    fn default() -> Self {
+        TransactionOptions::new(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test01_transaction_is_replaceable_by_default() {
+        assert_eq!(
+            SEQUENCE_REPLACEABLE,
+            TransactionOptions::default().get_sequence()
+        );
+        assert_eq!(
+            SEQUENCE_REPLACEABLE,
+            TransactionOptions::default()
+                .with_lock_time(800_000)
+                .get_sequence()
+        );
+    }
+
+    #[test]
+    fn test02_lock_time_is_enforced_in_transactions_not_replaceable() {
+        assert_eq!(
+            SEQUENCE_FINAL,
+            TransactionOptions::new(false).get_sequence()
+        );
+        assert_eq!(
+            SEQUENCE_LOCK_TIME,
+            TransactionOptions::new(false)
+                .with_lock_time(800_000)
+                .get_sequence()
+        );
+    }
+}
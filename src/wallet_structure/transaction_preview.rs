@@ -0,0 +1,297 @@
+use super::{account::Account, address::Address, error_wallet::ErrorWallet};
+
+use crate::{
+    block_structure::{transaction::Transaction, utxo_set::UTXOSet},
+    serialization::serializable_internal_order::SerializableInternalOrder,
+};
+
+/// It's the summary of a transaction created by an account, to be confirmed before it's broadcasted.
+/// Every amount is in satoshis
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionPreview {
+    /// The address that receives the amount
+    pub recipient: Address,
+
+    pub amount: i64,
+
+    /// The difference between the value of the inputs and the value of the outputs
+    pub fee: i64,
+
+    /// The amount of bytes of the serialized transaction
+    pub size: usize,
+
+    /// The value of the outputs that go back to the account
+    pub change: i64,
+
+    /// The balance of the account once the transaction is confirmed
+    pub resulting_balance: i64,
+}
+
+impl TransactionPreview {
+    /// Creates the summary of the transaction of the account to the recipient, with the values of
+    /// the outputs it spends from the utxo set
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotCreateNewTransaction`: It will appear when the transaction spends an output that is not in the utxo set or it cannot be serialized
+    pub fn new(
+        transaction: &Transaction,
+        account: &Account,
+        recipient: &Address,
+        utxo_set: &UTXOSet,
+    ) -> Result<Self, ErrorWallet> {
+        let total_input = match utxo_set.resolve_inputs(transaction) {
+            Ok(resolved_inputs) => resolved_inputs.total_value,
+            Err(_) => {
+                return Err(ErrorWallet::CannotCreateNewTransaction(
+                    "The transaction spends an output that is not available".to_string(),
+                ))
+            }
+        };
+
+        let mut amount: i64 = 0;
+        let mut change: i64 = 0;
+        for output in transaction.tx_out.iter() {
+            match account.verify_transaction_output_ownership(output) {
+                true => change += output.value,
+                false => amount += output.value,
+            }
+        }
+
+        let mut serialized_transaction: Vec<u8> = Vec::new();
+        if transaction
+            .io_serialize(&mut serialized_transaction)
+            .is_err()
+        {
+            return Err(ErrorWallet::CannotCreateNewTransaction(
+                "The transaction cannot be serialized".to_string(),
+            ));
+        }
+
+        let balance = utxo_set.get_balance_in_satoshis(&account.address);
+
+        Ok(TransactionPreview {
+            recipient: recipient.clone(),
+            amount,
+            fee: total_input - amount - change,
+            size: serialized_transaction.len(),
+            change,
+            resulting_balance: balance - total_input + change,
+        })
+    }
+
+    /// Returns the amount of satoshis paid for each byte of the transaction
+    pub fn fee_rate(&self) -> f64 {
+        match self.size {
+            0 => 0.0,
+            size => self.fee as f64 / size as f64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        block_structure::{
+            block::Block,
+            block_header::BlockHeader,
+            block_version,
+            compact256::Compact256,
+            transaction_input::{SEQUENCE_LOCK_TIME, SEQUENCE_REPLACEABLE},
+            transaction_output::TransactionOutput,
+        },
+        connections::network::Network,
+        messages::compact_size::CompactSize,
+        wallet_structure::{amount::Amount, transaction_options::TransactionOptions},
+    };
+
+    fn create_account() -> Account {
+        let priv_key_bytes: [u8; 32] = [
+            0x0a, 0x52, 0x65, 0x08, 0x2e, 0x24, 0x11, 0x5f, 0x77, 0x54, 0x0a, 0xb3, 0xb8, 0xc2,
+            0xb9, 0x20, 0x60, 0xaa, 0x30, 0xd6, 0xd2, 0xb8, 0x1a, 0x08, 0x5d, 0x71, 0xab, 0x37,
+            0xed, 0xa7, 0x68, 0x91,
+        ];
+        let pubkey_bytes: [u8; 33] = [
+            0x03, 0xBC, 0x6D, 0x45, 0xD2, 0x10, 0x1E, 0x91, 0x28, 0xDE, 0x14, 0xB5, 0xB6, 0x68,
+            0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
+            0x35, 0x14, 0x92, 0x4A, 0x22,
+        ];
+        Account::new("test", &priv_key_bytes, &pubkey_bytes, Network::Testnet).unwrap()
+    }
+
+    fn create_utxo_set(account: &Account, values: &[i64]) -> UTXOSet {
+        let mut block = Block::new(BlockHeader::new(
+            block_version::BlockVersion::version(1),
+            [0; 32],
+            [0; 32],
+            0,
+            Compact256::from(u32::MAX),
+            0,
+            CompactSize::new(1),
+        ));
+        block
+            .append_transaction(Transaction {
+                version: 1,
+                tx_in: vec![],
+                tx_out: values
+                    .iter()
+                    .map(|value| {
+                        TransactionOutput::new(
+                            *value,
+                            account.address.generate_script_pubkey_p2pkh(),
+                        )
+                    })
+                    .collect(),
+                lock_time: 0,
+            })
+            .unwrap();
+        UTXOSet::new(vec![block])
+    }
+
+    #[test]
+    fn test01_preview_has_the_fee_change_and_resulting_balance() {
+        let account = create_account();
+        let recipient = Address::new("mnEvYsxexfDEkCx2YLEfzhjrwKKcyAhMqV").unwrap();
+        let utxo_set = create_utxo_set(&account, &[50_000, 20_000]);
+
+        let transaction = account
+            .create_transaction(
+                recipient.clone(),
+                Amount::from_sat(30_000),
+                Amount::from_sat(1_000),
+                TransactionOptions::default(),
+                &utxo_set,
+            )
+            .unwrap();
+        let preview =
+            TransactionPreview::new(&transaction, &account, &recipient, &utxo_set).unwrap();
+
+        assert_eq!(recipient, preview.recipient);
+        assert_eq!(30_000, preview.amount);
+        assert_eq!(1_000, preview.fee);
+        assert_eq!(19_000, preview.change);
+        assert_eq!(39_000, preview.resulting_balance);
+        assert!(preview.size > 0);
+        assert_eq!(1_000.0 / preview.size as f64, preview.fee_rate());
+    }
+
+    #[test]
+    fn test02_preview_of_transaction_spending_unknown_outputs_fails() {
+        let account = create_account();
+        let recipient = Address::new("mnEvYsxexfDEkCx2YLEfzhjrwKKcyAhMqV").unwrap();
+        let utxo_set = create_utxo_set(&account, &[50_000]);
+        let transaction = account
+            .create_transaction(
+                recipient.clone(),
+                Amount::from_sat(30_000),
+                Amount::from_sat(1_000),
+                TransactionOptions::default(),
+                &utxo_set,
+            )
+            .unwrap();
+
+        assert!(matches!(
+            TransactionPreview::new(&transaction, &account, &recipient, &UTXOSet::new(vec![])),
+            Err(ErrorWallet::CannotCreateNewTransaction(_))
+        ));
+    }
+
+    #[test]
+    fn test03_transaction_of_a_dust_amount_is_not_created() {
+        let account = create_account();
+        let recipient = Address::new("mnEvYsxexfDEkCx2YLEfzhjrwKKcyAhMqV").unwrap();
+        let utxo_set = create_utxo_set(&account, &[50_000]);
+
+        assert!(matches!(
+            account.create_transaction(
+                recipient,
+                Amount::from_sat(545),
+                Amount::from_sat(1_000),
+                TransactionOptions::default(),
+                &utxo_set
+            ),
+            Err(ErrorWallet::DustOutput(_))
+        ));
+    }
+
+    #[test]
+    fn test04_dust_change_is_left_to_the_fee() {
+        let account = create_account();
+        let recipient = Address::new("mnEvYsxexfDEkCx2YLEfzhjrwKKcyAhMqV").unwrap();
+        let utxo_set = create_utxo_set(&account, &[31_500]);
+
+        let transaction = account
+            .create_transaction(
+                recipient.clone(),
+                Amount::from_sat(30_000),
+                Amount::from_sat(1_000),
+                TransactionOptions::default(),
+                &utxo_set,
+            )
+            .unwrap();
+        let preview =
+            TransactionPreview::new(&transaction, &account, &recipient, &utxo_set).unwrap();
+
+        assert_eq!(1, transaction.tx_out.len());
+        assert_eq!(0, preview.change);
+        assert_eq!(1_500, preview.fee);
+    }
+
+    #[test]
+    fn test05_transaction_has_the_lock_time_and_sequence_of_the_options() {
+        let account = create_account();
+        let recipient = Address::new("mnEvYsxexfDEkCx2YLEfzhjrwKKcyAhMqV").unwrap();
+        let utxo_set = create_utxo_set(&account, &[50_000, 20_000]);
+
+        let replaceable = account
+            .create_transaction(
+                recipient.clone(),
+                Amount::from_sat(60_000),
+                Amount::from_sat(1_000),
+                TransactionOptions::default(),
+                &utxo_set,
+            )
+            .unwrap();
+        assert_eq!(0, replaceable.lock_time);
+        assert!(replaceable.signals_replacement());
+        assert!(replaceable
+            .tx_in
+            .iter()
+            .all(|tx_in| tx_in.sequence == SEQUENCE_REPLACEABLE));
+
+        let locked = account
+            .create_transaction(
+                recipient,
+                Amount::from_sat(30_000),
+                Amount::from_sat(1_000),
+                TransactionOptions::new(false).with_lock_time(2_500_000),
+                &utxo_set,
+            )
+            .unwrap();
+        assert_eq!(2_500_000, locked.lock_time);
+        assert!(!locked.signals_replacement());
+        assert!(locked
+            .tx_in
+            .iter()
+            .all(|tx_in| tx_in.sequence == SEQUENCE_LOCK_TIME));
+    }
+
+    #[test]
+    fn test06_transaction_with_a_negative_fee_is_not_created() {
+        let account = create_account();
+        let recipient = Address::new("mnEvYsxexfDEkCx2YLEfzhjrwKKcyAhMqV").unwrap();
+        let utxo_set = create_utxo_set(&account, &[50_000]);
+
+        assert!(matches!(
+            account.create_transaction(
+                recipient,
+                Amount::from_sat(30_000),
+                Amount::from_sat(-1_000),
+                TransactionOptions::default(),
+                &utxo_set
+            ),
+            Err(ErrorWallet::InvalidAmount(_))
+        ));
+    }
+}
@@ -1,23 +1,65 @@
-use super::{account::Account, error_wallet::ErrorWallet};
+use super::{
+    account::Account,
+    account_metadata::AccountMetadata,
+    error_wallet::ErrorWallet,
+    json_value::JsonValue,
+    key_encryption::{KeyEncryption, SaltType},
+    private_key::{PrivateKey, PrivateKeyType},
+    public_key::PublicKey,
+    spending_lock::{self, SpendingLock},
+};
 
 use crate::{
+    block_structure::hash::{self, HashType},
     configurations::try_default::TryDefault,
+    connections::network::Network,
     serialization::{
+        deserializable_fix_size::DeserializableFixSize,
         deserializable_internal_order::DeserializableInternalOrder,
         deserializable_little_endian::DeserializableLittleEndian,
         error_serialization::ErrorSerialization,
         serializable_internal_order::SerializableInternalOrder,
         serializable_little_endian::SerializableLittleEndian,
+        versioned_format::{from_unversioned, Migration, VersionedFormat},
     },
 };
 
-use std::io::{Read, Write};
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+    time::Duration,
+};
+
+/// It marks the start of the metadata of the accounts. The wallets saved before the metadata
+/// existed end after the accounts, so they can still be read
+const METADATA_MARKER: u8 = 0x01;
+
+/// It marks the start of the labels of the transactions, after the metadata of the accounts. It's
+/// only written when a transaction has a label, so the wallets without them keep the same format
+const LABELS_MARKER: u8 = 0x02;
+
+/// It marks the start of the spending password of the wallet, after the metadata of the accounts. It's
+/// only written when the wallet has one, so the wallets without it keep the same format
+const SPENDING_LOCK_MARKER: u8 = 0x03;
+
+/// The version of the JSON format of the backups of a wallet
+const BACKUP_VERSION: f64 = 1.0;
 
 /// It's the internal representation of a wallet
 #[derive(Debug, PartialEq)]
 pub struct Wallet {
     selected_account: Option<Account>,
     accounts: Vec<Account>,
+
+    /// The notes given by the user to the transactions of the accounts, by their id
+    transaction_labels: HashMap<HashType, String>,
+
+    /// The password asked to use the private keys, which are kept encrypted while it's set
+    spending_lock: Option<SpendingLock>,
+
+    /// The network of the node, in which the addresses of the new accounts are. It's not saved
+    /// with the wallet, it's given by the configuration when the wallet is loaded
+    network: Network,
 }
 
 impl Wallet {
@@ -25,11 +67,42 @@ impl Wallet {
         Wallet {
             selected_account: accounts.first().cloned(),
             accounts,
+            transaction_labels: HashMap::new(),
+            spending_lock: None,
+            network: Network::default(),
         }
     }
 
-    /// Appends an account to the wallet
+    /// Sets the network of the node, in which the addresses of the new accounts are
+    pub fn with_network(mut self, network: Network) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Returns the network in which the addresses of the new accounts are
+    pub fn get_network(&self) -> Network {
+        self.network
+    }
+
+    /// Appends an account to the wallet. With a spending password, its private key is encrypted if
+    /// the wallet is unlocked, otherwise it's encrypted the next time the wallet is unlocked
     pub fn add_account(&mut self, account: Account) {
+        let account = match &mut self.spending_lock {
+            Some(spending_lock) => {
+                let encrypted_account = spending_lock
+                    .get_key()
+                    .and_then(|encryption| spending_lock::apply(encryption, &account).ok());
+                match encrypted_account {
+                    Some(encrypted_account) => encrypted_account,
+                    None => {
+                        spending_lock.add_unencrypted(&account);
+                        account
+                    }
+                }
+            }
+            None => account,
+        };
+
         self.accounts.push(account.clone());
 
         if self.selected_account.is_none() {
@@ -39,6 +112,12 @@ impl Wallet {
 
     /// Removes an account from the wallet if it exists
     pub fn remove_account(&mut self, account: Account) {
+        if let Some(spending_lock) = &mut self.spending_lock {
+            if let Some(removed_account) = self.accounts.iter().find(|x| *x == &account) {
+                spending_lock.remove_unencrypted(removed_account);
+            }
+        }
+
         self.accounts.retain(|x| x != &account);
         if self.selected_account.is_none() {
             self.selected_account = self.accounts.first().cloned();
@@ -68,6 +147,507 @@ impl Wallet {
     pub fn get_account_with_name(&self, name: &str) -> Option<&Account> {
         self.accounts.iter().find(|x| x.account_name == name)
     }
+
+    /// Changes the name of an account, keeping it selected if it was
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::AccountNotFound`: It will appear when there is no account with the given name
+    ///  * `ErrorWallet::InvalidAccountName`: It will appear when the new name is empty or another account already has it
+    pub fn rename_account(&mut self, name: &str, new_name: &str) -> Result<(), ErrorWallet> {
+        let new_name = new_name.trim();
+        if new_name.is_empty() {
+            return Err(ErrorWallet::InvalidAccountName(
+                "The name of an account cannot be empty".to_string(),
+            ));
+        }
+        if new_name != name && self.get_account_with_name(new_name).is_some() {
+            return Err(ErrorWallet::InvalidAccountName(format!(
+                "There is already an account named {new_name}"
+            )));
+        }
+
+        let account = self.get_mut_account_with_name(name)?;
+        account.account_name = new_name.to_string();
+        let account = account.clone();
+
+        if let Some(selected_account) = &self.selected_account {
+            if selected_account.account_name == name {
+                self.selected_account = Some(account);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Changes the label and description of an account
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::AccountNotFound`: It will appear when there is no account with the given name
+    pub fn set_account_description(
+        &mut self,
+        name: &str,
+        label: Option<String>,
+        description: Option<String>,
+    ) -> Result<(), ErrorWallet> {
+        let account = self.get_mut_account_with_name(name)?;
+        account.metadata.label = label;
+        account.metadata.description = description;
+        let account = account.clone();
+
+        if let Some(selected_account) = &self.selected_account {
+            if selected_account.account_name == name {
+                self.selected_account = Some(account);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns if the wallet has a spending password
+    pub fn has_spending_password(&self) -> bool {
+        self.spending_lock.is_some()
+    }
+
+    /// Returns if the spending password is needed to use the private keys, because it was not given
+    /// or its time ran out
+    pub fn is_locked(&self) -> bool {
+        match &self.spending_lock {
+            Some(spending_lock) => spending_lock.get_key().is_none(),
+            None => false,
+        }
+    }
+
+    /// Changes the spending password of the wallet, decrypting the private keys with the current one
+    /// and encrypting them with the new one. Without a new password the private keys are kept decrypted.
+    /// The wallet starts locked with the new password
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::InvalidSpendingPassword`: It will appear when the current password is missing or not the correct one
+    ///  * `ErrorWallet::CannotEncryptKeys`: It will appear when the private keys cannot be encrypted or decrypted
+    pub fn set_spending_password(
+        &mut self,
+        password: Option<&str>,
+        new_password: Option<&str>,
+    ) -> Result<(), ErrorWallet> {
+        let mut accounts = self.accounts.clone();
+        if let Some(spending_lock) = &self.spending_lock {
+            let encryption = match password {
+                Some(password) => spending_lock.verify(password)?,
+                None => {
+                    return Err(ErrorWallet::InvalidSpendingPassword(
+                        "The current spending password is needed".to_string(),
+                    ))
+                }
+            };
+
+            for account in accounts.iter_mut() {
+                if spending_lock.is_encrypted(account) {
+                    *account = spending_lock::apply(&encryption, account)?;
+                }
+            }
+        }
+
+        let spending_lock = match new_password {
+            Some(new_password) => {
+                let encryption = KeyEncryption::new(new_password)?;
+                for account in accounts.iter_mut() {
+                    *account = spending_lock::apply(&encryption, account)?;
+                }
+                Some(SpendingLock::new(&encryption)?)
+            }
+            None => None,
+        };
+
+        self.accounts = accounts;
+        self.spending_lock = spending_lock;
+        self.refresh_selected_account();
+        Ok(())
+    }
+
+    /// Lets the private keys be used for the given duration, encrypting the ones of the accounts added
+    /// while it was locked. A wallet without a spending password is always unlocked
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::InvalidSpendingPassword`: It will appear when the password is not the correct one
+    ///  * `ErrorWallet::CannotEncryptKeys`: It will appear when the private keys cannot be encrypted
+    pub fn unlock(&mut self, password: &str, duration: Duration) -> Result<(), ErrorWallet> {
+        let spending_lock = match &mut self.spending_lock {
+            Some(spending_lock) => spending_lock,
+            None => return Ok(()),
+        };
+
+        let encryption = spending_lock.verify(password)?;
+        for account in self.accounts.iter_mut() {
+            if !spending_lock.is_encrypted(account) {
+                *account = spending_lock::apply(&encryption, account)?;
+                spending_lock.remove_unencrypted(account);
+            }
+        }
+        spending_lock.unlock(encryption, duration);
+
+        self.refresh_selected_account();
+        Ok(())
+    }
+
+    /// Forgets the spending password, so it has to be given again to use the private keys
+    pub fn lock(&mut self) {
+        if let Some(spending_lock) = &mut self.spending_lock {
+            spending_lock.lock();
+        }
+    }
+
+    /// Returns the account with its private key ready to sign. The accounts of the wallet keep their
+    /// private keys encrypted while there is a spending password
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::WalletLocked`: It will appear when the spending password was not given or its time ran out
+    ///  * `ErrorWallet::CannotEncryptKeys`: It will appear when the private key cannot be decrypted
+    pub fn get_signing_account(&self, account: &Account) -> Result<Account, ErrorWallet> {
+        match &self.spending_lock {
+            Some(spending_lock) => spending_lock.decrypt(account),
+            None => Ok(account.clone()),
+        }
+    }
+
+    /// Updates the selected account with its account in the wallet, after the account was changed
+    fn refresh_selected_account(&mut self) {
+        if let Some(selected_account) = &self.selected_account {
+            if let Some(account) = self.get_account_with_name(&selected_account.account_name) {
+                self.selected_account = Some(account.clone());
+            }
+        }
+    }
+
+    /// Returns the label given to the transaction with the given id
+    pub fn get_transaction_label(&self, transaction_id: &HashType) -> Option<&String> {
+        self.transaction_labels.get(transaction_id)
+    }
+
+    /// Changes the label of the transaction with the given id. A label that is `None` or empty removes it
+    pub fn set_transaction_label(&mut self, transaction_id: HashType, label: Option<String>) {
+        match label.filter(|label| !label.trim().is_empty()) {
+            Some(label) => {
+                self.transaction_labels
+                    .insert(transaction_id, label.trim().to_string());
+            }
+            None => {
+                self.transaction_labels.remove(&transaction_id);
+            }
+        }
+    }
+
+    /// Returns the ids of the labelled transactions with their label, sorted by id
+    fn get_sorted_transaction_labels(&self) -> Vec<(&HashType, &String)> {
+        let mut transaction_labels: Vec<(&HashType, &String)> =
+            self.transaction_labels.iter().collect();
+        transaction_labels.sort();
+        transaction_labels
+    }
+
+    /// Returns the wallet as a JSON document, to keep a backup that can be read by other programs.
+    /// With a password, the private keys are encrypted with a key derived from it
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::WalletLocked`: It will appear when the wallet has a spending password and it's locked
+    ///  * `ErrorWallet::CannotEncryptKeys`: It will appear when the private keys cannot be encrypted
+    pub fn export_json(&self, password: Option<&str>) -> Result<String, ErrorWallet> {
+        let encryption = match password {
+            Some(password) => Some(KeyEncryption::new(password)?),
+            None => None,
+        };
+
+        let mut accounts: Vec<JsonValue> = Vec::new();
+        for (position, account) in self.accounts.iter().enumerate() {
+            let mut private_key = self.get_signing_account(account)?.private_key.as_bytes();
+            if let Some(encryption) = &encryption {
+                private_key = encryption.apply(position as u32, &private_key)?;
+            }
+
+            accounts.push(JsonValue::Object(vec![
+                (
+                    "name".to_string(),
+                    JsonValue::String(account.account_name.clone()),
+                ),
+                (
+                    "private_key".to_string(),
+                    JsonValue::String(hash::to_hex(&private_key)),
+                ),
+                (
+                    "public_key".to_string(),
+                    JsonValue::String(hash::to_hex(&account.public_key.as_bytes())),
+                ),
+                (
+                    "address".to_string(),
+                    JsonValue::String(account.address.to_string()),
+                ),
+                (
+                    "label".to_string(),
+                    JsonValue::from(account.metadata.label.clone()),
+                ),
+                (
+                    "description".to_string(),
+                    JsonValue::from(account.metadata.description.clone()),
+                ),
+                (
+                    "creation_timestamp".to_string(),
+                    match account.metadata.creation_timestamp {
+                        Some(timestamp) => JsonValue::Number(timestamp as f64),
+                        None => JsonValue::Null,
+                    },
+                ),
+            ]));
+        }
+
+        let encryption = match encryption {
+            Some(encryption) => JsonValue::Object(vec![
+                (
+                    "salt".to_string(),
+                    JsonValue::String(hash::to_hex(&encryption.get_salt())),
+                ),
+                (
+                    "check".to_string(),
+                    JsonValue::String(hash::to_hex(&encryption.check()?)),
+                ),
+            ]),
+            None => JsonValue::Null,
+        };
+
+        let selected_account = self
+            .selected_account
+            .as_ref()
+            .map(|account| account.account_name.clone());
+
+        let transaction_labels: Vec<JsonValue> = self
+            .get_sorted_transaction_labels()
+            .into_iter()
+            .map(|(transaction_id, label)| {
+                JsonValue::Object(vec![
+                    (
+                        "transaction_id".to_string(),
+                        JsonValue::String(hash::to_hex_be(transaction_id)),
+                    ),
+                    ("label".to_string(), JsonValue::String(label.clone())),
+                ])
+            })
+            .collect();
+
+        Ok(JsonValue::Object(vec![
+            ("version".to_string(), JsonValue::Number(BACKUP_VERSION)),
+            ("encryption".to_string(), encryption),
+            (
+                "selected_account".to_string(),
+                JsonValue::from(selected_account),
+            ),
+            ("accounts".to_string(), JsonValue::Array(accounts)),
+            (
+                "transaction_labels".to_string(),
+                JsonValue::Array(transaction_labels),
+            ),
+        ])
+        .to_string())
+    }
+
+    /// Creates a wallet from a backup written with `export_json`, with the addresses of its accounts in
+    /// the given network. The password is only needed when the private keys of the backup are encrypted
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::InvalidWalletBackup`: It will appear when the backup is not valid, or it's encrypted and the password is missing or not correct
+    ///  * `ErrorWallet::CannotEncryptKeys`: It will appear when the private keys cannot be decrypted
+    pub fn import_json(
+        json: &str,
+        password: Option<&str>,
+        network: Network,
+    ) -> Result<Wallet, ErrorWallet> {
+        let backup = JsonValue::parse(json)?;
+
+        if backup.get("version").and_then(JsonValue::as_f64) != Some(BACKUP_VERSION) {
+            return Err(ErrorWallet::InvalidWalletBackup(
+                "The version of the backup is not supported".to_string(),
+            ));
+        }
+
+        let encryption = match backup.get("encryption") {
+            Some(encryption) if !encryption.is_null() => {
+                Some(get_backup_encryption(encryption, password)?)
+            }
+            _ => None,
+        };
+
+        let accounts_backup = match backup.get("accounts").and_then(JsonValue::as_array) {
+            Some(accounts_backup) => accounts_backup,
+            None => {
+                return Err(ErrorWallet::InvalidWalletBackup(
+                    "The backup does not have the accounts".to_string(),
+                ))
+            }
+        };
+
+        let mut wallet = Wallet::new(Vec::new()).with_network(network);
+        for (position, account_backup) in accounts_backup.iter().enumerate() {
+            let account = get_backup_account(
+                account_backup,
+                position as u32,
+                encryption.as_ref(),
+                network,
+            )?;
+            if wallet
+                .get_account_with_name(&account.account_name)
+                .is_some()
+            {
+                return Err(ErrorWallet::InvalidWalletBackup(format!(
+                    "The account {} appears more than once",
+                    account.account_name
+                )));
+            }
+            wallet.add_account(account);
+        }
+
+        if let Some(selected_account) = backup.get("selected_account").and_then(JsonValue::as_str) {
+            if let Some(account) = wallet.get_account_with_name(selected_account) {
+                wallet.change_account(account.clone());
+            }
+        }
+
+        if let Some(labels_backup) = backup
+            .get("transaction_labels")
+            .and_then(JsonValue::as_array)
+        {
+            for label_backup in labels_backup {
+                let transaction_id =
+                    match hash::from_hex_be(get_backup_text(label_backup, "transaction_id")?) {
+                        Ok(transaction_id) => transaction_id,
+                        Err(_) => {
+                            return Err(ErrorWallet::InvalidWalletBackup(
+                                "The id of a labelled transaction is not valid".to_string(),
+                            ))
+                        }
+                    };
+                let label = get_backup_text(label_backup, "label")?;
+                wallet.set_transaction_label(transaction_id, Some(label.to_string()));
+            }
+        }
+
+        Ok(wallet)
+    }
+
+    /// Returns a mutable reference to an account from the wallet by its name
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::AccountNotFound`: It will appear when there is no account with the given name
+    fn get_mut_account_with_name(&mut self, name: &str) -> Result<&mut Account, ErrorWallet> {
+        match self.accounts.iter_mut().find(|x| x.account_name == name) {
+            Some(account) => Ok(account),
+            None => Err(ErrorWallet::AccountNotFound(format!(
+                "There is no account named {name}"
+            ))),
+        }
+    }
+}
+
+/// Returns the text of a field of a backup
+///
+/// ### Error
+///  * `ErrorWallet::InvalidWalletBackup`: It will appear when the field is missing or it's not a text
+fn get_backup_text<'a>(value: &'a JsonValue, field: &str) -> Result<&'a str, ErrorWallet> {
+    match value.get(field).and_then(JsonValue::as_str) {
+        Some(text) => Ok(text),
+        None => Err(ErrorWallet::InvalidWalletBackup(format!(
+            "The field {field} of the backup is missing or it's not a text"
+        ))),
+    }
+}
+
+/// Returns the bytes written in hexadecimal in a field of a backup
+///
+/// ### Error
+///  * `ErrorWallet::InvalidWalletBackup`: It will appear when the field is missing or it's not hexadecimal of the expected length
+fn get_backup_bytes<const N: usize>(
+    value: &JsonValue,
+    field: &str,
+) -> Result<[u8; N], ErrorWallet> {
+    match hash::from_hex::<N>(get_backup_text(value, field)?) {
+        Ok(bytes) => Ok(bytes),
+        Err(_) => Err(ErrorWallet::InvalidWalletBackup(format!(
+            "The field {field} of the backup is not valid"
+        ))),
+    }
+}
+
+/// Returns the key to decrypt the private keys of a backup, checking the password is the one used
+/// to encrypt them
+///
+/// ### Error
+///  * `ErrorWallet::InvalidWalletBackup`: It will appear when the password is missing or it's not the correct one
+///  * `ErrorWallet::CannotEncryptKeys`: It will appear when the key cannot be derived from the password
+fn get_backup_encryption(
+    encryption: &JsonValue,
+    password: Option<&str>,
+) -> Result<KeyEncryption, ErrorWallet> {
+    let password = match password {
+        Some(password) => password,
+        None => {
+            return Err(ErrorWallet::InvalidWalletBackup(
+                "The keys of the backup are encrypted, the password is needed".to_string(),
+            ))
+        }
+    };
+
+    let salt: SaltType = get_backup_bytes(encryption, "salt")?;
+    let check = get_backup_bytes(encryption, "check")?;
+
+    let key_encryption = KeyEncryption::with_salt(password, salt)?;
+    if key_encryption.check()? != check {
+        return Err(ErrorWallet::InvalidWalletBackup(
+            "The password of the backup is not correct".to_string(),
+        ));
+    }
+
+    Ok(key_encryption)
+}
+
+/// Returns the account of a backup, in the given position, with its address in the given network
+///
+/// ### Error
+///  * `ErrorWallet::InvalidWalletBackup`: It will appear when a field of the account is not valid or the keys do not belong to the same account
+///  * `ErrorWallet::CannotEncryptKeys`: It will appear when the private key cannot be decrypted
+fn get_backup_account(
+    account_backup: &JsonValue,
+    position: u32,
+    encryption: Option<&KeyEncryption>,
+    network: Network,
+) -> Result<Account, ErrorWallet> {
+    let name = get_backup_text(account_backup, "name")?;
+
+    let mut private_key_bytes: PrivateKeyType = get_backup_bytes(account_backup, "private_key")?;
+    if let Some(encryption) = encryption {
+        private_key_bytes = encryption.apply(position, &private_key_bytes)?;
+    }
+    let private_key = PrivateKey::new(&private_key_bytes)?;
+    let public_key = PublicKey::new(&get_backup_bytes(account_backup, "public_key")?);
+
+    if private_key.public_key() != public_key {
+        return Err(ErrorWallet::InvalidWalletBackup(format!(
+            "The keys of the account {name} do not belong to the same account"
+        )));
+    }
+
+    let mut account = Account::from_keys(name, private_key, public_key, network)?;
+    account.metadata = AccountMetadata {
+        label: account_backup
+            .get("label")
+            .and_then(JsonValue::as_str)
+            .map(|label| label.to_string()),
+        description: account_backup
+            .get("description")
+            .and_then(JsonValue::as_str)
+            .map(|description| description.to_string()),
+        creation_timestamp: account_backup
+            .get("creation_timestamp")
+            .and_then(JsonValue::as_f64)
+            .map(|timestamp| timestamp as i64),
+    };
+
+    Ok(account)
 }
 
 impl TryDefault for Wallet {
@@ -86,6 +666,26 @@ impl SerializableInternalOrder for Wallet {
             account.io_serialize(stream)?;
         }
 
+        METADATA_MARKER.le_serialize(stream)?;
+        for account in &self.accounts {
+            account.metadata.io_serialize(stream)?;
+        }
+
+        if !self.transaction_labels.is_empty() {
+            LABELS_MARKER.le_serialize(stream)?;
+            (self.transaction_labels.len() as u64).le_serialize(stream)?;
+            for (transaction_id, label) in self.get_sorted_transaction_labels() {
+                transaction_id.io_serialize(stream)?;
+                (label.len() as u64).le_serialize(stream)?;
+                label.le_serialize(stream)?;
+            }
+        }
+
+        if let Some(spending_lock) = &self.spending_lock {
+            SPENDING_LOCK_MARKER.le_serialize(stream)?;
+            spending_lock.io_serialize(stream)?;
+        }
+
         Ok(())
     }
 }
@@ -99,10 +699,56 @@ impl DeserializableInternalOrder for Wallet {
             accounts.push(Account::io_deserialize(stream)?);
         }
 
-        Ok(Wallet::new(accounts))
+        let mut marker = [0u8; 1];
+        match stream.read(&mut marker) {
+            Ok(0) => return Ok(Wallet::new(accounts)),
+            Ok(_) if marker[0] == METADATA_MARKER => {
+                for account in accounts.iter_mut() {
+                    account.metadata = AccountMetadata::io_deserialize(stream)?;
+                }
+            }
+            Ok(_) => {
+                return Err(ErrorSerialization::ErrorInDeserialization(
+                    "The metadata of the accounts is not valid".to_string(),
+                ))
+            }
+            Err(_) => return Err(ErrorSerialization::ErrorWhileReading),
+        }
+
+        let mut wallet = Wallet::new(accounts);
+        loop {
+            match stream.read(&mut marker) {
+                Ok(0) => break,
+                Ok(_) if marker[0] == LABELS_MARKER => {
+                    let labels_len = u64::le_deserialize(stream)?;
+                    for _ in 0..labels_len {
+                        let transaction_id = HashType::io_deserialize(stream)?;
+                        let label_len = u64::le_deserialize(stream)? as usize;
+                        let label = String::deserialize_fix_size(stream, label_len)?;
+                        wallet.transaction_labels.insert(transaction_id, label);
+                    }
+                }
+                Ok(_) if marker[0] == SPENDING_LOCK_MARKER => {
+                    wallet.spending_lock = Some(SpendingLock::io_deserialize(stream)?);
+                }
+                Ok(_) => {
+                    return Err(ErrorSerialization::ErrorInDeserialization(
+                        "The labels of the transactions or the spending password are not valid"
+                            .to_string(),
+                    ))
+                }
+                Err(_) => return Err(ErrorSerialization::ErrorWhileReading),
+            }
+        }
+
+        Ok(wallet)
     }
 }
 
+impl VersionedFormat for Wallet {
+    const MIGRATIONS: &'static [Migration] = &[from_unversioned];
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,6 +767,7 @@ mod tests {
                 0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
                 0x35, 0x14, 0x92, 0x4A, 0x22,
             ],
+            Network::Testnet,
         )
         .unwrap();
 
@@ -136,6 +783,7 @@ mod tests {
                 0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
                 0x35, 0x14, 0x92, 0x4A, 0x22,
             ],
+            Network::Testnet,
         )
         .unwrap();
 
@@ -158,6 +806,7 @@ mod tests {
                 0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
                 0x35, 0x14, 0x92, 0x4A, 0x22,
             ],
+            Network::Testnet,
         )
         .unwrap();
 
@@ -173,6 +822,7 @@ mod tests {
                 0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
                 0x35, 0x14, 0x92, 0x4A, 0x22,
             ],
+            Network::Testnet,
         )
         .unwrap();
 
@@ -185,6 +835,17 @@ mod tests {
             .unwrap();
         account_old.io_serialize(&mut serialized_fields).unwrap();
         account_new.io_serialize(&mut serialized_fields).unwrap();
+        METADATA_MARKER
+            .le_serialize(&mut serialized_fields)
+            .unwrap();
+        account_old
+            .metadata
+            .io_serialize(&mut serialized_fields)
+            .unwrap();
+        account_new
+            .metadata
+            .io_serialize(&mut serialized_fields)
+            .unwrap();
 
         let mut serialized_wallet = Vec::new();
         wallet.io_serialize(&mut serialized_wallet).unwrap();
@@ -206,6 +867,7 @@ mod tests {
                 0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
                 0x35, 0x14, 0x92, 0x4A, 0x22,
             ],
+            Network::Testnet,
         )
         .unwrap();
 
@@ -221,6 +883,7 @@ mod tests {
                 0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
                 0x35, 0x14, 0x92, 0x4A, 0x22,
             ],
+            Network::Testnet,
         )
         .unwrap();
 
@@ -250,6 +913,7 @@ mod tests {
                 0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
                 0x35, 0x14, 0x92, 0x4A, 0x22,
             ],
+            Network::Testnet,
         )
         .unwrap();
 
@@ -265,6 +929,7 @@ mod tests {
                 0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
                 0x35, 0x14, 0x92, 0x4A, 0x22,
             ],
+            Network::Testnet,
         )
         .unwrap();
 
@@ -290,6 +955,7 @@ mod tests {
                 0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
                 0x35, 0x14, 0x92, 0x4A, 0x22,
             ],
+            Network::Testnet,
         )
         .unwrap();
 
@@ -305,6 +971,7 @@ mod tests {
                 0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
                 0x35, 0x14, 0x92, 0x4A, 0x22,
             ],
+            Network::Testnet,
         )
         .unwrap();
 
@@ -330,6 +997,7 @@ mod tests {
                 0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
                 0x35, 0x14, 0x92, 0x4A, 0x22,
             ],
+            Network::Testnet,
         )
         .unwrap();
 
@@ -345,6 +1013,7 @@ mod tests {
                 0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
                 0x35, 0x14, 0x92, 0x4A, 0x22,
             ],
+            Network::Testnet,
         )
         .unwrap();
 
@@ -355,4 +1024,378 @@ mod tests {
 
         assert!(wallet.get_account_with_name("Old").is_none());
     }
+
+    #[test]
+    fn test_07_correct_account_renaming() {
+        let account_old = Account::new(
+            "Old",
+            &[
+                0x0A, 0x52, 0x65, 0x08, 0x2E, 0x24, 0x11, 0x5F, 0x77, 0x54, 0x0A, 0xB3, 0xB8, 0xC2,
+                0xB9, 0x20, 0x60, 0xAA, 0x30, 0xD6, 0xD2, 0xB8, 0x1A, 0x08, 0x5D, 0x71, 0xAB, 0x37,
+                0xED, 0xA7, 0x68, 0x91,
+            ],
+            &[
+                0x03, 0xBC, 0x6D, 0x45, 0xD2, 0x10, 0x1E, 0x91, 0x28, 0xDE, 0x14, 0xB5, 0xB6, 0x68,
+                0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
+                0x35, 0x14, 0x92, 0x4A, 0x22,
+            ],
+            Network::Testnet,
+        )
+        .unwrap();
+
+        let account_new = Account::new(
+            "New",
+            &[
+                0x0A, 0x52, 0x65, 0x08, 0x2E, 0x24, 0x11, 0x5F, 0x77, 0x54, 0x0A, 0xB3, 0xB8, 0xC2,
+                0xB9, 0x20, 0x60, 0xAA, 0x30, 0xD6, 0xD2, 0xB8, 0x1A, 0x08, 0x5D, 0x71, 0xAB, 0x37,
+                0xED, 0xA7, 0x68, 0x91,
+            ],
+            &[
+                0x03, 0xBC, 0x6D, 0x45, 0xD2, 0x10, 0x1E, 0x91, 0x28, 0xDE, 0x14, 0xB5, 0xB6, 0x68,
+                0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
+                0x35, 0x14, 0x92, 0x4A, 0x22,
+            ],
+            Network::Testnet,
+        )
+        .unwrap();
+
+        let account_vector = vec![account_old, account_new];
+        let mut wallet = Wallet::new(account_vector);
+
+        wallet.rename_account("Old", "Savings").unwrap();
+
+        assert!(wallet.get_account_with_name("Old").is_none());
+        assert!(wallet.get_account_with_name("Savings").is_some());
+        assert_eq!(
+            "Savings",
+            wallet.get_selected_account().unwrap().account_name
+        );
+    }
+
+    #[test]
+    fn test_08_account_cannot_be_renamed_with_a_used_name() {
+        let account_old = Account::new(
+            "Old",
+            &[
+                0x0A, 0x52, 0x65, 0x08, 0x2E, 0x24, 0x11, 0x5F, 0x77, 0x54, 0x0A, 0xB3, 0xB8, 0xC2,
+                0xB9, 0x20, 0x60, 0xAA, 0x30, 0xD6, 0xD2, 0xB8, 0x1A, 0x08, 0x5D, 0x71, 0xAB, 0x37,
+                0xED, 0xA7, 0x68, 0x91,
+            ],
+            &[
+                0x03, 0xBC, 0x6D, 0x45, 0xD2, 0x10, 0x1E, 0x91, 0x28, 0xDE, 0x14, 0xB5, 0xB6, 0x68,
+                0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
+                0x35, 0x14, 0x92, 0x4A, 0x22,
+            ],
+            Network::Testnet,
+        )
+        .unwrap();
+
+        let account_new = Account::new(
+            "New",
+            &[
+                0x0A, 0x52, 0x65, 0x08, 0x2E, 0x24, 0x11, 0x5F, 0x77, 0x54, 0x0A, 0xB3, 0xB8, 0xC2,
+                0xB9, 0x20, 0x60, 0xAA, 0x30, 0xD6, 0xD2, 0xB8, 0x1A, 0x08, 0x5D, 0x71, 0xAB, 0x37,
+                0xED, 0xA7, 0x68, 0x91,
+            ],
+            &[
+                0x03, 0xBC, 0x6D, 0x45, 0xD2, 0x10, 0x1E, 0x91, 0x28, 0xDE, 0x14, 0xB5, 0xB6, 0x68,
+                0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
+                0x35, 0x14, 0x92, 0x4A, 0x22,
+            ],
+            Network::Testnet,
+        )
+        .unwrap();
+
+        let account_vector = vec![account_old, account_new];
+        let mut wallet = Wallet::new(account_vector);
+
+        assert!(matches!(
+            wallet.rename_account("Old", "New"),
+            Err(ErrorWallet::InvalidAccountName(_))
+        ));
+        assert!(matches!(
+            wallet.rename_account("Old", " "),
+            Err(ErrorWallet::InvalidAccountName(_))
+        ));
+        assert!(matches!(
+            wallet.rename_account("Other", "Savings"),
+            Err(ErrorWallet::AccountNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_09_metadata_of_the_accounts_is_kept_after_serialization() {
+        let account_old = Account::new(
+            "Old",
+            &[
+                0x0A, 0x52, 0x65, 0x08, 0x2E, 0x24, 0x11, 0x5F, 0x77, 0x54, 0x0A, 0xB3, 0xB8, 0xC2,
+                0xB9, 0x20, 0x60, 0xAA, 0x30, 0xD6, 0xD2, 0xB8, 0x1A, 0x08, 0x5D, 0x71, 0xAB, 0x37,
+                0xED, 0xA7, 0x68, 0x91,
+            ],
+            &[
+                0x03, 0xBC, 0x6D, 0x45, 0xD2, 0x10, 0x1E, 0x91, 0x28, 0xDE, 0x14, 0xB5, 0xB6, 0x68,
+                0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
+                0x35, 0x14, 0x92, 0x4A, 0x22,
+            ],
+            Network::Testnet,
+        )
+        .unwrap();
+
+        let mut wallet = Wallet::new(vec![account_old]);
+        wallet
+            .set_account_description(
+                "Old",
+                Some("Savings".to_string()),
+                Some("Coins for the holidays".to_string()),
+            )
+            .unwrap();
+
+        let mut serialized_wallet = Vec::new();
+        wallet.io_serialize(&mut serialized_wallet).unwrap();
+
+        let deserialized_wallet =
+            Wallet::io_deserialize(&mut serialized_wallet.as_slice()).unwrap();
+
+        assert_eq!(
+            wallet.get_account_with_name("Old").unwrap().metadata,
+            deserialized_wallet
+                .get_account_with_name("Old")
+                .unwrap()
+                .metadata
+        );
+        assert_eq!(
+            Some("Savings".to_string()),
+            deserialized_wallet
+                .get_selected_account()
+                .unwrap()
+                .metadata
+                .label
+        );
+    }
+
+    #[test]
+    fn test_10_wallet_without_metadata_can_be_deserialized() {
+        let account_old = Account::new(
+            "Old",
+            &[
+                0x0A, 0x52, 0x65, 0x08, 0x2E, 0x24, 0x11, 0x5F, 0x77, 0x54, 0x0A, 0xB3, 0xB8, 0xC2,
+                0xB9, 0x20, 0x60, 0xAA, 0x30, 0xD6, 0xD2, 0xB8, 0x1A, 0x08, 0x5D, 0x71, 0xAB, 0x37,
+                0xED, 0xA7, 0x68, 0x91,
+            ],
+            &[
+                0x03, 0xBC, 0x6D, 0x45, 0xD2, 0x10, 0x1E, 0x91, 0x28, 0xDE, 0x14, 0xB5, 0xB6, 0x68,
+                0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
+                0x35, 0x14, 0x92, 0x4A, 0x22,
+            ],
+            Network::Testnet,
+        )
+        .unwrap();
+
+        let mut serialized_wallet = Vec::new();
+        1u64.le_serialize(&mut serialized_wallet).unwrap();
+        account_old.io_serialize(&mut serialized_wallet).unwrap();
+
+        let deserialized_wallet =
+            Wallet::io_deserialize(&mut serialized_wallet.as_slice()).unwrap();
+        let account = deserialized_wallet.get_account_with_name("Old").unwrap();
+
+        assert_eq!(AccountMetadata::default(), account.metadata);
+    }
+
+    #[test]
+    fn test_11_wallet_is_the_same_after_exporting_and_importing_it() {
+        let mut account_old = Account::generate("Old", Network::Testnet).unwrap();
+        account_old.metadata.label = Some("Savings \"2023\"".to_string());
+        let account_new = Account::generate("New", Network::Testnet).unwrap();
+
+        let mut wallet = Wallet::new(vec![account_old.clone(), account_new.clone()]);
+        wallet.change_account(account_new.clone());
+
+        let backup = wallet.export_json(None).unwrap();
+        let imported_wallet = Wallet::import_json(&backup, None, Network::Testnet).unwrap();
+
+        assert_eq!(wallet, imported_wallet);
+        for account in [account_old, account_new] {
+            let imported_account = imported_wallet
+                .get_account_with_name(&account.account_name)
+                .unwrap();
+            assert_eq!(account.private_key, imported_account.private_key);
+            assert_eq!(account.public_key, imported_account.public_key);
+            assert_eq!(account.metadata, imported_account.metadata);
+        }
+        assert!(backup.contains(&hash::to_hex(
+            &wallet.get_accounts()[0].private_key.as_bytes()
+        )));
+    }
+
+    #[test]
+    fn test_12_encrypted_backup_needs_its_password() {
+        let account = Account::generate("Old", Network::Testnet).unwrap();
+        let wallet = Wallet::new(vec![account.clone()]);
+
+        let backup = wallet.export_json(Some("password")).unwrap();
+
+        assert!(!backup.contains(&hash::to_hex(&account.private_key.as_bytes())));
+        assert!(matches!(
+            Wallet::import_json(&backup, None, Network::Testnet),
+            Err(ErrorWallet::InvalidWalletBackup(_))
+        ));
+        assert!(matches!(
+            Wallet::import_json(&backup, Some("other"), Network::Testnet),
+            Err(ErrorWallet::InvalidWalletBackup(_))
+        ));
+
+        let imported_wallet =
+            Wallet::import_json(&backup, Some("password"), Network::Testnet).unwrap();
+        assert_eq!(
+            account.private_key,
+            imported_wallet
+                .get_account_with_name("Old")
+                .unwrap()
+                .private_key
+        );
+    }
+
+    #[test]
+    fn test_13_transaction_labels_are_kept_after_serialization_and_backup() {
+        let mut wallet = Wallet::new(vec![Account::generate("Old", Network::Testnet).unwrap()]);
+        wallet.set_transaction_label([1; 32], Some(" rent May ".to_string()));
+        wallet.set_transaction_label([2; 32], Some("groceries".to_string()));
+        wallet.set_transaction_label([2; 32], Some("".to_string()));
+
+        assert_eq!(
+            Some(&"rent May".to_string()),
+            wallet.get_transaction_label(&[1; 32])
+        );
+        assert_eq!(None, wallet.get_transaction_label(&[2; 32]));
+
+        let mut serialized_wallet = Vec::new();
+        wallet.io_serialize(&mut serialized_wallet).unwrap();
+        assert_eq!(
+            wallet,
+            Wallet::io_deserialize(&mut serialized_wallet.as_slice()).unwrap()
+        );
+
+        let backup = wallet.export_json(None).unwrap();
+        assert!(backup.contains(&hash::to_hex_be(&[1; 32])));
+        assert_eq!(
+            wallet,
+            Wallet::import_json(&backup, None, Network::Testnet).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_14_private_keys_are_encrypted_with_the_spending_password() {
+        let account = Account::generate("Old", Network::Testnet).unwrap();
+        let mut wallet = Wallet::new(vec![account.clone()]);
+        assert!(!wallet.is_locked());
+
+        wallet
+            .set_spending_password(None, Some("password"))
+            .unwrap();
+        assert!(wallet.has_spending_password());
+        assert!(wallet.is_locked());
+        assert_ne!(
+            account.private_key,
+            wallet.get_selected_account().unwrap().private_key
+        );
+        assert!(matches!(
+            wallet.get_signing_account(&account),
+            Err(ErrorWallet::WalletLocked(_))
+        ));
+        assert!(matches!(
+            wallet.export_json(None),
+            Err(ErrorWallet::WalletLocked(_))
+        ));
+        assert!(matches!(
+            wallet.unlock("other", Duration::from_secs(60)),
+            Err(ErrorWallet::InvalidSpendingPassword(_))
+        ));
+
+        wallet.unlock("password", Duration::from_secs(60)).unwrap();
+        assert!(!wallet.is_locked());
+        let selected_account = wallet.get_selected_account().unwrap().clone();
+        assert_eq!(
+            account.private_key,
+            wallet
+                .get_signing_account(&selected_account)
+                .unwrap()
+                .private_key
+        );
+
+        wallet.lock();
+        assert!(wallet.is_locked());
+        wallet.unlock("password", Duration::ZERO).unwrap();
+        assert!(wallet.is_locked());
+    }
+
+    #[test]
+    fn test_15_spending_password_can_be_changed_and_removed() {
+        let account = Account::generate("Old", Network::Testnet).unwrap();
+        let mut wallet = Wallet::new(vec![account.clone()]);
+        wallet
+            .set_spending_password(None, Some("password"))
+            .unwrap();
+
+        assert!(matches!(
+            wallet.set_spending_password(None, Some("other")),
+            Err(ErrorWallet::InvalidSpendingPassword(_))
+        ));
+        assert!(matches!(
+            wallet.set_spending_password(Some("other"), Some("other")),
+            Err(ErrorWallet::InvalidSpendingPassword(_))
+        ));
+
+        wallet
+            .set_spending_password(Some("password"), Some("other"))
+            .unwrap();
+        wallet.unlock("other", Duration::from_secs(60)).unwrap();
+        let encrypted_account = wallet.get_account_with_name("Old").unwrap().clone();
+        assert_eq!(
+            account.private_key,
+            wallet
+                .get_signing_account(&encrypted_account)
+                .unwrap()
+                .private_key
+        );
+
+        wallet.set_spending_password(Some("other"), None).unwrap();
+        assert!(!wallet.has_spending_password());
+        assert_eq!(
+            account.private_key,
+            wallet.get_selected_account().unwrap().private_key
+        );
+    }
+
+    #[test]
+    fn test_16_accounts_added_while_locked_are_encrypted_when_unlocking() {
+        let mut wallet = Wallet::new(vec![Account::generate("Old", Network::Testnet).unwrap()]);
+        wallet
+            .set_spending_password(None, Some("password"))
+            .unwrap();
+
+        let account = Account::generate("New", Network::Testnet).unwrap();
+        wallet.add_account(account.clone());
+        assert_eq!(
+            account.private_key,
+            wallet.get_account_with_name("New").unwrap().private_key
+        );
+
+        let mut serialized_wallet = Vec::new();
+        wallet.io_serialize(&mut serialized_wallet).unwrap();
+        let mut wallet = Wallet::io_deserialize(&mut serialized_wallet.as_slice()).unwrap();
+        assert!(wallet.is_locked());
+        assert!(wallet.unlock("other", Duration::from_secs(60)).is_err());
+
+        wallet.unlock("password", Duration::from_secs(60)).unwrap();
+        let new_account = wallet.get_account_with_name("New").unwrap().clone();
+        assert_ne!(account.private_key, new_account.private_key);
+        assert_eq!(
+            account.private_key,
+            wallet
+                .get_signing_account(&new_account)
+                .unwrap()
+                .private_key
+        );
+    }
 }
@@ -0,0 +1,533 @@
+use super::{error_wallet::ErrorWallet, wallet::Wallet};
+
+use crate::{
+    configurations::try_default::TryDefault,
+    serialization::checked_file::{read_checked_file, write_checked_file, CheckedRead},
+};
+
+use std::{
+    fs,
+    mem::replace,
+    path::{Path, PathBuf},
+};
+
+pub const WALLET_EXTENSION: &str = "wallet";
+pub const DEFAULT_WALLET_NAME: &str = "default";
+
+/// The name of a wallet and the path of its file
+pub type SelectedWallet = (String, PathBuf);
+
+/// It's the directory where each wallet is saved in its own file, named after the wallet,
+/// and the wallet that is currently being used. The wallet in use can also be a file outside
+/// the directory, and there is none after it's closed
+#[derive(Debug, Clone, PartialEq)]
+pub struct WalletDirectory {
+    directory: PathBuf,
+    selected_wallet: Option<SelectedWallet>,
+}
+
+impl WalletDirectory {
+    /// Creates the wallet directory if it does not exist. If no wallet is selected the default one is used
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::InvalidWalletName`: It will appear when the selected wallet cannot be used as a file name
+    ///  * `ErrorWallet::CannotAccessWalletFile`: It will appear when the directory cannot be created
+    pub fn new(directory: &str, selected_wallet: Option<String>) -> Result<Self, ErrorWallet> {
+        let selected_wallet = match selected_wallet {
+            Some(selected_wallet) => selected_wallet,
+            None => DEFAULT_WALLET_NAME.to_string(),
+        };
+        validate_wallet_name(&selected_wallet)?;
+
+        if fs::create_dir_all(directory).is_err() {
+            return Err(ErrorWallet::CannotAccessWalletFile(format!(
+                "Cannot create the wallet directory {directory}"
+            )));
+        }
+
+        let directory = PathBuf::from(directory);
+        let path = directory.join(format!("{selected_wallet}.{WALLET_EXTENSION}"));
+
+        Ok(WalletDirectory {
+            directory,
+            selected_wallet: Some((selected_wallet, path)),
+        })
+    }
+
+    /// Returns the name of the wallet currently being used, or None if it was closed
+    pub fn get_selected_wallet(&self) -> Option<&str> {
+        self.selected_wallet.as_ref().map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the path of the file of the wallet currently being used, or None if it was closed
+    pub fn get_selected_path(&self) -> Option<&Path> {
+        self.selected_wallet
+            .as_ref()
+            .map(|(_, path)| path.as_path())
+    }
+
+    /// Returns the path of the file of the wallet with the given name
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::InvalidWalletName`: It will appear when the name cannot be used as a file name
+    pub fn get_wallet_path(&self, name: &str) -> Result<PathBuf, ErrorWallet> {
+        validate_wallet_name(name)?;
+        Ok(self.directory.join(format!("{name}.{WALLET_EXTENSION}")))
+    }
+
+    /// Returns the sorted names of the wallets in the directory, including the selected one even if it was never saved
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotAccessWalletFile`: It will appear when the directory cannot be read
+    pub fn get_wallet_names(&self) -> Result<Vec<String>, ErrorWallet> {
+        let entries = match fs::read_dir(&self.directory) {
+            Ok(entries) => entries,
+            Err(_) => {
+                return Err(ErrorWallet::CannotAccessWalletFile(format!(
+                    "Cannot read the wallet directory {}",
+                    self.directory.display()
+                )))
+            }
+        };
+
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == WALLET_EXTENSION)
+            })
+            .filter_map(|path| {
+                path.file_stem()
+                    .map(|name| name.to_string_lossy().to_string())
+            })
+            .collect();
+
+        if let Some(selected_wallet) = self.get_selected_wallet() {
+            if !names.iter().any(|name| name == selected_wallet) {
+                names.push(selected_wallet.to_string());
+            }
+        }
+
+        names.sort();
+        Ok(names)
+    }
+
+    /// Loads the wallet with the given name. If the wallet was never saved, an empty wallet is returned.
+    /// If its file is corrupted, the wallet is recovered from the backup of the previous save
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::InvalidWalletName`: It will appear when the name cannot be used as a file name
+    ///  * `ErrorWallet::CannotAccessWalletFile`: It will appear when the file of the wallet and its backup cannot be deserialized
+    pub fn load_wallet(&self, name: &str) -> Result<Wallet, ErrorWallet> {
+        let path = self.get_wallet_path(name)?;
+
+        match read_checked_file(&path.to_string_lossy()) {
+            Ok(CheckedRead::Value(wallet) | CheckedRead::Recovered(wallet, _)) => Ok(wallet),
+            Ok(CheckedRead::Missing) => Wallet::try_default(),
+            Err(error) => Err(ErrorWallet::CannotAccessWalletFile(format!(
+                "Cannot read the wallet {name}, error : {:?}",
+                error
+            ))),
+        }
+    }
+
+    /// Saves the wallet in the file of the given name
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::InvalidWalletName`: It will appear when the name cannot be used as a file name
+    ///  * `ErrorWallet::CannotAccessWalletFile`: It will appear when the file of the wallet cannot be written
+    pub fn save_wallet(&self, name: &str, wallet: &Wallet) -> Result<(), ErrorWallet> {
+        let path = self.get_wallet_path(name)?;
+
+        match write_checked_file(&path.to_string_lossy(), wallet) {
+            Ok(_) => Ok(()),
+            Err(error) => Err(ErrorWallet::CannotAccessWalletFile(format!(
+                "Cannot write the wallet {name}, error : {:?}",
+                error
+            ))),
+        }
+    }
+
+    /// Saves the wallet in the file of the wallet in use. If the wallet was closed nothing is saved
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotAccessWalletFile`: It will appear when the file of the wallet cannot be written
+    pub fn save_selected_wallet(&self, wallet: &Wallet) -> Result<(), ErrorWallet> {
+        let (name, path) = match &self.selected_wallet {
+            Some(selected_wallet) => selected_wallet,
+            None => return Ok(()),
+        };
+
+        match write_checked_file(&path.to_string_lossy(), wallet) {
+            Ok(_) => Ok(()),
+            Err(error) => Err(ErrorWallet::CannotAccessWalletFile(format!(
+                "Cannot write the wallet {name}, error : {:?}",
+                error
+            ))),
+        }
+    }
+
+    /// Saves the current wallet in its file and replaces it with the wallet of the given name
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::InvalidWalletName`: It will appear when the name cannot be used as a file name
+    ///  * `ErrorWallet::CannotAccessWalletFile`: It will appear when one of the wallet files cannot be read or written
+    pub fn switch_wallet(&mut self, wallet: &mut Wallet, name: &str) -> Result<(), ErrorWallet> {
+        let path = self.get_wallet_path(name)?;
+        self.save_selected_wallet(wallet)?;
+        let new_wallet = self.load_wallet(name)?.with_network(wallet.get_network());
+
+        let _ = replace(wallet, new_wallet);
+        self.selected_wallet = Some((name.to_string(), path));
+
+        Ok(())
+    }
+
+    /// Saves the current wallet in its file and replaces it with a new empty wallet with the given name
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::InvalidWalletName`: It will appear when the name cannot be used as a file name
+    ///  * `ErrorWallet::WalletAlreadyExists`: It will appear when there is already a wallet with the given name
+    ///  * `ErrorWallet::CannotAccessWalletFile`: It will appear when one of the wallet files cannot be read or written
+    pub fn create_wallet(&mut self, wallet: &mut Wallet, name: &str) -> Result<(), ErrorWallet> {
+        let is_used =
+            self.get_wallet_path(name)?.exists() || self.get_selected_wallet() == Some(name);
+        if is_used {
+            return Err(ErrorWallet::WalletAlreadyExists(name.to_string()));
+        }
+
+        self.switch_wallet(wallet, name)?;
+        self.save_selected_wallet(wallet)
+    }
+
+    /// Saves the current wallet in its file and replaces it with the wallet saved in the given file,
+    /// which can be outside the directory. The wallet is named after its file
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotAccessWalletFile`: It will appear when there is no wallet in the given file or one of the wallet files cannot be read or written
+    pub fn open_wallet_file(&mut self, wallet: &mut Wallet, path: &str) -> Result<(), ErrorWallet> {
+        let name = match Path::new(path).file_stem() {
+            Some(name) => name.to_string_lossy().to_string(),
+            None => {
+                return Err(ErrorWallet::CannotAccessWalletFile(format!(
+                    "The path {path} is not a wallet file"
+                )))
+            }
+        };
+
+        let new_wallet: Wallet = match read_checked_file(path) {
+            Ok(CheckedRead::Value(wallet) | CheckedRead::Recovered(wallet, _)) => wallet,
+            Ok(CheckedRead::Missing) => {
+                return Err(ErrorWallet::CannotAccessWalletFile(format!(
+                    "There is no wallet in {path}"
+                )))
+            }
+            Err(error) => {
+                return Err(ErrorWallet::CannotAccessWalletFile(format!(
+                    "Cannot read the wallet in {path}, error : {:?}",
+                    error
+                )))
+            }
+        };
+
+        self.save_selected_wallet(wallet)?;
+        let new_wallet = new_wallet.with_network(wallet.get_network());
+        let _ = replace(wallet, new_wallet);
+        self.selected_wallet = Some((name, PathBuf::from(path)));
+
+        Ok(())
+    }
+
+    /// Saves the current wallet in its file and leaves an empty wallet in its place, that is not
+    /// saved until another wallet is used
+    ///
+    /// ### Error
+    ///  * `ErrorWallet::CannotAccessWalletFile`: It will appear when the file of the wallet cannot be written
+    pub fn close_wallet(&mut self, wallet: &mut Wallet) -> Result<(), ErrorWallet> {
+        self.save_selected_wallet(wallet)?;
+        let new_wallet = Wallet::try_default()?.with_network(wallet.get_network());
+        let _ = replace(wallet, new_wallet);
+        self.selected_wallet = None;
+
+        Ok(())
+    }
+}
+
+/// Validates that the name of the wallet is not empty and can be used as a file name
+///
+/// ### Error
+///  * `ErrorWallet::InvalidWalletName`: It will appear when the name has characters that are not allowed
+pub fn validate_wallet_name(name: &str) -> Result<(), ErrorWallet> {
+    let is_valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || "-_ ".contains(character));
+
+    if !is_valid {
+        return Err(ErrorWallet::InvalidWalletName(name.to_string()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{connections::network::Network, wallet_structure::account::Account};
+
+    use std::env;
+
+    fn create_directory(test_name: &str) -> String {
+        let directory = env::temp_dir().join(format!("cargosos_wallets_{test_name}"));
+        let _ = fs::remove_dir_all(&directory);
+        directory.to_string_lossy().to_string()
+    }
+
+    fn create_account(name: &str) -> Account {
+        let private_key_bytes: [u8; 32] = [
+            0x0a, 0x52, 0x65, 0x08, 0x2e, 0x24, 0x11, 0x5f, 0x77, 0x54, 0x0a, 0xb3, 0xb8, 0xc2,
+            0xb9, 0x20, 0x60, 0xaa, 0x30, 0xd6, 0xd2, 0xb8, 0x1a, 0x08, 0x5d, 0x71, 0xab, 0x37,
+            0xed, 0xa7, 0x68, 0x91,
+        ];
+        let public_key_bytes: [u8; 33] = [
+            0x03, 0xBC, 0x6D, 0x45, 0xD2, 0x10, 0x1E, 0x91, 0x28, 0xDE, 0x14, 0xB5, 0xB6, 0x68,
+            0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
+            0x35, 0x14, 0x92, 0x4A, 0x22,
+        ];
+        Account::new(
+            name,
+            &private_key_bytes,
+            &public_key_bytes,
+            Network::Testnet,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test01_correct_wallet_names() {
+        let directory = create_directory("test01");
+        let wallet_directory = WalletDirectory::new(&directory, None).unwrap();
+
+        assert_eq!(
+            wallet_directory.get_selected_wallet(),
+            Some(DEFAULT_WALLET_NAME)
+        );
+        assert_eq!(
+            wallet_directory.get_wallet_names(),
+            Ok(vec![DEFAULT_WALLET_NAME.to_string()])
+        );
+
+        let wallet = Wallet::try_default().unwrap();
+        wallet_directory.save_wallet("testing", &wallet).unwrap();
+        fs::write(PathBuf::from(&directory).join("notes.txt"), "not a wallet").unwrap();
+
+        assert_eq!(
+            wallet_directory.get_wallet_names(),
+            Ok(vec![DEFAULT_WALLET_NAME.to_string(), "testing".to_string()])
+        );
+
+        let _ = fs::remove_dir_all(directory);
+    }
+
+    #[test]
+    fn test02_does_not_accept_invalid_wallet_names() {
+        let directory = create_directory("test02");
+
+        assert!(WalletDirectory::new(&directory, Some("../personal".to_string())).is_err());
+
+        let wallet_directory = WalletDirectory::new(&directory, None).unwrap();
+
+        assert_eq!(
+            wallet_directory.get_wallet_path(""),
+            Err(ErrorWallet::InvalidWalletName("".to_string()))
+        );
+        assert_eq!(
+            wallet_directory.get_wallet_path("a/b"),
+            Err(ErrorWallet::InvalidWalletName("a/b".to_string()))
+        );
+
+        let _ = fs::remove_dir_all(directory);
+    }
+
+    #[test]
+    fn test03_correct_switch_between_wallets() {
+        let directory = create_directory("test03");
+        let mut wallet_directory =
+            WalletDirectory::new(&directory, Some("personal".to_string())).unwrap();
+
+        let mut wallet = Wallet::new(vec![create_account("Ana")]);
+
+        wallet_directory
+            .switch_wallet(&mut wallet, "testing")
+            .unwrap();
+
+        assert_eq!(wallet_directory.get_selected_wallet(), Some("testing"));
+        assert!(wallet.get_accounts().is_empty());
+
+        wallet.add_account(create_account("Beto"));
+        wallet_directory
+            .switch_wallet(&mut wallet, "personal")
+            .unwrap();
+
+        assert_eq!(wallet, Wallet::new(vec![create_account("Ana")]));
+        assert_eq!(
+            wallet_directory.load_wallet("testing"),
+            Ok(Wallet::new(vec![create_account("Beto")]))
+        );
+
+        let _ = fs::remove_dir_all(directory);
+    }
+
+    #[test]
+    fn test04_corrupted_wallet_is_recovered_from_previous_save() {
+        let directory = create_directory("test04");
+        let wallet_directory = WalletDirectory::new(&directory, None).unwrap();
+
+        let wallet = Wallet::new(vec![create_account("Ana")]);
+        wallet_directory.save_wallet("personal", &wallet).unwrap();
+        wallet_directory
+            .save_wallet("personal", &Wallet::new(vec![create_account("Beto")]))
+            .unwrap();
+
+        let path = wallet_directory.get_wallet_path("personal").unwrap();
+        let bytes = fs::read(&path).unwrap();
+        fs::write(&path, &bytes[..bytes.len() / 2]).unwrap();
+
+        assert_eq!(wallet_directory.load_wallet("personal"), Ok(wallet));
+        assert_eq!(
+            wallet_directory.get_wallet_names(),
+            Ok(vec![
+                DEFAULT_WALLET_NAME.to_string(),
+                "personal".to_string()
+            ])
+        );
+
+        let _ = fs::remove_dir_all(directory);
+    }
+
+    #[test]
+    fn test05_correct_creation_of_a_new_wallet() {
+        let directory = create_directory("test05");
+        let mut wallet_directory = WalletDirectory::new(&directory, None).unwrap();
+
+        let mut wallet = Wallet::new(vec![create_account("Ana")]);
+        wallet_directory
+            .create_wallet(&mut wallet, "savings")
+            .unwrap();
+
+        assert_eq!(wallet_directory.get_selected_wallet(), Some("savings"));
+        assert!(wallet.get_accounts().is_empty());
+        assert!(wallet_directory
+            .get_wallet_path("savings")
+            .unwrap()
+            .exists());
+
+        assert_eq!(
+            wallet_directory.create_wallet(&mut wallet, DEFAULT_WALLET_NAME),
+            Err(ErrorWallet::WalletAlreadyExists(
+                DEFAULT_WALLET_NAME.to_string()
+            ))
+        );
+        assert_eq!(
+            wallet_directory.load_wallet(DEFAULT_WALLET_NAME),
+            Ok(Wallet::new(vec![create_account("Ana")]))
+        );
+
+        let _ = fs::remove_dir_all(directory);
+    }
+
+    #[test]
+    fn test06_wallet_file_outside_the_directory_is_opened_and_saved_in_place() {
+        let directory = create_directory("test06");
+        let outside = create_directory("test06_outside");
+        let outside_directory = WalletDirectory::new(&outside, None).unwrap();
+        outside_directory
+            .save_wallet("shared", &Wallet::new(vec![create_account("Beto")]))
+            .unwrap();
+        let outside_path = outside_directory.get_wallet_path("shared").unwrap();
+
+        let mut wallet_directory = WalletDirectory::new(&directory, None).unwrap();
+        let mut wallet = Wallet::new(vec![create_account("Ana")]);
+        wallet_directory
+            .open_wallet_file(&mut wallet, &outside_path.to_string_lossy())
+            .unwrap();
+
+        assert_eq!(wallet, Wallet::new(vec![create_account("Beto")]));
+        assert_eq!(wallet_directory.get_selected_wallet(), Some("shared"));
+        assert_eq!(
+            wallet_directory.get_selected_path(),
+            Some(outside_path.as_path())
+        );
+        assert_eq!(
+            wallet_directory.get_wallet_names(),
+            Ok(vec![DEFAULT_WALLET_NAME.to_string(), "shared".to_string()])
+        );
+
+        wallet.add_account(create_account("Carla"));
+        wallet_directory.save_selected_wallet(&wallet).unwrap();
+        assert_eq!(
+            outside_directory.load_wallet("shared"),
+            Ok(Wallet::new(vec![
+                create_account("Beto"),
+                create_account("Carla")
+            ]))
+        );
+
+        assert!(wallet_directory
+            .open_wallet_file(&mut wallet, &format!("{outside}/missing.wallet"))
+            .is_err());
+
+        let _ = fs::remove_dir_all(directory);
+        let _ = fs::remove_dir_all(outside);
+    }
+
+    #[test]
+    fn test07_closed_wallet_is_saved_and_not_used_anymore() {
+        let directory = create_directory("test07");
+        let mut wallet_directory = WalletDirectory::new(&directory, None).unwrap();
+
+        let mut wallet = Wallet::new(vec![create_account("Ana")]);
+        wallet_directory.close_wallet(&mut wallet).unwrap();
+
+        assert_eq!(wallet_directory.get_selected_wallet(), None);
+        assert!(wallet.get_accounts().is_empty());
+        assert_eq!(
+            wallet_directory.load_wallet(DEFAULT_WALLET_NAME),
+            Ok(Wallet::new(vec![create_account("Ana")]))
+        );
+
+        wallet.add_account(create_account("Beto"));
+        wallet_directory.save_selected_wallet(&wallet).unwrap();
+        assert_eq!(
+            wallet_directory.load_wallet(DEFAULT_WALLET_NAME),
+            Ok(Wallet::new(vec![create_account("Ana")]))
+        );
+
+        wallet_directory
+            .switch_wallet(&mut wallet, DEFAULT_WALLET_NAME)
+            .unwrap();
+        assert_eq!(wallet, Wallet::new(vec![create_account("Ana")]));
+
+        let _ = fs::remove_dir_all(directory);
+    }
+
+    #[test]
+    fn test08_switched_wallet_keeps_the_network_of_the_node() {
+        let directory = create_directory("test08");
+        let mut wallet_directory =
+            WalletDirectory::new(&directory, Some("personal".to_string())).unwrap();
+
+        let mut wallet = Wallet::new(vec![create_account("Ana")]).with_network(Network::Mainnet);
+        wallet_directory
+            .switch_wallet(&mut wallet, "testing")
+            .unwrap();
+        assert_eq!(Network::Mainnet, wallet.get_network());
+
+        wallet_directory.close_wallet(&mut wallet).unwrap();
+        assert_eq!(Network::Mainnet, wallet.get_network());
+
+        let _ = fs::remove_dir_all(directory);
+    }
+}
@@ -1,23 +1,24 @@
-mod common;
-
 #[cfg(test)]
 mod test_integration {
 
-    use super::common::{creation, serialize_message, stream::Stream};
-
     use cargosos_bitcoin::{
         block_structure::{
             block::Block, block_chain::BlockChain, hash::HashType, merkle_tree::MerkleTree,
+            utxo_set::UTXOSet,
+        },
+        connections::{
+            network::Network, p2p_protocol::ProtocolVersionP2P,
+            supported_services::SupportedServices, type_identifier::TypeIdentifier,
         },
-        connections::{p2p_protocol::ProtocolVersionP2P, supported_services::SupportedServices},
         logs::logger,
         messages::{
             bitfield_services::BitfieldServices,
             command_name::CommandName,
             get_headers_message::GetHeadersMessage,
+            inventory_message::InventoryMessage,
+            inventory_vector::InventoryVector,
             message::{self, Message},
             send_headers_message::SendHeadersMessage,
-            tx_message::TxMessage,
             verack_message::VerackMessage,
             version_message::VersionMessage,
         },
@@ -28,6 +29,8 @@ mod test_integration {
             message_to_peer::MessageToPeer, peer_manager::PeerManager,
         },
         notifications::{notification::Notification, notifier::Notifier},
+        testing::{creation, scenario::ScenarioBuilder, serialize_message, stream::Stream},
+        wallet_structure::account::Account,
     };
 
     use std::{
@@ -36,7 +39,7 @@ mod test_integration {
     };
 
     fn read_message<M: Message>(stream: &mut Stream, message_type: CommandName) -> M {
-        let header = message::deserialize_until_found(stream, message_type).unwrap();
+        let header = message::deserialize_until_found(stream, message_type.clone()).unwrap();
         assert_eq!(header.command_name, message_type);
         M::deserialize_message(stream, header).unwrap()
     }
@@ -56,13 +59,14 @@ mod test_integration {
     #[test]
     fn test01_program_run_correctly() {
         let mut stream = Vec::new();
-        let magic_numbers = [11, 17, 9, 7];
+        let network = Network::Testnet;
+        let magic_numbers = network.magic_numbers();
 
         let handshake_data = HandshakeData {
             nonce: 0,
             user_agent: "".to_string(),
             relay: false,
-            magic_number: magic_numbers.clone(),
+            network,
         };
 
         let local_ip: (Ipv4Addr, u16) = (Ipv4Addr::new(127, 0, 0, 1), 8333);
@@ -86,8 +90,11 @@ mod test_integration {
         )
         .unwrap();
 
-        serialize_message::serialize_verack_message(&mut stream, handshake_data.magic_number)
-            .unwrap();
+        serialize_message::serialize_verack_message(
+            &mut stream,
+            handshake_data.network.magic_numbers(),
+        )
+        .unwrap();
 
         let first_block = creation::create_genesis_block();
 
@@ -97,7 +104,7 @@ mod test_integration {
 
         let mut block_to_append = creation::create_block(first_block_header_hash.clone(), 2);
         block_to_append
-            .append_transaction(creation::create_transaction(1))
+            .append_transaction(creation::create_coinbase(1))
             .unwrap();
         block_to_append
             .append_transaction(creation::create_transaction(2))
@@ -176,14 +183,14 @@ mod test_integration {
             InitialHeaderDownload::new(p2p_protocol, magic_numbers.clone(), sender.clone());
 
         initial_headers_download
-            .get_headers(&mut stream, &mut blockchain)
+            .get_headers(&mut stream, &mut blockchain, 0)
             .unwrap();
 
         assert_eq!(expected_blockchain, blockchain);
 
         let hashed_headers: Vec<HashType> = vec![first_block_header_hash, second_block_header_hash];
 
-        let block_download = BlockDownload::new(magic_numbers.clone(), sender.clone());
+        let mut block_download = BlockDownload::new(network, sender.clone());
         let notifier = NotificationMock {};
 
         let blocks = block_download
@@ -238,11 +245,92 @@ mod test_integration {
 
         assert_eq!(
             get_headers_message.header_locator_hashes,
-            vec![first_block_header_hash, first_block_header_hash]
+            vec![first_block_header_hash]
         );
 
-        let transaction_message = read_message::<TxMessage>(&mut stream, CommandName::Tx);
+        let inventory_message =
+            read_message::<InventoryMessage>(&mut stream, CommandName::Inventory);
+
+        assert_eq!(
+            inventory_message.inventory_vectors,
+            vec![InventoryVector::new(
+                TypeIdentifier::TransactionId,
+                send_transaction.get_tx_id().unwrap()
+            )]
+        );
+    }
+
+    #[test]
+    fn test02_initial_block_download_updates_the_wallet() {
+        let private_key: [u8; 32] = [
+            0x0a, 0x52, 0x65, 0x08, 0x2e, 0x24, 0x11, 0x5f, 0x77, 0x54, 0x0a, 0xb3, 0xb8, 0xc2,
+            0xb9, 0x20, 0x60, 0xaa, 0x30, 0xd6, 0xd2, 0xb8, 0x1a, 0x08, 0x5d, 0x71, 0xab, 0x37,
+            0xed, 0xa7, 0x68, 0x91,
+        ];
+        let public_key: [u8; 33] = [
+            0x03, 0xBC, 0x6D, 0x45, 0xD2, 0x10, 0x1E, 0x91, 0x28, 0xDE, 0x14, 0xB5, 0xB6, 0x68,
+            0x83, 0xD6, 0x9C, 0xF1, 0xC3, 0x1A, 0x50, 0xB9, 0x6F, 0xEA, 0x2D, 0xAD, 0x4E, 0xD2,
+            0x35, 0x14, 0x92, 0x4A, 0x22,
+        ];
+        let account = Account::new("test", &private_key, &public_key, Network::Testnet).unwrap();
+
+        let scenario = ScenarioBuilder::new()
+            .empty_blocks(2)
+            .payment(&account, 5_000)
+            .empty_blocks(1)
+            .payment(&account, 2_500)
+            .peer(SocketAddr::new(
+                IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2)),
+                8333,
+            ))
+            .account(account.clone())
+            .build()
+            .unwrap();
+
+        let peer = scenario.peers[0];
+        let mut stream = scenario.peer_stream(&peer).unwrap();
+
+        let logger_text: Vec<u8> = Vec::new();
+        let (sender, _) = logger::initialize_logger(logger_text, false);
+
+        let handshake = scenario.handshake(sender.clone());
+        handshake
+            .send_version_message(&mut stream, &scenario.local_socket(), &peer.address)
+            .unwrap();
+        let _ = read_message::<VersionMessage>(&mut stream, CommandName::Version);
+        handshake
+            .send_verack_message(&mut stream, &peer.address)
+            .unwrap();
+        let _ = read_message::<VerackMessage>(&mut stream, CommandName::Verack);
 
-        assert_eq!(transaction_message.transaction, send_transaction);
+        let mut block_chain = scenario.initial_block_chain().unwrap();
+        InitialHeaderDownload::new(
+            scenario.protocol_version,
+            scenario.network.magic_numbers(),
+            sender.clone(),
+        )
+        .get_headers(&mut stream, &mut block_chain, 0)
+        .unwrap();
+
+        assert_eq!(block_chain, scenario.expected_headers().unwrap());
+
+        let blocks = BlockDownload::new(scenario.network, sender)
+            .get_data(&mut stream, scenario.block_hashes(), NotificationMock {})
+            .unwrap();
+
+        assert_eq!(blocks, scenario.blocks);
+
+        for block in blocks.iter() {
+            block_chain.update_block(block.clone()).unwrap();
+        }
+
+        assert_eq!(block_chain, scenario.expected_block_chain().unwrap());
+
+        let utxo_set = UTXOSet::from_blockchain(&block_chain);
+        assert_eq!(
+            utxo_set.get_balance_in_satoshis(&account.address),
+            scenario.expected_balance(&account)
+        );
+        assert_eq!(scenario.expected_balance(&scenario.accounts[0]), 7_500);
     }
 }